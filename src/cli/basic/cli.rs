@@ -28,7 +28,7 @@ use crate::{
     },
     common::{infra::config::USERS, meta},
     migration,
-    service::{compact, db, file_list, users},
+    service::{compact, db, file_list, replication, users},
 };
 
 pub async fn cli() -> Result<bool, anyhow::Error> {
@@ -161,6 +161,8 @@ pub async fn cli() -> Result<bool, anyhow::Error> {
                         .action(clap::ArgAction::SetTrue)
                         .help("insert file list into db"),
                 ]),
+            clap::Command::new("promote-replica")
+                .about("rebuild file list from the replication manifests of the secondary storage"),
             clap::Command::new("node").about("node command").subcommands([
                 clap::Command::new("offline").about("offline node"),
                 clap::Command::new("online").about("online node"),
@@ -455,6 +457,10 @@ pub async fn cli() -> Result<bool, anyhow::Error> {
             let insert = command.get_flag("insert");
             super::load::load_file_list_from_s3(&account, prefix, insert).await?;
         }
+        "promote-replica" => {
+            let count = replication::promote().await?;
+            println!("promoted replica with {count} files");
+        }
         "node" => {
             let command = command.subcommand();
             match command {
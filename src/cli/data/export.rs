@@ -65,6 +65,10 @@ impl Context for Export {
             skip_wal: false,
             streaming_output: false,
             streaming_id: None,
+            max_points: None,
+            time_ranges: vec![],
+            time_shifts: vec![],
+            variables: vec![],
         };
 
         let req = search::Request {
@@ -148,6 +148,7 @@ async fn register() -> Result<()> {
         ),
         role: LOCAL_NODE.role.clone(),
         role_group: LOCAL_NODE.role_group,
+        labels: LOCAL_NODE.labels.clone(),
         cpu_num: cfg.limit.cpu_num as u64,
         status: NodeStatus::Prepare,
         scheduled: true,
@@ -240,6 +241,7 @@ pub(crate) async fn set_status(status: NodeStatus, new_lease_id: bool) -> Result
             ),
             role: LOCAL_NODE.role.clone(),
             role_group: LOCAL_NODE.role_group,
+            labels: LOCAL_NODE.labels.clone(),
             cpu_num: cfg.limit.cpu_num as u64,
             status: status.clone(),
             scheduled: true,
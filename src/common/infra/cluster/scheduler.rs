@@ -93,6 +93,7 @@ mod tests {
             grpc_addr: format!("grpc://node-{}.example.com", id),
             role: vec![Role::Ingester],
             role_group: RoleGroup::None,
+            labels: Default::default(),
             scheduled: true,
             broadcasted: true,
             status: NodeStatus::Online,
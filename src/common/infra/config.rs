@@ -19,12 +19,20 @@ use config::{
     RwAHashMap, RwHashMap,
     meta::{
         alerts::alert::Alert,
+        column_mask::ColumnMaskPolicy,
         destinations::{Destination, Template},
         folder::Folder,
         function::Transform,
+        grok::GrokPattern,
+        log_metrics::LogMetricRule,
         promql::ClusterLeader,
         ratelimit::CachedUserRoles,
+        remote_write_filter::RemoteWriteMatcher,
+        row_policy::RowPolicy,
+        sql_policy::SqlPolicy,
         stream::StreamParams,
+        threat_intel::Indicator,
+        ui_profile::UiProfile,
         user::User,
     },
 };
@@ -40,6 +48,7 @@ use crate::{
     common::meta::{
         maxmind::MaxmindClient,
         organization::{Organization, OrganizationSetting},
+        service_account::ServiceAccountStatus,
         syslog::SyslogRoute,
     },
     handler::http::request::ws::session::WsSession,
@@ -78,6 +87,26 @@ pub static DESTINATIONS: Lazy<RwHashMap<String, Destination>> = Lazy::new(Defaul
 pub static SYSLOG_ROUTES: Lazy<RwHashMap<String, SyslogRoute>> = Lazy::new(Default::default);
 pub static SYSLOG_ENABLED: Lazy<Arc<RwLock<bool>>> = Lazy::new(|| Arc::new(RwLock::new(false)));
 pub static ENRICHMENT_TABLES: Lazy<RwHashMap<String, StreamTable>> = Lazy::new(Default::default);
+// Key for threat intel indicators cache is org/normalized_value
+pub static THREAT_INDICATORS: Lazy<RwHashMap<String, Indicator>> = Lazy::new(Default::default);
+// Key for custom grok patterns cache is org/name
+pub static GROK_PATTERNS: Lazy<RwHashMap<String, GrokPattern>> = Lazy::new(Default::default);
+// Key for sql policies cache is org
+pub static SQL_POLICIES: Lazy<RwHashMap<String, SqlPolicy>> = Lazy::new(Default::default);
+// Key for remote write filters cache is org, disabled filters are not cached
+pub static REMOTE_WRITE_FILTERS: Lazy<RwHashMap<String, Arc<RemoteWriteMatcher>>> =
+    Lazy::new(Default::default);
+// Key for row policies cache is org/stream_type/stream_name/role
+pub static ROW_POLICIES: Lazy<RwHashMap<String, RowPolicy>> = Lazy::new(Default::default);
+// Key for service account status cache is org/email
+pub static SERVICE_ACCOUNTS: Lazy<RwHashMap<String, ServiceAccountStatus>> =
+    Lazy::new(Default::default);
+// Key for ui profiles cache is org/role/{role} or org/user/{user_id}
+pub static UI_PROFILES: Lazy<RwHashMap<String, UiProfile>> = Lazy::new(Default::default);
+// Key for column masks cache is org/stream_type/stream_name/role
+pub static COLUMN_MASKS: Lazy<RwHashMap<String, ColumnMaskPolicy>> = Lazy::new(Default::default);
+// Key for log metric rules cache is org/name
+pub static LOG_METRIC_RULES: Lazy<RwHashMap<String, LogMetricRule>> = Lazy::new(Default::default);
 pub static ENRICHMENT_REGISTRY: Lazy<Arc<TableRegistry>> =
     Lazy::new(|| Arc::new(TableRegistry::default()));
 
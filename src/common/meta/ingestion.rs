@@ -81,9 +81,10 @@ pub struct StreamSchemaChk {
     pub has_metadata: bool,
 }
 
-pub const INGESTION_EP: [&str; 16] = [
+pub const INGESTION_EP: [&str; 17] = [
     "_bulk",
     "_json",
+    "_csv",
     "_multi",
     "traces",
     "write",
@@ -320,6 +321,22 @@ pub struct GCPIngestionResponse {
     pub timestamp: String,
 }
 
+/// Options of the csv ingestion API, given as query parameters
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct CsvIngestionQuery {
+    /// Single character column separator, `tab` for tsv. Defaults to `,`, or tab when the
+    /// content type is `text/tab-separated-values`
+    pub delimiter: Option<String>,
+    /// Whether the first row holds the column names, defaults to `true`
+    pub has_header: Option<bool>,
+    /// Comma separated column names by position, they take precedence over the header row
+    pub columns: Option<String>,
+    /// Column holding the event time, stored as `_timestamp`
+    pub timestamp_column: Option<String>,
+    /// Format of the timestamp column, one of the stream timestamp formats, defaults to `auto`
+    pub timestamp_format: Option<String>,
+}
+
 pub enum IngestionRequest<'a> {
     JSON(&'a web::Bytes),
     Multi(&'a web::Bytes),
@@ -328,6 +345,7 @@ pub enum IngestionRequest<'a> {
     RUM(&'a web::Bytes),
     Usage(&'a web::Bytes),
     Hec(&'a Vec<json::Value>),
+    Csv(&'a Vec<json::Value>),
 }
 
 pub enum IngestionData<'a> {
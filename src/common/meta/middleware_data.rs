@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::{collections::HashMap, net::IpAddr, sync::Arc};
+use std::{collections::HashMap, net::IpAddr};
 
 use actix_web::{
     Error as ActixErr, HttpMessage,
@@ -23,13 +23,12 @@ use actix_web::{
 };
 use actix_web_lab::middleware::Next;
 use maxminddb::geoip2::city::Location;
-use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use uaparser::{Parser, UserAgentParser};
+use uaparser::Parser;
 
-use crate::{
-    USER_AGENT_REGEX_FILE,
-    common::{infra::config::MAXMIND_DB_CLIENT, utils::http::parse_ip_addr},
+use crate::common::{
+    infra::config::MAXMIND_DB_CLIENT,
+    utils::{http::parse_ip_addr, parsers::UA_PARSER},
 };
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
@@ -40,16 +39,6 @@ pub struct GeoInfoData<'a> {
     pub location: Option<Location<'a>>,
 }
 
-/// This is a global cache for user agent parser. This is lazily initialized only when
-/// the first request comes in.
-static UA_PARSER: Lazy<Arc<UserAgentParser>> = Lazy::new(|| Arc::new(initialize_ua_parser()));
-
-pub fn initialize_ua_parser() -> UserAgentParser {
-    UserAgentParser::builder()
-        .build_from_bytes(USER_AGENT_REGEX_FILE)
-        .expect("User Agent Parser creation failed")
-}
-
 /// This is the custom data which is provided by `browser-sdk`
 /// in form of query-parameters.
 /// NOTE: the only condition is that the prefix of such params is `oo`.
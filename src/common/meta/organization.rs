@@ -13,6 +13,8 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::BTreeMap;
+
 use config::meta::user::UserRole;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -220,6 +222,8 @@ pub struct OrganizationSettingPayload {
     pub enable_streaming_search: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub min_auto_refresh_interval: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_normalization: Option<FieldNormalization>,
 }
 
 #[derive(Serialize, ToSchema, Deserialize, Debug, Clone)]
@@ -240,6 +244,20 @@ pub struct OrganizationSetting {
     pub enable_streaming_search: bool,
     #[serde(default = "default_auto_refresh_interval")]
     pub min_auto_refresh_interval: u32,
+    #[serde(default)]
+    pub field_normalization: FieldNormalization,
+}
+
+/// Renaming of the common variants of field names, e.g. `msg` or `lvl`, to a canonical name
+/// when logs are ingested, so the streams of different services share their field names.
+#[derive(Serialize, ToSchema, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct FieldNormalization {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Variant field name to canonical field name, on top of the built-in mappings. Mapping a
+    /// built-in variant to an empty name turns its renaming off.
+    #[serde(default)]
+    pub mappings: BTreeMap<String, String>,
 }
 
 impl Default for OrganizationSetting {
@@ -252,6 +270,7 @@ impl Default for OrganizationSetting {
             enable_websocket_search: default_enable_websocket_search(),
             enable_streaming_search: default_enable_streaming_search(),
             min_auto_refresh_interval: default_auto_refresh_interval(),
+            field_normalization: FieldNormalization::default(),
         }
     }
 }
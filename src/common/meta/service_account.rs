@@ -39,6 +39,22 @@ pub struct UpdateServiceAccountRequest {
     pub last_name: String,
 }
 
+/// Lifecycle of a service account, the token of a disabled or expired account is refused.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, Eq, PartialEq, Default)]
+pub struct ServiceAccountStatus {
+    #[serde(default)]
+    pub disabled: bool,
+    /// Expiry of the account in microseconds, it never expires when not set
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+}
+
+impl ServiceAccountStatus {
+    pub fn is_active(&self, now: i64) -> bool {
+        !self.disabled && self.expires_at.is_none_or(|expires_at| now < expires_at)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -158,6 +174,22 @@ mod tests {
         assert_eq!(request, deserialized);
     }
 
+    #[test]
+    fn test_service_account_status_is_active() {
+        assert!(ServiceAccountStatus::default().is_active(100));
+        let disabled = ServiceAccountStatus {
+            disabled: true,
+            expires_at: None,
+        };
+        assert!(!disabled.is_active(100));
+        let expiring = ServiceAccountStatus {
+            disabled: false,
+            expires_at: Some(200),
+        };
+        assert!(expiring.is_active(100));
+        assert!(!expiring.is_active(200));
+    }
+
     #[test]
     fn test_update_service_account_request_equality() {
         let request1 = UpdateServiceAccountRequest {
@@ -19,11 +19,15 @@ use arrow_schema::Field;
 use config::{
     meta::{
         promql::Metadata,
-        stream::{StreamSettings, StreamStats, StreamType},
+        stream::{
+            FieldMetadata, StorageGrowth, StreamFieldUsage, StreamHourlyStats, StreamSettings,
+            StreamStats, StreamStorageSample, StreamType,
+        },
     },
     utils::json,
 };
 use datafusion::arrow::datatypes::Schema;
+use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -48,6 +52,9 @@ pub struct StreamProperty {
     pub name: String,
     #[serde(rename = "type")]
     pub prop_type: String,
+    /// Description, unit, owner and PII flag of the field, from the stream settings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<FieldMetadata>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -69,6 +76,82 @@ pub struct ListStream {
     pub total: usize,
 }
 
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct StreamHourlyStatsResponse {
+    /// Hourly stats keyed by stream name, ordered by hour
+    #[schema(value_type = Object)]
+    pub streams: HashMap<String, Vec<StreamHourlyStats>>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct StreamFieldUsageResponse {
+    /// Usage of every field of the schema and of the fields queried in the time range, most
+    /// queried first
+    pub fields: Vec<StreamFieldUsage>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct StreamStorageUsage {
+    pub stream_name: String,
+    pub stream_type: StreamType,
+    /// Daily samples ordered by day
+    pub samples: Vec<StreamStorageSample>,
+    pub growth: StorageGrowth,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct StorageUsageResponse {
+    /// Daily totals of the listed streams
+    pub total: Vec<StreamStorageSample>,
+    pub growth: StorageGrowth,
+    pub streams: Vec<StreamStorageUsage>,
+}
+
+#[derive(Clone, Debug, Deserialize, ToSchema)]
+pub struct StreamCloneRequest {
+    /// Name of the new stream, it must not exist yet
+    pub target_stream: String,
+    /// Organization of the new stream, default the source organization
+    #[serde(default)]
+    pub target_org: Option<String>,
+    /// Copy the functions of the source stream pipeline and a disabled copy of the pipeline
+    #[serde(default = "default_true")]
+    pub include_functions: bool,
+    /// Copy a sample of the source records, logs streams only
+    #[serde(default)]
+    pub sample: Option<StreamCloneSample>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct StreamCloneSample {
+    /// Number of records to copy
+    pub size: i64,
+    /// Start time in microseconds, default 24 hours before end_time
+    pub start_time: Option<i64>,
+    /// End time in microseconds, default now
+    pub end_time: Option<i64>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, ToSchema)]
+pub struct StreamCloneResponse {
+    pub org_id: String,
+    pub stream_name: String,
+    pub stream_type: StreamType,
+    /// Number of schema fields copied
+    pub fields: usize,
+    /// Functions copied into the target organization
+    pub functions: Vec<String>,
+    /// Name of the disabled pipeline created for the new stream
+    pub pipeline: Option<String>,
+    /// Number of sampled records written to the new stream
+    pub records: usize,
+}
+
+fn default_true() -> bool {
+    true
+}
+
 pub struct SchemaEvolution {
     pub is_schema_changed: bool,
     pub types_delta: Option<Vec<Field>>,
@@ -105,6 +188,7 @@ mod tests {
         let property = StreamProperty {
             name: "test_field".to_string(),
             prop_type: "string".to_string(),
+            metadata: None,
         };
 
         assert_eq!(property.name, "test_field");
@@ -148,6 +232,7 @@ mod tests {
             schema: vec![StreamProperty {
                 name: "field1".to_string(),
                 prop_type: "string".to_string(),
+                metadata: None,
             }],
             uds_schema: None,
             settings: StreamSettings::default(),
@@ -228,6 +313,7 @@ mod tests {
             uds_schema: Some(vec![StreamProperty {
                 name: "uds_field".to_string(),
                 prop_type: "string".to_string(),
+                metadata: None,
             }]),
             settings: StreamSettings::default(),
             metrics_meta: None,
@@ -303,7 +303,16 @@ impl FromRequest for AuthExtractor {
             } else {
                 path_columns[0].to_string()
             }
-        } else if url_len == 2 || (url_len > 2 && path_columns[1].eq("settings")) {
+        } else if url_len == 2
+            || (url_len > 2
+                && (path_columns[1].eq("settings")
+                    || path_columns[1].eq("alert_silences")
+                    || path_columns[1].eq("escalation_policies")
+                    || path_columns[1].eq("index_recommendations")))
+            || (url_len == 3
+                && (path_columns[1].eq("invitations") || path_columns[1].eq("library_panels")))
+            || (url_len == 3 && path_columns[1].eq("alerts") && path_columns[2].eq("bulk"))
+        {
             // for settings, the post/delete require PUT permissions, GET needs LIST permissions
             // also the special settings exception is for 3-part urls for logo /text
             // which are of path /org/settings/logo , which need permission of operating
@@ -312,12 +321,28 @@ impl FromRequest for AuthExtractor {
                 if method.eq("POST") || method.eq("DELETE") {
                     method = "PUT".to_string();
                 }
+            } else if path_columns[1].eq("index_recommendations") && method.eq("POST") {
+                // applying a recommendation updates the stream settings
+                method = "PUT".to_string();
             } else if method.eq("GET") {
                 method = "LIST".to_string();
             }
             // this will take format of settings:{org_id} or pipelines:{org_id} etc
-            let key = if path_columns[1].eq("invites") {
+            let key = if path_columns[1].eq("invites") || path_columns[1].eq("invitations") {
                 "users"
+            } else if path_columns[1].eq("index_recommendations") {
+                // the recommendations are settings of the streams of the org
+                "streams"
+            } else if path_columns[1].eq("library_panels") {
+                // library panels are shared by the dashboards of the org
+                "dashboards"
+            } else if path_columns[1].eq("alert_silences")
+                || path_columns[1].eq("escalation_policies")
+                || (url_len == 3 && path_columns[1].eq("alerts"))
+            {
+                // silences mute and escalation policies notify the alerts of the org, and the
+                // bulk sync replaces all of them
+                "alert_folders"
             } else if path_columns[1].eq("rename") && method.eq("PUT") {
                 "organizations"
             } else {
@@ -606,8 +631,10 @@ impl FromRequest for AuthExtractor {
                     )
                 }
             }
-            //  this is specifically for enabling alerts
-            else if path_columns[url_len - 1].eq("enable") {
+            //  this is specifically for enabling and acknowledging alerts
+            else if path_columns[url_len - 1].eq("enable")
+                || path_columns[url_len - 1].eq("acknowledge")
+            {
                 // this will take form name:alert
                 format!(
                     "{}:{}",
@@ -667,7 +694,8 @@ impl FromRequest for AuthExtractor {
             if (method.eq("POST") && url_len > 1 && path_columns[1].starts_with("_search"))
                 || (method.eq("POST") && url_len > 1 && path.ends_with("actions/upload"))
                 || path.contains("/prometheus/api/v1/query")
-                || path.contains("/resources")
+                || (path.contains("/resources")
+                    && !(url_len > 1 && path_columns[1].eq("workspaces")))
                 || path.contains("/format_query")
                 || path.contains("/prometheus/api/v1/series")
                 || path.contains("/traces/latest")
@@ -677,6 +705,8 @@ impl FromRequest for AuthExtractor {
                 || path.contains("/ws")
                 || path.contains("/_values_stream")
                 || (url_len > 1 && path_columns[1].eq("ai"))
+                // every user can get the ui profile applying to them
+                || (url_len == 3 && path_columns[1].eq("ui_profiles") && path_columns[2].eq("_me"))
             {
                 return ready(Ok(AuthExtractor {
                     auth: auth_str.to_owned(),
@@ -787,6 +817,11 @@ impl FromRequest for AuthExtractor {
 
     #[cfg(not(feature = "enterprise"))]
     fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let local_path = req.path();
+        let path = local_path
+            .strip_prefix(format!("{}/api/", config::get_config().common.base_uri).as_str())
+            .unwrap_or(local_path);
+        let (method, org_id, o2_type) = get_authz_target(req.method().as_str(), path);
         let auth_str = if let Some(cookie) = req.cookie("auth_tokens") {
             let val = config::utils::base64::decode_raw(cookie.value()).unwrap_or_default();
             let auth_tokens: AuthTokens =
@@ -809,11 +844,13 @@ impl FromRequest for AuthExtractor {
 
         // if let Some(auth_header) = req.headers().get("Authorization") {
         if !auth_str.is_empty() {
+            // there is no RBAC, the action and resource are only given to the external
+            // authorization endpoint
             return ready(Ok(AuthExtractor {
                 auth: auth_str.to_owned(),
-                method: "".to_string(),
-                o2_type: "".to_string(),
-                org_id: "".to_string(),
+                method,
+                o2_type,
+                org_id,
                 bypass_check: true, // bypass check permissions
                 parent_id: "".to_string(),
             }));
@@ -825,6 +862,36 @@ impl FromRequest for AuthExtractor {
     }
 }
 
+/// Returns the action, org and `<type>:<id>` resource of an api request, for the external
+/// authorization of the builds without RBAC. The path is relative to `/api/`, e.g.
+/// `default/dashboards/123`. Reading a collection is the `LIST` action on `<type>:<org>`.
+#[cfg(not(feature = "enterprise"))]
+fn get_authz_target(method: &str, path: &str) -> (String, String, String) {
+    let mut path_columns = path
+        .split('/')
+        .filter(|c| !c.is_empty())
+        .collect::<Vec<_>>();
+    if path_columns.len() > 1 && path_columns[0].eq(V2_API_PREFIX) {
+        path_columns.remove(0);
+    }
+    let list_or = |method: &str| if method.eq("GET") { "LIST" } else { method }.to_string();
+    match path_columns.as_slice() {
+        [] => (method.to_string(), String::new(), String::new()),
+        ["organizations"] => (
+            list_or(method),
+            String::new(),
+            "org:##user_id##".to_string(),
+        ),
+        [org] => (method.to_string(), org.to_string(), format!("org:{org}")),
+        [org, entity] => (list_or(method), org.to_string(), format!("{entity}:{org}")),
+        [org, entity, id, ..] => (
+            method.to_string(),
+            org.to_string(),
+            format!("{entity}:{id}"),
+        ),
+    }
+}
+
 #[cfg(feature = "enterprise")]
 pub fn extract_auth_str(req: &HttpRequest) -> String {
     let auth_ext_cookie = |req: &HttpRequest| -> String {
@@ -1060,6 +1127,51 @@ mod tests {
         service::{self, organization, users},
     };
 
+    #[cfg(not(feature = "enterprise"))]
+    #[test]
+    fn test_get_authz_target() {
+        assert_eq!(
+            get_authz_target("GET", "default/dashboards"),
+            (
+                "LIST".to_string(),
+                "default".to_string(),
+                "dashboards:default".to_string()
+            )
+        );
+        assert_eq!(
+            get_authz_target("POST", "default/dashboards"),
+            (
+                "POST".to_string(),
+                "default".to_string(),
+                "dashboards:default".to_string()
+            )
+        );
+        assert_eq!(
+            get_authz_target("DELETE", "default/dashboards/123"),
+            (
+                "DELETE".to_string(),
+                "default".to_string(),
+                "dashboards:123".to_string()
+            )
+        );
+        assert_eq!(
+            get_authz_target("PUT", "v2/default/alerts/abc/enable"),
+            (
+                "PUT".to_string(),
+                "default".to_string(),
+                "alerts:abc".to_string()
+            )
+        );
+        assert_eq!(
+            get_authz_target("GET", "organizations"),
+            (
+                "LIST".to_string(),
+                "".to_string(),
+                "org:##user_id##".to_string()
+            )
+        );
+    }
+
     #[test]
     fn test_generate_presigned_url() {
         let password = "password";
@@ -3200,6 +3200,8 @@ mod tests {
                 metrics_leader_election_interval: i64::default(),
                 metrics_max_series_per_query: usize::default(),
                 metrics_max_points_per_series: usize::default(),
+                metrics_max_samples_per_query: usize::default(),
+                metrics_query_sharding_enabled: bool::default(),
                 metrics_cache_max_entries: usize::default(),
                 req_cols_per_record_limit: usize::default(),
                 node_heartbeat_ttl: i64::default(),
@@ -0,0 +1,431 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Grok expressions: `%{PATTERN:field:type}` references to a pattern library, expanded into a
+//! regex. The built-in library follows the Logstash `grok-patterns`, an org can add its own
+//! patterns which take precedence over the built-in ones.
+
+use std::{collections::HashMap, sync::Arc};
+
+use config::utils::json;
+use fancy_regex::Regex;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use vrl::prelude::*;
+
+use crate::common::infra::config::GROK_PATTERNS;
+
+const MAX_DEPTH: usize = 32;
+const MAX_CACHED: usize = 1000;
+
+/// The built-in pattern library, one `NAME pattern` definition per line.
+pub const BUILTIN_PATTERNS: &str = r#"
+USERNAME [a-zA-Z0-9._-]+
+USER %{USERNAME}
+EMAILLOCALPART [a-zA-Z][a-zA-Z0-9_.+-=:]+
+EMAILADDRESS %{EMAILLOCALPART}@%{HOSTNAME}
+INT (?:[+-]?(?:[0-9]+))
+BASE10NUM (?<![0-9.+-])(?>[+-]?(?:(?:[0-9]+(?:\.[0-9]+)?)|(?:\.[0-9]+)))
+NUMBER (?:%{BASE10NUM})
+BASE16NUM (?<![0-9A-Fa-f])(?:[+-]?(?:0x)?(?:[0-9A-Fa-f]+))
+BASE16FLOAT \b(?<![0-9A-Fa-f.])(?:[+-]?(?:0x)?(?:(?:[0-9A-Fa-f]+(?:\.[0-9A-Fa-f]*)?)|(?:\.[0-9A-Fa-f]+)))\b
+POSINT \b(?:[1-9][0-9]*)\b
+NONNEGINT \b(?:[0-9]+)\b
+WORD \b\w+\b
+NOTSPACE \S+
+SPACE \s*
+DATA .*?
+GREEDYDATA .*
+QUOTEDSTRING (?>(?<!\\)(?>"(?>\\.|[^\\"]+)+"|""|(?>'(?>\\.|[^\\']+)+')|''|(?>`(?>\\.|[^\\`]+)+`)|``))
+QS %{QUOTEDSTRING}
+UUID [A-Fa-f0-9]{8}-(?:[A-Fa-f0-9]{4}-){3}[A-Fa-f0-9]{12}
+URN urn:[0-9A-Za-z][0-9A-Za-z-]{0,31}:(?:%[0-9a-fA-F]{2}|[0-9A-Za-z()+,.:=@;$_!*'/?#-])+
+CISCOMAC (?:(?:[A-Fa-f0-9]{4}\.){2}[A-Fa-f0-9]{4})
+WINDOWSMAC (?:(?:[A-Fa-f0-9]{2}-){5}[A-Fa-f0-9]{2})
+COMMONMAC (?:(?:[A-Fa-f0-9]{2}:){5}[A-Fa-f0-9]{2})
+MAC (?:%{CISCOMAC}|%{WINDOWSMAC}|%{COMMONMAC})
+IPV6 ((([0-9A-Fa-f]{1,4}:){7}([0-9A-Fa-f]{1,4}|:))|(([0-9A-Fa-f]{1,4}:){6}(:[0-9A-Fa-f]{1,4}|((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3})|:))|(([0-9A-Fa-f]{1,4}:){5}(((:[0-9A-Fa-f]{1,4}){1,2})|:((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3})|:))|(([0-9A-Fa-f]{1,4}:){4}(((:[0-9A-Fa-f]{1,4}){1,3})|((:[0-9A-Fa-f]{1,4})?:((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3}))|:))|(([0-9A-Fa-f]{1,4}:){3}(((:[0-9A-Fa-f]{1,4}){1,4})|((:[0-9A-Fa-f]{1,4}){0,2}:((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3}))|:))|(([0-9A-Fa-f]{1,4}:){2}(((:[0-9A-Fa-f]{1,4}){1,5})|((:[0-9A-Fa-f]{1,4}){0,3}:((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3}))|:))|(([0-9A-Fa-f]{1,4}:){1}(((:[0-9A-Fa-f]{1,4}){1,6})|((:[0-9A-Fa-f]{1,4}){0,4}:((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3}))|:))|(:(((:[0-9A-Fa-f]{1,4}){1,7})|((:[0-9A-Fa-f]{1,4}){0,5}:((25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)(\.(25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)){3}))|:)))(%.+)?
+IPV4 (?<![0-9])(?:(?:[0-1]?[0-9]{1,2}|2[0-4][0-9]|25[0-5])[.](?:[0-1]?[0-9]{1,2}|2[0-4][0-9]|25[0-5])[.](?:[0-1]?[0-9]{1,2}|2[0-4][0-9]|25[0-5])[.](?:[0-1]?[0-9]{1,2}|2[0-4][0-9]|25[0-5]))(?![0-9])
+IP (?:%{IPV6}|%{IPV4})
+HOSTNAME \b(?:[0-9A-Za-z][0-9A-Za-z-]{0,62})(?:\.(?:[0-9A-Za-z][0-9A-Za-z-]{0,62}))*(\.?|\b)
+IPORHOST (?:%{IP}|%{HOSTNAME})
+HOSTPORT %{IPORHOST}:%{POSINT}
+UNIXPATH (/([\w_%!$@:.,+~-]+|\\.)*)+
+WINPATH (?>[A-Za-z]+:|\\)(?:\\[^\\?*]*)+
+PATH (?:%{UNIXPATH}|%{WINPATH})
+TTY (?:/dev/(pts|tty([pq])?)(\w+)?/?(?:[0-9]+))
+URIPROTO [A-Za-z]([A-Za-z0-9+\-.]+)+
+URIHOST %{IPORHOST}(?::%{POSINT:port})?
+URIPATH (?:/[A-Za-z0-9$.+!*'(){},~:;=@#%&_\-]*)+
+URIPARAM \?[A-Za-z0-9$.+!*'|(){},~@#%&/=:;_?\-\[\]<>]*
+URIPATHPARAM %{URIPATH}(?:%{URIPARAM})?
+URI %{URIPROTO}://(?:%{USER}(?::[^@]*)?@)?(?:%{URIHOST})?(?:%{URIPATHPARAM})?
+MONTH \b(?:[Jj]an(?:uary|uar)?|[Ff]eb(?:ruary|ruar)?|[Mm](?:a|ä)?r(?:ch|z)?|[Aa]pr(?:il)?|[Mm]a(?:y|i)?|[Jj]un(?:e|i)?|[Jj]ul(?:y|i)?|[Aa]ug(?:ust)?|[Ss]ep(?:tember)?|[Oo](?:c|k)?t(?:ober)?|[Nn]ov(?:ember)?|[Dd]e(?:c|z)(?:ember)?)\b
+MONTHNUM (?:0?[1-9]|1[0-2])
+MONTHNUM2 (?:0[1-9]|1[0-2])
+MONTHDAY (?:(?:0[1-9])|(?:[12][0-9])|(?:3[01])|[1-9])
+DAY (?:Mon(?:day)?|Tue(?:sday)?|Wed(?:nesday)?|Thu(?:rsday)?|Fri(?:day)?|Sat(?:urday)?|Sun(?:day)?)
+YEAR (?>\d\d){1,2}
+HOUR (?:2[0123]|[01]?[0-9])
+MINUTE (?:[0-5][0-9])
+SECOND (?:(?:[0-5]?[0-9]|60)(?:[:.,][0-9]+)?)
+TIME (?<![0-9])%{HOUR}:%{MINUTE}(?::%{SECOND})(?![0-9])
+DATE_US %{MONTHNUM}[/-]%{MONTHDAY}[/-]%{YEAR}
+DATE_EU %{MONTHDAY}[./-]%{MONTHNUM}[./-]%{YEAR}
+ISO8601_TIMEZONE (?:Z|[+-]%{HOUR}(?::?%{MINUTE}))
+ISO8601_SECOND (?:%{SECOND}|60)
+TIMESTAMP_ISO8601 %{YEAR}-%{MONTHNUM}-%{MONTHDAY}[T ]%{HOUR}:?%{MINUTE}(?::?%{SECOND})?%{ISO8601_TIMEZONE}?
+DATE %{DATE_US}|%{DATE_EU}
+DATESTAMP %{DATE}[- ]%{TIME}
+TZ (?:[APMCE][SD]T|UTC)
+DATESTAMP_RFC822 %{DAY} %{MONTH} %{MONTHDAY} %{YEAR} %{TIME} %{TZ}
+DATESTAMP_RFC2822 %{DAY}, %{MONTHDAY} %{MONTH} %{YEAR} %{TIME} %{ISO8601_TIMEZONE}
+DATESTAMP_OTHER %{DAY} %{MONTH} %{MONTHDAY} %{TIME} %{TZ} %{YEAR}
+DATESTAMP_EVENTLOG %{YEAR}%{MONTHNUM2}%{MONTHDAY}%{HOUR}%{MINUTE}%{SECOND}
+HTTPDATE %{MONTHDAY}/%{MONTH}/%{YEAR}:%{TIME} %{INT}
+SYSLOGTIMESTAMP %{MONTH} +%{MONTHDAY} %{TIME}
+PROG [\x21-\x5a\x5c\x5e-\x7e]+
+SYSLOGPROG %{PROG:program}(?:\[%{POSINT:pid}\])?
+SYSLOGHOST %{IPORHOST}
+SYSLOGFACILITY <%{NONNEGINT:facility}.%{NONNEGINT:priority}>
+SYSLOGBASE %{SYSLOGTIMESTAMP:timestamp} (?:%{SYSLOGFACILITY} )?%{SYSLOGHOST:logsource} %{SYSLOGPROG}:
+LOGLEVEL ([Aa]lert|ALERT|[Tt]race|TRACE|[Dd]ebug|DEBUG|[Nn]otice|NOTICE|[Ii]nfo|INFO|[Ww]arn?(?:ing)?|WARN?(?:ING)?|[Ee]rr?(?:or)?|ERR?(?:OR)?|[Cc]rit?(?:ical)?|CRIT?(?:ICAL)?|[Ff]atal|FATAL|[Ss]evere|SEVERE|EMERG(?:ENCY)?|[Ee]merg(?:ency)?)
+HTTPDUSER %{EMAILADDRESS}|%{USER}
+COMMONAPACHELOG %{IPORHOST:clientip} %{HTTPDUSER:ident} %{USER:auth} \[%{HTTPDATE:timestamp}\] "(?:%{WORD:verb} %{NOTSPACE:request}(?: HTTP/%{NUMBER:httpversion})?|%{DATA:rawrequest})" %{NUMBER:response} (?:%{NUMBER:bytes}|-)
+COMBINEDAPACHELOG %{COMMONAPACHELOG} %{QS:referrer} %{QS:agent}
+"#;
+
+pub static BUILTIN: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
+    BUILTIN_PATTERNS
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .collect()
+});
+
+static RE_REFERENCE: Lazy<regex::Regex> =
+    Lazy::new(|| regex::Regex::new(r"%\{(\w+)(?::([^:}]+))?(?::(\w+))?\}").unwrap());
+
+static COMPILED: Lazy<RwLock<HashMap<(String, String), Arc<Grok>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+#[derive(Debug, thiserror::Error)]
+pub enum GrokError {
+    #[error("unknown grok pattern: {0}")]
+    UnknownPattern(String),
+    #[error("grok pattern nested too deep, is it recursive? {0}")]
+    TooDeep(String),
+    #[error("invalid grok expression: {0}")]
+    InvalidRegex(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FieldType {
+    String,
+    Int,
+    Float,
+    Bool,
+}
+
+impl FieldType {
+    fn from_name(name: Option<&str>) -> Self {
+        match name {
+            Some("int") | Some("long") => FieldType::Int,
+            Some("float") | Some("double") => FieldType::Float,
+            Some("bool") | Some("boolean") => FieldType::Bool,
+            _ => FieldType::String,
+        }
+    }
+
+    fn convert(&self, value: &str) -> json::Value {
+        let converted = match self {
+            FieldType::String => None,
+            FieldType::Int => value.parse::<i64>().ok().map(json::Value::from),
+            FieldType::Float => value.parse::<f64>().ok().map(json::Value::from),
+            FieldType::Bool => value
+                .to_lowercase()
+                .parse::<bool>()
+                .ok()
+                .map(json::Value::from),
+        };
+        converted.unwrap_or_else(|| json::Value::String(value.to_string()))
+    }
+}
+
+#[derive(Debug)]
+struct Field {
+    group: String,
+    name: String,
+    kind: FieldType,
+}
+
+/// A compiled grok expression.
+#[derive(Debug)]
+pub struct Grok {
+    regex: Regex,
+    fields: Vec<Field>,
+}
+
+impl Grok {
+    /// Compiles a grok expression, `lookup` resolves the definition of a pattern name.
+    pub fn new(pattern: &str, lookup: &dyn Fn(&str) -> Option<String>) -> Result<Self, GrokError> {
+        let mut fields = Vec::new();
+        let expanded = expand(pattern, lookup, 0, &mut fields)?;
+        let regex = Regex::new(&expanded).map_err(|e| GrokError::InvalidRegex(e.to_string()))?;
+        // plain named groups of the expression are extracted as string fields too
+        let named = regex
+            .capture_names()
+            .flatten()
+            .filter(|name| !name.starts_with("_g"))
+            .map(|name| Field {
+                group: name.to_string(),
+                name: name.to_string(),
+                kind: FieldType::String,
+            })
+            .collect::<Vec<_>>();
+        fields.extend(named);
+        Ok(Self { regex, fields })
+    }
+
+    /// Returns the extracted fields, or `None` if the text doesn't match.
+    pub fn parse(&self, text: &str) -> Option<json::Map<String, json::Value>> {
+        let caps = self.regex.captures(text).ok()??;
+        let mut map = json::Map::new();
+        for field in self.fields.iter() {
+            if map.contains_key(&field.name) {
+                continue;
+            }
+            if let Some(m) = caps.name(&field.group) {
+                map.insert(field.name.clone(), field.kind.convert(m.as_str()));
+            }
+        }
+        Some(map)
+    }
+}
+
+fn expand(
+    pattern: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+    depth: usize,
+    fields: &mut Vec<Field>,
+) -> Result<String, GrokError> {
+    if depth > MAX_DEPTH {
+        return Err(GrokError::TooDeep(pattern.to_string()));
+    }
+    let mut expanded = String::with_capacity(pattern.len());
+    let mut last = 0;
+    for caps in RE_REFERENCE.captures_iter(pattern) {
+        let reference = caps.get(0).unwrap();
+        expanded.push_str(&pattern[last..reference.start()]);
+        last = reference.end();
+
+        let name = &caps[1];
+        let definition = lookup(name).ok_or_else(|| GrokError::UnknownPattern(name.to_string()))?;
+        let inner = expand(&definition, lookup, depth + 1, fields)?;
+        match caps.get(2) {
+            Some(field) => {
+                let group = format!("_g{}", fields.len());
+                expanded.push_str(&format!("(?P<{group}>{inner})"));
+                fields.push(Field {
+                    group,
+                    name: field.as_str().to_string(),
+                    kind: FieldType::from_name(caps.get(3).map(|t| t.as_str())),
+                });
+            }
+            None => expanded.push_str(&format!("(?:{inner})")),
+        }
+    }
+    expanded.push_str(&pattern[last..]);
+    Ok(expanded)
+}
+
+/// Resolves a pattern name for an org, custom patterns take precedence over the built-in ones.
+pub fn lookup_pattern(org_id: &str, name: &str) -> Option<String> {
+    GROK_PATTERNS
+        .get(&format!("{org_id}/{name}"))
+        .map(|p| p.pattern.clone())
+        .or_else(|| BUILTIN.get(name).map(|p| p.to_string()))
+}
+
+/// Compiles a grok expression with the patterns of an org, compiled expressions are cached.
+pub fn compile(org_id: &str, pattern: &str) -> Result<Arc<Grok>, GrokError> {
+    let key = (org_id.to_string(), pattern.to_string());
+    if let Some(grok) = COMPILED.read().get(&key) {
+        return Ok(grok.clone());
+    }
+    let grok = Arc::new(Grok::new(pattern, &|name| lookup_pattern(org_id, name))?);
+    let mut w = COMPILED.write();
+    if w.len() >= MAX_CACHED {
+        w.clear();
+    }
+    w.insert(key, grok.clone());
+    Ok(grok)
+}
+
+/// Drops the compiled expressions of an org, called when its custom patterns change.
+pub fn clear_cache(org_id: &str) {
+    COMPILED.write().retain(|(org, _), _| org != org_id);
+}
+
+/// VRL function `o2_parse_grok`, parses a value with a grok expression using the built-in
+/// patterns and the custom patterns of the org.
+#[derive(Clone, Debug)]
+pub struct O2ParseGrok {
+    org_id: String,
+}
+
+impl Function for O2ParseGrok {
+    fn identifier(&self) -> &'static str {
+        "o2_parse_grok"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "pattern",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "parse with grok",
+            source: r#"o2_parse_grok!("GET /index.html 200", "%{WORD:method} %{URIPATH:path} %{INT:status:int}").status"#,
+            result: Ok("200"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let pattern = arguments.required("pattern");
+        Ok(O2ParseGrokFn {
+            org_id: self.org_id.clone(),
+            value,
+            pattern,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct O2ParseGrokFn {
+    org_id: String,
+    value: Box<dyn Expression>,
+    pattern: Box<dyn Expression>,
+}
+
+impl FunctionExpression for O2ParseGrokFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let text = value.try_bytes_utf8_lossy()?;
+        let pattern = self.pattern.resolve(ctx)?;
+        let pattern = pattern.try_bytes_utf8_lossy()?;
+        let grok = compile(&self.org_id, &pattern).map_err(|e| e.to_string())?;
+        match grok.parse(&text) {
+            Some(fields) => Ok(Value::from(json::Value::Object(fields))),
+            None => Err("unable to parse value with grok pattern".into()),
+        }
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(Collection::any()).fallible()
+    }
+}
+
+/// The VRL functions of this module for an org, registered with the VRL compiler
+pub fn vrl_functions(org_id: &str) -> Vec<Box<dyn Function>> {
+    vec![Box::new(O2ParseGrok {
+        org_id: org_id.to_string(),
+    })]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn builtin(name: &str) -> Option<String> {
+        BUILTIN.get(name).map(|p| p.to_string())
+    }
+
+    #[test]
+    fn test_builtin_patterns_compile() {
+        for name in BUILTIN.keys() {
+            assert!(
+                Grok::new(&format!("%{{{name}}}"), &builtin).is_ok(),
+                "pattern {name} doesn't compile"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_apache_log() {
+        let grok = Grok::new("%{COMBINEDAPACHELOG}", &builtin).unwrap();
+        let line = r#"127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326 "http://www.example.com/start.html" "Mozilla/4.08""#;
+        let fields = grok.parse(line).unwrap();
+        assert_eq!(fields["clientip"], "127.0.0.1");
+        assert_eq!(fields["auth"], "frank");
+        assert_eq!(fields["timestamp"], "10/Oct/2000:13:55:36 -0700");
+        assert_eq!(fields["verb"], "GET");
+        assert_eq!(fields["request"], "/apache_pb.gif");
+        assert_eq!(fields["response"], "200");
+        assert_eq!(fields["agent"], r#""Mozilla/4.08""#);
+    }
+
+    #[test]
+    fn test_parse_types_and_custom_patterns() {
+        let lookup = |name: &str| match name {
+            "ACTION" => Some("(?:login|logout)".to_string()),
+            _ => builtin(name),
+        };
+        let grok = Grok::new(
+            r"%{ACTION:action} user=%{USER:user} took=%{NUMBER:took:float}ms retries=%{INT:retries:int} (?<rest>.*)",
+            &lookup,
+        )
+        .unwrap();
+        let fields = grok
+            .parse("login user=root took=1.5ms retries=3 done")
+            .unwrap();
+        assert_eq!(fields["action"], "login");
+        assert_eq!(fields["user"], "root");
+        assert_eq!(fields["took"], 1.5);
+        assert_eq!(fields["retries"], 3);
+        assert_eq!(fields["rest"], "done");
+        assert!(grok.parse("reboot user=root").is_none());
+    }
+
+    #[test]
+    fn test_invalid_patterns() {
+        assert!(matches!(
+            Grok::new("%{NOPE}", &builtin),
+            Err(GrokError::UnknownPattern(_))
+        ));
+        let recursive = |name: &str| (name == "LOOP").then(|| "%{LOOP}".to_string());
+        assert!(matches!(
+            Grok::new("%{LOOP}", &recursive),
+            Err(GrokError::TooDeep(_))
+        ));
+    }
+}
@@ -70,12 +70,15 @@ pub(crate) fn get_search_event_context_from_request(
     query: &Query<HashMap<String, String>>,
 ) -> Option<SearchEventContext> {
     match search_event_type {
-        SearchEventType::Dashboards => Some(SearchEventContext::with_dashboard(
-            query.get("dashboard_id").map(String::from),
-            query.get("dashboard_name").map(String::from),
-            query.get("folder_id").map(String::from),
-            query.get("folder_name").map(String::from),
-        )),
+        SearchEventType::Dashboards => Some(SearchEventContext {
+            panel_id: query.get("panel_id").map(String::from),
+            ..SearchEventContext::with_dashboard(
+                query.get("dashboard_id").map(String::from),
+                query.get("dashboard_name").map(String::from),
+                query.get("folder_id").map(String::from),
+                query.get("folder_name").map(String::from),
+            )
+        }),
         SearchEventType::Alerts => Some(SearchEventContext::with_alert(
             query.get("alert_key").map(String::from),
         )),
@@ -272,6 +275,7 @@ mod tests {
         query.insert("dashboard_name".to_string(), "Test Dashboard".to_string());
         query.insert("folder_id".to_string(), "456".to_string());
         query.insert("folder_name".to_string(), "Test Folder".to_string());
+        query.insert("panel_id".to_string(), "Panel_ID1".to_string());
 
         let context =
             get_search_event_context_from_request(&SearchEventType::Dashboards, &query).unwrap();
@@ -282,6 +286,7 @@ mod tests {
             context.dashboard_folder_name,
             Some("Test Folder".to_string())
         );
+        assert_eq!(context.panel_id, Some("Panel_ID1".to_string()));
 
         let mut query = Query::<HashMap<String, String>>(Default::default());
         query.insert("alert_key".to_string(), "alert123".to_string());
@@ -16,8 +16,10 @@
 pub mod auth;
 mod auth_tests;
 pub mod functions;
+pub mod grok;
 pub mod http;
 pub mod jwt;
+pub mod parsers;
 pub mod redirect_response;
 pub mod stream;
 pub mod websocket;
@@ -0,0 +1,331 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! User agent, url and logfmt parsers shared by the SQL functions and the ingestion, so a field
+//! can be analyzed at query time with the same result as if it was parsed at ingestion.
+
+use std::sync::Arc;
+
+use config::utils::json;
+use once_cell::sync::Lazy;
+use uaparser::{Parser, UserAgentParser};
+use url::Url;
+use vrl::prelude::*;
+
+use crate::USER_AGENT_REGEX_FILE;
+
+/// This is a global cache for user agent parser. This is lazily initialized only when
+/// the first user agent is parsed.
+pub static UA_PARSER: Lazy<Arc<UserAgentParser>> = Lazy::new(|| Arc::new(initialize_ua_parser()));
+
+pub fn initialize_ua_parser() -> UserAgentParser {
+    UserAgentParser::builder()
+        .build_from_bytes(USER_AGENT_REGEX_FILE)
+        .expect("User Agent Parser creation failed")
+}
+
+/// Parse a user agent to a map with `user_agent`, `os` and `device` objects
+pub fn parse_user_agent(user_agent: &str) -> json::Value {
+    json::to_value(UA_PARSER.parse(user_agent)).unwrap_or_default()
+}
+
+/// Parse an url to a map with `scheme`, `username`, `password`, `host`, `port`, `path`,
+/// `query` and `fragment`, the same shape as the VRL `parse_url` function. Returns `None`
+/// if the value is not an absolute url.
+pub fn parse_url(value: &str) -> Option<json::Value> {
+    let url = Url::parse(value.trim()).ok()?;
+    let query = url
+        .query_pairs()
+        .map(|(k, v)| (k.into_owned(), json::Value::String(v.into_owned())))
+        .collect::<json::Map<_, _>>();
+    Some(json::json!({
+        "scheme": url.scheme(),
+        "username": url.username(),
+        "password": url.password().unwrap_or_default(),
+        "host": url.host_str(),
+        "port": url.port_or_known_default(),
+        "path": url.path(),
+        "query": query,
+        "fragment": url.fragment(),
+    }))
+}
+
+/// Parse the `key=value` pairs of a logfmt / key-value message to a map. Quoted values may
+/// contain spaces and `\"` escapes, words without `=` are skipped. Integers, floats and
+/// booleans are typed, everything else is kept as string. Returns `None` if there are no pairs.
+pub fn parse_logfmt(text: &str) -> Option<json::Value> {
+    let bytes = text.as_bytes();
+    let len = bytes.len();
+    let is_separator = |b: u8| b.is_ascii_whitespace() || b == b',' || b == b';';
+    let mut map = json::Map::new();
+    let mut i = 0;
+    while i < len {
+        while i < len && is_separator(bytes[i]) {
+            i += 1;
+        }
+        let key_start = i;
+        while i < len && !bytes[i].is_ascii_whitespace() && bytes[i] != b'=' && bytes[i] != b'"' {
+            i += 1;
+        }
+        let key = &text[key_start..i];
+        if key.is_empty() || i >= len || bytes[i] != b'=' {
+            // not a pair, skip the word or quoted text
+            if i < len && bytes[i] == b'"' {
+                i = read_quoted(bytes, i + 1).1;
+            }
+            while i < len && !bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            continue;
+        }
+        i += 1;
+        let value = if i < len && bytes[i] == b'"' {
+            let (value, end) = read_quoted(bytes, i + 1);
+            i = end;
+            json::Value::String(value)
+        } else {
+            let start = i;
+            while i < len && !bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            typed_value(text[start..i].trim_end_matches([',', ';']))
+        };
+        map.insert(key.to_string(), value);
+    }
+    (!map.is_empty()).then_some(json::Value::Object(map))
+}
+
+/// Reads a quoted value starting after the opening quote, returns the unescaped value and the
+/// position after the closing quote.
+fn read_quoted(bytes: &[u8], mut i: usize) -> (String, usize) {
+    let mut value = Vec::new();
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if i + 1 < bytes.len() => {
+                value.push(match bytes[i + 1] {
+                    b'n' => b'\n',
+                    b't' => b'\t',
+                    b => b,
+                });
+                i += 2;
+            }
+            b'"' => {
+                i += 1;
+                break;
+            }
+            b => {
+                value.push(b);
+                i += 1;
+            }
+        }
+    }
+    (String::from_utf8_lossy(&value).into_owned(), i)
+}
+
+/// Infers the type of a raw text value, numbers and booleans are typed and
+/// everything else stays a string
+pub fn typed_value(raw: &str) -> json::Value {
+    match raw {
+        "true" => return json::Value::Bool(true),
+        "false" => return json::Value::Bool(false),
+        _ => {}
+    }
+    // keep values like zip codes or ids with leading zeros as string
+    let digits = raw.trim_start_matches('-');
+    if digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.") {
+        return json::Value::String(raw.to_string());
+    }
+    if let Ok(v) = raw.parse::<i64>() {
+        return json::Value::from(v);
+    }
+    // only plain decimals, `inf` or `1e5` like words stay strings
+    let numeric = raw
+        .bytes()
+        .all(|b| b.is_ascii_digit() || b == b'.' || b == b'-');
+    match numeric.then(|| raw.parse::<f64>().ok()).flatten() {
+        Some(v) => json::Number::from_f64(v)
+            .map(json::Value::Number)
+            .unwrap_or_else(|| json::Value::String(raw.to_string())),
+        None => json::Value::String(raw.to_string()),
+    }
+}
+
+/// Get a nested value from the parsed map by a dotted path like `os.family`
+pub fn get_path<'a>(value: &'a json::Value, path: &str) -> Option<&'a json::Value> {
+    path.split('.')
+        .try_fold(value, |value, key| value.as_object()?.get(key))
+}
+
+/// VRL function `o2_parse_user_agent`, same output as the SQL `parse_user_agent` function
+#[derive(Clone, Copy, Debug)]
+pub struct O2ParseUserAgent;
+
+impl Function for O2ParseUserAgent {
+    fn identifier(&self) -> &'static str {
+        "o2_parse_user_agent"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "parse user agent",
+            source: r#"o2_parse_user_agent!("curl/8.5.0").user_agent.family"#,
+            result: Ok(r#""curl""#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        Ok(O2ParseUserAgentFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct O2ParseUserAgentFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for O2ParseUserAgentFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let user_agent = value.try_bytes_utf8_lossy()?;
+        Ok(Value::from(parse_user_agent(&user_agent)))
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(Collection::any()).fallible()
+    }
+}
+
+/// VRL function `o2_parse_url`, same output as the SQL `parse_url` function
+#[derive(Clone, Copy, Debug)]
+pub struct O2ParseUrl;
+
+impl Function for O2ParseUrl {
+    fn identifier(&self) -> &'static str {
+        "o2_parse_url"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "parse url",
+            source: r#"o2_parse_url!("https://openobserve.ai/docs?q=1").host"#,
+            result: Ok(r#""openobserve.ai""#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        Ok(O2ParseUrlFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct O2ParseUrlFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for O2ParseUrlFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let url = value.try_bytes_utf8_lossy()?;
+        match parse_url(&url) {
+            Some(parsed) => Ok(Value::from(parsed)),
+            None => Err(format!("unable to parse url: {url}").into()),
+        }
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(Collection::any()).fallible()
+    }
+}
+
+/// The VRL functions of this module, registered with the VRL compiler
+pub fn vrl_functions() -> Vec<Box<dyn Function>> {
+    vec![Box::new(O2ParseUserAgent), Box::new(O2ParseUrl)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_url() {
+        let parsed =
+            parse_url("https://user@openobserve.ai/docs/sql?q=select&size=10#top").unwrap();
+        assert_eq!(get_path(&parsed, "scheme").unwrap(), "https");
+        assert_eq!(get_path(&parsed, "username").unwrap(), "user");
+        assert_eq!(get_path(&parsed, "host").unwrap(), "openobserve.ai");
+        assert_eq!(get_path(&parsed, "port").unwrap(), 443);
+        assert_eq!(get_path(&parsed, "path").unwrap(), "/docs/sql");
+        assert_eq!(get_path(&parsed, "query.size").unwrap(), "10");
+        assert_eq!(get_path(&parsed, "fragment").unwrap(), "top");
+        assert!(get_path(&parsed, "query.missing").is_none());
+        assert!(parse_url("not a url").is_none());
+    }
+
+    #[test]
+    fn test_parse_user_agent() {
+        let parsed = parse_user_agent(
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+        );
+        assert_eq!(get_path(&parsed, "user_agent.family").unwrap(), "Chrome");
+        assert_eq!(get_path(&parsed, "os.family").unwrap(), "Windows");
+    }
+
+    #[test]
+    fn test_parse_logfmt() {
+        let parsed = parse_logfmt(
+            r#"level=error msg="request failed: \"timeout\"" duration=25ms status=504 ratio=0.5 retry=true zip=01234 user=bob, done"#,
+        )
+        .unwrap();
+        assert_eq!(parsed["level"], "error");
+        assert_eq!(parsed["msg"], r#"request failed: "timeout""#);
+        assert_eq!(parsed["duration"], "25ms");
+        assert_eq!(parsed["status"], 504);
+        assert_eq!(parsed["ratio"], 0.5);
+        assert_eq!(parsed["retry"], true);
+        assert_eq!(parsed["zip"], "01234");
+        assert_eq!(parsed["user"], "bob");
+        assert!(parsed.get("done").is_none());
+        assert!(parse_logfmt("no pairs in this line").is_none());
+        assert!(parse_logfmt(r#"a "quoted = text" b=1"#).unwrap()["b"] == 1);
+    }
+}
@@ -22,7 +22,7 @@ use once_cell::sync::Lazy;
 
 use crate::{
     get_config, ider,
-    meta::cluster::{Node, NodeStatus, Role, RoleGroup},
+    meta::cluster::{Node, NodeStatus, Role, RoleGroup, parse_node_labels},
 };
 
 pub static mut LOCAL_NODE_ID: i32 = 0;
@@ -37,6 +37,7 @@ pub fn load_local_node() -> Node {
         uuid: load_local_node_uuid(),
         role: load_local_node_role(),
         role_group: load_role_group(),
+        labels: parse_node_labels(&cfg.common.node_labels),
         name: cfg.common.instance_name.clone(),
         http_addr: format!(
             "{}://{}:{}",
@@ -413,6 +413,7 @@ pub struct Config {
     pub etcd: Etcd,
     pub nats: Nats,
     pub s3: S3,
+    pub replication: Replication,
     pub sns: Sns,
     pub tcp: TCP,
     pub prom: Prometheus,
@@ -598,6 +599,50 @@ pub struct Auth {
     pub ext_auth_salt: String,
     #[env_config(name = "O2_ACTION_SERVER_TOKEN")]
     pub action_server_token: String,
+    #[env_config(
+        name = "ZO_EXTERNAL_AUTHZ_URL",
+        help = "Webhook or OPA endpoint deciding the authorization of every request, empty disables external authorization"
+    )]
+    pub external_authz_url: String,
+    #[env_config(
+        name = "ZO_EXTERNAL_AUTHZ_TOKEN",
+        help = "Bearer token sent to the external authorization endpoint"
+    )]
+    pub external_authz_token: String,
+    #[env_config(name = "ZO_EXTERNAL_AUTHZ_TIMEOUT", default = 2)] // seconds
+    pub external_authz_timeout: u64,
+    #[env_config(name = "ZO_EXTERNAL_AUTHZ_CACHE_TTL", default = 60)] // seconds
+    pub external_authz_cache_ttl: i64,
+    #[env_config(
+        name = "ZO_EXTERNAL_AUTHZ_FAIL_OPEN",
+        default = false,
+        help = "Allow the requests when the external authorization endpoint can't be reached"
+    )]
+    pub external_authz_fail_open: bool,
+    #[env_config(
+        name = "ZO_SIGNUP_ENABLED",
+        default = false,
+        help = "Allow users to sign up on their own after verifying their email"
+    )]
+    pub signup_enabled: bool,
+    #[env_config(
+        name = "ZO_SIGNUP_ALLOWED_DOMAINS",
+        default = "",
+        help = "Comma separated email domains allowed to sign up, empty means no domain is allowed"
+    )]
+    pub signup_allowed_domains: String,
+    #[env_config(
+        name = "ZO_SIGNUP_ORG",
+        default = "default",
+        help = "The organization self signed up users join"
+    )]
+    pub signup_org: String,
+    #[env_config(
+        name = "ZO_SIGNUP_ROLE",
+        default = "viewer",
+        help = "The role given to self signed up users"
+    )]
+    pub signup_role: String,
 }
 
 #[derive(EnvConfig)]
@@ -714,6 +759,18 @@ pub struct Common {
         help = "Role group can be empty (default), interactive, or background"
     )]
     pub node_role_group: String,
+    #[env_config(
+        name = "ZO_NODE_LABELS",
+        default = "",
+        help = "Comma separated key=value labels of the node, e.g. tier=batch, used to route queries to querier nodes"
+    )]
+    pub node_labels: String,
+    #[env_config(
+        name = "ZO_QUERY_ROUTING",
+        default = "",
+        help = "Semicolon separated rules routing the queries of some search event types to the querier nodes matching all the labels, e.g. reports,search_job:tier=batch;ui,dashboards:tier=interactive"
+    )]
+    pub query_routing: String,
     #[env_config(name = "ZO_CLUSTER_NAME", default = "zo1")]
     pub cluster_name: String,
     #[env_config(name = "ZO_INSTANCE_NAME", default = "")]
@@ -774,6 +831,12 @@ pub struct Common {
     pub feature_query_remove_filter_with_index: bool,
     #[env_config(name = "ZO_FEATURE_QUERY_STREAMING_AGGS", default = true)]
     pub feature_query_streaming_aggs: bool,
+    #[env_config(
+        name = "ZO_FEATURE_APPROX_PERCENTILE_ENABLED",
+        default = true,
+        help = "Rewrite exact percentile_cont and median in dashboard queries to t-digest based approx_percentile_cont"
+    )]
+    pub feature_approx_percentile_enabled: bool,
     #[env_config(name = "ZO_FEATURE_JOIN_MATCH_ONE_ENABLED", default = false)]
     pub feature_join_match_one_enabled: bool,
     #[env_config(
@@ -869,6 +932,24 @@ pub struct Common {
     pub print_key_sql: bool,
     #[env_config(name = "ZO_USAGE_REPORTING_ENABLED", default = false)]
     pub usage_enabled: bool,
+    #[env_config(
+        name = "ZO_QUERY_AUDIT_ENABLED",
+        default = false,
+        help = "Record every executed query into the hash-chained query_audit stream of the meta org"
+    )]
+    pub query_audit_enabled: bool,
+    #[env_config(
+        name = "ZO_SEARCH_ESTIMATE_CREDITS_PER_GB",
+        default = 1,
+        help = "Credits of the query cost estimate per GB of compressed data scanned"
+    )]
+    pub search_estimate_credits_per_gb: u64,
+    #[env_config(
+        name = "ZO_SEARCH_ESTIMATE_CREDITS_PER_1000_FILES",
+        default = 1,
+        help = "Credits of the query cost estimate per 1000 files scanned"
+    )]
+    pub search_estimate_credits_per_1000_files: u64,
     #[env_config(
         name = "ZO_USAGE_REPORTING_MODE",
         default = "local",
@@ -1073,6 +1154,96 @@ pub struct Common {
         help = "Enable result cache for query results"
     )]
     pub result_cache_enabled: bool,
+    #[env_config(
+        name = "ZO_SEARCH_INFLIGHT_DEDUP_ENABLED",
+        default = true,
+        help = "Share one execution between identical concurrent search queries"
+    )]
+    pub search_inflight_dedup_enabled: bool,
+    #[env_config(
+        name = "ZO_PANEL_CACHE_ENABLED",
+        default = true,
+        help = "Cache the results of dashboard panel queries"
+    )]
+    pub panel_cache_enabled: bool,
+    #[env_config(
+        name = "ZO_PANEL_CACHE_TTL",
+        default = 60,
+        help = "Seconds a cached panel result is served, also the width of the time range buckets"
+    )]
+    pub panel_cache_ttl: i64,
+    #[env_config(
+        name = "ZO_PANEL_CACHE_MAX_ENTRIES",
+        default = 1000,
+        help = "Maximum number of cached panel results"
+    )]
+    pub panel_cache_max_entries: usize,
+    #[env_config(
+        name = "ZO_DASHBOARD_LINT_INTERVAL",
+        default = 3600,
+        help = "Seconds between the checks of the dashboards for deleted streams and fields, 0 disables the background check"
+    )]
+    pub dashboard_lint_interval: u64,
+    #[env_config(
+        name = "ZO_STREAM_HOURLY_STATS_ENABLED",
+        default = true,
+        help = "Maintain per-hour stream statistics at flush and compaction time"
+    )]
+    pub stream_hourly_stats_enabled: bool,
+    #[env_config(
+        name = "ZO_STREAM_HOURLY_STATS_RETENTION_DAYS",
+        default = 30,
+        help = "Days to keep per-hour stream statistics"
+    )]
+    pub stream_hourly_stats_retention_days: i64,
+    #[env_config(
+        name = "ZO_STREAM_FIELD_USAGE_ENABLED",
+        default = true,
+        help = "Count the fields referenced by the queries of each stream"
+    )]
+    pub stream_field_usage_enabled: bool,
+    #[env_config(
+        name = "ZO_STREAM_FIELD_USAGE_RETENTION_DAYS",
+        default = 90,
+        help = "Days to keep the daily field usage counts of streams"
+    )]
+    pub stream_field_usage_retention_days: i64,
+    #[env_config(
+        name = "ZO_INDEX_ADVISOR_ENABLED",
+        default = true,
+        help = "Log the slow queries and recommend the stream settings indexing the fields they filter on"
+    )]
+    pub index_advisor_enabled: bool,
+    #[env_config(
+        name = "ZO_INDEX_ADVISOR_SLOW_QUERY_THRESHOLD",
+        default = 10,
+        help = "Seconds after which a query is logged as slow for the index advisor"
+    )]
+    pub index_advisor_slow_query_threshold: u64,
+    #[env_config(
+        name = "ZO_INDEX_ADVISOR_LOOKBACK_DAYS",
+        default = 7,
+        help = "Days of slow queries kept and analyzed by the index advisor"
+    )]
+    pub index_advisor_lookback_days: i64,
+    #[env_config(
+        name = "ZO_INDEX_ADVISOR_MIN_SLOW_QUERIES",
+        default = 5,
+        help = "Slow queries which must filter on a field before the index advisor recommends indexing it"
+    )]
+    pub index_advisor_min_slow_queries: i64,
+    #[env_config(
+        name = "ZO_INDEX_ADVISOR_INTERVAL",
+        default = 86400,
+        help = "Seconds between the runs of the index advisor"
+    )]
+    pub index_advisor_interval: u64,
+    #[env_config(
+        name = "ZO_STORAGE_USAGE_HISTORY_DAYS",
+        default = 365,
+        help = "Days to keep the daily storage usage samples of streams"
+    )]
+    pub storage_usage_history_days: i64,
     #[env_config(
         name = "ZO_USE_MULTIPLE_RESULT_CACHE",
         default = false,
@@ -1099,6 +1270,18 @@ pub struct Common {
     pub metrics_cache_enabled: bool,
     #[env_config(name = "ZO_SWAGGER_ENABLED", default = true)]
     pub swagger_enabled: bool,
+    #[env_config(
+        name = "ZO_CHAOS_ENABLED",
+        default = false,
+        help = "Enable fault injection on the object store and meta store for resilience testing, never enable in production"
+    )]
+    pub chaos_enabled: bool,
+    #[env_config(
+        name = "ZO_CROSS_ORG_QUERY_USERS",
+        default = "",
+        help = "Comma separated emails of the users of the meta org allowed to search a stream across all organizations, cross-org search is disabled when empty"
+    )]
+    pub cross_org_query_users: String,
     #[env_config(name = "ZO_FAKE_ES_VERSION", default = "")]
     pub fake_es_version: String,
     #[env_config(name = "ZO_WEBSOCKET_ENABLED", default = false)]
@@ -1258,6 +1441,18 @@ pub struct Limit {
     pub metrics_max_series_per_query: usize,
     #[env_config(name = "ZO_METRICS_MAX_POINTS_PER_SERIES", default = 30000)]
     pub metrics_max_points_per_series: usize,
+    #[env_config(
+        name = "ZO_METRICS_MAX_SAMPLES_PER_QUERY",
+        default = 50000000,
+        help = "Maximum number of samples a querier loads into memory for a PromQL query"
+    )]
+    pub metrics_max_samples_per_query: usize,
+    #[env_config(
+        name = "ZO_METRICS_QUERY_SHARDING_ENABLED",
+        default = true,
+        help = "Split PromQL aggregations like sum by (...) across the queriers by series instead of by time"
+    )]
+    pub metrics_query_sharding_enabled: bool,
     #[env_config(name = "ZO_METRICS_CACHE_MAX_ENTRIES", default = 100000)]
     pub metrics_cache_max_entries: usize,
     #[env_config(name = "ZO_COLS_PER_RECORD_LIMIT", default = 1000)]
@@ -1318,6 +1513,24 @@ pub struct Limit {
     pub scheduler_clean_interval: i64,
     #[env_config(name = "ZO_SCHEDULER_WATCH_INTERVAL", default = 30)] // seconds
     pub scheduler_watch_interval: i64,
+    #[env_config(
+        name = "ZO_DETECTIONS_CHECK_INTERVAL",
+        default = 60,
+        help = "Seconds between checks for detections due for evaluation, 0 disables detections"
+    )]
+    pub detections_check_interval: i64,
+    #[env_config(
+        name = "ZO_THREAT_INTEL_CHECK_INTERVAL",
+        default = 300,
+        help = "Seconds between pulls of due threat intel feeds and removal of expired indicators, 0 disables it"
+    )]
+    pub threat_intel_check_interval: i64,
+    #[env_config(
+        name = "ZO_LOG_METRICS_FLUSH_INTERVAL",
+        default = 60,
+        help = "Seconds between writes of the metrics extracted from logs at ingest"
+    )]
+    pub log_metrics_flush_interval: i64,
     #[env_config(name = "ZO_SEARCH_JOB_WORKS", default = 1)]
     pub search_job_workers: i64,
     #[env_config(name = "ZO_SEARCH_JOB_SCHEDULE_INTERVAL", default = 10)] // seconds
@@ -1342,6 +1555,12 @@ pub struct Limit {
         help = "Retention for search job"
     )]
     pub search_job_retention: i64,
+    #[env_config(
+        name = "ZO_SEARCH_ARCHIVE_AFTER_DAYS",
+        default = 0, // days
+        help = "Data older than this is in the archive tier, interactive searches over it are rejected and have to be submitted as search jobs with include_archived, 0 to disable"
+    )]
+    pub search_archive_after_days: i64,
     #[env_config(name = "ZO_STARTING_EXPECT_QUERIER_NUM", default = 0)]
     pub starting_expect_querier_num: usize,
     #[env_config(name = "ZO_QUERY_OPTIMIZATION_NUM_FIELDS", default = 1000)]
@@ -1400,6 +1619,24 @@ pub struct Limit {
         help = "use multi thread for file list query"
     )]
     pub file_list_multi_thread: bool,
+    #[env_config(
+        name = "ZO_FILE_LIST_BATCH_ENABLED",
+        default = false,
+        help = "group the file list registration of the files flushed by ingesters into batched transactions"
+    )]
+    pub file_list_batch_enabled: bool,
+    #[env_config(
+        name = "ZO_FILE_LIST_BATCH_SIZE",
+        default = 100,
+        help = "max number of files registered in one file list transaction"
+    )]
+    pub file_list_batch_size: usize,
+    #[env_config(
+        name = "ZO_FILE_LIST_BATCH_INTERVAL",
+        default = 100,
+        help = "max time a flushed file waits for its file list batch to fill up"
+    )] // milliseconds
+    pub file_list_batch_interval: u64,
     #[env_config(name = "ZO_DISTINCT_VALUES_INTERVAL", default = 10)] // seconds
     pub distinct_values_interval: u64,
     #[env_config(name = "ZO_DISTINCT_VALUES_HOURLY", default = false)]
@@ -1426,6 +1663,12 @@ pub struct Limit {
         help = "Maximum size of a single enrichment table in mb"
     )]
     pub enrichment_table_max_size: usize,
+    #[env_config(
+        name = "ZO_ENRICHMENT_TABLE_BROADCAST_JOIN_MAX_ROWS",
+        default = 100000,
+        help = "Enrichment tables with at most this many cached rows are joined from memory on the leader instead of being scanned by queriers, 0 to disable"
+    )]
+    pub enrichment_table_broadcast_join_max_rows: usize,
     #[env_config(name = "ZO_SHORT_URL_RETENTION_DAYS", default = 30)] // days
     pub short_url_retention_days: i64,
     #[env_config(
@@ -1790,6 +2033,38 @@ pub struct S3 {
     pub multi_part_upload_size: usize,
 }
 
+#[derive(Debug, EnvConfig)]
+pub struct Replication {
+    #[env_config(
+        name = "ZO_REPLICATION_ENABLED",
+        default = false,
+        help = "Mirror new parquet files and file list entries to a secondary object storage"
+    )]
+    pub enabled: bool,
+    #[env_config(name = "ZO_REPLICATION_PROVIDER", default = "")]
+    pub provider: String,
+    #[env_config(name = "ZO_REPLICATION_SERVER_URL", default = "")]
+    pub server_url: String,
+    #[env_config(name = "ZO_REPLICATION_REGION_NAME", default = "")]
+    pub region_name: String,
+    #[env_config(name = "ZO_REPLICATION_ACCESS_KEY", default = "")]
+    pub access_key: String,
+    #[env_config(name = "ZO_REPLICATION_SECRET_KEY", default = "")]
+    pub secret_key: String,
+    #[env_config(name = "ZO_REPLICATION_BUCKET_NAME", default = "")]
+    pub bucket_name: String,
+    #[env_config(name = "ZO_REPLICATION_BUCKET_PREFIX", default = "")]
+    pub bucket_prefix: String,
+    #[env_config(name = "ZO_REPLICATION_INTERVAL", default = 60)] // seconds
+    pub interval: u64,
+    #[env_config(
+        name = "ZO_REPLICATION_BATCH_SIZE",
+        default = 1000,
+        help = "Max number of file list entries replicated in one round"
+    )]
+    pub batch_size: i64,
+}
+
 #[derive(Debug, EnvConfig)]
 pub struct Sns {
     #[env_config(name = "ZO_SNS_ENDPOINT", default = "")]
@@ -1969,6 +2244,11 @@ pub fn init() -> Config {
         panic!("s3 config error: {e}");
     }
 
+    // check replication config
+    if let Err(e) = check_replication_config(&mut cfg) {
+        panic!("replication config error: {e}");
+    }
+
     // check sns config
     if let Err(e) = check_sns_config(&mut cfg) {
         panic!("sns config error: {e}");
@@ -2079,6 +2359,12 @@ fn check_limit_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
     if cfg.limit.file_list_id_batch_size == 0 {
         cfg.limit.file_list_id_batch_size = 5000;
     }
+    if cfg.limit.file_list_batch_size == 0 {
+        cfg.limit.file_list_batch_size = 100;
+    }
+    if cfg.limit.file_list_batch_interval == 0 {
+        cfg.limit.file_list_batch_interval = 100;
+    }
 
     if cfg.limit.consistent_hash_vnodes == 0 {
         cfg.limit.consistent_hash_vnodes = 1000;
@@ -2131,6 +2417,9 @@ fn check_common_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
     if cfg.limit.metrics_max_points_per_series == 0 {
         cfg.limit.metrics_max_points_per_series = 30_000;
     }
+    if cfg.limit.metrics_max_samples_per_query == 0 {
+        cfg.limit.metrics_max_samples_per_query = 50_000_000;
+    }
     if cfg.limit.metrics_cache_max_entries == 0 {
         cfg.limit.metrics_cache_max_entries = 100_000;
     }
@@ -2701,6 +2990,33 @@ fn check_s3_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+fn check_replication_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
+    if cfg.replication.enabled {
+        if cfg.common.is_local_storage {
+            return Err(anyhow::anyhow!(
+                "replication requires object storage as the primary storage"
+            ));
+        }
+        if cfg.replication.bucket_name.is_empty() {
+            return Err(anyhow::anyhow!("ZO_REPLICATION_BUCKET_NAME is required"));
+        }
+    }
+    if !cfg.replication.bucket_prefix.is_empty() && !cfg.replication.bucket_prefix.ends_with('/') {
+        cfg.replication.bucket_prefix = format!("{}/", cfg.replication.bucket_prefix);
+    }
+    if cfg.replication.provider.is_empty() {
+        cfg.replication.provider = cfg.s3.provider.clone();
+    }
+    cfg.replication.provider = cfg.replication.provider.to_lowercase();
+    if cfg.replication.interval == 0 {
+        cfg.replication.interval = 60;
+    }
+    if cfg.replication.batch_size <= 0 {
+        cfg.replication.batch_size = 1000;
+    }
+    Ok(())
+}
+
 fn check_pipeline_config(cfg: &mut Config) -> Result<(), anyhow::Error> {
     // pipeline
     if cfg.pipeline.remote_stream_wal_dir.is_empty() {
@@ -21,7 +21,11 @@ use utoipa::ToSchema;
 
 use crate::{
     meta::{
-        alerts::{QueryCondition, TriggerCondition},
+        alerts::{
+            CorrelationConfig, PanelSource, QueryCondition, TriggerCondition,
+            anomaly::AnomalyCondition, composite::CompositeCondition,
+            multi_condition::MultiCondition,
+        },
         stream::StreamType,
         triggers::{ScheduledTriggerData, Trigger},
     },
@@ -72,6 +76,26 @@ pub struct Alert {
     pub updated_at: Option<DateTime<FixedOffset>>,
     #[serde(default)]
     pub last_edited_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation: Option<CorrelationConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub panel_source: Option<PanelSource>,
+    /// Set for composite alerts, which evaluate the firing state of other alerts instead of the
+    /// query condition.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub composite: Option<CompositeCondition>,
+    /// Set for multi-condition alerts, which evaluate several queries with their own thresholds
+    /// instead of the query condition.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multi_condition: Option<MultiCondition>,
+    /// Set for anomaly detection alerts, which fire when the aggregate of the period deviates
+    /// from its value in the same period of the previous days.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anomaly: Option<AnomalyCondition>,
+    /// Id of the escalation policy notified in steps while the alert fires unacknowledged, in
+    /// addition to the destinations of the alert.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub escalation_policy: Option<String>,
 }
 
 impl PartialEq for Alert {
@@ -104,6 +128,12 @@ impl Default for Alert {
             updated_at: None,
             last_edited_by: None,
             last_satisfied_at: None,
+            correlation: None,
+            panel_source: None,
+            composite: None,
+            multi_condition: None,
+            anomaly: None,
+            escalation_policy: None,
         }
     }
 }
@@ -180,6 +210,24 @@ impl Alert {
     pub fn set_last_triggered_at(&mut self, last_triggered_at: Option<i64>) {
         self.last_triggered_at = last_triggered_at;
     }
+
+    /// Returns the value of a label of a firing of the alert. `alert_name`, `stream_name` and
+    /// `stream_type` describe the alert itself, any other label is a column of the first row.
+    pub fn label_value(
+        &self,
+        rows: &[json::Map<String, json::Value>],
+        label: &str,
+    ) -> Option<String> {
+        match label {
+            "alert_name" => Some(self.name.clone()),
+            "stream_name" => Some(self.stream_name.clone()),
+            "stream_type" => Some(self.stream_type.to_string()),
+            _ => rows
+                .first()
+                .and_then(|row| row.get(label))
+                .map(json::get_string_value),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -213,6 +261,9 @@ pub struct ListAlertsParams {
     /// The optional owner with which to filter alerts.
     pub owner: Option<String>,
 
+    /// The optional alert IDs with which to filter alerts.
+    pub alert_ids: Option<Vec<String>>,
+
     /// The optional page size and page index of results to retrieve.
     pub page_size_and_idx: Option<(u64, u64)>,
 }
@@ -228,6 +279,7 @@ impl ListAlertsParams {
             stream_type_and_name: None,
             enabled: None,
             owner: None,
+            alert_ids: None,
             page_size_and_idx: None,
         }
     }
@@ -250,6 +302,12 @@ impl ListAlertsParams {
         self
     }
 
+    /// Filter alerts by the given alert IDs.
+    pub fn with_alert_ids(mut self, alert_ids: Vec<String>) -> Self {
+        self.alert_ids = Some(alert_ids);
+        self
+    }
+
     /// Paginate the results by the given page size and page index.
     pub fn paginate(mut self, page_size: u64, page_idx: u64) -> Self {
         self.page_size_and_idx = Some((page_size, page_idx));
@@ -0,0 +1,174 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Anomaly detection alerts compare the aggregate of the current window with a seasonal
+//! baseline, the same window on each of the previous days, instead of a fixed threshold.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::AggFunction;
+
+/// Maximum number of previous days in a baseline.
+pub const MAX_BASELINE_DAYS: i64 = 30;
+/// Minimum number of previous days with data for the baseline to be used.
+pub const MIN_BASELINE_SAMPLES: usize = 2;
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct AnomalyCondition {
+    /// Aggregate of the window compared with the baseline, required for custom queries. SQL
+    /// queries return the value in the `alert_agg_value` column of their first row.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<AggFunction>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    /// Number of previous days whose same window makes the baseline.
+    #[serde(default = "default_baseline_days")]
+    pub baseline_days: i64,
+    /// Deviation from the baseline mean from which the value is an anomaly.
+    pub deviation: f64,
+    #[serde(default)]
+    pub deviation_type: DeviationType,
+    #[serde(default)]
+    pub direction: AnomalyDirection,
+}
+
+fn default_baseline_days() -> i64 {
+    7
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DeviationType {
+    /// Number of standard deviations of the baseline.
+    #[default]
+    Sigma,
+    /// Percentage of the baseline mean.
+    Percent,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AnomalyDirection {
+    #[default]
+    Both,
+    Above,
+    Below,
+}
+
+/// Comparison of a value with its baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnomalyScore {
+    pub mean: f64,
+    pub stddev: f64,
+    /// Signed deviation of the value, in the unit of the deviation type. Infinite when the
+    /// baseline doesn't vary but the value differs from it.
+    pub deviation: f64,
+    pub samples: usize,
+}
+
+impl AnomalyCondition {
+    /// Scores the value against the baseline values, `None` when there aren't enough of them.
+    pub fn score(&self, value: f64, baseline: &[f64]) -> Option<AnomalyScore> {
+        if baseline.len() < MIN_BASELINE_SAMPLES {
+            return None;
+        }
+        let n = baseline.len() as f64;
+        let mean = baseline.iter().sum::<f64>() / n;
+        let stddev = (baseline.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+        let diff = value - mean;
+        let scale = match self.deviation_type {
+            DeviationType::Sigma => stddev,
+            DeviationType::Percent => mean.abs() / 100.0,
+        };
+        let deviation = if diff == 0.0 {
+            0.0
+        } else if scale == 0.0 {
+            f64::INFINITY.copysign(diff)
+        } else {
+            diff / scale
+        };
+        Some(AnomalyScore {
+            mean,
+            stddev,
+            deviation,
+            samples: baseline.len(),
+        })
+    }
+
+    pub fn is_anomaly(&self, score: &AnomalyScore) -> bool {
+        match self.direction {
+            AnomalyDirection::Both => score.deviation.abs() >= self.deviation,
+            AnomalyDirection::Above => score.deviation >= self.deviation,
+            AnomalyDirection::Below => -score.deviation >= self.deviation,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition(deviation: f64, deviation_type: DeviationType) -> AnomalyCondition {
+        AnomalyCondition {
+            function: Some(AggFunction::Count),
+            column: Some("_timestamp".to_string()),
+            baseline_days: 7,
+            deviation,
+            deviation_type,
+            direction: AnomalyDirection::Both,
+        }
+    }
+
+    #[test]
+    fn test_anomaly_sigma() {
+        let mut cond = condition(3.0, DeviationType::Sigma);
+        let baseline = [90.0, 110.0, 90.0, 110.0];
+        assert!(cond.score(100.0, &baseline[..1]).is_none());
+
+        let score = cond.score(140.0, &baseline).unwrap();
+        assert_eq!(score.mean, 100.0);
+        assert_eq!(score.stddev, 10.0);
+        assert_eq!(score.deviation, 4.0);
+        assert!(cond.is_anomaly(&score));
+
+        let score = cond.score(75.0, &baseline).unwrap();
+        assert_eq!(score.deviation, -2.5);
+        assert!(!cond.is_anomaly(&score));
+
+        let score = cond.score(60.0, &baseline).unwrap();
+        assert!(cond.is_anomaly(&score));
+        cond.direction = AnomalyDirection::Above;
+        assert!(!cond.is_anomaly(&score));
+
+        // a flat baseline makes any change an anomaly
+        let score = cond.score(101.0, &[100.0, 100.0]).unwrap();
+        assert!(score.deviation.is_infinite());
+        assert!(cond.is_anomaly(&score));
+        let score = cond.score(100.0, &[100.0, 100.0]).unwrap();
+        assert!(!cond.is_anomaly(&score));
+    }
+
+    #[test]
+    fn test_anomaly_percent() {
+        let mut cond = condition(50.0, DeviationType::Percent);
+        cond.direction = AnomalyDirection::Below;
+        let score = cond.score(40.0, &[100.0, 100.0, 100.0]).unwrap();
+        assert_eq!(score.deviation, -60.0);
+        assert!(cond.is_anomaly(&score));
+        let score = cond.score(200.0, &[100.0, 100.0, 100.0]).unwrap();
+        assert!(!cond.is_anomaly(&score));
+    }
+}
@@ -0,0 +1,228 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Composite alerts fire on the firing state of other alerts instead of on a query, e.g.
+//! `db_down AND (api_errors OR api_latency)`, so correlated failures notify once.
+
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use svix_ksuid::Ksuid;
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct CompositeCondition {
+    /// Boolean expression over the keys of `alerts`, with `AND`, `OR`, `NOT` and parentheses.
+    pub expression: String,
+    /// Alerts referenced in the expression, keyed by the name used in it.
+    #[schema(value_type = Object)]
+    pub alerts: HashMap<String, Ksuid>,
+    /// (minutes) An alert counts as firing when its condition was satisfied within the window,
+    /// 0 uses the period of the trigger condition.
+    #[serde(default)]
+    pub window: i64,
+}
+
+impl CompositeCondition {
+    /// Parses the expression and checks that it only references known alerts.
+    pub fn parse(&self) -> Result<CompositeExpr, String> {
+        let expr = CompositeExpr::parse(&self.expression)?;
+        let mut names = vec![];
+        expr.names(&mut names);
+        if let Some(name) = names.iter().find(|n| !self.alerts.contains_key(**n)) {
+            return Err(format!("alert {name} is not defined"));
+        }
+        Ok(expr)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CompositeExpr {
+    Alert(String),
+    Not(Box<CompositeExpr>),
+    And(Box<CompositeExpr>, Box<CompositeExpr>),
+    Or(Box<CompositeExpr>, Box<CompositeExpr>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Name(String),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = expression.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RParen);
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '-' || c == '.' => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_' || c == '-' || c == '.') {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(match word.to_uppercase().as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    _ => Token::Name(word),
+                });
+            }
+            c => return Err(format!("unexpected character '{c}' in expression")),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent parser, `NOT` binds tighter than `AND`, which binds tighter than `OR`.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<CompositeExpr, String> {
+        let mut left = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            left = CompositeExpr::Or(Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<CompositeExpr, String> {
+        let mut left = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            left = CompositeExpr::And(Box::new(left), Box::new(self.unary()?));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<CompositeExpr, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(CompositeExpr::Not(Box::new(self.unary()?))),
+            Some(Token::Name(name)) => Ok(CompositeExpr::Alert(name)),
+            Some(Token::LParen) => {
+                let expr = self.or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err("missing closing parenthesis".to_string()),
+                }
+            }
+            Some(token) => Err(format!("unexpected {token:?} in expression")),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+}
+
+impl CompositeExpr {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            tokens: tokenize(expression)?,
+            pos: 0,
+        };
+        let expr = parser.or()?;
+        if let Some(token) = parser.peek() {
+            return Err(format!("unexpected {token:?} in expression"));
+        }
+        Ok(expr)
+    }
+
+    /// Collects the alert names referenced in the expression.
+    pub fn names<'a>(&'a self, names: &mut Vec<&'a str>) {
+        match self {
+            CompositeExpr::Alert(name) => names.push(name),
+            CompositeExpr::Not(expr) => expr.names(names),
+            CompositeExpr::And(left, right) | CompositeExpr::Or(left, right) => {
+                left.names(names);
+                right.names(names);
+            }
+        }
+    }
+
+    pub fn evaluate(&self, firing: &impl Fn(&str) -> bool) -> bool {
+        match self {
+            CompositeExpr::Alert(name) => firing(name),
+            CompositeExpr::Not(expr) => !expr.evaluate(firing),
+            CompositeExpr::And(left, right) => left.evaluate(firing) && right.evaluate(firing),
+            CompositeExpr::Or(left, right) => left.evaluate(firing) || right.evaluate(firing),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_composite_expr() {
+        let expr = CompositeExpr::parse("A and (B OR not C)").unwrap();
+        let firing = |fired: &'static [&'static str]| move |name: &str| fired.contains(&name);
+        assert!(expr.evaluate(&firing(&["A", "B", "C"])));
+        assert!(expr.evaluate(&firing(&["A"])));
+        assert!(!expr.evaluate(&firing(&["A", "C"])));
+        assert!(!expr.evaluate(&firing(&["B"])));
+
+        // AND binds tighter than OR
+        let expr = CompositeExpr::parse("A OR B AND C").unwrap();
+        assert!(expr.evaluate(&firing(&["A"])));
+        assert!(!expr.evaluate(&firing(&["B"])));
+
+        let mut names = vec![];
+        expr.names(&mut names);
+        assert_eq!(names, vec!["A", "B", "C"]);
+
+        assert!(CompositeExpr::parse("A AND").is_err());
+        assert!(CompositeExpr::parse("(A OR B").is_err());
+        assert!(CompositeExpr::parse("A B").is_err());
+        assert!(CompositeExpr::parse("A & B").is_err());
+        assert!(CompositeExpr::parse("").is_err());
+
+        let condition = CompositeCondition {
+            expression: "db_down AND api_errors".to_string(),
+            alerts: HashMap::from([("db_down".to_string(), Ksuid::new(None, None))]),
+            window: 0,
+        };
+        assert!(condition.parse().is_err());
+    }
+}
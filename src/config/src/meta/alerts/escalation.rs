@@ -0,0 +1,167 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Escalation policies notify their steps one after the other while a firing alert is not
+//! acknowledged, e.g. a team channel right away and the on-call person after 10 minutes.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    meta::triggers::Incident,
+    utils::{
+        json::{Map, Value},
+        time::second_micros,
+    },
+};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct EscalationPolicy {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Notified in order, each once its delay passed since the alert started firing.
+    pub steps: Vec<EscalationStep>,
+    #[serde(default)]
+    pub created_at: i64,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct EscalationStep {
+    /// (minutes) Delay after the alert started firing, 0 notifies the step right away.
+    #[serde(default)]
+    pub delay: i64,
+    /// Names of the alert destinations notified by the step
+    pub destinations: Vec<String>,
+}
+
+impl EscalationPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("escalation policy must have a name".to_string());
+        }
+        if self.steps.is_empty() {
+            return Err("escalation policy must have at least one step".to_string());
+        }
+        let mut previous_delay = 0;
+        for (i, step) in self.steps.iter().enumerate() {
+            if step.destinations.is_empty() {
+                return Err(format!("escalation step {i} must have a destination"));
+            }
+            if step.delay < previous_delay {
+                return Err(format!(
+                    "escalation step {i} must not have a delay shorter than the previous step"
+                ));
+            }
+            previous_delay = step.delay;
+        }
+        Ok(())
+    }
+
+    /// Returns the time in microseconds the step is due for an alert firing since `started_at`,
+    /// or None if there is no such step.
+    pub fn due_at(&self, step: usize, started_at: i64) -> Option<i64> {
+        self.steps
+            .get(step)
+            .map(|s| started_at + second_micros(s.delay * 60))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct EscalationPolicyList {
+    pub list: Vec<EscalationPolicy>,
+}
+
+/// State of the escalation of a firing alert, kept in the data of its scheduled trigger.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct EscalationState {
+    pub policy_id: String,
+    /// (microseconds) When the alert started firing
+    pub started_at: i64,
+    /// Index of the next step to notify
+    #[serde(default)]
+    pub next_step: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acknowledged_by: Option<String>,
+    /// (microseconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acknowledged_at: Option<i64>,
+    /// Incidents opened by the steps, resolved when the alert recovers
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub incidents: Vec<Incident>,
+    /// Rows of the notification which started the escalation, sent again by the later steps
+    #[serde(default)]
+    pub rows: Vec<Map<String, Value>>,
+    #[serde(default)]
+    pub rows_end_time: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<i64>,
+    #[serde(default)]
+    pub evaluation_timestamp: i64,
+}
+
+impl EscalationState {
+    pub fn is_acknowledged(&self) -> bool {
+        self.acknowledged_at.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(delay: i64, destination: &str) -> EscalationStep {
+        EscalationStep {
+            delay,
+            destinations: vec![destination.to_string()],
+        }
+    }
+
+    #[test]
+    fn test_escalation_policy_validate() {
+        let mut policy = EscalationPolicy {
+            name: "payments".to_string(),
+            steps: vec![step(0, "slack"), step(10, "pagerduty")],
+            ..Default::default()
+        };
+        assert!(policy.validate().is_ok());
+        policy.steps = vec![step(10, "slack"), step(5, "pagerduty")];
+        assert!(policy.validate().is_err());
+        policy.steps = vec![step(0, "slack"), step(10, "pagerduty")];
+        policy.steps[1].destinations.clear();
+        assert!(policy.validate().is_err());
+        policy.steps.clear();
+        assert!(policy.validate().is_err());
+        policy.steps = vec![step(0, "slack")];
+        policy.name = " ".to_string();
+        assert!(policy.validate().is_err());
+    }
+
+    #[test]
+    fn test_escalation_policy_due_at() {
+        let policy = EscalationPolicy {
+            name: "payments".to_string(),
+            steps: vec![step(0, "slack"), step(10, "pagerduty")],
+            ..Default::default()
+        };
+        assert_eq!(policy.due_at(0, 1_000), Some(1_000));
+        assert_eq!(policy.due_at(1, 1_000), Some(1_000 + 600_000_000));
+        assert_eq!(policy.due_at(2, 1_000), None);
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Name of the logs stream every scheduled alert writes its state transitions into.
+pub const ALERT_HISTORY_STREAM: &str = "_alert_history";
+
+/// State of a scheduled alert.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertState {
+    /// The condition is satisfied but no notification could be sent yet.
+    Pending,
+    Firing,
+    Resolved,
+}
+
+/// One state transition of an alert, as stored in the alert history stream.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AlertHistoryEntry {
+    /// (microseconds) When the transition happened
+    #[serde(rename = "_timestamp")]
+    pub timestamp: i64,
+    pub alert_id: String,
+    pub alert_name: String,
+    pub stream_type: String,
+    pub stream_name: String,
+    pub state: AlertState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_state: Option<AlertState>,
+    /// The aggregated value, or the number of rows without aggregation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evaluated_value: Option<f64>,
+    #[serde(default)]
+    pub matched_rows: i64,
+    /// (microseconds) When the previous state started, set on resolution
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Number of state transitions of an alert over a time range.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct AlertActivity {
+    pub alert_name: String,
+    pub stream_type: String,
+    pub stream_name: String,
+    /// Times the alert started firing
+    pub fired: i64,
+    /// Times the alert recovered
+    pub resolved: i64,
+    /// Times the notification of the alert could not be sent
+    pub pending: i64,
+}
+
+/// State transitions of an alert, most recent first.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct AlertHistory {
+    pub list: Vec<AlertHistoryEntry>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::json;
+
+    #[test]
+    fn test_alert_history_entry_from_hit() {
+        let hit = json::json!({
+            "_timestamp": 1_700_000_000_000_000i64,
+            "alert_id": "2a7Bq1",
+            "alert_name": "errors",
+            "stream_type": "logs",
+            "stream_name": "default",
+            "state": "resolved",
+            "previous_state": "firing",
+            "matched_rows": 0,
+            "since": 1_699_999_000_000_000i64,
+            "error": null,
+        });
+        let entry: AlertHistoryEntry = json::from_value(hit).unwrap();
+        assert_eq!(entry.state, AlertState::Resolved);
+        assert_eq!(entry.previous_state, Some(AlertState::Firing));
+        assert_eq!(entry.evaluated_value, None);
+        assert_eq!(entry.error, None);
+        assert_eq!(entry.since, Some(1_699_999_000_000_000));
+    }
+}
@@ -21,7 +21,10 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 use crate::{
-    meta::search::SearchEventType,
+    meta::{
+        dashboards::reports::ReportDashboardVariable, search::SearchEventType,
+        triggers::EvaluationStats,
+    },
     utils::{
         json::{Map, Value},
         rand::get_rand_num_within,
@@ -29,6 +32,12 @@ use crate::{
 };
 
 pub mod alert;
+pub mod anomaly;
+pub mod composite;
+pub mod escalation;
+pub mod history;
+pub mod multi_condition;
+pub mod silences;
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
 pub struct TriggerCondition {
@@ -256,6 +265,110 @@ pub struct TriggerEvalResults {
     pub data: Option<Vec<Map<String, Value>>>,
     pub end_time: i64,
     pub query_took: Option<i64>,
+    /// Records scanned by the queries of the evaluation
+    #[serde(default)]
+    pub scan_records: Option<i64>,
+}
+
+/// Result of replaying a scheduled alert over a past time range.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct AlertBacktest {
+    /// Number of times the alert was evaluated.
+    pub evaluations: usize,
+    pub firings: Vec<BacktestFiring>,
+    /// The time range needed more evaluations than allowed, the firings stop at the last one.
+    pub truncated: bool,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct BacktestFiring {
+    /// Time the alert would have fired at, in microseconds.
+    pub timestamp: i64,
+    pub start_time: i64,
+    pub end_time: i64,
+    /// Number of rows that satisfied the condition.
+    pub matched_rows: usize,
+    /// The first rows that satisfied the condition, with the trigger values.
+    #[schema(value_type = Vec<Object>)]
+    pub rows: Vec<Map<String, Value>>,
+}
+
+/// Evaluation statistics of a scheduled alert, to find the alerts whose queries are too
+/// expensive for their frequency.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct AlertMetrics {
+    /// Seconds between two evaluations of the alert.
+    pub frequency: i64,
+    /// When the statistics started to be collected, in microseconds.
+    pub since: i64,
+    pub evaluations: u64,
+    pub failures: u64,
+    /// Runs skipped because the alert was evaluated too late.
+    pub skipped_runs: u64,
+    pub avg_took_in_secs: f64,
+    pub max_took_in_secs: f64,
+    pub last_took_in_secs: f64,
+    pub avg_scan_records: u64,
+    pub last_scan_records: u64,
+    /// Share of the frequency taken by an average evaluation. Close to or above 1, the
+    /// evaluations can't keep up with the frequency.
+    pub load: f64,
+    pub last_evaluated_at: Option<i64>,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<i64>,
+}
+
+impl AlertMetrics {
+    pub fn new(frequency: i64, stats: EvaluationStats) -> Self {
+        let (avg_took_in_secs, avg_scan_records) = if stats.evaluations > 0 {
+            (
+                stats.total_took_in_secs / stats.evaluations as f64,
+                stats.total_scan_records / stats.evaluations,
+            )
+        } else {
+            (0.0, 0)
+        };
+        let load = if frequency > 0 {
+            avg_took_in_secs / frequency as f64
+        } else {
+            0.0
+        };
+        Self {
+            frequency,
+            since: stats.since,
+            evaluations: stats.evaluations,
+            failures: stats.failures,
+            skipped_runs: stats.skipped,
+            avg_took_in_secs,
+            max_took_in_secs: stats.max_took_in_secs,
+            last_took_in_secs: stats.last_took_in_secs,
+            avg_scan_records,
+            last_scan_records: stats.last_scan_records,
+            load,
+            last_evaluated_at: stats.last_evaluated_at,
+            last_error: stats.last_error,
+            last_error_at: stats.last_error_at,
+        }
+    }
+}
+
+/// Changes made, or only planned in a dry run, by the bulk sync of the alerts of an org.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct AlertsSyncPlan {
+    pub dry_run: bool,
+    /// Alerts are named `{stream_type}/{stream_name}/{name}`.
+    pub alerts: SyncChanges,
+    pub destinations: SyncChanges,
+    pub templates: SyncChanges,
+}
+
+/// Names of the resources of a kind, by the change the sync makes to them.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct SyncChanges {
+    pub create: Vec<String>,
+    pub update: Vec<String>,
+    pub delete: Vec<String>,
+    pub unchanged: Vec<String>,
 }
 
 #[derive(Clone, Default, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
@@ -264,6 +377,60 @@ pub struct CompareHistoricData {
     pub offset: String,
 }
 
+/// Contrast analysis run when an alert fires, comparing the records of the firing window with a
+/// baseline window to find the field values whose frequency shifted the most.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct CorrelationConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// (minutes) how far the baseline window is shifted back from the firing window, defaults
+    /// to the alert period so the baseline is the window right before.
+    #[serde(default)]
+    pub baseline_offset: i64,
+    /// Fields to analyze, empty analyzes every string field of the sampled records.
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// Number of contributors to keep, defaults to 5.
+    #[serde(default)]
+    pub top: usize,
+    /// Number of records sampled from each window, defaults to 1000.
+    #[serde(default)]
+    pub sample_size: i64,
+}
+
+/// A field value whose share of records changed between the baseline and the firing window.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct CorrelationContributor {
+    pub field: String,
+    pub value: String,
+    pub firing_count: usize,
+    pub baseline_count: usize,
+    pub firing_ratio: f64,
+    pub baseline_ratio: f64,
+    /// Difference of the firing and baseline ratios, higher means more specific to the firing
+    /// window.
+    pub shift: f64,
+}
+
+/// Dashboard panel an alert was created from. The query condition of the alert is rebuilt from
+/// the panel query when the alert is synced with the panel.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct PanelSource {
+    pub dashboard_id: String,
+    pub panel_id: String,
+    /// Index of the query in the panel.
+    #[serde(default)]
+    pub query_index: usize,
+    /// Values of the dashboard variables used by the panel query.
+    #[serde(default)]
+    pub variables: Vec<ReportDashboardVariable>,
+    /// Condition on a column of the panel query, or on the value of a PromQL panel.
+    pub condition: Condition,
+    /// Time the query condition was last built from the panel. Unix timestamp in microseconds.
+    #[serde(default)]
+    pub synced_at: i64,
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
 pub enum FrequencyType {
     #[serde(rename = "cron")]
@@ -0,0 +1,97 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Multi-condition alerts evaluate several queries on their stream, each with its own operator
+//! and threshold, and combine the results with `AND` or `OR`, e.g. an error rate above 5% AND a
+//! p99 latency above 2s, instead of encoding everything into one SQL statement.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{Operator, QueryCondition};
+
+/// How the results of the conditions are combined.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConditionsOperator {
+    #[default]
+    And,
+    Or,
+}
+
+impl ConditionsOperator {
+    pub fn combine(&self, mut satisfied: impl Iterator<Item = bool>) -> bool {
+        match self {
+            ConditionsOperator::And => satisfied.all(|v| v),
+            ConditionsOperator::Or => satisfied.any(|v| v),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct MultiCondition {
+    #[serde(default)]
+    pub operator: ConditionsOperator,
+    pub conditions: Vec<AlertCondition>,
+}
+
+/// One query of a multi-condition alert. Like the trigger condition of a single query alert,
+/// the operator and threshold apply to the number of rows the query returns.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct AlertCondition {
+    /// Name of the condition, set in the `alert_condition` column of the rows it returns.
+    pub name: String,
+    pub query_condition: QueryCondition,
+    #[serde(default = "default_operator")]
+    pub operator: Operator,
+    #[serde(default = "default_threshold")]
+    pub threshold: i64,
+}
+
+fn default_operator() -> Operator {
+    Operator::GreaterThanEquals
+}
+
+fn default_threshold() -> i64 {
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::json;
+
+    #[test]
+    fn test_multi_condition() {
+        let multi: MultiCondition = json::from_value(json::json!({
+            "operator": "or",
+            "conditions": [
+                {"name": "errors", "query_condition": {"type": "sql", "sql": "SELECT count(*) AS c FROM \"default\" WHERE level = 'error' HAVING c > 10"}},
+                {"name": "latency", "query_condition": {"type": "sql", "sql": "SELECT approx_percentile_cont(took, 0.99) AS p99 FROM \"default\" HAVING p99 > 2000"}, "operator": ">", "threshold": 0},
+            ],
+        }))
+        .unwrap();
+        assert_eq!(multi.operator, ConditionsOperator::Or);
+        assert_eq!(multi.conditions[0].operator, Operator::GreaterThanEquals);
+        assert_eq!(multi.conditions[0].threshold, 1);
+        assert_eq!(multi.conditions[1].operator, Operator::GreaterThan);
+        assert_eq!(multi.conditions[1].threshold, 0);
+
+        assert!(ConditionsOperator::And.combine([true, true].into_iter()));
+        assert!(!ConditionsOperator::And.combine([true, false].into_iter()));
+        assert!(ConditionsOperator::Or.combine([false, true].into_iter()));
+        assert!(!ConditionsOperator::Or.combine([false, false].into_iter()));
+    }
+}
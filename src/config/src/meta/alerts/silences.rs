@@ -0,0 +1,204 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Silences mute the notifications of the alerts matching their label matchers during a time
+//! window, e.g. for a maintenance. Muted alerts are still evaluated, only the notification is
+//! dropped and recorded as a [`SilencedNotification`].
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct Silence {
+    #[serde(default)]
+    pub id: String,
+    /// A notification is muted when every matcher matches the labels of the alert.
+    pub matchers: Vec<SilenceMatcher>,
+    /// (microseconds)
+    pub start_time: i64,
+    /// (microseconds)
+    pub end_time: i64,
+    #[serde(default)]
+    pub comment: String,
+    #[serde(default)]
+    pub created_by: String,
+    #[serde(default)]
+    pub created_at: i64,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct SilenceMatcher {
+    /// `alert_name`, `stream_name`, `stream_type` or a column of the alert rows.
+    pub name: String,
+    pub value: String,
+    #[serde(default)]
+    pub op: MatchOp,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub enum MatchOp {
+    #[default]
+    #[serde(rename = "=")]
+    Equal,
+    #[serde(rename = "!=")]
+    NotEqual,
+    #[serde(rename = "=~")]
+    Regex,
+    #[serde(rename = "!~")]
+    NotRegex,
+}
+
+impl SilenceMatcher {
+    fn regex(&self) -> Result<Regex, regex::Error> {
+        // anchored like the prometheus label matchers
+        Regex::new(&format!("^(?:{})$", self.value))
+    }
+
+    /// A missing label matches as an empty value.
+    fn matches(&self, value: Option<&str>) -> bool {
+        let value = value.unwrap_or_default();
+        match self.op {
+            MatchOp::Equal => value == self.value,
+            MatchOp::NotEqual => value != self.value,
+            MatchOp::Regex => self.regex().is_ok_and(|re| re.is_match(value)),
+            MatchOp::NotRegex => self.regex().is_ok_and(|re| !re.is_match(value)),
+        }
+    }
+}
+
+impl Silence {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.matchers.is_empty() {
+            return Err("silence must have at least one matcher".to_string());
+        }
+        if self.end_time <= self.start_time {
+            return Err("silence end_time must be after start_time".to_string());
+        }
+        for matcher in self.matchers.iter() {
+            if matcher.name.is_empty() {
+                return Err("silence matcher must have a name".to_string());
+            }
+            if matches!(matcher.op, MatchOp::Regex | MatchOp::NotRegex)
+                && let Err(e) = matcher.regex()
+            {
+                return Err(format!("invalid regex for matcher {}: {e}", matcher.name));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_active(&self, now: i64) -> bool {
+        self.start_time <= now && now < self.end_time
+    }
+
+    /// Checks if the silence is active at `now` and every matcher matches the labels returned by
+    /// `label_value`.
+    pub fn matches(&self, now: i64, label_value: impl Fn(&str) -> Option<String>) -> bool {
+        self.is_active(now)
+            && self
+                .matchers
+                .iter()
+                .all(|m| m.matches(label_value(&m.name).as_deref()))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SilenceList {
+    pub list: Vec<Silence>,
+}
+
+/// Audit record of a notification muted by a silence.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct SilencedNotification {
+    pub silence_id: String,
+    pub alert_id: String,
+    pub alert_name: String,
+    /// Number of rows of the muted notification
+    pub rows: i64,
+    /// (microseconds)
+    pub silenced_at: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SilencedNotificationList {
+    pub list: Vec<SilencedNotification>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matcher(name: &str, op: MatchOp, value: &str) -> SilenceMatcher {
+        SilenceMatcher {
+            name: name.to_string(),
+            value: value.to_string(),
+            op,
+        }
+    }
+
+    #[test]
+    fn test_silence_matches() {
+        let silence = Silence {
+            matchers: vec![
+                matcher("stream_name", MatchOp::Equal, "k8s_logs"),
+                matcher("host", MatchOp::Regex, "db-.*"),
+                matcher("env", MatchOp::NotEqual, "prod"),
+            ],
+            start_time: 100,
+            end_time: 200,
+            ..Default::default()
+        };
+        assert!(silence.validate().is_ok());
+        let labels = |label: &str| match label {
+            "stream_name" => Some("k8s_logs".to_string()),
+            "host" => Some("db-1".to_string()),
+            _ => None,
+        };
+        assert!(silence.matches(150, labels));
+        assert!(!silence.matches(200, labels));
+        assert!(!silence.matches(99, labels));
+        // regex is anchored
+        let labels = |label: &str| match label {
+            "stream_name" => Some("k8s_logs".to_string()),
+            "host" => Some("old-db-1".to_string()),
+            _ => None,
+        };
+        assert!(!silence.matches(150, labels));
+    }
+
+    #[test]
+    fn test_silence_validate() {
+        let mut silence = Silence {
+            matchers: vec![matcher("host", MatchOp::Regex, "db-(")],
+            start_time: 100,
+            end_time: 200,
+            ..Default::default()
+        };
+        assert!(silence.validate().is_err());
+        silence.matchers = vec![];
+        assert!(silence.validate().is_err());
+        silence.matchers = vec![matcher("host", MatchOp::Equal, "db")];
+        silence.end_time = 100;
+        assert!(silence.validate().is_err());
+        silence.end_time = 101;
+        assert!(silence.validate().is_ok());
+        let parsed: SilenceMatcher =
+            crate::utils::json::from_str(r#"{"name":"host","value":"db-.*","op":"=~"}"#).unwrap();
+        assert_eq!(parsed.op, MatchOp::Regex);
+    }
+}
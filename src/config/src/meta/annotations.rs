@@ -0,0 +1,234 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// An org level event, e.g. a deploy or an incident, which dashboards panels can overlay.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Annotation {
+    #[serde(default)]
+    pub id: String,
+    /// Time in microseconds
+    pub start_time: i64,
+    /// Time in microseconds, an annotation without end time marks a single point in time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<i64>,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// Who created the annotation, `api` or `webhook`
+    #[serde(default)]
+    pub source: String,
+    #[serde(default)]
+    pub created_at: i64,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+impl Annotation {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.title.trim().is_empty() {
+            return Err("title cannot be empty".to_string());
+        }
+        if self.start_time <= 0 {
+            return Err("start time must be positive".to_string());
+        }
+        if self
+            .end_time
+            .is_some_and(|end_time| end_time <= self.start_time)
+        {
+            return Err("end time must be greater than start time".to_string());
+        }
+        if self.tags.iter().any(|tag| tag.is_empty()) {
+            return Err("tag cannot be empty".to_string());
+        }
+        if self
+            .link
+            .as_ref()
+            .is_some_and(|link| !link.starts_with("http://") && !link.starts_with("https://"))
+        {
+            return Err("link must be an http or https url".to_string());
+        }
+        Ok(())
+    }
+
+    /// Returns true if the annotation overlaps with the given time range.
+    pub fn overlaps(&self, start_time: i64, end_time: i64) -> bool {
+        self.start_time <= end_time && self.end_time.unwrap_or(self.start_time) >= start_time
+    }
+
+    /// Returns true if the annotation has all the given tags.
+    pub fn has_tags(&self, tags: &[String]) -> bool {
+        tags.iter().all(|tag| self.tags.contains(tag))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AnnotationList {
+    pub list: Vec<Annotation>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(style = Form, parameter_in = Query)]
+#[serde(rename_all = "snake_case")]
+#[into_params(rename_all = "snake_case")]
+pub struct ListAnnotationsQuery {
+    /// Time in microseconds
+    pub start_time: i64,
+    /// Time in microseconds
+    pub end_time: i64,
+    /// Commas separated list of tags, only annotations having all of them are returned
+    pub tags: Option<String>,
+}
+
+impl ListAnnotationsQuery {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.start_time >= self.end_time {
+            return Err("start time must be less than end time".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn get_tags(&self) -> Vec<String> {
+        self.tags
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(|tag| tag.trim())
+            .filter(|tag| !tag.is_empty())
+            .map(|tag| tag.to_string())
+            .collect()
+    }
+}
+
+/// Generic event payload sent by CI/CD systems, e.g. at the end of a deploy job.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct AnnotationWebhookEvent {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default, alias = "description", alias = "message")]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default, alias = "url")]
+    pub link: Option<String>,
+    /// Time in microseconds, defaults to the time the event is received
+    #[serde(default, alias = "timestamp")]
+    pub start_time: Option<i64>,
+    /// Time in microseconds
+    #[serde(default)]
+    pub end_time: Option<i64>,
+    #[serde(default)]
+    pub service: Option<String>,
+    #[serde(default, alias = "env")]
+    pub environment: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+impl AnnotationWebhookEvent {
+    /// Maps the event to an annotation, deploy details are turned into tags and a default title.
+    pub fn into_annotation(self, now: i64) -> Annotation {
+        let mut tags = self.tags;
+        for (key, value) in [
+            ("service", &self.service),
+            ("env", &self.environment),
+            ("version", &self.version),
+        ] {
+            let Some(value) = value.as_ref().filter(|v| !v.is_empty()) else {
+                continue;
+            };
+            let tag = format!("{key}:{value}");
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        let title = self
+            .title
+            .filter(|t| !t.trim().is_empty())
+            .unwrap_or_else(|| {
+                let mut title = "Deploy".to_string();
+                if let Some(service) = self.service.as_ref() {
+                    title.push(' ');
+                    title.push_str(service);
+                }
+                if let Some(version) = self.version.as_ref() {
+                    title.push(' ');
+                    title.push_str(version);
+                }
+                if let Some(env) = self.environment.as_ref() {
+                    title.push_str(&format!(" to {env}"));
+                }
+                title
+            });
+        Annotation {
+            start_time: self.start_time.unwrap_or(now),
+            end_time: self.end_time,
+            title,
+            text: self.text,
+            tags,
+            link: self.link,
+            source: "webhook".to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotation_validate_and_overlaps() {
+        let mut a = Annotation {
+            start_time: 100,
+            title: "deploy".to_string(),
+            ..Default::default()
+        };
+        assert!(a.validate().is_ok());
+        assert!(a.overlaps(50, 100));
+        assert!(!a.overlaps(101, 200));
+
+        a.end_time = Some(200);
+        assert!(a.overlaps(150, 300));
+        a.end_time = Some(100);
+        assert!(a.validate().is_err());
+        a.end_time = None;
+        a.link = Some("javascript:alert(1)".to_string());
+        assert!(a.validate().is_err());
+    }
+
+    #[test]
+    fn test_webhook_event_into_annotation() {
+        let event: AnnotationWebhookEvent = serde_json::from_str(
+            r#"{"service":"api","env":"prod","version":"v1.2.0","url":"https://ci/1","tags":["deploy"]}"#,
+        )
+        .unwrap();
+        let a = event.into_annotation(42);
+        assert_eq!(a.title, "Deploy api v1.2.0 to prod");
+        assert_eq!(a.start_time, 42);
+        assert_eq!(a.link.as_deref(), Some("https://ci/1"));
+        assert_eq!(
+            a.tags,
+            vec!["deploy", "service:api", "env:prod", "version:v1.2.0"]
+        );
+        assert!(a.has_tags(&["deploy".to_string(), "env:prod".to_string()]));
+        assert!(a.validate().is_ok());
+    }
+}
@@ -0,0 +1,176 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::detections::Severity;
+use crate::utils::json;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CaseStatus {
+    #[default]
+    Open,
+    InProgress,
+    Closed,
+}
+
+impl fmt::Display for CaseStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CaseStatus::Open => write!(f, "open"),
+            CaseStatus::InProgress => write!(f, "in_progress"),
+            CaseStatus::Closed => write!(f, "closed"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentKind {
+    /// A search query, `data` holds the query request.
+    Query,
+    /// One or more log records.
+    Record,
+    /// A trace, `data` holds at least the `trace_id`.
+    Trace,
+    /// A finding of a detection or of the threat intel matcher.
+    Finding,
+    /// A snapshot of a dashboard, `data` holds the dashboard definition and the time range.
+    Dashboard,
+}
+
+impl fmt::Display for AttachmentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttachmentKind::Query => write!(f, "query"),
+            AttachmentKind::Record => write!(f, "record"),
+            AttachmentKind::Trace => write!(f, "trace"),
+            AttachmentKind::Finding => write!(f, "finding"),
+            AttachmentKind::Dashboard => write!(f, "dashboard"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct CaseAttachment {
+    #[serde(default)]
+    pub id: String,
+    pub kind: AttachmentKind,
+    #[serde(default)]
+    pub title: String,
+    #[schema(value_type = Object)]
+    pub data: json::Value,
+    #[serde(default)]
+    pub added_by: String,
+    #[serde(default)]
+    pub added_at: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct CaseComment {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub author: String,
+    pub text: String,
+    #[serde(default)]
+    pub created_at: i64,
+}
+
+/// An entry of the activity log of a case, the timeline export is built from these.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct CaseEvent {
+    pub timestamp: i64,
+    pub actor: String,
+    pub action: String,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct Case {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub org_id: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub status: CaseStatus,
+    #[serde(default)]
+    pub severity: Severity,
+    #[serde(default)]
+    pub assignee: Option<String>,
+    #[serde(default)]
+    pub created_by: String,
+    #[serde(default)]
+    pub created_at: i64,
+    #[serde(default)]
+    pub updated_at: i64,
+    #[serde(default)]
+    pub attachments: Vec<CaseAttachment>,
+    #[serde(default)]
+    pub comments: Vec<CaseComment>,
+    #[serde(default)]
+    pub events: Vec<CaseEvent>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct CaseList {
+    pub list: Vec<Case>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct CreateCaseRequest {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub severity: Severity,
+    #[serde(default)]
+    pub assignee: Option<String>,
+}
+
+/// Fields left out are not changed.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct UpdateCaseRequest {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub status: Option<CaseStatus>,
+    #[serde(default)]
+    pub severity: Option<Severity>,
+    /// An empty string removes the assignee.
+    #[serde(default)]
+    pub assignee: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct CaseTimelineEntry {
+    pub timestamp: i64,
+    pub actor: String,
+    pub action: String,
+    pub message: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub data: Option<json::Value>,
+}
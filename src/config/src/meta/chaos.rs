@@ -0,0 +1,170 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Longest latency a scenario can add to an operation, one minute.
+pub const MAX_LATENCY_MS: u64 = 60_000;
+
+/// Operations a scenario can be limited to.
+pub const CHAOS_OPERATIONS: [&str; 6] = ["get", "head", "put", "delete", "list", "acquire"];
+
+/// Layer faults are injected into.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChaosTarget {
+    /// The object store holding the data files
+    #[default]
+    Storage,
+    /// The meta store
+    Db,
+    /// The file list of the data files
+    FileList,
+    /// Acquiring a connection of the SQL meta store, which holds the ORM tables
+    Orm,
+}
+
+impl fmt::Display for ChaosTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChaosTarget::Storage => write!(f, "storage"),
+            ChaosTarget::Db => write!(f, "db"),
+            ChaosTarget::FileList => write!(f, "file_list"),
+            ChaosTarget::Orm => write!(f, "orm"),
+        }
+    }
+}
+
+impl FromStr for ChaosTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "storage" => Ok(ChaosTarget::Storage),
+            "db" => Ok(ChaosTarget::Db),
+            "file_list" => Ok(ChaosTarget::FileList),
+            "orm" => Ok(ChaosTarget::Orm),
+            _ => Err(format!(
+                "unknown chaos target [{s}], expected storage, db, file_list or orm"
+            )),
+        }
+    }
+}
+
+/// Faults injected into the operations of a target while the scenario is active.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ChaosScenario {
+    #[serde(default)]
+    pub target: ChaosTarget,
+    /// Latency added to every affected operation, in milliseconds
+    #[serde(default)]
+    pub latency_ms: u64,
+    /// Share of the affected operations which fail, between 0 and 1
+    #[serde(default)]
+    pub error_rate: f64,
+    /// Affected operations among `get`, `head`, `put`, `delete`, `list` and `acquire`, all if
+    /// empty
+    #[serde(default)]
+    pub operations: Vec<String>,
+    /// Only affect the object paths or keys with this prefix
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_prefix: Option<String>,
+    /// Time in microseconds after which the scenario is inactive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+}
+
+impl ChaosScenario {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.latency_ms > MAX_LATENCY_MS {
+            return Err(format!("latency must be at most {MAX_LATENCY_MS} ms"));
+        }
+        if !(0.0..=1.0).contains(&self.error_rate) {
+            return Err("error rate must be between 0 and 1".to_string());
+        }
+        if self.latency_ms == 0 && self.error_rate == 0.0 {
+            return Err("scenario must add latency or errors".to_string());
+        }
+        if let Some(op) = self
+            .operations
+            .iter()
+            .find(|op| !CHAOS_OPERATIONS.contains(&op.as_str()))
+        {
+            return Err(format!(
+                "unknown operation [{op}], expected one of {}",
+                CHAOS_OPERATIONS.join(", ")
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns true if the scenario affects the operation on the key at the given time.
+    pub fn applies(&self, operation: &str, key: &str, now: i64) -> bool {
+        self.expires_at.is_none_or(|t| now < t)
+            && (self.operations.is_empty() || self.operations.iter().any(|op| op == operation))
+            && self.key_prefix.as_deref().is_none_or(|prefix| {
+                key.trim_start_matches('/')
+                    .starts_with(prefix.trim_start_matches('/'))
+            })
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct ChaosScenarioList {
+    pub list: Vec<ChaosScenario>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chaos_scenario() {
+        let mut scenario = ChaosScenario {
+            target: ChaosTarget::Storage,
+            latency_ms: 200,
+            error_rate: 0.1,
+            operations: vec!["put".to_string()],
+            key_prefix: Some("files/default/logs".to_string()),
+            expires_at: Some(1000),
+        };
+        assert!(scenario.validate().is_ok());
+        assert!(scenario.applies("put", "files/default/logs/app/1.parquet", 10));
+        assert!(scenario.applies("put", "/files/default/logs/app/1.parquet", 10));
+        assert!(!scenario.applies("get", "files/default/logs/app/1.parquet", 10));
+        assert!(!scenario.applies("put", "files/other/logs/app/1.parquet", 10));
+        assert!(!scenario.applies("put", "files/default/logs/app/1.parquet", 1000));
+
+        scenario.operations = vec!["rename".to_string()];
+        assert!(scenario.validate().is_err());
+        scenario.operations.clear();
+        scenario.error_rate = 2.0;
+        assert!(scenario.validate().is_err());
+        scenario.error_rate = 0.0;
+        scenario.latency_ms = 0;
+        assert!(scenario.validate().is_err());
+
+        assert_eq!("db".parse::<ChaosTarget>(), Ok(ChaosTarget::Db));
+        assert_eq!(
+            "file_list".parse::<ChaosTarget>(),
+            Ok(ChaosTarget::FileList)
+        );
+        assert_eq!(ChaosTarget::Orm.to_string(), "orm");
+        assert!("queue".parse::<ChaosTarget>().is_err());
+    }
+}
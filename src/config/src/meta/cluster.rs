@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::{fmt::Debug, str::FromStr, sync::Arc};
+use std::{collections::HashMap, fmt::Debug, str::FromStr, sync::Arc};
 
 use serde::{Deserialize, Serialize};
 
@@ -48,6 +48,9 @@ pub struct Node {
     pub role: Vec<Role>,
     #[serde(default)]
     pub role_group: RoleGroup,
+    /// Workload labels of the node, e.g. `tier=batch`, queries are routed by them
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
     pub cpu_num: u64,
     #[serde(default)]
     pub scheduled: bool,
@@ -70,6 +73,7 @@ impl Node {
             grpc_addr: "".to_string(),
             role: vec![],
             role_group: RoleGroup::None,
+            labels: HashMap::new(),
             cpu_num: 0,
             scheduled: false,
             broadcasted: false,
@@ -86,6 +90,7 @@ impl Node {
             && self.grpc_addr == other.grpc_addr
             && self.role == other.role
             && self.role_group == other.role_group
+            && self.labels == other.labels
             && self.scheduled == other.scheduled
             && self.broadcasted == other.broadcasted
             && self.status == other.status
@@ -114,6 +119,12 @@ impl Node {
         self.is_querier()
             && (self.role_group == RoleGroup::None || self.role_group == RoleGroup::Background)
     }
+    /// Checks if the node has all the labels of the selector.
+    pub fn matches_labels(&self, selector: &HashMap<String, String>) -> bool {
+        selector
+            .iter()
+            .all(|(k, v)| self.labels.get(k).is_some_and(|l| l == v))
+    }
     pub fn is_compactor(&self) -> bool {
         self.role.contains(&Role::Compactor) || self.role.contains(&Role::All)
     }
@@ -279,6 +290,53 @@ impl std::fmt::Display for RoleGroup {
     }
 }
 
+/// Parses comma separated `key=value` labels, entries without a value are skipped.
+pub fn parse_node_labels(s: &str) -> HashMap<String, String> {
+    s.split(',')
+        .filter_map(|label| {
+            let (k, v) = label.split_once('=')?;
+            let (k, v) = (k.trim(), v.trim());
+            (!k.is_empty() && !v.is_empty()).then(|| (k.to_string(), v.to_string()))
+        })
+        .collect()
+}
+
+/// Parses the query routing rules, `;` separated `<search event types>:<labels>` such as
+/// `reports,search_job:tier=batch`. Rules with unknown search event types are skipped.
+pub fn parse_query_routing(s: &str) -> Vec<(Vec<SearchEventType>, HashMap<String, String>)> {
+    s.split(';')
+        .filter_map(|rule| {
+            let (types, labels) = rule.split_once(':')?;
+            let types = types
+                .split(',')
+                .map(|t| SearchEventType::try_from(t.trim()))
+                .collect::<Result<Vec<_>, _>>();
+            let types = match types {
+                Ok(types) => types,
+                Err(e) => {
+                    log::warn!("[CLUSTER] skip query routing rule {rule}: {e}");
+                    return None;
+                }
+            };
+            let labels = parse_node_labels(labels);
+            (!labels.is_empty()).then_some((types, labels))
+        })
+        .collect()
+}
+
+/// Returns the labels of the querier nodes the queries of the search event type are routed to,
+/// the first matching rule of `ZO_QUERY_ROUTING` wins.
+pub fn get_query_node_selector(event_type: SearchEventType) -> Option<HashMap<String, String>> {
+    let cfg = get_config();
+    if cfg.common.query_routing.is_empty() {
+        return None;
+    }
+    parse_query_routing(&cfg.common.query_routing)
+        .into_iter()
+        .find(|(types, _)| types.contains(&event_type))
+        .map(|(_, labels)| labels)
+}
+
 #[inline]
 pub fn get_internal_grpc_token() -> String {
     let cfg = get_config();
@@ -299,3 +357,29 @@ pub enum CompactionJobType {
     Current,
     Historical,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query_routing() {
+        let rules = parse_query_routing(
+            "reports, search_job:tier=batch;ui,dashboards:tier=interactive,zone=eu;bogus:tier=x",
+        );
+        assert_eq!(rules.len(), 2);
+        assert_eq!(
+            rules[0].0,
+            vec![SearchEventType::Reports, SearchEventType::SearchJob]
+        );
+        assert_eq!(rules[0].1.get("tier").map(String::as_str), Some("batch"));
+        assert_eq!(rules[1].1.len(), 2);
+
+        let mut node = Node::new();
+        node.labels = parse_node_labels("tier=interactive, zone=eu,invalid");
+        assert_eq!(node.labels.len(), 2);
+        assert!(node.matches_labels(&rules[1].1));
+        assert!(!node.matches_labels(&rules[0].1));
+        assert!(node.matches_labels(&HashMap::new()));
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{row_policy::policy_key, stream::StreamType, user::UserRole};
+
+/// Replacement text of the masked part of a value.
+pub const MASK: &str = "****";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MaskType {
+    /// The column reads as null.
+    #[default]
+    Null,
+    /// The column reads as the hex sha256 of its value, so values can still be grouped.
+    Hash,
+    /// Only the first `keep_start` and the last `keep_end` characters are kept.
+    Partial,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct ColumnMask {
+    pub field: String,
+    #[serde(rename = "type", default)]
+    pub mask_type: MaskType,
+    #[serde(default)]
+    pub keep_start: usize,
+    #[serde(default)]
+    pub keep_end: usize,
+}
+
+impl ColumnMask {
+    /// Sql expression of the masked value of the column, `column` is the quoted column name.
+    pub fn sql_expr(&self, column: &str) -> String {
+        match self.mask_type {
+            MaskType::Null => "NULL".to_string(),
+            MaskType::Hash => format!("encode(sha256(CAST({column} AS VARCHAR)), 'hex')"),
+            MaskType::Partial => {
+                let value = format!("CAST({column} AS VARCHAR)");
+                let keep = self.keep_start + self.keep_end;
+                // values too short to hide anything are fully masked
+                format!(
+                    "CASE WHEN {column} IS NULL THEN NULL WHEN character_length({value}) <= {keep} THEN '{MASK}' ELSE concat(substr({value}, 1, {}), '{MASK}', substr({value}, character_length({value}) - {} + 1)) END",
+                    self.keep_start, self.keep_end
+                )
+            }
+        }
+    }
+}
+
+/// Column masks of a role on a stream: the masked columns read as the masked values in every
+/// query of the users with the role, for the projection, the filters and the aggregations.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct ColumnMaskPolicy {
+    pub stream_type: StreamType,
+    pub stream_name: String,
+    pub role: UserRole,
+    pub columns: Vec<ColumnMask>,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+impl ColumnMaskPolicy {
+    /// Key of the policy in the org, `{stream_type}/{stream_name}/{role}`.
+    pub fn key(&self) -> String {
+        policy_key(self.stream_type, &self.stream_name, &self.role)
+    }
+
+    pub fn mask(&self, field: &str) -> Option<&ColumnMask> {
+        self.columns.iter().find(|c| c.field == field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::json;
+
+    #[test]
+    fn test_column_mask() {
+        let mask: ColumnMask = json::from_str(
+            r#"{"field": "email", "type": "partial", "keep_start": 2, "keep_end": 4}"#,
+        )
+        .unwrap();
+        assert_eq!(mask.mask_type, MaskType::Partial);
+        assert_eq!(
+            mask.sql_expr("\"email\""),
+            "CASE WHEN \"email\" IS NULL THEN NULL WHEN character_length(CAST(\"email\" AS VARCHAR)) <= 6 THEN '****' ELSE concat(substr(CAST(\"email\" AS VARCHAR), 1, 2), '****', substr(CAST(\"email\" AS VARCHAR), character_length(CAST(\"email\" AS VARCHAR)) - 4 + 1)) END"
+        );
+
+        let mask: ColumnMask = json::from_str(r#"{"field": "email"}"#).unwrap();
+        assert_eq!(mask.mask_type, MaskType::Null);
+        assert_eq!(mask.sql_expr("\"email\""), "NULL");
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Users and roles a dashboard ACL entry applies to.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct AclPrincipals {
+    /// Emails of the users.
+    #[serde(default)]
+    pub users: Vec<String>,
+    /// Names of the roles, either the built-in role of the user in the organization or a custom
+    /// role.
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+impl AclPrincipals {
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty() && self.roles.is_empty()
+    }
+
+    fn contains(&self, user_id: &str, roles: &[String]) -> bool {
+        self.users.iter().any(|u| u.eq_ignore_ascii_case(user_id))
+            || self.roles.iter().any(|r| roles.contains(r))
+    }
+}
+
+/// Dashboard level permissions, applied on top of the permissions of the folder.
+///
+/// An empty list doesn't restrict the access, so a dashboard without ACL can be viewed and
+/// edited by everybody who has access to its folder. Editors can also view the dashboard.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DashboardAcl {
+    #[serde(default)]
+    pub viewers: AclPrincipals,
+    #[serde(default)]
+    pub editors: AclPrincipals,
+}
+
+/// Kind of access to a dashboard checked against its ACL.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DashboardAccess {
+    View,
+    Edit,
+}
+
+impl DashboardAcl {
+    pub fn is_empty(&self) -> bool {
+        self.viewers.is_empty() && self.editors.is_empty()
+    }
+
+    /// Returns true if the user, with the given roles, has the access to the dashboard.
+    pub fn permits(&self, user_id: &str, roles: &[String], access: DashboardAccess) -> bool {
+        let is_editor = self.editors.contains(user_id, roles);
+        let can_view =
+            self.viewers.is_empty() || is_editor || self.viewers.contains(user_id, roles);
+        match access {
+            DashboardAccess::View => can_view,
+            DashboardAccess::Edit => can_view && (self.editors.is_empty() || is_editor),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn principals(users: &[&str], roles: &[&str]) -> AclPrincipals {
+        AclPrincipals {
+            users: users.iter().map(|s| s.to_string()).collect(),
+            roles: roles.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_empty_acl_permits_everybody() {
+        let acl = DashboardAcl::default();
+        assert!(acl.permits("a@example.com", &[], DashboardAccess::View));
+        assert!(acl.permits("a@example.com", &[], DashboardAccess::Edit));
+    }
+
+    #[test]
+    fn test_acl_permits() {
+        let acl = DashboardAcl {
+            viewers: principals(&["viewer@example.com"], &["sre"]),
+            editors: principals(&["Editor@example.com"], &[]),
+        };
+        let sre = vec!["sre".to_string()];
+        let dev = vec!["dev".to_string()];
+
+        assert!(acl.permits("viewer@example.com", &[], DashboardAccess::View));
+        assert!(!acl.permits("viewer@example.com", &[], DashboardAccess::Edit));
+        assert!(acl.permits("other@example.com", &sre, DashboardAccess::View));
+        assert!(!acl.permits("other@example.com", &dev, DashboardAccess::View));
+        assert!(acl.permits("editor@example.com", &[], DashboardAccess::View));
+        assert!(acl.permits("editor@example.com", &[], DashboardAccess::Edit));
+    }
+
+    #[test]
+    fn test_acl_restricting_only_editors() {
+        let acl = DashboardAcl {
+            viewers: AclPrincipals::default(),
+            editors: principals(&[], &["admin-team"]),
+        };
+        assert!(acl.permits("a@example.com", &[], DashboardAccess::View));
+        assert!(!acl.permits("a@example.com", &[], DashboardAccess::Edit));
+        assert!(acl.permits(
+            "a@example.com",
+            &["admin-team".to_string()],
+            DashboardAccess::Edit
+        ));
+    }
+}
@@ -0,0 +1,387 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Conversions of dashboards between schema versions. Upgrades are lossless, downgrades are best
+//! effort: the fields the older version doesn't know are dropped and reported as warnings, so
+//! dashboards can be exported to older installations.
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::{Dashboard, v1, v2, v3, v4, v5, v6};
+use crate::utils::json;
+
+/// Oldest version dashboards can be converted to or from.
+pub const MIN_CONVERTIBLE_VERSION: i32 = 3;
+/// Latest version of the dashboards.
+pub const LATEST_VERSION: i32 = 6;
+
+/// Result of downgrading a dashboard.
+#[derive(Debug)]
+pub struct Downgrade {
+    pub dashboard: Dashboard,
+    /// Fields of the dashboard dropped or changed by the downgrade.
+    pub warnings: Vec<String>,
+}
+
+/// Returns the JSON of the versioned dashboard, as created or exported by the users.
+pub fn inner_json(dashboard: &Dashboard) -> Option<json::Value> {
+    let value = match dashboard.version {
+        1 => json::to_value(dashboard.v1.as_ref()?),
+        2 => json::to_value(dashboard.v2.as_ref()?),
+        3 => json::to_value(dashboard.v3.as_ref()?),
+        4 => json::to_value(dashboard.v4.as_ref()?),
+        5 => json::to_value(dashboard.v5.as_ref()?),
+        6 => json::to_value(dashboard.v6.as_ref()?),
+        _ => return None,
+    };
+    value.ok()
+}
+
+/// Builds a dashboard of the given version from its JSON, the reverse of [`inner_json`].
+pub fn from_inner_json(version: i32, value: json::Value) -> Result<Dashboard, json::Error> {
+    Ok(match version {
+        1 => json::from_value::<v1::Dashboard>(value)?.into(),
+        2 => json::from_value::<v2::Dashboard>(value)?.into(),
+        3 => json::from_value::<v3::Dashboard>(value)?.into(),
+        4 => json::from_value::<v4::Dashboard>(value)?.into(),
+        5 => json::from_value::<v5::Dashboard>(value)?.into(),
+        _ => json::from_value::<v6::Dashboard>(value)?.into(),
+    })
+}
+
+/// Downgrades the dashboard to the given older version, one version at a time.
+pub fn downgrade(dashboard: &Dashboard, version: i32) -> Result<Downgrade, String> {
+    if !(MIN_CONVERTIBLE_VERSION..dashboard.version).contains(&version) {
+        return Err(format!(
+            "cannot downgrade dashboard from version {} to version {version}",
+            dashboard.version
+        ));
+    }
+    let mut value =
+        inner_json(dashboard).ok_or_else(|| "dashboard has no data for its version".to_string())?;
+    let mut warnings = Vec::new();
+    for from in ((version + 1)..=dashboard.version).rev() {
+        value = match from {
+            6 => reshape::<v5::Dashboard>(value, 5, &mut warnings),
+            5 => {
+                flatten_filters(&mut value, &mut warnings);
+                reshape::<v4::Dashboard>(value, 4, &mut warnings)
+            }
+            _ => reshape::<v3::Dashboard>(value, 3, &mut warnings),
+        }
+        .map_err(|e| format!("cannot downgrade dashboard to version {}: {e}", from - 1))?;
+    }
+    let dashboard = from_inner_json(version, value).map_err(|e| e.to_string())?;
+    Ok(Downgrade {
+        dashboard,
+        warnings,
+    })
+}
+
+/// Upgrades the dashboard to the given newer version, one version at a time.
+pub fn upgrade(dashboard: &Dashboard, version: i32) -> Result<Dashboard, String> {
+    if dashboard.version < MIN_CONVERTIBLE_VERSION
+        || !(dashboard.version + 1..=LATEST_VERSION).contains(&version)
+    {
+        return Err(format!(
+            "cannot upgrade dashboard from version {} to version {version}",
+            dashboard.version
+        ));
+    }
+    let mut value =
+        inner_json(dashboard).ok_or_else(|| "dashboard has no data for its version".to_string())?;
+    let mut warnings = Vec::new();
+    for to in (dashboard.version + 1)..=version {
+        value = match to {
+            4 => reshape::<v4::Dashboard>(value, 4, &mut warnings),
+            5 => {
+                group_filters(&mut value);
+                reshape::<v5::Dashboard>(value, 5, &mut warnings)
+            }
+            _ => reshape::<v6::Dashboard>(value, 6, &mut warnings),
+        }
+        .map_err(|e| format!("cannot upgrade dashboard to version {to}: {e}"))?;
+    }
+    from_inner_json(version, value).map_err(|e| e.to_string())
+}
+
+/// Parses the JSON of a dashboard as the given version and serializes it back, reporting the
+/// fields which are lost on the way.
+fn reshape<T: Serialize + DeserializeOwned>(
+    mut value: json::Value,
+    version: i32,
+    warnings: &mut Vec<String>,
+) -> Result<json::Value, json::Error> {
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), version.into());
+    }
+    let reshaped = json::to_value(json::from_value::<T>(value.clone())?)?;
+    collect_dropped("", &value, &reshaped, warnings);
+    Ok(reshaped)
+}
+
+fn collect_dropped(
+    path: &str,
+    before: &json::Value,
+    after: &json::Value,
+    warnings: &mut Vec<String>,
+) {
+    match (before, after) {
+        (json::Value::Object(before), json::Value::Object(after)) => {
+            for (key, value) in before {
+                let path = if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match after.get(key) {
+                    Some(after) => collect_dropped(&path, value, after, warnings),
+                    None if !is_empty(value) => {
+                        warnings.push(format!("{path} is not supported and was dropped"))
+                    }
+                    None => {}
+                }
+            }
+        }
+        (json::Value::Array(before), json::Value::Array(after)) => {
+            for (i, (before, after)) in before.iter().zip(after.iter()).enumerate() {
+                collect_dropped(&format!("{path}[{i}]"), before, after, warnings);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_empty(value: &json::Value) -> bool {
+    match value {
+        json::Value::Null => true,
+        json::Value::Array(v) => v.is_empty(),
+        json::Value::Object(v) => v.is_empty(),
+        _ => false,
+    }
+}
+
+fn query_fields_mut(value: &mut json::Value) -> Vec<(String, &mut json::Value)> {
+    let mut fields = Vec::new();
+    let Some(tabs) = value.get_mut("tabs").and_then(|v| v.as_array_mut()) else {
+        return fields;
+    };
+    for (t, tab) in tabs.iter_mut().enumerate() {
+        let Some(panels) = tab.get_mut("panels").and_then(|v| v.as_array_mut()) else {
+            continue;
+        };
+        for (p, panel) in panels.iter_mut().enumerate() {
+            let Some(queries) = panel.get_mut("queries").and_then(|v| v.as_array_mut()) else {
+                continue;
+            };
+            for (q, query) in queries.iter_mut().enumerate() {
+                if let Some(query_fields) = query.get_mut("fields") {
+                    fields.push((
+                        format!("tabs[{t}].panels[{p}].queries[{q}].fields"),
+                        query_fields,
+                    ));
+                }
+            }
+        }
+    }
+    fields
+}
+
+/// Replaces the filter groups of v5 queries by the flat list of conditions of v4, where all the
+/// conditions must match.
+fn flatten_filters(value: &mut json::Value, warnings: &mut Vec<String>) {
+    for (path, fields) in query_fields_mut(value) {
+        let Some(filter) = fields.get_mut("filter") else {
+            continue;
+        };
+        let mut conditions = Vec::new();
+        let mut has_or = false;
+        flatten_filter(filter, true, &mut conditions, &mut has_or);
+        if has_or {
+            warnings.push(format!(
+                "{path}.filter uses OR conditions which are combined with AND"
+            ));
+        }
+        *filter = json::Value::Array(conditions);
+    }
+}
+
+fn flatten_filter(
+    filter: &json::Value,
+    first: bool,
+    conditions: &mut Vec<json::Value>,
+    has_or: &mut bool,
+) {
+    // the logical operator of the first condition of a group is not used
+    let operator = filter.get("logicalOperator").and_then(|v| v.as_str());
+    if !first && operator.is_some_and(|op| !op.eq_ignore_ascii_case("and")) {
+        *has_or = true;
+    }
+    if let Some(group) = filter.get("conditions").and_then(|v| v.as_array()) {
+        for (i, condition) in group.iter().enumerate() {
+            flatten_filter(condition, i == 0, conditions, has_or);
+        }
+        return;
+    }
+    let mut condition = filter.clone();
+    if let Some(obj) = condition.as_object_mut() {
+        obj.remove("logicalOperator");
+        obj.remove("filterType");
+    }
+    conditions.push(condition);
+}
+
+/// Replaces the flat list of conditions of v4 queries by a v5 filter group matching all of them.
+fn group_filters(value: &mut json::Value) {
+    for (_, fields) in query_fields_mut(value) {
+        let Some(filter) = fields.get_mut("filter") else {
+            continue;
+        };
+        let conditions = filter
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|condition| {
+                let mut condition = condition.clone();
+                if let Some(obj) = condition.as_object_mut() {
+                    obj.insert("logicalOperator".to_string(), "AND".into());
+                    obj.insert("filterType".to_string(), "condition".into());
+                }
+                condition
+            })
+            .collect::<Vec<_>>();
+        *filter = json::json!({
+            "filterType": "group",
+            "logicalOperator": "AND",
+            "conditions": conditions,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dashboard_v5(filter: json::Value, config: json::Value) -> Dashboard {
+        let value = json::json!({
+            "version": 5,
+            "dashboardId": "d1",
+            "title": "t",
+            "description": "",
+            "tabs": [{
+                "tabId": "default",
+                "name": "Default",
+                "panels": [{
+                    "id": "Panel_ID1",
+                    "type": "bar",
+                    "title": "p",
+                    "description": "",
+                    "config": config,
+                    "queryType": "sql",
+                    "queries": [{
+                        "query": "SELECT * FROM \"logs\"",
+                        "vrlFunctionQuery": null,
+                        "customQuery": false,
+                        "fields": {
+                            "stream": "logs",
+                            "stream_type": "logs",
+                            "x": [],
+                            "y": [],
+                            "filter": filter,
+                        },
+                        "config": {"promql_legend": ""},
+                    }],
+                    "layout": {"x": 0, "y": 0, "w": 12, "h": 9, "i": 1},
+                }],
+            }],
+        });
+        from_inner_json(5, value).unwrap()
+    }
+
+    fn condition(column: &str, logical_operator: &str) -> json::Value {
+        json::json!({
+            "type": "condition",
+            "values": [],
+            "column": column,
+            "operator": "=",
+            "value": "'a'",
+            "logicalOperator": logical_operator,
+            "filterType": "condition",
+        })
+    }
+
+    #[test]
+    fn test_downgrade_and_upgrade() {
+        let filter = json::json!({
+            "filterType": "group",
+            "logicalOperator": "AND",
+            "conditions": [condition("host", "AND"), condition("level", "AND")],
+        });
+        let config = json::json!({
+            "show_legends": true,
+            "legends_position": null,
+            "trellis": {"layout": null, "num_of_columns": 2},
+            "top_results": 5.0,
+        });
+        let dashboard = dashboard_v5(filter, config);
+
+        let res = downgrade(&dashboard, 4).unwrap();
+        assert_eq!(res.dashboard.version, 4);
+        assert_eq!(
+            res.warnings,
+            vec!["tabs[0].panels[0].config.trellis is not supported and was dropped"]
+        );
+        let v4 = res.dashboard.v4.as_ref().unwrap();
+        let filter = &v4.tabs[0].panels[0].queries[0].fields.filter;
+        assert_eq!(filter.len(), 2);
+        assert_eq!(filter[1].column, "level");
+
+        let res = downgrade(&dashboard, 3).unwrap();
+        assert_eq!(res.dashboard.version, 3);
+        assert_eq!(res.warnings.len(), 2);
+        assert!(res.warnings[1].ends_with("config.top_results is not supported and was dropped"));
+
+        let upgraded = upgrade(&res.dashboard, 6).unwrap();
+        assert_eq!(upgraded.version, 6);
+        assert!(downgrade(&upgraded, 2).is_err());
+        assert!(upgrade(&upgraded, 5).is_err());
+    }
+
+    #[test]
+    fn test_downgrade_or_filters() {
+        let filter = json::json!({
+            "filterType": "group",
+            "logicalOperator": "AND",
+            "conditions": [
+                condition("host", "AND"),
+                {
+                    "filterType": "group",
+                    "logicalOperator": "AND",
+                    "conditions": [condition("level", "AND"), condition("code", "OR")],
+                },
+            ],
+        });
+        let config = json::json!({"show_legends": true, "legends_position": null});
+        let res = downgrade(&dashboard_v5(filter, config), 4).unwrap();
+        assert_eq!(
+            res.warnings,
+            vec![
+                "tabs[0].panels[0].queries[0].fields.filter uses OR conditions which are combined with AND"
+            ]
+        );
+        let v4 = res.dashboard.v4.as_ref().unwrap();
+        assert_eq!(v4.tabs[0].panels[0].queries[0].fields.filter.len(), 3);
+    }
+}
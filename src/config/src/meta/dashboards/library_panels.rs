@@ -0,0 +1,110 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::v6;
+
+/// A panel stored independently of the dashboards, v6 panels reference it by id.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct LibraryPanel {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Definition of the panel, its id and layout are ignored as they come from the dashboard.
+    pub panel: v6::Panel,
+    #[serde(default)]
+    pub owner: String,
+    #[serde(default)]
+    pub created_at: i64,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct LibraryPanelList {
+    pub list: Vec<LibraryPanel>,
+}
+
+/// Replaces the definition of a dashboard panel with the definition of its library panel. The
+/// id, layout and time range override of the dashboard panel are kept.
+pub fn apply_library_panel(panel: &mut v6::Panel, library_panel: &LibraryPanel) {
+    let definition = library_panel.panel.clone();
+    panel.typ = definition.typ;
+    panel.title = definition.title;
+    panel.description = definition.description;
+    panel.config = definition.config;
+    panel.query_type = definition.query_type;
+    panel.queries = definition.queries;
+    panel.html_content = definition.html_content;
+    panel.markdown_content = definition.markdown_content;
+    panel.custom_chart_content = definition.custom_chart_content;
+}
+
+/// Returns the ids of the library panels referenced by the dashboard.
+pub fn library_panel_ids(dashboard: &v6::Dashboard) -> Vec<String> {
+    let mut ids = dashboard
+        .tabs
+        .iter()
+        .flat_map(|tab| tab.panels.iter())
+        .filter_map(|panel| panel.library_panel_id.clone())
+        .collect::<Vec<_>>();
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::json;
+
+    fn panel(id: &str, title: &str, x: i64) -> v6::Panel {
+        json::from_value(json::json!({
+            "id": id,
+            "type": "line",
+            "title": title,
+            "description": "",
+            "config": {"show_legends": true, "legends_position": null},
+            "queryType": "sql",
+            "queries": [],
+            "layout": {"x": x, "y": 0, "w": 12, "h": 9, "i": 1}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_apply_library_panel() {
+        let library_panel = LibraryPanel {
+            id: "lib1".to_string(),
+            name: "errors".to_string(),
+            description: "".to_string(),
+            panel: panel("ignored", "Errors by service", 0),
+            owner: "".to_string(),
+            created_at: 0,
+            updated_at: 0,
+        };
+        let mut dashboard_panel = panel("Panel_ID1", "old title", 6);
+        dashboard_panel.library_panel_id = Some("lib1".to_string());
+        apply_library_panel(&mut dashboard_panel, &library_panel);
+        assert_eq!(dashboard_panel.id, "Panel_ID1");
+        assert_eq!(dashboard_panel.title, "Errors by service");
+        assert_eq!(dashboard_panel.layout.x, 6);
+        assert_eq!(dashboard_panel.library_panel_id.as_deref(), Some("lib1"));
+    }
+}
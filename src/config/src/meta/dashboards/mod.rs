@@ -14,9 +14,17 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use chrono::{DateTime, FixedOffset, Utc};
+use hashbrown::{HashMap, HashSet};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use crate::{
+    meta::{sql::resolve_stream_names, stream::StreamType},
+    utils::json,
+};
+
 type OrdF64 = ordered_float::OrderedFloat<f64>;
 
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
@@ -37,6 +45,7 @@ pub struct Dashboard {
     pub v3: Option<v3::Dashboard>,
     pub v4: Option<v4::Dashboard>,
     pub v5: Option<v5::Dashboard>,
+    pub v6: Option<v6::Dashboard>,
     pub version: i32,
     pub hash: String,
     #[serde(default)]
@@ -51,6 +60,7 @@ impl Dashboard {
             3 => self.v3.as_ref().map(|inner| inner.dashboard_id.as_str()),
             4 => self.v4.as_ref().map(|inner| inner.dashboard_id.as_str()),
             5 => self.v5.as_ref().map(|inner| inner.dashboard_id.as_str()),
+            6 => self.v6.as_ref().map(|inner| inner.dashboard_id.as_str()),
             _ => None,
         }
     }
@@ -82,6 +92,11 @@ impl Dashboard {
                 v5: Some(inner),
                 ..
             } => inner.dashboard_id = dashboard_id,
+            Self {
+                version: 6,
+                v6: Some(inner),
+                ..
+            } => inner.dashboard_id = dashboard_id,
             _ => {}
         };
     }
@@ -97,6 +112,7 @@ impl Dashboard {
             3 => self.v3.as_ref().map(|inner| inner.owner.as_str()),
             4 => self.v4.as_ref().map(|inner| inner.owner.as_str()),
             5 => self.v5.as_ref().map(|inner| inner.owner.as_str()),
+            6 => self.v6.as_ref().map(|inner| inner.owner.as_str()),
             _ => None,
         }
     }
@@ -128,6 +144,11 @@ impl Dashboard {
                 v5: Some(inner),
                 ..
             } => inner.owner = owner,
+            Self {
+                version: 6,
+                v6: Some(inner),
+                ..
+            } => inner.owner = owner,
             _ => {}
         };
     }
@@ -139,6 +160,7 @@ impl Dashboard {
             3 => self.v3.as_ref().map(|inner| inner.title.as_str()),
             4 => self.v4.as_ref().map(|inner| inner.title.as_str()),
             5 => self.v5.as_ref().map(|inner| inner.title.as_str()),
+            6 => self.v6.as_ref().map(|inner| inner.title.as_str()),
             _ => None,
         }
     }
@@ -170,6 +192,11 @@ impl Dashboard {
                 v5: Some(inner),
                 ..
             } => inner.title = title,
+            Self {
+                version: 6,
+                v6: Some(inner),
+                ..
+            } => inner.title = title,
             _ => {}
         };
     }
@@ -181,6 +208,7 @@ impl Dashboard {
             3 => self.v3.as_ref().map(|inner| inner.description.as_str()),
             4 => self.v4.as_ref().map(|inner| inner.description.as_str()),
             5 => self.v5.as_ref().map(|inner| inner.description.as_str()),
+            6 => self.v6.as_ref().map(|inner| inner.description.as_str()),
             _ => None,
         }
     }
@@ -192,6 +220,7 @@ impl Dashboard {
             3 => self.v3.as_ref().map(|inner| inner.role.as_str()),
             4 => self.v4.as_ref().map(|inner| inner.role.as_str()),
             5 => self.v5.as_ref().map(|inner| inner.role.as_str()),
+            6 => self.v6.as_ref().map(|inner| inner.role.as_str()),
             _ => None,
         }
     }
@@ -199,7 +228,7 @@ impl Dashboard {
     /// Returns the timestamp with timezone of the time at which the dashboard
     /// was created.
     ///
-    /// This value is stored in JSON for versions 1-5 of the dashboard. However
+    /// This value is stored in JSON for versions 1-6 of the dashboard. However
     /// future versions of the dashboard should utilize the `created_at` Unix
     /// timestamp field in the database to represent the creation timestamp.
     pub fn created_at_deprecated(&self) -> Option<DateTime<FixedOffset>> {
@@ -209,17 +238,27 @@ impl Dashboard {
             3 => self.v3.as_ref().map(|inner| inner.created),
             4 => self.v4.as_ref().map(|inner| inner.created),
             5 => self.v5.as_ref().map(|inner| inner.created),
+            6 => self.v6.as_ref().map(|inner| inner.created),
             _ => None,
         }
     }
 }
 
+pub mod acl;
+pub mod convert;
+pub mod library_panels;
+pub mod render;
 pub mod reports;
+pub mod share;
+pub mod snapshots;
+pub mod transformations;
 pub mod v1;
 pub mod v2;
 pub mod v3;
 pub mod v4;
 pub mod v5;
+pub mod v6;
+pub mod variables;
 
 pub fn datetime_now() -> DateTime<FixedOffset> {
     Utc::now().with_timezone(&FixedOffset::east_opt(0).expect(
@@ -227,6 +266,498 @@ pub fn datetime_now() -> DateTime<FixedOffset> {
     ))
 }
 
+/// Latest dashboard version, dashboards without version are v1.
+pub const LATEST_VERSION: i32 = 6;
+
+/// Dashboard variables like `$name`, `${name}` or `${name:csv}` used in panel queries.
+static RE_VARIABLE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\$\{?[a-zA-Z_][a-zA-Z0-9_]*(:[a-zA-Z]+)?\}?").unwrap());
+
+/// Replaces the dashboard variables of a panel query so that it can be parsed.
+const VARIABLE_PLACEHOLDER: &str = "o2_variable";
+
+/// Stream names following `FROM` or `JOIN` in a panel query, quoted or not.
+static RE_QUERY_STREAM: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)\b(from|join)(\s+)(?:"([^"]+)"|([a-zA-Z_][a-zA-Z0-9_]*))"#).unwrap()
+});
+
+/// Result of checking a dashboard JSON before importing it.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct DashboardValidation {
+    pub version: i32,
+    /// The dashboard can be imported without losing any field.
+    pub valid: bool,
+    /// Errors that prevent importing the dashboard.
+    pub errors: Vec<String>,
+    /// Paths of the fields that are not part of the dashboard version and would be dropped.
+    pub unknown_fields: Vec<String>,
+    /// Streams referenced by the panel queries.
+    pub streams: Vec<StreamReference>,
+    /// Referenced streams that don't exist in the organization.
+    pub missing_streams: Vec<StreamReference>,
+    /// Panel queries that are not valid SQL.
+    pub invalid_queries: Vec<InvalidPanelQuery>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub struct StreamReference {
+    pub stream_type: StreamType,
+    pub stream_name: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct InvalidPanelQuery {
+    pub panel_id: String,
+    pub query: String,
+    pub error: String,
+}
+
+impl DashboardValidation {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+            && self.unknown_fields.is_empty()
+            && self.missing_streams.is_empty()
+            && self.invalid_queries.is_empty()
+    }
+}
+
+/// Checks a dashboard JSON against the schema of its version without saving it. Reports the
+/// fields that deserializing the dashboard would drop, the streams referenced by the panel
+/// queries and the queries that are not valid SQL. The referenced streams are not checked here
+/// as that needs the organization, see [`DashboardValidation::missing_streams`].
+pub fn validate(value: &json::Value) -> DashboardValidation {
+    let version = value
+        .as_object()
+        .and_then(|o| o.get("version"))
+        .and_then(|v| v.as_i64())
+        .unwrap_or(1) as i32;
+    let mut validation = DashboardValidation {
+        version,
+        ..Default::default()
+    };
+
+    let parsed = match version {
+        1 => json::from_value::<v1::Dashboard>(value.clone()).and_then(json::to_value),
+        2 => json::from_value::<v2::Dashboard>(value.clone()).and_then(json::to_value),
+        3 => json::from_value::<v3::Dashboard>(value.clone()).and_then(json::to_value),
+        4 => json::from_value::<v4::Dashboard>(value.clone()).and_then(json::to_value),
+        5 => json::from_value::<v5::Dashboard>(value.clone()).and_then(json::to_value),
+        6 => json::from_value::<v6::Dashboard>(value.clone()).and_then(json::to_value),
+        v => {
+            validation.errors.push(format!(
+                "unsupported dashboard version {v}, the latest version is {LATEST_VERSION}"
+            ));
+            return validation;
+        }
+    };
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            validation
+                .errors
+                .push(format!("invalid v{version} dashboard: {e}"));
+            return validation;
+        }
+    };
+
+    if parsed
+        .get("title")
+        .and_then(|v| v.as_str())
+        .is_none_or(|t| t.trim().is_empty())
+    {
+        validation
+            .errors
+            .push("dashboard should have title".to_string());
+    }
+    collect_unknown_fields(value, &parsed, "", &mut validation.unknown_fields);
+
+    let mut streams = HashSet::new();
+    for panel in panels(&parsed) {
+        let panel_id = panel.get("id").and_then(|v| v.as_str()).unwrap_or_default();
+        let query_type = panel
+            .get("queryType")
+            .or(panel.get("query_type"))
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        // v1 panels hold a single query themselves
+        let queries = match panel.get("queries").and_then(|v| v.as_array()) {
+            Some(queries) => queries.iter().collect::<Vec<_>>(),
+            None => vec![panel],
+        };
+        for query in queries {
+            let fields = query.get("fields");
+            let stream_type = fields
+                .and_then(|v| v.get("stream_type"))
+                .and_then(|v| v.as_str())
+                .map(StreamType::from)
+                .unwrap_or_default();
+            let mut names = fields
+                .and_then(|v| v.get("stream"))
+                .and_then(|v| v.as_str())
+                .map(|v| vec![v.to_string()])
+                .unwrap_or_default();
+
+            let sql = query
+                .get("query")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            if !sql.trim().is_empty() && !query_type.eq_ignore_ascii_case("promql") {
+                let sql = RE_VARIABLE.replace_all(sql, VARIABLE_PLACEHOLDER);
+                match resolve_stream_names(&sql) {
+                    Ok(v) => names.extend(v),
+                    Err(e) => validation.invalid_queries.push(InvalidPanelQuery {
+                        panel_id: panel_id.to_string(),
+                        query: sql.to_string(),
+                        error: e.to_string(),
+                    }),
+                }
+            }
+
+            for stream_name in names {
+                if stream_name.is_empty()
+                    || stream_name.contains('$')
+                    || stream_name == VARIABLE_PLACEHOLDER
+                {
+                    continue;
+                }
+                let stream = StreamReference {
+                    stream_type,
+                    stream_name,
+                };
+                if streams.insert(stream.clone()) {
+                    validation.streams.push(stream);
+                }
+            }
+        }
+    }
+
+    validation.valid = validation.is_valid();
+    validation
+}
+
+/// Stream and columns a panel query reads through the query builder fields.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PanelFieldsReference {
+    pub panel_id: String,
+    pub panel_title: String,
+    pub stream: StreamReference,
+    /// Axis columns, empty for custom and PromQL queries as those don't select columns through
+    /// the axes.
+    pub columns: Vec<String>,
+}
+
+/// Returns the stream and axis columns of every panel query of a dashboard JSON of any version.
+/// References using dashboard variables and derived axis items are left out.
+pub fn panel_fields(dashboard: &json::Value) -> Vec<PanelFieldsReference> {
+    let mut references = Vec::new();
+    for panel in panels(dashboard) {
+        let str_field = |key: &str| {
+            panel
+                .get(key)
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        let query_type = panel
+            .get("queryType")
+            .or(panel.get("query_type"))
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        let queries = match panel.get("queries").and_then(|v| v.as_array()) {
+            Some(queries) => queries.iter().collect::<Vec<_>>(),
+            None => vec![panel],
+        };
+        for query in queries {
+            let Some(fields) = query.get("fields") else {
+                continue;
+            };
+            let stream_name = fields
+                .get("stream")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            if stream_name.is_empty() || stream_name.contains('$') {
+                continue;
+            }
+            let stream_type = fields
+                .get("stream_type")
+                .and_then(|v| v.as_str())
+                .map(StreamType::from)
+                .unwrap_or_default();
+            let custom_query = query
+                .get("customQuery")
+                .or(query.get("custom_query"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or_default();
+
+            let mut columns = Vec::new();
+            if !custom_query && !query_type.eq_ignore_ascii_case("promql") {
+                let items = AXIS_FIELDS.iter().flat_map(|key| match fields.get(*key) {
+                    Some(json::Value::Array(items)) => items.iter().collect::<Vec<_>>(),
+                    Some(item @ json::Value::Object(_)) => vec![item],
+                    _ => vec![],
+                });
+                for item in items {
+                    if item.get("isDerived").and_then(|v| v.as_bool()) == Some(true) {
+                        continue;
+                    }
+                    let Some(column) = item.get("column").and_then(|v| v.as_str()) else {
+                        continue;
+                    };
+                    if !column.is_empty()
+                        && !column.contains('$')
+                        && !columns.iter().any(|c| c == column)
+                    {
+                        columns.push(column.to_string());
+                    }
+                }
+            }
+            references.push(PanelFieldsReference {
+                panel_id: str_field("id"),
+                panel_title: str_field("title"),
+                stream: StreamReference {
+                    stream_type,
+                    stream_name: stream_name.to_string(),
+                },
+                columns,
+            });
+        }
+    }
+    references
+}
+
+/// A query of a dashboard panel, as written in the dashboard.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PanelQuery {
+    pub panel_title: String,
+    pub promql: bool,
+    pub query: String,
+    pub vrl_function: Option<String>,
+    pub stream: StreamReference,
+}
+
+/// Returns the query at `index` of the panel `panel_id` of a dashboard JSON of any version.
+pub fn panel_query(dashboard: &json::Value, panel_id: &str, index: usize) -> Option<PanelQuery> {
+    let panel = panels(dashboard)
+        .into_iter()
+        .find(|p| p.get("id").and_then(|v| v.as_str()) == Some(panel_id))?;
+    // v1 panels hold a single query themselves
+    let query = match panel.get("queries").and_then(|v| v.as_array()) {
+        Some(queries) => queries.get(index)?,
+        None if index == 0 => panel,
+        None => return None,
+    };
+    let str_field = |v: &json::Value, key: &str| {
+        v.get(key)
+            .and_then(|v| v.as_str())
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let promql = panel
+        .get("queryType")
+        .or(panel.get("query_type"))
+        .and_then(|v| v.as_str())
+        .is_some_and(|v| v.eq_ignore_ascii_case("promql"));
+    let fields = query.get("fields");
+    Some(PanelQuery {
+        panel_title: str_field(panel, "title").unwrap_or_default(),
+        promql,
+        query: str_field(query, "query")?,
+        vrl_function: str_field(query, "vrlFunctionQuery"),
+        stream: StreamReference {
+            stream_type: fields
+                .and_then(|v| v.get("stream_type"))
+                .and_then(|v| v.as_str())
+                .map(StreamType::from)
+                .unwrap_or_default(),
+            stream_name: fields
+                .and_then(|v| str_field(v, "stream"))
+                .unwrap_or_default(),
+        },
+    })
+}
+
+/// Renames the streams referenced by a dashboard JSON of any version following `mapping`, from
+/// the old stream name to the new one: the stream of the panel query fields, the streams read by
+/// the SQL panel queries and the streams of the query variables. PromQL queries are left as they
+/// are. Returns the number of references renamed.
+pub fn rename_streams(dashboard: &mut json::Value, mapping: &HashMap<String, String>) -> usize {
+    if mapping.is_empty() {
+        return 0;
+    }
+    let mut renamed = 0;
+    for panel in panels_mut(dashboard) {
+        let promql = panel
+            .get("queryType")
+            .or(panel.get("query_type"))
+            .and_then(|v| v.as_str())
+            .is_some_and(|v| v.eq_ignore_ascii_case("promql"));
+        // v1 panels hold a single query themselves
+        let queries = match panel.get_mut("queries").and_then(|v| v.as_array_mut()) {
+            Some(queries) => queries.iter_mut().collect::<Vec<_>>(),
+            None => vec![panel],
+        };
+        for query in queries {
+            if let Some(fields) = query.get_mut("fields") {
+                renamed += rename_stream(fields.get_mut("stream"), mapping);
+            }
+            if promql {
+                continue;
+            }
+            if let Some(json::Value::String(sql)) = query.get_mut("query") {
+                let (new_sql, count) = rename_query_streams(sql, mapping);
+                *sql = new_sql;
+                renamed += count;
+            }
+        }
+    }
+    let variables = dashboard
+        .get_mut("variables")
+        .and_then(|v| v.get_mut("list"))
+        .and_then(|v| v.as_array_mut());
+    for variable in variables.into_iter().flatten() {
+        if let Some(query_data) = variable.get_mut("queryData") {
+            renamed += rename_stream(query_data.get_mut("stream"), mapping);
+        }
+    }
+    renamed
+}
+
+fn rename_stream(stream: Option<&mut json::Value>, mapping: &HashMap<String, String>) -> usize {
+    let Some(json::Value::String(stream)) = stream else {
+        return 0;
+    };
+    match mapping.get(stream.as_str()) {
+        Some(new_stream) => {
+            *stream = new_stream.to_string();
+            1
+        }
+        None => 0,
+    }
+}
+
+/// Renames the streams read by a SQL query, renamed streams are always quoted.
+fn rename_query_streams(sql: &str, mapping: &HashMap<String, String>) -> (String, usize) {
+    let mut renamed = 0;
+    let sql = RE_QUERY_STREAM.replace_all(sql, |caps: &regex::Captures| {
+        let stream = caps
+            .get(3)
+            .or(caps.get(4))
+            .map(|m| m.as_str())
+            .unwrap_or_default();
+        match mapping.get(stream) {
+            Some(new_stream) => {
+                renamed += 1;
+                format!("{}{}\"{new_stream}\"", &caps[1], &caps[2])
+            }
+            None => caps[0].to_string(),
+        }
+    });
+    (sql.into_owned(), renamed)
+}
+
+/// Panel fields holding one or a list of axis items.
+const AXIS_FIELDS: [&str; 12] = [
+    "x",
+    "y",
+    "z",
+    "breakdown",
+    "latitude",
+    "longitude",
+    "weight",
+    "name",
+    "value_for_maps",
+    "source",
+    "target",
+    "value",
+];
+
+/// A panel referencing a stream or a field that doesn't exist anymore.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PanelLintIssue {
+    pub panel_id: String,
+    pub panel_title: String,
+    pub stream_type: StreamType,
+    pub stream_name: String,
+    /// The missing field, not set when the stream itself is missing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct DashboardLint {
+    pub dashboard_id: String,
+    pub title: String,
+    pub checked_at: i64,
+    pub issues: Vec<PanelLintIssue>,
+}
+
+/// Returns the panels of any dashboard version, v1 and v2 dashboards have no tabs.
+fn panels(dashboard: &json::Value) -> Vec<&json::Value> {
+    fn panels(v: Option<&json::Value>) -> Vec<&json::Value> {
+        v.and_then(|v| v.as_array()).into_iter().flatten().collect()
+    }
+    match dashboard.get("tabs").and_then(|v| v.as_array()) {
+        Some(tabs) => tabs
+            .iter()
+            .flat_map(|tab| panels(tab.get("panels")))
+            .collect(),
+        None => panels(dashboard.get("panels")),
+    }
+}
+
+/// Mutable counterpart of [`panels`].
+fn panels_mut(dashboard: &mut json::Value) -> Vec<&mut json::Value> {
+    fn panels(v: Option<&mut json::Value>) -> Vec<&mut json::Value> {
+        v.and_then(|v| v.as_array_mut())
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+    if dashboard.get("tabs").is_some_and(|v| v.is_array()) {
+        let tabs = dashboard.get_mut("tabs").and_then(|v| v.as_array_mut());
+        tabs.into_iter()
+            .flatten()
+            .flat_map(|tab| panels(tab.get_mut("panels")))
+            .collect()
+    } else {
+        panels(dashboard.get_mut("panels"))
+    }
+}
+
+/// Collects the paths of the fields of `input` that are missing in `parsed`, the same dashboard
+/// after a deserialization round trip. Null fields are skipped as `None` values are not
+/// serialized.
+fn collect_unknown_fields(
+    input: &json::Value,
+    parsed: &json::Value,
+    path: &str,
+    fields: &mut Vec<String>,
+) {
+    match (input, parsed) {
+        (json::Value::Object(input), json::Value::Object(parsed)) => {
+            for (key, value) in input {
+                let field_path = if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match parsed.get(key) {
+                    Some(parsed) => collect_unknown_fields(value, parsed, &field_path, fields),
+                    // updatedAt is read but never serialized
+                    None if value.is_null() || (path.is_empty() && key == "updatedAt") => {}
+                    None => fields.push(field_path),
+                }
+            }
+        }
+        (json::Value::Array(input), json::Value::Array(parsed)) => {
+            for (i, (value, parsed)) in input.iter().zip(parsed).enumerate() {
+                collect_unknown_fields(value, parsed, &format!("{path}[{i}]"), fields);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Parameters for listing dashboards.
 #[derive(Debug, Clone)]
 pub struct ListDashboardsParams {
@@ -240,6 +771,9 @@ pub struct ListDashboardsParams {
     /// dashboards.
     pub title_pat: Option<String>,
 
+    /// The optional dashboard IDs with which to filter dashboards.
+    pub dashboard_ids: Option<Vec<String>>,
+
     /// The optional page size and page index of results to retrieve.
     pub page_size_and_idx: Option<(u64, u64)>,
 }
@@ -252,6 +786,7 @@ impl ListDashboardsParams {
             org_id: org_id.to_string(),
             folder_id: None,
             title_pat: None,
+            dashboard_ids: None,
             page_size_and_idx: None,
         }
     }
@@ -271,9 +806,259 @@ impl ListDashboardsParams {
         self
     }
 
+    /// Filter dashboards by the given dashboard IDs.
+    pub fn with_dashboard_ids(mut self, dashboard_ids: Vec<String>) -> Self {
+        self.dashboard_ids = Some(dashboard_ids);
+        self
+    }
+
     /// Paginate the results by the given page size and page index.
     pub fn paginate(mut self, page_size: u64, page_idx: u64) -> Self {
         self.page_size_and_idx = Some((page_size, page_idx));
         self
     }
 }
+
+/// A line of a dashboards archive, the NDJSON export of the dashboards of an organization. The
+/// folders come before the dashboards, so an archive can be imported line by line.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ArchiveRecord {
+    Folder {
+        folder_id: String,
+        name: String,
+        #[serde(default)]
+        description: String,
+    },
+    Dashboard {
+        folder_id: String,
+        dashboard: Box<Dashboard>,
+    },
+}
+
+/// Result of importing a dashboards archive.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct DashboardImportSummary {
+    /// Ids of the folders created.
+    pub folders_created: Vec<String>,
+    /// Ids of the dashboards created.
+    pub dashboards_created: Vec<String>,
+    /// Ids of the existing dashboards overwritten.
+    pub dashboards_updated: Vec<String>,
+    /// Ids of the existing dashboards left as they are, as `overwrite` was not set.
+    pub dashboards_skipped: Vec<String>,
+    /// Folders and dashboards which could not be imported.
+    pub errors: Vec<String>,
+}
+
+/// A dashboard exported for another installation, possibly as an older version.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct DashboardExport {
+    /// The dashboard as created by the users of the installation it is imported into.
+    #[schema(value_type = Object)]
+    pub dashboard: json::Value,
+    /// Fields of the dashboard dropped or changed to export it as an older version.
+    pub warnings: Vec<String>,
+}
+
+/// Parses a dashboards archive, empty lines are skipped.
+pub fn parse_archive(body: &str) -> Result<Vec<ArchiveRecord>, String> {
+    body.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            json::from_str(line).map_err(|e| format!("invalid archive line {}: {e}", i + 1))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dashboard(version: i32, query: &str) -> json::Value {
+        json::json!({
+            "version": version,
+            "title": "d1",
+            "description": "",
+            "tabs": [{
+                "tabId": "default",
+                "name": "Default",
+                "panels": [{
+                    "id": "Panel_ID1",
+                    "type": "line",
+                    "title": "errors",
+                    "description": "",
+                    "config": {"show_legends": true, "legends_position": null},
+                    "queryType": "sql",
+                    "queries": [{
+                        "query": query,
+                        "vrlFunctionQuery": null,
+                        "customQuery": true,
+                        "fields": {
+                            "stream": "default",
+                            "stream_type": "logs",
+                            "x": [],
+                            "y": [],
+                            "filter": {"filterType": "group", "logicalOperator": "AND", "conditions": []}
+                        },
+                        "config": {"promql_legend": ""}
+                    }],
+                    "layout": {"x": 0, "y": 0, "w": 12, "h": 9, "i": 1}
+                }]
+            }]
+        })
+    }
+
+    #[test]
+    fn test_validate() {
+        let value = dashboard(
+            5,
+            "SELECT count(*) FROM \"k8s\" WHERE namespace IN ($namespace) AND host = '${host}'",
+        );
+        let validation = validate(&value);
+        assert!(validation.valid, "{validation:?}");
+        assert_eq!(validation.version, 5);
+        assert_eq!(
+            validation.streams,
+            vec![
+                StreamReference {
+                    stream_type: StreamType::Logs,
+                    stream_name: "default".to_string(),
+                },
+                StreamReference {
+                    stream_type: StreamType::Logs,
+                    stream_name: "k8s".to_string(),
+                },
+            ]
+        );
+
+        let mut value = dashboard(6, "SELECT count(* FROM default");
+        value["tabs"][0]["panels"][0]["legacyColors"] = json::json!(true);
+        let validation = validate(&value);
+        assert!(!validation.valid);
+        assert_eq!(
+            validation.unknown_fields,
+            vec!["tabs[0].panels[0].legacyColors".to_string()]
+        );
+        assert_eq!(validation.invalid_queries.len(), 1);
+        assert_eq!(validation.invalid_queries[0].panel_id, "Panel_ID1");
+
+        let validation = validate(&dashboard(LATEST_VERSION + 1, ""));
+        assert!(!validation.valid);
+        assert_eq!(validation.errors.len(), 1);
+
+        let validation = validate(&json::json!({"version": 5, "title": "d1"}));
+        assert!(!validation.valid);
+        assert!(validation.errors[0].starts_with("invalid v5 dashboard"));
+    }
+
+    #[test]
+    fn test_panel_fields() {
+        let mut value = dashboard(6, "SELECT * FROM default");
+        let fields = &mut value["tabs"][0]["panels"][0]["queries"][0]["fields"];
+        fields["x"] =
+            json::json!([{"label": "", "alias": "x", "column": "_timestamp", "color": null}]);
+        fields["y"] = json::json!([
+            {"label": "", "alias": "y", "column": "code", "color": null},
+            {"label": "", "alias": "y2", "column": "y", "color": null, "isDerived": true},
+            {"label": "", "alias": "y3", "column": "$field", "color": null}
+        ]);
+        fields["latitude"] =
+            json::json!({"label": "", "alias": "lat", "column": "lat", "color": null});
+        let references = panel_fields(&value);
+        assert_eq!(references.len(), 1);
+        assert_eq!(references[0].panel_id, "Panel_ID1");
+        assert_eq!(references[0].stream.stream_name, "default");
+        // custom queries select their own columns
+        assert!(references[0].columns.is_empty());
+
+        value["tabs"][0]["panels"][0]["queries"][0]["customQuery"] = json::json!(false);
+        let references = panel_fields(&value);
+        assert_eq!(references[0].columns, vec!["_timestamp", "code", "lat"]);
+
+        value["tabs"][0]["panels"][0]["queries"][0]["fields"]["stream"] = json::json!("$stream");
+        assert!(panel_fields(&value).is_empty());
+    }
+
+    #[test]
+    fn test_panel_query() {
+        let mut value = dashboard(6, "SELECT count(*) AS errors FROM default");
+        let query = panel_query(&value, "Panel_ID1", 0).unwrap();
+        assert_eq!(query.panel_title, "errors");
+        assert!(!query.promql);
+        assert_eq!(query.query, "SELECT count(*) AS errors FROM default");
+        assert_eq!(query.vrl_function, None);
+        assert_eq!(query.stream.stream_name, "default");
+        assert_eq!(query.stream.stream_type, StreamType::Logs);
+        assert!(panel_query(&value, "Panel_ID1", 1).is_none());
+        assert!(panel_query(&value, "Panel_ID2", 0).is_none());
+
+        value["tabs"][0]["panels"][0]["queries"][0]["query"] = json::json!(" ");
+        assert!(panel_query(&value, "Panel_ID1", 0).is_none());
+    }
+
+    #[test]
+    fn test_rename_streams() {
+        let mut value = dashboard(
+            5,
+            "SELECT a.x FROM \"default\" a JOIN k8s b ON a.x = b.x WHERE y IN (SELECT y FROM other)",
+        );
+        value["variables"] = json::json!({"list": [
+            {"type": "query_values", "name": "ns", "queryData": {"stream": "k8s", "stream_type": "logs", "field": "ns"}}
+        ]});
+        let mapping = HashMap::from([
+            ("default".to_string(), "prod_logs".to_string()),
+            ("k8s".to_string(), "prod-k8s".to_string()),
+        ]);
+        assert_eq!(rename_streams(&mut value, &mapping), 4);
+        let query = &value["tabs"][0]["panels"][0]["queries"][0];
+        assert_eq!(query["fields"]["stream"], "prod_logs");
+        assert_eq!(
+            query["query"],
+            "SELECT a.x FROM \"prod_logs\" a JOIN \"prod-k8s\" b ON a.x = b.x WHERE y IN (SELECT y FROM other)"
+        );
+        assert_eq!(
+            value["variables"]["list"][0]["queryData"]["stream"],
+            "prod-k8s"
+        );
+
+        let mut value = dashboard(5, "cpu_usage{job=\"default\"}");
+        value["tabs"][0]["panels"][0]["queryType"] = json::json!("promql");
+        assert_eq!(rename_streams(&mut value, &mapping), 1);
+        assert_eq!(
+            value["tabs"][0]["panels"][0]["queries"][0]["query"],
+            "cpu_usage{job=\"default\"}"
+        );
+    }
+
+    #[test]
+    fn test_parse_archive() {
+        let dashboard = Dashboard {
+            v6: Some(json::from_value(dashboard(6, "SELECT * FROM default")).unwrap()),
+            version: 6,
+            hash: "123".to_string(),
+            ..Default::default()
+        };
+        let records = vec![
+            ArchiveRecord::Folder {
+                folder_id: "f1".to_string(),
+                name: "infra".to_string(),
+                description: String::new(),
+            },
+            ArchiveRecord::Dashboard {
+                folder_id: "f1".to_string(),
+                dashboard: Box::new(dashboard),
+            },
+        ];
+        let body = records
+            .iter()
+            .map(|r| json::to_string(r).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        assert_eq!(parse_archive(&body).unwrap(), records);
+
+        let err = parse_archive(&format!("{body}\n{{\"kind\":\"panel\"}}")).unwrap_err();
+        assert!(err.starts_with("invalid archive line 4"));
+    }
+}
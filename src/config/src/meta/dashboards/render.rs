@@ -0,0 +1,82 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::reports::ReportDashboardVariable;
+
+/// File format of a rendered dashboard.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderFormat {
+    #[default]
+    Pdf,
+    Png,
+}
+
+impl RenderFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            RenderFormat::Pdf => "application/pdf",
+            RenderFormat::Png => "image/png",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            RenderFormat::Pdf => "pdf",
+            RenderFormat::Png => "png",
+        }
+    }
+}
+
+impl std::str::FromStr for RenderFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pdf" => Ok(RenderFormat::Pdf),
+            "png" => Ok(RenderFormat::Png),
+            _ => Err(format!("unsupported render format: {s}")),
+        }
+    }
+}
+
+/// Time range, variables and format of a dashboard rendered on the server.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RenderDashboardRequest {
+    pub format: RenderFormat,
+    /// Start of the time range in microseconds.
+    pub start_time: i64,
+    /// End of the time range in microseconds.
+    pub end_time: i64,
+    /// Values of the dashboard variables used by the panel queries.
+    pub variables: Vec<ReportDashboardVariable>,
+    /// Only render these tabs, all tabs if empty.
+    pub tabs: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_format() {
+        assert_eq!("PNG".parse::<RenderFormat>().unwrap(), RenderFormat::Png);
+        assert_eq!(RenderFormat::default().content_type(), "application/pdf");
+        assert!("svg".parse::<RenderFormat>().is_err());
+    }
+}
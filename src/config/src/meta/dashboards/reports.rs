@@ -23,7 +23,84 @@ use crate::meta::alerts::default_align_time;
 #[derive(Serialize, Debug, Deserialize, Clone, ToSchema)]
 pub enum ReportDestination {
     #[serde(rename = "email")]
-    Email(String), // Supports email only
+    Email(String),
+    /// Uploads the report to this path under `reports/{org_id}/` of the object store.
+    #[serde(rename = "storage")]
+    Storage(String),
+    #[serde(rename = "slack")]
+    Slack(ReportWebhook),
+    #[serde(rename = "teams")]
+    Teams(ReportWebhook),
+}
+
+impl ReportDestination {
+    /// Email and storage destinations need the rendered report, chat destinations only get a
+    /// message.
+    pub fn needs_artifact(&self) -> bool {
+        matches!(self, Self::Email(_) | Self::Storage(_))
+    }
+}
+
+/// Incoming webhook of a Slack or Teams channel.
+#[derive(Serialize, Debug, Default, Deserialize, Clone, ToSchema, PartialEq, Eq)]
+pub struct ReportWebhook {
+    pub url: String,
+    /// Message template, `{name}`, `{title}`, `{message}`, `{dashboard_url}` and `{artifacts}`
+    /// are replaced with the report values. Uses [`DEFAULT_REPORT_MESSAGE_TEMPLATE`] when empty.
+    #[serde(default)]
+    pub template: String,
+}
+
+pub const DEFAULT_REPORT_MESSAGE_TEMPLATE: &str =
+    "{title}\n{message}\nDashboard: {dashboard_url}\nReport: {artifacts}";
+
+/// Renders the chat message of a report, `artifacts` are the object store paths of the
+/// uploaded report.
+pub fn render_report_message(
+    template: &str,
+    report: &Report,
+    dashboard_url: &str,
+    artifacts: &[String],
+) -> String {
+    let template = if template.trim().is_empty() {
+        DEFAULT_REPORT_MESSAGE_TEMPLATE
+    } else {
+        template
+    };
+    template
+        .replace("{name}", &report.name)
+        .replace("{title}", &report.title)
+        .replace("{message}", &report.message)
+        .replace("{dashboard_url}", dashboard_url)
+        .replace("{artifacts}", &artifacts.join(", "))
+}
+
+/// Returns the object store path of a report uploaded to a storage destination, `None` when
+/// the destination path tries to leave the reports directory.
+pub fn report_storage_path(
+    org_id: &str,
+    path: &str,
+    report_name: &str,
+    timestamp: i64,
+) -> Option<String> {
+    report_storage_dir(org_id, path).map(|dir| format!("{dir}/{report_name}_{timestamp}.pdf"))
+}
+
+/// Returns the object store directory of a storage destination, `None` when the destination
+/// path tries to leave the reports directory.
+pub fn report_storage_dir(org_id: &str, path: &str) -> Option<String> {
+    let path = path.trim().trim_matches('/');
+    if path
+        .split('/')
+        .any(|segment| segment == ".." || segment == ".")
+    {
+        return None;
+    }
+    Some(if path.is_empty() {
+        format!("reports/{org_id}")
+    } else {
+        format!("reports/{org_id}/{path}")
+    })
 }
 
 #[derive(Serialize, Debug, Default, Deserialize, Clone, ToSchema)]
@@ -33,6 +110,133 @@ pub enum ReportMediaType {
     Pdf, // Supports Pdf only
 }
 
+/// Most rows of a panel included in the panel data of a report.
+pub const MAX_REPORT_PANEL_ROWS: i64 = 10_000;
+
+fn default_report_panel_rows() -> i64 {
+    1_000
+}
+
+/// File format of the panel data attached to a report.
+#[derive(Serialize, Debug, Default, Deserialize, Clone, Copy, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportDataFormat {
+    #[default]
+    Csv,
+    Xlsx,
+}
+
+impl ReportDataFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ReportDataFormat::Csv => "text/csv",
+            ReportDataFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportDataFormat::Csv => "csv",
+            ReportDataFormat::Xlsx => "xlsx",
+        }
+    }
+}
+
+/// Attaches the results of the panel queries to a report, one file per panel.
+#[derive(Serialize, Debug, Deserialize, Clone, ToSchema, PartialEq, Eq)]
+pub struct ReportPanelData {
+    #[serde(default)]
+    pub format: ReportDataFormat,
+    /// Rows of each panel, at most [`MAX_REPORT_PANEL_ROWS`].
+    #[serde(default = "default_report_panel_rows")]
+    pub max_rows: i64,
+    /// Only sends the panel data, without the rendered dashboard.
+    #[serde(default)]
+    pub data_only: bool,
+}
+
+/// Section of a digest report.
+#[derive(Serialize, Debug, Deserialize, Clone, Copy, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestSection {
+    /// Alerts which fired the most
+    AlertActivity,
+    /// Logs streams whose rate of error records changed the most
+    ErrorRateMovers,
+    /// Streams whose storage grew the most
+    StorageGrowth,
+}
+
+impl DigestSection {
+    pub const ALL: [DigestSection; 3] = [
+        DigestSection::AlertActivity,
+        DigestSection::ErrorRateMovers,
+        DigestSection::StorageGrowth,
+    ];
+}
+
+/// Period summarized by a digest, compared with the period before it.
+#[derive(Serialize, Debug, Default, Deserialize, Clone, Copy, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestPeriod {
+    Daily,
+    #[default]
+    Weekly,
+}
+
+impl DigestPeriod {
+    /// Length of the period in microseconds.
+    pub fn micros(&self) -> i64 {
+        match self {
+            DigestPeriod::Daily => 86_400_000_000,
+            DigestPeriod::Weekly => 7 * 86_400_000_000,
+        }
+    }
+}
+
+/// Most rows of a digest section.
+pub const MAX_DIGEST_TOP_N: usize = 100;
+
+fn default_digest_top_n() -> usize {
+    10
+}
+
+/// Sends a summary of the org activity computed from its alert history and stream stats instead
+/// of the rendered dashboards.
+#[derive(Serialize, Debug, Deserialize, Clone, ToSchema, PartialEq, Eq)]
+pub struct ReportDigest {
+    #[serde(default)]
+    pub period: DigestPeriod,
+    /// Sections of the digest, all of them when empty.
+    #[serde(default)]
+    pub sections: Vec<DigestSection>,
+    /// Rows of each section, at most [`MAX_DIGEST_TOP_N`].
+    #[serde(default = "default_digest_top_n")]
+    pub top_n: usize,
+}
+
+impl Default for ReportDigest {
+    fn default() -> Self {
+        Self {
+            period: DigestPeriod::default(),
+            sections: vec![],
+            top_n: default_digest_top_n(),
+        }
+    }
+}
+
+impl ReportDigest {
+    /// Returns the sections of the digest, in their display order.
+    pub fn sections(&self) -> Vec<DigestSection> {
+        DigestSection::ALL
+            .into_iter()
+            .filter(|s| self.sections.is_empty() || self.sections.contains(s))
+            .collect()
+    }
+}
+
 #[derive(Serialize, Debug, Default, Deserialize, Clone, ToSchema, PartialEq, Eq)]
 pub struct ReportDashboardVariable {
     pub key: String,
@@ -164,6 +368,13 @@ pub struct Report {
     pub updated_at: Option<DateTime<FixedOffset>>,
     pub owner: String,
     pub last_edited_by: String,
+    /// Raw data of the panels attached to the report, along with or instead of the rendered
+    /// dashboard.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub panel_data: Option<ReportPanelData>,
+    /// Sends a digest of the org activity by email instead of the dashboards.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<ReportDigest>,
 }
 
 impl Default for Report {
@@ -186,6 +397,8 @@ impl Default for Report {
             updated_at: None,
             owner: "".to_string(),
             last_edited_by: "".to_string(),
+            panel_data: None,
+            digest: None,
         }
     }
 }
@@ -319,4 +532,65 @@ mod tests {
             serde_json::from_str(&json_using_alias).unwrap();
         assert_eq!(email_details, email_details_from_alias);
     }
+
+    #[test]
+    fn test_report_destinations() {
+        let destinations: Vec<ReportDestination> = serde_json::from_str(
+            r#"[{"email": "foo@example.com"}, {"storage": "weekly"},
+                {"slack": {"url": "https://hooks.slack.com/services/x"}}]"#,
+        )
+        .unwrap();
+        assert!(destinations[0].needs_artifact());
+        assert!(destinations[1].needs_artifact());
+        assert!(!destinations[2].needs_artifact());
+
+        let report = Report {
+            name: "weekly".to_string(),
+            title: "Weekly errors".to_string(),
+            message: "see attached".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            render_report_message(
+                "{title}: {dashboard_url} {artifacts}",
+                &report,
+                "http://localhost/web",
+                &["a.pdf".to_string(), "b.pdf".to_string()],
+            ),
+            "Weekly errors: http://localhost/web a.pdf, b.pdf"
+        );
+        assert!(render_report_message("", &report, "", &[]).starts_with("Weekly errors\n"));
+    }
+
+    #[test]
+    fn test_report_digest() {
+        let digest: ReportDigest = serde_json::from_str(r#"{"period": "daily"}"#).unwrap();
+        assert_eq!(digest.period, DigestPeriod::Daily);
+        assert_eq!(digest.top_n, 10);
+        assert_eq!(digest.sections(), DigestSection::ALL.to_vec());
+
+        let digest: ReportDigest =
+            serde_json::from_str(r#"{"sections": ["storage_growth", "alert_activity"]}"#).unwrap();
+        assert_eq!(digest.period, DigestPeriod::Weekly);
+        assert_eq!(
+            digest.sections(),
+            vec![DigestSection::AlertActivity, DigestSection::StorageGrowth]
+        );
+    }
+
+    #[test]
+    fn test_report_storage_path() {
+        assert_eq!(
+            report_storage_path("default", "/weekly/", "errors", 1).as_deref(),
+            Some("reports/default/weekly/errors_1.pdf")
+        );
+        assert_eq!(
+            report_storage_path("default", "", "errors", 1).as_deref(),
+            Some("reports/default/errors_1.pdf")
+        );
+        assert_eq!(
+            report_storage_path("default", "../files", "errors", 1),
+            None
+        );
+    }
 }
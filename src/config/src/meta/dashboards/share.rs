@@ -0,0 +1,108 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::reports::ReportDashboardVariable;
+
+/// Request to share a dashboard with a read-only link that doesn't need an account.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct CreateShareRequest {
+    /// Only share these tabs, all tabs if empty.
+    #[serde(default)]
+    pub tabs: Vec<String>,
+    /// Values of the dashboard variables, fixed by the token so the viewers of the link can't
+    /// change the panel queries.
+    #[serde(default)]
+    pub variables: Vec<ReportDashboardVariable>,
+    /// Lifetime of the link in seconds, defaults to one day.
+    #[serde(default = "default_share_ttl")]
+    pub expires_in: i64,
+}
+
+fn default_share_ttl() -> i64 {
+    24 * 3600
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateShareResponse {
+    pub token: String,
+    /// Link to the shared dashboard, it doesn't need authentication.
+    pub url: String,
+    /// Expiry of the token in seconds since epoch.
+    pub expires_at: i64,
+}
+
+/// Claims of a dashboard share token.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DashboardShareClaims {
+    pub org: String,
+    pub dashboard: String,
+    /// User who shared the dashboard.
+    pub created_by: String,
+    #[serde(default)]
+    pub tabs: Vec<String>,
+    #[serde(default)]
+    pub variables: Vec<ReportDashboardVariable>,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+/// Signing key of the share tokens of a dashboard, deleting it revokes all the links.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DashboardShareKey {
+    pub secret: String,
+    /// Creation time in microseconds.
+    pub created_at: i64,
+}
+
+/// File format of a shared dashboard.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ShareFormat {
+    #[default]
+    Html,
+    Pdf,
+    Png,
+}
+
+impl std::str::FromStr for ShareFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "html" => Ok(ShareFormat::Html),
+            "pdf" => Ok(ShareFormat::Pdf),
+            "png" => Ok(ShareFormat::Png),
+            _ => Err(format!("unsupported share format: {s}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::json;
+
+    #[test]
+    fn test_create_share_request_defaults() {
+        let req: CreateShareRequest = json::from_str("{}").unwrap();
+        assert_eq!(req.expires_in, 24 * 3600);
+        assert!(req.tabs.is_empty());
+        assert_eq!("PDF".parse::<ShareFormat>().unwrap(), ShareFormat::Pdf);
+        assert!("svg".parse::<ShareFormat>().is_err());
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::reports::ReportDashboardVariable;
+
+/// A frozen copy of the data of a dashboard, rendered as a self-contained html page kept in the
+/// object storage until it expires.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct DashboardSnapshot {
+    pub id: String,
+    pub dashboard_id: String,
+    pub title: String,
+    pub created_by: String,
+    /// Creation time in microseconds.
+    pub created_at: i64,
+    /// Expiry time in microseconds, the snapshot is deleted afterwards.
+    pub expires_at: i64,
+    /// Start of the snapshot time range in microseconds.
+    pub start_time: i64,
+    /// End of the snapshot time range in microseconds.
+    pub end_time: i64,
+    /// Size of the html page in bytes.
+    pub size: usize,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct CreateSnapshotRequest {
+    /// Start of the time range in microseconds.
+    pub start_time: i64,
+    /// End of the time range in microseconds.
+    pub end_time: i64,
+    /// Values of the dashboard variables used by the panel queries.
+    #[serde(default)]
+    pub variables: Vec<ReportDashboardVariable>,
+    /// Only snapshot these tabs, all tabs if empty.
+    #[serde(default)]
+    pub tabs: Vec<String>,
+    /// Lifetime of the snapshot in seconds, defaults to 7 days.
+    #[serde(default = "default_snapshot_ttl")]
+    pub expires_in: i64,
+}
+
+fn default_snapshot_ttl() -> i64 {
+    7 * 24 * 3600
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateSnapshotResponse {
+    pub snapshot: DashboardSnapshot,
+    /// Link to the html page, it doesn't need authentication.
+    pub url: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct DashboardSnapshotList {
+    pub list: Vec<DashboardSnapshot>,
+}
+
+/// File format of the panel datasets written by a snapshot schedule.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotFormat {
+    #[default]
+    Json,
+    Parquet,
+}
+
+impl SnapshotFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SnapshotFormat::Json => "json",
+            SnapshotFormat::Parquet => "parquet",
+        }
+    }
+}
+
+/// Periodically runs the panel queries of a dashboard and writes their results to the object
+/// storage, each run under its own prefix.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct SnapshotSchedule {
+    pub id: String,
+    pub dashboard_id: String,
+    /// Seconds between two runs.
+    pub frequency: i64,
+    /// Each run covers the last `period` seconds.
+    pub period: i64,
+    #[serde(default)]
+    pub format: SnapshotFormat,
+    /// The datasets of a run are deleted after this many seconds.
+    pub retention: i64,
+    #[serde(default)]
+    pub variables: Vec<ReportDashboardVariable>,
+    #[serde(default)]
+    pub tabs: Vec<String>,
+    pub enabled: bool,
+    pub created_by: String,
+    /// Creation time in microseconds.
+    pub created_at: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateSnapshotScheduleRequest {
+    /// Seconds between two runs.
+    pub frequency: i64,
+    /// Each run covers the last `period` seconds, defaults to `frequency`.
+    #[serde(default)]
+    pub period: Option<i64>,
+    #[serde(default)]
+    pub format: SnapshotFormat,
+    /// Lifetime of the datasets of a run in seconds, defaults to 30 days.
+    #[serde(default = "default_schedule_retention")]
+    pub retention: i64,
+    #[serde(default)]
+    pub variables: Vec<ReportDashboardVariable>,
+    #[serde(default)]
+    pub tabs: Vec<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_schedule_retention() -> i64 {
+    30 * 24 * 3600
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SnapshotScheduleList {
+    pub list: Vec<SnapshotSchedule>,
+}
+
+/// The datasets written by one run of a snapshot schedule, kept as `manifest.json` next to them.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct ScheduledSnapshot {
+    /// Id of the run, the start of the run in microseconds.
+    pub id: String,
+    pub schedule_id: String,
+    pub dashboard_id: String,
+    pub title: String,
+    pub format: SnapshotFormat,
+    /// Creation time in microseconds.
+    pub created_at: i64,
+    /// Expiry time in microseconds, the datasets are deleted afterwards.
+    pub expires_at: i64,
+    /// Start of the time range in microseconds.
+    pub start_time: i64,
+    /// End of the time range in microseconds.
+    pub end_time: i64,
+    pub panels: Vec<ScheduledSnapshotPanel>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct ScheduledSnapshotPanel {
+    pub tab: String,
+    pub title: String,
+    /// Name of the dataset file in the run prefix, none when the panel query failed or returned
+    /// no rows.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    pub rows: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ScheduledSnapshotList {
+    pub list: Vec<ScheduledSnapshot>,
+}
@@ -0,0 +1,317 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Transformations of the results of panel queries, applied on the server after the query so
+//! that reshaping the data for a chart doesn't need a rewrite of the SQL.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::utils::json::{Map, Value};
+
+/// A step of the transformations of a panel, the steps run in order on the rows of each query.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Transformation {
+    /// Merges the rows with the same values of the `on` fields into one row, like the series
+    /// of a breakdown sharing the timestamp.
+    Merge { on: Vec<String> },
+    /// Renames fields, from the old to the new name.
+    Rename { fields: BTreeMap<String, String> },
+    /// Keeps the rows whose `field` compares to `value`.
+    FilterRows {
+        field: String,
+        operator: FilterOperator,
+        value: String,
+    },
+    /// Turns the values of `column` into fields holding `value`, with a row per value of `row`.
+    Pivot {
+        row: String,
+        column: String,
+        value: String,
+    },
+    /// Adds the field `alias` computed from two fields or numbers.
+    ComputeField {
+        alias: String,
+        left: String,
+        operator: ComputeOperator,
+        right: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum FilterOperator {
+    #[serde(rename = "=")]
+    Eq,
+    #[serde(rename = "!=")]
+    Ne,
+    #[serde(rename = ">")]
+    Gt,
+    #[serde(rename = ">=")]
+    Gte,
+    #[serde(rename = "<")]
+    Lt,
+    #[serde(rename = "<=")]
+    Lte,
+    #[serde(rename = "contains")]
+    Contains,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum ComputeOperator {
+    #[serde(rename = "+")]
+    Add,
+    #[serde(rename = "-")]
+    Subtract,
+    #[serde(rename = "*")]
+    Multiply,
+    #[serde(rename = "/")]
+    Divide,
+}
+
+/// Runs the transformations on the rows of a query.
+pub fn apply(transformations: &[Transformation], mut rows: Vec<Value>) -> Vec<Value> {
+    for transformation in transformations {
+        rows = match transformation {
+            Transformation::Merge { on } => merge(rows, on),
+            Transformation::Rename { fields } => rows
+                .into_iter()
+                .map(|row| match row {
+                    Value::Object(row) => Value::Object(
+                        row.into_iter()
+                            .map(|(k, v)| (fields.get(&k).cloned().unwrap_or(k), v))
+                            .collect(),
+                    ),
+                    row => row,
+                })
+                .collect(),
+            Transformation::FilterRows {
+                field,
+                operator,
+                value,
+            } => rows
+                .into_iter()
+                .filter(|row| matches(row.get(field), *operator, value))
+                .collect(),
+            Transformation::Pivot { row, column, value } => pivot(rows, row, column, value),
+            Transformation::ComputeField {
+                alias,
+                left,
+                operator,
+                right,
+            } => rows
+                .into_iter()
+                .map(|mut row| {
+                    let result = match (operand(&row, left), operand(&row, right)) {
+                        (Some(l), Some(r)) => compute(l, *operator, r),
+                        _ => None,
+                    };
+                    if let Value::Object(row) = &mut row {
+                        row.insert(
+                            alias.clone(),
+                            result.map(Value::from).unwrap_or(Value::Null),
+                        );
+                    }
+                    row
+                })
+                .collect(),
+        };
+    }
+    rows
+}
+
+/// Key of a row by the values of the fields, the rows missing a field have no key.
+fn row_key(row: &Value, fields: &[String]) -> Option<String> {
+    fields
+        .iter()
+        .map(|f| row.get(f).map(|v| v.to_string()))
+        .collect::<Option<Vec<_>>>()
+        .map(|values| values.join("\u{1f}"))
+}
+
+fn merge(rows: Vec<Value>, on: &[String]) -> Vec<Value> {
+    if on.is_empty() {
+        return rows;
+    }
+    let mut merged: Vec<Value> = Vec::with_capacity(rows.len());
+    let mut index: BTreeMap<String, usize> = BTreeMap::new();
+    for row in rows {
+        let Some(key) = row_key(&row, on) else {
+            merged.push(row);
+            continue;
+        };
+        match (index.get(&key), row) {
+            (Some(&i), Value::Object(row)) => {
+                if let Value::Object(target) = &mut merged[i] {
+                    for (k, v) in row {
+                        if !v.is_null() || !target.contains_key(&k) {
+                            target.insert(k, v);
+                        }
+                    }
+                }
+            }
+            (_, row) => {
+                index.insert(key, merged.len());
+                merged.push(row);
+            }
+        }
+    }
+    merged
+}
+
+fn pivot(rows: Vec<Value>, row_field: &str, column: &str, value: &str) -> Vec<Value> {
+    let mut pivoted: Vec<Map<String, Value>> = Vec::new();
+    let mut index: BTreeMap<String, usize> = BTreeMap::new();
+    for row in rows {
+        let (Some(key), Some(name)) = (row.get(row_field), row.get(column)) else {
+            continue;
+        };
+        let name = match name {
+            Value::String(s) => s.clone(),
+            v => v.to_string(),
+        };
+        let i = *index.entry(key.to_string()).or_insert_with(|| {
+            let mut new = Map::new();
+            new.insert(row_field.to_string(), key.clone());
+            pivoted.push(new);
+            pivoted.len() - 1
+        });
+        pivoted[i].insert(name, row.get(value).cloned().unwrap_or(Value::Null));
+    }
+    pivoted.into_iter().map(Value::Object).collect()
+}
+
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn matches(value: Option<&Value>, operator: FilterOperator, expected: &str) -> bool {
+    let Some(value) = value else {
+        return operator == FilterOperator::Ne;
+    };
+    let text = match value {
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    };
+    if operator == FilterOperator::Contains {
+        return text.contains(expected);
+    }
+    let ordering = match (as_f64(value), expected.parse::<f64>()) {
+        (Some(l), Ok(r)) => l.partial_cmp(&r),
+        _ => Some(text.as_str().cmp(expected)),
+    };
+    let Some(ordering) = ordering else {
+        return false;
+    };
+    match operator {
+        FilterOperator::Eq => ordering.is_eq(),
+        FilterOperator::Ne => ordering.is_ne(),
+        FilterOperator::Gt => ordering.is_gt(),
+        FilterOperator::Gte => ordering.is_ge(),
+        FilterOperator::Lt => ordering.is_lt(),
+        FilterOperator::Lte => ordering.is_le(),
+        FilterOperator::Contains => unreachable!(),
+    }
+}
+
+/// A field of the row or a number.
+fn operand(row: &Value, name: &str) -> Option<f64> {
+    match row.get(name) {
+        Some(value) => as_f64(value),
+        None => name.parse().ok(),
+    }
+}
+
+fn compute(left: f64, operator: ComputeOperator, right: f64) -> Option<f64> {
+    let result = match operator {
+        ComputeOperator::Add => left + right,
+        ComputeOperator::Subtract => left - right,
+        ComputeOperator::Multiply => left * right,
+        ComputeOperator::Divide if right == 0.0 => return None,
+        ComputeOperator::Divide => left / right,
+    };
+    result.is_finite().then_some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::json::{self, json};
+
+    #[test]
+    fn test_apply() {
+        let transformations: Vec<Transformation> = json::from_value(json!([
+            {"type": "filterRows", "field": "count", "operator": ">", "value": "1"},
+            {"type": "pivot", "row": "ts", "column": "host", "value": "count"},
+            {"type": "computeField", "alias": "total", "left": "a", "operator": "+", "right": "b"},
+            {"type": "rename", "fields": {"ts": "time"}}
+        ]))
+        .unwrap();
+        let rows = vec![
+            json!({"ts": 1, "host": "a", "count": 2}),
+            json!({"ts": 1, "host": "b", "count": 3}),
+            json!({"ts": 2, "host": "a", "count": 1}),
+            json!({"ts": 2, "host": "b", "count": "4"}),
+        ];
+        assert_eq!(
+            apply(&transformations, rows),
+            vec![
+                json!({"time": 1, "a": 2, "b": 3, "total": 5.0}),
+                json!({"time": 2, "b": "4", "total": null}),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let rows = vec![
+            json!({"ts": 1, "errors": 2}),
+            json!({"ts": 2, "errors": 1}),
+            json!({"ts": 1, "requests": 10, "errors": null}),
+            json!({"requests": 5}),
+        ];
+        let merge = Transformation::Merge {
+            on: vec!["ts".to_string()],
+        };
+        assert_eq!(
+            apply(&[merge], rows),
+            vec![
+                json!({"ts": 1, "errors": 2, "requests": 10}),
+                json!({"ts": 2, "errors": 1}),
+                json!({"requests": 5}),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filter_rows() {
+        assert!(matches(
+            Some(&json!("web-1")),
+            FilterOperator::Contains,
+            "web"
+        ));
+        assert!(matches(Some(&json!("b")), FilterOperator::Gt, "a"));
+        assert!(matches(Some(&json!(10)), FilterOperator::Gt, "9"));
+        assert!(matches(None, FilterOperator::Ne, "a"));
+        assert!(!matches(None, FilterOperator::Eq, "a"));
+        assert_eq!(compute(1.0, ComputeOperator::Divide, 0.0), None);
+    }
+}
@@ -16,11 +16,17 @@
 use std::hash::{Hash, Hasher};
 
 use chrono::{DateTime, FixedOffset};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 use super::{OrdF64, datetime_now};
-use crate::meta::stream::StreamType;
+use crate::{meta::stream::StreamType, utils::json};
+
+/// Variable references like `$name`, `${name}` or `${name:csv}`.
+static RE_VARIABLE_REF: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\$\{?([a-zA-Z_][a-zA-Z0-9_]*)").unwrap());
 
 #[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -63,6 +69,7 @@ impl From<Dashboard> for super::Dashboard {
             v3: None,
             v4: None,
             v5: Some(value),
+            v6: None,
             version,
             hash,
             updated_at,
@@ -302,6 +309,142 @@ pub struct PanelConfig {
     background: Option<Background>,
     #[serde(skip_serializing_if = "Option::is_none")]
     trellis: Option<Trellis>,
+    /// Overlay the org-level annotations on this panel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    show_annotations: Option<bool>,
+    /// Regional formatting of the numbers and dates rendered on the server, in reports and
+    /// snapshots.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format_options: Option<FormatOptions>,
+    /// Longest duration of a query of the panel, shorter than the timeout of the search.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query_timeout_secs: Option<u64>,
+    /// Most rows returned by a query of the panel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_rows: Option<u64>,
+    /// Steps coloring the value of gauge and stat panels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thresholds: Option<Vec<Threshold>>,
+    /// Secondary query computing threshold values, added to the static thresholds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    threshold_query: Option<ThresholdQuery>,
+}
+
+impl PanelConfig {
+    pub fn query_timeout_secs(&self) -> Option<u64> {
+        self.query_timeout_secs.filter(|v| *v > 0)
+    }
+
+    pub fn max_rows(&self) -> Option<u64> {
+        self.max_rows.filter(|v| *v > 0)
+    }
+
+    pub fn thresholds(&self) -> &[Threshold] {
+        self.thresholds.as_deref().unwrap_or_default()
+    }
+
+    pub fn trellis(&self) -> Option<&Trellis> {
+        self.trellis.as_ref()
+    }
+
+    pub fn threshold_query(&self) -> Option<&ThresholdQuery> {
+        self.threshold_query
+            .as_ref()
+            .filter(|q| !q.query.trim().is_empty() && !q.steps.is_empty())
+    }
+}
+
+/// A step of the thresholds of a panel, values from `value` up to the next step are shown in
+/// `color`.
+#[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
+pub struct Threshold {
+    pub value: OrdF64,
+    pub color: String,
+    #[serde(default)]
+    pub mode: ThresholdMode,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ThresholdMode {
+    /// `value` is compared with the value of the panel.
+    #[default]
+    Absolute,
+    /// `value` is a percentage of the range between the min and max of the panel.
+    Percentage,
+}
+
+/// Query producing threshold values that follow the data, like the 95th percentile of the last
+/// week. It is evaluated with the panel and each step takes its value from a column of the first
+/// row of the result.
+#[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
+pub struct ThresholdQuery {
+    pub query: String,
+    #[serde(default)]
+    pub stream_type: StreamType,
+    /// Period the query covers, ending at the end of the time range of the panel, like `7d`.
+    /// The query covers the time range of the panel if it is not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relative_time_period: Option<String>,
+    pub steps: Vec<ThresholdQueryStep>,
+}
+
+#[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
+pub struct ThresholdQueryStep {
+    /// Column of the result holding the value of the step.
+    pub field: String,
+    pub color: String,
+}
+
+impl ThresholdQuery {
+    /// Time range of the query in microseconds, for a panel showing `start_time..end_time`.
+    pub fn time_range(&self, start_time: i64, end_time: i64) -> (i64, i64) {
+        let period = self
+            .relative_time_period
+            .as_deref()
+            .and_then(|p| crate::utils::time::parse_milliseconds(p).ok())
+            .filter(|ms| *ms > 0);
+        match period {
+            Some(ms) => (end_time - ms as i64 * 1000, end_time),
+            None => (start_time, end_time),
+        }
+    }
+
+    /// Absolute thresholds from the row returned by the query, the steps whose column is
+    /// missing or not a number are left out.
+    pub fn thresholds(&self, row: &json::Value) -> Vec<Threshold> {
+        self.steps
+            .iter()
+            .filter_map(|step| {
+                let value = match row.get(&step.field)? {
+                    json::Value::Number(n) => n.as_f64()?,
+                    json::Value::String(s) => s.parse().ok()?,
+                    _ => return None,
+                };
+                Some(Threshold {
+                    value: OrdF64::from(value),
+                    color: step.color.clone(),
+                    mode: ThresholdMode::Absolute,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Color of `value` by the thresholds, the step with the highest bound not above the value
+/// wins. `min` and `max` are the range the percentage thresholds are relative to.
+pub fn threshold_color(thresholds: &[Threshold], value: f64, min: f64, max: f64) -> Option<&str> {
+    thresholds
+        .iter()
+        .filter_map(|t| {
+            let bound = match t.mode {
+                ThresholdMode::Absolute => t.value.into_inner(),
+                ThresholdMode::Percentage => min + (max - min) * t.value.into_inner() / 100.0,
+            };
+            (bound <= value).then_some((bound, t.color.as_str()))
+        })
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, color)| color)
 }
 
 #[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
@@ -442,11 +585,25 @@ pub struct QueryConfig {
     time_shift: Option<Vec<TimeShift>>,
 }
 
+impl QueryConfig {
+    /// Offsets of the time shifted comparisons of the query, to pass as the `time_shifts` of a
+    /// search request so that the comparisons are computed by the search.
+    pub fn time_shifts(&self) -> Vec<String> {
+        self.time_shift
+            .iter()
+            .flatten()
+            .filter_map(|shift| shift.off_set.clone())
+            .filter(|offset| !offset.is_empty())
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TimeShift {
+    /// Offset of the comparison like `1d` or `7d`
     #[serde(skip_serializing_if = "Option::is_none")]
-    off_set: Option<String>,
+    pub off_set: Option<String>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Hash, Serialize, Deserialize)]
@@ -491,6 +648,169 @@ pub struct VariableList {
     pub custom_multi_select_value: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub escape_single_quotes: Option<bool>,
+    /// Variables whose values this variable uses, on top of the variables referenced in its
+    /// query filters. Its values are resolved after theirs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// Automatic step of `interval` variables, their options are the fixed steps.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval: Option<IntervalVariable>,
+}
+
+impl VariableList {
+    /// Variables this variable depends on: the declared `depends_on` and the variables
+    /// referenced in the values of its query filters.
+    pub fn dependencies(&self) -> Vec<String> {
+        let mut deps = self.depends_on.clone();
+        let filters = self
+            .query_data
+            .iter()
+            .flat_map(|q| q.filter.iter().flatten());
+        for filter in filters {
+            for cap in RE_VARIABLE_REF.captures_iter(&filter.value) {
+                if !deps.iter().any(|d| d == &cap[1]) {
+                    deps.push(cap[1].to_string());
+                }
+            }
+        }
+        deps
+    }
+}
+
+/// Value of an `interval` variable selecting the automatic step.
+pub const AUTO_INTERVAL: &str = "auto";
+/// Built-in variable holding the automatic step of the panel, like Grafana's `$__interval`.
+pub const BUILTIN_INTERVAL_VARIABLE: &str = "__interval";
+/// Columns of the dashboard grid, the width of a panel is a number of columns.
+pub const GRID_COLUMNS: i64 = 48;
+/// Width in pixels of the dashboard, used to size the automatic step of a panel from its
+/// number of columns.
+pub const DASHBOARD_WIDTH: u32 = 1920;
+/// Pixels of panel width per step of the automatic interval.
+const PIXELS_PER_STEP: u32 = 10;
+/// Steps the automatic interval is rounded up to, in seconds.
+const INTERVAL_STEPS: [i64; 18] = [
+    1, 5, 10, 15, 30, 60, 300, 600, 900, 1800, 3600, 7200, 10800, 21600, 43200, 86400, 604800,
+    2592000,
+];
+
+#[derive(Default, Debug, Clone, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntervalVariable {
+    /// Adds the `auto` option, selected when the variable has no value.
+    #[serde(default)]
+    pub auto: bool,
+    /// Smallest automatic step, like `10 second` or `1m`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_interval: Option<String>,
+}
+
+impl IntervalVariable {
+    /// Step in seconds for the time range shown by a panel `width` pixels wide: one step every
+    /// few pixels, rounded up to a round interval and not below `min_interval`.
+    pub fn auto_step(&self, start_time: i64, end_time: i64, width: u32) -> i64 {
+        let steps = (width / PIXELS_PER_STEP).max(1) as i64;
+        let min = self
+            .min_interval
+            .as_deref()
+            .and_then(parse_interval)
+            .unwrap_or(1);
+        let step = ((end_time - start_time) / 1_000_000 / steps)
+            .max(min)
+            .max(1);
+        INTERVAL_STEPS
+            .iter()
+            .copied()
+            .find(|s| *s >= step)
+            .unwrap_or(step)
+    }
+}
+
+/// Width in pixels of a panel `columns` columns wide.
+pub fn panel_width(columns: i64) -> u32 {
+    let columns = if columns > 0 && columns < GRID_COLUMNS {
+        columns
+    } else {
+        GRID_COLUMNS
+    };
+    (DASHBOARD_WIDTH as i64 * columns / GRID_COLUMNS) as u32
+}
+
+/// Formats a step in seconds as a `histogram()` interval in the largest unit dividing it, like
+/// `5 minute`.
+pub fn format_interval(secs: i64) -> String {
+    let secs = secs.max(1);
+    for (unit, size) in [("day", 86400), ("hour", 3600), ("minute", 60)] {
+        if secs % size == 0 {
+            return format!("{} {unit}", secs / size);
+        }
+    }
+    format!("{secs} second")
+}
+
+/// Parses an interval like `10 second`, `5m` or `1 hour` in seconds.
+pub fn parse_interval(interval: &str) -> Option<i64> {
+    let interval = interval.trim();
+    let pos = interval.find(|c: char| !c.is_ascii_digit())?;
+    let (num, unit) = interval.split_at(pos);
+    let num = num.parse::<i64>().ok()?;
+    let size = match unit.trim().to_lowercase().as_str() {
+        "s" | "sec" | "secs" | "second" | "seconds" => 1,
+        "m" | "min" | "mins" | "minute" | "minutes" => 60,
+        "h" | "hr" | "hrs" | "hour" | "hours" => 3600,
+        "d" | "day" | "days" => 86400,
+        _ => return None,
+    };
+    Some(num * size)
+}
+
+impl Variables {
+    /// Groups the variables in levels, the variables of a level only depend on variables of
+    /// the previous levels so they can be resolved together. Fails on cycles and on unknown
+    /// variables in `depends_on`, unknown variables referenced by filters are ignored.
+    pub fn dependency_levels(&self) -> Result<Vec<Vec<&VariableList>>, String> {
+        let mut pending = Vec::with_capacity(self.list.len());
+        for var in self.list.iter() {
+            if let Some(name) = var
+                .depends_on
+                .iter()
+                .find(|d| !self.list.iter().any(|v| &v.name == *d))
+            {
+                return Err(format!(
+                    "variable {} depends on unknown variable {name}",
+                    var.name
+                ));
+            }
+            let deps = var
+                .dependencies()
+                .into_iter()
+                .filter(|d| self.list.iter().any(|v| &v.name == d))
+                .collect::<Vec<_>>();
+            pending.push((var, deps));
+        }
+
+        let mut resolved: Vec<&str> = Vec::with_capacity(self.list.len());
+        let mut levels = Vec::new();
+        while !pending.is_empty() {
+            let (ready, rest): (Vec<_>, Vec<_>) = pending
+                .into_iter()
+                .partition(|(_, deps)| deps.iter().all(|d| resolved.contains(&d.as_str())));
+            if ready.is_empty() {
+                let names = rest
+                    .iter()
+                    .map(|(v, _)| v.name.as_str())
+                    .collect::<Vec<_>>();
+                return Err(format!(
+                    "variables {} depend on each other",
+                    names.join(", ")
+                ));
+            }
+            resolved.extend(ready.iter().map(|(v, _)| v.name.as_str()));
+            levels.push(ready.into_iter().map(|(v, _)| v).collect());
+            pending = rest;
+        }
+        Ok(levels)
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Hash, Serialize, Deserialize)]
@@ -539,12 +859,37 @@ pub struct MapView {
     pub lng: OrdF64,
 }
 
+/// Locale and formatting hints of a panel. The separators and the date format default to the
+/// ones of the locale.
+#[derive(Debug, Clone, Default, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
+pub struct FormatOptions {
+    /// BCP 47 language tag like `en-US` or `de-DE`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thousands_separator: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimal_separator: Option<String>,
+    /// strftime format of dates like `%d.%m.%Y %H:%M`, dates are shown in UTC.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_format: Option<String>,
+    /// ISO 4217 code of the currency of the `currency` unit like `EUR`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
 pub struct Trellis {
     pub layout: Option<String>,
     pub num_of_columns: i64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub group_by_y_axis: Option<bool>,
+    /// Most breakdown groups rendered at once, the groups past it are split into pages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_panels: Option<u64>,
+    /// Page of breakdown groups rendered, starting at 0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
@@ -604,3 +949,146 @@ pub enum LabelPosition {
     InsideBottomRight,
     Outside,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_interval() {
+        let interval = IntervalVariable {
+            auto: true,
+            min_interval: None,
+        };
+        let hour = 3600 * 1_000_000;
+        // 192 steps over 2 hours is 37s, rounded up to 1 minute
+        assert_eq!(interval.auto_step(0, 2 * hour, DASHBOARD_WIDTH), 60);
+        // a quarter width panel has 48 steps
+        assert_eq!(interval.auto_step(0, 2 * hour, panel_width(12)), 300);
+        assert_eq!(interval.auto_step(0, 0, DASHBOARD_WIDTH), 1);
+
+        let interval = IntervalVariable {
+            auto: true,
+            min_interval: Some("15m".to_string()),
+        };
+        assert_eq!(interval.auto_step(0, 2 * hour, DASHBOARD_WIDTH), 900);
+
+        assert_eq!(format_interval(60), "1 minute");
+        assert_eq!(format_interval(90), "90 second");
+        assert_eq!(format_interval(7200), "2 hour");
+        assert_eq!(parse_interval("10 second"), Some(10));
+        assert_eq!(parse_interval("2h"), Some(7200));
+        assert_eq!(parse_interval("auto"), None);
+        assert_eq!(panel_width(0), DASHBOARD_WIDTH);
+    }
+
+    fn variable(name: &str, depends_on: &[&str], filter: Option<&str>) -> VariableList {
+        VariableList {
+            name: name.to_string(),
+            query_data: filter.map(|value| QueryData {
+                filter: Some(vec![Filters {
+                    name: Some("f".to_string()),
+                    operator: Some("=".to_string()),
+                    value: value.to_string(),
+                }]),
+                ..Default::default()
+            }),
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_dependency_levels() {
+        let vars = Variables {
+            list: vec![
+                variable("pod", &[], Some("${namespace}")),
+                variable("namespace", &["cluster"], None),
+                variable("cluster", &[], Some("$unknown")),
+                variable("env", &[], None),
+            ],
+            show_dynamic_filters: None,
+        };
+        let levels = vars
+            .dependency_levels()
+            .unwrap()
+            .into_iter()
+            .map(|l| l.into_iter().map(|v| v.name.as_str()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            levels,
+            vec![vec!["cluster", "env"], vec!["namespace"], vec!["pod"]]
+        );
+
+        let vars = Variables {
+            list: vec![variable("a", &["b"], None), variable("b", &[], Some("$a"))],
+            show_dynamic_filters: None,
+        };
+        assert!(vars.dependency_levels().is_err());
+        let vars = Variables {
+            list: vec![variable("a", &["b"], None)],
+            show_dynamic_filters: None,
+        };
+        assert!(vars.dependency_levels().is_err());
+    }
+
+    #[test]
+    fn test_threshold_color() {
+        let config: PanelConfig = crate::utils::json::from_str(
+            r##"{"show_legends": true, "legends_position": null, "base_map": null, "map_view": null,
+                "thresholds": [
+                    {"value": 0, "color": "#00ff00"},
+                    {"value": 80, "color": "#ff0000"},
+                    {"value": 50, "color": "#ffaa00", "mode": "percentage"}
+                ]}"##,
+        )
+        .unwrap();
+        let thresholds = config.thresholds();
+        assert_eq!(thresholds[2].mode, ThresholdMode::Percentage);
+        assert_eq!(threshold_color(thresholds, -1.0, 0.0, 200.0), None);
+        assert_eq!(
+            threshold_color(thresholds, 10.0, 0.0, 200.0),
+            Some("#00ff00")
+        );
+        assert_eq!(
+            threshold_color(thresholds, 90.0, 0.0, 200.0),
+            Some("#ff0000")
+        );
+        assert_eq!(
+            threshold_color(thresholds, 100.0, 0.0, 200.0),
+            Some("#ffaa00")
+        );
+        assert_eq!(
+            threshold_color(thresholds, 60.0, 0.0, 100.0),
+            Some("#ffaa00")
+        );
+    }
+
+    #[test]
+    fn test_threshold_query() {
+        let config: PanelConfig = crate::utils::json::from_str(
+            r##"{"show_legends": true, "legends_position": null, "base_map": null, "map_view": null,
+                "threshold_query": {
+                    "query": "SELECT approx_percentile_cont(latency, 0.95) AS p95 FROM logs",
+                    "relative_time_period": "7d",
+                    "steps": [
+                        {"field": "p95", "color": "#ff0000"},
+                        {"field": "missing", "color": "#ffaa00"}
+                    ]
+                }}"##,
+        )
+        .unwrap();
+        let query = config.threshold_query().unwrap();
+        assert_eq!(query.stream_type, StreamType::Logs);
+        let week = 7 * 86_400_000_000;
+        assert_eq!(query.time_range(10, week + 100), (100, week + 100));
+
+        let thresholds = query.thresholds(&json::json!({"p95": "250.5"}));
+        assert_eq!(thresholds.len(), 1);
+        assert_eq!(thresholds[0].value.into_inner(), 250.5);
+        assert_eq!(
+            threshold_color(&thresholds, 300.0, 0.0, 0.0),
+            Some("#ff0000")
+        );
+    }
+}
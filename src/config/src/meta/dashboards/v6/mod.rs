@@ -0,0 +1,213 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::hash::{Hash, Hasher};
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// v6 only changes the panels, the other types are the same as in v5
+pub use super::v5::{
+    AggregationFunc, AxisArg, AxisItem, Background, BackgroundValue, CustomFieldsOption,
+    DateTimeOptions, FilterCondition, Filters, FormatOptions, GroupType, HavingConditions, Layout,
+    PanelConfig, PanelFields, PanelFilter, Query, QueryConfig, QueryData, VariableList, Variables,
+};
+use super::{datetime_now, transformations::Transformation, v5};
+
+#[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Dashboard {
+    version: i32,
+    #[serde(default)]
+    pub dashboard_id: String,
+    pub title: String,
+    pub description: String,
+    #[serde(default)]
+    pub role: String,
+    #[serde(default)]
+    pub owner: String,
+    #[serde(default = "datetime_now")]
+    #[schema(value_type = String, format = DateTime)]
+    pub created: DateTime<FixedOffset>,
+    #[serde(default)]
+    pub tabs: Vec<Tab>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables: Option<Variables>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_datetime_duration: Option<DateTimeOptions>,
+    #[serde(default, skip_serializing)]
+    pub updated_at: i64,
+}
+
+impl From<Dashboard> for super::Dashboard {
+    fn from(value: Dashboard) -> Self {
+        let version: i32 = 6;
+
+        let mut hasher = std::hash::DefaultHasher::new();
+        hasher.write_i32(version);
+        value.hash(&mut hasher);
+        let hash = hasher.finish().to_string();
+        let updated_at = value.updated_at;
+
+        Self {
+            v1: None,
+            v2: None,
+            v3: None,
+            v4: None,
+            v5: None,
+            v6: Some(value),
+            version,
+            hash,
+            updated_at,
+        }
+    }
+}
+
+impl From<v5::Dashboard> for Dashboard {
+    fn from(value: v5::Dashboard) -> Self {
+        Self {
+            version: 6,
+            dashboard_id: value.dashboard_id,
+            title: value.title,
+            description: value.description,
+            role: value.role,
+            owner: value.owner,
+            created: value.created,
+            tabs: value.tabs.into_iter().map(Tab::from).collect(),
+            variables: value.variables,
+            default_datetime_duration: value.default_datetime_duration,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Tab {
+    pub tab_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub panels: Vec<Panel>,
+}
+
+impl From<v5::Tab> for Tab {
+    fn from(value: v5::Tab) -> Self {
+        Self {
+            tab_id: value.tab_id,
+            name: value.name,
+            panels: value.panels.into_iter().map(Panel::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Panel {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub typ: String,
+    pub title: String,
+    pub description: String,
+    pub config: PanelConfig,
+    #[serde(default)]
+    pub query_type: String,
+    pub queries: Vec<Query>,
+    pub layout: Layout,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html_content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub markdown_content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_chart_content: Option<String>,
+    /// Relative or absolute time range of the panel, replacing the time range of the dashboard.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_override: Option<DateTimeOptions>,
+    /// Library panel providing the definition of this panel, the embedded definition is the
+    /// last resolved copy and is used when the library panel is deleted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub library_panel_id: Option<String>,
+    /// Steps reshaping the rows of the queries on the server before they are returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transformations: Option<Vec<Transformation>>,
+}
+
+impl From<v5::Panel> for Panel {
+    fn from(value: v5::Panel) -> Self {
+        Self {
+            id: value.id,
+            typ: value.typ,
+            title: value.title,
+            description: value.description,
+            config: value.config,
+            query_type: value.query_type,
+            queries: value.queries,
+            layout: value.layout,
+            html_content: value.html_content,
+            markdown_content: value.markdown_content,
+            custom_chart_content: value.custom_chart_content,
+            time_override: None,
+            library_panel_id: None,
+            transformations: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::json;
+
+    const PANEL: &str = r#"{
+        "id": "Panel_ID1",
+        "type": "line",
+        "title": "errors",
+        "description": "",
+        "config": {"show_legends": true, "legends_position": null, "base_map": null, "map_view": null},
+        "queryType": "sql",
+        "queries": [],
+        "layout": {"x": 0, "y": 0, "w": 12, "h": 9, "i": 1}
+    }"#;
+
+    #[test]
+    fn test_migrate_v5() {
+        let v5: v5::Dashboard = json::from_str(&format!(
+            r#"{{"version": 5, "title": "d1", "description": "", "tabs": [{{"tabId": "default", "name": "Default", "panels": [{PANEL}]}}]}}"#
+        ))
+        .unwrap();
+        let v6 = Dashboard::from(v5);
+        assert_eq!(v6.version, 6);
+        assert_eq!(v6.title, "d1");
+        assert_eq!(v6.tabs[0].panels[0].id, "Panel_ID1");
+        assert!(v6.tabs[0].panels[0].time_override.is_none());
+
+        let dashboard: super::super::Dashboard = v6.into();
+        assert_eq!(dashboard.version, 6);
+        assert_eq!(dashboard.title(), Some("d1"));
+    }
+
+    #[test]
+    fn test_panel_time_override() {
+        let mut panel: json::Value = json::from_str(PANEL).unwrap();
+        panel["timeOverride"] = json::json!({"type": "relative", "relativeTimePeriod": "7d"});
+        let panel: Panel = json::from_value(panel).unwrap();
+        let time_override = panel.time_override.as_ref().unwrap();
+        assert_eq!(time_override.typee, "relative");
+        assert_eq!(time_override.relative_time_period.as_deref(), Some("7d"));
+
+        let value = json::to_value(&panel).unwrap();
+        assert_eq!(value["timeOverride"]["relativeTimePeriod"], "7d");
+    }
+}
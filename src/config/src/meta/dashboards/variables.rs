@@ -0,0 +1,58 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::utils::json;
+
+/// Request to resolve the values of the variables of a dashboard in one call.
+#[derive(Clone, Debug, Default, Deserialize, ToSchema)]
+pub struct ResolveVariablesRequest {
+    /// Start of the time range in microseconds.
+    pub start_time: i64,
+    /// End of the time range in microseconds.
+    pub end_time: i64,
+    /// Values selected by the user by variable name, a list for multi-select variables. Values
+    /// missing from the resolved options are replaced by the default of the variable.
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub values: HashMap<String, json::Value>,
+    /// Width in pixels of the panels, sizes the automatic step of `interval` variables.
+    /// Defaults to the width of the dashboard.
+    #[serde(default)]
+    pub width: Option<u32>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct ResolvedVariable {
+    pub name: String,
+    /// Values the variable can take, empty for constants and textboxes.
+    pub options: Vec<String>,
+    /// Selected value, a list for multi-select variables.
+    #[schema(value_type = Object)]
+    pub value: json::Value,
+    /// Error of the query of the options, the value is the default of the variable then.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct ResolveVariablesResponse {
+    /// Variables in the order they were resolved, a variable comes after the variables it
+    /// depends on.
+    pub variables: Vec<ResolvedVariable>,
+}
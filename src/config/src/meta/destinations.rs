@@ -39,7 +39,13 @@ impl Destination {
 pub enum Module {
     Alert {
         template: String,
+        /// Template of the notification sent when the alert stops firing, no notification is
+        /// sent on recovery without one.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        resolved_template: Option<String>,
         destination_type: DestinationType,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        grouping: Option<NotificationGrouping>,
     },
     Pipeline {
         endpoint: Endpoint,
@@ -56,6 +62,51 @@ impl std::fmt::Display for Module {
     }
 }
 
+/// Batches the notifications of alerts firing together on a destination into one message,
+/// similar to Alertmanager grouping.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct NotificationGrouping {
+    /// Alert fields (`alert_name`, `stream_name`, `stream_type`) or columns of the firing rows
+    /// the notifications are grouped by. All the alerts of the destination share one group
+    /// when empty.
+    #[serde(default)]
+    pub group_by: Vec<String>,
+    /// Seconds to buffer the alerts of a new group before sending its first notification.
+    #[serde(default)]
+    pub group_wait: i64,
+    /// Seconds before an alert already sent for a group is notified again.
+    #[serde(default)]
+    pub repeat_interval: i64,
+}
+
+impl NotificationGrouping {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.group_wait < 0 {
+            return Err("group_wait must not be negative".to_string());
+        }
+        if self.repeat_interval < 0 {
+            return Err("repeat_interval must not be negative".to_string());
+        }
+        if self.group_by.iter().any(|v| v.trim().is_empty()) {
+            return Err("group_by must not contain empty labels".to_string());
+        }
+        Ok(())
+    }
+
+    /// Returns the key of the group an alert belongs to, `label_value` looks up the value of
+    /// a group_by label for the alert.
+    pub fn group_key<F>(&self, label_value: F) -> String
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        self.group_by
+            .iter()
+            .map(|label| format!("{label}={}", label_value(label).unwrap_or_default()))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
 #[derive(Serialize, Debug, Deserialize, Clone)]
 #[serde(tag = "type")]
 #[serde(rename_all = "snake_case")]
@@ -63,6 +114,25 @@ pub enum DestinationType {
     Http(Endpoint),
     Email(Email),
     Sns(AwsSns),
+    #[serde(rename = "pagerduty")]
+    PagerDuty(PagerDuty),
+    #[serde(rename = "opsgenie")]
+    Opsgenie(Opsgenie),
+    #[serde(rename = "victorops")]
+    VictorOps(VictorOps),
+}
+
+impl DestinationType {
+    /// Whether the destination opens incidents which are kept per alert instead of receiving
+    /// the rendered notifications as messages.
+    pub fn is_incident(&self) -> bool {
+        matches!(
+            self,
+            DestinationType::PagerDuty(_)
+                | DestinationType::Opsgenie(_)
+                | DestinationType::VictorOps(_)
+        )
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -91,6 +161,141 @@ pub struct AwsSns {
     pub aws_region: String,
 }
 
+/// PagerDuty Events API v2 integration, alerts trigger incidents which are resolved when the
+/// alert recovers.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PagerDuty {
+    /// Integration key of the PagerDuty service
+    pub routing_key: String,
+    /// Severity of the incidents of the alerts without a `severity` context attribute
+    #[serde(default)]
+    pub severity: PagerDutySeverity,
+    /// Events API endpoint, defaults to `https://events.pagerduty.com/v2/enqueue`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub url: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PagerDutySeverity {
+    Critical,
+    #[default]
+    Error,
+    Warning,
+    Info,
+}
+
+/// Maps a severity of an alert such as `critical`, `high`, `P3` or `low` to a level from 1, the
+/// most severe, to 5.
+fn severity_level(severity: &str) -> Option<u8> {
+    match severity.trim().to_lowercase().as_str() {
+        "critical" | "fatal" | "emergency" | "p1" => Some(1),
+        "error" | "high" | "major" | "p2" => Some(2),
+        "warning" | "warn" | "medium" | "minor" | "p3" => Some(3),
+        "info" | "informational" | "low" | "p4" => Some(4),
+        "p5" | "debug" => Some(5),
+        _ => None,
+    }
+}
+
+impl PagerDutySeverity {
+    /// Maps a severity of an alert such as `critical`, `high`, `P3` or `low` to the PagerDuty
+    /// severity.
+    pub fn from_alert_severity(severity: &str) -> Option<Self> {
+        severity_level(severity).map(|level| match level {
+            1 => Self::Critical,
+            2 => Self::Error,
+            3 => Self::Warning,
+            _ => Self::Info,
+        })
+    }
+}
+
+/// Opsgenie Alert API integration, alerts create Opsgenie alerts which are closed when the alert
+/// recovers unless `auto_close` is disabled.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Opsgenie {
+    /// Key of the Opsgenie API integration
+    pub api_key: String,
+    /// Priority of the alerts without a `severity` context attribute
+    #[serde(default)]
+    pub priority: OpsgeniePriority,
+    #[serde(default = "default_auto_close")]
+    pub auto_close: bool,
+    /// API endpoint, defaults to `https://api.opsgenie.com`, EU accounts use
+    /// `https://api.eu.opsgenie.com`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub url: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum OpsgeniePriority {
+    P1,
+    P2,
+    #[default]
+    P3,
+    P4,
+    P5,
+}
+
+impl OpsgeniePriority {
+    /// Maps a severity of an alert such as `critical`, `high`, `P3` or `low` to the Opsgenie
+    /// priority.
+    pub fn from_alert_severity(severity: &str) -> Option<Self> {
+        severity_level(severity).map(|level| match level {
+            1 => Self::P1,
+            2 => Self::P2,
+            3 => Self::P3,
+            4 => Self::P4,
+            _ => Self::P5,
+        })
+    }
+}
+
+/// Splunk On-Call (VictorOps) REST endpoint integration, alerts open incidents which are
+/// recovered when the alert recovers unless `auto_close` is disabled.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VictorOps {
+    /// Key of the REST endpoint integration
+    pub api_key: String,
+    /// Routing key selecting the team paged by the incidents
+    pub routing_key: String,
+    /// Message type of the alerts without a `severity` context attribute
+    #[serde(default)]
+    pub message_type: VictorOpsMessageType,
+    #[serde(default = "default_auto_close")]
+    pub auto_close: bool,
+    /// REST endpoint, defaults to
+    /// `https://alert.victorops.com/integrations/generic/20131114/alert`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub url: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum VictorOpsMessageType {
+    #[default]
+    Critical,
+    Warning,
+    Info,
+}
+
+impl VictorOpsMessageType {
+    /// Maps a severity of an alert such as `critical`, `high`, `P3` or `low` to the VictorOps
+    /// message type.
+    pub fn from_alert_severity(severity: &str) -> Option<Self> {
+        severity_level(severity).map(|level| match level {
+            1 | 2 => Self::Critical,
+            3 => Self::Warning,
+            _ => Self::Info,
+        })
+    }
+}
+
+fn default_auto_close() -> bool {
+    true
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
 pub enum HTTPType {
     #[default]
@@ -152,3 +357,85 @@ impl fmt::Display for TemplateType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_grouping_key() {
+        let grouping = NotificationGrouping {
+            group_by: vec!["alert_name".to_string(), "host".to_string()],
+            group_wait: 30,
+            repeat_interval: 3600,
+        };
+        assert!(grouping.validate().is_ok());
+        let key = grouping.group_key(|label| match label {
+            "alert_name" => Some("cpu_high".to_string()),
+            _ => None,
+        });
+        assert_eq!(key, "alert_name=cpu_high,host=");
+        assert_eq!(NotificationGrouping::default().group_key(|_| None), "");
+
+        let grouping = NotificationGrouping {
+            group_wait: -1,
+            ..Default::default()
+        };
+        assert!(grouping.validate().is_err());
+    }
+
+    #[test]
+    fn test_pagerduty_destination_type() {
+        let dest_type: DestinationType = crate::utils::json::from_str(
+            r#"{"type":"pagerduty","routing_key":"R0UT1NG","severity":"warning"}"#,
+        )
+        .unwrap();
+        let DestinationType::PagerDuty(pagerduty) = dest_type else {
+            panic!("expected a pagerduty destination");
+        };
+        assert_eq!(pagerduty.routing_key, "R0UT1NG");
+        assert_eq!(pagerduty.severity, PagerDutySeverity::Warning);
+        assert!(pagerduty.url.is_empty());
+
+        assert_eq!(
+            PagerDutySeverity::from_alert_severity("P1"),
+            Some(PagerDutySeverity::Critical)
+        );
+        assert_eq!(
+            PagerDutySeverity::from_alert_severity(" low "),
+            Some(PagerDutySeverity::Info)
+        );
+        assert_eq!(PagerDutySeverity::from_alert_severity("unknown"), None);
+    }
+
+    #[test]
+    fn test_opsgenie_and_victorops_destination_types() {
+        let dest_type: DestinationType =
+            crate::utils::json::from_str(r#"{"type":"opsgenie","api_key":"K3Y","priority":"P2"}"#)
+                .unwrap();
+        let DestinationType::Opsgenie(opsgenie) = dest_type else {
+            panic!("expected an opsgenie destination");
+        };
+        assert_eq!(opsgenie.priority, OpsgeniePriority::P2);
+        assert!(opsgenie.auto_close);
+
+        let dest_type: DestinationType = crate::utils::json::from_str(
+            r#"{"type":"victorops","api_key":"K3Y","routing_key":"ops","auto_close":false}"#,
+        )
+        .unwrap();
+        let DestinationType::VictorOps(victorops) = dest_type else {
+            panic!("expected a victorops destination");
+        };
+        assert_eq!(victorops.message_type, VictorOpsMessageType::Critical);
+        assert!(!victorops.auto_close);
+
+        assert_eq!(
+            OpsgeniePriority::from_alert_severity("low"),
+            Some(OpsgeniePriority::P4)
+        );
+        assert_eq!(
+            VictorOpsMessageType::from_alert_severity("high"),
+            Some(VictorOpsMessageType::Critical)
+        );
+    }
+}
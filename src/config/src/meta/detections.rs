@@ -0,0 +1,125 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::fmt;
+
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Name of the logs stream every detection writes its findings into.
+pub const FINDINGS_STREAM: &str = "_findings";
+
+/// Severity of a detection, follows the Sigma `level` values.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Informational,
+    Low,
+    #[default]
+    Medium,
+    High,
+    Critical,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Informational => write!(f, "informational"),
+            Severity::Low => write!(f, "low"),
+            Severity::Medium => write!(f, "medium"),
+            Severity::High => write!(f, "high"),
+            Severity::Critical => write!(f, "critical"),
+        }
+    }
+}
+
+impl From<&str> for Severity {
+    fn from(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "informational" | "info" => Severity::Informational,
+            "low" => Severity::Low,
+            "high" => Severity::High,
+            "critical" => Severity::Critical,
+            _ => Severity::Medium,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct Detection {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub org_id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Logs stream the rule is evaluated against.
+    pub stream_name: String,
+    /// The Sigma rule in YAML.
+    pub rule: String,
+    /// Maps Sigma field names to the field names of the stream.
+    #[serde(default)]
+    pub field_mapping: HashMap<String, String>,
+    #[serde(default)]
+    pub severity: Severity,
+    /// MITRE ATT&CK tags of the rule, e.g. `attack.t1110`.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// The SQL query the rule compiles to.
+    #[serde(default)]
+    pub sql: String,
+    /// Evaluation interval in seconds, each run looks back over the same window.
+    #[serde(default = "default_frequency")]
+    pub frequency: i64,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// End of the window of the last evaluation, in microseconds.
+    #[serde(default)]
+    pub last_evaluated_at: i64,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+fn default_frequency() -> i64 {
+    300
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct DetectionList {
+    pub list: Vec<Detection>,
+}
+
+/// Request body to import a Sigma rule as a detection.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct DetectionRequest {
+    pub stream_name: String,
+    /// The Sigma rule in YAML.
+    pub rule: String,
+    #[serde(default)]
+    pub field_mapping: HashMap<String, String>,
+    #[serde(default)]
+    pub frequency: Option<i64>,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+}
@@ -0,0 +1,60 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::utils::json::{Map, Value};
+
+/// A custom grok pattern of an org, referenced as `%{NAME}` from other patterns. Custom
+/// patterns take precedence over the built-in library.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct GrokPattern {
+    #[serde(default)]
+    pub name: String,
+    pub pattern: String,
+    #[serde(default)]
+    pub description: String,
+    /// Whether this is a pattern of the built-in library, only set when listing.
+    #[serde(default)]
+    pub builtin: bool,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+impl GrokPattern {
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GrokPatternList {
+    pub list: Vec<GrokPattern>,
+}
+
+/// Request to try a grok expression against sample lines.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GrokTestRequest {
+    pub pattern: String,
+    pub samples: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GrokTestResponse {
+    /// The extracted fields of each sample, `null` if the sample didn't match.
+    #[schema(value_type = Vec<Object>)]
+    pub results: Vec<Option<Map<String, Value>>>,
+}
@@ -0,0 +1,192 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(style = Form, parameter_in = Query)]
+#[serde(rename_all = "snake_case")]
+#[into_params(rename_all = "snake_case")]
+pub struct IncidentTimelineQuery {
+    /// Time in microseconds
+    pub start_time: i64,
+    /// Time in microseconds
+    pub end_time: i64,
+    /// Commas separated list of services, matched against `service:` annotation tags, alert
+    /// names and audited request paths
+    pub services: Option<String>,
+    /// Commas separated list of log streams, used for alerts, audit events and anomalies
+    pub streams: Option<String>,
+    /// Output format, `json` (default) or `markdown`
+    pub format: Option<String>,
+}
+
+impl IncidentTimelineQuery {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.start_time >= self.end_time {
+            return Err("start time must be less than end time".to_string());
+        }
+        if !matches!(
+            self.format.as_deref(),
+            None | Some("json") | Some("markdown")
+        ) {
+            return Err("format must be json or markdown".to_string());
+        }
+        if self.get_streams().iter().any(|s| s.contains('"')) {
+            return Err("invalid stream name".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn get_services(&self) -> Vec<String> {
+        split_list(self.services.as_deref())
+    }
+
+    pub fn get_streams(&self) -> Vec<String> {
+        split_list(self.streams.as_deref())
+    }
+
+    pub fn is_markdown(&self) -> bool {
+        self.format.as_deref() == Some("markdown")
+    }
+}
+
+fn split_list(s: Option<&str>) -> Vec<String> {
+    s.unwrap_or_default()
+        .split(',')
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(|v| v.to_string())
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineEventKind {
+    Alert,
+    Annotation,
+    ConfigChange,
+    Anomaly,
+}
+
+impl std::fmt::Display for TimelineEventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimelineEventKind::Alert => write!(f, "alert"),
+            TimelineEventKind::Annotation => write!(f, "annotation"),
+            TimelineEventKind::ConfigChange => write!(f, "config change"),
+            TimelineEventKind::Anomaly => write!(f, "anomaly"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TimelineEvent {
+    /// Time in microseconds
+    pub timestamp: i64,
+    /// Time in microseconds, for events covering a range
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<i64>,
+    pub kind: TimelineEventKind,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct IncidentTimeline {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub events: Vec<TimelineEvent>,
+    /// Sources which could not be read, the timeline is partial if not empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+impl IncidentTimeline {
+    /// Renders the timeline as a Markdown document for postmortems.
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!(
+            "# Incident timeline\n\n{} - {}\n\n",
+            format_time(self.start_time),
+            format_time(self.end_time)
+        );
+        if self.events.is_empty() {
+            md.push_str("No events found.\n");
+        } else {
+            md.push_str("| Time (UTC) | Type | Event | Details |\n|---|---|---|---|\n");
+            for event in self.events.iter() {
+                let title = match event.link.as_ref() {
+                    Some(link) => format!("[{}]({link})", escape_cell(&event.title)),
+                    None => escape_cell(&event.title),
+                };
+                md.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    format_time(event.timestamp),
+                    event.kind,
+                    title,
+                    escape_cell(event.details.as_deref().unwrap_or_default())
+                ));
+            }
+        }
+        if !self.errors.is_empty() {
+            md.push_str("\n**Incomplete timeline:**\n\n");
+            for error in self.errors.iter() {
+                md.push_str(&format!("- {}\n", escape_cell(error)));
+            }
+        }
+        md
+    }
+}
+
+fn format_time(micros: i64) -> String {
+    DateTime::from_timestamp_micros(micros)
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+fn escape_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeline_to_markdown() {
+        let timeline = IncidentTimeline {
+            start_time: 1_700_000_000_000_000,
+            end_time: 1_700_003_600_000_000,
+            events: vec![TimelineEvent {
+                timestamp: 1_700_000_060_000_000,
+                end_time: None,
+                kind: TimelineEventKind::ConfigChange,
+                title: "PUT /api/default/streams/app|x".to_string(),
+                details: Some("by root@example.com".to_string()),
+                link: None,
+            }],
+            errors: vec![],
+        };
+        let md = timeline.to_markdown();
+        assert!(md.contains("2023-11-14 22:13:20 - 2023-11-14 23:13:20"));
+        assert!(md.contains(
+            "| 2023-11-14 22:14:20 | config change | PUT /api/default/streams/app\\|x | by root@example.com |"
+        ));
+    }
+}
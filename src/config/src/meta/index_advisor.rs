@@ -0,0 +1,134 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The index advisor looks for the fields the slow queries of a stream filter on which the
+//! stream settings don't index, and recommends a bloom filter, a partition key or a full text
+//! search index for them.
+
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::meta::stream::StreamType;
+
+/// A query which took longer than `ZO_INDEX_ADVISOR_SLOW_QUERY_THRESHOLD`, with the fields its
+/// predicates filter on.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct SlowQuery {
+    pub trace_id: String,
+    pub stream_type: StreamType,
+    pub stream_name: String,
+    pub user_id: Option<String>,
+    pub sql: String,
+    /// (milliseconds)
+    pub took: i64,
+    /// `field = value` and `field IN (...)` predicates, as (field, value)
+    pub equal_items: Vec<(String, String)>,
+    /// Fields searched for a substring, e.g. `field LIKE '%value%'` or `str_match(field, 'v')`
+    pub text_search_fields: Vec<String>,
+    pub created_at: i64,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, ToSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum RecommendationKind {
+    /// Add the field to the `bloom_filter_fields` of the stream
+    BloomFilter,
+    /// Add the field to the `partition_keys` of the stream
+    PartitionKey,
+    /// Add the field to the `full_text_search_keys` of the stream
+    FullTextSearch,
+}
+
+impl fmt::Display for RecommendationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecommendationKind::BloomFilter => write!(f, "bloom_filter"),
+            RecommendationKind::PartitionKey => write!(f, "partition_key"),
+            RecommendationKind::FullTextSearch => write!(f, "full_text_search"),
+        }
+    }
+}
+
+impl FromStr for RecommendationKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bloom_filter" => Ok(RecommendationKind::BloomFilter),
+            "partition_key" => Ok(RecommendationKind::PartitionKey),
+            "full_text_search" => Ok(RecommendationKind::FullTextSearch),
+            _ => Err(format!("invalid recommendation kind: {s}")),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecommendationStatus {
+    #[default]
+    Open,
+    /// Approved and applied to the stream settings
+    Applied,
+    /// Not recommended again for the stream
+    Dismissed,
+}
+
+impl fmt::Display for RecommendationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecommendationStatus::Open => write!(f, "open"),
+            RecommendationStatus::Applied => write!(f, "applied"),
+            RecommendationStatus::Dismissed => write!(f, "dismissed"),
+        }
+    }
+}
+
+impl FromStr for RecommendationStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open" => Ok(RecommendationStatus::Open),
+            "applied" => Ok(RecommendationStatus::Applied),
+            "dismissed" => Ok(RecommendationStatus::Dismissed),
+            _ => Err(format!("invalid recommendation status: {s}")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct IndexRecommendation {
+    pub id: String,
+    pub stream_type: StreamType,
+    pub stream_name: String,
+    pub kind: RecommendationKind,
+    pub field: String,
+    /// Why the setting is recommended, for the user reviewing it
+    pub reason: String,
+    /// Number of slow queries filtering on the field during the analyzed period
+    pub slow_queries: i64,
+    /// Number of queries referencing the field during the analyzed period
+    pub queries: i64,
+    pub status: RecommendationStatus,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct IndexRecommendationList {
+    pub list: Vec<IndexRecommendation>,
+}
@@ -0,0 +1,133 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::meta::user::UserRole;
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct Invitation {
+    pub token: String,
+    pub org_id: String,
+    pub email: String,
+    pub role: UserRole,
+    /// Empty for invitations created through self signup.
+    #[serde(default)]
+    pub invited_by: String,
+    pub created_at: i64,
+    pub expires_at: i64,
+}
+
+impl Invitation {
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at <= now
+    }
+}
+
+/// Pending invitation as listed to the admins. The token is left out, whoever holds it can
+/// accept the invitation.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct InvitationInfo {
+    pub org_id: String,
+    pub email: String,
+    pub role: UserRole,
+    pub invited_by: String,
+    pub created_at: i64,
+    pub expires_at: i64,
+}
+
+impl From<Invitation> for InvitationInfo {
+    fn from(invitation: Invitation) -> Self {
+        Self {
+            org_id: invitation.org_id,
+            email: invitation.email,
+            role: invitation.role,
+            invited_by: invitation.invited_by,
+            created_at: invitation.created_at,
+            expires_at: invitation.expires_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, ToSchema)]
+pub struct CreateInvitationsRequest {
+    pub emails: Vec<String>,
+    pub role: UserRole,
+}
+
+#[derive(Clone, Debug, Default, Serialize, ToSchema)]
+pub struct CreateInvitationsResponse {
+    pub invited: Vec<String>,
+    pub existing_members: Vec<String>,
+    pub invalid_emails: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, ToSchema)]
+pub struct AcceptInvitationRequest {
+    #[serde(default)]
+    pub first_name: String,
+    #[serde(default)]
+    pub last_name: String,
+    /// Required when the invited email doesn't belong to an existing user.
+    #[serde(default)]
+    pub password: String,
+}
+
+#[derive(Clone, Debug, Deserialize, ToSchema)]
+pub struct SignupRequest {
+    pub email: String,
+}
+
+/// Checks the domain of the email against a comma separated allowlist.
+pub fn is_domain_allowed(email: &str, allowed_domains: &str) -> bool {
+    let Some((_, domain)) = email.rsplit_once('@') else {
+        return false;
+    };
+    allowed_domains
+        .split(',')
+        .map(|d| d.trim().trim_start_matches('@'))
+        .filter(|d| !d.is_empty())
+        .any(|d| d.eq_ignore_ascii_case(domain))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_domain_allowed() {
+        assert!(is_domain_allowed("a@example.com", "example.com"));
+        assert!(is_domain_allowed("a@Example.COM", "foo.io, @example.com"));
+        assert!(!is_domain_allowed("a@sub.example.com", "example.com"));
+        assert!(!is_domain_allowed("a@example.com", ""));
+        assert!(!is_domain_allowed("example.com", "example.com"));
+    }
+
+    #[test]
+    fn test_invitation_expired() {
+        let inv = Invitation {
+            token: "t".to_string(),
+            org_id: "default".to_string(),
+            email: "a@example.com".to_string(),
+            role: UserRole::Viewer,
+            invited_by: "".to_string(),
+            created_at: 0,
+            expires_at: 10,
+        };
+        assert!(!inv.is_expired(9));
+        assert!(inv.is_expired(10));
+    }
+}
@@ -0,0 +1,240 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    meta::alerts::Operator,
+    utils::json::{Map, Value},
+};
+
+/// Most label combinations kept per rule, new combinations beyond it are dropped.
+pub const MAX_SERIES_PER_RULE: usize = 10_000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LogMetricType {
+    #[default]
+    Counter,
+    Histogram,
+}
+
+impl std::fmt::Display for LogMetricType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogMetricType::Counter => write!(f, "counter"),
+            LogMetricType::Histogram => write!(f, "histogram"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct LogMetricCondition {
+    pub field: String,
+    #[serde(default)]
+    pub operator: Operator,
+    pub value: String,
+}
+
+impl LogMetricCondition {
+    /// Numbers are compared numerically when both sides are numbers, anything else as string.
+    /// Names of the metrics streams written by the rule.
+    pub fn metric_names(&self) -> Vec<String> {
+        match self.metric_type {
+            LogMetricType::Counter => vec![self.name.clone()],
+            LogMetricType::Histogram => ["_bucket", "_sum", "_count"]
+                .iter()
+                .map(|suffix| format!("{}{suffix}", self.name))
+                .collect(),
+        }
+    }
+
+    pub fn matches(&self, record: &Map<String, Value>) -> bool {
+        let Some(value) = record.get(&self.field) else {
+            return self.operator == Operator::NotEqualTo || self.operator == Operator::NotContains;
+        };
+        let value = match value {
+            Value::String(s) => s.clone(),
+            Value::Null => return false,
+            v => v.to_string(),
+        };
+        let ordering = match (value.parse::<f64>(), self.value.parse::<f64>()) {
+            (Ok(a), Ok(b)) => a.partial_cmp(&b),
+            _ => Some(value.as_str().cmp(self.value.as_str())),
+        };
+        match self.operator {
+            Operator::EqualTo => ordering.is_some_and(|o| o.is_eq()),
+            Operator::NotEqualTo => ordering.is_none_or(|o| o.is_ne()),
+            Operator::GreaterThan => ordering.is_some_and(|o| o.is_gt()),
+            Operator::GreaterThanEquals => ordering.is_some_and(|o| o.is_ge()),
+            Operator::LessThan => ordering.is_some_and(|o| o.is_lt()),
+            Operator::LessThanEquals => ordering.is_some_and(|o| o.is_le()),
+            Operator::Contains => value.contains(&self.value),
+            Operator::NotContains => !value.contains(&self.value),
+        }
+    }
+}
+
+/// Computes a metric from the matching records of a log stream at ingest, so the metric can be
+/// kept and alerted on without keeping the raw logs.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct LogMetricRule {
+    /// Name of the metrics stream written to
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Source log stream
+    pub stream: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// All conditions must hold for a record to be counted
+    #[serde(default)]
+    pub conditions: Vec<LogMetricCondition>,
+    #[serde(default)]
+    pub metric_type: LogMetricType,
+    /// Numeric field observed by histograms, counters add it up instead of counting records
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_field: Option<String>,
+    /// Fields whose values become labels of the metric
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Upper bounds of the histogram buckets
+    #[serde(default = "default_buckets")]
+    pub buckets: Vec<f64>,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_buckets() -> Vec<f64> {
+    vec![
+        0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+    ]
+}
+
+impl LogMetricRule {
+    pub fn is_valid_name(name: &str) -> bool {
+        let mut chars = name.chars();
+        chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !Self::is_valid_name(&self.name) {
+            return Err(format!("invalid metric name: {}", self.name));
+        }
+        if self.stream.is_empty() {
+            return Err("stream cannot be empty".to_string());
+        }
+        if self.conditions.iter().any(|c| c.field.is_empty()) {
+            return Err("condition field cannot be empty".to_string());
+        }
+        if let Some(label) = self
+            .labels
+            .iter()
+            .find(|l| !Self::is_valid_name(l) || l.starts_with("__") || *l == "le")
+        {
+            return Err(format!("invalid label: {label}"));
+        }
+        if self.metric_type == LogMetricType::Histogram {
+            if self.value_field.is_none() {
+                return Err("histogram needs a value field".to_string());
+            }
+            if self.buckets.is_empty() || self.buckets.windows(2).any(|w| w[0] >= w[1]) {
+                return Err("histogram buckets must be increasing".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn matches(&self, record: &Map<String, Value>) -> bool {
+        self.conditions.iter().all(|c| c.matches(record))
+    }
+
+    /// Value observed for the record, `None` if the value field is missing or not numeric.
+    pub fn value(&self, record: &Map<String, Value>) -> Option<f64> {
+        let Some(field) = self.value_field.as_ref() else {
+            return Some(1.0);
+        };
+        match record.get(field)? {
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Label values of the record, a missing field gives an empty value.
+    pub fn label_values(&self, record: &Map<String, Value>) -> Vec<(String, String)> {
+        self.labels
+            .iter()
+            .map(|label| {
+                let value = match record.get(label) {
+                    Some(Value::String(s)) => s.clone(),
+                    Some(Value::Null) | None => String::new(),
+                    Some(v) => v.to_string(),
+                };
+                (label.clone(), value)
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct LogMetricRuleList {
+    pub list: Vec<LogMetricRule>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::json;
+
+    #[test]
+    fn test_log_metric_rule() {
+        let rule: LogMetricRule = json::from_str(
+            r#"{"name":"http_5xx_total","stream":"nginx","conditions":[{"field":"status","operator":">=","value":"500"}],"labels":["service"]}"#,
+        )
+        .unwrap();
+        assert!(rule.validate().is_ok());
+        assert!(rule.enabled);
+
+        let record = json::json!({"status": 503, "service": "api"});
+        let record = record.as_object().unwrap();
+        assert!(rule.matches(record));
+        assert_eq!(rule.value(record), Some(1.0));
+        assert_eq!(
+            rule.label_values(record),
+            vec![("service".to_string(), "api".to_string())]
+        );
+        assert!(!rule.matches(json::json!({"status": "200"}).as_object().unwrap()));
+        assert!(!rule.matches(json::json!({"service": "api"}).as_object().unwrap()));
+
+        let mut histogram = rule.clone();
+        histogram.metric_type = LogMetricType::Histogram;
+        assert!(histogram.validate().is_err());
+        histogram.value_field = Some("took".to_string());
+        assert!(histogram.validate().is_ok());
+        histogram.buckets = vec![1.0, 1.0];
+        assert!(histogram.validate().is_err());
+        histogram.labels = vec!["le".to_string()];
+        assert!(histogram.validate().is_err());
+    }
+}
@@ -15,26 +15,44 @@
 
 pub mod actions;
 pub mod alerts;
+pub mod annotations;
 pub mod bitvec;
+pub mod cases;
+pub mod chaos;
 pub mod cluster;
+pub mod column_mask;
 pub mod dashboards;
 pub mod destinations;
+pub mod detections;
 pub mod folder;
 pub mod function;
+pub mod grok;
+pub mod incident_timeline;
+pub mod index_advisor;
+pub mod invitation;
 pub mod inverted_index;
+pub mod log_metrics;
 pub mod logger;
 pub mod meta_store;
 pub mod organization;
 pub mod otlp;
 pub mod pipeline;
 pub mod promql;
+pub mod query_template;
 pub mod ratelimit;
+pub mod remote_write_filter;
+pub mod row_policy;
 pub mod search;
 pub mod self_reporting;
 pub mod short_url;
 pub mod sql;
+pub mod sql_policy;
 pub mod stream;
+pub mod synthetic_data;
+pub mod threat_intel;
 pub mod timed_annotations;
 pub mod triggers;
+pub mod ui_profile;
 pub mod user;
 pub mod websocket;
+pub mod workspace;
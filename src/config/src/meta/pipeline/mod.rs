@@ -58,6 +58,9 @@ pub struct Pipeline {
     pub source: PipelineSource,
     pub nodes: Vec<Node>,
     pub edges: Vec<Edge>,
+    /// User or service account that created the pipeline, set by the server
+    #[serde(default)]
+    pub created_by: String,
 }
 
 impl Pipeline {
@@ -308,6 +311,7 @@ where
     String: Type<R::Database> + Decode<'r, R::Database>,
     i32: Type<R::Database> + Decode<'r, R::Database>,
     bool: Type<R::Database> + Decode<'r, R::Database>,
+    Option<String>: Type<R::Database> + Decode<'r, R::Database>,
 {
     fn from_row(row: &'r R) -> Result<Self, Error> {
         let id: String = row.try_get("id")?;
@@ -317,6 +321,7 @@ where
         let name: String = row.try_get("name")?;
         let description: String = row.try_get("description")?;
         let source_type: String = row.try_get("source_type")?;
+        let created_by: Option<String> = row.try_get("created_by")?;
 
         let source = match source_type.as_str() {
             "realtime" => {
@@ -358,6 +363,7 @@ where
             source,
             nodes,
             edges,
+            created_by: created_by.unwrap_or_default(),
         })
     }
 }
@@ -29,6 +29,15 @@ pub const QUANTILE_LABEL: &str = "quantile";
 pub const METADATA_LABEL: &str = "prom_metadata"; // for schema metadata key
 pub const EXEMPLARS_LABEL: &str = "exemplars";
 
+/// Bits of the NaN prometheus writes as staleness marker when a series disappears, e.g. when its
+/// target restarts. It is stored as a null value.
+// cf. https://github.com/prometheus/prometheus/blob/main/model/value/value.go
+pub const STALE_NAN_BITS: u64 = 0x7ff0000000000002;
+
+pub fn is_stale_nan(value: f64) -> bool {
+    value.to_bits() == STALE_NAN_BITS
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct Metric<'a> {
     #[serde(flatten)]
@@ -220,6 +229,103 @@ pub struct RequestFormatQuery {
     pub query: String,
 }
 
+/// Labels with more distinct values than this are reported as medium
+/// cardinality by the metrics explorer.
+pub const EXPLORER_LOW_CARDINALITY: u64 = 100;
+/// Labels with more distinct values than this are reported as high
+/// cardinality by the metrics explorer.
+pub const EXPLORER_HIGH_CARDINALITY: u64 = 10_000;
+/// Default number of series returned by a metrics explorer preview.
+pub const EXPLORER_PREVIEW_LIMIT: usize = 20;
+
+/// Request a list of metrics for the metrics explorer.
+#[derive(Debug, Deserialize)]
+pub struct RequestExplorerMetrics {
+    /// Only return metrics whose name contains this substring.
+    pub search: Option<String>,
+    /// Maximum number of metrics to return.
+    pub limit: Option<usize>,
+    /// Start timestamp.
+    pub start: Option<String>,
+    /// End timestamp.
+    pub end: Option<String>,
+}
+
+/// Request the labels of a metric, or a preview of the series selected by
+/// `match[]`, for the metrics explorer.
+#[derive(Debug, Deserialize)]
+pub struct RequestExplorerSeries {
+    /// Series selector, it must contain a metric name.
+    #[serde(rename = "match[]")]
+    pub matcher: Option<String>,
+    /// Maximum number of series to return in a preview.
+    pub limit: Option<usize>,
+    /// Start timestamp.
+    pub start: Option<String>,
+    /// End timestamp.
+    pub end: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Cardinality {
+    Low,
+    Medium,
+    High,
+}
+
+impl From<u64> for Cardinality {
+    fn from(values: u64) -> Self {
+        if values > EXPLORER_HIGH_CARDINALITY {
+            Self::High
+        } else if values > EXPLORER_LOW_CARDINALITY {
+            Self::Medium
+        } else {
+            Self::Low
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExplorerMetric {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub metric_type: MetricType,
+    pub help: String,
+    pub unit: String,
+    /// Number of samples stored for the metric, taken from the stream stats.
+    pub samples: i64,
+    /// Number of labels the metric has, excluding `__name__`.
+    pub labels: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExplorerLabel {
+    pub name: String,
+    /// Approximate number of distinct values of the label.
+    pub values: u64,
+    pub cardinality: Cardinality,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExplorerLabels {
+    pub metric: String,
+    /// Approximate number of series of the metric.
+    pub series: u64,
+    pub labels: Vec<ExplorerLabel>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExplorerSeriesPreview {
+    pub metric: String,
+    /// Approximate number of series matching the selector.
+    pub total: u64,
+    /// True when `series` does not hold every matching series.
+    pub truncated: bool,
+    #[schema(value_type = Vec<Object>)]
+    pub series: Vec<HashMap<String, String>>,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Function {
     Avg,
@@ -316,4 +422,25 @@ mod tests {
         assert_eq!(format!("{}", MetricType::Unknown), "unknown");
         assert_eq!(MetricType::Unknown.to_string(), "unknown");
     }
+
+    #[test]
+    fn test_cardinality_from_values() {
+        assert_eq!(Cardinality::from(0), Cardinality::Low);
+        assert_eq!(
+            Cardinality::from(EXPLORER_LOW_CARDINALITY),
+            Cardinality::Low
+        );
+        assert_eq!(
+            Cardinality::from(EXPLORER_LOW_CARDINALITY + 1),
+            Cardinality::Medium
+        );
+        assert_eq!(
+            Cardinality::from(EXPLORER_HIGH_CARDINALITY),
+            Cardinality::Medium
+        );
+        assert_eq!(
+            Cardinality::from(EXPLORER_HIGH_CARDINALITY + 1),
+            Cardinality::High
+        );
+    }
 }
@@ -0,0 +1,203 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::stream::StreamType;
+use crate::utils::json::Value;
+
+/// A parameterized query external applications can run with a signed token, without access to
+/// the search API. Parameters are referenced as `$name` in the sql.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct QueryTemplate {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub sql: String,
+    #[serde(default)]
+    pub stream_type: StreamType,
+    #[serde(default)]
+    pub params: Vec<QueryTemplateParam>,
+    /// Maximum time range of an execution in hours, 0 means no limit.
+    #[serde(default)]
+    pub max_time_range_hours: i64,
+    /// Maximum number of rows an execution returns.
+    #[serde(default = "default_max_rows")]
+    pub max_rows: i64,
+    /// Key the tokens of the template are signed with, never returned by the API.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub secret: String,
+    #[serde(default)]
+    pub created_at: i64,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+fn default_max_rows() -> i64 {
+    1000
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamType {
+    #[default]
+    String,
+    Number,
+}
+
+/// Values a parameter can take, empty means any value of the parameter type.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct ParamRange {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[schema(value_type = Vec<Object>)]
+    pub values: Vec<Value>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+}
+
+impl ParamRange {
+    pub fn allows(&self, value: &Value) -> bool {
+        if !self.values.is_empty() && !self.values.contains(value) {
+            return false;
+        }
+        if self.min.is_none() && self.max.is_none() {
+            return true;
+        }
+        let Some(v) = value.as_f64() else {
+            return false;
+        };
+        self.min.is_none_or(|min| v >= min) && self.max.is_none_or(|max| v <= max)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct QueryTemplateParam {
+    pub name: String,
+    #[serde(default)]
+    #[serde(rename = "type")]
+    pub param_type: ParamType,
+    #[serde(default)]
+    #[serde(flatten)]
+    pub range: ParamRange,
+    /// Value used when the execution doesn't set the parameter, required otherwise.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub default: Option<Value>,
+}
+
+impl QueryTemplateParam {
+    pub fn is_valid_name(name: &str) -> bool {
+        name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct QueryTemplateList {
+    pub list: Vec<QueryTemplate>,
+}
+
+/// Request to issue a token for a template.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct QueryTemplateTokenRequest {
+    /// Narrows the values of the parameters on top of the template restrictions, e.g. pins a
+    /// `customer_id` to the customer the token is handed to.
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub params: HashMap<String, ParamRange>,
+    /// Lifetime of the token in seconds, defaults to one hour.
+    #[serde(default = "default_token_ttl")]
+    pub expires_in: i64,
+}
+
+fn default_token_ttl() -> i64 {
+    3600
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct QueryTemplateTokenResponse {
+    pub token: String,
+    /// Expiry of the token in seconds since epoch.
+    pub expires_at: i64,
+}
+
+/// Claims of a template token.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct QueryTemplateClaims {
+    pub org: String,
+    pub template: String,
+    /// User who issued the token, the template runs with their permissions.
+    pub created_by: String,
+    #[serde(default)]
+    pub params: HashMap<String, ParamRange>,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct QueryTemplateExecuteRequest {
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub params: HashMap<String, Value>,
+    /// Start of the time range in microseconds.
+    pub start_time: i64,
+    /// End of the time range in microseconds.
+    pub end_time: i64,
+    /// Number of rows to return, capped by the template.
+    #[serde(default)]
+    pub size: Option<i64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct QueryTemplateExecuteResponse {
+    pub took: usize,
+    pub total: usize,
+    #[schema(value_type = Vec<Object>)]
+    pub hits: Vec<Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::json;
+
+    #[test]
+    fn test_param_range() {
+        let range = ParamRange {
+            min: Some(1.0),
+            max: Some(10.0),
+            ..Default::default()
+        };
+        assert!(range.allows(&json::json!(5)));
+        assert!(!range.allows(&json::json!(11)));
+        assert!(!range.allows(&json::json!("5")));
+        let range = ParamRange {
+            values: vec![json::json!("acme")],
+            ..Default::default()
+        };
+        assert!(range.allows(&json::json!("acme")));
+        assert!(!range.allows(&json::json!("other")));
+        assert!(ParamRange::default().allows(&json::json!("anything")));
+    }
+}
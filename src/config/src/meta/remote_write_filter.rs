@@ -0,0 +1,193 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use hashbrown::HashMap;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::FxIndexMap;
+
+/// Filters the series sent to the prometheus remote write endpoint of an org. A series is kept
+/// when it matches one of the allow rules (or there are none) and none of the deny rules.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct RemoteWriteFilter {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub allow: Vec<MetricFilterRule>,
+    #[serde(default)]
+    pub deny: Vec<MetricFilterRule>,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+/// Regexes are fully anchored like prometheus relabeling, a missing label matches as an empty
+/// value.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct MetricFilterRule {
+    /// Regex on the metric name, empty matches any metric.
+    #[serde(default)]
+    pub metric: String,
+    /// Regexes on label values, keyed by label name.
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub labels: HashMap<String, String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FilterVerdict {
+    Accept,
+    NotAllowed,
+    Denied,
+}
+
+impl FilterVerdict {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FilterVerdict::Accept => "accept",
+            FilterVerdict::NotAllowed => "not_allowed",
+            FilterVerdict::Denied => "denied",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CompiledRule {
+    metric: Option<Regex>,
+    labels: Vec<(String, Regex)>,
+}
+
+impl CompiledRule {
+    fn matches(&self, metric: &str, labels: &FxIndexMap<String, String>) -> bool {
+        self.metric.as_ref().is_none_or(|re| re.is_match(metric))
+            && self.labels.iter().all(|(name, re)| {
+                re.is_match(labels.get(name).map(|v| v.as_str()).unwrap_or_default())
+            })
+    }
+}
+
+/// Compiled form of a [RemoteWriteFilter], kept in the cache of the ingesters.
+#[derive(Debug)]
+pub struct RemoteWriteMatcher {
+    allow: Vec<CompiledRule>,
+    deny: Vec<CompiledRule>,
+}
+
+impl RemoteWriteMatcher {
+    pub fn check(&self, metric: &str, labels: &FxIndexMap<String, String>) -> FilterVerdict {
+        if !self.allow.is_empty() && !self.allow.iter().any(|r| r.matches(metric, labels)) {
+            FilterVerdict::NotAllowed
+        } else if self.deny.iter().any(|r| r.matches(metric, labels)) {
+            FilterVerdict::Denied
+        } else {
+            FilterVerdict::Accept
+        }
+    }
+}
+
+fn compile_regex(pattern: &str) -> Result<Regex, String> {
+    Regex::new(&format!("^(?:{pattern})$")).map_err(|e| format!("invalid regex {pattern}: {e}"))
+}
+
+fn compile_rules(rules: &[MetricFilterRule]) -> Result<Vec<CompiledRule>, String> {
+    rules
+        .iter()
+        .map(|rule| {
+            let metric = if rule.metric.is_empty() {
+                None
+            } else {
+                Some(compile_regex(&rule.metric)?)
+            };
+            let labels = rule
+                .labels
+                .iter()
+                .map(|(name, pattern)| Ok((name.to_string(), compile_regex(pattern)?)))
+                .collect::<Result<Vec<_>, String>>()?;
+            Ok(CompiledRule { metric, labels })
+        })
+        .collect()
+}
+
+impl RemoteWriteFilter {
+    /// Returns `None` for a disabled filter.
+    pub fn compile(&self) -> Result<Option<RemoteWriteMatcher>, String> {
+        if !self.enabled {
+            return Ok(None);
+        }
+        Ok(Some(RemoteWriteMatcher {
+            allow: compile_rules(&self.allow)?,
+            deny: compile_rules(&self.deny)?,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(pairs: &[(&str, &str)]) -> FxIndexMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_remote_write_filter() {
+        let filter = RemoteWriteFilter {
+            enabled: true,
+            allow: vec![MetricFilterRule {
+                metric: "node_.*|up".to_string(),
+                labels: HashMap::new(),
+            }],
+            deny: vec![MetricFilterRule {
+                metric: String::new(),
+                labels: HashMap::from([("namespace".to_string(), "kube-.*".to_string())]),
+            }],
+            updated_at: 0,
+        };
+        let matcher = filter.compile().unwrap().unwrap();
+        assert_eq!(
+            matcher.check("node_cpu_seconds_total", &labels(&[("namespace", "web")])),
+            FilterVerdict::Accept
+        );
+        assert_eq!(matcher.check("up", &labels(&[])), FilterVerdict::Accept);
+        assert_eq!(
+            matcher.check("go_gc_duration_seconds", &labels(&[])),
+            FilterVerdict::NotAllowed
+        );
+        // anchored, so a partial match is not enough
+        assert_eq!(
+            matcher.check("upstream", &labels(&[])),
+            FilterVerdict::NotAllowed
+        );
+        assert_eq!(
+            matcher.check("up", &labels(&[("namespace", "kube-system")])),
+            FilterVerdict::Denied
+        );
+
+        assert!(RemoteWriteFilter::default().compile().unwrap().is_none());
+        let invalid = RemoteWriteFilter {
+            enabled: true,
+            deny: vec![MetricFilterRule {
+                metric: "(".to_string(),
+                labels: HashMap::new(),
+            }],
+            ..Default::default()
+        };
+        assert!(invalid.compile().is_err());
+    }
+}
@@ -0,0 +1,131 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{
+    stream::StreamType,
+    user::{User, UserRole},
+};
+
+/// Attributes of the user a row policy predicate can use as `{user.<attribute>}`.
+pub const USER_ATTRIBUTES: [&str; 6] =
+    ["email", "domain", "first_name", "last_name", "role", "org"];
+
+/// Row level security policy: the predicate is added with AND to every query of the users with
+/// the role on the stream, so several teams can share one stream and only see their rows.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct RowPolicy {
+    pub stream_type: StreamType,
+    pub stream_name: String,
+    pub role: UserRole,
+    /// Sql predicate like `tenant_id = '{user.domain}'`. The `{user.<attribute>}` placeholders
+    /// in string literals are replaced with the attributes of the user running the query.
+    pub predicate: String,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+impl RowPolicy {
+    /// Key of the policy in the org, `{stream_type}/{stream_name}/{role}`.
+    pub fn key(&self) -> String {
+        policy_key(self.stream_type, &self.stream_name, &self.role)
+    }
+}
+
+pub fn policy_key(stream_type: StreamType, stream_name: &str, role: &UserRole) -> String {
+    format!("{stream_type}/{stream_name}/{role}")
+}
+
+/// Value of an attribute of [`USER_ATTRIBUTES`] for the user.
+pub fn user_attribute(user: &User, name: &str) -> Option<String> {
+    match name {
+        "email" => Some(user.email.clone()),
+        "domain" => Some(
+            user.email
+                .rsplit_once('@')
+                .map(|(_, domain)| domain.to_string())
+                .unwrap_or_default(),
+        ),
+        "first_name" => Some(user.first_name.clone()),
+        "last_name" => Some(user.last_name.clone()),
+        "role" => Some(user.role.to_string()),
+        "org" => Some(user.org.clone()),
+        _ => None,
+    }
+}
+
+/// Replaces the `{user.<attribute>}` placeholders of the text, unknown attributes are an error.
+pub fn render_placeholders(
+    text: &str,
+    attribute: impl Fn(&str) -> Option<String>,
+) -> Result<String, String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{user.") {
+        out.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            return Err(format!("unclosed placeholder in {text}"));
+        };
+        let name = &rest[start + "{user.".len()..start + end];
+        match attribute(name) {
+            Some(value) => out.push_str(&value),
+            None => return Err(format!("unknown user attribute {name}")),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_placeholders() {
+        let user = User {
+            email: "jo@acme.io".to_string(),
+            first_name: "Jo".to_string(),
+            last_name: String::new(),
+            password: String::new(),
+            salt: String::new(),
+            token: String::new(),
+            rum_token: None,
+            role: UserRole::Viewer,
+            org: "default".to_string(),
+            is_external: false,
+            password_ext: None,
+        };
+        let attribute = |name: &str| user_attribute(&user, name);
+        assert_eq!(
+            render_placeholders("tenant_{user.domain} {user.role}", attribute).unwrap(),
+            "tenant_acme.io viewer"
+        );
+        assert_eq!(render_placeholders("plain", attribute).unwrap(), "plain");
+        assert!(render_placeholders("{user.tenant}", attribute).is_err());
+        assert!(render_placeholders("{user.email", attribute).is_err());
+
+        let policy = RowPolicy {
+            stream_type: StreamType::Logs,
+            stream_name: "web".to_string(),
+            role: UserRole::Viewer,
+            predicate: "tenant_id = '{user.domain}'".to_string(),
+            updated_at: 0,
+        };
+        assert_eq!(policy.key(), "logs/web/viewer");
+    }
+}
@@ -81,6 +81,51 @@ pub fn default_use_cache() -> bool {
     get_config().common.result_cache_enabled
 }
 
+/// Options of a search job, sent along the search request.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct SearchJobOptions {
+    /// Allow the job to read the archive tier, its files are rehydrated before the query runs
+    pub include_archived: bool,
+    /// Who to notify when the job is done
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify: Option<SearchJobNotify>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct SearchJobNotify {
+    /// Url receiving a POST request with the job status
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+    pub emails: Vec<String>,
+}
+
+impl SearchJobOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        let Some(notify) = self.notify.as_ref() else {
+            return Ok(());
+        };
+        if notify
+            .webhook_url
+            .as_ref()
+            .is_some_and(|url| !url.starts_with("http://") && !url.starts_with("https://"))
+        {
+            return Err("webhook url must be a http or https url".to_string());
+        }
+        if let Some(email) = notify.emails.iter().find(|e| !e.contains('@')) {
+            return Err(format!("invalid email [{email}]"));
+        }
+        Ok(())
+    }
+}
+
+/// Returns the time in microseconds before which data is in the archive tier, `None` when the
+/// archive tier is disabled.
+pub fn archive_boundary(archive_after_days: i64, now: i64) -> Option<i64> {
+    (archive_after_days > 0).then(|| now - archive_after_days * 86_400_000_000)
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub enum RequestEncoding {
     #[serde(rename = "base64")]
@@ -139,6 +184,310 @@ pub struct Query {
     pub streaming_output: bool,
     #[serde(default)]
     pub streaming_id: Option<String>,
+    /// Maximum number of points per series of a time-series query, usually the pixel width of
+    /// the panel. Larger results are downsampled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_points: Option<usize>,
+    /// Disjoint `[start_time, end_time)` ranges searched together in one query, e.g. the same
+    /// hour of every week. When set, `start_time` and `end_time` only clip the ranges.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub time_ranges: Vec<[i64; 2]>,
+    /// Offsets like `1d` or `7d`, the query is also run over the time range shifted back by each
+    /// offset and the results are returned aligned with the current results, see
+    /// [`Response::time_shifts`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub time_shifts: Vec<String>,
+    /// Values of the dashboard variables referenced in the sql, substituted by
+    /// [`Request::decode`], see [`substitute_variables`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<QueryVariable>,
+}
+
+/// Value of a dashboard variable, a string, a number, a boolean or a list of them for a
+/// multi-select variable.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct QueryVariable {
+    pub name: String,
+    #[schema(value_type = Object)]
+    pub value: json::Value,
+}
+
+/// Value of a multi-select variable when all values are selected, filters on it are removed
+/// from the query.
+pub const DASHBOARD_ALL: &str = "_o2_all_";
+
+/// Maximum number of time shifted comparisons of a query
+pub const MAX_TIME_SHIFTS: usize = 5;
+
+/// Parses the time shift offsets of a query into microseconds.
+pub fn parse_time_shifts(shifts: &[String]) -> Result<Vec<(String, i64)>, String> {
+    if shifts.len() > MAX_TIME_SHIFTS {
+        return Err(format!(
+            "too many time shifts, the limit is {MAX_TIME_SHIFTS}"
+        ));
+    }
+    shifts
+        .iter()
+        .map(|shift| {
+            let offset = shift.trim().trim_start_matches('-');
+            match crate::utils::time::parse_milliseconds(offset) {
+                Ok(ms) if ms > 0 => Ok((shift.to_string(), ms as i64 * 1000)),
+                _ => Err(format!("invalid time shift: {shift}")),
+            }
+        })
+        .collect()
+}
+
+/// Maximum number of values of a multi-select variable
+pub const MAX_VARIABLE_VALUES: usize = 1000;
+
+/// Where a variable is referenced in the sql, the value is quoted accordingly.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum VariableContext {
+    /// Outside of quotes, values become sql literals
+    Bare,
+    /// Inside a `'string'`
+    String,
+    /// Inside a `"identifier"`
+    Identifier,
+}
+
+/// A variable value, numbers and booleans are safe to use as is.
+enum VariableScalar {
+    Str(String),
+    Raw(String),
+}
+
+impl VariableScalar {
+    fn from_value(value: &json::Value) -> Result<Self, String> {
+        match value {
+            json::Value::String(s) if is_plain_number(s) => Ok(Self::Raw(s.to_string())),
+            json::Value::String(s) => Ok(Self::Str(s.to_string())),
+            json::Value::Number(n) => Ok(Self::Raw(n.to_string())),
+            json::Value::Bool(b) => Ok(Self::Raw(b.to_string())),
+            _ => Err(format!("unsupported variable value: {value}")),
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        match self {
+            Self::Str(s) | Self::Raw(s) => s,
+        }
+    }
+
+    fn to_literal(&self) -> String {
+        match self {
+            Self::Raw(s) if s.starts_with('-') => format!("({s})"),
+            Self::Raw(s) => s.to_string(),
+            Self::Str(s) => quote(s, '\''),
+        }
+    }
+}
+
+fn is_plain_number(s: &str) -> bool {
+    s.parse::<f64>().is_ok_and(|v| v.is_finite())
+        && s.chars()
+            .all(|c| c.is_ascii_digit() || c == '-' || c == '.')
+}
+
+fn quote(s: &str, quote: char) -> String {
+    let escaped = s.replace(quote, &format!("{quote}{quote}"));
+    format!("{quote}{escaped}{quote}")
+}
+
+/// Renders the values of a variable for the context it is referenced in. The optional format of
+/// `${name:format}` picks how multiple values are joined: `csv`, `pipe`, `singlequote` or
+/// `doublequote`.
+fn render_variable(
+    values: &[VariableScalar],
+    context: VariableContext,
+    format: Option<&str>,
+) -> Result<String, String> {
+    let join = |sep: &str, f: &dyn Fn(&VariableScalar) -> String| {
+        values.iter().map(f).collect::<Vec<_>>().join(sep)
+    };
+    let escape = |v: &VariableScalar, q: char| v.as_str().replace(q, &format!("{q}{q}"));
+    let all_raw = values.iter().all(|v| matches!(v, VariableScalar::Raw(_)));
+    match (context, format) {
+        (VariableContext::Bare, None) if values.is_empty() => Ok("''".to_string()),
+        (VariableContext::Bare, None) => Ok(join(", ", &|v| v.to_literal())),
+        (VariableContext::Bare, Some("singlequote")) => Ok(join(",", &|v| quote(v.as_str(), '\''))),
+        (VariableContext::Bare, Some("doublequote")) => Ok(join(",", &|v| quote(v.as_str(), '"'))),
+        (VariableContext::Bare, Some(sep @ ("csv" | "pipe"))) => {
+            let sep = if sep == "csv" { "," } else { "|" };
+            if all_raw && !values.is_empty() {
+                Ok(join(sep, &|v| v.to_literal()))
+            } else {
+                // joined strings are kept in a single literal
+                Ok(quote(&join(sep, &|v| v.as_str().to_string()), '\''))
+            }
+        }
+        (VariableContext::String, None) => Ok(join("','", &|v| escape(v, '\''))),
+        (VariableContext::String, Some("csv")) => Ok(join(",", &|v| escape(v, '\''))),
+        (VariableContext::String, Some("pipe")) => Ok(join("|", &|v| escape(v, '\''))),
+        (VariableContext::Identifier, None | Some("csv")) => Ok(join(",", &|v| escape(v, '"'))),
+        (VariableContext::Identifier, Some("pipe")) => Ok(join("|", &|v| escape(v, '"'))),
+        (_, Some(format @ ("singlequote" | "doublequote"))) => Err(format!(
+            "variable format {format} can't be used inside quotes"
+        )),
+        (_, Some(format)) => Err(format!("unknown variable format: {format}")),
+    }
+}
+
+/// Parses a `$name`, `${name}` or `${name:format}` reference at the start of `s`, returns the
+/// name, the format and the length of the reference.
+fn parse_variable_ref(s: &str) -> Option<(&str, Option<&str>, usize)> {
+    let is_name = |name: &str| {
+        name.chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    };
+    let rest = s.strip_prefix('$')?;
+    if let Some(inner) = rest.strip_prefix('{') {
+        let end = inner.find('}')?;
+        let (name, format) = match inner[..end].split_once(':') {
+            Some((name, format)) => (name, Some(format)),
+            None => (&inner[..end], None),
+        };
+        return is_name(name).then_some((name, format, end + 3));
+    }
+    let len = rest
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .unwrap_or(rest.len());
+    let name = &rest[..len];
+    is_name(name).then_some((name, None, len + 1))
+}
+
+/// Replaces the dashboard variables referenced in the sql by their values, quoted and escaped
+/// for where they are used: a variable inside a `'string'` is escaped, outside of quotes it
+/// becomes a literal, so a value can never change the structure of the query. Multi-select
+/// values are expanded to a list, a selection containing the [`DASHBOARD_ALL`] token is replaced
+/// by the token alone. Unknown variables and variables in comments are kept as is.
+pub fn substitute_variables(sql: &str, variables: &[QueryVariable]) -> Result<String, String> {
+    if variables.is_empty() {
+        return Ok(sql.to_string());
+    }
+    let mut resolved = Vec::with_capacity(variables.len());
+    for var in variables {
+        let values = match &var.value {
+            json::Value::Array(list) => list
+                .iter()
+                .map(VariableScalar::from_value)
+                .collect::<Result<Vec<_>, _>>()?,
+            json::Value::Null => vec![],
+            value => vec![VariableScalar::from_value(value)?],
+        };
+        if values.len() > MAX_VARIABLE_VALUES {
+            return Err(format!(
+                "variable {} has too many values, the limit is {MAX_VARIABLE_VALUES}",
+                var.name
+            ));
+        }
+        let values = if values.iter().any(|v| v.as_str() == DASHBOARD_ALL) {
+            vec![VariableScalar::Str(DASHBOARD_ALL.to_string())]
+        } else {
+            values
+        };
+        resolved.push((var.name.as_str(), values));
+    }
+
+    let mut out = String::with_capacity(sql.len());
+    let mut quote: Option<char> = None;
+    let mut pos = 0;
+    while let Some(c) = sql[pos..].chars().next() {
+        let rest = &sql[pos..];
+        match (quote, c) {
+            (None, '-') if rest.starts_with("--") => {
+                let end = rest.find('\n').unwrap_or(rest.len());
+                out.push_str(&rest[..end]);
+                pos += end;
+                continue;
+            }
+            (None, '/') if rest.starts_with("/*") => {
+                let end = rest.find("*/").map(|i| i + 2).unwrap_or(rest.len());
+                out.push_str(&rest[..end]);
+                pos += end;
+                continue;
+            }
+            (None, '\'' | '"') => quote = Some(c),
+            // a doubled quote is an escaped quote, it doesn't end the string
+            (Some(q), _) if c == q && rest[1..].starts_with(q) => {
+                out.push_str(&rest[..2]);
+                pos += 2;
+                continue;
+            }
+            (Some(q), _) if c == q => quote = None,
+            (_, '$') => {
+                if let Some((name, format, len)) = parse_variable_ref(rest)
+                    && let Some((_, values)) = resolved.iter().find(|(n, _)| *n == name)
+                {
+                    let context = match quote {
+                        None => VariableContext::Bare,
+                        Some('\'') => VariableContext::String,
+                        _ => VariableContext::Identifier,
+                    };
+                    out.push_str(&render_variable(values, context, format)?);
+                    pos += len;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        out.push(c);
+        pos += c.len_utf8();
+    }
+    Ok(out)
+}
+
+/// Maximum number of time ranges of a multi-range query
+pub const MAX_TIME_RANGES: usize = 100;
+
+/// Sorts and validates the time ranges of a multi-range query, clipping them to
+/// `[start_time, end_time)` when those are set. Touching ranges are merged, overlapping ranges
+/// are rejected.
+pub fn normalize_time_ranges(
+    ranges: &[[i64; 2]],
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<[i64; 2]>, String> {
+    if ranges.len() > MAX_TIME_RANGES {
+        return Err(format!(
+            "too many time ranges, the limit is {MAX_TIME_RANGES}"
+        ));
+    }
+    if let Some([start, end]) = ranges.iter().find(|[start, end]| start >= end) {
+        return Err(format!(
+            "invalid time range [{start}, {end}], start_time must be less than end_time"
+        ));
+    }
+    let mut sorted = ranges.to_vec();
+    sorted.sort_unstable();
+    let mut merged: Vec<[i64; 2]> = Vec::with_capacity(sorted.len());
+    for range in sorted {
+        match merged.last_mut() {
+            Some(last) if range[0] < last[1] => {
+                return Err(format!(
+                    "time ranges [{}, {}] and [{}, {}] overlap",
+                    last[0], last[1], range[0], range[1]
+                ));
+            }
+            Some(last) if range[0] == last[1] => last[1] = range[1],
+            _ => merged.push(range),
+        }
+    }
+    Ok(merged
+        .into_iter()
+        .filter_map(|[start, end]| {
+            let start = if start_time > 0 {
+                start.max(start_time)
+            } else {
+                start
+            };
+            let end = if end_time > 0 { end.min(end_time) } else { end };
+            (start < end).then_some([start, end])
+        })
+        .collect())
 }
 
 fn default_size() -> i64 {
@@ -162,6 +511,10 @@ impl Default for Query {
             skip_wal: false,
             streaming_output: false,
             streaming_id: None,
+            max_points: None,
+            time_ranges: vec![],
+            time_shifts: vec![],
+            variables: vec![],
         }
     }
 }
@@ -181,6 +534,11 @@ impl Request {
             RequestEncoding::Empty => {}
         }
         self.encoding = RequestEncoding::Empty;
+        if !self.query.variables.is_empty() {
+            self.query.sql = substitute_variables(&self.query.sql, &self.query.variables)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+            self.query.variables.clear();
+        }
         Ok(())
     }
 }
@@ -230,6 +588,38 @@ pub struct Response {
     pub work_group: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub order_by: Option<OrderBy>,
+    /// Number of hits before the result was downsampled to `max_points`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub downsampled_from: Option<usize>,
+    /// Results of the query over the time ranges shifted back by the `time_shifts` of the query.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub time_shifts: Vec<TimeShiftResult>,
+    /// Breakdown groups of a trellis panel query split into pages, the hits only hold the rows
+    /// of the groups of the page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trellis: Option<TrellisGroups>,
+}
+
+/// Page of the breakdown groups of a trellis panel query.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TrellisGroups {
+    pub page: u64,
+    pub max_panels: u64,
+    /// Number of breakdown groups returned by the query.
+    pub total: usize,
+    /// Breakdown groups of the page, in the order of the query.
+    pub groups: Vec<String>,
+}
+
+/// Result of a query over the time range shifted back by `time_shift`. The timestamps of the hits
+/// are moved forward by the same offset, so the series align with the current results.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct TimeShiftResult {
+    pub time_shift: String,
+    #[schema(value_type = Vec<Object>)]
+    pub hits: Vec<json::Value>,
+    pub total: usize,
+    pub scan_size: usize,
 }
 
 /// Iterator for Streaming response of search `Response`
@@ -391,6 +781,9 @@ impl Response {
             result_cache_ratio: 0,
             work_group: None,
             order_by: None,
+            downsampled_from: None,
+            time_shifts: vec![],
+            trellis: None,
         }
     }
 
@@ -508,6 +901,12 @@ pub struct SearchPartitionRequest {
     pub query_fn: Option<String>,
     #[serde(default)]
     pub streaming_output: bool,
+    /// Disjoint time ranges of a multi-range query, see [`Query::time_ranges`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub time_ranges: Vec<[i64; 2]>,
+    /// Values of the dashboard variables referenced in the sql, see [`Query::variables`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<QueryVariable>,
 }
 
 impl SearchPartitionRequest {
@@ -525,6 +924,11 @@ impl SearchPartitionRequest {
             RequestEncoding::Empty => {}
         }
         self.encoding = RequestEncoding::Empty;
+        if !self.variables.is_empty() {
+            self.sql = substitute_variables(&self.sql, &self.variables)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+            self.variables.clear();
+        }
         Ok(())
     }
 }
@@ -540,6 +944,8 @@ impl From<&Request> for SearchPartitionRequest {
             clusters: req.clusters.clone(),
             query_fn: req.query.query_fn.clone(),
             streaming_output: req.query.streaming_output,
+            time_ranges: req.query.time_ranges.clone(),
+            variables: req.query.variables.clone(),
         }
     }
 }
@@ -618,6 +1024,10 @@ impl SearchHistoryRequest {
                 skip_wal: false,
                 streaming_output: false,
                 streaming_id: None,
+                max_points: None,
+                time_ranges: vec![],
+                time_shifts: vec![],
+                variables: vec![],
             },
             encoding: RequestEncoding::Empty,
             regions: Vec::new(),
@@ -973,6 +1383,9 @@ pub struct SearchEventContext {
     #[serde(rename = "folder_name")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dashboard_folder_name: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub panel_id: Option<String>,
 }
 
 impl SearchEventContext {
@@ -1166,6 +1579,10 @@ impl MultiStreamRequest {
                     skip_wal: self.skip_wal,
                     streaming_output: false,
                     streaming_id: None,
+                    max_points: None,
+                    time_ranges: vec![],
+                    time_shifts: vec![],
+                    variables: vec![],
                 },
                 regions: self.regions.clone(),
                 clusters: self.clusters.clone(),
@@ -1225,10 +1642,379 @@ pub struct HashFileResponse {
     pub files: HashMap<String, HashMap<String, String>>,
 }
 
+/// One side of a search diff, the time range and the values of the `$variables` of the query.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct DiffRange {
+    pub start_time: i64,
+    pub end_time: i64,
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SearchDiffRequest {
+    pub sql: String,
+    pub base: DiffRange,
+    pub compare: DiffRange,
+    /// Columns identifying a group, defaults to the non numeric columns of the result.
+    #[serde(default)]
+    pub group_by: Vec<String>,
+    /// Numeric columns to compare, defaults to the numeric columns of the result.
+    #[serde(default)]
+    pub value_fields: Vec<String>,
+    /// Scale the compared values to the duration of the base range, for counts and sums over
+    /// ranges of different lengths.
+    #[serde(default)]
+    pub normalize_by_duration: bool,
+    /// Maximum number of rows fetched per side.
+    #[serde(default = "default_size")]
+    pub size: i64,
+    /// Maximum number of changes returned, 0 returns all.
+    #[serde(default)]
+    pub top: usize,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct DiffGroup {
+    #[schema(value_type = Object)]
+    pub keys: json::Map<String, json::Value>,
+    #[schema(value_type = Object)]
+    pub values: json::Map<String, json::Value>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct DiffChange {
+    #[schema(value_type = Object)]
+    pub keys: json::Map<String, json::Value>,
+    pub field: String,
+    pub base: f64,
+    pub compare: f64,
+    pub delta: f64,
+    /// Relative change in percent, not set when the base value is 0.
+    pub delta_percent: Option<f64>,
+    /// Significance of the change, changes are ordered by it.
+    pub score: f64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct SearchDiffResponse {
+    pub took: usize,
+    pub base_total: usize,
+    pub compare_total: usize,
+    /// Groups only found in the compared range.
+    pub added: Vec<DiffGroup>,
+    /// Groups only found in the base range.
+    pub removed: Vec<DiffGroup>,
+    pub changes: Vec<DiffChange>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OutlierMethod {
+    /// Median absolute deviation, robust to the outliers themselves.
+    #[default]
+    Mad,
+    /// Standard score against the mean and standard deviation.
+    Zscore,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SearchOutliersRequest {
+    /// Aggregation query with a group-by, optionally bucketed by time.
+    pub sql: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    /// Columns identifying a series, defaults to the non numeric columns of the result.
+    #[serde(default)]
+    pub group_by: Vec<String>,
+    /// Numeric column compared across series, defaults to the first numeric column.
+    #[serde(default)]
+    pub value_field: Option<String>,
+    /// Column of the time buckets, series are compared bucket by bucket. Defaults to
+    /// `_timestamp` when the result has it.
+    #[serde(default)]
+    pub timestamp_field: Option<String>,
+    #[serde(default)]
+    pub method: OutlierMethod,
+    /// Minimum score of an outlier series, defaults to 3.
+    #[serde(default)]
+    pub threshold: Option<f64>,
+    /// Maximum number of rows fetched.
+    #[serde(default = "default_size")]
+    pub size: i64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct OutlierSeries {
+    #[schema(value_type = Object)]
+    pub keys: json::Map<String, json::Value>,
+    /// Mean absolute deviation score of the series over all buckets.
+    pub score: f64,
+    /// Largest deviation score of a single bucket.
+    pub max_score: f64,
+    /// Number of buckets where the series is above the threshold.
+    pub outlier_buckets: usize,
+    pub buckets: usize,
+    pub is_outlier: bool,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct SearchOutliersResponse {
+    pub took: usize,
+    pub total_series: usize,
+    /// Series ordered by score, highest first.
+    pub series: Vec<OutlierSeries>,
+}
+
+/// Column holding the organization of the hits of a cross-org search.
+pub const CROSS_ORG_COLUMN: &str = "_org";
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct CrossOrgSearchRequest {
+    /// Query on streams with the same name in the searched organizations.
+    pub sql: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    /// Organizations to search, all the organizations but the meta org when empty.
+    #[serde(default)]
+    pub orgs: Vec<String>,
+    /// Maximum number of hits returned over all the organizations.
+    #[serde(default = "default_size")]
+    pub size: i64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct CrossOrgSearchResponse {
+    pub took: usize,
+    /// Hits of all the organizations with their organization in the `_org` column, latest
+    /// first when they have a `_timestamp`.
+    #[schema(value_type = Vec<Object>)]
+    pub hits: Vec<json::Value>,
+    pub total: usize,
+    /// Organizations which have the streams of the query.
+    pub orgs: Vec<String>,
+    /// Error of each organization whose search failed.
+    pub errors: HashMap<String, String>,
+}
+
+/// Projection of the data a query scans, from the file list of its streams before it runs.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct SearchEstimateResponse {
+    pub file_num: usize,
+    pub records: i64,
+    pub original_size: i64,
+    pub compressed_size: i64,
+    /// Abstract cost of the query, from the compressed size and the number of files scanned
+    pub credits: f64,
+    /// Shortest max query range of the streams in hours, 0 if they have none. Longer queries
+    /// are cut to it.
+    pub max_query_range: i64,
+    pub streams: Vec<StreamSearchEstimate>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct StreamSearchEstimate {
+    pub stream_type: StreamType,
+    pub stream_name: String,
+    pub file_num: usize,
+    pub records: i64,
+    pub original_size: i64,
+    /// Projected with the compression ratio of the stream, the file list has no compressed size
+    pub compressed_size: i64,
+}
+
+impl SearchEstimateResponse {
+    /// Adds the estimate of a stream to the totals.
+    pub fn add_stream(&mut self, stream: StreamSearchEstimate) {
+        self.file_num += stream.file_num;
+        self.records += stream.records;
+        self.original_size += stream.original_size;
+        self.compressed_size += stream.compressed_size;
+        self.streams.push(stream);
+    }
+
+    pub fn compute_credits(&mut self, credits_per_gb: u64, credits_per_1000_files: u64) {
+        let gb = self.compressed_size as f64 / (1024 * 1024 * 1024) as f64;
+        let credits = gb * credits_per_gb as f64
+            + self.file_num as f64 / 1000.0 * credits_per_1000_files as f64;
+        // rounded to the hundredth, the figure is a projection
+        self.credits = (credits * 100.0).round() / 100.0;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_search_job_options() {
+        let options: SearchJobOptions = json::from_str(
+            r#"{"query":{"sql":"select * from t"},"include_archived":true,"notify":{"emails":["a@b.c"]}}"#,
+        )
+        .unwrap();
+        assert!(options.include_archived);
+        assert!(options.validate().is_ok());
+
+        let options: SearchJobOptions =
+            json::from_str(r#"{"notify":{"webhook_url":"ftp://host"}}"#).unwrap();
+        assert!(!options.include_archived);
+        assert!(options.validate().is_err());
+
+        let now = 100 * 86_400_000_000;
+        assert_eq!(archive_boundary(0, now), None);
+        assert_eq!(archive_boundary(30, now), Some(70 * 86_400_000_000));
+    }
+
+    #[test]
+    fn test_search_estimate_credits() {
+        let mut estimate = SearchEstimateResponse::default();
+        estimate.add_stream(StreamSearchEstimate {
+            file_num: 1500,
+            records: 100,
+            original_size: 8 << 30,
+            compressed_size: 1 << 30,
+            ..Default::default()
+        });
+        estimate.add_stream(StreamSearchEstimate {
+            file_num: 500,
+            compressed_size: 1 << 29,
+            ..Default::default()
+        });
+        assert_eq!(estimate.file_num, 2000);
+        assert_eq!(estimate.compressed_size, 3 << 29);
+        estimate.compute_credits(2, 1);
+        assert_eq!(estimate.credits, 5.0);
+        estimate.compute_credits(0, 0);
+        assert_eq!(estimate.credits, 0.0);
+    }
+
+    #[test]
+    fn test_normalize_time_ranges() {
+        let ranges = [[30, 40], [10, 20], [20, 25]];
+        assert_eq!(
+            normalize_time_ranges(&ranges, 0, 0).unwrap(),
+            vec![[10, 25], [30, 40]]
+        );
+        assert_eq!(
+            normalize_time_ranges(&ranges, 15, 35).unwrap(),
+            vec![[15, 25], [30, 35]]
+        );
+        assert!(normalize_time_ranges(&ranges, 50, 60).unwrap().is_empty());
+        assert!(normalize_time_ranges(&[[10, 20], [15, 30]], 0, 0).is_err());
+        assert!(normalize_time_ranges(&[[20, 10]], 0, 0).is_err());
+        assert!(normalize_time_ranges(&vec![[0, 1]; MAX_TIME_RANGES + 1], 0, 0).is_err());
+
+        let query: Query =
+            json::from_str(r#"{"sql":"select * from t","time_ranges":[[10,20],[30,40]]}"#).unwrap();
+        assert_eq!(query.time_ranges, vec![[10, 20], [30, 40]]);
+    }
+
+    #[test]
+    fn test_substitute_variables() {
+        let vars = vec![
+            QueryVariable {
+                name: "host".to_string(),
+                value: json::json!("a'b"),
+            },
+            QueryVariable {
+                name: "code".to_string(),
+                value: json::json!(["200", 404]),
+            },
+            QueryVariable {
+                name: "ns".to_string(),
+                value: json::json!(["x", "y"]),
+            },
+            QueryVariable {
+                name: "all".to_string(),
+                value: json::json!(["x", DASHBOARD_ALL]),
+            },
+            QueryVariable {
+                name: "neg".to_string(),
+                value: json::json!(-1),
+            },
+        ];
+        let sub = |sql: &str| substitute_variables(sql, &vars).unwrap();
+        assert_eq!(
+            sub("select * from t where h = '$host'"),
+            "select * from t where h = 'a''b'"
+        );
+        assert_eq!(
+            sub("select * from t where h = $host"),
+            "select * from t where h = 'a''b'"
+        );
+        assert_eq!(
+            sub("select * from t where h = ${host} or 1=1"),
+            "select * from t where h = 'a''b' or 1=1"
+        );
+        assert_eq!(
+            sub("select * from t where c in ($code)"),
+            "select * from t where c in (200, 404)"
+        );
+        assert_eq!(
+            sub("select * from t where n in ('$ns')"),
+            "select * from t where n in ('x','y')"
+        );
+        assert_eq!(
+            sub("select * from t where n in (${ns:singlequote})"),
+            "select * from t where n in ('x','y')"
+        );
+        assert_eq!(
+            sub("select * from t where n in (${ns:doublequote})"),
+            "select * from t where n in (\"x\",\"y\")"
+        );
+        assert_eq!(
+            sub("select * from t where re_match(n, '${ns:pipe}')"),
+            "select * from t where re_match(n, 'x|y')"
+        );
+        assert_eq!(
+            sub("select * from t where n = ${ns:csv}"),
+            "select * from t where n = 'x,y'"
+        );
+        assert_eq!(
+            sub("select * from t where n in ('$all')"),
+            "select * from t where n in ('_o2_all_')"
+        );
+        assert_eq!(
+            sub("select * from t where n = 0 -$neg"),
+            "select * from t where n = 0 -(-1)"
+        );
+        assert_eq!(sub("select \"$host\" from t"), "select \"a'b\" from t");
+        assert_eq!(
+            sub("select * from t where h = 'it''s $host'"),
+            "select * from t where h = 'it''s a''b'"
+        );
+        assert_eq!(
+            sub("select * from t -- $host\nwhere x = '$unknown' and y = 'ok$'"),
+            "select * from t -- $host\nwhere x = '$unknown' and y = 'ok$'"
+        );
+        assert_eq!(sub("select 'é$host' from t"), "select 'éa''b' from t");
+        assert!(substitute_variables("select '${ns:singlequote}'", &vars).is_err());
+        assert!(substitute_variables("select ${ns:foo}", &vars).is_err());
+
+        let mut req: Request = json::from_str(
+            r#"{"query":{"sql":"select * from t where h = $host","variables":[{"name":"host","value":"x"}]}}"#,
+        )
+        .unwrap();
+        req.decode().unwrap();
+        assert_eq!(req.query.sql, "select * from t where h = 'x'");
+        assert!(req.query.variables.is_empty());
+    }
+
+    #[test]
+    fn test_parse_time_shifts() {
+        let shifts = vec!["1d".to_string(), "-7d".to_string()];
+        assert_eq!(
+            parse_time_shifts(&shifts).unwrap(),
+            vec![
+                ("1d".to_string(), 86_400_000_000),
+                ("-7d".to_string(), 7 * 86_400_000_000)
+            ]
+        );
+        assert!(parse_time_shifts(&["0s".to_string()]).is_err());
+        assert!(parse_time_shifts(&["abc".to_string()]).is_err());
+        assert!(parse_time_shifts(&vec!["1h".to_string(); MAX_TIME_SHIFTS + 1]).is_err());
+    }
+
     #[test]
     fn test_response() {
         let mut res = Response::default();
@@ -14,6 +14,7 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use error::ErrorData;
+use query_audit::QueryAuditRecord;
 use tokio::{
     sync::{mpsc, oneshot},
     time,
@@ -21,6 +22,7 @@ use tokio::{
 use usage::{TriggerData, UsageData};
 
 pub mod error;
+pub mod query_audit;
 pub mod usage;
 
 #[derive(Debug)]
@@ -40,6 +42,7 @@ pub enum ReportingData {
     Usage(Box<UsageData>),
     Trigger(Box<TriggerData>),
     Error(Box<ErrorData>),
+    QueryAudit(Box<QueryAuditRecord>),
 }
 
 #[derive(Debug)]
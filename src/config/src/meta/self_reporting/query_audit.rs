@@ -0,0 +1,150 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+
+use crate::meta::stream::StreamType;
+
+pub const QUERY_AUDIT_STREAM: &str = "query_audit";
+
+/// Audit record of an executed query.
+///
+/// The records written by a node form a hash chain: each record carries the hash of the previous
+/// record of its chain and a hash over its own content, so removing or editing a record breaks
+/// the chain. A node starts a new chain, with a new `chain_id`, every time it starts.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct QueryAuditRecord {
+    pub _timestamp: i64,
+    pub org_id: String,
+    pub user_email: String,
+    pub trace_id: String,
+    pub search_type: String,
+    pub stream_type: StreamType,
+    /// Comma separated streams read by the query
+    pub streams: String,
+    /// The executed SQL, after the access policies of the user were applied
+    pub sql: String,
+    /// WHERE clauses of the query, one per line
+    pub predicates: String,
+    /// Comma separated columns of the returned rows
+    pub columns: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub rows: i64,
+    pub node: String,
+    pub chain_id: String,
+    pub seq: i64,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl QueryAuditRecord {
+    /// Links the record after `prev_hash` as number `seq` of the chain and computes its hash.
+    pub fn seal(&mut self, chain_id: &str, seq: i64, prev_hash: &str) {
+        self.chain_id = chain_id.to_string();
+        self.seq = seq;
+        self.prev_hash = prev_hash.to_string();
+        self.hash = self.compute_hash();
+    }
+
+    /// Hex sha256 over the previous hash and every field of the record but `hash`.
+    pub fn compute_hash(&self) -> String {
+        let content = [
+            self.prev_hash.clone(),
+            self.chain_id.clone(),
+            self.seq.to_string(),
+            self._timestamp.to_string(),
+            self.org_id.clone(),
+            self.user_email.clone(),
+            self.trace_id.clone(),
+            self.search_type.clone(),
+            self.stream_type.to_string(),
+            self.streams.clone(),
+            self.sql.clone(),
+            self.predicates.clone(),
+            self.columns.clone(),
+            self.start_time.to_string(),
+            self.end_time.to_string(),
+            self.rows.to_string(),
+            self.node.clone(),
+        ];
+        sha256::digest(content.join("\u{1f}").as_str())
+    }
+}
+
+/// Verifies the records of one chain, ordered by `seq`, starting at `first_seq`. Returns the
+/// `seq` of the first record which was altered or doesn't follow its predecessor.
+pub fn verify_chain(records: &[QueryAuditRecord], first_seq: i64) -> Result<(), i64> {
+    let mut prev: Option<&QueryAuditRecord> = None;
+    for (i, record) in records.iter().enumerate() {
+        let linked = match prev {
+            Some(prev) => record.chain_id == prev.chain_id && record.prev_hash == prev.hash,
+            None => first_seq > 0 || record.prev_hash.is_empty(),
+        };
+        if !linked || record.seq != first_seq + i as i64 || record.hash != record.compute_hash() {
+            return Err(record.seq);
+        }
+        prev = Some(record);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(len: i64) -> Vec<QueryAuditRecord> {
+        let mut prev_hash = String::new();
+        (0..len)
+            .map(|seq| {
+                let mut record = QueryAuditRecord {
+                    _timestamp: seq,
+                    org_id: "default".to_string(),
+                    user_email: "root@example.com".to_string(),
+                    stream_type: StreamType::Logs,
+                    streams: "k8s".to_string(),
+                    sql: "SELECT * FROM k8s WHERE level = 'error'".to_string(),
+                    predicates: "level = 'error'".to_string(),
+                    rows: 10,
+                    ..Default::default()
+                };
+                record.seal("chain", seq, &prev_hash);
+                prev_hash = record.hash.clone();
+                record
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_verify_chain() {
+        let records = chain(4);
+        assert_eq!(verify_chain(&records, 0), Ok(()));
+        assert_eq!(verify_chain(&records[1..], 1), Ok(()));
+
+        let mut altered = records.clone();
+        altered[2].rows = 0;
+        assert_eq!(verify_chain(&altered, 0), Err(2));
+
+        let mut removed = records.clone();
+        removed.remove(1);
+        assert_eq!(verify_chain(&removed, 0), Err(2));
+
+        let mut rehashed = records;
+        rehashed[1].user_email = "other@example.com".to_string();
+        rehashed[1].hash = rehashed[1].compute_hash();
+        assert_eq!(verify_chain(&rehashed, 0), Err(2));
+    }
+}
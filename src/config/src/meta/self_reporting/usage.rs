@@ -50,6 +50,10 @@ pub enum TriggerDataType {
     Alert,
     #[serde(rename = "derived_stream")]
     DerivedStream,
+    #[serde(rename = "dashboard_snapshot")]
+    DashboardSnapshot,
+    #[serde(rename = "escalation")]
+    Escalation,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -75,6 +79,9 @@ pub struct TriggerData {
     pub query_took: Option<i64>,
     pub scheduler_trace_id: Option<String>,
     pub time_in_queue_ms: Option<i64>,
+    /// Top contributing field values of the alert correlation analysis, as JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -186,6 +193,8 @@ pub enum UsageType {
     Multi,
     #[serde(rename = "/logs/_hec")]
     Hec,
+    #[serde(rename = "/logs/_csv")]
+    Csv,
     #[serde(rename = "/_kinesis_firehose")]
     KinesisFirehose,
     #[serde(rename = "/gcp/_sub")]
@@ -220,6 +229,8 @@ pub enum UsageType {
     Syslog,
     #[serde(rename = "enrichment_table")]
     EnrichmentTable,
+    #[serde(rename = "storage_usage")]
+    StorageUsage,
 }
 
 impl UsageType {
@@ -240,6 +251,7 @@ impl UsageType {
             UsageType::Bulk
                 | UsageType::Json
                 | UsageType::Hec
+                | UsageType::Csv
                 | UsageType::Multi
                 | UsageType::KinesisFirehose
                 | UsageType::GCPSubscription
@@ -266,6 +278,7 @@ impl std::fmt::Display for UsageType {
             UsageType::Json => write!(f, "/logs/_json"),
             UsageType::Multi => write!(f, "/logs/_multi"),
             UsageType::Hec => write!(f, "/logs/_hec"),
+            UsageType::Csv => write!(f, "/logs/_csv"),
             UsageType::KinesisFirehose => write!(f, "/_kinesis_firehose"),
             UsageType::GCPSubscription => write!(f, "/gcp/_sub"),
             UsageType::Logs => write!(f, "/otlp/v1/logs"),
@@ -283,6 +296,7 @@ impl std::fmt::Display for UsageType {
             UsageType::Retention => write!(f, "data_retention"),
             UsageType::Syslog => write!(f, "syslog"),
             UsageType::EnrichmentTable => write!(f, "enrichment_table"),
+            UsageType::StorageUsage => write!(f, "storage_usage"),
         }
     }
 }
@@ -72,6 +72,39 @@ pub fn resolve_stream_names_with_type(sql: &str) -> Result<Vec<TableReference>,
     Ok(tables)
 }
 
+/// get the columns of the ORDER BY of a sql, stops at the first item which is not a column
+pub fn resolve_order_by(sql: &str) -> Result<Vec<(String, OrderBy)>, anyhow::Error> {
+    let dialect = &PostgreSqlDialect {};
+    let statement = Parser::parse_sql(dialect, sql)?
+        .pop()
+        .ok_or(anyhow::anyhow!("Failed to parse sql"))?;
+    let Statement::Query(query) = statement else {
+        return Ok(vec![]);
+    };
+    let Some(orders) = query.order_by.as_ref() else {
+        return Ok(vec![]);
+    };
+    let mut order_by = Vec::with_capacity(orders.exprs.len());
+    for expr in orders.exprs.iter() {
+        let name = match &expr.expr {
+            SqlExpr::Identifier(id) => id.value.to_string(),
+            SqlExpr::CompoundIdentifier(ids) => match ids.last() {
+                Some(id) => id.value.to_string(),
+                None => break,
+            },
+            _ => break,
+        };
+        // the default order of sql is ascending
+        let order = if expr.asc.unwrap_or(true) {
+            OrderBy::Asc
+        } else {
+            OrderBy::Desc
+        };
+        order_by.push((name, order));
+    }
+    Ok(order_by)
+}
+
 pub trait TableReferenceExt {
     fn stream_type(&self) -> String;
     fn stream_name(&self) -> String;
@@ -1271,6 +1304,24 @@ mod tests {
         println!("{:?}", names);
     }
 
+    #[test]
+    fn test_resolve_order_by() {
+        let sql = "select * from t order by _timestamp asc, t.code desc, length(log), level";
+        assert_eq!(
+            resolve_order_by(sql).unwrap(),
+            vec![
+                ("_timestamp".to_string(), OrderBy::Asc),
+                ("code".to_string(), OrderBy::Desc),
+            ]
+        );
+        let sql = "select code, count(*) as cnt from t group by code order by cnt";
+        assert_eq!(
+            resolve_order_by(sql).unwrap(),
+            vec![("cnt".to_string(), OrderBy::Asc)]
+        );
+        assert!(resolve_order_by("select * from t").unwrap().is_empty());
+    }
+
     #[test]
     fn test_resolve_stream_names_error() {
         let sql = "";
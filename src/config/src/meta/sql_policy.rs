@@ -0,0 +1,98 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::user::UserRole;
+
+/// Restricts the queries of the users with one of the given roles, e.g. the tokens behind
+/// customer facing embedded dashboards. Empty lists don't restrict anything.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct SqlPolicy {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Roles the policy applies to.
+    #[serde(default)]
+    pub roles: Vec<UserRole>,
+    /// Sql functions allowed in the query, case insensitive.
+    #[serde(default)]
+    pub allowed_functions: Vec<String>,
+    /// Streams allowed in the query, a trailing `*` matches a prefix.
+    #[serde(default)]
+    pub allowed_streams: Vec<String>,
+    /// Maximum time range of a query in hours, 0 means no limit.
+    #[serde(default)]
+    pub max_time_range_hours: i64,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+impl SqlPolicy {
+    pub fn applies_to(&self, role: &UserRole) -> bool {
+        self.enabled && self.roles.contains(role)
+    }
+
+    pub fn allows_function(&self, name: &str) -> bool {
+        self.allowed_functions.is_empty()
+            || self
+                .allowed_functions
+                .iter()
+                .any(|f| f.eq_ignore_ascii_case(name))
+    }
+
+    pub fn allows_stream(&self, name: &str) -> bool {
+        self.allowed_streams.is_empty()
+            || self
+                .allowed_streams
+                .iter()
+                .any(|s| match s.strip_suffix('*') {
+                    Some(prefix) => name.starts_with(prefix),
+                    None => s == name,
+                })
+    }
+
+    /// Checks the time range given in microseconds.
+    pub fn allows_time_range(&self, start_time: i64, end_time: i64) -> bool {
+        self.max_time_range_hours <= 0
+            || end_time - start_time <= self.max_time_range_hours * 3600 * 1_000_000
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sql_policy() {
+        let policy = SqlPolicy {
+            enabled: true,
+            roles: vec![UserRole::Viewer],
+            allowed_functions: vec!["count".to_string(), "histogram".to_string()],
+            allowed_streams: vec!["public_*".to_string(), "status".to_string()],
+            max_time_range_hours: 24,
+            updated_at: 0,
+        };
+        assert!(policy.applies_to(&UserRole::Viewer));
+        assert!(!policy.applies_to(&UserRole::Admin));
+        assert!(policy.allows_function("COUNT"));
+        assert!(!policy.allows_function("regexp_match"));
+        assert!(policy.allows_stream("public_web"));
+        assert!(policy.allows_stream("status"));
+        assert!(!policy.allows_stream("status_internal"));
+        assert!(policy.allows_time_range(0, 3600 * 1_000_000));
+        assert!(!policy.allows_time_range(0, 25 * 3600 * 1_000_000));
+        assert!(SqlPolicy::default().allows_stream("anything"));
+    }
+}
@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::{cmp::max, fmt::Display};
+use std::{cmp::max, collections::BTreeMap, fmt::Display};
 
 use chrono::{DateTime, Duration, TimeZone, Utc};
 use hashbrown::HashMap;
@@ -23,7 +23,7 @@ use utoipa::ToSchema;
 
 use super::bitvec::BitVec;
 use crate::{
-    get_config,
+    TIMESTAMP_COL_NAME, get_config,
     meta::self_reporting::usage::Stats,
     utils::{
         hash::{Sum64, gxhash},
@@ -448,6 +448,130 @@ impl std::ops::Add<&StreamStats> for &StreamStats {
     }
 }
 
+/// Statistics of a stream for one hour, maintained when files are flushed by the
+/// ingester and merged by the compactor.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StreamHourlyStats {
+    /// Start of the hour, in microseconds
+    pub hour: i64,
+    pub records: i64,
+    pub original_size: i64,
+    pub compressed_size: i64,
+    /// Records with an error level, only counted for logs
+    pub error_records: i64,
+}
+
+impl StreamHourlyStats {
+    /// Returns the start of the hour containing `ts`, in microseconds.
+    pub fn hour_of(ts: i64) -> i64 {
+        const HOUR_MICROS: i64 = 3_600_000_000;
+        ts - ts.rem_euclid(HOUR_MICROS)
+    }
+
+    /// Stats delta of adding (or removing, if `deleted`) a file.
+    pub fn from_file(meta: &FileMeta, deleted: bool) -> Self {
+        let sign = if deleted { -1 } else { 1 };
+        Self {
+            hour: Self::hour_of(meta.min_ts),
+            records: sign * meta.records,
+            original_size: sign * meta.original_size,
+            compressed_size: sign * meta.compressed_size,
+            error_records: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records == 0
+            && self.original_size == 0
+            && self.compressed_size == 0
+            && self.error_records == 0
+    }
+}
+
+impl std::ops::AddAssign<&StreamHourlyStats> for StreamHourlyStats {
+    fn add_assign(&mut self, rhs: &StreamHourlyStats) {
+        self.records += rhs.records;
+        self.original_size += rhs.original_size;
+        self.compressed_size += rhs.compressed_size;
+        self.error_records += rhs.error_records;
+    }
+}
+
+/// Daily sample of the storage used by a stream.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StreamStorageSample {
+    /// Start of the day, in microseconds
+    pub day: i64,
+    pub doc_num: i64,
+    /// Uncompressed size in bytes
+    pub storage_size: i64,
+    /// Compressed size in bytes
+    pub compressed_size: i64,
+    pub index_size: i64,
+}
+
+impl StreamStorageSample {
+    /// Returns the start of the day containing `ts`, in microseconds.
+    pub fn day_of(ts: i64) -> i64 {
+        const DAY_MICROS: i64 = 86_400_000_000;
+        ts - ts.rem_euclid(DAY_MICROS)
+    }
+
+    pub fn from_stats(day: i64, stats: &StreamStats) -> Self {
+        Self {
+            day: Self::day_of(day),
+            doc_num: stats.doc_num,
+            storage_size: stats.storage_size as i64,
+            compressed_size: stats.compressed_size as i64,
+            index_size: stats.index_size as i64,
+        }
+    }
+}
+
+/// Number of queries referencing a field of a stream, in their projection or predicates.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StreamFieldUsage {
+    pub field: String,
+    pub queries: i64,
+    /// Time of the last query referencing the field in microseconds, 0 if none did
+    pub last_queried: i64,
+}
+
+/// Growth of the storage over a series of daily samples.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StorageGrowth {
+    /// Average growth of the uncompressed size, in bytes per day
+    pub storage_bytes_per_day: f64,
+    /// Average growth of the compressed size, in bytes per day
+    pub compressed_bytes_per_day: f64,
+    /// Growth of the compressed size between the first and last sample, in percent
+    pub compressed_growth_rate: f64,
+}
+
+impl StorageGrowth {
+    /// Computes the growth between the first and last of the samples, which must
+    /// be ordered by day.
+    pub fn from_samples(samples: &[StreamStorageSample]) -> Self {
+        let (Some(first), Some(last)) = (samples.first(), samples.last()) else {
+            return Self::default();
+        };
+        let days = (last.day - first.day) as f64 / 86_400_000_000.0;
+        if days <= 0.0 {
+            return Self::default();
+        }
+        let compressed_delta = (last.compressed_size - first.compressed_size) as f64;
+        Self {
+            storage_bytes_per_day: (last.storage_size - first.storage_size) as f64 / days,
+            compressed_bytes_per_day: compressed_delta / days,
+            compressed_growth_rate: if first.compressed_size > 0 {
+                compressed_delta / first.compressed_size as f64 * 100.0
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
 impl From<&FileMeta> for cluster_rpc::FileMeta {
     fn from(req: &FileMeta) -> Self {
         cluster_rpc::FileMeta {
@@ -567,6 +691,9 @@ pub struct UpdateStreamSettings {
     pub data_retention: Option<i64>,
     #[serde(skip_serializing_if = "Option::None")]
     #[serde(default)]
+    pub derived_data_retention: Option<i64>,
+    #[serde(skip_serializing_if = "Option::None")]
+    #[serde(default)]
     pub flatten_level: Option<i64>,
     #[serde(default)]
     pub defined_schema_fields: UpdateSettingsWrapper<String>,
@@ -584,6 +711,21 @@ pub struct UpdateStreamSettings {
     pub index_original_data: Option<bool>,
     #[serde(default)]
     pub index_all_values: Option<bool>,
+    #[serde(default)]
+    pub ip_fields: UpdateSettingsWrapper<String>,
+    #[serde(default)]
+    pub threat_intel_fields: UpdateSettingsWrapper<String>,
+    #[serde(default)]
+    pub timestamp: Option<TimestampSettings>,
+    /// multi-line reassembly of raw inputs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub multiline: Option<MultilineSettings>,
+    #[serde(default)]
+    pub kv_extraction: Option<KvExtractSettings>,
+    /// metadata of the fields to set, keyed by field name, an empty entry removes it
+    #[serde(default)]
+    pub field_metadata: BTreeMap<String, FieldMetadata>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
@@ -677,6 +819,182 @@ impl TimeRange {
         result
     }
 }
+/// How the event timestamp of a record is picked and parsed at ingestion
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct TimestampSettings {
+    /// Source field holding the event time, empty means `_timestamp`
+    #[serde(default)]
+    pub field: String,
+    /// Formats tried in order, empty means auto detection
+    #[serde(default)]
+    pub formats: Vec<TimestampFormat>,
+    /// What to do when the value can't be parsed with any of the formats
+    #[serde(default)]
+    pub fallback: TimestampFallback,
+}
+
+impl TimestampSettings {
+    pub fn field(&self) -> &str {
+        if self.field.is_empty() {
+            TIMESTAMP_COL_NAME
+        } else {
+            &self.field
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampFallback {
+    /// Drop the record with an error
+    #[default]
+    Reject,
+    /// Use the ingestion time instead
+    IngestTime,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+#[schema(value_type = String)]
+pub enum TimestampFormat {
+    Auto,
+    EpochSeconds,
+    EpochMillis,
+    EpochMicros,
+    EpochNanos,
+    Rfc3339,
+    Rfc2822,
+    /// strptime style pattern, e.g. `%Y-%m-%d %H:%M:%S`
+    Strptime(String),
+}
+
+impl TryFrom<String> for TimestampFormat {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(match value.as_str() {
+            "auto" => TimestampFormat::Auto,
+            "epoch_s" => TimestampFormat::EpochSeconds,
+            "epoch_ms" => TimestampFormat::EpochMillis,
+            "epoch_us" => TimestampFormat::EpochMicros,
+            "epoch_ns" => TimestampFormat::EpochNanos,
+            "rfc3339" => TimestampFormat::Rfc3339,
+            "rfc2822" => TimestampFormat::Rfc2822,
+            v if v.contains('%') => TimestampFormat::Strptime(value),
+            _ => return Err(format!("invalid timestamp format: {value}")),
+        })
+    }
+}
+
+impl From<TimestampFormat> for String {
+    fn from(value: TimestampFormat) -> Self {
+        match value {
+            TimestampFormat::Auto => "auto".to_string(),
+            TimestampFormat::EpochSeconds => "epoch_s".to_string(),
+            TimestampFormat::EpochMillis => "epoch_ms".to_string(),
+            TimestampFormat::EpochMicros => "epoch_us".to_string(),
+            TimestampFormat::EpochNanos => "epoch_ns".to_string(),
+            TimestampFormat::Rfc3339 => "rfc3339".to_string(),
+            TimestampFormat::Rfc2822 => "rfc2822".to_string(),
+            TimestampFormat::Strptime(v) => v,
+        }
+    }
+}
+
+/// Reassembles lines that don't match the start pattern into the previous
+/// record, e.g. java stack traces shipped one line per event
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct MultilineSettings {
+    /// Field holding the raw line, empty means the input's message field
+    #[serde(default)]
+    pub field: String,
+    /// Regex matching the first line of a record
+    pub start_pattern: String,
+    /// Maximum lines merged into one record
+    #[serde(default = "default_multiline_max_lines")]
+    pub max_lines: usize,
+    /// How long a partial record waits for more lines on streaming inputs
+    #[serde(default = "default_multiline_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+fn default_multiline_max_lines() -> usize {
+    500
+}
+
+fn default_multiline_timeout_ms() -> u64 {
+    2000
+}
+
+impl MultilineSettings {
+    pub fn field<'a>(&'a self, default: &'a str) -> &'a str {
+        if self.field.is_empty() {
+            default
+        } else {
+            &self.field
+        }
+    }
+}
+
+/// Catalog information of a field, shown wherever the field can be picked
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct FieldMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Unit of the values, e.g. `ms` or `bytes`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    /// Team or person responsible for the field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// The field holds personally identifiable information
+    #[serde(default)]
+    pub pii: bool,
+}
+
+impl FieldMetadata {
+    /// Trims the values, dropping the empty ones.
+    pub fn normalize(mut self) -> Self {
+        let trim = |v: Option<String>| v.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        self.description = trim(self.description);
+        self.unit = trim(self.unit);
+        self.owner = trim(self.owner);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Extracts `key=value` pairs of a logfmt / key-value message into fields
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct KvExtractSettings {
+    /// Field holding the message
+    #[serde(default = "default_kv_field")]
+    pub field: String,
+    /// Prefix added to the extracted field names
+    #[serde(default)]
+    pub prefix: String,
+    /// Replace fields which already exist in the record
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+fn default_kv_field() -> String {
+    "message".to_string()
+}
+
+impl Default for KvExtractSettings {
+    fn default() -> Self {
+        Self {
+            field: default_kv_field(),
+            prefix: String::new(),
+            overwrite: false,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize, ToSchema, PartialEq)]
 pub struct StreamSettings {
     #[serde(skip_serializing_if = "Option::None")]
@@ -694,6 +1012,10 @@ pub struct StreamSettings {
     pub bloom_filter_fields: Vec<String>,
     #[serde(default)]
     pub data_retention: i64,
+    /// retention in days of the data derived from this stream, i.e. the destination streams of
+    /// its pipelines and the metrics extracted by its log metric rules, 0 means their own
+    #[serde(default)]
+    pub derived_data_retention: i64,
     #[serde(skip_serializing_if = "Option::None")]
     pub flatten_level: Option<i64>,
     #[serde(skip_serializing_if = "Option::None")]
@@ -715,6 +1037,28 @@ pub struct StreamSettings {
     pub index_original_data: bool,
     #[serde(default)]
     pub index_all_values: bool,
+    /// ip fields which get a normalized 128-bit companion field at ingestion
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub ip_fields: Vec<String>,
+    /// fields whose values are matched against the threat intel indicators at ingestion
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub threat_intel_fields: Vec<String>,
+    /// event timestamp field and accepted formats
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub timestamp: Option<TimestampSettings>,
+    #[serde(default)]
+    pub multiline: Option<MultilineSettings>,
+    /// logfmt / key-value extraction at ingestion
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub kv_extraction: Option<KvExtractSettings>,
+    /// descriptions of the fields, keyed by field name
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(default)]
+    pub field_metadata: BTreeMap<String, FieldMetadata>,
 }
 
 impl Serialize for StreamSettings {
@@ -737,6 +1081,7 @@ impl Serialize for StreamSettings {
         state.serialize_field("bloom_filter_fields", &self.bloom_filter_fields)?;
         state.serialize_field("distinct_value_fields", &self.distinct_value_fields)?;
         state.serialize_field("data_retention", &self.data_retention)?;
+        state.serialize_field("derived_data_retention", &self.derived_data_retention)?;
         state.serialize_field("max_query_range", &self.max_query_range)?;
         state.serialize_field("store_original_data", &self.store_original_data)?;
         state.serialize_field("approx_partition", &self.approx_partition)?;
@@ -744,6 +1089,37 @@ impl Serialize for StreamSettings {
         state.serialize_field("extended_retention_days", &self.extended_retention_days)?;
         state.serialize_field("index_original_data", &self.index_original_data)?;
         state.serialize_field("index_all_values", &self.index_all_values)?;
+        state.serialize_field("ip_fields", &self.ip_fields)?;
+        state.serialize_field("threat_intel_fields", &self.threat_intel_fields)?;
+        match self.timestamp.as_ref() {
+            Some(timestamp) => {
+                state.serialize_field("timestamp", timestamp)?;
+            }
+            None => {
+                state.skip_field("timestamp")?;
+            }
+        }
+        match self.multiline.as_ref() {
+            Some(multiline) => {
+                state.serialize_field("multiline", multiline)?;
+            }
+            None => {
+                state.skip_field("multiline")?;
+            }
+        }
+        match self.kv_extraction.as_ref() {
+            Some(kv_extraction) => {
+                state.serialize_field("kv_extraction", kv_extraction)?;
+            }
+            None => {
+                state.skip_field("kv_extraction")?;
+            }
+        }
+        if self.field_metadata.is_empty() {
+            state.skip_field("field_metadata")?;
+        } else {
+            state.serialize_field("field_metadata", &self.field_metadata)?;
+        }
 
         match self.defined_schema_fields.as_ref() {
             Some(fields) => {
@@ -832,6 +1208,11 @@ impl From<&str> for StreamSettings {
             data_retention = v.as_i64().unwrap();
         };
 
+        let derived_data_retention = settings
+            .get("derived_data_retention")
+            .and_then(|v| v.as_i64())
+            .unwrap_or_default();
+
         let mut max_query_range = 0;
         if let Some(v) = settings.get("max_query_range") {
             max_query_range = v.as_i64().unwrap();
@@ -907,6 +1288,41 @@ impl From<&str> for StreamSettings {
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
+        let mut ip_fields = Vec::new();
+        if let Some(value) = settings.get("ip_fields").and_then(|v| v.as_array()) {
+            for item in value {
+                if let Some(v) = item.as_str() {
+                    ip_fields.push(v.to_string())
+                }
+            }
+        }
+
+        let mut threat_intel_fields = Vec::new();
+        if let Some(value) = settings
+            .get("threat_intel_fields")
+            .and_then(|v| v.as_array())
+        {
+            for item in value {
+                if let Some(v) = item.as_str() {
+                    threat_intel_fields.push(v.to_string())
+                }
+            }
+        }
+
+        let timestamp = settings
+            .get("timestamp")
+            .and_then(|v| json::from_value(v.clone()).ok());
+        let multiline = settings
+            .get("multiline")
+            .and_then(|v| json::from_value(v.clone()).ok());
+        let kv_extraction = settings
+            .get("kv_extraction")
+            .and_then(|v| json::from_value(v.clone()).ok());
+        let field_metadata = settings
+            .get("field_metadata")
+            .and_then(|v| json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+
         Self {
             partition_time_level,
             partition_keys,
@@ -914,6 +1330,7 @@ impl From<&str> for StreamSettings {
             index_fields,
             bloom_filter_fields,
             data_retention,
+            derived_data_retention,
             max_query_range,
             flatten_level,
             defined_schema_fields,
@@ -924,6 +1341,12 @@ impl From<&str> for StreamSettings {
             extended_retention_days,
             index_original_data,
             index_all_values,
+            ip_fields,
+            threat_intel_fields,
+            timestamp,
+            multiline,
+            kv_extraction,
+            field_metadata,
         }
     }
 }
@@ -1241,6 +1664,31 @@ mod tests {
         assert_eq!(diff.storage_size, 700.0);
     }
 
+    #[test]
+    fn test_stream_settings_field_metadata() {
+        let mut settings = StreamSettings::default();
+        let s = json::to_string(&settings).unwrap();
+        assert!(!s.contains("field_metadata"));
+
+        settings.field_metadata.insert(
+            "latency".to_string(),
+            FieldMetadata {
+                description: Some(" request latency ".to_string()),
+                unit: Some("ms".to_string()),
+                owner: Some("".to_string()),
+                pii: false,
+            }
+            .normalize(),
+        );
+        let s = json::to_string(&settings).unwrap();
+        let parsed = StreamSettings::from(s.as_str());
+        let meta = parsed.field_metadata.get("latency").unwrap();
+        assert_eq!(meta.description.as_deref(), Some("request latency"));
+        assert_eq!(meta.unit.as_deref(), Some("ms"));
+        assert!(meta.owner.is_none());
+        assert!(FieldMetadata::default().normalize().is_empty());
+    }
+
     #[test]
     fn test_stream_partition_types() {
         // Test prefix partition
@@ -1382,4 +1830,41 @@ mod tests {
         let expected_res = vec![TimeRange::new(0, 199), TimeRange::new(200, 300)];
         assert_eq!(TimeRange::flatten_overlapping_ranges(ranges), expected_res);
     }
+
+    #[test]
+    fn test_stream_hourly_stats_from_file() {
+        let meta = FileMeta {
+            min_ts: 1_700_000_123_000_000,
+            max_ts: 1_700_000_999_000_000,
+            records: 10,
+            original_size: 1000,
+            compressed_size: 100,
+            ..Default::default()
+        };
+        let mut stats = StreamHourlyStats::from_file(&meta, false);
+        assert_eq!(stats.hour, 1_699_999_200_000_000);
+        assert_eq!(stats.records, 10);
+        stats += &StreamHourlyStats::from_file(&meta, true);
+        assert_eq!(stats.hour, 1_699_999_200_000_000);
+        assert!(stats.is_empty());
+    }
+
+    #[test]
+    fn test_storage_growth() {
+        let day = 86_400_000_000;
+        let sample = |d: i64, size: i64| StreamStorageSample {
+            day: d * day,
+            storage_size: size * 10,
+            compressed_size: size,
+            ..Default::default()
+        };
+        let growth = StorageGrowth::from_samples(&[sample(0, 100), sample(1, 120), sample(4, 200)]);
+        assert_eq!(growth.compressed_bytes_per_day, 25.0);
+        assert_eq!(growth.storage_bytes_per_day, 250.0);
+        assert_eq!(growth.compressed_growth_rate, 100.0);
+        assert_eq!(
+            StorageGrowth::from_samples(&[sample(0, 100)]),
+            StorageGrowth::default()
+        );
+    }
 }
@@ -0,0 +1,206 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use hashbrown::HashSet;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::meta::stream::StreamType;
+
+/// Most records generated per second by a job.
+pub const MAX_RATE: u64 = 100_000;
+/// Longest run of a job, one day.
+pub const MAX_DURATION_SECS: u64 = 86_400;
+/// Most distinct values of a generated field.
+pub const MAX_CARDINALITY: u64 = 1_000_000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SyntheticFieldType {
+    #[default]
+    String,
+    Integer,
+    Float,
+    Boolean,
+}
+
+/// A field added to every generated record.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SyntheticField {
+    pub name: String,
+    #[serde(default, rename = "type")]
+    pub field_type: SyntheticFieldType,
+    /// Number of distinct values, default the cardinality of the job
+    #[serde(default)]
+    pub cardinality: u64,
+}
+
+/// Parameters of a synthetic data generator job.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SyntheticDataRequest {
+    pub stream_name: String,
+    /// One of `logs`, `metrics` or `traces`
+    #[serde(default)]
+    pub stream_type: StreamType,
+    /// Records generated per second
+    #[serde(default = "default_rate")]
+    pub rate: u64,
+    /// How long the job runs, in seconds
+    #[serde(default = "default_duration_secs")]
+    pub duration_secs: u64,
+    /// Number of distinct services, which are also the metrics series
+    #[serde(default = "default_cardinality")]
+    pub cardinality: u64,
+    /// Share of the records which are errors, between 0 and 1
+    #[serde(default)]
+    pub error_ratio: f64,
+    /// Schema template, fields added to every record
+    #[serde(default)]
+    pub fields: Vec<SyntheticField>,
+}
+
+impl SyntheticDataRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.stream_name.trim().is_empty() {
+            return Err("stream name cannot be empty".to_string());
+        }
+        if !matches!(
+            self.stream_type,
+            StreamType::Logs | StreamType::Metrics | StreamType::Traces
+        ) {
+            return Err("stream type must be one of logs, metrics or traces".to_string());
+        }
+        if self.rate == 0 || self.rate > MAX_RATE {
+            return Err(format!("rate must be between 1 and {MAX_RATE}"));
+        }
+        if self.duration_secs == 0 || self.duration_secs > MAX_DURATION_SECS {
+            return Err(format!(
+                "duration must be between 1 and {MAX_DURATION_SECS} seconds"
+            ));
+        }
+        if self.cardinality == 0 || self.cardinality > MAX_CARDINALITY {
+            return Err(format!(
+                "cardinality must be between 1 and {MAX_CARDINALITY}"
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.error_ratio) {
+            return Err("error ratio must be between 0 and 1".to_string());
+        }
+        let mut names = HashSet::new();
+        for field in self.fields.iter() {
+            if field.name.is_empty() || field.name.starts_with('_') {
+                return Err(format!(
+                    "field name [{}] cannot be empty or start with '_'",
+                    field.name
+                ));
+            }
+            if !names.insert(field.name.as_str()) {
+                return Err(format!("duplicate field [{}]", field.name));
+            }
+            if field.cardinality > MAX_CARDINALITY {
+                return Err(format!(
+                    "cardinality of field [{}] must be at most {MAX_CARDINALITY}",
+                    field.name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn default_rate() -> u64 {
+    100
+}
+
+fn default_duration_secs() -> u64 {
+    60
+}
+
+fn default_cardinality() -> u64 {
+    10
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SyntheticJobStatus {
+    #[default]
+    Running,
+    Completed,
+    Failed,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SyntheticJob {
+    pub id: String,
+    pub request: SyntheticDataRequest,
+    pub status: SyntheticJobStatus,
+    /// Number of records written so far
+    pub generated: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct SyntheticJobList {
+    pub list: Vec<SyntheticJob>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::json;
+
+    #[test]
+    fn test_synthetic_data_request_validate() {
+        let req: SyntheticDataRequest = json::from_str(r#"{"stream_name":"load"}"#).unwrap();
+        assert_eq!(req.stream_type, StreamType::Logs);
+        assert_eq!(req.rate, 100);
+        assert!(req.validate().is_ok());
+
+        let mut bad = req.clone();
+        bad.stream_type = StreamType::EnrichmentTables;
+        assert!(bad.validate().is_err());
+
+        let mut bad = req.clone();
+        bad.error_ratio = 1.5;
+        assert!(bad.validate().is_err());
+
+        let mut bad = req.clone();
+        bad.rate = MAX_RATE + 1;
+        assert!(bad.validate().is_err());
+
+        let mut bad = req.clone();
+        bad.fields = vec![
+            SyntheticField {
+                name: "region".to_string(),
+                ..Default::default()
+            },
+            SyntheticField {
+                name: "region".to_string(),
+                field_type: SyntheticFieldType::Integer,
+                cardinality: 3,
+            },
+        ];
+        assert!(bad.validate().is_err());
+
+        bad.fields[1].name = "_timestamp".to_string();
+        assert!(bad.validate().is_err());
+
+        bad.fields[1].name = "zone".to_string();
+        assert!(bad.validate().is_ok());
+    }
+}
@@ -0,0 +1,168 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::detections::Severity;
+use crate::utils::ip;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum IndicatorKind {
+    Ip,
+    Domain,
+    Hash,
+}
+
+impl fmt::Display for IndicatorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndicatorKind::Ip => write!(f, "ip"),
+            IndicatorKind::Domain => write!(f, "domain"),
+            IndicatorKind::Hash => write!(f, "hash"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct Indicator {
+    pub value: String,
+    pub kind: IndicatorKind,
+    /// Where the indicator comes from, e.g. the name of the feed.
+    #[serde(default)]
+    pub source: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub severity: Severity,
+    /// Expiry time in microseconds, 0 means the indicator never expires.
+    #[serde(default)]
+    pub expires_at: i64,
+    #[serde(default)]
+    pub created_at: i64,
+}
+
+impl Indicator {
+    /// Returns the canonical form of an indicator value, used both when storing indicators and
+    /// when matching record values against them. IPs use their canonical text form, domains
+    /// and hashes are lowercased.
+    pub fn normalize(kind: IndicatorKind, value: &str) -> Option<String> {
+        let value = value.trim();
+        if value.is_empty() {
+            return None;
+        }
+        match kind {
+            IndicatorKind::Ip => ip::normalize_ip(value),
+            IndicatorKind::Domain => Some(value.trim_end_matches('.').to_lowercase()),
+            IndicatorKind::Hash => value
+                .chars()
+                .all(|c| c.is_ascii_hexdigit())
+                .then(|| value.to_lowercase()),
+        }
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at > 0 && self.expires_at <= now
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct IndicatorList {
+    pub list: Vec<Indicator>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedFormat {
+    /// A TAXII 2.1 collection, `url` points to the collection, e.g.
+    /// `https://host/api/collections/<id>/`.
+    #[default]
+    Taxii,
+    /// A STIX 2.x bundle served as a plain JSON document.
+    StixBundle,
+}
+
+/// A feed which is pulled periodically for STIX indicators.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct IndicatorFeed {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub org_id: String,
+    pub name: String,
+    pub url: String,
+    #[serde(default)]
+    pub format: FeedFormat,
+    /// Value of the `Authorization` header sent to the feed.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization: Option<String>,
+    #[serde(default)]
+    pub severity: Severity,
+    /// Pull interval in seconds.
+    #[serde(default = "default_feed_interval")]
+    pub interval: i64,
+    /// Lifetime in seconds of indicators which don't carry a `valid_until`.
+    #[serde(default = "default_indicator_ttl")]
+    pub ttl: i64,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub last_fetched_at: i64,
+}
+
+fn default_feed_interval() -> i64 {
+    3600
+}
+
+fn default_indicator_ttl() -> i64 {
+    7 * 24 * 3600
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct IndicatorFeedList {
+    pub list: Vec<IndicatorFeed>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            Indicator::normalize(IndicatorKind::Domain, " Evil.Example.COM. "),
+            Some("evil.example.com".to_string())
+        );
+        assert_eq!(
+            Indicator::normalize(IndicatorKind::Hash, "D41D8CD98F00B204E9800998ECF8427E"),
+            Some("d41d8cd98f00b204e9800998ecf8427e".to_string())
+        );
+        assert_eq!(Indicator::normalize(IndicatorKind::Hash, "xyz"), None);
+        assert_eq!(Indicator::normalize(IndicatorKind::Ip, "not an ip"), None);
+        assert_eq!(
+            Indicator::normalize(IndicatorKind::Ip, "10.0.0.1"),
+            ip::normalize_ip("10.0.0.1")
+        );
+        assert_eq!(Indicator::normalize(IndicatorKind::Domain, "  "), None);
+    }
+}
@@ -35,6 +35,8 @@ pub enum TriggerModule {
     #[default]
     Alert,
     DerivedStream,
+    DashboardSnapshot,
+    Escalation,
 }
 
 impl std::fmt::Display for TriggerModule {
@@ -43,6 +45,8 @@ impl std::fmt::Display for TriggerModule {
             TriggerModule::Alert => write!(f, "alert"),
             TriggerModule::Report => write!(f, "report"),
             TriggerModule::DerivedStream => write!(f, "derived_stream"),
+            TriggerModule::DashboardSnapshot => write!(f, "dashboard_snapshot"),
+            TriggerModule::Escalation => write!(f, "escalation"),
         }
     }
 }
@@ -82,6 +86,95 @@ pub struct ScheduledTriggerData {
     pub tolerance: i64,
     #[serde(default)]
     pub last_satisfied_at: Option<i64>,
+    /// Set while the alert is firing, cleared when its condition is no longer satisfied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub firing: Option<FiringState>,
+    /// (microseconds) Set while the condition is satisfied but no notification could be sent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_since: Option<i64>,
+    /// Evaluation statistics, kept with the trigger so that every alert manager adds to them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<EvaluationStats>,
+}
+
+/// Running statistics of the evaluations of a scheduled alert.
+#[derive(Clone, Default, Serialize, Deserialize, Debug, PartialEq)]
+pub struct EvaluationStats {
+    /// (microseconds) When the first evaluation was counted
+    pub since: i64,
+    pub evaluations: u64,
+    pub failures: u64,
+    /// Runs skipped because the trigger was processed too late
+    pub skipped: u64,
+    pub total_took_in_secs: f64,
+    pub max_took_in_secs: f64,
+    pub last_took_in_secs: f64,
+    pub total_scan_records: u64,
+    pub last_scan_records: u64,
+    /// (microseconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_evaluated_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// (microseconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error_at: Option<i64>,
+}
+
+impl EvaluationStats {
+    /// Counts an evaluation which ran at `timestamp`, failed when `error` is set.
+    pub fn record(
+        &mut self,
+        timestamp: i64,
+        took_in_secs: f64,
+        scan_records: u64,
+        error: Option<String>,
+    ) {
+        if self.since == 0 {
+            self.since = timestamp;
+        }
+        self.evaluations += 1;
+        self.total_took_in_secs += took_in_secs;
+        self.max_took_in_secs = self.max_took_in_secs.max(took_in_secs);
+        self.last_took_in_secs = took_in_secs;
+        self.total_scan_records += scan_records;
+        self.last_scan_records = scan_records;
+        self.last_evaluated_at = Some(timestamp);
+        if error.is_some() {
+            self.failures += 1;
+            self.last_error = error;
+            self.last_error_at = Some(timestamp);
+        }
+    }
+
+    /// Counts the runs skipped before an evaluation at `timestamp`.
+    pub fn skip(&mut self, timestamp: i64, runs: u64) {
+        if self.since == 0 {
+            self.since = timestamp;
+        }
+        self.skipped += runs;
+    }
+}
+
+/// State of a firing alert, kept to notify its recovery.
+#[derive(Clone, Default, Serialize, Deserialize, Debug, PartialEq)]
+pub struct FiringState {
+    /// (microseconds) When the alert started firing
+    pub since: i64,
+    /// Value the alert condition matched on its last evaluation, the aggregate of the query or
+    /// the number of matching rows
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_value: Option<f64>,
+    /// Incidents opened on the destinations which resolve them on recovery
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub incidents: Vec<Incident>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Incident {
+    pub destination: String,
+    /// Deduplication key of the incident on the destination
+    pub key: String,
 }
 
 impl ScheduledTriggerData {
@@ -99,3 +192,33 @@ impl ScheduledTriggerData {
         json::from_str(s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluation_stats() {
+        let mut stats = EvaluationStats::default();
+        stats.skip(10, 2);
+        stats.record(20, 1.5, 100, None);
+        stats.record(30, 0.5, 50, Some("timeout".to_string()));
+        stats.record(40, 1.0, 10, None);
+        assert_eq!(stats.since, 10);
+        assert_eq!(stats.skipped, 2);
+        assert_eq!(stats.evaluations, 3);
+        assert_eq!(stats.failures, 1);
+        assert_eq!(stats.total_took_in_secs, 3.0);
+        assert_eq!(stats.max_took_in_secs, 1.5);
+        assert_eq!(stats.last_took_in_secs, 1.0);
+        assert_eq!(stats.total_scan_records, 160);
+        assert_eq!(stats.last_scan_records, 10);
+        assert_eq!(stats.last_evaluated_at, Some(40));
+        assert_eq!(stats.last_error.as_deref(), Some("timeout"));
+        assert_eq!(stats.last_error_at, Some(30));
+
+        // trigger data written before the statistics were kept still parses
+        let data = ScheduledTriggerData::from_json_string(r#"{"tolerance":0}"#).unwrap();
+        assert!(data.stats.is_none());
+    }
+}
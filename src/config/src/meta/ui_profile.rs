@@ -0,0 +1,165 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! UI profiles curate what the users of a role, or a single user, see in the UI: the page they
+//! land on after logging in, the sections hidden from them and the dashboards they can open. The
+//! hidden explorers and dashboards are also refused by the server, so a profile is not only
+//! cosmetic.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{stream::StreamType, user::UserRole};
+
+#[derive(
+    Clone, Copy, Debug, Serialize, Deserialize, ToSchema, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum UiSection {
+    /// The logs explorer, hiding it refuses the explorer queries on logs streams
+    Logs,
+    /// The metrics explorer, hiding it refuses the explorer queries on metrics streams
+    Metrics,
+    /// The traces explorer, hiding it refuses the explorer queries on traces streams
+    Traces,
+    Rum,
+    /// Hiding the dashboards refuses to list and open them
+    Dashboards,
+    Alerts,
+    Reports,
+    Pipelines,
+    Streams,
+    Iam,
+    Settings,
+}
+
+impl UiSection {
+    /// Returns the explorer section of the stream type, if it has one.
+    pub fn explorer(stream_type: StreamType) -> Option<Self> {
+        match stream_type {
+            StreamType::Logs => Some(UiSection::Logs),
+            StreamType::Metrics => Some(UiSection::Metrics),
+            StreamType::Traces => Some(UiSection::Traces),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct UiProfile {
+    /// Role the profile applies to, unless it applies to a single user
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<UserRole>,
+    /// Email of the user the profile applies to, it takes precedence over the profile of their
+    /// role
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    /// Section opened after login, the home page if none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub landing_section: Option<UiSection>,
+    /// Dashboard opened after login, it takes precedence over the landing section
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub landing_dashboard: Option<String>,
+    #[serde(default)]
+    pub hidden_sections: Vec<UiSection>,
+    /// Dashboards the users can list and open, all of them when empty
+    #[serde(default)]
+    pub allowed_dashboards: Vec<String>,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+impl UiProfile {
+    /// Key of the profile in the org, `role/{role}` or `user/{user_id}`.
+    pub fn key(&self) -> Option<String> {
+        match (&self.role, &self.user_id) {
+            (Some(role), None) => Some(role_key(role)),
+            (None, Some(user_id)) => Some(user_key(user_id)),
+            _ => None,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.key().is_none() {
+            return Err("profile must apply to either a role or a user".to_string());
+        }
+        if let Some(section) = self.landing_section
+            && self.hides(section)
+        {
+            return Err(format!("landing section {section:?} is hidden"));
+        }
+        if let Some(dashboard_id) = &self.landing_dashboard
+            && !self.allows_dashboard(dashboard_id)
+        {
+            return Err(format!("landing dashboard {dashboard_id} is not allowed"));
+        }
+        Ok(())
+    }
+
+    pub fn hides(&self, section: UiSection) -> bool {
+        self.hidden_sections.contains(&section)
+    }
+
+    pub fn allows_dashboard(&self, dashboard_id: &str) -> bool {
+        !self.hides(UiSection::Dashboards)
+            && (self.allowed_dashboards.is_empty()
+                || self.allowed_dashboards.iter().any(|d| d == dashboard_id))
+    }
+}
+
+pub fn role_key(role: &UserRole) -> String {
+    format!("role/{role}")
+}
+
+pub fn user_key(user_id: &str) -> String {
+    format!("user/{user_id}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ui_profile() {
+        let profile = UiProfile {
+            role: Some(UserRole::Viewer),
+            landing_dashboard: Some("sales".to_string()),
+            hidden_sections: vec![UiSection::Logs],
+            allowed_dashboards: vec!["sales".to_string(), "ops".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(profile.key().unwrap(), "role/viewer");
+        assert!(profile.validate().is_ok());
+        assert!(profile.hides(UiSection::Logs));
+        assert!(!profile.hides(UiSection::Traces));
+        assert!(profile.allows_dashboard("ops"));
+        assert!(!profile.allows_dashboard("internal"));
+        assert!(UiProfile::default().allows_dashboard("internal"));
+
+        let profile = UiProfile {
+            hidden_sections: vec![UiSection::Dashboards],
+            ..profile
+        };
+        assert!(!profile.allows_dashboard("sales"));
+        assert!(profile.validate().is_err());
+
+        let profile = UiProfile {
+            role: Some(UserRole::Viewer),
+            user_id: Some("a@example.com".to_string()),
+            ..Default::default()
+        };
+        assert!(profile.validate().is_err());
+    }
+}
@@ -219,6 +219,8 @@ pub enum UserType {
     Internal,
     /// Is the user authenticated and created via LDAP
     External,
+    /// Non-human principal used by automation, authenticates with its token only
+    ServiceAccount,
 }
 
 impl From<i16> for UserType {
@@ -226,6 +228,7 @@ impl From<i16> for UserType {
         match user_type {
             0 => UserType::Internal,
             1 => UserType::External,
+            2 => UserType::ServiceAccount,
             _ => UserType::Internal,
         }
     }
@@ -236,6 +239,7 @@ impl From<UserType> for i16 {
         match user_type {
             UserType::Internal => 0,
             UserType::External => 1,
+            UserType::ServiceAccount => 2,
         }
     }
 }
@@ -243,8 +247,12 @@ impl From<UserType> for i16 {
 impl UserType {
     pub fn is_external(&self) -> bool {
         match self {
-            UserType::Internal => false,
+            UserType::Internal | UserType::ServiceAccount => false,
             UserType::External => true,
         }
     }
+
+    pub fn is_service_account(&self) -> bool {
+        matches!(self, UserType::ServiceAccount)
+    }
 }
@@ -0,0 +1,164 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Workspaces group the streams, dashboards and alerts of a team within an org. A resource
+//! belongs to at most one workspace and can be shared with other workspaces, which list it
+//! alongside their own resources. The members of a workspace restrict its resources to them, the
+//! members of the workspaces a resource is shared with can read it.
+
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct Workspace {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Emails of the users who can read and change the resources of the workspace, a workspace
+    /// without members doesn't restrict its resources
+    #[serde(default)]
+    pub members: Vec<String>,
+    #[serde(default)]
+    pub created_by: String,
+    #[serde(default)]
+    pub created_at: i64,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+impl Workspace {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("workspace must have a name".to_string());
+        }
+        if self.name.len() > 256 {
+            return Err("workspace name must be at most 256 characters".to_string());
+        }
+        if self.members.iter().any(|m| m.trim().is_empty()) {
+            return Err("workspace member must not be empty".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether the user is a member of the workspace, any user is when it has no members.
+    pub fn is_member(&self, user_id: &str) -> bool {
+        self.members.is_empty() || self.members.iter().any(|m| m.eq_ignore_ascii_case(user_id))
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct WorkspaceList {
+    pub list: Vec<Workspace>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, ToSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceType {
+    Stream,
+    Dashboard,
+    Alert,
+}
+
+impl fmt::Display for ResourceType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResourceType::Stream => write!(f, "stream"),
+            ResourceType::Dashboard => write!(f, "dashboard"),
+            ResourceType::Alert => write!(f, "alert"),
+        }
+    }
+}
+
+impl FromStr for ResourceType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "stream" => Ok(ResourceType::Stream),
+            "dashboard" => Ok(ResourceType::Dashboard),
+            "alert" => Ok(ResourceType::Alert),
+            _ => Err(format!("invalid workspace resource type: {s}")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct WorkspaceResource {
+    pub resource_type: ResourceType,
+    /// The dashboard ID, the alert ID, or `{stream_type}/{stream_name}` for a stream
+    pub resource_id: String,
+    /// Whether the resource belongs to another workspace and is shared with this one
+    #[serde(default)]
+    pub shared: bool,
+}
+
+impl WorkspaceResource {
+    /// Returns the resource ID of a stream.
+    pub fn stream_id(stream_type: impl fmt::Display, stream_name: &str) -> String {
+        format!("{stream_type}/{stream_name}")
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct WorkspaceResourceList {
+    pub list: Vec<WorkspaceResource>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_type_roundtrip() {
+        for ty in [
+            ResourceType::Stream,
+            ResourceType::Dashboard,
+            ResourceType::Alert,
+        ] {
+            assert_eq!(ty.to_string().parse::<ResourceType>().unwrap(), ty);
+        }
+        assert!("folder".parse::<ResourceType>().is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut workspace = Workspace {
+            name: "payments".to_string(),
+            ..Default::default()
+        };
+        assert!(workspace.validate().is_ok());
+        workspace.name = " ".to_string();
+        assert!(workspace.validate().is_err());
+        workspace.name = "payments".to_string();
+        workspace.members = vec!["".to_string()];
+        assert!(workspace.validate().is_err());
+    }
+
+    #[test]
+    fn test_is_member() {
+        let mut workspace = Workspace {
+            name: "payments".to_string(),
+            ..Default::default()
+        };
+        assert!(workspace.is_member("anyone@example.com"));
+        workspace.members = vec!["Alice@example.com".to_string()];
+        assert!(workspace.is_member("alice@example.com"));
+        assert!(!workspace.is_member("bob@example.com"));
+    }
+}
@@ -142,6 +142,30 @@ pub static INGEST_ERRORS: Lazy<IntCounterVec> = Lazy::new(|| {
     )
     .expect("Metric created")
 });
+pub static INGEST_TIMESTAMP_UNPARSABLE: Lazy<IntCounterVec> = Lazy::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "ingest_timestamp_unparsable",
+            "Records whose timestamp could not be parsed".to_owned() + HELP_SUFFIX,
+        )
+        .namespace(NAMESPACE)
+        .const_labels(create_const_labels()),
+        &["organization", "stream_type", "stream"],
+    )
+    .expect("Metric created")
+});
+pub static INGEST_REMOTE_WRITE_DROPPED_SAMPLES: Lazy<IntCounterVec> = Lazy::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "ingest_remote_write_dropped_samples",
+            "Remote write samples dropped by the org filter".to_owned() + HELP_SUFFIX,
+        )
+        .namespace(NAMESPACE)
+        .const_labels(create_const_labels()),
+        &["organization", "reason"],
+    )
+    .expect("Metric created")
+});
 pub static INGEST_WAL_USED_BYTES: Lazy<IntGaugeVec> = Lazy::new(|| {
     IntGaugeVec::new(
         Opts::new(
@@ -933,6 +957,113 @@ pub static QUERY_AGGREGATION_CACHE_BYTES: Lazy<IntGaugeVec> = Lazy::new(|| {
     .expect("Metric created")
 });
 
+// object storage replication
+pub static REPLICATION_PENDING_FILES: Lazy<IntGaugeVec> = Lazy::new(|| {
+    IntGaugeVec::new(
+        Opts::new(
+            "replication_pending_files",
+            "Number of file list entries not yet replicated to the secondary storage",
+        )
+        .namespace(NAMESPACE)
+        .const_labels(create_const_labels()),
+        &[],
+    )
+    .expect("Metric created")
+});
+
+pub static REPLICATION_LAG_SECONDS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    IntGaugeVec::new(
+        Opts::new(
+            "replication_lag_seconds",
+            "Seconds since the secondary storage last caught up with the file list",
+        )
+        .namespace(NAMESPACE)
+        .const_labels(create_const_labels()),
+        &[],
+    )
+    .expect("Metric created")
+});
+
+pub static REPLICATION_FILES: Lazy<IntCounterVec> = Lazy::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "replication_files",
+            "Number of files processed by the replication job",
+        )
+        .namespace(NAMESPACE)
+        .const_labels(create_const_labels()),
+        &["status"],
+    )
+    .expect("Metric created")
+});
+
+// scheduled alert evaluations
+pub static ALERT_EVALUATION_TIME: Lazy<HistogramVec> = Lazy::new(|| {
+    HistogramVec::new(
+        HistogramOpts::new(
+            "alert_evaluation_time",
+            "Evaluation time of the scheduled alerts in seconds.",
+        )
+        .namespace(NAMESPACE)
+        .buckets(vec![0.1, 0.5, 1.0, 5.0, 10.0, 30.0, 60.0, 300.0])
+        .const_labels(create_const_labels()),
+        &["organization", "alert_id"],
+    )
+    .expect("Metric created")
+});
+
+pub static ALERT_EVALUATION_SCAN_RECORDS: Lazy<IntCounterVec> = Lazy::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "alert_evaluation_scan_records",
+            "Records scanned by the queries of the scheduled alerts.",
+        )
+        .namespace(NAMESPACE)
+        .const_labels(create_const_labels()),
+        &["organization", "alert_id"],
+    )
+    .expect("Metric created")
+});
+
+pub static ALERT_EVALUATION_ERRORS: Lazy<IntCounterVec> = Lazy::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "alert_evaluation_errors",
+            "Failed evaluations of the scheduled alerts.",
+        )
+        .namespace(NAMESPACE)
+        .const_labels(create_const_labels()),
+        &["organization", "alert_id"],
+    )
+    .expect("Metric created")
+});
+
+pub static ALERT_EVALUATION_LAST_ERROR: Lazy<IntGaugeVec> = Lazy::new(|| {
+    IntGaugeVec::new(
+        Opts::new(
+            "alert_evaluation_last_error",
+            "Unix time in seconds of the last failed evaluation of the scheduled alerts.",
+        )
+        .namespace(NAMESPACE)
+        .const_labels(create_const_labels()),
+        &["organization", "alert_id"],
+    )
+    .expect("Metric created")
+});
+
+pub static ALERT_SKIPPED_RUNS: Lazy<IntCounterVec> = Lazy::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "alert_skipped_runs",
+            "Runs of the scheduled alerts skipped because they were processed too late.",
+        )
+        .namespace(NAMESPACE)
+        .const_labels(create_const_labels()),
+        &["organization", "alert_id"],
+    )
+    .expect("Metric created")
+});
+
 fn register_metrics(registry: &Registry) {
     // http latency
     registry
@@ -960,6 +1091,12 @@ fn register_metrics(registry: &Registry) {
     registry
         .register(Box::new(INGEST_ERRORS.clone()))
         .expect("Metric registered");
+    registry
+        .register(Box::new(INGEST_TIMESTAMP_UNPARSABLE.clone()))
+        .expect("Metric registered");
+    registry
+        .register(Box::new(INGEST_REMOTE_WRITE_DROPPED_SAMPLES.clone()))
+        .expect("Metric registered");
     registry
         .register(Box::new(INGEST_WAL_USED_BYTES.clone()))
         .expect("Metric registered");
@@ -1179,6 +1316,34 @@ fn register_metrics(registry: &Registry) {
     registry
         .register(Box::new(QUERY_AGGREGATION_CACHE_BYTES.clone()))
         .expect("Metric registered");
+
+    // replication
+    registry
+        .register(Box::new(REPLICATION_PENDING_FILES.clone()))
+        .expect("Metric registered");
+    registry
+        .register(Box::new(REPLICATION_LAG_SECONDS.clone()))
+        .expect("Metric registered");
+    registry
+        .register(Box::new(REPLICATION_FILES.clone()))
+        .expect("Metric registered");
+
+    // scheduled alert evaluations
+    registry
+        .register(Box::new(ALERT_EVALUATION_TIME.clone()))
+        .expect("Metric registered");
+    registry
+        .register(Box::new(ALERT_EVALUATION_SCAN_RECORDS.clone()))
+        .expect("Metric registered");
+    registry
+        .register(Box::new(ALERT_EVALUATION_ERRORS.clone()))
+        .expect("Metric registered");
+    registry
+        .register(Box::new(ALERT_EVALUATION_LAST_ERROR.clone()))
+        .expect("Metric registered");
+    registry
+        .register(Box::new(ALERT_SKIPPED_RUNS.clone()))
+        .expect("Metric registered");
 }
 
 pub fn create_const_labels() -> HashMap<String, String> {
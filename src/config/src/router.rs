@@ -50,8 +50,9 @@ const QUERIER_ROUTES_BY_BODY: [&str; 6] = [
     "/prometheus/api/v1/query_exemplars",
 ];
 const FIXED_QUERIER_ROUTES: [&str; 3] = ["/summary", "/schema", "/streams"];
-pub const INGESTER_ROUTES: [&str; 12] = [
+pub const INGESTER_ROUTES: [&str; 13] = [
     "/_json",
+    "/_csv",
     "/_bulk",
     "/_multi",
     "/_kinesis_firehose",
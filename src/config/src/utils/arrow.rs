@@ -13,10 +13,16 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use arrow::array::RecordBatch;
+use std::{collections::HashMap, sync::Arc};
+
+use arrow::{array::RecordBatch, ipc::writer::StreamWriter};
+use arrow_json::reader::{ReaderBuilder, infer_json_schema_from_iterator};
 
 use super::json::{Map as JsonMap, Value};
 
+/// Content type of an Arrow IPC stream.
+pub const ARROW_STREAM_CONTENT_TYPE: &str = "application/vnd.apache.arrow.stream";
+
 /// Converts an arrow [`RecordBatch`] into a `Vec` of Serde JSON
 /// [`JsonMap`]s (objects)
 pub fn record_batches_to_json_rows(
@@ -56,6 +62,29 @@ pub fn record_batches_to_json_rows(
     Ok(ret)
 }
 
+/// Converts JSON rows into an Arrow IPC stream with a single batch. The schema is inferred from
+/// the rows, conflicting types are widened to strings, and `metadata` is attached to it.
+pub fn json_rows_to_ipc_stream(
+    rows: &[Value],
+    metadata: HashMap<String, String>,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let schema = infer_json_schema_from_iterator(rows.iter().map(|v| Ok(v.clone())))?;
+    let schema = Arc::new(schema.with_metadata(metadata));
+    let mut decoder = ReaderBuilder::new(schema.clone())
+        .with_batch_size(rows.len().max(1))
+        .with_coerce_primitive(true)
+        .build_decoder()?;
+    decoder.serialize(rows)?;
+    let batch = decoder
+        .flush()?
+        .unwrap_or_else(|| RecordBatch::new_empty(schema.clone()));
+
+    let mut writer = StreamWriter::try_new(Vec::new(), &schema)?;
+    writer.write(&batch)?;
+    writer.finish()?;
+    Ok(writer.into_inner()?)
+}
+
 #[cfg(test)]
 mod tests {
     use arrow::{
@@ -178,4 +207,42 @@ mod tests {
         assert_eq!(result[2]["id"], 3);
         assert_eq!(result[2]["name"], "Charlie");
     }
+
+    #[test]
+    fn test_json_rows_to_ipc_stream() {
+        use arrow::{
+            array::{Array, AsArray},
+            datatypes::Int64Type,
+            ipc::reader::StreamReader,
+        };
+
+        let rows = vec![
+            serde_json::json!({"_timestamp": 1, "level": "info", "code": 200}),
+            serde_json::json!({"_timestamp": 2, "level": "error", "code": "timeout"}),
+            serde_json::json!({"_timestamp": 3, "msg": "no level"}),
+        ];
+        let metadata = HashMap::from([("took".to_string(), "12".to_string())]);
+        let buf = json_rows_to_ipc_stream(&rows, metadata).unwrap();
+
+        let reader = StreamReader::try_new(buf.as_slice(), None).unwrap();
+        assert_eq!(reader.schema().metadata()["took"], "12");
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 3);
+        let ts = batch
+            .column_by_name("_timestamp")
+            .unwrap()
+            .as_primitive::<Int64Type>();
+        assert_eq!(ts.values(), &[1, 2, 3]);
+        // mixed number and string values are widened to strings
+        let code = batch.column_by_name("code").unwrap().as_string::<i32>();
+        assert_eq!(code.value(0), "200");
+        assert_eq!(code.value(1), "timeout");
+        assert!(code.is_null(2));
+
+        let buf = json_rows_to_ipc_stream(&[], HashMap::new()).unwrap();
+        let reader = StreamReader::try_new(buf.as_slice(), None).unwrap();
+        assert_eq!(reader.schema().fields().len(), 0);
+    }
 }
@@ -0,0 +1,221 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Suffix of the field holding the 128-bit representation of a normalized ip field
+pub const IP128_FIELD_SUFFIX: &str = "_ip128";
+
+// ranges which are not routable on the public internet, as (network, prefix length)
+const NON_PUBLIC_V4: [(Ipv4Addr, u8); 9] = [
+    (Ipv4Addr::new(0, 0, 0, 0), 8),
+    (Ipv4Addr::new(10, 0, 0, 0), 8),
+    (Ipv4Addr::new(100, 64, 0, 0), 10),
+    (Ipv4Addr::new(127, 0, 0, 0), 8),
+    (Ipv4Addr::new(169, 254, 0, 0), 16),
+    (Ipv4Addr::new(172, 16, 0, 0), 12),
+    (Ipv4Addr::new(192, 168, 0, 0), 16),
+    (Ipv4Addr::new(224, 0, 0, 0), 4),
+    (Ipv4Addr::new(240, 0, 0, 0), 4),
+];
+const NON_PUBLIC_V6: [(Ipv6Addr, u8); 14] = [
+    (Ipv6Addr::UNSPECIFIED, 128),
+    (Ipv6Addr::LOCALHOST, 128),
+    (Ipv6Addr::new(0x64, 0xff9b, 0x1, 0, 0, 0, 0, 0), 48), // local-use translation
+    (Ipv6Addr::new(0x100, 0, 0, 0, 0, 0, 0, 0), 64),       // discard-only
+    (Ipv6Addr::new(0x2001, 0, 0, 0, 0, 0, 0, 0), 32),      // teredo
+    (Ipv6Addr::new(0x2001, 0x2, 0, 0, 0, 0, 0, 0), 48),    // benchmarking
+    (Ipv6Addr::new(0x2001, 0x10, 0, 0, 0, 0, 0, 0), 28),   // orchid
+    (Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 32),  // documentation
+    (Ipv6Addr::new(0x3fff, 0, 0, 0, 0, 0, 0, 0), 20),      // documentation
+    (Ipv6Addr::new(0x5f00, 0, 0, 0, 0, 0, 0, 0), 16),      // segment routing
+    (Ipv6Addr::new(0xfc00, 0, 0, 0, 0, 0, 0, 0), 7),
+    (Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 0), 10),
+    (Ipv6Addr::new(0xfec0, 0, 0, 0, 0, 0, 0, 0), 10), // deprecated site-local
+    (Ipv6Addr::new(0xff00, 0, 0, 0, 0, 0, 0, 0), 8),
+];
+
+/// Parse an ip address, accepts ipv4, ipv6 and bracketed ipv6 like `[::1]`
+pub fn parse_ip(s: &str) -> Option<IpAddr> {
+    let s = s.trim();
+    let s = s
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .unwrap_or(s);
+    s.parse::<IpAddr>().ok()
+}
+
+/// Convert an ip address to its 128-bit representation, ipv4 addresses are ipv4-mapped
+/// (`::ffff:a.b.c.d`) so both families share the same space.
+pub fn ip_to_u128(ip: IpAddr) -> u128 {
+    match ip {
+        IpAddr::V4(v4) => u128::from(v4.to_ipv6_mapped()),
+        IpAddr::V6(v6) => u128::from(v6),
+    }
+}
+
+/// Convert a 128-bit representation back to an ip address, ipv4-mapped addresses are
+/// returned as ipv4.
+pub fn u128_to_ip(v: u128) -> IpAddr {
+    let v6 = Ipv6Addr::from(v);
+    match v6.to_ipv4_mapped() {
+        Some(v4) => IpAddr::V4(v4),
+        None => IpAddr::V6(v6),
+    }
+}
+
+/// Format the 128-bit representation as a fixed width hex string, the lexical order of the
+/// string is the numeric order of the address, so min/max statistics can prune ranges.
+pub fn ip_to_hex128(ip: IpAddr) -> String {
+    format!("{:032x}", ip_to_u128(ip))
+}
+
+/// Parse the fixed width hex string generated by [`ip_to_hex128`]
+pub fn hex128_to_ip(s: &str) -> Option<IpAddr> {
+    if s.len() != 32 {
+        return None;
+    }
+    u128::from_str_radix(s, 16).ok().map(u128_to_ip)
+}
+
+/// Canonical text form of an ip address, ipv6 is compressed and lowercase, ipv4-mapped
+/// ipv6 addresses are returned as ipv4.
+pub fn normalize_ip(s: &str) -> Option<String> {
+    parse_ip(s).map(|ip| u128_to_ip(ip_to_u128(ip)).to_string())
+}
+
+/// Parse a cidr like `10.0.0.0/8` or `2001:db8::/32` to the first and the last address of
+/// the range in the 128-bit space, a plain address is a range of one address.
+pub fn parse_cidr(s: &str) -> Option<(u128, u128)> {
+    let s = s.trim();
+    let (addr, prefix) = match s.split_once('/') {
+        Some((addr, prefix)) => (addr, Some(prefix.trim().parse::<u8>().ok()?)),
+        None => (s, None),
+    };
+    let ip = parse_ip(addr)?;
+    let prefix = match (ip, prefix) {
+        (IpAddr::V4(_), Some(p)) if p <= 32 => p + 96,
+        (IpAddr::V6(_), Some(p)) if p <= 128 => p,
+        (_, Some(_)) => return None,
+        (_, None) => 128,
+    };
+    Some(range_of(ip_to_u128(ip), prefix))
+}
+
+fn range_of(network: u128, prefix: u8) -> (u128, u128) {
+    let host_mask = match prefix {
+        0 => u128::MAX,
+        p if p >= 128 => 0,
+        p => u128::MAX >> p,
+    };
+    (network & !host_mask, network | host_mask)
+}
+
+/// Check if the ip is inside the cidr, returns false if any of them can't be parsed
+pub fn ip_in_cidr(ip: &str, cidr: &str) -> bool {
+    match (parse_ip(ip), parse_cidr(cidr)) {
+        (Some(ip), Some((start, end))) => (start..=end).contains(&ip_to_u128(ip)),
+        _ => false,
+    }
+}
+
+/// Check if the ip belongs to a private, loopback, link-local, shared, multicast or
+/// reserved range.
+pub fn is_private_ip(ip: IpAddr) -> bool {
+    let v = ip_to_u128(ip);
+    let in_range = |network: u128, prefix: u8| {
+        let (start, end) = range_of(network, prefix);
+        (start..=end).contains(&v)
+    };
+    NON_PUBLIC_V4
+        .iter()
+        .any(|(net, prefix)| in_range(ip_to_u128(IpAddr::V4(*net)), prefix + 96))
+        || NON_PUBLIC_V6
+            .iter()
+            .any(|(net, prefix)| in_range(u128::from(*net), *prefix))
+}
+
+/// Check if the ip is routable on the public internet
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    !is_private_ip(ip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_normalize_ip() {
+        assert_eq!(normalize_ip(" 10.1.2.3 "), Some("10.1.2.3".to_string()));
+        assert_eq!(normalize_ip("[::1]"), Some("::1".to_string()));
+        assert_eq!(
+            normalize_ip("2001:0DB8:0000::0001"),
+            Some("2001:db8::1".to_string())
+        );
+        assert_eq!(
+            normalize_ip("::ffff:192.168.0.1"),
+            Some("192.168.0.1".to_string())
+        );
+        assert_eq!(normalize_ip("not an ip"), None);
+    }
+
+    #[test]
+    fn test_hex128_roundtrip() {
+        let ip = parse_ip("192.168.0.1").unwrap();
+        let hex = ip_to_hex128(ip);
+        assert_eq!(hex, "00000000000000000000ffffc0a80001");
+        assert_eq!(hex128_to_ip(&hex), Some(ip));
+        // lexical order matches numeric order
+        let a = ip_to_hex128(parse_ip("10.0.0.2").unwrap());
+        let b = ip_to_hex128(parse_ip("10.0.0.10").unwrap());
+        assert!(a < b);
+        assert_eq!(hex128_to_ip("ffff"), None);
+    }
+
+    #[test]
+    fn test_ip_in_cidr() {
+        assert!(ip_in_cidr("10.1.2.3", "10.0.0.0/8"));
+        assert!(!ip_in_cidr("11.1.2.3", "10.0.0.0/8"));
+        assert!(ip_in_cidr("192.168.1.1", "192.168.1.1"));
+        assert!(ip_in_cidr("8.8.8.8", "0.0.0.0/0"));
+        assert!(!ip_in_cidr("2001:db8::1", "0.0.0.0/0"));
+        assert!(ip_in_cidr("2001:db8::1", "2001:db8::/32"));
+        assert!(!ip_in_cidr("2001:db9::1", "2001:db8::/32"));
+        assert!(ip_in_cidr("::1", "::/0"));
+        assert!(!ip_in_cidr("10.1.2.3", "10.0.0.0/33"));
+        assert!(!ip_in_cidr("invalid", "10.0.0.0/8"));
+    }
+
+    #[test]
+    fn test_private_ip() {
+        for ip in [
+            "10.0.0.1",
+            "172.16.5.4",
+            "192.168.1.1",
+            "127.0.0.1",
+            "::1",
+            "fd00::1",
+            "2001:db8::1",
+            "3fff::1",
+            "100::1",
+            "fec0::1",
+        ] {
+            assert!(is_private_ip(parse_ip(ip).unwrap()), "{ip}");
+        }
+        for ip in ["8.8.8.8", "172.32.0.1", "2001:4860:4860::8888"] {
+            assert!(is_public_ip(parse_ip(ip).unwrap()), "{ip}");
+        }
+    }
+}
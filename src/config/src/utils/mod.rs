@@ -21,6 +21,7 @@ pub mod file;
 pub mod flatten;
 pub mod hash;
 pub mod inverted_index;
+pub mod ip;
 pub mod json;
 pub mod md5;
 pub mod parquet;
@@ -36,4 +37,5 @@ pub mod str;
 pub mod sysinfo;
 pub mod tantivy;
 pub mod time;
+pub mod units;
 pub mod util;
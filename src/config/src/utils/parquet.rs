@@ -25,8 +25,8 @@ use arrow_schema::Schema;
 use futures::TryStreamExt;
 use parquet::{
     arrow::{
-        AsyncArrowWriter, ParquetRecordBatchStreamBuilder, arrow_reader::ArrowReaderMetadata,
-        async_reader::ParquetRecordBatchStream,
+        AsyncArrowWriter, ParquetRecordBatchStreamBuilder, ProjectionMask,
+        arrow_reader::ArrowReaderMetadata, async_reader::ParquetRecordBatchStream,
     },
     basic::{Compression, Encoding},
     file::{metadata::KeyValue, properties::WriterProperties},
@@ -155,6 +155,33 @@ pub async fn read_recordbatch_from_bytes(
     Ok((schema, batches))
 }
 
+/// Reads only the given top level columns, columns missing from the file are skipped.
+pub async fn read_columns_from_bytes(
+    data: &bytes::Bytes,
+    columns: &[&str],
+) -> Result<(Arc<Schema>, Vec<RecordBatch>), anyhow::Error> {
+    let schema_reader = Cursor::new(data.clone());
+    let arrow_reader = ParquetRecordBatchStreamBuilder::new(schema_reader).await?;
+    let indices = arrow_reader
+        .schema()
+        .fields()
+        .iter()
+        .enumerate()
+        .filter_map(|(i, f)| columns.contains(&f.name().as_str()).then_some(i))
+        .collect::<Vec<_>>();
+    let mask = ProjectionMask::roots(arrow_reader.parquet_schema(), indices);
+    let reader = arrow_reader
+        .with_projection(mask)
+        .with_batch_size(PARQUET_BATCH_SIZE)
+        .build()?;
+    let batches: Vec<RecordBatch> = reader.try_collect().await?;
+    let schema = match batches.first() {
+        Some(batch) => batch.schema(),
+        None => Arc::new(Schema::empty()),
+    };
+    Ok((schema, batches))
+}
+
 pub async fn read_recordbatch_from_file(
     path: &PathBuf,
 ) -> Result<(Arc<Schema>, Vec<RecordBatch>), anyhow::Error> {
@@ -19,8 +19,8 @@ use hashbrown::HashSet;
 use sqlparser::{
     ast::{
         DuplicateTreatment, Expr, Function, FunctionArg, FunctionArgExpr, FunctionArgumentList,
-        FunctionArguments, GroupByExpr, Query, SelectItem, SetExpr, Statement, TableFactor, Visit,
-        Visitor,
+        FunctionArguments, GroupByExpr, Query, SelectItem, SetExpr, Statement, TableFactor, Value,
+        Visit, Visitor,
     },
     dialect::GenericDialect,
     parser::Parser,
@@ -28,7 +28,7 @@ use sqlparser::{
 
 use crate::TIMESTAMP_COL_NAME;
 
-pub const AGGREGATE_UDF_LIST: [&str; 10] = [
+pub const AGGREGATE_UDF_LIST: [&str; 11] = [
     "min",
     "max",
     "avg",
@@ -39,6 +39,7 @@ pub const AGGREGATE_UDF_LIST: [&str; 10] = [
     "approx_percentile_cont",
     "percentile_cont",
     "summary_percentile",
+    "forecast",
 ];
 
 pub fn is_aggregate_query(query: &str) -> Result<bool, sqlparser::parser::ParserError> {
@@ -105,6 +106,20 @@ pub fn is_timestamp_selected(query: &str) -> Result<bool, sqlparser::parser::Par
     Ok(false)
 }
 
+/// Functions searching the text of the field given as first argument.
+const TEXT_SEARCH_FUNCTIONS: [&str; 3] = ["str_match", "str_match_ignore_case", "fuzzy_match"];
+
+/// Returns the fields whose text is searched for a substring, i.e. by `LIKE '%...'` or `ILIKE
+/// '%...'` patterns or by the `str_match` functions, which can't use the min/max statistics.
+pub fn text_search_fields(query: &str) -> Result<Vec<String>, sqlparser::parser::ParserError> {
+    let ast = Parser::parse_sql(&GenericDialect {}, query)?;
+    let mut visitor = TextSearchVisitor::default();
+    for statement in ast.iter() {
+        let _ = statement.visit(&mut visitor);
+    }
+    Ok(visitor.fields)
+}
+
 fn is_aggregate_in_select(query: &Query) -> bool {
     if let SetExpr::Select(ref select) = *query.body {
         if select.distinct.is_some() {
@@ -495,6 +510,65 @@ fn has_cte(query: &Query) -> bool {
     query.with.is_some()
 }
 
+#[derive(Default)]
+struct TextSearchVisitor {
+    fields: Vec<String>,
+}
+
+impl TextSearchVisitor {
+    fn add(&mut self, expr: &Expr) {
+        let field = match expr {
+            Expr::Identifier(ident) => &ident.value,
+            Expr::CompoundIdentifier(idents) => match idents.last() {
+                Some(ident) => &ident.value,
+                None => return,
+            },
+            _ => return,
+        };
+        if !self.fields.contains(field) {
+            self.fields.push(field.to_string());
+        }
+    }
+}
+
+impl Visitor for TextSearchVisitor {
+    type Break = ();
+
+    fn pre_visit_expr(&mut self, expr: &Expr) -> ControlFlow<Self::Break> {
+        match expr {
+            Expr::Like {
+                negated: false,
+                expr,
+                pattern,
+                ..
+            }
+            | Expr::ILike {
+                negated: false,
+                expr,
+                pattern,
+                ..
+            } => {
+                if let Expr::Value(Value::SingleQuotedString(pattern)) = pattern.as_ref()
+                    && pattern.starts_with('%')
+                {
+                    self.add(expr);
+                }
+            }
+            Expr::Function(Function {
+                name,
+                args: FunctionArguments::List(FunctionArgumentList { args, .. }),
+                ..
+            }) if TEXT_SEARCH_FUNCTIONS.contains(&name.to_string().to_lowercase().as_str()) => {
+                if let Some(FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))) = args.first() {
+                    self.add(expr);
+                }
+            }
+            _ => {}
+        }
+        ControlFlow::Continue(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1005,4 +1079,20 @@ mod tests {
             assert_eq!(is_simple_aggregate, false);
         }
     }
+
+    #[test]
+    fn test_text_search_fields() {
+        let fields = text_search_fields(
+            "SELECT * FROM t WHERE message LIKE '%error%' AND t.log ILIKE '%timeout' \
+             AND path LIKE '/api/%' AND host NOT LIKE '%internal%' \
+             AND str_match_ignore_case(body, 'oom') AND str_match(message, 'panic')",
+        )
+        .unwrap();
+        assert_eq!(fields, vec!["message", "log", "body"]);
+        assert!(
+            text_search_fields("SELECT * FROM t WHERE code = 'a'")
+                .unwrap()
+                .is_empty()
+        );
+    }
 }
@@ -16,7 +16,7 @@
 use chrono::{DateTime, Datelike, Duration, NaiveDateTime, TimeZone, Utc};
 use once_cell::sync::Lazy;
 
-use crate::utils::json;
+use crate::{meta::stream::TimestampFormat, utils::json};
 
 // BASE_TIME is the time when the timestamp is 1 year, used to check a timestamp
 // is in seconds or milliseconds or microseconds or nanoseconds
@@ -169,6 +169,53 @@ pub fn parse_timestamp_micro_from_value(v: &json::Value) -> Result<i64, anyhow::
     Ok(parse_i64_to_timestamp_micros(n))
 }
 
+/// Parse a timestamp value trying the given formats in order, an empty list
+/// falls back to auto detection.
+pub fn parse_timestamp_with_formats(
+    v: &json::Value,
+    formats: &[TimestampFormat],
+) -> Result<i64, anyhow::Error> {
+    if formats.is_empty() {
+        return parse_timestamp_micro_from_value(v);
+    }
+    for format in formats {
+        if let Some(ts) = parse_timestamp_with_format(v, format) {
+            return Ok(ts);
+        }
+    }
+    Err(anyhow::anyhow!("Invalid time format [no format matched]"))
+}
+
+fn parse_timestamp_with_format(v: &json::Value, format: &TimestampFormat) -> Option<i64> {
+    let epoch = || match v {
+        json::Value::Number(n) => n.as_i64().or_else(|| n.as_f64().map(|f| f as i64)),
+        json::Value::String(s) => s.parse::<i64>().ok(),
+        _ => None,
+    };
+    match format {
+        TimestampFormat::Auto => parse_timestamp_micro_from_value(v).ok(),
+        TimestampFormat::EpochSeconds => epoch()?.checked_mul(1_000_000),
+        TimestampFormat::EpochMillis => epoch()?.checked_mul(1000),
+        TimestampFormat::EpochMicros => epoch(),
+        TimestampFormat::EpochNanos => epoch().map(|n| n / 1000),
+        TimestampFormat::Rfc3339 => DateTime::parse_from_rfc3339(v.as_str()?)
+            .ok()
+            .map(|t| t.timestamp_micros()),
+        TimestampFormat::Rfc2822 => DateTime::parse_from_rfc2822(v.as_str()?)
+            .ok()
+            .map(|t| t.timestamp_micros()),
+        TimestampFormat::Strptime(fmt) => {
+            let s = v.as_str()?;
+            match DateTime::parse_from_str(s, fmt) {
+                Ok(t) => Some(t.timestamp_micros()),
+                Err(_) => NaiveDateTime::parse_from_str(s, fmt)
+                    .ok()
+                    .map(|t| t.and_utc().timestamp_micros()),
+            }
+        }
+    }
+}
+
 pub fn parse_milliseconds(s: &str) -> Result<u64, anyhow::Error> {
     let chars = s.chars().collect::<Vec<char>>();
 
@@ -541,4 +588,35 @@ mod tests {
         assert_eq!(format_duration(3661000), "1h1m1s");
         assert_eq!(format_duration(61000), "1m1s");
     }
+
+    #[test]
+    fn test_parse_timestamp_with_formats() {
+        let formats = vec![
+            TimestampFormat::Strptime("%d/%b/%Y:%H:%M:%S %z".to_string()),
+            TimestampFormat::EpochMillis,
+        ];
+        let v = json::json!("01/Jan/2021:00:00:00 +0000");
+        assert_eq!(
+            parse_timestamp_with_formats(&v, &formats).unwrap(),
+            1609459200000000
+        );
+        let v = json::json!(1609459200);
+        assert_eq!(
+            parse_timestamp_with_formats(&v, &formats).unwrap(),
+            1609459200000
+        );
+        let v = json::json!("2021-01-01T00:00:00Z");
+        assert!(parse_timestamp_with_formats(&v, &formats).is_err());
+        assert_eq!(
+            parse_timestamp_with_formats(&v, &[]).unwrap(),
+            1609459200000000
+        );
+
+        let formats = vec![TimestampFormat::Strptime("%Y-%m-%d %H:%M:%S".to_string())];
+        let v = json::json!("2021-01-01 00:00:00");
+        assert_eq!(
+            parse_timestamp_with_formats(&v, &formats).unwrap(),
+            1609459200000000
+        );
+    }
 }
@@ -0,0 +1,193 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Units of panel and alert values. The values are scaled to the largest unit they fill, like
+//! `1610612736` bytes to `1.5 GiB`, the same way for the panels rendered on the server and the
+//! alert messages.
+
+const BYTES: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+const BITS: [&str; 7] = ["b", "Kb", "Mb", "Gb", "Tb", "Pb", "Eb"];
+const SI: [&str; 6] = ["", "K", "M", "G", "T", "P"];
+/// Duration units from nanoseconds with their length in nanoseconds.
+const DURATIONS: [(&str, f64); 7] = [
+    ("ns", 1.0),
+    ("us", 1e3),
+    ("ms", 1e6),
+    ("s", 1e9),
+    ("m", 60e9),
+    ("h", 3600e9),
+    ("d", 86400e9),
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Unit {
+    /// Plain numbers with SI prefixes.
+    Numbers,
+    Bytes,
+    Kilobytes,
+    Megabytes,
+    BytesPerSecond,
+    Bits,
+    BitsPerSecond,
+    Nanoseconds,
+    Microseconds,
+    Milliseconds,
+    Seconds,
+    /// Percentages between 0 and 100.
+    Percent,
+    /// Percentages between 0 and 1.
+    PercentUnit,
+    /// Any other unit, shown after the number unscaled.
+    Custom(String),
+}
+
+impl Unit {
+    /// Parses the unit names of the panel config, `custom` takes the `unit_custom` of the
+    /// config. The default unit and an empty unit are `None`.
+    pub fn parse(unit: &str, custom_unit: Option<&str>) -> Option<Self> {
+        let unit = unit.trim();
+        Some(match unit.to_lowercase().as_str() {
+            "" | "default" => return None,
+            "numbers" | "short" => Self::Numbers,
+            "bytes" | "b" => Self::Bytes,
+            "kilobytes" | "kb" | "kib" => Self::Kilobytes,
+            "megabytes" | "mb" | "mib" => Self::Megabytes,
+            "bps" | "bytes/s" | "bytes/sec" => Self::BytesPerSecond,
+            "bits" => Self::Bits,
+            "bitps" | "bits/s" | "bits/sec" => Self::BitsPerSecond,
+            "nanoseconds" | "ns" => Self::Nanoseconds,
+            "microseconds" | "us" | "µs" => Self::Microseconds,
+            "milliseconds" | "ms" => Self::Milliseconds,
+            "seconds" | "s" => Self::Seconds,
+            "percent" | "%" => Self::Percent,
+            "percent-1" => Self::PercentUnit,
+            "custom" => match custom_unit.map(|v| v.trim()).filter(|v| !v.is_empty()) {
+                Some(custom) => Self::Custom(custom.to_string()),
+                None => return None,
+            },
+            _ => Self::Custom(unit.to_string()),
+        })
+    }
+
+    /// Scales the value to the unit it fills, returns the scaled value and its suffix.
+    pub fn scale(&self, value: f64) -> (f64, String) {
+        match self {
+            Self::Numbers => {
+                let (v, suffix) = step(value, 1000.0, &SI);
+                (v, suffix.to_string())
+            }
+            Self::Bytes => bytes(value, ""),
+            Self::Kilobytes => bytes(value * 1024.0, ""),
+            Self::Megabytes => bytes(value * 1024.0 * 1024.0, ""),
+            Self::BytesPerSecond => bytes(value, "/s"),
+            Self::Bits => bits(value, ""),
+            Self::BitsPerSecond => bits(value, "/s"),
+            Self::Nanoseconds => duration(value),
+            Self::Microseconds => duration(value * 1e3),
+            Self::Milliseconds => duration(value * 1e6),
+            Self::Seconds => duration(value * 1e9),
+            Self::Percent => (value, "%".to_string()),
+            Self::PercentUnit => (value * 100.0, "%".to_string()),
+            Self::Custom(unit) => (value, format!(" {unit}")),
+        }
+    }
+
+    /// Formats the value in the unit with the given decimals, by default up to two decimals
+    /// without trailing zeros.
+    pub fn format(&self, value: f64, decimals: Option<usize>) -> String {
+        let (v, suffix) = self.scale(value);
+        format!("{}{suffix}", format_decimals(v, decimals))
+    }
+}
+
+/// Formats a number with `decimals` decimals, or with up to two decimals without trailing zeros.
+pub fn format_decimals(v: f64, decimals: Option<usize>) -> String {
+    match decimals {
+        Some(decimals) => format!("{v:.decimals$}"),
+        None => {
+            let s = format!("{v:.2}");
+            let s = s.trim_end_matches('0').trim_end_matches('.');
+            if s == "-0" { "0" } else { s }.to_string()
+        }
+    }
+}
+
+/// Divides the value by `base` while it fills the next unit.
+fn step<'a>(value: f64, base: f64, units: &[&'a str]) -> (f64, &'a str) {
+    let mut v = value;
+    let mut index = 0;
+    while v.abs() >= base && index < units.len() - 1 {
+        v /= base;
+        index += 1;
+    }
+    (v, units[index])
+}
+
+fn bytes(value: f64, per: &str) -> (f64, String) {
+    let (v, unit) = step(value, 1024.0, &BYTES);
+    (v, format!(" {unit}{per}"))
+}
+
+fn bits(value: f64, per: &str) -> (f64, String) {
+    let (v, unit) = step(value, 1000.0, &BITS);
+    (v, format!(" {unit}{per}"))
+}
+
+fn duration(nanos: f64) -> (f64, String) {
+    let (unit, length) = DURATIONS
+        .iter()
+        .rev()
+        .find(|(_, length)| nanos.abs() >= *length)
+        .unwrap_or(&DURATIONS[0]);
+    (nanos / length, format!(" {unit}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Unit::parse("default", None), None);
+        assert_eq!(Unit::parse("Bytes", None), Some(Unit::Bytes));
+        assert_eq!(Unit::parse("percent-1", None), Some(Unit::PercentUnit));
+        assert_eq!(
+            Unit::parse("custom", Some("req")),
+            Some(Unit::Custom("req".to_string()))
+        );
+        assert_eq!(Unit::parse("custom", Some("")), None);
+        assert_eq!(
+            Unit::parse("widgets", None),
+            Some(Unit::Custom("widgets".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(Unit::Bytes.format(1610612736.0, None), "1.5 GiB");
+        assert_eq!(Unit::Bytes.format(512.0, None), "512 B");
+        assert_eq!(Unit::Kilobytes.format(2048.0, Some(1)), "2.0 MiB");
+        assert_eq!(Unit::BytesPerSecond.format(-3072.0, None), "-3 KiB/s");
+        assert_eq!(Unit::BitsPerSecond.format(2_500_000.0, None), "2.5 Mb/s");
+        assert_eq!(Unit::Milliseconds.format(0.25, None), "250 us");
+        assert_eq!(Unit::Milliseconds.format(1500.0, None), "1.5 s");
+        assert_eq!(Unit::Seconds.format(5400.0, None), "1.5 h");
+        assert_eq!(Unit::Nanoseconds.format(0.0, None), "0 ns");
+        assert_eq!(Unit::Numbers.format(1234567.0, None), "1.23M");
+        assert_eq!(Unit::PercentUnit.format(0.125, Some(1)), "12.5%");
+        assert_eq!(Unit::Custom("req".to_string()).format(3.0, None), "3 req");
+        assert_eq!(format_decimals(-0.001, None), "0");
+    }
+}
@@ -70,6 +70,8 @@ impl From<promql::MetricsQueryRequest> for cluster_rpc::MetricsQueryRequest {
             query: Some(req_query),
             timeout: 0,
             no_cache: req.no_cache.unwrap_or_default(),
+            shard: 0,
+            shards: 0,
         }
     }
 }
@@ -35,7 +35,7 @@ pub async fn token_validator(
 ) -> Result<ServiceRequest, (Error, ServiceRequest)> {
     use actix_web::error::ErrorForbidden;
 
-    use super::validator::check_permissions;
+    use super::validator::is_request_allowed;
     use crate::common::utils::auth::V2_API_PREFIX;
 
     let user;
@@ -112,9 +112,7 @@ pub async fn token_validator(
                             header::HeaderName::from_static("user_id"),
                             header::HeaderValue::from_str(&res.0.user_email).unwrap(),
                         );
-                        if auth_info.bypass_check
-                            || check_permissions(user_id, auth_info, user.role, user.is_external)
-                                .await
+                        if is_request_allowed(user_id, auth_info, user.role, user.is_external).await
                         {
                             Ok(req)
                         } else {
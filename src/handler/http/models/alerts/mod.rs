@@ -94,6 +94,34 @@ pub struct Alert {
     #[serde(default)]
     #[schema(read_only)]
     pub last_edited_by: Option<String>,
+
+    /// Contrast analysis attached to the trigger record when the alert fires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation: Option<meta_alerts::CorrelationConfig>,
+
+    /// Dashboard panel the alert was created from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub panel_source: Option<meta_alerts::PanelSource>,
+
+    /// Makes this a composite alert, firing on the firing state of other
+    /// alerts. The query condition and the stream are not used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub composite: Option<meta_alerts::composite::CompositeCondition>,
+
+    /// Makes this a multi-condition alert, evaluating several queries with
+    /// their own thresholds. The query condition is not used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multi_condition: Option<meta_alerts::multi_condition::MultiCondition>,
+
+    /// Makes this an anomaly detection alert, comparing the aggregate of the
+    /// period with the same period of the previous days.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anomaly: Option<meta_alerts::anomaly::AnomalyCondition>,
+
+    /// Id of the escalation policy notified step by step while the alert fires
+    /// and is not acknowledged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub escalation_policy: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema, PartialEq)]
@@ -304,6 +332,12 @@ impl From<(meta_alerts::alert::Alert, Option<Trigger>)> for Alert {
             owner: alert.owner,
             updated_at: alert.updated_at.map(|t| t.timestamp()),
             last_edited_by: alert.last_edited_by,
+            correlation: alert.correlation,
+            panel_source: alert.panel_source,
+            composite: alert.composite,
+            multi_condition: alert.multi_condition,
+            anomaly: alert.anomaly,
+            escalation_policy: alert.escalation_policy,
         }
     }
 }
@@ -475,6 +509,12 @@ impl From<Alert> for meta_alerts::alert::Alert {
         alert.enabled = value.enabled;
         alert.tz_offset = value.tz_offset;
         alert.owner = value.owner;
+        alert.correlation = value.correlation;
+        alert.panel_source = value.panel_source;
+        alert.composite = value.composite;
+        alert.multi_condition = value.multi_condition;
+        alert.anomaly = value.anomaly;
+        alert.escalation_policy = value.escalation_policy;
 
         alert
     }
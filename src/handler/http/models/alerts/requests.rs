@@ -13,12 +13,13 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use config::meta::alerts::alert as meta_alerts;
+use config::meta::alerts::{PanelSource, alert as meta_alerts};
 use serde::Deserialize;
 use svix_ksuid::Ksuid;
 use utoipa::ToSchema;
 
-use super::{Alert, StreamType};
+use super::{Alert, StreamType, TriggerCondition};
+use crate::handler::http::models::destinations::{Destination, Template};
 
 /// HTTP request body for `CreateAlert` endpoint.
 #[derive(Clone, Debug, Deserialize, ToSchema)]
@@ -31,10 +32,52 @@ pub struct CreateAlertRequestBody {
     pub alert: Alert,
 }
 
+/// HTTP request body for `SyncAlerts` endpoint.
+#[derive(Clone, Debug, Deserialize, ToSchema)]
+pub struct SyncAlertsRequestBody {
+    /// The full set of alerts of the org. The alerts of the org missing from it are deleted.
+    pub alerts: Vec<CreateAlertRequestBody>,
+
+    /// Optional full set of the alert destinations of the org. When given, the alert
+    /// destinations missing from it are deleted.
+    #[serde(default)]
+    pub destinations: Option<Vec<Destination>>,
+
+    /// Optional full set of the templates of the org. When given, the templates missing from it
+    /// are deleted.
+    #[serde(default)]
+    pub templates: Option<Vec<Template>>,
+
+    /// Set to `true` to only return the changes the sync would make.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
 /// HTTP request body for `UpdateAlert` endpoint.
 #[derive(Clone, Debug, Deserialize, ToSchema)]
 pub struct UpdateAlertRequestBody(pub Alert);
 
+/// HTTP request body for `CreateAlertFromPanel` endpoint.
+#[derive(Clone, Debug, Deserialize, ToSchema)]
+pub struct CreateAlertFromPanelRequestBody {
+    pub name: String,
+
+    /// The dashboard panel query the alert is built from, and the condition
+    /// on its results.
+    pub panel_source: PanelSource,
+
+    #[serde(default)]
+    pub trigger_condition: TriggerCondition,
+
+    pub destinations: Vec<String>,
+
+    #[serde(default)]
+    pub description: String,
+
+    #[serde(default)]
+    pub enabled: bool,
+}
+
 /// HTTP request body for `MoveAlerts` endpoint.
 #[derive(Clone, Debug, Deserialize, ToSchema)]
 pub struct MoveAlertsRequestBody {
@@ -45,6 +88,16 @@ pub struct MoveAlertsRequestBody {
     pub dst_folder_id: String,
 }
 
+/// HTTP request body for `BacktestAlert` endpoint.
+#[derive(Clone, Debug, Deserialize, ToSchema)]
+pub struct BacktestAlertRequestBody {
+    /// Start of the replayed time range, in microseconds.
+    pub start_time: i64,
+
+    /// End of the replayed time range, in microseconds.
+    pub end_time: i64,
+}
+
 /// HTTP URL query component that contains parameters for listing alerts.
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
 #[into_params(style = Form, parameter_in = Query)]
@@ -71,6 +124,10 @@ pub struct ListAlertsQuery {
     /// Optional enabled filter parameter.
     pub enabled: Option<bool>,
 
+    /// Optional workspace ID filter parameter. Lists the alerts which belong
+    /// to the workspace or are shared with it.
+    pub workspace: Option<String>,
+
     /// The optional number of alerts to retrieve. If not set then all alerts
     /// that match the query parameters will be returned.
     pub page_size: Option<u64>,
@@ -81,6 +138,22 @@ pub struct ListAlertsQuery {
     pub page_idx: Option<u64>,
 }
 
+/// HTTP URL query component that contains parameters for getting the history of an alert.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(style = Form, parameter_in = Query)]
+#[serde(rename_all = "snake_case")]
+#[into_params(rename_all = "snake_case")]
+pub struct AlertHistoryQuery {
+    /// Optional start of the time range, in microseconds. Defaults to a week before the end.
+    pub start_time: Option<i64>,
+
+    /// Optional end of the time range, in microseconds. Defaults to now.
+    pub end_time: Option<i64>,
+
+    /// Optional maximum number of transitions to return.
+    pub size: Option<i64>,
+}
+
 /// HTTP URL query component that contains parameters for enabling alerts.
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
 #[into_params(style = Form, parameter_in = Query)]
@@ -96,6 +169,19 @@ impl From<CreateAlertRequestBody> for meta_alerts::Alert {
     }
 }
 
+impl From<CreateAlertFromPanelRequestBody> for meta_alerts::Alert {
+    fn from(value: CreateAlertFromPanelRequestBody) -> Self {
+        let mut alert = meta_alerts::Alert::default();
+        alert.name = value.name;
+        alert.trigger_condition = value.trigger_condition.into();
+        alert.destinations = value.destinations;
+        alert.description = value.description;
+        alert.enabled = value.enabled;
+        alert.panel_source = Some(value.panel_source);
+        alert
+    }
+}
+
 impl From<UpdateAlertRequestBody> for meta_alerts::Alert {
     fn from(value: UpdateAlertRequestBody) -> Self {
         value.0.into()
@@ -113,6 +199,7 @@ impl ListAlertsQuery {
                 .map(|stream_type| (stream_type.into(), self.stream_name)),
             enabled: self.enabled,
             owner: self.owner,
+            alert_ids: None,
             page_size_and_idx: self
                 .page_size
                 .map(|page_size| (page_size, self.page_idx.unwrap_or(0))),
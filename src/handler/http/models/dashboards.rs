@@ -15,9 +15,10 @@
 
 use chrono::{DateTime, FixedOffset, Utc};
 use config::meta::{
-    dashboards::{Dashboard as MetaDashboard, v1, v2, v3, v4, v5},
+    dashboards::{Dashboard as MetaDashboard, v1, v2, v3, v4, v5, v6},
     folder::Folder as MetaFolder,
 };
+use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use utoipa::ToSchema;
@@ -42,6 +43,16 @@ pub struct UpdateDashboardRequestBody(JsonValue);
 #[derive(Debug, Serialize, ToSchema)]
 pub struct UpdateDashboardResponseBody(DashboardDetails);
 
+/// HTTP request body for `ValidateDashboard` endpoint.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ValidateDashboardRequestBody(JsonValue);
+
+impl From<ValidateDashboardRequestBody> for JsonValue {
+    fn from(value: ValidateDashboardRequestBody) -> Self {
+        value.0
+    }
+}
+
 /// HTTP URL query component that contains parameters for listing dashboards.
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
 #[into_params(style = Form, parameter_in = Query)]
@@ -60,6 +71,12 @@ pub struct ListDashboardsQuery {
     /// dashboards.
     title: Option<String>,
 
+    /// Optional workspace ID filter parameter
+    ///
+    /// Lists the dashboards in all folders which belong to the workspace or
+    /// are shared with it.
+    workspace: Option<String>,
+
     /// The optional number of dashboards to retrieve. If not set then all
     /// dashboards that match the query parameters will be returned.
     ///
@@ -87,6 +104,8 @@ pub struct ListDashboardsResponseBodyItem {
     pub v4: Option<v4::Dashboard>,
     #[deprecated(note = "use GetDashboard endpoint to get dashboard details")]
     pub v5: Option<v5::Dashboard>,
+    #[deprecated(note = "use GetDashboard endpoint to get dashboard details")]
+    pub v6: Option<v6::Dashboard>,
 
     pub version: i32,
     pub hash: String,
@@ -123,6 +142,27 @@ pub struct MoveDashboardsRequestBody {
     pub dst_folder_id: String,
 }
 
+/// HTTP request body for `CopyDashboard` endpoint.
+#[derive(Clone, Debug, Deserialize, ToSchema)]
+pub struct CopyDashboardRequestBody {
+    /// Organization to copy the dashboard into.
+    pub target_org: String,
+
+    /// Folder of the target organization, the default folder if not set.
+    #[serde(default)]
+    pub folder_id: Option<String>,
+
+    /// Streams to rename in the copy, from the stream name in the original
+    /// dashboard to the stream name in the target organization.
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub stream_mapping: HashMap<String, String>,
+
+    /// Title of the copy, the title of the original dashboard if not set.
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
 /// Version-specific dashboard details and hash.
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -132,6 +172,7 @@ pub struct DashboardDetails {
     pub v3: Option<v3::Dashboard>,
     pub v4: Option<v4::Dashboard>,
     pub v5: Option<v5::Dashboard>,
+    pub v6: Option<v6::Dashboard>,
     pub version: i32,
     pub hash: String,
     pub updated_at: i64,
@@ -174,6 +215,10 @@ impl From<MetaDashboard> for UpdateDashboardResponseBody {
 }
 
 impl ListDashboardsQuery {
+    pub fn workspace(&self) -> Option<&str> {
+        self.workspace.as_deref()
+    }
+
     pub fn into(self, org_id: &str) -> config::meta::dashboards::ListDashboardsParams {
         let mut query = match &self {
             Self {
@@ -198,6 +243,13 @@ impl ListDashboardsQuery {
             Self {
                 folder: None,
                 title: None,
+                workspace: Some(_),
+                ..
+            } => config::meta::dashboards::ListDashboardsParams::new(org_id),
+            Self {
+                folder: None,
+                title: None,
+                workspace: None,
                 ..
             } => {
                 // To preserve backwards-compatability when no filter parameters
@@ -258,6 +310,7 @@ impl From<(MetaFolder, MetaDashboard)> for ListDashboardsResponseBodyItem {
             v3: dashboard.v3,
             v4: dashboard.v4,
             v5: dashboard.v5,
+            v6: dashboard.v6,
         }
     }
 }
@@ -271,6 +324,7 @@ impl From<MetaDashboard> for DashboardDetails {
             v3: value.v3,
             v4: value.v4,
             v5: value.v5,
+            v6: value.v6,
             hash: value.hash,
             updated_at: value.updated_at,
         }
@@ -304,10 +358,14 @@ fn parse_dashboard_request(value: JsonValue) -> Result<MetaDashboard, serde_json
             let inner: v4::Dashboard = serde_json::from_value(value)?;
             inner.into()
         }
-        _ => {
+        5 => {
             let inner: v5::Dashboard = serde_json::from_value(value)?;
             inner.into()
         }
+        _ => {
+            let inner: v6::Dashboard = serde_json::from_value(value)?;
+            inner.into()
+        }
     };
     Ok(dash)
 }
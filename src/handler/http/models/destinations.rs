@@ -18,7 +18,10 @@
 
 use std::fmt;
 
-use config::meta::destinations as meta_dest;
+use config::{
+    meta::{alerts::alert::Alert, destinations as meta_dest},
+    utils::json::{Map, Value},
+};
 use hashbrown::HashMap;
 #[cfg(feature = "enterprise")]
 use o2_enterprise::enterprise::actions::action_manager::ActionEndpoint;
@@ -32,13 +35,17 @@ impl From<meta_dest::Destination> for Destination {
         match value.module {
             meta_dest::Module::Alert {
                 template,
+                resolved_template,
                 destination_type,
+                grouping,
             } => match destination_type {
                 meta_dest::DestinationType::Email(email) => Self {
                     name: value.name,
                     emails: email.recipients,
                     template: Some(template),
+                    resolved_template,
                     destination_type: DestinationType::Email,
+                    grouping,
                     ..Default::default()
                 },
                 meta_dest::DestinationType::Http(endpoint) => Self {
@@ -56,17 +63,57 @@ impl From<meta_dest::Destination> for Destination {
                     #[cfg(not(feature = "enterprise"))]
                     destination_type: DestinationType::Http,
                     template: Some(template),
+                    resolved_template,
                     #[cfg(feature = "enterprise")]
                     action_id: endpoint.action_id,
                     output_format: endpoint.output_format,
+                    grouping,
                     ..Default::default()
                 },
                 meta_dest::DestinationType::Sns(aws_sns) => Self {
                     name: value.name,
                     template: Some(template),
+                    resolved_template,
                     sns_topic_arn: Some(aws_sns.sns_topic_arn),
                     aws_region: Some(aws_sns.aws_region),
                     destination_type: DestinationType::Sns,
+                    grouping,
+                    ..Default::default()
+                },
+                meta_dest::DestinationType::PagerDuty(pagerduty) => Self {
+                    name: value.name,
+                    url: pagerduty.url,
+                    template: Some(template),
+                    resolved_template,
+                    routing_key: Some(pagerduty.routing_key),
+                    severity: Some(pagerduty.severity),
+                    destination_type: DestinationType::PagerDuty,
+                    grouping,
+                    ..Default::default()
+                },
+                meta_dest::DestinationType::Opsgenie(opsgenie) => Self {
+                    name: value.name,
+                    url: opsgenie.url,
+                    template: Some(template),
+                    resolved_template,
+                    api_key: Some(opsgenie.api_key),
+                    priority: Some(opsgenie.priority),
+                    auto_close: Some(opsgenie.auto_close),
+                    destination_type: DestinationType::Opsgenie,
+                    grouping,
+                    ..Default::default()
+                },
+                meta_dest::DestinationType::VictorOps(victorops) => Self {
+                    name: value.name,
+                    url: victorops.url,
+                    template: Some(template),
+                    resolved_template,
+                    api_key: Some(victorops.api_key),
+                    routing_key: Some(victorops.routing_key),
+                    message_type: Some(victorops.message_type),
+                    auto_close: Some(victorops.auto_close),
+                    destination_type: DestinationType::VictorOps,
+                    grouping,
                     ..Default::default()
                 },
             },
@@ -106,6 +153,34 @@ impl Destination {
                         sns_topic_arn: self.sns_topic_arn.ok_or(DestinationError::InvalidSns)?,
                         aws_region: self.aws_region.ok_or(DestinationError::InvalidSns)?,
                     }),
+                    DestinationType::PagerDuty => {
+                        meta_dest::DestinationType::PagerDuty(meta_dest::PagerDuty {
+                            routing_key: self
+                                .routing_key
+                                .ok_or(DestinationError::InvalidPagerDuty)?,
+                            severity: self.severity.unwrap_or_default(),
+                            url: self.url,
+                        })
+                    }
+                    DestinationType::Opsgenie => {
+                        meta_dest::DestinationType::Opsgenie(meta_dest::Opsgenie {
+                            api_key: self.api_key.ok_or(DestinationError::InvalidOpsgenie)?,
+                            priority: self.priority.unwrap_or_default(),
+                            auto_close: self.auto_close.unwrap_or(true),
+                            url: self.url,
+                        })
+                    }
+                    DestinationType::VictorOps => {
+                        meta_dest::DestinationType::VictorOps(meta_dest::VictorOps {
+                            api_key: self.api_key.ok_or(DestinationError::InvalidVictorOps)?,
+                            routing_key: self
+                                .routing_key
+                                .ok_or(DestinationError::InvalidVictorOps)?,
+                            message_type: self.message_type.unwrap_or_default(),
+                            auto_close: self.auto_close.unwrap_or(true),
+                            url: self.url,
+                        })
+                    }
                     #[cfg(feature = "enterprise")]
                     DestinationType::Action => {
                         if let Some(action_id) = self.action_id {
@@ -136,7 +211,9 @@ impl Destination {
                     name: self.name,
                     module: meta_dest::Module::Alert {
                         template,
+                        resolved_template: self.resolved_template,
                         destination_type,
+                        grouping: self.grouping,
                     },
                 })
             }
@@ -178,12 +255,32 @@ impl From<meta_dest::Template> for Template {
     }
 }
 
+impl TemplatePreviewRequest {
+    /// Returns the template and the sample alert to render it with.
+    pub fn into(self, org_id: &str) -> (meta_dest::Template, Alert, Vec<Map<String, Value>>) {
+        let alert = Alert {
+            org_id: org_id.to_string(),
+            name: self
+                .alert_name
+                .unwrap_or_else(|| "sample_alert".to_string()),
+            stream_name: self.stream_name.unwrap_or_else(|| "default".to_string()),
+            row_template: self.row_template.trim().to_string(),
+            context_attributes: self.context_attributes,
+            ..Default::default()
+        };
+        (self.template.into(org_id), alert, self.rows)
+    }
+}
+
 impl Template {
     pub fn into(self, org_id: &str) -> meta_dest::Template {
         let template_type = match self.template_type {
             DestinationType::Email => meta_dest::TemplateType::Email { title: self.title },
             DestinationType::Sns => meta_dest::TemplateType::Sns,
-            DestinationType::Http => meta_dest::TemplateType::Http,
+            DestinationType::Http
+            | DestinationType::PagerDuty
+            | DestinationType::Opsgenie
+            | DestinationType::VictorOps => meta_dest::TemplateType::Http,
             #[cfg(feature = "enterprise")]
             DestinationType::Action => meta_dest::TemplateType::Http,
         };
@@ -214,6 +311,10 @@ pub struct Destination {
     pub headers: Option<HashMap<String, String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub template: Option<String>,
+    /// Template of the notification sent when the alert stops firing, with its firing duration
+    /// and last value. Alert destinations without one only notify when the alert fires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_template: Option<String>,
     /// Required when `destination_type` is `Email`
     #[serde(default)]
     pub emails: Vec<String>,
@@ -222,6 +323,28 @@ pub struct Destination {
     pub sns_topic_arn: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub aws_region: Option<String>,
+    /// Required when `destination_type` is `Pagerduty` or `Victorops`, the integration key of
+    /// the PagerDuty service or the routing key of the VictorOps team. `url` optionally
+    /// overrides the endpoint of the incident destinations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub routing_key: Option<String>,
+    /// Severity of the PagerDuty incidents of the alerts without a `severity` context attribute
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<meta_dest::PagerDutySeverity>,
+    /// Required when `destination_type` is `Opsgenie` or `Victorops`, the key of the integration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    /// Priority of the Opsgenie alerts of the alerts without a `severity` context attribute
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<meta_dest::OpsgeniePriority>,
+    /// Message type of the VictorOps incidents of the alerts without a `severity` context
+    /// attribute
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_type: Option<meta_dest::VictorOpsMessageType>,
+    /// Whether the Opsgenie or VictorOps incidents are closed when the alert recovers, defaults
+    /// to true
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_close: Option<bool>,
     #[serde(rename = "type")]
     #[serde(default)]
     pub destination_type: DestinationType,
@@ -231,6 +354,9 @@ pub struct Destination {
     pub action_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub output_format: Option<meta_dest::HTTPOutputFormat>,
+    /// Batches the notifications of alerts firing together, only for alert destinations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grouping: Option<meta_dest::NotificationGrouping>,
 }
 
 #[derive(Serialize, Debug, Default, PartialEq, Eq, Deserialize, Clone, ToSchema)]
@@ -240,6 +366,12 @@ pub enum DestinationType {
     Http,
     Email,
     Sns,
+    #[serde(rename = "pagerduty")]
+    PagerDuty,
+    #[serde(rename = "opsgenie")]
+    Opsgenie,
+    #[serde(rename = "victorops")]
+    VictorOps,
     #[cfg(feature = "enterprise")]
     Action,
 }
@@ -249,6 +381,9 @@ impl From<&str> for DestinationType {
         match value.to_lowercase().as_str() {
             "email" => DestinationType::Email,
             "sns" => DestinationType::Sns,
+            "pagerduty" => DestinationType::PagerDuty,
+            "opsgenie" => DestinationType::Opsgenie,
+            "victorops" => DestinationType::VictorOps,
             #[cfg(feature = "enterprise")]
             "action" => DestinationType::Action,
             _ => DestinationType::Http,
@@ -262,6 +397,9 @@ impl fmt::Display for DestinationType {
             DestinationType::Email => write!(f, "email"),
             DestinationType::Http => write!(f, "http"),
             DestinationType::Sns => write!(f, "sns"),
+            DestinationType::PagerDuty => write!(f, "pagerduty"),
+            DestinationType::Opsgenie => write!(f, "opsgenie"),
+            DestinationType::VictorOps => write!(f, "victorops"),
             #[cfg(feature = "enterprise")]
             DestinationType::Action => write!(f, "action"),
         }
@@ -286,3 +424,32 @@ pub struct Template {
     #[serde(default)]
     pub title: String,
 }
+
+/// Request to render a template against sample alert data.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct TemplatePreviewRequest {
+    #[serde(flatten)]
+    pub template: Template,
+    /// Name of the sample alert, defaults to `sample_alert`
+    #[serde(default)]
+    pub alert_name: Option<String>,
+    /// Stream of the sample alert, defaults to `default`
+    #[serde(default)]
+    pub stream_name: Option<String>,
+    /// Row template of the sample alert
+    #[serde(default)]
+    pub row_template: String,
+    #[serde(default)]
+    pub context_attributes: Option<HashMap<String, String>>,
+    /// Rows matched by the sample alert, a few example log rows are used when empty
+    #[serde(default)]
+    #[schema(value_type = Vec<Object>)]
+    pub rows: Vec<Map<String, Value>>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct TemplatePreviewResponse {
+    /// Rendered email subject, the template name for the other template types
+    pub title: String,
+    pub body: String,
+}
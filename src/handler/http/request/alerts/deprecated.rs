@@ -34,6 +34,7 @@ use crate::{
     service::{
         alerts::alert::{self, AlertError},
         db::scheduler,
+        users,
     },
 };
 
@@ -69,9 +70,7 @@ pub async fn save_alert(
     // Hack for frequency: convert minutes to seconds
     let mut alert = alert.into_inner();
     alert.trigger_condition.frequency *= 60;
-    if alert.owner.clone().filter(|o| !o.is_empty()).is_none() {
-        alert.owner = Some(user_email.user_id.clone());
-    };
+    alert.owner = Some(users::resource_owner(&user_email.user_id, alert.owner.as_deref()).await);
     alert.last_edited_by = Some(user_email.user_id);
     alert.updated_at = Some(datetime_now());
     alert.set_last_satisfied_at(None);
@@ -0,0 +1,226 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpResponse, delete, get, patch, post, put, web};
+use config::meta::alerts::escalation::{EscalationPolicy, EscalationPolicyList};
+use svix_ksuid::Ksuid;
+
+use crate::{
+    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    service::alerts::escalation::{self, EscalationError},
+};
+
+fn map_error(e: EscalationError) -> HttpResponse {
+    match e {
+        EscalationError::NotFound | EscalationError::NotEscalating => {
+            MetaHttpResponse::not_found(e)
+        }
+        EscalationError::AlreadyExists | EscalationError::InUse(_) => MetaHttpResponse::conflict(e),
+        EscalationError::Invalid(_) => MetaHttpResponse::bad_request(e),
+        e => MetaHttpResponse::internal_error(e),
+    }
+}
+
+/// CreateEscalationPolicy
+///
+/// Alerts using the policy notify its steps one after the other while they fire and are not
+/// acknowledged, each step once its delay passed since the alert started firing.
+///
+/// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "CreateEscalationPolicy",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = EscalationPolicy, description = "Escalation policy details", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = EscalationPolicy),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 409, description = "Conflict", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/escalation_policies")]
+pub async fn create_escalation_policy(
+    path: web::Path<String>,
+    req: web::Json<EscalationPolicy>,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match escalation::create(&org_id, req.into_inner()).await {
+        Ok(policy) => Ok(MetaHttpResponse::json(policy)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// UpdateEscalationPolicy
+///
+/// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "UpdateEscalationPolicy",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("policy_id" = String, Path, description = "Escalation policy ID"),
+    ),
+    request_body(content = EscalationPolicy, description = "Escalation policy details", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = EscalationPolicy),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/escalation_policies/{policy_id}")]
+pub async fn update_escalation_policy(
+    path: web::Path<(String, String)>,
+    req: web::Json<EscalationPolicy>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, policy_id) = path.into_inner();
+    match escalation::update(&org_id, &policy_id, req.into_inner()).await {
+        Ok(policy) => Ok(MetaHttpResponse::json(policy)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// ListEscalationPolicies
+///
+/// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "ListEscalationPolicies",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = EscalationPolicyList),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/escalation_policies")]
+pub async fn list_escalation_policies(path: web::Path<String>) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match escalation::list(&org_id).await {
+        Ok(list) => Ok(MetaHttpResponse::json(EscalationPolicyList { list })),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// GetEscalationPolicy
+///
+/// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "GetEscalationPolicy",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("policy_id" = String, Path, description = "Escalation policy ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = EscalationPolicy),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/escalation_policies/{policy_id}")]
+pub async fn get_escalation_policy(
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, policy_id) = path.into_inner();
+    match escalation::get(&org_id, &policy_id).await {
+        Ok(policy) => Ok(MetaHttpResponse::json(policy)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteEscalationPolicy
+///
+/// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "DeleteEscalationPolicy",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("policy_id" = String, Path, description = "Escalation policy ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+        (status = 409, description = "Conflict", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/escalation_policies/{policy_id}")]
+pub async fn delete_escalation_policy(
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, policy_id) = path.into_inner();
+    match escalation::delete(&org_id, &policy_id).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Escalation policy deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// AcknowledgeAlert
+///
+/// Stops the escalation of the firing alert, the later steps of its escalation policy are not
+/// notified. The escalation ends when the alert recovers.
+///
+/// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "AcknowledgeAlert",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("alert_id" = Ksuid, Path, description = "Alert ID"),
+    ),
+    responses(
+        (status = 200, description = "Success",  content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[patch("/v2/{org_id}/alerts/{alert_id}/acknowledge")]
+pub async fn acknowledge_alert(
+    path: web::Path<(String, Ksuid)>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, alert_id) = path.into_inner();
+    match escalation::acknowledge(&org_id, alert_id, &user_email.user_id).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Alert acknowledged")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
@@ -15,8 +15,13 @@
 
 use actix_web::{HttpRequest, HttpResponse, delete, get, http::StatusCode, patch, post, put, web};
 use config::meta::{
-    alerts::alert::Alert as MetaAlert,
+    alerts::{
+        AlertBacktest, AlertMetrics, AlertsSyncPlan, alert::Alert as MetaAlert,
+        history::AlertHistory,
+    },
+    folder::DEFAULT_FOLDER,
     triggers::{Trigger, TriggerModule},
+    workspace::ResourceType,
 };
 use hashbrown::HashMap;
 use infra::db::{ORM_CLIENT, connect_to_orm};
@@ -27,22 +32,34 @@ use crate::{
     handler::http::{
         models::alerts::{
             requests::{
+                AlertHistoryQuery, BacktestAlertRequestBody, CreateAlertFromPanelRequestBody,
                 CreateAlertRequestBody, EnableAlertQuery, ListAlertsQuery, MoveAlertsRequestBody,
-                UpdateAlertRequestBody,
+                SyncAlertsRequestBody, UpdateAlertRequestBody,
             },
             responses::{EnableAlertResponseBody, GetAlertResponseBody, ListAlertsResponseBody},
         },
         request::dashboards::get_folder,
     },
     service::{
-        alerts::alert::{self, AlertError},
-        db::scheduler,
+        alerts::{
+            alert::{self, AlertError},
+            backtest,
+            bulk::{self, SyncError},
+            history, metrics, panel,
+        },
+        db::{
+            alerts::{destinations::DestinationError, templates::TemplateError},
+            scheduler,
+        },
+        users, workspaces,
     },
 };
 
 #[allow(deprecated)]
 pub mod deprecated;
 pub mod destinations;
+pub mod escalations;
+pub mod silences;
 pub mod templates;
 
 impl From<AlertError> for HttpResponse {
@@ -78,10 +95,58 @@ impl From<AlertError> for HttpResponse {
             AlertError::PermissionDenied => MetaHttpResponse::forbidden("Unauthorized access"),
             AlertError::UserNotFound => MetaHttpResponse::forbidden("Unauthorized access"),
             AlertError::AlertIdMissing => MetaHttpResponse::bad_request(value),
+            AlertError::PanelSourceMissing => MetaHttpResponse::bad_request(value),
+            AlertError::PanelSource(_) => MetaHttpResponse::bad_request(value),
+            AlertError::BacktestRealtime => MetaHttpResponse::bad_request(value),
+            AlertError::BacktestTimeRange => MetaHttpResponse::bad_request(value),
+            AlertError::Backtest(_) => MetaHttpResponse::internal_error(value),
+            AlertError::HistoryTimeRange => MetaHttpResponse::bad_request(value),
+            AlertError::InvalidComposite(_) => MetaHttpResponse::bad_request(value),
+            AlertError::InvalidMultiCondition(_) => MetaHttpResponse::bad_request(value),
+            AlertError::InvalidAnomaly(_) => MetaHttpResponse::bad_request(value),
+            AlertError::InvalidRowTemplate(_) => MetaHttpResponse::bad_request(value),
+            AlertError::InvalidEscalationPolicy(_) => MetaHttpResponse::bad_request(value),
         }
     }
 }
 
+impl From<SyncError> for HttpResponse {
+    fn from(value: SyncError) -> Self {
+        match &value {
+            SyncError::List { .. }
+            | SyncError::Alert {
+                source: AlertError::InfraError(_),
+                ..
+            }
+            | SyncError::Destination {
+                source: DestinationError::InfraError(_),
+                ..
+            }
+            | SyncError::Template {
+                source: TemplateError::InfraError(_),
+                ..
+            } => MetaHttpResponse::internal_error(value),
+            _ => MetaHttpResponse::bad_request(value),
+        }
+    }
+}
+
+/// Checks that the members of the workspace of the alert permit the user to read it, or to
+/// change it when `write` is set.
+async fn check_workspace(
+    org_id: &str,
+    user_id: &str,
+    alert_id: Ksuid,
+    write: bool,
+) -> Result<(), AlertError> {
+    let alert_id = alert_id.to_string();
+    if workspaces::can_access(org_id, user_id, ResourceType::Alert, &alert_id, write).await? {
+        Ok(())
+    } else {
+        Err(AlertError::PermissionDenied)
+    }
+}
+
 /// CreateAlert
 ///
 /// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"create"}#
@@ -113,9 +178,7 @@ pub async fn create_alert(
 
     let folder_id = get_folder(req);
     let mut alert: MetaAlert = req_body.into();
-    if alert.owner.clone().filter(|o| !o.is_empty()).is_none() {
-        alert.owner = Some(user_email.user_id.clone());
-    }
+    alert.owner = Some(users::resource_owner(&user_email.user_id, alert.owner.as_deref()).await);
     alert.last_edited_by = Some(user_email.user_id);
 
     let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
@@ -129,6 +192,86 @@ pub async fn create_alert(
     }
 }
 
+/// CreateAlertFromPanel
+///
+/// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "CreateAlertFromPanel",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+      ),
+    request_body(content = CreateAlertFromPanelRequestBody, description = "Dashboard panel and alert condition", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 400, description = "Error",   content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/v2/{org_id}/alerts/from_panel")]
+pub async fn create_alert_from_panel(
+    path: web::Path<String>,
+    req_body: web::Json<CreateAlertFromPanelRequestBody>,
+    user_email: UserEmail,
+    req: HttpRequest,
+) -> HttpResponse {
+    let org_id = path.into_inner();
+    let req_body = req_body.into_inner();
+
+    let folder_id = get_folder(req);
+    let mut alert: MetaAlert = req_body.into();
+    alert.owner = Some(user_email.user_id.clone());
+    alert.last_edited_by = Some(user_email.user_id.clone());
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    match panel::create_from_panel(client, &org_id, &folder_id, &user_email.user_id, alert).await {
+        Ok(v) => MetaHttpResponse::json(
+            MetaHttpResponse::message(StatusCode::OK, "Alert saved")
+                .with_id(v.id.map(|id| id.to_string()).unwrap_or_default())
+                .with_name(v.name),
+        ),
+        Err(e) => e.into(),
+    }
+}
+
+/// SyncAlertWithPanel
+///
+/// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "SyncAlertWithPanel",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("alert_id" = Ksuid, Path, description = "Alert ID"),
+    ),
+    responses(
+        (status = 200, description = "Success",  content_type = "application/json", body = HttpResponse),
+        (status = 400, description = "Error",    content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[patch("/v2/{org_id}/alerts/{alert_id}/sync_panel")]
+async fn sync_alert_with_panel(
+    path: web::Path<(String, Ksuid)>,
+    user_email: UserEmail,
+) -> HttpResponse {
+    let (org_id, alert_id) = path.into_inner();
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    match panel::sync_with_panel(client, &org_id, alert_id, &user_email.user_id).await {
+        Ok(_) => MetaHttpResponse::ok("Alert synced with panel"),
+        Err(e) => e.into(),
+    }
+}
+
 /// GetAlert
 ///
 /// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"get"}#
@@ -145,12 +288,16 @@ pub async fn create_alert(
       ),
     responses(
         (status = 200, description = "Success",  content_type = "application/json", body = GetAlertResponseBody),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
         (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
     )
 )]
 #[get("/v2/{org_id}/alerts/{alert_id}")]
-async fn get_alert(path: web::Path<(String, Ksuid)>) -> HttpResponse {
+async fn get_alert(path: web::Path<(String, Ksuid)>, user_email: UserEmail) -> HttpResponse {
     let (org_id, alert_id) = path.into_inner();
+    if let Err(e) = check_workspace(&org_id, &user_email.user_id, alert_id, false).await {
+        return e.into();
+    }
 
     let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
     match alert::get_by_id(client, &org_id, alert_id).await {
@@ -184,6 +331,7 @@ async fn get_alert(path: web::Path<(String, Ksuid)>) -> HttpResponse {
     responses(
         (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
         (status = 400, description = "Error",   content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
     )
 )]
 #[put("/v2/{org_id}/alerts/{alert_id}")]
@@ -193,6 +341,9 @@ pub async fn update_alert(
     user_email: UserEmail,
 ) -> HttpResponse {
     let (org_id, alert_id) = path.into_inner();
+    if let Err(e) = check_workspace(&org_id, &user_email.user_id, alert_id, true).await {
+        return e.into();
+    }
     let req_body = req_body.into_inner();
 
     let mut alert: MetaAlert = req_body.into();
@@ -222,12 +373,16 @@ pub async fn update_alert(
     ),
     responses(
         (status = 200, description = "Success",  content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
         (status = 500, description = "Failure",  content_type = "application/json", body = HttpResponse),
     )
 )]
 #[delete("/v2/{org_id}/alerts/{alert_id}")]
-async fn delete_alert(path: web::Path<(String, Ksuid)>) -> HttpResponse {
+async fn delete_alert(path: web::Path<(String, Ksuid)>, user_email: UserEmail) -> HttpResponse {
     let (org_id, alert_id) = path.into_inner();
+    if let Err(e) = check_workspace(&org_id, &user_email.user_id, alert_id, true).await {
+        return e.into();
+    }
 
     let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
     match alert::delete_by_id(client, &org_id, alert_id).await {
@@ -269,6 +424,15 @@ async fn list_alerts(path: web::Path<String>, req: HttpRequest) -> HttpResponse
         return MetaHttpResponse::forbidden("");
     };
 
+    let workspace = query.workspace.clone();
+    let mut params = query.into(&org_id);
+    if let Some(workspace) = workspace {
+        match workspaces::resource_ids(&org_id, &workspace, ResourceType::Alert).await {
+            Ok(alert_ids) => params = params.with_alert_ids(alert_ids),
+            Err(e) => return e.into(),
+        }
+    }
+
     let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
     let scheduled_jobs = scheduler::list_by_org(&org_id, Some(TriggerModule::Alert))
         .await
@@ -277,20 +441,19 @@ async fn list_alerts(path: web::Path<String>, req: HttpRequest) -> HttpResponse
         .into_iter()
         .map(|t| (t.module_key.clone(), t))
         .collect();
-    let folders_and_alerts_scheduled_job =
-        match alert::list_v2(client, user_id, query.into(&org_id)).await {
-            Ok(f_a) => {
-                let f_a: Vec<_> = f_a
-                    .into_iter()
-                    .map(|(folder, alert)| {
-                        let key = alert.get_unique_key();
-                        (folder, alert, scheduled_jobs.remove(&key))
-                    })
-                    .collect();
-                f_a
-            }
-            Err(e) => return e.into(),
-        };
+    let folders_and_alerts_scheduled_job = match alert::list_v2(client, user_id, params).await {
+        Ok(f_a) => {
+            let f_a: Vec<_> = f_a
+                .into_iter()
+                .map(|(folder, alert)| {
+                    let key = alert.get_unique_key();
+                    (folder, alert, scheduled_jobs.remove(&key))
+                })
+                .collect();
+            f_a
+        }
+        Err(e) => return e.into(),
+    };
     let Ok(resp_body) = ListAlertsResponseBody::try_from(folders_and_alerts_scheduled_job) else {
         return MetaHttpResponse::internal_error("");
     };
@@ -314,13 +477,21 @@ async fn list_alerts(path: web::Path<String>, req: HttpRequest) -> HttpResponse
     ),
     responses(
         (status = 200, description = "Success",  content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
         (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
         (status = 500, description = "Failure",  content_type = "application/json", body = HttpResponse),
     )
 )]
 #[patch("/v2/{org_id}/alerts/{alert_id}/enable")]
-async fn enable_alert(path: web::Path<(String, Ksuid)>, req: HttpRequest) -> HttpResponse {
+async fn enable_alert(
+    path: web::Path<(String, Ksuid)>,
+    req: HttpRequest,
+    user_email: UserEmail,
+) -> HttpResponse {
     let (org_id, alert_id) = path.into_inner();
+    if let Err(e) = check_workspace(&org_id, &user_email.user_id, alert_id, true).await {
+        return e.into();
+    }
     let Ok(query) = web::Query::<EnableAlertQuery>::from_query(req.query_string()) else {
         return MetaHttpResponse::bad_request("Error parsing query parameters");
     };
@@ -354,13 +525,17 @@ async fn enable_alert(path: web::Path<(String, Ksuid)>, req: HttpRequest) -> Htt
     ),
     responses(
         (status = 200, description = "Success",  content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
         (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
         (status = 500, description = "Failure",  content_type = "application/json", body = HttpResponse),
     )
 )]
 #[patch("/v2/{org_id}/alerts/{alert_id}/trigger")]
-async fn trigger_alert(path: web::Path<(String, Ksuid)>) -> HttpResponse {
+async fn trigger_alert(path: web::Path<(String, Ksuid)>, user_email: UserEmail) -> HttpResponse {
     let (org_id, alert_id) = path.into_inner();
+    if let Err(e) = check_workspace(&org_id, &user_email.user_id, alert_id, true).await {
+        return e.into();
+    }
 
     let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
     match alert::trigger_by_id(client, &org_id, alert_id).await {
@@ -369,6 +544,185 @@ async fn trigger_alert(path: web::Path<(String, Ksuid)>) -> HttpResponse {
     }
 }
 
+/// BacktestAlert
+///
+/// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "BacktestAlert",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("alert_id" = Ksuid, Path, description = "Alert ID"),
+    ),
+    request_body(content = BacktestAlertRequestBody, description = "Time range to replay the alert over", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success",  content_type = "application/json", body = AlertBacktest),
+        (status = 400, description = "Error",    content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/v2/{org_id}/alerts/{alert_id}/backtest")]
+async fn backtest_alert(
+    path: web::Path<(String, Ksuid)>,
+    req_body: web::Json<BacktestAlertRequestBody>,
+) -> HttpResponse {
+    let (org_id, alert_id) = path.into_inner();
+    let req_body = req_body.into_inner();
+    match backtest::backtest(&org_id, alert_id, req_body.start_time, req_body.end_time).await {
+        Ok(result) => MetaHttpResponse::json(result),
+        Err(e) => e.into(),
+    }
+}
+
+/// GetAlertHistory
+///
+/// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "GetAlertHistory",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("alert_id" = Ksuid, Path, description = "Alert ID"),
+        AlertHistoryQuery,
+    ),
+    responses(
+        (status = 200, description = "Success",  content_type = "application/json", body = AlertHistory),
+        (status = 400, description = "Error",    content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/v2/{org_id}/alerts/{alert_id}/history")]
+async fn get_alert_history(
+    path: web::Path<(String, Ksuid)>,
+    query: web::Query<AlertHistoryQuery>,
+) -> HttpResponse {
+    let (org_id, alert_id) = path.into_inner();
+    let query = query.into_inner();
+    match history::list(
+        &org_id,
+        alert_id,
+        query.start_time,
+        query.end_time,
+        query.size,
+    )
+    .await
+    {
+        Ok(history) => MetaHttpResponse::json(history),
+        Err(e) => e.into(),
+    }
+}
+
+/// SyncAlerts
+///
+/// Replaces the alerts of the org, and optionally its alert destinations and templates, with
+/// the given full set, deleting what is missing from it. A dry run only returns the changes.
+///
+/// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "SyncAlerts",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = SyncAlertsRequestBody, description = "Full set of the alerts of the org", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = AlertsSyncPlan),
+        (status = 400, description = "Error",   content_type = "application/json", body = HttpResponse),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/alerts/bulk")]
+async fn sync_alerts(
+    path: web::Path<String>,
+    req_body: web::Json<SyncAlertsRequestBody>,
+    user_email: UserEmail,
+) -> HttpResponse {
+    let org_id = path.into_inner();
+    let req_body = req_body.into_inner();
+
+    let alerts = req_body
+        .alerts
+        .into_iter()
+        .map(|alert| {
+            let folder_id = alert
+                .folder_id
+                .clone()
+                .unwrap_or_else(|| DEFAULT_FOLDER.to_string());
+            (folder_id, MetaAlert::from(alert))
+        })
+        .collect();
+    let destinations = match req_body
+        .destinations
+        .map(|dests| {
+            dests
+                .into_iter()
+                .map(|dest| dest.into(org_id.clone()))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()
+    {
+        Ok(destinations) => destinations,
+        Err(e) => return e.into(),
+    };
+    let templates = req_body
+        .templates
+        .map(|tmpls| tmpls.into_iter().map(|tmpl| tmpl.into(&org_id)).collect());
+
+    match bulk::sync(
+        &org_id,
+        &user_email.user_id,
+        alerts,
+        destinations,
+        templates,
+        req_body.dry_run,
+    )
+    .await
+    {
+        Ok(plan) => MetaHttpResponse::json(plan),
+        Err(e) => e.into(),
+    }
+}
+
+/// GetAlertMetrics
+///
+/// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "GetAlertMetrics",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("alert_id" = Ksuid, Path, description = "Alert ID"),
+    ),
+    responses(
+        (status = 200, description = "Success",  content_type = "application/json", body = AlertMetrics),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/v2/{org_id}/alerts/{alert_id}/metrics")]
+async fn get_alert_metrics(path: web::Path<(String, Ksuid)>) -> HttpResponse {
+    let (org_id, alert_id) = path.into_inner();
+    match metrics::get(&org_id, alert_id).await {
+        Ok(alert_metrics) => MetaHttpResponse::json(alert_metrics),
+        Err(e) => e.into(),
+    }
+}
+
 /// MoveAlerts
 ///
 /// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"update"}#
@@ -0,0 +1,222 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{collections::HashMap, io::Error};
+
+use actix_web::{HttpResponse, delete, get, post, put, web};
+use config::meta::alerts::silences::{Silence, SilenceList, SilencedNotificationList};
+
+use crate::{
+    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    service::alerts::silences::{self, SilenceError},
+};
+
+fn map_error(e: SilenceError) -> HttpResponse {
+    match e {
+        SilenceError::NotFound => MetaHttpResponse::not_found(e),
+        SilenceError::Invalid(_) => MetaHttpResponse::bad_request(e),
+        e => MetaHttpResponse::internal_error(e),
+    }
+}
+
+/// CreateSilence
+///
+/// Mutes the notifications of the alerts matching every matcher between the start and end time.
+/// The alerts are still evaluated.
+///
+/// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "CreateSilence",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = Silence, description = "Silence details", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Silence),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/alert_silences")]
+pub async fn create_silence(
+    path: web::Path<String>,
+    req: web::Json<Silence>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match silences::create(&org_id, &user_email.user_id, req.into_inner()).await {
+        Ok(silence) => Ok(MetaHttpResponse::json(silence)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// UpdateSilence
+///
+/// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "UpdateSilence",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("silence_id" = String, Path, description = "Silence ID"),
+    ),
+    request_body(content = Silence, description = "Silence details", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Silence),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/alert_silences/{silence_id}")]
+pub async fn update_silence(
+    path: web::Path<(String, String)>,
+    req: web::Json<Silence>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, silence_id) = path.into_inner();
+    match silences::update(&org_id, &silence_id, req.into_inner()).await {
+        Ok(silence) => Ok(MetaHttpResponse::json(silence)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// ListSilences
+///
+/// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "ListSilences",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("include_expired" = Option<bool>, Query, description = "Include the silences which already ended"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = SilenceList),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/alert_silences")]
+pub async fn list_silences(
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    let include_expired = query
+        .get("include_expired")
+        .is_some_and(|v| v.parse::<bool>().unwrap_or_default());
+    match silences::list(&org_id, include_expired).await {
+        Ok(list) => Ok(MetaHttpResponse::json(SilenceList { list })),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// GetSilence
+///
+/// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "GetSilence",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("silence_id" = String, Path, description = "Silence ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Silence),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/alert_silences/{silence_id}")]
+pub async fn get_silence(path: web::Path<(String, String)>) -> Result<HttpResponse, Error> {
+    let (org_id, silence_id) = path.into_inner();
+    match silences::get(&org_id, &silence_id).await {
+        Ok(silence) => Ok(MetaHttpResponse::json(silence)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteSilence
+///
+/// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "DeleteSilence",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("silence_id" = String, Path, description = "Silence ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/alert_silences/{silence_id}")]
+pub async fn delete_silence(path: web::Path<(String, String)>) -> Result<HttpResponse, Error> {
+    let (org_id, silence_id) = path.into_inner();
+    match silences::delete(&org_id, &silence_id).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Silence deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// ListSilencedNotifications
+///
+/// Lists the latest alert notifications muted by the silence.
+///
+/// #{"ratelimit_module":"Alerts", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Alerts",
+    operation_id = "ListSilencedNotifications",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("silence_id" = String, Path, description = "Silence ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = SilencedNotificationList),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/alert_silences/{silence_id}/notifications")]
+pub async fn list_silenced_notifications(
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, silence_id) = path.into_inner();
+    match silences::list_silenced_notifications(&org_id, &silence_id).await {
+        Ok(list) => Ok(MetaHttpResponse::json(SilencedNotificationList { list })),
+        Err(e) => Ok(map_error(e)),
+    }
+}
@@ -19,7 +19,9 @@ use actix_web::{HttpRequest, HttpResponse, delete, get, http::StatusCode, post,
 
 use crate::{
     common::meta::http::HttpResponse as MetaHttpResponse,
-    handler::http::models::destinations::Template,
+    handler::http::models::destinations::{
+        Template, TemplatePreviewRequest, TemplatePreviewResponse,
+    },
     service::{alerts::templates, db::alerts::templates::TemplateError},
 };
 
@@ -219,3 +221,38 @@ async fn delete_template(path: web::Path<(String, String)>) -> Result<HttpRespon
         Err(e) => Ok(e.into()),
     }
 }
+
+/// PreviewTemplate
+///
+/// #{"ratelimit_module":"Templates", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Templates",
+    operation_id = "PreviewTemplate",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+      ),
+    request_body(content = TemplatePreviewRequest, description = "Template and sample alert data", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = TemplatePreviewResponse),
+        (status = 400, description = "Error",   content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/templates/preview")]
+pub async fn preview_template(
+    path: web::Path<String>,
+    req: web::Json<TemplatePreviewRequest>,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    let (tmpl, alert, rows) = req.into_inner().into(&org_id);
+    match templates::preview(&tmpl, &alert, rows).await {
+        Ok((title, body)) => Ok(MetaHttpResponse::json(TemplatePreviewResponse {
+            title,
+            body,
+        })),
+        Err(e) => Ok(e.into()),
+    }
+}
@@ -0,0 +1,190 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use std::io::Error;
+
+use actix_web::{HttpResponse, delete, get, post, put, web};
+use config::meta::annotations::{
+    Annotation, AnnotationList, AnnotationWebhookEvent, ListAnnotationsQuery,
+};
+
+use crate::{
+    common::meta::http::HttpResponse as MetaHttpResponse,
+    service::annotations::{self, AnnotationError},
+};
+
+fn map_error(e: AnnotationError) -> HttpResponse {
+    match e {
+        AnnotationError::NotFound => MetaHttpResponse::not_found(e),
+        AnnotationError::InfraError(e) => MetaHttpResponse::internal_error(e),
+        e => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// ListAnnotations
+///
+/// #{"ratelimit_module":"Annotations", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Annotations",
+    operation_id = "ListAnnotations",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ListAnnotationsQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = AnnotationList),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/annotations")]
+pub async fn list_annotations(
+    path: web::Path<String>,
+    query: web::Query<ListAnnotationsQuery>,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match annotations::list(&org_id, &query).await {
+        Ok(list) => Ok(MetaHttpResponse::json(AnnotationList { list })),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// CreateAnnotation
+///
+/// #{"ratelimit_module":"Annotations", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Annotations",
+    operation_id = "CreateAnnotation",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = Annotation, description = "Annotation data", content_type = "application/json", example = json!({"start_time": 1700000000000000_i64, "title": "Database failover", "tags": ["incident"], "link": "https://status.example.com/incidents/42"})),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Annotation),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/annotations")]
+pub async fn create_annotation(
+    path: web::Path<String>,
+    req: web::Json<Annotation>,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match annotations::create(&org_id, req.into_inner(), "api").await {
+        Ok(annotation) => Ok(MetaHttpResponse::json(annotation)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// CreateAnnotationFromWebhook
+///
+/// Creates an annotation from a CI/CD event, e.g. at the end of a deploy job. Service, environment
+/// and version are added as tags and the event time defaults to the time it is received.
+///
+/// #{"ratelimit_module":"Annotations", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Annotations",
+    operation_id = "CreateAnnotationFromWebhook",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = AnnotationWebhookEvent, description = "CI/CD event", content_type = "application/json", example = json!({"service": "checkout", "environment": "prod", "version": "v2.3.1", "url": "https://ci.example.com/pipelines/1234", "tags": ["deploy"]})),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Annotation),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/annotations/_webhook")]
+pub async fn create_from_webhook(
+    path: web::Path<String>,
+    req: web::Json<AnnotationWebhookEvent>,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match annotations::create_from_webhook(&org_id, req.into_inner()).await {
+        Ok(annotation) => Ok(MetaHttpResponse::json(annotation)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// UpdateAnnotation
+///
+/// #{"ratelimit_module":"Annotations", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Annotations",
+    operation_id = "UpdateAnnotation",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("annotation_id" = String, Path, description = "Annotation ID"),
+    ),
+    request_body(content = Annotation, description = "Annotation data", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Annotation),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/annotations/{annotation_id}")]
+pub async fn update_annotation(
+    path: web::Path<(String, String)>,
+    req: web::Json<Annotation>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, annotation_id) = path.into_inner();
+    match annotations::update(&org_id, &annotation_id, req.into_inner()).await {
+        Ok(annotation) => Ok(MetaHttpResponse::json(annotation)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteAnnotation
+///
+/// #{"ratelimit_module":"Annotations", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Annotations",
+    operation_id = "DeleteAnnotation",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("annotation_id" = String, Path, description = "Annotation ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/annotations/{annotation_id}")]
+pub async fn delete_annotation(path: web::Path<(String, String)>) -> Result<HttpResponse, Error> {
+    let (org_id, annotation_id) = path.into_inner();
+    match annotations::delete(&org_id, &annotation_id).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Annotation deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
@@ -0,0 +1,362 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpRequest, HttpResponse, delete, get, http::header, post, put, web};
+use config::meta::cases::{
+    Case, CaseAttachment, CaseComment, CaseList, CaseStatus, CaseTimelineEntry, CreateCaseRequest,
+    UpdateCaseRequest,
+};
+use serde::Deserialize;
+
+use crate::{
+    common::meta::http::HttpResponse as MetaHttpResponse,
+    service::cases::{self, CaseError},
+};
+
+#[derive(Debug, Deserialize)]
+struct ListQuery {
+    status: Option<CaseStatus>,
+    assignee: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimelineQuery {
+    format: Option<String>,
+}
+
+fn get_user_id(req: &HttpRequest) -> String {
+    req.headers()
+        .get("user_id")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn map_error(e: CaseError) -> HttpResponse {
+    match e {
+        CaseError::NotFound | CaseError::AttachmentNotFound => MetaHttpResponse::not_found(e),
+        CaseError::InfraError(_) | CaseError::ExportError(_) => MetaHttpResponse::internal_error(e),
+        e => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// CreateCase
+///
+/// #{"ratelimit_module":"Cases", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Cases",
+    operation_id = "CreateCase",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = CreateCaseRequest, description = "Case details", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Case),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/cases")]
+pub async fn create(
+    path: web::Path<String>,
+    body: web::Json<CreateCaseRequest>,
+    req: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match cases::create(&org_id, &get_user_id(&req), body.into_inner()).await {
+        Ok(case) => Ok(MetaHttpResponse::json(case)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// ListCases
+///
+/// #{"ratelimit_module":"Cases", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Cases",
+    operation_id = "ListCases",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("status" = Option<CaseStatus>, Query, description = "Only cases with this status"),
+        ("assignee" = Option<String>, Query, description = "Only cases assigned to this user"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = CaseList),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/cases")]
+pub async fn list(
+    path: web::Path<String>,
+    query: web::Query<ListQuery>,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match cases::list(&org_id, query.status, query.assignee.as_deref()).await {
+        Ok(list) => Ok(MetaHttpResponse::json(CaseList { list })),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// GetCase
+///
+/// #{"ratelimit_module":"Cases", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Cases",
+    operation_id = "GetCase",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Case id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Case),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/cases/{id}")]
+pub async fn get(path: web::Path<(String, String)>) -> Result<HttpResponse, Error> {
+    let (org_id, id) = path.into_inner();
+    match cases::get(&org_id, &id).await {
+        Ok(case) => Ok(MetaHttpResponse::json(case)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// UpdateCase
+///
+/// Changes the title, description, severity, status or assignee of a case.
+///
+/// #{"ratelimit_module":"Cases", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Cases",
+    operation_id = "UpdateCase",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Case id"),
+    ),
+    request_body(content = UpdateCaseRequest, description = "Fields to change", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Case),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/cases/{id}")]
+pub async fn update(
+    path: web::Path<(String, String)>,
+    body: web::Json<UpdateCaseRequest>,
+    req: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let (org_id, id) = path.into_inner();
+    match cases::update(&org_id, &id, &get_user_id(&req), body.into_inner()).await {
+        Ok(case) => Ok(MetaHttpResponse::json(case)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteCase
+///
+/// #{"ratelimit_module":"Cases", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Cases",
+    operation_id = "DeleteCase",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Case id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/cases/{id}")]
+pub async fn delete(path: web::Path<(String, String)>) -> Result<HttpResponse, Error> {
+    let (org_id, id) = path.into_inner();
+    match cases::delete(&org_id, &id).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Case deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// AddCaseAttachment
+///
+/// Attaches a query, records, a trace, a finding or a dashboard snapshot to a case.
+///
+/// #{"ratelimit_module":"Cases", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Cases",
+    operation_id = "AddCaseAttachment",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Case id"),
+    ),
+    request_body(content = CaseAttachment, description = "Attachment", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Case),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/cases/{id}/attachments")]
+pub async fn add_attachment(
+    path: web::Path<(String, String)>,
+    body: web::Json<CaseAttachment>,
+    req: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let (org_id, id) = path.into_inner();
+    match cases::add_attachment(&org_id, &id, &get_user_id(&req), body.into_inner()).await {
+        Ok(case) => Ok(MetaHttpResponse::json(case)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// RemoveCaseAttachment
+///
+/// #{"ratelimit_module":"Cases", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Cases",
+    operation_id = "RemoveCaseAttachment",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Case id"),
+        ("attachment_id" = String, Path, description = "Attachment id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Case),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/cases/{id}/attachments/{attachment_id}")]
+pub async fn remove_attachment(
+    path: web::Path<(String, String, String)>,
+    req: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let (org_id, id, attachment_id) = path.into_inner();
+    match cases::remove_attachment(&org_id, &id, &get_user_id(&req), &attachment_id).await {
+        Ok(case) => Ok(MetaHttpResponse::json(case)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// AddCaseComment
+///
+/// #{"ratelimit_module":"Cases", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Cases",
+    operation_id = "AddCaseComment",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Case id"),
+    ),
+    request_body(content = CaseComment, description = "Comment", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Case),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/cases/{id}/comments")]
+pub async fn add_comment(
+    path: web::Path<(String, String)>,
+    body: web::Json<CaseComment>,
+    req: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let (org_id, id) = path.into_inner();
+    match cases::add_comment(&org_id, &id, &get_user_id(&req), body.into_inner().text).await {
+        Ok(case) => Ok(MetaHttpResponse::json(case)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// ExportCaseTimeline
+///
+/// Returns the activity, attachments and comments of a case ordered by time, as JSON or as
+/// CSV with `format=csv`.
+///
+/// #{"ratelimit_module":"Cases", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Cases",
+    operation_id = "ExportCaseTimeline",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Case id"),
+        ("format" = Option<String>, Query, description = "json (default) or csv"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Vec<CaseTimelineEntry>),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/cases/{id}/timeline")]
+pub async fn timeline(
+    path: web::Path<(String, String)>,
+    query: web::Query<TimelineQuery>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, id) = path.into_inner();
+    let case = match cases::get(&org_id, &id).await {
+        Ok(case) => case,
+        Err(e) => return Ok(map_error(e)),
+    };
+    let entries = cases::timeline(&case);
+    match query.format.as_deref() {
+        Some("csv") => match cases::timeline_to_csv(&entries) {
+            Ok(csv) => Ok(HttpResponse::Ok()
+                .content_type("text/csv")
+                .insert_header((
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"case_{id}_timeline.csv\""),
+                ))
+                .body(csv)),
+            Err(e) => Ok(map_error(e)),
+        },
+        _ => Ok(MetaHttpResponse::json(entries)),
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpResponse, delete, get, put, web};
+use config::{
+    META_ORG_ID,
+    meta::chaos::{ChaosScenario, ChaosScenarioList, ChaosTarget},
+};
+
+use crate::{
+    common::{
+        meta::http::HttpResponse as MetaHttpResponse,
+        utils::auth::{UserEmail, is_root_user},
+    },
+    service::chaos::{self, ChaosError},
+};
+
+fn map_error(e: ChaosError) -> HttpResponse {
+    match e {
+        ChaosError::NotFound => MetaHttpResponse::not_found(e),
+        ChaosError::InfraError(e) => MetaHttpResponse::internal_error(e),
+        e => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// Fault injection affects the whole cluster, only the root user of the meta org can manage it.
+fn check_permission(org_id: &str, user_id: &str) -> Option<HttpResponse> {
+    if org_id != META_ORG_ID || !is_root_user(user_id) {
+        return Some(MetaHttpResponse::forbidden(
+            "Only root user of the _meta organization can manage chaos scenarios",
+        ));
+    }
+    None
+}
+
+/// ListChaosScenarios
+///
+/// #{"ratelimit_module":"Chaos", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Chaos",
+    operation_id = "ListChaosScenarios",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name, must be _meta"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = ChaosScenarioList),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/chaos")]
+pub async fn list_scenarios(
+    path: web::Path<String>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    if let Some(resp) = check_permission(&org_id, &user_email.user_id) {
+        return Ok(resp);
+    }
+    match chaos::list().await {
+        Ok(list) => Ok(MetaHttpResponse::json(ChaosScenarioList { list })),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// SetChaosScenario
+///
+/// Injects latency and errors into the operations of the object store (`storage`) or the meta
+/// store (`db`) on all the nodes, to test how compaction, ingestion retries and query fallbacks
+/// behave. Requires `ZO_CHAOS_ENABLED=true`.
+///
+/// #{"ratelimit_module":"Chaos", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Chaos",
+    operation_id = "SetChaosScenario",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name, must be _meta"),
+        ("target" = String, Path, description = "storage, db, file_list or orm"),
+    ),
+    request_body(content = ChaosScenario, description = "Scenario", content_type = "application/json", example = json!({"latency_ms": 500, "error_rate": 0.2, "operations": ["put"], "key_prefix": "files/default/logs"})),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = ChaosScenario),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/chaos/{target}")]
+pub async fn set_scenario(
+    path: web::Path<(String, String)>,
+    user_email: UserEmail,
+    req: web::Json<ChaosScenario>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, target) = path.into_inner();
+    if let Some(resp) = check_permission(&org_id, &user_email.user_id) {
+        return Ok(resp);
+    }
+    let target = match target.parse::<ChaosTarget>() {
+        Ok(target) => target,
+        Err(e) => return Ok(MetaHttpResponse::bad_request(e)),
+    };
+    match chaos::set(target, req.into_inner()).await {
+        Ok(scenario) => Ok(MetaHttpResponse::json(scenario)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteChaosScenario
+///
+/// #{"ratelimit_module":"Chaos", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Chaos",
+    operation_id = "DeleteChaosScenario",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name, must be _meta"),
+        ("target" = String, Path, description = "storage, db, file_list or orm"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/chaos/{target}")]
+pub async fn delete_scenario(
+    path: web::Path<(String, String)>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, target) = path.into_inner();
+    if let Some(resp) = check_permission(&org_id, &user_email.user_id) {
+        return Ok(resp);
+    }
+    let target = match target.parse::<ChaosTarget>() {
+        Ok(target) => target,
+        Err(e) => return Ok(MetaHttpResponse::bad_request(e)),
+    };
+    match chaos::delete(target).await {
+        Ok(()) => Ok(MetaHttpResponse::ok("Chaos scenario deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
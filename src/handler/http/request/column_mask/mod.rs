@@ -0,0 +1,137 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpResponse, delete, get, put, web};
+use config::meta::{column_mask::ColumnMaskPolicy, stream::StreamType, user::UserRole};
+
+use crate::{
+    common::meta::http::HttpResponse as MetaHttpResponse,
+    service::column_mask::{self, ColumnMaskError},
+};
+
+fn map_error(e: ColumnMaskError) -> HttpResponse {
+    match e {
+        ColumnMaskError::NotFound => MetaHttpResponse::not_found(e),
+        ColumnMaskError::InfraError(e) => MetaHttpResponse::internal_error(e),
+        e => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// ListColumnMasks
+///
+/// #{"ratelimit_module":"Column Mask", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Column Mask",
+    operation_id = "ListColumnMasks",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Vec<ColumnMaskPolicy>),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/column_masks")]
+pub async fn list_masks(path: web::Path<String>) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match column_mask::list(&org_id).await {
+        Ok(list) => Ok(MetaHttpResponse::json(list)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// SaveColumnMask
+///
+/// Creates or replaces the column masks of a role on a stream. The masked columns read as null,
+/// as the hex sha256 of their value or partially masked in every query of the users with the
+/// role. The `_original` and `_all_values` columns of the stream read as null for them.
+///
+/// #{"ratelimit_module":"Column Mask", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Column Mask",
+    operation_id = "SaveColumnMask",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = ColumnMaskPolicy, description = "Column mask data", content_type = "application/json", example = json!({"stream_type": "logs", "stream_name": "shared", "role": "viewer", "columns": [{"field": "email", "type": "partial", "keep_start": 2, "keep_end": 4}, {"field": "client_ip", "type": "hash"}]})),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = ColumnMaskPolicy),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/column_masks")]
+pub async fn save_mask(
+    path: web::Path<String>,
+    req: web::Json<ColumnMaskPolicy>,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match column_mask::save(&org_id, req.into_inner()).await {
+        Ok(policy) => Ok(MetaHttpResponse::json(policy)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteColumnMask
+///
+/// #{"ratelimit_module":"Column Mask", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Column Mask",
+    operation_id = "DeleteColumnMask",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_type" = String, Path, description = "Stream type"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("role" = String, Path, description = "Role the policy applies to"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/column_masks/{stream_type}/{stream_name}/{role}")]
+pub async fn delete_mask(
+    path: web::Path<(String, String, String, String)>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, stream_type, stream_name, role) = path.into_inner();
+    let stream_type = StreamType::from(stream_type.as_str());
+    // unknown roles parse as admin
+    let Some(role) = role
+        .parse::<UserRole>()
+        .ok()
+        .filter(|r| r.to_string() == role)
+    else {
+        return Ok(MetaHttpResponse::bad_request(format!(
+            "invalid role {role}"
+        )));
+    };
+    match column_mask::delete(&org_id, stream_type, &stream_name, &role).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Column mask deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
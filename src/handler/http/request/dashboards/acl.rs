@@ -0,0 +1,113 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpResponse, get, put, web};
+use config::meta::dashboards::acl::{DashboardAccess, DashboardAcl};
+
+use crate::{
+    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    service::dashboards::acl,
+};
+
+/// GetDashboardAcl
+///
+/// Gets the viewers and editors of the dashboard. Empty lists don't restrict the access given
+/// by the folder.
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "GetDashboardAcl",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("dashboard_id" = String, Path, description = "Dashboard ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = DashboardAcl),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/dashboards/{dashboard_id}/acl")]
+pub async fn get_dashboard_acl(
+    path: web::Path<(String, String)>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, dashboard_id) = path.into_inner();
+    if let Err(e) = acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::View,
+    )
+    .await
+    {
+        return Ok(e.into());
+    }
+    match acl::get_acl(&org_id, &dashboard_id).await {
+        Ok(acl) => Ok(MetaHttpResponse::json(acl)),
+        Err(e) => Ok(e.into()),
+    }
+}
+
+/// UpdateDashboardAcl
+///
+/// Replaces the viewers and editors of the dashboard, the user needs to be able to edit the
+/// dashboard. The owner of the dashboard and the admins are never restricted.
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "UpdateDashboardAcl",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("dashboard_id" = String, Path, description = "Dashboard ID"),
+    ),
+    request_body(content = DashboardAcl, description = "Dashboard viewers and editors", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = DashboardAcl),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/dashboards/{dashboard_id}/acl")]
+pub async fn update_dashboard_acl(
+    path: web::Path<(String, String)>,
+    req: web::Json<DashboardAcl>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, dashboard_id) = path.into_inner();
+    match acl::set_acl(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        req.into_inner(),
+    )
+    .await
+    {
+        Ok(acl) => Ok(MetaHttpResponse::json(acl)),
+        Err(e) => Ok(e.into()),
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpResponse, get, http::header, post, web};
+use config::meta::dashboards::{DashboardExport, DashboardImportSummary};
+use hashbrown::HashMap;
+
+use crate::{
+    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    service::dashboards::archive::{self, ArchiveError},
+};
+
+fn map_error(e: ArchiveError) -> HttpResponse {
+    match e {
+        ArchiveError::InvalidArchive(_) | ArchiveError::InvalidVersion(_) => {
+            MetaHttpResponse::bad_request(e)
+        }
+        ArchiveError::DashboardError(e) => e.into(),
+        ArchiveError::FolderError(e) => e.into(),
+    }
+}
+
+/// ExportDashboards
+///
+/// Exports the dashboards of the organization with their folders as an NDJSON archive, one
+/// folder or dashboard per line. The archive keeps the folder and dashboard ids, tabs and
+/// variables, and can be imported into another organization.
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "ExportDashboards",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/x-ndjson", body = String),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/dashboards/export")]
+pub async fn export_dashboards(
+    path: web::Path<String>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match archive::export(&org_id, &user_email.user_id).await {
+        Ok(data) => Ok(HttpResponse::Ok()
+            .content_type("application/x-ndjson")
+            .insert_header((
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{org_id}_dashboards.ndjson\""),
+            ))
+            .body(data)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// ExportDashboard
+///
+/// Exports a dashboard as the given version, to import it into an older installation. Older
+/// versions are created on a best-effort basis, the fields they don't support are dropped and
+/// listed in the warnings.
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "ExportDashboard",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("dashboard_id" = String, Path, description = "Dashboard ID"),
+        ("version" = Option<i32>, Query, description = "Version of the exported dashboard, 3 or newer, default the version of the dashboard"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = DashboardExport),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/dashboards/{dashboard_id}/export")]
+pub async fn export_dashboard(
+    path: web::Path<(String, String)>,
+    query: web::Query<HashMap<String, String>>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, dashboard_id) = path.into_inner();
+    let version = match query.get("version").map(|v| v.parse::<i32>()) {
+        None => None,
+        Some(Ok(version)) => Some(version),
+        Some(Err(_)) => return Ok(MetaHttpResponse::bad_request("version must be a number")),
+    };
+    match archive::export_dashboard(&org_id, &dashboard_id, &user_email.user_id, version).await {
+        Ok(export) => Ok(MetaHttpResponse::json(export)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// ImportDashboards
+///
+/// Imports an NDJSON archive created by the export. Folders and dashboards keep their ids,
+/// existing dashboards are only replaced when `overwrite` is set.
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "ImportDashboards",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("overwrite" = Option<bool>, Query, description = "Replace the existing dashboards with the same id, default false"),
+    ),
+    request_body(content = String, description = "NDJSON archive", content_type = "application/x-ndjson"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = DashboardImportSummary),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/dashboards/import")]
+pub async fn import_dashboards(
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+    body: web::Bytes,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    let overwrite = query
+        .get("overwrite")
+        .is_some_and(|v| v.eq_ignore_ascii_case("true"));
+    let Ok(body) = std::str::from_utf8(&body) else {
+        return Ok(MetaHttpResponse::bad_request("archive is not valid UTF-8"));
+    };
+    match archive::import(&org_id, &user_email.user_id, body, overwrite).await {
+        Ok(summary) => Ok(MetaHttpResponse::json(summary)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
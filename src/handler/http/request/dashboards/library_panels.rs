@@ -0,0 +1,202 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpResponse, delete, get, post, put, web};
+use config::meta::dashboards::library_panels::{LibraryPanel, LibraryPanelList};
+
+use crate::{
+    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    service::dashboards::{
+        acl,
+        library_panels::{self, LibraryPanelError},
+    },
+};
+
+fn map_error(e: LibraryPanelError) -> HttpResponse {
+    match e {
+        LibraryPanelError::NotFound => MetaHttpResponse::not_found(e),
+        LibraryPanelError::NameConflict(_) => MetaHttpResponse::conflict(e),
+        LibraryPanelError::MissingName => MetaHttpResponse::bad_request(e),
+        e => MetaHttpResponse::internal_error(e),
+    }
+}
+
+/// CreateLibraryPanel
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "CreateLibraryPanel",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = LibraryPanel, description = "Library panel details", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = LibraryPanel),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 409, description = "Conflict", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/library_panels")]
+pub async fn create_library_panel(
+    path: web::Path<String>,
+    req: web::Json<LibraryPanel>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match library_panels::create(&org_id, &user_email.user_id, req.into_inner()).await {
+        Ok(library_panel) => Ok(MetaHttpResponse::json(library_panel)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// UpdateLibraryPanel
+///
+/// Updates the definition of the library panel, every dashboard panel referencing it shows the
+/// new definition. The user needs to be able to edit the dashboards referencing it.
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "UpdateLibraryPanel",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("library_panel_id" = String, Path, description = "Library panel ID"),
+    ),
+    request_body(content = LibraryPanel, description = "Library panel details", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = LibraryPanel),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+        (status = 409, description = "Conflict", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/library_panels/{library_panel_id}")]
+pub async fn update_library_panel(
+    path: web::Path<(String, String)>,
+    req: web::Json<LibraryPanel>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, library_panel_id) = path.into_inner();
+    if let Err(e) = acl::check_library_panel(&org_id, &library_panel_id, &user_email.user_id).await
+    {
+        return Ok(e.into());
+    }
+    match library_panels::update(&org_id, &library_panel_id, req.into_inner()).await {
+        Ok(library_panel) => Ok(MetaHttpResponse::json(library_panel)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// ListLibraryPanels
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "ListLibraryPanels",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = LibraryPanelList),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/library_panels")]
+pub async fn list_library_panels(path: web::Path<String>) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match library_panels::list(&org_id).await {
+        Ok(list) => Ok(MetaHttpResponse::json(LibraryPanelList { list })),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// GetLibraryPanel
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "GetLibraryPanel",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("library_panel_id" = String, Path, description = "Library panel ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = LibraryPanel),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/library_panels/{library_panel_id}")]
+pub async fn get_library_panel(path: web::Path<(String, String)>) -> Result<HttpResponse, Error> {
+    let (org_id, library_panel_id) = path.into_inner();
+    match library_panels::get(&org_id, &library_panel_id).await {
+        Ok(library_panel) => Ok(MetaHttpResponse::json(library_panel)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteLibraryPanel
+///
+/// Dashboard panels referencing the deleted library panel keep their last resolved definition.
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "DeleteLibraryPanel",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("library_panel_id" = String, Path, description = "Library panel ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/library_panels/{library_panel_id}")]
+pub async fn delete_library_panel(
+    path: web::Path<(String, String)>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, library_panel_id) = path.into_inner();
+    if let Err(e) = acl::check_library_panel(&org_id, &library_panel_id, &user_email.user_id).await
+    {
+        return Ok(e.into());
+    }
+    match library_panels::delete(&org_id, &library_panel_id).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Library panel deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
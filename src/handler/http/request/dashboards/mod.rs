@@ -14,20 +14,39 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use actix_web::{HttpRequest, HttpResponse, Responder, delete, get, http, patch, post, put, web};
+use config::meta::{
+    dashboards::{DashboardLint, DashboardValidation, acl::DashboardAccess},
+    folder::DEFAULT_FOLDER,
+    workspace::ResourceType,
+};
 use hashbrown::HashMap;
 
 use crate::{
-    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    common::{
+        meta::http::HttpResponse as MetaHttpResponse,
+        utils::auth::{UserEmail, is_root_user},
+    },
     handler::http::models::dashboards::{
-        CreateDashboardRequestBody, CreateDashboardResponseBody, GetDashboardResponseBody,
-        ListDashboardsQuery, ListDashboardsResponseBody, MoveDashboardRequestBody,
-        MoveDashboardsRequestBody, UpdateDashboardRequestBody, UpdateDashboardResponseBody,
+        CopyDashboardRequestBody, CreateDashboardRequestBody, CreateDashboardResponseBody,
+        GetDashboardResponseBody, ListDashboardsQuery, ListDashboardsResponseBody,
+        MoveDashboardRequestBody, MoveDashboardsRequestBody, UpdateDashboardRequestBody,
+        UpdateDashboardResponseBody, ValidateDashboardRequestBody,
+    },
+    service::{
+        dashboards::{self, DashboardError},
+        users, workspaces,
     },
-    service::dashboards::{self, DashboardError},
 };
 
+pub mod acl;
+pub mod archive;
+pub mod library_panels;
+pub mod render;
 pub mod reports;
+pub mod share;
+pub mod snapshots;
 pub mod timed_annotations;
+pub mod variables;
 
 impl From<DashboardError> for HttpResponse {
     fn from(value: DashboardError) -> Self {
@@ -64,6 +83,7 @@ impl From<DashboardError> for HttpResponse {
             DashboardError::ListPermittedDashboardsError(err) => MetaHttpResponse::forbidden(err),
             DashboardError::UserNotFound => MetaHttpResponse::unauthorized("User not found"),
             DashboardError::PermissionDenied => MetaHttpResponse::forbidden("Permission denied"),
+            DashboardError::CopyDashboard(err) => MetaHttpResponse::internal_error(err),
         }
     }
 }
@@ -109,7 +129,8 @@ pub async fn create_dashboard(
         Err(_) => return MetaHttpResponse::bad_request("Error parsing request body"),
     };
 
-    set_dashboard_owner_if_empty(&mut dashboard, &user_email.user_id);
+    let owner = users::resource_owner(&user_email.user_id, dashboard.owner()).await;
+    dashboard.set_owner(owner);
 
     let saved = match dashboards::create_dashboard(&org_id, &folder, dashboard).await {
         Ok(saved) => saved,
@@ -139,6 +160,7 @@ pub async fn create_dashboard(
     ),
     responses(
         (status = StatusCode::OK, description = "Dashboard updated", body = UpdateDashboardResponseBody),
+        (status = StatusCode::FORBIDDEN, description = "Not permitted by the dashboard ACL", body = HttpResponse),
         (status = StatusCode::NOT_FOUND, description = "Dashboard not found", body = HttpResponse),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Failed to update the dashboard", body = HttpResponse),
     ),
@@ -163,6 +185,17 @@ async fn update_dashboard(
 
     set_dashboard_owner_if_empty(&mut dashboard, &user_email.user_id);
 
+    if let Err(err) = dashboards::acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::Edit,
+    )
+    .await
+    {
+        return err.into();
+    }
+
     let saved = match dashboards::update_dashboard(&org_id, &dashboard_id, &folder, dashboard, hash)
         .await
     {
@@ -173,6 +206,131 @@ async fn update_dashboard(
     MetaHttpResponse::json(resp_body)
 }
 
+/// ValidateDashboard
+///
+/// Checks a dashboard JSON before importing it, without saving it. Reports an unsupported
+/// version, the fields that the dashboard version doesn't know and that would be dropped, the
+/// referenced streams that don't exist and the panel queries that are not valid SQL.
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "ValidateDashboard",
+    security(
+        ("Authorization" = [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(
+        content = ValidateDashboardRequestBody,
+        description = "Dashboard JSON to import",
+    ),
+    responses(
+        (status = StatusCode::OK, description = "Validation result", body = DashboardValidation),
+        (status = StatusCode::BAD_REQUEST, description = "Invalid JSON", body = HttpResponse),
+    ),
+)]
+#[post("/{org_id}/dashboards/validate")]
+pub async fn validate_dashboard(
+    path: web::Path<String>,
+    req_body: web::Json<ValidateDashboardRequestBody>,
+) -> impl Responder {
+    let org_id = path.into_inner();
+    let value = req_body.into_inner().into();
+    let validation = dashboards::validate_dashboard(&org_id, &value).await;
+    MetaHttpResponse::json(validation)
+}
+
+/// LintDashboard
+///
+/// Checks the panels of a saved dashboard against the current stream schemas. Reports the
+/// panels whose stream was deleted and the axis fields that no longer exist in the stream.
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "LintDashboard",
+    security(
+        ("Authorization" = [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("dashboard_id" = String, Path, description = "Dashboard ID"),
+    ),
+    responses(
+        (status = StatusCode::OK, description = "Lint result", body = DashboardLint),
+        (status = StatusCode::NOT_FOUND, description = "Dashboard not found", body = HttpResponse),
+    ),
+)]
+#[post("/{org_id}/dashboards/{dashboard_id}/lint")]
+pub async fn lint_dashboard(
+    path: web::Path<(String, String)>,
+    user_email: UserEmail,
+) -> impl Responder {
+    let (org_id, dashboard_id) = path.into_inner();
+    if let Err(err) = dashboards::acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::View,
+    )
+    .await
+    {
+        return err.into();
+    }
+    match dashboards::lint::lint(&org_id, &dashboard_id).await {
+        Ok(lint) => MetaHttpResponse::json(lint),
+        Err(err) => err.into(),
+    }
+}
+
+/// ListDashboardLints
+///
+/// Returns the dashboards with broken stream or field references found by the latest
+/// background check, which runs every `ZO_DASHBOARD_LINT_INTERVAL` seconds. Only the dashboards
+/// the user can view are listed.
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "ListDashboardLints",
+    security(
+        ("Authorization" = [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = StatusCode::OK, description = "Dashboards with issues", body = Vec<DashboardLint>),
+    ),
+)]
+#[get("/{org_id}/dashboards/lint")]
+pub async fn list_dashboard_lints(
+    path: web::Path<String>,
+    user_email: UserEmail,
+) -> impl Responder {
+    let org_id = path.into_inner();
+    let mut lints = vec![];
+    for lint in dashboards::lint::get_reports(&org_id) {
+        if dashboards::acl::check(
+            &org_id,
+            &lint.dashboard_id,
+            &user_email.user_id,
+            DashboardAccess::View,
+        )
+        .await
+        .is_ok()
+        {
+            lints.push(lint);
+        }
+    }
+    MetaHttpResponse::json(lints)
+}
+
 /// ListDashboards
 ///
 /// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"list"}#
@@ -196,7 +354,17 @@ async fn list_dashboards(org_id: web::Path<String>, req: HttpRequest) -> impl Re
     let Ok(query) = web::Query::<ListDashboardsQuery>::from_query(req.query_string()) else {
         return MetaHttpResponse::bad_request("Error parsing query parameters");
     };
-    let params = query.into_inner().into(&org_id.into_inner());
+    let org_id = org_id.into_inner();
+    let query = query.into_inner();
+    let params = match query.workspace().map(str::to_string) {
+        Some(workspace) => {
+            match workspaces::resource_ids(&org_id, &workspace, ResourceType::Dashboard).await {
+                Ok(dashboard_ids) => query.into(&org_id).with_dashboard_ids(dashboard_ids),
+                Err(e) => return e.into(),
+            }
+        }
+        None => query.into(&org_id),
+    };
     let Some(user_id) = get_user_id(req) else {
         return MetaHttpResponse::unauthorized("User ID not found in request headers");
     };
@@ -224,12 +392,23 @@ async fn list_dashboards(org_id: web::Path<String>, req: HttpRequest) -> impl Re
     ),
     responses(
         (status = StatusCode::OK, body = GetDashboardResponseBody),
+        (status = StatusCode::FORBIDDEN, description = "Not permitted by the dashboard ACL", body = HttpResponse),
         (status = StatusCode::NOT_FOUND, description = "Dashboard not found", body = HttpResponse),
     ),
 )]
 #[get("/{org_id}/dashboards/{dashboard_id}")]
-async fn get_dashboard(path: web::Path<(String, String)>) -> impl Responder {
+async fn get_dashboard(path: web::Path<(String, String)>, user_email: UserEmail) -> impl Responder {
     let (org_id, dashboard_id) = path.into_inner();
+    if let Err(err) = dashboards::acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::View,
+    )
+    .await
+    {
+        return err.into();
+    }
     let dashboard = match dashboards::get_dashboard(&org_id, &dashboard_id).await {
         Ok(dashboard) => dashboard,
         Err(err) => return err.into(),
@@ -254,13 +433,27 @@ async fn get_dashboard(path: web::Path<(String, String)>) -> impl Responder {
     ),
     responses(
         (status = StatusCode::OK, description = "Success", body = HttpResponse),
+        (status = StatusCode::FORBIDDEN, description = "Not permitted by the dashboard ACL", body = HttpResponse),
         (status = StatusCode::NOT_FOUND, description = "NotFound", body = HttpResponse),
         (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Error", body = HttpResponse),
     ),
 )]
 #[delete("/{org_id}/dashboards/{dashboard_id}")]
-async fn delete_dashboard(path: web::Path<(String, String)>) -> impl Responder {
+async fn delete_dashboard(
+    path: web::Path<(String, String)>,
+    user_email: UserEmail,
+) -> impl Responder {
     let (org_id, dashboard_id) = path.into_inner();
+    if let Err(err) = dashboards::acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::Edit,
+    )
+    .await
+    {
+        return err.into();
+    }
     match dashboards::delete_dashboard(&org_id, &dashboard_id).await {
         Ok(()) => HttpResponse::Ok().json(MetaHttpResponse::message(
             http::StatusCode::OK,
@@ -304,6 +497,16 @@ async fn move_dashboard(
     user_email: UserEmail,
 ) -> impl Responder {
     let (org_id, dashboard_id) = path.into_inner();
+    if let Err(err) = dashboards::acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::Edit,
+    )
+    .await
+    {
+        return err.into();
+    }
     // For this endpoint, openfga check is already done in the middleware
     match dashboards::move_dashboard(
         &org_id,
@@ -351,6 +554,18 @@ async fn move_dashboards(
     let org_id = path.into_inner();
     // For this endpoint, openfga check is needed here, as we don't do openfga check in the
     // middleware for this api endpoint, because it includes a batch of dashboards
+    for dashboard_id in req_body.dashboard_ids.iter() {
+        if let Err(err) = dashboards::acl::check(
+            &org_id,
+            dashboard_id,
+            &user_email.user_id,
+            DashboardAccess::Edit,
+        )
+        .await
+        {
+            return err.into();
+        }
+    }
     match dashboards::move_dashboards(
         &org_id,
         &req_body.dashboard_ids,
@@ -372,6 +587,104 @@ async fn move_dashboards(
     }
 }
 
+/// CopyDashboard
+///
+/// Copies a dashboard into another organization, renaming the streams it reads through the
+/// stream mapping, so dashboards maintained in one organization can be used as templates.
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "CopyDashboard",
+    security(
+        ("Authorization" = [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("dashboard_id" = String, Path, description = "Dashboard ID"),
+    ),
+    request_body(
+        content = CopyDashboardRequestBody,
+        description = "Target organization and stream mapping",
+        example = json!({
+            "target_org": "team_a",
+            "folder_id": "default",
+            "stream_mapping": {"golden_logs": "team_a_logs"},
+        }),
+    ),
+    responses(
+        (status = StatusCode::OK, description = "Dashboard copied", body = CreateDashboardResponseBody),
+        (status = StatusCode::BAD_REQUEST, description = "Invalid request", body = HttpResponse),
+        (status = StatusCode::FORBIDDEN, description = "Not a member of the target organization or not allowed to create dashboards in the folder", body = HttpResponse),
+        (status = StatusCode::NOT_FOUND, description = "Dashboard or folder not found", body = HttpResponse),
+        (status = StatusCode::INTERNAL_SERVER_ERROR, description = "Internal Server Error", body = HttpResponse),
+    ),
+)]
+#[post("/{org_id}/dashboards/{dashboard_id}/copy")]
+async fn copy_dashboard(
+    path: web::Path<(String, String)>,
+    req_body: web::Json<CopyDashboardRequestBody>,
+    user_email: UserEmail,
+) -> impl Responder {
+    let (org_id, dashboard_id) = path.into_inner();
+    let req_body = req_body.into_inner();
+    let target_org = req_body.target_org.trim();
+    if target_org.is_empty() {
+        return MetaHttpResponse::bad_request("target_org is required");
+    }
+    if req_body
+        .stream_mapping
+        .iter()
+        .any(|(from, to)| from.is_empty() || to.trim().is_empty())
+    {
+        return MetaHttpResponse::bad_request("stream_mapping can't contain empty stream names");
+    }
+    if let Err(err) = dashboards::acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::View,
+    )
+    .await
+    {
+        return err.into();
+    }
+    // copying into another organization requires being a member of it
+    if target_org != org_id
+        && !is_root_user(&user_email.user_id)
+        && users::get_user(Some(target_org), &user_email.user_id)
+            .await
+            .is_none()
+    {
+        return MetaHttpResponse::forbidden(format!(
+            "user is not a member of organization [{target_org}]"
+        ));
+    }
+    let folder_id = req_body
+        .folder_id
+        .as_deref()
+        .filter(|f| !f.is_empty())
+        .unwrap_or(DEFAULT_FOLDER);
+    match dashboards::copy_dashboard(
+        &org_id,
+        &dashboard_id,
+        target_org,
+        folder_id,
+        &req_body.stream_mapping,
+        req_body.title.as_deref(),
+        &user_email.user_id,
+    )
+    .await
+    {
+        Ok(saved) => {
+            let resp_body: CreateDashboardResponseBody = saved.into();
+            MetaHttpResponse::json(resp_body)
+        }
+        Err(err) => err.into(),
+    }
+}
+
 pub fn get_folder(req: HttpRequest) -> String {
     let query = web::Query::<HashMap<String, String>>::from_query(req.query_string()).unwrap();
     crate::common::utils::http::get_folder(&query)
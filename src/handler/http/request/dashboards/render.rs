@@ -0,0 +1,176 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpResponse, get, http::header, web};
+use config::{
+    meta::dashboards::{
+        render::{RenderDashboardRequest, RenderFormat},
+        reports::ReportDashboardVariable,
+    },
+    utils::time::{now_micros, parse_milliseconds},
+};
+use hashbrown::HashMap;
+
+use crate::{
+    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    service::dashboards::render::{self, RenderError},
+};
+
+/// Time range rendered when the request sets neither `period` nor `start_time`.
+const DEFAULT_PERIOD: &str = "15m";
+
+fn map_error(e: RenderError) -> HttpResponse {
+    match e {
+        RenderError::DashboardError(e) => e.into(),
+        RenderError::InvalidRequest(_) => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// Reads the time range from the `start_time` and `end_time` or the `period` query parameters.
+pub(super) fn parse_time_range(query: &HashMap<String, String>) -> Result<(i64, i64), String> {
+    let time = |key: &str| {
+        query
+            .get(key)
+            .map(|v| v.parse::<i64>().map_err(|_| format!("invalid {key}: {v}")))
+            .transpose()
+    };
+    match (time("start_time")?, time("end_time")?) {
+        (Some(start_time), end_time) => Ok((start_time, end_time.unwrap_or_else(now_micros))),
+        (None, _) => {
+            let period = query
+                .get("period")
+                .map(|v| v.as_str())
+                .unwrap_or(DEFAULT_PERIOD);
+            let ms = parse_milliseconds(period).map_err(|_| format!("invalid period: {period}"))?;
+            let end_time = now_micros();
+            Ok((end_time - ms as i64 * 1000, end_time))
+        }
+    }
+}
+
+/// Reads the render request from the query parameters, variables are passed as `var-<name>`.
+fn parse_query(query: &HashMap<String, String>) -> Result<RenderDashboardRequest, String> {
+    let format = match query.get("format") {
+        Some(format) => format.parse::<RenderFormat>()?,
+        None => RenderFormat::default(),
+    };
+    let (start_time, end_time) = parse_time_range(query)?;
+    let tabs = query
+        .get("tab")
+        .map(|v| v.split(',').map(|t| t.trim().to_string()).collect())
+        .unwrap_or_default();
+    let variables = query
+        .iter()
+        .filter_map(|(k, v)| {
+            k.strip_prefix("var-").map(|key| ReportDashboardVariable {
+                key: key.to_string(),
+                value: v.to_string(),
+                id: None,
+            })
+        })
+        .collect();
+    Ok(RenderDashboardRequest {
+        format,
+        start_time,
+        end_time,
+        variables,
+        tabs,
+    })
+}
+
+/// RenderDashboard
+///
+/// Runs the panel queries of the dashboard and returns the results drawn as a pdf document or a
+/// png image, without a browser. Panels are drawn as simple charts, tables and values.
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "RenderDashboard",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("dashboard_id" = String, Path, description = "Dashboard ID"),
+        ("format" = Option<String>, Query, description = "pdf or png, default pdf"),
+        ("period" = Option<String>, Query, description = "Relative time range ending now like 15m, 1h or 7d, default 15m"),
+        ("start_time" = Option<i64>, Query, description = "Start of the time range in microseconds, instead of period"),
+        ("end_time" = Option<i64>, Query, description = "End of the time range in microseconds, default now"),
+        ("tab" = Option<String>, Query, description = "Comma separated ids or names of the tabs to render, default all tabs"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/pdf", body = Vec<u8>),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/dashboards/{dashboard_id}/render")]
+pub async fn render_dashboard(
+    path: web::Path<(String, String)>,
+    query: web::Query<HashMap<String, String>>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, dashboard_id) = path.into_inner();
+    let req = match parse_query(&query) {
+        Ok(req) => req,
+        Err(e) => return Ok(MetaHttpResponse::bad_request(e)),
+    };
+    match render::render(&org_id, &dashboard_id, &user_email.user_id, &req).await {
+        Ok(data) => Ok(HttpResponse::Ok()
+            .content_type(req.format.content_type())
+            .insert_header((
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "inline; filename=\"{dashboard_id}.{}\"",
+                    req.format.extension()
+                ),
+            ))
+            .body(data)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query() {
+        let query = HashMap::from_iter([
+            ("format".to_string(), "png".to_string()),
+            ("start_time".to_string(), "10".to_string()),
+            ("end_time".to_string(), "20".to_string()),
+            ("tab".to_string(), "a, b".to_string()),
+            ("var-host".to_string(), "web-1".to_string()),
+        ]);
+        let req = parse_query(&query).unwrap();
+        assert_eq!(req.format, RenderFormat::Png);
+        assert_eq!((req.start_time, req.end_time), (10, 20));
+        assert_eq!(req.tabs, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(req.variables.len(), 1);
+        assert_eq!(req.variables[0].key, "host");
+
+        let req = parse_query(&HashMap::new()).unwrap();
+        assert_eq!(req.format, RenderFormat::Pdf);
+        assert_eq!(req.end_time - req.start_time, 15 * 60 * 1_000_000);
+
+        let query = HashMap::from_iter([("period".to_string(), "abc".to_string())]);
+        assert!(parse_query(&query).is_err());
+    }
+}
@@ -17,7 +17,10 @@ use std::{collections::HashMap, io::Error};
 
 use actix_web::{HttpRequest, HttpResponse, delete, get, post, put, web};
 use config::meta::{
-    dashboards::reports::{Report, ReportListFilters},
+    dashboards::{
+        acl::DashboardAccess,
+        reports::{Report, ReportListFilters},
+    },
     triggers::{Trigger, TriggerModule},
 };
 
@@ -25,7 +28,10 @@ use crate::{
     common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
     handler::http::models::reports::{ListReportsResponseBody, ListReportsResponseBodyItem},
     service::{
-        dashboards::reports::{self, ReportError},
+        dashboards::{
+            DashboardError, acl,
+            reports::{self, ReportError},
+        },
         db::scheduler,
     },
 };
@@ -46,6 +52,8 @@ impl From<ReportError> for HttpResponse {
             ReportError::NoDashboards => MetaHttpResponse::bad_request(value),
             ReportError::NoDashboardTabs => MetaHttpResponse::bad_request(value),
             ReportError::NoDestinations => MetaHttpResponse::bad_request(value),
+            ReportError::InvalidDestination(_) => MetaHttpResponse::bad_request(value),
+            ReportError::InvalidDigest(_) => MetaHttpResponse::bad_request(value),
             ReportError::DashboardTabNotFound => MetaHttpResponse::not_found(value),
             ReportError::ParseCronError(e) => MetaHttpResponse::bad_request(e),
             ReportError::DbError(e) => MetaHttpResponse::internal_error(e),
@@ -55,6 +63,18 @@ impl From<ReportError> for HttpResponse {
     }
 }
 
+/// Checks that the user can view the dashboards of the report.
+async fn check_dashboards(
+    org_id: &str,
+    user_id: &str,
+    report: &Report,
+) -> Result<(), DashboardError> {
+    for dashboard in report.dashboards.iter() {
+        acl::check(org_id, &dashboard.dashboard, user_id, DashboardAccess::View).await?;
+    }
+    Ok(())
+}
+
 /// CreateReport
 ///
 /// #{"ratelimit_module":"Reports", "ratelimit_module_operation":"create"}#
@@ -90,6 +110,9 @@ pub async fn create_report(
     let org_id = path.into_inner();
 
     let mut report = report.into_inner();
+    if let Err(e) = check_dashboards(&org_id, &user_email.user_id, &report).await {
+        return Ok(e.into());
+    }
     if report.owner.is_empty() {
         report.owner = user_email.user_id;
     }
@@ -131,6 +154,9 @@ async fn update_report(
 ) -> Result<HttpResponse, Error> {
     let (org_id, name) = path.into_inner();
     let mut report = report.into_inner();
+    if let Err(e) = check_dashboards(&org_id, &user_email.user_id, &report).await {
+        return Ok(e.into());
+    }
     report.last_edited_by = user_email.user_id;
     match reports::save(&org_id, &name, report, false).await {
         Ok(_) => Ok(MetaHttpResponse::ok("Report saved")),
@@ -349,8 +375,20 @@ async fn enable_report(
     )
 )]
 #[put("/{org_id}/reports/{name}/trigger")]
-async fn trigger_report(path: web::Path<(String, String)>) -> Result<HttpResponse, Error> {
+async fn trigger_report(
+    path: web::Path<(String, String)>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
     let (org_id, name) = path.into_inner();
+    match reports::get(&org_id, &name).await {
+        Ok(report) => {
+            if let Err(e) = check_dashboards(&org_id, &user_email.user_id, &report).await {
+                return Ok(e.into());
+            }
+        }
+        Err(e @ ReportError::ReportNotFound) => return Ok(MetaHttpResponse::not_found(e)),
+        Err(e) => return Ok(MetaHttpResponse::internal_error(e)),
+    }
     match reports::trigger(&org_id, &name).await {
         Ok(_) => Ok(MetaHttpResponse::ok("Report triggered")),
         Err(e) => match e {
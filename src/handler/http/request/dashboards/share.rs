@@ -0,0 +1,216 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpRequest, HttpResponse, delete, get, http::header, post, web};
+use config::meta::dashboards::{
+    acl::DashboardAccess,
+    share::{CreateShareRequest, CreateShareResponse, ShareFormat},
+};
+use hashbrown::HashMap;
+
+use super::render::parse_time_range;
+use crate::{
+    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    service::dashboards::{
+        DashboardError, acl,
+        share::{self, ShareError},
+    },
+};
+
+fn map_error(e: ShareError) -> HttpResponse {
+    match e {
+        ShareError::DashboardError(e) => e.into(),
+        ShareError::InvalidRequest(_) => MetaHttpResponse::bad_request(e),
+        ShareError::InvalidToken(_) => MetaHttpResponse::unauthorized(e),
+        e => MetaHttpResponse::internal_error(e),
+    }
+}
+
+/// CreateDashboardShare
+///
+/// Issues an expiring link to view the dashboard without an account. The link is read-only, its
+/// tabs and variables are fixed and its viewers can only change the time range.
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "CreateDashboardShare",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("dashboard_id" = String, Path, description = "Dashboard ID"),
+    ),
+    request_body(content = CreateShareRequest, description = "Share details", content_type = "application/json", example = json!({"tabs": ["default"], "expires_in": 86400})),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = CreateShareResponse),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/dashboards/{dashboard_id}/share")]
+pub async fn create_share(
+    path: web::Path<(String, String)>,
+    req: web::Json<CreateShareRequest>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, dashboard_id) = path.into_inner();
+    if let Err(e) = acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::Edit,
+    )
+    .await
+    {
+        return Ok(e.into());
+    }
+    match share::create(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        req.into_inner(),
+    )
+    .await
+    {
+        Ok(resp) => Ok(MetaHttpResponse::json(resp)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// RevokeDashboardShares
+///
+/// Revokes every share link of the dashboard.
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "RevokeDashboardShares",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("dashboard_id" = String, Path, description = "Dashboard ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/dashboards/{dashboard_id}/share")]
+pub async fn revoke_shares(
+    path: web::Path<(String, String)>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, dashboard_id) = path.into_inner();
+    if let Err(e) = acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::Edit,
+    )
+    .await
+    {
+        return Ok(e.into());
+    }
+    match share::revoke(&org_id, &dashboard_id).await {
+        Ok(()) => Ok(MetaHttpResponse::ok("Dashboard share links revoked")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// GetSharedDashboard
+///
+/// Renders a shared dashboard, authenticated with the share token given as bearer token or as
+/// `token` query parameter instead of user credentials. Only the panel queries of the shared
+/// dashboard are run.
+#[utoipa::path(
+    context_path = "/share",
+    tag = "Dashboards",
+    operation_id = "GetSharedDashboard",
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("dashboard_id" = String, Path, description = "Dashboard ID"),
+        ("token" = Option<String>, Query, description = "Share token, if not given in the Authorization header"),
+        ("format" = Option<String>, Query, description = "html, pdf or png, default html"),
+        ("period" = Option<String>, Query, description = "Relative time range ending now like 15m, 1h or 7d, default 15m"),
+        ("start_time" = Option<i64>, Query, description = "Start of the time range in microseconds, instead of period"),
+        ("end_time" = Option<i64>, Query, description = "End of the time range in microseconds, default now"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "text/html", body = String),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 401, description = "Unauthorized", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/{dashboard_id}")]
+pub async fn get_shared_dashboard(
+    path: web::Path<(String, String)>,
+    req: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let (org_id, dashboard_id) = path.into_inner();
+    let query = web::Query::<HashMap<String, String>>::from_query(req.query_string())
+        .map(|q| q.into_inner())
+        .unwrap_or_default();
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|v| v.trim().to_string())
+        .or_else(|| query.get("token").cloned());
+    let Some(token) = token else {
+        return Ok(MetaHttpResponse::unauthorized("Missing share token"));
+    };
+    let format = match query.get("format").map(|f| f.parse::<ShareFormat>()) {
+        Some(Ok(format)) => format,
+        Some(Err(e)) => return Ok(MetaHttpResponse::bad_request(e)),
+        None => ShareFormat::default(),
+    };
+    let (start_time, end_time) = match parse_time_range(&query) {
+        Ok(range) => range,
+        Err(e) => return Ok(MetaHttpResponse::bad_request(e)),
+    };
+
+    match share::render(&org_id, &dashboard_id, &token, format, start_time, end_time).await {
+        Ok(data) => {
+            let mut resp = HttpResponse::Ok();
+            match format {
+                ShareFormat::Html => resp
+                    .content_type(header::ContentType::html())
+                    .insert_header((
+                        "Content-Security-Policy",
+                        "default-src 'none'; style-src 'unsafe-inline'",
+                    )),
+                ShareFormat::Pdf => resp.content_type("application/pdf"),
+                ShareFormat::Png => resp.content_type("image/png"),
+            };
+            Ok(resp.body(data))
+        }
+        // don't tell the viewers of a link whether the dashboard exists
+        Err(ShareError::DashboardError(DashboardError::DashboardNotFound)) => Ok(
+            MetaHttpResponse::unauthorized("token is not valid for this dashboard"),
+        ),
+        Err(e) => Ok(map_error(e)),
+    }
+}
@@ -0,0 +1,449 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use std::io::Error;
+
+use actix_web::{HttpResponse, delete, get, http::header, post, web};
+use config::meta::dashboards::{
+    acl::DashboardAccess,
+    snapshots::{
+        CreateSnapshotRequest, CreateSnapshotResponse, CreateSnapshotScheduleRequest,
+        DashboardSnapshotList, ScheduledSnapshotList, SnapshotFormat, SnapshotSchedule,
+        SnapshotScheduleList,
+    },
+};
+
+use crate::{
+    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    service::dashboards::{
+        acl, scheduled_snapshots,
+        snapshots::{self, SnapshotError},
+    },
+};
+
+fn map_error(e: SnapshotError) -> HttpResponse {
+    match e {
+        SnapshotError::NotFound => MetaHttpResponse::not_found(e),
+        SnapshotError::DashboardError(e) => e.into(),
+        SnapshotError::InvalidRequest(_) => MetaHttpResponse::bad_request(e),
+        e => MetaHttpResponse::internal_error(e),
+    }
+}
+
+/// CreateDashboardSnapshot
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "CreateDashboardSnapshot",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("dashboard_id" = String, Path, description = "Dashboard ID"),
+    ),
+    request_body(content = CreateSnapshotRequest, description = "Time range and variables of the snapshot", content_type = "application/json", example = json!({"start_time": 1700000000000000_i64, "end_time": 1700003600000000_i64, "variables": [{"key": "host", "value": "web-1"}], "expires_in": 604800})),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = CreateSnapshotResponse),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/dashboards/{dashboard_id}/snapshots")]
+pub async fn create_snapshot(
+    path: web::Path<(String, String)>,
+    req: web::Json<CreateSnapshotRequest>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, dashboard_id) = path.into_inner();
+    if let Err(e) = acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::View,
+    )
+    .await
+    {
+        return Ok(e.into());
+    }
+    match snapshots::create(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        req.into_inner(),
+    )
+    .await
+    {
+        Ok(resp) => Ok(MetaHttpResponse::json(resp)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// ListDashboardSnapshots
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "ListDashboardSnapshots",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("dashboard_id" = String, Path, description = "Dashboard ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = DashboardSnapshotList),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/dashboards/{dashboard_id}/snapshots")]
+pub async fn list_snapshots(
+    path: web::Path<(String, String)>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, dashboard_id) = path.into_inner();
+    if let Err(e) = acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::View,
+    )
+    .await
+    {
+        return Ok(e.into());
+    }
+    match snapshots::list(&org_id, &dashboard_id).await {
+        Ok(list) => Ok(MetaHttpResponse::json(DashboardSnapshotList { list })),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteDashboardSnapshot
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "DeleteDashboardSnapshot",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("dashboard_id" = String, Path, description = "Dashboard ID"),
+        ("snapshot_id" = String, Path, description = "Snapshot ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/dashboards/{dashboard_id}/snapshots/{snapshot_id}")]
+pub async fn delete_snapshot(
+    path: web::Path<(String, String, String)>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, dashboard_id, snapshot_id) = path.into_inner();
+    if let Err(e) = acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::Edit,
+    )
+    .await
+    {
+        return Ok(e.into());
+    }
+    match snapshots::delete(&org_id, &dashboard_id, &snapshot_id).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Snapshot deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// CreateDashboardSnapshotSchedule
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "CreateDashboardSnapshotSchedule",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("dashboard_id" = String, Path, description = "Dashboard ID"),
+    ),
+    request_body(content = CreateSnapshotScheduleRequest, description = "Frequency, time range and format of the scheduled snapshots", content_type = "application/json", example = json!({"frequency": 3600, "period": 3600, "format": "parquet", "retention": 2592000, "variables": [{"key": "host", "value": "web-1"}]})),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = SnapshotSchedule),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/dashboards/{dashboard_id}/snapshot_schedules")]
+pub async fn create_snapshot_schedule(
+    path: web::Path<(String, String)>,
+    req: web::Json<CreateSnapshotScheduleRequest>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, dashboard_id) = path.into_inner();
+    if let Err(e) = acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::Edit,
+    )
+    .await
+    {
+        return Ok(e.into());
+    }
+    match scheduled_snapshots::create_schedule(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        req.into_inner(),
+    )
+    .await
+    {
+        Ok(schedule) => Ok(MetaHttpResponse::json(schedule)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// ListDashboardSnapshotSchedules
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "ListDashboardSnapshotSchedules",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("dashboard_id" = String, Path, description = "Dashboard ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = SnapshotScheduleList),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/dashboards/{dashboard_id}/snapshot_schedules")]
+pub async fn list_snapshot_schedules(
+    path: web::Path<(String, String)>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, dashboard_id) = path.into_inner();
+    if let Err(e) = acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::View,
+    )
+    .await
+    {
+        return Ok(e.into());
+    }
+    match scheduled_snapshots::list_schedules(&org_id, &dashboard_id).await {
+        Ok(list) => Ok(MetaHttpResponse::json(SnapshotScheduleList { list })),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteDashboardSnapshotSchedule
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "DeleteDashboardSnapshotSchedule",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("dashboard_id" = String, Path, description = "Dashboard ID"),
+        ("schedule_id" = String, Path, description = "Snapshot schedule ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/dashboards/{dashboard_id}/snapshot_schedules/{schedule_id}")]
+pub async fn delete_snapshot_schedule(
+    path: web::Path<(String, String, String)>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, dashboard_id, schedule_id) = path.into_inner();
+    if let Err(e) = acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::Edit,
+    )
+    .await
+    {
+        return Ok(e.into());
+    }
+    match scheduled_snapshots::delete_schedule(&org_id, &dashboard_id, &schedule_id).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Snapshot schedule deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// ListScheduledDashboardSnapshots
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "ListScheduledDashboardSnapshots",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("dashboard_id" = String, Path, description = "Dashboard ID"),
+        ("schedule_id" = String, Path, description = "Snapshot schedule ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = ScheduledSnapshotList),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/dashboards/{dashboard_id}/snapshot_schedules/{schedule_id}/snapshots")]
+pub async fn list_scheduled_snapshots(
+    path: web::Path<(String, String, String)>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, dashboard_id, schedule_id) = path.into_inner();
+    if let Err(e) = acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::View,
+    )
+    .await
+    {
+        return Ok(e.into());
+    }
+    match scheduled_snapshots::list_runs(&org_id, &dashboard_id, &schedule_id).await {
+        Ok(list) => Ok(MetaHttpResponse::json(ScheduledSnapshotList { list })),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// GetScheduledDashboardSnapshotDataset
+///
+/// Downloads the dataset of a panel, as listed in the `file` of the panel in the snapshot.
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "GetScheduledDashboardSnapshotDataset",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("dashboard_id" = String, Path, description = "Dashboard ID"),
+        ("schedule_id" = String, Path, description = "Snapshot schedule ID"),
+        ("snapshot_id" = String, Path, description = "Snapshot ID"),
+        ("file" = String, Path, description = "Dataset file name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/octet-stream", body = String),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get(
+    "/{org_id}/dashboards/{dashboard_id}/snapshot_schedules/{schedule_id}/snapshots/{snapshot_id}/{file}"
+)]
+pub async fn get_scheduled_snapshot_dataset(
+    path: web::Path<(String, String, String, String, String)>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, dashboard_id, schedule_id, snapshot_id, file) = path.into_inner();
+    if let Err(e) = acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::View,
+    )
+    .await
+    {
+        return Ok(e.into());
+    }
+    match scheduled_snapshots::get_dataset(
+        &org_id,
+        &dashboard_id,
+        &schedule_id,
+        &snapshot_id,
+        &file,
+    )
+    .await
+    {
+        Ok((format, data)) => {
+            let content_type = match format {
+                SnapshotFormat::Json => header::ContentType::json(),
+                SnapshotFormat::Parquet => header::ContentType::octet_stream(),
+            };
+            Ok(HttpResponse::Ok()
+                .content_type(content_type)
+                .insert_header(header::ContentDisposition::attachment(file))
+                .body(data))
+        }
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// GetDashboardSnapshotPage
+///
+/// Serves the html page of a snapshot, the unguessable snapshot id is the only credential so the
+/// link can be attached to incident reviews until it expires.
+#[utoipa::path(
+    context_path = "/snapshots",
+    tag = "Dashboards",
+    operation_id = "GetDashboardSnapshotPage",
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("snapshot_id" = String, Path, description = "Snapshot ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "text/html", body = String),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/{snapshot_id}")]
+pub async fn get_snapshot_page(path: web::Path<(String, String)>) -> Result<HttpResponse, Error> {
+    let (org_id, snapshot_id) = path.into_inner();
+    match snapshots::get_html(&org_id, &snapshot_id).await {
+        Ok(html) => Ok(HttpResponse::Ok()
+            .content_type(header::ContentType::html())
+            .insert_header((
+                "Content-Security-Policy",
+                "default-src 'none'; style-src 'unsafe-inline'",
+            ))
+            .body(html)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
@@ -16,12 +16,16 @@
 use std::io::Error;
 
 use actix_web::{HttpRequest, HttpResponse, delete, get, http::StatusCode, post, put, web};
-use config::meta::timed_annotations::{
-    ListTimedAnnotationsQuery, TimedAnnotation, TimedAnnotationDelete, TimedAnnotationReq,
+use config::meta::{
+    dashboards::acl::DashboardAccess,
+    timed_annotations::{
+        ListTimedAnnotationsQuery, TimedAnnotation, TimedAnnotationDelete, TimedAnnotationReq,
+    },
 };
 
 use crate::{
-    common::meta::http::HttpResponse as MetaHttpResponse, service::dashboards::timed_annotations,
+    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    service::dashboards::{acl, timed_annotations},
 };
 
 /// Create Timed Annotations
@@ -55,8 +59,19 @@ use crate::{
 pub async fn create_annotations(
     path: web::Path<(String, String)>,
     body: web::Bytes,
+    user_email: UserEmail,
 ) -> Result<HttpResponse, Error> {
-    let (_org_id, dashboard_id) = path.into_inner();
+    let (org_id, dashboard_id) = path.into_inner();
+    if let Err(e) = acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::Edit,
+    )
+    .await
+    {
+        return Ok(e.into());
+    }
     let req = serde_json::from_slice::<TimedAnnotationReq>(&body)?;
     if let Err(validation_err) = req.validate() {
         return Ok(MetaHttpResponse::bad_request(validation_err));
@@ -106,8 +121,19 @@ pub async fn create_annotations(
 pub async fn get_annotations(
     path: web::Path<(String, String)>,
     req: HttpRequest,
+    user_email: UserEmail,
 ) -> Result<HttpResponse, Error> {
-    let (_org_id, dashboard_id) = path.into_inner();
+    let (org_id, dashboard_id) = path.into_inner();
+    if let Err(e) = acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::View,
+    )
+    .await
+    {
+        return Ok(e.into());
+    }
     let Ok(query) = web::Query::<ListTimedAnnotationsQuery>::from_query(req.query_string()) else {
         return Ok(MetaHttpResponse::bad_request(
             "Error parsing query parameters".to_string(),
@@ -165,8 +191,19 @@ pub async fn get_annotations(
 pub async fn delete_annotations(
     path: web::Path<(String, String)>,
     body: web::Bytes,
+    user_email: UserEmail,
 ) -> Result<HttpResponse, Error> {
-    let (_org_id, dashboard_id) = path.into_inner();
+    let (org_id, dashboard_id) = path.into_inner();
+    if let Err(e) = acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::Edit,
+    )
+    .await
+    {
+        return Ok(e.into());
+    }
     let req: TimedAnnotationDelete = serde_json::from_slice(&body)?;
     if let Err(validation_err) = req.validate() {
         return Ok(MetaHttpResponse::bad_request(validation_err));
@@ -215,8 +252,19 @@ pub async fn delete_annotations(
 pub async fn update_annotations(
     path: web::Path<(String, String, String)>,
     body: web::Bytes,
+    user_email: UserEmail,
 ) -> Result<HttpResponse, Error> {
-    let (_org_id, dashboard_id, timed_annotation_id) = path.into_inner();
+    let (org_id, dashboard_id, timed_annotation_id) = path.into_inner();
+    if let Err(e) = acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::Edit,
+    )
+    .await
+    {
+        return Ok(e.into());
+    }
     let mut req: TimedAnnotation = serde_json::from_slice(&body)?;
     // ensure the annotation id is always set for update
     req.annotation_id = Some(timed_annotation_id.clone());
@@ -269,15 +317,32 @@ pub async fn update_annotations(
 pub async fn delete_annotation_panels(
     path: web::Path<(String, String, String)>,
     body: web::Bytes,
+    user_email: UserEmail,
 ) -> Result<HttpResponse, Error> {
-    let (_org_id, _dashboard_id, timed_annotation_id) = path.into_inner();
+    let (org_id, dashboard_id, timed_annotation_id) = path.into_inner();
+    if let Err(e) = acl::check(
+        &org_id,
+        &dashboard_id,
+        &user_email.user_id,
+        DashboardAccess::Edit,
+    )
+    .await
+    {
+        return Ok(e.into());
+    }
     let panels: Vec<String> = serde_json::from_slice(&body)?;
     if panels.is_empty() {
         return Ok(MetaHttpResponse::bad_request(
             "panels cannot be empty".to_string(),
         ));
     }
-    match timed_annotations::delete_timed_annotation_panels(&timed_annotation_id, panels).await {
+    match timed_annotations::delete_timed_annotation_panels(
+        &dashboard_id,
+        &timed_annotation_id,
+        panels,
+    )
+    .await
+    {
         Ok(_) => Ok(HttpResponse::Ok().finish()),
         Err(e) => {
             log::error!("Error deleting timed annotation panels: {}", e);
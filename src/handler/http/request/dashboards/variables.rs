@@ -0,0 +1,69 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpResponse, post, web};
+use config::meta::dashboards::variables::{ResolveVariablesRequest, ResolveVariablesResponse};
+
+use crate::{
+    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    service::dashboards::variables::{self, VariablesError},
+};
+
+fn map_error(e: VariablesError) -> HttpResponse {
+    match e {
+        VariablesError::DashboardError(e) => e.into(),
+        VariablesError::InvalidRequest(_) => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// ResolveDashboardVariables
+///
+/// Resolves the options and the selected value of every variable of the dashboard in one call.
+/// Variables are resolved after the variables they depend on, declared in `depends_on` or
+/// referenced in their filters, so their queries use the values selected for them.
+///
+/// #{"ratelimit_module":"Dashboards", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Dashboards",
+    operation_id = "ResolveDashboardVariables",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("dashboard_id" = String, Path, description = "Dashboard ID"),
+    ),
+    request_body(content = ResolveVariablesRequest, description = "Time range and selected values of the variables", content_type = "application/json", example = json!({"start_time": 1700000000000000_i64, "end_time": 1700003600000000_i64, "values": {"namespace": ["prod"], "pod": "web-1"}})),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = ResolveVariablesResponse),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/dashboards/{dashboard_id}/variables/resolve")]
+pub async fn resolve_variables(
+    path: web::Path<(String, String)>,
+    req: web::Json<ResolveVariablesRequest>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, dashboard_id) = path.into_inner();
+    match variables::resolve(&org_id, &dashboard_id, &user_email.user_id, &req).await {
+        Ok(resp) => Ok(MetaHttpResponse::json(resp)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
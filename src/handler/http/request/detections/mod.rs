@@ -0,0 +1,179 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpResponse, delete, get, post, put, web};
+use config::meta::detections::{Detection, DetectionList, DetectionRequest};
+
+use crate::{
+    common::meta::http::HttpResponse as MetaHttpResponse,
+    service::detections::{self, DetectionError},
+};
+
+fn map_error(e: DetectionError) -> HttpResponse {
+    match e {
+        DetectionError::NotFound => MetaHttpResponse::not_found(e),
+        DetectionError::InfraError(e) => MetaHttpResponse::internal_error(e),
+        e => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// CreateDetection
+///
+/// #{"ratelimit_module":"Detections", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Detections",
+    operation_id = "CreateDetection",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = DetectionRequest, description = "Sigma rule and the stream to evaluate it on", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Detection),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/detections")]
+pub async fn create(
+    path: web::Path<String>,
+    req: web::Json<DetectionRequest>,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match detections::create(&org_id, req.into_inner()).await {
+        Ok(detection) => Ok(MetaHttpResponse::json(detection)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// UpdateDetection
+///
+/// #{"ratelimit_module":"Detections", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Detections",
+    operation_id = "UpdateDetection",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Detection id"),
+    ),
+    request_body(content = DetectionRequest, description = "Sigma rule and the stream to evaluate it on", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Detection),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/detections/{id}")]
+pub async fn update(
+    path: web::Path<(String, String)>,
+    req: web::Json<DetectionRequest>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, id) = path.into_inner();
+    match detections::update(&org_id, &id, req.into_inner()).await {
+        Ok(detection) => Ok(MetaHttpResponse::json(detection)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// GetDetection
+///
+/// #{"ratelimit_module":"Detections", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Detections",
+    operation_id = "GetDetection",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Detection id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Detection),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/detections/{id}")]
+pub async fn get(path: web::Path<(String, String)>) -> Result<HttpResponse, Error> {
+    let (org_id, id) = path.into_inner();
+    match detections::get(&org_id, &id).await {
+        Ok(detection) => Ok(MetaHttpResponse::json(detection)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// ListDetections
+///
+/// #{"ratelimit_module":"Detections", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Detections",
+    operation_id = "ListDetections",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = DetectionList),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/detections")]
+pub async fn list(path: web::Path<String>) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match detections::list(&org_id).await {
+        Ok(list) => Ok(MetaHttpResponse::json(DetectionList { list })),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteDetection
+///
+/// #{"ratelimit_module":"Detections", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Detections",
+    operation_id = "DeleteDetection",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Detection id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/detections/{id}")]
+pub async fn delete(path: web::Path<(String, String)>) -> Result<HttpResponse, Error> {
+    let (org_id, id) = path.into_inner();
+    match detections::delete(&org_id, &id).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Detection deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
@@ -0,0 +1,159 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{collections::HashMap, io::Error};
+
+use actix_web::{HttpRequest, HttpResponse, delete, get, post, put, web};
+use config::meta::grok::{GrokPattern, GrokPatternList, GrokTestRequest, GrokTestResponse};
+
+use crate::{
+    common::meta::http::HttpResponse as MetaHttpResponse,
+    service::grok::{self, GrokPatternError},
+};
+
+fn map_error(e: GrokPatternError) -> HttpResponse {
+    match e {
+        GrokPatternError::NotFound => MetaHttpResponse::not_found(e),
+        GrokPatternError::InfraError(e) => MetaHttpResponse::internal_error(e),
+        e => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// ListGrokPatterns
+///
+/// #{"ratelimit_module":"Grok Patterns", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Grok",
+    operation_id = "ListGrokPatterns",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("builtin" = Option<bool>, Query, description = "Include the built-in pattern library"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = GrokPatternList),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/grok/patterns")]
+pub async fn list_patterns(
+    path: web::Path<String>,
+    req: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    let query = web::Query::<HashMap<String, String>>::from_query(req.query_string()).unwrap();
+    let builtin = query
+        .get("builtin")
+        .and_then(|v| v.parse::<bool>().ok())
+        .unwrap_or_default();
+    match grok::list(&org_id, builtin).await {
+        Ok(list) => Ok(MetaHttpResponse::json(GrokPatternList { list })),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// SaveGrokPattern
+///
+/// #{"ratelimit_module":"Grok Patterns", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Grok",
+    operation_id = "SaveGrokPattern",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "Pattern name"),
+    ),
+    request_body(content = GrokPattern, description = "Grok pattern data", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = GrokPattern),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/grok/patterns/{name}")]
+pub async fn save_pattern(
+    path: web::Path<(String, String)>,
+    req: web::Json<GrokPattern>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, name) = path.into_inner();
+    match grok::save(&org_id, &name, req.into_inner()).await {
+        Ok(pattern) => Ok(MetaHttpResponse::json(pattern)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteGrokPattern
+///
+/// #{"ratelimit_module":"Grok Patterns", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Grok",
+    operation_id = "DeleteGrokPattern",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "Pattern name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/grok/patterns/{name}")]
+pub async fn delete_pattern(path: web::Path<(String, String)>) -> Result<HttpResponse, Error> {
+    let (org_id, name) = path.into_inner();
+    match grok::delete(&org_id, &name).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Grok pattern deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// TestGrokPattern
+///
+/// #{"ratelimit_module":"Grok Patterns", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Grok",
+    operation_id = "TestGrokPattern",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = GrokTestRequest, description = "Grok expression and sample lines", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = GrokTestResponse),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/grok/_test")]
+pub async fn test_pattern(
+    path: web::Path<String>,
+    req: web::Json<GrokTestRequest>,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match grok::test(&org_id, req.into_inner()) {
+        Ok(resp) => Ok(MetaHttpResponse::json(resp)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use std::io::Error;
+
+use actix_web::{HttpResponse, get, web};
+use config::meta::incident_timeline::{IncidentTimeline, IncidentTimelineQuery};
+
+use crate::{
+    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    service::incident_timeline::{self, IncidentTimelineError},
+};
+
+/// GetIncidentTimeline
+///
+/// Assembles a timeline of fired alerts, annotations, config changes and log volume anomalies
+/// for a time range, as JSON or as a Markdown document for postmortems.
+///
+/// #{"ratelimit_module":"Incident Timeline", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Incident Timeline",
+    operation_id = "GetIncidentTimeline",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        IncidentTimelineQuery,
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = IncidentTimeline),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/incident_timeline")]
+pub async fn get_timeline(
+    path: web::Path<String>,
+    query: web::Query<IncidentTimelineQuery>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match incident_timeline::assemble(&org_id, &user_email.user_id, &query).await {
+        Ok(timeline) if query.is_markdown() => Ok(HttpResponse::Ok()
+            .content_type("text/markdown; charset=utf-8")
+            .body(timeline.to_markdown())),
+        Ok(timeline) => Ok(MetaHttpResponse::json(timeline)),
+        Err(e @ IncidentTimelineError::InvalidRequest(_)) => Ok(MetaHttpResponse::bad_request(e)),
+    }
+}
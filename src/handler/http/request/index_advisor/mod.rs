@@ -0,0 +1,151 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{collections::HashMap, io::Error, str::FromStr};
+
+use actix_web::{HttpResponse, get, post, web};
+use config::meta::index_advisor::{
+    IndexRecommendation, IndexRecommendationList, RecommendationStatus,
+};
+
+use crate::{
+    common::{
+        meta::http::HttpResponse as MetaHttpResponse, utils::http::get_stream_type_from_request,
+    },
+    service::index_advisor::{self, IndexAdvisorError},
+};
+
+fn map_error(e: IndexAdvisorError) -> HttpResponse {
+    match e {
+        IndexAdvisorError::NotFound => MetaHttpResponse::not_found(e),
+        IndexAdvisorError::NotOpen(_) => MetaHttpResponse::conflict(e),
+        e => MetaHttpResponse::internal_error(e),
+    }
+}
+
+/// ListIndexRecommendations
+///
+/// Lists the stream settings recommended by the index advisor, indexing the fields the slow
+/// queries of the streams filter on.
+///
+/// #{"ratelimit_module":"Streams", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "ListIndexRecommendations",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = Option<String>, Query, description = "Only the recommendations of the stream"),
+        ("type" = Option<String>, Query, description = "Stream type, default logs"),
+        ("status" = Option<String>, Query, description = "Only the recommendations with the status: open, applied or dismissed"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = IndexRecommendationList),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/index_recommendations")]
+pub async fn list_recommendations(
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    let stream = query
+        .get("stream_name")
+        .map(|stream_name| (stream_type, stream_name.as_str()));
+    let status = match query
+        .get("status")
+        .map(|s| RecommendationStatus::from_str(s))
+    {
+        Some(Ok(status)) => Some(status),
+        Some(Err(e)) => return Ok(MetaHttpResponse::bad_request(e)),
+        None => None,
+    };
+    match index_advisor::list(&org_id, stream, status).await {
+        Ok(list) => Ok(MetaHttpResponse::json(IndexRecommendationList { list })),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// ApplyIndexRecommendation
+///
+/// Approves the recommendation, adding its field to the bloom filter fields, partition keys or
+/// full text search keys of the stream.
+///
+/// #{"ratelimit_module":"Streams", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "ApplyIndexRecommendation",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Recommendation ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = IndexRecommendation),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+        (status = 409, description = "Conflict", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/index_recommendations/{id}/apply")]
+pub async fn apply_recommendation(
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, id) = path.into_inner();
+    match index_advisor::apply(&org_id, &id).await {
+        Ok(rec) => Ok(MetaHttpResponse::json(rec)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DismissIndexRecommendation
+///
+/// Dismisses the recommendation, which the index advisor doesn't make again.
+///
+/// #{"ratelimit_module":"Streams", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "DismissIndexRecommendation",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Recommendation ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+        (status = 409, description = "Conflict", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/index_recommendations/{id}/dismiss")]
+pub async fn dismiss_recommendation(
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, id) = path.into_inner();
+    match index_advisor::dismiss(&org_id, &id).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Index recommendation dismissed")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use std::io::Error;
+
+use actix_web::{HttpResponse, delete, get, put, web};
+use config::meta::log_metrics::{LogMetricRule, LogMetricRuleList};
+
+use crate::{
+    common::meta::http::HttpResponse as MetaHttpResponse,
+    service::log_metrics::{self, LogMetricError},
+};
+
+fn map_error(e: LogMetricError) -> HttpResponse {
+    match e {
+        LogMetricError::NotFound => MetaHttpResponse::not_found(e),
+        LogMetricError::InfraError(e) => MetaHttpResponse::internal_error(e),
+        e => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// ListLogMetricRules
+///
+/// #{"ratelimit_module":"Log Metrics", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Log Metrics",
+    operation_id = "ListLogMetricRules",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = LogMetricRuleList),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/log_metrics")]
+pub async fn list_rules(path: web::Path<String>) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match log_metrics::list(&org_id).await {
+        Ok(list) => Ok(MetaHttpResponse::json(LogMetricRuleList { list })),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// GetLogMetricRule
+///
+/// #{"ratelimit_module":"Log Metrics", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Log Metrics",
+    operation_id = "GetLogMetricRule",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "Metric name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = LogMetricRule),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/log_metrics/{name}")]
+pub async fn get_rule(path: web::Path<(String, String)>) -> Result<HttpResponse, Error> {
+    let (org_id, name) = path.into_inner();
+    match log_metrics::get(&org_id, &name).await {
+        Ok(rule) => Ok(MetaHttpResponse::json(rule)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// SaveLogMetricRule
+///
+/// #{"ratelimit_module":"Log Metrics", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Log Metrics",
+    operation_id = "SaveLogMetricRule",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "Metric name"),
+    ),
+    request_body(content = LogMetricRule, description = "Extraction rule", content_type = "application/json", example = json!({"stream": "nginx", "conditions": [{"field": "status", "operator": ">=", "value": "500"}], "metric_type": "counter", "labels": ["service"]})),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = LogMetricRule),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/log_metrics/{name}")]
+pub async fn save_rule(
+    path: web::Path<(String, String)>,
+    req: web::Json<LogMetricRule>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, name) = path.into_inner();
+    match log_metrics::save(&org_id, &name, req.into_inner()).await {
+        Ok(rule) => Ok(MetaHttpResponse::json(rule)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteLogMetricRule
+///
+/// #{"ratelimit_module":"Log Metrics", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Log Metrics",
+    operation_id = "DeleteLogMetricRule",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "Metric name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/log_metrics/{name}")]
+pub async fn delete_rule(path: web::Path<(String, String)>) -> Result<HttpResponse, Error> {
+    let (org_id, name) = path.into_inner();
+    match log_metrics::delete(&org_id, &name).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Log metric rule deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
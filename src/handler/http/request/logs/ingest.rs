@@ -24,7 +24,7 @@ use crate::{
     common::meta::{
         http::HttpResponse as MetaHttpResponse,
         ingestion::{
-            GCPIngestionRequest, HecResponse, HecStatus, IngestionRequest,
+            CsvIngestionQuery, GCPIngestionRequest, HecResponse, HecStatus, IngestionRequest,
             KinesisFHIngestionResponse, KinesisFHRequest,
         },
     },
@@ -238,6 +238,101 @@ pub async fn json(
     Ok(resp)
 }
 
+/// _csv ingestion API
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Logs",
+    operation_id = "LogsIngestionCsv",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("delimiter" = Option<String>, Query, description = "Column separator, a single character or `tab`, defaults to `,` (tab for text/tab-separated-values)"),
+        ("has_header" = Option<bool>, Query, description = "Whether the first row holds the column names, defaults to true"),
+        ("columns" = Option<String>, Query, description = "Comma separated column names by position, they take precedence over the header row"),
+        ("timestamp_column" = Option<String>, Query, description = "Column holding the event time"),
+        ("timestamp_format" = Option<String>, Query, description = "Format of the timestamp column: auto, epoch_s, epoch_ms, epoch_us, epoch_ns, rfc3339, rfc2822 or a strptime format"),
+    ),
+    request_body(content = String, description = "Ingest data (csv/tsv)", content_type = "text/csv", example = "time,level,message\n2024-01-02T03:04:05Z,error,failed to connect"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = IngestionResponse, example = json!({"code": 200,"status": [{"name": "olympics","successful": 3,"failed": 0}]})),
+        (status = 400, description = "Invalid csv or options", content_type = "application/json", body = HttpResponse),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/{stream_name}/_csv")]
+pub async fn csv(
+    thread_id: web::Data<usize>,
+    path: web::Path<(String, String)>,
+    body: web::Bytes,
+    in_req: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let (org_id, stream_name) = path.into_inner();
+    let user_email = in_req.headers().get("user_id").unwrap().to_str().unwrap();
+
+    // log start processing time
+    let process_time = if config::get_config().limit.http_slow_log_threshold > 0 {
+        config::utils::time::now_micros()
+    } else {
+        0
+    };
+
+    let Ok(query) = web::Query::<CsvIngestionQuery>::from_query(in_req.query_string()) else {
+        return Ok(MetaHttpResponse::bad_request("Invalid query parameters"));
+    };
+    let content_type = in_req
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok());
+    let options = match logs::csv::CsvOptions::new(&query, content_type) {
+        Ok(v) => v,
+        Err(e) => return Ok(MetaHttpResponse::bad_request(e)),
+    };
+    let records = match logs::csv::parse(&body, &options) {
+        Ok(v) => v,
+        Err(e) => return Ok(MetaHttpResponse::bad_request(format!("Invalid csv: {e}"))),
+    };
+
+    let mut resp = match logs::ingest::ingest(
+        **thread_id,
+        &org_id,
+        &stream_name,
+        IngestionRequest::Csv(&records),
+        user_email,
+        None,
+    )
+    .await
+    {
+        Ok(v) => match v.code {
+            503 => HttpResponse::ServiceUnavailable().json(v),
+            _ => MetaHttpResponse::json(v),
+        },
+        Err(e) => {
+            log::error!("Error processing request {org_id}/{stream_name}/_csv: {e}");
+            if matches!(e, infra::errors::Error::ResourceError(_)) {
+                HttpResponse::ServiceUnavailable().json(MetaHttpResponse::error(
+                    http::StatusCode::SERVICE_UNAVAILABLE,
+                    e,
+                ))
+            } else {
+                HttpResponse::BadRequest()
+                    .json(MetaHttpResponse::error(http::StatusCode::BAD_REQUEST, e))
+            }
+        }
+    };
+
+    if process_time > 0 {
+        resp.headers_mut().insert(
+            header::HeaderName::from_static("o2_process_time"),
+            header::HeaderValue::from_str(&process_time.to_string()).unwrap(),
+        );
+    }
+
+    Ok(resp)
+}
+
 /// _kinesis_firehose ingestion API
 #[utoipa::path(
     context_path = "/api",
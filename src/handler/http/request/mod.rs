@@ -17,34 +17,51 @@ pub mod actions;
 #[cfg(feature = "enterprise")]
 pub mod ai;
 pub mod alerts;
+pub mod annotations;
 pub mod authz;
 #[cfg(feature = "cloud")]
 pub mod billings;
+pub mod cases;
+pub mod chaos;
 pub mod clusters;
+pub mod column_mask;
 pub mod dashboards;
+pub mod detections;
 pub mod enrichment_table;
 #[allow(deprecated)]
 pub mod folders;
 pub mod functions;
+pub mod grok;
+pub mod incident_timeline;
+pub mod index_advisor;
 pub mod keys;
 pub mod kv;
+pub mod log_metrics;
 pub mod logs;
 pub mod metrics;
 pub mod organization;
 pub mod pipeline;
 pub mod promql;
+pub mod query_template;
 pub mod ratelimit;
+pub mod remote_write_filter;
+pub mod row_policy;
 pub mod rum;
 #[cfg(feature = "enterprise")]
 pub mod script_server;
 pub mod search;
 pub mod service_accounts;
 pub mod short_url;
+pub mod sql_policy;
 pub mod status;
 pub mod stream;
+pub mod synthetic_data;
 pub mod syslog;
+pub mod threat_intel;
 pub mod traces;
+pub mod ui_profile;
 pub mod users;
+pub mod workspaces;
 pub mod ws;
 
 pub const CONTENT_TYPE_JSON: &str = "application/json";
@@ -32,7 +32,10 @@ use crate::{
             OrganizationSetting, OrganizationSettingPayload, OrganizationSettingResponse,
         },
     },
-    service::db::organization::{get_org_setting, set_org_setting},
+    service::{
+        db::organization::{get_org_setting, set_org_setting},
+        ingestion::semconv,
+    },
 };
 
 /// Organization specific settings
@@ -108,6 +111,14 @@ async fn create(
         data.enable_streaming_search = enable_streaming_search;
     }
 
+    if let Some(field_normalization) = settings.field_normalization {
+        if let Err(e) = semconv::validate(&field_normalization) {
+            return Ok(MetaHttpResponse::bad_request(e));
+        }
+        field_found = true;
+        data.field_normalization = field_normalization;
+    }
+
     if !field_found {
         return Ok(MetaHttpResponse::bad_request("No valid field found"));
     }
@@ -667,49 +667,12 @@ async fn series(
 
     #[cfg(feature = "enterprise")]
     {
-        use crate::{
-            common::utils::auth::{AuthExtractor, is_root_user},
-            service::db::org_users::get_cached_user_org,
-        };
-
-        let metric_name = match selector
+        let metric_name = selector
             .as_ref()
             .and_then(metrics::prom::try_into_metric_name)
-        {
-            Some(name) => name,
-            None => "".to_string(),
-        };
-
-        let user_id = _in_req.headers().get("user_id").unwrap();
-        let user_email = user_id.to_str().unwrap();
-
-        if !is_root_user(user_email) {
-            let user: config::meta::user::User = get_cached_user_org(org_id, user_email).unwrap();
-            let stream_type_str = StreamType::Metrics.as_str();
-            if user.is_external
-                && !crate::handler::http::auth::validator::check_permissions(
-                    user_email,
-                    AuthExtractor {
-                        auth: "".to_string(),
-                        method: "GET".to_string(),
-                        o2_type: format!(
-                            "{}:{}",
-                            OFGA_MODELS
-                                .get(stream_type_str)
-                                .map_or(stream_type_str, |model| model.key),
-                            metric_name
-                        ),
-                        org_id: org_id.to_string(),
-                        bypass_check: false,
-                        parent_id: "".to_string(),
-                    },
-                    user.role,
-                    user.is_external,
-                )
-                .await
-            {
-                return Ok(MetaHttpResponse::forbidden("Unauthorized Access"));
-            }
+            .unwrap_or_default();
+        if !check_metric_permission(org_id, &metric_name, &_in_req).await {
+            return Ok(MetaHttpResponse::forbidden("Unauthorized Access"));
         }
     }
 
@@ -726,6 +689,44 @@ async fn series(
     )
 }
 
+/// Returns false when an external user doesn't have access to the metric.
+#[cfg(feature = "enterprise")]
+async fn check_metric_permission(org_id: &str, metric_name: &str, in_req: &HttpRequest) -> bool {
+    use crate::{
+        common::utils::auth::{AuthExtractor, is_root_user},
+        service::db::org_users::get_cached_user_org,
+    };
+
+    let user_id = in_req.headers().get("user_id").unwrap();
+    let user_email = user_id.to_str().unwrap();
+    if is_root_user(user_email) {
+        return true;
+    }
+    let user: config::meta::user::User = get_cached_user_org(org_id, user_email).unwrap();
+    let stream_type_str = StreamType::Metrics.as_str();
+    !user.is_external
+        || crate::handler::http::auth::validator::check_permissions(
+            user_email,
+            AuthExtractor {
+                auth: "".to_string(),
+                method: "GET".to_string(),
+                o2_type: format!(
+                    "{}:{}",
+                    OFGA_MODELS
+                        .get(stream_type_str)
+                        .map_or(stream_type_str, |model| model.key),
+                    metric_name
+                ),
+                org_id: org_id.to_string(),
+                bypass_check: false,
+                parent_id: "".to_string(),
+            },
+            user.role,
+            user.is_external,
+        )
+        .await
+}
+
 /// prometheus getting label names
 ///
 /// #{"ratelimit_module":"Metrics", "ratelimit_module_operation":"get"}#
@@ -884,6 +885,224 @@ pub async fn label_values(
     )
 }
 
+/// MetricsExplorerMetrics
+///
+/// #{"ratelimit_module":"Metrics", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Metrics",
+    operation_id = "MetricsExplorerMetrics",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("search" = Option<String>, Query, description = "Only return metrics whose name contains this string"),
+        ("limit" = Option<usize>, Query, description = "Maximum number of metrics to return"),
+        ("start" = Option<String>, Query, description = "<rfc3339 | unix_timestamp>: Start timestamp"),
+        ("end" = Option<String>, Query, description = "<rfc3339 | unix_timestamp>: End timestamp"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse, example = json!({
+            "status": "success",
+            "data": [
+                {
+                    "name": "http_requests_total",
+                    "type": "counter",
+                    "help": "Number of HTTP requests",
+                    "unit": "",
+                    "samples": 1024000,
+                    "labels": 4
+                }
+            ]
+        })),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/prometheus/api/v1/explorer/metrics")]
+pub async fn explorer_metrics(
+    org_id: web::Path<String>,
+    req: web::Query<config::meta::promql::RequestExplorerMetrics>,
+) -> Result<HttpResponse, Error> {
+    let config::meta::promql::RequestExplorerMetrics {
+        search,
+        limit,
+        start,
+        end,
+    } = req.into_inner();
+    let (_, start, end) = match validate_metadata_params(None, start, end) {
+        Ok(v) => v,
+        Err(e) => {
+            return Ok(HttpResponse::BadRequest()
+                .json(promql::ApiFuncResponse::<()>::err_bad_data(e, None)));
+        }
+    };
+    Ok(
+        match metrics::explorer::list_metrics(&org_id, search.as_deref(), limit, start, end).await {
+            Ok(resp) => HttpResponse::Ok().json(promql::ApiFuncResponse::ok(resp, None)),
+            Err(err) => {
+                log::error!("explorer list_metrics failed: {err}");
+                HttpResponse::InternalServerError().json(
+                    promql::ApiFuncResponse::<()>::err_internal(err.to_string(), None),
+                )
+            }
+        },
+    )
+}
+
+/// MetricsExplorerLabels
+///
+/// #{"ratelimit_module":"Metrics", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Metrics",
+    operation_id = "MetricsExplorerLabels",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("match[]" = String, Query, description = "<series_selector>: Series selector, it must contain a metric name"),
+        ("start" = Option<String>, Query, description = "<rfc3339 | unix_timestamp>: Start timestamp"),
+        ("end" = Option<String>, Query, description = "<rfc3339 | unix_timestamp>: End timestamp"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse, example = json!({
+            "status": "success",
+            "data": {
+                "metric": "http_requests_total",
+                "series": 1200,
+                "labels": [
+                    { "name": "job", "values": 3, "cardinality": "low" },
+                    { "name": "path", "values": 400, "cardinality": "medium" }
+                ]
+            }
+        })),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/prometheus/api/v1/explorer/labels")]
+pub async fn explorer_labels(
+    org_id: web::Path<String>,
+    req: web::Query<config::meta::promql::RequestExplorerSeries>,
+    _in_req: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let req = req.into_inner();
+    let (selector, start, end) = match validate_explorer_params(req.matcher, req.start, req.end) {
+        Ok(v) => v,
+        Err(e) => {
+            return Ok(HttpResponse::BadRequest()
+                .json(promql::ApiFuncResponse::<()>::err_bad_data(e, None)));
+        }
+    };
+
+    #[cfg(feature = "enterprise")]
+    {
+        let metric_name = metrics::prom::try_into_metric_name(&selector).unwrap_or_default();
+        if !check_metric_permission(&org_id, &metric_name, &_in_req).await {
+            return Ok(MetaHttpResponse::forbidden("Unauthorized Access"));
+        }
+    }
+
+    Ok(
+        match metrics::explorer::get_labels(&org_id, &selector, start, end).await {
+            Ok(resp) => HttpResponse::Ok().json(promql::ApiFuncResponse::ok(resp, None)),
+            Err(err) => {
+                log::error!("explorer get_labels failed: {err}");
+                HttpResponse::InternalServerError().json(
+                    promql::ApiFuncResponse::<()>::err_internal(err.to_string(), None),
+                )
+            }
+        },
+    )
+}
+
+/// MetricsExplorerSeries
+///
+/// #{"ratelimit_module":"Metrics", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Metrics",
+    operation_id = "MetricsExplorerSeries",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("match[]" = String, Query, description = "<series_selector>: Series selector, it must contain a metric name"),
+        ("limit" = Option<usize>, Query, description = "Maximum number of series to return, defaults to 20"),
+        ("start" = Option<String>, Query, description = "<rfc3339 | unix_timestamp>: Start timestamp"),
+        ("end" = Option<String>, Query, description = "<rfc3339 | unix_timestamp>: End timestamp"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse, example = json!({
+            "status": "success",
+            "data": {
+                "metric": "up",
+                "total": 42,
+                "truncated": true,
+                "series": [
+                    { "__name__": "up", "job": "node", "instance": "localhost:9100" }
+                ]
+            }
+        })),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/prometheus/api/v1/explorer/series")]
+pub async fn explorer_series(
+    org_id: web::Path<String>,
+    req: web::Query<config::meta::promql::RequestExplorerSeries>,
+    _in_req: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let req = req.into_inner();
+    let limit = req
+        .limit
+        .unwrap_or(config::meta::promql::EXPLORER_PREVIEW_LIMIT);
+    let (selector, start, end) = match validate_explorer_params(req.matcher, req.start, req.end) {
+        Ok(v) => v,
+        Err(e) => {
+            return Ok(HttpResponse::BadRequest()
+                .json(promql::ApiFuncResponse::<()>::err_bad_data(e, None)));
+        }
+    };
+
+    #[cfg(feature = "enterprise")]
+    {
+        let metric_name = metrics::prom::try_into_metric_name(&selector).unwrap_or_default();
+        if !check_metric_permission(&org_id, &metric_name, &_in_req).await {
+            return Ok(MetaHttpResponse::forbidden("Unauthorized Access"));
+        }
+    }
+
+    Ok(
+        match metrics::explorer::preview_series(&org_id, &selector, limit, start, end).await {
+            Ok(resp) => HttpResponse::Ok().json(promql::ApiFuncResponse::ok(resp, None)),
+            Err(err) => {
+                log::error!("explorer preview_series failed: {err}");
+                HttpResponse::InternalServerError().json(
+                    promql::ApiFuncResponse::<()>::err_internal(err.to_string(), None),
+                )
+            }
+        },
+    )
+}
+
+/// Same as [`validate_metadata_params`], but the series selector is required.
+fn validate_explorer_params(
+    matcher: Option<String>,
+    start: Option<String>,
+    end: Option<String>,
+) -> Result<(parser::VectorSelector, i64, i64), String> {
+    match validate_metadata_params(matcher, start, end)? {
+        (Some(selector), start, end) => Ok((selector, start, end)),
+        (None, ..) => Err("match[] argument is required, e.g. `match[]=up`".to_owned()),
+    }
+}
+
 fn validate_metadata_params(
     matcher: Option<String>,
     start: Option<String>,
@@ -1029,6 +1248,12 @@ async fn search(
             data,
             Some(trace_id.to_string()),
         ))),
+        Err(errors::Error::ErrorCode(errors::ErrorCodes::SearchSamplesLimitExceeded(err))) => Ok(
+            HttpResponse::UnprocessableEntity().json(promql::ApiFuncResponse::<()>::err_exec(
+                err,
+                Some(trace_id.to_string()),
+            )),
+        ),
         Err(err) => {
             let err = match err {
                 errors::Error::ErrorCode(code) => code.get_error_detail(),
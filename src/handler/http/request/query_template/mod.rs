@@ -0,0 +1,233 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use std::{collections::HashMap, io::Error};
+
+use actix_web::{HttpRequest, HttpResponse, delete, get, http::header, post, put, web};
+use config::meta::query_template::{
+    QueryTemplate, QueryTemplateExecuteRequest, QueryTemplateExecuteResponse, QueryTemplateList,
+    QueryTemplateTokenRequest, QueryTemplateTokenResponse,
+};
+
+use crate::{
+    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    service::query_template::{self, QueryTemplateError},
+};
+
+fn map_error(e: QueryTemplateError) -> HttpResponse {
+    match e {
+        QueryTemplateError::NotFound => MetaHttpResponse::not_found(e),
+        QueryTemplateError::InvalidToken(_) => MetaHttpResponse::unauthorized(e),
+        QueryTemplateError::InfraError(e) => MetaHttpResponse::internal_error(e),
+        e => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// ListQueryTemplates
+///
+/// #{"ratelimit_module":"Query Templates", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Query Templates",
+    operation_id = "ListQueryTemplates",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = QueryTemplateList),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/query_templates")]
+pub async fn list_templates(path: web::Path<String>) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match query_template::list(&org_id).await {
+        Ok(list) => Ok(MetaHttpResponse::json(QueryTemplateList { list })),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// GetQueryTemplate
+///
+/// #{"ratelimit_module":"Query Templates", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Query Templates",
+    operation_id = "GetQueryTemplate",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "Template name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = QueryTemplate),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/query_templates/{name}")]
+pub async fn get_template(path: web::Path<(String, String)>) -> Result<HttpResponse, Error> {
+    let (org_id, name) = path.into_inner();
+    match query_template::get(&org_id, &name).await {
+        Ok(template) => Ok(MetaHttpResponse::json(template)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// SaveQueryTemplate
+///
+/// #{"ratelimit_module":"Query Templates", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Query Templates",
+    operation_id = "SaveQueryTemplate",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "Template name"),
+    ),
+    request_body(content = QueryTemplate, description = "Query template data", content_type = "application/json", example = json!({"sql": "SELECT histogram(_timestamp) AS t, COUNT(*) AS requests FROM usage WHERE customer_id = $customer GROUP BY t", "params": [{"name": "customer", "type": "string"}], "max_time_range_hours": 720})),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = QueryTemplate),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/query_templates/{name}")]
+pub async fn save_template(
+    path: web::Path<(String, String)>,
+    req: web::Json<QueryTemplate>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, name) = path.into_inner();
+    match query_template::save(&org_id, &name, req.into_inner()).await {
+        Ok(template) => Ok(MetaHttpResponse::json(template)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteQueryTemplate
+///
+/// #{"ratelimit_module":"Query Templates", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Query Templates",
+    operation_id = "DeleteQueryTemplate",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "Template name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/query_templates/{name}")]
+pub async fn delete_template(path: web::Path<(String, String)>) -> Result<HttpResponse, Error> {
+    let (org_id, name) = path.into_inner();
+    match query_template::delete(&org_id, &name).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Query template deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// CreateQueryTemplateToken
+///
+/// #{"ratelimit_module":"Query Templates", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Query Templates",
+    operation_id = "CreateQueryTemplateToken",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "Template name"),
+    ),
+    request_body(content = QueryTemplateTokenRequest, description = "Token restrictions", content_type = "application/json", example = json!({"params": {"customer": {"values": ["acme"]}}, "expires_in": 3600})),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = QueryTemplateTokenResponse),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/query_templates/{name}/token")]
+pub async fn create_token(
+    path: web::Path<(String, String)>,
+    req: web::Json<QueryTemplateTokenRequest>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, name) = path.into_inner();
+    match query_template::create_token(&org_id, &name, &user_email.user_id, req.into_inner()).await
+    {
+        Ok(token) => Ok(MetaHttpResponse::json(token)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// ExecuteQueryTemplate
+///
+/// Runs a query template for an external application, authenticated with a template token given
+/// as bearer token or as `token` query parameter instead of user credentials.
+#[utoipa::path(
+    context_path = "/embed",
+    tag = "Query Templates",
+    operation_id = "ExecuteQueryTemplate",
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("name" = String, Path, description = "Template name"),
+        ("token" = Option<String>, Query, description = "Template token, if not given in the Authorization header"),
+    ),
+    request_body(content = QueryTemplateExecuteRequest, description = "Params and time range", content_type = "application/json", example = json!({"params": {"customer": "acme"}, "start_time": 1700000000000000_i64, "end_time": 1700086400000000_i64})),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = QueryTemplateExecuteResponse),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 401, description = "Unauthorized", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/query_templates/{name}/_execute")]
+pub async fn execute_template(
+    path: web::Path<(String, String)>,
+    body: web::Json<QueryTemplateExecuteRequest>,
+    req: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let (org_id, name) = path.into_inner();
+    let query = web::Query::<HashMap<String, String>>::from_query(req.query_string())
+        .map(|q| q.into_inner())
+        .unwrap_or_default();
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|v| v.trim().to_string())
+        .or_else(|| query.get("token").cloned());
+    let Some(token) = token else {
+        return Ok(MetaHttpResponse::unauthorized(
+            "Missing query template token",
+        ));
+    };
+    match query_template::execute(&org_id, &name, &token, body.into_inner()).await {
+        Ok(resp) => Ok(MetaHttpResponse::json(resp)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use std::io::Error;
+
+use actix_web::{HttpResponse, delete, get, put, web};
+use config::meta::remote_write_filter::RemoteWriteFilter;
+
+use crate::{
+    common::meta::http::HttpResponse as MetaHttpResponse,
+    service::remote_write_filter::{self, RemoteWriteFilterError},
+};
+
+fn map_error(e: RemoteWriteFilterError) -> HttpResponse {
+    match e {
+        RemoteWriteFilterError::NotFound => MetaHttpResponse::not_found(e),
+        RemoteWriteFilterError::InfraError(e) => MetaHttpResponse::internal_error(e),
+        e => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// GetRemoteWriteFilter
+///
+/// #{"ratelimit_module":"Remote Write Filter", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Remote Write Filter",
+    operation_id = "GetRemoteWriteFilter",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = RemoteWriteFilter),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/remote_write_filter")]
+pub async fn get_filter(path: web::Path<String>) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match remote_write_filter::get(&org_id).await {
+        Ok(filter) => Ok(MetaHttpResponse::json(filter)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// SaveRemoteWriteFilter
+///
+/// #{"ratelimit_module":"Remote Write Filter", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Remote Write Filter",
+    operation_id = "SaveRemoteWriteFilter",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = RemoteWriteFilter, description = "Remote write filter data", content_type = "application/json", example = json!({"enabled": true, "allow": [{"metric": "node_.*|up"}], "deny": [{"labels": {"namespace": "kube-.*"}}]})),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = RemoteWriteFilter),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/remote_write_filter")]
+pub async fn save_filter(
+    path: web::Path<String>,
+    req: web::Json<RemoteWriteFilter>,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match remote_write_filter::save(&org_id, req.into_inner()).await {
+        Ok(filter) => Ok(MetaHttpResponse::json(filter)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteRemoteWriteFilter
+///
+/// #{"ratelimit_module":"Remote Write Filter", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Remote Write Filter",
+    operation_id = "DeleteRemoteWriteFilter",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/remote_write_filter")]
+pub async fn delete_filter(path: web::Path<String>) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match remote_write_filter::delete(&org_id).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Remote write filter deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpResponse, delete, get, put, web};
+use config::meta::{row_policy::RowPolicy, stream::StreamType, user::UserRole};
+
+use crate::{
+    common::meta::http::HttpResponse as MetaHttpResponse,
+    service::row_policy::{self, RowPolicyError},
+};
+
+fn map_error(e: RowPolicyError) -> HttpResponse {
+    match e {
+        RowPolicyError::NotFound => MetaHttpResponse::not_found(e),
+        RowPolicyError::InfraError(e) => MetaHttpResponse::internal_error(e),
+        e => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// ListRowPolicies
+///
+/// #{"ratelimit_module":"Row Policy", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Row Policy",
+    operation_id = "ListRowPolicies",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Vec<RowPolicy>),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/row_policies")]
+pub async fn list_policies(path: web::Path<String>) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match row_policy::list(&org_id).await {
+        Ok(list) => Ok(MetaHttpResponse::json(list)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// SaveRowPolicy
+///
+/// Creates or replaces the row policy of a role on a stream. The predicate is added with AND to
+/// every query of the users with the role on the stream, `{user.<attribute>}` placeholders in
+/// string literals are replaced with the email, domain, first_name, last_name, role or org of
+/// the user.
+///
+/// #{"ratelimit_module":"Row Policy", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Row Policy",
+    operation_id = "SaveRowPolicy",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = RowPolicy, description = "Row policy data", content_type = "application/json", example = json!({"stream_type": "logs", "stream_name": "shared", "role": "viewer", "predicate": "tenant_id = '{user.domain}'"})),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = RowPolicy),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/row_policies")]
+pub async fn save_policy(
+    path: web::Path<String>,
+    req: web::Json<RowPolicy>,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match row_policy::save(&org_id, req.into_inner()).await {
+        Ok(policy) => Ok(MetaHttpResponse::json(policy)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteRowPolicy
+///
+/// #{"ratelimit_module":"Row Policy", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Row Policy",
+    operation_id = "DeleteRowPolicy",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_type" = String, Path, description = "Stream type"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("role" = String, Path, description = "Role the policy applies to"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/row_policies/{stream_type}/{stream_name}/{role}")]
+pub async fn delete_policy(
+    path: web::Path<(String, String, String, String)>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, stream_type, stream_name, role) = path.into_inner();
+    let stream_type = StreamType::from(stream_type.as_str());
+    // unknown roles parse as admin
+    let Some(role) = role
+        .parse::<UserRole>()
+        .ok()
+        .filter(|r| r.to_string() == role)
+    else {
+        return Ok(MetaHttpResponse::bad_request(format!(
+            "invalid role {role}"
+        )));
+    };
+    match row_policy::delete(&org_id, stream_type, &stream_name, &role).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Row policy deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
@@ -152,6 +152,10 @@ pub(crate) async fn around(
             skip_wal: false,
             streaming_output: false,
             streaming_id: None,
+            max_points: None,
+            time_ranges: vec![],
+            time_shifts: vec![],
+            variables: vec![],
         },
         encoding: config::meta::search::RequestEncoding::Empty,
         regions: regions.clone(),
@@ -185,6 +189,10 @@ pub(crate) async fn around(
             skip_wal: false,
             streaming_output: false,
             streaming_id: None,
+            max_points: None,
+            time_ranges: vec![],
+            time_shifts: vec![],
+            variables: vec![],
         },
         encoding: config::meta::search::RequestEncoding::Empty,
         regions,
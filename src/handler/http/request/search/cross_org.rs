@@ -0,0 +1,106 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpRequest, HttpResponse, post, web};
+use config::{META_ORG_ID, get_config, meta::search::CrossOrgSearchRequest};
+use hashbrown::HashMap;
+use tracing::Span;
+
+use crate::{
+    common::{
+        meta::http::HttpResponse as MetaHttpResponse,
+        utils::http::{get_or_create_trace_id, get_stream_type_from_request},
+    },
+    handler::http::request::search::error_utils::map_error_to_http_response,
+    service::search::cross_org,
+};
+
+/// SearchCrossOrg
+///
+/// Runs the query on the streams with the same name in multiple organizations and merges the
+/// hits, adding their organization in the `_org` column. Reserved to the users of the `_meta`
+/// organization listed in `ZO_CROSS_ORG_QUERY_USERS`, every search is recorded.
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Search",
+    operation_id = "SearchCrossOrg",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name, must be _meta"),
+    ),
+    request_body(content = CrossOrgSearchRequest, description = "Search query", content_type = "application/json", example = json!({
+        "sql": "SELECT * FROM k8s WHERE level = 'error'",
+        "start_time": 1675182660872049i64,
+        "end_time": 1675185660872049i64,
+        "orgs": ["acme", "globex"],
+        "size": 100
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = CrossOrgSearchResponse),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+/// #{"ratelimit_module":"Search", "ratelimit_module_operation":"get"}#
+#[post("/{org_id}/_search_cross_org")]
+pub async fn search_cross_org(
+    org_id: web::Path<String>,
+    in_req: HttpRequest,
+    body: web::Json<CrossOrgSearchRequest>,
+) -> Result<HttpResponse, Error> {
+    let cfg = get_config();
+    let org_id = org_id.into_inner();
+    let req = body.into_inner();
+
+    let http_span = if cfg.common.tracing_search_enabled {
+        tracing::info_span!("/api/{org_id}/_search_cross_org", org_id = org_id.clone())
+    } else {
+        Span::none()
+    };
+    let trace_id = get_or_create_trace_id(in_req.headers(), &http_span);
+    let user_id = in_req
+        .headers()
+        .get("user_id")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+
+    if org_id != META_ORG_ID || !cross_org::is_permitted(&user_id) {
+        log::warn!(
+            "[trace_id {trace_id}] [CROSS ORG] user {user_id} denied cross-org search from org {org_id}"
+        );
+        return Ok(MetaHttpResponse::forbidden(
+            "Cross-org search is reserved to the users of the _meta organization granted in ZO_CROSS_ORG_QUERY_USERS",
+        ));
+    }
+
+    let query = web::Query::<HashMap<String, String>>::from_query(in_req.query_string()).unwrap();
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    if req.end_time <= req.start_time {
+        return Ok(MetaHttpResponse::bad_request(
+            "end_time must be greater than start_time",
+        ));
+    }
+
+    match cross_org::search(&trace_id, &user_id, stream_type, &req).await {
+        Ok(resp) => Ok(MetaHttpResponse::json(resp)),
+        Err(e) => Ok(map_error_to_http_response(&e, Some(trace_id))),
+    }
+}
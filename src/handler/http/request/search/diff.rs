@@ -0,0 +1,216 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpRequest, HttpResponse, post, web};
+use config::{
+    get_config,
+    meta::{
+        search::{self, DiffRange, SearchDiffRequest},
+        sql::resolve_stream_names,
+        stream::StreamType,
+    },
+};
+use hashbrown::HashMap;
+use tracing::Span;
+
+#[cfg(feature = "enterprise")]
+use crate::handler::http::request::search::utils::check_stream_permissions;
+use crate::{
+    common::{
+        meta::http::HttpResponse as MetaHttpResponse,
+        utils::http::{
+            get_or_create_trace_id, get_search_type_from_request, get_stream_type_from_request,
+        },
+    },
+    handler::http::request::search::error_utils::map_error_to_http_response,
+    service::search as SearchService,
+};
+
+/// SearchDiff
+///
+/// Runs the same query over two time ranges, or with two sets of variable values, and returns
+/// the groups that were added or removed and the changed values ranked by significance.
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Search",
+    operation_id = "SearchDiff",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = SearchDiffRequest, description = "Query and the ranges to compare", content_type = "application/json", example = json!({
+        "sql": "SELECT service, count(*) AS cnt FROM default WHERE level = '$level' GROUP BY service",
+        "base": {"start_time": 1675182660872049i64, "end_time": 1675185660872049i64, "variables": {"level": "error"}},
+        "compare": {"start_time": 1675185660872049i64, "end_time": 1675188660872049i64, "variables": {"level": "error"}},
+        "top": 10
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = SearchDiffResponse),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+/// #{"ratelimit_module":"Search", "ratelimit_module_operation":"get"}#
+#[post("/{org_id}/_search_diff")]
+pub async fn search_diff(
+    org_id: web::Path<String>,
+    in_req: HttpRequest,
+    body: web::Json<SearchDiffRequest>,
+) -> Result<HttpResponse, Error> {
+    let start = std::time::Instant::now();
+    let cfg = get_config();
+    let org_id = org_id.into_inner();
+    let diff_req = body.into_inner();
+
+    let http_span = if cfg.common.tracing_search_enabled {
+        tracing::info_span!("/api/{org_id}/_search_diff", org_id = org_id.clone())
+    } else {
+        Span::none()
+    };
+    let trace_id = get_or_create_trace_id(in_req.headers(), &http_span);
+    let user_id = in_req
+        .headers()
+        .get("user_id")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+
+    let query = web::Query::<HashMap<String, String>>::from_query(in_req.query_string()).unwrap();
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    let search_type = match get_search_type_from_request(&query) {
+        Ok(v) => v,
+        Err(e) => return Ok(MetaHttpResponse::bad_request(e)),
+    };
+
+    for range in [&diff_req.base, &diff_req.compare] {
+        if range.end_time <= range.start_time {
+            return Ok(MetaHttpResponse::bad_request(
+                "end_time must be greater than start_time",
+            ));
+        }
+    }
+
+    let mut reqs = Vec::with_capacity(2);
+    for range in [&diff_req.base, &diff_req.compare] {
+        let req = match build_request(&diff_req, range, search_type.clone()) {
+            Ok(v) => v,
+            Err(e) => return Ok(map_error_to_http_response(&e, Some(trace_id))),
+        };
+        #[cfg(feature = "enterprise")]
+        {
+            let stream_names = match resolve_stream_names(&req.query.sql) {
+                Ok(v) => v,
+                Err(e) => return Ok(map_error_to_http_response(&e.into(), Some(trace_id))),
+            };
+            for stream_name in stream_names.iter() {
+                if let Some(res) =
+                    check_stream_permissions(stream_name, &org_id, &user_id, &stream_type).await
+                {
+                    return Ok(res);
+                }
+            }
+        }
+        reqs.push(req);
+    }
+
+    let (base, compare) = tokio::join!(
+        run(&trace_id, &org_id, stream_type, &user_id, &reqs[0], "base"),
+        run(
+            &trace_id,
+            &org_id,
+            stream_type,
+            &user_id,
+            &reqs[1],
+            "compare"
+        ),
+    );
+    let (base, compare) = match (base, compare) {
+        (Ok(base), Ok(compare)) => (base, compare),
+        (Err(e), _) | (_, Err(e)) => return Ok(map_error_to_http_response(&e, Some(trace_id))),
+    };
+
+    let scale = if diff_req.normalize_by_duration {
+        let base_duration = diff_req.base.end_time - diff_req.base.start_time;
+        let compare_duration = diff_req.compare.end_time - diff_req.compare.start_time;
+        base_duration as f64 / compare_duration as f64
+    } else {
+        1.0
+    };
+    let mut resp = SearchService::diff::compute_diff(
+        &base.hits,
+        &compare.hits,
+        &diff_req.group_by,
+        &diff_req.value_fields,
+        scale,
+        diff_req.top,
+    );
+    resp.took = start.elapsed().as_millis() as usize;
+    Ok(MetaHttpResponse::json(resp))
+}
+
+fn build_request(
+    diff_req: &SearchDiffRequest,
+    range: &DiffRange,
+    search_type: Option<search::SearchEventType>,
+) -> Result<search::Request, infra::errors::Error> {
+    let variables = range
+        .variables
+        .iter()
+        .map(|(name, value)| search::QueryVariable {
+            name: name.to_string(),
+            value: value.as_str().into(),
+        })
+        .collect::<Vec<_>>();
+    let sql = search::substitute_variables(&diff_req.sql, &variables).map_err(|e| {
+        infra::errors::Error::ErrorCode(infra::errors::ErrorCodes::SearchSQLNotValid(e))
+    })?;
+    // make sure the query still parses after the variables are replaced
+    resolve_stream_names(&sql)?;
+    Ok(search::Request {
+        query: search::Query {
+            sql,
+            start_time: range.start_time,
+            end_time: range.end_time,
+            size: diff_req.size,
+            ..Default::default()
+        },
+        search_type,
+        ..Default::default()
+    })
+}
+
+async fn run(
+    trace_id: &str,
+    org_id: &str,
+    stream_type: StreamType,
+    user_id: &str,
+    req: &search::Request,
+    side: &str,
+) -> Result<search::Response, infra::errors::Error> {
+    let trace_id = format!("{trace_id}-{side}");
+    SearchService::cache::search(
+        &trace_id,
+        org_id,
+        stream_type,
+        Some(user_id.to_string()),
+        req,
+        String::new(),
+    )
+    .await
+}
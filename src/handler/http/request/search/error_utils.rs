@@ -32,6 +32,7 @@ pub fn map_error_to_http_response(err: &errors::Error, trace_id: Option<String>)
                 .append_header((ERROR_HEADER, code.to_json()))
                 .json(MetaHttpResponse::error_code_with_trace_id(code, trace_id)),
             errors::ErrorCodes::InvalidParams(_)
+            | errors::ErrorCodes::SearchSamplesLimitExceeded(_)
             | errors::ErrorCodes::SearchSQLExecuteError(_)
             | errors::ErrorCodes::SearchFieldHasNoCompatibleDataType(_)
             | errors::ErrorCodes::SearchFunctionNotDefined(_)
@@ -0,0 +1,141 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpRequest, HttpResponse, post, web};
+use config::{get_config, meta::search::SearchPartitionRequest, utils::json};
+use hashbrown::HashMap;
+use tracing::{Instrument, Span};
+#[cfg(feature = "enterprise")]
+use {
+    crate::handler::http::request::search::utils::check_stream_permissions,
+    config::meta::sql::resolve_stream_names,
+};
+
+use crate::{
+    common::{
+        meta::http::HttpResponse as MetaHttpResponse,
+        utils::http::{get_or_create_trace_id, get_stream_type_from_request},
+    },
+    handler::http::request::search::error_utils::map_error_to_http_response,
+    service::search as SearchService,
+};
+
+/// SearchEstimate
+///
+/// Projects the files, records, bytes and credits a query would scan, from the file list of its
+/// streams and without running it, so that the cost of a long full-scan query is known before it
+/// is launched.
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Search",
+    operation_id = "SearchEstimate",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = SearchPartitionRequest, description = "Query to estimate", content_type = "application/json", example = json!({
+        "sql": "select * from k8s",
+        "start_time": 1675182660872049i64,
+        "end_time": 1682958660872049i64
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = SearchEstimateResponse, example = json!({
+            "file_num": 2000,
+            "records": 120000000,
+            "original_size": 12884901888i64,
+            "compressed_size": 1610612736,
+            "credits": 3.5,
+            "max_query_range": 0,
+            "streams": [{
+                "stream_type": "logs",
+                "stream_name": "k8s",
+                "file_num": 2000,
+                "records": 120000000,
+                "original_size": 12884901888i64,
+                "compressed_size": 1610612736
+            }]
+        })),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+/// #{"ratelimit_module":"Search", "ratelimit_module_operation":"get"}#
+#[post("/{org_id}/_search/estimate")]
+pub async fn search_estimate(
+    org_id: web::Path<String>,
+    in_req: HttpRequest,
+    body: web::Bytes,
+) -> Result<HttpResponse, Error> {
+    let cfg = get_config();
+    let org_id = org_id.into_inner();
+
+    let http_span = if cfg.common.tracing_search_enabled {
+        tracing::info_span!("/api/{org_id}/_search/estimate", org_id = org_id.clone())
+    } else {
+        Span::none()
+    };
+    let trace_id = get_or_create_trace_id(in_req.headers(), &http_span);
+    let user_id = in_req
+        .headers()
+        .get("user_id")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let query = web::Query::<HashMap<String, String>>::from_query(in_req.query_string()).unwrap();
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+
+    let mut req: SearchPartitionRequest = match json::from_slice(&body) {
+        Ok(v) => v,
+        Err(e) => return Ok(MetaHttpResponse::bad_request(e)),
+    };
+    if let Err(e) = req.decode() {
+        return Ok(MetaHttpResponse::bad_request(e));
+    }
+    if req.end_time <= req.start_time {
+        return Ok(MetaHttpResponse::bad_request(
+            "end_time must be greater than start_time",
+        ));
+    }
+
+    #[cfg(feature = "enterprise")]
+    {
+        let stream_names = match resolve_stream_names(&req.sql) {
+            Ok(v) => v,
+            Err(e) => return Ok(map_error_to_http_response(&e.into(), Some(trace_id))),
+        };
+        for stream_name in stream_names.iter() {
+            if let Some(res) =
+                check_stream_permissions(stream_name, &org_id, &user_id, &stream_type).await
+            {
+                return Ok(res);
+            }
+        }
+    }
+
+    match SearchService::estimate::estimate(&trace_id, &org_id, Some(&user_id), stream_type, &req)
+        .instrument(http_span)
+        .await
+    {
+        Ok(resp) => Ok(MetaHttpResponse::json(resp)),
+        Err(e) => {
+            log::error!("[trace_id {trace_id}] search estimate error: {e}");
+            Ok(map_error_to_http_response(&e, Some(trace_id)))
+        }
+    }
+}
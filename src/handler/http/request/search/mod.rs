@@ -15,7 +15,7 @@
 
 use std::{cmp::Reverse, collections::BinaryHeap, io::Error};
 
-use actix_web::{HttpRequest, HttpResponse, get, post, web};
+use actix_web::{HttpRequest, HttpResponse, get, http::header, post, web};
 use arrow_schema::Schema;
 use chrono::Utc;
 use config::{
@@ -26,7 +26,11 @@ use config::{
         sql::resolve_stream_names,
         stream::StreamType,
     },
-    utils::{base64, json, time::now_micros},
+    utils::{
+        arrow::{ARROW_STREAM_CONTENT_TYPE, json_rows_to_ipc_stream},
+        base64, json,
+        time::now_micros,
+    },
 };
 use error_utils::map_error_to_http_response;
 use hashbrown::HashMap;
@@ -58,8 +62,12 @@ use crate::{
 };
 
 pub(crate) mod around;
+pub mod cross_org;
+pub mod diff;
+pub mod estimate;
 pub(crate) mod error_utils;
 pub mod multi_streams;
+pub mod outliers;
 pub mod query_manager;
 pub mod saved_view;
 pub mod search_inspector;
@@ -145,6 +153,10 @@ async fn can_use_distinct_stream(
 
 /// SearchStreamData
 ///
+/// Responds with an Arrow IPC stream of the hits instead of JSON when the request has
+/// `Accept: application/vnd.apache.arrow.stream`, the other response fields are in the schema
+/// metadata. Both formats are compressed with zstd when the request has `Accept-Encoding: zstd`.
+///
 /// #{"ratelimit_module":"Search", "ratelimit_module_operation":"get"}#
 #[utoipa::path(
     context_path = "/api",
@@ -356,6 +368,9 @@ pub async fn search(
     match res {
         Ok(mut res) => {
             res.set_took(start.elapsed().as_millis() as usize);
+            if accepts_arrow_stream(&in_req) {
+                return Ok(arrow_stream_response(res, &trace_id));
+            }
             Ok(HttpResponse::Ok().json(res))
         }
         Err(err) => {
@@ -381,6 +396,46 @@ pub async fn search(
     }
 }
 
+fn accepts_arrow_stream(req: &HttpRequest) -> bool {
+    req.headers()
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains(ARROW_STREAM_CONTENT_TYPE))
+}
+
+/// Writes the hits as an Arrow IPC stream, the other fields of the response are attached as
+/// schema metadata.
+fn arrow_stream_response(res: config::meta::search::Response, trace_id: &str) -> HttpResponse {
+    let mut metadata = std::collections::HashMap::from([
+        ("took".to_string(), res.took.to_string()),
+        ("total".to_string(), res.total.to_string()),
+        ("from".to_string(), res.from.to_string()),
+        ("size".to_string(), res.size.to_string()),
+        ("scan_size".to_string(), res.scan_size.to_string()),
+        ("scan_records".to_string(), res.scan_records.to_string()),
+        ("trace_id".to_string(), res.trace_id.clone()),
+        ("is_partial".to_string(), res.is_partial.to_string()),
+    ]);
+    if !res.function_error.is_empty() {
+        metadata.insert(
+            "function_error".to_string(),
+            json::to_string(&res.function_error).unwrap_or_default(),
+        );
+    }
+    if let Some(interval) = res.histogram_interval {
+        metadata.insert("histogram_interval".to_string(), interval.to_string());
+    }
+    match json_rows_to_ipc_stream(&res.hits, metadata) {
+        Ok(buf) => HttpResponse::Ok()
+            .content_type(ARROW_STREAM_CONTENT_TYPE)
+            .body(buf),
+        Err(e) => {
+            log::error!("[trace_id {trace_id}] search arrow encoding error: {e}");
+            MetaHttpResponse::internal_error(e)
+        }
+    }
+}
+
 /// SearchAround
 ///
 /// #{"ratelimit_module":"Search", "ratelimit_module_operation":"get"}#
@@ -0,0 +1,157 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpRequest, HttpResponse, post, web};
+use config::{
+    get_config,
+    meta::search::{self, SearchOutliersRequest},
+};
+use hashbrown::HashMap;
+use tracing::Span;
+#[cfg(feature = "enterprise")]
+use {
+    crate::handler::http::request::search::utils::check_stream_permissions,
+    config::meta::sql::resolve_stream_names,
+};
+
+use crate::{
+    common::{
+        meta::http::HttpResponse as MetaHttpResponse,
+        utils::http::{
+            get_or_create_trace_id, get_search_type_from_request, get_stream_type_from_request,
+        },
+    },
+    handler::http::request::search::error_utils::map_error_to_http_response,
+    service::search as SearchService,
+};
+
+/// SearchOutliers
+///
+/// Runs an aggregation with a group-by and flags the series that deviate from the population,
+/// comparing the series bucket by bucket when the result has a time column.
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Search",
+    operation_id = "SearchOutliers",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = SearchOutliersRequest, description = "Grouped aggregation to analyze", content_type = "application/json", example = json!({
+        "sql": "SELECT histogram(_timestamp, '5 minute') AS ts, k8s_pod_name, avg(cpu) AS cpu FROM k8s GROUP BY ts, k8s_pod_name",
+        "start_time": 1675182660872049i64,
+        "end_time": 1675185660872049i64,
+        "timestamp_field": "ts",
+        "method": "mad",
+        "threshold": 3.0
+    })),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = SearchOutliersResponse),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+/// #{"ratelimit_module":"Search", "ratelimit_module_operation":"get"}#
+#[post("/{org_id}/_search_outliers")]
+pub async fn search_outliers(
+    org_id: web::Path<String>,
+    in_req: HttpRequest,
+    body: web::Json<SearchOutliersRequest>,
+) -> Result<HttpResponse, Error> {
+    let start = std::time::Instant::now();
+    let cfg = get_config();
+    let org_id = org_id.into_inner();
+    let outliers_req = body.into_inner();
+
+    let http_span = if cfg.common.tracing_search_enabled {
+        tracing::info_span!("/api/{org_id}/_search_outliers", org_id = org_id.clone())
+    } else {
+        Span::none()
+    };
+    let trace_id = get_or_create_trace_id(in_req.headers(), &http_span);
+    let user_id = in_req
+        .headers()
+        .get("user_id")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+
+    let query = web::Query::<HashMap<String, String>>::from_query(in_req.query_string()).unwrap();
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    let search_type = match get_search_type_from_request(&query) {
+        Ok(v) => v,
+        Err(e) => return Ok(MetaHttpResponse::bad_request(e)),
+    };
+    if outliers_req.end_time <= outliers_req.start_time {
+        return Ok(MetaHttpResponse::bad_request(
+            "end_time must be greater than start_time",
+        ));
+    }
+
+    #[cfg(feature = "enterprise")]
+    {
+        let stream_names = match resolve_stream_names(&outliers_req.sql) {
+            Ok(v) => v,
+            Err(e) => return Ok(map_error_to_http_response(&e.into(), Some(trace_id))),
+        };
+        for stream_name in stream_names.iter() {
+            if let Some(res) =
+                check_stream_permissions(stream_name, &org_id, &user_id, &stream_type).await
+            {
+                return Ok(res);
+            }
+        }
+    }
+
+    let req = search::Request {
+        query: search::Query {
+            sql: outliers_req.sql.clone(),
+            start_time: outliers_req.start_time,
+            end_time: outliers_req.end_time,
+            size: outliers_req.size,
+            ..Default::default()
+        },
+        search_type,
+        ..Default::default()
+    };
+    let res = match SearchService::cache::search(
+        &trace_id,
+        &org_id,
+        stream_type,
+        Some(user_id),
+        &req,
+        String::new(),
+    )
+    .await
+    {
+        Ok(v) => v,
+        Err(e) => return Ok(map_error_to_http_response(&e, Some(trace_id))),
+    };
+
+    let mut resp = SearchService::outliers::detect_outliers(
+        &res.hits,
+        &outliers_req.group_by,
+        outliers_req.value_field.as_deref(),
+        outliers_req.timestamp_field.as_deref(),
+        outliers_req.method,
+        outliers_req.threshold,
+    );
+    resp.took = start.elapsed().as_millis() as usize;
+    Ok(MetaHttpResponse::json(resp))
+}
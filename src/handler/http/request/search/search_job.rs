@@ -36,11 +36,11 @@ use {
     config::{
         get_config,
         meta::{
-            search::{Request, Response, SearchEventType},
+            search::{Request, Response, SearchEventType, SearchJobOptions, archive_boundary},
             sql::resolve_stream_names,
             stream::StreamType,
         },
-        utils::json,
+        utils::{json, time::now_micros},
     },
     hashbrown::HashMap,
     infra::table::entity::search_jobs::Model as JobModel,
@@ -53,6 +53,9 @@ use crate::handler::http::request::search::error_utils::map_error_to_http_respon
 // 1. submit
 /// SearchSQL
 ///
+/// Set `include_archived` to search data in the archive tier, its files are rehydrated before the
+/// query runs, and `notify` to receive a webhook call or an email when the job is done.
+///
 /// #{"ratelimit_module":"Search Jobs", "ratelimit_module_operation":"create"}#
 #[utoipa::path(
     context_path = "/api",
@@ -71,7 +74,9 @@ use crate::handler::http::request::search::error_utils::map_error_to_http_respon
             "end_time": 1675185660872049i64,
             "limit": 100,
             "offset": 0,
-        }
+        },
+        "include_archived": true,
+        "notify": {"webhook_url": "https://hooks.example.com/search-jobs", "emails": ["oncall@example.com"]}
     })),
     responses(
         (status = 200, description = "Search Job submitted successfully", body = MetaHttpResponse),
@@ -118,6 +123,22 @@ pub async fn submit_job(
         if let Err(e) = req.decode() {
             return Ok(MetaHttpResponse::bad_request(e));
         }
+        let options: SearchJobOptions = match json::from_slice(&body) {
+            Ok(v) => v,
+            Err(e) => return Ok(MetaHttpResponse::bad_request(e)),
+        };
+        if let Err(e) = options.validate() {
+            return Ok(MetaHttpResponse::bad_request(e));
+        }
+        if !options.include_archived
+            && archive_boundary(cfg.limit.search_archive_after_days, now_micros())
+                .is_some_and(|boundary| req.query.start_time < boundary)
+        {
+            return Ok(MetaHttpResponse::bad_request(format!(
+                "the query reads data older than {} days from the archive tier, set include_archived to search it",
+                cfg.limit.search_archive_after_days
+            )));
+        }
 
         req.use_cache = get_use_cache_from_request(&query);
 
@@ -155,6 +176,14 @@ pub async fn submit_job(
         // add stream_names for rbac
         let stream_names = json::to_string(&stream_names).unwrap();
 
+        // the job options are kept in the payload next to the request
+        let mut payload = json::to_value(&req).unwrap();
+        if let (Some(payload), json::Value::Object(options)) =
+            (payload.as_object_mut(), json::to_value(&options).unwrap())
+        {
+            payload.extend(options);
+        }
+
         // submit query to db
         let res = submit(
             &trace_id,
@@ -162,7 +191,7 @@ pub async fn submit_job(
             &user_id,
             stream_type.as_str(),
             &stream_names,
-            &json::to_string(&req).unwrap(),
+            &payload.to_string(),
             req.query.start_time,
             req.query.end_time,
         )
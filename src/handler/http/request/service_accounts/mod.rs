@@ -28,12 +28,14 @@ use crate::{
         meta::{
             self,
             http::HttpResponse as MetaHttpResponse,
-            service_account::{APIToken, ServiceAccountRequest, UpdateServiceAccountRequest},
+            service_account::{
+                APIToken, ServiceAccountRequest, ServiceAccountStatus, UpdateServiceAccountRequest,
+            },
             user::{UpdateUser, UserRequest},
         },
         utils::auth::UserEmail,
     },
-    service::users,
+    service::{db, users},
 };
 
 /// ListServiceAccounts
@@ -117,6 +119,12 @@ pub async fn save(
     let org_id = org_id.into_inner();
     let initiator_id = user_email.user_id;
     let service_account = service_account.into_inner();
+    if users::is_service_account(&service_account.email.trim().to_lowercase()).await == Some(false)
+    {
+        return Ok(MetaHttpResponse::conflict(
+            "A user with this email already exists",
+        ));
+    }
     let user = UserRequest {
         email: service_account.email.trim().to_string(),
         first_name: service_account.first_name.trim().to_string(),
@@ -237,7 +245,58 @@ pub async fn delete(
 ) -> Result<HttpResponse, Error> {
     let (org_id, email_id) = path.into_inner();
     let initiator_id = user_email.user_id;
-    users::remove_user_from_org(&org_id, &email_id, &initiator_id).await
+    let email = users::get_user(Some(&org_id), &email_id)
+        .await
+        .map(|user| user.email);
+    let resp = users::remove_user_from_org(&org_id, &email_id, &initiator_id).await?;
+    if let Some(email) = email
+        && resp.status().is_success()
+        && let Err(e) = db::service_account::delete(&org_id, &email).await
+    {
+        log::error!("Error deleting the status of service account {email}: {e}");
+    }
+    Ok(resp)
+}
+
+/// UpdateServiceAccountStatus
+///
+/// #{"ratelimit_module":"Service Accounts", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "ServiceAccounts",
+    operation_id = "ServiceAccountUpdateStatus",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("email_id" = String, Path, description = "Service Account email id"),
+    ),
+    request_body(content = ServiceAccountStatus, description = "Disable the service account or set its expiry", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/service_accounts/{email_id}/status")]
+pub async fn update_status(
+    path: web::Path<(String, String)>,
+    status: web::Json<ServiceAccountStatus>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, email_id) = path.into_inner();
+    let Some(user) = users::get_user(Some(&org_id), email_id.trim())
+        .await
+        .filter(|user| user.role.eq(&UserRole::ServiceAccount))
+    else {
+        return Ok(MetaHttpResponse::not_found("Service account not found"));
+    };
+    match db::service_account::set(&org_id, &user.email, &status.into_inner()).await {
+        Ok(()) => Ok(HttpResponse::Ok().json(MetaHttpResponse::message(
+            http::StatusCode::OK,
+            "Service account status updated",
+        ))),
+        Err(e) => Ok(MetaHttpResponse::internal_error(e)),
+    }
 }
 
 /// GetAPIToken
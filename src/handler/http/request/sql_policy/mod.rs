@@ -0,0 +1,116 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use std::io::Error;
+
+use actix_web::{HttpResponse, delete, get, put, web};
+use config::meta::sql_policy::SqlPolicy;
+
+use crate::{
+    common::meta::http::HttpResponse as MetaHttpResponse,
+    service::sql_policy::{self, SqlPolicyError},
+};
+
+fn map_error(e: SqlPolicyError) -> HttpResponse {
+    match e {
+        SqlPolicyError::NotFound => MetaHttpResponse::not_found(e),
+        SqlPolicyError::InfraError(e) => MetaHttpResponse::internal_error(e),
+        e => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// GetSqlPolicy
+///
+/// #{"ratelimit_module":"Sql Policy", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Sql Policy",
+    operation_id = "GetSqlPolicy",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = SqlPolicy),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/sql_policy")]
+pub async fn get_policy(path: web::Path<String>) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match sql_policy::get(&org_id).await {
+        Ok(policy) => Ok(MetaHttpResponse::json(policy)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// SaveSqlPolicy
+///
+/// #{"ratelimit_module":"Sql Policy", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Sql Policy",
+    operation_id = "SaveSqlPolicy",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = SqlPolicy, description = "Sql policy data", content_type = "application/json", example = json!({"enabled": true, "roles": ["viewer"], "allowed_functions": ["count", "histogram"], "allowed_streams": ["public_*"], "max_time_range_hours": 24})),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = SqlPolicy),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/sql_policy")]
+pub async fn save_policy(
+    path: web::Path<String>,
+    req: web::Json<SqlPolicy>,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match sql_policy::save(&org_id, req.into_inner()).await {
+        Ok(policy) => Ok(MetaHttpResponse::json(policy)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteSqlPolicy
+///
+/// #{"ratelimit_module":"Sql Policy", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Sql Policy",
+    operation_id = "DeleteSqlPolicy",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/sql_policy")]
+pub async fn delete_policy(path: web::Path<String>) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match sql_policy::delete(&org_id).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Sql policy deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
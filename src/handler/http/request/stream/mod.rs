@@ -22,8 +22,11 @@ use actix_web::{
     HttpRequest, HttpResponse, Responder, delete, get, http, http::StatusCode, post, put, web,
 };
 use config::{
-    meta::stream::{StreamSettings, StreamType, UpdateStreamSettings},
-    utils::schema::format_stream_name,
+    meta::{
+        stream::{StreamSettings, StreamType, UpdateStreamSettings},
+        workspace::{ResourceType, WorkspaceResource},
+    },
+    utils::{schema::format_stream_name, time::now_micros},
 };
 use hashbrown::HashMap;
 
@@ -32,11 +35,17 @@ use crate::{
         meta::{
             self,
             http::HttpResponse as MetaHttpResponse,
-            stream::{ListStream, StreamDeleteFields},
+            stream::{
+                ListStream, StorageUsageResponse, StreamCloneRequest, StreamCloneResponse,
+                StreamDeleteFields, StreamFieldUsageResponse, StreamHourlyStatsResponse,
+            },
         },
-        utils::http::get_stream_type_from_request,
+        utils::{auth::is_root_user, http::get_stream_type_from_request},
+    },
+    service::{
+        stream, stream_clone, stream_clone::StreamCloneError, stream_field_usage,
+        stream_hourly_stats, stream_storage_usage, users, workspaces,
     },
-    service::stream,
 };
 
 /// GetSchema
@@ -59,6 +68,7 @@ use crate::{
     ),
     responses(
         (status = 200, description = "Success", content_type = "application/json", body = Stream),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
         (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
     )
 )]
@@ -73,6 +83,9 @@ async fn schema(
     }
     let query = web::Query::<HashMap<String, String>>::from_query(req.query_string()).unwrap();
     let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    if let Some(resp) = workspace_denied(&org_id, &stream_name, stream_type, &req, false).await {
+        return Ok(resp);
+    }
     let schema = stream::get_stream(&org_id, &stream_name, stream_type).await;
     let Some(mut schema) = schema else {
         return Ok(HttpResponse::NotFound().json(MetaHttpResponse::error(
@@ -94,10 +107,16 @@ async fn schema(
         );
     }
 
-    // filter by keyword
+    // filter by keyword, on the field name or description
     if let Some(keyword) = query.get("keyword") {
         if !keyword.is_empty() {
-            schema.schema.retain(|f| f.name.contains(keyword));
+            schema.schema.retain(|f| {
+                f.name.contains(keyword)
+                    || f.metadata
+                        .as_ref()
+                        .and_then(|m| m.description.as_ref())
+                        .is_some_and(|d| d.contains(keyword))
+            });
         }
     }
 
@@ -141,6 +160,7 @@ async fn schema(
     request_body(content = StreamSettings, description = "Stream settings", content_type = "application/json"),
     responses(
         (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
         (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
     )
 )]
@@ -164,6 +184,9 @@ async fn settings(
             )),
         );
     }
+    if let Some(resp) = workspace_denied(&org_id, &stream_name, stream_type, &req, true).await {
+        return Ok(resp);
+    }
     stream::save_stream_settings(&org_id, &stream_name, stream_type, settings.into_inner()).await
 }
 
@@ -185,6 +208,7 @@ async fn settings(
     request_body(content = UpdateStreamSettings, description = "Stream settings", content_type = "application/json"),
     responses(
         (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
         (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
     )
 )]
@@ -209,6 +233,9 @@ async fn update_settings(
             )),
         );
     }
+    if let Some(resp) = workspace_denied(&org_id, &stream_name, stream_type, &req, true).await {
+        return Ok(resp);
+    }
     let stream_settings: UpdateStreamSettings = stream_settings.into_inner();
     let main_stream_res =
         stream::update_stream_settings(&org_id, &stream_name, stream_type, stream_settings.clone())
@@ -278,6 +305,7 @@ async fn update_settings(
     request_body(content = StreamDeleteFields, description = "Stream delete fields", content_type = "application/json"),
     responses(
         (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
         (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
     )
 )]
@@ -293,6 +321,17 @@ async fn delete_fields(
     }
     let query = web::Query::<HashMap<String, String>>::from_query(req.query_string()).unwrap();
     let stream_type = get_stream_type_from_request(&query);
+    if let Some(resp) = workspace_denied(
+        &org_id,
+        &stream_name,
+        stream_type.unwrap_or_default(),
+        &req,
+        true,
+    )
+    .await
+    {
+        return Ok(resp);
+    }
     match stream::delete_fields(
         &org_id,
         &stream_name,
@@ -327,6 +366,7 @@ async fn delete_fields(
     ),
     responses(
         (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
         (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
     )
 )]
@@ -341,9 +381,103 @@ async fn delete(
     }
     let query = web::Query::<HashMap<String, String>>::from_query(req.query_string()).unwrap();
     let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    if let Some(resp) = workspace_denied(&org_id, &stream_name, stream_type, &req, true).await {
+        return Ok(resp);
+    }
     stream::delete_stream(&org_id, &stream_name, stream_type).await
 }
 
+/// Returns the response refusing the request when the members of the workspace of the stream
+/// don't permit the user to read it, or to change it when `write` is set.
+async fn workspace_denied(
+    org_id: &str,
+    stream_name: &str,
+    stream_type: StreamType,
+    req: &HttpRequest,
+    write: bool,
+) -> Option<HttpResponse> {
+    let user_id = req
+        .headers()
+        .get("user_id")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let stream_id = WorkspaceResource::stream_id(stream_type, stream_name);
+    match workspaces::can_access(org_id, user_id, ResourceType::Stream, &stream_id, write).await {
+        Ok(true) => None,
+        Ok(false) => Some(MetaHttpResponse::forbidden(
+            "Not permitted by the members of the workspace",
+        )),
+        Err(e) => Some(MetaHttpResponse::internal_error(e)),
+    }
+}
+
+/// CloneStream
+///
+/// Creates a new stream, possibly in another organization, with the schema and settings of the
+/// stream. The functions of the stream pipeline and a disabled copy of the pipeline, and a sample
+/// of the logs records can be copied along, so pipeline changes can be tried on realistic data.
+///
+/// #{"ratelimit_module":"Streams", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "StreamClone",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("type" = String, Query, description = "Stream type"),
+    ),
+    request_body(content = StreamCloneRequest, description = "Clone options", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = StreamCloneResponse),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+        (status = 409, description = "Conflict", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/streams/{stream_name}/_clone")]
+async fn clone(
+    path: web::Path<(String, String)>,
+    body: web::Json<StreamCloneRequest>,
+    req: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let (org_id, mut stream_name) = path.into_inner();
+    if !config::get_config().common.skip_formatting_stream_name {
+        stream_name = format_stream_name(&stream_name);
+    }
+    let query = web::Query::<HashMap<String, String>>::from_query(req.query_string()).unwrap();
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    let body = body.into_inner();
+
+    let user_id = req.headers().get("user_id").unwrap().to_str().unwrap();
+    // cloning into another organization requires being a member of it
+    if let Some(target_org) = body
+        .target_org
+        .as_deref()
+        .filter(|o| !o.is_empty() && *o != org_id)
+    {
+        if !is_root_user(user_id) && users::get_user(Some(target_org), user_id).await.is_none() {
+            return Ok(MetaHttpResponse::forbidden(format!(
+                "user is not a member of organization [{target_org}]"
+            )));
+        }
+    }
+
+    match stream_clone::clone(&org_id, user_id, &stream_name, stream_type, body).await {
+        Ok(resp) => Ok(MetaHttpResponse::json(resp)),
+        Err(e) => match e {
+            StreamCloneError::NotFound => Ok(MetaHttpResponse::not_found(e)),
+            StreamCloneError::AlreadyExists(_) => Ok(MetaHttpResponse::conflict(e)),
+            StreamCloneError::InvalidRequest(_) => Ok(MetaHttpResponse::bad_request(e)),
+            _ => Ok(MetaHttpResponse::internal_error(e)),
+        },
+    }
+}
+
 /// ListStreams
 ///
 /// #{"ratelimit_module":"Streams", "ratelimit_module_operation":"list"}#
@@ -358,6 +492,7 @@ async fn delete(
         ("org_id" = String, Path, description = "Organization name"),
         ("type" = String, Query, description = "Stream type"),
         ("keyword" = String, Query, description = "Keyword"),
+        ("workspace" = String, Query, description = "Workspace ID, lists the streams which belong to the workspace or are shared with it"),
         ("offset" = u32, Query, description = "Offset"),
         ("limit" = u32, Query, description = "Limit"),
         ("sort" = String, Query, description = "Sort"),
@@ -432,6 +567,17 @@ async fn list(org_id: web::Path<String>, req: HttpRequest) -> impl Responder {
         }
     }
 
+    // filter by workspace
+    if let Some(workspace) = query.get("workspace") {
+        let stream_ids =
+            match workspaces::resource_ids(&org_id, workspace, ResourceType::Stream).await {
+                Ok(stream_ids) => stream_ids,
+                Err(e) => return Ok(HttpResponse::from(e)),
+            };
+        indices
+            .retain(|s| stream_ids.contains(&WorkspaceResource::stream_id(s.stream_type, &s.name)));
+    }
+
     // sort by
     let mut sort = "name".to_string();
     if let Some(s) = query.get("sort") {
@@ -507,6 +653,263 @@ async fn list(org_id: web::Path<String>, req: HttpRequest) -> impl Responder {
     }))
 }
 
+/// StreamHourlyStats
+///
+/// Returns the per-hour record counts, sizes and error-level record counts of
+/// the streams, maintained at flush and compaction time.
+///
+/// #{"ratelimit_module":"Streams", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "StreamHourlyStats",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("type" = String, Query, description = "Stream type"),
+        ("streams" = Option<String>, Query, description = "Comma separated stream names, all streams if empty"),
+        ("start_time" = Option<i64>, Query, description = "Start time in microseconds, default 24 hours ago"),
+        ("end_time" = Option<i64>, Query, description = "End time in microseconds, default now"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = StreamHourlyStatsResponse),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/streams/_hourly_stats")]
+async fn hourly_stats(org_id: web::Path<String>, req: HttpRequest) -> Result<HttpResponse, Error> {
+    let query = web::Query::<HashMap<String, String>>::from_query(req.query_string()).unwrap();
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    let stream_names = query
+        .get("streams")
+        .map(|v| {
+            v.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let end_time = query
+        .get("end_time")
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or_else(now_micros);
+    let start_time = query
+        .get("start_time")
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(end_time - 24 * 3_600_000_000);
+    if start_time > end_time {
+        return Ok(MetaHttpResponse::bad_request(
+            "start_time should be less than end_time",
+        ));
+    }
+
+    let stats =
+        match stream_hourly_stats::list(&org_id, stream_type, &stream_names, start_time, end_time)
+            .await
+        {
+            Ok(v) => v,
+            Err(e) => return Ok(MetaHttpResponse::internal_error(e)),
+        };
+
+    #[cfg(feature = "enterprise")]
+    let stats = {
+        let mut stats = stats;
+        let user_id = req.headers().get("user_id").unwrap().to_str().unwrap();
+        match permitted_streams(&org_id, user_id, stream_type).await {
+            Ok(Some(permitted)) => stats.retain(|name, _| permitted.contains(name)),
+            Ok(None) => {}
+            Err(e) => return Ok(MetaHttpResponse::forbidden(e.to_string())),
+        }
+        stats
+    };
+
+    Ok(MetaHttpResponse::json(StreamHourlyStatsResponse {
+        streams: stats,
+    }))
+}
+
+/// StreamStorageUsage
+///
+/// Returns the daily history of the storage used by the streams with their
+/// growth rates, sampled by the compactor.
+///
+/// #{"ratelimit_module":"Streams", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "StreamStorageUsage",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("type" = Option<String>, Query, description = "Stream type, all types if empty"),
+        ("streams" = Option<String>, Query, description = "Comma separated stream names, all streams if empty"),
+        ("start_time" = Option<i64>, Query, description = "Start time in microseconds, default 30 days ago"),
+        ("end_time" = Option<i64>, Query, description = "End time in microseconds, default now"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = StorageUsageResponse),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/streams/_storage_usage")]
+async fn storage_usage(org_id: web::Path<String>, req: HttpRequest) -> Result<HttpResponse, Error> {
+    let query = web::Query::<HashMap<String, String>>::from_query(req.query_string()).unwrap();
+    let stream_type = get_stream_type_from_request(&query);
+    let stream_names = query
+        .get("streams")
+        .map(|v| {
+            v.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let end_time = query
+        .get("end_time")
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or_else(now_micros);
+    let start_time = query
+        .get("start_time")
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(end_time - 30 * 86_400_000_000);
+    if start_time > end_time {
+        return Ok(MetaHttpResponse::bad_request(
+            "start_time should be less than end_time",
+        ));
+    }
+
+    #[cfg(feature = "enterprise")]
+    let stream_names = {
+        // restrict the listing to the permitted streams
+        let Some(stream_type) = stream_type else {
+            return Ok(MetaHttpResponse::bad_request(
+                "type is required when access control is enabled",
+            ));
+        };
+        let user_id = req.headers().get("user_id").unwrap().to_str().unwrap();
+        match permitted_streams(&org_id, user_id, stream_type).await {
+            Ok(Some(permitted)) if stream_names.is_empty() => {
+                if permitted.is_empty() {
+                    return Ok(MetaHttpResponse::json(StorageUsageResponse::default()));
+                }
+                permitted.into_iter().collect()
+            }
+            Ok(Some(permitted)) => stream_names
+                .into_iter()
+                .filter(|name| permitted.contains(name))
+                .collect(),
+            Ok(None) => stream_names,
+            Err(e) => return Ok(MetaHttpResponse::forbidden(e.to_string())),
+        }
+    };
+
+    match stream_storage_usage::usage(&org_id, stream_type, &stream_names, start_time, end_time)
+        .await
+    {
+        Ok(usage) => Ok(MetaHttpResponse::json(usage)),
+        Err(e) => Ok(MetaHttpResponse::internal_error(e)),
+    }
+}
+
+/// StreamFieldUsage
+///
+/// Returns how many queries referenced each field of the stream, in their
+/// projection or predicates, fields never queried having no queries.
+///
+/// #{"ratelimit_module":"Streams", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Streams",
+    operation_id = "StreamFieldUsage",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("stream_name" = String, Path, description = "Stream name"),
+        ("type" = String, Query, description = "Stream type"),
+        ("start_time" = Option<i64>, Query, description = "Start time in microseconds, default 30 days ago"),
+        ("end_time" = Option<i64>, Query, description = "End time in microseconds, default now"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = StreamFieldUsageResponse),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/streams/{stream_name}/field_usage")]
+async fn field_usage(
+    path: web::Path<(String, String)>,
+    req: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let (org_id, mut stream_name) = path.into_inner();
+    if !config::get_config().common.skip_formatting_stream_name {
+        stream_name = format_stream_name(&stream_name);
+    }
+    let query = web::Query::<HashMap<String, String>>::from_query(req.query_string()).unwrap();
+    let stream_type = get_stream_type_from_request(&query).unwrap_or_default();
+    let end_time = query
+        .get("end_time")
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or_else(now_micros);
+    let start_time = query
+        .get("start_time")
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(end_time - 30 * 86_400_000_000);
+    if start_time > end_time {
+        return Ok(MetaHttpResponse::bad_request(
+            "start_time should be less than end_time",
+        ));
+    }
+
+    match stream_field_usage::list(&org_id, stream_type, &stream_name, start_time, end_time).await {
+        Ok(fields) => Ok(MetaHttpResponse::json(StreamFieldUsageResponse { fields })),
+        Err(e) => Ok(MetaHttpResponse::internal_error(e)),
+    }
+}
+
+/// Returns the names of the streams of the type the user is allowed to list,
+/// `None` if the user can list all of them.
+#[cfg(feature = "enterprise")]
+async fn permitted_streams(
+    org_id: &str,
+    user_id: &str,
+    stream_type: StreamType,
+) -> Result<Option<hashbrown::HashSet<String>>, actix_web::Error> {
+    use o2_openfga::meta::mapping::OFGA_MODELS;
+
+    let stream_type_str = stream_type.to_string();
+    let Some(permitted) = crate::handler::http::auth::validator::list_objects_for_user(
+        org_id,
+        user_id,
+        "GET",
+        OFGA_MODELS
+            .get(stream_type_str.as_str())
+            .map_or(stream_type_str.as_str(), |model| model.key),
+    )
+    .await?
+    else {
+        return Ok(None);
+    };
+    let s_type = match stream_type {
+        StreamType::EnrichmentTables => "enrichment_table",
+        _ => stream_type.as_str(),
+    };
+    if permitted.contains(&format!("{}:_all_{}", s_type, org_id)) {
+        return Ok(None);
+    }
+    let prefix = format!("{s_type}:");
+    Ok(Some(
+        permitted
+            .into_iter()
+            .filter_map(|s| s.strip_prefix(&prefix).map(|s| s.to_string()))
+            .collect(),
+    ))
+}
+
 /// StreamDeleteCache
 ///
 /// #{"ratelimit_module":"Streams", "ratelimit_module_operation":"delete"}#
@@ -0,0 +1,183 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpResponse, delete, get, post, web};
+use config::meta::synthetic_data::{SyntheticDataRequest, SyntheticJob, SyntheticJobList};
+
+use crate::{
+    common::{
+        meta::http::HttpResponse as MetaHttpResponse,
+        utils::auth::{UserEmail, is_root_user},
+    },
+    service::synthetic_data::{self, SyntheticDataError},
+};
+
+fn map_error(e: SyntheticDataError) -> HttpResponse {
+    match e {
+        SyntheticDataError::NotFound => MetaHttpResponse::not_found(e),
+        SyntheticDataError::InfraError(e) => MetaHttpResponse::internal_error(e),
+        e => MetaHttpResponse::bad_request(e),
+    }
+}
+
+fn forbidden() -> HttpResponse {
+    MetaHttpResponse::forbidden("Only root user can generate synthetic data")
+}
+
+/// StartSyntheticDataJob
+///
+/// Starts generating synthetic logs, metrics or traces into a stream at the given rate, e.g. to
+/// load test retention, compaction and queries before ingesting real data. Root user only.
+///
+/// #{"ratelimit_module":"Synthetic Data", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Synthetic Data",
+    operation_id = "StartSyntheticDataJob",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = SyntheticDataRequest, description = "Generator parameters", content_type = "application/json", example = json!({"stream_name": "load_test", "stream_type": "logs", "rate": 500, "duration_secs": 600, "cardinality": 20, "error_ratio": 0.05, "fields": [{"name": "region", "type": "string", "cardinality": 3}]})),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = SyntheticJob),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/synthetic_data")]
+pub async fn start_job(
+    path: web::Path<String>,
+    user_email: UserEmail,
+    req: web::Json<SyntheticDataRequest>,
+) -> Result<HttpResponse, Error> {
+    if !is_root_user(&user_email.user_id) {
+        return Ok(forbidden());
+    }
+    let org_id = path.into_inner();
+    match synthetic_data::start(&org_id, req.into_inner()).await {
+        Ok(job) => Ok(MetaHttpResponse::json(job)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// ListSyntheticDataJobs
+///
+/// #{"ratelimit_module":"Synthetic Data", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Synthetic Data",
+    operation_id = "ListSyntheticDataJobs",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = SyntheticJobList),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/synthetic_data")]
+pub async fn list_jobs(
+    path: web::Path<String>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    if !is_root_user(&user_email.user_id) {
+        return Ok(forbidden());
+    }
+    let org_id = path.into_inner();
+    match synthetic_data::list(&org_id).await {
+        Ok(list) => Ok(MetaHttpResponse::json(SyntheticJobList { list })),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// GetSyntheticDataJob
+///
+/// #{"ratelimit_module":"Synthetic Data", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Synthetic Data",
+    operation_id = "GetSyntheticDataJob",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("job_id" = String, Path, description = "Job id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = SyntheticJob),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/synthetic_data/{job_id}")]
+pub async fn get_job(
+    path: web::Path<(String, String)>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    if !is_root_user(&user_email.user_id) {
+        return Ok(forbidden());
+    }
+    let (org_id, job_id) = path.into_inner();
+    match synthetic_data::get(&org_id, &job_id).await {
+        Ok(job) => Ok(MetaHttpResponse::json(job)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteSyntheticDataJob
+///
+/// Deletes the job, a running job stops generating records.
+///
+/// #{"ratelimit_module":"Synthetic Data", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Synthetic Data",
+    operation_id = "DeleteSyntheticDataJob",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("job_id" = String, Path, description = "Job id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/synthetic_data/{job_id}")]
+pub async fn delete_job(
+    path: web::Path<(String, String)>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    if !is_root_user(&user_email.user_id) {
+        return Ok(forbidden());
+    }
+    let (org_id, job_id) = path.into_inner();
+    match synthetic_data::delete(&org_id, &job_id).await {
+        Ok(()) => Ok(MetaHttpResponse::ok("Synthetic data job deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
@@ -0,0 +1,268 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpResponse, delete, get, post, put, web};
+use config::meta::threat_intel::{IndicatorFeed, IndicatorFeedList, IndicatorKind, IndicatorList};
+
+use crate::{
+    common::meta::http::HttpResponse as MetaHttpResponse,
+    service::threat_intel::{self, ThreatIntelError},
+};
+
+fn map_error(e: ThreatIntelError) -> HttpResponse {
+    match e {
+        ThreatIntelError::FeedNotFound => MetaHttpResponse::not_found(e),
+        ThreatIntelError::InfraError(e) => MetaHttpResponse::internal_error(e),
+        e => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// AddIndicators
+///
+/// #{"ratelimit_module":"Threat Intel", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Threat Intel",
+    operation_id = "AddThreatIndicators",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = IndicatorList, description = "Indicators to add", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = IndicatorList),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/threat_intel/indicators")]
+pub async fn add_indicators(
+    path: web::Path<String>,
+    req: web::Json<IndicatorList>,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match threat_intel::add_indicators(&org_id, req.into_inner().list).await {
+        Ok(list) => Ok(MetaHttpResponse::json(IndicatorList { list })),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// ListIndicators
+///
+/// #{"ratelimit_module":"Threat Intel", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Threat Intel",
+    operation_id = "ListThreatIndicators",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = IndicatorList),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/threat_intel/indicators")]
+pub async fn list_indicators(path: web::Path<String>) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match threat_intel::list_indicators(&org_id).await {
+        Ok(list) => Ok(MetaHttpResponse::json(IndicatorList { list })),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteIndicator
+///
+/// #{"ratelimit_module":"Threat Intel", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Threat Intel",
+    operation_id = "DeleteThreatIndicator",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("kind" = IndicatorKind, Path, description = "Indicator kind: ip, domain or hash"),
+        ("value" = String, Path, description = "Indicator value"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/threat_intel/indicators/{kind}/{value}")]
+pub async fn delete_indicator(
+    path: web::Path<(String, IndicatorKind, String)>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, kind, value) = path.into_inner();
+    match threat_intel::delete_indicator(&org_id, kind, &value).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Indicator deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// CreateIndicatorFeed
+///
+/// #{"ratelimit_module":"Threat Intel", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Threat Intel",
+    operation_id = "CreateThreatIndicatorFeed",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = IndicatorFeed, description = "TAXII collection or STIX bundle to pull", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = IndicatorFeed),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/threat_intel/feeds")]
+pub async fn create_feed(
+    path: web::Path<String>,
+    req: web::Json<IndicatorFeed>,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match threat_intel::create_feed(&org_id, req.into_inner()).await {
+        Ok(feed) => Ok(MetaHttpResponse::json(feed)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// UpdateIndicatorFeed
+///
+/// #{"ratelimit_module":"Threat Intel", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Threat Intel",
+    operation_id = "UpdateThreatIndicatorFeed",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Feed id"),
+    ),
+    request_body(content = IndicatorFeed, description = "TAXII collection or STIX bundle to pull", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = IndicatorFeed),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/threat_intel/feeds/{id}")]
+pub async fn update_feed(
+    path: web::Path<(String, String)>,
+    req: web::Json<IndicatorFeed>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, id) = path.into_inner();
+    match threat_intel::update_feed(&org_id, &id, req.into_inner()).await {
+        Ok(feed) => Ok(MetaHttpResponse::json(feed)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// GetIndicatorFeed
+///
+/// #{"ratelimit_module":"Threat Intel", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Threat Intel",
+    operation_id = "GetThreatIndicatorFeed",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Feed id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = IndicatorFeed),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/threat_intel/feeds/{id}")]
+pub async fn get_feed(path: web::Path<(String, String)>) -> Result<HttpResponse, Error> {
+    let (org_id, id) = path.into_inner();
+    match threat_intel::get_feed(&org_id, &id).await {
+        Ok(feed) => Ok(MetaHttpResponse::json(feed)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// ListIndicatorFeeds
+///
+/// #{"ratelimit_module":"Threat Intel", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Threat Intel",
+    operation_id = "ListThreatIndicatorFeeds",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = IndicatorFeedList),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/threat_intel/feeds")]
+pub async fn list_feeds(path: web::Path<String>) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match threat_intel::list_feeds(&org_id).await {
+        Ok(list) => Ok(MetaHttpResponse::json(IndicatorFeedList { list })),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteIndicatorFeed
+///
+/// #{"ratelimit_module":"Threat Intel", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Threat Intel",
+    operation_id = "DeleteThreatIndicatorFeed",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("id" = String, Path, description = "Feed id"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/threat_intel/feeds/{id}")]
+pub async fn delete_feed(path: web::Path<(String, String)>) -> Result<HttpResponse, Error> {
+    let (org_id, id) = path.into_inner();
+    match threat_intel::delete_feed(&org_id, &id).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Feed deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
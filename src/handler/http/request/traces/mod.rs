@@ -273,6 +273,10 @@ pub async fn get_latest_traces(
             skip_wal: false,
             streaming_output: false,
             streaming_id: None,
+            max_points: None,
+            time_ranges: vec![],
+            time_shifts: vec![],
+            variables: vec![],
         },
         encoding: config::meta::search::RequestEncoding::Empty,
         regions: vec![],
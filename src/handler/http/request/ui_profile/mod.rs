@@ -0,0 +1,184 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpResponse, delete, get, put, web};
+use config::meta::{
+    ui_profile::{self as meta_ui_profile, UiProfile},
+    user::UserRole,
+};
+
+use crate::{
+    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    service::ui_profile::{self, UiProfileError},
+};
+
+fn map_error(e: UiProfileError) -> HttpResponse {
+    match e {
+        UiProfileError::NotFound => MetaHttpResponse::not_found(e),
+        UiProfileError::InfraError(e) => MetaHttpResponse::internal_error(e),
+        e => MetaHttpResponse::bad_request(e),
+    }
+}
+
+/// ListUiProfiles
+///
+/// #{"ratelimit_module":"Ui Profiles", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Ui Profiles",
+    operation_id = "ListUiProfiles",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Vec<UiProfile>),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/ui_profiles")]
+pub async fn list_profiles(path: web::Path<String>) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match ui_profile::list(&org_id).await {
+        Ok(list) => Ok(MetaHttpResponse::json(list)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// SaveUiProfile
+///
+/// Creates or replaces the UI profile of a role or of a user: the page they land on after
+/// logging in, the hidden sections and the dashboards they can open. The profile of a user takes
+/// precedence over the profile of their role. The hidden explorers and the dashboards which are
+/// not allowed are also refused by the server.
+///
+/// #{"ratelimit_module":"Ui Profiles", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Ui Profiles",
+    operation_id = "SaveUiProfile",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = UiProfile, description = "Ui profile data", content_type = "application/json", example = json!({"role": "viewer", "landing_dashboard": "7278302271256543232", "hidden_sections": ["logs", "traces"], "allowed_dashboards": ["7278302271256543232"]})),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = UiProfile),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/ui_profiles")]
+pub async fn save_profile(
+    path: web::Path<String>,
+    req: web::Json<UiProfile>,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match ui_profile::save(&org_id, req.into_inner()).await {
+        Ok(profile) => Ok(MetaHttpResponse::json(profile)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// DeleteUiProfile
+///
+/// #{"ratelimit_module":"Ui Profiles", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Ui Profiles",
+    operation_id = "DeleteUiProfile",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("kind" = String, Path, description = "Who the profile applies to: role or user"),
+        ("name" = String, Path, description = "Role or user email"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/ui_profiles/{kind}/{name}")]
+pub async fn delete_profile(
+    path: web::Path<(String, String, String)>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, kind, name) = path.into_inner();
+    let key = match kind.as_str() {
+        "role" => {
+            // unknown roles parse as admin
+            let Some(role) = name
+                .parse::<UserRole>()
+                .ok()
+                .filter(|r| r.to_string() == name)
+            else {
+                return Ok(MetaHttpResponse::bad_request(format!(
+                    "invalid role {name}"
+                )));
+            };
+            meta_ui_profile::role_key(&role)
+        }
+        "user" => meta_ui_profile::user_key(&name.to_lowercase()),
+        _ => {
+            return Ok(MetaHttpResponse::bad_request(format!(
+                "invalid profile kind {kind}, expected role or user"
+            )));
+        }
+    };
+    match ui_profile::delete(&org_id, &key).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Ui profile deleted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// GetMyUiProfile
+///
+/// Returns the UI profile applying to the current user, their own profile or else the profile
+/// of their role. A user without a profile gets an empty profile, which doesn't restrict
+/// anything.
+///
+/// #{"ratelimit_module":"Ui Profiles", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Ui Profiles",
+    operation_id = "GetMyUiProfile",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = UiProfile),
+    )
+)]
+#[get("/{org_id}/ui_profiles/_me")]
+pub async fn get_my_profile(
+    path: web::Path<String>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    let profile = ui_profile::effective(&org_id, &user_email.user_id)
+        .await
+        .unwrap_or_default();
+    Ok(MetaHttpResponse::json(profile))
+}
@@ -0,0 +1,197 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Error;
+
+use actix_web::{HttpResponse, delete, get, post, web};
+use config::meta::invitation::{
+    AcceptInvitationRequest, CreateInvitationsRequest, CreateInvitationsResponse, InvitationInfo,
+    SignupRequest,
+};
+
+use crate::{
+    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    service::invitations::{self, InvitationError},
+};
+
+fn map_error(e: InvitationError) -> HttpResponse {
+    match e {
+        InvitationError::SignupDisabled
+        | InvitationError::DomainNotAllowed
+        | InvitationError::NotAllowed => MetaHttpResponse::forbidden(e),
+        InvitationError::NotFound | InvitationError::Expired => MetaHttpResponse::not_found(e),
+        InvitationError::AlreadyMember => MetaHttpResponse::conflict(e),
+        InvitationError::InvalidEmail(_)
+        | InvitationError::InvalidRole(_)
+        | InvitationError::PasswordRequired => MetaHttpResponse::bad_request(e),
+        InvitationError::SmtpDisabled | InvitationError::Other(_) => {
+            MetaHttpResponse::internal_error(e)
+        }
+    }
+}
+
+/// CreateInvitations
+///
+/// Invites the emails into the organization with the given role. Each email receives an
+/// invitation token valid for `ZO_ORG_INVITE_EXPIRY` days.
+///
+/// #{"ratelimit_module":"Users", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Users",
+    operation_id = "CreateInvitations",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = CreateInvitationsRequest, description = "Emails to invite", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = CreateInvitationsResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/invitations")]
+pub async fn create_invitations(
+    path: web::Path<String>,
+    body: web::Json<CreateInvitationsRequest>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match invitations::create(&org_id, &user_email.user_id, body.into_inner()).await {
+        Ok(res) => Ok(MetaHttpResponse::json(res)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// ListInvitations
+///
+/// Lists the pending invitations of the organization, only admins can list them.
+///
+/// #{"ratelimit_module":"Users", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Users",
+    operation_id = "ListInvitations",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Vec<InvitationInfo>),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/invitations")]
+pub async fn list_invitations(
+    path: web::Path<String>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match invitations::list(&org_id, &user_email.user_id).await {
+        Ok(list) => Ok(MetaHttpResponse::json(list)),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// RevokeInvitation
+///
+/// Revokes the pending invitations of the email, only admins can revoke them.
+///
+/// #{"ratelimit_module":"Users", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Users",
+    operation_id = "RevokeInvitation",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("email" = String, Path, description = "Invited email"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/invitations/{email}")]
+pub async fn revoke_invitation(
+    path: web::Path<(String, String)>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, email) = path.into_inner();
+    match invitations::revoke(&org_id, &user_email.user_id, &email).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Invitation revoked")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// Signup
+///
+/// Sends an invitation for the signup organization to the email, when self signup is enabled
+/// and the email belongs to one of the `ZO_SIGNUP_ALLOWED_DOMAINS`.
+#[utoipa::path(
+    context_path = "/auth",
+    tag = "Auth",
+    operation_id = "Signup",
+    request_body(content = SignupRequest, description = "Email to sign up with", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/signup")]
+pub async fn signup(body: web::Json<SignupRequest>) -> Result<HttpResponse, Error> {
+    match invitations::signup(&body.email).await {
+        Ok(_) => Ok(MetaHttpResponse::ok(
+            "Check your email to complete the signup",
+        )),
+        Err(e) => Ok(map_error(e)),
+    }
+}
+
+/// AcceptInvitation
+///
+/// Accepts the invitation, creating the user when the invited email has no account yet.
+#[utoipa::path(
+    context_path = "/auth",
+    tag = "Auth",
+    operation_id = "AcceptInvitation",
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("token" = String, Path, description = "Invitation token"),
+    ),
+    request_body(content = AcceptInvitationRequest, description = "User details", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/invitations/{org_id}/{token}")]
+pub async fn accept_invitation(
+    path: web::Path<(String, String)>,
+    body: web::Json<AcceptInvitationRequest>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, token) = path.into_inner();
+    match invitations::accept(&org_id, &token, body.into_inner()).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Invitation accepted")),
+        Err(e) => Ok(map_error(e)),
+    }
+}
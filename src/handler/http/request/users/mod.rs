@@ -51,6 +51,7 @@ use crate::{
     service::users,
 };
 
+pub mod invitations;
 pub mod service_accounts;
 
 /// ListUsers
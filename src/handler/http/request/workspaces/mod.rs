@@ -0,0 +1,345 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{collections::HashMap, io::Error, str::FromStr};
+
+use actix_web::{HttpResponse, delete, get, post, put, web};
+use config::meta::workspace::{
+    ResourceType, Workspace, WorkspaceList, WorkspaceResource, WorkspaceResourceList,
+};
+
+use crate::{
+    common::{meta::http::HttpResponse as MetaHttpResponse, utils::auth::UserEmail},
+    service::workspaces::{self, WorkspaceError},
+};
+
+impl From<WorkspaceError> for HttpResponse {
+    fn from(e: WorkspaceError) -> Self {
+        match e {
+            WorkspaceError::NotFound | WorkspaceError::ResourceNotFound(_) => {
+                MetaHttpResponse::not_found(e)
+            }
+            WorkspaceError::AlreadyExists => MetaHttpResponse::conflict(e),
+            WorkspaceError::Invalid(_) => MetaHttpResponse::bad_request(e),
+            WorkspaceError::PermissionDenied => MetaHttpResponse::forbidden(e),
+            e => MetaHttpResponse::internal_error(e),
+        }
+    }
+}
+
+/// CreateWorkspace
+///
+/// Workspaces group the streams, dashboards and alerts of a team, the lists of streams,
+/// dashboards and alerts can be filtered by workspace. When the workspace has members, only they
+/// can read and change its resources, and change the workspace.
+///
+/// #{"ratelimit_module":"Organizations", "ratelimit_module_operation":"create"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Workspaces",
+    operation_id = "CreateWorkspace",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    request_body(content = Workspace, description = "Workspace details", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Workspace),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 409, description = "Conflict", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[post("/{org_id}/workspaces")]
+pub async fn create_workspace(
+    path: web::Path<String>,
+    req: web::Json<Workspace>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match workspaces::create(&org_id, &user_email.user_id, req.into_inner()).await {
+        Ok(workspace) => Ok(MetaHttpResponse::json(workspace)),
+        Err(e) => Ok(e.into()),
+    }
+}
+
+/// UpdateWorkspace
+///
+/// #{"ratelimit_module":"Organizations", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Workspaces",
+    operation_id = "UpdateWorkspace",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("workspace_id" = String, Path, description = "Workspace ID"),
+    ),
+    request_body(content = Workspace, description = "Workspace details", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Workspace),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/workspaces/{workspace_id}")]
+pub async fn update_workspace(
+    path: web::Path<(String, String)>,
+    req: web::Json<Workspace>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, workspace_id) = path.into_inner();
+    match workspaces::update(
+        &org_id,
+        &user_email.user_id,
+        &workspace_id,
+        req.into_inner(),
+    )
+    .await
+    {
+        Ok(workspace) => Ok(MetaHttpResponse::json(workspace)),
+        Err(e) => Ok(e.into()),
+    }
+}
+
+/// ListWorkspaces
+///
+/// #{"ratelimit_module":"Organizations", "ratelimit_module_operation":"list"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Workspaces",
+    operation_id = "ListWorkspaces",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = WorkspaceList),
+        (status = 500, description = "Failure", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/workspaces")]
+pub async fn list_workspaces(path: web::Path<String>) -> Result<HttpResponse, Error> {
+    let org_id = path.into_inner();
+    match workspaces::list(&org_id).await {
+        Ok(list) => Ok(MetaHttpResponse::json(WorkspaceList { list })),
+        Err(e) => Ok(e.into()),
+    }
+}
+
+/// GetWorkspace
+///
+/// #{"ratelimit_module":"Organizations", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Workspaces",
+    operation_id = "GetWorkspace",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("workspace_id" = String, Path, description = "Workspace ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = Workspace),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/workspaces/{workspace_id}")]
+pub async fn get_workspace(path: web::Path<(String, String)>) -> Result<HttpResponse, Error> {
+    let (org_id, workspace_id) = path.into_inner();
+    match workspaces::get(&org_id, &workspace_id).await {
+        Ok(workspace) => Ok(MetaHttpResponse::json(workspace)),
+        Err(e) => Ok(e.into()),
+    }
+}
+
+/// DeleteWorkspace
+///
+/// Deletes the workspace, its streams, dashboards and alerts are kept and no longer belong to
+/// any workspace.
+///
+/// #{"ratelimit_module":"Organizations", "ratelimit_module_operation":"delete"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Workspaces",
+    operation_id = "DeleteWorkspace",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("workspace_id" = String, Path, description = "Workspace ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/workspaces/{workspace_id}")]
+pub async fn delete_workspace(
+    path: web::Path<(String, String)>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, workspace_id) = path.into_inner();
+    match workspaces::delete(&org_id, &user_email.user_id, &workspace_id).await {
+        Ok(_) => Ok(MetaHttpResponse::ok("Workspace deleted")),
+        Err(e) => Ok(e.into()),
+    }
+}
+
+/// ListWorkspaceResources
+///
+/// Lists the streams, dashboards and alerts which belong to the workspace, then the ones shared
+/// with it.
+///
+/// #{"ratelimit_module":"Organizations", "ratelimit_module_operation":"get"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Workspaces",
+    operation_id = "ListWorkspaceResources",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("workspace_id" = String, Path, description = "Workspace ID"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = WorkspaceResourceList),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[get("/{org_id}/workspaces/{workspace_id}/resources")]
+pub async fn list_workspace_resources(
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse, Error> {
+    let (org_id, workspace_id) = path.into_inner();
+    match workspaces::list_resources(&org_id, &workspace_id).await {
+        Ok(list) => Ok(MetaHttpResponse::json(WorkspaceResourceList { list })),
+        Err(e) => Ok(e.into()),
+    }
+}
+
+/// AddWorkspaceResource
+///
+/// Adds a stream, dashboard or alert to the workspace. A resource which is not shared is moved
+/// from the workspace it belonged to, a shared resource is also listed in its own workspace.
+///
+/// #{"ratelimit_module":"Organizations", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Workspaces",
+    operation_id = "AddWorkspaceResource",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("workspace_id" = String, Path, description = "Workspace ID"),
+    ),
+    request_body(content = WorkspaceResource, description = "Resource to add", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = WorkspaceResource),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[put("/{org_id}/workspaces/{workspace_id}/resources")]
+pub async fn add_workspace_resource(
+    path: web::Path<(String, String)>,
+    req: web::Json<WorkspaceResource>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, workspace_id) = path.into_inner();
+    match workspaces::add_resource(
+        &org_id,
+        &user_email.user_id,
+        &workspace_id,
+        req.into_inner(),
+    )
+    .await
+    {
+        Ok(resource) => Ok(MetaHttpResponse::json(resource)),
+        Err(e) => Ok(e.into()),
+    }
+}
+
+/// RemoveWorkspaceResource
+///
+/// Removes a stream, dashboard or alert from the workspace, or stops sharing it with the
+/// workspace.
+///
+/// #{"ratelimit_module":"Organizations", "ratelimit_module_operation":"update"}#
+#[utoipa::path(
+    context_path = "/api",
+    tag = "Workspaces",
+    operation_id = "RemoveWorkspaceResource",
+    security(
+        ("Authorization"= [])
+    ),
+    params(
+        ("org_id" = String, Path, description = "Organization name"),
+        ("workspace_id" = String, Path, description = "Workspace ID"),
+        ("resource_type" = String, Query, description = "Resource type: stream, dashboard or alert"),
+        ("resource_id" = String, Query, description = "Dashboard ID, alert ID, or {stream_type}/{stream_name} for a stream"),
+    ),
+    responses(
+        (status = 200, description = "Success", content_type = "application/json", body = HttpResponse),
+        (status = 400, description = "Failure", content_type = "application/json", body = HttpResponse),
+        (status = 403, description = "Forbidden", content_type = "application/json", body = HttpResponse),
+        (status = 404, description = "NotFound", content_type = "application/json", body = HttpResponse),
+    )
+)]
+#[delete("/{org_id}/workspaces/{workspace_id}/resources")]
+pub async fn remove_workspace_resource(
+    path: web::Path<(String, String)>,
+    query: web::Query<HashMap<String, String>>,
+    user_email: UserEmail,
+) -> Result<HttpResponse, Error> {
+    let (org_id, workspace_id) = path.into_inner();
+    let resource_type = match query
+        .get("resource_type")
+        .map(|s| ResourceType::from_str(s))
+    {
+        Some(Ok(resource_type)) => resource_type,
+        Some(Err(e)) => return Ok(MetaHttpResponse::bad_request(e)),
+        None => return Ok(MetaHttpResponse::bad_request("resource_type is required")),
+    };
+    let Some(resource_id) = query.get("resource_id") else {
+        return Ok(MetaHttpResponse::bad_request("resource_id is required"));
+    };
+    match workspaces::remove_resource(
+        &org_id,
+        &user_email.user_id,
+        &workspace_id,
+        resource_type,
+        resource_id,
+    )
+    .await
+    {
+        Ok(_) => Ok(MetaHttpResponse::ok("Resource removed from the workspace")),
+        Err(e) => Ok(e.into()),
+    }
+}
@@ -253,7 +253,9 @@ pub fn get_basic_routes(svc: &mut web::ServiceConfig) {
             .wrap(cors.clone())
             .service(users::authentication)
             .service(users::get_presigned_url)
-            .service(users::get_auth),
+            .service(users::get_auth)
+            .service(users::invitations::signup)
+            .service(users::invitations::accept_invitation),
     );
 
     svc.service(
@@ -379,6 +381,9 @@ pub fn get_service_routes(svc: &mut web::ServiceConfig) {
         .service(users::add_user_to_org)
         .service(users::list_invitations)
         .service(users::list_roles)
+        .service(users::invitations::create_invitations)
+        .service(users::invitations::list_invitations)
+        .service(users::invitations::revoke_invitation)
         .service(organization::org::organizations)
         .service(organization::settings::get)
         .service(organization::settings::create)
@@ -386,6 +391,14 @@ pub fn get_service_routes(svc: &mut web::ServiceConfig) {
         .service(organization::settings::delete_logo)
         .service(organization::settings::set_logo_text)
         .service(organization::settings::delete_logo_text)
+        .service(workspaces::create_workspace)
+        .service(workspaces::update_workspace)
+        .service(workspaces::list_workspaces)
+        .service(workspaces::get_workspace)
+        .service(workspaces::delete_workspace)
+        .service(workspaces::list_workspace_resources)
+        .service(workspaces::add_workspace_resource)
+        .service(workspaces::remove_workspace_resource)
         .service(organization::org::org_summary)
         .service(organization::org::get_user_passcode)
         .service(organization::org::update_user_passcode)
@@ -410,9 +423,17 @@ pub fn get_service_routes(svc: &mut web::ServiceConfig) {
         .service(stream::delete_fields)
         .service(stream::delete)
         .service(stream::list)
+        .service(stream::hourly_stats)
+        .service(stream::storage_usage)
+        .service(stream::field_usage)
+        .service(index_advisor::list_recommendations)
+        .service(index_advisor::apply_recommendation)
+        .service(index_advisor::dismiss_recommendation)
+        .service(stream::clone)
         .service(logs::ingest::bulk)
         .service(logs::ingest::multi)
         .service(logs::ingest::json)
+        .service(logs::ingest::csv)
         .service(logs::ingest::hec)
         .service(logs::ingest::otlp_logs_write)
         .service(logs::loki::loki_push)
@@ -434,6 +455,9 @@ pub fn get_service_routes(svc: &mut web::ServiceConfig) {
         .service(promql::labels_get)
         .service(promql::labels_post)
         .service(promql::label_values)
+        .service(promql::explorer_metrics)
+        .service(promql::explorer_labels)
+        .service(promql::explorer_series)
         .service(promql::format_query_get)
         .service(promql::format_query_post)
         .service(search::search)
@@ -456,6 +480,12 @@ pub fn get_service_routes(svc: &mut web::ServiceConfig) {
         .service(functions::delete_function)
         .service(functions::update_function)
         .service(functions::list_pipeline_dependencies)
+        .service(dashboards::validate_dashboard)
+        .service(dashboards::list_dashboard_lints)
+        .service(dashboards::lint_dashboard)
+        .service(dashboards::archive::export_dashboards)
+        .service(dashboards::archive::import_dashboards)
+        .service(dashboards::archive::export_dashboard)
         .service(dashboards::create_dashboard)
         .service(dashboards::update_dashboard)
         .service(dashboards::list_dashboards)
@@ -463,6 +493,11 @@ pub fn get_service_routes(svc: &mut web::ServiceConfig) {
         .service(dashboards::delete_dashboard)
         .service(dashboards::move_dashboard)
         .service(dashboards::move_dashboards)
+        .service(dashboards::copy_dashboard)
+        .service(dashboards::acl::get_dashboard_acl)
+        .service(dashboards::acl::update_dashboard_acl)
+        .service(dashboards::share::create_share)
+        .service(dashboards::share::revoke_shares)
         .service(dashboards::reports::create_report)
         .service(dashboards::reports::update_report)
         .service(dashboards::reports::get_report)
@@ -475,6 +510,21 @@ pub fn get_service_routes(svc: &mut web::ServiceConfig) {
         .service(dashboards::timed_annotations::delete_annotations)
         .service(dashboards::timed_annotations::update_annotations)
         .service(dashboards::timed_annotations::delete_annotation_panels)
+        .service(dashboards::render::render_dashboard)
+        .service(dashboards::variables::resolve_variables)
+        .service(dashboards::snapshots::create_snapshot)
+        .service(dashboards::snapshots::list_snapshots)
+        .service(dashboards::snapshots::delete_snapshot)
+        .service(dashboards::snapshots::create_snapshot_schedule)
+        .service(dashboards::snapshots::list_snapshot_schedules)
+        .service(dashboards::snapshots::delete_snapshot_schedule)
+        .service(dashboards::snapshots::list_scheduled_snapshots)
+        .service(dashboards::snapshots::get_scheduled_snapshot_dataset)
+        .service(dashboards::library_panels::create_library_panel)
+        .service(dashboards::library_panels::update_library_panel)
+        .service(dashboards::library_panels::list_library_panels)
+        .service(dashboards::library_panels::get_library_panel)
+        .service(dashboards::library_panels::delete_library_panel)
         .service(folders::create_folder)
         .service(folders::list_folders)
         .service(folders::update_folder)
@@ -488,12 +538,18 @@ pub fn get_service_routes(svc: &mut web::ServiceConfig) {
         .service(folders::deprecated::get_folder_by_name)
         .service(folders::deprecated::delete_folder)
         .service(alerts::create_alert)
+        .service(alerts::create_alert_from_panel)
+        .service(alerts::sync_alert_with_panel)
         .service(alerts::get_alert)
         .service(alerts::update_alert)
         .service(alerts::delete_alert)
         .service(alerts::list_alerts)
         .service(alerts::enable_alert)
         .service(alerts::trigger_alert)
+        .service(alerts::backtest_alert)
+        .service(alerts::get_alert_history)
+        .service(alerts::get_alert_metrics)
+        .service(alerts::sync_alerts)
         .service(alerts::move_alerts)
         .service(alerts::deprecated::save_alert)
         .service(alerts::deprecated::update_alert)
@@ -508,15 +564,92 @@ pub fn get_service_routes(svc: &mut web::ServiceConfig) {
         .service(alerts::templates::get_template)
         .service(alerts::templates::delete_template)
         .service(alerts::templates::list_templates)
+        .service(alerts::templates::preview_template)
         .service(alerts::destinations::save_destination)
         .service(alerts::destinations::update_destination)
         .service(alerts::destinations::get_destination)
         .service(alerts::destinations::list_destinations)
         .service(alerts::destinations::delete_destination)
+        .service(alerts::silences::create_silence)
+        .service(alerts::silences::update_silence)
+        .service(alerts::silences::list_silences)
+        .service(alerts::silences::get_silence)
+        .service(alerts::silences::delete_silence)
+        .service(alerts::silences::list_silenced_notifications)
+        .service(alerts::escalations::create_escalation_policy)
+        .service(alerts::escalations::update_escalation_policy)
+        .service(alerts::escalations::list_escalation_policies)
+        .service(alerts::escalations::get_escalation_policy)
+        .service(alerts::escalations::delete_escalation_policy)
+        .service(alerts::escalations::acknowledge_alert)
         .service(kv::get)
         .service(kv::set)
         .service(kv::delete)
         .service(kv::list)
+        .service(cases::create)
+        .service(cases::list)
+        .service(cases::get)
+        .service(cases::update)
+        .service(cases::delete)
+        .service(cases::add_attachment)
+        .service(cases::remove_attachment)
+        .service(cases::add_comment)
+        .service(cases::timeline)
+        .service(detections::create)
+        .service(detections::update)
+        .service(detections::get)
+        .service(detections::list)
+        .service(detections::delete)
+        .service(threat_intel::add_indicators)
+        .service(threat_intel::list_indicators)
+        .service(threat_intel::delete_indicator)
+        .service(threat_intel::create_feed)
+        .service(threat_intel::update_feed)
+        .service(threat_intel::get_feed)
+        .service(threat_intel::list_feeds)
+        .service(threat_intel::delete_feed)
+        .service(grok::list_patterns)
+        .service(grok::save_pattern)
+        .service(grok::delete_pattern)
+        .service(grok::test_pattern)
+        .service(sql_policy::get_policy)
+        .service(sql_policy::save_policy)
+        .service(sql_policy::delete_policy)
+        .service(remote_write_filter::get_filter)
+        .service(remote_write_filter::save_filter)
+        .service(remote_write_filter::delete_filter)
+        .service(row_policy::list_policies)
+        .service(row_policy::save_policy)
+        .service(row_policy::delete_policy)
+        .service(ui_profile::get_my_profile)
+        .service(ui_profile::list_profiles)
+        .service(ui_profile::save_profile)
+        .service(ui_profile::delete_profile)
+        .service(column_mask::list_masks)
+        .service(column_mask::save_mask)
+        .service(column_mask::delete_mask)
+        .service(query_template::list_templates)
+        .service(query_template::get_template)
+        .service(query_template::save_template)
+        .service(query_template::delete_template)
+        .service(query_template::create_token)
+        .service(annotations::list_annotations)
+        .service(annotations::create_annotation)
+        .service(annotations::create_from_webhook)
+        .service(annotations::update_annotation)
+        .service(annotations::delete_annotation)
+        .service(incident_timeline::get_timeline)
+        .service(log_metrics::list_rules)
+        .service(log_metrics::get_rule)
+        .service(log_metrics::save_rule)
+        .service(log_metrics::delete_rule)
+        .service(synthetic_data::start_job)
+        .service(synthetic_data::list_jobs)
+        .service(synthetic_data::get_job)
+        .service(synthetic_data::delete_job)
+        .service(chaos::list_scenarios)
+        .service(chaos::set_scenario)
+        .service(chaos::delete_scenario)
         .service(syslog::list_routes)
         .service(syslog::create_route)
         .service(syslog::delete_route)
@@ -551,6 +684,10 @@ pub fn get_service_routes(svc: &mut web::ServiceConfig) {
         .service(search::multi_streams::search_multi)
         .service(search::multi_streams::_search_partition_multi)
         .service(search::multi_streams::around_multi)
+        .service(search::diff::search_diff)
+        .service(search::outliers::search_outliers)
+        .service(search::estimate::search_estimate)
+        .service(search::cross_org::search_cross_org)
         .service(stream::delete_stream_cache)
         .service(short_url::shorten)
         .service(short_url::retrieve)
@@ -558,6 +695,7 @@ pub fn get_service_routes(svc: &mut web::ServiceConfig) {
         .service(service_accounts::save)
         .service(service_accounts::delete)
         .service(service_accounts::update)
+        .service(service_accounts::update_status)
         .service(service_accounts::get_api_token)
         .service(ws::websocket);
 
@@ -629,6 +767,27 @@ pub fn get_other_service_routes(svc: &mut web::ServiceConfig) {
             .service(logs::ingest::handle_gcp_request),
     );
 
+    // snapshot pages are shared by link, the snapshot id is the credential
+    svc.service(
+        web::scope("/snapshots")
+            .wrap(cors.clone())
+            .service(dashboards::snapshots::get_snapshot_page),
+    );
+
+    // shared dashboards authenticate with their own signed tokens
+    svc.service(
+        web::scope("/share")
+            .wrap(cors.clone())
+            .service(dashboards::share::get_shared_dashboard),
+    );
+
+    // query templates authenticate with their own signed tokens
+    svc.service(
+        web::scope("/embed")
+            .wrap(cors.clone())
+            .service(query_template::execute_template),
+    );
+
     // NOTE: Here the order of middlewares matter. Once we consume the api-token in
     // `rum_auth`, we drop it in the RumExtraData data.
     // https://docs.rs/actix-web/latest/actix_web/middleware/index.html#ordering
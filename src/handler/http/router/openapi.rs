@@ -29,6 +29,11 @@ use crate::{common::meta, handler::http::request};
         request::users::update,
         request::users::delete,
         request::users::add_user_to_org,
+        request::users::invitations::create_invitations,
+        request::users::invitations::list_invitations,
+        request::users::invitations::revoke_invitation,
+        request::users::invitations::signup,
+        request::users::invitations::accept_invitation,
         request::organization::org::organizations,
         request::organization::org::create_org,
         request::organization::org::rename_org,
@@ -40,15 +45,31 @@ use crate::{common::meta, handler::http::request};
         request::organization::org::create_user_rumtoken,
         request::organization::settings::get,
         request::organization::settings::create,
+        request::workspaces::create_workspace,
+        request::workspaces::update_workspace,
+        request::workspaces::list_workspaces,
+        request::workspaces::get_workspace,
+        request::workspaces::delete_workspace,
+        request::workspaces::list_workspace_resources,
+        request::workspaces::add_workspace_resource,
+        request::workspaces::remove_workspace_resource,
         request::stream::list,
+        request::stream::hourly_stats,
+        request::stream::storage_usage,
+        request::stream::field_usage,
+        request::index_advisor::list_recommendations,
+        request::index_advisor::apply_recommendation,
+        request::index_advisor::dismiss_recommendation,
         request::stream::schema,
         request::stream::settings,
         request::stream::update_settings,
         request::stream::delete_fields,
         request::stream::delete,
+        request::stream::clone,
         request::logs::ingest::bulk,
         request::logs::ingest::multi,
         request::logs::ingest::json,
+        request::logs::ingest::csv,
         request::logs::loki::loki_push,
         request::traces::traces_write,
         request::traces::get_latest_traces,
@@ -60,6 +81,9 @@ use crate::{common::meta, handler::http::request};
         request::promql::series_get,
         request::promql::labels_get,
         request::promql::label_values,
+        request::promql::explorer_metrics,
+        request::promql::explorer_labels,
+        request::promql::explorer_series,
         request::promql::format_query_get,
         request::enrichment_table::save_enrichment_table,
         request::rum::ingest::log,
@@ -71,6 +95,10 @@ use crate::{common::meta, handler::http::request};
         request::search::around_v2,
         request::search::values,
         request::search::search_history,
+        request::search::diff::search_diff,
+        request::search::outliers::search_outliers,
+        request::search::estimate::search_estimate,
+        request::search::cross_org::search_cross_org,
         request::search::saved_view::create_view,
         request::search::saved_view::delete_view,
         request::search::saved_view::get_view,
@@ -101,6 +129,15 @@ use crate::{common::meta, handler::http::request};
         request::dashboards::delete_dashboard,
         request::dashboards::move_dashboard,
         request::dashboards::move_dashboards,
+        request::dashboards::copy_dashboard,
+        request::dashboards::acl::get_dashboard_acl,
+        request::dashboards::acl::update_dashboard_acl,
+        request::dashboards::share::create_share,
+        request::dashboards::share::revoke_shares,
+        request::dashboards::share::get_shared_dashboard,
+        request::dashboards::validate_dashboard,
+        request::dashboards::lint_dashboard,
+        request::dashboards::list_dashboard_lints,
         request::dashboards::timed_annotations::create_annotations,
         request::dashboards::timed_annotations::get_annotations,
         request::dashboards::timed_annotations::delete_annotations,
@@ -121,21 +158,105 @@ use crate::{common::meta, handler::http::request};
         request::alerts::list_alerts,
         request::alerts::enable_alert,
         request::alerts::trigger_alert,
+        request::alerts::backtest_alert,
+        request::alerts::get_alert_history,
+        request::alerts::get_alert_metrics,
+        request::alerts::sync_alerts,
         request::alerts::move_alerts,
+        request::alerts::create_alert_from_panel,
+        request::alerts::sync_alert_with_panel,
         request::alerts::templates::list_templates,
         request::alerts::templates::get_template,
         request::alerts::templates::save_template,
         request::alerts::templates::update_template,
         request::alerts::templates::delete_template,
+        request::alerts::templates::preview_template,
         request::alerts::destinations::list_destinations,
         request::alerts::destinations::get_destination,
         request::alerts::destinations::save_destination,
         request::alerts::destinations::update_destination,
         request::alerts::destinations::delete_destination,
+        request::alerts::silences::create_silence,
+        request::alerts::silences::update_silence,
+        request::alerts::silences::list_silences,
+        request::alerts::silences::get_silence,
+        request::alerts::silences::delete_silence,
+        request::alerts::silences::list_silenced_notifications,
+        request::alerts::escalations::create_escalation_policy,
+        request::alerts::escalations::update_escalation_policy,
+        request::alerts::escalations::list_escalation_policies,
+        request::alerts::escalations::get_escalation_policy,
+        request::alerts::escalations::delete_escalation_policy,
+        request::alerts::escalations::acknowledge_alert,
         request::kv::get,
         request::kv::set,
         request::kv::delete,
         request::kv::list,
+        request::cases::create,
+        request::cases::list,
+        request::cases::get,
+        request::cases::update,
+        request::cases::delete,
+        request::cases::add_attachment,
+        request::cases::remove_attachment,
+        request::cases::add_comment,
+        request::cases::timeline,
+        request::detections::create,
+        request::detections::update,
+        request::detections::get,
+        request::detections::list,
+        request::detections::delete,
+        request::threat_intel::add_indicators,
+        request::threat_intel::list_indicators,
+        request::threat_intel::delete_indicator,
+        request::threat_intel::create_feed,
+        request::threat_intel::update_feed,
+        request::threat_intel::get_feed,
+        request::threat_intel::list_feeds,
+        request::threat_intel::delete_feed,
+        request::grok::list_patterns,
+        request::grok::save_pattern,
+        request::grok::delete_pattern,
+        request::grok::test_pattern,
+        request::sql_policy::get_policy,
+        request::sql_policy::save_policy,
+        request::sql_policy::delete_policy,
+        request::remote_write_filter::get_filter,
+        request::remote_write_filter::save_filter,
+        request::remote_write_filter::delete_filter,
+        request::row_policy::list_policies,
+        request::row_policy::save_policy,
+        request::row_policy::delete_policy,
+        request::ui_profile::list_profiles,
+        request::ui_profile::save_profile,
+        request::ui_profile::delete_profile,
+        request::ui_profile::get_my_profile,
+        request::column_mask::list_masks,
+        request::column_mask::save_mask,
+        request::column_mask::delete_mask,
+        request::query_template::list_templates,
+        request::query_template::get_template,
+        request::query_template::save_template,
+        request::query_template::delete_template,
+        request::query_template::create_token,
+        request::query_template::execute_template,
+        request::annotations::list_annotations,
+        request::annotations::create_annotation,
+        request::annotations::create_from_webhook,
+        request::annotations::update_annotation,
+        request::annotations::delete_annotation,
+        request::incident_timeline::get_timeline,
+        request::log_metrics::list_rules,
+        request::log_metrics::get_rule,
+        request::log_metrics::save_rule,
+        request::log_metrics::delete_rule,
+        request::synthetic_data::start_job,
+        request::synthetic_data::list_jobs,
+        request::synthetic_data::get_job,
+        request::synthetic_data::delete_job,
+        request::chaos::list_scenarios,
+        request::chaos::set_scenario,
+        request::chaos::delete_scenario,
         request::syslog::create_route,
         request::syslog::update_route,
         request::syslog::list_routes,
@@ -149,6 +270,7 @@ use crate::{common::meta, handler::http::request};
         request::service_accounts::list,
         request::service_accounts::save,
         request::service_accounts::update,
+        request::service_accounts::update_status,
         request::service_accounts::delete,
         request::service_accounts::get_api_token,
         request::pipeline::save_pipeline,
@@ -164,6 +286,25 @@ use crate::{common::meta, handler::http::request};
         request::dashboards::reports::delete_report,
         request::dashboards::reports::enable_report,
         request::dashboards::reports::trigger_report,
+        request::dashboards::render::render_dashboard,
+        request::dashboards::archive::export_dashboards,
+        request::dashboards::archive::import_dashboards,
+        request::dashboards::archive::export_dashboard,
+        request::dashboards::variables::resolve_variables,
+        request::dashboards::snapshots::create_snapshot,
+        request::dashboards::snapshots::list_snapshots,
+        request::dashboards::snapshots::delete_snapshot,
+        request::dashboards::snapshots::get_snapshot_page,
+        request::dashboards::snapshots::create_snapshot_schedule,
+        request::dashboards::snapshots::list_snapshot_schedules,
+        request::dashboards::snapshots::delete_snapshot_schedule,
+        request::dashboards::snapshots::list_scheduled_snapshots,
+        request::dashboards::snapshots::get_scheduled_snapshot_dataset,
+        request::dashboards::library_panels::create_library_panel,
+        request::dashboards::library_panels::update_library_panel,
+        request::dashboards::library_panels::list_library_panels,
+        request::dashboards::library_panels::get_library_panel,
+        request::dashboards::library_panels::delete_library_panel,
         request::actions::action::upload_zipped_action,
         request::actions::action::delete_action,
         request::actions::action::serve_action_zip,
@@ -204,12 +345,33 @@ use crate::{common::meta, handler::http::request};
             meta::stream::StreamProperty,
             meta::stream::StreamDeleteFields,
             meta::stream::ListStream,
+            meta::stream::StreamHourlyStatsResponse,
+            meta::stream::StorageUsageResponse,
+            meta::stream::StreamFieldUsageResponse,
+            meta::stream::StreamStorageUsage,
+            meta::stream::StreamCloneRequest,
+            meta::stream::StreamCloneSample,
+            meta::stream::StreamCloneResponse,
             config::meta::stream::StreamSettings,
             config::meta::stream::StreamPartition,
             config::meta::stream::StreamPartitionType,
             config::meta::stream::StreamStats,
+            config::meta::stream::StreamHourlyStats,
+            config::meta::stream::StreamStorageSample,
+            config::meta::stream::StreamFieldUsage,
+            config::meta::index_advisor::IndexRecommendation,
+            config::meta::index_advisor::IndexRecommendationList,
+            config::meta::index_advisor::RecommendationKind,
+            config::meta::index_advisor::RecommendationStatus,
+            config::meta::workspace::Workspace,
+            config::meta::workspace::WorkspaceList,
+            config::meta::workspace::ResourceType,
+            config::meta::workspace::WorkspaceResource,
+            config::meta::workspace::WorkspaceResourceList,
+            config::meta::stream::StorageGrowth,
             config::meta::stream::PartitionTimeLevel,
             config::meta::stream::UpdateStreamSettings,
+            config::meta::stream::FieldMetadata,
             config::meta::dashboards::Dashboard,
             config::meta::dashboards::v1::AxisItem,
             config::meta::dashboards::v1::Dashboard,
@@ -223,6 +385,18 @@ use crate::{common::meta, handler::http::request};
             config::meta::dashboards::v1::QueryData,
             config::meta::dashboards::v1::CustomFieldsOption,
             config::meta::dashboards::v1::VariableList,
+            config::meta::dashboards::v5::Threshold,
+            config::meta::dashboards::v5::ThresholdMode,
+            config::meta::dashboards::v5::ThresholdQuery,
+            config::meta::dashboards::v5::ThresholdQueryStep,
+            config::meta::dashboards::transformations::Transformation,
+            config::meta::dashboards::transformations::FilterOperator,
+            config::meta::dashboards::transformations::ComputeOperator,
+            config::meta::dashboards::acl::DashboardAcl,
+            config::meta::dashboards::acl::AclPrincipals,
+            config::meta::dashboards::share::CreateShareRequest,
+            config::meta::dashboards::share::CreateShareResponse,
+            config::meta::dashboards::share::ShareFormat,
             config::meta::alerts::alert::Alert,
             config::meta::alerts::Aggregation,
             config::meta::alerts::AggFunction,
@@ -233,7 +407,36 @@ use crate::{common::meta, handler::http::request};
             config::meta::alerts::QueryType,
             config::meta::alerts::QueryCondition,
             config::meta::alerts::TriggerCondition,
+            config::meta::alerts::PanelSource,
+            config::meta::alerts::AlertBacktest,
+            config::meta::alerts::composite::CompositeCondition,
+            config::meta::alerts::multi_condition::MultiCondition,
+            config::meta::alerts::multi_condition::AlertCondition,
+            config::meta::alerts::multi_condition::ConditionsOperator,
+            config::meta::alerts::anomaly::AnomalyCondition,
+            config::meta::alerts::anomaly::DeviationType,
+            config::meta::alerts::anomaly::AnomalyDirection,
+            config::meta::alerts::BacktestFiring,
+            config::meta::alerts::history::AlertHistory,
+            config::meta::alerts::history::AlertHistoryEntry,
+            config::meta::alerts::history::AlertState,
+            config::meta::alerts::AlertMetrics,
+            config::meta::alerts::AlertsSyncPlan,
+            config::meta::alerts::SyncChanges,
+            config::meta::alerts::silences::Silence,
+            config::meta::alerts::silences::SilenceMatcher,
+            config::meta::alerts::silences::MatchOp,
+            config::meta::alerts::silences::SilenceList,
+            config::meta::alerts::silences::SilencedNotification,
+            config::meta::alerts::silences::SilencedNotificationList,
+            config::meta::alerts::escalation::EscalationPolicy,
+            config::meta::alerts::escalation::EscalationStep,
+            config::meta::alerts::escalation::EscalationPolicyList,
             config::meta::destinations::HTTPType,
+            config::meta::destinations::NotificationGrouping,
+            config::meta::destinations::OpsgeniePriority,
+            config::meta::destinations::PagerDutySeverity,
+            config::meta::destinations::VictorOpsMessageType,
             config::meta::timed_annotations::TimedAnnotation,
             config::meta::timed_annotations::TimedAnnotationReq,
             config::meta::timed_annotations::TimedAnnotationDelete,
@@ -248,14 +451,45 @@ use crate::{common::meta, handler::http::request};
             crate::handler::http::models::dashboards::ListDashboardsResponseBodyItem,
             crate::handler::http::models::dashboards::MoveDashboardRequestBody,
             crate::handler::http::models::dashboards::MoveDashboardsRequestBody,
+            crate::handler::http::models::dashboards::CopyDashboardRequestBody,
+            crate::handler::http::models::dashboards::ValidateDashboardRequestBody,
+            config::meta::dashboards::DashboardValidation,
+            config::meta::dashboards::DashboardLint,
+            config::meta::dashboards::PanelLintIssue,
+            config::meta::dashboards::DashboardImportSummary,
+            config::meta::dashboards::DashboardExport,
+            config::meta::dashboards::StreamReference,
+            config::meta::dashboards::InvalidPanelQuery,
+            config::meta::dashboards::render::RenderFormat,
+            config::meta::dashboards::variables::ResolveVariablesRequest,
+            config::meta::dashboards::variables::ResolvedVariable,
+            config::meta::dashboards::variables::ResolveVariablesResponse,
+            config::meta::dashboards::snapshots::DashboardSnapshot,
+            config::meta::dashboards::snapshots::CreateSnapshotRequest,
+            config::meta::dashboards::snapshots::CreateSnapshotResponse,
+            config::meta::dashboards::snapshots::DashboardSnapshotList,
+            config::meta::dashboards::snapshots::SnapshotFormat,
+            config::meta::dashboards::snapshots::SnapshotSchedule,
+            config::meta::dashboards::snapshots::CreateSnapshotScheduleRequest,
+            config::meta::dashboards::snapshots::SnapshotScheduleList,
+            config::meta::dashboards::snapshots::ScheduledSnapshot,
+            config::meta::dashboards::snapshots::ScheduledSnapshotPanel,
+            config::meta::dashboards::snapshots::ScheduledSnapshotList,
+            config::meta::dashboards::library_panels::LibraryPanel,
+            config::meta::dashboards::library_panels::LibraryPanelList,
             // Destinations
             crate::handler::http::models::destinations::Destination,
             crate::handler::http::models::destinations::DestinationType,
             crate::handler::http::models::destinations::Template,
+            crate::handler::http::models::destinations::TemplatePreviewRequest,
+            crate::handler::http::models::destinations::TemplatePreviewResponse,
             // Alerts
             crate::handler::http::models::alerts::requests::CreateAlertRequestBody,
+            crate::handler::http::models::alerts::requests::CreateAlertFromPanelRequestBody,
             crate::handler::http::models::alerts::requests::UpdateAlertRequestBody,
             crate::handler::http::models::alerts::requests::MoveAlertsRequestBody,
+            crate::handler::http::models::alerts::requests::BacktestAlertRequestBody,
+            crate::handler::http::models::alerts::requests::SyncAlertsRequestBody,
             crate::handler::http::models::alerts::responses::GetAlertResponseBody,
             crate::handler::http::models::alerts::responses::ListAlertsResponseBody,
             crate::handler::http::models::alerts::responses::ListAlertsResponseBodyItem,
@@ -283,23 +517,104 @@ use crate::{common::meta, handler::http::request};
             config::meta::function::TestVRLRequest,
             config::meta::sql::OrderBy,
             config::meta::search::Query,
+            config::meta::search::QueryVariable,
             config::meta::search::Request,
             config::meta::search::RequestEncoding,
             config::meta::search::Response,
             config::meta::search::ResponseTook,
+            config::meta::search::TimeShiftResult,
             config::meta::search::SearchEventType,
             config::meta::search::SearchEventContext,
             config::meta::search::SearchPartitionRequest,
             config::meta::search::SearchPartitionResponse,
             config::meta::search::SearchHistoryRequest,
+            config::meta::search::SearchDiffRequest,
+            config::meta::search::DiffRange,
+            config::meta::search::SearchDiffResponse,
+            config::meta::search::DiffGroup,
+            config::meta::search::DiffChange,
+            config::meta::search::SearchOutliersRequest,
+            config::meta::search::OutlierMethod,
+            config::meta::search::OutlierSeries,
+            config::meta::search::SearchOutliersResponse,
+            config::meta::search::SearchEstimateResponse,
+            config::meta::search::StreamSearchEstimate,
+            config::meta::search::CrossOrgSearchRequest,
+            config::meta::search::CrossOrgSearchResponse,
             config::meta::search::CancelQueryResponse,
             config::meta::search::QueryStatusResponse,
             config::meta::search::QueryStatus,
             config::meta::search::QueryInfo,
             config::meta::search::ScanStats,
+            config::meta::cases::Case,
+            config::meta::cases::CaseList,
+            config::meta::cases::CaseStatus,
+            config::meta::cases::CaseAttachment,
+            config::meta::cases::AttachmentKind,
+            config::meta::cases::CaseComment,
+            config::meta::cases::CaseEvent,
+            config::meta::cases::CaseTimelineEntry,
+            config::meta::cases::CreateCaseRequest,
+            config::meta::cases::UpdateCaseRequest,
+            config::meta::detections::Detection,
+            config::meta::detections::DetectionList,
+            config::meta::detections::DetectionRequest,
+            config::meta::detections::Severity,
+            config::meta::threat_intel::Indicator,
+            config::meta::threat_intel::IndicatorKind,
+            config::meta::threat_intel::IndicatorList,
+            config::meta::threat_intel::IndicatorFeed,
+            config::meta::threat_intel::IndicatorFeedList,
+            config::meta::threat_intel::FeedFormat,
+            config::meta::grok::GrokPattern,
+            config::meta::grok::GrokPatternList,
+            config::meta::grok::GrokTestRequest,
+            config::meta::grok::GrokTestResponse,
+            config::meta::sql_policy::SqlPolicy,
+            config::meta::remote_write_filter::RemoteWriteFilter,
+            config::meta::remote_write_filter::MetricFilterRule,
+            config::meta::row_policy::RowPolicy,
+            config::meta::ui_profile::UiProfile,
+            config::meta::ui_profile::UiSection,
+            config::meta::column_mask::ColumnMaskPolicy,
+            config::meta::column_mask::ColumnMask,
+            config::meta::column_mask::MaskType,
+            config::meta::query_template::QueryTemplate,
+            config::meta::query_template::QueryTemplateParam,
+            config::meta::query_template::ParamType,
+            config::meta::query_template::ParamRange,
+            config::meta::query_template::QueryTemplateList,
+            config::meta::query_template::QueryTemplateTokenRequest,
+            config::meta::query_template::QueryTemplateTokenResponse,
+            config::meta::query_template::QueryTemplateExecuteRequest,
+            config::meta::query_template::QueryTemplateExecuteResponse,
+            config::meta::annotations::Annotation,
+            config::meta::annotations::AnnotationList,
+            config::meta::annotations::AnnotationWebhookEvent,
+            config::meta::incident_timeline::IncidentTimeline,
+            config::meta::incident_timeline::TimelineEvent,
+            config::meta::incident_timeline::TimelineEventKind,
+            config::meta::log_metrics::LogMetricRule,
+            config::meta::log_metrics::LogMetricRuleList,
+            config::meta::log_metrics::LogMetricCondition,
+            config::meta::log_metrics::LogMetricType,
+            config::meta::synthetic_data::SyntheticDataRequest,
+            config::meta::synthetic_data::SyntheticField,
+            config::meta::synthetic_data::SyntheticFieldType,
+            config::meta::synthetic_data::SyntheticJob,
+            config::meta::synthetic_data::SyntheticJobList,
+            config::meta::synthetic_data::SyntheticJobStatus,
+            config::meta::chaos::ChaosScenario,
+            config::meta::chaos::ChaosScenarioList,
+            config::meta::chaos::ChaosTarget,
             config::meta::short_url::ShortenUrlRequest,
             config::meta::short_url::ShortenUrlResponse,
             config::meta::user::UserRole,
+            config::meta::invitation::InvitationInfo,
+            config::meta::invitation::CreateInvitationsRequest,
+            config::meta::invitation::CreateInvitationsResponse,
+            config::meta::invitation::AcceptInvitationRequest,
+            config::meta::invitation::SignupRequest,
             meta::ingestion::RecordStatus,
             meta::ingestion::StreamStatus,
             meta::ingestion::IngestionResponse,
@@ -334,6 +649,7 @@ use crate::{common::meta, handler::http::request};
             meta::organization::OrgRenameBody,
             meta::organization::OrganizationSetting,
             meta::organization::OrganizationSettingResponse,
+            meta::organization::FieldNormalization,
             meta::organization::RumIngestionResponse,
             meta::organization::RumIngestionToken,
             request::status::HealthzResponse,
@@ -345,6 +661,11 @@ use crate::{common::meta, handler::http::request};
             meta::syslog::SyslogRoutes,
             config::meta::promql::Metadata,
             config::meta::promql::MetricType,
+            config::meta::promql::Cardinality,
+            config::meta::promql::ExplorerMetric,
+            config::meta::promql::ExplorerLabel,
+            config::meta::promql::ExplorerLabels,
+            config::meta::promql::ExplorerSeriesPreview,
             // Functions
          ),
     ),
@@ -359,9 +680,25 @@ use crate::{common::meta, handler::http::request};
         (name = "Alerts", description = "Alerts retrieval & management operations"),
         (name = "Functions", description = "Functions retrieval & management operations"),
         (name = "Organizations", description = "Organizations retrieval & management operations"),
+        (name = "Workspaces", description = "Per-team grouping of the streams, dashboards and alerts of an org"),
         (name = "Streams", description = "Stream retrieval & management operations"),
         (name = "Users", description = "Users retrieval & management operations"),
         (name = "KV", description = "Key Value retrieval & management operations"),
+        (name = "Cases", description = "Investigation cases retrieval & management operations"),
+        (name = "Detections", description = "Security detection rules retrieval & management operations"),
+        (name = "Threat Intel", description = "Threat intel indicators and feeds retrieval & management operations"),
+        (name = "Grok", description = "Grok patterns retrieval & management operations"),
+        (name = "Sql Policy", description = "Org sql restrictions for scoped users"),
+        (name = "Remote Write Filter", description = "Org filters on prometheus remote write"),
+        (name = "Row Policy", description = "Row level security filters of roles on streams"),
+        (name = "Ui Profiles", description = "Landing page and visible UI sections of roles and users"),
+        (name = "Column Mask", description = "Masking of sensitive columns for roles on streams"),
+        (name = "Query Templates", description = "Parameterized queries for embedded analytics"),
+        (name = "Annotations", description = "Org level events shown on dashboard panels"),
+        (name = "Incident Timeline", description = "Timelines of alerts, changes and anomalies for postmortems"),
+        (name = "Log Metrics", description = "Metrics extracted from logs at ingest"),
+        (name = "Synthetic Data", description = "Synthetic data generators for demos and load testing"),
+        (name = "Chaos", description = "Fault injection for resilience testing"),
         (name = "Metrics", description = "Metrics data ingestion operations"),
         (name = "Traces", description = "Traces data ingestion operations"),
         (name = "Syslog Routes", description = "Syslog Routes retrieval & management operations"),
@@ -0,0 +1,589 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Fault injection on the object store, the meta store, the file list and the connections of the
+//! SQL meta store, enabled with `ZO_CHAOS_ENABLED` to test how compaction, ingestion and queries
+//! cope with a slow or failing backend.
+
+use std::{collections::HashMap as stdHashMap, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use config::{
+    get_config,
+    meta::{
+        chaos::{ChaosScenario, ChaosTarget},
+        stream::{FileKey, FileListDeleted, FileMeta, PartitionTimeLevel, StreamStats, StreamType},
+    },
+    utils::{rand::get_rand_u128, time::now_micros},
+};
+use futures::future::BoxFuture;
+use hashbrown::HashMap;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use sqlx::pool::PoolConnectionMetadata;
+use tokio::sync::mpsc;
+
+use crate::{
+    db::{Db, Event, Stats, UpdateFn},
+    errors::{Error, Result},
+    file_list::{FileId, FileList, FileRecord, MergeJobRecord},
+};
+
+/// Keys of the scenarios in the meta store, never affected by the db scenario.
+pub const CHAOS_KEY_PREFIX: &str = "/chaos/";
+
+static SCENARIOS: Lazy<RwLock<HashMap<ChaosTarget, ChaosScenario>>> = Lazy::new(Default::default);
+
+pub fn set_scenario(scenario: ChaosScenario) {
+    SCENARIOS.write().insert(scenario.target, scenario);
+}
+
+pub fn remove_scenario(target: ChaosTarget) {
+    SCENARIOS.write().remove(&target);
+}
+
+pub fn list_scenarios() -> Vec<ChaosScenario> {
+    SCENARIOS.read().values().cloned().collect()
+}
+
+/// Delays or fails the operation as configured by the active scenario of the target.
+pub async fn inject(
+    target: ChaosTarget,
+    operation: &str,
+    key: &str,
+) -> std::result::Result<(), String> {
+    if !get_config().common.chaos_enabled {
+        return Ok(());
+    }
+    let Some(scenario) = SCENARIOS
+        .read()
+        .get(&target)
+        .filter(|s| s.applies(operation, key, now_micros()))
+        .cloned()
+    else {
+        return Ok(());
+    };
+    if scenario.latency_ms > 0 {
+        tokio::time::sleep(Duration::from_millis(scenario.latency_ms)).await;
+    }
+    if scenario.error_rate > 0.0 && roll() < scenario.error_rate {
+        log::warn!("[CHAOS] injected {target} {operation} failure on {key}");
+        return Err(format!("chaos: injected {target} {operation} failure"));
+    }
+    Ok(())
+}
+
+/// Pool hook injecting the faults of the orm scenario when a connection is acquired. A failed
+/// acquire closes the pooled connection, so the pool has to open a new one.
+pub fn before_acquire<C>(
+    _conn: &mut C,
+    _meta: PoolConnectionMetadata,
+) -> BoxFuture<'_, std::result::Result<bool, sqlx::Error>> {
+    Box::pin(async move {
+        inject(ChaosTarget::Orm, "acquire", "")
+            .await
+            .map_err(sqlx::Error::Protocol)?;
+        Ok(true)
+    })
+}
+
+/// Returns a random number between 0 and 1.
+fn roll() -> f64 {
+    get_rand_u128().map_or(1.0, |n| n as u64 as f64 / u64::MAX as f64)
+}
+
+/// Meta store wrapper injecting the faults of the db scenario.
+pub struct ChaosDb(pub Box<dyn Db>);
+
+impl ChaosDb {
+    async fn inject(&self, operation: &str, key: &str) -> Result<()> {
+        if key.starts_with(CHAOS_KEY_PREFIX) {
+            return Ok(());
+        }
+        inject(ChaosTarget::Db, operation, key)
+            .await
+            .map_err(Error::Message)
+    }
+}
+
+#[async_trait]
+impl Db for ChaosDb {
+    async fn create_table(&self) -> Result<()> {
+        self.0.create_table().await
+    }
+
+    async fn stats(&self) -> Result<Stats> {
+        self.0.stats().await
+    }
+
+    async fn get(&self, key: &str) -> Result<Bytes> {
+        self.inject("get", key).await?;
+        self.0.get(key).await
+    }
+
+    async fn put(
+        &self,
+        key: &str,
+        value: Bytes,
+        need_watch: bool,
+        start_dt: Option<i64>,
+    ) -> Result<()> {
+        self.inject("put", key).await?;
+        self.0.put(key, value, need_watch, start_dt).await
+    }
+
+    async fn get_for_update(
+        &self,
+        key: &str,
+        need_watch: bool,
+        start_dt: Option<i64>,
+        update_fn: Box<UpdateFn>,
+    ) -> Result<()> {
+        self.inject("put", key).await?;
+        self.0
+            .get_for_update(key, need_watch, start_dt, update_fn)
+            .await
+    }
+
+    async fn delete(
+        &self,
+        key: &str,
+        with_prefix: bool,
+        need_watch: bool,
+        start_dt: Option<i64>,
+    ) -> Result<()> {
+        self.inject("delete", key).await?;
+        self.0.delete(key, with_prefix, need_watch, start_dt).await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<HashMap<String, Bytes>> {
+        self.inject("list", prefix).await?;
+        self.0.list(prefix).await
+    }
+
+    async fn list_keys(&self, prefix: &str) -> Result<Vec<String>> {
+        self.inject("list", prefix).await?;
+        self.0.list_keys(prefix).await
+    }
+
+    async fn list_values(&self, prefix: &str) -> Result<Vec<Bytes>> {
+        self.inject("list", prefix).await?;
+        self.0.list_values(prefix).await
+    }
+
+    async fn list_values_by_start_dt(
+        &self,
+        prefix: &str,
+        start_dt: Option<(i64, i64)>,
+    ) -> Result<Vec<(i64, Bytes)>> {
+        self.inject("list", prefix).await?;
+        self.0.list_values_by_start_dt(prefix, start_dt).await
+    }
+
+    async fn count(&self, prefix: &str) -> Result<i64> {
+        self.inject("list", prefix).await?;
+        self.0.count(prefix).await
+    }
+
+    async fn watch(&self, prefix: &str) -> Result<Arc<mpsc::Receiver<Event>>> {
+        self.0.watch(prefix).await
+    }
+
+    async fn close(&self) -> Result<()> {
+        self.0.close().await
+    }
+
+    async fn add_start_dt_column(&self) -> Result<()> {
+        self.0.add_start_dt_column().await
+    }
+}
+
+/// File list wrapper injecting the faults of the file_list scenario, the keys are the data file
+/// paths or the `files/{org_id}/{stream_type}/{stream_name}` prefix of the queried stream.
+pub struct ChaosFileList(pub Box<dyn FileList>);
+
+fn stream_key(org_id: &str, stream_type: StreamType, stream_name: &str) -> String {
+    format!("files/{org_id}/{stream_type}/{stream_name}")
+}
+
+fn files_key(files: &[FileKey]) -> &str {
+    files.first().map_or("", |f| f.key.as_str())
+}
+
+impl ChaosFileList {
+    async fn inject(&self, operation: &str, key: &str) -> Result<()> {
+        inject(ChaosTarget::FileList, operation, key)
+            .await
+            .map_err(Error::Message)
+    }
+}
+
+#[async_trait]
+impl FileList for ChaosFileList {
+    async fn create_table(&self) -> Result<()> {
+        self.0.create_table().await
+    }
+
+    async fn create_table_index(&self) -> Result<()> {
+        self.0.create_table_index().await
+    }
+
+    async fn add(&self, account: &str, file: &str, meta: &FileMeta) -> Result<i64> {
+        self.inject("put", file).await?;
+        self.0.add(account, file, meta).await
+    }
+
+    async fn add_history(&self, account: &str, file: &str, meta: &FileMeta) -> Result<i64> {
+        self.inject("put", file).await?;
+        self.0.add_history(account, file, meta).await
+    }
+
+    async fn remove(&self, file: &str) -> Result<()> {
+        self.inject("delete", file).await?;
+        self.0.remove(file).await
+    }
+
+    async fn batch_add(&self, files: &[FileKey]) -> Result<()> {
+        self.inject("put", files_key(files)).await?;
+        self.0.batch_add(files).await
+    }
+
+    async fn batch_add_with_id(&self, files: &[FileKey]) -> Result<()> {
+        self.inject("put", files_key(files)).await?;
+        self.0.batch_add_with_id(files).await
+    }
+
+    async fn batch_add_history(&self, files: &[FileKey]) -> Result<()> {
+        self.inject("put", files_key(files)).await?;
+        self.0.batch_add_history(files).await
+    }
+
+    async fn update_dump_records(&self, dump_file: &FileKey, dumped_ids: &[i64]) -> Result<()> {
+        self.inject("put", &dump_file.key).await?;
+        self.0.update_dump_records(dump_file, dumped_ids).await
+    }
+
+    async fn batch_process(&self, files: &[FileKey]) -> Result<()> {
+        self.inject("put", files_key(files)).await?;
+        self.0.batch_process(files).await
+    }
+
+    async fn batch_add_deleted(
+        &self,
+        org_id: &str,
+        created_at: i64,
+        files: &[FileListDeleted],
+    ) -> Result<()> {
+        self.inject("put", &format!("files/{org_id}")).await?;
+        self.0.batch_add_deleted(org_id, created_at, files).await
+    }
+
+    async fn batch_remove_deleted(&self, files: &[FileKey]) -> Result<()> {
+        self.inject("delete", files_key(files)).await?;
+        self.0.batch_remove_deleted(files).await
+    }
+
+    async fn get(&self, file: &str) -> Result<FileMeta> {
+        self.inject("get", file).await?;
+        self.0.get(file).await
+    }
+
+    async fn contains(&self, file: &str) -> Result<bool> {
+        self.inject("get", file).await?;
+        self.0.contains(file).await
+    }
+
+    async fn update_flattened(&self, file: &str, flattened: bool) -> Result<()> {
+        self.inject("put", file).await?;
+        self.0.update_flattened(file, flattened).await
+    }
+
+    async fn update_compressed_size(&self, file: &str, size: i64) -> Result<()> {
+        self.inject("put", file).await?;
+        self.0.update_compressed_size(file, size).await
+    }
+
+    async fn list(&self) -> Result<Vec<FileKey>> {
+        self.inject("list", "").await?;
+        self.0.list().await
+    }
+
+    async fn query(
+        &self,
+        org_id: &str,
+        stream_type: StreamType,
+        stream_name: &str,
+        time_level: PartitionTimeLevel,
+        time_range: Option<(i64, i64)>,
+        flattened: Option<bool>,
+    ) -> Result<Vec<FileKey>> {
+        self.inject("list", &stream_key(org_id, stream_type, stream_name))
+            .await?;
+        self.0
+            .query(
+                org_id,
+                stream_type,
+                stream_name,
+                time_level,
+                time_range,
+                flattened,
+            )
+            .await
+    }
+
+    async fn query_for_merge(
+        &self,
+        org_id: &str,
+        stream_type: StreamType,
+        stream_name: &str,
+        date_range: Option<(String, String)>,
+    ) -> Result<Vec<FileKey>> {
+        self.inject("list", &stream_key(org_id, stream_type, stream_name))
+            .await?;
+        self.0
+            .query_for_merge(org_id, stream_type, stream_name, date_range)
+            .await
+    }
+
+    async fn query_by_ids(&self, ids: &[i64]) -> Result<Vec<FileKey>> {
+        self.inject("list", "").await?;
+        self.0.query_by_ids(ids).await
+    }
+
+    async fn query_ids(
+        &self,
+        org_id: &str,
+        stream_type: StreamType,
+        stream_name: &str,
+        time_range: Option<(i64, i64)>,
+    ) -> Result<Vec<FileId>> {
+        self.inject("list", &stream_key(org_id, stream_type, stream_name))
+            .await?;
+        self.0
+            .query_ids(org_id, stream_type, stream_name, time_range)
+            .await
+    }
+
+    async fn query_ids_by_files(&self, files: &[FileKey]) -> Result<stdHashMap<String, i64>> {
+        self.inject("list", files_key(files)).await?;
+        self.0.query_ids_by_files(files).await
+    }
+
+    async fn query_old_data_hours(
+        &self,
+        org_id: &str,
+        stream_type: StreamType,
+        stream_name: &str,
+        time_range: Option<(i64, i64)>,
+    ) -> Result<Vec<String>> {
+        self.inject("list", &stream_key(org_id, stream_type, stream_name))
+            .await?;
+        self.0
+            .query_old_data_hours(org_id, stream_type, stream_name, time_range)
+            .await
+    }
+
+    async fn query_deleted(
+        &self,
+        org_id: &str,
+        time_max: i64,
+        limit: i64,
+    ) -> Result<Vec<FileListDeleted>> {
+        self.inject("list", &format!("files/{org_id}")).await?;
+        self.0.query_deleted(org_id, time_max, limit).await
+    }
+
+    async fn list_deleted(&self) -> Result<Vec<FileListDeleted>> {
+        self.inject("list", "").await?;
+        self.0.list_deleted().await
+    }
+
+    async fn get_min_ts(
+        &self,
+        org_id: &str,
+        stream_type: StreamType,
+        stream_name: &str,
+    ) -> Result<i64> {
+        self.inject("get", &stream_key(org_id, stream_type, stream_name))
+            .await?;
+        self.0.get_min_ts(org_id, stream_type, stream_name).await
+    }
+
+    async fn get_max_pk_value(&self) -> Result<i64> {
+        self.inject("get", "").await?;
+        self.0.get_max_pk_value().await
+    }
+
+    async fn get_min_pk_value(&self) -> Result<i64> {
+        self.inject("get", "").await?;
+        self.0.get_min_pk_value().await
+    }
+
+    async fn clean_by_min_pk_value(&self, val: i64) -> Result<()> {
+        self.inject("delete", "").await?;
+        self.0.clean_by_min_pk_value(val).await
+    }
+
+    async fn stats(
+        &self,
+        org_id: &str,
+        stream_type: Option<StreamType>,
+        stream_name: Option<&str>,
+        pk_value: Option<(i64, i64)>,
+        deleted: bool,
+    ) -> Result<Vec<(String, StreamStats)>> {
+        self.inject("list", &format!("files/{org_id}")).await?;
+        self.0
+            .stats(org_id, stream_type, stream_name, pk_value, deleted)
+            .await
+    }
+
+    async fn get_stream_stats(
+        &self,
+        org_id: &str,
+        stream_type: Option<StreamType>,
+        stream_name: Option<&str>,
+    ) -> Result<Vec<(String, StreamStats)>> {
+        self.inject("list", &format!("files/{org_id}")).await?;
+        self.0
+            .get_stream_stats(org_id, stream_type, stream_name)
+            .await
+    }
+
+    async fn del_stream_stats(
+        &self,
+        org_id: &str,
+        stream_type: StreamType,
+        stream_name: &str,
+    ) -> Result<()> {
+        self.inject("delete", &stream_key(org_id, stream_type, stream_name))
+            .await?;
+        self.0
+            .del_stream_stats(org_id, stream_type, stream_name)
+            .await
+    }
+
+    async fn set_stream_stats(
+        &self,
+        org_id: &str,
+        streams: &[(String, StreamStats)],
+        pk_value: Option<(i64, i64)>,
+    ) -> Result<()> {
+        self.inject("put", &format!("files/{org_id}")).await?;
+        self.0.set_stream_stats(org_id, streams, pk_value).await
+    }
+
+    async fn reset_stream_stats(&self) -> Result<()> {
+        self.inject("put", "").await?;
+        self.0.reset_stream_stats().await
+    }
+
+    async fn reset_stream_stats_min_ts(
+        &self,
+        org_id: &str,
+        stream: &str,
+        min_ts: i64,
+    ) -> Result<()> {
+        self.inject("put", &format!("files/{org_id}/{stream}"))
+            .await?;
+        self.0
+            .reset_stream_stats_min_ts(org_id, stream, min_ts)
+            .await
+    }
+
+    async fn len(&self) -> usize {
+        self.0.len().await
+    }
+
+    async fn is_empty(&self) -> bool {
+        self.0.is_empty().await
+    }
+
+    async fn clear(&self) -> Result<()> {
+        self.0.clear().await
+    }
+
+    async fn add_job(
+        &self,
+        org_id: &str,
+        stream_type: StreamType,
+        stream: &str,
+        offset: i64,
+    ) -> Result<i64> {
+        self.inject("put", &stream_key(org_id, stream_type, stream))
+            .await?;
+        self.0.add_job(org_id, stream_type, stream, offset).await
+    }
+
+    async fn get_pending_jobs(&self, node: &str, limit: i64) -> Result<Vec<MergeJobRecord>> {
+        self.inject("list", "").await?;
+        self.0.get_pending_jobs(node, limit).await
+    }
+
+    async fn get_pending_jobs_count(&self) -> Result<stdHashMap<String, stdHashMap<String, i64>>> {
+        self.inject("list", "").await?;
+        self.0.get_pending_jobs_count().await
+    }
+
+    async fn set_job_pending(&self, ids: &[i64]) -> Result<()> {
+        self.inject("put", "").await?;
+        self.0.set_job_pending(ids).await
+    }
+
+    async fn set_job_done(&self, ids: &[i64]) -> Result<()> {
+        self.inject("put", "").await?;
+        self.0.set_job_done(ids).await
+    }
+
+    async fn update_running_jobs(&self, ids: &[i64]) -> Result<()> {
+        self.inject("put", "").await?;
+        self.0.update_running_jobs(ids).await
+    }
+
+    async fn check_running_jobs(&self, before_date: i64) -> Result<()> {
+        self.inject("put", "").await?;
+        self.0.check_running_jobs(before_date).await
+    }
+
+    async fn clean_done_jobs(&self, before_date: i64) -> Result<()> {
+        self.inject("delete", "").await?;
+        self.0.clean_done_jobs(before_date).await
+    }
+
+    async fn get_entries_in_range(
+        &self,
+        org: &str,
+        stream: Option<&str>,
+        start_time: i64,
+        end_time: i64,
+        min_id: Option<i64>,
+    ) -> Result<Vec<FileRecord>> {
+        self.inject("list", &format!("files/{org}")).await?;
+        self.0
+            .get_entries_in_range(org, stream, start_time, end_time, min_id)
+            .await
+    }
+
+    async fn get_pending_dump_jobs(&self) -> Result<Vec<(i64, String, String, i64)>> {
+        self.inject("list", "").await?;
+        self.0.get_pending_dump_jobs().await
+    }
+
+    async fn set_job_dumped_status(&self, id: i64, dumped: bool) -> Result<()> {
+        self.inject("put", "").await?;
+        self.0.set_job_dumped_status(id, dumped).await
+    }
+}
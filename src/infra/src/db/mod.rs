@@ -41,6 +41,9 @@ static SUPER_CLUSTER: OnceCell<Box<dyn Db>> = OnceCell::const_new();
 pub const SQLITE_STORE: &str = "sqlite";
 
 pub static ORM_CLIENT: OnceCell<DatabaseConnection> = OnceCell::const_new();
+/// Client of the queries which only read. For SQLite it uses the read pool, so the reads don't
+/// queue behind the single writer connection of [`ORM_CLIENT`].
+pub static ORM_CLIENT_RO: OnceCell<DatabaseConnection> = OnceCell::const_new();
 pub static ORM_CLIENT_DDL: OnceCell<DatabaseConnection> = OnceCell::const_new();
 
 pub async fn connect_to_orm() -> DatabaseConnection {
@@ -60,6 +63,13 @@ pub async fn connect_to_orm() -> DatabaseConnection {
     }
 }
 
+pub async fn connect_to_orm_ro() -> DatabaseConnection {
+    match get_config().common.meta_store.as_str().into() {
+        MetaStore::MySQL | MetaStore::PostgreSQL => connect_to_orm().await,
+        _ => SqlxSqliteConnector::from_sqlx_sqlite_pool(sqlite::CLIENT_RO.clone()),
+    }
+}
+
 pub async fn connect_to_orm_ddl() -> DatabaseConnection {
     match get_config().common.meta_store.as_str().into() {
         MetaStore::MySQL => {
@@ -110,13 +120,18 @@ async fn default() -> Box<dyn Db> {
         panic!("cluster mode is not supported for ZO_META_STORE=sqlite");
     }
 
-    match cfg.common.meta_store.as_str().into() {
+    let db: Box<dyn Db> = match cfg.common.meta_store.as_str().into() {
         MetaStore::Sqlite => Box::<sqlite::SqliteDb>::default(),
         MetaStore::Etcd => Box::<etcd::Etcd>::default(),
         MetaStore::Nats => Box::<nats::NatsDb>::default(),
         MetaStore::MySQL => Box::<mysql::MysqlDb>::default(),
         MetaStore::PostgreSQL => Box::<postgres::PostgresDb>::default(),
+    };
+    if cfg.common.chaos_enabled {
+        log::warn!("[CHAOS] fault injection is enabled on the meta store");
+        return Box::new(crate::chaos::ChaosDb(db));
     }
+    db
 }
 
 async fn init_local_cache() -> Box<dyn Db> {
@@ -393,4 +408,59 @@ mod tests {
         assert_eq!(db.list_keys("/foo/del/").await.unwrap().len(), 3);
         assert_eq!(db.list_values("/foo/del/").await.unwrap().len(), 3);
     }
+
+    #[tokio::test]
+    async fn test_concurrent_reads_and_writes() {
+        use std::time::Duration;
+
+        use sea_orm::{ConnectionTrait, Statement, TransactionTrait};
+
+        create_table().await.unwrap();
+        let db = get_db().await;
+        let hello = Bytes::from("hello");
+        db.put("/foo/concurrent/seed", hello.clone(), false, None)
+            .await
+            .unwrap();
+
+        // the reads don't wait for an open write transaction
+        let rw = ORM_CLIENT.get_or_init(connect_to_orm).await;
+        let txn = rw.begin().await.unwrap();
+        txn.execute_unprepared("UPDATE meta SET value = value WHERE module = 'foo'")
+            .await
+            .unwrap();
+        let ro = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+        let count = tokio::time::timeout(
+            Duration::from_secs(5),
+            ro.query_one(Statement::from_string(
+                ro.get_database_backend(),
+                "SELECT COUNT(*) AS num FROM meta",
+            )),
+        )
+        .await
+        .expect("read waited for the writer")
+        .unwrap();
+        assert!(count.is_some());
+        let value = tokio::time::timeout(Duration::from_secs(5), db.get("/foo/concurrent/seed"))
+            .await
+            .expect("read waited for the writer")
+            .unwrap();
+        assert_eq!(value, hello);
+        txn.commit().await.unwrap();
+
+        // the writers are queued, none of them fails with `database is locked`
+        let mut tasks = tokio::task::JoinSet::new();
+        for i in 0..32 {
+            let value = hello.clone();
+            tasks.spawn(async move {
+                let db = get_db().await;
+                db.put(&format!("/foo/concurrent/key{i}"), value, false, None)
+                    .await?;
+                db.get(&format!("/foo/concurrent/key{i}")).await
+            });
+        }
+        while let Some(ret) = tasks.join_next().await {
+            assert_eq!(ret.unwrap().unwrap(), hello);
+        }
+        assert_eq!(db.list_keys("/foo/concurrent/").await.unwrap().len(), 33);
+    }
 }
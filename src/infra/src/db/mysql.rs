@@ -57,13 +57,16 @@ fn connect(readonly: bool, ddl: bool) -> Pool<MySql> {
     let idle_timeout = zero_or(cfg.limit.sql_db_connections_idle_timeout, 600);
     let max_lifetime = zero_or(cfg.limit.sql_db_connections_max_lifetime, 1800);
 
-    MySqlPoolOptions::new()
+    let mut pool_opts = MySqlPoolOptions::new()
         .min_connections(cfg.limit.sql_db_connections_min)
         .max_connections(cfg.limit.sql_db_connections_max)
         .acquire_timeout(Duration::from_secs(acquire_timeout))
         .idle_timeout(Some(Duration::from_secs(idle_timeout)))
-        .max_lifetime(Some(Duration::from_secs(max_lifetime)))
-        .connect_lazy_with(db_opts)
+        .max_lifetime(Some(Duration::from_secs(max_lifetime)));
+    if cfg.common.chaos_enabled {
+        pool_opts = pool_opts.before_acquire(crate::chaos::before_acquire);
+    }
+    pool_opts.connect_lazy_with(db_opts)
 }
 
 async fn cache_indices() -> HashSet<DBIndex> {
@@ -35,7 +35,9 @@ use crate::{
     errors::*,
 };
 
+/// Read pool, the reads don't wait for the writer in WAL mode.
 pub static CLIENT_RO: Lazy<Pool<Sqlite>> = Lazy::new(connect_ro);
+/// Write pool, the lock queues the writers.
 pub static CLIENT_RW: Lazy<Arc<Mutex<Pool<Sqlite>>>> =
     Lazy::new(|| Arc::new(Mutex::new(connect_rw())));
 static INDICES: OnceCell<HashSet<DBIndex>> = OnceCell::const_new();
@@ -68,25 +70,34 @@ fn connect_rw() -> Pool<Sqlite> {
         .busy_timeout(Duration::from_secs(acquire_timeout))
         .create_if_missing(true);
 
-    SqlitePoolOptions::new()
+    // SQLite allows a single writer, the writers are queued by the lock of `CLIENT_RW` and the
+    // reads, including the ORM reads of `ORM_CLIENT_RO`, use the read pool and don't wait in
+    // that queue. The pool still needs several connections, as some paths acquire `ORM_CLIENT`
+    // again while holding a connection or a transaction, see `REQUIRED_DB_CONNECTIONS`.
+    let mut pool_opts = SqlitePoolOptions::new()
         .min_connections(cfg.limit.sql_db_connections_min)
         .max_connections(cfg.limit.sql_db_connections_max)
         .acquire_timeout(Duration::from_secs(acquire_timeout))
         .idle_timeout(Some(Duration::from_secs(idle_timeout)))
-        .max_lifetime(Some(Duration::from_secs(max_lifetime)))
-        .connect_lazy_with(db_opts)
+        .max_lifetime(Some(Duration::from_secs(max_lifetime)));
+    if cfg.common.chaos_enabled {
+        pool_opts = pool_opts.before_acquire(crate::chaos::before_acquire);
+    }
+    pool_opts.connect_lazy_with(db_opts)
 }
 
 fn connect_ro() -> Pool<Sqlite> {
     let cfg = config::get_config();
 
+    let acquire_timeout = zero_or(cfg.limit.sql_db_connections_acquire_timeout, 30);
+
     let url = format!("{}{}", cfg.common.data_db_dir, "metadata.sqlite");
     let db_opts = SqliteConnectOptions::from_str(&url)
         .expect("sqlite connect options create failed")
         .journal_mode(SqliteJournalMode::Wal)
         .synchronous(SqliteSynchronous::Normal)
         .locking_mode(SqliteLockingMode::Normal)
-        .busy_timeout(Duration::from_secs(30))
+        .busy_timeout(Duration::from_secs(acquire_timeout))
         // .disable_statement_logging()
         .read_only(true);
 
@@ -97,12 +108,15 @@ fn connect_ro() -> Pool<Sqlite> {
     } else {
         None
     };
-    SqlitePoolOptions::new()
+    let mut pool_opts = SqlitePoolOptions::new()
         .min_connections(cfg.limit.sql_db_connections_min)
         .max_connections(cfg.limit.sql_db_connections_max)
         .max_lifetime(max_lifetime)
-        .acquire_timeout(Duration::from_secs(30))
-        .connect_lazy_with(db_opts)
+        .acquire_timeout(Duration::from_secs(acquire_timeout));
+    if cfg.common.chaos_enabled {
+        pool_opts = pool_opts.before_acquire(crate::chaos::before_acquire);
+    }
+    pool_opts.connect_lazy_with(db_opts)
 }
 
 async fn cache_indices() -> HashSet<DBIndex> {
@@ -844,3 +858,20 @@ pub async fn delete_index(idx_name: &str, table: &str) -> Result<()> {
     log::info!("[SQLITE] index {} deleted successfully", idx_name);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_acquire_in_transaction() {
+        let pool = CLIENT_RW.lock().await.clone();
+        let mut tx = pool.begin().await.unwrap();
+        sqlx::query("SELECT 1").execute(&mut *tx).await.unwrap();
+
+        // the ORM paths acquire another connection while their transaction is open
+        let conn = tokio::time::timeout(Duration::from_secs(5), pool.acquire()).await;
+        assert!(conn.is_ok_and(|c| c.is_ok()));
+        tx.rollback().await.unwrap();
+    }
+}
@@ -17,6 +17,9 @@ use datafusion::{common::SchemaError, error::DataFusionError};
 
 use super::{Error, ErrorCodes};
 
+/// Prefix of the error returned when a PromQL query loads more samples than allowed.
+pub const SAMPLES_LIMIT_EXCEEDED: &str = "query processing would load too many samples";
+
 fn get_key_from_error(err: &str, pos: usize) -> Option<String> {
     for punctuation in ['\'', '"'] {
         let pos_start = err[pos..].find(punctuation);
@@ -55,6 +58,9 @@ impl From<DataFusionError> for Error {
                 None => Error::ErrorCode(ErrorCodes::SearchSQLExecuteError(err)),
             };
         }
+        if err.contains(SAMPLES_LIMIT_EXCEEDED) {
+            return Error::ErrorCode(ErrorCodes::SearchSamplesLimitExceeded(err));
+        }
         if err.contains("parquet not found") {
             log::error!("[Datafusion] Parquet file not found: {}", err);
             return Error::ErrorCode(ErrorCodes::SearchParquetFileNotFound);
@@ -178,7 +178,7 @@ pub enum TemplateError {
     ConvertingId(String),
 }
 
-#[derive(ThisError, Debug)]
+#[derive(ThisError, Clone, Debug)]
 pub enum ErrorCodes {
     ServerInternalError(String),
     SearchSQLNotValid(String),
@@ -193,6 +193,7 @@ pub enum ErrorCodes {
     SearchTimeout(String),
     InvalidParams(String),
     RatelimitExceeded(String),
+    SearchSamplesLimitExceeded(String),
 }
 
 impl From<sea_orm::DbErr> for Error {
@@ -247,6 +248,7 @@ impl ErrorCodes {
             ErrorCodes::SearchTimeout(_) => 20010,
             ErrorCodes::InvalidParams(_) => 20011,
             ErrorCodes::RatelimitExceeded(_) => 20012,
+            ErrorCodes::SearchSamplesLimitExceeded(_) => 20013,
         }
     }
 
@@ -273,6 +275,9 @@ impl ErrorCodes {
             ErrorCodes::SearchTimeout(_) => "Search query timed out".to_string(),
             ErrorCodes::InvalidParams(_) => "Invalid parameters".to_string(),
             ErrorCodes::RatelimitExceeded(_) => "Ratelimit exceeded".to_string(),
+            ErrorCodes::SearchSamplesLimitExceeded(_) => {
+                "Search query loads too many samples".to_string()
+            }
         }
     }
 
@@ -291,6 +296,7 @@ impl ErrorCodes {
             ErrorCodes::SearchTimeout(msg) => msg.to_owned(),
             ErrorCodes::InvalidParams(msg) => msg.to_owned(),
             ErrorCodes::RatelimitExceeded(msg) => msg.to_owned(),
+            ErrorCodes::SearchSamplesLimitExceeded(msg) => msg.to_owned(),
         }
     }
 
@@ -309,6 +315,7 @@ impl ErrorCodes {
             ErrorCodes::SearchTimeout(msg) => msg.to_owned(),
             ErrorCodes::InvalidParams(msg) => msg.to_owned(),
             ErrorCodes::RatelimitExceeded(msg) => msg.to_owned(),
+            ErrorCodes::SearchSamplesLimitExceeded(msg) => msg.to_owned(),
         }
     }
 
@@ -358,6 +365,7 @@ impl ErrorCodes {
             20008 => Ok(ErrorCodes::SearchSQLExecuteError(message)),
             20009 => Ok(ErrorCodes::SearchCancelQuery(message)),
             20010 => Ok(ErrorCodes::SearchTimeout(message)),
+            20013 => Ok(ErrorCodes::SearchSamplesLimitExceeded(message)),
             _ => Ok(ErrorCodes::ServerInternalError(json.to_string())),
         }
     }
@@ -388,4 +396,14 @@ mod tests {
             &err.to_string()
         );
     }
+
+    #[test]
+    fn test_samples_limit_error_code_round_trip() {
+        let code = ErrorCodes::SearchSamplesLimitExceeded("limit: 100".to_string());
+        assert_eq!(code.get_code(), 20013);
+        match ErrorCodes::from_json(&code.to_json()).unwrap() {
+            ErrorCodes::SearchSamplesLimitExceeded(msg) => assert_eq!(msg, "limit: 100"),
+            other => panic!("unexpected error code: {other:?}"),
+        }
+    }
 }
@@ -35,13 +35,19 @@ static CLIENT: Lazy<Box<dyn FileList>> = Lazy::new(connect_default);
 pub static LOCAL_CACHE: Lazy<Box<dyn FileList>> = Lazy::new(connect_local_cache);
 
 pub fn connect_default() -> Box<dyn FileList> {
-    match config::get_config().common.meta_store.as_str().into() {
+    let cfg = config::get_config();
+    let client: Box<dyn FileList> = match cfg.common.meta_store.as_str().into() {
         MetaStore::Sqlite => Box::<sqlite::SqliteFileList>::default(),
         MetaStore::Etcd => Box::<sqlite::SqliteFileList>::default(),
         MetaStore::Nats => Box::<sqlite::SqliteFileList>::default(),
         MetaStore::MySQL => Box::<mysql::MysqlFileList>::default(),
         MetaStore::PostgreSQL => Box::<postgres::PostgresFileList>::default(),
+    };
+    if cfg.common.chaos_enabled {
+        log::warn!("[CHAOS] fault injection is enabled on the file list");
+        return Box::new(crate::chaos::ChaosFileList(client));
     }
+    client
 }
 
 pub fn connect_local_cache() -> Box<dyn FileList> {
@@ -204,7 +210,9 @@ pub async fn add_history(account: &str, file: &str, meta: &FileMeta) -> Result<i
 
 #[inline]
 pub async fn remove(file: &str) -> Result<()> {
-    CLIENT.remove(file).await
+    CLIENT.remove(file).await?;
+    crate::storage::replica::record_removed([file]);
+    Ok(())
 }
 
 #[inline]
@@ -219,7 +227,11 @@ pub async fn batch_add_history(files: &[FileKey]) -> Result<()> {
 
 #[inline]
 pub async fn batch_process(files: &[FileKey]) -> Result<()> {
-    CLIENT.batch_process(files).await
+    CLIENT.batch_process(files).await?;
+    crate::storage::replica::record_removed(
+        files.iter().filter(|f| f.deleted).map(|f| f.key.as_str()),
+    );
+    Ok(())
 }
 
 #[inline]
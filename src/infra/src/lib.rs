@@ -16,6 +16,7 @@
 #![feature(btree_extract_if)]
 
 pub mod cache;
+pub mod chaos;
 pub mod cluster_coordinator;
 pub mod db;
 pub mod dist_lock;
@@ -63,6 +63,7 @@ CREATE TABLE IF NOT EXISTS pipeline
     derived_stream  TEXT,
     nodes           TEXT,
     edges           TEXT,
+    created_by      VARCHAR(256),
     created_at      TIMESTAMP DEFAULT CURRENT_TIMESTAMP
 );
             "#,
@@ -70,6 +71,16 @@ CREATE TABLE IF NOT EXISTS pipeline
         .execute(&pool)
         .await?;
 
+        // tables created before the creator was recorded
+        if let Err(e) = sqlx::query("ALTER TABLE pipeline ADD COLUMN created_by VARCHAR(256);")
+            .execute(&pool)
+            .await
+        {
+            if !e.to_string().contains("Duplicate column name") {
+                return Err(e.into());
+            }
+        }
+
         Ok(())
     }
 
@@ -118,8 +129,8 @@ CREATE TABLE IF NOT EXISTS pipeline
                 );
                 sqlx::query(
                     r#"
-INSERT IGNORE INTO pipeline (id, version, enabled, name, description, org, source_type, stream_org, stream_name, stream_type, nodes, edges)
-    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?);
+INSERT IGNORE INTO pipeline (id, version, enabled, name, description, org, source_type, stream_org, stream_name, stream_type, nodes, edges, created_by)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?);
                     "#,
                 )
                 .bind(&pipeline.id)
@@ -134,6 +145,7 @@ INSERT IGNORE INTO pipeline (id, version, enabled, name, description, org, sourc
                 .bind(stream_type)
                 .bind(json::to_string(&pipeline.nodes).expect("Serializing pipeline nodes error"))
                 .bind(json::to_string(&pipeline.edges).expect("Serializing pipeline edges error"))
+                .bind(&pipeline.created_by)
                 .execute(&mut *tx)
                 .await
             }
@@ -145,8 +157,8 @@ INSERT IGNORE INTO pipeline (id, version, enabled, name, description, org, sourc
                 );
                 sqlx::query(
                     r#"
-INSERT IGNORE INTO pipeline (id, version, enabled, name, description, org, source_type, derived_stream, nodes, edges)
-    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?);
+INSERT IGNORE INTO pipeline (id, version, enabled, name, description, org, source_type, derived_stream, nodes, edges, created_by)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?);
                     "#,
                 )
                 .bind(&pipeline.id)
@@ -159,6 +171,7 @@ INSERT IGNORE INTO pipeline (id, version, enabled, name, description, org, sourc
                 .bind(derived_stream_str)
                 .bind(json::to_string(&pipeline.nodes).expect("Serializing pipeline nodes error"))
                 .bind(json::to_string(&pipeline.edges).expect("Serializing pipeline edges error"))
+                .bind(&pipeline.created_by)
                 .execute(&mut *tx)
                 .await
             }
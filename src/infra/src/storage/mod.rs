@@ -17,7 +17,11 @@ use std::{fmt::Debug, ops::Range, sync::Arc};
 
 use async_trait::async_trait;
 use bytes::{Bytes, buf::Buf};
-use config::{get_config, is_local_disk_storage, meta::stream::FileMeta, metrics};
+use config::{
+    get_config, is_local_disk_storage,
+    meta::{chaos::ChaosTarget, stream::FileMeta},
+    metrics,
+};
 use datafusion::parquet::{data_type::AsBytes, file::metadata::ParquetMetaData};
 use futures::{StreamExt, TryStreamExt, stream::BoxStream};
 use hashbrown::HashMap;
@@ -31,6 +35,7 @@ use parquet::file::metadata::ParquetMetaDataReader;
 pub mod accounts;
 mod local;
 mod remote;
+pub mod replica;
 pub mod wal;
 
 pub use remote::test_config as test_remote_config;
@@ -100,6 +105,7 @@ pub trait ObjectStoreExt: std::fmt::Display + Send + Sync + Debug + 'static {
 }
 
 pub async fn list(account: &str, prefix: &str) -> Result<Vec<String>> {
+    inject_fault("list", prefix).await?;
     let files = MULTI_ACCOUNTS
         .list(account, Some(&prefix.into()))
         .map_ok(|meta| meta.location.to_string())
@@ -114,20 +120,24 @@ pub fn get_account(file: &str) -> Option<String> {
 }
 
 pub async fn get(account: &str, file: &str) -> Result<GetResult> {
+    inject_fault("get", file).await?;
     MULTI_ACCOUNTS.get(account, &file.into()).await
 }
 
 pub async fn get_opts(account: &str, file: &str, options: GetOptions) -> Result<GetResult> {
+    inject_fault("get", file).await?;
     MULTI_ACCOUNTS
         .get_opts(account, &file.into(), options)
         .await
 }
 
 pub async fn get_range(account: &str, file: &str, range: Range<usize>) -> Result<bytes::Bytes> {
+    inject_fault("get", file).await?;
     MULTI_ACCOUNTS.get_range(account, &file.into(), range).await
 }
 
 pub async fn head(account: &str, file: &str) -> Result<ObjectMeta> {
+    inject_fault("head", file).await?;
     MULTI_ACCOUNTS.head(account, &file.into()).await
 }
 
@@ -150,6 +160,7 @@ pub async fn put(account: &str, file: &str, data: bytes::Bytes) -> Result<()> {
     if multi_part_upload_size > 0 && multi_part_upload_size < bytes_size_in_mb(&data) as usize {
         put_multipart(account, file, data).await?;
     } else {
+        inject_fault("put", file).await?;
         MULTI_ACCOUNTS
             .put(account, &file.into(), data.into())
             .await?;
@@ -158,6 +169,7 @@ pub async fn put(account: &str, file: &str, data: bytes::Bytes) -> Result<()> {
 }
 
 pub async fn put_multipart(account: &str, file: &str, data: bytes::Bytes) -> Result<()> {
+    inject_fault("put", file).await?;
     let path = Path::from(file);
     let upload = MULTI_ACCOUNTS.put_multipart(account, &path).await?;
     let mut write = WriteMultipart::new(upload);
@@ -173,6 +185,7 @@ pub async fn del(files: Vec<(&str, &str)>) -> Result<()> {
         return Ok(());
     }
 
+    inject_fault("delete", files[0].1).await?;
     let start = std::time::Instant::now();
     let columns = files[0].1.split('/').collect::<Vec<&str>>();
 
@@ -241,6 +254,16 @@ pub async fn del(files: Vec<(&str, &str)>) -> Result<()> {
     Ok(())
 }
 
+/// Applies the storage chaos scenario, a no-op unless fault injection is enabled.
+async fn inject_fault(operation: &str, file: &str) -> Result<()> {
+    crate::chaos::inject(ChaosTarget::Storage, operation, file)
+        .await
+        .map_err(|e| object_store::Error::Generic {
+            store: "chaos",
+            source: e.into(),
+        })
+}
+
 pub async fn get_file_meta(account: &str, file: &str) -> Result<FileMeta, anyhow::Error> {
     let mut file_meta = FileMeta::default();
     let (file_size, parquet_meta) = get_parquet_metadata(account, file).await?;
@@ -355,7 +355,7 @@ fn init_gcp_config(
     builder.build()
 }
 
-fn init_client(config: StorageConfig) -> Box<dyn object_store::ObjectStore> {
+pub(crate) fn init_client(config: StorageConfig) -> Box<dyn object_store::ObjectStore> {
     if get_config().common.print_key_config {
         log::info!("s3 init config: {:?}", config);
     }
@@ -0,0 +1,248 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Asynchronous replication of object storage writes to a secondary bucket.
+//!
+//! The replication job copies every parquet file committed to the file list into the
+//! secondary storage and records the file list changes as JSON manifests next to them, so a
+//! standby deployment can rebuild its file list from the secondary bucket on failover.
+
+use bytes::Bytes;
+use config::{
+    get_config,
+    meta::stream::{FileMeta, StreamType},
+    utils::json,
+};
+use futures::TryStreamExt;
+use hashbrown::HashSet;
+use object_store::{ObjectStore, Result, WriteMultipart, path::Path};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use super::remote::{StorageConfig, init_client};
+
+pub const MANIFEST_PREFIX: &str = "_replication/manifests/";
+
+static CLIENT: Lazy<Box<dyn ObjectStore>> = Lazy::new(|| {
+    let cfg = &get_config().replication;
+    init_client(StorageConfig {
+        name: "replication".to_string(),
+        provider: cfg.provider.clone(),
+        server_url: cfg.server_url.clone(),
+        region_name: cfg.region_name.clone(),
+        access_key: cfg.access_key.clone(),
+        secret_key: cfg.secret_key.clone(),
+        bucket_name: cfg.bucket_name.clone(),
+        bucket_prefix: cfg.bucket_prefix.clone(),
+    })
+});
+
+// files removed from the file list on this node which are not written to a manifest yet
+static REMOVED_FILES: Lazy<Mutex<Vec<String>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ManifestFile {
+    pub account: String,
+    pub key: String,
+    pub meta: FileMeta,
+}
+
+/// A batch of file list changes mirrored to the secondary storage.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    pub created_at: i64,
+    /// The file list primary key covered by this manifest, 0 if it only has removals
+    #[serde(default)]
+    pub offset: i64,
+    #[serde(default)]
+    pub added: Vec<ManifestFile>,
+    #[serde(default)]
+    pub removed: Vec<String>,
+}
+
+impl Manifest {
+    pub fn new(offset: i64, added: Vec<ManifestFile>, removed: Vec<String>) -> Self {
+        Self {
+            created_at: config::utils::time::now_micros(),
+            offset,
+            added,
+            removed,
+        }
+    }
+
+    pub fn path(&self) -> String {
+        format!(
+            "{MANIFEST_PREFIX}{:020}_{}.json",
+            self.created_at,
+            config::ider::generate()
+        )
+    }
+}
+
+#[inline]
+pub fn is_enabled() -> bool {
+    get_config().replication.enabled
+}
+
+fn format_key(file: &str) -> Path {
+    format!("{}{}", get_config().replication.bucket_prefix, file).into()
+}
+
+/// Remember the files removed from the file list so that the next manifest written by this
+/// node drops them from the replica.
+pub fn record_removed<'a>(keys: impl IntoIterator<Item = &'a str>) {
+    if !is_enabled() {
+        return;
+    }
+    let removed = keys.into_iter().map(|k| k.to_string()).collect::<Vec<_>>();
+    if !removed.is_empty() {
+        REMOVED_FILES.lock().extend(removed);
+    }
+}
+
+pub fn take_removed() -> Vec<String> {
+    std::mem::take(&mut *REMOVED_FILES.lock())
+}
+
+/// Put back removals which failed to be written, they are retried with the next manifest.
+pub fn restore_removed(files: Vec<String>) {
+    REMOVED_FILES.lock().extend(files);
+}
+
+pub async fn put(file: &str, data: Bytes) -> Result<()> {
+    let multi_part_upload_size = get_config().s3.multi_part_upload_size;
+    if multi_part_upload_size > 0
+        && multi_part_upload_size < super::bytes_size_in_mb(&data) as usize
+    {
+        let upload = CLIENT.put_multipart(&format_key(file)).await?;
+        let mut write = WriteMultipart::new(upload);
+        write.write(&data);
+        write.finish().await?;
+    } else {
+        CLIENT.put(&format_key(file), data.into()).await?;
+    }
+    Ok(())
+}
+
+pub async fn get_bytes(file: &str) -> Result<Bytes> {
+    CLIENT.get(&format_key(file)).await?.bytes().await
+}
+
+/// List the files of the replica under the prefix, the returned names have no bucket prefix.
+pub async fn list(prefix: &str) -> Result<Vec<String>> {
+    let bucket_prefix = &get_config().replication.bucket_prefix;
+    CLIENT
+        .list(Some(&format_key(prefix)))
+        .map_ok(|meta| {
+            let location = meta.location.to_string();
+            location
+                .strip_prefix(bucket_prefix.as_str())
+                .unwrap_or(&location)
+                .to_string()
+        })
+        .try_collect::<Vec<String>>()
+        .await
+}
+
+/// Copy a file from the primary storage to the replica.
+///
+/// Returns `false` if the file is already gone from the primary storage, which happens when it
+/// was merged by the compactor before it could be replicated.
+pub async fn copy(account: &str, file: &str) -> Result<bool> {
+    let data = match super::get_bytes(account, file).await {
+        Ok(data) => data,
+        Err(object_store::Error::NotFound { .. }) => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    put(file, data).await?;
+    Ok(true)
+}
+
+pub async fn put_manifest(manifest: &Manifest) -> Result<()> {
+    let data = json::to_vec(manifest).map_err(|e| object_store::Error::Generic {
+        store: "replication",
+        source: Box::new(e),
+    })?;
+    put(&manifest.path(), data.into()).await
+}
+
+/// Rebuild the file list from the manifests stored in the replica.
+///
+/// File keys are never reused, so a removal drops the file no matter in which order its
+/// manifests were written. Dumped file lists are skipped because the files they contain were
+/// replicated on their own.
+pub fn replay(manifests: Vec<Manifest>) -> Vec<ManifestFile> {
+    let removed = manifests
+        .iter()
+        .flat_map(|m| m.removed.iter().map(|k| k.as_str()))
+        .collect::<HashSet<_>>();
+    let dump_stream = StreamType::Filelist.to_string();
+    let mut seen = HashSet::new();
+    let mut files = Vec::new();
+    for manifest in manifests.iter() {
+        for file in manifest.added.iter() {
+            if removed.contains(file.key.as_str())
+                || file.key.split('/').nth(2) == Some(dump_stream.as_str())
+                || !seen.insert(file.key.as_str())
+            {
+                continue;
+            }
+            files.push(file.clone());
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(key: &str) -> ManifestFile {
+        ManifestFile {
+            account: String::new(),
+            key: key.to_string(),
+            meta: FileMeta::default(),
+        }
+    }
+
+    #[test]
+    fn test_replay() {
+        let manifests = vec![
+            Manifest {
+                created_at: 1,
+                offset: 0,
+                added: vec![],
+                removed: vec!["files/org/logs/a/2025/01/01/00/2.parquet".to_string()],
+            },
+            Manifest {
+                created_at: 2,
+                offset: 3,
+                added: vec![
+                    file("files/org/logs/a/2025/01/01/00/1.parquet"),
+                    file("files/org/logs/a/2025/01/01/00/2.parquet"),
+                    file("files/org/file_list/logs_a/2025/01/01/00/3.parquet"),
+                    file("files/org/logs/a/2025/01/01/00/1.parquet"),
+                ],
+                removed: vec![],
+            },
+        ];
+        let files = replay(manifests);
+        assert_eq!(
+            files,
+            vec![file("files/org/logs/a/2025/01/01/00/1.parquet")]
+        );
+    }
+}
@@ -27,7 +27,7 @@ use super::{
     get_lock,
 };
 use crate::{
-    db::{ORM_CLIENT, connect_to_orm},
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
     errors::{self, DbError, Error},
     table::entity::action_scripts::{Column, Entity},
 };
@@ -162,7 +162,7 @@ pub async fn remove(org_id: &str, id: &str) -> Result<(), errors::Error> {
 }
 
 pub async fn get(id: &str, org_id: &str) -> Result<Action, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let record = Entity::find_by_id(id)
         .filter(Column::OrgId.eq(org_id))
         .one(client)
@@ -176,7 +176,7 @@ pub async fn get(id: &str, org_id: &str) -> Result<Action, errors::Error> {
 
 pub async fn list(org_id: &str, limit: Option<i64>) -> Result<Vec<Action>, errors::Error> {
     let limit = limit.unwrap_or(100);
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let res = Entity::find()
         .filter(Column::OrgId.eq(org_id))
         .limit(limit as u64)
@@ -193,7 +193,7 @@ pub async fn list(org_id: &str, limit: Option<i64>) -> Result<Vec<Action>, error
 }
 
 pub async fn contains(id: &str, org_id: &str) -> Result<bool, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let record = Entity::find()
         .filter(Column::Id.eq(id))
         .filter(Column::OrgId.eq(org_id))
@@ -204,7 +204,7 @@ pub async fn contains(id: &str, org_id: &str) -> Result<bool, errors::Error> {
 }
 
 pub async fn len() -> Result<usize, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let len = Entity::find().count(client).await?;
     Ok(len as usize)
 }
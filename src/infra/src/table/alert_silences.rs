@@ -0,0 +1,201 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::{
+    meta::alerts::silences::{Silence, SilencedNotification},
+    utils::json,
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
+
+use super::{
+    entity::{alert_silences, silenced_notifications},
+    get_lock,
+};
+use crate::{
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
+    errors,
+};
+
+impl TryFrom<alert_silences::Model> for Silence {
+    type Error = errors::Error;
+
+    fn try_from(value: alert_silences::Model) -> Result<Self, Self::Error> {
+        Ok(Silence {
+            id: value.id,
+            matchers: json::from_value(value.matchers)?,
+            start_time: value.start_time,
+            end_time: value.end_time,
+            comment: value.comment.unwrap_or_default(),
+            created_by: value.created_by,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        })
+    }
+}
+
+impl From<silenced_notifications::Model> for SilencedNotification {
+    fn from(value: silenced_notifications::Model) -> Self {
+        SilencedNotification {
+            silence_id: value.silence_id,
+            alert_id: value.alert_id,
+            alert_name: value.alert_name,
+            rows: value.rows,
+            silenced_at: value.silenced_at,
+        }
+    }
+}
+
+/// Creates the silence, or updates it if a silence with the same id exists.
+pub async fn put(org_id: &str, silence: &Silence) -> Result<(), errors::Error> {
+    let matchers = json::to_value(&silence.matchers)?;
+
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let existing = alert_silences::Entity::find_by_id(&silence.id)
+        .filter(alert_silences::Column::Org.eq(org_id))
+        .one(client)
+        .await?;
+    if let Some(existing) = existing {
+        let mut record: alert_silences::ActiveModel = existing.into();
+        record.matchers = Set(matchers);
+        record.start_time = Set(silence.start_time);
+        record.end_time = Set(silence.end_time);
+        record.comment = Set(Some(silence.comment.clone()));
+        record.updated_at = Set(silence.updated_at);
+        record.update(client).await?;
+        return Ok(());
+    }
+
+    let record = alert_silences::ActiveModel {
+        id: Set(silence.id.clone()),
+        org: Set(org_id.to_string()),
+        matchers: Set(matchers),
+        start_time: Set(silence.start_time),
+        end_time: Set(silence.end_time),
+        comment: Set(Some(silence.comment.clone())),
+        created_by: Set(silence.created_by.clone()),
+        created_at: Set(silence.created_at),
+        updated_at: Set(silence.updated_at),
+    };
+    alert_silences::Entity::insert(record).exec(client).await?;
+    Ok(())
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<Option<Silence>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let record = alert_silences::Entity::find_by_id(id)
+        .filter(alert_silences::Column::Org.eq(org_id))
+        .one(client)
+        .await?;
+    record.map(Silence::try_from).transpose()
+}
+
+/// Lists the silences of the org, newest first. Expired silences are skipped unless
+/// `include_expired` is set.
+pub async fn list(
+    org_id: &str,
+    now: i64,
+    include_expired: bool,
+) -> Result<Vec<Silence>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let mut query = alert_silences::Entity::find().filter(alert_silences::Column::Org.eq(org_id));
+    if !include_expired {
+        query = query.filter(alert_silences::Column::EndTime.gt(now));
+    }
+    let records = query
+        .order_by_desc(alert_silences::Column::StartTime)
+        .all(client)
+        .await?;
+    records.into_iter().map(Silence::try_from).collect()
+}
+
+/// Lists the silences of the org active at `now`.
+pub async fn list_active(org_id: &str, now: i64) -> Result<Vec<Silence>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let records = alert_silences::Entity::find()
+        .filter(alert_silences::Column::Org.eq(org_id))
+        .filter(alert_silences::Column::EndTime.gt(now))
+        .filter(alert_silences::Column::StartTime.lte(now))
+        .all(client)
+        .await?;
+    records.into_iter().map(Silence::try_from).collect()
+}
+
+/// Deletes the silence and its muted notifications, returns false if it doesn't exist.
+pub async fn delete(org_id: &str, id: &str) -> Result<bool, errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let res = alert_silences::Entity::delete_many()
+        .filter(alert_silences::Column::Org.eq(org_id))
+        .filter(alert_silences::Column::Id.eq(id))
+        .exec(client)
+        .await?;
+    silenced_notifications::Entity::delete_many()
+        .filter(silenced_notifications::Column::Org.eq(org_id))
+        .filter(silenced_notifications::Column::SilenceId.eq(id))
+        .exec(client)
+        .await?;
+    Ok(res.rows_affected > 0)
+}
+
+/// Records a notification muted by a silence.
+pub async fn add_silenced_notification(
+    org_id: &str,
+    notification: &SilencedNotification,
+) -> Result<(), errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let record = silenced_notifications::ActiveModel {
+        org: Set(org_id.to_string()),
+        silence_id: Set(notification.silence_id.clone()),
+        alert_id: Set(notification.alert_id.clone()),
+        alert_name: Set(notification.alert_name.clone()),
+        rows: Set(notification.rows),
+        silenced_at: Set(notification.silenced_at),
+        ..Default::default()
+    };
+    silenced_notifications::Entity::insert(record)
+        .exec(client)
+        .await?;
+    Ok(())
+}
+
+/// Lists the latest notifications muted by the silence, newest first.
+pub async fn list_silenced_notifications(
+    org_id: &str,
+    silence_id: &str,
+    limit: u64,
+) -> Result<Vec<SilencedNotification>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let records = silenced_notifications::Entity::find()
+        .filter(silenced_notifications::Column::Org.eq(org_id))
+        .filter(silenced_notifications::Column::SilenceId.eq(silence_id))
+        .order_by_desc(silenced_notifications::Column::SilencedAt)
+        .limit(limit)
+        .all(client)
+        .await?;
+    Ok(records
+        .into_iter()
+        .map(SilencedNotification::from)
+        .collect())
+}
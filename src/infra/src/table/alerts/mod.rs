@@ -18,9 +18,12 @@ use std::str::FromStr;
 use chrono::{DateTime, FixedOffset, TimeZone, Utc};
 use config::meta::{
     alerts::{
-        ConditionList, QueryCondition as MetaQueryCondition,
+        ConditionList, CorrelationConfig, PanelSource, QueryCondition as MetaQueryCondition,
         TriggerCondition as MetaTriggerCondition,
         alert::{Alert as MetaAlert, ListAlertsParams},
+        anomaly::AnomalyCondition,
+        composite::CompositeCondition,
+        multi_condition::MultiCondition,
     },
     folder::{Folder as MetaFolder, FolderType},
     stream::StreamType as MetaStreamType,
@@ -90,6 +93,18 @@ impl TryFrom<alerts::Model> for MetaAlert {
             .query_multi_time_range
             .map(serde_json::from_value)
             .transpose()?;
+        let correlation: Option<CorrelationConfig> =
+            value.correlation.map(serde_json::from_value).transpose()?;
+        let panel_source: Option<PanelSource> =
+            value.panel_source.map(serde_json::from_value).transpose()?;
+        let composite: Option<CompositeCondition> =
+            value.composite.map(serde_json::from_value).transpose()?;
+        let multi_condition: Option<MultiCondition> = value
+            .multi_condition
+            .map(serde_json::from_value)
+            .transpose()?;
+        let anomaly: Option<AnomalyCondition> =
+            value.anomaly.map(serde_json::from_value).transpose()?;
 
         // Transform the Unix timestamp into a date time that will always use
         // the UTC timezone.
@@ -114,6 +129,12 @@ impl TryFrom<alerts::Model> for MetaAlert {
         alert.owner = value.owner;
         alert.last_edited_by = value.last_edited_by;
         alert.updated_at = updated_at_utc;
+        alert.correlation = correlation;
+        alert.panel_source = panel_source;
+        alert.composite = composite;
+        alert.multi_condition = multi_condition;
+        alert.anomaly = anomaly;
+        alert.escalation_policy = value.escalation_policy;
         alert.query_condition = MetaQueryCondition {
             query_type: query_type.into(),
             conditions: query_conditions,
@@ -533,6 +554,13 @@ async fn list_models<C: ConnectionTrait>(
         query
     };
 
+    // Apply the optional alert IDs filter.
+    let query = if let Some(alert_ids) = params.alert_ids {
+        query.filter(alerts::Column::Id.is_in(alert_ids))
+    } else {
+        query
+    };
+
     // Apply ordering.
     let query = query
         .order_by_asc(alerts::Column::Name)
@@ -647,6 +675,14 @@ fn update_mutable_fields(
     let owner = alert.owner.filter(|s| !s.is_empty());
     let last_edited_by = alert.last_edited_by.filter(|s| !s.is_empty());
     let align_time = alert.trigger_condition.align_time;
+    let correlation = alert.correlation.map(serde_json::to_value).transpose()?;
+    let panel_source = alert.panel_source.map(serde_json::to_value).transpose()?;
+    let composite = alert.composite.map(serde_json::to_value).transpose()?;
+    let multi_condition = alert
+        .multi_condition
+        .map(serde_json::to_value)
+        .transpose()?;
+    let anomaly = alert.anomaly.map(serde_json::to_value).transpose()?;
     let updated_at: i64 = chrono::Utc::now().timestamp_micros();
 
     alert_am.is_real_time = Set(is_real_time);
@@ -680,6 +716,12 @@ fn update_mutable_fields(
     alert_am.last_edited_by = Set(last_edited_by);
     alert_am.updated_at = Set(Some(updated_at));
     alert_am.align_time = Set(align_time);
+    alert_am.correlation = Set(correlation);
+    alert_am.panel_source = Set(panel_source);
+    alert_am.composite = Set(composite);
+    alert_am.multi_condition = Set(multi_condition);
+    alert_am.anomaly = Set(anomaly);
+    alert_am.escalation_policy = Set(alert.escalation_policy);
     Ok(())
 }
 
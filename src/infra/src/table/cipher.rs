@@ -22,7 +22,7 @@ use serde::{Deserialize, Serialize};
 
 use super::{entity::cipher_keys::*, get_lock};
 use crate::{
-    db::{ORM_CLIENT, connect_to_orm},
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
     errors,
 };
 
@@ -233,7 +233,7 @@ pub async fn get_data(
 }
 
 async fn get(org: &str, kind: EntryKind, name: &str) -> Result<Option<Model>, errors::DbError> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     Entity::find()
         .filter(Column::Org.eq(org))
         .filter(Column::Name.eq(name))
@@ -245,7 +245,7 @@ async fn get(org: &str, kind: EntryKind, name: &str) -> Result<Option<Model>, er
 }
 
 pub async fn list_all(limit: Option<i64>) -> Result<Vec<CipherEntry>, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let mut res = Entity::find().order_by(Column::CreatedAt, Order::Desc);
     if let Some(limit) = limit {
         res = res.limit(limit as u64);
@@ -273,7 +273,7 @@ pub async fn list_filtered(
     filter: ListFilter,
     limit: Option<i64>,
 ) -> Result<Vec<CipherEntry>, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let mut res = Entity::find().order_by(Column::CreatedAt, Order::Desc);
     if let Some(ref org) = filter.org {
         res = res.filter(Column::Org.eq(org));
@@ -15,12 +15,13 @@
 
 use config::meta::{
     dashboards::{
-        Dashboard, ListDashboardsParams, v1::Dashboard as DashboardV1,
+        Dashboard, ListDashboardsParams, acl::DashboardAcl, v1::Dashboard as DashboardV1,
         v2::Dashboard as DashboardV2, v3::Dashboard as DashboardV3, v4::Dashboard as DashboardV4,
-        v5::Dashboard as DashboardV5,
+        v5::Dashboard as DashboardV5, v6::Dashboard as DashboardV6,
     },
     folder::{Folder, FolderType},
 };
+use hashbrown::HashMap;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
     IntoActiveModel, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
@@ -35,7 +36,7 @@ use super::{
     folders::folder_type_into_i16,
 };
 use crate::{
-    db::{ORM_CLIENT, connect_to_orm},
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
     errors::{self, GetDashboardError},
 };
 
@@ -44,7 +45,7 @@ impl TryFrom<dashboards::Model> for Dashboard {
 
     fn try_from(mut value: dashboards::Model) -> Result<Self, Self::Error> {
         if let Some(obj) = value.data.as_object_mut() {
-            // The domain model JSON deserialization logic for v1-v5 expects
+            // The domain model JSON deserialization logic for v1-v6 expects
             // some or all these fields to be present in the JSON even though we
             // store them in DB columns. Therefore we add these values back into
             // the JSON object so that deserializing the JSON can succeed.
@@ -86,6 +87,11 @@ impl TryFrom<dashboards::Model> for Dashboard {
                 let dash = inner.into();
                 Ok(dash)
             }
+            6 => {
+                let inner: DashboardV6 = serde_json::from_value(value.data)?;
+                let dash = inner.into();
+                Ok(dash)
+            }
             _ => Err(GetDashboardError::UnsupportedVersion(value.version).into()),
         }
     }
@@ -101,7 +107,7 @@ pub async fn get_from_folder(
     folder_id: &str,
     dashboard_id: &str,
 ) -> Result<Option<Dashboard>, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let model = get_model_from_folder(client, org_id, folder_id, dashboard_id)
         .await?
         .and_then(|(_folder, maybe_dash)| maybe_dash);
@@ -123,7 +129,7 @@ pub async fn get_by_id(
     org_id: &str,
     dashboard_id: &str,
 ) -> Result<Option<(Folder, Dashboard)>, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let Some((folder_m, dash_m)) = get_model_by_id(client, org_id, dashboard_id).await? else {
         return Ok(None);
     };
@@ -134,7 +140,7 @@ pub async fn get_by_id(
 
 /// Lists dashboards.
 pub async fn list(params: ListDashboardsParams) -> Result<Vec<(Folder, Dashboard)>, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let dashboards = list_models(client, params)
         .await?
         .into_iter()
@@ -149,7 +155,7 @@ pub async fn list(params: ListDashboardsParams) -> Result<Vec<(Folder, Dashboard
 
 /// Lists all existing dashboards
 pub async fn list_all() -> Result<Vec<(String, Dashboard)>, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let dashboards = list_all_models(client)
         .await?
         .into_iter()
@@ -269,6 +275,7 @@ pub async fn put(
                     title: Set(title),
                     description: Set(description),
                     data: Set(data),
+                    acl: Set(None),
                     version: Set(version),
                     created_at: Set(created_at_unix),
                     updated_at: Set(updated_at),
@@ -282,6 +289,62 @@ pub async fn put(
     Ok(dash)
 }
 
+/// Gets the ACL of a dashboard. Returns `None` if the dashboard doesn't exist and the default
+/// ACL, which doesn't restrict the access, if the dashboard has no ACL.
+pub async fn get_acl(
+    org_id: &str,
+    dashboard_id: &str,
+) -> Result<Option<DashboardAcl>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let Some((_folder_m, dash_m)) = get_model_by_id(client, org_id, dashboard_id).await? else {
+        return Ok(None);
+    };
+    let acl = match dash_m.acl {
+        Some(acl) => serde_json::from_value(acl)?,
+        None => DashboardAcl::default(),
+    };
+    Ok(Some(acl))
+}
+
+/// Lists the ACLs of the dashboards of the organization that have one, by dashboard ID.
+pub async fn list_acls(org_id: &str) -> Result<HashMap<String, DashboardAcl>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let models = dashboards::Entity::find()
+        .find_also_related(folders::Entity)
+        .filter(folders::Column::Org.eq(org_id))
+        .filter(folders::Column::Type.eq::<i16>(folder_type_into_i16(FolderType::Dashboards)))
+        .filter(dashboards::Column::Acl.is_not_null())
+        .all(client)
+        .await?;
+    models
+        .into_iter()
+        .filter_map(|(d, _)| d.acl.map(|acl| (d.dashboard_id, acl)))
+        .map(|(id, acl)| Ok((id, serde_json::from_value(acl)?)))
+        .collect()
+}
+
+/// Sets the ACL of a dashboard, an empty ACL removes it. Returns `false` if the dashboard
+/// doesn't exist.
+pub async fn set_acl(
+    org_id: &str,
+    dashboard_id: &str,
+    acl: &DashboardAcl,
+) -> Result<bool, errors::Error> {
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let Some((_folder_m, dash_m)) = get_model_by_id(client, org_id, dashboard_id).await? else {
+        return Ok(false);
+    };
+    let acl = if acl.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_value(acl)?)
+    };
+    let mut dash_am = dash_m.into_active_model();
+    dash_am.acl = Set(acl);
+    dash_am.update(client).await?;
+    Ok(true)
+}
+
 /// Deletes a dashboard with the given `folder_id` and `dashboard_id` surrogate
 /// keys.
 pub async fn delete_from_folder(
@@ -401,6 +464,13 @@ async fn list_models(
         query
     };
 
+    // Apply the optional dashboard IDs filter.
+    let query = if let Some(dashboard_ids) = params.dashboard_ids {
+        query.filter(dashboards::Column::DashboardId.is_in(dashboard_ids))
+    } else {
+        query
+    };
+
     // Apply ordering.
     let query = query
         .order_by_asc(dashboards::Column::Title)
@@ -480,6 +550,11 @@ fn inner_data_as_json(dashboard: Dashboard) -> Result<JsonValue, errors::Error>
             v5: Some(inner),
             ..
         } => serde_json::to_value(inner).map_err(errors::Error::SerdeJsonError),
+        Dashboard {
+            version: 6,
+            v6: Some(inner),
+            ..
+        } => serde_json::to_value(inner).map_err(errors::Error::SerdeJsonError),
         Dashboard { version: v, .. } => Err(errors::PutDashboardError::MissingInnerData(v).into()),
     }?;
 
@@ -513,6 +588,7 @@ mod tests {
             org_id: "orgId".to_owned(),
             folder_id: Some("folderId".to_owned()),
             title_pat: Some("tItLePat".to_owned()),
+            dashboard_ids: None,
             page_size_and_idx: Some((100, 2)),
         };
         list_models(&db, params).await?;
@@ -520,7 +596,7 @@ mod tests {
             db.into_transaction_log(),
             vec![Transaction::from_sql_and_values(
                 DatabaseBackend::Postgres,
-                r#"SELECT "dashboards"."id" AS "A_id", "dashboards"."dashboard_id" AS "A_dashboard_id", "dashboards"."folder_id" AS "A_folder_id", "dashboards"."owner" AS "A_owner", "dashboards"."role" AS "A_role", "dashboards"."title" AS "A_title", "dashboards"."description" AS "A_description", "dashboards"."data" AS "A_data", "dashboards"."version" AS "A_version", "dashboards"."created_at" AS "A_created_at", "dashboards"."updated_at" AS "A_updated_at", "folders"."id" AS "B_id", "folders"."org" AS "B_org", "folders"."folder_id" AS "B_folder_id", "folders"."name" AS "B_name", "folders"."description" AS "B_description", "folders"."type" AS "B_type" FROM "dashboards" LEFT JOIN "folders" ON "dashboards"."folder_id" = "folders"."id" WHERE "folders"."org" = $1 AND "folders"."type" = $2 AND "folders"."folder_id" = $3 AND LOWER("title") LIKE $4 ORDER BY "dashboards"."title" ASC, "folders"."name" ASC LIMIT $5 OFFSET $6"#,
+                r#"SELECT "dashboards"."id" AS "A_id", "dashboards"."dashboard_id" AS "A_dashboard_id", "dashboards"."folder_id" AS "A_folder_id", "dashboards"."owner" AS "A_owner", "dashboards"."role" AS "A_role", "dashboards"."title" AS "A_title", "dashboards"."description" AS "A_description", "dashboards"."data" AS "A_data", "dashboards"."acl" AS "A_acl", "dashboards"."version" AS "A_version", "dashboards"."created_at" AS "A_created_at", "dashboards"."updated_at" AS "A_updated_at", "folders"."id" AS "B_id", "folders"."org" AS "B_org", "folders"."folder_id" AS "B_folder_id", "folders"."name" AS "B_name", "folders"."description" AS "B_description", "folders"."type" AS "B_type" FROM "dashboards" LEFT JOIN "folders" ON "dashboards"."folder_id" = "folders"."id" WHERE "folders"."org" = $1 AND "folders"."type" = $2 AND "folders"."folder_id" = $3 AND LOWER("title") LIKE $4 ORDER BY "dashboards"."title" ASC, "folders"."name" ASC LIMIT $5 OFFSET $6"#,
                 [
                     "orgId".into(),
                     0i16.into(),
@@ -543,6 +619,7 @@ mod tests {
             org_id: "orgId".to_owned(),
             folder_id: Some("folderId".to_owned()),
             title_pat: Some("tItLePat".to_owned()),
+            dashboard_ids: None,
             page_size_and_idx: Some((100, 2)),
         };
         list_models(&db, params).await?;
@@ -550,7 +627,7 @@ mod tests {
             db.into_transaction_log(),
             vec![Transaction::from_sql_and_values(
                 DatabaseBackend::MySql,
-                r#"SELECT `dashboards`.`id` AS `A_id`, `dashboards`.`dashboard_id` AS `A_dashboard_id`, `dashboards`.`folder_id` AS `A_folder_id`, `dashboards`.`owner` AS `A_owner`, `dashboards`.`role` AS `A_role`, `dashboards`.`title` AS `A_title`, `dashboards`.`description` AS `A_description`, `dashboards`.`data` AS `A_data`, `dashboards`.`version` AS `A_version`, `dashboards`.`created_at` AS `A_created_at`, `dashboards`.`updated_at` AS `A_updated_at`, `folders`.`id` AS `B_id`, `folders`.`org` AS `B_org`, `folders`.`folder_id` AS `B_folder_id`, `folders`.`name` AS `B_name`, `folders`.`description` AS `B_description`, `folders`.`type` AS `B_type` FROM `dashboards` LEFT JOIN `folders` ON `dashboards`.`folder_id` = `folders`.`id` WHERE `folders`.`org` = ? AND `folders`.`type` = ? AND `folders`.`folder_id` = ? AND LOWER(`title`) LIKE ? ORDER BY `dashboards`.`title` ASC, `folders`.`name` ASC LIMIT ? OFFSET ?"#,
+                r#"SELECT `dashboards`.`id` AS `A_id`, `dashboards`.`dashboard_id` AS `A_dashboard_id`, `dashboards`.`folder_id` AS `A_folder_id`, `dashboards`.`owner` AS `A_owner`, `dashboards`.`role` AS `A_role`, `dashboards`.`title` AS `A_title`, `dashboards`.`description` AS `A_description`, `dashboards`.`data` AS `A_data`, `dashboards`.`acl` AS `A_acl`, `dashboards`.`version` AS `A_version`, `dashboards`.`created_at` AS `A_created_at`, `dashboards`.`updated_at` AS `A_updated_at`, `folders`.`id` AS `B_id`, `folders`.`org` AS `B_org`, `folders`.`folder_id` AS `B_folder_id`, `folders`.`name` AS `B_name`, `folders`.`description` AS `B_description`, `folders`.`type` AS `B_type` FROM `dashboards` LEFT JOIN `folders` ON `dashboards`.`folder_id` = `folders`.`id` WHERE `folders`.`org` = ? AND `folders`.`type` = ? AND `folders`.`folder_id` = ? AND LOWER(`title`) LIKE ? ORDER BY `dashboards`.`title` ASC, `folders`.`name` ASC LIMIT ? OFFSET ?"#,
                 [
                     "orgId".into(),
                     0i16.into(),
@@ -573,6 +650,7 @@ mod tests {
             org_id: "orgId".to_owned(),
             folder_id: Some("folderId".to_owned()),
             title_pat: Some("tItLePat".to_owned()),
+            dashboard_ids: None,
             page_size_and_idx: Some((100, 2)),
         };
         list_models(&db, params).await?;
@@ -580,7 +658,7 @@ mod tests {
             db.into_transaction_log(),
             vec![Transaction::from_sql_and_values(
                 DatabaseBackend::Sqlite,
-                r#"SELECT "dashboards"."id" AS "A_id", "dashboards"."dashboard_id" AS "A_dashboard_id", "dashboards"."folder_id" AS "A_folder_id", "dashboards"."owner" AS "A_owner", "dashboards"."role" AS "A_role", "dashboards"."title" AS "A_title", "dashboards"."description" AS "A_description", "dashboards"."data" AS "A_data", "dashboards"."version" AS "A_version", "dashboards"."created_at" AS "A_created_at", "dashboards"."updated_at" AS "A_updated_at", "folders"."id" AS "B_id", "folders"."org" AS "B_org", "folders"."folder_id" AS "B_folder_id", "folders"."name" AS "B_name", "folders"."description" AS "B_description", "folders"."type" AS "B_type" FROM "dashboards" LEFT JOIN "folders" ON "dashboards"."folder_id" = "folders"."id" WHERE "folders"."org" = ? AND "folders"."type" = ? AND "folders"."folder_id" = ? AND LOWER("title") LIKE ? ORDER BY "dashboards"."title" ASC, "folders"."name" ASC LIMIT ? OFFSET ?"#,
+                r#"SELECT "dashboards"."id" AS "A_id", "dashboards"."dashboard_id" AS "A_dashboard_id", "dashboards"."folder_id" AS "A_folder_id", "dashboards"."owner" AS "A_owner", "dashboards"."role" AS "A_role", "dashboards"."title" AS "A_title", "dashboards"."description" AS "A_description", "dashboards"."data" AS "A_data", "dashboards"."acl" AS "A_acl", "dashboards"."version" AS "A_version", "dashboards"."created_at" AS "A_created_at", "dashboards"."updated_at" AS "A_updated_at", "folders"."id" AS "B_id", "folders"."org" AS "B_org", "folders"."folder_id" AS "B_folder_id", "folders"."name" AS "B_name", "folders"."description" AS "B_description", "folders"."type" AS "B_type" FROM "dashboards" LEFT JOIN "folders" ON "dashboards"."folder_id" = "folders"."id" WHERE "folders"."org" = ? AND "folders"."type" = ? AND "folders"."folder_id" = ? AND LOWER("title") LIKE ? ORDER BY "dashboards"."title" ASC, "folders"."name" ASC LIMIT ? OFFSET ?"#,
                 [
                     "orgId".into(),
                     0i16.into(),
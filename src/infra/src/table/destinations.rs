@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr};
 
 use config::{ider, meta::destinations, utils::json};
 use sea_orm::{
@@ -22,7 +22,7 @@ use sea_orm::{
 };
 
 use crate::{
-    db::{ORM_CLIENT, connect_to_orm},
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
     errors::{DestinationError, Error},
     table::{
         entity::{
@@ -34,15 +34,22 @@ use crate::{
 };
 
 impl Model {
-    fn try_into(self, template: Option<String>) -> Result<destinations::Destination, Error> {
+    fn try_into(
+        self,
+        template: Option<String>,
+        resolved_template: Option<String>,
+    ) -> Result<destinations::Destination, Error> {
         let module = match self.module.to_lowercase().as_str() {
             "alert" => {
                 let destination_type: destinations::DestinationType =
                     json::from_value(self.r#type)?;
                 let template = template.ok_or(DestinationError::AlertDestTemplateNotFound)?;
+                let grouping = self.grouping.map(json::from_value).transpose()?;
                 destinations::Module::Alert {
                     template,
+                    resolved_template,
                     destination_type,
+                    grouping,
                 }
             }
             _ => {
@@ -71,12 +78,24 @@ pub async fn put(
     } else {
         None
     };
+    let resolved_template_id = match &destination.module {
+        destinations::Module::Alert {
+            resolved_template: Some(template),
+            ..
+        } => Some(
+            super::templates::get(&destination.org_id, template)
+                .await?
+                .and_then(|temp| temp.id.map(|id| id.to_string()))
+                .ok_or(DestinationError::AlertDestTemplateNotFound)?,
+        ),
+        _ => None,
+    };
 
     // make sure only one client is writing to the database(only for sqlite)
     let _lock = get_lock().await;
     let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
 
-    let (model, template): (Model, Option<String>) =
+    let (model, (template, resolved_template)): (Model, (Option<String>, Option<String>)) =
         match get_model_and_template(client, &destination.org_id, &destination.name).await? {
             Some((model, ..)) => {
                 let mut active: ActiveModel = model.into();
@@ -85,20 +104,26 @@ pub async fn put(
                 let new_template = match destination.module {
                     destinations::Module::Alert {
                         template: new_template,
+                        resolved_template,
                         destination_type,
+                        grouping,
                     } => {
                         let template_id =
                             template_id.ok_or(DestinationError::AlertDestEmptyTemplateId)?;
                         active.template_id = Set(Some(template_id));
+                        active.resolved_template_id = Set(resolved_template_id);
                         active.module = Set("alert".to_string());
                         active.r#type = Set(json::to_value(destination_type)?);
-                        Some(new_template)
+                        active.grouping = Set(grouping.map(json::to_value).transpose()?);
+                        (Some(new_template), resolved_template)
                     }
                     destinations::Module::Pipeline { endpoint } => {
                         active.template_id = Set(None);
+                        active.resolved_template_id = Set(None);
                         active.module = Set("pipeline".to_string());
                         active.r#type = Set(json::to_value(endpoint)?);
-                        None
+                        active.grouping = Set(None);
+                        (None, None)
                     }
                 };
                 (active.update(client).await?.try_into_model()?, new_template)
@@ -112,23 +137,29 @@ pub async fn put(
                         template_id: Set(None),
                         r#type: NotSet,
                         module: NotSet,
+                        grouping: Set(None),
+                        resolved_template_id: Set(None),
                     };
                     let new_template = match destination.module {
                         destinations::Module::Alert {
                             template: new_template,
+                            resolved_template,
                             destination_type,
+                            grouping,
                         } => {
                             let template_id =
                                 template_id.ok_or(DestinationError::AlertDestEmptyTemplateId)?;
                             active.module = Set("alert".to_string());
                             active.template_id = Set(Some(template_id));
+                            active.resolved_template_id = Set(resolved_template_id);
                             active.r#type = Set(json::to_value(destination_type)?);
-                            Some(new_template)
+                            active.grouping = Set(grouping.map(json::to_value).transpose()?);
+                            (Some(new_template), resolved_template)
                         }
                         destinations::Module::Pipeline { endpoint } => {
                             active.module = Set("pipeline".to_string());
                             active.r#type = Set(json::to_value(endpoint)?);
-                            None
+                            (None, None)
                         }
                     };
                     (active, new_template)
@@ -136,13 +167,13 @@ pub async fn put(
                 (active.insert(client).await?.try_into_model()?, new_template)
             }
         };
-    model.try_into(template)
+    model.try_into(template, resolved_template)
 }
 
 pub async fn get(org_id: &str, name: &str) -> Result<Option<destinations::Destination>, Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     match get_model_and_template(client, org_id, name).await? {
-        Some((model, template)) => Ok(Some(model.try_into(template)?)),
+        Some(model) => Ok(into_destinations(client, vec![model]).await?.pop()),
         None => Ok(None),
     }
 }
@@ -151,23 +182,15 @@ pub async fn list(
     org_id: &str,
     module: Option<&str>,
 ) -> Result<Vec<destinations::Destination>, Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
-    let destinations = list_models(client, Some(org_id), module)
-        .await?
-        .into_iter()
-        .map(|(model, template)| model.try_into(template))
-        .collect::<Result<_, Error>>()?;
-    Ok(destinations)
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let models = list_models(client, Some(org_id), module).await?;
+    into_destinations(client, models).await
 }
 
 pub async fn list_all() -> Result<Vec<destinations::Destination>, Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
-    let destinations = list_models(client, None, None)
-        .await?
-        .into_iter()
-        .map(|(model, template)| model.try_into(template))
-        .collect::<Result<_, Error>>()?;
-    Ok(destinations)
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let models = list_models(client, None, None).await?;
+    into_destinations(client, models).await
 }
 
 pub async fn delete(org_id: &str, name: &str) -> Result<(), Error> {
@@ -184,6 +207,41 @@ pub async fn delete(org_id: &str, name: &str) -> Result<(), Error> {
     Ok(())
 }
 
+/// Converts the models with the names of their templates into destinations, looking up the
+/// names of their resolved templates.
+async fn into_destinations(
+    db: &DatabaseConnection,
+    models: Vec<(Model, Option<String>)>,
+) -> Result<Vec<destinations::Destination>, Error> {
+    let mut ids = models
+        .iter()
+        .filter_map(|(model, _)| model.resolved_template_id.clone())
+        .collect::<Vec<_>>();
+    ids.sort();
+    ids.dedup();
+    let names: HashMap<String, String> = if ids.is_empty() {
+        HashMap::new()
+    } else {
+        templates::Entity::find()
+            .filter(templates::Column::Id.is_in(ids))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|temp| (temp.id, temp.name))
+            .collect()
+    };
+    models
+        .into_iter()
+        .map(|(model, template)| {
+            let resolved_template = model
+                .resolved_template_id
+                .as_ref()
+                .and_then(|id| names.get(id).cloned());
+            model.try_into(template, resolved_template)
+        })
+        .collect()
+}
+
 async fn get_model_and_template(
     db: &DatabaseConnection,
     org_id: &str,
@@ -21,7 +21,10 @@ use serde::{Deserialize, Serialize};
 
 use super::get_lock;
 use crate::{
-    db::{ORM_CLIENT, ORM_CLIENT_DDL, connect_to_orm, connect_to_orm_ddl},
+    db::{
+        ORM_CLIENT, ORM_CLIENT_DDL, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ddl,
+        connect_to_orm_ro,
+    },
     errors::{self, DbError, Error},
 };
 
@@ -217,7 +220,7 @@ pub async fn batch_remove(origin: OriginType, origin_id: &str) -> Result<(), err
 
 pub async fn len() -> Result<u64, errors::Error> {
     let _lock = get_lock().await;
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     Entity::find()
         .count(client)
         .await
@@ -43,6 +43,12 @@ pub struct Model {
     pub last_edited_by: Option<String>,
     pub updated_at: Option<i64>,
     pub align_time: bool,
+    pub correlation: Option<Json>,
+    pub panel_source: Option<Json>,
+    pub composite: Option<Json>,
+    pub multi_condition: Option<Json>,
+    pub anomaly: Option<Json>,
+    pub escalation_policy: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -14,6 +14,7 @@ pub struct Model {
     pub title: String,
     pub description: Option<String>,
     pub data: Json,
+    pub acl: Option<Json>,
     pub version: i32,
     pub created_at: i64,
     pub updated_at: i64,
@@ -12,6 +12,8 @@ pub struct Model {
     pub module: String,
     pub template_id: Option<String>,
     pub r#type: Json,
+    pub grouping: Option<Json>,
+    pub resolved_template_id: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
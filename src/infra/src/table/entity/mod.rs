@@ -3,12 +3,17 @@
 pub mod prelude;
 
 pub mod action_scripts;
+pub mod alert_silences;
 pub mod alerts;
 pub mod cipher_keys;
 pub mod dashboards;
 pub mod destinations;
 pub mod distinct_value_fields;
+pub mod escalation_policies;
+pub mod file_list_retired;
 pub mod folders;
+pub mod index_recommendations;
+pub mod library_panels;
 pub mod org_users;
 pub mod organizations;
 pub mod rate_limit_rules;
@@ -18,7 +23,15 @@ pub mod search_job_partitions;
 pub mod search_job_results;
 pub mod search_jobs;
 pub mod search_queue;
+pub mod silenced_notifications;
+pub mod slow_queries;
+pub mod stream_field_usage;
+pub mod stream_hourly_stats;
+pub mod stream_storage_usage;
 pub mod templates;
 pub mod timed_annotation_panels;
 pub mod timed_annotations;
 pub mod users;
+pub mod workspace_members;
+pub mod workspace_resources;
+pub mod workspaces;
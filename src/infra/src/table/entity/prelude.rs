@@ -1,14 +1,20 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.0
 
 pub use super::{
-    action_scripts::Entity as ActionScripts, alerts::Entity as Alerts,
-    cipher_keys::Entity as CipherKeys, dashboards::Entity as Dashboards,
+    action_scripts::Entity as ActionScripts, alert_silences::Entity as AlertSilences,
+    alerts::Entity as Alerts, cipher_keys::Entity as CipherKeys, dashboards::Entity as Dashboards,
     destinations::Entity as Destinations, distinct_value_fields::Entity as DistinctValueFields,
-    folders::Entity as Folders, org_users::Entity as OrgUsers,
+    escalation_policies::Entity as EscalationPolicies,
+    file_list_retired::Entity as FileListRetired, folders::Entity as Folders,
+    library_panels::Entity as LibraryPanels, org_users::Entity as OrgUsers,
     organizations::Entity as Organizations, report_dashboards::Entity as ReportDashboards,
     reports::Entity as Reports, search_job_partitions::Entity as SearchJobPartitions,
     search_job_results::Entity as SearchJobResults, search_jobs::Entity as SearchJobs,
-    search_queue::Entity as SearchQueue, templates::Entity as Templates,
+    search_queue::Entity as SearchQueue, silenced_notifications::Entity as SilencedNotifications,
+    stream_hourly_stats::Entity as StreamHourlyStats,
+    stream_storage_usage::Entity as StreamStorageUsage, templates::Entity as Templates,
     timed_annotation_panels::Entity as TimedAnnotationPanels,
     timed_annotations::Entity as TimedAnnotations, users::Entity as Users,
+    workspace_members::Entity as WorkspaceMembers,
+    workspace_resources::Entity as WorkspaceResources, workspaces::Entity as Workspaces,
 };
@@ -23,6 +23,8 @@ pub struct Model {
     pub created_at: i64,
     pub updated_at: Option<i64>,
     pub start_at: i64,
+    pub panel_data: Option<Json>,
+    pub digest: Option<Json>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
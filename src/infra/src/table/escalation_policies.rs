@@ -0,0 +1,125 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::{meta::alerts::escalation::EscalationPolicy, utils::json};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+
+use super::{entity::escalation_policies, get_lock};
+use crate::{
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
+    errors,
+};
+
+impl TryFrom<escalation_policies::Model> for EscalationPolicy {
+    type Error = errors::Error;
+
+    fn try_from(value: escalation_policies::Model) -> Result<Self, Self::Error> {
+        Ok(EscalationPolicy {
+            id: value.id,
+            name: value.name,
+            description: value.description.unwrap_or_default(),
+            steps: json::from_value(value.steps)?,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        })
+    }
+}
+
+/// Creates the policy, or updates it if a policy with the same id exists.
+pub async fn put(org_id: &str, policy: &EscalationPolicy) -> Result<(), errors::Error> {
+    let steps = json::to_value(&policy.steps)?;
+
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let existing = escalation_policies::Entity::find_by_id(&policy.id)
+        .filter(escalation_policies::Column::Org.eq(org_id))
+        .one(client)
+        .await?;
+    if let Some(existing) = existing {
+        let mut record: escalation_policies::ActiveModel = existing.into();
+        record.name = Set(policy.name.clone());
+        record.description = Set(Some(policy.description.clone()));
+        record.steps = Set(steps);
+        record.updated_at = Set(policy.updated_at);
+        record.update(client).await?;
+        return Ok(());
+    }
+
+    let record = escalation_policies::ActiveModel {
+        id: Set(policy.id.clone()),
+        org: Set(org_id.to_string()),
+        name: Set(policy.name.clone()),
+        description: Set(Some(policy.description.clone())),
+        steps: Set(steps),
+        created_at: Set(policy.created_at),
+        updated_at: Set(policy.updated_at),
+    };
+    escalation_policies::Entity::insert(record)
+        .exec(client)
+        .await?;
+    Ok(())
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<Option<EscalationPolicy>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let record = escalation_policies::Entity::find_by_id(id)
+        .filter(escalation_policies::Column::Org.eq(org_id))
+        .one(client)
+        .await?;
+    record.map(EscalationPolicy::try_from).transpose()
+}
+
+pub async fn get_by_name(
+    org_id: &str,
+    name: &str,
+) -> Result<Option<EscalationPolicy>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let record = escalation_policies::Entity::find()
+        .filter(escalation_policies::Column::Org.eq(org_id))
+        .filter(escalation_policies::Column::Name.eq(name))
+        .one(client)
+        .await?;
+    record.map(EscalationPolicy::try_from).transpose()
+}
+
+/// Lists the policies of the org ordered by name.
+pub async fn list(org_id: &str) -> Result<Vec<EscalationPolicy>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let records = escalation_policies::Entity::find()
+        .filter(escalation_policies::Column::Org.eq(org_id))
+        .order_by_asc(escalation_policies::Column::Name)
+        .all(client)
+        .await?;
+    records
+        .into_iter()
+        .map(EscalationPolicy::try_from)
+        .collect()
+}
+
+/// Deletes the policy, returns false if it doesn't exist.
+pub async fn delete(org_id: &str, id: &str) -> Result<bool, errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let res = escalation_policies::Entity::delete_many()
+        .filter(escalation_policies::Column::Org.eq(org_id))
+        .filter(escalation_policies::Column::Id.eq(id))
+        .exec(client)
+        .await?;
+    Ok(res.rows_affected > 0)
+}
@@ -0,0 +1,150 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::meta::stream::{FileKey, FileMeta};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, Set, sea_query::OnConflict};
+
+use super::{entity::file_list_retired::*, get_lock};
+use crate::{
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
+    errors,
+};
+
+impl From<Model> for FileKey {
+    fn from(value: Model) -> Self {
+        FileKey::new(
+            value.id,
+            value.account,
+            value.file,
+            FileMeta {
+                min_ts: value.min_ts,
+                max_ts: value.max_ts,
+                records: value.records,
+                original_size: value.original_size,
+                compressed_size: value.compressed_size,
+                index_size: value.index_size,
+                flattened: value.flattened,
+            },
+            false,
+        )
+    }
+}
+
+/// Keeps the file list entries replaced by the compactor, so that queries which listed the
+/// files before the replacement can still resolve them by id.
+pub async fn batch_add(
+    org_id: &str,
+    retired_at: i64,
+    files: &[FileKey],
+) -> Result<(), errors::Error> {
+    let records = files
+        .iter()
+        .filter(|f| f.id > 0)
+        .map(|f| ActiveModel {
+            id: Set(f.id),
+            org: Set(org_id.to_string()),
+            account: Set(f.account.clone()),
+            file: Set(f.key.clone()),
+            min_ts: Set(f.meta.min_ts),
+            max_ts: Set(f.meta.max_ts),
+            records: Set(f.meta.records),
+            original_size: Set(f.meta.original_size),
+            compressed_size: Set(f.meta.compressed_size),
+            index_size: Set(f.meta.index_size),
+            flattened: Set(f.meta.flattened),
+            retired_at: Set(retired_at),
+        })
+        .collect::<Vec<_>>();
+    if records.is_empty() {
+        return Ok(());
+    }
+
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    for chunk in records.chunks(100) {
+        Entity::insert_many(chunk.to_vec())
+            .on_conflict(OnConflict::column(Column::Id).do_nothing().to_owned())
+            .exec_without_returning(client)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Returns the retired entries with the given file list ids.
+pub async fn query_by_ids(ids: &[i64]) -> Result<Vec<FileKey>, errors::Error> {
+    if ids.is_empty() {
+        return Ok(vec![]);
+    }
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let mut files = Vec::new();
+    for chunk in ids.chunks(1000) {
+        let records = Entity::find()
+            .filter(Column::Id.is_in(chunk.to_vec()))
+            .all(client)
+            .await?;
+        files.extend(records.into_iter().map(FileKey::from));
+    }
+    Ok(files)
+}
+
+/// Removes the entries of the org retired before `time_max`, their files are gone from storage.
+pub async fn clean(org_id: &str, time_max: i64) -> Result<u64, errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let res = Entity::delete_many()
+        .filter(Column::Org.eq(org_id))
+        .filter(Column::RetiredAt.lt(time_max))
+        .exec(client)
+        .await?;
+    Ok(res.rows_affected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retired_model_into_file_key() {
+        let model = Model {
+            id: 42,
+            org: "default".to_string(),
+            account: "acc".to_string(),
+            file: "files/default/logs/app/1.parquet".to_string(),
+            min_ts: 10,
+            max_ts: 20,
+            records: 5,
+            original_size: 100,
+            compressed_size: 50,
+            index_size: 7,
+            flattened: true,
+            retired_at: 30,
+        };
+        let file = FileKey::from(model);
+        assert_eq!(file.id, 42);
+        assert_eq!(file.account, "acc");
+        assert_eq!(file.key, "files/default/logs/app/1.parquet");
+        assert_eq!((file.meta.min_ts, file.meta.max_ts), (10, 20));
+        assert_eq!(file.meta.records, 5);
+        assert_eq!(file.meta.compressed_size, 50);
+        assert_eq!(file.meta.index_size, 7);
+        assert!(file.meta.flattened);
+        // the query still reads the retired file, it is not a deletion
+        assert!(!file.deleted);
+    }
+}
@@ -0,0 +1,188 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::str::FromStr;
+
+use config::meta::{
+    index_advisor::{IndexRecommendation, RecommendationKind, RecommendationStatus},
+    stream::StreamType,
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set, sea_query::Expr,
+};
+
+use super::{entity::index_recommendations::*, get_lock};
+use crate::{
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
+    errors::{self, Error},
+};
+
+impl TryFrom<Model> for IndexRecommendation {
+    type Error = errors::Error;
+
+    fn try_from(value: Model) -> Result<Self, Self::Error> {
+        Ok(IndexRecommendation {
+            id: value.id,
+            stream_type: StreamType::from(value.stream_type.as_str()),
+            stream_name: value.stream_name,
+            kind: RecommendationKind::from_str(&value.kind).map_err(Error::Message)?,
+            field: value.field,
+            reason: value.reason,
+            slow_queries: value.slow_queries,
+            queries: value.queries,
+            status: RecommendationStatus::from_str(&value.status).map_err(Error::Message)?,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        })
+    }
+}
+
+/// Creates the recommendation, or updates its reason and counts if a recommendation with the
+/// same id exists.
+pub async fn put(org_id: &str, rec: &IndexRecommendation) -> Result<(), errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let existing = Entity::find_by_id(&rec.id)
+        .filter(Column::Org.eq(org_id))
+        .one(client)
+        .await?;
+    if let Some(existing) = existing {
+        let mut record: ActiveModel = existing.into();
+        record.reason = Set(rec.reason.clone());
+        record.slow_queries = Set(rec.slow_queries);
+        record.queries = Set(rec.queries);
+        record.updated_at = Set(rec.updated_at);
+        record.update(client).await?;
+        return Ok(());
+    }
+
+    let record = ActiveModel {
+        id: Set(rec.id.clone()),
+        org: Set(org_id.to_string()),
+        stream_type: Set(rec.stream_type.to_string()),
+        stream_name: Set(rec.stream_name.clone()),
+        kind: Set(rec.kind.to_string()),
+        field: Set(rec.field.clone()),
+        reason: Set(rec.reason.clone()),
+        slow_queries: Set(rec.slow_queries),
+        queries: Set(rec.queries),
+        status: Set(rec.status.to_string()),
+        created_at: Set(rec.created_at),
+        updated_at: Set(rec.updated_at),
+    };
+    Entity::insert(record).exec(client).await?;
+    Ok(())
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<Option<IndexRecommendation>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let record = Entity::find_by_id(id)
+        .filter(Column::Org.eq(org_id))
+        .one(client)
+        .await?;
+    record.map(IndexRecommendation::try_from).transpose()
+}
+
+/// Lists the recommendations of the org, of the stream and with the status if given, the ones
+/// filtered on by the most slow queries first.
+pub async fn list(
+    org_id: &str,
+    stream: Option<(StreamType, &str)>,
+    status: Option<RecommendationStatus>,
+) -> Result<Vec<IndexRecommendation>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let mut query = Entity::find().filter(Column::Org.eq(org_id));
+    if let Some((stream_type, stream_name)) = stream {
+        query = query
+            .filter(Column::StreamType.eq(stream_type.to_string()))
+            .filter(Column::StreamName.eq(stream_name));
+    }
+    if let Some(status) = status {
+        query = query.filter(Column::Status.eq(status.to_string()));
+    }
+    let records = query
+        .order_by_desc(Column::SlowQueries)
+        .order_by_asc(Column::Id)
+        .all(client)
+        .await?;
+    records
+        .into_iter()
+        .map(IndexRecommendation::try_from)
+        .collect()
+}
+
+/// Lists the recommendations of all the organizations, keyed by org.
+pub async fn list_all() -> Result<Vec<(String, IndexRecommendation)>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let records = Entity::find().order_by_asc(Column::Org).all(client).await?;
+    records
+        .into_iter()
+        .map(|r| Ok((r.org.clone(), IndexRecommendation::try_from(r)?)))
+        .collect()
+}
+
+/// Sets the status of the recommendation, returns false if it doesn't exist.
+pub async fn set_status(
+    org_id: &str,
+    id: &str,
+    status: RecommendationStatus,
+    updated_at: i64,
+) -> Result<bool, errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let res = Entity::update_many()
+        .col_expr(Column::Status, Expr::value(status.to_string()))
+        .col_expr(Column::UpdatedAt, Expr::value(updated_at))
+        .filter(Column::Org.eq(org_id))
+        .filter(Column::Id.eq(id))
+        .exec(client)
+        .await?;
+    Ok(res.rows_affected > 0)
+}
+
+pub async fn delete(org_id: &str, id: &str) -> Result<(), errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    Entity::delete_many()
+        .filter(Column::Org.eq(org_id))
+        .filter(Column::Id.eq(id))
+        .exec(client)
+        .await?;
+    Ok(())
+}
+
+pub async fn delete_stream(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> Result<(), errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    Entity::delete_many()
+        .filter(Column::Org.eq(org_id))
+        .filter(Column::StreamType.eq(stream_type.to_string()))
+        .filter(Column::StreamName.eq(stream_name))
+        .exec(client)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,131 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::{meta::dashboards::library_panels::LibraryPanel, utils::json};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set, entity::prelude::*,
+};
+
+use super::{entity::library_panels::*, get_lock};
+use crate::{
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
+    errors::{self, DbError, Error},
+};
+
+impl TryFrom<Model> for LibraryPanel {
+    type Error = errors::Error;
+
+    fn try_from(value: Model) -> Result<Self, Self::Error> {
+        Ok(LibraryPanel {
+            id: value.id,
+            name: value.name,
+            description: value.description.unwrap_or_default(),
+            panel: json::from_value(value.panel)?,
+            owner: value.owner,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        })
+    }
+}
+
+/// Creates the library panel, or updates it if a library panel with the same id exists.
+pub async fn put(org_id: &str, library_panel: &LibraryPanel) -> Result<(), errors::Error> {
+    let panel = json::to_value(&library_panel.panel)?;
+
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let existing = Entity::find_by_id(&library_panel.id)
+        .filter(Column::Org.eq(org_id))
+        .one(client)
+        .await?;
+    if let Some(existing) = existing {
+        let mut record: ActiveModel = existing.into();
+        record.name = Set(library_panel.name.clone());
+        record.description = Set(Some(library_panel.description.clone()));
+        record.panel = Set(panel);
+        record.updated_at = Set(library_panel.updated_at);
+        record.update(client).await?;
+        return Ok(());
+    }
+
+    let record = ActiveModel {
+        id: Set(library_panel.id.clone()),
+        org: Set(org_id.to_string()),
+        name: Set(library_panel.name.clone()),
+        description: Set(Some(library_panel.description.clone())),
+        panel: Set(panel),
+        owner: Set(library_panel.owner.clone()),
+        created_at: Set(library_panel.created_at),
+        updated_at: Set(library_panel.updated_at),
+    };
+    match Entity::insert(record).exec(client).await {
+        Ok(_) => Ok(()),
+        Err(DbErr::Exec(RuntimeErr::SqlxError(SqlxError::Database(e))))
+            if e.is_unique_violation() =>
+        {
+            Err(Error::DbError(DbError::UniqueViolation))
+        }
+        Err(e) => Err(Error::DbError(DbError::SeaORMError(e.to_string()))),
+    }
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<Option<LibraryPanel>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let record = Entity::find_by_id(id)
+        .filter(Column::Org.eq(org_id))
+        .one(client)
+        .await?;
+    record.map(LibraryPanel::try_from).transpose()
+}
+
+/// Gets the library panels with the given ids, missing ids are skipped.
+pub async fn get_many(org_id: &str, ids: &[String]) -> Result<Vec<LibraryPanel>, errors::Error> {
+    if ids.is_empty() {
+        return Ok(vec![]);
+    }
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let records = Entity::find()
+        .filter(Column::Org.eq(org_id))
+        .filter(Column::Id.is_in(ids.to_vec()))
+        .all(client)
+        .await?;
+    records.into_iter().map(LibraryPanel::try_from).collect()
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<LibraryPanel>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let records = Entity::find()
+        .filter(Column::Org.eq(org_id))
+        .order_by_asc(Column::Name)
+        .all(client)
+        .await?;
+    records.into_iter().map(LibraryPanel::try_from).collect()
+}
+
+/// Deletes the library panel, returns false if it doesn't exist.
+pub async fn delete(org_id: &str, id: &str) -> Result<bool, errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let res = Entity::delete_many()
+        .filter(Column::Org.eq(org_id))
+        .filter(Column::Id.eq(id))
+        .exec(client)
+        .await?;
+    Ok(res.rows_affected > 0)
+}
@@ -0,0 +1,123 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use sea_orm_migration::prelude::*;
+
+const STREAM_HOURLY_STATS_STREAM_HOUR_IDX: &str = "stream_hourly_stats_stream_hour_idx";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(create_table_stmt()).await?;
+        manager
+            .create_index(create_index_stream_hour_stmt())
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(STREAM_HOURLY_STATS_STREAM_HOUR_IDX)
+                    .table(StreamHourlyStats::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(StreamHourlyStats::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+/// Statement to create table.
+fn create_table_stmt() -> TableCreateStatement {
+    Table::create()
+        .table(StreamHourlyStats::Table)
+        .if_not_exists()
+        .col(
+            ColumnDef::new(StreamHourlyStats::Id)
+                .big_integer()
+                .not_null()
+                .auto_increment()
+                .primary_key(),
+        )
+        .col(ColumnDef::new(StreamHourlyStats::Org).string_len(100).not_null())
+        .col(
+            ColumnDef::new(StreamHourlyStats::StreamType)
+                .string_len(32)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(StreamHourlyStats::StreamName)
+                .string_len(256)
+                .not_null(),
+        )
+        // Start of the hour in microseconds.
+        .col(ColumnDef::new(StreamHourlyStats::Hour).big_integer().not_null())
+        .col(
+            ColumnDef::new(StreamHourlyStats::Records)
+                .big_integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(StreamHourlyStats::OriginalSize)
+                .big_integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(StreamHourlyStats::CompressedSize)
+                .big_integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(StreamHourlyStats::ErrorRecords)
+                .big_integer()
+                .not_null(),
+        )
+        .to_owned()
+}
+
+/// Statement to create the unique index on stream and hour.
+fn create_index_stream_hour_stmt() -> IndexCreateStatement {
+    sea_query::Index::create()
+        .if_not_exists()
+        .name(STREAM_HOURLY_STATS_STREAM_HOUR_IDX)
+        .table(StreamHourlyStats::Table)
+        .col(StreamHourlyStats::Org)
+        .col(StreamHourlyStats::StreamType)
+        .col(StreamHourlyStats::StreamName)
+        .col(StreamHourlyStats::Hour)
+        .unique()
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum StreamHourlyStats {
+    Table,
+    Id,
+    Org,
+    StreamType,
+    StreamName,
+    Hour,
+    Records,
+    OriginalSize,
+    CompressedSize,
+    ErrorRecords,
+}
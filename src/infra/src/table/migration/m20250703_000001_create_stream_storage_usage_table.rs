@@ -0,0 +1,129 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use sea_orm_migration::prelude::*;
+
+const STREAM_STORAGE_USAGE_STREAM_DAY_IDX: &str = "stream_storage_usage_stream_day_idx";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(create_table_stmt()).await?;
+        manager.create_index(create_index_stream_day_stmt()).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(STREAM_STORAGE_USAGE_STREAM_DAY_IDX)
+                    .table(StreamStorageUsage::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(StreamStorageUsage::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+/// Statement to create table.
+fn create_table_stmt() -> TableCreateStatement {
+    Table::create()
+        .table(StreamStorageUsage::Table)
+        .if_not_exists()
+        .col(
+            ColumnDef::new(StreamStorageUsage::Id)
+                .big_integer()
+                .not_null()
+                .auto_increment()
+                .primary_key(),
+        )
+        .col(
+            ColumnDef::new(StreamStorageUsage::Org)
+                .string_len(100)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(StreamStorageUsage::StreamType)
+                .string_len(32)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(StreamStorageUsage::StreamName)
+                .string_len(256)
+                .not_null(),
+        )
+        // Start of the day in microseconds.
+        .col(
+            ColumnDef::new(StreamStorageUsage::Day)
+                .big_integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(StreamStorageUsage::DocNum)
+                .big_integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(StreamStorageUsage::StorageSize)
+                .big_integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(StreamStorageUsage::CompressedSize)
+                .big_integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(StreamStorageUsage::IndexSize)
+                .big_integer()
+                .not_null(),
+        )
+        .to_owned()
+}
+
+/// Statement to create the unique index on stream and day.
+fn create_index_stream_day_stmt() -> IndexCreateStatement {
+    sea_query::Index::create()
+        .if_not_exists()
+        .name(STREAM_STORAGE_USAGE_STREAM_DAY_IDX)
+        .table(StreamStorageUsage::Table)
+        .col(StreamStorageUsage::Org)
+        .col(StreamStorageUsage::StreamType)
+        .col(StreamStorageUsage::StreamName)
+        .col(StreamStorageUsage::Day)
+        .unique()
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum StreamStorageUsage {
+    Table,
+    Id,
+    Org,
+    StreamType,
+    StreamName,
+    Day,
+    DocNum,
+    StorageSize,
+    CompressedSize,
+    IndexSize,
+}
@@ -0,0 +1,92 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use sea_orm_migration::prelude::*;
+
+const LIBRARY_PANELS_ORG_NAME_IDX: &str = "library_panels_org_name_idx";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(create_table_stmt()).await?;
+        manager.create_index(create_index_org_name_stmt()).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(LIBRARY_PANELS_ORG_NAME_IDX)
+                    .table(LibraryPanels::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(LibraryPanels::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+/// Statement to create table.
+fn create_table_stmt() -> TableCreateStatement {
+    Table::create()
+        .table(LibraryPanels::Table)
+        .if_not_exists()
+        // The ID is 27-character human readable KSUID.
+        .col(
+            ColumnDef::new(LibraryPanels::Id)
+                .char_len(27)
+                .not_null()
+                .primary_key(),
+        )
+        .col(ColumnDef::new(LibraryPanels::Org).string_len(100).not_null())
+        .col(ColumnDef::new(LibraryPanels::Name).string_len(256).not_null())
+        .col(ColumnDef::new(LibraryPanels::Description).text().null())
+        .col(ColumnDef::new(LibraryPanels::Panel).json().not_null())
+        .col(ColumnDef::new(LibraryPanels::Owner).string_len(256).not_null())
+        .col(ColumnDef::new(LibraryPanels::CreatedAt).big_integer().not_null())
+        .col(ColumnDef::new(LibraryPanels::UpdatedAt).big_integer().not_null())
+        .to_owned()
+}
+
+/// Statement to create the unique index on org and name.
+fn create_index_org_name_stmt() -> IndexCreateStatement {
+    sea_query::Index::create()
+        .if_not_exists()
+        .name(LIBRARY_PANELS_ORG_NAME_IDX)
+        .table(LibraryPanels::Table)
+        .col(LibraryPanels::Org)
+        .col(LibraryPanels::Name)
+        .unique()
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum LibraryPanels {
+    Table,
+    Id,
+    Org,
+    Name,
+    Description,
+    Panel,
+    Owner,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,179 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use sea_orm_migration::prelude::*;
+
+const ALERT_SILENCES_ORG_IDX: &str = "alert_silences_org_idx";
+const SILENCED_NOTIFICATIONS_ORG_SILENCE_IDX: &str = "silenced_notifications_org_silence_idx";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(create_silences_table_stmt()).await?;
+        manager.create_index(create_silences_org_idx_stmt()).await?;
+        manager
+            .create_table(create_silenced_notifications_table_stmt())
+            .await?;
+        manager
+            .create_index(create_silenced_notifications_org_silence_idx_stmt())
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(SILENCED_NOTIFICATIONS_ORG_SILENCE_IDX)
+                    .table(SilencedNotifications::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(SilencedNotifications::Table).to_owned())
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(ALERT_SILENCES_ORG_IDX)
+                    .table(AlertSilences::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(AlertSilences::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+/// Statement to create the silences table.
+fn create_silences_table_stmt() -> TableCreateStatement {
+    Table::create()
+        .table(AlertSilences::Table)
+        .if_not_exists()
+        // The ID is 27-character human readable KSUID.
+        .col(
+            ColumnDef::new(AlertSilences::Id)
+                .char_len(27)
+                .not_null()
+                .primary_key(),
+        )
+        .col(ColumnDef::new(AlertSilences::Org).string_len(100).not_null())
+        .col(ColumnDef::new(AlertSilences::Matchers).json().not_null())
+        .col(ColumnDef::new(AlertSilences::StartTime).big_integer().not_null())
+        .col(ColumnDef::new(AlertSilences::EndTime).big_integer().not_null())
+        .col(ColumnDef::new(AlertSilences::Comment).text().null())
+        .col(ColumnDef::new(AlertSilences::CreatedBy).string_len(256).not_null())
+        .col(ColumnDef::new(AlertSilences::CreatedAt).big_integer().not_null())
+        .col(ColumnDef::new(AlertSilences::UpdatedAt).big_integer().not_null())
+        .to_owned()
+}
+
+/// Statement to create the index on org and end time, used to look up the active silences.
+fn create_silences_org_idx_stmt() -> IndexCreateStatement {
+    sea_query::Index::create()
+        .if_not_exists()
+        .name(ALERT_SILENCES_ORG_IDX)
+        .table(AlertSilences::Table)
+        .col(AlertSilences::Org)
+        .col(AlertSilences::EndTime)
+        .to_owned()
+}
+
+/// Statement to create the table of the notifications muted by a silence.
+fn create_silenced_notifications_table_stmt() -> TableCreateStatement {
+    Table::create()
+        .table(SilencedNotifications::Table)
+        .if_not_exists()
+        .col(
+            ColumnDef::new(SilencedNotifications::Id)
+                .big_integer()
+                .not_null()
+                .auto_increment()
+                .primary_key(),
+        )
+        .col(
+            ColumnDef::new(SilencedNotifications::Org)
+                .string_len(100)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(SilencedNotifications::SilenceId)
+                .char_len(27)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(SilencedNotifications::AlertId)
+                .char_len(27)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(SilencedNotifications::AlertName)
+                .string_len(256)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(SilencedNotifications::Rows)
+                .big_integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(SilencedNotifications::SilencedAt)
+                .big_integer()
+                .not_null(),
+        )
+        .to_owned()
+}
+
+/// Statement to create the index on org and silence id.
+fn create_silenced_notifications_org_silence_idx_stmt() -> IndexCreateStatement {
+    sea_query::Index::create()
+        .if_not_exists()
+        .name(SILENCED_NOTIFICATIONS_ORG_SILENCE_IDX)
+        .table(SilencedNotifications::Table)
+        .col(SilencedNotifications::Org)
+        .col(SilencedNotifications::SilenceId)
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum AlertSilences {
+    Table,
+    Id,
+    Org,
+    Matchers,
+    StartTime,
+    EndTime,
+    Comment,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum SilencedNotifications {
+    Table,
+    Id,
+    Org,
+    SilenceId,
+    AlertId,
+    AlertName,
+    Rows,
+    SilencedAt,
+}
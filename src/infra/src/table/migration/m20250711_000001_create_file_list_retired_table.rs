@@ -0,0 +1,119 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use sea_orm_migration::prelude::*;
+
+const FILE_LIST_RETIRED_ORG_IDX: &str = "file_list_retired_org_idx";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(create_table_stmt()).await?;
+        manager.create_index(create_org_idx_stmt()).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(FILE_LIST_RETIRED_ORG_IDX)
+                    .table(FileListRetired::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(FileListRetired::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+/// Statement to create the table of the file list entries replaced by the compactor.
+fn create_table_stmt() -> TableCreateStatement {
+    Table::create()
+        .table(FileListRetired::Table)
+        .if_not_exists()
+        // The ID is the id the file had in the file_list table.
+        .col(
+            ColumnDef::new(FileListRetired::Id)
+                .big_integer()
+                .not_null()
+                .primary_key(),
+        )
+        .col(ColumnDef::new(FileListRetired::Org).string_len(100).not_null())
+        .col(
+            ColumnDef::new(FileListRetired::Account)
+                .string_len(32)
+                .not_null(),
+        )
+        .col(ColumnDef::new(FileListRetired::File).string_len(1024).not_null())
+        .col(ColumnDef::new(FileListRetired::MinTs).big_integer().not_null())
+        .col(ColumnDef::new(FileListRetired::MaxTs).big_integer().not_null())
+        .col(ColumnDef::new(FileListRetired::Records).big_integer().not_null())
+        .col(
+            ColumnDef::new(FileListRetired::OriginalSize)
+                .big_integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(FileListRetired::CompressedSize)
+                .big_integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(FileListRetired::IndexSize)
+                .big_integer()
+                .not_null(),
+        )
+        .col(ColumnDef::new(FileListRetired::Flattened).boolean().not_null())
+        .col(
+            ColumnDef::new(FileListRetired::RetiredAt)
+                .big_integer()
+                .not_null(),
+        )
+        .to_owned()
+}
+
+/// Statement to create the index on org and retired time, used to clean up old entries.
+fn create_org_idx_stmt() -> IndexCreateStatement {
+    sea_query::Index::create()
+        .if_not_exists()
+        .name(FILE_LIST_RETIRED_ORG_IDX)
+        .table(FileListRetired::Table)
+        .col(FileListRetired::Org)
+        .col(FileListRetired::RetiredAt)
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum FileListRetired {
+    Table,
+    Id,
+    Org,
+    Account,
+    File,
+    MinTs,
+    MaxTs,
+    Records,
+    OriginalSize,
+    CompressedSize,
+    IndexSize,
+    Flattened,
+    RetiredAt,
+}
@@ -0,0 +1,114 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use sea_orm_migration::prelude::*;
+
+const STREAM_FIELD_USAGE_STREAM_FIELD_DAY_IDX: &str = "stream_field_usage_stream_field_day_idx";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(create_table_stmt()).await?;
+        manager
+            .create_index(create_index_stream_field_day_stmt())
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(STREAM_FIELD_USAGE_STREAM_FIELD_DAY_IDX)
+                    .table(StreamFieldUsage::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(StreamFieldUsage::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+/// Statement to create table.
+fn create_table_stmt() -> TableCreateStatement {
+    Table::create()
+        .table(StreamFieldUsage::Table)
+        .if_not_exists()
+        .col(
+            ColumnDef::new(StreamFieldUsage::Id)
+                .big_integer()
+                .not_null()
+                .auto_increment()
+                .primary_key(),
+        )
+        .col(ColumnDef::new(StreamFieldUsage::Org).string_len(100).not_null())
+        .col(
+            ColumnDef::new(StreamFieldUsage::StreamType)
+                .string_len(32)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(StreamFieldUsage::StreamName)
+                .string_len(256)
+                .not_null(),
+        )
+        .col(ColumnDef::new(StreamFieldUsage::Field).string_len(256).not_null())
+        // Start of the day in microseconds.
+        .col(ColumnDef::new(StreamFieldUsage::Day).big_integer().not_null())
+        .col(
+            ColumnDef::new(StreamFieldUsage::Queries)
+                .big_integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(StreamFieldUsage::LastQueried)
+                .big_integer()
+                .not_null(),
+        )
+        .to_owned()
+}
+
+/// Statement to create the unique index on stream, field and day.
+fn create_index_stream_field_day_stmt() -> IndexCreateStatement {
+    sea_query::Index::create()
+        .if_not_exists()
+        .name(STREAM_FIELD_USAGE_STREAM_FIELD_DAY_IDX)
+        .table(StreamFieldUsage::Table)
+        .col(StreamFieldUsage::Org)
+        .col(StreamFieldUsage::StreamType)
+        .col(StreamFieldUsage::StreamName)
+        .col(StreamFieldUsage::Field)
+        .col(StreamFieldUsage::Day)
+        .unique()
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum StreamFieldUsage {
+    Table,
+    Id,
+    Org,
+    StreamType,
+    StreamName,
+    Field,
+    Day,
+    Queries,
+    LastQueried,
+}
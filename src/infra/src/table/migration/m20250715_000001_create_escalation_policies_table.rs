@@ -0,0 +1,109 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Creates the escalation policies table.
+
+use sea_orm_migration::prelude::*;
+
+const ESCALATION_POLICIES_ORG_NAME_IDX: &str = "escalation_policies_org_name_idx";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(create_table_stmt()).await?;
+        manager.create_index(create_org_name_idx_stmt()).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(ESCALATION_POLICIES_ORG_NAME_IDX)
+                    .table(EscalationPolicies::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(EscalationPolicies::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+/// Statement to create the escalation policies table.
+fn create_table_stmt() -> TableCreateStatement {
+    Table::create()
+        .table(EscalationPolicies::Table)
+        .if_not_exists()
+        // The ID is 27-character human readable KSUID.
+        .col(
+            ColumnDef::new(EscalationPolicies::Id)
+                .char_len(27)
+                .not_null()
+                .primary_key(),
+        )
+        .col(
+            ColumnDef::new(EscalationPolicies::Org)
+                .string_len(100)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(EscalationPolicies::Name)
+                .string_len(256)
+                .not_null(),
+        )
+        .col(ColumnDef::new(EscalationPolicies::Description).text().null())
+        .col(ColumnDef::new(EscalationPolicies::Steps).json().not_null())
+        .col(
+            ColumnDef::new(EscalationPolicies::CreatedAt)
+                .big_integer()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(EscalationPolicies::UpdatedAt)
+                .big_integer()
+                .not_null(),
+        )
+        .to_owned()
+}
+
+/// Statement to create the unique index on the org and the name of the policies.
+fn create_org_name_idx_stmt() -> IndexCreateStatement {
+    sea_query::Index::create()
+        .if_not_exists()
+        .name(ESCALATION_POLICIES_ORG_NAME_IDX)
+        .table(EscalationPolicies::Table)
+        .col(EscalationPolicies::Org)
+        .col(EscalationPolicies::Name)
+        .unique()
+        .to_owned()
+}
+
+/// Identifiers used in queries on the escalation policies table.
+#[derive(DeriveIden)]
+enum EscalationPolicies {
+    Table,
+    Id,
+    Org,
+    Name,
+    Description,
+    Steps,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,111 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Creates the slow queries table, the log of slow queries analyzed by the index advisor.
+
+use sea_orm_migration::prelude::*;
+
+use super::get_text_type;
+
+const SLOW_QUERIES_STREAM_CREATED_AT_IDX: &str = "slow_queries_stream_created_at_idx";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(create_table_stmt()).await?;
+        manager
+            .create_index(create_index_stream_created_at_stmt())
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(SLOW_QUERIES_STREAM_CREATED_AT_IDX)
+                    .table(SlowQueries::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(SlowQueries::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+/// Statement to create table.
+fn create_table_stmt() -> TableCreateStatement {
+    let text_type = get_text_type();
+    Table::create()
+        .table(SlowQueries::Table)
+        .if_not_exists()
+        .col(
+            ColumnDef::new(SlowQueries::Id)
+                .big_integer()
+                .not_null()
+                .auto_increment()
+                .primary_key(),
+        )
+        .col(ColumnDef::new(SlowQueries::Org).string_len(100).not_null())
+        .col(ColumnDef::new(SlowQueries::StreamType).string_len(32).not_null())
+        .col(ColumnDef::new(SlowQueries::StreamName).string_len(256).not_null())
+        .col(ColumnDef::new(SlowQueries::TraceId).string_len(256).not_null())
+        .col(ColumnDef::new(SlowQueries::UserId).string_len(256).null())
+        .col(
+            ColumnDef::new(SlowQueries::Sql)
+                .custom(Alias::new(&text_type))
+                .not_null(),
+        )
+        // Duration of the query in milliseconds.
+        .col(ColumnDef::new(SlowQueries::Took).big_integer().not_null())
+        .col(ColumnDef::new(SlowQueries::EqualItems).json().not_null())
+        .col(ColumnDef::new(SlowQueries::TextSearchFields).json().not_null())
+        .col(ColumnDef::new(SlowQueries::CreatedAt).big_integer().not_null())
+        .to_owned()
+}
+
+/// Statement to create the index on stream and creation time.
+fn create_index_stream_created_at_stmt() -> IndexCreateStatement {
+    sea_query::Index::create()
+        .if_not_exists()
+        .name(SLOW_QUERIES_STREAM_CREATED_AT_IDX)
+        .table(SlowQueries::Table)
+        .col(SlowQueries::Org)
+        .col(SlowQueries::StreamType)
+        .col(SlowQueries::StreamName)
+        .col(SlowQueries::CreatedAt)
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum SlowQueries {
+    Table,
+    Id,
+    Org,
+    StreamType,
+    StreamName,
+    TraceId,
+    UserId,
+    Sql,
+    Took,
+    EqualItems,
+    TextSearchFields,
+    CreatedAt,
+}
@@ -0,0 +1,109 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Creates the index recommendations table, the stream settings recommended by the index
+//! advisor.
+
+use sea_orm_migration::prelude::*;
+
+const INDEX_RECOMMENDATIONS_STREAM_KIND_FIELD_IDX: &str =
+    "index_recommendations_stream_kind_field_idx";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(create_table_stmt()).await?;
+        manager
+            .create_index(create_index_stream_kind_field_stmt())
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(INDEX_RECOMMENDATIONS_STREAM_KIND_FIELD_IDX)
+                    .table(IndexRecommendations::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(IndexRecommendations::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+/// Statement to create table.
+fn create_table_stmt() -> TableCreateStatement {
+    Table::create()
+        .table(IndexRecommendations::Table)
+        .if_not_exists()
+        // The ID is 27-character human readable KSUID.
+        .col(
+            ColumnDef::new(IndexRecommendations::Id)
+                .char_len(27)
+                .not_null()
+                .primary_key(),
+        )
+        .col(ColumnDef::new(IndexRecommendations::Org).string_len(100).not_null())
+        .col(ColumnDef::new(IndexRecommendations::StreamType).string_len(32).not_null())
+        .col(ColumnDef::new(IndexRecommendations::StreamName).string_len(256).not_null())
+        .col(ColumnDef::new(IndexRecommendations::Kind).string_len(32).not_null())
+        .col(ColumnDef::new(IndexRecommendations::Field).string_len(256).not_null())
+        .col(ColumnDef::new(IndexRecommendations::Reason).text().not_null())
+        .col(ColumnDef::new(IndexRecommendations::SlowQueries).big_integer().not_null())
+        .col(ColumnDef::new(IndexRecommendations::Queries).big_integer().not_null())
+        .col(ColumnDef::new(IndexRecommendations::Status).string_len(16).not_null())
+        .col(ColumnDef::new(IndexRecommendations::CreatedAt).big_integer().not_null())
+        .col(ColumnDef::new(IndexRecommendations::UpdatedAt).big_integer().not_null())
+        .to_owned()
+}
+
+/// Statement to create the unique index on stream, kind and field.
+fn create_index_stream_kind_field_stmt() -> IndexCreateStatement {
+    sea_query::Index::create()
+        .if_not_exists()
+        .name(INDEX_RECOMMENDATIONS_STREAM_KIND_FIELD_IDX)
+        .table(IndexRecommendations::Table)
+        .col(IndexRecommendations::Org)
+        .col(IndexRecommendations::StreamType)
+        .col(IndexRecommendations::StreamName)
+        .col(IndexRecommendations::Kind)
+        .col(IndexRecommendations::Field)
+        .unique()
+        .to_owned()
+}
+
+#[derive(DeriveIden)]
+enum IndexRecommendations {
+    Table,
+    Id,
+    Org,
+    StreamType,
+    StreamName,
+    Kind,
+    Field,
+    Reason,
+    SlowQueries,
+    Queries,
+    Status,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,97 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Creates the workspaces table.
+
+use sea_orm_migration::prelude::*;
+
+const WORKSPACES_ORG_NAME_IDX: &str = "workspaces_org_name_idx";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(create_table_stmt()).await?;
+        manager.create_index(create_org_name_idx_stmt()).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(WORKSPACES_ORG_NAME_IDX)
+                    .table(Workspaces::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Workspaces::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+/// Statement to create the workspaces table.
+fn create_table_stmt() -> TableCreateStatement {
+    Table::create()
+        .table(Workspaces::Table)
+        .if_not_exists()
+        // The ID is 27-character human readable KSUID.
+        .col(
+            ColumnDef::new(Workspaces::Id)
+                .char_len(27)
+                .not_null()
+                .primary_key(),
+        )
+        .col(ColumnDef::new(Workspaces::Org).string_len(100).not_null())
+        .col(ColumnDef::new(Workspaces::Name).string_len(256).not_null())
+        .col(ColumnDef::new(Workspaces::Description).text().null())
+        .col(
+            ColumnDef::new(Workspaces::CreatedBy)
+                .string_len(256)
+                .not_null(),
+        )
+        .col(ColumnDef::new(Workspaces::CreatedAt).big_integer().not_null())
+        .col(ColumnDef::new(Workspaces::UpdatedAt).big_integer().not_null())
+        .to_owned()
+}
+
+/// Statement to create the unique index on the org and the name of the workspaces.
+fn create_org_name_idx_stmt() -> IndexCreateStatement {
+    sea_query::Index::create()
+        .if_not_exists()
+        .name(WORKSPACES_ORG_NAME_IDX)
+        .table(Workspaces::Table)
+        .col(Workspaces::Org)
+        .col(Workspaces::Name)
+        .unique()
+        .to_owned()
+}
+
+/// Identifiers used in queries on the workspaces table.
+#[derive(DeriveIden)]
+enum Workspaces {
+    Table,
+    Id,
+    Org,
+    Name,
+    Description,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
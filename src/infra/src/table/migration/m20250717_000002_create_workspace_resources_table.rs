@@ -0,0 +1,144 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Creates the workspace resources table, the streams, dashboards and alerts which belong to a
+//! workspace or are shared with it.
+
+use sea_orm_migration::prelude::*;
+
+const WORKSPACE_RESOURCES_WORKSPACE_RESOURCE_IDX: &str =
+    "workspace_resources_workspace_resource_idx";
+const WORKSPACE_RESOURCES_ORG_RESOURCE_IDX: &str = "workspace_resources_org_resource_idx";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(create_table_stmt()).await?;
+        manager
+            .create_index(create_workspace_resource_idx_stmt())
+            .await?;
+        manager.create_index(create_org_resource_idx_stmt()).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(WORKSPACE_RESOURCES_ORG_RESOURCE_IDX)
+                    .table(WorkspaceResources::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(WORKSPACE_RESOURCES_WORKSPACE_RESOURCE_IDX)
+                    .table(WorkspaceResources::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(WorkspaceResources::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+/// Statement to create the workspace resources table.
+fn create_table_stmt() -> TableCreateStatement {
+    Table::create()
+        .table(WorkspaceResources::Table)
+        .if_not_exists()
+        // The ID is 27-character human readable KSUID.
+        .col(
+            ColumnDef::new(WorkspaceResources::Id)
+                .char_len(27)
+                .not_null()
+                .primary_key(),
+        )
+        .col(
+            ColumnDef::new(WorkspaceResources::Org)
+                .string_len(100)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(WorkspaceResources::WorkspaceId)
+                .char_len(27)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(WorkspaceResources::ResourceType)
+                .string_len(32)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(WorkspaceResources::ResourceId)
+                .string_len(512)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(WorkspaceResources::Shared)
+                .boolean()
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(WorkspaceResources::CreatedAt)
+                .big_integer()
+                .not_null(),
+        )
+        .to_owned()
+}
+
+/// Statement to create the unique index on the resources of a workspace.
+fn create_workspace_resource_idx_stmt() -> IndexCreateStatement {
+    sea_query::Index::create()
+        .if_not_exists()
+        .name(WORKSPACE_RESOURCES_WORKSPACE_RESOURCE_IDX)
+        .table(WorkspaceResources::Table)
+        .col(WorkspaceResources::WorkspaceId)
+        .col(WorkspaceResources::ResourceType)
+        .col(WorkspaceResources::ResourceId)
+        .unique()
+        .to_owned()
+}
+
+/// Statement to create the index used to find the workspaces of a resource.
+fn create_org_resource_idx_stmt() -> IndexCreateStatement {
+    sea_query::Index::create()
+        .if_not_exists()
+        .name(WORKSPACE_RESOURCES_ORG_RESOURCE_IDX)
+        .table(WorkspaceResources::Table)
+        .col(WorkspaceResources::Org)
+        .col(WorkspaceResources::ResourceType)
+        .col(WorkspaceResources::ResourceId)
+        .to_owned()
+}
+
+/// Identifiers used in queries on the workspace resources table.
+#[derive(DeriveIden)]
+enum WorkspaceResources {
+    Table,
+    Id,
+    Org,
+    WorkspaceId,
+    ResourceType,
+    ResourceId,
+    Shared,
+    CreatedAt,
+}
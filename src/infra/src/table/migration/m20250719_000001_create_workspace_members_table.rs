@@ -0,0 +1,101 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Creates the workspace members table, the users the resources of a workspace are restricted
+//! to.
+
+use sea_orm_migration::prelude::*;
+
+const WORKSPACE_MEMBERS_WORKSPACE_USER_IDX: &str = "workspace_members_workspace_user_idx";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(create_table_stmt()).await?;
+        manager
+            .create_index(create_workspace_user_idx_stmt())
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name(WORKSPACE_MEMBERS_WORKSPACE_USER_IDX)
+                    .table(WorkspaceMembers::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(WorkspaceMembers::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+/// Statement to create the workspace members table.
+fn create_table_stmt() -> TableCreateStatement {
+    Table::create()
+        .table(WorkspaceMembers::Table)
+        .if_not_exists()
+        // The ID is 27-character human readable KSUID.
+        .col(
+            ColumnDef::new(WorkspaceMembers::Id)
+                .char_len(27)
+                .not_null()
+                .primary_key(),
+        )
+        .col(
+            ColumnDef::new(WorkspaceMembers::Org)
+                .string_len(100)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(WorkspaceMembers::WorkspaceId)
+                .char_len(27)
+                .not_null(),
+        )
+        .col(
+            ColumnDef::new(WorkspaceMembers::UserEmail)
+                .string_len(256)
+                .not_null(),
+        )
+        .to_owned()
+}
+
+/// Statement to create the unique index on the members of a workspace.
+fn create_workspace_user_idx_stmt() -> IndexCreateStatement {
+    sea_query::Index::create()
+        .if_not_exists()
+        .name(WORKSPACE_MEMBERS_WORKSPACE_USER_IDX)
+        .table(WorkspaceMembers::Table)
+        .col(WorkspaceMembers::WorkspaceId)
+        .col(WorkspaceMembers::UserEmail)
+        .unique()
+        .to_owned()
+}
+
+/// Identifiers used in queries on the workspace members table.
+#[derive(DeriveIden)]
+enum WorkspaceMembers {
+    Table,
+    Id,
+    Org,
+    WorkspaceId,
+    UserEmail,
+}
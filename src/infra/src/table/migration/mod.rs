@@ -57,6 +57,29 @@ mod m20250422_000001_add_alert_align_time;
 mod m20250611_000001_create_reports_table;
 mod m20250611_000002_populate_reports_table;
 mod m20250611_000003_populate_reports_scheduled_jobs;
+mod m20250701_000001_add_alert_correlation;
+mod m20250702_000001_create_stream_hourly_stats_table;
+mod m20250703_000001_create_stream_storage_usage_table;
+mod m20250704_000001_create_library_panels_table;
+mod m20250705_000001_add_report_panel_data;
+mod m20250706_000001_add_dashboard_acl;
+mod m20250707_000001_add_alert_panel_source;
+mod m20250708_000001_add_alert_composite;
+mod m20250709_000001_add_destination_grouping;
+mod m20250710_000001_create_alert_silences_table;
+mod m20250711_000001_create_file_list_retired_table;
+mod m20250712_000001_add_alert_multi_condition;
+mod m20250713_000001_add_alert_anomaly;
+mod m20250714_000001_create_stream_field_usage_table;
+mod m20250715_000001_create_escalation_policies_table;
+mod m20250715_000002_add_alert_escalation_policy;
+mod m20250716_000001_create_slow_queries_table;
+mod m20250716_000002_create_index_recommendations_table;
+mod m20250717_000001_create_workspaces_table;
+mod m20250717_000002_create_workspace_resources_table;
+mod m20250718_000001_add_destination_resolved_template;
+mod m20250718_000002_add_report_digest;
+mod m20250719_000001_create_workspace_members_table;
 
 pub struct Migrator;
 
@@ -106,6 +129,29 @@ impl MigratorTrait for Migrator {
             Box::new(m20250611_000001_create_reports_table::Migration),
             Box::new(m20250611_000002_populate_reports_table::Migration),
             Box::new(m20250611_000003_populate_reports_scheduled_jobs::Migration),
+            Box::new(m20250701_000001_add_alert_correlation::Migration),
+            Box::new(m20250702_000001_create_stream_hourly_stats_table::Migration),
+            Box::new(m20250703_000001_create_stream_storage_usage_table::Migration),
+            Box::new(m20250704_000001_create_library_panels_table::Migration),
+            Box::new(m20250705_000001_add_report_panel_data::Migration),
+            Box::new(m20250706_000001_add_dashboard_acl::Migration),
+            Box::new(m20250707_000001_add_alert_panel_source::Migration),
+            Box::new(m20250708_000001_add_alert_composite::Migration),
+            Box::new(m20250709_000001_add_destination_grouping::Migration),
+            Box::new(m20250710_000001_create_alert_silences_table::Migration),
+            Box::new(m20250711_000001_create_file_list_retired_table::Migration),
+            Box::new(m20250712_000001_add_alert_multi_condition::Migration),
+            Box::new(m20250713_000001_add_alert_anomaly::Migration),
+            Box::new(m20250714_000001_create_stream_field_usage_table::Migration),
+            Box::new(m20250715_000001_create_escalation_policies_table::Migration),
+            Box::new(m20250715_000002_add_alert_escalation_policy::Migration),
+            Box::new(m20250716_000001_create_slow_queries_table::Migration),
+            Box::new(m20250716_000002_create_index_recommendations_table::Migration),
+            Box::new(m20250717_000001_create_workspaces_table::Migration),
+            Box::new(m20250717_000002_create_workspace_resources_table::Migration),
+            Box::new(m20250718_000001_add_destination_resolved_template::Migration),
+            Box::new(m20250718_000002_add_report_digest::Migration),
+            Box::new(m20250719_000001_create_workspace_members_table::Migration),
         ]
     }
 }
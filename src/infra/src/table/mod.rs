@@ -23,6 +23,7 @@ use crate::{
 };
 
 pub mod action_scripts;
+pub mod alert_silences;
 pub mod alerts;
 pub mod cipher;
 pub mod dashboards;
@@ -30,7 +31,11 @@ pub mod destinations;
 pub mod distinct_values;
 #[allow(unused_imports)]
 pub mod entity;
+pub mod escalation_policies;
+pub mod file_list_retired;
 pub mod folders;
+pub mod index_recommendations;
+pub mod library_panels;
 mod migration;
 pub mod org_users;
 pub mod organizations;
@@ -39,10 +44,15 @@ pub mod reports;
 pub mod search_job;
 pub mod search_queue;
 pub mod short_urls;
+pub mod slow_queries;
+pub mod stream_field_usage;
+pub mod stream_hourly_stats;
+pub mod stream_storage_usage;
 pub mod templates;
 pub mod timed_annotation_panels;
 pub mod timed_annotations;
 pub mod users;
+pub mod workspaces;
 
 pub async fn init() -> Result<(), anyhow::Error> {
     distinct_values::init().await?;
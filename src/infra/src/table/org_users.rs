@@ -36,7 +36,7 @@ use super::{
     get_lock,
 };
 use crate::{
-    db::{ORM_CLIENT, connect_to_orm},
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
     errors::{self, DbError, Error},
 };
 
@@ -324,7 +324,7 @@ pub async fn update_token(org_id: &str, email: &str, token: &str) -> Result<(),
 }
 
 pub async fn get(org_id: &str, email: &str) -> Result<OrgUserRecord, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let record = Entity::find()
         .filter(Column::OrgId.eq(org_id))
         .filter(Column::Email.eq(email))
@@ -340,7 +340,7 @@ pub async fn get_expanded_user_org(
     org_id: &str,
     email: &str,
 ) -> Result<OrgUserExpandedRecord, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let record = Entity::find()
         .filter(Column::OrgId.eq(org_id))
         .filter(Column::Email.eq(email))
@@ -375,7 +375,7 @@ pub async fn get_user_by_rum_token(
     org_id: &str,
     rum_token: &str,
 ) -> Result<OrgUserExpandedRecord, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let record = Entity::find()
         .filter(Column::RumToken.eq(rum_token))
         .filter(Column::OrgId.eq(org_id))
@@ -406,7 +406,7 @@ pub async fn get_user_by_rum_token(
 }
 
 pub async fn list_users_by_org(org_id: &str) -> Result<Vec<OrgUserRecord>, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let records = Entity::find()
         .filter(Column::OrgId.eq(org_id))
         .all(client)
@@ -420,7 +420,7 @@ pub async fn list_users_by_org(org_id: &str) -> Result<Vec<OrgUserRecord>, error
 }
 
 pub async fn list_orgs_by_user(email: &str) -> Result<Vec<UserOrgExpandedRecord>, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let records = Entity::find()
         .filter(Column::Email.eq(email))
         .order_by(Column::CreatedAt, Order::Desc)
@@ -443,7 +443,7 @@ pub async fn list_orgs_by_user(email: &str) -> Result<Vec<UserOrgExpandedRecord>
 }
 
 pub async fn list(limit: Option<i64>) -> Result<Vec<OrgUserRecord>, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let mut res = Entity::find().order_by(Column::CreatedAt, Order::Desc);
     if let Some(limit) = limit {
         res = res.limit(limit as u64);
@@ -460,7 +460,7 @@ pub async fn list(limit: Option<i64>) -> Result<Vec<OrgUserRecord>, errors::Erro
 }
 
 pub async fn len() -> usize {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let len = Entity::find().count(client).await;
 
     match len {
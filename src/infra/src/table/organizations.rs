@@ -24,7 +24,7 @@ use super::{
     get_lock,
 };
 use crate::{
-    db::{ORM_CLIENT, connect_to_orm},
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
     errors::{self, DbError, Error},
 };
 
@@ -138,7 +138,7 @@ pub async fn remove(org_id: &str) -> Result<(), errors::Error> {
 }
 
 pub async fn get(org_id: &str) -> Result<OrganizationRecord, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let record = Entity::find()
         .filter(Column::Identifier.eq(org_id))
         .one(client)
@@ -152,7 +152,7 @@ pub async fn get(org_id: &str) -> Result<OrganizationRecord, errors::Error> {
 }
 
 pub async fn list(limit: Option<i64>) -> Result<Vec<OrganizationRecord>, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let mut res = Entity::find().order_by(Column::CreatedAt, Order::Desc);
     if let Some(limit) = limit {
         res = res.limit(limit as u64);
@@ -169,7 +169,7 @@ pub async fn list(limit: Option<i64>) -> Result<Vec<OrganizationRecord>, errors:
 }
 
 pub async fn get_by_name(org_name: &str) -> Result<Vec<OrganizationRecord>, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let records = Entity::find()
         .filter(Column::OrgName.eq(org_name))
         .all(client)
@@ -183,7 +183,7 @@ pub async fn get_by_name(org_name: &str) -> Result<Vec<OrganizationRecord>, erro
 }
 
 pub async fn len() -> usize {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let len = Entity::find().count(client).await;
 
     match len {
@@ -26,7 +26,7 @@ use sea_orm::{
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    db::{ORM_CLIENT, connect_to_orm},
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
     orm_err,
     table::{
         entity::rate_limit_rules::{ActiveModel, Column, Entity},
@@ -57,7 +57,7 @@ pub async fn fetch_rules(
     org_id: Option<String>,
     user_role: Option<String>,
 ) -> Result<Vec<RatelimitRule>, anyhow::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let mut res = Entity::find()
         .select_only()
         .column(Column::Org)
@@ -89,7 +89,7 @@ pub async fn fetch_rules(
 }
 
 pub async fn fetch_rules_by_id(rule_id: &str) -> Result<Option<RatelimitRule>, anyhow::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let res = Entity::find()
         .select_only()
         .column(Column::Org)
@@ -513,7 +513,7 @@ pub async fn list(
     api: Option<&str>,
     user_id: Option<&str>,
 ) -> Result<Vec<RatelimitRule>, anyhow::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let mut query = Entity::find()
         .select_only()
         .column(Column::Org)
@@ -21,10 +21,13 @@ use std::num::TryFromIntError;
 use config::meta::{
     alerts::default_align_time,
     dashboards::reports::{
+        DigestPeriod as MetaDigestPeriod, DigestSection as MetaDigestSection,
         ReportDashboardVariable as MetaReportDashboardVariable,
-        ReportDestination as MetaReportDestination, ReportFrequency as MetaReportFrequency,
-        ReportFrequencyType as MetaReportFrequencyType, ReportTimerange as MetaReportTimeRange,
-        ReportTimerangeType as MetaReportTimeRangeType,
+        ReportDataFormat as MetaReportDataFormat, ReportDestination as MetaReportDestination,
+        ReportDigest as MetaReportDigest, ReportFrequency as MetaReportFrequency,
+        ReportFrequencyType as MetaReportFrequencyType, ReportPanelData as MetaReportPanelData,
+        ReportTimerange as MetaReportTimeRange, ReportTimerangeType as MetaReportTimeRangeType,
+        ReportWebhook as MetaReportWebhook,
     },
 };
 use serde::{Deserialize, Serialize};
@@ -48,12 +51,18 @@ impl From<Vec<MetaReportDestination>> for ReportDestinations {
 #[serde(rename_all = "snake_case")]
 pub enum ReportDestination {
     Email(String),
+    Storage(String),
+    Slack(ReportWebhook),
+    Teams(ReportWebhook),
 }
 
 impl From<ReportDestination> for MetaReportDestination {
     fn from(value: ReportDestination) -> Self {
         match value {
             ReportDestination::Email(email) => Self::Email(email),
+            ReportDestination::Storage(path) => Self::Storage(path),
+            ReportDestination::Slack(webhook) => Self::Slack(webhook.into()),
+            ReportDestination::Teams(webhook) => Self::Teams(webhook.into()),
         }
     }
 }
@@ -62,6 +71,34 @@ impl From<MetaReportDestination> for ReportDestination {
     fn from(value: MetaReportDestination) -> Self {
         match value {
             MetaReportDestination::Email(email) => Self::Email(email),
+            MetaReportDestination::Storage(path) => Self::Storage(path),
+            MetaReportDestination::Slack(webhook) => Self::Slack(webhook.into()),
+            MetaReportDestination::Teams(webhook) => Self::Teams(webhook.into()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ReportWebhook {
+    pub url: String,
+    #[serde(default)]
+    pub template: String,
+}
+
+impl From<ReportWebhook> for MetaReportWebhook {
+    fn from(value: ReportWebhook) -> Self {
+        Self {
+            url: value.url,
+            template: value.template,
+        }
+    }
+}
+
+impl From<MetaReportWebhook> for ReportWebhook {
+    fn from(value: MetaReportWebhook) -> Self {
+        Self {
+            url: value.url,
+            template: value.template,
         }
     }
 }
@@ -260,3 +297,107 @@ impl From<MetaReportTimeRange> for ReportTimerange {
         }
     }
 }
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportDataFormat {
+    Csv,
+    Xlsx,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ReportPanelData {
+    pub format: ReportDataFormat,
+    pub max_rows: i64,
+    pub data_only: bool,
+}
+
+impl From<ReportPanelData> for MetaReportPanelData {
+    fn from(value: ReportPanelData) -> Self {
+        Self {
+            format: match value.format {
+                ReportDataFormat::Csv => MetaReportDataFormat::Csv,
+                ReportDataFormat::Xlsx => MetaReportDataFormat::Xlsx,
+            },
+            max_rows: value.max_rows,
+            data_only: value.data_only,
+        }
+    }
+}
+
+impl From<MetaReportPanelData> for ReportPanelData {
+    fn from(value: MetaReportPanelData) -> Self {
+        Self {
+            format: match value.format {
+                MetaReportDataFormat::Csv => ReportDataFormat::Csv,
+                MetaReportDataFormat::Xlsx => ReportDataFormat::Xlsx,
+            },
+            max_rows: value.max_rows,
+            data_only: value.data_only,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestPeriod {
+    Daily,
+    Weekly,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestSection {
+    AlertActivity,
+    ErrorRateMovers,
+    StorageGrowth,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ReportDigest {
+    pub period: DigestPeriod,
+    pub sections: Vec<DigestSection>,
+    pub top_n: usize,
+}
+
+impl From<ReportDigest> for MetaReportDigest {
+    fn from(value: ReportDigest) -> Self {
+        Self {
+            period: match value.period {
+                DigestPeriod::Daily => MetaDigestPeriod::Daily,
+                DigestPeriod::Weekly => MetaDigestPeriod::Weekly,
+            },
+            sections: value
+                .sections
+                .into_iter()
+                .map(|s| match s {
+                    DigestSection::AlertActivity => MetaDigestSection::AlertActivity,
+                    DigestSection::ErrorRateMovers => MetaDigestSection::ErrorRateMovers,
+                    DigestSection::StorageGrowth => MetaDigestSection::StorageGrowth,
+                })
+                .collect(),
+            top_n: value.top_n,
+        }
+    }
+}
+
+impl From<MetaReportDigest> for ReportDigest {
+    fn from(value: MetaReportDigest) -> Self {
+        Self {
+            period: match value.period {
+                MetaDigestPeriod::Daily => DigestPeriod::Daily,
+                MetaDigestPeriod::Weekly => DigestPeriod::Weekly,
+            },
+            sections: value
+                .sections
+                .into_iter()
+                .map(|s| match s {
+                    MetaDigestSection::AlertActivity => DigestSection::AlertActivity,
+                    MetaDigestSection::ErrorRateMovers => DigestSection::ErrorRateMovers,
+                    MetaDigestSection::StorageGrowth => DigestSection::StorageGrowth,
+                })
+                .collect(),
+            top_n: value.top_n,
+        }
+    }
+}
@@ -137,6 +137,16 @@ pub async fn create_report<C: ConnectionTrait + TransactionTrait>(
     let destinations_intermediate: intermediate::ReportDestinations =
         report.destinations.clone().into();
     let destinations_json = serde_json::to_value(destinations_intermediate)?;
+    let panel_data_json = report
+        .panel_data
+        .clone()
+        .map(|p| serde_json::to_value(intermediate::ReportPanelData::from(p)))
+        .transpose()?;
+    let digest_json = report
+        .digest
+        .clone()
+        .map(|d| serde_json::to_value(intermediate::ReportDigest::from(d)))
+        .transpose()?;
 
     // Create the new `report` record.
     let report_active_model = reports::ActiveModel {
@@ -157,6 +167,8 @@ pub async fn create_report<C: ConnectionTrait + TransactionTrait>(
         created_at: Set(now),
         updated_at: Set(Some(now)),
         start_at: Set(report.start),
+        panel_data: Set(panel_data_json),
+        digest: Set(digest_json),
     };
     let report_model = report_active_model.insert(&txn).await?;
 
@@ -241,6 +253,16 @@ pub async fn update_report<C: ConnectionTrait + TransactionTrait>(
     let destinations_intermediate: intermediate::ReportDestinations =
         report.destinations.clone().into();
     let destinations_json = serde_json::to_value(destinations_intermediate)?;
+    let panel_data_json = report
+        .panel_data
+        .clone()
+        .map(|p| serde_json::to_value(intermediate::ReportPanelData::from(p)))
+        .transpose()?;
+    let digest_json = report
+        .digest
+        .clone()
+        .map(|d| serde_json::to_value(intermediate::ReportDigest::from(d)))
+        .transpose()?;
 
     // Update the `reports` record.
     let report_active_model = reports::ActiveModel {
@@ -261,6 +283,8 @@ pub async fn update_report<C: ConnectionTrait + TransactionTrait>(
         created_at: NotSet, // Never updated after creation.
         updated_at: Set(Some(Utc::now().timestamp_micros())),
         start_at: Set(report.start),
+        panel_data: Set(panel_data_json),
+        digest: Set(digest_json),
     };
     report_active_model.update(&txn).await?;
 
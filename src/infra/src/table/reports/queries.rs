@@ -229,6 +229,16 @@ impl TryFrom<SelectReportAndJoinRelationsResult> for (MetaFolder, MetaReport) {
         let destinations_intermediate: intermediate::ReportDestinations =
             serde_json::from_value(report_model.destinations)?;
         let destinations: Vec<MetaReportDestination> = destinations_intermediate.into();
+        let panel_data = report_model
+            .panel_data
+            .map(serde_json::from_value::<intermediate::ReportPanelData>)
+            .transpose()?
+            .map(|p| p.into());
+        let digest = report_model
+            .digest
+            .map(serde_json::from_value::<intermediate::ReportDigest>)
+            .transpose()?
+            .map(|d| d.into());
 
         // Transform the Unix timestamps into datetimes that will always use the UTC timezone.
         let created_at_utc: DateTime<FixedOffset> = Utc
@@ -260,6 +270,8 @@ impl TryFrom<SelectReportAndJoinRelationsResult> for (MetaFolder, MetaReport) {
             updated_at: updated_at_utc,
             owner: report_model.owner.unwrap_or_default(),
             last_edited_by: report_model.last_edited_by.unwrap_or_default(),
+            panel_data,
+            digest,
         };
 
         Ok((report_folder, report))
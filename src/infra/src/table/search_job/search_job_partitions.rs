@@ -25,7 +25,7 @@ use super::{
     common::{OperatorType, Value},
 };
 use crate::{
-    db::{ORM_CLIENT, connect_to_orm},
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
     errors, orm_err,
 };
 
@@ -178,7 +178,7 @@ pub async fn submit_partitions(job_id: &str, jobs: Vec<Model>) -> Result<(), err
 }
 
 pub async fn get_partition_jobs(job_id: &str) -> Result<Vec<Model>, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
 
     // sql: select * from search_job_partitions where job_id = job_id
     let res = Entity::find()
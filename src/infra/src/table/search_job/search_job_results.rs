@@ -18,7 +18,7 @@ use serde::{Deserialize, Serialize};
 
 use super::super::{entity::search_job_results::*, get_lock};
 use crate::{
-    db::{ORM_CLIENT, connect_to_orm},
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
     errors, orm_err,
 };
 
@@ -31,7 +31,7 @@ pub async fn get(job_id: &str) -> Result<Vec<Model>, errors::Error> {
     // make sure only one client is writing to the database(only for sqlite)
     let _lock = get_lock().await;
 
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
 
     let res = Entity::find()
         .filter(Column::JobId.eq(job_id))
@@ -34,7 +34,7 @@ use super::{
     common::{OperatorType, Value},
 };
 use crate::{
-    db::{ORM_CLIENT, connect_to_orm},
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
     errors, orm_err,
 };
 
@@ -465,7 +465,7 @@ pub async fn retry_search_job(
 }
 
 pub async fn get(job_id: &str, org_id: &str) -> Result<Model, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let res = Entity::find()
         .filter(Column::Id.eq(job_id))
         .filter(Column::OrgId.eq(org_id))
@@ -480,7 +480,7 @@ pub async fn get(job_id: &str, org_id: &str) -> Result<Model, errors::Error> {
 }
 
 pub async fn list_status_by_org_id(org_id: &str) -> Result<Vec<Model>, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let res = Entity::find()
         .filter(Column::OrgId.eq(org_id))
         .filter(Column::Status.ne(4))
@@ -497,7 +497,7 @@ pub async fn list_status_by_org_id(org_id: &str) -> Result<Vec<Model>, errors::E
 }
 
 pub async fn get_deleted_jobs() -> Result<Vec<Model>, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
 
     let res = Entity::find()
         .filter(Column::Status.eq(4))
@@ -18,7 +18,7 @@ use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, Set};
 
 use super::{entity::search_queue::*, get_lock};
 use crate::{
-    db::{ORM_CLIENT, connect_to_orm},
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
     errors,
 };
 
@@ -67,7 +67,7 @@ pub async fn delete_by_trace_id(trace_id: &str) -> Result<(), errors::Error> {
 }
 
 pub async fn count(work_group: &str, user_id: Option<&str>) -> Result<usize, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let mut query = Entity::find().filter(Column::WorkGroup.eq(work_group));
     if let Some(user_id) = user_id {
         query = query.filter(Column::UserId.eq(user_id));
@@ -25,8 +25,8 @@ use serde::{Deserialize, Serialize};
 use super::get_lock;
 use crate::{
     db::{
-        IndexStatement, ORM_CLIENT, ORM_CLIENT_DDL, connect_to_orm, connect_to_orm_ddl, mysql,
-        postgres, sqlite,
+        IndexStatement, ORM_CLIENT, ORM_CLIENT_DDL, ORM_CLIENT_RO, connect_to_orm,
+        connect_to_orm_ddl, connect_to_orm_ro, mysql, postgres, sqlite,
     },
     errors::{self, DbError, Error},
 };
@@ -159,7 +159,7 @@ pub async fn remove(short_id: &str) -> Result<(), errors::Error> {
 }
 
 pub async fn get(short_id: &str) -> Result<ShortUrlRecord, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let record = Entity::find()
         .select_only()
         .column(Column::ShortId)
@@ -174,7 +174,7 @@ pub async fn get(short_id: &str) -> Result<ShortUrlRecord, errors::Error> {
 }
 
 pub async fn list(limit: Option<i64>) -> Result<Vec<ShortUrlRecord>, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let mut res = Entity::find()
         .select_only()
         .column(Column::ShortId)
@@ -189,7 +189,7 @@ pub async fn list(limit: Option<i64>) -> Result<Vec<ShortUrlRecord>, errors::Err
 }
 
 pub async fn contains(short_id: &str) -> Result<bool, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let record = Entity::find()
         .filter(Column::ShortId.eq(short_id))
         .into_model::<ShortUrlRecord>()
@@ -200,7 +200,7 @@ pub async fn contains(short_id: &str) -> Result<bool, errors::Error> {
 }
 
 pub async fn len() -> usize {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let len = Entity::find().count(client).await;
 
     match len {
@@ -230,7 +230,7 @@ pub async fn get_expired(
     expired_before: i64,
     limit: Option<i64>,
 ) -> Result<Vec<String>, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let mut res = Entity::find()
         .select_only()
         .column(Column::ShortId)
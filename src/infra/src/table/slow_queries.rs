@@ -0,0 +1,115 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::{
+    meta::{index_advisor::SlowQuery, stream::StreamType},
+    utils::json,
+};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+
+use super::{entity::slow_queries::*, get_lock};
+use crate::{
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
+    errors,
+};
+
+impl TryFrom<Model> for SlowQuery {
+    type Error = errors::Error;
+
+    fn try_from(value: Model) -> Result<Self, Self::Error> {
+        Ok(SlowQuery {
+            trace_id: value.trace_id,
+            stream_type: StreamType::from(value.stream_type.as_str()),
+            stream_name: value.stream_name,
+            user_id: value.user_id,
+            sql: value.sql,
+            took: value.took,
+            equal_items: json::from_value(value.equal_items)?,
+            text_search_fields: json::from_value(value.text_search_fields)?,
+            created_at: value.created_at,
+        })
+    }
+}
+
+pub async fn add(org_id: &str, query: &SlowQuery) -> Result<(), errors::Error> {
+    let record = ActiveModel {
+        org: Set(org_id.to_string()),
+        stream_type: Set(query.stream_type.to_string()),
+        stream_name: Set(query.stream_name.clone()),
+        trace_id: Set(query.trace_id.clone()),
+        user_id: Set(query.user_id.clone()),
+        sql: Set(query.sql.clone()),
+        took: Set(query.took),
+        equal_items: Set(json::to_value(&query.equal_items)?),
+        text_search_fields: Set(json::to_value(&query.text_search_fields)?),
+        created_at: Set(query.created_at),
+        ..Default::default()
+    };
+
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    Entity::insert(record).exec(client).await?;
+    Ok(())
+}
+
+/// Lists the slow queries of all the organizations since `start_time`, keyed by org, ordered by
+/// stream.
+pub async fn list_since(start_time: i64) -> Result<Vec<(String, SlowQuery)>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let records = Entity::find()
+        .filter(Column::CreatedAt.gte(start_time))
+        .order_by_asc(Column::Org)
+        .order_by_asc(Column::StreamType)
+        .order_by_asc(Column::StreamName)
+        .all(client)
+        .await?;
+    records
+        .into_iter()
+        .map(|r| Ok((r.org.clone(), SlowQuery::try_from(r)?)))
+        .collect()
+}
+
+pub async fn delete_stream(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> Result<(), errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    Entity::delete_many()
+        .filter(Column::Org.eq(org_id))
+        .filter(Column::StreamType.eq(stream_type.to_string()))
+        .filter(Column::StreamName.eq(stream_name))
+        .exec(client)
+        .await?;
+    Ok(())
+}
+
+/// Removes the slow queries logged before `created_at`.
+pub async fn delete_before(created_at: i64) -> Result<(), errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    Entity::delete_many()
+        .filter(Column::CreatedAt.lt(created_at))
+        .exec(client)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,153 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::meta::stream::{StreamFieldUsage, StreamType};
+use hashbrown::HashMap;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, Set, entity::prelude::*, sea_query::Expr};
+
+use super::{entity::stream_field_usage::*, get_lock};
+use crate::{
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
+    errors::{self, DbError, Error},
+};
+
+/// Adds the usage delta to the field's row for `day`, creating the row if it doesn't exist
+/// yet.
+pub async fn add(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    day: i64,
+    usage: &StreamFieldUsage,
+) -> Result<(), errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    if increment(client, org_id, stream_type, stream_name, day, usage).await? {
+        return Ok(());
+    }
+
+    let record = ActiveModel {
+        org: Set(org_id.to_string()),
+        stream_type: Set(stream_type.to_string()),
+        stream_name: Set(stream_name.to_string()),
+        field: Set(usage.field.clone()),
+        day: Set(day),
+        queries: Set(usage.queries),
+        last_queried: Set(usage.last_queried),
+        ..Default::default()
+    };
+    match Entity::insert(record).exec(client).await {
+        Ok(_) => Ok(()),
+        Err(DbErr::Exec(RuntimeErr::SqlxError(SqlxError::Database(e))))
+            if e.is_unique_violation() =>
+        {
+            // another node created the row in the meantime
+            increment(client, org_id, stream_type, stream_name, day, usage).await?;
+            Ok(())
+        }
+        Err(e) => Err(Error::DbError(DbError::SeaORMError(e.to_string()))),
+    }
+}
+
+async fn increment(
+    client: &DatabaseConnection,
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    day: i64,
+    usage: &StreamFieldUsage,
+) -> Result<bool, errors::Error> {
+    let res = Entity::update_many()
+        .col_expr(
+            Column::Queries,
+            Expr::col(Column::Queries).add(usage.queries),
+        )
+        .col_expr(Column::LastQueried, Expr::value(usage.last_queried))
+        .filter(Column::Org.eq(org_id))
+        .filter(Column::StreamType.eq(stream_type.to_string()))
+        .filter(Column::StreamName.eq(stream_name))
+        .filter(Column::Field.eq(usage.field.as_str()))
+        .filter(Column::Day.eq(day))
+        .exec(client)
+        .await?;
+    Ok(res.rows_affected > 0)
+}
+
+/// Sums the usage of the fields of the stream over the days in `[start_day, end_day]`, keyed
+/// by field name.
+pub async fn list(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    start_day: i64,
+    end_day: i64,
+) -> Result<HashMap<String, StreamFieldUsage>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let records = Entity::find()
+        .filter(Column::Org.eq(org_id))
+        .filter(Column::StreamType.eq(stream_type.to_string()))
+        .filter(Column::StreamName.eq(stream_name))
+        .filter(Column::Day.gte(start_day))
+        .filter(Column::Day.lte(end_day))
+        .all(client)
+        .await?;
+    let mut fields: HashMap<String, StreamFieldUsage> = HashMap::new();
+    for r in records {
+        let usage = fields
+            .entry(r.field.clone())
+            .or_insert_with(|| StreamFieldUsage {
+                field: r.field,
+                ..Default::default()
+            });
+        usage.queries += r.queries;
+        usage.last_queried = usage.last_queried.max(r.last_queried);
+    }
+    Ok(fields)
+}
+
+pub async fn delete_stream(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> Result<(), errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    Entity::delete_many()
+        .filter(Column::Org.eq(org_id))
+        .filter(Column::StreamType.eq(stream_type.to_string()))
+        .filter(Column::StreamName.eq(stream_name))
+        .exec(client)
+        .await?;
+
+    Ok(())
+}
+
+/// Removes the usage of days before `day`.
+pub async fn delete_before(day: i64) -> Result<(), errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    Entity::delete_many()
+        .filter(Column::Day.lt(day))
+        .exec(client)
+        .await?;
+
+    Ok(())
+}
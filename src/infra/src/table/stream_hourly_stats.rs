@@ -0,0 +1,171 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::meta::stream::{StreamHourlyStats, StreamType};
+use sea_orm::{
+    ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set, entity::prelude::*, sea_query::Expr,
+};
+
+use super::{entity::stream_hourly_stats::*, get_lock};
+use crate::{
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
+    errors::{self, DbError, Error},
+};
+
+/// Adds the stats delta to the stream's row for `stats.hour`, creating the row if
+/// it doesn't exist yet.
+pub async fn add(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    stats: &StreamHourlyStats,
+) -> Result<(), errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    if increment(client, org_id, stream_type, stream_name, stats).await? {
+        return Ok(());
+    }
+
+    let record = ActiveModel {
+        org: Set(org_id.to_string()),
+        stream_type: Set(stream_type.to_string()),
+        stream_name: Set(stream_name.to_string()),
+        hour: Set(stats.hour),
+        records: Set(stats.records),
+        original_size: Set(stats.original_size),
+        compressed_size: Set(stats.compressed_size),
+        error_records: Set(stats.error_records),
+        ..Default::default()
+    };
+    match Entity::insert(record).exec(client).await {
+        Ok(_) => Ok(()),
+        Err(DbErr::Exec(RuntimeErr::SqlxError(SqlxError::Database(e))))
+            if e.is_unique_violation() =>
+        {
+            // another node created the row in the meantime
+            increment(client, org_id, stream_type, stream_name, stats).await?;
+            Ok(())
+        }
+        Err(e) => Err(Error::DbError(DbError::SeaORMError(e.to_string()))),
+    }
+}
+
+async fn increment(
+    client: &DatabaseConnection,
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    stats: &StreamHourlyStats,
+) -> Result<bool, errors::Error> {
+    let res = Entity::update_many()
+        .col_expr(
+            Column::Records,
+            Expr::col(Column::Records).add(stats.records),
+        )
+        .col_expr(
+            Column::OriginalSize,
+            Expr::col(Column::OriginalSize).add(stats.original_size),
+        )
+        .col_expr(
+            Column::CompressedSize,
+            Expr::col(Column::CompressedSize).add(stats.compressed_size),
+        )
+        .col_expr(
+            Column::ErrorRecords,
+            Expr::col(Column::ErrorRecords).add(stats.error_records),
+        )
+        .filter(Column::Org.eq(org_id))
+        .filter(Column::StreamType.eq(stream_type.to_string()))
+        .filter(Column::StreamName.eq(stream_name))
+        .filter(Column::Hour.eq(stats.hour))
+        .exec(client)
+        .await?;
+    Ok(res.rows_affected > 0)
+}
+
+/// Lists the hourly stats of the given streams (all streams of the type if empty)
+/// for the hours in `[start_hour, end_hour]`, ordered by stream and hour.
+pub async fn list(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_names: &[String],
+    start_hour: i64,
+    end_hour: i64,
+) -> Result<Vec<(String, StreamHourlyStats)>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let mut query = Entity::find()
+        .filter(Column::Org.eq(org_id))
+        .filter(Column::StreamType.eq(stream_type.to_string()))
+        .filter(Column::Hour.gte(start_hour))
+        .filter(Column::Hour.lte(end_hour));
+    if !stream_names.is_empty() {
+        query = query.filter(Column::StreamName.is_in(stream_names.to_vec()));
+    }
+    let records = query
+        .order_by_asc(Column::StreamName)
+        .order_by_asc(Column::Hour)
+        .all(client)
+        .await?;
+    Ok(records
+        .into_iter()
+        .map(|r| {
+            (
+                r.stream_name,
+                StreamHourlyStats {
+                    hour: r.hour,
+                    records: r.records,
+                    original_size: r.original_size,
+                    compressed_size: r.compressed_size,
+                    error_records: r.error_records,
+                },
+            )
+        })
+        .collect())
+}
+
+pub async fn delete_stream(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> Result<(), errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    Entity::delete_many()
+        .filter(Column::Org.eq(org_id))
+        .filter(Column::StreamType.eq(stream_type.to_string()))
+        .filter(Column::StreamName.eq(stream_name))
+        .exec(client)
+        .await?;
+
+    Ok(())
+}
+
+/// Removes the stats of hours before `hour`.
+pub async fn delete_before(hour: i64) -> Result<(), errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    Entity::delete_many()
+        .filter(Column::Hour.lt(hour))
+        .exec(client)
+        .await?;
+
+    Ok(())
+}
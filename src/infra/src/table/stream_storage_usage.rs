@@ -0,0 +1,154 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::meta::stream::{StreamStorageSample, StreamType};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set, entity::prelude::*,
+};
+
+use super::{entity::stream_storage_usage::*, get_lock};
+use crate::{
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
+    errors::{self, DbError, Error},
+};
+
+/// Stores the sample of the stream for `sample.day`, replacing the previous
+/// sample of that day. Returns true if it's the first sample of the day.
+pub async fn set(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    sample: &StreamStorageSample,
+) -> Result<bool, errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let existing = Entity::find()
+        .filter(Column::Org.eq(org_id))
+        .filter(Column::StreamType.eq(stream_type.to_string()))
+        .filter(Column::StreamName.eq(stream_name))
+        .filter(Column::Day.eq(sample.day))
+        .one(client)
+        .await?;
+    if let Some(existing) = existing {
+        let mut record: ActiveModel = existing.into();
+        record.doc_num = Set(sample.doc_num);
+        record.storage_size = Set(sample.storage_size);
+        record.compressed_size = Set(sample.compressed_size);
+        record.index_size = Set(sample.index_size);
+        record.update(client).await?;
+        return Ok(false);
+    }
+
+    let record = ActiveModel {
+        org: Set(org_id.to_string()),
+        stream_type: Set(stream_type.to_string()),
+        stream_name: Set(stream_name.to_string()),
+        day: Set(sample.day),
+        doc_num: Set(sample.doc_num),
+        storage_size: Set(sample.storage_size),
+        compressed_size: Set(sample.compressed_size),
+        index_size: Set(sample.index_size),
+        ..Default::default()
+    };
+    match Entity::insert(record).exec(client).await {
+        Ok(_) => Ok(true),
+        // another node sampled the same day in the meantime
+        Err(DbErr::Exec(RuntimeErr::SqlxError(SqlxError::Database(e))))
+            if e.is_unique_violation() =>
+        {
+            Ok(false)
+        }
+        Err(e) => Err(Error::DbError(DbError::SeaORMError(e.to_string()))),
+    }
+}
+
+/// Lists the samples of the given streams (all streams if empty) for the days
+/// in `[start_day, end_day]`, ordered by stream and day.
+pub async fn list(
+    org_id: &str,
+    stream_type: Option<StreamType>,
+    stream_names: &[String],
+    start_day: i64,
+    end_day: i64,
+) -> Result<Vec<(StreamType, String, StreamStorageSample)>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let mut query = Entity::find()
+        .filter(Column::Org.eq(org_id))
+        .filter(Column::Day.gte(start_day))
+        .filter(Column::Day.lte(end_day));
+    if let Some(stream_type) = stream_type {
+        query = query.filter(Column::StreamType.eq(stream_type.to_string()));
+    }
+    if !stream_names.is_empty() {
+        query = query.filter(Column::StreamName.is_in(stream_names.to_vec()));
+    }
+    let records = query
+        .order_by_asc(Column::StreamType)
+        .order_by_asc(Column::StreamName)
+        .order_by_asc(Column::Day)
+        .all(client)
+        .await?;
+    Ok(records
+        .into_iter()
+        .map(|r| {
+            (
+                StreamType::from(r.stream_type.as_str()),
+                r.stream_name,
+                StreamStorageSample {
+                    day: r.day,
+                    doc_num: r.doc_num,
+                    storage_size: r.storage_size,
+                    compressed_size: r.compressed_size,
+                    index_size: r.index_size,
+                },
+            )
+        })
+        .collect())
+}
+
+pub async fn delete_stream(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> Result<(), errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    Entity::delete_many()
+        .filter(Column::Org.eq(org_id))
+        .filter(Column::StreamType.eq(stream_type.to_string()))
+        .filter(Column::StreamName.eq(stream_name))
+        .exec(client)
+        .await?;
+
+    Ok(())
+}
+
+/// Removes the samples of days before `day`.
+pub async fn delete_before(day: i64) -> Result<(), errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    Entity::delete_many()
+        .filter(Column::Day.lt(day))
+        .exec(client)
+        .await?;
+
+    Ok(())
+}
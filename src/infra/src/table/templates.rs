@@ -25,7 +25,7 @@ use sea_orm::{
 };
 
 use crate::{
-    db::{ORM_CLIENT, connect_to_orm},
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
     errors::{Error, TemplateError},
     table::{
         entity::templates::{ActiveModel, Column, Entity, Model},
@@ -91,7 +91,7 @@ pub async fn put(template: Template) -> Result<Template, Error> {
 }
 
 pub async fn get(org_id: &str, name: &str) -> Result<Option<Template>, Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     match get_model(client, org_id, name).await? {
         Some(model) => Ok(Some(Template::try_from(model)?)),
         None => Ok(None),
@@ -99,7 +99,7 @@ pub async fn get(org_id: &str, name: &str) -> Result<Option<Template>, Error> {
 }
 
 pub async fn list(org_id: &str) -> Result<Vec<Template>, Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let templates = list_models(client, Some(org_id))
         .await?
         .into_iter()
@@ -109,7 +109,7 @@ pub async fn list(org_id: &str) -> Result<Vec<Template>, Error> {
 }
 
 pub async fn list_all() -> Result<Vec<(String, Template)>, Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let templates = list_models(client, None)
         .await?
         .into_iter()
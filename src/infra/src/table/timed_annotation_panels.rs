@@ -18,7 +18,7 @@ use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, Set, TransactionTrait};
 
 use super::{entity::timed_annotation_panels, get_lock};
 use crate::{
-    db::{ORM_CLIENT, connect_to_orm},
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
     errors,
 };
 
@@ -26,7 +26,7 @@ pub async fn get_panels(timed_annotation_id: &str) -> Result<Vec<String>, errors
     // make sure only one client is writing to the database(only for sqlite)
     let _lock = get_lock().await;
 
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let panels = timed_annotation_panels::Entity::find()
         .filter(timed_annotation_panels::Column::TimedAnnotationId.eq(timed_annotation_id))
         .all(client)
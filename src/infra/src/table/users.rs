@@ -28,7 +28,7 @@ use super::{
     get_lock,
 };
 use crate::{
-    db::{ORM_CLIENT, connect_to_orm},
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
     errors::{self, DbError, Error},
 };
 
@@ -90,6 +90,13 @@ impl From<&DBUser> for UserRecord {
             password_ext: user.password_ext.clone(),
             user_type: if user.is_external {
                 UserType::External
+            } else if !user.organizations.is_empty()
+                && user
+                    .organizations
+                    .iter()
+                    .all(|org| org.role.eq(&UserRole::ServiceAccount))
+            {
+                UserType::ServiceAccount
             } else {
                 UserType::Internal
             },
@@ -201,7 +208,7 @@ pub async fn remove(email: &str) -> Result<(), errors::Error> {
 }
 
 pub async fn get(email: &str) -> Result<UserRecord, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let record = Entity::find()
         .filter(Column::Email.eq(email))
         .one(client)
@@ -213,7 +220,7 @@ pub async fn get(email: &str) -> Result<UserRecord, errors::Error> {
 }
 
 pub async fn get_root_user() -> Result<UserRecord, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let record = Entity::find()
         .filter(Column::IsRoot.eq(true))
         .one(client)
@@ -225,7 +232,7 @@ pub async fn get_root_user() -> Result<UserRecord, errors::Error> {
 }
 
 pub async fn list(limit: Option<i64>) -> Result<Vec<UserRecord>, errors::Error> {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let mut res = Entity::find().order_by(Column::CreatedAt, Order::Desc);
     if let Some(limit) = limit {
         res = res.limit(limit as u64);
@@ -242,7 +249,7 @@ pub async fn list(limit: Option<i64>) -> Result<Vec<UserRecord>, errors::Error>
 }
 
 pub async fn len() -> usize {
-    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
     let len = Entity::find().count(client).await;
 
     match len {
@@ -0,0 +1,351 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{collections::HashMap, str::FromStr};
+
+use config::meta::workspace::{ResourceType, Workspace, WorkspaceResource};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+
+use super::{
+    entity::{workspace_members, workspace_resources, workspaces},
+    get_lock,
+};
+use crate::{
+    db::{ORM_CLIENT, ORM_CLIENT_RO, connect_to_orm, connect_to_orm_ro},
+    errors::{self, Error},
+};
+
+impl From<workspaces::Model> for Workspace {
+    fn from(value: workspaces::Model) -> Self {
+        Workspace {
+            id: value.id,
+            name: value.name,
+            description: value.description.unwrap_or_default(),
+            members: vec![],
+            created_by: value.created_by,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+impl TryFrom<workspace_resources::Model> for WorkspaceResource {
+    type Error = errors::Error;
+
+    fn try_from(value: workspace_resources::Model) -> Result<Self, Self::Error> {
+        Ok(WorkspaceResource {
+            resource_type: ResourceType::from_str(&value.resource_type).map_err(Error::Message)?,
+            resource_id: value.resource_id,
+            shared: value.shared,
+        })
+    }
+}
+
+/// Creates the workspace, or updates it if a workspace with the same id exists. The members of
+/// the workspace are replaced.
+pub async fn put(org_id: &str, workspace: &Workspace) -> Result<(), errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let description = Some(workspace.description.clone()).filter(|d| !d.is_empty());
+    workspace_members::Entity::delete_many()
+        .filter(workspace_members::Column::Org.eq(org_id))
+        .filter(workspace_members::Column::WorkspaceId.eq(&workspace.id))
+        .exec(client)
+        .await?;
+    if !workspace.members.is_empty() {
+        let members = workspace
+            .members
+            .iter()
+            .map(|user_email| workspace_members::ActiveModel {
+                id: Set(svix_ksuid::Ksuid::new(None, None).to_string()),
+                org: Set(org_id.to_string()),
+                workspace_id: Set(workspace.id.clone()),
+                user_email: Set(user_email.clone()),
+            });
+        workspace_members::Entity::insert_many(members)
+            .exec(client)
+            .await?;
+    }
+    let existing = workspaces::Entity::find_by_id(&workspace.id)
+        .filter(workspaces::Column::Org.eq(org_id))
+        .one(client)
+        .await?;
+    if let Some(existing) = existing {
+        let mut record: workspaces::ActiveModel = existing.into();
+        record.name = Set(workspace.name.clone());
+        record.description = Set(description);
+        record.updated_at = Set(workspace.updated_at);
+        record.update(client).await?;
+        return Ok(());
+    }
+
+    let record = workspaces::ActiveModel {
+        id: Set(workspace.id.clone()),
+        org: Set(org_id.to_string()),
+        name: Set(workspace.name.clone()),
+        description: Set(description),
+        created_by: Set(workspace.created_by.clone()),
+        created_at: Set(workspace.created_at),
+        updated_at: Set(workspace.updated_at),
+    };
+    workspaces::Entity::insert(record).exec(client).await?;
+    Ok(())
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<Option<Workspace>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let record = workspaces::Entity::find_by_id(id)
+        .filter(workspaces::Column::Org.eq(org_id))
+        .one(client)
+        .await?;
+    with_members(org_id, record).await
+}
+
+pub async fn get_by_name(org_id: &str, name: &str) -> Result<Option<Workspace>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let record = workspaces::Entity::find()
+        .filter(workspaces::Column::Org.eq(org_id))
+        .filter(workspaces::Column::Name.eq(name))
+        .one(client)
+        .await?;
+    with_members(org_id, record).await
+}
+
+async fn with_members(
+    org_id: &str,
+    record: Option<workspaces::Model>,
+) -> Result<Option<Workspace>, errors::Error> {
+    let Some(record) = record else {
+        return Ok(None);
+    };
+    let mut members = list_members(org_id, Some(&record.id)).await?;
+    let mut workspace = Workspace::from(record);
+    workspace.members = members.remove(&workspace.id).unwrap_or_default();
+    Ok(Some(workspace))
+}
+
+/// Lists the workspaces of the org ordered by name.
+pub async fn list(org_id: &str) -> Result<Vec<Workspace>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let records = workspaces::Entity::find()
+        .filter(workspaces::Column::Org.eq(org_id))
+        .order_by_asc(workspaces::Column::Name)
+        .all(client)
+        .await?;
+    let mut members = list_members(org_id, None).await?;
+    Ok(records
+        .into_iter()
+        .map(|record| {
+            let mut workspace = Workspace::from(record);
+            workspace.members = members.remove(&workspace.id).unwrap_or_default();
+            workspace
+        })
+        .collect())
+}
+
+/// Returns the members of the workspaces of the org, or of the given workspace, by workspace id.
+async fn list_members(
+    org_id: &str,
+    workspace_id: Option<&str>,
+) -> Result<HashMap<String, Vec<String>>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let mut query =
+        workspace_members::Entity::find().filter(workspace_members::Column::Org.eq(org_id));
+    if let Some(workspace_id) = workspace_id {
+        query = query.filter(workspace_members::Column::WorkspaceId.eq(workspace_id));
+    }
+    let records = query
+        .order_by_asc(workspace_members::Column::UserEmail)
+        .all(client)
+        .await?;
+    let mut members: HashMap<String, Vec<String>> = HashMap::new();
+    for record in records {
+        members
+            .entry(record.workspace_id)
+            .or_default()
+            .push(record.user_email);
+    }
+    Ok(members)
+}
+
+/// Deletes the workspace and its resource assignments, returns false if it doesn't exist. The
+/// resources themselves are kept.
+pub async fn delete(org_id: &str, id: &str) -> Result<bool, errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    workspace_resources::Entity::delete_many()
+        .filter(workspace_resources::Column::Org.eq(org_id))
+        .filter(workspace_resources::Column::WorkspaceId.eq(id))
+        .exec(client)
+        .await?;
+    workspace_members::Entity::delete_many()
+        .filter(workspace_members::Column::Org.eq(org_id))
+        .filter(workspace_members::Column::WorkspaceId.eq(id))
+        .exec(client)
+        .await?;
+    let res = workspaces::Entity::delete_many()
+        .filter(workspaces::Column::Org.eq(org_id))
+        .filter(workspaces::Column::Id.eq(id))
+        .exec(client)
+        .await?;
+    Ok(res.rows_affected > 0)
+}
+
+/// Lists the resources of the workspace, of the given type if any, its own resources first.
+pub async fn list_resources(
+    org_id: &str,
+    workspace_id: &str,
+    resource_type: Option<ResourceType>,
+) -> Result<Vec<WorkspaceResource>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let mut query = workspace_resources::Entity::find()
+        .filter(workspace_resources::Column::Org.eq(org_id))
+        .filter(workspace_resources::Column::WorkspaceId.eq(workspace_id));
+    if let Some(resource_type) = resource_type {
+        query =
+            query.filter(workspace_resources::Column::ResourceType.eq(resource_type.to_string()));
+    }
+    let records = query
+        .order_by_asc(workspace_resources::Column::Shared)
+        .order_by_asc(workspace_resources::Column::ResourceType)
+        .order_by_asc(workspace_resources::Column::ResourceId)
+        .all(client)
+        .await?;
+    records
+        .into_iter()
+        .map(WorkspaceResource::try_from)
+        .collect()
+}
+
+/// Returns the id of the workspace the resource belongs to, if any.
+pub async fn get_owner(
+    org_id: &str,
+    resource_type: ResourceType,
+    resource_id: &str,
+) -> Result<Option<String>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let record = workspace_resources::Entity::find()
+        .filter(workspace_resources::Column::Org.eq(org_id))
+        .filter(workspace_resources::Column::ResourceType.eq(resource_type.to_string()))
+        .filter(workspace_resources::Column::ResourceId.eq(resource_id))
+        .filter(workspace_resources::Column::Shared.eq(false))
+        .one(client)
+        .await?;
+    Ok(record.map(|r| r.workspace_id))
+}
+
+/// Returns the ids of the workspaces the resource is shared with.
+pub async fn list_sharing(
+    org_id: &str,
+    resource_type: ResourceType,
+    resource_id: &str,
+) -> Result<Vec<String>, errors::Error> {
+    let client = ORM_CLIENT_RO.get_or_init(connect_to_orm_ro).await;
+    let records = workspace_resources::Entity::find()
+        .filter(workspace_resources::Column::Org.eq(org_id))
+        .filter(workspace_resources::Column::ResourceType.eq(resource_type.to_string()))
+        .filter(workspace_resources::Column::ResourceId.eq(resource_id))
+        .filter(workspace_resources::Column::Shared.eq(true))
+        .all(client)
+        .await?;
+    Ok(records.into_iter().map(|r| r.workspace_id).collect())
+}
+
+/// Adds the resource to the workspace. A resource which is not shared is moved from the
+/// workspace it belonged to, if any.
+pub async fn add_resource(
+    org_id: &str,
+    workspace_id: &str,
+    resource: &WorkspaceResource,
+    created_at: i64,
+) -> Result<(), errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let resource_type = resource.resource_type.to_string();
+    let mut stale = workspace_resources::Entity::delete_many()
+        .filter(workspace_resources::Column::Org.eq(org_id))
+        .filter(workspace_resources::Column::ResourceType.eq(&resource_type))
+        .filter(workspace_resources::Column::ResourceId.eq(&resource.resource_id));
+    stale = if resource.shared {
+        stale.filter(workspace_resources::Column::WorkspaceId.eq(workspace_id))
+    } else {
+        stale.filter(
+            workspace_resources::Column::WorkspaceId
+                .eq(workspace_id)
+                .or(workspace_resources::Column::Shared.eq(false)),
+        )
+    };
+    stale.exec(client).await?;
+
+    let record = workspace_resources::ActiveModel {
+        id: Set(svix_ksuid::Ksuid::new(None, None).to_string()),
+        org: Set(org_id.to_string()),
+        workspace_id: Set(workspace_id.to_string()),
+        resource_type: Set(resource_type),
+        resource_id: Set(resource.resource_id.clone()),
+        shared: Set(resource.shared),
+        created_at: Set(created_at),
+    };
+    workspace_resources::Entity::insert(record)
+        .exec(client)
+        .await?;
+    Ok(())
+}
+
+/// Removes the resource from the workspace, returns false if it wasn't in the workspace.
+pub async fn remove_resource(
+    org_id: &str,
+    workspace_id: &str,
+    resource_type: ResourceType,
+    resource_id: &str,
+) -> Result<bool, errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let res = workspace_resources::Entity::delete_many()
+        .filter(workspace_resources::Column::Org.eq(org_id))
+        .filter(workspace_resources::Column::WorkspaceId.eq(workspace_id))
+        .filter(workspace_resources::Column::ResourceType.eq(resource_type.to_string()))
+        .filter(workspace_resources::Column::ResourceId.eq(resource_id))
+        .exec(client)
+        .await?;
+    Ok(res.rows_affected > 0)
+}
+
+/// Removes the resource from all the workspaces, when the resource is deleted.
+pub async fn delete_resource(
+    org_id: &str,
+    resource_type: ResourceType,
+    resource_id: &str,
+) -> Result<(), errors::Error> {
+    // make sure only one client is writing to the database(only for sqlite)
+    let _lock = get_lock().await;
+
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    workspace_resources::Entity::delete_many()
+        .filter(workspace_resources::Column::Org.eq(org_id))
+        .filter(workspace_resources::Column::ResourceType.eq(resource_type.to_string()))
+        .filter(workspace_resources::Column::ResourceId.eq(resource_id))
+        .exec(client)
+        .await?;
+    Ok(())
+}
@@ -65,6 +65,8 @@ pub async fn run() -> Result<(), anyhow::Error> {
 
     tokio::task::spawn(async move { run_schedule_jobs().await });
     tokio::task::spawn(async move { watch_timeout_jobs().await });
+    tokio::task::spawn(async move { run_detections().await });
+    tokio::task::spawn(async move { run_threat_intel().await });
     for i in 0..cfg.limit.search_job_workers {
         tokio::task::spawn(async move { run_search_jobs(i).await });
     }
@@ -95,6 +97,36 @@ async fn watch_timeout_jobs() -> Result<(), anyhow::Error> {
     }
 }
 
+async fn run_detections() -> Result<(), anyhow::Error> {
+    let check_interval = get_config().limit.detections_check_interval;
+    if check_interval <= 0 {
+        return Ok(());
+    }
+    let mut interval = time::interval(time::Duration::from_secs(check_interval as u64));
+    interval.tick().await; // trigger the first run
+    loop {
+        interval.tick().await;
+        if let Err(e) = service::detections::run().await {
+            log::error!("[DETECTIONS] run detections error: {}", e);
+        }
+    }
+}
+
+async fn run_threat_intel() -> Result<(), anyhow::Error> {
+    let check_interval = get_config().limit.threat_intel_check_interval;
+    if check_interval <= 0 {
+        return Ok(());
+    }
+    let mut interval = time::interval(time::Duration::from_secs(check_interval as u64));
+    interval.tick().await; // trigger the first run
+    loop {
+        interval.tick().await;
+        if let Err(e) = service::threat_intel::run().await {
+            log::error!("[THREAT INTEL] run threat intel feeds error: {}", e);
+        }
+    }
+}
+
 #[cfg(feature = "enterprise")]
 async fn run_search_jobs(id: i64) -> Result<(), anyhow::Error> {
     let interval = get_config().limit.search_job_scheduler_interval;
@@ -34,6 +34,9 @@ pub async fn run() -> Result<(), anyhow::Error> {
 
     tokio::task::spawn(async move { parquet::run().await });
     tokio::task::spawn(async move { broadcast::run().await });
+    if config::get_config().limit.file_list_batch_enabled {
+        tokio::task::spawn(async move { crate::service::db::file_list::batch::run().await });
+    }
     tokio::task::spawn(async move { clean_empty_dirs().await });
 
     Ok(())
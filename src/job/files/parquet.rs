@@ -40,7 +40,9 @@ use config::{
         bitvec::BitVec,
         inverted_index::InvertedIndexFormat,
         search::StorageType,
-        stream::{FileKey, FileMeta, PartitionTimeLevel, StreamSettings, StreamType},
+        stream::{
+            FileKey, FileMeta, PartitionTimeLevel, StreamHourlyStats, StreamSettings, StreamType,
+        },
     },
     metrics,
     utils::{
@@ -86,6 +88,7 @@ use crate::{
             datafusion::exec::{self, MergeParquetResult},
             tantivy::puffin_directory::writer::PuffinDirWriter,
         },
+        stream_hourly_stats,
     },
 };
 
@@ -848,6 +851,22 @@ async fn merge_files(
     let account = storage::get_account(&new_file_key).unwrap_or_default();
     storage::put(&account, &new_file_key, buf.clone()).await?;
 
+    // hourly stats of the flushed file
+    if cfg.common.stream_hourly_stats_enabled {
+        let mut stats = StreamHourlyStats::from_file(&new_file_meta, false);
+        if stream_type == StreamType::Logs {
+            stats.error_records = stream_hourly_stats::count_error_records(&buf)
+                .await
+                .unwrap_or_else(|e| {
+                    log::warn!(
+                        "[INGESTER:JOB:{thread_id}] count error records for file: {new_file_key} failed: {e}"
+                    );
+                    0
+                });
+        }
+        stream_hourly_stats::record(&org_id, stream_type, &stream_name, &stats);
+    }
+
     // skip index generation if not enabled or not basic type
     if !cfg.common.inverted_index_enabled || !stream_type.is_basic_type() {
         return Ok((account, new_file_key, new_file_meta, retain_file_list));
@@ -27,7 +27,7 @@ use crate::{
             user::{UserOrgRole, UserRequest},
         },
     },
-    service::{db, self_reporting, users},
+    service::{dashboards, db, index_advisor, self_reporting, users},
 };
 
 mod alert_manager;
@@ -42,6 +42,7 @@ pub mod metrics;
 mod mmdb_downloader;
 mod promql;
 mod promql_self_consume;
+mod replication;
 mod stats;
 pub(crate) mod syslog_server;
 mod telemetry;
@@ -152,6 +153,16 @@ pub async fn init() -> Result<(), anyhow::Error> {
 
     tokio::task::spawn(async move { self_reporting::run().await });
 
+    // check dashboards for deleted streams and fields
+    if LOCAL_NODE.is_querier() {
+        tokio::task::spawn(async move { dashboards::lint::run().await });
+    }
+
+    // recommend indexing the fields filtered on by the slow queries
+    if LOCAL_NODE.is_compactor() {
+        tokio::task::spawn(async move { index_advisor::run().await });
+    }
+
     // cache short_urls
     tokio::task::spawn(async move { db::short_url::watch().await });
     db::short_url::cache()
@@ -161,6 +172,19 @@ pub async fn init() -> Result<(), anyhow::Error> {
     // initialize metadata watcher
     tokio::task::spawn(async move { db::schema::watch().await });
     tokio::task::spawn(async move { db::functions::watch().await });
+    tokio::task::spawn(async move { db::grok::watch().await });
+    tokio::task::spawn(async move { db::sql_policy::watch().await });
+    tokio::task::spawn(async move { db::row_policy::watch().await });
+    tokio::task::spawn(async move { db::service_account::watch().await });
+    tokio::task::spawn(async move { db::ui_profile::watch().await });
+    tokio::task::spawn(async move { db::column_mask::watch().await });
+    if LOCAL_NODE.is_ingester() {
+        tokio::task::spawn(async move { db::log_metrics::watch().await });
+        tokio::task::spawn(async move { db::remote_write_filter::watch().await });
+    }
+    if cfg.common.chaos_enabled {
+        tokio::task::spawn(async move { db::chaos::watch().await });
+    }
     tokio::task::spawn(async move { db::compact::retention::watch().await });
     tokio::task::spawn(async move { db::metrics::watch_prom_cluster_leader().await });
     tokio::task::spawn(async move { db::alerts::templates::watch().await });
@@ -181,6 +205,9 @@ pub async fn init() -> Result<(), anyhow::Error> {
     if LOCAL_NODE.is_ingester() || LOCAL_NODE.is_querier() || LOCAL_NODE.is_alert_manager() {
         tokio::task::spawn(async move { db::enrichment_table::watch().await });
     }
+    if LOCAL_NODE.is_ingester() || LOCAL_NODE.is_alert_manager() {
+        tokio::task::spawn(async move { db::threat_intel::watch().await });
+    }
 
     tokio::task::yield_now().await;
 
@@ -189,6 +216,35 @@ pub async fn init() -> Result<(), anyhow::Error> {
     db::functions::cache()
         .await
         .expect("functions cache failed");
+    db::grok::cache().await.expect("grok patterns cache failed");
+    db::sql_policy::cache()
+        .await
+        .expect("sql policies cache failed");
+    db::row_policy::cache()
+        .await
+        .expect("row policies cache failed");
+    db::service_account::cache()
+        .await
+        .expect("service accounts cache failed");
+    db::ui_profile::cache()
+        .await
+        .expect("ui profiles cache failed");
+    db::column_mask::cache()
+        .await
+        .expect("column masks cache failed");
+    if LOCAL_NODE.is_ingester() {
+        db::log_metrics::cache()
+            .await
+            .expect("log metric rules cache failed");
+        db::remote_write_filter::cache()
+            .await
+            .expect("remote write filters cache failed");
+    }
+    if cfg.common.chaos_enabled {
+        db::chaos::cache()
+            .await
+            .expect("chaos scenarios cache failed");
+    }
     db::compact::retention::cache()
         .await
         .expect("compact delete cache failed");
@@ -213,6 +269,11 @@ pub async fn init() -> Result<(), anyhow::Error> {
     db::syslog::cache_syslog_settings()
         .await
         .expect("syslog settings cache failed");
+    if LOCAL_NODE.is_ingester() || LOCAL_NODE.is_alert_manager() {
+        db::threat_intel::cache()
+            .await
+            .expect("threat intel indicators cache failed");
+    }
 
     infra_file_list::create_table_index().await?;
     infra_file_list::LOCAL_CACHE.create_table_index().await?;
@@ -240,11 +301,24 @@ pub async fn init() -> Result<(), anyhow::Error> {
     tokio::task::spawn(async move { promql::run().await });
     tokio::task::spawn(async move { alert_manager::run().await });
     tokio::task::spawn(async move { file_downloader::run().await });
+    tokio::task::spawn(async move { replication::run().await });
 
     if LOCAL_NODE.is_compactor() {
         tokio::task::spawn(async move { file_list_dump::run().await });
     }
 
+    // write threat intel findings produced while ingesting
+    if LOCAL_NODE.is_ingester() {
+        tokio::task::spawn(
+            async move { crate::service::threat_intel::run_findings_writer().await },
+        );
+    }
+
+    // write the metrics extracted from logs while ingesting
+    if LOCAL_NODE.is_ingester() {
+        tokio::task::spawn(async move { crate::service::log_metrics::run().await });
+    }
+
     // load metrics disk cache
     tokio::task::spawn(async move { crate::service::promql::search::init().await });
     // start pipeline data retention
@@ -278,6 +352,7 @@ pub async fn init() -> Result<(), anyhow::Error> {
     // Syslog server start
     tokio::task::spawn(async move { db::syslog::watch().await });
     tokio::task::spawn(async move { db::syslog::watch_syslog_settings().await });
+    tokio::task::spawn(async move { syslog_server::flush_multiline().await });
 
     let start_syslog = *SYSLOG_ENABLED.read();
     if start_syslog {
@@ -0,0 +1,53 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::{cluster::LOCAL_NODE, get_config};
+use tokio::time;
+
+use crate::service::replication;
+
+pub async fn run() -> Result<(), anyhow::Error> {
+    if !get_config().replication.enabled {
+        return Ok(());
+    }
+    // every node may remove files from the file list, only compactors copy the new ones
+    tokio::task::spawn(async move { flush_removed().await });
+    if LOCAL_NODE.is_compactor() {
+        tokio::task::spawn(async move { replicate().await });
+    }
+    Ok(())
+}
+
+async fn replicate() -> Result<(), anyhow::Error> {
+    let mut interval = time::interval(time::Duration::from_secs(get_config().replication.interval));
+    interval.tick().await; // trigger the first run
+    loop {
+        interval.tick().await;
+        if let Err(e) = replication::replicate().await {
+            log::error!("[REPLICATION] replicate file list error: {e}");
+        }
+    }
+}
+
+async fn flush_removed() -> Result<(), anyhow::Error> {
+    let mut interval = time::interval(time::Duration::from_secs(get_config().replication.interval));
+    interval.tick().await; // trigger the first run
+    loop {
+        interval.tick().await;
+        if let Err(e) = replication::flush_removed().await {
+            log::error!("[REPLICATION] write removed files error: {e}");
+        }
+    }
+}
@@ -16,12 +16,21 @@
 use config::{cluster::LOCAL_NODE, get_config};
 use tokio::time;
 
-use crate::service::{compact::stats::update_stats_from_file_list, db};
+use crate::service::{
+    compact::stats::update_stats_from_file_list, dashboards, db, stream_field_usage,
+    stream_hourly_stats, stream_storage_usage,
+};
 
 pub async fn run() -> Result<(), anyhow::Error> {
     // tokio::task::spawn(async move { usage_report_stats().await });
     tokio::task::spawn(async move { file_list_update_stats().await });
     tokio::task::spawn(async move { cache_stream_stats().await });
+    tokio::task::spawn(async move { flush_stream_hourly_stats().await });
+    tokio::task::spawn(async move { clean_stream_hourly_stats().await });
+    tokio::task::spawn(async move { flush_stream_field_usage().await });
+    tokio::task::spawn(async move { clean_stream_field_usage().await });
+    tokio::task::spawn(async move { sample_stream_storage_usage().await });
+    tokio::task::spawn(async move { clean_dashboard_snapshots().await });
     Ok(())
 }
 
@@ -88,3 +97,99 @@ async fn cache_stream_stats() -> Result<(), anyhow::Error> {
         }
     }
 }
+
+// write the hourly stats accumulated at flush and compaction time
+async fn flush_stream_hourly_stats() -> Result<(), anyhow::Error> {
+    if !LOCAL_NODE.is_ingester() && !LOCAL_NODE.is_compactor() {
+        return Ok(());
+    }
+
+    let mut interval = time::interval(time::Duration::from_secs(10));
+    interval.tick().await; // trigger the first run
+    loop {
+        interval.tick().await;
+        if let Err(e) = stream_hourly_stats::flush().await {
+            log::error!("[STATS] flush stream hourly stats error: {}", e);
+        }
+    }
+}
+
+async fn clean_stream_hourly_stats() -> Result<(), anyhow::Error> {
+    if !LOCAL_NODE.is_compactor() {
+        return Ok(());
+    }
+
+    let mut interval = time::interval(time::Duration::from_secs(3600));
+    loop {
+        interval.tick().await;
+        if let Err(e) = stream_hourly_stats::clean_expired().await {
+            log::error!("[STATS] clean stream hourly stats error: {}", e);
+        }
+    }
+}
+
+// write the field usage counted by the search leaders
+async fn flush_stream_field_usage() -> Result<(), anyhow::Error> {
+    if !LOCAL_NODE.is_querier() {
+        return Ok(());
+    }
+
+    let mut interval = time::interval(time::Duration::from_secs(60));
+    interval.tick().await; // trigger the first run
+    loop {
+        interval.tick().await;
+        if let Err(e) = stream_field_usage::flush().await {
+            log::error!("[STATS] flush stream field usage error: {}", e);
+        }
+    }
+}
+
+async fn clean_stream_field_usage() -> Result<(), anyhow::Error> {
+    if !LOCAL_NODE.is_compactor() {
+        return Ok(());
+    }
+
+    let mut interval = time::interval(time::Duration::from_secs(3600));
+    loop {
+        interval.tick().await;
+        if let Err(e) = stream_field_usage::clean_expired().await {
+            log::error!("[STATS] clean stream field usage error: {}", e);
+        }
+    }
+}
+
+// sample the storage used by the streams, the latest sample of a day is kept
+async fn sample_stream_storage_usage() -> Result<(), anyhow::Error> {
+    if !LOCAL_NODE.is_compactor() {
+        return Ok(());
+    }
+
+    let mut interval = time::interval(time::Duration::from_secs(3600));
+    loop {
+        interval.tick().await;
+        if let Err(e) = stream_storage_usage::sample().await {
+            log::error!("[STATS] sample stream storage usage error: {}", e);
+        }
+        if let Err(e) = stream_storage_usage::clean_expired().await {
+            log::error!("[STATS] clean stream storage usage error: {}", e);
+        }
+    }
+}
+
+// delete the dashboard snapshots after they expire
+async fn clean_dashboard_snapshots() -> Result<(), anyhow::Error> {
+    if !LOCAL_NODE.is_compactor() {
+        return Ok(());
+    }
+
+    let mut interval = time::interval(time::Duration::from_secs(3600));
+    loop {
+        interval.tick().await;
+        if let Err(e) = dashboards::snapshots::clean_expired().await {
+            log::error!("[STATS] clean dashboard snapshots error: {}", e);
+        }
+        if let Err(e) = dashboards::scheduled_snapshots::clean_expired().await {
+            log::error!("[STATS] clean scheduled dashboard snapshots error: {}", e);
+        }
+    }
+}
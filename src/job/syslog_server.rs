@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::{io::BufReader, net::SocketAddr, sync::Arc};
+use std::{io::BufReader, net::SocketAddr, sync::Arc, time::Duration};
 
 use once_cell::sync::Lazy;
 use rustls::pki_types::ServerName;
@@ -29,6 +29,7 @@ use crate::{
     handler::tcp_udp::{STOP_SRV, tls_tcp_server, udp_server},
     service::{
         db::syslog::toggle_syslog_setting,
+        logs::syslog,
         tls::{
             get_server_url_from_cert, tcp_tls_self_connect_client_config, tcp_tls_server_config,
         },
@@ -124,6 +125,16 @@ pub async fn run(start_srv: bool, is_init: bool) -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+/// Periodically ingests the syslog multi-line records whose timeout expired
+pub async fn flush_multiline() {
+    let mut interval = tokio::time::interval(Duration::from_millis(500));
+    interval.tick().await; // trigger the first run
+    loop {
+        interval.tick().await;
+        syslog::flush_multiline().await;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::run;
@@ -152,6 +152,7 @@ async fn migrate_pipelines() -> Result<(), anyhow::Error> {
                     source: pipeline_source,
                     nodes,
                     edges,
+                    created_by: String::new(),
                 };
                 new_pipeline_by_source.insert(
                     StreamParams::new(
@@ -192,6 +193,7 @@ async fn migrate_pipelines() -> Result<(), anyhow::Error> {
                     source: pipeline_source,
                     nodes: vec![source_node],
                     edges: vec![],
+                    created_by: String::new(),
                 }
             });
 
@@ -323,6 +325,7 @@ async fn migrate_pipelines() -> Result<(), anyhow::Error> {
                 source: pipeline_source,
                 nodes: vec![source_node],
                 edges: vec![],
+                created_by: String::new(),
             }
         });
 
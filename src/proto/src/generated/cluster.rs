@@ -3079,6 +3079,11 @@ pub struct NodeDetails {
     pub version: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "13")]
     pub metrics: ::core::option::Option<NodeMetrics>,
+    #[prost(map = "string, string", tag = "14")]
+    pub labels: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        ::prost::alloc::string::String,
+    >,
 }
 /// Node status enum
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
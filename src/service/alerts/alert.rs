@@ -36,10 +36,13 @@ use config::{
         search::{SearchEventContext, SearchEventType},
         sql::resolve_stream_names,
         stream::StreamType,
+        workspace::ResourceType,
     },
     utils::{
         base64,
         json::{Map, Value},
+        time::format_duration,
+        units::Unit,
     },
 };
 use cron::Schedule;
@@ -73,10 +76,13 @@ use crate::{
         utils::auth::{is_ofga_unsupported, remove_ownership, set_ownership},
     },
     service::{
-        alerts::{QueryConditionExt, build_sql, destinations},
+        alerts::{
+            QueryConditionExt, anomaly, build_sql, composite, destinations, grouping, incidents,
+            multi_condition, rendering,
+        },
         db, folders,
         search::sql::RE_ONLY_SELECT,
-        short_url,
+        short_url, workspaces,
     },
 };
 
@@ -182,6 +188,43 @@ pub enum AlertError {
     /// Not support save destination remote pipeline for alert so far
     #[error("Not support save destination {0} type for alert so far")]
     NotSupportedAlertDestinationType(Module),
+
+    /// Error that occurs when syncing an alert that was not created from a
+    /// dashboard panel.
+    #[error("Alert was not created from a dashboard panel")]
+    PanelSourceMissing,
+
+    /// Error that occurs when the dashboard panel of an alert cannot be
+    /// turned into a query condition.
+    #[error("Invalid panel source: {0}")]
+    PanelSource(String),
+
+    #[error("Realtime alerts can't be backtested")]
+    BacktestRealtime,
+
+    #[error("Backtest end time should be after the start time")]
+    BacktestTimeRange,
+
+    #[error("Error backtesting alert: {0}")]
+    Backtest(#[source] anyhow::Error),
+
+    #[error("History end time should be after the start time")]
+    HistoryTimeRange,
+
+    #[error("Invalid composite alert: {0}")]
+    InvalidComposite(String),
+
+    #[error("Invalid multi-condition alert: {0}")]
+    InvalidMultiCondition(String),
+
+    #[error("Invalid anomaly alert: {0}")]
+    InvalidAnomaly(String),
+
+    #[error("Invalid row template: {0}")]
+    InvalidRowTemplate(String),
+
+    #[error("Invalid escalation policy: {0}")]
+    InvalidEscalationPolicy(String),
 }
 
 pub async fn save(
@@ -253,6 +296,7 @@ async fn prepare_alert(
     let stream_type = alert.stream_type;
     alert.stream_name = stream_name.to_string();
     alert.row_template = alert.row_template.trim().to_string();
+    rendering::validate(&alert.row_template).map_err(AlertError::InvalidRowTemplate)?;
 
     if alert.id.is_none() && !create {
         return Err(AlertError::AlertIdMissing);
@@ -292,7 +336,8 @@ async fn prepare_alert(
         }
     }
 
-    if alert.name.is_empty() || alert.stream_name.is_empty() {
+    // composite alerts don't query a stream
+    if alert.name.is_empty() || (alert.stream_name.is_empty() && alert.composite.is_none()) {
         return Err(AlertError::AlertNameMissing);
     }
     if alert.name.contains('/') {
@@ -317,10 +362,26 @@ async fn prepare_alert(
         }
     }
 
-    // before saving alert check alert destination
-    if alert.destinations.is_empty() {
+    // before saving alert check alert destination, an alert with an escalation policy can notify
+    // only the steps of the policy
+    if alert.destinations.is_empty() && alert.escalation_policy.is_none() {
         return Err(AlertError::AlertDestinationMissing);
     }
+    if let Some(policy_id) = alert.escalation_policy.as_ref() {
+        if alert.is_real_time {
+            return Err(AlertError::InvalidEscalationPolicy(
+                "escalation policies are only supported by scheduled alerts".to_string(),
+            ));
+        }
+        if table::escalation_policies::get(org_id, policy_id)
+            .await?
+            .is_none()
+        {
+            return Err(AlertError::InvalidEscalationPolicy(format!(
+                "escalation policy {policy_id} not found"
+            )));
+        }
+    }
     for dest in alert.destinations.iter() {
         match db::alerts::destinations::get(org_id, dest).await {
             Ok(d) => {
@@ -349,6 +410,20 @@ async fn prepare_alert(
         alert.context_attributes = Some(new_attrs);
     }
 
+    if let Some(condition) = alert.composite.as_ref() {
+        if alert.multi_condition.is_some() {
+            return Err(AlertError::InvalidMultiCondition(
+                "an alert can't be both composite and multi-condition".to_string(),
+            ));
+        }
+        if alert.anomaly.is_some() {
+            return Err(AlertError::InvalidAnomaly(
+                "an alert can't be both composite and anomaly".to_string(),
+            ));
+        }
+        return composite::validate(alert, condition).await;
+    }
+
     // before saving alert check column type to decide numeric condition
     let schema = infra::schema::get(org_id, stream_name, stream_type).await?;
     if stream_name.is_empty() || schema.fields().is_empty() {
@@ -371,6 +446,15 @@ async fn prepare_alert(
         }
     }
 
+    // the query condition isn't used by multi-condition alerts
+    if alert.multi_condition.is_some() {
+        return multi_condition::validate(alert);
+    }
+
+    if alert.anomaly.is_some() {
+        return anomaly::validate(alert).await;
+    }
+
     if alert.is_real_time && alert.query_condition.query_type != QueryType::Custom {
         return Err(AlertError::RealtimeMissingCustomQuery);
     }
@@ -742,6 +826,11 @@ pub async fn delete_by_id<C: ConnectionTrait>(
     match db::alerts::alert::delete_by_id(conn, org_id, alert_id).await {
         Ok(_) => {
             remove_ownership(org_id, "alerts", Authz::new(&alert_id_str)).await;
+            if let Err(e) =
+                workspaces::delete_resource(org_id, ResourceType::Alert, &alert_id_str).await
+            {
+                log::error!("Failed to remove alert {alert_id_str} from the workspaces: {e}");
+            }
             Ok(())
         }
         Err(e) => Err(e.into()),
@@ -854,6 +943,15 @@ pub trait AlertExt: Sync + Send + 'static {
         start_time: Option<i64>,
         evaluation_timestamp: i64,
     ) -> Result<(String, String), AlertError>;
+
+    /// Sends the resolved notification of the alert which stopped firing to its destinations
+    /// with a resolved template, returns the success and the error messages like
+    /// `send_notification`.
+    async fn send_resolved_notification(
+        &self,
+        recovery: Recovery,
+        evaluation_timestamp: i64,
+    ) -> Result<(String, String), AlertError>;
 }
 
 #[async_trait]
@@ -864,7 +962,13 @@ impl AlertExt for Alert {
         (start_time, end_time): (Option<i64>, i64),
         trace_id: Option<String>,
     ) -> Result<TriggerEvalResults, anyhow::Error> {
-        if self.is_real_time {
+        if let Some(condition) = self.composite.as_ref() {
+            composite::evaluate(self, condition, end_time).await
+        } else if let Some(condition) = self.multi_condition.as_ref() {
+            multi_condition::evaluate(self, condition, (start_time, end_time), trace_id).await
+        } else if let Some(condition) = self.anomaly.as_ref() {
+            anomaly::evaluate(self, condition, (start_time, end_time), trace_id).await
+        } else if self.is_real_time {
             self.query_condition.evaluate_realtime(row).await
         } else {
             let search_event_ctx = SearchEventContext::with_alert(Some(format!(
@@ -899,24 +1003,57 @@ impl AlertExt for Alert {
         for dest in self.destinations.iter() {
             let (dest, template) = destinations::get_with_template(&self.org_id, dest).await?;
             let Module::Alert {
-                destination_type, ..
+                destination_type,
+                grouping: notification_grouping,
+                ..
             } = dest.module
             else {
                 return Err(AlertError::GetDestinationWithTemplateError(
                     db::alerts::destinations::DestinationError::UnsupportedType,
                 ));
             };
-            match send_notification(
-                self,
-                &destination_type,
-                &template,
-                rows,
-                rows_end_time,
-                start_time,
-                evaluation_timestamp,
-            )
-            .await
-            {
+            let ret = match (&destination_type, notification_grouping) {
+                // incidents are grouped by the destination itself, the grouping only tells
+                // them apart
+                (_, notification_grouping) if destination_type.is_incident() => {
+                    let group_key = notification_grouping
+                        .map(|g| g.group_key(|label| self.label_value(rows, label)))
+                        .unwrap_or_default();
+                    let (_, msg) = render_notification(
+                        self,
+                        &destination_type,
+                        &template,
+                        rows,
+                        rows_end_time,
+                        start_time,
+                        evaluation_timestamp,
+                    )
+                    .await;
+                    incidents::trigger(self, &destination_type, &group_key, rows, &msg).await
+                }
+                (_, Some(notification_grouping)) => Ok(grouping::enqueue(
+                    self,
+                    &dest.name,
+                    &notification_grouping,
+                    rows,
+                    rows_end_time,
+                    start_time,
+                    evaluation_timestamp,
+                )),
+                (_, None) => {
+                    send_notification(
+                        self,
+                        &destination_type,
+                        &template,
+                        rows,
+                        rows_end_time,
+                        start_time,
+                        evaluation_timestamp,
+                    )
+                    .await
+                }
+            };
+            match ret {
                 Ok(resp) => {
                     success_message =
                         format!("{success_message} destination {} {resp};", dest.name);
@@ -939,7 +1076,7 @@ impl AlertExt for Alert {
                 }
             }
         }
-        if no_of_error == self.destinations.len() {
+        if no_of_error > 0 && no_of_error == self.destinations.len() {
             Err(AlertError::SendNotificationError {
                 error_message: err_message,
             })
@@ -947,6 +1084,105 @@ impl AlertExt for Alert {
             Ok((success_message, err_message))
         }
     }
+
+    async fn send_resolved_notification(
+        &self,
+        recovery: Recovery,
+        evaluation_timestamp: i64,
+    ) -> Result<(String, String), AlertError> {
+        let mut err_message = "".to_string();
+        let mut success_message = "".to_string();
+        let mut no_of_sent = 0;
+        let mut no_of_error = 0;
+        for dest in self.destinations.iter() {
+            let dest = destinations::get(&self.org_id, dest).await?;
+            // incident destinations resolve their incidents instead
+            let Module::Alert {
+                resolved_template: Some(resolved_template),
+                destination_type,
+                ..
+            } = dest.module
+            else {
+                continue;
+            };
+            if destination_type.is_incident() {
+                continue;
+            }
+            no_of_sent += 1;
+            let ret = match db::alerts::templates::get(&self.org_id, &resolved_template).await {
+                Ok(template) => {
+                    let options = ProcessTemplateOptions {
+                        rows_end_time: evaluation_timestamp,
+                        start_time: None,
+                        evaluation_timestamp,
+                        is_email: matches!(destination_type, DestinationType::Email(_)),
+                        recovery: Some(recovery),
+                    };
+                    let (email_subject, msg) = render_message(self, &template, &[], options).await;
+                    send_message(&self.name, &destination_type, &email_subject, msg).await
+                }
+                Err(e) => Err(anyhow::anyhow!(
+                    "resolved template {resolved_template}: {e}"
+                )),
+            };
+            match ret {
+                Ok(resp) => {
+                    success_message =
+                        format!("{success_message} destination {} {resp};", dest.name);
+                }
+                Err(e) => {
+                    log::error!(
+                        "Error sending resolved notification for {}/{}/{}/{} for destination {} err: {}",
+                        self.org_id,
+                        self.stream_type,
+                        self.stream_name,
+                        self.name,
+                        dest.name,
+                        e
+                    );
+                    no_of_error += 1;
+                    err_message = format!(
+                        "{err_message} Error sending resolved notification for destination {} err: {e};",
+                        dest.name
+                    );
+                }
+            }
+        }
+        if no_of_error > 0 && no_of_error == no_of_sent {
+            Err(AlertError::SendNotificationError {
+                error_message: err_message,
+            })
+        } else {
+            Ok((success_message, err_message))
+        }
+    }
+}
+
+/// Recovery of a firing alert, rendered by the resolved templates of its destinations.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Recovery {
+    /// (microseconds) How long the alert was firing
+    pub firing_duration: i64,
+    /// Value the alert condition matched on its last firing evaluation
+    pub last_value: Option<f64>,
+}
+
+impl Recovery {
+    /// Returns the variables of the recovery available to the templates.
+    fn template_vars(&self) -> Vec<(&'static str, Value)> {
+        let seconds = self.firing_duration.max(0) / 1_000_000;
+        vec![
+            ("alert_firing_duration", seconds.into()),
+            (
+                "alert_firing_duration_str",
+                format_duration(seconds as u64 * 1000).into(),
+            ),
+            (
+                "alert_last_value",
+                self.last_value.map(Value::from).unwrap_or(Value::Null),
+            ),
+        ]
+    }
 }
 
 async fn send_notification(
@@ -958,6 +1194,46 @@ async fn send_notification(
     start_time: Option<i64>,
     evaluation_timestamp: i64,
 ) -> Result<String, anyhow::Error> {
+    let (email_subject, msg) = render_notification(
+        alert,
+        dest_type,
+        template,
+        rows,
+        rows_end_time,
+        start_time,
+        evaluation_timestamp,
+    )
+    .await;
+    send_message(&alert.name, dest_type, &email_subject, msg).await
+}
+
+/// Returns the email subject and the message of the alert notification for the destination.
+pub(super) async fn render_notification(
+    alert: &Alert,
+    dest_type: &DestinationType,
+    template: &Template,
+    rows: &[Map<String, Value>],
+    rows_end_time: i64,
+    start_time: Option<i64>,
+    evaluation_timestamp: i64,
+) -> (String, String) {
+    let options = ProcessTemplateOptions {
+        rows_end_time,
+        start_time,
+        evaluation_timestamp,
+        is_email: matches!(dest_type, DestinationType::Email(_)),
+        recovery: None,
+    };
+    render_message(alert, template, rows, options).await
+}
+
+/// Returns the email subject and the message rendered by the template.
+async fn render_message(
+    alert: &Alert,
+    template: &Template,
+    rows: &[Map<String, Value>],
+    options: ProcessTemplateOptions,
+) -> (String, String) {
     let org_name = if let Some(org) = ORGANIZATIONS.read().await.get(&alert.org_id) {
         org.name.clone()
     } else {
@@ -968,45 +1244,39 @@ async fn send_notification(
     } else {
         process_row_template(&org_name, &alert.row_template, alert, rows)
     };
-    let is_email = matches!(dest_type, DestinationType::Email(_));
     let msg: String = process_dest_template(
         &org_name,
         &template.body,
         alert,
         rows,
         &rows_tpl_val,
-        ProcessTemplateOptions {
-            rows_end_time,
-            start_time,
-            evaluation_timestamp,
-            is_email,
-        },
+        options,
     )
     .await;
 
     let email_subject = if let TemplateType::Email { title } = &template.template_type {
-        process_dest_template(
-            &org_name,
-            title,
-            alert,
-            rows,
-            &rows_tpl_val,
-            ProcessTemplateOptions {
-                rows_end_time,
-                start_time,
-                evaluation_timestamp,
-                is_email,
-            },
-        )
-        .await
+        process_dest_template(&org_name, title, alert, rows, &rows_tpl_val, options).await
     } else {
         template.name.clone()
     };
+    (email_subject, msg)
+}
 
+pub(super) async fn send_message(
+    alert_name: &str,
+    dest_type: &DestinationType,
+    email_subject: &str,
+    msg: String,
+) -> Result<String, anyhow::Error> {
     match dest_type {
         DestinationType::Http(endpoint) => send_http_notification(endpoint, msg).await,
-        DestinationType::Email(email) => send_email_notification(&email_subject, email, msg).await,
-        DestinationType::Sns(aws_sns) => send_sns_notification(&alert.name, aws_sns, msg).await,
+        DestinationType::Email(email) => send_email_notification(email_subject, email, msg).await,
+        DestinationType::Sns(aws_sns) => send_sns_notification(alert_name, aws_sns, msg).await,
+        DestinationType::PagerDuty(_)
+        | DestinationType::Opsgenie(_)
+        | DestinationType::VictorOps(_) => Err(anyhow::anyhow!(
+            "incident notifications are sent per alert, not as a message"
+        )),
     }
 }
 
@@ -1177,12 +1447,16 @@ fn process_row_template(
         "scheduled"
     };
     let alert_count = rows.len();
+    let is_jinja = rendering::is_jinja(tpl);
     let mut rows_tpl = Vec::with_capacity(rows.len());
     for row in rows.iter() {
         let mut resp = tpl.to_string();
         let mut alert_start_time = 0;
         let mut alert_end_time = 0;
         for (key, value) in row.iter() {
+            if !is_jinja {
+                process_unit_replace(&mut resp, key, &[value]);
+            }
             let value = if value.is_string() {
                 value.as_str().unwrap_or_default().to_string()
             } else if value.is_f64() {
@@ -1190,7 +1464,9 @@ fn process_row_template(
             } else {
                 value.to_string()
             };
-            process_variable_replace(&mut resp, key, &VarValue::Str(&value), false);
+            if !is_jinja {
+                process_variable_replace(&mut resp, key, &VarValue::Str(&value), false);
+            }
 
             // calculate start and end time
             if key == TIMESTAMP_COL_NAME {
@@ -1232,6 +1508,16 @@ fn process_row_template(
             String::from("N/A")
         };
 
+        if is_jinja {
+            let mut ctx = template_vars(org_name, alert, alert_count);
+            ctx.insert("alert_start_time".to_string(), alert_start_time_str.into());
+            ctx.insert("alert_end_time".to_string(), alert_end_time_str.into());
+            ctx.extend(row.clone());
+            ctx.insert("row".to_string(), Value::Object(row.clone()));
+            rows_tpl.push(render_jinja(alert, tpl, &ctx));
+            continue;
+        }
+
         resp = resp
             .replace("{org_name}", org_name)
             .replace("{stream_type}", alert.stream_type.as_str())
@@ -1272,11 +1558,14 @@ fn process_row_template(
     rows_tpl
 }
 
+#[derive(Clone, Copy)]
 struct ProcessTemplateOptions {
     pub rows_end_time: i64,
     pub start_time: Option<i64>,
     pub evaluation_timestamp: i64,
     pub is_email: bool,
+    /// Set for the resolved notification of an alert which stopped firing
+    pub recovery: Option<Recovery>,
 }
 
 async fn process_dest_template(
@@ -1293,6 +1582,7 @@ async fn process_dest_template(
         start_time,
         evaluation_timestamp,
         is_email,
+        recovery,
     } = options;
     // format values
     let alert_count = rows.len();
@@ -1442,17 +1732,60 @@ async fn process_dest_template(
         )
     };
 
-    // Shorten the alert url
-    let alert_url = match short_url::shorten(&alert.org_id, &alert_url).await {
-        Ok(short_url) => short_url,
-        Err(e) => {
-            log::error!("Error shortening alert url: {e}");
-            alert_url
+    // Shorten the alert url, sample alerts rendered for a preview aren't saved and don't need one
+    let alert_url = if alert.id.is_none() {
+        alert_url
+    } else {
+        match short_url::shorten(&alert.org_id, &alert_url).await {
+            Ok(short_url) => short_url,
+            Err(e) => {
+                log::error!("Error shortening alert url: {e}");
+                alert_url
+            }
         }
     };
 
     let evaluation_timestamp_millis = evaluation_timestamp / 1000;
     let evaluation_timestamp_seconds = evaluation_timestamp_millis / 1000;
+    let alert_status = if recovery.is_some() {
+        "resolved"
+    } else {
+        "firing"
+    };
+    if rendering::is_jinja(tpl) {
+        let mut ctx = Map::new();
+        for (key, value) in vars.iter() {
+            let val = value.iter().cloned().collect::<Vec<_>>();
+            ctx.insert(key.to_string(), val.join(", ").into());
+        }
+        ctx.extend(template_vars(org_name, alert, alert_count));
+        for (key, value) in [
+            ("alert_start_time", alert_start_time_str),
+            ("alert_end_time", alert_end_time_str),
+            ("alert_url", alert_url),
+            ("alert_trigger_time_str", evaluation_timestamp_str),
+        ] {
+            ctx.insert(key.to_string(), value.into());
+        }
+        for (key, value) in [
+            ("alert_trigger_time", evaluation_timestamp),
+            ("alert_trigger_time_millis", evaluation_timestamp_millis),
+            ("alert_trigger_time_seconds", evaluation_timestamp_seconds),
+        ] {
+            ctx.insert(key.to_string(), value.into());
+        }
+        ctx.insert("alert_status".to_string(), alert_status.into());
+        for (key, value) in recovery.iter().flat_map(Recovery::template_vars) {
+            ctx.insert(key.to_string(), value);
+        }
+        ctx.insert(
+            "rows".to_string(),
+            Value::Array(rows.iter().cloned().map(Value::Object).collect()),
+        );
+        ctx.insert("rendered_rows".to_string(), rows_tpl_val.into());
+        return render_jinja(alert, tpl, &ctx);
+    }
+
     let mut resp = tpl
         .replace("{org_name}", org_name)
         .replace("{stream_type}", alert.stream_type.as_str())
@@ -1484,7 +1817,16 @@ async fn process_dest_template(
             "{alert_trigger_time_seconds}",
             &evaluation_timestamp_seconds.to_string(),
         )
-        .replace("{alert_trigger_time_str}", &evaluation_timestamp_str);
+        .replace("{alert_trigger_time_str}", &evaluation_timestamp_str)
+        .replace("{alert_status}", alert_status);
+    for (key, value) in recovery.iter().flat_map(Recovery::template_vars) {
+        let value = match value {
+            Value::String(v) => v,
+            Value::Null => String::from("N/A"),
+            v => v.to_string(),
+        };
+        resp = resp.replace(&format!("{{{key}}}"), &value);
+    }
 
     if let Some(contidion) = &alert.query_condition.promql_condition {
         resp = resp
@@ -1493,6 +1835,17 @@ async fn process_dest_template(
     }
 
     process_variable_replace(&mut resp, "rows", &VarValue::Vector(rows_tpl_val), is_email);
+    for key in vars.keys() {
+        if resp.contains(&format!("{{{key}|")) {
+            let mut values = Vec::new();
+            for value in rows.iter().filter_map(|row| row.get(key)) {
+                if !values.contains(&value) {
+                    values.push(value);
+                }
+            }
+            process_unit_replace(&mut resp, key, &values);
+        }
+    }
     for (key, value) in vars.iter() {
         if resp.contains(&format!("{{{key}}}")) {
             let val = value.iter().cloned().collect::<Vec<_>>();
@@ -1508,6 +1861,65 @@ async fn process_dest_template(
     resp
 }
 
+/// Returns the alert variables available to jinja templates. Context attributes come first so that
+/// they can't shadow the variables of the alert.
+fn template_vars(org_name: &str, alert: &Alert, alert_count: usize) -> Map<String, Value> {
+    let mut vars = Map::new();
+    if let Some(attrs) = &alert.context_attributes {
+        for (key, value) in attrs.iter() {
+            vars.insert(key.to_string(), value.as_str().into());
+        }
+    }
+    let alert_type = if alert.is_real_time {
+        "realtime"
+    } else {
+        "scheduled"
+    };
+    for (key, value) in [
+        ("org_name", org_name),
+        ("stream_type", alert.stream_type.as_str()),
+        ("stream_name", &alert.stream_name),
+        ("alert_name", &alert.name),
+        ("alert_type", alert_type),
+    ] {
+        vars.insert(key.to_string(), value.into());
+    }
+    vars.insert(
+        "alert_period".to_string(),
+        alert.trigger_condition.period.into(),
+    );
+    vars.insert(
+        "alert_operator".to_string(),
+        alert.trigger_condition.operator.to_string().into(),
+    );
+    vars.insert(
+        "alert_threshold".to_string(),
+        alert.trigger_condition.threshold.into(),
+    );
+    vars.insert("alert_count".to_string(), alert_count.into());
+    if let Some(condition) = &alert.query_condition.promql_condition {
+        vars.insert(
+            "alert_promql_operator".to_string(),
+            condition.operator.to_string().into(),
+        );
+        vars.insert("alert_promql_value".to_string(), condition.value.clone());
+    }
+    vars
+}
+
+/// Renders a jinja template, falling back to the template itself so that a broken template still
+/// sends a notification.
+fn render_jinja(alert: &Alert, tpl: &str, ctx: &Map<String, Value>) -> String {
+    rendering::render(tpl, ctx).unwrap_or_else(|e| {
+        log::error!(
+            "Error rendering template for alert {}/{}: {e}",
+            alert.org_id,
+            alert.name
+        );
+        tpl.to_string()
+    })
+}
+
 fn process_variable_replace(tpl: &mut String, var_name: &str, var_val: &VarValue, is_email: bool) {
     let pattern = "{".to_owned() + var_name + "}";
     if tpl.contains(&pattern) {
@@ -1530,6 +1942,38 @@ fn process_variable_replace(tpl: &mut String, var_name: &str, var_val: &VarValue
     }
 }
 
+/// Replaces `{name|unit}` with the values of the variable formatted in the unit, like
+/// `{bytes_sent|bytes}` becoming `1.5 GiB`. Values that aren't numbers are kept as they are.
+fn process_unit_replace(tpl: &mut String, var_name: &str, values: &[&Value]) {
+    let pattern = "{".to_owned() + var_name + "|";
+    let mut from = 0;
+    while let Some(start) = tpl[from..].find(&pattern).map(|p| p + from) {
+        let p = start + pattern.len();
+        let Some(end) = tpl[p..].find('}') else {
+            break;
+        };
+        let unit = Unit::parse(&tpl[p..p + end], None);
+        let formatted = values
+            .iter()
+            .map(|value| {
+                let number = value
+                    .as_f64()
+                    .or_else(|| value.as_str().and_then(|v| v.parse().ok()));
+                match (unit.as_ref(), number) {
+                    (Some(unit), Some(number)) => unit.format(number, None),
+                    _ => match value.as_str() {
+                        Some(v) => v.to_string(),
+                        None => value.to_string(),
+                    },
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        tpl.replace_range(start..p + end + 1, &formatted);
+        from = start + formatted.len();
+    }
+}
+
 pub fn get_row_column_map(rows: &[Map<String, Value>]) -> HashMap<String, HashSet<String>> {
     let mut vars = HashMap::with_capacity(rows.len());
     for row in rows.iter() {
@@ -1840,6 +2284,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_process_unit_replace() {
+        let mut tpl = "sent {bytes|bytes} in {took|ms}, {took|default} {host|bytes}".to_string();
+        process_unit_replace(&mut tpl, "bytes", &[&Value::from(1610612736)]);
+        process_unit_replace(&mut tpl, "took", &[&Value::from("1500")]);
+        process_unit_replace(&mut tpl, "host", &[&Value::from("web-1")]);
+        assert_eq!(tpl, "sent 1.5 GiB in 1.5 s, 1500 web-1");
+
+        let mut tpl = "{v|percent-1}".to_string();
+        process_unit_replace(&mut tpl, "v", &[&Value::from(0.5), &Value::from(0.25)]);
+        assert_eq!(tpl, "50%, 25%");
+    }
+
+    #[test]
+    fn test_recovery_template_vars() {
+        let recovery = Recovery {
+            firing_duration: 3_725_000_000,
+            last_value: Some(42.5),
+        };
+        let vars = recovery.template_vars();
+        assert_eq!(
+            vars,
+            vec![
+                ("alert_firing_duration", Value::from(3725)),
+                ("alert_firing_duration_str", Value::from("1h2m5s")),
+                ("alert_last_value", Value::from(42.5)),
+            ]
+        );
+
+        let vars = Recovery::default().template_vars();
+        assert_eq!(vars[1].1, Value::from("0s"));
+        assert_eq!(vars[2].1, Value::Null);
+    }
+
     #[tokio::test]
     async fn test_alert_create() {
         let org_id = "default";
@@ -0,0 +1,297 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Evaluation of anomaly detection alerts, which compare the aggregate of the period with the
+//! same period on each of the previous days.
+
+use std::collections::HashMap;
+
+use chrono::Duration;
+use config::{
+    RwHashMap, ider,
+    meta::{
+        alerts::{
+            QueryType, TriggerEvalResults,
+            alert::Alert,
+            anomaly::{AnomalyCondition, MAX_BASELINE_DAYS},
+        },
+        cluster::RoleGroup,
+        search::{self, SearchEventContext, SearchEventType},
+    },
+    utils::{
+        json::{self, Map, Value},
+        time::{day_micros, second_micros},
+    },
+};
+use once_cell::sync::Lazy;
+
+use super::{AGG_VALUE_COL, ConditionListExt, agg_expr, alert::AlertError};
+use crate::service::search::{self as SearchService, sql::RE_ONLY_SELECT};
+
+/// Aggregates of the past windows of each alert, keyed by the alert id. The baseline windows
+/// of one evaluation are mostly the ones of the previous evaluations, so they are only queried
+/// once.
+static BASELINES: Lazy<RwHashMap<String, Baseline>> = Lazy::new(Default::default);
+
+struct Baseline {
+    /// Query the values were computed with, the cache is dropped when the alert changes.
+    sql: String,
+    values: HashMap<(i64, i64), Option<f64>>,
+}
+
+/// Checks the anomaly condition of an alert before it is saved.
+pub async fn validate(alert: &Alert) -> Result<(), AlertError> {
+    let Some(anomaly) = alert.anomaly.as_ref() else {
+        return Ok(());
+    };
+    if alert.is_real_time {
+        return Err(AlertError::InvalidAnomaly(
+            "anomaly alerts can't be realtime".to_string(),
+        ));
+    }
+    if alert.multi_condition.is_some() {
+        return Err(AlertError::InvalidAnomaly(
+            "an alert can't be both multi-condition and anomaly".to_string(),
+        ));
+    }
+    if !(1..=MAX_BASELINE_DAYS).contains(&anomaly.baseline_days) {
+        return Err(AlertError::InvalidAnomaly(format!(
+            "baseline days should be between 1 and {MAX_BASELINE_DAYS}"
+        )));
+    }
+    if !anomaly.deviation.is_finite() || anomaly.deviation <= 0.0 {
+        return Err(AlertError::InvalidAnomaly(
+            "deviation should be greater than 0".to_string(),
+        ));
+    }
+    match alert.query_condition.query_type {
+        QueryType::Custom => {
+            if anomaly.function.is_none() || anomaly.column.as_ref().is_none_or(|c| c.is_empty()) {
+                return Err(AlertError::InvalidAnomaly(
+                    "custom queries should have an aggregate function and column".to_string(),
+                ));
+            }
+        }
+        QueryType::SQL => {
+            let Some(sql) = alert.query_condition.sql.as_ref().filter(|s| !s.is_empty()) else {
+                return Err(AlertError::SqlMissingQuery);
+            };
+            if RE_ONLY_SELECT.is_match(sql) {
+                return Err(AlertError::SqlContainsSelectStar);
+            }
+        }
+        QueryType::PromQL => {
+            return Err(AlertError::InvalidAnomaly(
+                "PromQL queries are not supported".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Evaluates the aggregate of the period against its baseline. When it is an anomaly, a single
+/// row is returned with the value, the baseline and the deviation.
+pub async fn evaluate(
+    alert: &Alert,
+    anomaly: &AnomalyCondition,
+    (start_time, end_time): (Option<i64>, i64),
+    trace_id: Option<String>,
+) -> Result<TriggerEvalResults, anyhow::Error> {
+    let mut eval_results = TriggerEvalResults {
+        end_time,
+        ..Default::default()
+    };
+    let period = Duration::try_minutes(alert.trigger_condition.period)
+        .unwrap()
+        .num_microseconds()
+        .unwrap();
+    let start_time = start_time.unwrap_or(end_time - period);
+    let window = end_time - start_time;
+    let sql = query_sql(alert, anomaly).await?;
+    let trace_id = trace_id.unwrap_or_else(ider::generate_trace_id);
+
+    let (value, took, scan_records) =
+        aggregate(alert, &sql, (start_time, end_time), &trace_id).await?;
+    eval_results.query_took = Some(took);
+    eval_results.scan_records = Some(scan_records);
+    // no data in the period, nothing to compare
+    let Some(value) = value else {
+        return Ok(eval_results);
+    };
+
+    // baseline windows end on whole minutes, so that the next days' evaluations find them in
+    // the cache even when the scheduler runs a bit late
+    let minute = second_micros(60);
+    let aligned_end = end_time - end_time % minute;
+    let key = alert
+        .id
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| format!("{}/{}", alert.org_id, alert.name));
+    let mut baseline = Vec::with_capacity(anomaly.baseline_days as usize);
+    for day in 1..=anomaly.baseline_days {
+        let end = aligned_end - day_micros(day);
+        let range = (end - window, end);
+        let cached = BASELINES
+            .get(&key)
+            .filter(|cache| cache.sql == sql)
+            .and_then(|cache| cache.values.get(&range).copied());
+        let past = match cached {
+            Some(past) => past,
+            None => {
+                let (past, took, scan_records) = aggregate(alert, &sql, range, &trace_id).await?;
+                *eval_results.query_took.get_or_insert(0) += took;
+                *eval_results.scan_records.get_or_insert(0) += scan_records;
+                cache_value(&key, &sql, range, past);
+                past
+            }
+        };
+        if let Some(past) = past {
+            baseline.push(past);
+        }
+    }
+    // the current window is part of the baseline of the next days
+    if end_time == aligned_end {
+        cache_value(&key, &sql, (start_time, end_time), Some(value));
+    }
+    if let Some(mut cache) = BASELINES.get_mut(&key) {
+        let oldest = aligned_end - day_micros(anomaly.baseline_days + 1) - window;
+        cache.values.retain(|(start, _), _| *start >= oldest);
+    }
+
+    let Some(score) = anomaly.score(value, &baseline) else {
+        log::debug!(
+            "[ALERT anomaly] {}/{}: not enough baseline data, {} days",
+            alert.org_id,
+            alert.name,
+            baseline.len()
+        );
+        return Ok(eval_results);
+    };
+    if anomaly.is_anomaly(&score) {
+        let mut row = Map::new();
+        row.insert(AGG_VALUE_COL.to_string(), Value::from(value));
+        row.insert("baseline_mean".to_string(), Value::from(score.mean));
+        row.insert("baseline_stddev".to_string(), Value::from(score.stddev));
+        row.insert("baseline_days".to_string(), Value::from(score.samples));
+        // an infinite deviation, from a flat baseline, is serialized as null
+        row.insert("deviation".to_string(), Value::from(score.deviation));
+        row.insert(
+            "deviation_type".to_string(),
+            json::to_value(anomaly.deviation_type)?,
+        );
+        eval_results.data = Some(vec![row]);
+    }
+    Ok(eval_results)
+}
+
+fn cache_value(key: &str, sql: &str, range: (i64, i64), value: Option<f64>) {
+    let mut cache = BASELINES
+        .entry(key.to_string())
+        .or_insert_with(|| Baseline {
+            sql: sql.to_string(),
+            values: HashMap::new(),
+        });
+    if cache.sql != sql {
+        cache.sql = sql.to_string();
+        cache.values.clear();
+    }
+    cache.values.insert(range, value);
+}
+
+/// Query returning the aggregate of a window in the `alert_agg_value` column.
+async fn query_sql(alert: &Alert, anomaly: &AnomalyCondition) -> Result<String, anyhow::Error> {
+    let query = &alert.query_condition;
+    match query.query_type {
+        QueryType::SQL => query
+            .sql
+            .clone()
+            .filter(|sql| !sql.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Anomaly alert should have a query")),
+        QueryType::Custom => {
+            let (Some(function), Some(column)) =
+                (anomaly.function.as_ref(), anomaly.column.as_ref())
+            else {
+                return Err(anyhow::anyhow!(
+                    "Anomaly alert should have an aggregate function and column"
+                ));
+            };
+            let mut sql = format!(
+                "SELECT {} AS {AGG_VALUE_COL} FROM \"{}\"",
+                agg_expr(function, column),
+                alert.stream_name
+            );
+            if let Some(conditions) = query.conditions.as_ref()
+                && !conditions.is_empty().await
+            {
+                let schema =
+                    infra::schema::get(&alert.org_id, &alert.stream_name, alert.stream_type)
+                        .await?;
+                sql.push_str(&format!(" WHERE {}", conditions.to_sql(&schema).await?));
+            }
+            Ok(sql)
+        }
+        QueryType::PromQL => Err(anyhow::anyhow!(
+            "PromQL queries are not supported by anomaly alerts"
+        )),
+    }
+}
+
+/// Runs the query over the window, returning the aggregate, if there was any data, the time the
+/// query took and the records it scanned.
+async fn aggregate(
+    alert: &Alert,
+    sql: &str,
+    (start_time, end_time): (i64, i64),
+    trace_id: &str,
+) -> Result<(Option<f64>, i64, i64), anyhow::Error> {
+    let req = search::Request {
+        query: search::Query {
+            sql: sql.to_string(),
+            from: 0,
+            size: 1,
+            start_time,
+            end_time,
+            ..Default::default()
+        },
+        search_type: Some(SearchEventType::Alerts),
+        search_event_context: Some(SearchEventContext::with_alert(Some(format!(
+            "/alerts/{}/{}/{}/{}",
+            alert.org_id, alert.stream_type, alert.stream_name, alert.name
+        )))),
+        ..Default::default()
+    };
+    let resp = SearchService::grpc_search::grpc_search(
+        trace_id,
+        &alert.org_id,
+        alert.stream_type,
+        None,
+        &req,
+        Some(RoleGroup::Background),
+    )
+    .await?;
+    if resp.is_partial {
+        return Err(anyhow::anyhow!(
+            "Partial response: {}",
+            resp.function_error.join(", ")
+        ));
+    }
+    let value = resp
+        .hits
+        .first()
+        .and_then(|hit| hit.get(AGG_VALUE_COL))
+        .filter(|value| !value.is_null())
+        .map(json::get_float_value);
+    Ok((value, resp.took as i64, resp.scan_records as i64))
+}
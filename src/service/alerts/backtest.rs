@@ -0,0 +1,209 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Replays a scheduled alert over a past time range, evaluating it at the times the scheduler
+//! would have, so the thresholds can be tuned before the alert is enabled.
+
+use std::future::Future;
+
+use chrono::Duration;
+use config::meta::alerts::{AlertBacktest, BacktestFiring, TriggerCondition, TriggerEvalResults};
+use svix_ksuid::Ksuid;
+
+use super::alert::{self, AlertError, AlertExt};
+
+/// Maximum number of evaluations of one backtest.
+pub const MAX_BACKTEST_EVALUATIONS: usize = 1000;
+/// Maximum number of rows returned for every firing.
+const MAX_BACKTEST_ROWS: usize = 10;
+
+/// Evaluates the alert between `start_time` and `end_time`, given in microseconds. Like the
+/// scheduler, the alert is not evaluated during the silence period following a firing.
+pub async fn backtest(
+    org_id: &str,
+    alert_id: Ksuid,
+    start_time: i64,
+    end_time: i64,
+) -> Result<AlertBacktest, AlertError> {
+    if end_time <= start_time {
+        return Err(AlertError::BacktestTimeRange);
+    }
+    let alert = alert::get_by_id_db(org_id, alert_id).await?;
+    if alert.is_real_time {
+        return Err(AlertError::BacktestRealtime);
+    }
+    // the random delay of the scheduler makes no sense for a replay
+    let mut trigger_condition = alert.trigger_condition.clone();
+    trigger_condition.tolerance_in_secs = None;
+    replay(
+        &trigger_condition,
+        alert.tz_offset,
+        start_time,
+        end_time,
+        |window_start, eval_time| alert.evaluate(None, (Some(window_start), eval_time), None),
+    )
+    .await
+}
+
+/// Replays the evaluations of the scheduler between `start_time` and `end_time`, `evaluate`
+/// is given the time range of every evaluation.
+async fn replay<F, Fut>(
+    trigger_condition: &TriggerCondition,
+    tz_offset: i32,
+    start_time: i64,
+    end_time: i64,
+    mut evaluate: F,
+) -> Result<AlertBacktest, AlertError>
+where
+    F: FnMut(i64, i64) -> Fut,
+    Fut: Future<Output = Result<TriggerEvalResults, anyhow::Error>>,
+{
+    let period = Duration::try_minutes(trigger_condition.period)
+        .unwrap_or_default()
+        .num_microseconds()
+        .unwrap_or_default();
+
+    let mut result = AlertBacktest::default();
+    let mut fired = false;
+    let mut eval_time = start_time;
+    loop {
+        let next = trigger_condition
+            .get_next_trigger_time_non_aligned(
+                true,
+                tz_offset,
+                fired && trigger_condition.silence > 0,
+                Some(eval_time),
+            )
+            .map_err(AlertError::Backtest)?;
+        if next <= eval_time {
+            return Err(AlertError::Backtest(anyhow::anyhow!(
+                "alert frequency should be greater than zero"
+            )));
+        }
+        eval_time = next;
+        if eval_time > end_time {
+            break;
+        }
+        if result.evaluations >= MAX_BACKTEST_EVALUATIONS {
+            result.truncated = true;
+            break;
+        }
+
+        result.evaluations += 1;
+        let window_start = eval_time - period;
+        let eval_results = evaluate(window_start, eval_time)
+            .await
+            .map_err(AlertError::Backtest)?;
+        fired = false;
+        if let Some(mut rows) = eval_results.data {
+            fired = true;
+            let matched_rows = rows.len();
+            rows.truncate(MAX_BACKTEST_ROWS);
+            result.firings.push(BacktestFiring {
+                timestamp: eval_time,
+                start_time: window_start,
+                end_time: eval_results.end_time,
+                matched_rows,
+                rows,
+            });
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use config::{
+        meta::alerts::{FrequencyType, Operator},
+        utils::json::{Map, Value},
+    };
+
+    use super::*;
+    use crate::service::alerts::apply_threshold;
+
+    const MINUTE: i64 = 60_000_000;
+
+    fn condition(operator: Operator, threshold: i64, silence: i64) -> TriggerCondition {
+        TriggerCondition {
+            period: 5,
+            operator,
+            threshold,
+            frequency: 300,
+            frequency_type: FrequencyType::Minutes,
+            silence,
+            ..Default::default()
+        }
+    }
+
+    /// Evaluates the condition against windows holding `counts[i]` rows for the i-th
+    /// evaluation.
+    async fn run(condition: &TriggerCondition, counts: &[usize]) -> AlertBacktest {
+        let end_time = counts.len() as i64 * 5 * MINUTE;
+        let mut i = 0;
+        replay(condition, 0, 0, end_time, |_, eval_time| {
+            let records = vec![Map::<String, Value>::new(); counts[i]];
+            i += 1;
+            let data = apply_threshold(condition, records);
+            async move {
+                Ok(TriggerEvalResults {
+                    data,
+                    end_time: eval_time,
+                    ..Default::default()
+                })
+            }
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_replay_threshold() {
+        let cond = condition(Operator::GreaterThanEquals, 3, 0);
+        let result = run(&cond, &[0, 3, 2, 12, 5]).await;
+        assert_eq!(result.evaluations, 5);
+        assert!(!result.truncated);
+        let fired = result
+            .firings
+            .iter()
+            .map(|f| (f.timestamp / MINUTE, f.matched_rows, f.rows.len()))
+            .collect::<Vec<_>>();
+        // the rows returned are capped, not the count of matched rows
+        assert_eq!(fired, [(10, 3, 3), (20, 12, 10), (25, 5, 5)]);
+        assert_eq!(result.firings[0].start_time, 5 * MINUTE);
+
+        let cond = condition(Operator::LessThan, 1, 0);
+        let result = run(&cond, &[0, 3, 0]).await;
+        let fired = result
+            .firings
+            .iter()
+            .map(|f| f.timestamp / MINUTE)
+            .collect::<Vec<_>>();
+        assert_eq!(fired, [5, 15]);
+    }
+
+    #[tokio::test]
+    async fn test_replay_silence() {
+        // no evaluation during the 10 minutes following a firing
+        let cond = condition(Operator::GreaterThan, 0, 10);
+        let result = run(&cond, &[1, 1, 1, 1]).await;
+        let fired = result
+            .firings
+            .iter()
+            .map(|f| f.timestamp / MINUTE)
+            .collect::<Vec<_>>();
+        assert_eq!(fired, [5, 15]);
+        assert_eq!(result.evaluations, 2);
+    }
+}
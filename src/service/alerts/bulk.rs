@@ -0,0 +1,450 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Declarative sync of the alerts of an org, with optionally their destinations and templates,
+//! from the full set of them. What is missing from the set is deleted, which lets alerts be
+//! managed as code without racing individual create, update and delete calls.
+
+use std::collections::{HashMap, HashSet};
+
+use config::{
+    meta::{
+        alerts::{
+            AlertsSyncPlan, SyncChanges,
+            alert::{Alert, ListAlertsParams},
+        },
+        destinations::{Destination, Module, Template},
+        folder::{DEFAULT_FOLDER, FolderType},
+    },
+    utils::json::{self, Value},
+};
+use infra::{
+    db::{ORM_CLIENT, connect_to_orm},
+    table,
+};
+use itertools::Itertools;
+
+use super::{
+    alert::{self, AlertError},
+    destinations as destination_service, templates as template_service,
+};
+use crate::service::db::alerts::{destinations::DestinationError, templates::TemplateError};
+
+/// Fields of the alerts set by the server, left out when comparing them.
+const ALERT_MANAGED_FIELDS: [&str; 7] = [
+    "id",
+    "org_id",
+    "owner",
+    "updated_at",
+    "last_edited_by",
+    "last_triggered_at",
+    "last_satisfied_at",
+];
+const DESTINATION_MANAGED_FIELDS: [&str; 2] = ["id", "org_id"];
+const TEMPLATE_MANAGED_FIELDS: [&str; 3] = ["id", "org_id", "is_default"];
+
+#[derive(Debug, thiserror::Error)]
+pub enum SyncError {
+    #[error("Error listing the existing {kind}: {error}")]
+    List { kind: &'static str, error: String },
+
+    #[error("Duplicate {kind} {name} in the set")]
+    Duplicate { kind: &'static str, name: String },
+
+    #[error("Alert {alert} uses destination {destination} which doesn't exist")]
+    MissingDestination { alert: String, destination: String },
+
+    #[error("Destination {destination} uses template {template} which doesn't exist")]
+    MissingTemplate {
+        destination: String,
+        template: String,
+    },
+
+    #[error("Alert {alert} is in folder {folder} which doesn't exist")]
+    FolderNotFound { alert: String, folder: String },
+
+    #[error("Error syncing alert {name}: {source}")]
+    Alert { name: String, source: AlertError },
+
+    #[error("Error syncing destination {name}: {source}")]
+    Destination {
+        name: String,
+        source: DestinationError,
+    },
+
+    #[error("Error syncing template {name}: {source}")]
+    Template { name: String, source: TemplateError },
+}
+
+/// Turns the alerts, destinations and templates of the org into the given ones. `alerts` holds
+/// the folder of every alert, `None` destinations or templates are left as they are.
+///
+/// The set is checked before anything changes. The changes are then made in dependency order, a
+/// failure stops the sync and keeps the changes made before it: syncing the same set again
+/// finishes it.
+pub async fn sync(
+    org_id: &str,
+    user_id: &str,
+    alerts: Vec<(String, Alert)>,
+    destinations: Option<Vec<Destination>>,
+    templates: Option<Vec<Template>>,
+    dry_run: bool,
+) -> Result<AlertsSyncPlan, SyncError> {
+    let existing_templates =
+        template_service::list(org_id, None)
+            .await
+            .map_err(|e| SyncError::List {
+                kind: "templates",
+                error: e.to_string(),
+            })?;
+    let existing_destinations = destination_service::list(org_id, None, None)
+        .await
+        .map_err(|e| SyncError::List {
+            kind: "destinations",
+            error: e.to_string(),
+        })?;
+    let existing_alerts = alert::list_with_folders_db(ListAlertsParams::new(org_id))
+        .await
+        .map_err(|e| SyncError::List {
+            kind: "alerts",
+            error: e.to_string(),
+        })?;
+
+    // the default templates belong to every org, they are never synced
+    let managed_templates: HashMap<String, &Template> = existing_templates
+        .iter()
+        .filter(|t| t.org_id == org_id)
+        .map(|t| (t.name.clone(), t))
+        .collect();
+    let managed_destinations: HashMap<String, &Destination> = existing_destinations
+        .iter()
+        .filter(|d| d.is_alert_destinations())
+        .map(|d| (d.name.clone(), d))
+        .collect();
+    let managed_alerts: HashMap<String, (String, &Alert)> = existing_alerts
+        .iter()
+        .map(|(folder, a)| (alert_key(a), (folder.folder_id.clone(), a)))
+        .collect();
+
+    let mut plan = AlertsSyncPlan {
+        dry_run,
+        ..Default::default()
+    };
+    let template_names: HashSet<String> = match &templates {
+        Some(templates) => {
+            let desired = templates
+                .iter()
+                .map(|t| {
+                    let key = t.name.trim().to_string();
+                    let value = comparable(t, &TEMPLATE_MANAGED_FIELDS);
+                    (key, value)
+                })
+                .collect_vec();
+            check_duplicates("template", &desired)?;
+            let existing = managed_templates
+                .iter()
+                .map(|(k, t)| (k.clone(), comparable(t, &TEMPLATE_MANAGED_FIELDS)))
+                .collect();
+            plan.templates = diff(&existing, &desired);
+            existing_templates
+                .iter()
+                .filter(|t| t.org_id != org_id)
+                .map(|t| t.name.clone())
+                .chain(desired.into_iter().map(|(k, _)| k))
+                .collect()
+        }
+        None => existing_templates.iter().map(|t| t.name.clone()).collect(),
+    };
+
+    let kept_destinations = match &destinations {
+        Some(destinations) => {
+            let desired = destinations
+                .iter()
+                .map(|d| {
+                    let key = d.name.trim().to_string();
+                    let value = comparable(d, &DESTINATION_MANAGED_FIELDS);
+                    (key, value)
+                })
+                .collect_vec();
+            check_duplicates("destination", &desired)?;
+            let existing = managed_destinations
+                .iter()
+                .map(|(k, d)| (k.clone(), comparable(d, &DESTINATION_MANAGED_FIELDS)))
+                .collect();
+            plan.destinations = diff(&existing, &desired);
+            destinations.iter().collect_vec()
+        }
+        None => managed_destinations.values().copied().collect_vec(),
+    };
+    for destination in kept_destinations.iter() {
+        let Module::Alert {
+            template,
+            resolved_template,
+            ..
+        } = &destination.module
+        else {
+            continue;
+        };
+        if let Some(template) = std::iter::once(template)
+            .chain(resolved_template)
+            .find(|t| !template_names.contains(*t))
+        {
+            return Err(SyncError::MissingTemplate {
+                destination: destination.name.clone(),
+                template: template.clone(),
+            });
+        }
+    }
+    let destination_names: HashSet<&str> =
+        kept_destinations.iter().map(|d| d.name.trim()).collect();
+
+    let desired_alerts = alerts
+        .iter()
+        .map(|(folder_id, a)| (alert_key(a), comparable_alert(folder_id, a)))
+        .collect_vec();
+    check_duplicates("alert", &desired_alerts)?;
+    let mut folders = HashSet::new();
+    for (folder_id, a) in alerts.iter() {
+        if let Some(destination) = a
+            .destinations
+            .iter()
+            .find(|d| !destination_names.contains(d.as_str()))
+        {
+            return Err(SyncError::MissingDestination {
+                alert: alert_key(a),
+                destination: destination.clone(),
+            });
+        }
+        // the folders are not synced, they have to exist, but for the default one created with
+        // the first alert
+        if folder_id != DEFAULT_FOLDER && folders.insert(folder_id.as_str()) {
+            let exists = table::folders::exists(org_id, folder_id, FolderType::Alerts)
+                .await
+                .map_err(|e| SyncError::Alert {
+                    name: alert_key(a),
+                    source: e.into(),
+                })?;
+            if !exists {
+                return Err(SyncError::FolderNotFound {
+                    alert: alert_key(a),
+                    folder: folder_id.clone(),
+                });
+            }
+        }
+    }
+    let existing = managed_alerts
+        .iter()
+        .map(|(k, (folder_id, a))| (k.clone(), comparable_alert(folder_id, a)))
+        .collect();
+    plan.alerts = diff(&existing, &desired_alerts);
+
+    if dry_run {
+        return Ok(plan);
+    }
+
+    // create and update in dependency order, then delete in the reverse one
+    for mut template in templates.unwrap_or_default() {
+        template.name = template.name.trim().to_string();
+        template.org_id = org_id.to_string();
+        let name = template.name.clone();
+        let res = if plan.templates.create.contains(&name) {
+            template_service::save("", template, true).await
+        } else if plan.templates.update.contains(&name) {
+            template_service::save(&name, template, false).await
+        } else {
+            continue;
+        };
+        res.map_err(|source| SyncError::Template { name, source })?;
+    }
+    for mut destination in destinations.unwrap_or_default() {
+        destination.name = destination.name.trim().to_string();
+        destination.org_id = org_id.to_string();
+        let name = destination.name.clone();
+        let res = if plan.destinations.create.contains(&name) {
+            destination_service::save("", destination, true).await
+        } else if plan.destinations.update.contains(&name) {
+            destination_service::save(&name, destination, false).await
+        } else {
+            continue;
+        };
+        res.map_err(|source| SyncError::Destination { name, source })?;
+    }
+
+    let conn = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    for (folder_id, mut a) in alerts {
+        let name = alert_key(&a);
+        a.last_edited_by = Some(user_id.to_string());
+        let res = if plan.alerts.create.contains(&name) {
+            if a.owner.as_deref().is_none_or(str::is_empty) {
+                a.owner = Some(user_id.to_string());
+            }
+            alert::create(conn, org_id, &folder_id, a).await
+        } else if plan.alerts.update.contains(&name) {
+            let (curr_folder_id, existing) = &managed_alerts[&name];
+            a.id = existing.id;
+            if a.owner.as_deref().is_none_or(str::is_empty) {
+                a.owner = existing.owner.clone();
+            }
+            let move_folders = (*curr_folder_id != folder_id)
+                .then_some((curr_folder_id.as_str(), folder_id.as_str()));
+            alert::update(conn, org_id, move_folders, a).await
+        } else {
+            continue;
+        };
+        res.map_err(|source| SyncError::Alert { name, source })?;
+    }
+    for name in plan.alerts.delete.iter() {
+        let Some(alert_id) = managed_alerts[name].1.id else {
+            continue;
+        };
+        alert::delete_by_id(conn, org_id, alert_id)
+            .await
+            .map_err(|source| SyncError::Alert {
+                name: name.clone(),
+                source,
+            })?;
+    }
+    for name in plan.destinations.delete.iter() {
+        destination_service::delete(org_id, name)
+            .await
+            .map_err(|source| SyncError::Destination {
+                name: name.clone(),
+                source,
+            })?;
+    }
+    for name in plan.templates.delete.iter() {
+        template_service::delete(org_id, name)
+            .await
+            .map_err(|source| SyncError::Template {
+                name: name.clone(),
+                source,
+            })?;
+    }
+    Ok(plan)
+}
+
+/// Alerts are unique by their name within their stream.
+fn alert_key(alert: &Alert) -> String {
+    format!(
+        "{}/{}/{}",
+        alert.stream_type,
+        alert.stream_name,
+        alert.name.trim()
+    )
+}
+
+fn comparable_alert(folder_id: &str, alert: &Alert) -> Value {
+    let mut value = comparable(alert, &ALERT_MANAGED_FIELDS);
+    if let Some(map) = value.as_object_mut() {
+        map.insert(
+            "folder_id".to_string(),
+            Value::String(folder_id.to_string()),
+        );
+    }
+    value
+}
+
+/// The resource as JSON, without the fields the server sets.
+fn comparable<T: serde::Serialize>(resource: &T, managed_fields: &[&str]) -> Value {
+    let mut value = json::to_value(resource).unwrap_or_default();
+    if let Some(map) = value.as_object_mut() {
+        for field in managed_fields {
+            map.remove(*field);
+        }
+    }
+    value
+}
+
+fn check_duplicates(kind: &'static str, desired: &[(String, Value)]) -> Result<(), SyncError> {
+    let mut seen = HashSet::new();
+    match desired.iter().find(|(key, _)| !seen.insert(key)) {
+        Some((key, _)) => Err(SyncError::Duplicate {
+            kind,
+            name: key.clone(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Sorts the desired resources into the changes turning the existing ones into them, the
+/// resources missing from the desired ones being deleted.
+fn diff(existing: &HashMap<String, Value>, desired: &[(String, Value)]) -> SyncChanges {
+    let mut changes = SyncChanges::default();
+    for (key, value) in desired {
+        match existing.get(key) {
+            None => changes.create.push(key.clone()),
+            Some(existing) if existing != value => changes.update.push(key.clone()),
+            Some(_) => changes.unchanged.push(key.clone()),
+        }
+    }
+    let desired_keys: HashSet<&String> = desired.iter().map(|(key, _)| key).collect();
+    changes.delete = existing
+        .keys()
+        .filter(|key| !desired_keys.contains(key))
+        .cloned()
+        .sorted()
+        .collect();
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let existing = HashMap::from([
+            ("a".to_string(), json::json!({"body": "a"})),
+            ("b".to_string(), json::json!({"body": "b"})),
+            ("d".to_string(), json::json!({"body": "d"})),
+            ("c".to_string(), json::json!({"body": "c"})),
+        ]);
+        let desired = vec![
+            ("b".to_string(), json::json!({"body": "b2"})),
+            ("a".to_string(), json::json!({"body": "a"})),
+            ("e".to_string(), json::json!({"body": "e"})),
+        ];
+        let changes = diff(&existing, &desired);
+        assert_eq!(changes.create, vec!["e"]);
+        assert_eq!(changes.update, vec!["b"]);
+        assert_eq!(changes.unchanged, vec!["a"]);
+        assert_eq!(changes.delete, vec!["c", "d"]);
+
+        assert!(check_duplicates("template", &desired).is_ok());
+        let desired = vec![
+            ("a".to_string(), Value::Null),
+            ("a".to_string(), Value::Null),
+        ];
+        assert!(matches!(
+            check_duplicates("template", &desired),
+            Err(SyncError::Duplicate { name, .. }) if name == "a"
+        ));
+    }
+
+    #[test]
+    fn test_comparable_alert() {
+        let mut alert = Alert {
+            name: "errors".to_string(),
+            destinations: vec!["slack".to_string()],
+            ..Default::default()
+        };
+        let desired = comparable_alert("default", &alert);
+        alert.id = Some(svix_ksuid::Ksuid::new(None, None));
+        alert.org_id = "org".to_string();
+        alert.owner = Some("root@example.com".to_string());
+        assert_eq!(comparable_alert("default", &alert), desired);
+        assert_ne!(comparable_alert("ops", &alert), desired);
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Evaluation of composite alerts, which fire on the firing state of other alerts.
+
+use chrono::Duration;
+use config::{
+    meta::{
+        alerts::{TriggerEvalResults, alert::Alert, composite::CompositeCondition},
+        triggers::{ScheduledTriggerData, TriggerModule},
+    },
+    utils::json::{self, Map, Value},
+};
+use hashbrown::HashMap;
+
+use super::alert::{AlertError, get_by_id_db};
+use crate::service::db;
+
+/// Checks the composite condition of an alert before it is saved.
+pub async fn validate(alert: &Alert, composite: &CompositeCondition) -> Result<(), AlertError> {
+    if alert.is_real_time {
+        return Err(AlertError::InvalidComposite(
+            "composite alerts can't be realtime".to_string(),
+        ));
+    }
+    if composite.window < 0 {
+        return Err(AlertError::InvalidComposite(
+            "window can't be negative".to_string(),
+        ));
+    }
+    composite.parse().map_err(AlertError::InvalidComposite)?;
+    for (name, alert_id) in composite.alerts.iter() {
+        if alert.id.as_ref() == Some(alert_id) {
+            return Err(AlertError::InvalidComposite(format!(
+                "alert {name} is the composite alert itself"
+            )));
+        }
+        match get_by_id_db(&alert.org_id, *alert_id).await {
+            Ok(_) => {}
+            Err(AlertError::AlertNotFound) => {
+                return Err(AlertError::InvalidComposite(format!(
+                    "alert {name} not found"
+                )));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+/// Fires when the expression holds, with the alerts satisfied within the window up to
+/// `end_time` counting as firing. Every referenced alert is returned as a row with its state.
+pub async fn evaluate(
+    alert: &Alert,
+    composite: &CompositeCondition,
+    end_time: i64,
+) -> Result<TriggerEvalResults, anyhow::Error> {
+    let expr = composite
+        .parse()
+        .map_err(|e| anyhow::anyhow!("Invalid composite condition: {e}"))?;
+    let window = if composite.window > 0 {
+        composite.window
+    } else {
+        alert.trigger_condition.period
+    };
+    let window_start = end_time
+        - Duration::try_minutes(window)
+            .unwrap_or_default()
+            .num_microseconds()
+            .unwrap_or_default();
+
+    let mut states = HashMap::with_capacity(composite.alerts.len());
+    for (name, alert_id) in composite.alerts.iter() {
+        // an alert that was never scheduled has never fired
+        let last_satisfied_at =
+            match db::scheduler::get(&alert.org_id, TriggerModule::Alert, &alert_id.to_string())
+                .await
+            {
+                Ok(trigger) => json::from_str::<ScheduledTriggerData>(&trigger.data)
+                    .ok()
+                    .and_then(|data| data.last_satisfied_at),
+                Err(_) => None,
+            };
+        let firing = last_satisfied_at.is_some_and(|t| t >= window_start && t <= end_time);
+        states.insert(name.as_str(), (firing, last_satisfied_at));
+    }
+
+    let mut eval_results = TriggerEvalResults {
+        end_time,
+        ..Default::default()
+    };
+    if expr.evaluate(&|name| states.get(name).is_some_and(|(firing, _)| *firing)) {
+        let rows = composite
+            .alerts
+            .iter()
+            .map(|(name, alert_id)| {
+                let (firing, last_satisfied_at) = states[name.as_str()];
+                let mut row = Map::new();
+                row.insert("alert".to_string(), Value::String(name.to_string()));
+                row.insert("alert_id".to_string(), Value::String(alert_id.to_string()));
+                row.insert("firing".to_string(), Value::Bool(firing));
+                row.insert(
+                    "last_satisfied_at".to_string(),
+                    last_satisfied_at.map_or(Value::Null, Value::from),
+                );
+                row
+            })
+            .collect();
+        eval_results.data = Some(rows);
+    }
+    Ok(eval_results)
+}
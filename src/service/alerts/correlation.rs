@@ -0,0 +1,220 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Contrast analysis between the firing window of an alert and a baseline window, used to point
+//! at the field values that shifted the most when the alert fired.
+
+use std::cmp::Ordering;
+
+use chrono::Duration;
+use config::{
+    TIMESTAMP_COL_NAME, ider,
+    meta::{
+        alerts::{CorrelationContributor, alert::Alert},
+        cluster::RoleGroup,
+        search::{self, SearchEventType},
+    },
+    utils::json::{Map, Value},
+};
+use hashbrown::{HashMap, HashSet};
+
+use crate::service::search as SearchService;
+
+const DEFAULT_TOP: usize = 5;
+const DEFAULT_SAMPLE_SIZE: i64 = 1000;
+// values longer than this are most likely messages, not dimensions
+const MAX_VALUE_LEN: usize = 256;
+
+/// Samples the records of the alert stream in the firing window `[start_time, end_time)` and in
+/// the baseline window, and returns the top field values ranked by how much their share of
+/// records grew in the firing window.
+pub async fn analyze(
+    alert: &Alert,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<CorrelationContributor>, anyhow::Error> {
+    let Some(cfg) = alert.correlation.as_ref().filter(|c| c.enabled) else {
+        return Ok(vec![]);
+    };
+    let offset_minutes = if cfg.baseline_offset > 0 {
+        cfg.baseline_offset
+    } else {
+        alert.trigger_condition.period
+    };
+    let offset = Duration::try_minutes(offset_minutes)
+        .and_then(|d| d.num_microseconds())
+        .unwrap_or_default();
+    if offset <= 0 {
+        return Ok(vec![]);
+    }
+    let sample_size = if cfg.sample_size > 0 {
+        cfg.sample_size
+    } else {
+        DEFAULT_SAMPLE_SIZE
+    };
+    let top = if cfg.top > 0 { cfg.top } else { DEFAULT_TOP };
+
+    let (firing, baseline) = tokio::try_join!(
+        sample(alert, start_time, end_time, sample_size),
+        sample(alert, start_time - offset, end_time - offset, sample_size),
+    )?;
+    Ok(top_contributors(&firing, &baseline, &cfg.fields, top))
+}
+
+async fn sample(
+    alert: &Alert,
+    start_time: i64,
+    end_time: i64,
+    size: i64,
+) -> Result<Vec<Value>, anyhow::Error> {
+    let req = search::Request {
+        query: search::Query {
+            sql: format!("SELECT * FROM \"{}\"", alert.stream_name),
+            from: 0,
+            size,
+            start_time,
+            end_time,
+            ..Default::default()
+        },
+        search_type: Some(SearchEventType::Alerts),
+        ..Default::default()
+    };
+    let trace_id = ider::generate_trace_id();
+    let resp = SearchService::grpc_search::grpc_search(
+        &trace_id,
+        &alert.org_id,
+        alert.stream_type,
+        None,
+        &req,
+        Some(RoleGroup::Background),
+    )
+    .await?;
+    Ok(resp.hits)
+}
+
+/// Ranks the field values of the `firing` records by the difference between their share of
+/// `firing` and of `baseline` records. Only string and boolean values are considered, and only
+/// values more frequent in the firing window are returned.
+pub fn top_contributors(
+    firing: &[Value],
+    baseline: &[Value],
+    fields: &[String],
+    top: usize,
+) -> Vec<CorrelationContributor> {
+    if firing.is_empty() {
+        return vec![];
+    }
+    let fields: HashSet<&str> = fields.iter().map(|f| f.as_str()).collect();
+    let firing_counts = count_values(firing, &fields);
+    let baseline_counts = count_values(baseline, &fields);
+
+    let mut contributors = firing_counts
+        .into_iter()
+        .filter_map(|(key, firing_count)| {
+            let baseline_count = baseline_counts.get(&key).copied().unwrap_or_default();
+            let firing_ratio = firing_count as f64 / firing.len() as f64;
+            let baseline_ratio = if baseline.is_empty() {
+                0.0
+            } else {
+                baseline_count as f64 / baseline.len() as f64
+            };
+            let shift = firing_ratio - baseline_ratio;
+            (shift > 0.0).then(|| CorrelationContributor {
+                field: key.0,
+                value: key.1,
+                firing_count,
+                baseline_count,
+                firing_ratio,
+                baseline_ratio,
+                shift,
+            })
+        })
+        .collect::<Vec<_>>();
+    contributors.sort_by(|a, b| {
+        b.shift
+            .partial_cmp(&a.shift)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| b.firing_count.cmp(&a.firing_count))
+            .then_with(|| a.field.cmp(&b.field))
+            .then_with(|| a.value.cmp(&b.value))
+    });
+    contributors.truncate(top);
+    contributors
+}
+
+fn count_values(records: &[Value], fields: &HashSet<&str>) -> HashMap<(String, String), usize> {
+    let mut counts = HashMap::new();
+    for record in records.iter().filter_map(|r| r.as_object()) {
+        for (field, value) in record_values(record) {
+            if field == TIMESTAMP_COL_NAME || (!fields.is_empty() && !fields.contains(field)) {
+                continue;
+            }
+            *counts.entry((field.to_string(), value)).or_default() += 1;
+        }
+    }
+    counts
+}
+
+fn record_values(record: &Map<String, Value>) -> impl Iterator<Item = (&str, String)> {
+    record.iter().filter_map(|(field, value)| {
+        let value = match value {
+            Value::String(v) if !v.is_empty() && v.len() <= MAX_VALUE_LEN => v.clone(),
+            Value::Bool(v) => v.to_string(),
+            _ => return None,
+        };
+        Some((field.as_str(), value))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use config::utils::json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_top_contributors() {
+        let baseline = vec![
+            json!({"_timestamp": 1, "host": "a", "code": "200"}),
+            json!({"_timestamp": 2, "host": "b", "code": "200"}),
+            json!({"_timestamp": 3, "host": "a", "code": "200"}),
+            json!({"_timestamp": 4, "host": "b", "code": "500"}),
+        ];
+        let firing = vec![
+            json!({"_timestamp": 5, "host": "c", "code": "500"}),
+            json!({"_timestamp": 6, "host": "c", "code": "500"}),
+            json!({"_timestamp": 7, "host": "a", "code": "500"}),
+            json!({"_timestamp": 8, "host": "c", "code": "200"}),
+        ];
+        let top = top_contributors(&firing, &baseline, &[], 10);
+        assert_eq!(top.len(), 2);
+        assert_eq!(
+            (top[0].field.as_str(), top[0].value.as_str()),
+            ("host", "c")
+        );
+        assert_eq!(top[0].shift, 0.75);
+        assert_eq!(
+            (top[1].field.as_str(), top[1].value.as_str()),
+            ("code", "500")
+        );
+        assert_eq!(top[1].shift, 0.5);
+
+        let top = top_contributors(&firing, &baseline, &["code".to_string()], 10);
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].value, "500");
+
+        assert!(top_contributors(&[], &baseline, &[], 10).is_empty());
+    }
+}
@@ -30,6 +30,33 @@ pub async fn save(
     create: bool,
 ) -> Result<Destination, DestinationError> {
     // First validate the `destination` according to its `destination_type`
+    if let Module::Alert {
+        grouping: Some(grouping),
+        ..
+    } = &destination.module
+    {
+        grouping
+            .validate()
+            .map_err(DestinationError::InvalidGrouping)?;
+    }
+    if let Module::Alert {
+        resolved_template, ..
+    } = &mut destination.module
+    {
+        *resolved_template = resolved_template
+            .take()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty());
+        if let Some(template) = resolved_template.as_deref()
+            && db::alerts::templates::get(&destination.org_id, template)
+                .await
+                .is_err()
+        {
+            return Err(DestinationError::ResolvedTemplateNotFound(
+                template.to_string(),
+            ));
+        }
+    }
     match &mut destination.module {
         Module::Alert {
             destination_type, ..
@@ -63,6 +90,25 @@ pub async fn save(
                     return Err(DestinationError::InvalidSns);
                 }
             }
+            DestinationType::PagerDuty(pagerduty) => {
+                pagerduty.routing_key = pagerduty.routing_key.trim().to_string();
+                if pagerduty.routing_key.is_empty() {
+                    return Err(DestinationError::InvalidPagerDuty);
+                }
+            }
+            DestinationType::Opsgenie(opsgenie) => {
+                opsgenie.api_key = opsgenie.api_key.trim().to_string();
+                if opsgenie.api_key.is_empty() {
+                    return Err(DestinationError::InvalidOpsgenie);
+                }
+            }
+            DestinationType::VictorOps(victorops) => {
+                victorops.api_key = victorops.api_key.trim().to_string();
+                victorops.routing_key = victorops.routing_key.trim().to_string();
+                if victorops.api_key.is_empty() || victorops.routing_key.is_empty() {
+                    return Err(DestinationError::InvalidVictorOps);
+                }
+            }
         },
         Module::Pipeline { endpoint, .. } => {
             if endpoint.url.is_empty() {
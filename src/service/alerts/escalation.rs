@@ -0,0 +1,354 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Escalation policies notify their steps one after the other while a firing alert is not
+//! acknowledged. The escalation of an alert starts when it starts firing and is kept as a
+//! scheduled trigger of the [`TriggerModule::Escalation`] module, run by the scheduler when the
+//! next step is due. It ends when the alert recovers, which also resolves the incidents opened by
+//! the steps. Acknowledging the alert stops notifying the later steps.
+
+use std::str::FromStr;
+
+use config::{
+    ider,
+    meta::{
+        alerts::{
+            alert::{Alert, ListAlertsParams},
+            escalation::{EscalationPolicy, EscalationState},
+        },
+        triggers::{ScheduledTriggerData, Trigger, TriggerModule, TriggerStatus},
+    },
+    utils::{
+        json::{self, Map, Value},
+        time::{now_micros, second_micros},
+    },
+};
+use infra::{
+    db::{ORM_CLIENT, connect_to_orm},
+    table,
+};
+use svix_ksuid::Ksuid;
+
+use crate::service::{
+    alerts::{alert::AlertExt, destinations, incidents},
+    db,
+};
+
+/// Number of rows of the notification which started the escalation sent again by the later steps
+const ESCALATION_MAX_ROWS: usize = 100;
+
+/// (seconds) How often an escalation without pending steps checks that its alert still fires, in
+/// case its recovery was missed
+const ESCALATION_RECHECK_INTERVAL: i64 = 3600;
+
+#[derive(Debug, thiserror::Error)]
+pub enum EscalationError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Escalation policy not found")]
+    NotFound,
+
+    #[error("Escalation policy with the same name already exists")]
+    AlreadyExists,
+
+    #[error("Invalid escalation policy: {0}")]
+    Invalid(String),
+
+    #[error("Escalation policy is in use by alert {0}")]
+    InUse(String),
+
+    #[error("Alert has no escalation in progress")]
+    NotEscalating,
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<EscalationPolicy>, EscalationError> {
+    Ok(table::escalation_policies::list(org_id).await?)
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<EscalationPolicy, EscalationError> {
+    table::escalation_policies::get(org_id, id)
+        .await?
+        .ok_or(EscalationError::NotFound)
+}
+
+pub async fn create(
+    org_id: &str,
+    mut policy: EscalationPolicy,
+) -> Result<EscalationPolicy, EscalationError> {
+    let now = now_micros();
+    policy.id = ider::uuid();
+    policy.created_at = now;
+    policy.updated_at = now;
+    save(org_id, policy).await
+}
+
+pub async fn update(
+    org_id: &str,
+    id: &str,
+    policy: EscalationPolicy,
+) -> Result<EscalationPolicy, EscalationError> {
+    let existing = get(org_id, id).await?;
+    let policy = EscalationPolicy {
+        id: existing.id,
+        created_at: existing.created_at,
+        updated_at: now_micros(),
+        ..policy
+    };
+    save(org_id, policy).await
+}
+
+async fn save(
+    org_id: &str,
+    mut policy: EscalationPolicy,
+) -> Result<EscalationPolicy, EscalationError> {
+    policy.name = policy.name.trim().to_string();
+    policy.validate().map_err(EscalationError::Invalid)?;
+    if let Some(existing) = table::escalation_policies::get_by_name(org_id, &policy.name).await?
+        && existing.id != policy.id
+    {
+        return Err(EscalationError::AlreadyExists);
+    }
+    for step in policy.steps.iter() {
+        for name in step.destinations.iter() {
+            if destinations::get(org_id, name).await.is_err() {
+                return Err(EscalationError::Invalid(format!(
+                    "destination {name} not found"
+                )));
+            }
+        }
+    }
+    table::escalation_policies::put(org_id, &policy).await?;
+    Ok(policy)
+}
+
+/// Deletes the policy unless an alert uses it.
+pub async fn delete(org_id: &str, id: &str) -> Result<(), EscalationError> {
+    let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+    let alerts =
+        db::alerts::alert::list_with_folders(client, ListAlertsParams::new(org_id)).await?;
+    if let Some((_, alert)) = alerts
+        .iter()
+        .find(|(_, alert)| alert.escalation_policy.as_deref() == Some(id))
+    {
+        return Err(EscalationError::InUse(alert.name.clone()));
+    }
+    if !table::escalation_policies::delete(org_id, id).await? {
+        return Err(EscalationError::NotFound);
+    }
+    Ok(())
+}
+
+/// Starts the escalation of the alert which started firing, notifying the steps due right away.
+/// Does nothing if the alert has no escalation policy or its escalation is already in progress.
+pub async fn start(
+    alert: &Alert,
+    rows: &[Map<String, Value>],
+    rows_end_time: i64,
+    start_time: Option<i64>,
+    evaluation_timestamp: i64,
+) -> Result<(), anyhow::Error> {
+    let Some(policy_id) = alert.escalation_policy.as_ref() else {
+        return Ok(());
+    };
+    let alert_id = alert.get_unique_key();
+    if db::scheduler::exists(&alert.org_id, TriggerModule::Escalation, &alert_id).await {
+        return Ok(());
+    }
+    let Some(policy) = table::escalation_policies::get(&alert.org_id, policy_id).await? else {
+        return Err(anyhow::anyhow!("escalation policy {policy_id} not found"));
+    };
+
+    let now = now_micros();
+    let mut state = EscalationState {
+        policy_id: policy_id.to_string(),
+        started_at: now,
+        rows: rows.iter().take(ESCALATION_MAX_ROWS).cloned().collect(),
+        rows_end_time,
+        start_time,
+        evaluation_timestamp,
+        ..Default::default()
+    };
+    notify_due_steps(alert, &policy, &mut state, now).await;
+    let trigger = Trigger {
+        org: alert.org_id.clone(),
+        module: TriggerModule::Escalation,
+        module_key: alert_id,
+        next_run_at: next_run_at(Some(&policy), &state, now),
+        data: json::to_string(&state)?,
+        ..Default::default()
+    };
+    db::scheduler::push(trigger).await?;
+    Ok(())
+}
+
+/// Ends the escalation of the alert which recovered, resolving the incidents opened by its steps.
+pub async fn stop(alert: &Alert) -> Result<(), anyhow::Error> {
+    let alert_id = alert.get_unique_key();
+    let Ok(trigger) = db::scheduler::get(&alert.org_id, TriggerModule::Escalation, &alert_id).await
+    else {
+        return Ok(());
+    };
+    let state: EscalationState = json::from_str(&trigger.data)?;
+    db::scheduler::delete(&alert.org_id, TriggerModule::Escalation, &alert_id).await?;
+    if !state.incidents.is_empty() {
+        incidents::resolve(alert, &state.incidents).await?;
+    }
+    Ok(())
+}
+
+/// Stops notifying the later steps of the escalation of the alert. The incidents already opened
+/// are still resolved when the alert recovers.
+pub async fn acknowledge(
+    org_id: &str,
+    alert_id: Ksuid,
+    user_id: &str,
+) -> Result<(), EscalationError> {
+    let alert_id = alert_id.to_string();
+    let Ok(trigger) = db::scheduler::get(org_id, TriggerModule::Escalation, &alert_id).await else {
+        return Err(EscalationError::NotEscalating);
+    };
+    let mut state: EscalationState =
+        json::from_str(&trigger.data).map_err(infra::errors::Error::from)?;
+    if state.is_acknowledged() {
+        return Ok(());
+    }
+    let now = now_micros();
+    state.acknowledged_by = Some(user_id.to_string());
+    state.acknowledged_at = Some(now);
+    log::info!("[ALERT] escalation of alert {org_id}/{alert_id} acknowledged by {user_id}");
+    db::scheduler::update_trigger(Trigger {
+        next_run_at: next_run_at(None, &state, now),
+        status: TriggerStatus::Waiting,
+        retries: 0,
+        data: json::to_string(&state).map_err(infra::errors::Error::from)?,
+        ..trigger
+    })
+    .await?;
+    Ok(())
+}
+
+/// Runs the escalation trigger: notifies the steps which are due, or ends the escalation if its
+/// alert no longer fires.
+pub async fn run(trigger: Trigger) -> Result<(), anyhow::Error> {
+    let mut state: EscalationState = json::from_str(&trigger.data)?;
+    let alert = match Ksuid::from_str(&trigger.module_key) {
+        Ok(alert_id) => {
+            let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+            db::alerts::alert::get_by_id(client, &trigger.org, alert_id)
+                .await?
+                .map(|(_, alert)| alert)
+        }
+        Err(_) => None,
+    };
+    let Some(alert) = alert.filter(|alert| alert.enabled) else {
+        log::info!(
+            "[ALERT] ending escalation of alert {}/{} which no longer exists or is disabled",
+            trigger.org,
+            trigger.module_key
+        );
+        db::scheduler::delete(&trigger.org, TriggerModule::Escalation, &trigger.module_key).await?;
+        return Ok(());
+    };
+    if !is_firing(&alert).await {
+        return stop(&alert).await;
+    }
+
+    let now = now_micros();
+    let policy = table::escalation_policies::get(&trigger.org, &state.policy_id).await?;
+    if let Some(policy) = policy.as_ref()
+        && !state.is_acknowledged()
+    {
+        notify_due_steps(&alert, policy, &mut state, now).await;
+    }
+    db::scheduler::update_trigger(Trigger {
+        next_run_at: next_run_at(policy.as_ref(), &state, now),
+        status: TriggerStatus::Waiting,
+        retries: 0,
+        data: json::to_string(&state)?,
+        ..trigger
+    })
+    .await?;
+    Ok(())
+}
+
+/// Checks the firing state kept in the scheduled trigger of the alert.
+async fn is_firing(alert: &Alert) -> bool {
+    db::scheduler::get(&alert.org_id, TriggerModule::Alert, &alert.get_unique_key())
+        .await
+        .ok()
+        .and_then(|trigger| json::from_str::<ScheduledTriggerData>(&trigger.data).ok())
+        .is_some_and(|data| data.firing.is_some())
+}
+
+/// Notifies the steps due at `now` with the rows which started the escalation.
+async fn notify_due_steps(
+    alert: &Alert,
+    policy: &EscalationPolicy,
+    state: &mut EscalationState,
+    now: i64,
+) {
+    while let Some(due_at) = policy.due_at(state.next_step, state.started_at)
+        && due_at <= now
+    {
+        let step_alert = Alert {
+            destinations: policy.steps[state.next_step].destinations.clone(),
+            ..alert.clone()
+        };
+        state.next_step += 1;
+        match step_alert
+            .send_notification(
+                &state.rows,
+                state.rows_end_time,
+                state.start_time,
+                state.evaluation_timestamp,
+            )
+            .await
+        {
+            Ok((_, err_msg)) if !err_msg.trim().is_empty() => log::error!(
+                "[ALERT] some notifications of escalation step {} of alert {}/{} could not be sent: {err_msg}",
+                state.next_step - 1,
+                alert.org_id,
+                alert.name
+            ),
+            Ok(_) => log::info!(
+                "[ALERT] escalation step {} of alert {}/{} notified",
+                state.next_step - 1,
+                alert.org_id,
+                alert.name
+            ),
+            Err(e) => log::error!(
+                "[ALERT] error notifying escalation step {} of alert {}/{}: {e}",
+                state.next_step - 1,
+                alert.org_id,
+                alert.name
+            ),
+        }
+        for incident in incidents::incidents(&step_alert, &state.rows).await {
+            if !state.incidents.contains(&incident) {
+                state.incidents.push(incident);
+            }
+        }
+    }
+}
+
+/// Returns when the escalation trigger runs next: when the next step is due, or to recheck that
+/// the alert still fires once there is no step left to notify.
+fn next_run_at(policy: Option<&EscalationPolicy>, state: &EscalationState, now: i64) -> i64 {
+    policy
+        .filter(|_| !state.is_acknowledged())
+        .and_then(|policy| policy.due_at(state.next_step, state.started_at))
+        .unwrap_or(now + second_micros(ESCALATION_RECHECK_INTERVAL))
+}
@@ -0,0 +1,272 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Grouping of alert notifications.
+//!
+//! Destinations with a [`NotificationGrouping`] don't send the notification of an alert right
+//! away, the alert joins the group given by its `group_by` labels and the group is sent as one
+//! message `group_wait` seconds after its first alert arrived. An alert sent for a group is not
+//! notified again before `repeat_interval` seconds passed.
+
+use config::{
+    meta::{
+        alerts::alert::Alert,
+        destinations::{DestinationType, Module, NotificationGrouping},
+    },
+    utils::{
+        json::{self, Map, Value},
+        time::now_micros,
+    },
+};
+use hashbrown::HashMap;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+use crate::service::alerts::{
+    alert::{render_notification, send_message},
+    destinations,
+};
+
+struct PendingNotification {
+    alert: Alert,
+    rows: Vec<Map<String, Value>>,
+    rows_end_time: i64,
+    start_time: Option<i64>,
+    evaluation_timestamp: i64,
+}
+
+#[derive(Default)]
+struct NotificationGroup {
+    pending: Vec<PendingNotification>,
+    /// Last time in microseconds each alert of the group was sent
+    last_sent: HashMap<String, i64>,
+    flush_scheduled: bool,
+}
+
+struct FlushTarget {
+    org_id: String,
+    destination: String,
+    repeat_interval: i64,
+}
+
+static GROUPS: Lazy<Mutex<HashMap<String, NotificationGroup>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Adds the notification of the alert to its group on the destination, returns the message
+/// recorded in the trigger history.
+pub fn enqueue(
+    alert: &Alert,
+    destination: &str,
+    grouping: &NotificationGrouping,
+    rows: &[Map<String, Value>],
+    rows_end_time: i64,
+    start_time: Option<i64>,
+    evaluation_timestamp: i64,
+) -> String {
+    let group_key = grouping.group_key(|label| alert.label_value(rows, label));
+    let key = format!("{}/{destination}/{group_key}", alert.org_id);
+    let alert_key = alert.get_unique_key();
+    let now = now_micros();
+
+    let mut groups = GROUPS.lock();
+    let group = groups.entry(key.clone()).or_default();
+    if grouping.repeat_interval > 0
+        && let Some(last_sent) = group.last_sent.get(&alert_key)
+        && now - last_sent < grouping.repeat_interval * 1_000_000
+    {
+        return format!(
+            "destination {destination} suppressed by repeat_interval of group [{group_key}]"
+        );
+    }
+
+    let notification = PendingNotification {
+        alert: alert.clone(),
+        rows: rows.to_vec(),
+        rows_end_time,
+        start_time,
+        evaluation_timestamp,
+    };
+    // the latest evaluation of an alert replaces the one waiting in the group
+    match group
+        .pending
+        .iter_mut()
+        .find(|v| v.alert.get_unique_key() == alert_key)
+    {
+        Some(pending) => *pending = notification,
+        None => group.pending.push(notification),
+    }
+
+    if !group.flush_scheduled {
+        group.flush_scheduled = true;
+        let group_wait = grouping.group_wait.max(0) as u64;
+        let target = FlushTarget {
+            org_id: alert.org_id.clone(),
+            destination: destination.to_string(),
+            repeat_interval: grouping.repeat_interval,
+        };
+        let key = key.clone();
+        tokio::task::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_secs(group_wait)).await;
+            flush(&key, target).await;
+        });
+    }
+
+    format!(
+        "destination {destination} grouped in [{group_key}] with {} alerts",
+        group.pending.len()
+    )
+}
+
+/// Sends the alerts waiting in the group as one notification.
+async fn flush(key: &str, target: FlushTarget) {
+    let now = now_micros();
+    let pending = {
+        let mut groups = GROUPS.lock();
+        let Some(group) = groups.get_mut(key) else {
+            return;
+        };
+        group.flush_scheduled = false;
+        let pending = std::mem::take(&mut group.pending);
+        for notification in pending.iter() {
+            group
+                .last_sent
+                .insert(notification.alert.get_unique_key(), now);
+        }
+        group
+            .last_sent
+            .retain(|_, sent| now - *sent < target.repeat_interval * 1_000_000);
+        if group.last_sent.is_empty() {
+            groups.remove(key);
+        }
+        pending
+    };
+    if pending.is_empty() {
+        return;
+    }
+
+    let (dest, template) =
+        match destinations::get_with_template(&target.org_id, &target.destination).await {
+            Ok(v) => v,
+            Err(e) => {
+                log::error!(
+                    "[ALERT GROUPING] error getting destination {}/{}: {e}",
+                    target.org_id,
+                    target.destination
+                );
+                return;
+            }
+        };
+    let Module::Alert {
+        destination_type, ..
+    } = dest.module
+    else {
+        return;
+    };
+
+    let mut subjects = Vec::with_capacity(pending.len());
+    let mut messages = Vec::with_capacity(pending.len());
+    for notification in pending.iter() {
+        let (subject, msg) = render_notification(
+            &notification.alert,
+            &destination_type,
+            &template,
+            &notification.rows,
+            notification.rows_end_time,
+            notification.start_time,
+            notification.evaluation_timestamp,
+        )
+        .await;
+        subjects.push(subject);
+        messages.push(msg);
+    }
+
+    let alert_name = if pending.len() == 1 {
+        pending[0].alert.name.clone()
+    } else {
+        format!("{} alerts", pending.len())
+    };
+    let subject = if subjects.len() == 1 {
+        subjects.remove(0)
+    } else {
+        format!("[{} alerts] {}", subjects.len(), subjects[0])
+    };
+    let msg = combine_messages(&destination_type, messages);
+    if let Err(e) = send_message(&alert_name, &destination_type, &subject, msg).await {
+        log::error!(
+            "[ALERT GROUPING] error sending grouped notification to destination {}/{}: {e}",
+            target.org_id,
+            target.destination
+        );
+    }
+}
+
+/// Combines the messages of the alerts of a group into the body of one notification, http
+/// destinations get a json array when every message is json.
+fn combine_messages(dest_type: &DestinationType, mut messages: Vec<String>) -> String {
+    if messages.len() == 1 {
+        return messages.remove(0);
+    }
+    match dest_type {
+        DestinationType::Http(_) => {
+            let values = messages
+                .iter()
+                .map(|msg| json::from_str::<Value>(msg))
+                .collect::<Result<Vec<_>, _>>();
+            match values {
+                Ok(values) => Value::Array(values).to_string(),
+                Err(_) => messages.join("\n"),
+            }
+        }
+        DestinationType::Email(_) => messages.join("<hr/>"),
+        DestinationType::Sns(_)
+        | DestinationType::PagerDuty(_)
+        | DestinationType::Opsgenie(_)
+        | DestinationType::VictorOps(_) => messages.join("\n\n"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use config::meta::destinations::{Email, Endpoint};
+
+    use super::*;
+
+    #[test]
+    fn test_combine_messages() {
+        let http = DestinationType::Http(Endpoint {
+            url: "http://localhost".to_string(),
+            method: Default::default(),
+            skip_tls_verify: false,
+            headers: None,
+            action_id: None,
+            output_format: None,
+        });
+        let messages = vec![
+            r#"{"alert":"a"}"#.to_string(),
+            r#"{"alert":"b"}"#.to_string(),
+        ];
+        assert_eq!(
+            combine_messages(&http, messages),
+            r#"[{"alert":"a"},{"alert":"b"}]"#
+        );
+        let messages = vec!["a".to_string(), r#"{"alert":"b"}"#.to_string()];
+        assert_eq!(combine_messages(&http, messages), "a\n{\"alert\":\"b\"}");
+        assert_eq!(combine_messages(&http, vec!["a".to_string()]), "a");
+
+        let email = DestinationType::Email(Email { recipients: vec![] });
+        let messages = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(combine_messages(&email, messages), "a<hr/>b");
+    }
+}
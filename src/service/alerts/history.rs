@@ -0,0 +1,293 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Keeps the state transitions of the scheduled alerts in the `_alert_history` stream of their
+//! org, so they can be searched like any other logs and shown as a timeline.
+
+use config::{
+    TIMESTAMP_COL_NAME, get_config, ider,
+    meta::{
+        alerts::{
+            alert::Alert,
+            history::{
+                ALERT_HISTORY_STREAM, AlertActivity, AlertHistory, AlertHistoryEntry, AlertState,
+            },
+        },
+        cluster::RoleGroup,
+        search::{self, SearchEventType},
+        stream::StreamType,
+    },
+    utils::{
+        json::{self, Map, Value},
+        time::{day_micros, now_micros},
+    },
+};
+use svix_ksuid::Ksuid;
+
+use super::{
+    AGG_VALUE_COL,
+    alert::{self, AlertError},
+};
+use crate::service::{ingestion::ingest_internal_records, search as SearchService};
+
+/// Time range searched when the request doesn't give one.
+const DEFAULT_HISTORY_DAYS: i64 = 7;
+
+/// Builds the entry of a transition of the alert, `rows` being the rows which satisfied the
+/// condition.
+pub fn transition(
+    alert: &Alert,
+    timestamp: i64,
+    state: AlertState,
+    previous_state: Option<AlertState>,
+    rows: &[Map<String, Value>],
+) -> AlertHistoryEntry {
+    AlertHistoryEntry {
+        timestamp,
+        alert_id: alert.id.map(|id| id.to_string()).unwrap_or_default(),
+        alert_name: alert.name.clone(),
+        stream_type: alert.stream_type.to_string(),
+        stream_name: alert.stream_name.clone(),
+        state,
+        previous_state,
+        evaluated_value: evaluated_value(rows),
+        matched_rows: rows.len() as i64,
+        since: None,
+        error: None,
+    }
+}
+
+/// Writes the entry into the alert history stream of the org. Failures are only logged, the
+/// history never holds up the evaluation of the alert.
+pub async fn record(org_id: &str, entry: AlertHistoryEntry) {
+    let alert_id = entry.alert_id.clone();
+    let record = match json::to_value(entry) {
+        Ok(record) => record,
+        Err(e) => {
+            log::error!(
+                "[ALERT HISTORY] error serializing entry of alert {org_id}/{alert_id}: {e}"
+            );
+            return;
+        }
+    };
+    if let Err(e) = ingest_internal_records(org_id, ALERT_HISTORY_STREAM, vec![record]).await {
+        log::error!("[ALERT HISTORY] error recording transition of alert {org_id}/{alert_id}: {e}");
+    }
+}
+
+/// Lists the transitions of the alert between `start_time` and `end_time`, in microseconds,
+/// most recent first. Defaults to the last week and to the default query limit.
+pub async fn list(
+    org_id: &str,
+    alert_id: Ksuid,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    size: Option<i64>,
+) -> Result<AlertHistory, AlertError> {
+    let end_time = end_time.unwrap_or_else(now_micros);
+    let start_time = start_time.unwrap_or(end_time - day_micros(DEFAULT_HISTORY_DAYS));
+    if end_time <= start_time {
+        return Err(AlertError::HistoryTimeRange);
+    }
+    // the alert has to exist, even if its history outlives it
+    alert::get_by_id_db(org_id, alert_id).await?;
+
+    // nothing was recorded in the org yet
+    let schema = infra::schema::get(org_id, ALERT_HISTORY_STREAM, StreamType::Logs).await?;
+    if schema.fields().is_empty() {
+        return Ok(AlertHistory::default());
+    }
+
+    let req = search::Request {
+        query: search::Query {
+            sql: format!(
+                "SELECT * FROM \"{ALERT_HISTORY_STREAM}\" WHERE alert_id = '{alert_id}' ORDER BY {TIMESTAMP_COL_NAME} DESC"
+            ),
+            from: 0,
+            size: size.unwrap_or(get_config().limit.query_default_limit),
+            start_time,
+            end_time,
+            ..Default::default()
+        },
+        search_type: Some(SearchEventType::Other),
+        ..Default::default()
+    };
+    let trace_id = ider::generate_trace_id();
+    let resp = SearchService::grpc_search::grpc_search(
+        &trace_id,
+        org_id,
+        StreamType::Logs,
+        None,
+        &req,
+        Some(RoleGroup::Interactive),
+    )
+    .await?;
+
+    let list = resp
+        .hits
+        .into_iter()
+        .filter_map(|hit| json::from_value(hit).ok())
+        .collect();
+    Ok(AlertHistory { list })
+}
+
+/// Counts the transitions of the alerts of the org between `start_time` and `end_time`, in
+/// microseconds, the alerts which fired the most first.
+pub async fn activity(
+    org_id: &str,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<AlertActivity>, AlertError> {
+    // nothing was recorded in the org yet
+    let schema = infra::schema::get(org_id, ALERT_HISTORY_STREAM, StreamType::Logs).await?;
+    if schema.fields().is_empty() {
+        return Ok(vec![]);
+    }
+
+    let req = search::Request {
+        query: search::Query {
+            sql: format!(
+                "SELECT alert_name, stream_type, stream_name, state, COUNT(*) AS transitions FROM \"{ALERT_HISTORY_STREAM}\" GROUP BY alert_name, stream_type, stream_name, state"
+            ),
+            from: 0,
+            size: get_config().limit.query_default_limit,
+            start_time,
+            end_time,
+            ..Default::default()
+        },
+        search_type: Some(SearchEventType::Other),
+        ..Default::default()
+    };
+    let trace_id = ider::generate_trace_id();
+    let resp = SearchService::grpc_search::grpc_search(
+        &trace_id,
+        org_id,
+        StreamType::Logs,
+        None,
+        &req,
+        Some(RoleGroup::Background),
+    )
+    .await?;
+    Ok(count_activity(&resp.hits))
+}
+
+/// Sums the transitions counted per alert and state into the activity of each alert.
+fn count_activity(hits: &[Value]) -> Vec<AlertActivity> {
+    let mut activity: Vec<AlertActivity> = Vec::new();
+    for hit in hits {
+        let field = |name: &str| {
+            hit.get(name)
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        let (alert_name, stream_type, stream_name) = (
+            field("alert_name"),
+            field("stream_type"),
+            field("stream_name"),
+        );
+        let Ok(state) = json::from_value::<AlertState>(Value::from(field("state"))) else {
+            continue;
+        };
+        let transitions = hit.get("transitions").and_then(|v| v.as_i64()).unwrap_or(0);
+        let idx = match activity.iter().position(|a| {
+            a.alert_name == alert_name
+                && a.stream_type == stream_type
+                && a.stream_name == stream_name
+        }) {
+            Some(idx) => idx,
+            None => {
+                activity.push(AlertActivity {
+                    alert_name,
+                    stream_type,
+                    stream_name,
+                    ..Default::default()
+                });
+                activity.len() - 1
+            }
+        };
+        let entry = &mut activity[idx];
+        match state {
+            AlertState::Firing => entry.fired += transitions,
+            AlertState::Resolved => entry.resolved += transitions,
+            AlertState::Pending => entry.pending += transitions,
+        }
+    }
+    activity.sort_by(|a, b| {
+        b.fired
+            .cmp(&a.fired)
+            .then_with(|| b.pending.cmp(&a.pending))
+            .then_with(|| a.alert_name.cmp(&b.alert_name))
+    });
+    activity
+}
+
+/// The highest aggregated value of the rows, or the number of rows when the alert doesn't
+/// aggregate.
+pub(super) fn evaluated_value(rows: &[Map<String, Value>]) -> Option<f64> {
+    if rows.is_empty() {
+        return None;
+    }
+    rows.iter()
+        .filter_map(|row| row.get(AGG_VALUE_COL))
+        .map(json::get_float_value)
+        .reduce(f64::max)
+        .or(Some(rows.len() as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluated_value() {
+        assert_eq!(evaluated_value(&[]), None);
+
+        let rows = vec![Map::new(), Map::new(), Map::new()];
+        assert_eq!(evaluated_value(&rows), Some(3.0));
+
+        let rows = [
+            json::json!({"host": "a", AGG_VALUE_COL: 12}),
+            json::json!({"host": "b", AGG_VALUE_COL: 40.5}),
+        ]
+        .into_iter()
+        .map(|v| v.as_object().unwrap().clone())
+        .collect::<Vec<_>>();
+        assert_eq!(evaluated_value(&rows), Some(40.5));
+    }
+
+    #[test]
+    fn test_count_activity() {
+        let hits = vec![
+            json::json!({"alert_name": "cpu", "stream_type": "metrics", "stream_name": "node", "state": "firing", "transitions": 2}),
+            json::json!({"alert_name": "cpu", "stream_type": "metrics", "stream_name": "node", "state": "resolved", "transitions": 2}),
+            json::json!({"alert_name": "errors", "stream_type": "logs", "stream_name": "default", "state": "firing", "transitions": 5}),
+            json::json!({"alert_name": "errors", "stream_type": "logs", "stream_name": "default", "state": "pending", "transitions": 1}),
+            json::json!({"alert_name": "errors", "stream_type": "logs", "stream_name": "default", "state": "unknown", "transitions": 9}),
+        ];
+        let activity = count_activity(&hits);
+        assert_eq!(activity.len(), 2);
+        assert_eq!(activity[0].alert_name, "errors");
+        assert_eq!(
+            (activity[0].fired, activity[0].resolved, activity[0].pending),
+            (5, 0, 1)
+        );
+        assert_eq!(activity[1].alert_name, "cpu");
+        assert_eq!(
+            (activity[1].fired, activity[1].resolved, activity[1].pending),
+            (2, 2, 0)
+        );
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Destinations managing incidents: PagerDuty, Opsgenie and Splunk On-Call (VictorOps). Every
+//! firing alert opens an incident deduplicated by the alert id and the group of the destination
+//! grouping, the incidents are closed when the alert recovers.
+
+use config::{
+    meta::{
+        alerts::alert::Alert,
+        destinations::{DestinationType, Module},
+        triggers::Incident,
+    },
+    utils::{
+        hash::{Sum64, fnv},
+        json::{Map, Value},
+    },
+};
+
+use crate::service::alerts::{destinations, opsgenie, pagerduty, victorops};
+
+/// Deduplication key of the incident of the alert for a group of the destination grouping.
+pub fn dedup_key(alert: &Alert, group_key: &str) -> String {
+    let alert_id = alert.get_unique_key();
+    if group_key.is_empty() {
+        alert_id
+    } else {
+        // hashed as the keys are limited in length and used in urls
+        format!("{alert_id}-{:016x}", fnv::new().sum64(group_key))
+    }
+}
+
+/// Severity of the alert, from its `severity` context attribute or the `severity` column of the
+/// rows.
+pub(super) fn alert_severity(alert: &Alert, rows: &[Map<String, Value>]) -> Option<String> {
+    alert
+        .context_attributes
+        .as_ref()
+        .and_then(|attrs| attrs.get("severity").cloned())
+        .or_else(|| alert.label_value(rows, "severity"))
+}
+
+/// Summary of the incident, the message rendered from the template unless it is json.
+pub(super) fn summary(alert: &Alert, msg: &str) -> String {
+    match config::utils::json::from_str::<Value>(msg) {
+        Ok(Value::Object(_) | Value::Array(_)) => format!(
+            "Alert {} fired on {} {}",
+            alert.name, alert.stream_type, alert.stream_name
+        ),
+        _ => msg.trim().to_string(),
+    }
+}
+
+/// Opens the incident of the alert on the destination, `msg` is the notification rendered from
+/// the template of the destination.
+pub(super) async fn trigger(
+    alert: &Alert,
+    destination_type: &DestinationType,
+    group_key: &str,
+    rows: &[Map<String, Value>],
+    msg: &str,
+) -> Result<String, anyhow::Error> {
+    let key = dedup_key(alert, group_key);
+    match destination_type {
+        DestinationType::PagerDuty(pagerduty) => {
+            pagerduty::trigger(alert, pagerduty, &key, group_key, rows, msg).await
+        }
+        DestinationType::Opsgenie(opsgenie) => {
+            opsgenie::trigger(alert, opsgenie, &key, group_key, rows, msg).await
+        }
+        DestinationType::VictorOps(victorops) => {
+            victorops::trigger(alert, victorops, &key, group_key, rows, msg).await
+        }
+        _ => Err(anyhow::anyhow!("destination type doesn't manage incidents")),
+    }
+}
+
+/// Returns the incidents the notification of the alert for the rows opened on its destinations
+/// which close them on recovery.
+pub async fn incidents(alert: &Alert, rows: &[Map<String, Value>]) -> Vec<Incident> {
+    let mut incidents = vec![];
+    for name in alert.destinations.iter() {
+        let Ok(dest) = destinations::get(&alert.org_id, name).await else {
+            continue;
+        };
+        let Module::Alert {
+            destination_type,
+            grouping,
+            ..
+        } = dest.module
+        else {
+            continue;
+        };
+        let auto_close = match destination_type {
+            DestinationType::PagerDuty(_) => true,
+            DestinationType::Opsgenie(opsgenie) => opsgenie.auto_close,
+            DestinationType::VictorOps(victorops) => victorops.auto_close,
+            _ => false,
+        };
+        if !auto_close {
+            continue;
+        }
+        let group_key = grouping
+            .map(|g| g.group_key(|label| alert.label_value(rows, label)))
+            .unwrap_or_default();
+        incidents.push(Incident {
+            destination: dest.name,
+            key: dedup_key(alert, &group_key),
+        });
+    }
+    incidents
+}
+
+/// Closes the incidents opened by the alert, called when the alert recovers.
+pub async fn resolve(alert: &Alert, incidents: &[Incident]) -> Result<(), anyhow::Error> {
+    let mut errors = vec![];
+    for incident in incidents {
+        let destination_type = match destinations::get(&alert.org_id, &incident.destination).await {
+            Ok(dest) => match dest.module {
+                Module::Alert {
+                    destination_type, ..
+                } => destination_type,
+                _ => continue,
+            },
+            Err(e) => {
+                errors.push(format!("destination {}: {e}", incident.destination));
+                continue;
+            }
+        };
+        let ret = match &destination_type {
+            DestinationType::PagerDuty(pagerduty) => {
+                pagerduty::resolve(pagerduty, &incident.key).await
+            }
+            DestinationType::Opsgenie(opsgenie) => opsgenie::close(opsgenie, &incident.key).await,
+            DestinationType::VictorOps(victorops) => {
+                victorops::recover(alert, victorops, &incident.key).await
+            }
+            // the destination no longer manages incidents
+            _ => continue,
+        };
+        if let Err(e) = ret {
+            errors.push(format!("destination {}: {e}", incident.destination));
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "error resolving incidents: {}",
+            errors.join("; ")
+        ))
+    }
+}
+
+/// Posts the event to the incident api, returning the response as the notification result.
+pub(super) async fn send_event(
+    url: &str,
+    auth_header: Option<String>,
+    event: &Value,
+) -> Result<String, anyhow::Error> {
+    let mut req = reqwest::Client::new()
+        .post(url)
+        .header("Content-type", "application/json");
+    if let Some(auth_header) = auth_header {
+        req = req.header("Authorization", auth_header);
+    }
+    let resp = req.body(event.to_string()).send().await?;
+    let resp_status = resp.status();
+    let resp_body = resp.text().await?;
+    if !resp_status.is_success() {
+        return Err(anyhow::anyhow!(
+            "sent error status: {}, err: {}",
+            resp_status,
+            resp_body
+        ));
+    }
+    Ok(format!("sent status: {}, body: {}", resp_status, resp_body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dedup_key() {
+        let alert = Alert {
+            name: "db_errors".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(dedup_key(&alert, ""), alert.get_unique_key());
+        assert_ne!(
+            dedup_key(&alert, "host=db-1"),
+            dedup_key(&alert, "host=db-2")
+        );
+        assert!(
+            dedup_key(&alert, "host=db-1")
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        );
+    }
+}
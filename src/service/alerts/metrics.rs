@@ -0,0 +1,90 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Statistics of the evaluations of the scheduled alerts. They are exported as internal metrics
+//! and kept in the data of the alert triggers, so that any node can return them.
+
+use config::{
+    meta::{
+        alerts::AlertMetrics,
+        triggers::{EvaluationStats, ScheduledTriggerData, TriggerModule},
+    },
+    metrics,
+};
+use svix_ksuid::Ksuid;
+
+use super::alert::{self, AlertError};
+use crate::service::db::scheduler;
+
+/// Counts an evaluation of the alert which ran at `timestamp`, failed when `error` is set.
+pub fn record_evaluation(
+    data: &mut ScheduledTriggerData,
+    org_id: &str,
+    alert_id: &str,
+    timestamp: i64,
+    took_in_secs: f64,
+    scan_records: Option<i64>,
+    error: Option<String>,
+) {
+    let scan_records = scan_records.unwrap_or_default().max(0) as u64;
+    metrics::ALERT_EVALUATION_TIME
+        .with_label_values(&[org_id, alert_id])
+        .observe(took_in_secs);
+    metrics::ALERT_EVALUATION_SCAN_RECORDS
+        .with_label_values(&[org_id, alert_id])
+        .inc_by(scan_records);
+    if error.is_some() {
+        metrics::ALERT_EVALUATION_ERRORS
+            .with_label_values(&[org_id, alert_id])
+            .inc();
+        metrics::ALERT_EVALUATION_LAST_ERROR
+            .with_label_values(&[org_id, alert_id])
+            .set(timestamp / 1_000_000);
+    }
+    data.stats
+        .get_or_insert_default()
+        .record(timestamp, took_in_secs, scan_records, error);
+}
+
+/// Counts the runs of the alert skipped before the evaluation at `timestamp`.
+pub fn record_skipped(
+    data: &mut ScheduledTriggerData,
+    org_id: &str,
+    alert_id: &str,
+    timestamp: i64,
+    runs: u64,
+) {
+    if runs == 0 {
+        return;
+    }
+    metrics::ALERT_SKIPPED_RUNS
+        .with_label_values(&[org_id, alert_id])
+        .inc_by(runs);
+    data.stats.get_or_insert_default().skip(timestamp, runs);
+}
+
+/// Returns the evaluation statistics of the alert, empty until the scheduler evaluates it.
+pub async fn get(org_id: &str, alert_id: Ksuid) -> Result<AlertMetrics, AlertError> {
+    let alert = alert::get_by_id_db(org_id, alert_id).await?;
+    let stats = match scheduler::get(org_id, TriggerModule::Alert, &alert_id.to_string()).await {
+        Ok(trigger) => ScheduledTriggerData::from_json_string(&trigger.data)
+            .ok()
+            .and_then(|data| data.stats)
+            .unwrap_or_default(),
+        // no trigger, the alert was never scheduled
+        Err(_) => EvaluationStats::default(),
+    };
+    Ok(AlertMetrics::new(alert.trigger_condition.frequency, stats))
+}
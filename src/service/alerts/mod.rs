@@ -42,10 +42,30 @@ use crate::service::{
 };
 
 pub mod alert;
+pub mod anomaly;
+pub mod backtest;
+pub mod bulk;
+pub mod composite;
+pub mod correlation;
 pub mod derived_streams;
 pub mod destinations;
+pub mod escalation;
+pub mod grouping;
+pub mod history;
+pub mod incidents;
+pub mod metrics;
+pub mod multi_condition;
+mod opsgenie;
+mod pagerduty;
+pub mod panel;
+pub mod rendering;
 pub mod scheduler;
+pub mod silences;
 pub mod templates;
+mod victorops;
+
+/// Column holding the aggregated value in the results of aggregation alerts.
+const AGG_VALUE_COL: &str = "alert_agg_value";
 
 #[async_trait]
 pub trait QueryConditionExt: Sync + Send + 'static {
@@ -388,6 +408,10 @@ impl QueryConditionExt for QueryCondition {
                     skip_wal: false,
                     streaming_output: false,
                     streaming_id: None,
+                    max_points: None,
+                    time_ranges: vec![],
+                    time_shifts: vec![],
+                    variables: vec![],
                 },
                 encoding: config::meta::search::RequestEncoding::Empty,
                 regions: vec![],
@@ -469,17 +493,9 @@ impl QueryConditionExt for QueryCondition {
             records.len()
         );
         eval_results.query_took = Some(resp.took as i64);
+        eval_results.scan_records = Some(resp.scan_records as i64);
         eval_results.data = if self.search_event_type.is_none() {
-            let threshold = trigger_condition.threshold as usize;
-            match trigger_condition.operator {
-                Operator::EqualTo => (records.len() == threshold).then_some(records),
-                Operator::NotEqualTo => (records.len() != threshold).then_some(records),
-                Operator::GreaterThan => (records.len() > threshold).then_some(records),
-                Operator::GreaterThanEquals => (records.len() >= threshold).then_some(records),
-                Operator::LessThan => (records.len() < threshold).then_some(records),
-                Operator::LessThanEquals => (records.len() <= threshold).then_some(records),
-                _ => None,
-            }
+            apply_threshold(trigger_condition, records)
         } else {
             Some(records)
         };
@@ -488,6 +504,23 @@ impl QueryConditionExt for QueryCondition {
     }
 }
 
+/// Returns the records when their number satisfies the threshold of the trigger condition.
+pub(crate) fn apply_threshold(
+    trigger_condition: &TriggerCondition,
+    records: Vec<Map<String, Value>>,
+) -> Option<Vec<Map<String, Value>>> {
+    let threshold = trigger_condition.threshold as usize;
+    match trigger_condition.operator {
+        Operator::EqualTo => (records.len() == threshold).then_some(records),
+        Operator::NotEqualTo => (records.len() != threshold).then_some(records),
+        Operator::GreaterThan => (records.len() > threshold).then_some(records),
+        Operator::GreaterThanEquals => (records.len() >= threshold).then_some(records),
+        Operator::LessThan => (records.len() < threshold).then_some(records),
+        Operator::LessThanEquals => (records.len() <= threshold).then_some(records),
+        _ => None,
+    }
+}
+
 #[async_trait]
 pub trait ConditionListExt: Sync + Send + 'static {
     async fn len(&self) -> u32;
@@ -720,22 +753,10 @@ async fn build_sql(
                 ));
             }
         };
-        build_expr(&agg.having, "alert_agg_value", data_type)?
+        build_expr(&agg.having, AGG_VALUE_COL, data_type)?
     };
 
-    let func_expr = match agg.function {
-        AggFunction::Avg => format!("AVG(\"{}\")", agg.having.column),
-        AggFunction::Max => format!("MAX(\"{}\")", agg.having.column),
-        AggFunction::Min => format!("MIN(\"{}\")", agg.having.column),
-        AggFunction::Sum => format!("SUM(\"{}\")", agg.having.column),
-        AggFunction::Count => format!("COUNT(\"{}\")", agg.having.column),
-        AggFunction::Median => format!("MEDIAN(\"{}\")", agg.having.column),
-        AggFunction::P50 => format!("approx_percentile_cont(\"{}\", 0.5)", agg.having.column),
-        AggFunction::P75 => format!("approx_percentile_cont(\"{}\", 0.75)", agg.having.column),
-        AggFunction::P90 => format!("approx_percentile_cont(\"{}\", 0.9)", agg.having.column),
-        AggFunction::P95 => format!("approx_percentile_cont(\"{}\", 0.95)", agg.having.column),
-        AggFunction::P99 => format!("approx_percentile_cont(\"{}\", 0.99)", agg.having.column),
-    };
+    let func_expr = agg_expr(&agg.function, &agg.having.column);
 
     if let Some(group) = agg.group_by.as_ref() {
         if !group.is_empty() {
@@ -761,6 +782,23 @@ async fn build_sql(
     Ok(sql)
 }
 
+/// SQL expression of the aggregate of the column.
+fn agg_expr(function: &AggFunction, column: &str) -> String {
+    match function {
+        AggFunction::Avg => format!("AVG(\"{column}\")"),
+        AggFunction::Max => format!("MAX(\"{column}\")"),
+        AggFunction::Min => format!("MIN(\"{column}\")"),
+        AggFunction::Sum => format!("SUM(\"{column}\")"),
+        AggFunction::Count => format!("COUNT(\"{column}\")"),
+        AggFunction::Median => format!("MEDIAN(\"{column}\")"),
+        AggFunction::P50 => format!("approx_percentile_cont(\"{column}\", 0.5)"),
+        AggFunction::P75 => format!("approx_percentile_cont(\"{column}\", 0.75)"),
+        AggFunction::P90 => format!("approx_percentile_cont(\"{column}\", 0.9)"),
+        AggFunction::P95 => format!("approx_percentile_cont(\"{column}\", 0.95)"),
+        AggFunction::P99 => format!("approx_percentile_cont(\"{column}\", 0.99)"),
+    }
+}
+
 fn build_expr(
     cond: &Condition,
     field_alias: &str,
@@ -0,0 +1,169 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Evaluation of multi-condition alerts, which combine several queries with their own
+//! thresholds.
+
+use config::{
+    meta::{
+        alerts::{
+            Operator, QueryType, TriggerEvalResults,
+            alert::Alert,
+            multi_condition::{ConditionsOperator, MultiCondition},
+        },
+        search::{SearchEventContext, SearchEventType},
+    },
+    utils::json::Value,
+};
+use hashbrown::HashSet;
+
+use super::{QueryConditionExt, alert::AlertError};
+use crate::service::search::sql::RE_ONLY_SELECT;
+
+/// Column added to the returned rows with the name of the condition they satisfied.
+const CONDITION_COL: &str = "alert_condition";
+
+/// Checks the conditions of a multi-condition alert before it is saved.
+pub fn validate(alert: &mut Alert) -> Result<(), AlertError> {
+    let is_real_time = alert.is_real_time;
+    let Some(multi) = alert.multi_condition.as_mut() else {
+        return Ok(());
+    };
+    if is_real_time {
+        return Err(AlertError::InvalidMultiCondition(
+            "multi-condition alerts can't be realtime".to_string(),
+        ));
+    }
+    if multi.conditions.is_empty() {
+        return Err(AlertError::InvalidMultiCondition(
+            "at least one condition is required".to_string(),
+        ));
+    }
+    let mut names = HashSet::with_capacity(multi.conditions.len());
+    for condition in multi.conditions.iter_mut() {
+        condition.name = condition.name.trim().to_string();
+        if condition.name.is_empty() {
+            return Err(AlertError::InvalidMultiCondition(
+                "condition name is required".to_string(),
+            ));
+        }
+        if !names.insert(condition.name.clone()) {
+            return Err(AlertError::InvalidMultiCondition(format!(
+                "condition {} is defined more than once",
+                condition.name
+            )));
+        }
+        let query = &condition.query_condition;
+        match query.query_type {
+            QueryType::Custom => {
+                if query.aggregation.is_some() {
+                    // like single query alerts, any aggregated row satisfies the condition
+                    condition.operator = Operator::GreaterThanEquals;
+                    condition.threshold = 1;
+                }
+            }
+            QueryType::SQL => {
+                let Some(sql) = query.sql.as_ref().filter(|sql| !sql.is_empty()) else {
+                    return Err(AlertError::InvalidMultiCondition(format!(
+                        "condition {} should have a query",
+                        condition.name
+                    )));
+                };
+                if RE_ONLY_SELECT.is_match(sql) {
+                    return Err(AlertError::InvalidMultiCondition(format!(
+                        "condition {} can not contain SELECT * in the SQL query",
+                        condition.name
+                    )));
+                }
+            }
+            QueryType::PromQL => {
+                if query.promql.as_ref().is_none_or(|q| q.is_empty())
+                    || query.promql_condition.is_none()
+                {
+                    return Err(AlertError::InvalidMultiCondition(format!(
+                        "condition {} should have a PromQL query and condition",
+                        condition.name
+                    )));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Evaluates every condition over the same time range and fires when their combination holds.
+/// The rows of the satisfied conditions are returned, tagged with the name of the condition.
+pub async fn evaluate(
+    alert: &Alert,
+    multi: &MultiCondition,
+    (start_time, end_time): (Option<i64>, i64),
+    trace_id: Option<String>,
+) -> Result<TriggerEvalResults, anyhow::Error> {
+    let mut eval_results = TriggerEvalResults {
+        end_time,
+        ..Default::default()
+    };
+    let mut satisfied = Vec::with_capacity(multi.conditions.len());
+    let mut rows = vec![];
+    for condition in multi.conditions.iter() {
+        let mut trigger_condition = alert.trigger_condition.clone();
+        trigger_condition.operator = condition.operator;
+        trigger_condition.threshold = condition.threshold;
+        let search_event_ctx = SearchEventContext::with_alert(Some(format!(
+            "/alerts/{}/{}/{}/{}",
+            alert.org_id, alert.stream_type, alert.stream_name, alert.name
+        )));
+        let results = condition
+            .query_condition
+            .evaluate_scheduled(
+                &alert.org_id,
+                Some(&alert.stream_name),
+                alert.stream_type,
+                &trigger_condition,
+                (start_time, end_time),
+                Some(SearchEventType::Alerts),
+                Some(search_event_ctx),
+                trace_id.clone(),
+            )
+            .await?;
+        eval_results.end_time = results.end_time;
+        if let Some(took) = results.query_took {
+            *eval_results.query_took.get_or_insert(0) += took;
+        }
+        if let Some(scan_records) = results.scan_records {
+            *eval_results.scan_records.get_or_insert(0) += scan_records;
+        }
+        let is_satisfied = results.data.is_some();
+        if let Some(data) = results.data {
+            rows.extend(data.into_iter().map(|mut row| {
+                row.insert(
+                    CONDITION_COL.to_string(),
+                    Value::String(condition.name.clone()),
+                );
+                row
+            }));
+        }
+        satisfied.push(is_satisfied);
+        // a single unsatisfied condition settles AND, no need to run the other queries
+        if !is_satisfied && multi.operator == ConditionsOperator::And {
+            break;
+        }
+    }
+
+    if multi.operator.combine(satisfied.into_iter()) {
+        eval_results.data = Some(rows);
+    }
+    Ok(eval_results)
+}
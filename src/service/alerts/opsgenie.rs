@@ -0,0 +1,140 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Opsgenie Alert API destinations, the alerts are closed when the alert recovers.
+
+use config::{
+    meta::{
+        alerts::alert::Alert,
+        destinations::{Opsgenie, OpsgeniePriority},
+    },
+    utils::json::{self, Map, Value},
+};
+
+use crate::service::alerts::incidents::{alert_severity, send_event, summary};
+
+const DEFAULT_API_URL: &str = "https://api.opsgenie.com";
+/// Opsgenie truncates the message to 130 characters and the description to 15000
+const MAX_MESSAGE_LEN: usize = 130;
+const MAX_DESCRIPTION_LEN: usize = 15000;
+
+fn create_event(
+    alert: &Alert,
+    opsgenie: &Opsgenie,
+    alias: &str,
+    group_key: &str,
+    rows: &[Map<String, Value>],
+    msg: &str,
+) -> Value {
+    let priority = alert_severity(alert, rows)
+        .and_then(|s| OpsgeniePriority::from_alert_severity(&s))
+        .unwrap_or(opsgenie.priority);
+    let mut details = json::json!({
+        "org": alert.org_id,
+        "stream_type": alert.stream_type.to_string(),
+        "stream_name": alert.stream_name,
+        "rows": rows.len().to_string(),
+    });
+    if !group_key.is_empty() {
+        details["group"] = Value::String(group_key.to_string());
+    }
+    json::json!({
+        "message": summary(alert, msg).chars().take(MAX_MESSAGE_LEN).collect::<String>(),
+        "alias": alias,
+        "description": msg.chars().take(MAX_DESCRIPTION_LEN).collect::<String>(),
+        "priority": priority,
+        "source": "OpenObserve",
+        "entity": alert.stream_name,
+        "tags": [alert.name, alert.stream_type.to_string()],
+        "details": details,
+    })
+}
+
+fn api_url(opsgenie: &Opsgenie) -> &str {
+    if opsgenie.url.is_empty() {
+        DEFAULT_API_URL
+    } else {
+        opsgenie.url.trim_end_matches('/')
+    }
+}
+
+fn auth_header(opsgenie: &Opsgenie) -> Option<String> {
+    Some(format!("GenieKey {}", opsgenie.api_key))
+}
+
+pub(super) async fn trigger(
+    alert: &Alert,
+    opsgenie: &Opsgenie,
+    alias: &str,
+    group_key: &str,
+    rows: &[Map<String, Value>],
+    msg: &str,
+) -> Result<String, anyhow::Error> {
+    let event = create_event(alert, opsgenie, alias, group_key, rows, msg);
+    send_event(
+        &format!("{}/v2/alerts", api_url(opsgenie)),
+        auth_header(opsgenie),
+        &event,
+    )
+    .await
+}
+
+pub(super) async fn close(opsgenie: &Opsgenie, alias: &str) -> Result<String, anyhow::Error> {
+    let event = json::json!({
+        "source": "OpenObserve",
+        "note": "The alert recovered",
+    });
+    send_event(
+        &format!(
+            "{}/v2/alerts/{alias}/close?identifierType=alias",
+            api_url(opsgenie)
+        ),
+        auth_header(opsgenie),
+        &event,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_event() {
+        let alert = Alert {
+            name: "db_errors".to_string(),
+            stream_name: "k8s_logs".to_string(),
+            ..Default::default()
+        };
+        let opsgenie = Opsgenie {
+            api_key: "K3Y".to_string(),
+            priority: OpsgeniePriority::P2,
+            auto_close: true,
+            url: String::new(),
+        };
+        let mut row = Map::new();
+        row.insert("severity".to_string(), Value::String("low".to_string()));
+        let msg = "x".repeat(200);
+        let event = create_event(&alert, &opsgenie, "alias-1", "host=db-1", &[row], &msg);
+        assert_eq!(event["alias"], "alias-1");
+        assert_eq!(event["priority"], "P4");
+        assert_eq!(event["message"].as_str().unwrap().len(), MAX_MESSAGE_LEN);
+        assert_eq!(event["details"]["group"], "host=db-1");
+
+        let event = create_event(&alert, &opsgenie, "alias-1", "", &[], "db is down");
+        assert_eq!(event["priority"], "P2");
+        assert!(event["details"].get("group").is_none());
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! PagerDuty Events API v2 destinations, the incidents are resolved when the alert recovers.
+
+use chrono::Utc;
+use config::{
+    get_config,
+    meta::{
+        alerts::alert::Alert,
+        destinations::{PagerDuty, PagerDutySeverity},
+    },
+    utils::json::{self, Map, Value},
+};
+
+use crate::service::alerts::incidents::{alert_severity, send_event, summary};
+
+const DEFAULT_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+/// PagerDuty rejects summaries longer than 1024 characters
+const MAX_SUMMARY_LEN: usize = 1024;
+
+/// Builds the trigger event, a json message rendered from the template becomes the details of
+/// the incident, any other message its summary.
+fn trigger_event(
+    alert: &Alert,
+    pagerduty: &PagerDuty,
+    dedup_key: &str,
+    group_key: &str,
+    rows: &[Map<String, Value>],
+    msg: &str,
+    timestamp: &str,
+) -> Value {
+    let custom_details = match json::from_str::<Value>(msg) {
+        Ok(details @ (Value::Object(_) | Value::Array(_))) => details,
+        _ => json::json!({ "alert_name": alert.name, "rows": rows.len() }),
+    };
+    let summary = summary(alert, msg)
+        .chars()
+        .take(MAX_SUMMARY_LEN)
+        .collect::<String>();
+    let severity = alert_severity(alert, rows)
+        .and_then(|s| PagerDutySeverity::from_alert_severity(&s))
+        .unwrap_or(pagerduty.severity);
+    let mut payload = json::json!({
+        "summary": summary,
+        "source": format!("{}/{}/{}", alert.org_id, alert.stream_type, alert.stream_name),
+        "severity": severity,
+        "timestamp": timestamp,
+        "component": alert.stream_name,
+        "class": alert.name,
+        "custom_details": custom_details,
+    });
+    if !group_key.is_empty() {
+        payload["group"] = Value::String(group_key.to_string());
+    }
+    json::json!({
+        "routing_key": pagerduty.routing_key,
+        "event_action": "trigger",
+        "dedup_key": dedup_key,
+        "payload": payload,
+        "client": "OpenObserve",
+        "client_url": get_config().common.web_url,
+    })
+}
+
+fn resolve_event(pagerduty: &PagerDuty, dedup_key: &str) -> Value {
+    json::json!({
+        "routing_key": pagerduty.routing_key,
+        "event_action": "resolve",
+        "dedup_key": dedup_key,
+    })
+}
+
+fn events_url(pagerduty: &PagerDuty) -> &str {
+    if pagerduty.url.is_empty() {
+        DEFAULT_EVENTS_URL
+    } else {
+        &pagerduty.url
+    }
+}
+
+pub(super) async fn trigger(
+    alert: &Alert,
+    pagerduty: &PagerDuty,
+    dedup_key: &str,
+    group_key: &str,
+    rows: &[Map<String, Value>],
+    msg: &str,
+) -> Result<String, anyhow::Error> {
+    let event = trigger_event(
+        alert,
+        pagerduty,
+        dedup_key,
+        group_key,
+        rows,
+        msg,
+        &Utc::now().to_rfc3339(),
+    );
+    send_event(events_url(pagerduty), None, &event).await
+}
+
+pub(super) async fn resolve(
+    pagerduty: &PagerDuty,
+    dedup_key: &str,
+) -> Result<String, anyhow::Error> {
+    send_event(
+        events_url(pagerduty),
+        None,
+        &resolve_event(pagerduty, dedup_key),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use hashbrown::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_trigger_event() {
+        let alert = Alert {
+            name: "db_errors".to_string(),
+            org_id: "default".to_string(),
+            stream_name: "k8s_logs".to_string(),
+            context_attributes: Some(HashMap::from([("severity".to_string(), "P1".to_string())])),
+            ..Default::default()
+        };
+        let pagerduty = PagerDuty {
+            routing_key: "R0UT1NG".to_string(),
+            severity: PagerDutySeverity::Warning,
+            url: String::new(),
+        };
+        let event = trigger_event(
+            &alert,
+            &pagerduty,
+            "key-1",
+            "host=db-1",
+            &[],
+            "db-1 has 12 errors",
+            "2025-07-10T00:00:00+00:00",
+        );
+        assert_eq!(event["event_action"], "trigger");
+        assert_eq!(event["payload"]["summary"], "db-1 has 12 errors");
+        assert_eq!(event["payload"]["severity"], "critical");
+        assert_eq!(event["payload"]["group"], "host=db-1");
+        assert_eq!(event["dedup_key"], "key-1");
+
+        let event = trigger_event(&alert, &pagerduty, "key", "", &[], r#"{"errors":12}"#, "");
+        assert_eq!(event["payload"]["custom_details"]["errors"], 12);
+        assert_eq!(
+            event["payload"]["summary"],
+            "Alert db_errors fired on logs k8s_logs"
+        );
+        assert!(event["payload"].get("group").is_none());
+    }
+}
@@ -0,0 +1,414 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Alerts created from a dashboard panel. The query condition of the alert is built from the
+//! panel query, with the dashboard variables replaced and the alert condition added, and the alert
+//! keeps a link to the panel so the query condition can be rebuilt when the panel changes.
+
+use std::ops::ControlFlow;
+
+use chrono::Utc;
+use config::{
+    meta::{
+        alerts::{Condition, Operator, PanelSource, QueryCondition, QueryType, alert::Alert},
+        dashboards::{PanelQuery, acl::DashboardAccess, convert::inner_json, panel_query},
+        search::{QueryVariable, substitute_variables},
+        sql::resolve_stream_names,
+        stream::StreamType,
+    },
+    utils::{base64, json::Value},
+};
+use sea_orm::{ConnectionTrait, TransactionTrait};
+use sqlparser::{
+    ast::{BinaryOperator, Expr, Ident, SelectItem, SetExpr, Statement, visit_expressions},
+    dialect::PostgreSqlDialect,
+    parser::Parser,
+};
+use svix_ksuid::Ksuid;
+
+use super::alert::{self, AlertError};
+use crate::service::dashboards::{self, DashboardError, acl as dashboard_acl};
+
+/// Aggregate functions, a condition on their result goes in the HAVING clause.
+const AGGREGATE_FUNCTIONS: [&str; 14] = [
+    "count",
+    "sum",
+    "avg",
+    "min",
+    "max",
+    "median",
+    "stddev",
+    "variance",
+    "approx_distinct",
+    "approx_median",
+    "approx_percentile_cont",
+    "array_agg",
+    "first_value",
+    "last_value",
+];
+
+/// Creates the alert from the panel of its `panel_source`.
+pub async fn create_from_panel<C: TransactionTrait>(
+    conn: &C,
+    org_id: &str,
+    folder_id: &str,
+    user_id: &str,
+    mut alert: Alert,
+) -> Result<Alert, AlertError> {
+    apply_panel(org_id, user_id, &mut alert).await?;
+    alert::create(conn, org_id, folder_id, alert).await
+}
+
+/// Rebuilds the query condition of the alert from the current definition of its panel.
+pub async fn sync_with_panel<C: ConnectionTrait + TransactionTrait>(
+    conn: &C,
+    org_id: &str,
+    alert_id: Ksuid,
+    user_id: &str,
+) -> Result<Alert, AlertError> {
+    let mut alert = alert::get_by_id(conn, org_id, alert_id).await?;
+    apply_panel(org_id, user_id, &mut alert).await?;
+    alert.last_edited_by = Some(user_id.to_string());
+    alert::update(conn, org_id, None, alert).await
+}
+
+/// Sets the stream and the query condition of the alert from its panel.
+async fn apply_panel(org_id: &str, user_id: &str, alert: &mut Alert) -> Result<(), AlertError> {
+    let Some(source) = alert.panel_source.as_mut() else {
+        return Err(AlertError::PanelSourceMissing);
+    };
+    dashboard_acl::check(org_id, &source.dashboard_id, user_id, DashboardAccess::View)
+        .await
+        .map_err(panel_error)?;
+    let dashboard = dashboards::get_dashboard(org_id, &source.dashboard_id)
+        .await
+        .map_err(panel_error)?;
+    let query = inner_json(&dashboard)
+        .and_then(|v| panel_query(&v, &source.panel_id, source.query_index))
+        .ok_or_else(|| {
+            AlertError::PanelSource(format!(
+                "query {} of panel {} not found",
+                source.query_index, source.panel_id
+            ))
+        })?;
+
+    let (stream_type, stream_name, query_condition) =
+        build_query_condition(&query, source).map_err(AlertError::PanelSource)?;
+    source.synced_at = Utc::now().timestamp_micros();
+    alert.stream_type = stream_type;
+    alert.stream_name = stream_name;
+    alert.query_condition = query_condition;
+    if alert.description.is_empty() {
+        alert.description = format!("Created from dashboard panel {}", query.panel_title);
+    }
+    Ok(())
+}
+
+fn panel_error(e: DashboardError) -> AlertError {
+    match e {
+        DashboardError::PermissionDenied => AlertError::PermissionDenied,
+        DashboardError::InfraError(e) => AlertError::InfraError(e),
+        e => AlertError::PanelSource(e.to_string()),
+    }
+}
+
+/// Builds the stream and the query condition of the alert from the panel query. PromQL queries
+/// are compared with the condition value, SQL queries get the condition added to them.
+fn build_query_condition(
+    query: &PanelQuery,
+    source: &PanelSource,
+) -> Result<(StreamType, String, QueryCondition), String> {
+    let variables = source
+        .variables
+        .iter()
+        .map(|v| QueryVariable {
+            name: v.key.clone(),
+            value: Value::String(v.value.clone()),
+        })
+        .collect::<Vec<_>>();
+    let query_str = substitute_variables(&query.query, &variables)?;
+    let mut stream_name = substitute_stream_name(&query.stream.stream_name, &variables)?;
+
+    if query.promql {
+        if stream_name.is_empty() {
+            return Err("the stream of the PromQL panel query is not set".to_string());
+        }
+        let query_condition = QueryCondition {
+            query_type: QueryType::PromQL,
+            promql: Some(query_str),
+            promql_condition: Some(source.condition.clone()),
+            ..Default::default()
+        };
+        return Ok((StreamType::Metrics, stream_name, query_condition));
+    }
+
+    let sql = add_condition(&query_str, &source.condition)?;
+    if stream_name.is_empty() {
+        stream_name = resolve_stream_names(&sql)
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+    }
+    let query_condition = QueryCondition {
+        query_type: QueryType::SQL,
+        sql: Some(sql),
+        vrl_function: query.vrl_function.as_deref().map(base64::encode_url),
+        ..Default::default()
+    };
+    Ok((query.stream.stream_type, stream_name, query_condition))
+}
+
+/// Replaces the variables of the stream name of the panel query. The name is substituted as a
+/// quoted identifier and the result has to be a valid stream name.
+fn substitute_stream_name(name: &str, variables: &[QueryVariable]) -> Result<String, String> {
+    let quoted = format!("\"{}\"", name.replace('"', "\"\""));
+    let substituted = substitute_variables(&quoted, variables)?;
+    let stream_name = substituted[1..substituted.len() - 1].replace("\"\"", "\"");
+    if !stream_name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+    {
+        return Err(format!(
+            "invalid stream name of the panel query: {stream_name}"
+        ));
+    }
+    Ok(stream_name)
+}
+
+/// Adds the condition to the panel query. The column is matched with the aliases of the
+/// selected expressions, falling back to a stream field. Conditions on aggregates go in the
+/// HAVING clause, the others in the WHERE clause, both are ANDed with the existing clause.
+fn add_condition(sql: &str, condition: &Condition) -> Result<String, String> {
+    let mut statements =
+        Parser::parse_sql(&PostgreSqlDialect {}, sql).map_err(|e| e.to_string())?;
+    let mut statement = match statements.pop() {
+        Some(statement) if statements.is_empty() => statement,
+        _ => return Err("panel query must be a single SELECT query".to_string()),
+    };
+    let Statement::Query(query) = &mut statement else {
+        return Err("panel query must be a single SELECT query".to_string());
+    };
+    let SetExpr::Select(select) = query.body.as_mut() else {
+        return Err("panel query must be a single SELECT query".to_string());
+    };
+
+    let column = select
+        .projection
+        .iter()
+        .find_map(|item| match item {
+            SelectItem::ExprWithAlias { expr, alias } if alias.value == condition.column => {
+                Some(expr.clone())
+            }
+            SelectItem::UnnamedExpr(expr) if expr_name(expr) == condition.column => {
+                Some(expr.clone())
+            }
+            _ => None,
+        })
+        .unwrap_or_else(|| Expr::Identifier(Ident::with_quote('"', &condition.column)));
+    let aggregated = visit_expressions(&column, |expr| match expr {
+        Expr::Function(f)
+            if AGGREGATE_FUNCTIONS
+                .iter()
+                .any(|name| f.name.to_string().eq_ignore_ascii_case(name)) =>
+        {
+            ControlFlow::Break(())
+        }
+        _ => ControlFlow::Continue(()),
+    })
+    .is_break();
+
+    let expr = condition_sql(&column, condition);
+    let expr = Parser::new(&PostgreSqlDialect {})
+        .try_with_sql(&expr)
+        .and_then(|mut p| p.parse_expr())
+        .map_err(|e| format!("invalid condition {expr}: {e}"))?;
+    let clause = if aggregated {
+        &mut select.having
+    } else {
+        &mut select.selection
+    };
+    *clause = Some(match clause.take() {
+        Some(existing) => Expr::BinaryOp {
+            left: Box::new(Expr::Nested(Box::new(existing))),
+            op: BinaryOperator::And,
+            right: Box::new(expr),
+        },
+        None => expr,
+    });
+    Ok(statement.to_string())
+}
+
+/// Name of a selected expression without alias, the column name for columns.
+fn expr_name(expr: &Expr) -> String {
+    match expr {
+        Expr::Identifier(ident) => ident.value.clone(),
+        Expr::CompoundIdentifier(idents) => idents
+            .last()
+            .map(|ident| ident.value.clone())
+            .unwrap_or_default(),
+        expr => expr.to_string(),
+    }
+}
+
+/// SQL of the condition on the expression.
+fn condition_sql(expr: &Expr, condition: &Condition) -> String {
+    let value = match &condition.value {
+        Value::String(s) => s.to_string(),
+        v => v.to_string(),
+    };
+    let (expr, value) = if condition.ignore_case {
+        (format!("LOWER({expr})"), value.to_lowercase())
+    } else {
+        (expr.to_string(), value)
+    };
+    let escaped = value.replace('\'', "''");
+    match condition.operator {
+        Operator::Contains => format!("{expr} LIKE '%{escaped}%'"),
+        Operator::NotContains => format!("{expr} NOT LIKE '%{escaped}%'"),
+        op if condition.value.is_number() => format!("{expr} {op} {value}"),
+        op => format!("{expr} {op} '{escaped}'"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use config::{
+        meta::dashboards::{StreamReference, reports::ReportDashboardVariable},
+        utils::json::json,
+    };
+
+    use super::*;
+
+    fn condition(column: &str, operator: Operator, value: Value) -> Condition {
+        Condition {
+            column: column.to_string(),
+            operator,
+            value,
+            ignore_case: false,
+        }
+    }
+
+    #[test]
+    fn test_add_condition() {
+        let sql = "SELECT histogram(_timestamp) AS x_axis_1, count(_timestamp) AS y_axis_1 FROM \"default\" WHERE level = 'error' OR level = 'warn' GROUP BY x_axis_1";
+        let got = add_condition(
+            sql,
+            &condition("y_axis_1", Operator::GreaterThan, json!(10)),
+        )
+        .unwrap();
+        assert!(got.ends_with("GROUP BY x_axis_1 HAVING count(_timestamp) > 10"));
+
+        let sql =
+            "SELECT _timestamp, code FROM \"default\" WHERE level = 'error' OR level = 'warn'";
+        let got = add_condition(
+            sql,
+            &condition("code", Operator::GreaterThanEquals, json!(500)),
+        )
+        .unwrap();
+        assert!(got.ends_with("WHERE (level = 'error' OR level = 'warn') AND code >= 500"));
+
+        let mut cond = condition("message", Operator::Contains, json!("it's"));
+        cond.ignore_case = true;
+        let got = add_condition("SELECT count(*) AS total FROM \"default\"", &cond).unwrap();
+        assert!(got.ends_with("WHERE LOWER(\"message\") LIKE '%it''s%'"));
+
+        assert!(add_condition("SELECT 1; SELECT 2", &cond).is_err());
+        assert!(add_condition("SELECT a FROM x UNION SELECT b FROM y", &cond).is_err());
+        assert!(add_condition("DELETE FROM x", &cond).is_err());
+    }
+
+    #[test]
+    fn test_build_query_condition() {
+        let query = PanelQuery {
+            panel_title: "errors".to_string(),
+            promql: false,
+            query: "SELECT count(*) AS total FROM \"$stream\" WHERE host = '$host'".to_string(),
+            vrl_function: None,
+            stream: StreamReference {
+                stream_type: StreamType::Logs,
+                stream_name: "$stream".to_string(),
+            },
+        };
+        let source = PanelSource {
+            dashboard_id: "d1".to_string(),
+            panel_id: "Panel_ID1".to_string(),
+            variables: vec![
+                ReportDashboardVariable {
+                    key: "stream".to_string(),
+                    value: "default".to_string(),
+                    id: None,
+                },
+                ReportDashboardVariable {
+                    key: "host".to_string(),
+                    value: "h1".to_string(),
+                    id: None,
+                },
+            ],
+            query_index: 0,
+            condition: condition("total", Operator::GreaterThan, json!(5)),
+            synced_at: 0,
+        };
+        let (stream_type, stream_name, query_condition) =
+            build_query_condition(&query, &source).unwrap();
+        assert_eq!(stream_type, StreamType::Logs);
+        assert_eq!(stream_name, "default");
+        assert_eq!(query_condition.query_type, QueryType::SQL);
+        assert_eq!(
+            query_condition.sql.as_deref(),
+            Some("SELECT count(*) AS total FROM \"default\" WHERE host = 'h1' HAVING count(*) > 5")
+        );
+
+        let query = PanelQuery {
+            promql: true,
+            query: "rate(http_requests_total[5m])".to_string(),
+            stream: StreamReference {
+                stream_type: StreamType::Metrics,
+                stream_name: "http_requests_total".to_string(),
+            },
+            ..query
+        };
+        let (stream_type, stream_name, query_condition) =
+            build_query_condition(&query, &source).unwrap();
+        assert_eq!(stream_type, StreamType::Metrics);
+        assert_eq!(stream_name, "http_requests_total");
+        assert_eq!(query_condition.query_type, QueryType::PromQL);
+        assert_eq!(query_condition.promql_condition, Some(source.condition));
+    }
+
+    #[test]
+    fn test_substitute_stream_name() {
+        let variables = vec![
+            QueryVariable {
+                name: "env".to_string(),
+                value: json!("prod"),
+            },
+            QueryVariable {
+                name: "evil".to_string(),
+                value: json!("x\" WHERE 1=1 --"),
+            },
+        ];
+        assert_eq!(
+            substitute_stream_name("logs_$env", &variables).unwrap(),
+            "logs_prod"
+        );
+        assert_eq!(
+            substitute_stream_name("default", &variables).unwrap(),
+            "default"
+        );
+        assert!(substitute_stream_name("$evil", &variables).is_err());
+        assert!(substitute_stream_name("a b", &variables).is_err());
+    }
+}
@@ -0,0 +1,163 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Rendering of alert templates written in jinja syntax.
+//!
+//! Templates containing `{{ ... }}` or `{% ... %}` are rendered with minijinja and can loop over
+//! the matched rows, use conditionals, `tojson` and the filters registered here. All other
+//! templates keep the plain `{var}` substitution.
+
+use chrono::{Local, TimeZone};
+use config::utils::units::Unit;
+use minijinja::{Environment, Error, ErrorKind, Value};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+const DEFAULT_DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+static ENV: Lazy<Environment<'static>> = Lazy::new(|| {
+    let mut env = Environment::new();
+    env.add_filter("datetime", datetime);
+    env.add_filter("truncate", truncate);
+    env.add_filter("unit", unit);
+    env
+});
+
+/// Returns true if the template uses jinja syntax and should be rendered with [`render`].
+pub fn is_jinja(tpl: &str) -> bool {
+    tpl.contains("{{") || tpl.contains("{%")
+}
+
+/// Renders the template with the given context.
+pub fn render<S: Serialize>(tpl: &str, ctx: S) -> Result<String, Error> {
+    ENV.render_str(tpl, ctx)
+}
+
+/// Checks the syntax of a jinja template, returning the error message if it doesn't compile.
+pub fn validate(tpl: &str) -> Result<(), String> {
+    if !is_jinja(tpl) {
+        return Ok(());
+    }
+    ENV.template_from_str(tpl)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+fn to_f64(value: &Value) -> Option<f64> {
+    match value.as_str() {
+        Some(v) => v.trim().parse().ok(),
+        None => f64::try_from(value.clone()).ok(),
+    }
+}
+
+/// `{{ ts | datetime }}` or `{{ ts | datetime("%H:%M") }}` formats a timestamp in microseconds.
+fn datetime(value: Value, format: Option<String>) -> Result<String, Error> {
+    let Some(micros) = to_f64(&value) else {
+        return Err(Error::new(
+            ErrorKind::InvalidOperation,
+            format!("cannot format {value} as a datetime"),
+        ));
+    };
+    let format = format.as_deref().unwrap_or(DEFAULT_DATETIME_FORMAT);
+    Ok(Local
+        .timestamp_nanos(micros as i64 * 1000)
+        .format(format)
+        .to_string())
+}
+
+/// `{{ message | truncate(100) }}` cuts the value to the given number of characters, appending
+/// the optional second argument (default `...`) if it was cut.
+fn truncate(value: Value, length: usize, end: Option<String>) -> String {
+    let value = match value.as_str() {
+        Some(v) => v.to_string(),
+        None => value.to_string(),
+    };
+    if value.chars().count() <= length {
+        return value;
+    }
+    let mut out = value.chars().take(length).collect::<String>();
+    out.push_str(end.as_deref().unwrap_or("..."));
+    out
+}
+
+/// `{{ bytes_sent | unit("bytes") }}` formats a number in the unit, like `1.5 GiB`. Values that
+/// aren't numbers are kept as they are.
+fn unit(value: Value, unit: String) -> String {
+    match (Unit::parse(&unit, None), to_f64(&value)) {
+        (Some(unit), Some(number)) => unit.format(number, None),
+        _ => match value.as_str() {
+            Some(v) => v.to_string(),
+            None => value.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_is_jinja() {
+        assert!(is_jinja("{{ alert_name }}"));
+        assert!(is_jinja("{% if rows %}x{% endif %}"));
+        assert!(!is_jinja("{\"text\": \"{alert_name} {rows}\"}"));
+    }
+
+    #[test]
+    fn test_render_loops_and_conditionals() {
+        let tpl = "{{ alert_name }}:{% for row in rows %} {{ row.host }}{% if row.code >= 500 %}!{% endif %}{% endfor %}";
+        let ctx = json!({
+            "alert_name": "errors",
+            "rows": [{"host": "a", "code": 500}, {"host": "b", "code": 404}],
+        });
+        assert_eq!(render(tpl, &ctx).unwrap(), "errors: a! b");
+    }
+
+    #[test]
+    fn test_render_filters() {
+        let ctx = json!({"msg": "hello world", "ts": 0, "size": 2048});
+        assert_eq!(render("{{ msg | truncate(5) }}", &ctx).unwrap(), "hello...");
+        assert_eq!(
+            render("{{ msg | truncate(5, '') }}", &ctx).unwrap(),
+            "hello"
+        );
+        assert_eq!(
+            render("{{ msg | truncate(50) }}", &ctx).unwrap(),
+            "hello world"
+        );
+        assert_eq!(
+            render("{{ ts | datetime('%Y') }}", &ctx).unwrap(),
+            Local.timestamp_nanos(0).format("%Y").to_string()
+        );
+        assert_eq!(
+            render("{{ size | unit('bytes') }}", &ctx).unwrap(),
+            Unit::parse("bytes", None).unwrap().format(2048.0, None)
+        );
+        assert_eq!(
+            render("{{ msg | unit('bytes') }}", &ctx).unwrap(),
+            "hello world"
+        );
+        assert!(render("{{ msg | datetime }}", &ctx).is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate("{alert_name}").is_ok());
+        assert!(validate("{% for row in rows %}{{ row }}{% endfor %}").is_ok());
+        assert!(validate("{% for row in rows %}{{ row }}").is_err());
+    }
+}
@@ -20,14 +20,14 @@ use config::{
     cluster::LOCAL_NODE,
     get_config, ider,
     meta::{
-        alerts::TriggerCondition,
+        alerts::{TriggerCondition, history::AlertState},
         dashboards::reports::ReportFrequencyType,
         self_reporting::{
             error::{ErrorData, ErrorSource, PipelineError},
             usage::{TriggerData, TriggerDataStatus, TriggerDataType},
         },
         stream::{StreamParams, StreamType},
-        triggers::ScheduledTriggerData,
+        triggers::{FiringState, ScheduledTriggerData},
     },
     utils::{
         json,
@@ -44,10 +44,12 @@ use proto::cluster_rpc;
 
 use crate::service::{
     alerts::{
-        alert::{AlertExt, get_alert_start_end_time, get_by_id_db, get_row_column_map},
+        alert::{AlertExt, Recovery, get_alert_start_end_time, get_by_id_db, get_row_column_map},
+        correlation,
         derived_streams::DerivedStreamExt,
+        escalation, history, incidents, metrics, silences,
     },
-    dashboards::reports::SendReport,
+    dashboards::{reports::SendReport, scheduled_snapshots},
     db::{self, alerts::alert::set_without_updating_trigger},
     ingestion::ingestion_service,
     pipeline::batch_execution::ExecutablePipeline,
@@ -64,6 +66,12 @@ pub async fn handle_triggers(
         db::scheduler::TriggerModule::DerivedStream => {
             handle_derived_stream_triggers(trace_id, trigger).await
         }
+        db::scheduler::TriggerModule::DashboardSnapshot => {
+            handle_dashboard_snapshot_triggers(trace_id, trigger).await
+        }
+        db::scheduler::TriggerModule::Escalation => {
+            handle_escalation_triggers(trace_id, trigger).await
+        }
     }
 }
 
@@ -242,6 +250,9 @@ async fn handle_alert_triggers(
             period_end_time: None,
             tolerance: 0,
             last_satisfied_at: None,
+            firing: None,
+            pending_since: None,
+            stats: None,
         }
     };
 
@@ -291,6 +302,13 @@ async fn handle_alert_triggers(
         );
         final_end_time = skipped_timestamps_end_timestamp.1;
         let skipped_timestamps = skipped_timestamps_end_timestamp.0;
+        metrics::record_skipped(
+            &mut trigger_data,
+            &trigger.org,
+            &trigger.module_key,
+            now,
+            skipped_timestamps.len() as u64,
+        );
 
         // Skip Alerts: Say for some reason, this alert trigger (period: 10mins, frequency 5mins)
         // which was supposed to run at 10am is now processed after a delay of 5 mins (may be alert
@@ -327,6 +345,7 @@ async fn handle_alert_triggers(
                 query_took: None,
                 scheduler_trace_id: Some(scheduler_trace_id.clone()),
                 time_in_queue_ms: Some(time_in_queue),
+                correlation: None,
             })
             .await;
         }
@@ -371,6 +390,7 @@ async fn handle_alert_triggers(
         query_took: None,
         scheduler_trace_id: Some(scheduler_trace_id.clone()),
         time_in_queue_ms: Some(time_in_queue),
+        correlation: None,
     };
 
     let evaluation_took = Instant::now();
@@ -384,6 +404,19 @@ async fn handle_alert_triggers(
         .await;
     let evaluation_took = evaluation_took.elapsed().as_secs_f64();
     trigger_data_stream.evaluation_took_in_secs = Some(evaluation_took);
+    let (scan_records, error) = match &result {
+        Ok(results) => (results.scan_records, None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    metrics::record_evaluation(
+        &mut trigger_data,
+        &trigger.org,
+        &trigger.module_key,
+        now,
+        evaluation_took,
+        scan_records,
+        error,
+    );
     if result.is_err() {
         let err = result.err().unwrap();
         trigger_data_stream.status = TriggerDataStatus::Failed;
@@ -424,14 +457,15 @@ async fn handle_alert_triggers(
             trigger_data_stream.next_run_at = new_trigger.next_run_at;
             db::scheduler::update_trigger(new_trigger).await?;
         } else {
-            // update its status and retries
+            // update its status and retries, the data keeps the evaluation statistics
+            let trigger_data = json::to_string(&trigger_data).unwrap();
             db::scheduler::update_status(
                 &new_trigger.org,
                 new_trigger.module,
                 &new_trigger.module_key,
                 db::scheduler::TriggerStatus::Waiting,
                 trigger.retries + 1,
-                None,
+                Some(&trigger_data),
             )
             .await?;
         }
@@ -504,15 +538,38 @@ async fn handle_alert_triggers(
         );
         trigger_data_stream.start_time = alert_start_time;
         trigger_data_stream.end_time = alert_end_time;
-        match alert
-            .send_notification(
-                &data,
-                trigger_results.end_time,
-                Some(start_time),
-                final_end_time,
-            )
-            .await
-        {
+        if alert.correlation.as_ref().is_some_and(|c| c.enabled) {
+            match correlation::analyze(&alert, alert_start_time, alert_end_time).await {
+                Ok(contributors) if !contributors.is_empty() => {
+                    trigger_data_stream.correlation = json::to_string(&contributors).ok();
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    log::warn!(
+                        "[SCHEDULER trace_id {scheduler_trace_id}] alert {} correlation analysis failed: {e}",
+                        &new_trigger.module_key
+                    );
+                }
+            }
+        }
+        let silence = silences::mute(&alert, &data).await;
+        let result = match &silence {
+            Some(silence_id) => Ok((
+                format!("notification muted by silence {silence_id}"),
+                String::new(),
+            )),
+            None => {
+                alert
+                    .send_notification(
+                        &data,
+                        trigger_results.end_time,
+                        Some(start_time),
+                        final_end_time,
+                    )
+                    .await
+            }
+        };
+        match result {
             Ok((success_msg, err_msg)) => {
                 let success_msg = success_msg.trim().to_owned();
                 let err_msg = err_msg.trim().to_owned();
@@ -531,6 +588,48 @@ async fn handle_alert_triggers(
                     );
                 }
                 trigger_data_stream.success_response = Some(success_msg);
+                if trigger_data.firing.is_none() {
+                    let previous_state = trigger_data
+                        .pending_since
+                        .take()
+                        .map(|_| AlertState::Pending);
+                    let entry = history::transition(
+                        &alert,
+                        triggered_at,
+                        AlertState::Firing,
+                        previous_state,
+                        &data,
+                    );
+                    history::record(&new_trigger.org, entry).await;
+                    if silence.is_none()
+                        && let Err(e) = escalation::start(
+                            &alert,
+                            &data,
+                            trigger_results.end_time,
+                            Some(start_time),
+                            final_end_time,
+                        )
+                        .await
+                    {
+                        log::error!(
+                            "[SCHEDULER trace_id {scheduler_trace_id}] Error starting the escalation of alert {}/{}: {e}",
+                            &new_trigger.org,
+                            &new_trigger.module_key
+                        );
+                    }
+                }
+                // Keep the incidents opened by the notification to resolve them on recovery
+                let firing = trigger_data.firing.get_or_insert_with(|| FiringState {
+                    since: triggered_at,
+                    last_value: None,
+                    incidents: vec![],
+                });
+                firing.last_value = history::evaluated_value(&data);
+                for incident in incidents::incidents(&alert, &data).await {
+                    if !firing.incidents.contains(&incident) {
+                        firing.incidents.push(incident);
+                    }
+                }
                 // Notification was sent successfully, store the last used end_time in the triggers
                 trigger_data.period_end_time = if should_store_last_end_time {
                     Some(trigger_results.end_time)
@@ -548,6 +647,13 @@ async fn handle_alert_triggers(
                     &new_trigger.org,
                     &new_trigger.module_key
                 );
+                if trigger_data.firing.is_none() && trigger_data.pending_since.is_none() {
+                    trigger_data.pending_since = Some(triggered_at);
+                    let mut entry =
+                        history::transition(&alert, triggered_at, AlertState::Pending, None, &data);
+                    entry.error = Some(e.to_string());
+                    history::record(&new_trigger.org, entry).await;
+                }
                 if trigger.retries + 1 >= max_retries {
                     // It has been tried the maximum time, just update the
                     // next_run_at to the next expected trigger time
@@ -594,6 +700,85 @@ async fn handle_alert_triggers(
             &new_trigger.org,
             &new_trigger.module_key
         );
+        let firing = trigger_data.firing.take();
+        let previous = match (&firing, trigger_data.pending_since.take()) {
+            (Some(firing), _) => Some((AlertState::Firing, firing.since)),
+            (None, Some(since)) => Some((AlertState::Pending, since)),
+            (None, None) => None,
+        };
+        if let Some((previous_state, since)) = previous {
+            let mut entry = history::transition(
+                &alert,
+                triggered_at,
+                AlertState::Resolved,
+                Some(previous_state),
+                &[],
+            );
+            entry.since = Some(since);
+            history::record(&new_trigger.org, entry).await;
+        }
+        // The alert recovered, end its escalation and resolve the incidents it opened
+        if firing.is_some()
+            && let Err(e) = escalation::stop(&alert).await
+        {
+            log::error!(
+                "[SCHEDULER trace_id {scheduler_trace_id}] Error ending the escalation of alert {}/{}: {e}",
+                &new_trigger.org,
+                &new_trigger.module_key
+            );
+        }
+        if let Some(firing) = &firing {
+            let recovery = Recovery {
+                firing_duration: triggered_at - firing.since,
+                last_value: firing.last_value,
+            };
+            match alert
+                .send_resolved_notification(recovery, triggered_at)
+                .await
+            {
+                Ok((success_msg, err_msg)) => {
+                    let err_msg = err_msg.trim();
+                    if !err_msg.is_empty() {
+                        log::error!(
+                            "[SCHEDULER trace_id {scheduler_trace_id}] Some resolved notifications for alert {}/{} could not be sent: {err_msg}",
+                            &new_trigger.org,
+                            &new_trigger.module_key
+                        );
+                        trigger_data_stream.error = Some(err_msg.to_string());
+                    }
+                    let success_msg = success_msg.trim();
+                    if !success_msg.is_empty() {
+                        trigger_data_stream.success_response = Some(success_msg.to_string());
+                    }
+                }
+                Err(e) => {
+                    log::error!(
+                        "[SCHEDULER trace_id {scheduler_trace_id}] Error sending resolved notification of alert {}/{}: {e}",
+                        &new_trigger.org,
+                        &new_trigger.module_key
+                    );
+                    trigger_data_stream.error = Some(e.to_string());
+                }
+            }
+        }
+        if let Some(firing) = firing
+            && !firing.incidents.is_empty()
+        {
+            if let Err(e) = incidents::resolve(&alert, &firing.incidents).await {
+                log::error!(
+                    "[SCHEDULER trace_id {scheduler_trace_id}] Error resolving incidents of alert {}/{}: {e}",
+                    &new_trigger.org,
+                    &new_trigger.module_key
+                );
+                trigger_data_stream.error = Some(e.to_string());
+            } else {
+                log::info!(
+                    "[SCHEDULER trace_id {scheduler_trace_id}] Alert recovered, resolved its incidents, org: {}, module_key: {}",
+                    &new_trigger.org,
+                    &new_trigger.module_key
+                );
+            }
+        }
         // Condition did not match, store the last used end_time in the triggers
         // In the next run, the alert will be checked from the last end_time
         trigger_data.period_end_time = if should_store_last_end_time {
@@ -750,6 +935,7 @@ async fn handle_report_triggers(
         query_took: None,
         scheduler_trace_id: Some(scheduler_trace_id.clone()),
         time_in_queue_ms: Some(Duration::microseconds(time_in_queue).num_milliseconds()),
+        correlation: None,
     };
 
     if trigger.retries >= max_retries {
@@ -823,6 +1009,177 @@ async fn handle_report_triggers(
     Ok(())
 }
 
+async fn handle_dashboard_snapshot_triggers(
+    trace_id: &str,
+    trigger: db::scheduler::Trigger,
+) -> Result<(), anyhow::Error> {
+    let scheduler_trace_id = format!("{}/{}", trace_id, ider::generate_trace_id());
+    let (_, max_retries) = get_scheduler_max_retries();
+    let org_id = &trigger.org;
+    // For dashboard snapshots, trigger.module_key is the schedule id
+    let schedule_id = &trigger.module_key;
+    log::debug!(
+        "[SCHEDULER trace_id {scheduler_trace_id}] Inside handle_dashboard_snapshot_triggers, org: {org_id}, schedule: {schedule_id}"
+    );
+
+    let schedule = match db::dashboard_snapshots::get_schedule(org_id, schedule_id).await {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            log::warn!(
+                "[SCHEDULER trace_id {scheduler_trace_id}] Snapshot schedule {org_id}/{schedule_id} not found, deleting its trigger: {e}"
+            );
+            db::scheduler::delete(org_id, trigger.module.clone(), schedule_id).await?;
+            return Ok(());
+        }
+    };
+    let now = now_micros();
+    let new_trigger = db::scheduler::Trigger {
+        next_run_at: now + second_micros(schedule.frequency),
+        is_realtime: false,
+        is_silenced: false,
+        status: db::scheduler::TriggerStatus::Waiting,
+        retries: 0,
+        ..trigger.clone()
+    };
+    if !schedule.enabled || trigger.retries >= max_retries {
+        db::scheduler::update_trigger(new_trigger).await?;
+        return Ok(());
+    }
+
+    let mut trigger_data_stream = TriggerData {
+        _timestamp: now,
+        org: trigger.org.clone(),
+        module: TriggerDataType::DashboardSnapshot,
+        key: format!("{}/{}", schedule.dashboard_id, schedule_id),
+        next_run_at: new_trigger.next_run_at,
+        is_realtime: false,
+        is_silenced: false,
+        status: TriggerDataStatus::Completed,
+        start_time: trigger.start_time.unwrap_or_default(),
+        end_time: trigger.end_time.unwrap_or_default(),
+        retries: trigger.retries,
+        error: None,
+        success_response: None,
+        is_partial: None,
+        delay_in_secs: Some(Duration::microseconds(now - trigger.next_run_at).num_seconds()),
+        evaluation_took_in_secs: None,
+        source_node: Some(LOCAL_NODE.name.clone()),
+        query_took: None,
+        scheduler_trace_id: Some(scheduler_trace_id.clone()),
+        time_in_queue_ms: Some(
+            Duration::microseconds(now - trigger.start_time.unwrap_or_default()).num_milliseconds(),
+        ),
+        correlation: None,
+    };
+
+    let evaluation_took = Instant::now();
+    match scheduled_snapshots::run(org_id, &schedule).await {
+        Ok(snapshot) => {
+            log::info!(
+                "[SCHEDULER trace_id {scheduler_trace_id}] Snapshot {} of schedule {org_id}/{schedule_id} written",
+                snapshot.id
+            );
+            db::scheduler::update_trigger(new_trigger).await?;
+        }
+        Err(e) => {
+            log::error!(
+                "[SCHEDULER trace_id {scheduler_trace_id}] Error running snapshot schedule {org_id}/{schedule_id}: {e}"
+            );
+            if trigger.retries + 1 >= max_retries {
+                db::scheduler::update_trigger(new_trigger).await?;
+            } else {
+                db::scheduler::update_status(
+                    &trigger.org,
+                    trigger.module.clone(),
+                    &trigger.module_key,
+                    db::scheduler::TriggerStatus::Waiting,
+                    trigger.retries + 1,
+                    None,
+                )
+                .await?;
+            }
+            trigger_data_stream.status = TriggerDataStatus::Failed;
+            trigger_data_stream.error = Some(format!("error running dashboard snapshot: {e}"));
+        }
+    }
+    trigger_data_stream.evaluation_took_in_secs = Some(evaluation_took.elapsed().as_secs_f64());
+    trigger_data_stream.end_time = now_micros();
+    publish_triggers_usage(trigger_data_stream).await;
+
+    Ok(())
+}
+
+async fn handle_escalation_triggers(
+    trace_id: &str,
+    trigger: db::scheduler::Trigger,
+) -> Result<(), anyhow::Error> {
+    let scheduler_trace_id = format!("{}/{}", trace_id, ider::generate_trace_id());
+    let (_, max_retries) = get_scheduler_max_retries();
+    // For escalations, trigger.module_key is the alert id
+    log::debug!(
+        "[SCHEDULER trace_id {scheduler_trace_id}] Inside handle_escalation_triggers, org: {}, alert: {}",
+        &trigger.org,
+        &trigger.module_key
+    );
+
+    let now = now_micros();
+    let mut trigger_data_stream = TriggerData {
+        _timestamp: now,
+        org: trigger.org.clone(),
+        module: TriggerDataType::Escalation,
+        key: trigger.module_key.clone(),
+        next_run_at: trigger.next_run_at,
+        is_realtime: false,
+        is_silenced: false,
+        status: TriggerDataStatus::Completed,
+        start_time: trigger.start_time.unwrap_or_default(),
+        end_time: trigger.end_time.unwrap_or_default(),
+        retries: trigger.retries,
+        error: None,
+        success_response: None,
+        is_partial: None,
+        delay_in_secs: Some(Duration::microseconds(now - trigger.next_run_at).num_seconds()),
+        evaluation_took_in_secs: None,
+        source_node: Some(LOCAL_NODE.name.clone()),
+        query_took: None,
+        scheduler_trace_id: Some(scheduler_trace_id.clone()),
+        time_in_queue_ms: Some(
+            Duration::microseconds(now - trigger.start_time.unwrap_or_default()).num_milliseconds(),
+        ),
+        correlation: None,
+    };
+
+    let evaluation_took = Instant::now();
+    if let Err(e) = escalation::run(trigger.clone()).await {
+        log::error!(
+            "[SCHEDULER trace_id {scheduler_trace_id}] Error running the escalation of alert {}/{}: {e}",
+            &trigger.org,
+            &trigger.module_key
+        );
+        if trigger.retries + 1 >= max_retries {
+            db::scheduler::delete(&trigger.org, trigger.module.clone(), &trigger.module_key)
+                .await?;
+        } else {
+            db::scheduler::update_status(
+                &trigger.org,
+                trigger.module.clone(),
+                &trigger.module_key,
+                db::scheduler::TriggerStatus::Waiting,
+                trigger.retries + 1,
+                None,
+            )
+            .await?;
+        }
+        trigger_data_stream.status = TriggerDataStatus::Failed;
+        trigger_data_stream.error = Some(format!("error running escalation: {e}"));
+    }
+    trigger_data_stream.evaluation_took_in_secs = Some(evaluation_took.elapsed().as_secs_f64());
+    trigger_data_stream.end_time = now_micros();
+    publish_triggers_usage(trigger_data_stream).await;
+
+    Ok(())
+}
+
 async fn handle_derived_stream_triggers(
     trace_id: &str,
     trigger: db::scheduler::Trigger,
@@ -899,6 +1256,7 @@ async fn handle_derived_stream_triggers(
             query_took: None,
             scheduler_trace_id: Some(scheduler_trace_id.clone()),
             time_in_queue_ms: Some(time_in_queue),
+            correlation: None,
         };
 
         log::error!("[SCHEDULER trace_id {scheduler_trace_id}] {}", err_msg);
@@ -943,6 +1301,7 @@ async fn handle_derived_stream_triggers(
             query_took: None,
             scheduler_trace_id: Some(scheduler_trace_id.clone()),
             time_in_queue_ms: Some(time_in_queue),
+            correlation: None,
         };
         log::info!("[SCHEDULER trace_id {scheduler_trace_id}] {}", msg);
         new_trigger_data.reset();
@@ -982,6 +1341,7 @@ async fn handle_derived_stream_triggers(
             query_took: None,
             scheduler_trace_id: Some(scheduler_trace_id.clone()),
             time_in_queue_ms: Some(time_in_queue),
+            correlation: None,
         };
         log::error!("[SCHEDULER trace_id {scheduler_trace_id}] {}", err_msg);
         new_trigger_data.reset();
@@ -1121,6 +1481,7 @@ async fn handle_derived_stream_triggers(
         query_took: None,
         scheduler_trace_id: Some(scheduler_trace_id.clone()),
         time_in_queue_ms: Some(time_in_queue),
+        correlation: None,
     };
 
     // evaluate trigger and configure trigger next run time
@@ -1332,6 +1693,9 @@ async fn handle_derived_stream_triggers(
             period_end_time: Some(start_time),
             tolerance: 0,
             last_satisfied_at: None,
+            firing: None,
+            pending_since: None,
+            stats: None,
         })
         .unwrap();
     }
@@ -0,0 +1,155 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Silences mute the notifications of matching alerts during a time window. The alerts are still
+//! evaluated and their trigger history is recorded, only the notification is dropped and recorded
+//! against the silence which muted it.
+
+use config::{
+    ider,
+    meta::alerts::{
+        alert::Alert,
+        silences::{Silence, SilencedNotification},
+    },
+    utils::{
+        json::{Map, Value},
+        time::now_micros,
+    },
+};
+use infra::table;
+
+/// Number of muted notifications returned for a silence.
+const SILENCED_NOTIFICATIONS_LIMIT: u64 = 1000;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SilenceError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Silence not found")]
+    NotFound,
+
+    #[error("Invalid silence: {0}")]
+    Invalid(String),
+}
+
+pub async fn list(org_id: &str, include_expired: bool) -> Result<Vec<Silence>, SilenceError> {
+    Ok(table::alert_silences::list(org_id, now_micros(), include_expired).await?)
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<Silence, SilenceError> {
+    table::alert_silences::get(org_id, id)
+        .await?
+        .ok_or(SilenceError::NotFound)
+}
+
+pub async fn create(
+    org_id: &str,
+    created_by: &str,
+    mut silence: Silence,
+) -> Result<Silence, SilenceError> {
+    let now = now_micros();
+    silence.id = ider::uuid();
+    silence.created_by = created_by.to_string();
+    silence.created_at = now;
+    silence.updated_at = now;
+    save(org_id, silence).await
+}
+
+/// Updates the matchers, time window and comment of the silence, e.g. to expire it early.
+pub async fn update(org_id: &str, id: &str, silence: Silence) -> Result<Silence, SilenceError> {
+    let existing = get(org_id, id).await?;
+    let silence = Silence {
+        id: existing.id,
+        created_by: existing.created_by,
+        created_at: existing.created_at,
+        updated_at: now_micros(),
+        ..silence
+    };
+    save(org_id, silence).await
+}
+
+async fn save(org_id: &str, silence: Silence) -> Result<Silence, SilenceError> {
+    silence.validate().map_err(SilenceError::Invalid)?;
+    table::alert_silences::put(org_id, &silence).await?;
+    Ok(silence)
+}
+
+/// Deletes the silence along with the record of the notifications it muted.
+pub async fn delete(org_id: &str, id: &str) -> Result<(), SilenceError> {
+    if !table::alert_silences::delete(org_id, id).await? {
+        return Err(SilenceError::NotFound);
+    }
+    Ok(())
+}
+
+pub async fn list_silenced_notifications(
+    org_id: &str,
+    id: &str,
+) -> Result<Vec<SilencedNotification>, SilenceError> {
+    get(org_id, id).await?;
+    Ok(
+        table::alert_silences::list_silenced_notifications(
+            org_id,
+            id,
+            SILENCED_NOTIFICATIONS_LIMIT,
+        )
+        .await?,
+    )
+}
+
+/// Checks if the notification of the alert for the given rows is muted by an active silence of
+/// the org. When it is, the muted notification is recorded and the id of the silence returned.
+/// Failing to load the silences never mutes a notification.
+pub async fn mute(alert: &Alert, rows: &[Map<String, Value>]) -> Option<String> {
+    let now = now_micros();
+    let silences = match table::alert_silences::list_active(&alert.org_id, now).await {
+        Ok(silences) => silences,
+        Err(e) => {
+            log::error!(
+                "[ALERT] failed to get the silences of org {}: {e}",
+                alert.org_id
+            );
+            return None;
+        }
+    };
+    let silence = silences
+        .into_iter()
+        .find(|s| s.matches(now, |label| alert.label_value(rows, label)))?;
+    log::info!(
+        "[ALERT] notification of alert {}/{} muted by silence {}",
+        alert.org_id,
+        alert.name,
+        silence.id
+    );
+    let notification = SilencedNotification {
+        silence_id: silence.id.clone(),
+        alert_id: alert.get_unique_key(),
+        alert_name: alert.name.clone(),
+        rows: rows.len() as i64,
+        silenced_at: now,
+    };
+    if let Err(e) =
+        table::alert_silences::add_silenced_notification(&alert.org_id, &notification).await
+    {
+        log::error!(
+            "[ALERT] failed to record the notification of alert {}/{} muted by silence {}: {e}",
+            alert.org_id,
+            alert.name,
+            silence.id
+        );
+    }
+    Some(silence.id)
+}
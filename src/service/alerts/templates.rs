@@ -13,14 +13,24 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use config::meta::destinations::{Template, TemplateType};
+use config::{
+    TIMESTAMP_COL_NAME,
+    meta::{
+        alerts::alert::Alert,
+        destinations::{DestinationType, Email, Endpoint, HTTPType, Template, TemplateType},
+    },
+    utils::json::{self, Map, Value},
+};
 
 use crate::{
     common::{
         meta::{authz::Authz, organization::DEFAULT_ORG},
         utils::auth::{is_ofga_unsupported, remove_ownership, set_ownership},
     },
-    service::db::{self, alerts::templates::TemplateError},
+    service::{
+        alerts::{alert::render_notification, rendering},
+        db::{self, alerts::templates::TemplateError},
+    },
 };
 
 pub async fn save(
@@ -47,6 +57,7 @@ pub async fn save(
             return Err(TemplateError::EmptyTitle);
         }
     }
+    validate(&template)?;
 
     match db::alerts::templates::get(&template.org_id, &template.name).await {
         Ok(existing) => {
@@ -75,6 +86,79 @@ pub async fn get(org_id: &str, name: &str) -> Result<Template, TemplateError> {
     db::alerts::templates::get(org_id, name).await
 }
 
+/// Checks the syntax of the body and the email title of templates using jinja syntax.
+fn validate(template: &Template) -> Result<(), TemplateError> {
+    rendering::validate(&template.body).map_err(TemplateError::InvalidTemplate)?;
+    if let TemplateType::Email { title } = &template.template_type {
+        rendering::validate(title).map_err(TemplateError::InvalidTemplate)?;
+    }
+    Ok(())
+}
+
+/// Renders the template the way a notification of the alert matching the rows would be, returning
+/// the email subject and the message. Sample rows are used when none are given.
+pub async fn preview(
+    template: &Template,
+    alert: &Alert,
+    rows: Vec<Map<String, Value>>,
+) -> Result<(String, String), TemplateError> {
+    if template.body.is_empty() {
+        return Err(TemplateError::EmptyBody);
+    }
+    validate(template)?;
+    rendering::validate(&alert.row_template).map_err(TemplateError::InvalidTemplate)?;
+
+    let now = chrono::Utc::now().timestamp_micros();
+    let rows = if rows.is_empty() {
+        sample_rows(now)
+    } else {
+        rows
+    };
+    let dest_type = match template.template_type {
+        TemplateType::Email { .. } => DestinationType::Email(Email { recipients: vec![] }),
+        TemplateType::Http | TemplateType::Sns => DestinationType::Http(Endpoint {
+            url: String::new(),
+            method: HTTPType::default(),
+            skip_tls_verify: false,
+            headers: None,
+            action_id: None,
+            output_format: None,
+        }),
+    };
+    Ok(render_notification(alert, &dest_type, template, &rows, now, None, now).await)
+}
+
+fn sample_rows(now: i64) -> Vec<Map<String, Value>> {
+    [
+        (
+            "error",
+            "payment-service",
+            "upstream connect error",
+            503,
+            12,
+        ),
+        ("error", "checkout-service", "request timed out", 504, 7),
+        ("warn", "payment-service", "retrying request", 429, 3),
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(i, (level, service, message, status, count))| {
+        let row = json::json!({
+            TIMESTAMP_COL_NAME: now - i as i64 * 60_000_000,
+            "level": level,
+            "service_name": service,
+            "message": message,
+            "status": status,
+            "count": count,
+        });
+        match row {
+            Value::Object(row) => row,
+            _ => unreachable!(),
+        }
+    })
+    .collect()
+}
+
 pub async fn list(
     org_id: &str,
     permitted: Option<Vec<String>>,
@@ -0,0 +1,142 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Splunk On-Call (VictorOps) REST endpoint destinations, the incidents are recovered when the
+//! alert recovers.
+
+use chrono::Utc;
+use config::{
+    meta::{
+        alerts::alert::Alert,
+        destinations::{VictorOps, VictorOpsMessageType},
+    },
+    utils::json::{self, Map, Value},
+};
+
+use crate::service::alerts::incidents::{alert_severity, send_event, summary};
+
+const DEFAULT_REST_URL: &str = "https://alert.victorops.com/integrations/generic/20131114/alert";
+
+fn alert_event(
+    alert: &Alert,
+    victorops: &VictorOps,
+    entity_id: &str,
+    group_key: &str,
+    rows: &[Map<String, Value>],
+    msg: &str,
+    timestamp: i64,
+) -> Value {
+    let message_type = alert_severity(alert, rows)
+        .and_then(|s| VictorOpsMessageType::from_alert_severity(&s))
+        .unwrap_or(victorops.message_type);
+    let mut event = json::json!({
+        "message_type": message_type,
+        "entity_id": entity_id,
+        "entity_display_name": summary(alert, msg),
+        "state_message": msg,
+        "state_start_time": timestamp,
+        "monitoring_tool": "OpenObserve",
+        "alert_name": alert.name,
+        "org": alert.org_id,
+        "stream": format!("{}/{}", alert.stream_type, alert.stream_name),
+    });
+    if !group_key.is_empty() {
+        event["group"] = Value::String(group_key.to_string());
+    }
+    event
+}
+
+fn recovery_event(alert: &Alert, entity_id: &str) -> Value {
+    json::json!({
+        "message_type": "RECOVERY",
+        "entity_id": entity_id,
+        "state_message": format!("Alert {} recovered", alert.name),
+        "monitoring_tool": "OpenObserve",
+    })
+}
+
+/// The keys are part of the url, which authenticates the requests.
+fn rest_url(victorops: &VictorOps) -> String {
+    let url = if victorops.url.is_empty() {
+        DEFAULT_REST_URL
+    } else {
+        victorops.url.trim_end_matches('/')
+    };
+    format!("{url}/{}/{}", victorops.api_key, victorops.routing_key)
+}
+
+pub(super) async fn trigger(
+    alert: &Alert,
+    victorops: &VictorOps,
+    entity_id: &str,
+    group_key: &str,
+    rows: &[Map<String, Value>],
+    msg: &str,
+) -> Result<String, anyhow::Error> {
+    let event = alert_event(
+        alert,
+        victorops,
+        entity_id,
+        group_key,
+        rows,
+        msg,
+        Utc::now().timestamp(),
+    );
+    send_event(&rest_url(victorops), None, &event).await
+}
+
+pub(super) async fn recover(
+    alert: &Alert,
+    victorops: &VictorOps,
+    entity_id: &str,
+) -> Result<String, anyhow::Error> {
+    send_event(
+        &rest_url(victorops),
+        None,
+        &recovery_event(alert, entity_id),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alert_event() {
+        let alert = Alert {
+            name: "db_errors".to_string(),
+            stream_name: "k8s_logs".to_string(),
+            ..Default::default()
+        };
+        let victorops = VictorOps {
+            api_key: "K3Y".to_string(),
+            routing_key: "ops".to_string(),
+            message_type: VictorOpsMessageType::Warning,
+            auto_close: true,
+            url: String::new(),
+        };
+        assert_eq!(rest_url(&victorops), format!("{DEFAULT_REST_URL}/K3Y/ops"));
+
+        let event = alert_event(&alert, &victorops, "id-1", "", &[], "db is down", 1);
+        assert_eq!(event["message_type"], "WARNING");
+        assert_eq!(event["entity_id"], "id-1");
+        assert_eq!(event["entity_display_name"], "db is down");
+
+        let event = recovery_event(&alert, "id-1");
+        assert_eq!(event["message_type"], "RECOVERY");
+        assert_eq!(event["entity_id"], "id-1");
+    }
+}
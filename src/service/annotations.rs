@@ -0,0 +1,105 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use chrono::Utc;
+use config::{
+    ider,
+    meta::annotations::{Annotation, AnnotationWebhookEvent, ListAnnotationsQuery},
+};
+
+use crate::service::db;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AnnotationError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Annotation not found")]
+    NotFound,
+
+    #[error("Invalid annotation: {0}")]
+    InvalidAnnotation(String),
+}
+
+/// Returns the annotations overlapping the requested time range, ordered by start time.
+pub async fn list(
+    org_id: &str,
+    query: &ListAnnotationsQuery,
+) -> Result<Vec<Annotation>, AnnotationError> {
+    query
+        .validate()
+        .map_err(AnnotationError::InvalidAnnotation)?;
+    let tags = query.get_tags();
+    let mut list = db::annotations::list(org_id)
+        .await?
+        .into_iter()
+        .filter(|a| a.overlaps(query.start_time, query.end_time) && a.has_tags(&tags))
+        .collect::<Vec<_>>();
+    list.sort_by(|a, b| a.start_time.cmp(&b.start_time).then(a.id.cmp(&b.id)));
+    Ok(list)
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<Annotation, AnnotationError> {
+    db::annotations::get(org_id, id)
+        .await
+        .map_err(|_| AnnotationError::NotFound)
+}
+
+pub async fn create(
+    org_id: &str,
+    mut annotation: Annotation,
+    source: &str,
+) -> Result<Annotation, AnnotationError> {
+    annotation
+        .validate()
+        .map_err(AnnotationError::InvalidAnnotation)?;
+    let now = Utc::now().timestamp_micros();
+    annotation.id = ider::generate();
+    annotation.source = source.to_string();
+    annotation.created_at = now;
+    annotation.updated_at = now;
+    db::annotations::set(org_id, &annotation).await?;
+    Ok(annotation)
+}
+
+/// Creates an annotation from a CI/CD event received by the webhook.
+pub async fn create_from_webhook(
+    org_id: &str,
+    event: AnnotationWebhookEvent,
+) -> Result<Annotation, AnnotationError> {
+    let annotation = event.into_annotation(Utc::now().timestamp_micros());
+    create(org_id, annotation, "webhook").await
+}
+
+pub async fn update(
+    org_id: &str,
+    id: &str,
+    mut annotation: Annotation,
+) -> Result<Annotation, AnnotationError> {
+    let existing = get(org_id, id).await?;
+    annotation
+        .validate()
+        .map_err(AnnotationError::InvalidAnnotation)?;
+    annotation.id = existing.id;
+    annotation.source = existing.source;
+    annotation.created_at = existing.created_at;
+    annotation.updated_at = Utc::now().timestamp_micros();
+    db::annotations::set(org_id, &annotation).await?;
+    Ok(annotation)
+}
+
+pub async fn delete(org_id: &str, id: &str) -> Result<(), AnnotationError> {
+    get(org_id, id).await?;
+    Ok(db::annotations::delete(org_id, id).await?)
+}
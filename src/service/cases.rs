@@ -0,0 +1,365 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use chrono::Utc;
+use config::{
+    ider,
+    meta::cases::{
+        Case, CaseAttachment, CaseComment, CaseEvent, CaseStatus, CaseTimelineEntry,
+        CreateCaseRequest, UpdateCaseRequest,
+    },
+};
+
+use crate::service::db;
+
+/// Errors that can occur when managing cases.
+#[derive(Debug, thiserror::Error)]
+pub enum CaseError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Case not found")]
+    NotFound,
+
+    #[error("Attachment not found")]
+    AttachmentNotFound,
+
+    #[error("Case title cannot be empty")]
+    MissingTitle,
+
+    #[error("Comment cannot be empty")]
+    EmptyComment,
+
+    #[error("Closed cases cannot be changed, reopen the case first")]
+    Closed,
+
+    #[error("Timeline export error: {0}")]
+    ExportError(String),
+}
+
+pub async fn create(org_id: &str, user: &str, req: CreateCaseRequest) -> Result<Case, CaseError> {
+    if req.title.trim().is_empty() {
+        return Err(CaseError::MissingTitle);
+    }
+    let now = Utc::now().timestamp_micros();
+    let mut case = Case {
+        id: ider::uuid(),
+        org_id: org_id.to_string(),
+        title: req.title,
+        description: req.description,
+        status: CaseStatus::Open,
+        severity: req.severity,
+        assignee: req.assignee.filter(|a| !a.is_empty()),
+        created_by: user.to_string(),
+        created_at: now,
+        updated_at: now,
+        ..Default::default()
+    };
+    let message = format!("created case {}", case.title);
+    add_event(&mut case, user, "created", message);
+    if let Some(assignee) = case.assignee.clone() {
+        add_event(
+            &mut case,
+            user,
+            "assigned",
+            format!("assigned to {assignee}"),
+        );
+    }
+    db::cases::set(&case).await?;
+    Ok(case)
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<Case, CaseError> {
+    db::cases::get(org_id, id)
+        .await
+        .map_err(|_| CaseError::NotFound)
+}
+
+/// Lists the cases of the org, newest first, optionally filtered by status and assignee.
+pub async fn list(
+    org_id: &str,
+    status: Option<CaseStatus>,
+    assignee: Option<&str>,
+) -> Result<Vec<Case>, CaseError> {
+    let list = db::cases::list(org_id).await?;
+    Ok(list
+        .into_iter()
+        .filter(|c| status.is_none_or(|s| c.status == s))
+        .filter(|c| assignee.is_none_or(|a| c.assignee.as_deref() == Some(a)))
+        .collect())
+}
+
+pub async fn delete(org_id: &str, id: &str) -> Result<(), CaseError> {
+    get(org_id, id).await?;
+    Ok(db::cases::delete(org_id, id).await?)
+}
+
+pub async fn update(
+    org_id: &str,
+    id: &str,
+    user: &str,
+    req: UpdateCaseRequest,
+) -> Result<Case, CaseError> {
+    let mut case = get(org_id, id).await?;
+    // a closed case can only be reopened
+    if case.status == CaseStatus::Closed && req.status.is_none_or(|s| s == CaseStatus::Closed) {
+        return Err(CaseError::Closed);
+    }
+
+    if let Some(title) = req.title {
+        if title.trim().is_empty() {
+            return Err(CaseError::MissingTitle);
+        }
+        if title != case.title {
+            add_event(
+                &mut case,
+                user,
+                "edited",
+                format!("renamed case to {title}"),
+            );
+            case.title = title;
+        }
+    }
+    if let Some(description) = req.description {
+        if description != case.description {
+            add_event(&mut case, user, "edited", "changed description".to_string());
+            case.description = description;
+        }
+    }
+    if let Some(severity) = req.severity {
+        if severity != case.severity {
+            add_event(
+                &mut case,
+                user,
+                "severity",
+                format!("changed severity to {severity}"),
+            );
+            case.severity = severity;
+        }
+    }
+    if let Some(assignee) = req.assignee {
+        let assignee = (!assignee.is_empty()).then_some(assignee);
+        if assignee != case.assignee {
+            let message = match assignee.as_ref() {
+                Some(a) => format!("assigned to {a}"),
+                None => "removed assignee".to_string(),
+            };
+            add_event(&mut case, user, "assigned", message);
+            case.assignee = assignee;
+        }
+    }
+    if let Some(status) = req.status {
+        if status != case.status {
+            add_event(
+                &mut case,
+                user,
+                "status",
+                format!("changed status to {status}"),
+            );
+            case.status = status;
+        }
+    }
+
+    case.updated_at = Utc::now().timestamp_micros();
+    db::cases::set(&case).await?;
+    Ok(case)
+}
+
+pub async fn add_attachment(
+    org_id: &str,
+    id: &str,
+    user: &str,
+    mut attachment: CaseAttachment,
+) -> Result<Case, CaseError> {
+    let mut case = get_open(org_id, id).await?;
+    let now = Utc::now().timestamp_micros();
+    attachment.id = ider::uuid();
+    attachment.added_by = user.to_string();
+    attachment.added_at = now;
+    case.attachments.push(attachment);
+    case.updated_at = now;
+    db::cases::set(&case).await?;
+    Ok(case)
+}
+
+pub async fn remove_attachment(
+    org_id: &str,
+    id: &str,
+    user: &str,
+    attachment_id: &str,
+) -> Result<Case, CaseError> {
+    let mut case = get_open(org_id, id).await?;
+    let Some(pos) = case.attachments.iter().position(|a| a.id == attachment_id) else {
+        return Err(CaseError::AttachmentNotFound);
+    };
+    let attachment = case.attachments.remove(pos);
+    add_event(
+        &mut case,
+        user,
+        "detached",
+        format!("removed {} {}", attachment.kind, attachment.title),
+    );
+    case.updated_at = Utc::now().timestamp_micros();
+    db::cases::set(&case).await?;
+    Ok(case)
+}
+
+pub async fn add_comment(
+    org_id: &str,
+    id: &str,
+    user: &str,
+    text: String,
+) -> Result<Case, CaseError> {
+    if text.trim().is_empty() {
+        return Err(CaseError::EmptyComment);
+    }
+    let mut case = get_open(org_id, id).await?;
+    let now = Utc::now().timestamp_micros();
+    case.comments.push(CaseComment {
+        id: ider::uuid(),
+        author: user.to_string(),
+        text,
+        created_at: now,
+    });
+    case.updated_at = now;
+    db::cases::set(&case).await?;
+    Ok(case)
+}
+
+async fn get_open(org_id: &str, id: &str) -> Result<Case, CaseError> {
+    let case = get(org_id, id).await?;
+    if case.status == CaseStatus::Closed {
+        return Err(CaseError::Closed);
+    }
+    Ok(case)
+}
+
+fn add_event(case: &mut Case, actor: &str, action: &str, message: String) {
+    case.events.push(CaseEvent {
+        timestamp: Utc::now().timestamp_micros(),
+        actor: actor.to_string(),
+        action: action.to_string(),
+        message,
+    });
+}
+
+/// Merges the activity log, the attachments and the comments of the case into one timeline,
+/// ordered by time.
+pub fn timeline(case: &Case) -> Vec<CaseTimelineEntry> {
+    let mut entries =
+        Vec::with_capacity(case.events.len() + case.attachments.len() + case.comments.len());
+    entries.extend(case.events.iter().map(|e| CaseTimelineEntry {
+        timestamp: e.timestamp,
+        actor: e.actor.clone(),
+        action: e.action.clone(),
+        message: e.message.clone(),
+        data: None,
+    }));
+    entries.extend(case.attachments.iter().map(|a| CaseTimelineEntry {
+        timestamp: a.added_at,
+        actor: a.added_by.clone(),
+        action: "attached".to_string(),
+        message: format!("attached {} {}", a.kind, a.title),
+        data: Some(a.data.clone()),
+    }));
+    entries.extend(case.comments.iter().map(|c| CaseTimelineEntry {
+        timestamp: c.created_at,
+        actor: c.author.clone(),
+        action: "commented".to_string(),
+        message: c.text.clone(),
+        data: None,
+    }));
+    entries.sort_by_key(|e| e.timestamp);
+    entries
+}
+
+/// Renders the timeline as CSV with RFC 3339 timestamps, attachment data is left out.
+pub fn timeline_to_csv(entries: &[CaseTimelineEntry]) -> Result<String, CaseError> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record(["time", "actor", "action", "message"])
+        .map_err(|e| CaseError::ExportError(e.to_string()))?;
+    for e in entries {
+        let time = chrono::DateTime::from_timestamp_micros(e.timestamp)
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_default();
+        wtr.write_record([time.as_str(), &e.actor, &e.action, &e.message])
+            .map_err(|e| CaseError::ExportError(e.to_string()))?;
+    }
+    let data = wtr
+        .into_inner()
+        .map_err(|e| CaseError::ExportError(e.to_string()))?;
+    String::from_utf8(data).map_err(|e| CaseError::ExportError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use config::{meta::cases::AttachmentKind, utils::json};
+
+    use super::*;
+
+    #[test]
+    fn test_timeline() {
+        let mut case = Case {
+            id: "1".to_string(),
+            org_id: "default".to_string(),
+            title: "Brute force on vpn".to_string(),
+            ..Default::default()
+        };
+        case.events.push(CaseEvent {
+            timestamp: 1,
+            actor: "a@example.com".to_string(),
+            action: "created".to_string(),
+            message: "created case Brute force on vpn".to_string(),
+        });
+        case.events.push(CaseEvent {
+            timestamp: 4,
+            actor: "b@example.com".to_string(),
+            action: "status".to_string(),
+            message: "changed status to closed".to_string(),
+        });
+        case.comments.push(CaseComment {
+            id: "c".to_string(),
+            author: "b@example.com".to_string(),
+            text: "blocked the source, \"done\"".to_string(),
+            created_at: 3,
+        });
+        case.attachments.push(CaseAttachment {
+            id: "a".to_string(),
+            kind: AttachmentKind::Query,
+            title: "failed logins".to_string(),
+            data: json::json!({"sql": "SELECT * FROM vpn"}),
+            added_by: "a@example.com".to_string(),
+            added_at: 2,
+        });
+
+        let entries = timeline(&case);
+        let actions = entries
+            .iter()
+            .map(|e| e.action.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(actions, vec!["created", "attached", "commented", "status"]);
+        assert_eq!(entries[1].message, "attached query failed logins");
+        assert!(entries[1].data.is_some());
+
+        let csv = timeline_to_csv(&entries).unwrap();
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "time,actor,action,message");
+        assert_eq!(
+            lines[3],
+            "1970-01-01T00:00:00.000003+00:00,b@example.com,commented,\"blocked the source, \"\"done\"\"\""
+        );
+    }
+}
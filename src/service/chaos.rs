@@ -0,0 +1,72 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::{
+    get_config,
+    meta::chaos::{ChaosScenario, ChaosTarget},
+};
+
+use crate::service::db;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ChaosError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Fault injection is disabled, set ZO_CHAOS_ENABLED=true to enable it")]
+    Disabled,
+
+    #[error("Chaos scenario not found")]
+    NotFound,
+
+    #[error("Invalid chaos scenario: {0}")]
+    InvalidScenario(String),
+}
+
+fn check_enabled() -> Result<(), ChaosError> {
+    if get_config().common.chaos_enabled {
+        Ok(())
+    } else {
+        Err(ChaosError::Disabled)
+    }
+}
+
+pub async fn list() -> Result<Vec<ChaosScenario>, ChaosError> {
+    check_enabled()?;
+    Ok(db::chaos::list().await?)
+}
+
+/// Activates the scenario on all the nodes, replacing the previous scenario of the target.
+pub async fn set(
+    target: ChaosTarget,
+    mut scenario: ChaosScenario,
+) -> Result<ChaosScenario, ChaosError> {
+    check_enabled()?;
+    scenario.target = target;
+    scenario.validate().map_err(ChaosError::InvalidScenario)?;
+    db::chaos::set(&scenario).await?;
+    infra::chaos::set_scenario(scenario.clone());
+    Ok(scenario)
+}
+
+pub async fn delete(target: ChaosTarget) -> Result<(), ChaosError> {
+    check_enabled()?;
+    if !db::chaos::list().await?.iter().any(|s| s.target == target) {
+        return Err(ChaosError::NotFound);
+    }
+    db::chaos::delete(target).await?;
+    infra::chaos::remove_scenario(target);
+    Ok(())
+}
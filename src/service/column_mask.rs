@@ -0,0 +1,92 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use chrono::Utc;
+use config::{
+    TIMESTAMP_COL_NAME,
+    meta::{column_mask::ColumnMaskPolicy, stream::StreamType, user::UserRole},
+};
+use hashbrown::HashSet;
+
+use crate::service::db;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ColumnMaskError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Column mask not found")]
+    NotFound,
+
+    #[error("Invalid column mask: {0}")]
+    InvalidPolicy(String),
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<ColumnMaskPolicy>, ColumnMaskError> {
+    Ok(db::column_mask::list(org_id).await?)
+}
+
+pub async fn save(
+    org_id: &str,
+    mut policy: ColumnMaskPolicy,
+) -> Result<ColumnMaskPolicy, ColumnMaskError> {
+    policy.stream_name = policy.stream_name.trim().to_string();
+    if policy.stream_name.is_empty() {
+        return Err(ColumnMaskError::InvalidPolicy(
+            "stream_name can't be empty".to_string(),
+        ));
+    }
+    if policy.columns.is_empty() {
+        return Err(ColumnMaskError::InvalidPolicy(
+            "at least one column is needed".to_string(),
+        ));
+    }
+    let mut fields = HashSet::with_capacity(policy.columns.len());
+    for column in policy.columns.iter_mut() {
+        column.field = column.field.trim().to_string();
+        if column.field.is_empty() {
+            return Err(ColumnMaskError::InvalidPolicy(
+                "field can't be empty".to_string(),
+            ));
+        }
+        // the time column is needed to partition and sort the queries
+        if column.field == TIMESTAMP_COL_NAME {
+            return Err(ColumnMaskError::InvalidPolicy(format!(
+                "{TIMESTAMP_COL_NAME} can't be masked"
+            )));
+        }
+        if !fields.insert(column.field.clone()) {
+            return Err(ColumnMaskError::InvalidPolicy(format!(
+                "field {} is masked twice",
+                column.field
+            )));
+        }
+    }
+    policy.updated_at = Utc::now().timestamp_micros();
+    db::column_mask::set(org_id, &policy).await?;
+    Ok(policy)
+}
+
+pub async fn delete(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    role: &UserRole,
+) -> Result<(), ColumnMaskError> {
+    db::column_mask::get(org_id, stream_type, stream_name, role)
+        .await
+        .map_err(|_| ColumnMaskError::NotFound)?;
+    Ok(db::column_mask::delete(org_id, stream_type, stream_name, role).await?)
+}
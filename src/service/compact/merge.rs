@@ -68,6 +68,7 @@ use crate::{
             DATAFUSION_RUNTIME,
             datafusion::exec::{self, MergeParquetResult},
         },
+        stream_hourly_stats,
     },
 };
 
@@ -657,6 +658,7 @@ pub async fn merge_by_stream(
                     tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
                     continue;
                 }
+                stream_hourly_stats::record_files(&events);
             }
             drop(permit);
             if let Some(e) = last_error {
@@ -1200,6 +1202,18 @@ async fn write_file_list(org_id: &str, events: &[FileKey]) -> Result<(), anyhow:
     }
 
     if success {
+        // keep the replaced files resolvable for the queries which listed them already
+        let retired = events
+            .iter()
+            .filter(|v| v.deleted)
+            .cloned()
+            .collect::<Vec<_>>();
+        if let Err(e) =
+            infra::table::file_list_retired::batch_add(org_id, created_at, &retired).await
+        {
+            log::error!("[COMPACTOR] add retired file list failed: {}", e);
+        }
+
         // send broadcast to other nodes
         if get_config().cache_latest_files.enabled {
             // get id for all the new files
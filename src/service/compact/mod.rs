@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use chrono::{Datelike, Duration, TimeZone, Timelike, Utc};
 use config::{
@@ -21,7 +21,7 @@ use config::{
     get_config,
     meta::{
         cluster::{CompactionJobType, Role},
-        stream::{ALL_STREAM_TYPES, PartitionTimeLevel, StreamType},
+        stream::{ALL_STREAM_TYPES, PartitionTimeLevel, StreamParams, StreamType},
     },
 };
 use infra::{
@@ -50,10 +50,11 @@ pub async fn run_retention() -> Result<(), anyhow::Error> {
     }
 
     let now = config::utils::time::now();
-    let data_lifecycle_end = now - Duration::try_days(cfg.compact.data_retention_days).unwrap();
 
     let orgs = db::schema::list_organizations_from_cache().await;
     for org_id in orgs {
+        // derived streams keep their data as long as their source asks for
+        let derived_retention = retention::derived_retention_days(&org_id, None).await;
         for stream_type in ALL_STREAM_TYPES {
             if stream_type == StreamType::EnrichmentTables {
                 continue; // skip data retention for enrichment tables
@@ -73,11 +74,15 @@ pub async fn run_retention() -> Result<(), anyhow::Error> {
                     infra::schema::get_settings(&org_id, &stream_name, stream_type)
                         .await
                         .unwrap_or_default();
-                let stream_data_retention_end = if stream_settings.data_retention > 0 {
-                    now - Duration::try_days(stream_settings.data_retention).unwrap()
-                } else {
-                    data_lifecycle_end
-                };
+                let stream_data_retention_end = now
+                    - Duration::try_days(retention::stream_retention_days(
+                        stream_settings.data_retention,
+                        cfg.compact.data_retention_days,
+                        derived_retention
+                            .get(&StreamParams::new(&org_id, &stream_name, stream_type))
+                            .copied(),
+                    ))
+                    .unwrap();
 
                 let extended_retention_days = &stream_settings.extended_retention_days;
                 // creates jobs to delete data
@@ -337,12 +342,12 @@ pub async fn run_merge(job_tx: mpsc::Sender<worker::MergeJob>) -> Result<(), any
     }
 
     let now = config::utils::time::now();
-    let data_lifecycle_end = now - Duration::try_days(cfg.compact.data_retention_days).unwrap();
 
     // check the stream, if the stream partition_time_level is daily or compact step secs less than
     // 1 hour, we only allow one compactor to working on it
     let mut need_release_ids = Vec::new();
     let mut need_done_ids = Vec::new();
+    let mut derived_retention: HashMap<String, HashMap<StreamParams, i64>> = HashMap::new();
     for job in jobs.iter() {
         let columns = job.stream.split('/').collect::<Vec<&str>>();
         assert_eq!(columns.len(), 3);
@@ -355,11 +360,19 @@ pub async fn run_merge(job_tx: mpsc::Sender<worker::MergeJob>) -> Result<(), any
         let partition_time_level =
             unwrap_partition_time_level(stream_settings.partition_time_level, stream_type);
         // to avoid compacting conflict with retention, need check the data retention time
-        let stream_data_retention_end = if stream_settings.data_retention > 0 {
-            now - Duration::try_days(stream_settings.data_retention).unwrap()
-        } else {
-            data_lifecycle_end
-        };
+        if !derived_retention.contains_key(&org_id) {
+            let days = retention::derived_retention_days(&org_id, None).await;
+            derived_retention.insert(org_id.clone(), days);
+        }
+        let stream_data_retention_end = now
+            - Duration::try_days(retention::stream_retention_days(
+                stream_settings.data_retention,
+                cfg.compact.data_retention_days,
+                derived_retention[&org_id]
+                    .get(&StreamParams::new(&org_id, &stream_name, stream_type))
+                    .copied(),
+            ))
+            .unwrap();
         if job.offsets <= stream_data_retention_end.timestamp_micros() {
             need_done_ids.push(job.id); // the data will be deleted by retention, just skip
             continue;
@@ -502,6 +515,11 @@ pub async fn run_delay_deletion() -> Result<(), anyhow::Error> {
             tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
         }
 
+        // the files are gone from storage, queries can't resolve them anymore
+        if let Err(e) = infra::table::file_list_retired::clean(&org_id, time_max).await {
+            log::error!("[COMPACTOR] clean retired file list error: {}", e);
+        }
+
         // update offset
         db::compact::organization::set_offset(
             &org_id,
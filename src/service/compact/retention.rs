@@ -19,7 +19,13 @@ use chrono::{DateTime, Duration, TimeZone, Utc};
 use config::{
     cluster::LOCAL_NODE,
     get_config, is_local_disk_storage,
-    meta::stream::{FileKey, FileListDeleted, PartitionTimeLevel, StreamType, TimeRange},
+    meta::{
+        pipeline::components::NodeData,
+        stream::{
+            FileKey, FileListDeleted, PartitionTimeLevel, StreamParams, StreamType, TimeRange,
+            UpdateStreamSettings,
+        },
+    },
     utils::time::{BASE_TIME, hour_micros},
 };
 use infra::{cache, dist_lock, file_list as infra_file_list};
@@ -27,7 +33,7 @@ use itertools::Itertools;
 
 use crate::{
     common::infra::cluster::get_node_by_uuid,
-    service::{db, file_list},
+    service::{db, file_list, format_stream_name},
 };
 
 /// This function will split the original time range based on the exclude range
@@ -563,12 +569,176 @@ fn generate_local_dirs(
     dirs_to_delete
 }
 
+/// Returns the retention days of a stream, its own or the default one, extended by the
+/// `derived_data_retention` of the stream it is derived from.
+pub fn stream_retention_days(
+    data_retention: i64,
+    default_retention: i64,
+    derived_retention: Option<i64>,
+) -> i64 {
+    let days = if data_retention > 0 {
+        data_retention
+    } else {
+        default_retention
+    };
+    derived_retention.map_or(days, |derived| days.max(derived))
+}
+
+/// Returns the streams derived from `source`, or from any stream of the org if `None`, with the
+/// retention days asked by the `derived_data_retention` of their source. Derived streams are the
+/// destination streams of pipelines and the metrics streams written by log metric rules.
+pub async fn derived_retention_days(
+    org_id: &str,
+    source: Option<&StreamParams>,
+) -> HashMap<StreamParams, i64> {
+    let mut derived = Vec::new();
+    match db::pipeline::list_by_org(org_id).await {
+        Ok(pipelines) => {
+            for pipeline in pipelines {
+                let src = pipeline.get_source_stream_params();
+                let src = StreamParams::new(org_id, &src.stream_name, src.stream_type);
+                derived.extend(pipeline.nodes.iter().filter_map(|node| match &node.data {
+                    NodeData::Stream(params) => Some((
+                        src.clone(),
+                        StreamParams::new(org_id, &params.stream_name, params.stream_type),
+                    )),
+                    _ => None,
+                }));
+            }
+        }
+        Err(e) => log::error!("[COMPACTOR] list pipelines of org {org_id} error: {e}"),
+    }
+    match db::log_metrics::list(org_id).await {
+        Ok(rules) => {
+            for rule in rules {
+                let src = StreamParams::new(org_id, &rule.stream, StreamType::Logs);
+                derived.extend(rule.metric_names().iter().map(|name| {
+                    (
+                        src.clone(),
+                        StreamParams::new(org_id, &format_stream_name(name), StreamType::Metrics),
+                    )
+                }));
+            }
+        }
+        Err(e) => log::error!("[COMPACTOR] list log metric rules of org {org_id} error: {e}"),
+    }
+
+    let mut source_days: HashMap<StreamParams, i64> = HashMap::new();
+    let mut days_by_stream: HashMap<StreamParams, i64> = HashMap::new();
+    for (src, target) in derived {
+        if src == target || source.is_some_and(|s| *s != src) {
+            continue;
+        }
+        let days = match source_days.get(&src) {
+            Some(days) => *days,
+            None => {
+                let days = infra::schema::get_settings(org_id, &src.stream_name, src.stream_type)
+                    .await
+                    .map(|s| s.derived_data_retention)
+                    .unwrap_or_default();
+                source_days.insert(src, days);
+                days
+            }
+        };
+        if days > 0 {
+            let entry = days_by_stream.entry(target).or_default();
+            *entry = (*entry).max(days);
+        }
+    }
+    days_by_stream
+}
+
+/// Sets the retention asked by a stream for its derived streams on the derived streams
+/// themselves, so the derived data outlives the deletion of the stream and of its pipelines.
+pub async fn pin_derived_retention(org_id: &str, stream_type: StreamType, stream_name: &str) {
+    let source = StreamParams::new(org_id, stream_name, stream_type);
+    let days_by_stream = derived_retention_days(org_id, Some(&source)).await;
+    pin_retention(org_id, days_by_stream).await;
+}
+
+/// Sets the retention asked by `source` for its derived data on `targets`, used before the
+/// pipeline or the rule deriving them from `source` is deleted.
+pub async fn pin_source_retention(org_id: &str, source: &StreamParams, targets: &[StreamParams]) {
+    let days = infra::schema::get_settings(org_id, &source.stream_name, source.stream_type)
+        .await
+        .map(|s| s.derived_data_retention)
+        .unwrap_or_default();
+    if days <= 0 {
+        return;
+    }
+    let days_by_stream = targets
+        .iter()
+        .filter(|target| *target != source)
+        .map(|target| {
+            (
+                StreamParams::new(org_id, &target.stream_name, target.stream_type),
+                days,
+            )
+        })
+        .collect();
+    pin_retention(org_id, days_by_stream).await;
+}
+
+async fn pin_retention(org_id: &str, days_by_stream: HashMap<StreamParams, i64>) {
+    let default_retention = get_config().compact.data_retention_days;
+    for (target, days) in days_by_stream {
+        let Some(settings) =
+            infra::schema::get_settings(org_id, &target.stream_name, target.stream_type).await
+        else {
+            continue; // nothing written yet
+        };
+        if stream_retention_days(settings.data_retention, default_retention, None) >= days {
+            continue;
+        }
+        let update = UpdateStreamSettings {
+            data_retention: Some(days),
+            ..Default::default()
+        };
+        match crate::service::stream::update_stream_settings(
+            org_id,
+            &target.stream_name,
+            target.stream_type,
+            update,
+        )
+        .await
+        {
+            Ok(resp) if resp.status().is_success() => log::info!(
+                "[COMPACTOR] retention of derived stream {}/{}/{} set to {days} days",
+                org_id,
+                target.stream_type,
+                target.stream_name
+            ),
+            Ok(resp) => log::error!(
+                "[COMPACTOR] set retention of derived stream {}/{}/{} failed: {}",
+                org_id,
+                target.stream_type,
+                target.stream_name,
+                resp.status()
+            ),
+            Err(e) => log::error!(
+                "[COMPACTOR] set retention of derived stream {}/{}/{} error: {e}",
+                org_id,
+                target.stream_type,
+                target.stream_name
+            ),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use itertools::Itertools;
 
     use super::*;
 
+    #[test]
+    fn test_stream_retention_days() {
+        assert_eq!(stream_retention_days(0, 30, None), 30);
+        assert_eq!(stream_retention_days(7, 30, None), 7);
+        assert_eq!(stream_retention_days(7, 30, Some(365)), 365);
+        assert_eq!(stream_retention_days(400, 30, Some(365)), 400);
+    }
+
     #[tokio::test]
     async fn test_delete_by_stream() {
         infra_file_list::create_table().await.unwrap();
@@ -0,0 +1,196 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Dashboard level permissions. The ACL of a dashboard restricts who can view and edit it on top
+//! of the permissions of its folder, so that a dashboard of a shared folder can be limited to a
+//! subgroup. The owner of the dashboard and the admins of the organization are not restricted.
+//! The UI profile of the user and the members of the workspace of the dashboard can further limit
+//! the dashboards they can open.
+
+use config::meta::{
+    dashboards::{
+        Dashboard,
+        acl::{DashboardAccess, DashboardAcl},
+        library_panels::library_panel_ids,
+    },
+    folder::Folder,
+    user::UserRole,
+    workspace::ResourceType,
+};
+use infra::table;
+
+use super::DashboardError;
+use crate::service::{db, ui_profile, workspaces};
+
+/// Gets the ACL of the dashboard.
+pub async fn get_acl(org_id: &str, dashboard_id: &str) -> Result<DashboardAcl, DashboardError> {
+    table::dashboards::get_acl(org_id, dashboard_id)
+        .await?
+        .ok_or(DashboardError::DashboardNotFound)
+}
+
+/// Replaces the ACL of the dashboard, the user needs to be able to edit the dashboard.
+pub async fn set_acl(
+    org_id: &str,
+    dashboard_id: &str,
+    user_id: &str,
+    mut acl: DashboardAcl,
+) -> Result<DashboardAcl, DashboardError> {
+    check(org_id, dashboard_id, user_id, DashboardAccess::Edit).await?;
+    for principals in [&mut acl.viewers, &mut acl.editors] {
+        principals.users.retain(|u| !u.trim().is_empty());
+        principals.roles.retain(|r| !r.trim().is_empty());
+        principals.users.sort();
+        principals.users.dedup();
+        principals.roles.sort();
+        principals.roles.dedup();
+    }
+    if !table::dashboards::set_acl(org_id, dashboard_id, &acl).await? {
+        return Err(DashboardError::DashboardNotFound);
+    }
+    Ok(acl)
+}
+
+/// Checks that the user has the access to the dashboard according to its ACL.
+pub async fn check(
+    org_id: &str,
+    dashboard_id: &str,
+    user_id: &str,
+    access: DashboardAccess,
+) -> Result<(), DashboardError> {
+    if !ui_profile::allows_dashboard(org_id, user_id, dashboard_id).await {
+        return Err(DashboardError::PermissionDenied);
+    }
+    let write = matches!(access, DashboardAccess::Edit);
+    if !workspaces::can_access(
+        org_id,
+        user_id,
+        ResourceType::Dashboard,
+        dashboard_id,
+        write,
+    )
+    .await?
+    {
+        return Err(DashboardError::PermissionDenied);
+    }
+    let acl = get_acl(org_id, dashboard_id).await?;
+    if acl.is_empty() {
+        return Ok(());
+    }
+    let Some(roles) = user_roles(org_id, user_id).await? else {
+        return Ok(());
+    };
+    let (_folder, dashboard) = table::dashboards::get_by_id(org_id, dashboard_id)
+        .await?
+        .ok_or(DashboardError::DashboardNotFound)?;
+    if permits(&acl, &dashboard, user_id, &roles, access) {
+        Ok(())
+    } else {
+        Err(DashboardError::PermissionDenied)
+    }
+}
+
+/// Checks that the user can edit the dashboards with an ACL referencing the library panel, as
+/// changing the library panel changes those dashboards.
+pub async fn check_library_panel(
+    org_id: &str,
+    library_panel_id: &str,
+    user_id: &str,
+) -> Result<(), DashboardError> {
+    let acls = table::dashboards::list_acls(org_id).await?;
+    for (dashboard_id, acl) in acls.iter() {
+        if acl.is_empty() {
+            continue;
+        }
+        let Some((_folder, dashboard)) = table::dashboards::get_by_id(org_id, dashboard_id).await?
+        else {
+            continue;
+        };
+        let references = dashboard.v6.as_ref().is_some_and(|inner| {
+            library_panel_ids(inner)
+                .iter()
+                .any(|id| id == library_panel_id)
+        });
+        if references {
+            check(org_id, dashboard_id, user_id, DashboardAccess::Edit).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Filters dashboards, returning only those that the user can view according to their ACLs.
+pub(super) async fn filter_viewable(
+    org_id: &str,
+    user_id: &str,
+    mut dashboards: Vec<(Folder, Dashboard)>,
+) -> Result<Vec<(Folder, Dashboard)>, DashboardError> {
+    if let Some(profile) = ui_profile::effective(org_id, user_id).await {
+        dashboards.retain(|(_folder, dashboard)| {
+            dashboard
+                .dashboard_id()
+                .is_some_and(|id| profile.allows_dashboard(id))
+        });
+    }
+    let acls = table::dashboards::list_acls(org_id).await?;
+    if acls.is_empty() {
+        return Ok(dashboards);
+    }
+    let Some(roles) = user_roles(org_id, user_id).await? else {
+        return Ok(dashboards);
+    };
+    let dashboards = dashboards
+        .into_iter()
+        .filter(|(_folder, dashboard)| {
+            dashboard
+                .dashboard_id()
+                .and_then(|id| acls.get(id))
+                .is_none_or(|acl| permits(acl, dashboard, user_id, &roles, DashboardAccess::View))
+        })
+        .collect();
+    Ok(dashboards)
+}
+
+fn permits(
+    acl: &DashboardAcl,
+    dashboard: &Dashboard,
+    user_id: &str,
+    roles: &[String],
+    access: DashboardAccess,
+) -> bool {
+    dashboard.owner() == Some(user_id) || acl.permits(user_id, roles, access)
+}
+
+/// Returns the roles the ACLs are checked against for the user, that is the role of the user
+/// in the organization and its custom roles. Returns `None` for the admins, who are not
+/// restricted by ACLs.
+async fn user_roles(org_id: &str, user_id: &str) -> Result<Option<Vec<String>>, DashboardError> {
+    let user = match db::user::get(Some(org_id), user_id).await {
+        Ok(Some(user)) => user,
+        _ => return Err(DashboardError::UserNotFound),
+    };
+    if matches!(user.role, UserRole::Root | UserRole::Admin) {
+        return Ok(None);
+    }
+
+    #[cfg(feature = "enterprise")]
+    let custom_roles = crate::service::users::get_user_roles(user_id, Some(org_id)).await;
+    #[cfg(not(feature = "enterprise"))]
+    let custom_roles: Vec<String> = vec![];
+
+    let roles = std::iter::once(user.role.to_string())
+        .chain(custom_roles)
+        .collect();
+    Ok(Some(roles))
+}
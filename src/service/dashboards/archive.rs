@@ -0,0 +1,233 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Bulk export and import of the dashboards of an organization together with their folders, as
+//! an NDJSON archive of [`ArchiveRecord`]s. The ids of the folders and dashboards are kept, so an
+//! archive can be used to migrate an organization or to keep dashboards in git.
+
+use config::meta::{
+    dashboards::{
+        ArchiveRecord, Dashboard, DashboardExport, DashboardImportSummary, ListDashboardsParams,
+        acl::DashboardAccess, convert, parse_archive,
+    },
+    folder::{Folder, FolderType},
+};
+use hashbrown::HashSet;
+use infra::table;
+#[cfg(feature = "enterprise")]
+use o2_enterprise::enterprise::common::config::get_config as get_o2_config;
+
+use super::{DashboardError, acl};
+use crate::{
+    common::{meta::authz::Authz, utils::auth::set_ownership},
+    service::folders::{self, FolderError},
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ArchiveError {
+    #[error("{0}")]
+    InvalidArchive(String),
+    #[error("{0}")]
+    InvalidVersion(String),
+    #[error(transparent)]
+    DashboardError(#[from] DashboardError),
+    #[error(transparent)]
+    FolderError(#[from] FolderError),
+}
+
+/// Exports the dashboard as the given version, downgrading it when the version is older than
+/// the one of the dashboard. The user needs to be able to view the dashboard.
+pub async fn export_dashboard(
+    org_id: &str,
+    dashboard_id: &str,
+    user_id: &str,
+    version: Option<i32>,
+) -> Result<DashboardExport, ArchiveError> {
+    acl::check(org_id, dashboard_id, user_id, DashboardAccess::View).await?;
+    let dashboard = super::get_dashboard(org_id, dashboard_id).await?;
+    let (dashboard, warnings) = match version {
+        Some(version) if version != dashboard.version => {
+            let downgrade =
+                convert::downgrade(&dashboard, version).map_err(ArchiveError::InvalidVersion)?;
+            (downgrade.dashboard, downgrade.warnings)
+        }
+        _ => (dashboard, vec![]),
+    };
+    let dashboard = convert::inner_json(&dashboard).ok_or_else(|| {
+        ArchiveError::InvalidVersion("dashboard has no data for its version".to_string())
+    })?;
+    Ok(DashboardExport {
+        dashboard,
+        warnings,
+    })
+}
+
+enum ImportOutcome {
+    Created,
+    Updated,
+    Skipped,
+}
+
+/// Exports the dashboards the user may read, with all the dashboard folders of the user, as an
+/// NDJSON archive.
+pub async fn export(org_id: &str, user_id: &str) -> Result<String, ArchiveError> {
+    let mut folders = folders::list_folders(org_id, Some(user_id), FolderType::Dashboards).await?;
+    let dashboards = super::list_dashboards(user_id, ListDashboardsParams::new(org_id)).await?;
+
+    // the folder of a dashboard may not be in the list when the dashboard was shared alone
+    let mut folder_ids = folders
+        .iter()
+        .map(|f| f.folder_id.clone())
+        .collect::<HashSet<_>>();
+    for (folder, _) in dashboards.iter() {
+        if folder_ids.insert(folder.folder_id.clone()) {
+            folders.push(folder.clone());
+        }
+    }
+
+    let records = folders
+        .into_iter()
+        .map(|folder| ArchiveRecord::Folder {
+            folder_id: folder.folder_id,
+            name: folder.name,
+            description: folder.description,
+        })
+        .chain(
+            dashboards
+                .into_iter()
+                .map(|(folder, dashboard)| ArchiveRecord::Dashboard {
+                    folder_id: folder.folder_id,
+                    dashboard: Box::new(dashboard),
+                }),
+        );
+    let mut archive = String::new();
+    for record in records {
+        archive.push_str(&config::utils::json::to_string(&record).map_err(|e| {
+            ArchiveError::InvalidArchive(format!("failed to serialize the archive: {e}"))
+        })?);
+        archive.push('\n');
+    }
+    Ok(archive)
+}
+
+/// Imports an NDJSON archive. Existing folders are kept, existing dashboards are overwritten, and
+/// moved to the folder of the archive, only when `overwrite` is set. A folder or dashboard which
+/// can't be imported is reported in the summary and doesn't stop the import.
+pub async fn import(
+    org_id: &str,
+    user_id: &str,
+    body: &str,
+    overwrite: bool,
+) -> Result<DashboardImportSummary, ArchiveError> {
+    let records = parse_archive(body).map_err(ArchiveError::InvalidArchive)?;
+    let mut summary = DashboardImportSummary::default();
+    for record in records {
+        match record {
+            ArchiveRecord::Folder {
+                folder_id,
+                name,
+                description,
+            } => {
+                let folder = Folder {
+                    folder_id: folder_id.clone(),
+                    name,
+                    description,
+                };
+                match folders::import_folder(org_id, folder, FolderType::Dashboards).await {
+                    Ok(Some(_)) => summary.folders_created.push(folder_id),
+                    Ok(None) => {}
+                    Err(e) => summary.errors.push(format!("folder {folder_id}: {e}")),
+                }
+            }
+            ArchiveRecord::Dashboard {
+                folder_id,
+                dashboard,
+            } => {
+                let dashboard_id = dashboard.dashboard_id().unwrap_or_default().to_string();
+                match import_dashboard(org_id, user_id, &folder_id, *dashboard, overwrite).await {
+                    Ok(ImportOutcome::Created) => summary.dashboards_created.push(dashboard_id),
+                    Ok(ImportOutcome::Updated) => summary.dashboards_updated.push(dashboard_id),
+                    Ok(ImportOutcome::Skipped) => summary.dashboards_skipped.push(dashboard_id),
+                    Err(e) => summary
+                        .errors
+                        .push(format!("dashboard {dashboard_id}: {e}")),
+                }
+            }
+        }
+    }
+    Ok(summary)
+}
+
+async fn import_dashboard(
+    org_id: &str,
+    user_id: &str,
+    folder_id: &str,
+    dashboard: Dashboard,
+    overwrite: bool,
+) -> Result<ImportOutcome, DashboardError> {
+    if !table::folders::exists(org_id, folder_id, FolderType::Dashboards).await? {
+        return Err(DashboardError::CreateFolderNotFound);
+    }
+    let dashboard_id = match dashboard.dashboard_id() {
+        Some(id) if !id.is_empty() => id.to_string(),
+        _ => {
+            super::create_dashboard(org_id, folder_id, dashboard).await?;
+            return Ok(ImportOutcome::Created);
+        }
+    };
+
+    match table::dashboards::get_by_id(org_id, &dashboard_id).await? {
+        None => {
+            let _saved =
+                super::put(org_id, &dashboard_id, folder_id, None, dashboard, None).await?;
+            set_ownership(
+                org_id,
+                "dashboards",
+                Authz {
+                    obj_id: dashboard_id,
+                    parent_type: "folders".to_owned(),
+                    parent: folder_id.to_owned(),
+                },
+            )
+            .await;
+
+            #[cfg(feature = "enterprise")]
+            if get_o2_config().super_cluster.enabled {
+                let _ = o2_enterprise::enterprise::super_cluster::queue::dashboards_put(
+                    org_id, folder_id, _saved,
+                )
+                .await;
+            }
+
+            Ok(ImportOutcome::Created)
+        }
+        Some(_) if !overwrite => Ok(ImportOutcome::Skipped),
+        Some((folder, existing)) => {
+            acl::check(org_id, &dashboard_id, user_id, DashboardAccess::Edit).await?;
+            super::update_dashboard(
+                org_id,
+                &dashboard_id,
+                &folder.folder_id,
+                dashboard,
+                Some(&existing.hash),
+            )
+            .await?;
+            if folder.folder_id != folder_id {
+                super::move_dashboard(org_id, &dashboard_id, folder_id, user_id, false).await?;
+            }
+            Ok(ImportOutcome::Updated)
+        }
+    }
+}
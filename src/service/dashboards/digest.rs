@@ -0,0 +1,388 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Digest reports, which email a summary of the org computed from its alert history and stream
+//! stats: the alerts which fired the most, the logs streams whose rate of error records moved
+//! the most and the streams whose storage grew the most.
+
+use config::{
+    SMTP_CLIENT, get_config,
+    meta::{
+        alerts::history::AlertActivity,
+        dashboards::reports::{DigestSection, Report, ReportDestination, ReportDigest},
+        stream::{StorageGrowth, StreamHourlyStats, StreamType},
+    },
+    utils::{time::now_micros, units::Unit},
+};
+use infra::table::stream_hourly_stats;
+use lettre::{AsyncTransport, Message, message::SinglePart};
+
+use super::{
+    reports::SendReportError,
+    snapshots::{escape_html, format_time},
+};
+use crate::service::{alerts::history, stream_storage_usage};
+
+/// Change of the rate of error records of a logs stream from the previous period.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ErrorRateMover {
+    pub stream_name: String,
+    /// Percentage of error records in the previous period
+    pub previous_rate: f64,
+    /// Percentage of error records in the period of the digest
+    pub current_rate: f64,
+    /// Error records in the period of the digest
+    pub error_records: i64,
+}
+
+impl ErrorRateMover {
+    /// Change of the rate, in percentage points.
+    pub fn change(&self) -> f64 {
+        self.current_rate - self.previous_rate
+    }
+}
+
+/// Storage growth of a stream over the period of the digest.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StreamGrowth {
+    pub stream_type: StreamType,
+    pub stream_name: String,
+    /// Growth of the compressed size, in bytes
+    pub compressed_bytes: i64,
+    pub growth: StorageGrowth,
+}
+
+/// Data of a digest, sections which aren't part of the digest are `None`.
+#[derive(Clone, Debug, Default)]
+pub struct Digest {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub alert_activity: Option<Vec<AlertActivity>>,
+    pub error_rate_movers: Option<Vec<ErrorRateMover>>,
+    /// Growth of the whole org, and of its streams which grew the most
+    pub storage_growth: Option<(StorageGrowth, Vec<StreamGrowth>)>,
+}
+
+/// Collects the sections of the digest of the period ending at `end_time`.
+pub async fn collect(
+    org_id: &str,
+    digest: &ReportDigest,
+    end_time: i64,
+) -> Result<Digest, SendReportError> {
+    let period = digest.period.micros();
+    let start_time = end_time - period;
+    let mut data = Digest {
+        start_time,
+        end_time,
+        ..Default::default()
+    };
+    for section in digest.sections() {
+        match section {
+            DigestSection::AlertActivity => {
+                let mut activity = history::activity(org_id, start_time, end_time)
+                    .await
+                    .map_err(|e| SendReportError::DigestError(e.to_string()))?;
+                activity.truncate(digest.top_n);
+                data.alert_activity = Some(activity);
+            }
+            DigestSection::ErrorRateMovers => {
+                let stats = stream_hourly_stats::list(
+                    org_id,
+                    StreamType::Logs,
+                    &[],
+                    StreamHourlyStats::hour_of(start_time - period),
+                    end_time,
+                )
+                .await
+                .map_err(|e| SendReportError::DigestError(e.to_string()))?;
+                data.error_rate_movers = Some(error_rate_movers(&stats, start_time, digest.top_n));
+            }
+            DigestSection::StorageGrowth => {
+                let usage = stream_storage_usage::usage(org_id, None, &[], start_time, end_time)
+                    .await
+                    .map_err(|e| SendReportError::DigestError(e.to_string()))?;
+                let mut streams = usage
+                    .streams
+                    .into_iter()
+                    .map(|s| StreamGrowth {
+                        compressed_bytes: match (s.samples.first(), s.samples.last()) {
+                            (Some(first), Some(last)) => {
+                                last.compressed_size - first.compressed_size
+                            }
+                            _ => 0,
+                        },
+                        stream_type: s.stream_type,
+                        stream_name: s.stream_name,
+                        growth: s.growth,
+                    })
+                    .collect::<Vec<_>>();
+                streams.sort_by(|a, b| b.compressed_bytes.cmp(&a.compressed_bytes));
+                streams.truncate(digest.top_n);
+                data.storage_growth = Some((usage.growth, streams));
+            }
+        }
+    }
+    Ok(data)
+}
+
+/// Compares the rate of error records of each stream before and after `start_time`, the
+/// streams whose rate changed the most first. Streams without errors in both periods are left
+/// out.
+fn error_rate_movers(
+    stats: &[(String, StreamHourlyStats)],
+    start_time: i64,
+    top_n: usize,
+) -> Vec<ErrorRateMover> {
+    // records and error records of the previous and the current period, per stream
+    let mut totals: Vec<(&str, [i64; 4])> = Vec::new();
+    for (stream_name, stats) in stats {
+        // stats are ordered by stream
+        if !totals
+            .last()
+            .is_some_and(|(name, _)| *name == stream_name.as_str())
+        {
+            totals.push((stream_name, [0; 4]));
+        }
+        let entry = &mut totals.last_mut().unwrap().1;
+        let offset = if stats.hour < start_time { 0 } else { 2 };
+        entry[offset] += stats.records;
+        entry[offset + 1] += stats.error_records;
+    }
+    let rate = |records: i64, errors: i64| {
+        if records > 0 {
+            errors as f64 / records as f64 * 100.0
+        } else {
+            0.0
+        }
+    };
+    let mut movers = totals
+        .into_iter()
+        .filter(|(_, [_, previous_errors, _, errors])| *previous_errors > 0 || *errors > 0)
+        .map(
+            |(stream_name, [previous_records, previous_errors, records, errors])| ErrorRateMover {
+                stream_name: stream_name.to_string(),
+                previous_rate: rate(previous_records, previous_errors),
+                current_rate: rate(records, errors),
+                error_records: errors,
+            },
+        )
+        .collect::<Vec<_>>();
+    movers.sort_by(|a, b| b.change().abs().total_cmp(&a.change().abs()));
+    movers.truncate(top_n);
+    movers
+}
+
+/// Collects the digest of the report and emails it to its recipients.
+pub async fn send(report: &Report, digest: &ReportDigest) -> Result<(), SendReportError> {
+    let cfg = get_config();
+    if !cfg.smtp.smtp_enabled {
+        return Err(SendReportError::SmtpNotEnabled);
+    }
+    let recipients = report
+        .destinations
+        .iter()
+        .filter_map(|d| match d {
+            ReportDestination::Email(email) => Some(email),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if recipients.is_empty() {
+        return Ok(());
+    }
+
+    let data = collect(&report.org_id, digest, now_micros()).await?;
+    let web_url = format!("{}{}/web", cfg.common.web_url, cfg.common.base_uri);
+    let body = render_html(report, &data, &web_url);
+
+    let mut email = Message::builder()
+        .from(cfg.smtp.smtp_from_email.parse()?)
+        .subject(report.title.to_string());
+    for recipient in recipients {
+        email = email.to(recipient.parse()?);
+    }
+    if !cfg.smtp.smtp_reply_to.is_empty() {
+        email = email.reply_to(cfg.smtp.smtp_reply_to.parse()?);
+    }
+    let email = email.singlepart(SinglePart::html(body)).unwrap();
+    match SMTP_CLIENT.as_ref().unwrap().send(email).await {
+        Ok(_) => {
+            log::info!("email sent successfully for the digest {}", &report.name);
+            Ok(())
+        }
+        Err(e) => Err(SendReportError::SendEmailError(e)),
+    }
+}
+
+/// Renders the html body of the digest email.
+fn render_html(report: &Report, data: &Digest, web_url: &str) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "<h2>{}</h2>\n<p>{} - {}</p>\n",
+        escape_html(&report.title),
+        format_time(data.start_time),
+        format_time(data.end_time)
+    ));
+    if !report.message.is_empty() {
+        out.push_str(&format!("<p>{}</p>\n", report.message));
+    }
+
+    if let Some(activity) = data.alert_activity.as_ref() {
+        out.push_str("<h3>Alert activity</h3>\n");
+        let rows = activity
+            .iter()
+            .map(|a| {
+                vec![
+                    a.alert_name.clone(),
+                    format!("{}/{}", a.stream_type, a.stream_name),
+                    a.fired.to_string(),
+                    a.resolved.to_string(),
+                    a.pending.to_string(),
+                ]
+            })
+            .collect::<Vec<_>>();
+        render_table(
+            &mut out,
+            &["Alert", "Stream", "Fired", "Resolved", "Pending"],
+            &rows,
+        );
+    }
+
+    if let Some(movers) = data.error_rate_movers.as_ref() {
+        out.push_str("<h3>Top error rate movers</h3>\n");
+        let rows = movers
+            .iter()
+            .map(|m| {
+                vec![
+                    m.stream_name.clone(),
+                    format!("{:.2}%", m.previous_rate),
+                    format!("{:.2}%", m.current_rate),
+                    format!("{:+.2}", m.change()),
+                    m.error_records.to_string(),
+                ]
+            })
+            .collect::<Vec<_>>();
+        render_table(
+            &mut out,
+            &["Stream", "Previous", "Current", "Change", "Errors"],
+            &rows,
+        );
+    }
+
+    if let Some((growth, streams)) = data.storage_growth.as_ref() {
+        out.push_str(&format!(
+            "<h3>Storage growth</h3>\n<p>{}/day compressed ({:+.2}%)</p>\n",
+            escape_html(&Unit::Bytes.format(growth.compressed_bytes_per_day, None)),
+            growth.compressed_growth_rate
+        ));
+        let rows = streams
+            .iter()
+            .map(|s| {
+                vec![
+                    format!("{}/{}", s.stream_type, s.stream_name),
+                    Unit::Bytes.format(s.compressed_bytes as f64, None),
+                    format!("{:+.2}%", s.growth.compressed_growth_rate),
+                ]
+            })
+            .collect::<Vec<_>>();
+        render_table(&mut out, &["Stream", "Growth", "Rate"], &rows);
+    }
+
+    out.push_str(&format!(
+        "<p><a href='{web_url}?org_identifier={}' target='_blank'>Open OpenObserve</a></p>\n",
+        escape_html(&report.org_id)
+    ));
+    out
+}
+
+fn render_table(out: &mut String, columns: &[&str], rows: &[Vec<String>]) {
+    if rows.is_empty() {
+        out.push_str("<p>No data</p>\n");
+        return;
+    }
+    out.push_str("<table>\n<tr>");
+    for column in columns {
+        out.push_str(&format!("<th>{}</th>", escape_html(column)));
+    }
+    out.push_str("</tr>\n");
+    for row in rows {
+        out.push_str("<tr>");
+        for value in row {
+            out.push_str(&format!("<td>{}</td>", escape_html(value)));
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</table>\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(hour: i64, records: i64, error_records: i64) -> StreamHourlyStats {
+        StreamHourlyStats {
+            hour,
+            records,
+            error_records,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_error_rate_movers() {
+        let stats = vec![
+            ("api".to_string(), stats(0, 100, 1)),
+            ("api".to_string(), stats(10, 100, 21)),
+            ("quiet".to_string(), stats(0, 100, 0)),
+            ("quiet".to_string(), stats(10, 100, 0)),
+            ("web".to_string(), stats(0, 100, 10)),
+            ("web".to_string(), stats(10, 50, 0)),
+        ];
+        let movers = error_rate_movers(&stats, 10, 10);
+        assert_eq!(movers.len(), 2);
+        assert_eq!(movers[0].stream_name, "api");
+        assert_eq!(movers[0].previous_rate, 1.0);
+        assert_eq!(movers[0].current_rate, 21.0);
+        assert_eq!(movers[0].error_records, 21);
+        assert_eq!(movers[1].stream_name, "web");
+        assert_eq!(movers[1].change(), -10.0);
+
+        assert_eq!(error_rate_movers(&stats, 10, 1).len(), 1);
+    }
+
+    #[test]
+    fn test_render_html() {
+        let report = Report {
+            title: "Weekly <digest>".to_string(),
+            org_id: "default".to_string(),
+            ..Default::default()
+        };
+        let data = Digest {
+            alert_activity: Some(vec![AlertActivity {
+                alert_name: "errors".to_string(),
+                stream_type: "logs".to_string(),
+                stream_name: "default".to_string(),
+                fired: 3,
+                ..Default::default()
+            }]),
+            error_rate_movers: Some(vec![]),
+            ..Default::default()
+        };
+        let html = render_html(&report, &data, "http://localhost/web");
+        assert!(html.contains("<h2>Weekly &lt;digest&gt;</h2>"));
+        assert!(html.contains("<td>errors</td><td>logs/default</td><td>3</td>"));
+        assert!(html.contains("<h3>Top error rate movers</h3>\n<p>No data</p>"));
+        assert!(!html.contains("Storage growth"));
+    }
+}
@@ -0,0 +1,334 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Formatting of the numbers and dates the server-side renderers draw, following the unit and
+//! the `format_options` of the panel config.
+
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use config::utils::{
+    json::Value,
+    units::{Unit, format_decimals},
+};
+
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Formatting of the values of a panel, resolved from its config and locale.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct ValueFormat {
+    thousands_separator: String,
+    decimal_separator: String,
+    date_format: String,
+    /// Currency code and whether it goes before the number
+    currency: Option<(String, bool)>,
+    decimals: Option<usize>,
+    unit: Option<Unit>,
+    /// Units of single columns from the override config of the panel
+    column_units: Vec<(String, Unit)>,
+}
+
+impl Default for ValueFormat {
+    fn default() -> Self {
+        Self {
+            thousands_separator: ",".to_string(),
+            decimal_separator: ".".to_string(),
+            date_format: DEFAULT_DATE_FORMAT.to_string(),
+            currency: None,
+            decimals: None,
+            unit: None,
+            column_units: Vec::new(),
+        }
+    }
+}
+
+impl ValueFormat {
+    /// Reads the format of a panel config, panels without a unit or `format_options` keep the
+    /// default rendering of the values.
+    pub(super) fn from_config(config: &Value) -> Option<Self> {
+        let field = |key: &str| config.get(key).and_then(|v| v.as_str());
+        let unit_name = field("unit").unwrap_or_default();
+        let is_currency = unit_name == "currency";
+        let unit = match is_currency {
+            true => None,
+            false => Unit::parse(unit_name, field("unit_custom")),
+        };
+        let column_units = override_units(config);
+        let options = config.get("format_options").and_then(|v| v.as_object());
+        if options.is_none() && !is_currency && unit.is_none() && column_units.is_empty() {
+            return None;
+        }
+
+        let option = |key: &str| {
+            options
+                .and_then(|o| o.get(key))
+                .and_then(|v| v.as_str())
+                .filter(|v| !v.is_empty())
+        };
+        // without format options the dates stay in the iso format
+        let locale = option("locale").or(options.map(|_| "en-US"));
+        let language = locale
+            .and_then(|l| l.split(['-', '_']).next())
+            .unwrap_or("en")
+            .to_lowercase();
+        let (thousands, decimal) = locale
+            .map(|l| locale_separators(l, &language))
+            .unwrap_or((",", "."));
+        let date_format = locale
+            .map(|l| locale_date_format(l, &language))
+            .unwrap_or(DEFAULT_DATE_FORMAT);
+        let currency = is_currency.then(|| {
+            let code = option("currency").unwrap_or("USD").to_uppercase();
+            (code, currency_before(&language))
+        });
+        let decimals = match config.get("decimals").and_then(|v| v.as_f64()) {
+            Some(d) if d >= 0.0 => Some(d as usize),
+            _ if currency.is_some() => Some(2),
+            _ => None,
+        };
+        Some(Self {
+            thousands_separator: option("thousands_separator")
+                .unwrap_or(thousands)
+                .to_string(),
+            decimal_separator: option("decimal_separator").unwrap_or(decimal).to_string(),
+            date_format: option("date_format").unwrap_or(date_format).to_string(),
+            currency,
+            decimals,
+            unit,
+            column_units,
+        })
+    }
+
+    /// Formats a number with the separators, decimals and unit or currency of the panel.
+    pub(super) fn number(&self, v: f64) -> String {
+        self.format(v, self.unit.as_ref())
+    }
+
+    fn format(&self, v: f64, unit: Option<&Unit>) -> String {
+        let (v, suffix) = match unit {
+            Some(unit) => unit.scale(v),
+            None => (v, String::new()),
+        };
+        let s = format_decimals(v.abs(), self.decimals);
+        let (int, frac) = s.split_once('.').unwrap_or((&s, ""));
+        let mut out = String::new();
+        if v < 0.0 && s.chars().any(|c| c.is_ascii_digit() && c != '0') {
+            out.push('-');
+        }
+        for (i, c) in int.chars().enumerate() {
+            if i > 0 && (int.len() - i) % 3 == 0 {
+                out.push_str(&self.thousands_separator);
+            }
+            out.push(c);
+        }
+        if !frac.is_empty() {
+            out.push_str(&self.decimal_separator);
+            out.push_str(frac);
+        }
+        out.push_str(&suffix);
+        // columns with a unit of their own aren't amounts of the currency
+        match self.currency.as_ref().filter(|_| unit.is_none()) {
+            Some((code, true)) if code == "USD" => format!("${out}"),
+            Some((code, true)) => format!("{code} {out}"),
+            Some((code, false)) => format!("{out} {code}"),
+            None => out,
+        }
+    }
+
+    /// Formats a number shortened to its unit or to thousands, millions or billions, like on
+    /// the chart axes.
+    pub(super) fn compact(&self, v: f64) -> String {
+        let format = Self {
+            currency: None,
+            decimals: None,
+            ..self.clone()
+        };
+        format.format(v, Some(self.unit.as_ref().unwrap_or(&Unit::Numbers)))
+    }
+
+    /// Formats a timestamp in microseconds.
+    pub(super) fn date(&self, micros: i64) -> String {
+        Utc.timestamp_micros(micros)
+            .single()
+            .map(|t| t.format(&self.date_format).to_string())
+            .unwrap_or_default()
+    }
+
+    /// Formats a value of the query result. Numbers are formatted in the unit of their column
+    /// unless they are the `_timestamp`, which is formatted as date like the timestamps the
+    /// histograms return.
+    pub(super) fn value(&self, column: &str, value: Option<&Value>) -> String {
+        match value {
+            Some(Value::Number(n)) if column == "_timestamp" => match n.as_i64() {
+                Some(micros) => self.date(micros),
+                None => n.to_string(),
+            },
+            Some(Value::Number(n)) => match n.as_f64() {
+                Some(v) => match self.column_units.iter().find(|(c, _)| c == column) {
+                    Some((_, unit)) => self.format(v, Some(unit)),
+                    None => self.number(v),
+                },
+                None => n.to_string(),
+            },
+            Some(Value::String(s)) => match parse_datetime(s) {
+                Some(t) => t.format(&self.date_format).to_string(),
+                None => s.clone(),
+            },
+            Some(Value::Null) | None => String::new(),
+            Some(v) => v.to_string(),
+        }
+    }
+}
+
+/// Units of the columns matched by name in the `override_config` of the panel.
+fn override_units(config: &Value) -> Vec<(String, Unit)> {
+    config
+        .get("override_config")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|item| {
+            let field = item.get("field")?;
+            if field.get("matchBy")?.as_str()? != "name" {
+                return None;
+            }
+            let column = field.get("value")?.as_str()?;
+            let value = item
+                .get("config")?
+                .as_array()?
+                .iter()
+                .find(|c| c.get("type").and_then(|v| v.as_str()) == Some("unit"))?
+                .get("value")?;
+            let unit = Unit::parse(
+                value.get("unit")?.as_str()?,
+                value.get("customUnit").and_then(|v| v.as_str()),
+            )?;
+            Some((column.to_string(), unit))
+        })
+        .collect()
+}
+
+fn parse_datetime(s: &str) -> Option<NaiveDateTime> {
+    // histogram buckets look like `2025-01-01T10:00:00`
+    if s.len() < 19 || s.as_bytes()[10] != b'T' {
+        return None;
+    }
+    NaiveDateTime::parse_from_str(s.trim_end_matches('Z'), "%Y-%m-%dT%H:%M:%S%.f").ok()
+}
+
+fn locale_separators<'a>(locale: &str, language: &str) -> (&'a str, &'a str) {
+    if locale.eq_ignore_ascii_case("de-CH") {
+        return ("'", ".");
+    }
+    match language {
+        "de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el" | "ro" => (".", ","),
+        "fr" | "ru" | "pl" | "cs" | "sk" | "sv" | "nb" | "no" | "fi" | "uk" | "hu" => (" ", ","),
+        _ => (",", "."),
+    }
+}
+
+fn locale_date_format<'a>(locale: &str, language: &str) -> &'a str {
+    match language {
+        "en" if locale.eq_ignore_ascii_case("en-US") || locale.eq_ignore_ascii_case("en") => {
+            "%m/%d/%Y %H:%M:%S"
+        }
+        "en" | "fr" | "es" | "it" | "pt" | "el" | "id" => "%d/%m/%Y %H:%M:%S",
+        "de" | "ru" | "pl" | "cs" | "sk" | "fi" | "nb" | "no" | "da" | "tr" | "uk" | "ro" => {
+            "%d.%m.%Y %H:%M:%S"
+        }
+        "ja" | "zh" | "ko" | "hu" => "%Y/%m/%d %H:%M:%S",
+        _ => DEFAULT_DATE_FORMAT,
+    }
+}
+
+/// Languages writing the currency before the amount.
+fn currency_before(language: &str) -> bool {
+    matches!(language, "en" | "ja" | "zh" | "ko" | "he" | "th")
+}
+
+#[cfg(test)]
+mod tests {
+    use config::utils::json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_from_config() {
+        assert!(ValueFormat::from_config(&json!({"unit": "default"})).is_none());
+
+        let format = ValueFormat::from_config(&json!({"format_options": {}})).unwrap();
+        assert_eq!(format.number(1234567.891), "1,234,567.89");
+        assert_eq!(format.number(-0.001), "0");
+        assert_eq!(format.date(0), "01/01/1970 00:00:00");
+
+        let format = ValueFormat::from_config(&json!({
+            "unit": "currency",
+            "format_options": {"locale": "de-DE", "currency": "eur"}
+        }))
+        .unwrap();
+        assert_eq!(format.number(-1234.5), "-1.234,50 EUR");
+        assert_eq!(format.compact(1_500_000.0), "1,5M");
+        assert_eq!(format.date(86_400_000_000), "02.01.1970 00:00:00");
+
+        let format = ValueFormat::from_config(&json!({
+            "unit": "currency",
+            "decimals": 0,
+            "format_options": {"locale": "en-GB", "currency": "GBP", "thousands_separator": " "}
+        }))
+        .unwrap();
+        assert_eq!(format.number(1999.5), "GBP 2 000");
+    }
+
+    #[test]
+    fn test_value() {
+        let format = ValueFormat::from_config(&json!({
+            "format_options": {"locale": "fr-FR", "date_format": "%d/%m %H:%M"}
+        }))
+        .unwrap();
+        assert_eq!(format.value("count", Some(&json!(12345.5))), "12 345,5");
+        assert_eq!(format.value("_timestamp", Some(&json!(0))), "01/01 00:00");
+        assert_eq!(
+            format.value("x_axis_1", Some(&json!("2025-03-04T05:06:00"))),
+            "04/03 05:06"
+        );
+        assert_eq!(format.value("host", Some(&json!("web-1"))), "web-1");
+        assert_eq!(format.value("host", None), "");
+    }
+
+    #[test]
+    fn test_units() {
+        let format = ValueFormat::from_config(&json!({
+            "unit": "bytes",
+            "override_config": [{
+                "field": {"matchBy": "name", "value": "latency"},
+                "config": [{"type": "unit", "value": {"unit": "milliseconds", "customUnit": ""}}]
+            }]
+        }))
+        .unwrap();
+        assert_eq!(format.number(1610612736.0), "1.5 GiB");
+        assert_eq!(format.compact(2048.0), "2 KiB");
+        assert_eq!(format.value("latency", Some(&json!(1500))), "1.5 s");
+        assert_eq!(format.value("size", Some(&json!(512))), "512 B");
+        assert_eq!(
+            format.value("x_axis_1", Some(&json!("2025-03-04T05:06:00"))),
+            "2025-03-04 05:06:00"
+        );
+
+        let format = ValueFormat::from_config(&json!({
+            "unit": "bytes",
+            "format_options": {"locale": "de-DE"}
+        }))
+        .unwrap();
+        assert_eq!(format.number(1610612736.0), "1,5 GiB");
+    }
+}
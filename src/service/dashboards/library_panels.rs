@@ -0,0 +1,134 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Library panels are panels stored independently of the dashboards. A v6 dashboard panel
+//! referencing a library panel keeps an embedded copy of its definition, which is replaced by the
+//! current definition of the library panel whenever the dashboard is read.
+
+use config::{
+    ider,
+    meta::dashboards::{
+        Dashboard,
+        library_panels::{LibraryPanel, apply_library_panel, library_panel_ids},
+    },
+};
+use infra::{errors::DbError, table};
+
+#[derive(Debug, thiserror::Error)]
+pub enum LibraryPanelError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Library panel not found")]
+    NotFound,
+
+    #[error("Library panel with name {0} already exists")]
+    NameConflict(String),
+
+    #[error("Library panel cannot have empty name")]
+    MissingName,
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<LibraryPanel>, LibraryPanelError> {
+    Ok(table::library_panels::list(org_id).await?)
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<LibraryPanel, LibraryPanelError> {
+    table::library_panels::get(org_id, id)
+        .await?
+        .ok_or(LibraryPanelError::NotFound)
+}
+
+pub async fn create(
+    org_id: &str,
+    owner: &str,
+    mut library_panel: LibraryPanel,
+) -> Result<LibraryPanel, LibraryPanelError> {
+    let now = chrono::Utc::now().timestamp_micros();
+    library_panel.id = ider::uuid();
+    library_panel.owner = owner.to_string();
+    library_panel.created_at = now;
+    library_panel.updated_at = now;
+    save(org_id, library_panel).await
+}
+
+/// Updates the definition of the library panel, the dashboards referencing it show the new
+/// definition the next time they are read.
+pub async fn update(
+    org_id: &str,
+    id: &str,
+    library_panel: LibraryPanel,
+) -> Result<LibraryPanel, LibraryPanelError> {
+    let existing = get(org_id, id).await?;
+    let library_panel = LibraryPanel {
+        id: existing.id,
+        owner: existing.owner,
+        created_at: existing.created_at,
+        updated_at: chrono::Utc::now().timestamp_micros(),
+        ..library_panel
+    };
+    save(org_id, library_panel).await
+}
+
+async fn save(
+    org_id: &str,
+    mut library_panel: LibraryPanel,
+) -> Result<LibraryPanel, LibraryPanelError> {
+    library_panel.name = library_panel.name.trim().to_string();
+    if library_panel.name.is_empty() {
+        return Err(LibraryPanelError::MissingName);
+    }
+    library_panel.panel.id = library_panel.id.clone();
+    library_panel.panel.library_panel_id = None;
+    match table::library_panels::put(org_id, &library_panel).await {
+        Ok(()) => Ok(library_panel),
+        Err(infra::errors::Error::DbError(DbError::UniqueViolation)) => {
+            Err(LibraryPanelError::NameConflict(library_panel.name))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Deletes the library panel, the dashboards referencing it keep their last resolved copy.
+pub async fn delete(org_id: &str, id: &str) -> Result<(), LibraryPanelError> {
+    if !table::library_panels::delete(org_id, id).await? {
+        return Err(LibraryPanelError::NotFound);
+    }
+    Ok(())
+}
+
+/// Replaces the panels of the dashboard referencing library panels with the current definition
+/// of the library panels. Panels referencing deleted library panels keep their embedded copy.
+pub async fn resolve(org_id: &str, dashboard: &mut Dashboard) -> Result<(), LibraryPanelError> {
+    let Some(inner) = dashboard.v6.as_mut() else {
+        return Ok(());
+    };
+    let ids = library_panel_ids(inner);
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let library_panels = table::library_panels::get_many(org_id, &ids).await?;
+    for panel in inner.tabs.iter_mut().flat_map(|tab| tab.panels.iter_mut()) {
+        let Some(library_panel) = panel
+            .library_panel_id
+            .as_ref()
+            .and_then(|id| library_panels.iter().find(|p| &p.id == id))
+        else {
+            continue;
+        };
+        apply_library_panel(panel, library_panel);
+    }
+    Ok(())
+}
@@ -0,0 +1,139 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Checks the dashboard panels against the current stream schemas, reporting the panels that
+//! reference deleted streams or renamed fields.
+
+use config::{
+    RwHashMap, get_config,
+    meta::{
+        dashboards::{Dashboard, DashboardLint, PanelLintIssue, panel_fields},
+        stream::StreamType,
+    },
+    utils::{json, time::now_micros},
+};
+use hashbrown::HashMap;
+use infra::{schema::SchemaCache, table};
+use once_cell::sync::Lazy;
+
+use super::{DashboardError, get_folder_and_dashboard};
+
+/// Latest background check of the dashboards with issues, by organization.
+static LINT_REPORTS: Lazy<RwHashMap<String, Vec<DashboardLint>>> = Lazy::new(Default::default);
+
+/// Schemas looked up while checking, missing streams are `None`.
+type Schemas = HashMap<(StreamType, String), Option<SchemaCache>>;
+
+/// Checks the dashboard on demand.
+pub async fn lint(org_id: &str, dashboard_id: &str) -> Result<DashboardLint, DashboardError> {
+    let (_, dashboard) = get_folder_and_dashboard(org_id, dashboard_id).await?;
+    Ok(lint_dashboard(org_id, &dashboard, &mut Schemas::new()).await)
+}
+
+/// Returns the dashboards with issues found by the latest background check.
+pub fn get_reports(org_id: &str) -> Vec<DashboardLint> {
+    LINT_REPORTS
+        .get(org_id)
+        .map(|r| r.value().clone())
+        .unwrap_or_default()
+}
+
+/// Checks the dashboards of all the organizations every `ZO_DASHBOARD_LINT_INTERVAL` seconds.
+pub async fn run() -> Result<(), anyhow::Error> {
+    let interval = get_config().common.dashboard_lint_interval;
+    if interval == 0 {
+        return Ok(());
+    }
+    let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(interval));
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        if let Err(e) = lint_all().await {
+            log::error!("[DASHBOARD_LINT] error checking dashboards: {e}");
+        }
+    }
+}
+
+async fn lint_all() -> Result<(), anyhow::Error> {
+    let mut reports: HashMap<String, Vec<DashboardLint>> = HashMap::new();
+    let mut schemas: HashMap<String, Schemas> = HashMap::new();
+    for (org_id, dashboard) in table::dashboards::list_all().await? {
+        let lint = lint_dashboard(
+            &org_id,
+            &dashboard,
+            schemas.entry(org_id.clone()).or_default(),
+        )
+        .await;
+        if !lint.issues.is_empty() {
+            log::warn!(
+                "[DASHBOARD_LINT] org: {org_id}, dashboard: {}, {} panel references are broken",
+                lint.dashboard_id,
+                lint.issues.len()
+            );
+            reports.entry(org_id).or_default().push(lint);
+        }
+    }
+    LINT_REPORTS.retain(|org_id, _| reports.contains_key(org_id));
+    for (org_id, lints) in reports {
+        LINT_REPORTS.insert(org_id, lints);
+    }
+    Ok(())
+}
+
+async fn lint_dashboard(
+    org_id: &str,
+    dashboard: &Dashboard,
+    schemas: &mut Schemas,
+) -> DashboardLint {
+    let mut lint = DashboardLint {
+        dashboard_id: dashboard.dashboard_id().unwrap_or_default().to_string(),
+        title: dashboard.title().unwrap_or_default().to_string(),
+        checked_at: now_micros(),
+        issues: vec![],
+    };
+    let value = json::to_value(dashboard).unwrap_or_default();
+    let Some(value) = value.get(format!("v{}", dashboard.version)) else {
+        return lint;
+    };
+
+    for reference in panel_fields(value) {
+        let stream = reference.stream;
+        let key = (stream.stream_type, stream.stream_name.clone());
+        if !schemas.contains_key(&key) {
+            let schema = infra::schema::get_cache(org_id, &stream.stream_name, stream.stream_type)
+                .await
+                .ok()
+                .filter(|schema| !schema.schema().fields().is_empty());
+            schemas.insert(key.clone(), schema);
+        }
+        let issue = |field: Option<String>| PanelLintIssue {
+            panel_id: reference.panel_id.clone(),
+            panel_title: reference.panel_title.clone(),
+            stream_type: stream.stream_type,
+            stream_name: stream.stream_name.clone(),
+            field,
+        };
+        let Some(schema) = schemas.get(&key).unwrap() else {
+            lint.issues.push(issue(None));
+            continue;
+        };
+        for column in reference.columns {
+            if !schema.contains_field(&column) {
+                lint.issues.push(issue(Some(column)));
+            }
+        }
+    }
+    lint
+}
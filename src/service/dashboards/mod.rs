@@ -16,11 +16,12 @@
 use config::{
     TIMESTAMP_COL_NAME, ider,
     meta::{
-        dashboards::{Dashboard, ListDashboardsParams},
+        dashboards::{Dashboard, DashboardValidation, ListDashboardsParams, convert, v6},
         folder::{DEFAULT_FOLDER, Folder, FolderType},
         stream::{DistinctField, StreamType},
+        workspace::ResourceType,
     },
-    utils::time::now_micros,
+    utils::{json, time::now_micros},
 };
 use futures::future::join_all;
 use hashbrown::HashMap;
@@ -29,13 +30,26 @@ use infra::table::{
     distinct_values::{DistinctFieldRecord, OriginType},
 };
 
-use super::{db::distinct_values, folders, stream::save_stream_settings};
+use super::{db::distinct_values, folders, stream::save_stream_settings, workspaces};
 use crate::common::{
     meta::authz::Authz,
     utils::auth::{remove_ownership, set_ownership},
 };
+pub mod acl;
+pub mod archive;
+pub mod digest;
+pub mod library_panels;
+pub mod lint;
+pub mod panel_data;
+pub mod render;
 pub mod reports;
+pub mod scheduled_snapshots;
+pub mod share;
+pub mod snapshots;
 pub mod timed_annotations;
+pub mod variables;
+
+mod format;
 
 #[cfg(feature = "enterprise")]
 use o2_enterprise::enterprise::common::config::get_config as get_o2_config;
@@ -118,6 +132,11 @@ pub enum DashboardError {
 
     #[error("Permission denied")]
     PermissionDenied,
+
+    /// Error that occurs when the copy of a dashboard into another organization
+    /// cannot be built from the original dashboard.
+    #[error("error copying dashboard: {0}")]
+    CopyDashboard(String),
 }
 
 async fn add_distinct_field_entry(
@@ -288,8 +307,12 @@ pub fn get_query_variables(
             let dash = dashboard.v5.as_ref().unwrap();
             _get_variables!(map, dash);
         }
+        6 => {
+            let dash = dashboard.v6.as_ref().unwrap();
+            _get_variables!(map, dash);
+        }
         _ => {
-            unreachable!("we only have 5 dashboard versions")
+            unreachable!("we only have 6 dashboard versions")
         }
     }
     map
@@ -380,6 +403,63 @@ pub async fn update_dashboard(
     Ok(dashboard)
 }
 
+/// Copies the dashboard into the folder of another organization, renaming the streams the
+/// dashboard reads following `stream_mapping`. Panels linked to library panels keep their
+/// current definition but are unlinked, as library panels belong to the original organization.
+#[tracing::instrument(skip(stream_mapping))]
+pub async fn copy_dashboard(
+    org_id: &str,
+    dashboard_id: &str,
+    target_org_id: &str,
+    folder_id: &str,
+    stream_mapping: &HashMap<String, String>,
+    title: Option<&str>,
+    owner: &str,
+) -> Result<Dashboard, DashboardError> {
+    // the copy is a new dashboard in the target folder, so it needs the same permission as
+    // creating one there
+    #[cfg(feature = "enterprise")]
+    if get_openfga_config().enabled
+        && !check_permissions(
+            Some(folder_id.to_owned()),
+            target_org_id,
+            owner,
+            "folders",
+            "POST",
+            "",
+        )
+        .await
+    {
+        return Err(DashboardError::PermissionDenied);
+    }
+
+    let dashboard = get_dashboard(org_id, dashboard_id).await?;
+    let mut value = convert::inner_json(&dashboard)
+        .ok_or_else(|| DashboardError::CopyDashboard("dashboard has no data".to_string()))?;
+    let renamed = config::meta::dashboards::rename_streams(&mut value, stream_mapping);
+    if let Some(tabs) = value.get_mut("tabs").and_then(|v| v.as_array_mut()) {
+        let panels = tabs
+            .iter_mut()
+            .filter_map(|tab| tab.get_mut("panels").and_then(|v| v.as_array_mut()))
+            .flatten();
+        for panel in panels.filter_map(|panel| panel.as_object_mut()) {
+            panel.remove("libraryPanelId");
+        }
+    }
+    let mut copy = convert::from_inner_json(dashboard.version, value)
+        .map_err(|e| DashboardError::CopyDashboard(e.to_string()))?;
+    if let Some(title) = title.filter(|t| !t.trim().is_empty()) {
+        copy.set_title(title.trim().to_string());
+    }
+    copy.set_owner(owner.to_string());
+    let saved = create_dashboard(target_org_id, folder_id, copy).await?;
+    log::info!(
+        "[DASHBOARD] copied dashboard {org_id}/{dashboard_id} to {target_org_id}/{}, {renamed} stream references renamed",
+        saved.dashboard_id().unwrap_or_default()
+    );
+    Ok(saved)
+}
+
 #[tracing::instrument]
 pub async fn list_dashboards(
     user_id: &str,
@@ -389,15 +469,21 @@ pub async fn list_dashboards(
     let folder_id = params.folder_id.clone();
     let dashboards = table::dashboards::list(params).await?;
     let dashboards = filter_permitted_dashboards(&org_id, user_id, dashboards, folder_id).await?;
+    let dashboards = acl::filter_viewable(&org_id, user_id, dashboards).await?;
     Ok(dashboards)
 }
 
 #[tracing::instrument]
 pub async fn get_dashboard(org_id: &str, dashboard_id: &str) -> Result<Dashboard, DashboardError> {
-    table::dashboards::get_by_id(org_id, dashboard_id)
+    let (_folder, mut dashboard) = table::dashboards::get_by_id(org_id, dashboard_id)
         .await?
-        .ok_or(DashboardError::DashboardNotFound)
-        .map(|(_f, d)| d)
+        .ok_or(DashboardError::DashboardNotFound)?;
+    if let Err(e) = library_panels::resolve(org_id, &mut dashboard).await {
+        log::warn!(
+            "[DASHBOARD] failed to resolve library panels of dashboard {org_id}/{dashboard_id}: {e}"
+        );
+    }
+    Ok(dashboard)
 }
 
 #[tracing::instrument]
@@ -408,6 +494,10 @@ pub async fn delete_dashboard(org_id: &str, dashboard_id: &str) -> Result<(), Da
     };
     table::dashboards::delete_from_folder(org_id, &folder.folder_id, dashboard_id).await?;
     distinct_values::batch_remove(OriginType::Dashboard, dashboard_id).await?;
+    if let Err(e) = workspaces::delete_resource(org_id, ResourceType::Dashboard, dashboard_id).await
+    {
+        log::error!("Failed to remove dashboard {dashboard_id} from the workspaces: {e}");
+    }
     remove_ownership(
         org_id,
         "dashboards",
@@ -568,10 +658,40 @@ async fn put(
     dashboard.set_title(title);
 
     dashboard.set_dashboard_id(dashboard_id.to_owned());
+    let dashboard = migrate_to_latest(dashboard);
     let dash = table::dashboards::put(org_id, folder_id, new_folder_id, dashboard, false).await?;
     Ok(dash)
 }
 
+/// Checks a dashboard JSON without saving it, including that the streams referenced by its
+/// panels exist in the organization.
+pub async fn validate_dashboard(org_id: &str, value: &json::Value) -> DashboardValidation {
+    let mut validation = config::meta::dashboards::validate(value);
+    for stream in validation.streams.iter() {
+        let exists = infra::schema::get_cache(org_id, &stream.stream_name, stream.stream_type)
+            .await
+            .is_ok_and(|schema| !schema.schema().fields().is_empty());
+        if !exists {
+            validation.missing_streams.push(stream.clone());
+        }
+    }
+    validation.valid = validation.is_valid();
+    validation
+}
+
+/// Migrates v5 dashboards to v6 when they are saved, the panels of v5 dashboards have no time
+/// range override. Older versions are kept as they are.
+fn migrate_to_latest(dashboard: Dashboard) -> Dashboard {
+    match dashboard {
+        Dashboard {
+            version: 5,
+            v5: Some(inner),
+            ..
+        } => v6::Dashboard::from(inner).into(),
+        dashboard => dashboard,
+    }
+}
+
 /// Internal helper function find dashboard and its folder by id.
 ///
 /// Used by self_reporting to enrich dashboard SearchEventContext
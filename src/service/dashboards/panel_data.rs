@@ -0,0 +1,244 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Raw data of the dashboard panels as CSV or XLSX files, one file per panel, attached to the
+//! scheduled reports. The panel queries are run like for a snapshot, with the row limit of the
+//! report.
+
+use std::io::Write;
+
+use config::{
+    meta::dashboards::reports::{
+        MAX_REPORT_PANEL_ROWS, ReportDashboardVariable, ReportDataFormat, ReportPanelData,
+    },
+    utils::json::Value,
+};
+
+use super::{
+    DashboardError,
+    snapshots::{collect_panels, run_panel_rows},
+};
+
+const XLSX_CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/></Types>"#;
+
+const XLSX_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#;
+
+const XLSX_WORKBOOK: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Data" sheetId="1" r:id="rId1"/></sheets></workbook>"#;
+
+const XLSX_WORKBOOK_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#;
+
+/// Data of a panel encoded in the format of the report.
+#[derive(Debug)]
+pub struct PanelDataFile {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+/// Runs the queries of the panels of the tabs and encodes the results of each panel in a file.
+/// Text panels are skipped, as are the panels whose queries fail.
+pub async fn export(
+    org_id: &str,
+    dashboard_id: &str,
+    user_id: &str,
+    tabs: &[String],
+    variables: &[ReportDashboardVariable],
+    start_time: i64,
+    end_time: i64,
+    panel_data: &ReportPanelData,
+) -> Result<Vec<PanelDataFile>, DashboardError> {
+    let dashboard = super::get_dashboard(org_id, dashboard_id).await?;
+    let variables = variables
+        .iter()
+        .map(|v| (v.key.as_str(), v.value.as_str()))
+        .collect::<Vec<_>>();
+    let max_rows = panel_data.max_rows.clamp(1, MAX_REPORT_PANEL_ROWS);
+
+    let mut files = Vec::new();
+    for (i, panel) in collect_panels(&dashboard, tabs).into_iter().enumerate() {
+        if panel.text.is_some() {
+            continue;
+        }
+        let hits = match run_panel_rows(
+            org_id, user_id, &panel, &variables, start_time, end_time, max_rows,
+        )
+        .await
+        {
+            Ok(hits) => hits,
+            Err(e) => {
+                log::warn!(
+                    "[REPORT] skipping data of panel {} of dashboard {dashboard_id}: {e}",
+                    panel.title
+                );
+                continue;
+            }
+        };
+        let data = match panel_data.format {
+            ReportDataFormat::Csv => encode_csv(&hits),
+            ReportDataFormat::Xlsx => encode_xlsx(&hits),
+        };
+        match data {
+            Ok(data) => files.push(PanelDataFile {
+                name: format!(
+                    "{}_{}.{}",
+                    i + 1,
+                    super::reports::sanitize_filename(&panel.title),
+                    panel_data.format.extension()
+                ),
+                data,
+            }),
+            Err(e) => log::error!(
+                "[REPORT] error encoding data of panel {} of dashboard {dashboard_id}: {e}",
+                panel.title
+            ),
+        }
+    }
+    Ok(files)
+}
+
+/// Columns of the hits in the order they first appear.
+fn columns(hits: &[Value]) -> Vec<&str> {
+    let mut columns: Vec<&str> = Vec::new();
+    for hit in hits {
+        for key in hit.as_object().into_iter().flat_map(|o| o.keys()) {
+            if !columns.contains(&key.as_str()) {
+                columns.push(key);
+            }
+        }
+    }
+    columns
+}
+
+fn cell_text(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Null) | None => String::new(),
+        Some(v) => v.to_string(),
+    }
+}
+
+fn encode_csv(hits: &[Value]) -> Result<Vec<u8>, anyhow::Error> {
+    let columns = columns(hits);
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    wtr.write_record(&columns)?;
+    for hit in hits {
+        wtr.write_record(columns.iter().map(|c| cell_text(hit.get(c))))?;
+    }
+    Ok(wtr.into_inner()?)
+}
+
+fn encode_xlsx(hits: &[Value]) -> Result<Vec<u8>, anyhow::Error> {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for (name, content) in [
+        ("[Content_Types].xml", XLSX_CONTENT_TYPES.to_string()),
+        ("_rels/.rels", XLSX_RELS.to_string()),
+        ("xl/workbook.xml", XLSX_WORKBOOK.to_string()),
+        ("xl/_rels/workbook.xml.rels", XLSX_WORKBOOK_RELS.to_string()),
+        ("xl/worksheets/sheet1.xml", sheet_xml(hits)),
+    ] {
+        zip.start_file(name, options)?;
+        zip.write_all(content.as_bytes())?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
+
+/// Worksheet with the columns in the first row, numbers and booleans are kept as such and the
+/// other values are written as text.
+fn sheet_xml(hits: &[Value]) -> String {
+    let columns = columns(hits);
+    let mut out = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#,
+    );
+    out.push_str("<row>");
+    for column in columns.iter() {
+        push_text_cell(&mut out, column);
+    }
+    out.push_str("</row>");
+    for hit in hits {
+        out.push_str("<row>");
+        for column in columns.iter() {
+            match hit.get(column) {
+                Some(Value::Number(n)) => out.push_str(&format!("<c><v>{n}</v></c>")),
+                Some(Value::Bool(b)) => {
+                    out.push_str(&format!("<c t=\"b\"><v>{}</v></c>", *b as u8))
+                }
+                Some(Value::Null) | None => out.push_str("<c/>"),
+                value => push_text_cell(&mut out, &cell_text(value)),
+            }
+        }
+        out.push_str("</row>");
+    }
+    out.push_str("</sheetData></worksheet>");
+    out
+}
+
+fn push_text_cell(out: &mut String, text: &str) {
+    out.push_str("<c t=\"inlineStr\"><is><t xml:space=\"preserve\">");
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            // control characters are not allowed in xml
+            c if c.is_control() && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => out.push(c),
+        }
+    }
+    out.push_str("</t></is></c>");
+}
+
+#[cfg(test)]
+mod tests {
+    use config::utils::json;
+
+    use super::*;
+
+    fn hits() -> Vec<Value> {
+        vec![
+            json::json!({"service": "api", "errors": 3, "ok": true}),
+            json::json!({"service": "web, \"edge\"", "errors": 1.5, "region": "<eu>"}),
+        ]
+    }
+
+    #[test]
+    fn test_encode_csv() {
+        let data = String::from_utf8(encode_csv(&hits()).unwrap()).unwrap();
+        assert_eq!(
+            data,
+            "errors,ok,service,region\n3,true,api,\n1.5,,\"web, \"\"edge\"\"\",<eu>\n"
+        );
+    }
+
+    #[test]
+    fn test_sheet_xml() {
+        let xml = sheet_xml(&hits());
+        assert!(
+            xml.contains(
+                "<row><c t=\"inlineStr\"><is><t xml:space=\"preserve\">errors</t></is></c>"
+            )
+        );
+        assert!(xml.contains("<row><c><v>3</v></c><c t=\"b\"><v>1</v></c>"));
+        assert!(xml.contains("<c><v>1.5</v></c><c/>"));
+        assert!(xml.contains("<t xml:space=\"preserve\">&lt;eu&gt;</t>"));
+        assert!(xml.ends_with("</row></sheetData></worksheet>"));
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+/// Color of a shape as RGB.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    pub const TEXT: Color = Color(34, 34, 34);
+    pub const MUTED: Color = Color(110, 110, 110);
+    pub const BORDER: Color = Color(204, 204, 204);
+    pub const HEADER: Color = Color(243, 243, 243);
+    pub const ERROR: Color = Color(187, 0, 0);
+
+    /// Parses `#rgb` and `#rrggbb` colors, other css colors are not supported.
+    pub fn from_hex(s: &str) -> Option<Color> {
+        let hex = s.trim().strip_prefix('#')?;
+        if !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize, len: usize| {
+            let v = u8::from_str_radix(hex.get(i * len..(i + 1) * len)?, 16).ok()?;
+            Some(if len == 1 { v * 17 } else { v })
+        };
+        let len = match hex.len() {
+            3 => 1,
+            6 => 2,
+            _ => return None,
+        };
+        Some(Color(channel(0, len)?, channel(1, len)?, channel(2, len)?))
+    }
+}
+
+/// Colors of the series of a chart.
+pub const PALETTE: [Color; 8] = [
+    Color(84, 112, 198),
+    Color(145, 204, 117),
+    Color(250, 200, 88),
+    Color(238, 102, 102),
+    Color(115, 192, 222),
+    Color(59, 162, 114),
+    Color(252, 132, 82),
+    Color(154, 96, 180),
+];
+
+/// Width of a character relative to the font size, the same for every character as the
+/// rendered text uses a monospace font.
+pub const CHAR_WIDTH: f64 = 0.6;
+
+/// Shapes drawn on a page, coordinates are in points from the top left corner of the page.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    /// Filled rectangle
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        color: Color,
+    },
+    /// Line through the points, one point wide
+    Line {
+        points: Vec<(f64, f64)>,
+        color: Color,
+    },
+    /// Single line of text, `y` is the top of the text
+    Text {
+        x: f64,
+        y: f64,
+        size: f64,
+        text: String,
+        color: Color,
+    },
+}
+
+/// A page of the rendered dashboard, encoded by the pdf and png writers.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Page {
+    pub width: f64,
+    pub height: f64,
+    pub shapes: Vec<Shape>,
+}
+
+impl Page {
+    pub fn new(width: f64, height: f64) -> Self {
+        Self {
+            width,
+            height,
+            shapes: Vec::new(),
+        }
+    }
+
+    pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, color: Color) {
+        self.shapes.push(Shape::Rect {
+            x,
+            y,
+            width,
+            height,
+            color,
+        });
+    }
+
+    pub fn line(&mut self, points: Vec<(f64, f64)>, color: Color) {
+        if points.len() > 1 {
+            self.shapes.push(Shape::Line { points, color });
+        }
+    }
+
+    /// Draws the text, truncated to `max_width`.
+    pub fn text(&mut self, x: f64, y: f64, size: f64, text: &str, max_width: f64, color: Color) {
+        let text = truncate(text, size, max_width);
+        if !text.is_empty() {
+            self.shapes.push(Shape::Text {
+                x,
+                y,
+                size,
+                text,
+                color,
+            });
+        }
+    }
+
+    /// Draws the outline of a rectangle.
+    pub fn frame(&mut self, x: f64, y: f64, width: f64, height: f64, color: Color) {
+        self.line(
+            vec![
+                (x, y),
+                (x + width, y),
+                (x + width, y + height),
+                (x, y + height),
+                (x, y),
+            ],
+            color,
+        );
+    }
+}
+
+pub fn text_width(text: &str, size: f64) -> f64 {
+    text.chars().count() as f64 * size * CHAR_WIDTH
+}
+
+/// Cuts the text to fit in `max_width`, marking the cut with `...`.
+pub fn truncate(text: &str, size: f64, max_width: f64) -> String {
+    let max_chars = (max_width / (size * CHAR_WIDTH)).floor().max(0.0) as usize;
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    if max_chars <= 3 {
+        return text.chars().take(max_chars).collect();
+    }
+    let mut out = text.chars().take(max_chars - 3).collect::<String>();
+    out.push_str("...");
+    out
+}
@@ -0,0 +1,630 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Renders dashboards to pdf or png on the server, without a browser. The panel queries are run
+//! like for a snapshot and the results are drawn as simple charts, tables and values.
+
+use config::{
+    meta::dashboards::{
+        acl::DashboardAccess,
+        render::{RenderDashboardRequest, RenderFormat},
+    },
+    utils::json::Value,
+};
+
+use self::canvas::{Color, PALETTE, Page};
+use super::{
+    DashboardError, acl,
+    format::ValueFormat,
+    snapshots::{SnapshotPanel, format_time, run_panels},
+};
+
+mod canvas;
+mod pdf;
+mod png;
+
+/// A4 landscape in points.
+const PAGE_WIDTH: f64 = 842.0;
+const PAGE_HEIGHT: f64 = 595.0;
+const MARGIN: f64 = 24.0;
+const GAP: f64 = 12.0;
+const HEADER_HEIGHT: f64 = 36.0;
+/// Panels of a page, in a grid of two columns and two rows.
+const PANEL_COLUMNS: usize = 2;
+const PANEL_ROWS: usize = 2;
+/// Series drawn by a chart, the others are left out.
+const MAX_SERIES: usize = 8;
+
+/// Panel types drawn as charts, the other types are drawn as tables.
+const CHART_TYPES: [&str; 8] = [
+    "line",
+    "area",
+    "area-stacked",
+    "bar",
+    "h-bar",
+    "stacked",
+    "h-stacked",
+    "scatter",
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum RenderError {
+    #[error(transparent)]
+    DashboardError(#[from] DashboardError),
+
+    #[error("Invalid render request: {0}")]
+    InvalidRequest(String),
+}
+
+/// Runs the panel queries of the dashboard and renders the results in the requested format, the
+/// user needs to be able to view the dashboard.
+pub async fn render(
+    org_id: &str,
+    dashboard_id: &str,
+    user_id: &str,
+    req: &RenderDashboardRequest,
+) -> Result<Vec<u8>, RenderError> {
+    acl::check(org_id, dashboard_id, user_id, DashboardAccess::View).await?;
+    draw(org_id, dashboard_id, user_id, req).await
+}
+
+/// Renders the dashboard without checking its ACL, for the reports, whose owner is checked
+/// instead of the report user the queries run as.
+pub(super) async fn draw(
+    org_id: &str,
+    dashboard_id: &str,
+    user_id: &str,
+    req: &RenderDashboardRequest,
+) -> Result<Vec<u8>, RenderError> {
+    if req.start_time >= req.end_time {
+        return Err(RenderError::InvalidRequest(
+            "start_time must be before end_time".to_string(),
+        ));
+    }
+
+    let dashboard = super::get_dashboard(org_id, dashboard_id).await?;
+    let title = dashboard.title().unwrap_or_default().to_string();
+    let results = run_panels(
+        org_id,
+        user_id,
+        &dashboard,
+        &req.tabs,
+        &req.variables,
+        req.start_time,
+        req.end_time,
+    )
+    .await;
+
+    let pages = layout(&title, req.start_time, req.end_time, &results);
+    Ok(match req.format {
+        RenderFormat::Pdf => pdf::encode(&pages),
+        RenderFormat::Png => png::encode(&pages),
+    })
+}
+
+/// Part of a page a panel is drawn in.
+#[derive(Clone, Copy, Debug)]
+struct Area {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+/// Values of a chart: the x axis labels and a series per y axis or breakdown value.
+#[derive(Debug, Default, PartialEq)]
+struct ChartData {
+    labels: Vec<String>,
+    series: Vec<(String, Vec<Option<f64>>)>,
+}
+
+/// Lays out the panels in a grid of pages with the dashboard title on top of each page.
+fn layout(
+    title: &str,
+    start_time: i64,
+    end_time: i64,
+    results: &[(SnapshotPanel, Result<Vec<Value>, String>)],
+) -> Vec<Page> {
+    let per_page = PANEL_COLUMNS * PANEL_ROWS;
+    let width =
+        (PAGE_WIDTH - 2.0 * MARGIN - GAP * (PANEL_COLUMNS - 1) as f64) / PANEL_COLUMNS as f64;
+    let height = (PAGE_HEIGHT - 2.0 * MARGIN - HEADER_HEIGHT - GAP * (PANEL_ROWS - 1) as f64)
+        / PANEL_ROWS as f64;
+    let range = format!("{} - {}", format_time(start_time), format_time(end_time));
+
+    let mut pages = Vec::new();
+    for chunk in results
+        .chunks(per_page)
+        .chain(results.is_empty().then_some(&[][..]))
+    {
+        let mut page = Page::new(PAGE_WIDTH, PAGE_HEIGHT);
+        let max_width = PAGE_WIDTH - 2.0 * MARGIN;
+        page.text(MARGIN, MARGIN, 14.0, title, max_width, Color::TEXT);
+        page.text(MARGIN, MARGIN + 18.0, 8.0, &range, max_width, Color::MUTED);
+        if chunk.is_empty() {
+            let y = MARGIN + HEADER_HEIGHT;
+            page.text(MARGIN, y, 10.0, "No panels", max_width, Color::MUTED);
+        }
+        for (i, (panel, data)) in chunk.iter().enumerate() {
+            let area = Area {
+                x: MARGIN + (i % PANEL_COLUMNS) as f64 * (width + GAP),
+                y: MARGIN + HEADER_HEIGHT + (i / PANEL_COLUMNS) as f64 * (height + GAP),
+                width,
+                height,
+            };
+            draw_panel(&mut page, area, panel, data);
+        }
+        pages.push(page);
+    }
+    pages
+}
+
+fn draw_panel(
+    page: &mut Page,
+    area: Area,
+    panel: &SnapshotPanel,
+    data: &Result<Vec<Value>, String>,
+) {
+    page.frame(area.x, area.y, area.width, area.height, Color::BORDER);
+    let title = if panel.tab.is_empty() {
+        panel.title.clone()
+    } else {
+        format!("{} / {}", panel.tab, panel.title)
+    };
+    page.text(
+        area.x + 6.0,
+        area.y + 6.0,
+        10.0,
+        &title,
+        area.width - 12.0,
+        Color::TEXT,
+    );
+    let content = Area {
+        x: area.x + 6.0,
+        y: area.y + 22.0,
+        width: area.width - 12.0,
+        height: area.height - 28.0,
+    };
+
+    if let Some(text) = panel.text.as_ref() {
+        draw_lines(page, content, text, Color::TEXT);
+        return;
+    }
+    let hits = match data {
+        Ok(hits) if hits.is_empty() => {
+            draw_lines(page, content, "No data", Color::MUTED);
+            return;
+        }
+        Ok(hits) => hits,
+        Err(e) => {
+            draw_lines(page, content, e, Color::ERROR);
+            return;
+        }
+    };
+    let format = panel.format.as_ref();
+    match panel.panel_type.as_str() {
+        "metric" => draw_metric(page, content, panel, hits),
+        t if CHART_TYPES.contains(&t) => match chart_data(panel, hits) {
+            Some(chart) => draw_chart(
+                page,
+                content,
+                &chart,
+                t.contains("bar") || t.contains("stacked"),
+                format,
+            ),
+            None => draw_table(page, content, hits, format),
+        },
+        _ => draw_table(page, content, hits, format),
+    }
+}
+
+fn draw_lines(page: &mut Page, area: Area, text: &str, color: Color) {
+    let size = 8.0;
+    let max_lines = (area.height / (size + 3.0)).floor() as usize;
+    for (i, line) in text.lines().take(max_lines).enumerate() {
+        let y = area.y + i as f64 * (size + 3.0);
+        page.text(area.x, y, size, line, area.width, color);
+    }
+}
+
+fn value_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn value_label(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Null) | None => String::new(),
+        Some(v) => v.to_string(),
+    }
+}
+
+fn format_number(v: f64) -> String {
+    let abs = v.abs();
+    let (v, suffix) = if abs >= 1e9 {
+        (v / 1e9, "G")
+    } else if abs >= 1e6 {
+        (v / 1e6, "M")
+    } else if abs >= 1e3 {
+        (v / 1e3, "K")
+    } else {
+        (v, "")
+    };
+    let s = format!("{v:.2}");
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    format!("{s}{suffix}")
+}
+
+/// Picks the x and y columns of the panel from its query fields. Panels without fields use the
+/// `x_axis_*`, `_timestamp` or first text column as x axis and the numeric columns as series.
+fn chart_data(panel: &SnapshotPanel, hits: &[Value]) -> Option<ChartData> {
+    let first = hits.first()?.as_object()?;
+    let x = match panel.x_axis.first() {
+        Some(x) => x.clone(),
+        None => first
+            .keys()
+            .find(|k| k.starts_with("x_axis"))
+            .or(first.keys().find(|k| *k == "_timestamp"))
+            .or(first.iter().find(|(_, v)| v.is_string()).map(|(k, _)| k))?
+            .clone(),
+    };
+    let breakdown = panel.breakdown.first();
+    let y = if panel.y_axis.is_empty() {
+        first
+            .iter()
+            .filter(|(k, v)| *k != &x && Some(*k) != breakdown && value_f64(v).is_some())
+            .map(|(k, _)| k.clone())
+            .collect::<Vec<_>>()
+    } else {
+        panel.y_axis.clone()
+    };
+    if y.is_empty() {
+        return None;
+    }
+
+    let mut chart = ChartData::default();
+    for hit in hits {
+        let label = match panel.format.as_ref() {
+            Some(format) => format.value(&x, hit.get(&x)),
+            None => value_label(hit.get(&x)),
+        };
+        let index = match chart.labels.iter().position(|l| *l == label) {
+            Some(i) => i,
+            None => {
+                chart.labels.push(label);
+                chart.labels.len() - 1
+            }
+        };
+        for column in y.iter() {
+            // a breakdown splits the first y axis into a series per breakdown value
+            let name = match breakdown {
+                Some(b) => value_label(hit.get(b)),
+                None => column.clone(),
+            };
+            let series = match chart.series.iter().position(|(n, _)| *n == name) {
+                Some(i) => &mut chart.series[i].1,
+                None if chart.series.len() < MAX_SERIES => {
+                    chart.series.push((name, Vec::new()));
+                    &mut chart.series.last_mut().unwrap().1
+                }
+                None => continue,
+            };
+            series.resize(index + 1, None);
+            series[index] = hit.get(column).and_then(value_f64);
+            if breakdown.is_some() {
+                break;
+            }
+        }
+    }
+    for (_, values) in chart.series.iter_mut() {
+        values.resize(chart.labels.len(), None);
+    }
+    Some(chart)
+}
+
+fn draw_chart(
+    page: &mut Page,
+    area: Area,
+    chart: &ChartData,
+    bars: bool,
+    format: Option<&ValueFormat>,
+) {
+    let size = 7.0;
+    let axis_width = 36.0;
+    let plot = Area {
+        x: area.x + axis_width,
+        y: area.y + 4.0,
+        width: area.width - axis_width,
+        height: area.height - 2.0 * (size + 6.0) - 4.0,
+    };
+    let values = chart.series.iter().flat_map(|(_, v)| v.iter().flatten());
+    let (min, max) = values.fold((0.0f64, 0.0f64), |(min, max), v| (min.min(*v), max.max(*v)));
+    let max = if max > min { max } else { min + 1.0 };
+    let y_of = |v: f64| plot.y + plot.height * (max - v) / (max - min);
+
+    // axes and their bounds
+    page.line(
+        vec![
+            (plot.x, plot.y),
+            (plot.x, plot.y + plot.height),
+            (plot.x + plot.width, plot.y + plot.height),
+        ],
+        Color::BORDER,
+    );
+    if min < 0.0 {
+        page.line(
+            vec![(plot.x, y_of(0.0)), (plot.x + plot.width, y_of(0.0))],
+            Color::BORDER,
+        );
+    }
+    for v in [max, min] {
+        let label = match format {
+            Some(format) => format.compact(v),
+            None => format_number(v),
+        };
+        let x = plot.x - 3.0 - canvas::text_width(&label, size);
+        page.text(
+            x,
+            y_of(v) - size / 2.0,
+            size,
+            &label,
+            axis_width,
+            Color::MUTED,
+        );
+    }
+    let label_y = plot.y + plot.height + 3.0;
+    if let Some(first) = chart.labels.first() {
+        page.text(plot.x, label_y, size, first, plot.width / 2.0, Color::MUTED);
+    }
+    if chart.labels.len() > 1 {
+        let last = canvas::truncate(chart.labels.last().unwrap(), size, plot.width / 2.0);
+        let x = plot.x + plot.width - canvas::text_width(&last, size);
+        page.text(x, label_y, size, &last, plot.width / 2.0, Color::MUTED);
+    }
+
+    let count = chart.labels.len().max(1) as f64;
+    for (i, (_, values)) in chart.series.iter().enumerate() {
+        let color = PALETTE[i % PALETTE.len()];
+        if bars {
+            let slot = plot.width / count;
+            let bar = slot * 0.8 / chart.series.len() as f64;
+            for (j, v) in values.iter().enumerate() {
+                let Some(v) = v else { continue };
+                let x = plot.x + slot * (j as f64 + 0.1) + bar * i as f64;
+                let (top, bottom) = (y_of(v.max(0.0)), y_of(v.min(0.0)));
+                page.rect(x, top, bar, bottom - top, color);
+            }
+            continue;
+        }
+        let x_of = |j: usize| match chart.labels.len() {
+            1 => plot.x + plot.width / 2.0,
+            n => plot.x + plot.width * j as f64 / (n - 1) as f64,
+        };
+        // gaps in the values break the line
+        let mut points = Vec::new();
+        for (j, v) in values.iter().enumerate() {
+            match v {
+                Some(v) => points.push((x_of(j), y_of(*v))),
+                None => draw_points(page, std::mem::take(&mut points), color),
+            }
+        }
+        draw_points(page, points, color);
+    }
+
+    // legend
+    let mut x = area.x;
+    let y = area.y + area.height - size - 2.0;
+    for (i, (name, _)) in chart.series.iter().enumerate() {
+        let max_width = area.x + area.width - x - size - 3.0;
+        if max_width < size * 4.0 {
+            break;
+        }
+        let name = canvas::truncate(name, size, max_width.min(area.width / 3.0));
+        page.rect(
+            x,
+            y + 1.0,
+            size - 2.0,
+            size - 2.0,
+            PALETTE[i % PALETTE.len()],
+        );
+        page.text(x + size, y, size, &name, max_width, Color::MUTED);
+        x += size + canvas::text_width(&name, size) + 10.0;
+    }
+}
+
+/// Draws a line through the points, a single point as a dot.
+fn draw_points(page: &mut Page, points: Vec<(f64, f64)>, color: Color) {
+    match points.as_slice() {
+        [] => {}
+        [(x, y)] => page.rect(x - 1.5, y - 1.5, 3.0, 3.0, color),
+        _ => page.line(points, color),
+    }
+}
+
+fn draw_metric(page: &mut Page, area: Area, panel: &SnapshotPanel, hits: &[Value]) {
+    let (column, value) = match panel.y_axis.first() {
+        Some(y) => (y.as_str(), hits[0].get(y)),
+        None => hits[0]
+            .as_object()
+            .and_then(|o| o.iter().find(|(_, v)| value_f64(v).is_some()))
+            .map(|(k, v)| (k.as_str(), Some(v)))
+            .unwrap_or_default(),
+    };
+    let text = match (value.and_then(value_f64), panel.format.as_ref()) {
+        (Some(_), Some(format)) => format.value(column, value),
+        (Some(v), None) => format_number(v),
+        (None, _) => value_label(value),
+    };
+    let size = (area.height * 0.4).min(36.0);
+    let width = canvas::text_width(&text, size).min(area.width);
+    let x = area.x + (area.width - width) / 2.0;
+    let y = area.y + (area.height - size) / 2.0;
+    let color = panel
+        .threshold_color(column, value)
+        .and_then(Color::from_hex)
+        .unwrap_or(Color::TEXT);
+    page.text(x, y, size, &text, area.width, color);
+}
+
+fn draw_table(page: &mut Page, area: Area, hits: &[Value], format: Option<&ValueFormat>) {
+    let size = 7.0;
+    let row_height = size + 4.0;
+    let mut columns: Vec<&str> = Vec::new();
+    for hit in hits {
+        for key in hit.as_object().into_iter().flat_map(|o| o.keys()) {
+            if !columns.contains(&key.as_str()) {
+                columns.push(key);
+            }
+        }
+    }
+    let fit = ((area.width / 60.0).floor() as usize).clamp(1, columns.len().max(1));
+    columns.truncate(fit);
+    let column_width = area.width / fit as f64;
+
+    page.rect(area.x, area.y, area.width, row_height, Color::HEADER);
+    for (i, column) in columns.iter().enumerate() {
+        let x = area.x + i as f64 * column_width + 2.0;
+        page.text(
+            x,
+            area.y + 2.0,
+            size,
+            column,
+            column_width - 4.0,
+            Color::TEXT,
+        );
+    }
+    let max_rows = ((area.height / row_height).floor() as usize).saturating_sub(1);
+    let rows = if hits.len() > max_rows {
+        max_rows.saturating_sub(1)
+    } else {
+        hits.len()
+    };
+    for (r, hit) in hits.iter().take(rows).enumerate() {
+        let y = area.y + (r + 1) as f64 * row_height + 2.0;
+        for (i, column) in columns.iter().enumerate() {
+            let x = area.x + i as f64 * column_width + 2.0;
+            let cell = match format {
+                Some(format) => format.value(column, hit.get(column)),
+                None => value_label(hit.get(column)),
+            };
+            page.text(x, y, size, &cell, column_width - 4.0, Color::TEXT);
+        }
+    }
+    if rows < hits.len() {
+        let y = area.y + (rows + 1) as f64 * row_height + 2.0;
+        let more = format!("{} more rows", hits.len() - rows);
+        page.text(area.x + 2.0, y, size, &more, area.width, Color::MUTED);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use config::utils::json::json;
+
+    use super::{canvas::Shape, *};
+
+    #[test]
+    fn test_chart_data() {
+        let panel = SnapshotPanel {
+            panel_type: "line".to_string(),
+            x_axis: vec!["x_axis_1".to_string()],
+            y_axis: vec!["y_axis_1".to_string()],
+            breakdown: vec!["host".to_string()],
+            ..Default::default()
+        };
+        let hits = vec![
+            json!({"x_axis_1": "10:00", "host": "a", "y_axis_1": 1}),
+            json!({"x_axis_1": "10:00", "host": "b", "y_axis_1": "2.5"}),
+            json!({"x_axis_1": "10:01", "host": "b", "y_axis_1": 3}),
+        ];
+        assert_eq!(
+            chart_data(&panel, &hits).unwrap(),
+            ChartData {
+                labels: vec!["10:00".to_string(), "10:01".to_string()],
+                series: vec![
+                    ("a".to_string(), vec![Some(1.0), None]),
+                    ("b".to_string(), vec![Some(2.5), Some(3.0)]),
+                ],
+            }
+        );
+
+        // without query fields the numeric columns are the series
+        let panel = SnapshotPanel::default();
+        let hits = vec![json!({"_timestamp": 1, "count": 4, "host": "a"})];
+        let chart = chart_data(&panel, &hits).unwrap();
+        assert_eq!(chart.labels, vec!["1".to_string()]);
+        assert_eq!(chart.series, vec![("count".to_string(), vec![Some(4.0)])]);
+        assert!(chart_data(&panel, &[json!({"host": "a"})]).is_none());
+    }
+
+    #[test]
+    fn test_layout() {
+        let panel = |title: &str, panel_type: &str| SnapshotPanel {
+            title: title.to_string(),
+            panel_type: panel_type.to_string(),
+            ..Default::default()
+        };
+        let hits = vec![
+            json!({"x_axis_1": "a", "y_axis_1": 1}),
+            json!({"x_axis_1": "b", "y_axis_1": -2}),
+        ];
+        let results = vec![
+            (panel("Requests", "bar"), Ok(hits.clone())),
+            (panel("Latency", "line"), Ok(hits.clone())),
+            (panel("Total", "metric"), Ok(hits.clone())),
+            (panel("Rows", "table"), Ok(hits)),
+            (panel("Broken", "line"), Err("bad query".to_string())),
+        ];
+        let pages = layout("Service", 0, 1, &results);
+        assert_eq!(pages.len(), 2);
+        let texts = |page: &Page| {
+            page.shapes
+                .iter()
+                .filter_map(|s| match s {
+                    Shape::Text { text, .. } => Some(text.clone()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let first = texts(&pages[0]);
+        for text in [
+            "Service", "Requests", "Latency", "Total", "Rows", "-2", "x_axis_1",
+        ] {
+            assert!(first.contains(&text.to_string()), "{text} not in {first:?}");
+        }
+        assert!(texts(&pages[1]).contains(&"bad query".to_string()));
+        assert_eq!(layout("Empty", 0, 1, &[]).len(), 1);
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(1500.0), "1.5K");
+        assert_eq!(format_number(-2.0), "-2");
+        assert_eq!(format_number(0.126), "0.13");
+        assert_eq!(format_number(3_000_000.0), "3M");
+    }
+
+    #[test]
+    fn test_color_from_hex() {
+        assert_eq!(Color::from_hex("#f80"), Some(Color(255, 136, 0)));
+        assert_eq!(Color::from_hex("#00ff7f"), Some(Color(0, 255, 127)));
+        assert_eq!(Color::from_hex("red"), None);
+        assert_eq!(Color::from_hex("#12345"), None);
+    }
+}
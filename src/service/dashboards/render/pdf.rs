@@ -0,0 +1,213 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::Write;
+
+use flate2::{Compression, write::ZlibEncoder};
+
+use super::canvas::{Color, Page, Shape};
+
+/// Writes the pages as a pdf document, text uses the standard Courier font.
+pub fn encode(pages: &[Page]) -> Vec<u8> {
+    let mut out = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::new();
+    let mut add_object = |out: &mut Vec<u8>, body: &[u8]| {
+        offsets.push(out.len());
+        out.extend_from_slice(format!("{} 0 obj\n", offsets.len()).as_bytes());
+        out.extend_from_slice(body);
+        out.extend_from_slice(b"\nendobj\n");
+    };
+
+    // objects 1 to 3 are the catalog, the page tree and the font, each page is followed by
+    // its content stream
+    let kids = (0..pages.len())
+        .map(|i| format!("{} 0 R", 4 + 2 * i))
+        .collect::<Vec<_>>()
+        .join(" ");
+    add_object(&mut out, b"<< /Type /Catalog /Pages 2 0 R >>");
+    add_object(
+        &mut out,
+        format!("<< /Type /Pages /Kids [{kids}] /Count {} >>", pages.len()).as_bytes(),
+    );
+    add_object(
+        &mut out,
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>",
+    );
+    for (i, page) in pages.iter().enumerate() {
+        add_object(
+            &mut out,
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+                num(page.width),
+                num(page.height),
+                5 + 2 * i
+            )
+            .as_bytes(),
+        );
+        let content = compress(content_stream(page).as_bytes());
+        let mut body = format!(
+            "<< /Length {} /Filter /FlateDecode >>\nstream\n",
+            content.len()
+        )
+        .into_bytes();
+        body.extend_from_slice(&content);
+        body.extend_from_slice(b"\nendstream");
+        add_object(&mut out, &body);
+    }
+
+    let xref = out.len();
+    out.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", offsets.len() + 1).as_bytes(),
+    );
+    for offset in offsets.iter() {
+        out.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+    }
+    out.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            offsets.len() + 1
+        )
+        .as_bytes(),
+    );
+    out
+}
+
+/// Drawing operators of the page, pdf coordinates start at the bottom left corner.
+fn content_stream(page: &Page) -> String {
+    let mut out = String::new();
+    for shape in page.shapes.iter() {
+        match shape {
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                color,
+            } => {
+                out.push_str(&format!(
+                    "{} rg {} {} {} {} re f\n",
+                    rgb(*color),
+                    num(*x),
+                    num(page.height - y - height),
+                    num(*width),
+                    num(*height)
+                ));
+            }
+            Shape::Line { points, color } => {
+                out.push_str(&format!("{} RG 1 w ", rgb(*color)));
+                for (i, (x, y)) in points.iter().enumerate() {
+                    let op = if i == 0 { "m" } else { "l" };
+                    out.push_str(&format!("{} {} {op} ", num(*x), num(page.height - y)));
+                }
+                out.push_str("S\n");
+            }
+            Shape::Text {
+                x,
+                y,
+                size,
+                text,
+                color,
+            } => {
+                out.push_str(&format!(
+                    "BT /F1 {} Tf {} rg {} {} Td ({}) Tj ET\n",
+                    num(*size),
+                    rgb(*color),
+                    num(*x),
+                    num(page.height - y - size * 0.75),
+                    escape(text)
+                ));
+            }
+        }
+    }
+    out
+}
+
+fn compress(data: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    // writing to a vec can't fail
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn num(v: f64) -> String {
+    let s = format!("{v:.2}");
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn rgb(color: Color) -> String {
+    format!(
+        "{} {} {}",
+        num(color.0 as f64 / 255.0),
+        num(color.1 as f64 / 255.0),
+        num(color.2 as f64 / 255.0)
+    )
+}
+
+/// Escapes a pdf string, characters outside of ascii are replaced by `?`.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            ' '..='~' => out.push(c),
+            _ => out.push('?'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let mut page = Page::new(100.0, 50.0);
+        page.rect(10.0, 10.0, 20.0, 5.0, Color::BORDER);
+        page.text(5.0, 5.0, 10.0, "a (b) é", 100.0, Color::TEXT);
+        let pdf = encode(&[page.clone(), page]);
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.starts_with("%PDF-1.4\n"));
+        assert!(text.contains("/Kids [4 0 R 6 0 R] /Count 2"));
+        assert!(text.contains("trailer\n<< /Size 8 /Root 1 0 R >>"));
+        assert!(text.ends_with("%%EOF\n"));
+
+        // the xref offsets point at the objects
+        let xref = pdf.windows(6).position(|w| w == b"\nxref\n").unwrap();
+        let table = String::from_utf8(pdf[xref + 1..].to_vec()).unwrap();
+        let offset = table.lines().nth(4).unwrap()[..10]
+            .parse::<usize>()
+            .unwrap();
+        assert!(pdf[offset..].starts_with(b"2 0 obj"));
+
+        let page = Page {
+            shapes: vec![Shape::Text {
+                x: 5.0,
+                y: 5.0,
+                size: 10.0,
+                text: "a (b) é".to_string(),
+                color: Color::TEXT,
+            }],
+            ..Page::new(100.0, 50.0)
+        };
+        assert_eq!(
+            content_stream(&page),
+            "BT /F1 10 Tf 0.13 0.13 0.13 rg 5 37.5 Td (a \\(b\\) ?) Tj ET\n"
+        );
+    }
+}
@@ -21,13 +21,17 @@ use chrono::Timelike;
 use config::{
     SMTP_CLIENT, get_chrome_launch_options, get_config,
     meta::dashboards::{
+        acl::DashboardAccess,
         datetime_now,
+        render::{RenderDashboardRequest, RenderFormat},
         reports::{
-            HttpReportPayload, Report, ReportDashboard, ReportDestination, ReportEmailDetails,
-            ReportFrequencyType, ReportListFilters, ReportTimerangeType,
+            HttpReportPayload, MAX_DIGEST_TOP_N, MAX_REPORT_PANEL_ROWS, Report, ReportDashboard,
+            ReportDataFormat, ReportDestination, ReportEmailDetails, ReportFrequencyType,
+            ReportListFilters, ReportTimerange, ReportTimerangeType, ReportWebhook,
+            render_report_message, report_storage_dir, report_storage_path,
         },
     },
-    utils::time::now_micros,
+    utils::{json, time::now_micros},
 };
 use cron::Schedule;
 use futures::{StreamExt, future::try_join_all};
@@ -42,6 +46,11 @@ use lettre::{
 };
 use reqwest::Client;
 
+use super::{
+    DashboardError, acl, digest,
+    panel_data::{self, PanelDataFile},
+    render::{self, RenderError},
+};
 use crate::{
     common::{
         meta::authz::Authz,
@@ -86,6 +95,12 @@ pub enum ReportError {
     #[error("Atleast one destination is required")]
     NoDestinations,
 
+    #[error("Invalid destination: {0}")]
+    InvalidDestination(String),
+
+    #[error("Invalid digest: {0}")]
+    InvalidDigest(String),
+
     #[error("Some dashboards/tabs not found")]
     DashboardTabNotFound,
 
@@ -110,18 +125,43 @@ pub async fn save(
 ) -> Result<(), ReportError> {
     let conn = ORM_CLIENT.get_or_init(connect_to_orm).await;
     let cfg = get_config();
-    if cfg.common.report_server_url.is_empty() {
-        // Check if SMTP is enabled, otherwise don't save the report
-        if !cfg.smtp.smtp_enabled && !report.destinations.is_empty() {
+    if let Some(digest) = report.digest.as_mut() {
+        // Digests are built and emailed by this node, without rendering any dashboard
+        if !cfg.smtp.smtp_enabled {
             return Err(ReportError::SmtpNotEnabled);
         }
-
-        // Check if Chrome is enabled, otherwise don't save the report
-        if !cfg.chrome.chrome_enabled || cfg.chrome.chrome_path.is_empty() {
-            return Err(ReportError::ChromeNotEnabled);
+        if !report.dashboards.is_empty() {
+            return Err(ReportError::InvalidDigest(
+                "digest reports don't include dashboards".to_string(),
+            ));
+        }
+        if report
+            .destinations
+            .iter()
+            .any(|d| !matches!(d, ReportDestination::Email(_)))
+        {
+            return Err(ReportError::InvalidDestination(
+                "digest reports are only sent by email".to_string(),
+            ));
+        }
+        digest.top_n = digest.top_n.clamp(1, MAX_DIGEST_TOP_N);
+    } else if cfg.common.report_server_url.is_empty() {
+        // Check if SMTP is enabled, otherwise don't save the report
+        if !cfg.smtp.smtp_enabled
+            && report
+                .destinations
+                .iter()
+                .any(|d| matches!(d, ReportDestination::Email(_)))
+        {
+            return Err(ReportError::SmtpNotEnabled);
         }
 
-        if cfg.common.report_user_name.is_empty() || cfg.common.report_user_password.is_empty() {
+        // Without Chrome the dashboards are rendered by the built-in renderer, Chrome needs the
+        // report user to log in
+        if cfg.chrome.chrome_enabled
+            && (cfg.common.report_user_name.is_empty()
+                || cfg.common.report_user_password.is_empty())
+        {
             return Err(ReportError::ReportUsernamePasswordNotSet);
         }
     }
@@ -141,6 +181,33 @@ pub async fn save(
         return Err(ReportError::NameContainsForwardSlash);
     }
 
+    if let Some(panel_data) = report.panel_data.as_mut() {
+        panel_data.max_rows = panel_data.max_rows.clamp(1, MAX_REPORT_PANEL_ROWS);
+    }
+
+    for destination in report.destinations.iter() {
+        match destination {
+            ReportDestination::Email(_) => {}
+            ReportDestination::Storage(path) => {
+                if report_storage_path(org_id, path, &report.name, 0).is_none() {
+                    return Err(ReportError::InvalidDestination(format!(
+                        "storage path {path} is not allowed"
+                    )));
+                }
+            }
+            ReportDestination::Slack(webhook) | ReportDestination::Teams(webhook) => {
+                if !url::Url::parse(&webhook.url)
+                    .is_ok_and(|url| url.scheme() == "https" || url.scheme() == "http")
+                {
+                    return Err(ReportError::InvalidDestination(format!(
+                        "webhook url {} is not valid",
+                        webhook.url
+                    )));
+                }
+            }
+        }
+    }
+
     if report.frequency.frequency_type == ReportFrequencyType::Cron {
         let now = chrono::Utc::now().second();
         report.frequency.cron =
@@ -169,7 +236,7 @@ pub async fn save(
     }
 
     // Atleast one `ReportDashboard` needs to be present
-    if report.dashboards.is_empty() {
+    if report.digest.is_none() && report.dashboards.is_empty() {
         return Err(ReportError::NoDashboards);
     }
 
@@ -335,6 +402,21 @@ pub enum SendReportError {
 
     #[error(transparent)]
     GenerateReportError(#[from] GenerateReportError),
+
+    #[error("Error uploading report to {0}: {1}")]
+    UploadReportError(String, String),
+
+    #[error("Error posting report to webhook: {0}")]
+    WebhookError(String),
+
+    #[error("Error exporting panel data: {0}")]
+    PanelDataError(#[from] DashboardError),
+
+    #[error("Error collecting digest: {0}")]
+    DigestError(String),
+
+    #[error("Report owner can't view the dashboard {0}: {1}")]
+    OwnerAccessError(String, DashboardError),
 }
 
 #[async_trait]
@@ -347,19 +429,55 @@ pub trait SendReport {
 impl SendReport for Report {
     /// Sends the report to subscribers
     async fn send_subscribers(&self) -> Result<(), SendReportError> {
+        if let Some(report_digest) = self.digest.as_ref() {
+            return digest::send(self, report_digest).await;
+        }
         if self.dashboards.is_empty() {
             return Err(SendReportError::NoDashboards);
         }
+        // the dashboards are captured as the report user, the owner has to be able to view them
+        for dashboard in self.dashboards.iter() {
+            acl::check(
+                &self.org_id,
+                &dashboard.dashboard,
+                &self.owner,
+                DashboardAccess::View,
+            )
+            .await
+            .map_err(|e| SendReportError::OwnerAccessError(dashboard.dashboard.clone(), e))?;
+        }
 
         let cfg = get_config();
         let mut recipients = vec![];
-        for recipient in &self.destinations {
-            match recipient {
+        let mut storage_paths = vec![];
+        let mut webhooks = vec![];
+        for destination in &self.destinations {
+            match destination {
                 ReportDestination::Email(email) => recipients.push(email.clone()),
+                ReportDestination::Storage(path) => storage_paths.push(path),
+                ReportDestination::Slack(webhook) | ReportDestination::Teams(webhook) => {
+                    webhooks.push(webhook)
+                }
             }
         }
         let no_of_recipients = recipients.len();
+        let dashb_url = format!("{}{}/web", cfg.common.web_url, cfg.common.base_uri);
         if !cfg.common.report_server_url.is_empty() {
+            if !storage_paths.is_empty() {
+                log::warn!(
+                    "[REPORT] storage destinations of the report {} are skipped, the report server only sends emails",
+                    &self.name
+                );
+            }
+            if self.panel_data.is_some() {
+                log::warn!(
+                    "[REPORT] panel data of the report {} is skipped, the report server only sends the dashboard",
+                    &self.name
+                );
+            }
+            if recipients.is_empty() {
+                return send_webhooks(self, &webhooks, &dashb_url, &[]).await;
+            }
             let report_data = HttpReportPayload {
                 dashboards: self.dashboards.clone(),
                 email_details: ReportEmailDetails {
@@ -367,7 +485,7 @@ impl SendReport for Report {
                     recipients,
                     name: self.name.clone(),
                     message: self.message.clone(),
-                    dashb_url: format!("{}{}/web", cfg.common.web_url, cfg.common.base_uri),
+                    dashb_url: dashb_url.clone(),
                 },
             };
 
@@ -399,29 +517,129 @@ impl SendReport for Report {
                     return Err(SendReportError::ReportServerClientError(e));
                 }
             }
-            Ok(())
+            send_webhooks(self, &webhooks, &dashb_url, &[]).await
         } else {
             // Currently only one `ReportDashboard` can be captured and sent
             let dashboard = &self.dashboards[0];
-            let report = generate_report(
-                dashboard,
-                &self.org_id,
-                &cfg.common.report_user_name,
-                &cfg.common.report_user_password,
-                &self.timezone,
-                no_of_recipients,
-                &self.name,
-            )
-            .await?;
-            send_email(self, &report.0, report.1).await
+            let data_only = self.panel_data.as_ref().is_some_and(|p| p.data_only);
+            let (pdf_data, dashb_url) = if data_only {
+                let (start_time, end_time) = report_time_range(&dashboard.timerange)?;
+                let url = dashboard_link(
+                    dashboard,
+                    &self.org_id,
+                    &self.timezone,
+                    start_time,
+                    end_time,
+                )
+                .await;
+                (None, url)
+            } else {
+                let (pdf_data, url) = generate_report(
+                    dashboard,
+                    &self.org_id,
+                    &cfg.common.report_user_name,
+                    &cfg.common.report_user_password,
+                    &self.timezone,
+                    no_of_recipients + storage_paths.len(),
+                    &self.name,
+                )
+                .await?;
+                (Some(pdf_data), url)
+            };
+            let data_files = match self.panel_data.as_ref() {
+                Some(panel_data) if no_of_recipients + storage_paths.len() > 0 => {
+                    let (start_time, end_time) = report_time_range(&dashboard.timerange)?;
+                    panel_data::export(
+                        &self.org_id,
+                        &dashboard.dashboard,
+                        &cfg.common.report_user_name,
+                        &dashboard.tabs,
+                        &dashboard.variables,
+                        start_time,
+                        end_time,
+                        panel_data,
+                    )
+                    .await?
+                }
+                _ => vec![],
+            };
+            if no_of_recipients > 0 {
+                send_email(self, pdf_data.as_deref(), &data_files, dashb_url.clone()).await?;
+            }
+            let mut artifacts = Vec::with_capacity(storage_paths.len());
+            let timestamp = now_micros();
+            for path in storage_paths {
+                let (Some(file), Some(dir)) = (
+                    report_storage_path(&self.org_id, path, &self.name, timestamp),
+                    report_storage_dir(&self.org_id, path),
+                ) else {
+                    continue;
+                };
+                if let Some(pdf_data) = pdf_data.as_ref() {
+                    upload(&file, pdf_data.clone()).await?;
+                    artifacts.push(file);
+                }
+                // the data files of a run are kept together in a directory next to the pdf
+                for data_file in data_files.iter() {
+                    let file = format!("{dir}/{}_{timestamp}/{}", self.name, data_file.name);
+                    upload(&file, data_file.data.clone()).await?;
+                    artifacts.push(file);
+                }
+            }
+            send_webhooks(self, &webhooks, &dashb_url, &artifacts).await
+        }
+    }
+}
+
+async fn upload(file: &str, data: Vec<u8>) -> Result<(), SendReportError> {
+    infra::storage::put("", file, data.into())
+        .await
+        .map_err(|e| SendReportError::UploadReportError(file.to_string(), e.to_string()))
+}
+
+/// Posts the report message to the Slack and Teams webhooks, both accept a `text` payload.
+async fn send_webhooks(
+    report: &Report,
+    webhooks: &[&ReportWebhook],
+    dashb_url: &str,
+    artifacts: &[String],
+) -> Result<(), SendReportError> {
+    if webhooks.is_empty() {
+        return Ok(());
+    }
+    let client = Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| SendReportError::WebhookError(e.to_string()))?;
+    for webhook in webhooks {
+        let text = render_report_message(&webhook.template, report, dashb_url, artifacts);
+        let resp = client
+            .post(&webhook.url)
+            .json(&json::json!({ "text": text }))
+            .send()
+            .await
+            .map_err(|e| SendReportError::WebhookError(e.to_string()))?;
+        if !resp.status().is_success() {
+            return Err(SendReportError::WebhookError(format!(
+                "status: {}, body: {}",
+                resp.status(),
+                resp.text().await.unwrap_or_default()
+            )));
         }
     }
+    log::info!(
+        "webhook messages sent successfully for the report {}",
+        &report.name
+    );
+    Ok(())
 }
 
-/// Sends emails to the [`Report`] recipients. Currently only one pdf data is supported.
+/// Sends emails to the [`Report`] recipients with the rendered dashboard and the panel data
+/// attached. Currently only one pdf data is supported.
 async fn send_email(
     report: &Report,
-    pdf_data: &[u8],
+    pdf_data: Option<&[u8]>,
+    data_files: &[PanelDataFile],
     dashb_url: String,
 ) -> Result<(), SendReportError> {
     let cfg = get_config();
@@ -429,12 +647,14 @@ async fn send_email(
         return Err(SendReportError::SmtpNotEnabled);
     }
 
-    let mut recipients = vec![];
-    for recipient in &report.destinations {
-        match recipient {
-            ReportDestination::Email(email) => recipients.push(email),
-        }
-    }
+    let recipients = report
+        .destinations
+        .iter()
+        .filter_map(|d| match d {
+            ReportDestination::Email(email) => Some(email),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
 
     if recipients.is_empty() {
         return Ok(());
@@ -452,23 +672,31 @@ async fn send_email(
         email = email.reply_to(cfg.smtp.smtp_reply_to.parse()?);
     }
 
-    let email = email
-        .multipart(
-            MultiPart::mixed()
-                .singlepart(SinglePart::html(format!(
-                    "{}\n\n<p><a href='{dashb_url}' target='_blank'>Link to dashboard</a></p>",
-                    report.message
-                )))
-                .singlepart(
-                    // Only supports PDF for now, attach the PDF
-                    lettre::message::Attachment::new(format!(
-                        "{}.pdf",
-                        sanitize_filename(&report.title)
-                    ))
-                    .body(pdf_data.to_owned(), ContentType::parse("application/pdf")?),
-                ),
-        )
-        .unwrap();
+    let mut body = MultiPart::mixed().singlepart(SinglePart::html(format!(
+        "{}\n\n<p><a href='{dashb_url}' target='_blank'>Link to dashboard</a></p>",
+        report.message
+    )));
+    if let Some(pdf_data) = pdf_data {
+        body = body.singlepart(
+            lettre::message::Attachment::new(format!("{}.pdf", sanitize_filename(&report.title)))
+                .body(pdf_data.to_owned(), ContentType::parse("application/pdf")?),
+        );
+    }
+    if !data_files.is_empty() {
+        let format = report
+            .panel_data
+            .as_ref()
+            .map(|p| p.format)
+            .unwrap_or(ReportDataFormat::Csv);
+        let content_type = ContentType::parse(format.content_type())?;
+        for data_file in data_files {
+            body = body.singlepart(
+                lettre::message::Attachment::new(data_file.name.clone())
+                    .body(data_file.data.clone(), content_type.clone()),
+            );
+        }
+    }
+    let email = email.multipart(body).unwrap();
 
     // Send the email
     match SMTP_CLIENT.as_ref().unwrap().send(email).await {
@@ -514,6 +742,70 @@ pub enum GenerateReportError {
 
     #[error("span element indicator for data load not rendered yet")]
     DataLoadElementNotRendered,
+
+    #[error(transparent)]
+    RenderError(#[from] RenderError),
+}
+
+/// Start of a relative report time range like `15m` or `4M` ending at `end_time`, months are
+/// counted as 30 days.
+fn relative_start_time(period: &str, end_time: i64) -> Result<i64, GenerateReportError> {
+    let (time_duration, time_unit) = period.split_at(period.len() - 1);
+    let time_duration: i64 = time_duration
+        .parse()
+        .map_err(GenerateReportError::ParseTimeDurationError)?;
+    let duration = match time_unit {
+        "m" => chrono::Duration::try_minutes(time_duration),
+        "h" => chrono::Duration::try_hours(time_duration),
+        "d" => chrono::Duration::try_days(time_duration),
+        "w" => chrono::Duration::try_weeks(time_duration),
+        _ => chrono::Duration::try_days(30 * time_duration),
+    };
+    Ok(end_time - duration.unwrap().num_microseconds().unwrap())
+}
+
+/// Start and end of the data of a report, relative time ranges end now.
+fn report_time_range(timerange: &ReportTimerange) -> Result<(i64, i64), GenerateReportError> {
+    Ok(match timerange.range_type {
+        ReportTimerangeType::Relative => {
+            let end_time = now_micros();
+            (relative_start_time(&timerange.period, end_time)?, end_time)
+        }
+        ReportTimerangeType::Absolute => (timerange.from, timerange.to),
+    })
+}
+
+/// Short link to the dashboard showing the data of the report.
+async fn dashboard_link(
+    dashboard: &ReportDashboard,
+    org_id: &str,
+    timezone: &str,
+    start_time: i64,
+    end_time: i64,
+) -> String {
+    let cfg = get_config();
+    let dashboard_id = &dashboard.dashboard;
+    let folder_id = &dashboard.folder;
+    let tab_id = dashboard
+        .tabs
+        .first()
+        .map(|t| t.as_str())
+        .unwrap_or_default();
+    let mut dashb_vars = "".to_string();
+    for variable in dashboard.variables.iter() {
+        dashb_vars = format!("{}&var-{}={}", dashb_vars, variable.key, variable.value);
+    }
+    let web_url = format!("{}{}/web", cfg.common.web_url, cfg.common.base_uri);
+    let email_dashb_url = format!(
+        "{web_url}/dashboards/view?org_identifier={org_id}&dashboard={dashboard_id}&folder={folder_id}&tab={tab_id}&refresh=Off&from={start_time}&to={end_time}&timezone={timezone}&var-Dynamic+filters=%255B%255D{dashb_vars}",
+    );
+    match short_url::shorten(org_id, &email_dashb_url).await {
+        Ok(short_url) => short_url,
+        Err(e) => {
+            log::error!("Error shortening email dashboard url: {e}");
+            email_dashb_url
+        }
+    }
 }
 
 async fn generate_report(
@@ -526,11 +818,6 @@ async fn generate_report(
     report_name: &str,
 ) -> Result<(Vec<u8>, String), GenerateReportError> {
     let cfg = get_config();
-    // Check if Chrome is enabled, otherwise don't save the report
-    if !cfg.chrome.chrome_enabled {
-        return Err(GenerateReportError::ChromeNotEnabled);
-    }
-
     let dashboard_id = &dashboard.dashboard;
     let folder_id = &dashboard.folder;
 
@@ -544,6 +831,26 @@ async fn generate_report(
         dashb_vars = format!("{}&var-{}={}", dashb_vars, variable.key, variable.value);
     }
 
+    // Without Chrome the dashboard is rendered by the built-in renderer
+    if !cfg.chrome.chrome_enabled {
+        let (start_time, end_time) = report_time_range(&dashboard.timerange)?;
+        let pdf_data = if no_of_recipients != 0 {
+            let req = RenderDashboardRequest {
+                format: RenderFormat::Pdf,
+                start_time,
+                end_time,
+                variables: dashboard.variables.clone(),
+                tabs: vec![tab_id.clone()],
+            };
+            render::draw(org_id, dashboard_id, &cfg.common.report_user_name, &req).await?
+        } else {
+            vec![]
+        };
+        let email_dashb_url =
+            dashboard_link(dashboard, org_id, timezone, start_time, end_time).await;
+        return Ok((pdf_data, email_dashb_url));
+    }
+
     log::info!("launching browser for dashboard {dashboard_id}");
     let (mut browser, mut handler) =
         Browser::launch(get_chrome_launch_options().await.as_ref().unwrap().clone()).await?;
@@ -599,52 +906,12 @@ async fn generate_report(
     let (dashb_url, email_dashb_url) = match timerange.range_type {
         ReportTimerangeType::Relative => {
             let period = &timerange.period;
-            let (time_duration, time_unit) = period.split_at(period.len() - 1);
             let dashb_url = format!(
                 "{web_url}/dashboards/view?org_identifier={org_id}&dashboard={dashboard_id}&folder={folder_id}&tab={tab_id}&refresh=Off&{search_type_params}&period={period}&timezone={timezone}&var-Dynamic+filters=%255B%255D&print=true{dashb_vars}",
             );
 
-            let time_duration: i64 = time_duration
-                .parse()
-                .map_err(GenerateReportError::ParseTimeDurationError)?;
             let end_time = now_micros();
-            let start_time = match time_unit {
-                "m" => {
-                    end_time
-                        - chrono::Duration::try_minutes(time_duration)
-                            .unwrap()
-                            .num_microseconds()
-                            .unwrap()
-                }
-                "h" => {
-                    end_time
-                        - chrono::Duration::try_hours(time_duration)
-                            .unwrap()
-                            .num_microseconds()
-                            .unwrap()
-                }
-                "d" => {
-                    end_time
-                        - chrono::Duration::try_days(time_duration)
-                            .unwrap()
-                            .num_microseconds()
-                            .unwrap()
-                }
-                "w" => {
-                    end_time
-                        - chrono::Duration::try_weeks(time_duration)
-                            .unwrap()
-                            .num_microseconds()
-                            .unwrap()
-                }
-                _ => {
-                    end_time
-                        - chrono::Duration::try_days(30 * time_duration)
-                            .unwrap()
-                            .num_microseconds()
-                            .unwrap()
-                }
-            };
+            let start_time = relative_start_time(period, end_time)?;
 
             let email_dashb_url = format!(
                 "{web_url}/dashboards/view?org_identifier={org_id}&dashboard={dashboard_id}&folder={folder_id}&tab={tab_id}&refresh=Off&from={start_time}&to={end_time}&timezone={timezone}&var-Dynamic+filters=%255B%255D&print=true{dashb_vars}",
@@ -763,7 +1030,7 @@ async fn wait_for_panel_data_load(page: &Page) -> Result<(), GenerateReportError
     }
 }
 
-fn sanitize_filename(filename: &str) -> String {
+pub(super) fn sanitize_filename(filename: &str) -> String {
     filename
         .chars()
         .map(|c| {
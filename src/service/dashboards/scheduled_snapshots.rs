@@ -0,0 +1,378 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Scheduled snapshots of dashboards. A schedule periodically runs the panel queries of a
+//! dashboard and writes the results of each panel as a dataset to the object storage, so past
+//! states of the dashboard can be audited and compared without querying the data again.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use config::{
+    meta::{
+        dashboards::snapshots::{
+            CreateSnapshotScheduleRequest, ScheduledSnapshot, ScheduledSnapshotPanel,
+            SnapshotFormat, SnapshotSchedule,
+        },
+        stream::{FileMeta, StreamType},
+    },
+    utils::{
+        json::{self, Value},
+        parquet::write_recordbatch_to_parquet,
+        record_batch_ext::convert_json_to_record_batch,
+        schema::infer_json_schema_from_values,
+    },
+};
+use infra::storage;
+
+use super::snapshots::{SnapshotError, collect_panels, run_panel_rows};
+use crate::service::db;
+
+/// Rows of a panel query kept in a scheduled snapshot.
+const MAX_DATASET_ROWS: i64 = 10_000;
+/// Shortest time between two runs of a schedule, 5 minutes.
+const MIN_SCHEDULE_FREQUENCY: i64 = 300;
+/// Longest lifetime of the datasets of a run, 1 year.
+const MAX_SCHEDULE_RETENTION: i64 = 365 * 24 * 3600;
+const MANIFEST_FILE: &str = "manifest.json";
+
+fn schedule_prefix(org_id: &str, schedule_id: &str) -> String {
+    format!("dashboard_snapshots/{org_id}/scheduled/{schedule_id}/")
+}
+
+fn run_path(org_id: &str, schedule_id: &str, run_id: &str, file: &str) -> String {
+    format!("{}{run_id}/{file}", schedule_prefix(org_id, schedule_id))
+}
+
+pub async fn create_schedule(
+    org_id: &str,
+    dashboard_id: &str,
+    user_id: &str,
+    req: CreateSnapshotScheduleRequest,
+) -> Result<SnapshotSchedule, SnapshotError> {
+    let period = req.period.unwrap_or(req.frequency);
+    if req.frequency < MIN_SCHEDULE_FREQUENCY {
+        return Err(SnapshotError::InvalidRequest(format!(
+            "frequency must be at least {MIN_SCHEDULE_FREQUENCY} seconds"
+        )));
+    }
+    if period <= 0 {
+        return Err(SnapshotError::InvalidRequest(
+            "period must be positive".to_string(),
+        ));
+    }
+    if req.retention <= 0 || req.retention > MAX_SCHEDULE_RETENTION {
+        return Err(SnapshotError::InvalidRequest(format!(
+            "retention must be between 1 and {MAX_SCHEDULE_RETENTION} seconds"
+        )));
+    }
+    // the dashboard must exist
+    super::get_dashboard(org_id, dashboard_id).await?;
+
+    let now = Utc::now().timestamp_micros();
+    let schedule = SnapshotSchedule {
+        id: hex::encode(rand::random::<[u8; 16]>()),
+        dashboard_id: dashboard_id.to_string(),
+        frequency: req.frequency,
+        period,
+        format: req.format,
+        retention: req.retention,
+        variables: req.variables,
+        tabs: req.tabs,
+        enabled: req.enabled,
+        created_by: user_id.to_string(),
+        created_at: now,
+    };
+    db::dashboard_snapshots::set_schedule(org_id, &schedule).await?;
+    let trigger = db::scheduler::Trigger {
+        org: org_id.to_string(),
+        module: db::scheduler::TriggerModule::DashboardSnapshot,
+        module_key: schedule.id.clone(),
+        next_run_at: now,
+        ..Default::default()
+    };
+    db::scheduler::push(trigger).await?;
+    Ok(schedule)
+}
+
+pub async fn list_schedules(
+    org_id: &str,
+    dashboard_id: &str,
+) -> Result<Vec<SnapshotSchedule>, SnapshotError> {
+    Ok(db::dashboard_snapshots::list_schedules(org_id)
+        .await?
+        .into_iter()
+        .filter(|s| s.dashboard_id == dashboard_id)
+        .collect())
+}
+
+async fn get_schedule(
+    org_id: &str,
+    dashboard_id: &str,
+    schedule_id: &str,
+) -> Result<SnapshotSchedule, SnapshotError> {
+    match db::dashboard_snapshots::get_schedule(org_id, schedule_id).await {
+        Ok(schedule) if schedule.dashboard_id == dashboard_id => Ok(schedule),
+        _ => Err(SnapshotError::NotFound),
+    }
+}
+
+/// Deletes the schedule along with the datasets of all its runs.
+pub async fn delete_schedule(
+    org_id: &str,
+    dashboard_id: &str,
+    schedule_id: &str,
+) -> Result<(), SnapshotError> {
+    get_schedule(org_id, dashboard_id, schedule_id).await?;
+    if let Err(e) = db::scheduler::delete(
+        org_id,
+        db::scheduler::TriggerModule::DashboardSnapshot,
+        schedule_id,
+    )
+    .await
+    {
+        log::warn!("[SNAPSHOT] delete trigger of schedule {org_id}/{schedule_id} error: {e}");
+    }
+    let files = storage::list("", &schedule_prefix(org_id, schedule_id))
+        .await
+        .map_err(|e| SnapshotError::StorageError(e.to_string()))?;
+    if !files.is_empty() {
+        let files = files.iter().map(|f| ("", f.as_str())).collect::<Vec<_>>();
+        if let Err(e) = storage::del(files).await {
+            log::warn!("[SNAPSHOT] delete datasets of schedule {org_id}/{schedule_id} error: {e}");
+        }
+    }
+    Ok(db::dashboard_snapshots::delete_schedule(org_id, schedule_id).await?)
+}
+
+/// Runs the panel queries of the dashboard of the schedule over the last `period` seconds and
+/// writes a dataset per panel followed by the manifest of the run.
+pub async fn run(
+    org_id: &str,
+    schedule: &SnapshotSchedule,
+) -> Result<ScheduledSnapshot, SnapshotError> {
+    let dashboard = super::get_dashboard(org_id, &schedule.dashboard_id).await?;
+    let now = Utc::now().timestamp_micros();
+    let start_time = now - schedule.period * 1_000_000;
+    let run_id = now.to_string();
+    let variables = schedule
+        .variables
+        .iter()
+        .map(|v| (v.key.as_str(), v.value.as_str()))
+        .collect::<Vec<_>>();
+
+    let mut panels = Vec::new();
+    for (i, panel) in collect_panels(&dashboard, &schedule.tabs)
+        .into_iter()
+        .enumerate()
+    {
+        let mut snapshot_panel = ScheduledSnapshotPanel {
+            tab: panel.tab.clone(),
+            title: panel.title.clone(),
+            file: None,
+            rows: 0,
+            error: None,
+        };
+        let hits = match run_panel_rows(
+            org_id,
+            &schedule.created_by,
+            &panel,
+            &variables,
+            start_time,
+            now,
+            MAX_DATASET_ROWS,
+        )
+        .await
+        {
+            Ok(hits) => hits,
+            Err(e) => {
+                snapshot_panel.error = Some(e);
+                panels.push(snapshot_panel);
+                continue;
+            }
+        };
+        snapshot_panel.rows = hits.len();
+        if !hits.is_empty() {
+            let file = format!("panel_{i}.{}", schedule.format.extension());
+            let data = encode(schedule.format, hits)
+                .await
+                .map_err(|e| SnapshotError::StorageError(e.to_string()))?;
+            storage::put("", &run_path(org_id, &schedule.id, &run_id, &file), data)
+                .await
+                .map_err(|e| SnapshotError::StorageError(e.to_string()))?;
+            snapshot_panel.file = Some(file);
+        }
+        panels.push(snapshot_panel);
+    }
+
+    let snapshot = ScheduledSnapshot {
+        id: run_id,
+        schedule_id: schedule.id.clone(),
+        dashboard_id: schedule.dashboard_id.clone(),
+        title: dashboard.title().unwrap_or_default().to_string(),
+        format: schedule.format,
+        created_at: now,
+        expires_at: now + schedule.retention * 1_000_000,
+        start_time,
+        end_time: now,
+        panels,
+    };
+    // the manifest is written last, a run without manifest is incomplete and not listed
+    storage::put(
+        "",
+        &run_path(org_id, &schedule.id, &snapshot.id, MANIFEST_FILE),
+        json::to_vec(&snapshot)
+            .map_err(|e| SnapshotError::StorageError(e.to_string()))?
+            .into(),
+    )
+    .await
+    .map_err(|e| SnapshotError::StorageError(e.to_string()))?;
+    Ok(snapshot)
+}
+
+async fn encode(format: SnapshotFormat, hits: Vec<Value>) -> Result<bytes::Bytes, anyhow::Error> {
+    match format {
+        SnapshotFormat::Json => Ok(json::to_vec(&hits)?.into()),
+        SnapshotFormat::Parquet => {
+            let schema = Arc::new(infer_json_schema_from_values(
+                hits.iter(),
+                StreamType::Logs,
+            )?);
+            let rows = hits.into_iter().map(Arc::new).collect::<Vec<_>>();
+            let batch = convert_json_to_record_batch(&schema, &rows)?;
+            let buf =
+                write_recordbatch_to_parquet(schema, &[batch], &[], &FileMeta::default()).await?;
+            Ok(buf.into())
+        }
+    }
+}
+
+async fn list_all_runs(
+    org_id: &str,
+    schedule_id: &str,
+) -> Result<Vec<ScheduledSnapshot>, SnapshotError> {
+    let manifests = storage::list("", &schedule_prefix(org_id, schedule_id))
+        .await
+        .map_err(|e| SnapshotError::StorageError(e.to_string()))?
+        .into_iter()
+        .filter(|f| f.ends_with(&format!("/{MANIFEST_FILE}")));
+    let mut runs = Vec::new();
+    for file in manifests {
+        match storage::get_bytes("", &file).await {
+            Ok(data) => match json::from_slice::<ScheduledSnapshot>(&data) {
+                Ok(run) => runs.push(run),
+                Err(e) => log::warn!("[SNAPSHOT] parse manifest {file} error: {e}"),
+            },
+            Err(e) => log::warn!("[SNAPSHOT] get manifest {file} error: {e}"),
+        }
+    }
+    runs.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(runs)
+}
+
+/// Lists the runs of the schedule that didn't expire yet, newest first.
+pub async fn list_runs(
+    org_id: &str,
+    dashboard_id: &str,
+    schedule_id: &str,
+) -> Result<Vec<ScheduledSnapshot>, SnapshotError> {
+    get_schedule(org_id, dashboard_id, schedule_id).await?;
+    let now = Utc::now().timestamp_micros();
+    Ok(list_all_runs(org_id, schedule_id)
+        .await?
+        .into_iter()
+        .filter(|r| r.expires_at > now)
+        .collect())
+}
+
+/// Gets the dataset of a panel of a run, the file must be listed in the manifest of the run.
+pub async fn get_dataset(
+    org_id: &str,
+    dashboard_id: &str,
+    schedule_id: &str,
+    run_id: &str,
+    file: &str,
+) -> Result<(SnapshotFormat, bytes::Bytes), SnapshotError> {
+    get_schedule(org_id, dashboard_id, schedule_id).await?;
+    let manifest = storage::get_bytes("", &run_path(org_id, schedule_id, run_id, MANIFEST_FILE))
+        .await
+        .map_err(|_| SnapshotError::NotFound)?;
+    let run = json::from_slice::<ScheduledSnapshot>(&manifest)
+        .map_err(|e| SnapshotError::StorageError(e.to_string()))?;
+    if run.expires_at <= Utc::now().timestamp_micros()
+        || !run.panels.iter().any(|p| p.file.as_deref() == Some(file))
+    {
+        return Err(SnapshotError::NotFound);
+    }
+    let data = storage::get_bytes("", &run_path(org_id, schedule_id, run_id, file))
+        .await
+        .map_err(|e| SnapshotError::StorageError(e.to_string()))?;
+    Ok((run.format, data))
+}
+
+/// Deletes the expired runs of the schedules of all orgs.
+pub async fn clean_expired() -> Result<(), anyhow::Error> {
+    let now = Utc::now().timestamp_micros();
+    for (org_id, schedule) in db::dashboard_snapshots::list_all_schedules().await? {
+        let expired = list_all_runs(&org_id, &schedule.id)
+            .await?
+            .into_iter()
+            .filter(|r| r.expires_at <= now);
+        for run in expired {
+            let paths = run
+                .panels
+                .iter()
+                .filter_map(|p| p.file.as_deref())
+                .chain(std::iter::once(MANIFEST_FILE))
+                .map(|f| run_path(&org_id, &schedule.id, &run.id, f))
+                .collect::<Vec<_>>();
+            let files = paths.iter().map(|p| ("", p.as_str())).collect::<Vec<_>>();
+            if let Err(e) = storage::del(files).await {
+                log::error!(
+                    "[SNAPSHOT] delete expired run {org_id}/{}/{} error: {e}",
+                    schedule.id,
+                    run.id
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use config::utils::json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_run_path() {
+        assert_eq!(
+            run_path("default", "abc", "1700000000000000", MANIFEST_FILE),
+            "dashboard_snapshots/default/scheduled/abc/1700000000000000/manifest.json"
+        );
+        assert!(
+            run_path("default", "abc", "1", "panel_0.json")
+                .starts_with(&schedule_prefix("default", "abc"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_encode_json() {
+        let hits = vec![json!({"host": "web-1", "count": 3})];
+        let data = encode(SnapshotFormat::Json, hits.clone()).await.unwrap();
+        assert_eq!(json::from_slice::<Vec<Value>>(&data).unwrap(), hits);
+    }
+}
@@ -0,0 +1,234 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Read-only links to dashboards for viewers without an account. A link carries a token signed
+//! with a key of the dashboard, so deleting the key revokes all the links of the dashboard. The
+//! token pins the tabs and variables, the viewer only picks the time range, and the panel queries
+//! run as the user who shared the dashboard, so that their row policies and column masks apply.
+//! The links stop working once that user is removed from the organization.
+
+use chrono::Utc;
+use config::{
+    get_config,
+    meta::dashboards::{
+        acl::DashboardAccess,
+        render::{RenderDashboardRequest, RenderFormat},
+        share::{
+            CreateShareRequest, CreateShareResponse, DashboardShareClaims, DashboardShareKey,
+            ShareFormat,
+        },
+    },
+};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
+
+use super::{
+    DashboardError, acl,
+    render::{self, RenderError},
+    snapshots::{render_html, run_panels},
+};
+use crate::service::db;
+
+/// Longest lifetime of a share link, 30 days.
+const MAX_SHARE_TTL: i64 = 30 * 24 * 3600;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ShareError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error(transparent)]
+    DashboardError(#[from] DashboardError),
+
+    #[error("Invalid share request: {0}")]
+    InvalidRequest(String),
+
+    #[error("Invalid token: {0}")]
+    InvalidToken(String),
+}
+
+impl From<RenderError> for ShareError {
+    fn from(e: RenderError) -> Self {
+        match e {
+            RenderError::DashboardError(e) => ShareError::DashboardError(e),
+            RenderError::InvalidRequest(e) => ShareError::InvalidRequest(e),
+        }
+    }
+}
+
+pub fn share_url(org_id: &str, dashboard_id: &str, token: &str) -> String {
+    let cfg = get_config();
+    format!(
+        "{}{}/share/{org_id}/{dashboard_id}?token={token}",
+        cfg.common.web_url, cfg.common.base_uri
+    )
+}
+
+/// Issues a token for a read-only link to the dashboard.
+pub async fn create(
+    org_id: &str,
+    dashboard_id: &str,
+    user_id: &str,
+    req: CreateShareRequest,
+) -> Result<CreateShareResponse, ShareError> {
+    if req.expires_in <= 0 || req.expires_in > MAX_SHARE_TTL {
+        return Err(ShareError::InvalidRequest(format!(
+            "expires_in must be between 1 and {MAX_SHARE_TTL} seconds"
+        )));
+    }
+    super::get_dashboard(org_id, dashboard_id).await?;
+
+    let key = match db::dashboard_share_keys::get(org_id, dashboard_id).await {
+        Ok(key) => key,
+        Err(_) => {
+            let key = DashboardShareKey {
+                secret: hex::encode(rand::random::<[u8; 32]>()),
+                created_at: Utc::now().timestamp_micros(),
+            };
+            db::dashboard_share_keys::set(org_id, dashboard_id, &key).await?;
+            key
+        }
+    };
+
+    let now = Utc::now().timestamp();
+    let claims = DashboardShareClaims {
+        org: org_id.to_string(),
+        dashboard: dashboard_id.to_string(),
+        created_by: user_id.to_string(),
+        tabs: req.tabs,
+        variables: req.variables,
+        iat: now,
+        exp: now + req.expires_in,
+    };
+    let token = encode(
+        &Header::new(Algorithm::HS256),
+        &claims,
+        &EncodingKey::from_secret(key.secret.as_bytes()),
+    )
+    .map_err(|e| ShareError::InvalidToken(e.to_string()))?;
+    Ok(CreateShareResponse {
+        url: share_url(org_id, dashboard_id, &token),
+        token,
+        expires_at: claims.exp,
+    })
+}
+
+/// Revokes all the links to the dashboard.
+pub async fn revoke(org_id: &str, dashboard_id: &str) -> Result<(), ShareError> {
+    if db::dashboard_share_keys::get(org_id, dashboard_id)
+        .await
+        .is_err()
+    {
+        return Ok(());
+    }
+    Ok(db::dashboard_share_keys::delete(org_id, dashboard_id).await?)
+}
+
+/// Checks that the token was issued for the dashboard and is not expired or revoked.
+async fn verify(
+    org_id: &str,
+    dashboard_id: &str,
+    token: &str,
+) -> Result<DashboardShareClaims, ShareError> {
+    let key = db::dashboard_share_keys::get(org_id, dashboard_id)
+        .await
+        .map_err(|_| ShareError::InvalidToken("the dashboard is not shared".to_string()))?;
+    let claims = decode::<DashboardShareClaims>(
+        token,
+        &DecodingKey::from_secret(key.secret.as_bytes()),
+        &Validation::new(Algorithm::HS256),
+    )
+    .map_err(|e| ShareError::InvalidToken(e.to_string()))?
+    .claims;
+    if claims.org != org_id || claims.dashboard != dashboard_id {
+        return Err(ShareError::InvalidToken(
+            "token is not valid for this dashboard".to_string(),
+        ));
+    }
+    Ok(claims)
+}
+
+/// Returns the user the panel queries of the shared dashboard run as, that is the user who
+/// shared it as long as they are still part of the organization and can view the dashboard.
+async fn sharer(org_id: &str, claims: &DashboardShareClaims) -> Result<String, ShareError> {
+    match db::user::get(Some(org_id), &claims.created_by).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Err(ShareError::InvalidToken(
+                "the user who shared the dashboard no longer exists".to_string(),
+            ));
+        }
+        Err(e) => return Err(infra::errors::Error::Message(e.to_string()).into()),
+    }
+    match acl::check(
+        org_id,
+        &claims.dashboard,
+        &claims.created_by,
+        DashboardAccess::View,
+    )
+    .await
+    {
+        Ok(()) => Ok(claims.created_by.clone()),
+        Err(DashboardError::PermissionDenied | DashboardError::UserNotFound) => Err(
+            ShareError::InvalidToken("the user who shared the dashboard can't view it".to_string()),
+        ),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Runs the panel queries of a shared dashboard and renders the results in the requested format.
+pub async fn render(
+    org_id: &str,
+    dashboard_id: &str,
+    token: &str,
+    format: ShareFormat,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<u8>, ShareError> {
+    let claims = verify(org_id, dashboard_id, token).await?;
+    if start_time >= end_time {
+        return Err(ShareError::InvalidRequest(
+            "start_time must be before end_time".to_string(),
+        ));
+    }
+    let identity = sharer(org_id, &claims).await?;
+    let format = match format {
+        ShareFormat::Html => {
+            let dashboard = super::get_dashboard(org_id, dashboard_id).await?;
+            let title = dashboard.title().unwrap_or_default().to_string();
+            let results = run_panels(
+                org_id,
+                &identity,
+                &dashboard,
+                &claims.tabs,
+                &claims.variables,
+                start_time,
+                end_time,
+            )
+            .await;
+            let now = Utc::now().timestamp_micros();
+            return Ok(render_html(&title, start_time, end_time, now, &results).into_bytes());
+        }
+        ShareFormat::Pdf => RenderFormat::Pdf,
+        ShareFormat::Png => RenderFormat::Png,
+    };
+    let req = RenderDashboardRequest {
+        format,
+        start_time,
+        end_time,
+        variables: claims.variables,
+        tabs: claims.tabs,
+    };
+    Ok(render::render(org_id, dashboard_id, &identity, &req).await?)
+}
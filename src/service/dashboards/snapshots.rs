@@ -0,0 +1,772 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Static html snapshots of dashboards. The panel queries are run once over the requested time
+//! range and their results rendered as tables into a single html page, so the snapshot keeps
+//! showing the same data after the underlying data changes or expires.
+
+use chrono::{TimeZone, Utc};
+use config::{
+    get_config, ider,
+    meta::{
+        dashboards::{
+            Dashboard,
+            reports::ReportDashboardVariable,
+            snapshots::{CreateSnapshotRequest, CreateSnapshotResponse, DashboardSnapshot},
+            transformations::{self, Transformation},
+            v5::{
+                AUTO_INTERVAL, BUILTIN_INTERVAL_VARIABLE, IntervalVariable, Threshold,
+                ThresholdQuery, format_interval, panel_width, threshold_color,
+            },
+        },
+        search::{self, SearchEventType},
+        stream::StreamType,
+    },
+    utils::json::{self, Value},
+};
+use infra::storage;
+
+use super::{DashboardError, format::ValueFormat};
+use crate::service::{db, search as SearchService};
+
+/// Rows of a panel query kept in the snapshot.
+const MAX_PANEL_ROWS: i64 = 100;
+/// Longest lifetime of a snapshot, 90 days.
+const MAX_SNAPSHOT_TTL: i64 = 90 * 24 * 3600;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error(transparent)]
+    DashboardError(#[from] DashboardError),
+
+    #[error("Snapshot not found")]
+    NotFound,
+
+    #[error("Invalid snapshot request: {0}")]
+    InvalidRequest(String),
+
+    #[error("Storage error: {0}")]
+    StorageError(String),
+}
+
+/// A panel of the dashboard as needed to render it, independent of the dashboard version.
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct SnapshotPanel {
+    pub(super) tab: String,
+    pub(super) title: String,
+    pub(super) panel_type: String,
+    pub(super) query_type: String,
+    pub(super) queries: Vec<(String, StreamType)>,
+    /// Aliases of the x, y and breakdown fields of the first query
+    pub(super) x_axis: Vec<String>,
+    pub(super) y_axis: Vec<String>,
+    pub(super) breakdown: Vec<String>,
+    pub(super) text: Option<String>,
+    /// Regional formatting of the values, `None` keeps the values as the query returns them
+    pub(super) format: Option<ValueFormat>,
+    pub(super) transformations: Vec<Transformation>,
+    /// Static thresholds of the panel, extended with those of the threshold query once it ran
+    pub(super) thresholds: Vec<Threshold>,
+    pub(super) threshold_query: Option<ThresholdQuery>,
+    /// Min and max of the first query, the range of the percentage thresholds
+    pub(super) value_range: Option<(f64, f64)>,
+    /// Width of the panel in grid columns, 0 for the full width
+    pub(super) columns: i64,
+    /// `interval` variables of the dashboard and the built-in `__interval`
+    pub(super) intervals: Vec<(String, IntervalVariable)>,
+}
+
+impl SnapshotPanel {
+    /// Color of a value by the thresholds of the panel. Only the y axis values of metric and
+    /// gauge panels are colored.
+    pub(super) fn threshold_color(&self, column: &str, value: Option<&Value>) -> Option<&str> {
+        if self.thresholds.is_empty() || !matches!(self.panel_type.as_str(), "metric" | "gauge") {
+            return None;
+        }
+        if !self.y_axis.is_empty() && !self.y_axis.iter().any(|y| y == column) {
+            return None;
+        }
+        let value = match value? {
+            Value::Number(n) => n.as_f64()?,
+            Value::String(s) => s.parse().ok()?,
+            _ => return None,
+        };
+        let (min, max) = self.value_range.unwrap_or((0.0, 100.0));
+        threshold_color(&self.thresholds, value, min, max)
+    }
+}
+
+/// Steps of the `interval` variables taking the automatic step, those set to `auto` or without
+/// value when they have an automatic step, sized for the time range and the panel width.
+fn auto_intervals(
+    panel: &SnapshotPanel,
+    variables: &[(&str, &str)],
+    start_time: i64,
+    end_time: i64,
+) -> Vec<(String, String)> {
+    let width = panel_width(panel.columns);
+    panel
+        .intervals
+        .iter()
+        .filter(|(name, interval)| {
+            let value = variables.iter().find(|(k, _)| *k == name.as_str());
+            value.map_or(interval.auto, |(_, v)| *v == AUTO_INTERVAL)
+        })
+        .map(|(name, interval)| {
+            let step = interval.auto_step(start_time, end_time, width);
+            (name.clone(), format_interval(step))
+        })
+        .collect()
+}
+
+fn snapshot_path(org_id: &str, id: &str) -> String {
+    format!("dashboard_snapshots/{org_id}/{id}.html")
+}
+
+pub fn snapshot_url(org_id: &str, id: &str) -> String {
+    let cfg = get_config();
+    format!(
+        "{}{}/snapshots/{org_id}/{id}",
+        cfg.common.web_url, cfg.common.base_uri
+    )
+}
+
+/// Runs the panel queries of the dashboard and stores the rendered page.
+pub async fn create(
+    org_id: &str,
+    dashboard_id: &str,
+    user_id: &str,
+    req: CreateSnapshotRequest,
+) -> Result<CreateSnapshotResponse, SnapshotError> {
+    if req.start_time >= req.end_time {
+        return Err(SnapshotError::InvalidRequest(
+            "start_time must be before end_time".to_string(),
+        ));
+    }
+    if req.expires_in <= 0 || req.expires_in > MAX_SNAPSHOT_TTL {
+        return Err(SnapshotError::InvalidRequest(format!(
+            "expires_in must be between 1 and {MAX_SNAPSHOT_TTL} seconds"
+        )));
+    }
+
+    let dashboard = super::get_dashboard(org_id, dashboard_id).await?;
+    let title = dashboard.title().unwrap_or_default().to_string();
+    let results = run_panels(
+        org_id,
+        user_id,
+        &dashboard,
+        &req.tabs,
+        &req.variables,
+        req.start_time,
+        req.end_time,
+    )
+    .await;
+
+    let now = Utc::now().timestamp_micros();
+    let html = render_html(&title, req.start_time, req.end_time, now, &results);
+    let snapshot = DashboardSnapshot {
+        id: hex::encode(rand::random::<[u8; 16]>()),
+        dashboard_id: dashboard_id.to_string(),
+        title,
+        created_by: user_id.to_string(),
+        created_at: now,
+        expires_at: now + req.expires_in * 1_000_000,
+        start_time: req.start_time,
+        end_time: req.end_time,
+        size: html.len(),
+    };
+    storage::put("", &snapshot_path(org_id, &snapshot.id), html.into())
+        .await
+        .map_err(|e| SnapshotError::StorageError(e.to_string()))?;
+    db::dashboard_snapshots::set(org_id, &snapshot).await?;
+
+    Ok(CreateSnapshotResponse {
+        url: snapshot_url(org_id, &snapshot.id),
+        snapshot,
+    })
+}
+
+pub async fn list(
+    org_id: &str,
+    dashboard_id: &str,
+) -> Result<Vec<DashboardSnapshot>, SnapshotError> {
+    let now = Utc::now().timestamp_micros();
+    Ok(db::dashboard_snapshots::list(org_id)
+        .await?
+        .into_iter()
+        .filter(|s| s.dashboard_id == dashboard_id && s.expires_at > now)
+        .collect())
+}
+
+/// Gets the html page of a snapshot that didn't expire yet.
+pub async fn get_html(org_id: &str, id: &str) -> Result<bytes::Bytes, SnapshotError> {
+    let snapshot = db::dashboard_snapshots::get(org_id, id)
+        .await
+        .map_err(|_| SnapshotError::NotFound)?;
+    if snapshot.expires_at <= Utc::now().timestamp_micros() {
+        return Err(SnapshotError::NotFound);
+    }
+    storage::get_bytes("", &snapshot_path(org_id, id))
+        .await
+        .map_err(|e| SnapshotError::StorageError(e.to_string()))
+}
+
+/// Deletes the snapshot of the dashboard.
+pub async fn delete(org_id: &str, dashboard_id: &str, id: &str) -> Result<(), SnapshotError> {
+    match db::dashboard_snapshots::get(org_id, id).await {
+        Ok(snapshot) if snapshot.dashboard_id == dashboard_id => remove(org_id, id).await,
+        _ => Err(SnapshotError::NotFound),
+    }
+}
+
+async fn remove(org_id: &str, id: &str) -> Result<(), SnapshotError> {
+    let path = snapshot_path(org_id, id);
+    if let Err(e) = storage::del(vec![("", path.as_str())]).await {
+        log::warn!("[SNAPSHOT] delete snapshot page {path} error: {e}");
+    }
+    Ok(db::dashboard_snapshots::delete(org_id, id).await?)
+}
+
+/// Deletes the expired snapshots of all orgs.
+pub async fn clean_expired() -> Result<(), anyhow::Error> {
+    let now = Utc::now().timestamp_micros();
+    let expired = db::dashboard_snapshots::list_all()
+        .await?
+        .into_iter()
+        .filter(|(_, s)| s.expires_at <= now);
+    for (org_id, snapshot) in expired {
+        if let Err(e) = remove(&org_id, &snapshot.id).await {
+            log::error!(
+                "[SNAPSHOT] delete expired snapshot {org_id}/{} error: {e}",
+                snapshot.id
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Runs the queries of the panels of the tabs, each panel with the hits of its queries or the
+/// error of the failing query.
+pub(super) async fn run_panels(
+    org_id: &str,
+    user_id: &str,
+    dashboard: &Dashboard,
+    tabs: &[String],
+    variables: &[ReportDashboardVariable],
+    start_time: i64,
+    end_time: i64,
+) -> Vec<(SnapshotPanel, Result<Vec<Value>, String>)> {
+    let variables = variables
+        .iter()
+        .map(|v| (v.key.as_str(), v.value.as_str()))
+        .collect::<Vec<_>>();
+    let mut results = Vec::new();
+    for mut panel in collect_panels(dashboard, tabs) {
+        resolve_thresholds(
+            org_id, user_id, &mut panel, &variables, start_time, end_time,
+        )
+        .await;
+        let data = run_panel(org_id, user_id, &panel, &variables, start_time, end_time).await;
+        results.push((panel, data));
+    }
+    results
+}
+
+/// Runs the queries of the panel, the hits of all queries are returned together.
+async fn run_panel(
+    org_id: &str,
+    user_id: &str,
+    panel: &SnapshotPanel,
+    variables: &[(&str, &str)],
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<Value>, String> {
+    run_panel_rows(
+        org_id,
+        user_id,
+        panel,
+        variables,
+        start_time,
+        end_time,
+        MAX_PANEL_ROWS,
+    )
+    .await
+}
+
+/// Runs the queries of the panel keeping up to `size` rows of each query.
+pub(super) async fn run_panel_rows(
+    org_id: &str,
+    user_id: &str,
+    panel: &SnapshotPanel,
+    variables: &[(&str, &str)],
+    start_time: i64,
+    end_time: i64,
+    size: i64,
+) -> Result<Vec<Value>, String> {
+    if panel.query_type == "promql" {
+        return Err("PromQL panels are not included in snapshots".to_string());
+    }
+    let steps = auto_intervals(panel, variables, start_time, end_time);
+    let variables = steps
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .chain(variables.iter().copied())
+        .collect::<Vec<_>>();
+    let variables = query_variables(&variables);
+    let mut hits = Vec::new();
+    for (sql, stream_type) in panel.queries.iter() {
+        let search_req = search::Request {
+            query: search::Query {
+                sql: search::substitute_variables(sql, &variables)?,
+                from: 0,
+                size,
+                start_time,
+                end_time,
+                ..Default::default()
+            },
+            search_type: Some(SearchEventType::Dashboards),
+            ..Default::default()
+        };
+        let trace_id = ider::generate_trace_id();
+        let resp = SearchService::search(
+            &trace_id,
+            org_id,
+            *stream_type,
+            Some(user_id.to_string()),
+            &search_req,
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+        hits.extend(transformations::apply(&panel.transformations, resp.hits));
+    }
+    Ok(hits)
+}
+
+/// Runs the threshold query of the panel and adds the thresholds it produces to the static ones.
+/// The thresholds of a failing query are left out, the panel is still rendered.
+async fn resolve_thresholds(
+    org_id: &str,
+    user_id: &str,
+    panel: &mut SnapshotPanel,
+    variables: &[(&str, &str)],
+    start_time: i64,
+    end_time: i64,
+) {
+    let Some(query) = panel.threshold_query.as_ref() else {
+        return;
+    };
+    let (start_time, end_time) = query.time_range(start_time, end_time);
+    let sql = match search::substitute_variables(&query.query, &query_variables(variables)) {
+        Ok(sql) => sql,
+        Err(e) => {
+            log::warn!(
+                "[DASHBOARD] threshold query of panel {} is invalid: {e}",
+                panel.title
+            );
+            return;
+        }
+    };
+    let search_req = search::Request {
+        query: search::Query {
+            sql,
+            from: 0,
+            size: 1,
+            start_time,
+            end_time,
+            ..Default::default()
+        },
+        search_type: Some(SearchEventType::Dashboards),
+        ..Default::default()
+    };
+    let trace_id = ider::generate_trace_id();
+    match SearchService::search(
+        &trace_id,
+        org_id,
+        query.stream_type,
+        Some(user_id.to_string()),
+        &search_req,
+    )
+    .await
+    {
+        Ok(resp) => {
+            if let Some(row) = resp.hits.first() {
+                let thresholds = query.thresholds(row);
+                panel.thresholds.extend(thresholds);
+            }
+        }
+        Err(e) => log::warn!(
+            "[DASHBOARD] threshold query of panel {} failed: {e}",
+            panel.title
+        ),
+    }
+}
+
+/// Dashboard variables of the panel queries, they are substituted with
+/// [`search::substitute_variables`] so their values are quoted and escaped.
+fn query_variables(variables: &[(&str, &str)]) -> Vec<search::QueryVariable> {
+    variables
+        .iter()
+        .map(|(name, value)| search::QueryVariable {
+            name: name.to_string(),
+            value: Value::String(value.to_string()),
+        })
+        .collect()
+}
+
+/// Collects the panels of any dashboard version, v1 and v2 dashboards have no tabs.
+pub(super) fn collect_panels(dashboard: &Dashboard, tabs: &[String]) -> Vec<SnapshotPanel> {
+    let inner = match dashboard.version {
+        1 => json::to_value(&dashboard.v1),
+        2 => json::to_value(&dashboard.v2),
+        3 => json::to_value(&dashboard.v3),
+        4 => json::to_value(&dashboard.v4),
+        5 => json::to_value(&dashboard.v5),
+        6 => json::to_value(&dashboard.v6),
+        _ => return vec![],
+    }
+    .unwrap_or_default();
+
+    let mut groups = Vec::new();
+    match inner.get("tabs").and_then(|v| v.as_array()) {
+        Some(list) => {
+            for tab in list {
+                let id = tab
+                    .get("tabId")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default();
+                let name = tab.get("name").and_then(|v| v.as_str()).unwrap_or_default();
+                if !tabs.is_empty() && !tabs.iter().any(|t| t == id || t == name) {
+                    continue;
+                }
+                groups.push((name.to_string(), tab.get("panels")));
+            }
+        }
+        None => groups.push((String::new(), inner.get("panels"))),
+    }
+
+    let mut intervals = vec![(
+        BUILTIN_INTERVAL_VARIABLE.to_string(),
+        IntervalVariable {
+            auto: true,
+            min_interval: None,
+        },
+    )];
+    for var in super::variables::dashboard_variables(dashboard)
+        .into_iter()
+        .flat_map(|v| v.list)
+        .filter(|v| v.type_field == "interval")
+    {
+        intervals.push((var.name, var.interval.unwrap_or_default()));
+    }
+
+    let mut panels = Vec::new();
+    for (tab, list) in groups {
+        for panel in list.and_then(|v| v.as_array()).into_iter().flatten() {
+            let str_field = |v: &Value, key: &str| {
+                v.get(key)
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            // v1 panels hold a single query themselves
+            let queries = match panel.get("queries").and_then(|v| v.as_array()) {
+                Some(queries) => queries.iter().collect::<Vec<_>>(),
+                None => vec![panel],
+            };
+            let aliases = |key: &str| {
+                queries
+                    .first()
+                    .and_then(|q| q.get("fields"))
+                    .and_then(|v| v.get(key))
+                    .and_then(|v| v.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|v| v.get("alias").and_then(|v| v.as_str()))
+                    .map(|v| v.to_string())
+                    .collect::<Vec<_>>()
+            };
+            let (x_axis, y_axis, breakdown) = (aliases("x"), aliases("y"), aliases("breakdown"));
+            let value_range = queries.first().and_then(|q| q.get("config")).and_then(|c| {
+                let min = c.get("min").and_then(|v| v.as_f64());
+                let max = c.get("max").and_then(|v| v.as_f64());
+                (min.is_some() || max.is_some()).then(|| (min.unwrap_or(0.0), max.unwrap_or(100.0)))
+            });
+            let queries = queries
+                .into_iter()
+                .filter_map(|q| {
+                    let sql = q.get("query").and_then(|v| v.as_str())?;
+                    if sql.trim().is_empty() {
+                        return None;
+                    }
+                    let stream_type = q
+                        .get("fields")
+                        .and_then(|v| v.get("stream_type"))
+                        .and_then(|v| v.as_str())
+                        .map(StreamType::from)
+                        .unwrap_or_default();
+                    Some((sql.to_string(), stream_type))
+                })
+                .collect();
+            let text = panel
+                .get("markdownContent")
+                .or(panel.get("htmlContent"))
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string());
+            let config = panel.get("config");
+            let format = config.and_then(ValueFormat::from_config);
+            let thresholds = config
+                .and_then(|v| v.get("thresholds"))
+                .and_then(|v| json::from_value(v.clone()).ok())
+                .unwrap_or_default();
+            let threshold_query = config
+                .and_then(|v| v.get("threshold_query"))
+                .and_then(|v| json::from_value::<ThresholdQuery>(v.clone()).ok())
+                .filter(|q| !q.query.trim().is_empty() && !q.steps.is_empty());
+
+            let transformations = panel
+                .get("transformations")
+                .and_then(|v| json::from_value(v.clone()).ok())
+                .unwrap_or_default();
+            let columns = panel
+                .get("layout")
+                .and_then(|v| v.get("w"))
+                .and_then(|v| v.as_i64())
+                .unwrap_or_default();
+            let title = match str_field(panel, "title") {
+                title if title.is_empty() => str_field(panel, "id"),
+                title => title,
+            };
+            panels.push(SnapshotPanel {
+                tab: tab.clone(),
+                title,
+                panel_type: str_field(panel, "type"),
+                query_type: str_field(panel, "queryType"),
+                queries,
+                x_axis,
+                y_axis,
+                breakdown,
+                text,
+                format,
+                transformations,
+                thresholds,
+                threshold_query,
+                value_range,
+                columns,
+                intervals: intervals.clone(),
+            });
+        }
+    }
+    panels
+}
+
+pub(super) fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+pub(super) fn format_time(micros: i64) -> String {
+    Utc.timestamp_micros(micros)
+        .single()
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_default()
+}
+
+fn render_table(out: &mut String, panel: &SnapshotPanel, hits: &[Value]) {
+    if hits.is_empty() {
+        out.push_str("<p class=\"empty\">No data</p>\n");
+        return;
+    }
+    let mut columns: Vec<&str> = Vec::new();
+    for hit in hits {
+        for key in hit.as_object().into_iter().flat_map(|o| o.keys()) {
+            if !columns.contains(&key.as_str()) {
+                columns.push(key);
+            }
+        }
+    }
+    out.push_str("<table>\n<tr>");
+    for column in columns.iter() {
+        out.push_str(&format!("<th>{}</th>", escape_html(column)));
+    }
+    out.push_str("</tr>\n");
+    for hit in hits {
+        out.push_str("<tr>");
+        for column in columns.iter() {
+            let cell = match (panel.format.as_ref(), hit.get(column)) {
+                (Some(format), value) => format.value(column, value),
+                (None, Some(Value::String(s))) => s.clone(),
+                (None, Some(Value::Null) | None) => String::new(),
+                (None, Some(v)) => v.to_string(),
+            };
+            match panel.threshold_color(column, hit.get(column)) {
+                Some(color) => out.push_str(&format!(
+                    "<td style=\"color:{}\">{}</td>",
+                    escape_html(color),
+                    escape_html(&cell)
+                )),
+                None => out.push_str(&format!("<td>{}</td>", escape_html(&cell))),
+            }
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</table>\n");
+}
+
+pub(super) fn render_html(
+    title: &str,
+    start_time: i64,
+    end_time: i64,
+    created_at: i64,
+    results: &[(SnapshotPanel, Result<Vec<Value>, String>)],
+) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str(&format!("<title>{}</title>\n", escape_html(title)));
+    out.push_str(
+        "<style>body{font-family:sans-serif;margin:24px;color:#222}\
+         table{border-collapse:collapse;margin-bottom:16px;font-size:13px}\
+         th,td{border:1px solid #ccc;padding:4px 8px;text-align:left;vertical-align:top}\
+         th{background:#f3f3f3}.meta,.empty{color:#666}.error{color:#b00}\
+         pre{white-space:pre-wrap;background:#f8f8f8;padding:8px}</style>\n",
+    );
+    out.push_str("</head>\n<body>\n");
+    out.push_str(&format!("<h1>{}</h1>\n", escape_html(title)));
+    out.push_str(&format!(
+        "<p class=\"meta\">Data from {} to {}, snapshot taken at {}</p>\n",
+        format_time(start_time),
+        format_time(end_time),
+        format_time(created_at)
+    ));
+
+    let mut current_tab = None;
+    for (panel, data) in results {
+        if !panel.tab.is_empty() && current_tab != Some(&panel.tab) {
+            out.push_str(&format!("<h2>{}</h2>\n", escape_html(&panel.tab)));
+            current_tab = Some(&panel.tab);
+        }
+        out.push_str(&format!("<h3>{}</h3>\n", escape_html(&panel.title)));
+        if let Some(text) = panel.text.as_ref() {
+            out.push_str(&format!("<pre>{}</pre>\n", escape_html(text)));
+            continue;
+        }
+        match data {
+            Ok(hits) => render_table(&mut out, panel, hits),
+            Err(e) => out.push_str(&format!("<p class=\"error\">{}</p>\n", escape_html(e))),
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use config::utils::json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_query_variables() {
+        let sql = "SELECT * FROM logs WHERE host = '$host' AND env = ${env} AND k = '$other'";
+        let variables = query_variables(&[("host", "web-1"), ("env", "x' OR '1'='1")]);
+        assert_eq!(
+            search::substitute_variables(sql, &variables).unwrap(),
+            "SELECT * FROM logs WHERE host = 'web-1' AND env = 'x'' OR ''1''=''1' AND k = '$other'"
+        );
+    }
+
+    #[test]
+    fn test_auto_intervals() {
+        let hour = 3600 * 1_000_000;
+        let panel = SnapshotPanel {
+            columns: 12,
+            intervals: vec![
+                (
+                    BUILTIN_INTERVAL_VARIABLE.to_string(),
+                    IntervalVariable {
+                        auto: true,
+                        min_interval: None,
+                    },
+                ),
+                ("step".to_string(), IntervalVariable::default()),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            auto_intervals(&panel, &[], 0, 2 * hour),
+            vec![("__interval".to_string(), "5 minute".to_string())]
+        );
+        let steps = auto_intervals(&panel, &[("step", "auto")], 0, 2 * hour);
+        assert_eq!(steps[1], ("step".to_string(), "5 minute".to_string()));
+        assert_eq!(
+            auto_intervals(&panel, &[("step", "1 hour")], 0, 2 * hour).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_render_html() {
+        let panel = SnapshotPanel {
+            tab: "Overview".to_string(),
+            title: "Errors <5xx>".to_string(),
+            queries: vec![("SELECT 1".to_string(), StreamType::Logs)],
+            ..Default::default()
+        };
+        let results = vec![(panel, Ok(vec![json!({"code": 500, "msg": "a&b"})]))];
+        let html = render_html("Service", 0, 3600 * 1_000_000, 0, &results);
+        assert!(html.contains("<h2>Overview</h2>"));
+        assert!(html.contains("<h3>Errors &lt;5xx&gt;</h3>"));
+        assert!(html.contains("<th>code</th><th>msg</th>"));
+        assert!(html.contains("<td>500</td><td>a&amp;b</td>"));
+        assert!(html.contains("1970-01-01 01:00:00 UTC"));
+
+        let panel = SnapshotPanel {
+            title: "Revenue".to_string(),
+            format: ValueFormat::from_config(&json!({
+                "unit": "currency",
+                "format_options": {"locale": "de-DE", "currency": "EUR"}
+            })),
+            ..Default::default()
+        };
+        let results = vec![(panel, Ok(vec![json!({"total": 1234.5})]))];
+        let html = render_html("Sales", 0, 1, 0, &results);
+        assert!(html.contains("<td>1.234,50 EUR</td>"));
+        let panel = SnapshotPanel {
+            title: "Latency".to_string(),
+            panel_type: "metric".to_string(),
+            y_axis: vec!["p99".to_string()],
+            thresholds: json::from_value(json!([
+                {"value": 0, "color": "green"},
+                {"value": 200, "color": "red"}
+            ]))
+            .unwrap(),
+            ..Default::default()
+        };
+        let results = vec![(panel, Ok(vec![json!({"p99": 250, "host": "a"})]))];
+        let html = render_html("Service", 0, 1, 0, &results);
+        assert!(html.contains("<td>a</td><td style=\"color:red\">250</td>"));
+    }
+}
@@ -0,0 +1,417 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Resolves the values of the dashboard variables on the server. Variables are resolved level by
+//! level of their dependency graph, so the filters of a variable use the values selected for the
+//! variables it depends on, and the variables of a level are queried concurrently.
+
+use config::{
+    ider,
+    meta::{
+        dashboards::{
+            Dashboard,
+            acl::DashboardAccess,
+            v5::{AUTO_INTERVAL, DASHBOARD_WIDTH, VariableList, Variables, format_interval},
+            variables::{ResolveVariablesRequest, ResolveVariablesResponse, ResolvedVariable},
+        },
+        search::{self, DASHBOARD_ALL, QueryVariable, SearchEventType},
+    },
+    utils::json::{self, Value},
+};
+use futures::future::join_all;
+
+use super::{DashboardError, acl};
+use crate::service::search as SearchService;
+
+/// Options of a `query_values` variable when it doesn't set `max_record_size`.
+const DEFAULT_MAX_OPTIONS: i64 = 10;
+
+#[derive(Debug, thiserror::Error)]
+pub enum VariablesError {
+    #[error(transparent)]
+    DashboardError(#[from] DashboardError),
+
+    #[error("Invalid variables request: {0}")]
+    InvalidRequest(String),
+}
+
+/// Resolves the options and the selected value of every variable of the dashboard, in
+/// dependency order.
+pub async fn resolve(
+    org_id: &str,
+    dashboard_id: &str,
+    user_id: &str,
+    req: &ResolveVariablesRequest,
+) -> Result<ResolveVariablesResponse, VariablesError> {
+    if req.start_time >= req.end_time {
+        return Err(VariablesError::InvalidRequest(
+            "start_time must be before end_time".to_string(),
+        ));
+    }
+    acl::check(org_id, dashboard_id, user_id, DashboardAccess::View).await?;
+    let dashboard = super::get_dashboard(org_id, dashboard_id).await?;
+    let Some(variables) = dashboard_variables(&dashboard) else {
+        return Ok(ResolveVariablesResponse::default());
+    };
+    let levels = variables
+        .dependency_levels()
+        .map_err(VariablesError::InvalidRequest)?;
+
+    let mut resolved: Vec<ResolvedVariable> = Vec::with_capacity(variables.list.len());
+    for level in levels {
+        let values = resolved
+            .iter()
+            .map(|v| QueryVariable {
+                name: v.name.clone(),
+                value: v.value.clone(),
+            })
+            .collect::<Vec<_>>();
+        let tasks = level.iter().map(|var| {
+            resolve_variable(org_id, user_id, var, &values, req.start_time, req.end_time)
+        });
+        for (var, options) in level.iter().zip(join_all(tasks).await) {
+            let (options, error) = match options {
+                Ok(options) => (options, None),
+                Err(e) => (vec![], Some(e)),
+            };
+            let value = match select_value(var, &options, req.values.get(&var.name)) {
+                Value::String(v) if var.type_field == "interval" && v == AUTO_INTERVAL => {
+                    let interval = var.interval.clone().unwrap_or_default();
+                    let width = req.width.unwrap_or(DASHBOARD_WIDTH);
+                    Value::String(format_interval(interval.auto_step(
+                        req.start_time,
+                        req.end_time,
+                        width,
+                    )))
+                }
+                value => value,
+            };
+            resolved.push(ResolvedVariable {
+                name: var.name.clone(),
+                value,
+                options,
+                error,
+            });
+        }
+    }
+    Ok(ResolveVariablesResponse {
+        variables: resolved,
+    })
+}
+
+/// Variables of any dashboard version, the variables of the older versions have the same shape.
+pub(super) fn dashboard_variables(dashboard: &Dashboard) -> Option<Variables> {
+    let inner = match dashboard.version {
+        1 => json::to_value(&dashboard.v1),
+        2 => json::to_value(&dashboard.v2),
+        3 => json::to_value(&dashboard.v3),
+        4 => json::to_value(&dashboard.v4),
+        5 => json::to_value(&dashboard.v5),
+        6 => json::to_value(&dashboard.v6),
+        _ => return None,
+    }
+    .ok()?;
+    json::from_value(inner.get("variables")?.clone()).ok()
+}
+
+/// Options of the variable, `query_values` variables query the distinct values of their field
+/// filtered by the values of the variables resolved before.
+async fn resolve_variable(
+    org_id: &str,
+    user_id: &str,
+    var: &VariableList,
+    values: &[QueryVariable],
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<String>, String> {
+    match var.type_field.as_str() {
+        "custom" => Ok(var
+            .options
+            .iter()
+            .flatten()
+            .map(|o| o.value.clone())
+            .collect()),
+        "interval" => Ok(interval_options(var)),
+        "query_values" => {
+            let Some(query_data) = var.query_data.as_ref() else {
+                return Ok(vec![]);
+            };
+            let sql = values_sql(var)?;
+            let sql = search::substitute_variables(&sql, values)?;
+            let req = search::Request {
+                query: search::Query {
+                    sql,
+                    from: 0,
+                    size: query_data.max_record_size.unwrap_or(DEFAULT_MAX_OPTIONS),
+                    start_time,
+                    end_time,
+                    ..Default::default()
+                },
+                search_type: Some(SearchEventType::Values),
+                ..Default::default()
+            };
+            let resp = SearchService::search(
+                &ider::generate_trace_id(),
+                org_id,
+                query_data.stream_type,
+                Some(user_id.to_string()),
+                &req,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+            Ok(resp
+                .hits
+                .iter()
+                .filter_map(|hit| match hit.get("zo_sql_key") {
+                    Some(Value::String(s)) => Some(s.clone()),
+                    Some(Value::Null) | None => None,
+                    Some(v) => Some(v.to_string()),
+                })
+                .collect())
+        }
+        _ => Ok(vec![]),
+    }
+}
+
+/// Steps of an `interval` variable, `auto` first when the variable has an automatic step.
+fn interval_options(var: &VariableList) -> Vec<String> {
+    let auto = var.interval.as_ref().is_some_and(|i| i.auto);
+    auto.then(|| AUTO_INTERVAL.to_string())
+        .into_iter()
+        .chain(var.options.iter().flatten().map(|o| o.value.clone()))
+        .collect()
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Query of the distinct values of a `query_values` variable, the variables referenced by the
+/// filters are kept to be substituted with their resolved values.
+fn values_sql(var: &VariableList) -> Result<String, String> {
+    let Some(query_data) = var.query_data.as_ref() else {
+        return Err(format!("variable {} has no query", var.name));
+    };
+    let mut conditions = Vec::new();
+    for filter in query_data.filter.iter().flatten() {
+        let Some(name) = filter.name.as_deref().filter(|n| !n.is_empty()) else {
+            continue;
+        };
+        let field = quote_identifier(name);
+        // literal text is escaped here, variables inside quotes are escaped on substitution
+        let value = filter.value.replace('\'', "''");
+        let operator = filter.operator.as_deref().unwrap_or("=");
+        let condition = match operator.to_lowercase().as_str() {
+            op @ ("=" | "!=" | "<>" | ">" | "<" | ">=" | "<=") => {
+                format!("{field} {op} '{value}'")
+            }
+            "in" => format!("{field} IN ('{value}')"),
+            "not in" => format!("{field} NOT IN ('{value}')"),
+            "contains" => format!("{field} LIKE '%{value}%'"),
+            "not contains" => format!("{field} NOT LIKE '%{value}%'"),
+            "starts with" => format!("{field} LIKE '{value}%'"),
+            "ends with" => format!("{field} LIKE '%{value}'"),
+            "is null" => format!("{field} IS NULL"),
+            "is not null" => format!("{field} IS NOT NULL"),
+            _ => {
+                return Err(format!(
+                    "variable {} uses unsupported filter operator {operator}",
+                    var.name
+                ));
+            }
+        };
+        conditions.push(condition);
+    }
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", conditions.join(" AND "))
+    };
+    Ok(format!(
+        "SELECT {} AS zo_sql_key, COUNT(*) AS zo_sql_num FROM {}{where_clause} GROUP BY zo_sql_key ORDER BY zo_sql_num DESC",
+        quote_identifier(&query_data.field),
+        quote_identifier(&query_data.stream),
+    ))
+}
+
+/// Picks the value of the variable: the requested values found in the options, otherwise the
+/// default of the variable.
+fn select_value(var: &VariableList, options: &[String], requested: Option<&Value>) -> Value {
+    let multi = var.multi_select.unwrap_or_default();
+    let requested = match requested {
+        Some(Value::Array(list)) => list.iter().map(value_string).collect(),
+        Some(Value::Null) | None => vec![],
+        Some(v) => vec![value_string(v)],
+    };
+    let selected = match var.type_field.as_str() {
+        "constant" => var.value.iter().cloned().collect(),
+        "textbox" if requested.is_empty() => var.value.iter().cloned().collect(),
+        "textbox" => requested,
+        _ => {
+            let mut selected = requested
+                .into_iter()
+                .filter(|v| options.contains(v) || (multi && v == DASHBOARD_ALL))
+                .collect::<Vec<_>>();
+            if selected.is_empty() {
+                selected = default_selection(var, options, multi);
+            }
+            selected
+        }
+    };
+    if multi {
+        Value::Array(selected.into_iter().map(Value::String).collect())
+    } else {
+        Value::String(selected.into_iter().next().unwrap_or_default())
+    }
+}
+
+fn default_selection(var: &VariableList, options: &[String], multi: bool) -> Vec<String> {
+    let custom_selected = var
+        .options
+        .iter()
+        .flatten()
+        .filter(|o| o.selected.unwrap_or_default() && options.contains(&o.value))
+        .map(|o| o.value.clone())
+        .collect::<Vec<_>>();
+    if !custom_selected.is_empty() {
+        return custom_selected;
+    }
+    if multi {
+        match var.select_all_value_for_multi_select.as_deref() {
+            Some("all") => return vec![DASHBOARD_ALL.to_string()],
+            Some("custom") => {
+                let custom = var
+                    .custom_multi_select_value
+                    .iter()
+                    .flatten()
+                    .filter(|v| options.contains(v))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if !custom.is_empty() {
+                    return custom;
+                }
+            }
+            _ => {}
+        }
+    }
+    match var.value.as_ref().filter(|v| options.contains(v)) {
+        Some(v) => vec![v.clone()],
+        None => options.first().cloned().into_iter().collect(),
+    }
+}
+
+fn value_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use config::{
+        meta::dashboards::v5::{CustomFieldsOption, Filters, IntervalVariable, QueryData},
+        utils::json::json,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_values_sql() {
+        let var = VariableList {
+            name: "pod".to_string(),
+            type_field: "query_values".to_string(),
+            query_data: Some(QueryData {
+                stream: "k8s".to_string(),
+                field: "pod".to_string(),
+                filter: Some(vec![
+                    Filters {
+                        name: Some("namespace".to_string()),
+                        operator: Some("IN".to_string()),
+                        value: "$namespace".to_string(),
+                    },
+                    Filters {
+                        name: Some("env".to_string()),
+                        operator: None,
+                        value: "it's".to_string(),
+                    },
+                ]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let sql = values_sql(&var).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT \"pod\" AS zo_sql_key, COUNT(*) AS zo_sql_num FROM \"k8s\" WHERE \"namespace\" IN ('$namespace') AND \"env\" = 'it''s' GROUP BY zo_sql_key ORDER BY zo_sql_num DESC"
+        );
+        let values = vec![QueryVariable {
+            name: "namespace".to_string(),
+            value: json!(["a", "b'c"]),
+        }];
+        assert!(
+            search::substitute_variables(&sql, &values)
+                .unwrap()
+                .contains("\"namespace\" IN ('a','b''c')")
+        );
+    }
+
+    #[test]
+    fn test_interval_options() {
+        let mut var = VariableList {
+            type_field: "interval".to_string(),
+            options: Some(vec![CustomFieldsOption {
+                label: "5m".to_string(),
+                value: "5 minute".to_string(),
+                selected: None,
+            }]),
+            ..Default::default()
+        };
+        assert_eq!(interval_options(&var), vec!["5 minute"]);
+
+        var.interval = Some(IntervalVariable {
+            auto: true,
+            min_interval: None,
+        });
+        let options = interval_options(&var);
+        assert_eq!(options, vec![AUTO_INTERVAL, "5 minute"]);
+        assert_eq!(select_value(&var, &options, None), json!(AUTO_INTERVAL));
+    }
+
+    #[test]
+    fn test_select_value() {
+        let options = vec!["a".to_string(), "b".to_string()];
+        let mut var = VariableList {
+            type_field: "query_values".to_string(),
+            value: Some("b".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(select_value(&var, &options, Some(&json!("a"))), json!("a"));
+        assert_eq!(select_value(&var, &options, Some(&json!("x"))), json!("b"));
+        assert_eq!(select_value(&var, &[], None), json!(""));
+
+        var.multi_select = Some(true);
+        var.select_all_value_for_multi_select = Some("all".to_string());
+        assert_eq!(
+            select_value(&var, &options, Some(&json!(["a", "x"]))),
+            json!(["a"])
+        );
+        assert_eq!(select_value(&var, &options, None), json!([DASHBOARD_ALL]));
+
+        var.type_field = "textbox".to_string();
+        var.multi_select = None;
+        assert_eq!(select_value(&var, &[], Some(&json!("free"))), json!("free"));
+    }
+}
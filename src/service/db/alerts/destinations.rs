@@ -40,14 +40,24 @@ pub enum DestinationError {
     EmptyUrl,
     #[error("SNS destination must have Topic ARN and Region")]
     InvalidSns,
+    #[error("PagerDuty destination must have a routing key")]
+    InvalidPagerDuty,
+    #[error("Opsgenie destination must have an API key")]
+    InvalidOpsgenie,
+    #[error("VictorOps destination must have an API key and a routing key")]
+    InvalidVictorOps,
     #[error("Email destination must have at least one email recipient")]
     EmptyEmail,
     #[error("Email destination recipients must be part of this org")]
     UserNotPermitted,
     #[error("Email destination must have SMTP configured")]
     SMTPUnavailable,
+    #[error("Invalid notification grouping: {0}")]
+    InvalidGrouping(String),
     #[error("Alert destination must have a template")]
     TemplateNotFound,
+    #[error("Resolved template not found: {0}")]
+    ResolvedTemplateNotFound(String),
     #[error("Pipeline destination must have a pipeline id")]
     EmptyPipelineId,
     #[error("Destination with the same name already exists")]
@@ -44,6 +44,8 @@ pub enum TemplateError {
     EmptyTitle,
     #[error("Template body cannot be empty")]
     EmptyBody,
+    #[error("Invalid template: {0}")]
+    InvalidTemplate(String),
     #[error("Template with the same name already exists")]
     AlreadyExists,
     #[error("Template is in use for destination {0}")]
@@ -102,7 +104,11 @@ pub async fn delete(org_id: &str, name: &str) -> Result<(), TemplateError> {
     for dest in DESTINATIONS.iter() {
         let d = dest.value();
         if (dest.key().starts_with(org_id) || dest.key().starts_with(DEFAULT_ORG))
-            && matches!(&d.module, Module::Alert { template, .. } if template.eq(name))
+            && matches!(
+                &d.module,
+                Module::Alert { template, resolved_template, .. }
+                    if template.eq(name) || resolved_template.as_deref() == Some(name)
+            )
         {
             return Err(TemplateError::DeleteWithDestination(dest.name.to_string()));
         }
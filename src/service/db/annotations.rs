@@ -0,0 +1,51 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use config::{meta::annotations::Annotation, utils::json};
+use infra::errors::Error;
+
+use crate::service::db;
+
+pub const ANNOTATIONS_KEY_PREFIX: &str = "/annotations/";
+
+pub async fn set(org_id: &str, annotation: &Annotation) -> Result<(), Error> {
+    let key = format!("{ANNOTATIONS_KEY_PREFIX}{org_id}/{}", annotation.id);
+    db::put(
+        &key,
+        json::to_vec(annotation)?.into(),
+        db::NO_NEED_WATCH,
+        None,
+    )
+    .await
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<Annotation, Error> {
+    let val = db::get(&format!("{ANNOTATIONS_KEY_PREFIX}{org_id}/{id}")).await?;
+    Ok(json::from_slice(&val)?)
+}
+
+pub async fn delete(org_id: &str, id: &str) -> Result<(), Error> {
+    let key = format!("{ANNOTATIONS_KEY_PREFIX}{org_id}/{id}");
+    db::delete(&key, false, db::NO_NEED_WATCH, None).await
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<Annotation>, Error> {
+    let key = format!("{ANNOTATIONS_KEY_PREFIX}{org_id}/");
+    let list = db::list_values(&key)
+        .await?
+        .into_iter()
+        .map(|v| json::from_slice::<Annotation>(&v))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(list)
+}
@@ -0,0 +1,95 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use config::{
+    meta::chaos::{ChaosScenario, ChaosTarget},
+    utils::json,
+};
+use infra::{chaos::CHAOS_KEY_PREFIX, errors::Error};
+
+use crate::service::db;
+
+pub async fn set(scenario: &ChaosScenario) -> Result<(), Error> {
+    let key = format!("{CHAOS_KEY_PREFIX}{}", scenario.target);
+    db::put(&key, json::to_vec(scenario)?.into(), db::NEED_WATCH, None).await
+}
+
+pub async fn delete(target: ChaosTarget) -> Result<(), Error> {
+    let key = format!("{CHAOS_KEY_PREFIX}{target}");
+    db::delete(&key, false, db::NEED_WATCH, None).await
+}
+
+pub async fn list() -> Result<Vec<ChaosScenario>, Error> {
+    let list = db::list_values(CHAOS_KEY_PREFIX)
+        .await?
+        .into_iter()
+        .map(|v| json::from_slice::<ChaosScenario>(&v))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(list)
+}
+
+pub async fn watch() -> Result<(), anyhow::Error> {
+    let key = CHAOS_KEY_PREFIX;
+    let cluster_coordinator = db::get_coordinator().await;
+    let mut events = cluster_coordinator.watch(key).await?;
+    let events = Arc::get_mut(&mut events).unwrap();
+    log::info!("Start watching chaos scenarios");
+    loop {
+        let ev = match events.recv().await {
+            Some(ev) => ev,
+            None => {
+                log::error!("watch_chaos_scenarios: event channel closed");
+                break;
+            }
+        };
+        match ev {
+            db::Event::Put(ev) => {
+                let item_value: ChaosScenario = match db::get(&ev.key).await {
+                    Ok(val) => match json::from_slice(&val) {
+                        Ok(val) => val,
+                        Err(e) => {
+                            log::error!("Error getting value: {}", e);
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        log::error!("Error getting value: {}", e);
+                        continue;
+                    }
+                };
+                infra::chaos::set_scenario(item_value);
+            }
+            db::Event::Delete(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                match item_key.parse::<ChaosTarget>() {
+                    Ok(target) => infra::chaos::remove_scenario(target),
+                    Err(e) => log::error!("Error parsing chaos target: {}", e),
+                }
+            }
+            db::Event::Empty => {}
+        }
+    }
+    Ok(())
+}
+
+pub async fn cache() -> Result<(), anyhow::Error> {
+    for scenario in list().await? {
+        infra::chaos::set_scenario(scenario);
+    }
+    log::info!("Chaos scenarios Cached");
+    Ok(())
+}
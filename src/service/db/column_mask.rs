@@ -0,0 +1,117 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use config::{
+    meta::{column_mask::ColumnMaskPolicy, stream::StreamType, user::UserRole},
+    utils::json,
+};
+use infra::errors::Error;
+
+use crate::{common::infra::config::COLUMN_MASKS, service::db};
+
+pub const COLUMN_MASK_KEY_PREFIX: &str = "/column_mask/";
+
+pub async fn set(org_id: &str, policy: &ColumnMaskPolicy) -> Result<(), Error> {
+    let key = format!("{COLUMN_MASK_KEY_PREFIX}{org_id}/{}", policy.key());
+    db::put(&key, json::to_vec(policy)?.into(), db::NEED_WATCH, None).await
+}
+
+pub async fn get(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    role: &UserRole,
+) -> Result<ColumnMaskPolicy, Error> {
+    let key = config::meta::row_policy::policy_key(stream_type, stream_name, role);
+    let val = db::get(&format!("{COLUMN_MASK_KEY_PREFIX}{org_id}/{key}")).await?;
+    Ok(json::from_slice(&val)?)
+}
+
+pub async fn delete(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    role: &UserRole,
+) -> Result<(), Error> {
+    let key = config::meta::row_policy::policy_key(stream_type, stream_name, role);
+    let key = format!("{COLUMN_MASK_KEY_PREFIX}{org_id}/{key}");
+    db::delete(&key, false, db::NEED_WATCH, None).await
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<ColumnMaskPolicy>, Error> {
+    let key = format!("{COLUMN_MASK_KEY_PREFIX}{org_id}/");
+    let mut list = db::list_values(&key)
+        .await?
+        .into_iter()
+        .map(|v| json::from_slice::<ColumnMaskPolicy>(&v))
+        .collect::<Result<Vec<_>, _>>()?;
+    list.sort_by_key(|p| p.key());
+    Ok(list)
+}
+
+pub async fn watch() -> Result<(), anyhow::Error> {
+    let key = COLUMN_MASK_KEY_PREFIX;
+    let cluster_coordinator = db::get_coordinator().await;
+    let mut events = cluster_coordinator.watch(key).await?;
+    let events = Arc::get_mut(&mut events).unwrap();
+    log::info!("Start watching column masks");
+    loop {
+        let ev = match events.recv().await {
+            Some(ev) => ev,
+            None => {
+                log::error!("watch_column_masks: event channel closed");
+                break;
+            }
+        };
+        match ev {
+            db::Event::Put(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                let item_value: ColumnMaskPolicy = match db::get(&ev.key).await {
+                    Ok(val) => match json::from_slice(&val) {
+                        Ok(val) => val,
+                        Err(e) => {
+                            log::error!("Error getting value: {}", e);
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        log::error!("Error getting value: {}", e);
+                        continue;
+                    }
+                };
+                COLUMN_MASKS.insert(item_key.to_owned(), item_value);
+            }
+            db::Event::Delete(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                COLUMN_MASKS.remove(item_key);
+            }
+            db::Event::Empty => {}
+        }
+    }
+    Ok(())
+}
+
+pub async fn cache() -> Result<(), anyhow::Error> {
+    let ret = db::list(COLUMN_MASK_KEY_PREFIX).await?;
+    for (item_key, item_value) in ret {
+        let item_key = item_key.strip_prefix(COLUMN_MASK_KEY_PREFIX).unwrap();
+        let json_val: ColumnMaskPolicy = json::from_slice(&item_value)?;
+        COLUMN_MASKS.insert(item_key.to_owned(), json_val);
+    }
+    log::info!("Column masks Cached");
+    Ok(())
+}
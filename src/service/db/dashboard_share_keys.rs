@@ -0,0 +1,41 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::{meta::dashboards::share::DashboardShareKey, utils::json};
+use infra::errors::Error;
+
+use crate::service::db;
+
+pub const SHARE_KEYS_KEY_PREFIX: &str = "/dashboard_share_keys/";
+
+pub async fn set(org_id: &str, dashboard_id: &str, key: &DashboardShareKey) -> Result<(), Error> {
+    db::put(
+        &format!("{SHARE_KEYS_KEY_PREFIX}{org_id}/{dashboard_id}"),
+        json::to_vec(key)?.into(),
+        db::NO_NEED_WATCH,
+        None,
+    )
+    .await
+}
+
+pub async fn get(org_id: &str, dashboard_id: &str) -> Result<DashboardShareKey, Error> {
+    let val = db::get(&format!("{SHARE_KEYS_KEY_PREFIX}{org_id}/{dashboard_id}")).await?;
+    Ok(json::from_slice(&val)?)
+}
+
+pub async fn delete(org_id: &str, dashboard_id: &str) -> Result<(), Error> {
+    let key = format!("{SHARE_KEYS_KEY_PREFIX}{org_id}/{dashboard_id}");
+    db::delete(&key, false, db::NO_NEED_WATCH, None).await
+}
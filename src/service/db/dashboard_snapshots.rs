@@ -0,0 +1,118 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use config::{
+    meta::dashboards::snapshots::{DashboardSnapshot, SnapshotSchedule},
+    utils::json,
+};
+use infra::errors::Error;
+
+use crate::service::db;
+
+pub const SNAPSHOTS_KEY_PREFIX: &str = "/dashboard_snapshots/";
+pub const SCHEDULES_KEY_PREFIX: &str = "/dashboard_snapshot_schedules/";
+
+pub async fn set(org_id: &str, snapshot: &DashboardSnapshot) -> Result<(), Error> {
+    let key = format!("{SNAPSHOTS_KEY_PREFIX}{org_id}/{}", snapshot.id);
+    db::put(
+        &key,
+        json::to_vec(snapshot)?.into(),
+        db::NO_NEED_WATCH,
+        None,
+    )
+    .await
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<DashboardSnapshot, Error> {
+    let val = db::get(&format!("{SNAPSHOTS_KEY_PREFIX}{org_id}/{id}")).await?;
+    Ok(json::from_slice(&val)?)
+}
+
+pub async fn delete(org_id: &str, id: &str) -> Result<(), Error> {
+    let key = format!("{SNAPSHOTS_KEY_PREFIX}{org_id}/{id}");
+    db::delete(&key, false, db::NO_NEED_WATCH, None).await
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<DashboardSnapshot>, Error> {
+    let key = format!("{SNAPSHOTS_KEY_PREFIX}{org_id}/");
+    let mut list = db::list_values(&key)
+        .await?
+        .into_iter()
+        .map(|v| json::from_slice::<DashboardSnapshot>(&v))
+        .collect::<Result<Vec<_>, _>>()?;
+    list.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(list)
+}
+
+/// Lists the snapshots of all orgs as `(org_id, snapshot)`.
+pub async fn list_all() -> Result<Vec<(String, DashboardSnapshot)>, Error> {
+    let mut list = Vec::new();
+    for (key, val) in db::list(SNAPSHOTS_KEY_PREFIX).await? {
+        let Some((org_id, _)) = key
+            .strip_prefix(SNAPSHOTS_KEY_PREFIX)
+            .and_then(|k| k.split_once('/'))
+        else {
+            continue;
+        };
+        list.push((org_id.to_string(), json::from_slice(&val)?));
+    }
+    Ok(list)
+}
+
+pub async fn set_schedule(org_id: &str, schedule: &SnapshotSchedule) -> Result<(), Error> {
+    let key = format!("{SCHEDULES_KEY_PREFIX}{org_id}/{}", schedule.id);
+    db::put(
+        &key,
+        json::to_vec(schedule)?.into(),
+        db::NO_NEED_WATCH,
+        None,
+    )
+    .await
+}
+
+pub async fn get_schedule(org_id: &str, id: &str) -> Result<SnapshotSchedule, Error> {
+    let val = db::get(&format!("{SCHEDULES_KEY_PREFIX}{org_id}/{id}")).await?;
+    Ok(json::from_slice(&val)?)
+}
+
+pub async fn delete_schedule(org_id: &str, id: &str) -> Result<(), Error> {
+    let key = format!("{SCHEDULES_KEY_PREFIX}{org_id}/{id}");
+    db::delete(&key, false, db::NO_NEED_WATCH, None).await
+}
+
+pub async fn list_schedules(org_id: &str) -> Result<Vec<SnapshotSchedule>, Error> {
+    let key = format!("{SCHEDULES_KEY_PREFIX}{org_id}/");
+    let mut list = db::list_values(&key)
+        .await?
+        .into_iter()
+        .map(|v| json::from_slice::<SnapshotSchedule>(&v))
+        .collect::<Result<Vec<_>, _>>()?;
+    list.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(list)
+}
+
+/// Lists the snapshot schedules of all orgs as `(org_id, schedule)`.
+pub async fn list_all_schedules() -> Result<Vec<(String, SnapshotSchedule)>, Error> {
+    let mut list = Vec::new();
+    for (key, val) in db::list(SCHEDULES_KEY_PREFIX).await? {
+        let Some((org_id, _)) = key
+            .strip_prefix(SCHEDULES_KEY_PREFIX)
+            .and_then(|k| k.split_once('/'))
+        else {
+            continue;
+        };
+        list.push((org_id.to_string(), json::from_slice(&val)?));
+    }
+    Ok(list)
+}
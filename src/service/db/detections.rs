@@ -0,0 +1,62 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::{meta::detections::Detection, utils::json};
+use infra::errors::Error;
+
+use crate::service::db;
+
+pub const DETECTIONS_KEY_PREFIX: &str = "/detections";
+
+pub async fn get(org_id: &str, id: &str) -> Result<Detection, Error> {
+    let key = format!("{DETECTIONS_KEY_PREFIX}/{org_id}/{id}");
+    let val = db::get(&key).await?;
+    Ok(json::from_slice(&val)?)
+}
+
+pub async fn set(detection: &Detection) -> Result<(), Error> {
+    let key = format!(
+        "{DETECTIONS_KEY_PREFIX}/{}/{}",
+        detection.org_id, detection.id
+    );
+    db::put(
+        &key,
+        json::to_vec(detection)?.into(),
+        db::NO_NEED_WATCH,
+        None,
+    )
+    .await
+}
+
+pub async fn delete(org_id: &str, id: &str) -> Result<(), Error> {
+    let key = format!("{DETECTIONS_KEY_PREFIX}/{org_id}/{id}");
+    db::delete(&key, false, db::NO_NEED_WATCH, None).await
+}
+
+/// Lists the detections of an org, or of all orgs when `org_id` is empty.
+pub async fn list(org_id: &str) -> Result<Vec<Detection>, Error> {
+    let key = if org_id.is_empty() {
+        format!("{DETECTIONS_KEY_PREFIX}/")
+    } else {
+        format!("{DETECTIONS_KEY_PREFIX}/{org_id}/")
+    };
+    let mut list = db::list_values(&key)
+        .await?
+        .into_iter()
+        .map(|v| json::from_slice::<Detection>(&v))
+        .collect::<Result<Vec<_>, _>>()?;
+    list.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(list)
+}
@@ -0,0 +1,182 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Groups the file list registration of the files flushed by the ingester into batched
+//! transactions. Many small flushes would otherwise cost one meta database write each.
+
+use std::collections::HashMap;
+
+use config::meta::stream::{FileKey, FileMeta};
+use infra::errors::{Error, Result};
+use once_cell::sync::Lazy;
+use tokio::sync::{Mutex, mpsc, oneshot};
+
+struct PendingFile {
+    file: FileKey,
+    done: oneshot::Sender<Result<i64>>,
+}
+
+type Queue = (
+    mpsc::UnboundedSender<PendingFile>,
+    Mutex<Option<mpsc::UnboundedReceiver<PendingFile>>>,
+);
+
+static QUEUE: Lazy<Queue> = Lazy::new(|| {
+    let (tx, rx) = mpsc::unbounded_channel();
+    (tx, Mutex::new(Some(rx)))
+});
+
+/// Queues the file for the next batch and waits until it is registered, returns its id.
+pub async fn add(account: &str, key: &str, meta: &FileMeta) -> Result<i64> {
+    let (done, rx) = oneshot::channel();
+    let file = FileKey::new(0, account.to_string(), key.to_string(), meta.clone(), false);
+    QUEUE
+        .0
+        .send(PendingFile { file, done })
+        .map_err(|_| Error::Message("file list batch queue is closed".to_string()))?;
+    rx.await
+        .map_err(|_| Error::Message("file list batch writer is stopped".to_string()))?
+}
+
+/// Writes the queued files. A batch is written once it is full or once its first file waited
+/// for the batch interval, the next batch fills up in the meantime.
+pub async fn run() -> Result<()> {
+    let Some(mut rx) = QUEUE.1.lock().await.take() else {
+        return Ok(()); // already running
+    };
+    let cfg = config::get_config();
+    let batch_size = cfg.limit.file_list_batch_size;
+    let interval = tokio::time::Duration::from_millis(cfg.limit.file_list_batch_interval);
+    while let Some(batch) = next_batch(&mut rx, batch_size, interval).await {
+        write(batch).await;
+    }
+    log::info!("[FILE_LIST] batch writer is stopped");
+    Ok(())
+}
+
+/// Waits for the first file of the next batch, then collects files until the batch is full or
+/// the interval has passed. Returns `None` once the queue is closed.
+async fn next_batch(
+    rx: &mut mpsc::UnboundedReceiver<PendingFile>,
+    batch_size: usize,
+    interval: tokio::time::Duration,
+) -> Option<Vec<PendingFile>> {
+    let first = rx.recv().await?;
+    let mut batch = Vec::with_capacity(batch_size);
+    batch.push(first);
+    let deadline = tokio::time::Instant::now() + interval;
+    while batch.len() < batch_size {
+        match tokio::time::timeout_at(deadline, rx.recv()).await {
+            Ok(Some(file)) => batch.push(file),
+            _ => break,
+        }
+    }
+    Some(batch)
+}
+
+async fn write(batch: Vec<PendingFile>) {
+    let files = batch.iter().map(|p| p.file.clone()).collect::<Vec<_>>();
+    if let Err(e) = infra::file_list::batch_add(&files).await {
+        // a file registered already fails the whole transaction, and a single file failing
+        // shouldn't hold up the others, so fall back to registering them one by one
+        log::warn!(
+            "[FILE_LIST] batch add of {} files failed, adding them one by one: {e}",
+            files.len()
+        );
+        for p in batch {
+            let ret = infra::file_list::add(&p.file.account, &p.file.key, &p.file.meta).await;
+            _ = p.done.send(ret);
+        }
+        return;
+    }
+
+    // the ids are only used to notify the other nodes, don't fail the batch for them
+    let ids = match infra::file_list::query_ids_by_files(&files).await {
+        Ok(ids) => ids,
+        Err(e) => {
+            log::warn!(
+                "[FILE_LIST] query ids of {} batched files error: {e}",
+                files.len()
+            );
+            HashMap::new()
+        }
+    };
+    for p in batch {
+        let id = ids.get(&p.file.key).copied().unwrap_or_default();
+        _ = p.done.send(Ok(id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::time::Duration;
+
+    use super::*;
+
+    fn pending(key: &str) -> PendingFile {
+        let (done, _) = oneshot::channel();
+        let file = FileKey::new(
+            0,
+            String::new(),
+            key.to_string(),
+            FileMeta::default(),
+            false,
+        );
+        PendingFile { file, done }
+    }
+
+    fn keys(batch: &[PendingFile]) -> Vec<&str> {
+        batch.iter().map(|p| p.file.key.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_next_batch_full() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        for key in ["a", "b", "c", "d", "e"] {
+            tx.send(pending(key)).unwrap();
+        }
+        let interval = Duration::from_millis(200);
+        let batch = next_batch(&mut rx, 2, interval).await.unwrap();
+        assert_eq!(keys(&batch), ["a", "b"]);
+        let batch = next_batch(&mut rx, 2, interval).await.unwrap();
+        assert_eq!(keys(&batch), ["c", "d"]);
+        // the last batch isn't full, it is written once the interval has passed
+        let start = tokio::time::Instant::now();
+        let batch = next_batch(&mut rx, 2, interval).await.unwrap();
+        assert_eq!(keys(&batch), ["e"]);
+        assert!(start.elapsed() >= interval);
+    }
+
+    #[tokio::test]
+    async fn test_next_batch_interval() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        tx.send(pending("a")).unwrap();
+        let sender = tx.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            sender.send(pending("b")).unwrap();
+            // after the interval of the first file, goes to the next batch
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            sender.send(pending("c")).unwrap();
+        });
+        let interval = Duration::from_millis(200);
+        let batch = next_batch(&mut rx, 10, interval).await.unwrap();
+        assert_eq!(keys(&batch), ["a", "b"]);
+        drop(tx);
+        let batch = next_batch(&mut rx, 10, interval).await.unwrap();
+        assert_eq!(keys(&batch), ["c"]);
+        assert!(next_batch(&mut rx, 10, interval).await.is_none());
+    }
+}
@@ -27,6 +27,7 @@ use o2_enterprise::enterprise::{
     super_cluster::stream::client::super_cluster_cache_stats,
 };
 use once_cell::sync::Lazy;
+pub mod batch;
 pub mod broadcast;
 pub mod local;
 
@@ -87,12 +88,17 @@ async fn progress(account: &str, key: &str, data: Option<&FileMeta>, delete: boo
             log::error!("service:db:file_list: delete {}, remove error: {}", key, e);
         }
     } else if let Some(data) = data {
-        match infra::file_list::add(account, key, data).await {
-            Ok(v) => {
-                id = v;
-            }
-            Err(e) => {
-                log::error!("service:db:file_list: add {}, add error: {}", key, e);
+        if config::get_config().limit.file_list_batch_enabled {
+            // a failed batch is retried by the caller
+            id = batch::add(account, key, data).await?;
+        } else {
+            match infra::file_list::add(account, key, data).await {
+                Ok(v) => {
+                    id = v;
+                }
+                Err(e) => {
+                    log::error!("service:db:file_list: add {}, add error: {}", key, e);
+                }
             }
         }
         // update stream stats realtime
@@ -0,0 +1,111 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use config::{meta::grok::GrokPattern, utils::json};
+use infra::errors::Error;
+
+use crate::{
+    common::{infra::config::GROK_PATTERNS, utils::grok},
+    service::db,
+};
+
+pub const GROK_PATTERNS_KEY_PREFIX: &str = "/grok/patterns/";
+
+pub async fn set(org_id: &str, pattern: &GrokPattern) -> Result<(), Error> {
+    let key = format!("{GROK_PATTERNS_KEY_PREFIX}{org_id}/{}", pattern.name);
+    db::put(&key, json::to_vec(pattern)?.into(), db::NEED_WATCH, None).await
+}
+
+pub async fn get(org_id: &str, name: &str) -> Result<GrokPattern, Error> {
+    let val = db::get(&format!("{GROK_PATTERNS_KEY_PREFIX}{org_id}/{name}")).await?;
+    Ok(json::from_slice(&val)?)
+}
+
+pub async fn delete(org_id: &str, name: &str) -> Result<(), Error> {
+    let key = format!("{GROK_PATTERNS_KEY_PREFIX}{org_id}/{name}");
+    db::delete(&key, false, db::NEED_WATCH, None).await
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<GrokPattern>, Error> {
+    let key = format!("{GROK_PATTERNS_KEY_PREFIX}{org_id}/");
+    let mut list = db::list_values(&key)
+        .await?
+        .into_iter()
+        .map(|v| json::from_slice::<GrokPattern>(&v))
+        .collect::<Result<Vec<_>, _>>()?;
+    list.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(list)
+}
+
+pub async fn watch() -> Result<(), anyhow::Error> {
+    let key = GROK_PATTERNS_KEY_PREFIX;
+    let cluster_coordinator = db::get_coordinator().await;
+    let mut events = cluster_coordinator.watch(key).await?;
+    let events = Arc::get_mut(&mut events).unwrap();
+    log::info!("Start watching grok patterns");
+    loop {
+        let ev = match events.recv().await {
+            Some(ev) => ev,
+            None => {
+                log::error!("watch_grok_patterns: event channel closed");
+                break;
+            }
+        };
+        match ev {
+            db::Event::Put(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                let item_value: GrokPattern = match db::get(&ev.key).await {
+                    Ok(val) => match json::from_slice(&val) {
+                        Ok(val) => val,
+                        Err(e) => {
+                            log::error!("Error getting value: {}", e);
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        log::error!("Error getting value: {}", e);
+                        continue;
+                    }
+                };
+                GROK_PATTERNS.insert(item_key.to_owned(), item_value);
+                if let Some((org_id, _)) = item_key.split_once('/') {
+                    grok::clear_cache(org_id);
+                }
+            }
+            db::Event::Delete(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                GROK_PATTERNS.remove(item_key);
+                if let Some((org_id, _)) = item_key.split_once('/') {
+                    grok::clear_cache(org_id);
+                }
+            }
+            db::Event::Empty => {}
+        }
+    }
+    Ok(())
+}
+
+pub async fn cache() -> Result<(), anyhow::Error> {
+    let ret = db::list(GROK_PATTERNS_KEY_PREFIX).await?;
+    for (item_key, item_value) in ret {
+        let item_key = item_key.strip_prefix(GROK_PATTERNS_KEY_PREFIX).unwrap();
+        let json_val: GrokPattern = json::from_slice(&item_value)?;
+        GROK_PATTERNS.insert(item_key.to_owned(), json_val);
+    }
+    log::info!("Grok patterns Cached");
+    Ok(())
+}
@@ -0,0 +1,56 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::{meta::invitation::Invitation, utils::json};
+use infra::errors::Error;
+
+use crate::service::db;
+
+pub const INVITATION_KEY_PREFIX: &str = "/invitation/";
+
+pub async fn set(invitation: &Invitation) -> Result<(), Error> {
+    let key = format!(
+        "{INVITATION_KEY_PREFIX}{}/{}",
+        invitation.org_id, invitation.token
+    );
+    db::put(
+        &key,
+        json::to_vec(invitation)?.into(),
+        db::NO_NEED_WATCH,
+        None,
+    )
+    .await
+}
+
+pub async fn get(org_id: &str, token: &str) -> Result<Invitation, Error> {
+    let val = db::get(&format!("{INVITATION_KEY_PREFIX}{org_id}/{token}")).await?;
+    Ok(json::from_slice(&val)?)
+}
+
+pub async fn delete(org_id: &str, token: &str) -> Result<(), Error> {
+    let key = format!("{INVITATION_KEY_PREFIX}{org_id}/{token}");
+    db::delete(&key, false, db::NO_NEED_WATCH, None).await
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<Invitation>, Error> {
+    let key = format!("{INVITATION_KEY_PREFIX}{org_id}/");
+    let mut list = db::list_values(&key)
+        .await?
+        .into_iter()
+        .map(|v| json::from_slice::<Invitation>(&v))
+        .collect::<Result<Vec<_>, _>>()?;
+    list.sort_by_key(|i| i.created_at);
+    Ok(list)
+}
@@ -22,15 +22,25 @@ use {
 };
 
 pub mod alerts;
+pub mod annotations;
+pub mod cases;
+pub mod chaos;
+pub mod column_mask;
 pub mod compact;
+pub mod dashboard_share_keys;
+pub mod dashboard_snapshots;
 pub mod dashboards;
+pub mod detections;
 pub mod distinct_values;
 pub mod enrichment_table;
 pub mod file_list;
 pub mod functions;
+pub mod grok;
+pub mod invitation;
 #[cfg(feature = "enterprise")]
 pub mod keys;
 pub mod kv;
+pub mod log_metrics;
 pub mod metas;
 pub mod metrics;
 #[cfg(feature = "enterprise")]
@@ -38,13 +48,22 @@ pub mod ofga;
 pub mod org_users;
 pub mod organization;
 pub mod pipeline;
+pub mod query_template;
+pub mod remote_write_filter;
+pub mod replication;
+pub mod row_policy;
 pub mod saved_view;
 pub mod scheduler;
 pub mod schema;
 pub mod search_job;
+pub mod service_account;
 pub mod session;
 pub mod short_url;
+pub mod sql_policy;
+pub mod synthetic_data;
 pub mod syslog;
+pub mod threat_intel;
+pub mod ui_profile;
 pub mod user;
 
 pub(crate) use infra_db::{Event, NEED_WATCH, NO_NEED_WATCH, get_coordinator};
@@ -71,6 +71,13 @@ pub async fn get_org_setting(org_id: &str) -> Result<OrganizationSetting, Error>
     Ok(settings)
 }
 
+/// Gets the org setting from the cache only, which holds every saved setting, so orgs without
+/// settings don't hit the db on the ingestion path.
+pub async fn get_cached_org_setting(org_id: &str) -> Option<OrganizationSetting> {
+    let key = format!("{}/{}", ORG_SETTINGS_KEY_PREFIX, org_id);
+    ORGANIZATION_SETTING.read().await.get(&key).cloned()
+}
+
 /// Cache the existing org settings in the beginning
 pub async fn org_settings_cache() -> Result<(), anyhow::Error> {
     let prefix = ORG_SETTINGS_KEY_PREFIX;
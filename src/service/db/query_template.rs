@@ -0,0 +1,52 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use config::{meta::query_template::QueryTemplate, utils::json};
+use infra::errors::Error;
+
+use crate::service::db;
+
+pub const QUERY_TEMPLATES_KEY_PREFIX: &str = "/query_templates/";
+
+pub async fn set(org_id: &str, template: &QueryTemplate) -> Result<(), Error> {
+    let key = format!("{QUERY_TEMPLATES_KEY_PREFIX}{org_id}/{}", template.name);
+    db::put(
+        &key,
+        json::to_vec(template)?.into(),
+        db::NO_NEED_WATCH,
+        None,
+    )
+    .await
+}
+
+pub async fn get(org_id: &str, name: &str) -> Result<QueryTemplate, Error> {
+    let val = db::get(&format!("{QUERY_TEMPLATES_KEY_PREFIX}{org_id}/{name}")).await?;
+    Ok(json::from_slice(&val)?)
+}
+
+pub async fn delete(org_id: &str, name: &str) -> Result<(), Error> {
+    let key = format!("{QUERY_TEMPLATES_KEY_PREFIX}{org_id}/{name}");
+    db::delete(&key, false, db::NO_NEED_WATCH, None).await
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<QueryTemplate>, Error> {
+    let key = format!("{QUERY_TEMPLATES_KEY_PREFIX}{org_id}/");
+    let mut list = db::list_values(&key)
+        .await?
+        .into_iter()
+        .map(|v| json::from_slice::<QueryTemplate>(&v))
+        .collect::<Result<Vec<_>, _>>()?;
+    list.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(list)
+}
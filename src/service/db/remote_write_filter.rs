@@ -0,0 +1,108 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use config::{meta::remote_write_filter::RemoteWriteFilter, utils::json};
+use infra::errors::Error;
+
+use crate::{common::infra::config::REMOTE_WRITE_FILTERS, service::db};
+
+pub const REMOTE_WRITE_FILTER_KEY_PREFIX: &str = "/remote_write_filter/";
+
+pub async fn set(org_id: &str, filter: &RemoteWriteFilter) -> Result<(), Error> {
+    let key = format!("{REMOTE_WRITE_FILTER_KEY_PREFIX}{org_id}");
+    db::put(&key, json::to_vec(filter)?.into(), db::NEED_WATCH, None).await
+}
+
+pub async fn get(org_id: &str) -> Result<RemoteWriteFilter, Error> {
+    let val = db::get(&format!("{REMOTE_WRITE_FILTER_KEY_PREFIX}{org_id}")).await?;
+    Ok(json::from_slice(&val)?)
+}
+
+pub async fn delete(org_id: &str) -> Result<(), Error> {
+    let key = format!("{REMOTE_WRITE_FILTER_KEY_PREFIX}{org_id}");
+    db::delete(&key, false, db::NEED_WATCH, None).await
+}
+
+fn cache_filter(org_id: &str, filter: &RemoteWriteFilter) {
+    match filter.compile() {
+        Ok(Some(matcher)) => {
+            REMOTE_WRITE_FILTERS.insert(org_id.to_owned(), Arc::new(matcher));
+        }
+        Ok(None) => {
+            REMOTE_WRITE_FILTERS.remove(org_id);
+        }
+        Err(e) => {
+            log::error!("Error compiling remote write filter of org {org_id}: {e}");
+            REMOTE_WRITE_FILTERS.remove(org_id);
+        }
+    }
+}
+
+pub async fn watch() -> Result<(), anyhow::Error> {
+    let key = REMOTE_WRITE_FILTER_KEY_PREFIX;
+    let cluster_coordinator = db::get_coordinator().await;
+    let mut events = cluster_coordinator.watch(key).await?;
+    let events = Arc::get_mut(&mut events).unwrap();
+    log::info!("Start watching remote write filters");
+    loop {
+        let ev = match events.recv().await {
+            Some(ev) => ev,
+            None => {
+                log::error!("watch_remote_write_filters: event channel closed");
+                break;
+            }
+        };
+        match ev {
+            db::Event::Put(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                let item_value: RemoteWriteFilter = match db::get(&ev.key).await {
+                    Ok(val) => match json::from_slice(&val) {
+                        Ok(val) => val,
+                        Err(e) => {
+                            log::error!("Error getting value: {}", e);
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        log::error!("Error getting value: {}", e);
+                        continue;
+                    }
+                };
+                cache_filter(item_key, &item_value);
+            }
+            db::Event::Delete(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                REMOTE_WRITE_FILTERS.remove(item_key);
+            }
+            db::Event::Empty => {}
+        }
+    }
+    Ok(())
+}
+
+pub async fn cache() -> Result<(), anyhow::Error> {
+    let ret = db::list(REMOTE_WRITE_FILTER_KEY_PREFIX).await?;
+    for (item_key, item_value) in ret {
+        let item_key = item_key
+            .strip_prefix(REMOTE_WRITE_FILTER_KEY_PREFIX)
+            .unwrap();
+        let json_val: RemoteWriteFilter = json::from_slice(&item_value)?;
+        cache_filter(item_key, &json_val);
+    }
+    log::info!("Remote write filters Cached");
+    Ok(())
+}
@@ -0,0 +1,39 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::service::db;
+
+const OFFSET_KEY: &str = "/replication/file_list/offset";
+
+/// Returns the last file list primary key replicated and the node running the job.
+pub async fn get_offset() -> (i64, String) {
+    let value = match db::get(OFFSET_KEY).await {
+        Ok(ret) => String::from_utf8_lossy(&ret).to_string(),
+        Err(_) => String::from("0"),
+    };
+    match value.split_once(';') {
+        Some((offset, node)) => (offset.parse().unwrap_or_default(), node.to_string()),
+        None => (value.parse().unwrap_or_default(), String::new()),
+    }
+}
+
+pub async fn set_offset(offset: i64, node: Option<&str>) -> Result<(), anyhow::Error> {
+    let val = if let Some(node) = node {
+        format!("{offset};{node}")
+    } else {
+        offset.to_string()
+    };
+    Ok(db::put(OFFSET_KEY, val.into(), db::NO_NEED_WATCH, None).await?)
+}
@@ -0,0 +1,102 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use config::utils::{json, time::now_micros};
+use infra::errors::Error;
+
+use crate::{
+    common::{infra::config::SERVICE_ACCOUNTS, meta::service_account::ServiceAccountStatus},
+    service::db,
+};
+
+pub const SERVICE_ACCOUNT_KEY_PREFIX: &str = "/service_account/";
+
+pub async fn set(org_id: &str, email: &str, status: &ServiceAccountStatus) -> Result<(), Error> {
+    let key = format!("{SERVICE_ACCOUNT_KEY_PREFIX}{org_id}/{email}");
+    db::put(&key, json::to_vec(status)?.into(), db::NEED_WATCH, None).await
+}
+
+pub async fn get(org_id: &str, email: &str) -> Result<ServiceAccountStatus, Error> {
+    let val = db::get(&format!("{SERVICE_ACCOUNT_KEY_PREFIX}{org_id}/{email}")).await?;
+    Ok(json::from_slice(&val)?)
+}
+
+pub async fn delete(org_id: &str, email: &str) -> Result<(), Error> {
+    let key = format!("{SERVICE_ACCOUNT_KEY_PREFIX}{org_id}/{email}");
+    db::delete(&key, false, db::NEED_WATCH, None).await
+}
+
+/// Returns whether the service account can authenticate, accounts without a recorded status
+/// are active.
+pub fn is_active(org_id: &str, email: &str) -> bool {
+    SERVICE_ACCOUNTS
+        .get(&format!("{org_id}/{email}"))
+        .is_none_or(|status| status.is_active(now_micros()))
+}
+
+pub async fn watch() -> Result<(), anyhow::Error> {
+    let key = SERVICE_ACCOUNT_KEY_PREFIX;
+    let cluster_coordinator = db::get_coordinator().await;
+    let mut events = cluster_coordinator.watch(key).await?;
+    let events = Arc::get_mut(&mut events).unwrap();
+    log::info!("Start watching service accounts");
+    loop {
+        let ev = match events.recv().await {
+            Some(ev) => ev,
+            None => {
+                log::error!("watch_service_accounts: event channel closed");
+                break;
+            }
+        };
+        match ev {
+            db::Event::Put(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                let item_value: ServiceAccountStatus = match db::get(&ev.key).await {
+                    Ok(val) => match json::from_slice(&val) {
+                        Ok(val) => val,
+                        Err(e) => {
+                            log::error!("Error getting value: {}", e);
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        log::error!("Error getting value: {}", e);
+                        continue;
+                    }
+                };
+                SERVICE_ACCOUNTS.insert(item_key.to_owned(), item_value);
+            }
+            db::Event::Delete(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                SERVICE_ACCOUNTS.remove(item_key);
+            }
+            db::Event::Empty => {}
+        }
+    }
+    Ok(())
+}
+
+pub async fn cache() -> Result<(), anyhow::Error> {
+    let ret = db::list(SERVICE_ACCOUNT_KEY_PREFIX).await?;
+    for (item_key, item_value) in ret {
+        let item_key = item_key.strip_prefix(SERVICE_ACCOUNT_KEY_PREFIX).unwrap();
+        let json_val: ServiceAccountStatus = json::from_slice(&item_value)?;
+        SERVICE_ACCOUNTS.insert(item_key.to_owned(), json_val);
+    }
+    log::info!("Service accounts Cached");
+    Ok(())
+}
@@ -0,0 +1,90 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use std::sync::Arc;
+
+use config::{meta::sql_policy::SqlPolicy, utils::json};
+use infra::errors::Error;
+
+use crate::{common::infra::config::SQL_POLICIES, service::db};
+
+pub const SQL_POLICY_KEY_PREFIX: &str = "/sql_policy/";
+
+pub async fn set(org_id: &str, policy: &SqlPolicy) -> Result<(), Error> {
+    let key = format!("{SQL_POLICY_KEY_PREFIX}{org_id}");
+    db::put(&key, json::to_vec(policy)?.into(), db::NEED_WATCH, None).await
+}
+
+pub async fn get(org_id: &str) -> Result<SqlPolicy, Error> {
+    let val = db::get(&format!("{SQL_POLICY_KEY_PREFIX}{org_id}")).await?;
+    Ok(json::from_slice(&val)?)
+}
+
+pub async fn delete(org_id: &str) -> Result<(), Error> {
+    let key = format!("{SQL_POLICY_KEY_PREFIX}{org_id}");
+    db::delete(&key, false, db::NEED_WATCH, None).await
+}
+
+pub async fn watch() -> Result<(), anyhow::Error> {
+    let key = SQL_POLICY_KEY_PREFIX;
+    let cluster_coordinator = db::get_coordinator().await;
+    let mut events = cluster_coordinator.watch(key).await?;
+    let events = Arc::get_mut(&mut events).unwrap();
+    log::info!("Start watching sql policies");
+    loop {
+        let ev = match events.recv().await {
+            Some(ev) => ev,
+            None => {
+                log::error!("watch_sql_policies: event channel closed");
+                break;
+            }
+        };
+        match ev {
+            db::Event::Put(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                let item_value: SqlPolicy = match db::get(&ev.key).await {
+                    Ok(val) => match json::from_slice(&val) {
+                        Ok(val) => val,
+                        Err(e) => {
+                            log::error!("Error getting value: {}", e);
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        log::error!("Error getting value: {}", e);
+                        continue;
+                    }
+                };
+                SQL_POLICIES.insert(item_key.to_owned(), item_value);
+            }
+            db::Event::Delete(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                SQL_POLICIES.remove(item_key);
+            }
+            db::Event::Empty => {}
+        }
+    }
+    Ok(())
+}
+
+pub async fn cache() -> Result<(), anyhow::Error> {
+    let ret = db::list(SQL_POLICY_KEY_PREFIX).await?;
+    for (item_key, item_value) in ret {
+        let item_key = item_key.strip_prefix(SQL_POLICY_KEY_PREFIX).unwrap();
+        let json_val: SqlPolicy = json::from_slice(&item_value)?;
+        SQL_POLICIES.insert(item_key.to_owned(), json_val);
+    }
+    log::info!("Sql policies Cached");
+    Ok(())
+}
@@ -0,0 +1,132 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use config::{
+    meta::threat_intel::{Indicator, IndicatorFeed},
+    utils::json,
+};
+use infra::errors::Error;
+
+use crate::{common::infra::config::THREAT_INDICATORS, service::db};
+
+pub const INDICATORS_KEY_PREFIX: &str = "/threat_intel/indicators/";
+pub const FEEDS_KEY_PREFIX: &str = "/threat_intel/feeds/";
+
+pub async fn set_indicator(org_id: &str, indicator: &Indicator) -> Result<(), Error> {
+    let key = format!("{INDICATORS_KEY_PREFIX}{org_id}/{}", indicator.value);
+    db::put(&key, json::to_vec(indicator)?.into(), db::NEED_WATCH, None).await
+}
+
+pub async fn delete_indicator(org_id: &str, value: &str) -> Result<(), Error> {
+    let key = format!("{INDICATORS_KEY_PREFIX}{org_id}/{value}");
+    db::delete(&key, false, db::NEED_WATCH, None).await
+}
+
+pub async fn list_indicators(org_id: &str) -> Result<Vec<Indicator>, Error> {
+    let key = format!("{INDICATORS_KEY_PREFIX}{org_id}/");
+    let mut list = db::list_values(&key)
+        .await?
+        .into_iter()
+        .map(|v| json::from_slice::<Indicator>(&v))
+        .collect::<Result<Vec<_>, _>>()?;
+    list.sort_by(|a, b| a.value.cmp(&b.value));
+    Ok(list)
+}
+
+pub async fn get_feed(org_id: &str, id: &str) -> Result<IndicatorFeed, Error> {
+    let val = db::get(&format!("{FEEDS_KEY_PREFIX}{org_id}/{id}")).await?;
+    Ok(json::from_slice(&val)?)
+}
+
+pub async fn set_feed(feed: &IndicatorFeed) -> Result<(), Error> {
+    let key = format!("{FEEDS_KEY_PREFIX}{}/{}", feed.org_id, feed.id);
+    db::put(&key, json::to_vec(feed)?.into(), db::NO_NEED_WATCH, None).await
+}
+
+pub async fn delete_feed(org_id: &str, id: &str) -> Result<(), Error> {
+    let key = format!("{FEEDS_KEY_PREFIX}{org_id}/{id}");
+    db::delete(&key, false, db::NO_NEED_WATCH, None).await
+}
+
+/// Lists the feeds of an org, or of all orgs when `org_id` is empty.
+pub async fn list_feeds(org_id: &str) -> Result<Vec<IndicatorFeed>, Error> {
+    let key = if org_id.is_empty() {
+        FEEDS_KEY_PREFIX.to_string()
+    } else {
+        format!("{FEEDS_KEY_PREFIX}{org_id}/")
+    };
+    let mut list = db::list_values(&key)
+        .await?
+        .into_iter()
+        .map(|v| json::from_slice::<IndicatorFeed>(&v))
+        .collect::<Result<Vec<_>, _>>()?;
+    list.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(list)
+}
+
+pub async fn watch() -> Result<(), anyhow::Error> {
+    let key = INDICATORS_KEY_PREFIX;
+    let cluster_coordinator = db::get_coordinator().await;
+    let mut events = cluster_coordinator.watch(key).await?;
+    let events = Arc::get_mut(&mut events).unwrap();
+    log::info!("Start watching threat intel indicators");
+    loop {
+        let ev = match events.recv().await {
+            Some(ev) => ev,
+            None => {
+                log::error!("watch_threat_intel_indicators: event channel closed");
+                break;
+            }
+        };
+        match ev {
+            db::Event::Put(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                let item_value: Indicator = match db::get(&ev.key).await {
+                    Ok(val) => match json::from_slice(&val) {
+                        Ok(val) => val,
+                        Err(e) => {
+                            log::error!("Error getting value: {}", e);
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        log::error!("Error getting value: {}", e);
+                        continue;
+                    }
+                };
+                THREAT_INDICATORS.insert(item_key.to_owned(), item_value);
+            }
+            db::Event::Delete(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                THREAT_INDICATORS.remove(item_key);
+            }
+            db::Event::Empty => {}
+        }
+    }
+    Ok(())
+}
+
+pub async fn cache() -> Result<(), anyhow::Error> {
+    let ret = db::list(INDICATORS_KEY_PREFIX).await?;
+    for (item_key, item_value) in ret {
+        let item_key = item_key.strip_prefix(INDICATORS_KEY_PREFIX).unwrap();
+        let json_val: Indicator = json::from_slice(&item_value)?;
+        THREAT_INDICATORS.insert(item_key.to_owned(), json_val);
+    }
+    log::info!("Threat intel indicators Cached");
+    Ok(())
+}
@@ -0,0 +1,103 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use config::{meta::ui_profile::UiProfile, utils::json};
+use infra::errors::Error;
+
+use crate::{common::infra::config::UI_PROFILES, service::db};
+
+pub const UI_PROFILE_KEY_PREFIX: &str = "/ui_profile/";
+
+/// Saves the profile under its key, see [`UiProfile::key`].
+pub async fn set(org_id: &str, key: &str, profile: &UiProfile) -> Result<(), Error> {
+    let key = format!("{UI_PROFILE_KEY_PREFIX}{org_id}/{key}");
+    db::put(&key, json::to_vec(profile)?.into(), db::NEED_WATCH, None).await
+}
+
+pub async fn get(org_id: &str, key: &str) -> Result<UiProfile, Error> {
+    let val = db::get(&format!("{UI_PROFILE_KEY_PREFIX}{org_id}/{key}")).await?;
+    Ok(json::from_slice(&val)?)
+}
+
+pub async fn delete(org_id: &str, key: &str) -> Result<(), Error> {
+    let key = format!("{UI_PROFILE_KEY_PREFIX}{org_id}/{key}");
+    db::delete(&key, false, db::NEED_WATCH, None).await
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<UiProfile>, Error> {
+    let key = format!("{UI_PROFILE_KEY_PREFIX}{org_id}/");
+    let mut list = db::list_values(&key)
+        .await?
+        .into_iter()
+        .map(|v| json::from_slice::<UiProfile>(&v))
+        .collect::<Result<Vec<_>, _>>()?;
+    list.sort_by_key(|p| p.key());
+    Ok(list)
+}
+
+pub async fn watch() -> Result<(), anyhow::Error> {
+    let key = UI_PROFILE_KEY_PREFIX;
+    let cluster_coordinator = db::get_coordinator().await;
+    let mut events = cluster_coordinator.watch(key).await?;
+    let events = Arc::get_mut(&mut events).unwrap();
+    log::info!("Start watching ui profiles");
+    loop {
+        let ev = match events.recv().await {
+            Some(ev) => ev,
+            None => {
+                log::error!("watch_ui_profiles: event channel closed");
+                break;
+            }
+        };
+        match ev {
+            db::Event::Put(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                let item_value: UiProfile = match db::get(&ev.key).await {
+                    Ok(val) => match json::from_slice(&val) {
+                        Ok(val) => val,
+                        Err(e) => {
+                            log::error!("Error getting value: {}", e);
+                            continue;
+                        }
+                    },
+                    Err(e) => {
+                        log::error!("Error getting value: {}", e);
+                        continue;
+                    }
+                };
+                UI_PROFILES.insert(item_key.to_owned(), item_value);
+            }
+            db::Event::Delete(ev) => {
+                let item_key = ev.key.strip_prefix(key).unwrap();
+                UI_PROFILES.remove(item_key);
+            }
+            db::Event::Empty => {}
+        }
+    }
+    Ok(())
+}
+
+pub async fn cache() -> Result<(), anyhow::Error> {
+    let ret = db::list(UI_PROFILE_KEY_PREFIX).await?;
+    for (item_key, item_value) in ret {
+        let item_key = item_key.strip_prefix(UI_PROFILE_KEY_PREFIX).unwrap();
+        let json_val: UiProfile = json::from_slice(&item_value)?;
+        UI_PROFILES.insert(item_key.to_owned(), json_val);
+    }
+    log::info!("Ui profiles Cached");
+    Ok(())
+}
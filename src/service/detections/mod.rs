@@ -0,0 +1,245 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use chrono::Utc;
+use config::{
+    TIMESTAMP_COL_NAME, get_config, ider,
+    meta::{
+        cluster::RoleGroup,
+        detections::{Detection, DetectionRequest, FINDINGS_STREAM},
+        search::{self, SearchEventType},
+        stream::StreamType,
+    },
+    utils::json,
+};
+use infra::{
+    dist_lock,
+    errors::{DbError, Error},
+};
+
+use crate::service::{db, ingestion::ingest_internal_records, search as SearchService};
+
+pub mod sigma;
+
+/// Most findings written by one evaluation of a detection, the matches past it are left out.
+const MAX_FINDINGS: i64 = 10_000;
+
+/// Errors that can occur when managing or evaluating detections.
+#[derive(Debug, thiserror::Error)]
+pub enum DetectionError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error(transparent)]
+    SigmaError(#[from] sigma::SigmaError),
+
+    #[error("Detection not found")]
+    NotFound,
+
+    #[error("Stream {0} not found")]
+    StreamNotFound(String),
+
+    #[error("Frequency must be greater than 0")]
+    InvalidFrequency,
+}
+
+/// Imports a Sigma rule as a new detection of the org.
+pub async fn create(org_id: &str, req: DetectionRequest) -> Result<Detection, DetectionError> {
+    let mut detection = Detection {
+        id: ider::uuid(),
+        org_id: org_id.to_string(),
+        ..Default::default()
+    };
+    apply_request(&mut detection, req).await?;
+    db::detections::set(&detection).await?;
+    Ok(detection)
+}
+
+pub async fn update(
+    org_id: &str,
+    id: &str,
+    req: DetectionRequest,
+) -> Result<Detection, DetectionError> {
+    let mut detection = get(org_id, id).await?;
+    apply_request(&mut detection, req).await?;
+    db::detections::set(&detection).await?;
+    Ok(detection)
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<Detection, DetectionError> {
+    match db::detections::get(org_id, id).await {
+        Ok(detection) => Ok(detection),
+        Err(Error::DbError(DbError::KeyNotExists(_))) => Err(DetectionError::NotFound),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<Detection>, DetectionError> {
+    Ok(db::detections::list(org_id).await?)
+}
+
+pub async fn delete(org_id: &str, id: &str) -> Result<(), DetectionError> {
+    get(org_id, id).await?;
+    Ok(db::detections::delete(org_id, id).await?)
+}
+
+/// Compiles the rule of the request and copies its metadata into `detection`.
+async fn apply_request(
+    detection: &mut Detection,
+    req: DetectionRequest,
+) -> Result<(), DetectionError> {
+    let schema = infra::schema::get(&detection.org_id, &req.stream_name, StreamType::Logs).await?;
+    if schema.fields().is_empty() {
+        return Err(DetectionError::StreamNotFound(req.stream_name));
+    }
+    let rule = sigma::SigmaRule::parse(&req.rule)?;
+    detection.sql = rule.to_sql(&req.stream_name, &req.field_mapping)?;
+    detection.name = rule.title.clone();
+    detection.description = rule.description.clone();
+    detection.severity = rule.level;
+    detection.tags = rule.mitre_tags();
+    detection.stream_name = req.stream_name;
+    detection.rule = req.rule;
+    detection.field_mapping = req.field_mapping;
+    if let Some(frequency) = req.frequency {
+        if frequency <= 0 {
+            return Err(DetectionError::InvalidFrequency);
+        }
+        detection.frequency = frequency;
+    }
+    if let Some(enabled) = req.enabled {
+        detection.enabled = enabled;
+    }
+    detection.updated_at = Utc::now().timestamp_micros();
+    Ok(())
+}
+
+/// Evaluates all enabled detections which are due, called periodically by the detections job.
+/// The evaluation runs under a distributed lock, so a detection is evaluated by one alert manager
+/// only.
+pub async fn run() -> Result<(), DetectionError> {
+    let locker = dist_lock::lock("/detections/run", 0).await?;
+    let ret = run_due().await;
+    dist_lock::unlock(&locker).await?;
+    ret
+}
+
+async fn run_due() -> Result<(), DetectionError> {
+    // listed under the lock, the detections evaluated by another node are no longer due
+    let now = Utc::now().timestamp_micros();
+    for mut detection in db::detections::list("").await? {
+        if !detection.enabled || now - detection.last_evaluated_at < detection.frequency * 1_000_000
+        {
+            continue;
+        }
+        if let Err(e) = evaluate(&mut detection, now).await {
+            log::error!(
+                "[DETECTIONS] evaluate detection {}/{} error: {e}",
+                detection.org_id,
+                detection.id
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Runs the detection over the window since its last evaluation and writes every matching
+/// event as a finding into the `_findings` stream. The matches are read a page at a time, up to
+/// [`MAX_FINDINGS`]. Returns the number of findings.
+pub async fn evaluate(detection: &mut Detection, now: i64) -> Result<usize, DetectionError> {
+    let cfg = get_config();
+    let window = detection.frequency * 1_000_000;
+    let start_time = if detection.last_evaluated_at > 0 {
+        detection.last_evaluated_at.max(now - window * 2)
+    } else {
+        now - window
+    };
+    let page_size = cfg.limit.query_default_limit.clamp(1, MAX_FINDINGS);
+    let mut count = 0;
+    loop {
+        let req = search::Request {
+            query: search::Query {
+                sql: detection.sql.clone(),
+                from: count as i64,
+                size: page_size,
+                start_time,
+                end_time: now,
+                ..Default::default()
+            },
+            search_type: Some(SearchEventType::Other),
+            ..Default::default()
+        };
+        let trace_id = ider::generate_trace_id();
+        let resp = SearchService::grpc_search::grpc_search(
+            &trace_id,
+            &detection.org_id,
+            StreamType::Logs,
+            None,
+            &req,
+            Some(RoleGroup::Background),
+        )
+        .await?;
+        let hits = resp.hits.len();
+        count += write_findings(detection, resp.hits, now).await?;
+        if (hits as i64) < page_size {
+            break;
+        }
+        if count as i64 >= MAX_FINDINGS {
+            log::warn!(
+                "[DETECTIONS] detection {}/{} matched more than {MAX_FINDINGS} events, the findings are truncated",
+                detection.org_id,
+                detection.id
+            );
+            break;
+        }
+    }
+
+    detection.last_evaluated_at = now;
+    db::detections::set(detection).await?;
+    Ok(count)
+}
+
+/// Writes the matching events as findings, returns the number of findings written.
+async fn write_findings(
+    detection: &Detection,
+    hits: Vec<json::Value>,
+    now: i64,
+) -> Result<usize, DetectionError> {
+    let tags = detection.tags.join(",");
+    let findings = hits
+        .into_iter()
+        .map(|hit| {
+            let event_time = hit
+                .get(TIMESTAMP_COL_NAME)
+                .and_then(|v| v.as_i64())
+                .unwrap_or(now);
+            json::json!({
+                TIMESTAMP_COL_NAME: now,
+                "detection_id": detection.id,
+                "detection_name": detection.name,
+                "severity": detection.severity.to_string(),
+                "mitre_tags": tags,
+                "source_stream": detection.stream_name,
+                "event_timestamp": event_time,
+                "event": json::to_string(&hit).unwrap_or_default(),
+            })
+        })
+        .collect::<Vec<_>>();
+    let count = findings.len();
+    if count > 0 {
+        ingest_internal_records(&detection.org_id, FINDINGS_STREAM, findings).await?;
+    }
+    Ok(count)
+}
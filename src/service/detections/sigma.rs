@@ -0,0 +1,588 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Compiles Sigma rules (https://sigmahq.io) into SQL queries over a logs stream.
+//!
+//! Supported: field/value maps, value lists, keyword lists, the modifiers `contains`,
+//! `startswith`, `endswith`, `all`, `re`, `cidr`, `exists`, `gt`, `gte`, `lt`, `lte`, and
+//! conditions built from `and`, `or`, `not`, parentheses, `1 of` and `all of`. String matches
+//! are case-insensitive as the Sigma specification requires.
+
+use config::meta::detections::Severity;
+use hashbrown::HashMap;
+use serde_yaml::{Mapping, Value as YamlValue};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SigmaError {
+    #[error("invalid Sigma rule: {0}")]
+    InvalidRule(String),
+    #[error("invalid Sigma condition: {0}")]
+    InvalidCondition(String),
+    #[error("unsupported Sigma feature: {0}")]
+    Unsupported(String),
+}
+
+#[derive(Debug)]
+pub struct SigmaRule {
+    pub title: String,
+    pub description: String,
+    pub level: Severity,
+    pub tags: Vec<String>,
+    detection: Mapping,
+}
+
+impl SigmaRule {
+    pub fn parse(yaml: &str) -> Result<Self, SigmaError> {
+        let doc: YamlValue =
+            serde_yaml::from_str(yaml).map_err(|e| SigmaError::InvalidRule(e.to_string()))?;
+        let title = doc
+            .get("title")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| SigmaError::InvalidRule("missing title".to_string()))?
+            .to_string();
+        let description = doc
+            .get("description")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        let level = doc
+            .get("level")
+            .and_then(|v| v.as_str())
+            .map(Severity::from)
+            .unwrap_or_default();
+        let tags = doc
+            .get("tags")
+            .and_then(|v| v.as_sequence())
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(|t| t.as_str())
+                    .map(|t| t.to_lowercase())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let detection = doc
+            .get("detection")
+            .and_then(|v| v.as_mapping())
+            .ok_or_else(|| SigmaError::InvalidRule("missing detection".to_string()))?
+            .clone();
+        Ok(Self {
+            title,
+            description,
+            level,
+            tags,
+            detection,
+        })
+    }
+
+    /// MITRE ATT&CK tags of the rule, i.e. the tags in the `attack` namespace.
+    pub fn mitre_tags(&self) -> Vec<String> {
+        self.tags
+            .iter()
+            .filter(|t| t.starts_with("attack."))
+            .cloned()
+            .collect()
+    }
+
+    /// Compiles the rule into a query over `stream_name`. `field_mapping` translates the Sigma
+    /// field names to the field names used in the stream.
+    pub fn to_sql(
+        &self,
+        stream_name: &str,
+        field_mapping: &HashMap<String, String>,
+    ) -> Result<String, SigmaError> {
+        let mut searches = HashMap::new();
+        let mut conditions = vec![];
+        for (key, value) in self.detection.iter() {
+            let key = key
+                .as_str()
+                .ok_or_else(|| SigmaError::InvalidRule("invalid detection key".to_string()))?;
+            match key {
+                "condition" => match value {
+                    YamlValue::String(c) => conditions.push(c.clone()),
+                    YamlValue::Sequence(cs) => {
+                        conditions.extend(cs.iter().filter_map(|c| c.as_str().map(String::from)))
+                    }
+                    _ => {
+                        return Err(SigmaError::InvalidCondition(
+                            "condition must be a string".to_string(),
+                        ));
+                    }
+                },
+                "timeframe" => {}
+                _ => {
+                    searches.insert(key.to_string(), compile_search(value, field_mapping)?);
+                }
+            }
+        }
+        if conditions.is_empty() {
+            return Err(SigmaError::InvalidCondition(
+                "missing condition".to_string(),
+            ));
+        }
+
+        let mut wheres = Vec::with_capacity(conditions.len());
+        for condition in conditions.iter() {
+            let tokens = tokenize(condition)?;
+            let mut parser = ConditionParser {
+                tokens: &tokens,
+                pos: 0,
+                searches: &searches,
+            };
+            let expr = parser.parse_or()?;
+            if parser.pos != tokens.len() {
+                return Err(SigmaError::InvalidCondition(format!(
+                    "unexpected token '{}'",
+                    tokens[parser.pos]
+                )));
+            }
+            wheres.push(expr);
+        }
+        let filter = if wheres.len() == 1 {
+            wheres.remove(0)
+        } else {
+            format!("({})", wheres.join(" OR "))
+        };
+        Ok(format!(
+            "SELECT * FROM {} WHERE {}",
+            quote_ident(stream_name),
+            filter
+        ))
+    }
+}
+
+fn compile_search(
+    value: &YamlValue,
+    field_mapping: &HashMap<String, String>,
+) -> Result<String, SigmaError> {
+    match value {
+        YamlValue::Mapping(map) => compile_map(map, field_mapping),
+        YamlValue::Sequence(items) if items.iter().all(|v| v.is_mapping()) => {
+            let parts = items
+                .iter()
+                .map(|v| compile_map(v.as_mapping().unwrap(), field_mapping))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(join(parts, " OR "))
+        }
+        YamlValue::Sequence(items) => {
+            let parts = items
+                .iter()
+                .map(compile_keyword)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(join(parts, " OR "))
+        }
+        v => compile_keyword(v),
+    }
+}
+
+fn compile_keyword(value: &YamlValue) -> Result<String, SigmaError> {
+    let keyword = scalar_to_string(value)
+        .ok_or_else(|| SigmaError::InvalidRule("keywords must be scalar values".to_string()))?;
+    let keyword = keyword.trim_matches('*');
+    Ok(format!("match_all({})", quote_literal(keyword)))
+}
+
+fn compile_map(
+    map: &Mapping,
+    field_mapping: &HashMap<String, String>,
+) -> Result<String, SigmaError> {
+    let mut parts = Vec::with_capacity(map.len());
+    for (key, value) in map.iter() {
+        let key = key
+            .as_str()
+            .ok_or_else(|| SigmaError::InvalidRule("invalid field name".to_string()))?;
+        let mut items = key.split('|');
+        let field = items.next().unwrap_or_default();
+        let field = field_mapping
+            .get(field)
+            .map(|f| f.as_str())
+            .unwrap_or(field);
+        let mut match_all = false;
+        let mut modifier = None;
+        for m in items {
+            match m {
+                "all" => match_all = true,
+                _ if modifier.is_none() => modifier = Some(m),
+                _ => return Err(SigmaError::Unsupported(format!("modifier chain {key}"))),
+            }
+        }
+
+        let column = quote_ident(field);
+        let values = match value {
+            YamlValue::Sequence(values) => values.iter().collect::<Vec<_>>(),
+            v => vec![v],
+        };
+        let exprs = values
+            .into_iter()
+            .map(|v| compile_value(&column, modifier, v))
+            .collect::<Result<Vec<_>, _>>()?;
+        parts.push(join(exprs, if match_all { " AND " } else { " OR " }));
+    }
+    Ok(join(parts, " AND "))
+}
+
+fn compile_value(
+    column: &str,
+    modifier: Option<&str>,
+    value: &YamlValue,
+) -> Result<String, SigmaError> {
+    if value.is_null() {
+        return Ok(format!("{column} IS NULL"));
+    }
+    let Some(raw) = scalar_to_string(value) else {
+        return Err(SigmaError::InvalidRule(format!(
+            "invalid value for field {column}"
+        )));
+    };
+    let expr = match modifier {
+        None if value.is_number() || value.is_bool() => format!("{column} = {raw}"),
+        None => format!("{column} ILIKE {}", like_pattern(&raw, "", "")),
+        Some("contains") => format!("{column} ILIKE {}", like_pattern(&raw, "%", "%")),
+        Some("startswith") => format!("{column} ILIKE {}", like_pattern(&raw, "", "%")),
+        Some("endswith") => format!("{column} ILIKE {}", like_pattern(&raw, "%", "")),
+        Some("re") => format!("{column} ~ {}", quote_literal(&raw)),
+        Some("cidr") => format!("ip_in_cidr({column}, {})", quote_literal(&raw)),
+        Some("exists") => match value.as_bool() {
+            Some(true) => format!("{column} IS NOT NULL"),
+            Some(false) => format!("{column} IS NULL"),
+            None => {
+                return Err(SigmaError::InvalidRule(
+                    "exists expects a boolean".to_string(),
+                ));
+            }
+        },
+        Some(op @ ("gt" | "gte" | "lt" | "lte")) => {
+            if !value.is_number() {
+                return Err(SigmaError::InvalidRule(format!("{op} expects a number")));
+            }
+            let op = match op {
+                "gt" => ">",
+                "gte" => ">=",
+                "lt" => "<",
+                _ => "<=",
+            };
+            format!("{column} {op} {raw}")
+        }
+        Some(m) => return Err(SigmaError::Unsupported(format!("modifier {m}"))),
+    };
+    Ok(expr)
+}
+
+fn scalar_to_string(value: &YamlValue) -> Option<String> {
+    match value {
+        YamlValue::String(s) => Some(s.clone()),
+        YamlValue::Number(n) => Some(n.to_string()),
+        YamlValue::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Converts a Sigma value with `*` and `?` wildcards into a quoted LIKE pattern.
+fn like_pattern(value: &str, prefix: &str, suffix: &str) -> String {
+    let mut pattern = String::with_capacity(value.len() + 2);
+    pattern.push_str(prefix);
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if matches!(chars.peek(), Some('*' | '?' | '\\')) => {
+                let c = chars.next().unwrap();
+                if c == '\\' {
+                    pattern.push_str("\\\\");
+                } else {
+                    pattern.push(c);
+                }
+            }
+            '\\' => pattern.push_str("\\\\"),
+            '*' => pattern.push('%'),
+            '?' => pattern.push('_'),
+            '%' | '_' => {
+                pattern.push('\\');
+                pattern.push(c);
+            }
+            _ => pattern.push(c),
+        }
+    }
+    pattern.push_str(suffix);
+    quote_literal(&pattern)
+}
+
+fn quote_literal(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+fn quote_ident(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\"\""))
+}
+
+fn join(mut parts: Vec<String>, sep: &str) -> String {
+    if parts.len() == 1 {
+        parts.remove(0)
+    } else {
+        format!("({})", parts.join(sep))
+    }
+}
+
+fn tokenize(condition: &str) -> Result<Vec<String>, SigmaError> {
+    let mut tokens = vec![];
+    let mut current = String::new();
+    for c in condition.chars() {
+        match c {
+            '(' | ')' => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+                tokens.push(c.to_string());
+            }
+            '|' => {
+                return Err(SigmaError::Unsupported(
+                    "aggregations in conditions".to_string(),
+                ));
+            }
+            c if c.is_whitespace() => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+struct ConditionParser<'a> {
+    tokens: &'a [String],
+    pos: usize,
+    searches: &'a HashMap<String, String>,
+}
+
+impl ConditionParser<'_> {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(|t| t.as_str())
+    }
+
+    fn next(&mut self) -> Result<&str, SigmaError> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .ok_or_else(|| SigmaError::InvalidCondition("unexpected end".to_string()))?;
+        self.pos += 1;
+        Ok(token.as_str())
+    }
+
+    fn parse_or(&mut self) -> Result<String, SigmaError> {
+        let mut parts = vec![self.parse_and()?];
+        while self.peek().is_some_and(|t| t.eq_ignore_ascii_case("or")) {
+            self.pos += 1;
+            parts.push(self.parse_and()?);
+        }
+        Ok(join(parts, " OR "))
+    }
+
+    fn parse_and(&mut self) -> Result<String, SigmaError> {
+        let mut parts = vec![self.parse_not()?];
+        while self.peek().is_some_and(|t| t.eq_ignore_ascii_case("and")) {
+            self.pos += 1;
+            parts.push(self.parse_not()?);
+        }
+        Ok(join(parts, " AND "))
+    }
+
+    fn parse_not(&mut self) -> Result<String, SigmaError> {
+        if self.peek().is_some_and(|t| t.eq_ignore_ascii_case("not")) {
+            self.pos += 1;
+            return Ok(format!("NOT {}", self.parse_not()?));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<String, SigmaError> {
+        let token = self.next()?.to_string();
+        match token.to_lowercase().as_str() {
+            "(" => {
+                let expr = self.parse_or()?;
+                if self.next()? != ")" {
+                    return Err(SigmaError::InvalidCondition("missing ')'".to_string()));
+                }
+                // compound expressions are already wrapped in parentheses
+                Ok(expr)
+            }
+            quantifier @ ("1" | "any" | "all") => {
+                if !self.next()?.eq_ignore_ascii_case("of") {
+                    return Err(SigmaError::InvalidCondition(format!(
+                        "expected 'of' after '{token}'"
+                    )));
+                }
+                let target = self.next()?.to_string();
+                let mut names = self
+                    .searches
+                    .keys()
+                    .filter(|name| {
+                        if target.eq_ignore_ascii_case("them") {
+                            !name.starts_with('_')
+                        } else if let Some(prefix) = target.strip_suffix('*') {
+                            name.starts_with(prefix)
+                        } else {
+                            **name == target
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                if names.is_empty() {
+                    return Err(SigmaError::InvalidCondition(format!(
+                        "no search identifier matches '{target}'"
+                    )));
+                }
+                names.sort();
+                let parts = names
+                    .into_iter()
+                    .map(|name| self.searches[name].clone())
+                    .collect::<Vec<_>>();
+                let sep = if quantifier == "all" { " AND " } else { " OR " };
+                Ok(join(parts, sep))
+            }
+            ")" => Err(SigmaError::InvalidCondition("unexpected ')'".to_string())),
+            _ => self.searches.get(&token).cloned().ok_or_else(|| {
+                SigmaError::InvalidCondition(format!("unknown search identifier '{token}'"))
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULE: &str = r#"
+title: Suspicious Failed Logons
+id: 5f3a1c7e-0000-4000-8000-000000000001
+status: experimental
+description: Detects failed logons of machine accounts
+logsource:
+  product: windows
+  service: security
+detection:
+  selection:
+    EventID: 4625
+    TargetUserName|endswith: '$'
+  filter_local:
+    IpAddress|cidr:
+      - '10.0.0.0/8'
+      - '192.168.0.0/16'
+  keywords:
+    - 'mimikatz'
+  condition: (selection and not 1 of filter_*) or keywords
+level: high
+tags:
+  - attack.credential_access
+  - attack.t1110
+  - cve.2021.1234
+"#;
+
+    #[test]
+    fn test_parse_rule() {
+        let rule = SigmaRule::parse(RULE).unwrap();
+        assert_eq!(rule.title, "Suspicious Failed Logons");
+        assert_eq!(rule.level, Severity::High);
+        assert_eq!(
+            rule.mitre_tags(),
+            vec!["attack.credential_access", "attack.t1110"]
+        );
+    }
+
+    #[test]
+    fn test_to_sql() {
+        let rule = SigmaRule::parse(RULE).unwrap();
+        let mapping = HashMap::from([("EventID".to_string(), "event_id".to_string())]);
+        let sql = rule.to_sql("winlogs", &mapping).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM \"winlogs\" WHERE (((\"event_id\" = 4625 AND \"TargetUserName\" ILIKE '%$') AND NOT (ip_in_cidr(\"IpAddress\", '10.0.0.0/8') OR ip_in_cidr(\"IpAddress\", '192.168.0.0/16'))) OR match_all('mimikatz'))"
+        );
+    }
+
+    #[test]
+    fn test_modifiers() {
+        let rule = SigmaRule::parse(
+            r#"
+title: Modifiers
+detection:
+  selection:
+    CommandLine|contains|all:
+      - '-enc'
+      - 'http://'
+    Image: 'C:\Windows\System32\power?hell.exe'
+    OriginalFileName: '*hell.exe'
+    Count|gte: 10
+    ParentImage|exists: false
+  condition: all of them
+"#,
+        )
+        .unwrap();
+        let sql = rule.to_sql("logs", &HashMap::new()).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM \"logs\" WHERE ((\"CommandLine\" ILIKE '%-enc%' AND \"CommandLine\" ILIKE '%http://%') AND \"Image\" ILIKE 'C:\\\\Windows\\\\System32\\\\power_hell.exe' AND \"OriginalFileName\" ILIKE '%hell.exe' AND \"Count\" >= 10 AND \"ParentImage\" IS NULL)"
+        );
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        let rule = SigmaRule::parse(
+            r#"
+title: Bad
+detection:
+  selection:
+    Image|base64offset: foo
+  condition: selection
+"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            rule.to_sql("logs", &HashMap::new()),
+            Err(SigmaError::Unsupported(_))
+        ));
+
+        let rule = SigmaRule::parse(
+            r#"
+title: Bad
+detection:
+  selection:
+    Image: foo
+  condition: selection | count() > 5
+"#,
+        )
+        .unwrap();
+        assert!(rule.to_sql("logs", &HashMap::new()).is_err());
+
+        let rule = SigmaRule::parse(
+            r#"
+title: Bad
+detection:
+  selection:
+    Image: foo
+  condition: selection and other
+"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            rule.to_sql("logs", &HashMap::new()),
+            Err(SigmaError::InvalidCondition(_))
+        ));
+
+        assert!(SigmaRule::parse("detection: {}").is_err());
+    }
+}
@@ -13,8 +13,14 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::sync::Arc;
+
+use arrow::{array::RecordBatch, error::ArrowError};
+use arrow_schema::Schema;
 use async_trait::async_trait;
-use config::utils::time::parse_str_to_time;
+use config::utils::{
+    json, record_batch_ext::convert_json_to_record_batch, time::parse_str_to_time,
+};
 use vector_enrichment::{Case, IndexHandle, Table};
 use vrl::value::{ObjectMap, Value};
 
@@ -27,7 +33,21 @@ pub struct StreamTable {
     pub stream_name: String,
     pub data: Vec<vrl::value::Value>,
 }
-impl StreamTable {}
+
+impl StreamTable {
+    /// Convert the cached rows into a single record batch using the given schema,
+    /// so the table can be registered as an in-memory table for query time joins.
+    pub fn to_record_batch(&self, schema: &Arc<Schema>) -> Result<RecordBatch, ArrowError> {
+        let rows = self
+            .data
+            .iter()
+            .filter(|v| v.is_object())
+            .filter_map(|v| json::Value::try_from(v.clone()).ok())
+            .map(Arc::new)
+            .collect::<Vec<_>>();
+        convert_json_to_record_batch(schema, &rows)
+    }
+}
 
 #[async_trait]
 impl Table for StreamTable {
@@ -0,0 +1,135 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! External authorization of the requests.
+//!
+//! When `ZO_EXTERNAL_AUTHZ_URL` is set, the endpoint decides every authorized request instead of
+//! the built-in RBAC. The request follows the OPA data API, `{"input": {...}}`. The decision is
+//! read from `result`, either a boolean or an object with `allow`, or from a top level `allow`,
+//! so plain webhooks work as well. Decisions are cached for `ZO_EXTERNAL_AUTHZ_CACHE_TTL` seconds.
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+use config::{
+    RwHashMap, get_config,
+    utils::{json, time::now_micros},
+};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+/// Cached decisions above this number trigger the removal of the expired ones.
+const MAX_CACHED_DECISIONS: usize = 10_000;
+
+/// Input of an authorization decision.
+#[derive(Clone, Debug, Serialize, PartialEq, Eq, Hash)]
+pub struct AuthzInput {
+    pub user: String,
+    pub role: String,
+    pub org_id: String,
+    /// HTTP method of the request
+    pub action: String,
+    /// Object of the request, `<type>:<id>`
+    pub resource: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub parent: String,
+}
+
+/// Decision and its expiry in microseconds.
+static DECISIONS: Lazy<RwHashMap<AuthzInput, (bool, i64)>> = Lazy::new(Default::default);
+
+/// Client of the endpoint, shared so the connections are reused across decisions.
+static CLIENT: Lazy<reqwest::Client> = Lazy::new(reqwest::Client::new);
+
+pub fn is_enabled() -> bool {
+    !get_config().auth.external_authz_url.is_empty()
+}
+
+/// Asks the external endpoint whether the request is allowed. When the endpoint can't be reached
+/// or its response has no decision the request is denied, unless `ZO_EXTERNAL_AUTHZ_FAIL_OPEN`
+/// is set.
+pub async fn is_allowed(input: AuthzInput) -> bool {
+    let cfg = get_config();
+    let now = now_micros();
+    if let Some(decision) = DECISIONS.get(&input)
+        && decision.1 > now
+    {
+        return decision.0;
+    }
+
+    match request_decision(&input).await {
+        Ok(allowed) => {
+            if cfg.auth.external_authz_cache_ttl > 0 {
+                if DECISIONS.len() >= MAX_CACHED_DECISIONS {
+                    DECISIONS.retain(|_, (_, expires_at)| *expires_at > now);
+                }
+                let expires_at = now + cfg.auth.external_authz_cache_ttl * 1_000_000;
+                DECISIONS.insert(input, (allowed, expires_at));
+            }
+            allowed
+        }
+        Err(e) => {
+            log::error!(
+                "[EXTERNAL AUTHZ] decision for user {} on {} {} failed: {e}",
+                input.user,
+                input.action,
+                input.resource
+            );
+            cfg.auth.external_authz_fail_open
+        }
+    }
+}
+
+async fn request_decision(input: &AuthzInput) -> Result<bool, anyhow::Error> {
+    let cfg = get_config();
+    let mut req = CLIENT
+        .post(&cfg.auth.external_authz_url)
+        .timeout(Duration::from_secs(cfg.auth.external_authz_timeout))
+        .json(&json::json!({ "input": input }));
+    if !cfg.auth.external_authz_token.is_empty() {
+        req = req.bearer_auth(&cfg.auth.external_authz_token);
+    }
+    let resp = req.send().await?;
+    if !resp.status().is_success() {
+        return Err(anyhow!("endpoint responded with status {}", resp.status()));
+    }
+    let body: json::Value = resp.json().await?;
+    parse_decision(&body).ok_or_else(|| anyhow!("no decision in the response: {body}"))
+}
+
+fn parse_decision(body: &json::Value) -> Option<bool> {
+    match body.get("result") {
+        Some(json::Value::Bool(allowed)) => Some(*allowed),
+        Some(result) => result.get("allow").and_then(json::Value::as_bool),
+        None => body.get("allow").and_then(json::Value::as_bool),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_decision() {
+        assert_eq!(parse_decision(&json::json!({"result": true})), Some(true));
+        assert_eq!(
+            parse_decision(&json::json!({"result": {"allow": false, "reason": "x"}})),
+            Some(false)
+        );
+        assert_eq!(parse_decision(&json::json!({"allow": true})), Some(true));
+        assert_eq!(parse_decision(&json::json!({})), None);
+        assert_eq!(parse_decision(&json::json!({"result": {}})), None);
+    }
+}
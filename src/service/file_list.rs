@@ -247,6 +247,28 @@ pub async fn query_by_ids(
         }
     }
 
+    // files replaced by the compactor after the leader listed the ids are resolved from the
+    // retired entries, so the query keeps reading the snapshot it started with
+    let missing_ids = unresolved_ids(ids, db_files.iter().chain(dumped_files.iter()));
+    let retired_files = if missing_ids.is_empty() {
+        Vec::new()
+    } else {
+        match infra::table::file_list_retired::query_by_ids(&missing_ids).await {
+            Ok(files) => files,
+            Err(e) => {
+                log::error!("[trace_id {trace_id}] file_list query retired files failed: {e}");
+                Vec::new()
+            }
+        }
+    };
+    if retired_files.len() < missing_ids.len() {
+        log::warn!(
+            "[trace_id {trace_id}] file_list can't resolve {} of {} ids, the files were removed",
+            missing_ids.len() - retired_files.len(),
+            missing_ids.len()
+        );
+    }
+
     // 3. set the local cache
     if !cfg.common.local_mode {
         let start = std::time::Instant::now();
@@ -290,11 +312,21 @@ pub async fn query_by_ids(
     if !cfg.common.file_list_dump_dual_write {
         files.extend(dumped_files);
     }
+    files.extend(retired_files);
     files.par_sort_unstable_by(|a, b| a.key.cmp(&b.key));
     files.dedup_by(|a, b| a.key == b.key);
     Ok(files)
 }
 
+/// Returns the ids none of the files has.
+fn unresolved_ids<'a>(ids: &[i64], files: impl Iterator<Item = &'a FileKey>) -> Vec<i64> {
+    let found_ids = files.map(|f| f.id).collect::<HashSet<_>>();
+    ids.iter()
+        .filter(|id| !found_ids.contains(*id))
+        .copied()
+        .collect()
+}
+
 #[tracing::instrument(
     name = "service::file_list::query_ids",
     skip_all,
@@ -392,3 +424,32 @@ pub async fn update_compressed_size(key: &str, size: i64) -> Result<()> {
         .await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use config::meta::stream::FileMeta;
+
+    use super::*;
+
+    fn file(id: i64) -> FileKey {
+        FileKey::new(
+            id,
+            String::new(),
+            format!("files/default/logs/app/{id}.parquet"),
+            FileMeta::default(),
+            false,
+        )
+    }
+
+    #[test]
+    fn test_unresolved_ids() {
+        let db_files = vec![file(1), file(3)];
+        let dumped_files = vec![file(4)];
+        assert_eq!(
+            unresolved_ids(&[1, 2, 3, 4, 5], db_files.iter().chain(dumped_files.iter())),
+            vec![2, 5]
+        );
+        assert!(unresolved_ids(&[1, 3], db_files.iter()).is_empty());
+        assert_eq!(unresolved_ids(&[7], std::iter::empty()), vec![7]);
+    }
+}
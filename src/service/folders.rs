@@ -114,6 +114,41 @@ pub async fn save_folder(
         return Err(FolderError::FolderNameAlreadyExists);
     }
 
+    create_folder(org_id, folder, folder_type).await
+}
+
+/// Saves a folder keeping its `folder_id`, used to import folders exported from another
+/// organization. Returns `None` when a folder with the same id exists already, it is kept as it
+/// is.
+#[tracing::instrument(skip(folder))]
+pub async fn import_folder(
+    org_id: &str,
+    mut folder: Folder,
+    folder_type: FolderType,
+) -> Result<Option<Folder>, FolderError> {
+    folder.name = folder.name.trim().to_string();
+    if folder.name.is_empty() {
+        return Err(FolderError::MissingName);
+    }
+
+    if table::folders::exists(org_id, &folder.folder_id, folder_type).await? {
+        return Ok(None);
+    }
+    if get_folder_by_name(org_id, &folder.name, folder_type)
+        .await
+        .is_ok()
+    {
+        return Err(FolderError::FolderNameAlreadyExists);
+    }
+
+    create_folder(org_id, folder, folder_type).await.map(Some)
+}
+
+async fn create_folder(
+    org_id: &str,
+    folder: Folder,
+    folder_type: FolderType,
+) -> Result<Folder, FolderError> {
     let (_id, folder) = table::folders::put(org_id, None, folder, folder_type).await?;
     let folder_type_ofga = match folder_type {
         FolderType::Dashboards => "folders",
@@ -0,0 +1,97 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use chrono::Utc;
+use config::meta::grok::{GrokPattern, GrokTestRequest, GrokTestResponse};
+
+use crate::{
+    common::utils::grok::{self, BUILTIN, Grok, GrokError},
+    service::db,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum GrokPatternError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Invalid grok pattern name: {0}")]
+    InvalidName(String),
+
+    #[error("Grok pattern not found")]
+    NotFound,
+
+    #[error(transparent)]
+    InvalidPattern(#[from] GrokError),
+}
+
+/// Lists the custom patterns of an org, optionally followed by the built-in library.
+pub async fn list(org_id: &str, builtin: bool) -> Result<Vec<GrokPattern>, GrokPatternError> {
+    let mut list = db::grok::list(org_id).await?;
+    if builtin {
+        let mut builtin = BUILTIN
+            .iter()
+            .map(|(name, pattern)| GrokPattern {
+                name: name.to_string(),
+                pattern: pattern.to_string(),
+                description: String::new(),
+                builtin: true,
+                updated_at: 0,
+            })
+            .collect::<Vec<_>>();
+        builtin.sort_by(|a, b| a.name.cmp(&b.name));
+        list.extend(builtin);
+    }
+    Ok(list)
+}
+
+/// Validates and stores a custom pattern, it must compile together with the other patterns of
+/// the org.
+pub async fn save(
+    org_id: &str,
+    name: &str,
+    mut pattern: GrokPattern,
+) -> Result<GrokPattern, GrokPatternError> {
+    if !GrokPattern::is_valid_name(name) {
+        return Err(GrokPatternError::InvalidName(name.to_string()));
+    }
+    let lookup = |n: &str| {
+        if n == name {
+            Some(pattern.pattern.clone())
+        } else {
+            grok::lookup_pattern(org_id, n)
+        }
+    };
+    Grok::new(&format!("%{{{name}}}"), &lookup)?;
+
+    pattern.name = name.to_string();
+    pattern.builtin = false;
+    pattern.updated_at = Utc::now().timestamp_micros();
+    db::grok::set(org_id, &pattern).await?;
+    Ok(pattern)
+}
+
+pub async fn delete(org_id: &str, name: &str) -> Result<(), GrokPatternError> {
+    if db::grok::get(org_id, name).await.is_err() {
+        return Err(GrokPatternError::NotFound);
+    }
+    Ok(db::grok::delete(org_id, name).await?)
+}
+
+/// Parses the samples with a grok expression, without storing anything.
+pub fn test(org_id: &str, req: GrokTestRequest) -> Result<GrokTestResponse, GrokPatternError> {
+    let grok = grok::compile(org_id, &req.pattern)?;
+    let results = req.samples.iter().map(|s| grok.parse(s)).collect();
+    Ok(GrokTestResponse { results })
+}
@@ -0,0 +1,358 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use chrono::NaiveDateTime;
+use config::{
+    META_ORG_ID, ider,
+    meta::{
+        alerts::alert::ListAlertsParams,
+        annotations::ListAnnotationsQuery,
+        incident_timeline::{
+            IncidentTimeline, IncidentTimelineQuery, TimelineEvent, TimelineEventKind,
+        },
+        search::{self, SearchEventType},
+        self_reporting::usage::TRIGGERS_USAGE_STREAM,
+        stream::StreamType,
+    },
+    utils::json::Value,
+};
+
+use crate::service::{alerts::alert as alert_service, annotations, search as SearchService};
+
+/// Stream of the audited API requests in the meta org.
+const AUDIT_STREAM: &str = "audit";
+/// Most events read from each of the alert and audit sources.
+const MAX_SOURCE_EVENTS: i64 = 1000;
+/// Most anomalies added to the timeline.
+const MAX_ANOMALIES: usize = 10;
+/// Smallest z-score of a volume bucket to be reported as anomaly.
+const ANOMALY_MIN_Z_SCORE: f64 = 3.0;
+/// Number of histogram buckets used to find anomalies.
+const ANOMALY_BUCKETS: i64 = 60;
+
+#[derive(Debug, thiserror::Error)]
+pub enum IncidentTimelineError {
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+}
+
+/// Assembles a timeline of fired alerts, annotations, config changes and volume anomalies. A
+/// source which can't be read, e.g. the audit stream when auditing is disabled, is reported in
+/// `errors` instead of failing the whole timeline. The streams of the org are searched as the
+/// user, the alert and audit sources of the meta org are only read for the events of the org.
+pub async fn assemble(
+    org_id: &str,
+    user_id: &str,
+    query: &IncidentTimelineQuery,
+) -> Result<IncidentTimeline, IncidentTimelineError> {
+    query
+        .validate()
+        .map_err(IncidentTimelineError::InvalidRequest)?;
+    let services = query.get_services();
+    let streams = query.get_streams();
+    let filters = services
+        .iter()
+        .chain(streams.iter())
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let mut timeline = IncidentTimeline {
+        start_time: query.start_time,
+        end_time: query.end_time,
+        ..Default::default()
+    };
+    let (alerts, annotations, changes, anomalies) = tokio::join!(
+        fired_alerts(org_id, query, &services, &streams),
+        annotation_events(org_id, query, &services),
+        config_changes(org_id, query, &filters),
+        anomalies(org_id, user_id, query, &streams),
+    );
+    for (source, events) in [
+        ("alerts", alerts),
+        ("annotations", annotations),
+        ("audit", changes),
+        ("anomalies", anomalies),
+    ] {
+        match events {
+            Ok(events) => timeline.events.extend(events),
+            Err(e) => {
+                log::warn!("[INCIDENT TIMELINE] org {org_id}: failed to read {source}: {e}");
+                timeline.errors.push(format!("{source}: {e}"));
+            }
+        }
+    }
+    timeline.events.sort_by_key(|e| e.timestamp);
+    Ok(timeline)
+}
+
+async fn search_hits(
+    org_id: &str,
+    user_id: Option<&str>,
+    sql: String,
+    query: &IncidentTimelineQuery,
+    size: i64,
+) -> Result<Vec<Value>, infra::errors::Error> {
+    let req = search::Request {
+        query: search::Query {
+            sql,
+            size,
+            start_time: query.start_time,
+            end_time: query.end_time,
+            ..Default::default()
+        },
+        search_type: Some(SearchEventType::Other),
+        ..Default::default()
+    };
+    let trace_id = ider::generate_trace_id();
+    let resp = SearchService::search(
+        &trace_id,
+        org_id,
+        StreamType::Logs,
+        user_id.map(str::to_string),
+        &req,
+    )
+    .await?;
+    Ok(resp.hits)
+}
+
+fn escape_str(s: &str) -> String {
+    s.replace('\'', "''")
+}
+
+fn matches_any(haystack: &str, needles: &[String]) -> bool {
+    needles.iter().any(|n| haystack.contains(n.as_str()))
+}
+
+/// Alerts which sent notifications, read from the triggers usage stream.
+async fn fired_alerts(
+    org_id: &str,
+    query: &IncidentTimelineQuery,
+    services: &[String],
+    streams: &[String],
+) -> Result<Vec<TimelineEvent>, anyhow::Error> {
+    let alerts = alert_service::list_with_folders_db(ListAlertsParams::new(org_id))
+        .await?
+        .into_iter()
+        .map(|(_, alert)| alert)
+        .filter(|alert| {
+            (services.is_empty() && streams.is_empty())
+                || streams.contains(&alert.stream_name)
+                || matches_any(&alert.name, services)
+        })
+        .collect::<Vec<_>>();
+    if alerts.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let sql = format!(
+        "SELECT _timestamp, key, success_response, error FROM \"{TRIGGERS_USAGE_STREAM}\" WHERE org = '{}' AND module = 'alert' AND success_response IS NOT NULL ORDER BY _timestamp DESC",
+        escape_str(org_id)
+    );
+    let hits = search_hits(META_ORG_ID, None, sql, query, MAX_SOURCE_EVENTS).await?;
+    let events = hits
+        .iter()
+        .filter_map(|hit| {
+            let key = hit.get("key")?.as_str()?;
+            let alert = alerts
+                .iter()
+                .find(|a| key.starts_with(&format!("{}/", a.name)))?;
+            Some(TimelineEvent {
+                timestamp: hit.get("_timestamp")?.as_i64()?,
+                end_time: None,
+                kind: TimelineEventKind::Alert,
+                title: format!("Alert {} fired", alert.name),
+                details: Some(match hit.get("error").and_then(|v| v.as_str()) {
+                    Some(err) if !err.is_empty() => {
+                        format!("stream {}, notification errors: {err}", alert.stream_name)
+                    }
+                    _ => format!("stream {}", alert.stream_name),
+                }),
+                link: None,
+            })
+        })
+        .collect();
+    Ok(events)
+}
+
+/// Org annotations, those tagged with a `service:` not in the requested services are skipped.
+async fn annotation_events(
+    org_id: &str,
+    query: &IncidentTimelineQuery,
+    services: &[String],
+) -> Result<Vec<TimelineEvent>, anyhow::Error> {
+    let list_query = ListAnnotationsQuery {
+        start_time: query.start_time,
+        end_time: query.end_time,
+        tags: None,
+    };
+    let events = annotations::list(org_id, &list_query)
+        .await?
+        .into_iter()
+        .filter(|a| {
+            let tagged = a
+                .tags
+                .iter()
+                .filter_map(|t| t.strip_prefix("service:"))
+                .collect::<Vec<_>>();
+            services.is_empty()
+                || tagged.is_empty()
+                || tagged.iter().any(|s| services.iter().any(|v| v == s))
+        })
+        .map(|a| TimelineEvent {
+            timestamp: a.start_time,
+            end_time: a.end_time,
+            kind: TimelineEventKind::Annotation,
+            title: a.title,
+            details: match (a.text, a.tags.is_empty()) {
+                (Some(text), false) => Some(format!("{text} [{}]", a.tags.join(", "))),
+                (Some(text), true) => Some(text),
+                (None, false) => Some(format!("[{}]", a.tags.join(", "))),
+                (None, true) => None,
+            },
+            link: a.link,
+        })
+        .collect();
+    Ok(events)
+}
+
+/// Successful write requests of the org, read from the audit stream.
+async fn config_changes(
+    org_id: &str,
+    query: &IncidentTimelineQuery,
+    filters: &[String],
+) -> Result<Vec<TimelineEvent>, anyhow::Error> {
+    let sql = format!(
+        "SELECT _timestamp, user_email, method, path, response_code FROM \"{AUDIT_STREAM}\" WHERE org_id = '{}' AND method IN ('POST', 'PUT', 'PATCH', 'DELETE') AND response_code < 400 ORDER BY _timestamp DESC",
+        escape_str(org_id)
+    );
+    let hits = search_hits(META_ORG_ID, None, sql, query, MAX_SOURCE_EVENTS).await?;
+    let events = hits
+        .iter()
+        .filter_map(|hit| {
+            let path = hit.get("path")?.as_str()?;
+            // ingestion and search requests are not changes
+            if path.contains("/_") || (!filters.is_empty() && !matches_any(path, filters)) {
+                return None;
+            }
+            let method = hit.get("method")?.as_str()?;
+            Some(TimelineEvent {
+                timestamp: hit.get("_timestamp")?.as_i64()?,
+                end_time: None,
+                kind: TimelineEventKind::ConfigChange,
+                title: format!("{method} {path}"),
+                details: hit
+                    .get("user_email")
+                    .and_then(|v| v.as_str())
+                    .map(|user| format!("by {user}")),
+                link: None,
+            })
+        })
+        .collect();
+    Ok(events)
+}
+
+/// Log volume spikes of the requested streams.
+async fn anomalies(
+    org_id: &str,
+    user_id: &str,
+    query: &IncidentTimelineQuery,
+    streams: &[String],
+) -> Result<Vec<TimelineEvent>, anyhow::Error> {
+    let interval = ((query.end_time - query.start_time) / 1_000_000 / ANOMALY_BUCKETS).max(60);
+    let mut spikes = Vec::new();
+    for stream in streams {
+        let sql = format!(
+            "SELECT histogram(_timestamp, '{interval} second') AS zo_sql_key, COUNT(*) AS zo_sql_num FROM \"{stream}\" GROUP BY zo_sql_key ORDER BY zo_sql_key"
+        );
+        let hits = search_hits(org_id, Some(user_id), sql, query, ANOMALY_BUCKETS * 2).await?;
+        let buckets = hits
+            .iter()
+            .filter_map(|hit| {
+                let ts = parse_bucket_time(hit.get("zo_sql_key")?)?;
+                Some((ts, hit.get("zo_sql_num")?.as_f64()?))
+            })
+            .collect::<Vec<_>>();
+        spikes.extend(
+            find_spikes(&buckets, ANOMALY_MIN_Z_SCORE)
+                .into_iter()
+                .map(|(ts, count, z)| (stream, ts, count, z)),
+        );
+    }
+    spikes.sort_by(|a, b| b.3.total_cmp(&a.3));
+    spikes.truncate(MAX_ANOMALIES);
+    let events = spikes
+        .into_iter()
+        .map(|(stream, ts, count, z)| TimelineEvent {
+            timestamp: ts,
+            end_time: Some(ts + interval * 1_000_000),
+            kind: TimelineEventKind::Anomaly,
+            title: format!("Log volume spike in {stream}"),
+            details: Some(format!("{count} records, z-score {z:.1}")),
+            link: None,
+        })
+        .collect();
+    Ok(events)
+}
+
+fn parse_bucket_time(v: &Value) -> Option<i64> {
+    match v {
+        Value::Number(n) => n.as_i64(),
+        Value::String(s) => NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S")
+            .ok()
+            .map(|t| t.and_utc().timestamp_micros()),
+        _ => None,
+    }
+}
+
+/// Returns the buckets whose count is at least `min_z` standard deviations above the mean, as
+/// `(time, count, z-score)`.
+fn find_spikes(buckets: &[(i64, f64)], min_z: f64) -> Vec<(i64, f64, f64)> {
+    if buckets.len() < 3 {
+        return vec![];
+    }
+    let n = buckets.len() as f64;
+    let mean = buckets.iter().map(|(_, c)| c).sum::<f64>() / n;
+    let std_dev = (buckets.iter().map(|(_, c)| (c - mean).powi(2)).sum::<f64>() / n).sqrt();
+    if std_dev == 0.0 {
+        return vec![];
+    }
+    buckets
+        .iter()
+        .map(|(ts, c)| (*ts, *c, (c - mean) / std_dev))
+        .filter(|(_, _, z)| *z >= min_z)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_spikes() {
+        let mut buckets = (0..20).map(|i| (i, 100.0)).collect::<Vec<_>>();
+        assert!(find_spikes(&buckets, 3.0).is_empty());
+        buckets[10].1 = 1000.0;
+        let spikes = find_spikes(&buckets, 3.0);
+        assert_eq!(spikes.len(), 1);
+        assert_eq!(spikes[0].0, 10);
+        assert!(find_spikes(&buckets[..2], 3.0).is_empty());
+    }
+
+    #[test]
+    fn test_parse_bucket_time() {
+        let ts = parse_bucket_time(&Value::String("2023-11-14T22:13:20".to_string()));
+        assert_eq!(ts, Some(1_700_000_000_000_000));
+        assert_eq!(parse_bucket_time(&Value::from(42)), Some(42));
+    }
+}
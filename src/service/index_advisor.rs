@@ -0,0 +1,563 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The index advisor logs the queries slower than `ZO_INDEX_ADVISOR_SLOW_QUERY_THRESHOLD` with
+//! the fields their predicates filter on, and periodically compares the fields filtered on by
+//! the slow queries of each stream with its settings. The fields the stream doesn't index are
+//! recommended as:
+//! - partition key, when filtered on by equality with a few distinct values, e.g. `service`
+//! - bloom filter, when filtered on by equality with many distinct values, e.g. `trace_id`
+//! - full text search key, when searched for a substring, e.g. `message LIKE '%error%'`
+//!
+//! The recommendations are applied to the stream settings once approved by a user, dismissed
+//! ones are not recommended again.
+
+use config::{
+    TIMESTAMP_COL_NAME, get_config, ider,
+    meta::{
+        index_advisor::{IndexRecommendation, RecommendationKind, RecommendationStatus, SlowQuery},
+        sql::TableReferenceExt,
+        stream::{
+            StreamFieldUsage, StreamPartition, StreamStorageSample, StreamType,
+            UpdateStreamSettings,
+        },
+    },
+    utils::time::{day_micros, now_micros},
+};
+use hashbrown::{HashMap, HashSet};
+use infra::{
+    dist_lock,
+    schema::{
+        get_stream_setting_bloom_filter_fields, get_stream_setting_fts_fields,
+        unwrap_stream_settings,
+    },
+    table,
+};
+
+use crate::service::{db, search::sql::Sql, stream};
+
+/// Fields filtered on by equality with at most this many distinct values, each of them queried
+/// at least twice, are recommended as partition keys rather than bloom filters.
+const PARTITION_KEY_MAX_VALUES: usize = 32;
+
+/// Number of distinct values of a field counted, enough to tell partition keys apart.
+const MAX_DISTINCT_VALUES: usize = 1000;
+
+/// Time of the last analysis, shared by the compactors.
+const LAST_RUN_KEY: &str = "/index_advisor/last_run";
+
+#[derive(Debug, thiserror::Error)]
+pub enum IndexAdvisorError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Index recommendation not found")]
+    NotFound,
+
+    #[error("Index recommendation was already {0}")]
+    NotOpen(RecommendationStatus),
+
+    #[error("Error applying the index recommendation: {0}")]
+    ApplyFailed(String),
+}
+
+/// Logs the query if it was slow, written in the background.
+pub fn record_query(sql: &Sql, trace_id: &str, user_id: Option<&str>, took_secs: f64) {
+    let cfg = get_config();
+    if !cfg.common.index_advisor_enabled
+        || took_secs < cfg.common.index_advisor_slow_query_threshold as f64
+    {
+        return;
+    }
+    let text_search_fields = config::utils::sql::text_search_fields(&sql.sql).unwrap_or_default();
+    let now = now_micros();
+    for stream in sql.stream_names.iter() {
+        let columns = sql.columns.get(stream);
+        let query = SlowQuery {
+            trace_id: trace_id.to_string(),
+            stream_type: stream.get_stream_type(sql.stream_type),
+            stream_name: stream.stream_name(),
+            user_id: user_id.map(|v| v.to_string()),
+            sql: sql.sql.clone(),
+            took: (took_secs * 1000.0) as i64,
+            equal_items: sql.equal_items.get(stream).cloned().unwrap_or_default(),
+            text_search_fields: text_search_fields
+                .iter()
+                .filter(|field| columns.is_some_and(|columns| columns.contains(*field)))
+                .cloned()
+                .collect(),
+            created_at: now,
+        };
+        let org_id = sql.org_id.clone();
+        tokio::task::spawn(async move {
+            if let Err(e) = table::slow_queries::add(&org_id, &query).await {
+                log::error!(
+                    "[INDEX_ADVISOR] error logging slow query of {org_id}/{}/{}: {e}",
+                    query.stream_type,
+                    query.stream_name
+                );
+            }
+        });
+    }
+}
+
+/// Lists the recommendations of the org, of the stream and with the status if given.
+pub async fn list(
+    org_id: &str,
+    stream: Option<(StreamType, &str)>,
+    status: Option<RecommendationStatus>,
+) -> Result<Vec<IndexRecommendation>, IndexAdvisorError> {
+    Ok(table::index_recommendations::list(org_id, stream, status).await?)
+}
+
+/// Approves the recommendation, adding its field to the stream settings.
+pub async fn apply(org_id: &str, id: &str) -> Result<IndexRecommendation, IndexAdvisorError> {
+    let mut rec = get_open(org_id, id).await?;
+    let mut settings = UpdateStreamSettings::default();
+    match rec.kind {
+        RecommendationKind::BloomFilter => settings.bloom_filter_fields.add.push(rec.field.clone()),
+        RecommendationKind::PartitionKey => settings
+            .partition_keys
+            .add
+            .push(StreamPartition::new(&rec.field)),
+        RecommendationKind::FullTextSearch => {
+            settings.full_text_search_keys.add.push(rec.field.clone())
+        }
+    }
+    let resp = stream::update_stream_settings(org_id, &rec.stream_name, rec.stream_type, settings)
+        .await
+        .map_err(|e| IndexAdvisorError::ApplyFailed(e.to_string()))?;
+    if !resp.status().is_success() {
+        return Err(IndexAdvisorError::ApplyFailed(format!(
+            "updating the settings of stream {} failed with status {}",
+            rec.stream_name,
+            resp.status()
+        )));
+    }
+
+    rec.status = RecommendationStatus::Applied;
+    rec.updated_at = now_micros();
+    table::index_recommendations::set_status(org_id, id, rec.status, rec.updated_at).await?;
+    log::info!(
+        "[INDEX_ADVISOR] applied {} on {org_id}/{}/{} field {}",
+        rec.kind,
+        rec.stream_type,
+        rec.stream_name,
+        rec.field
+    );
+    Ok(rec)
+}
+
+/// Dismisses the recommendation, which is not recommended again.
+pub async fn dismiss(org_id: &str, id: &str) -> Result<(), IndexAdvisorError> {
+    get_open(org_id, id).await?;
+    table::index_recommendations::set_status(
+        org_id,
+        id,
+        RecommendationStatus::Dismissed,
+        now_micros(),
+    )
+    .await?;
+    Ok(())
+}
+
+async fn get_open(org_id: &str, id: &str) -> Result<IndexRecommendation, IndexAdvisorError> {
+    let rec = table::index_recommendations::get(org_id, id)
+        .await?
+        .ok_or(IndexAdvisorError::NotFound)?;
+    if rec.status != RecommendationStatus::Open {
+        return Err(IndexAdvisorError::NotOpen(rec.status));
+    }
+    Ok(rec)
+}
+
+pub async fn delete_stream(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> Result<(), infra::errors::Error> {
+    table::slow_queries::delete_stream(org_id, stream_type, stream_name).await?;
+    table::index_recommendations::delete_stream(org_id, stream_type, stream_name).await
+}
+
+/// Analyzes the slow queries every `ZO_INDEX_ADVISOR_INTERVAL` seconds. The analysis runs under a
+/// distributed lock and is skipped when another compactor did it during the interval.
+pub async fn run() -> Result<(), anyhow::Error> {
+    let cfg = get_config();
+    if !cfg.common.index_advisor_enabled || cfg.common.index_advisor_interval == 0 {
+        return Ok(());
+    }
+    let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(
+        cfg.common.index_advisor_interval,
+    ));
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        if let Err(e) = advise_once(cfg.common.index_advisor_interval).await {
+            log::error!("[INDEX_ADVISOR] error analyzing the slow queries: {e}");
+        }
+    }
+}
+
+async fn advise_once(interval_secs: u64) -> Result<(), infra::errors::Error> {
+    let locker = dist_lock::lock("/index_advisor/run", 0).await?;
+    let ret = advise_if_due(interval_secs).await;
+    dist_lock::unlock(&locker).await?;
+    ret
+}
+
+async fn advise_if_due(interval_secs: u64) -> Result<(), infra::errors::Error> {
+    let now = now_micros();
+    let last_run = match db::get(LAST_RUN_KEY).await {
+        Ok(val) => String::from_utf8_lossy(&val).parse().unwrap_or_default(),
+        Err(_) => 0,
+    };
+    if now - last_run < interval_secs as i64 * 1_000_000 {
+        return Ok(());
+    }
+    advise().await?;
+    db::put(
+        LAST_RUN_KEY,
+        now.to_string().into(),
+        db::NO_NEED_WATCH,
+        None,
+    )
+    .await
+}
+
+type StreamKey = (String, StreamType, String);
+
+/// Updates the recommendations of all the streams from their slow queries of the lookback
+/// period. The open recommendations the slow queries no longer support are removed.
+async fn advise() -> Result<(), infra::errors::Error> {
+    let cfg = get_config();
+    let now = now_micros();
+    let start_time = now - day_micros(cfg.common.index_advisor_lookback_days);
+    table::slow_queries::delete_before(start_time).await?;
+
+    let mut streams: HashMap<StreamKey, Vec<SlowQuery>> = HashMap::new();
+    for (org_id, query) in table::slow_queries::list_since(start_time).await? {
+        streams
+            .entry((org_id, query.stream_type, query.stream_name.clone()))
+            .or_default()
+            .push(query);
+    }
+    let mut existing: HashMap<(StreamKey, RecommendationKind, String), IndexRecommendation> =
+        table::index_recommendations::list_all()
+            .await?
+            .into_iter()
+            .map(|(org_id, rec)| {
+                (
+                    (
+                        (org_id, rec.stream_type, rec.stream_name.clone()),
+                        rec.kind,
+                        rec.field.clone(),
+                    ),
+                    rec,
+                )
+            })
+            .collect();
+
+    for (key, queries) in streams {
+        let (org_id, stream_type, stream_name) = &key;
+        let recs = match analyze_stream(
+            org_id,
+            *stream_type,
+            stream_name,
+            &queries,
+            start_time,
+            now,
+        )
+        .await
+        {
+            Ok(recs) => recs,
+            Err(e) => {
+                log::error!(
+                    "[INDEX_ADVISOR] error analyzing {org_id}/{stream_type}/{stream_name}: {e}"
+                );
+                continue;
+            }
+        };
+        for rec in recs {
+            let rec = match existing.remove(&(key.clone(), rec.kind, rec.field.clone())) {
+                // dismissed recommendations are not made again
+                Some(prev) if prev.status != RecommendationStatus::Open => continue,
+                Some(prev) => IndexRecommendation {
+                    id: prev.id,
+                    created_at: prev.created_at,
+                    ..rec
+                },
+                None => IndexRecommendation {
+                    id: ider::uuid(),
+                    created_at: now,
+                    ..rec
+                },
+            };
+            table::index_recommendations::put(org_id, &rec).await?;
+        }
+    }
+
+    for ((key, ..), rec) in existing {
+        if rec.status == RecommendationStatus::Open {
+            table::index_recommendations::delete(&key.0, &rec.id).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn analyze_stream(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    queries: &[SlowQuery],
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<IndexRecommendation>, infra::errors::Error> {
+    let schema = infra::schema::get(org_id, stream_name, stream_type).await?;
+    if schema.fields().is_empty() {
+        // the stream was deleted
+        return Ok(vec![]);
+    }
+    let settings = unwrap_stream_settings(&schema);
+    let indexes = StreamIndexes {
+        fields: schema
+            .fields()
+            .iter()
+            .map(|f| f.name().to_string())
+            .collect(),
+        bloom_filter_fields: get_stream_setting_bloom_filter_fields(&settings),
+        partition_keys: settings
+            .as_ref()
+            .map(|s| {
+                s.partition_keys
+                    .iter()
+                    .filter(|p| !p.disabled)
+                    .map(|p| p.field.clone())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        fts_fields: get_stream_setting_fts_fields(&settings),
+    };
+    let usage = table::stream_field_usage::list(
+        org_id,
+        stream_type,
+        stream_name,
+        StreamStorageSample::day_of(start_time),
+        end_time,
+    )
+    .await?;
+    let mut recs = recommend(
+        queries,
+        &usage,
+        &indexes,
+        get_config().common.index_advisor_min_slow_queries,
+    );
+    for rec in recs.iter_mut() {
+        rec.stream_type = stream_type;
+        rec.stream_name = stream_name.to_string();
+        rec.updated_at = end_time;
+    }
+    Ok(recs)
+}
+
+/// The fields of a stream and the ones its settings index.
+#[derive(Debug, Default)]
+struct StreamIndexes {
+    fields: HashSet<String>,
+    bloom_filter_fields: Vec<String>,
+    partition_keys: Vec<String>,
+    fts_fields: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct FieldStats {
+    equal_queries: i64,
+    values: HashSet<String>,
+    text_queries: i64,
+}
+
+/// Recommends indexing the fields filtered on by at least `min_slow_queries` of the slow
+/// queries of the stream, most filtered on first.
+fn recommend(
+    queries: &[SlowQuery],
+    usage: &HashMap<String, StreamFieldUsage>,
+    indexes: &StreamIndexes,
+    min_slow_queries: i64,
+) -> Vec<IndexRecommendation> {
+    let mut stats: HashMap<&str, FieldStats> = HashMap::new();
+    for query in queries {
+        let mut filtered = HashSet::new();
+        for (field, value) in query.equal_items.iter() {
+            let field_stats = stats.entry(field.as_str()).or_default();
+            if filtered.insert(field.as_str()) {
+                field_stats.equal_queries += 1;
+            }
+            if field_stats.values.len() < MAX_DISTINCT_VALUES {
+                field_stats.values.insert(value.to_string());
+            }
+        }
+        for field in query.text_search_fields.iter() {
+            stats.entry(field.as_str()).or_default().text_queries += 1;
+        }
+    }
+
+    let mut recs = vec![];
+    for (field, field_stats) in stats {
+        if field == TIMESTAMP_COL_NAME || !indexes.fields.contains(field) {
+            continue;
+        }
+        let queries = usage.get(field).map(|u| u.queries).unwrap_or_default();
+        let rec = |kind, reason, slow_queries| IndexRecommendation {
+            id: String::new(),
+            stream_type: StreamType::default(),
+            stream_name: String::new(),
+            kind,
+            field: field.to_string(),
+            reason,
+            slow_queries,
+            queries,
+            status: RecommendationStatus::Open,
+            created_at: 0,
+            updated_at: 0,
+        };
+
+        if field_stats.text_queries >= min_slow_queries
+            && !indexes.fts_fields.iter().any(|f| f == field)
+        {
+            recs.push(rec(
+                RecommendationKind::FullTextSearch,
+                format!(
+                    "{} slow queries searched the text of {field}, indexing it for full text search lets match_all() find the values without scanning the files",
+                    field_stats.text_queries
+                ),
+                field_stats.text_queries,
+            ));
+        }
+
+        if field_stats.equal_queries >= min_slow_queries
+            && !indexes.partition_keys.iter().any(|f| f == field)
+            && !indexes.bloom_filter_fields.iter().any(|f| f == field)
+        {
+            let values = field_stats.values.len();
+            if values <= PARTITION_KEY_MAX_VALUES
+                && (values as i64) * 2 <= field_stats.equal_queries
+            {
+                recs.push(rec(
+                    RecommendationKind::PartitionKey,
+                    format!(
+                        "{} slow queries filtered {field} on {values} distinct values, partitioning the files by {field} skips the files of the other values",
+                        field_stats.equal_queries
+                    ),
+                    field_stats.equal_queries,
+                ));
+            } else {
+                recs.push(rec(
+                    RecommendationKind::BloomFilter,
+                    format!(
+                        "{} slow queries filtered {field} on {values} distinct values, a bloom filter skips the files without the value",
+                        field_stats.equal_queries
+                    ),
+                    field_stats.equal_queries,
+                ));
+            }
+        }
+    }
+    recs.sort_by(|a, b| {
+        b.slow_queries
+            .cmp(&a.slow_queries)
+            .then_with(|| a.field.cmp(&b.field))
+    });
+    recs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(equal_items: &[(&str, &str)], text_search_fields: &[&str]) -> SlowQuery {
+        SlowQuery {
+            equal_items: equal_items
+                .iter()
+                .map(|(f, v)| (f.to_string(), v.to_string()))
+                .collect(),
+            text_search_fields: text_search_fields.iter().map(|f| f.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn indexes() -> StreamIndexes {
+        StreamIndexes {
+            fields: ["_timestamp", "trace_id", "service", "message", "code"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            fts_fields: vec!["log".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_recommend() {
+        let queries = (0..6)
+            .map(|i| {
+                query(
+                    &[
+                        ("trace_id", &format!("t{i}")),
+                        ("service", if i % 2 == 0 { "api" } else { "db" }),
+                        ("missing", "x"),
+                    ],
+                    &["message"],
+                )
+            })
+            .collect::<Vec<_>>();
+        let usage = HashMap::from([(
+            "trace_id".to_string(),
+            StreamFieldUsage {
+                field: "trace_id".to_string(),
+                queries: 40,
+                last_queried: 1,
+            },
+        )]);
+        let recs = recommend(&queries, &usage, &indexes(), 5);
+        let kinds = recs
+            .iter()
+            .map(|r| (r.kind, r.field.as_str(), r.slow_queries))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                (RecommendationKind::FullTextSearch, "message", 6),
+                (RecommendationKind::PartitionKey, "service", 6),
+                (RecommendationKind::BloomFilter, "trace_id", 6),
+            ]
+        );
+        assert_eq!(recs[2].queries, 40);
+    }
+
+    #[test]
+    fn test_recommend_skips_indexed_and_rare_fields() {
+        let queries = (0..6)
+            .map(|i| query(&[("trace_id", &format!("t{i}"))], &["code"]))
+            .collect::<Vec<_>>();
+        let indexes = StreamIndexes {
+            bloom_filter_fields: vec!["trace_id".to_string()],
+            ..indexes()
+        };
+        assert!(recommend(&queries, &HashMap::new(), &indexes, 7).is_empty());
+        let recs = recommend(&queries, &HashMap::new(), &indexes, 5);
+        assert_eq!(recs.len(), 1);
+        assert_eq!(recs[0].kind, RecommendationKind::FullTextSearch);
+    }
+}
@@ -28,11 +28,12 @@ use config::{
         function::{VRLResultResolver, VRLRuntimeConfig},
         self_reporting::usage::{RequestStats, TriggerData, TriggerDataStatus, TriggerDataType},
         stream::{
-            PartitionTimeLevel, PartitioningDetails, StreamParams, StreamPartition, StreamType,
+            KvExtractSettings, PartitionTimeLevel, PartitioningDetails, StreamParams,
+            StreamPartition, StreamType,
         },
     },
     metrics,
-    utils::{flatten, json::*, schema::format_partition_key},
+    utils::{flatten, ip, json::*, schema::format_partition_key},
 };
 use infra::{
     errors::{Error, Result},
@@ -53,17 +54,18 @@ use crate::{
     common::{
         infra::config::{REALTIME_ALERT_TRIGGERS, STREAM_ALERTS},
         meta::{ingestion::IngestionRequest, stream::SchemaRecords},
-        utils::functions::get_vrl_compiler_config,
+        utils::{functions::get_vrl_compiler_config, parsers},
     },
     service::{
-        alerts::alert::AlertExt,
+        alerts::{alert::AlertExt, silences},
         db::{self, alerts::alert::scheduler_key},
-        logs::bulk::TRANSFORM_FAILED,
+        logs::{self, bulk::TRANSFORM_FAILED},
     },
 };
 
 pub mod grpc;
 pub mod ingestion_service;
+pub mod semconv;
 
 pub type TriggerAlertData = Vec<(Alert, Vec<Map<String, Value>>)>;
 
@@ -261,8 +263,16 @@ pub async fn evaluate_trigger(triggers: TriggerAlertData) {
             query_took: None,
             scheduler_trace_id: None,
             time_in_queue_ms: None,
+            correlation: None,
         };
-        match alert.send_notification(val, now, None, now).await {
+        let result = match silences::mute(alert, val).await {
+            Some(silence_id) => Ok((
+                format!("notification muted by silence {silence_id}"),
+                String::new(),
+            )),
+            None => alert.send_notification(val, now, None, now).await,
+        };
+        match result {
             Err(e) => {
                 log::error!("Failed to send notification: {}", e);
                 trigger_data_stream.status = TriggerDataStatus::Failed;
@@ -569,6 +579,50 @@ pub fn generate_record_id(org_id: &str, stream_name: &str, stream_type: &StreamT
         .generate()
 }
 
+/// Adds a `<field>_ip128` field with the 128-bit representation of each configured ip field,
+/// the original value is kept as is and values which are not an ip address are skipped.
+pub fn normalize_ip_fields(record: &mut Map<String, Value>, ip_fields: &[String]) {
+    for field in ip_fields {
+        let Some(ip) = record
+            .get(field)
+            .and_then(|v| v.as_str())
+            .and_then(ip::parse_ip)
+        else {
+            continue;
+        };
+        record.insert(
+            format!("{field}{}", ip::IP128_FIELD_SUFFIX),
+            Value::String(ip::ip_to_hex128(ip)),
+        );
+    }
+}
+
+/// Maximum fields extracted from a single key-value message, so a noisy message can't
+/// blow up the schema.
+const MAX_KV_FIELDS: usize = 100;
+
+/// Adds the `key=value` pairs of the message field as fields of the record.
+pub fn extract_kv_fields(record: &mut Map<String, Value>, settings: &KvExtractSettings) {
+    let Some(Value::Object(pairs)) = record
+        .get(&settings.field)
+        .and_then(|v| v.as_str())
+        .and_then(parsers::parse_logfmt)
+    else {
+        return;
+    };
+    for (key, value) in pairs.into_iter().take(MAX_KV_FIELDS) {
+        let mut key = format!("{}{key}", settings.prefix);
+        flatten::format_key(&mut key);
+        if key == TIMESTAMP_COL_NAME || key == settings.field {
+            continue;
+        }
+        if !settings.overwrite && record.contains_key(&key) {
+            continue;
+        }
+        record.insert(key, value);
+    }
+}
+
 pub fn create_log_ingestion_req(
     ingestion_type: i32,
     data: &bytes::Bytes,
@@ -584,12 +638,100 @@ pub fn create_log_ingestion_req(
     }
 }
 
+/// Writes records generated by the system itself (e.g. detection findings) into a logs stream
+/// of the given org. Ingesters write directly, other nodes forward the records over gRPC.
+/// The records are not counted as ingestion usage.
+pub async fn ingest_internal_records(
+    org_id: &str,
+    stream_name: &str,
+    records: Vec<Value>,
+) -> Result<()> {
+    if records.is_empty() {
+        return Ok(());
+    }
+
+    if LOCAL_NODE.is_ingester() {
+        let bytes = bytes::Bytes::from(to_vec(&records)?);
+        let req = IngestionRequest::Usage(&bytes);
+        let resp = logs::ingest::ingest(0, org_id, stream_name, req, "", None).await?;
+        if resp.code != 200 {
+            return Err(Error::IngestionError(resp.error.unwrap_or_default()));
+        }
+    } else {
+        let req = proto::cluster_rpc::IngestionRequest {
+            org_id: org_id.to_string(),
+            stream_name: stream_name.to_string(),
+            stream_type: StreamType::Logs.to_string(),
+            data: Some(proto::cluster_rpc::IngestionData::from(records)),
+            ingestion_type: Some(IngestionType::Usage.into()),
+            metadata: None,
+        };
+        let resp = ingestion_service::ingest(req).await?;
+        if resp.status_code != 200 {
+            return Err(Error::IngestionError(resp.message));
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use infra::schema::{STREAM_SETTINGS, unwrap_stream_settings};
 
     use super::*;
 
+    #[test]
+    fn test_normalize_ip_fields() {
+        let mut record = Map::new();
+        record.insert(
+            "client_ip".to_string(),
+            Value::String("10.0.0.1".to_string()),
+        );
+        record.insert(
+            "server_ip".to_string(),
+            Value::String("unknown".to_string()),
+        );
+        normalize_ip_fields(
+            &mut record,
+            &["client_ip".to_string(), "server_ip".to_string()],
+        );
+        assert_eq!(
+            record.get("client_ip_ip128"),
+            Some(&Value::String(
+                "00000000000000000000ffff0a000001".to_string()
+            ))
+        );
+        assert!(!record.contains_key("server_ip_ip128"));
+    }
+
+    #[test]
+    fn test_extract_kv_fields() {
+        let mut record = Map::new();
+        record.insert(
+            "message".to_string(),
+            Value::String("level=error duration=25ms code=500 user=web".to_string()),
+        );
+        record.insert("user".to_string(), Value::String("api".to_string()));
+        extract_kv_fields(&mut record, &KvExtractSettings::default());
+        assert_eq!(
+            record.get("level"),
+            Some(&Value::String("error".to_string()))
+        );
+        assert_eq!(record.get("code"), Some(&Value::from(500)));
+        assert_eq!(record.get("user"), Some(&Value::String("api".to_string())));
+
+        let settings = KvExtractSettings {
+            prefix: "kv_".to_string(),
+            overwrite: true,
+            ..Default::default()
+        };
+        extract_kv_fields(&mut record, &settings);
+        assert_eq!(
+            record.get("kv_user"),
+            Some(&Value::String("web".to_string()))
+        );
+    }
+
     #[test]
     fn test_format_partition_key() {
         assert_eq!(format_partition_key("default/olympics"), "defaultolympics");
@@ -0,0 +1,189 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Renames the common variants of field names to canonical names at ingestion, e.g. `msg` to
+//! `message` or the ECS / OpenTelemetry `log.level` and `severity_text` to `level`.
+
+use config::{
+    TIMESTAMP_COL_NAME,
+    utils::{
+        flatten,
+        json::{Map, Value},
+    },
+};
+
+use crate::common::meta::organization::FieldNormalization;
+
+/// Built-in mappings of variant to canonical field name. The names are the flattened ones, so
+/// `log.level` is `log_level`.
+pub const DEFAULT_MAPPINGS: &[(&str, &str)] = &[
+    ("msg", "message"),
+    ("log_message", "message"),
+    ("lvl", "level"),
+    ("severity", "level"),
+    ("severity_text", "level"),
+    ("log_level", "level"),
+    ("loglevel", "level"),
+    ("levelname", "level"),
+    ("traceid", "trace_id"),
+    ("spanid", "span_id"),
+    ("service", "service_name"),
+    ("hostname", "host_name"),
+    ("host", "host_name"),
+    ("http_method", "http_request_method"),
+    ("http_status_code", "http_response_status_code"),
+    ("http_url", "url_full"),
+    ("http_user_agent", "user_agent_original"),
+    ("error_msg", "error_message"),
+];
+
+/// Field name mappings of an org.
+#[derive(Debug)]
+pub struct Normalizer {
+    mappings: Vec<(String, String)>,
+}
+
+impl Normalizer {
+    /// The normalizer of the settings, `None` when the normalization is off.
+    pub fn new(settings: &FieldNormalization) -> Option<Self> {
+        if !settings.enabled {
+            return None;
+        }
+        let mut mappings = Vec::with_capacity(DEFAULT_MAPPINGS.len() + settings.mappings.len());
+        // the org's mappings take precedence over the built-in ones
+        for (variant, canonical) in settings.mappings.iter() {
+            let (Some(variant), canonical) = (format_name(variant), format_name(canonical)) else {
+                continue;
+            };
+            if let Some(canonical) = canonical {
+                mappings.push((variant, canonical));
+            }
+        }
+        for (variant, canonical) in DEFAULT_MAPPINGS {
+            if !settings
+                .mappings
+                .keys()
+                .any(|v| format_name(v).as_deref() == Some(*variant))
+            {
+                mappings.push((variant.to_string(), canonical.to_string()));
+            }
+        }
+        Some(Self { mappings })
+    }
+
+    /// Renames the variant fields of the record. A variant is left alone when the record
+    /// already has the canonical field, so no value is lost.
+    pub fn normalize(&self, record: &mut Map<String, Value>) {
+        // fields are renamed once, a canonical name being the variant of another mapping
+        let mut renamed = Vec::new();
+        for (variant, canonical) in self.mappings.iter() {
+            if variant == canonical || record.contains_key(canonical) || renamed.contains(&variant)
+            {
+                continue;
+            }
+            if let Some(value) = record.remove(variant) {
+                record.insert(canonical.clone(), value);
+                renamed.push(canonical);
+            }
+        }
+    }
+}
+
+/// Checks the mappings of the settings, returning the first invalid name.
+pub fn validate(settings: &FieldNormalization) -> Result<(), String> {
+    for (variant, canonical) in settings.mappings.iter() {
+        if format_name(variant).is_none() {
+            return Err("field normalization variant name can not be empty".to_string());
+        }
+        if variant == TIMESTAMP_COL_NAME || canonical == TIMESTAMP_COL_NAME {
+            return Err(format!(
+                "field normalization can not rename {TIMESTAMP_COL_NAME}"
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn format_name(name: &str) -> Option<String> {
+    let mut name = name.trim().to_string();
+    if name.is_empty() {
+        return None;
+    }
+    flatten::format_key(&mut name);
+    Some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use config::utils::json;
+
+    use super::*;
+
+    fn record(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_normalize_defaults() {
+        let settings = FieldNormalization {
+            enabled: true,
+            ..Default::default()
+        };
+        let normalizer = Normalizer::new(&settings).unwrap();
+        let mut rec = record(json::json!({"msg": "hello", "lvl": "info", "hostname": "a"}));
+        normalizer.normalize(&mut rec);
+        assert_eq!(
+            rec,
+            record(json::json!({"message": "hello", "level": "info", "host_name": "a"}))
+        );
+
+        // an existing canonical field is kept, as well as the variant
+        let mut rec = record(json::json!({"message": "hello", "msg": "hi"}));
+        normalizer.normalize(&mut rec);
+        assert_eq!(rec, record(json::json!({"message": "hello", "msg": "hi"})));
+
+        assert!(Normalizer::new(&FieldNormalization::default()).is_none());
+    }
+
+    #[test]
+    fn test_normalize_custom_mappings() {
+        let settings = FieldNormalization {
+            enabled: true,
+            mappings: [
+                ("Log.Msg".to_string(), "message".to_string()),
+                ("host".to_string(), "".to_string()),
+                ("severity".to_string(), "severity_text".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        let normalizer = Normalizer::new(&settings).unwrap();
+        let mut rec = record(json::json!({"log_msg": "hello", "host": "a", "severity": "warn"}));
+        normalizer.normalize(&mut rec);
+        assert_eq!(
+            rec,
+            record(json::json!({"message": "hello", "host": "a", "severity_text": "warn"}))
+        );
+
+        assert!(validate(&settings).is_ok());
+        let settings = FieldNormalization {
+            enabled: true,
+            mappings: [("time".to_string(), TIMESTAMP_COL_NAME.to_string())]
+                .into_iter()
+                .collect(),
+        };
+        assert!(validate(&settings).is_err());
+    }
+}
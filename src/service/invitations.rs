@@ -0,0 +1,425 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Organization invitations and self signup.
+//!
+//! Admins invite emails into an organization with a role. Self signup, when enabled, creates the
+//! same kind of invitation for emails of the allowed domains, so following the emailed token both
+//! verifies the email and adds the user to `ZO_SIGNUP_ORG`.
+
+use std::str::FromStr;
+
+use config::{
+    SMTP_CLIENT, get_config, ider,
+    meta::{
+        invitation::{
+            AcceptInvitationRequest, CreateInvitationsRequest, CreateInvitationsResponse,
+            Invitation, InvitationInfo, is_domain_allowed,
+        },
+        user::UserRole,
+    },
+    utils::{rand::generate_random_string, time::now_micros},
+};
+use lettre::{AsyncTransport, Message};
+#[cfg(feature = "enterprise")]
+use o2_openfga::config::get_config as get_openfga_config;
+
+use crate::{
+    common::{
+        meta::user::{UserOrgRole, UserRequest},
+        utils::auth::{get_hash, get_role, is_root_user, is_valid_email},
+    },
+    service::{db, organization, users},
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum InvitationError {
+    #[error("Self signup is not enabled")]
+    SignupDisabled,
+    #[error("Signup is not allowed for the domain of this email")]
+    DomainNotAllowed,
+    #[error("Invalid email: {0}")]
+    InvalidEmail(String),
+    #[error("Invitations can't be created with the role {0}")]
+    InvalidRole(UserRole),
+    #[error("Not Allowed")]
+    NotAllowed,
+    #[error("Invitation not found")]
+    NotFound,
+    #[error("Invitation has expired")]
+    Expired,
+    #[error("User is already part of the organization")]
+    AlreadyMember,
+    #[error("Password required to create new user")]
+    PasswordRequired,
+    #[error("SMTP configuration not enabled")]
+    SmtpDisabled,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+/// Invites the emails into the organization and sends each of them the invitation token.
+/// Emails already part of the organization are skipped.
+pub async fn create(
+    org_id: &str,
+    initiator_id: &str,
+    req: CreateInvitationsRequest,
+) -> Result<CreateInvitationsResponse, InvitationError> {
+    if !is_admin(org_id, initiator_id).await {
+        return Err(InvitationError::NotAllowed);
+    }
+    if matches!(req.role, UserRole::Root | UserRole::ServiceAccount) {
+        return Err(InvitationError::InvalidRole(req.role));
+    }
+
+    let mut res = CreateInvitationsResponse::default();
+    for email in req.emails {
+        let email = email.trim().to_lowercase();
+        if !is_valid_email(&email) {
+            res.invalid_emails.push(email);
+            continue;
+        }
+        if db::org_users::get(org_id, &email).await.is_ok() {
+            res.existing_members.push(email);
+            continue;
+        }
+        let invitation = new_invitation(org_id, &email, req.role.clone(), initiator_id);
+        db::invitation::set(&invitation)
+            .await
+            .map_err(|e| anyhow::anyhow!("Error saving invitation: {e}"))?;
+        if get_config().smtp.smtp_enabled {
+            if let Err(e) = send_invitation(&invitation).await {
+                log::error!("[INVITATION] error sending invitation to {email}: {e}");
+            }
+        } else {
+            log::warn!("[INVITATION] SMTP is not enabled, invitation to {email} is not emailed");
+        }
+        res.invited.push(email);
+    }
+    Ok(res)
+}
+
+/// Lists the pending invitations of the organization, without their tokens.
+pub async fn list(
+    org_id: &str,
+    initiator_id: &str,
+) -> Result<Vec<InvitationInfo>, InvitationError> {
+    if !is_admin(org_id, initiator_id).await {
+        return Err(InvitationError::NotAllowed);
+    }
+    let now = now_micros();
+    Ok(db::invitation::list(org_id)
+        .await
+        .map_err(|e| anyhow::anyhow!("Error listing invitations: {e}"))?
+        .into_iter()
+        .filter(|i| !i.is_expired(now))
+        .map(InvitationInfo::from)
+        .collect())
+}
+
+/// Revokes the invitations of the email into the organization.
+pub async fn revoke(org_id: &str, initiator_id: &str, email: &str) -> Result<(), InvitationError> {
+    if !is_admin(org_id, initiator_id).await {
+        return Err(InvitationError::NotAllowed);
+    }
+    let email = email.trim().to_lowercase();
+    let invitations = db::invitation::list(org_id)
+        .await
+        .map_err(|e| anyhow::anyhow!("Error listing invitations: {e}"))?
+        .into_iter()
+        .filter(|i| i.email == email)
+        .collect::<Vec<_>>();
+    if invitations.is_empty() {
+        return Err(InvitationError::NotFound);
+    }
+    for invitation in invitations {
+        db::invitation::delete(org_id, &invitation.token)
+            .await
+            .map_err(|e| anyhow::anyhow!("Error deleting invitation: {e}"))?;
+    }
+    Ok(())
+}
+
+/// Emails a signup invitation for `ZO_SIGNUP_ORG`. Existing members get no email, but the
+/// response is the same so that the endpoint can't be used to find out who is a member.
+pub async fn signup(email: &str) -> Result<(), InvitationError> {
+    let cfg = get_config();
+    if !cfg.auth.signup_enabled {
+        return Err(InvitationError::SignupDisabled);
+    }
+    let email = email.trim().to_lowercase();
+    if !is_valid_email(&email) {
+        return Err(InvitationError::InvalidEmail(email));
+    }
+    if !is_domain_allowed(&email, &cfg.auth.signup_allowed_domains) {
+        return Err(InvitationError::DomainNotAllowed);
+    }
+    if !cfg.smtp.smtp_enabled {
+        return Err(InvitationError::SmtpDisabled);
+    }
+    let org_id = cfg.auth.signup_org.as_str();
+    if db::org_users::get(org_id, &email).await.is_ok() {
+        return Ok(());
+    }
+    let role = UserRole::from_str(&cfg.auth.signup_role).unwrap_or(UserRole::Viewer);
+    if matches!(role, UserRole::Root | UserRole::ServiceAccount) {
+        return Err(InvitationError::InvalidRole(role));
+    }
+    let invitation = new_invitation(org_id, &email, role, "");
+    db::invitation::set(&invitation)
+        .await
+        .map_err(|e| anyhow::anyhow!("Error saving invitation: {e}"))?;
+    send_invitation(&invitation).await?;
+    Ok(())
+}
+
+/// Accepts the invitation, creating the user when the email doesn't belong to one yet.
+pub async fn accept(
+    org_id: &str,
+    token: &str,
+    req: AcceptInvitationRequest,
+) -> Result<(), InvitationError> {
+    let Ok(invitation) = db::invitation::get(org_id, token).await else {
+        return Err(InvitationError::NotFound);
+    };
+    if invitation.is_expired(now_micros()) {
+        _ = db::invitation::delete(org_id, token).await;
+        return Err(InvitationError::Expired);
+    }
+    if invitation.invited_by.is_empty() {
+        // self signup org might not have been created yet
+        organization::check_and_create_org(org_id).await?;
+    }
+
+    let email = invitation.email.as_str();
+    if users::is_service_account(email).await == Some(true) {
+        return Err(InvitationError::NotAllowed);
+    }
+    let role = UserOrgRole {
+        base_role: invitation.role.clone(),
+        custom_role: None,
+    };
+    let base_role = get_role(&role);
+    let token = generate_random_string(16);
+    let rum_token = format!("rum{}", generate_random_string(16));
+    if db::user::get_user_record(email).await.is_ok() {
+        if db::org_users::get(org_id, email).await.is_ok() {
+            _ = db::invitation::delete(org_id, &invitation.token).await;
+            return Err(InvitationError::AlreadyMember);
+        }
+        db::org_users::add(org_id, email, base_role.clone(), &token, Some(rum_token)).await?;
+    } else {
+        if req.password.is_empty() {
+            return Err(InvitationError::PasswordRequired);
+        }
+        let cfg = get_config();
+        let usr_req = UserRequest {
+            email: email.to_string(),
+            first_name: req.first_name,
+            last_name: req.last_name,
+            password: req.password,
+            role: UserOrgRole {
+                base_role: base_role.clone(),
+                custom_role: None,
+            },
+            is_external: false,
+            token: None,
+        };
+        let salt = ider::uuid();
+        let password = get_hash(&usr_req.password, &salt);
+        let password_ext = get_hash(&usr_req.password, &cfg.auth.ext_auth_salt);
+        let user = usr_req.to_new_dbuser(
+            password,
+            salt,
+            org_id.to_string(),
+            token,
+            rum_token,
+            false,
+            password_ext,
+        );
+        db::user::add(&user).await?;
+    }
+
+    // Update OFGA
+    #[cfg(feature = "enterprise")]
+    if get_openfga_config().enabled {
+        use o2_openfga::authorizer::authz::{get_user_role_tuple, update_tuples};
+
+        let mut tuples = vec![];
+        get_user_role_tuple(&base_role.to_string(), email, org_id, &mut tuples);
+        if let Err(e) = update_tuples(tuples, vec![]).await {
+            log::error!("Error adding invited user to the org in openfga: {}", e);
+        }
+    }
+
+    db::invitation::delete(org_id, &invitation.token)
+        .await
+        .map_err(|e| anyhow::anyhow!("Error deleting invitation: {e}"))?;
+    Ok(())
+}
+
+async fn is_admin(org_id: &str, user_id: &str) -> bool {
+    if is_root_user(user_id) {
+        return true;
+    }
+    #[cfg(feature = "enterprise")]
+    if get_openfga_config().enabled {
+        // Permission already checked through RBAC
+        return true;
+    }
+    matches!(
+        db::user::get(Some(org_id), user_id).await,
+        Ok(Some(user)) if user.role.eq(&UserRole::Admin)
+    )
+}
+
+fn new_invitation(org_id: &str, email: &str, role: UserRole, invited_by: &str) -> Invitation {
+    let created_at = now_micros();
+    let expiry = get_config().common.org_invite_expiry as i64;
+    Invitation {
+        token: generate_random_string(32),
+        org_id: org_id.to_string(),
+        email: email.to_string(),
+        role,
+        invited_by: invited_by.to_string(),
+        created_at,
+        expires_at: created_at + chrono::Duration::days(expiry).num_microseconds().unwrap(),
+    }
+}
+
+async fn send_invitation(invitation: &Invitation) -> Result<(), InvitationError> {
+    let cfg = get_config();
+    if !cfg.smtp.smtp_enabled {
+        return Err(InvitationError::SmtpDisabled);
+    }
+    let url = format!(
+        "{}{}/auth/invitations/{}/{}",
+        cfg.common.web_url, cfg.common.base_uri, invitation.org_id, invitation.token
+    );
+    let intro = if invitation.invited_by.is_empty() {
+        "You requested to join".to_string()
+    } else {
+        format!("{} invited you to join", invitation.invited_by)
+    };
+    let msg = format!(
+        "{intro} the organization {} on {} as {}.\n\n\
+        Accept the invitation by sending a POST request to {url} with your first_name, \
+        last_name and password in the JSON body. The password is only needed if you don't have \
+        an account yet.\n\nThe invitation expires in {} days.",
+        invitation.org_id, cfg.common.instance_name, invitation.role, cfg.common.org_invite_expiry,
+    );
+    let mut email = Message::builder()
+        .from(
+            cfg.smtp
+                .smtp_from_email
+                .parse()
+                .map_err(|e| anyhow::anyhow!("{e}"))?,
+        )
+        .to(invitation
+            .email
+            .parse()
+            .map_err(|_| InvitationError::InvalidEmail(invitation.email.clone()))?)
+        .subject(format!("Invitation to {}", invitation.org_id));
+    if !cfg.smtp.smtp_reply_to.is_empty() {
+        email = email.reply_to(
+            cfg.smtp
+                .smtp_reply_to
+                .parse()
+                .map_err(|e| anyhow::anyhow!("{e}"))?,
+        );
+    }
+    let email = email.body(msg).map_err(|e| anyhow::anyhow!("{e}"))?;
+    SMTP_CLIENT
+        .as_ref()
+        .unwrap()
+        .send(email)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use infra::{db as infra_db, table as infra_table};
+
+    use super::*;
+
+    async fn add_user(org_id: &str, email: &str, role: UserRole) {
+        let usr_req = UserRequest {
+            email: email.to_string(),
+            password: "Complexpass#123".to_string(),
+            role: UserOrgRole {
+                base_role: role,
+                custom_role: None,
+            },
+            first_name: "first".to_owned(),
+            last_name: "".to_owned(),
+            is_external: false,
+            token: None,
+        };
+        let user = usr_req.to_new_dbuser(
+            "hash".to_string(),
+            "salt".to_string(),
+            org_id.to_string(),
+            generate_random_string(16),
+            format!("rum{}", generate_random_string(16)),
+            false,
+            "hash_ext".to_string(),
+        );
+        db::user::add(&user).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_list_and_revoke_require_admin() {
+        let org_id = "invitations_org";
+        let admin = "inv_admin@example.com";
+        let viewer = "inv_viewer@example.com";
+
+        infra_db::create_table().await.unwrap();
+        infra_table::create_user_tables().await.unwrap();
+        add_user(org_id, admin, UserRole::Admin).await;
+        add_user(org_id, viewer, UserRole::Viewer).await;
+        let invitation = new_invitation(org_id, "new@example.com", UserRole::Admin, admin);
+        db::invitation::set(&invitation).await.unwrap();
+
+        // other members can't read the invitations, nor revoke them
+        assert!(matches!(
+            list(org_id, viewer).await,
+            Err(InvitationError::NotAllowed)
+        ));
+        assert!(matches!(
+            revoke(org_id, viewer, "new@example.com").await,
+            Err(InvitationError::NotAllowed)
+        ));
+        assert!(db::invitation::get(org_id, &invitation.token).await.is_ok());
+
+        let list = list(org_id, admin).await.unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].email, "new@example.com");
+        let listed = config::utils::json::to_string(&list).unwrap();
+        assert!(!listed.contains(&invitation.token));
+
+        revoke(org_id, admin, "new@example.com").await.unwrap();
+        assert!(
+            db::invitation::get(org_id, &invitation.token)
+                .await
+                .is_err()
+        );
+        assert!(matches!(
+            revoke(org_id, admin, "new@example.com").await,
+            Err(InvitationError::NotFound)
+        ));
+    }
+}
@@ -0,0 +1,380 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use config::{
+    TIMESTAMP_COL_NAME,
+    cluster::LOCAL_NODE,
+    get_config,
+    meta::{
+        log_metrics::{LogMetricRule, LogMetricType, MAX_SERIES_PER_RULE},
+        promql::{NAME_LABEL, TYPE_LABEL, VALUE_LABEL},
+        stream::{StreamParams, StreamType},
+    },
+    utils::{
+        json::{self, Map, Value},
+        time::now_micros,
+    },
+};
+use hashbrown::HashMap;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+use crate::{
+    common::infra::config::LOG_METRIC_RULES,
+    service::{compact, db, format_stream_name},
+};
+
+/// Label added to every series, each ingester keeps its own series.
+const INSTANCE_LABEL: &str = "instance";
+
+/// Series of the metrics extracted on this node since it started, written periodically.
+static AGGREGATOR: Lazy<Mutex<Aggregator>> = Lazy::new(Default::default);
+
+#[derive(Debug, thiserror::Error)]
+pub enum LogMetricError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Log metric rule not found")]
+    NotFound,
+
+    #[error("Invalid log metric rule: {0}")]
+    InvalidRule(String),
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<LogMetricRule>, LogMetricError> {
+    Ok(db::log_metrics::list(org_id).await?)
+}
+
+pub async fn get(org_id: &str, name: &str) -> Result<LogMetricRule, LogMetricError> {
+    db::log_metrics::get(org_id, name)
+        .await
+        .map_err(|_| LogMetricError::NotFound)
+}
+
+pub async fn save(
+    org_id: &str,
+    name: &str,
+    mut rule: LogMetricRule,
+) -> Result<LogMetricRule, LogMetricError> {
+    rule.name = name.to_string();
+    rule.validate().map_err(LogMetricError::InvalidRule)?;
+    rule.updated_at = now_micros();
+    db::log_metrics::set(org_id, &rule).await?;
+    Ok(rule)
+}
+
+pub async fn delete(org_id: &str, name: &str) -> Result<(), LogMetricError> {
+    let rule = get(org_id, name).await?;
+    // keep the retention the source stream asks for on the extracted metrics
+    let metrics = rule
+        .metric_names()
+        .iter()
+        .map(|name| StreamParams::new(org_id, &format_stream_name(name), StreamType::Metrics))
+        .collect::<Vec<_>>();
+    compact::retention::pin_source_retention(
+        org_id,
+        &StreamParams::new(org_id, &rule.stream, StreamType::Logs),
+        &metrics,
+    )
+    .await;
+    Ok(db::log_metrics::delete(org_id, name).await?)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct SeriesKey {
+    org_id: String,
+    rule: String,
+    labels: Vec<(String, String)>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum SeriesValue {
+    Counter(f64),
+    Histogram {
+        bounds: Vec<f64>,
+        /// Cumulative count of each bucket
+        buckets: Vec<u64>,
+        sum: f64,
+        count: u64,
+    },
+}
+
+impl SeriesValue {
+    fn new(rule: &LogMetricRule) -> Self {
+        match rule.metric_type {
+            LogMetricType::Counter => SeriesValue::Counter(0.0),
+            LogMetricType::Histogram => SeriesValue::Histogram {
+                bounds: rule.buckets.clone(),
+                buckets: vec![0; rule.buckets.len()],
+                sum: 0.0,
+                count: 0,
+            },
+        }
+    }
+
+    fn fits(&self, rule: &LogMetricRule) -> bool {
+        match self {
+            SeriesValue::Counter(_) => rule.metric_type == LogMetricType::Counter,
+            SeriesValue::Histogram { bounds, .. } => {
+                rule.metric_type == LogMetricType::Histogram && *bounds == rule.buckets
+            }
+        }
+    }
+
+    fn add(&mut self, v: f64) {
+        match self {
+            SeriesValue::Counter(total) => *total += v,
+            SeriesValue::Histogram {
+                bounds,
+                buckets,
+                sum,
+                count,
+            } => {
+                for (bound, bucket) in bounds.iter().zip(buckets.iter_mut()) {
+                    if v <= *bound {
+                        *bucket += 1;
+                    }
+                }
+                *sum += v;
+                *count += 1;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Aggregator {
+    series: HashMap<SeriesKey, SeriesValue>,
+    series_per_rule: HashMap<(String, String), usize>,
+}
+
+impl Aggregator {
+    fn observe(&mut self, org_id: &str, rule: &LogMetricRule, record: &Map<String, Value>) {
+        let Some(v) = rule.value(record) else {
+            return;
+        };
+        let key = SeriesKey {
+            org_id: org_id.to_string(),
+            rule: rule.name.clone(),
+            labels: rule.label_values(record),
+        };
+        if !self.series.contains_key(&key) {
+            let num = self
+                .series_per_rule
+                .entry((key.org_id.clone(), key.rule.clone()))
+                .or_default();
+            if *num >= MAX_SERIES_PER_RULE {
+                return;
+            }
+            *num += 1;
+        }
+        let series = self
+            .series
+            .entry(key)
+            .or_insert_with(|| SeriesValue::new(rule));
+        // the rule changed its type or buckets, start over
+        if !series.fits(rule) {
+            *series = SeriesValue::new(rule);
+        }
+        series.add(v);
+    }
+
+    /// Drops the series whose rule doesn't exist anymore or is disabled.
+    fn retain(&mut self, keep: impl Fn(&str, &str) -> bool) {
+        self.series.retain(|k, _| keep(&k.org_id, &k.rule));
+        self.series_per_rule
+            .retain(|(org_id, rule), _| keep(org_id, rule));
+    }
+
+    /// Returns the metrics records of all series, by org.
+    fn records(&self, timestamp: i64, instance: &str) -> HashMap<String, Vec<Value>> {
+        let mut records: HashMap<String, Vec<Value>> = HashMap::new();
+        for (key, value) in self.series.iter() {
+            let record = |name: String, metric_type: LogMetricType, v: f64, le: Option<String>| {
+                let mut record = Map::new();
+                record.insert(NAME_LABEL.to_string(), Value::String(name));
+                record.insert(
+                    TYPE_LABEL.to_string(),
+                    Value::String(metric_type.to_string()),
+                );
+                record.insert(TIMESTAMP_COL_NAME.to_string(), Value::from(timestamp));
+                record.insert(VALUE_LABEL.to_string(), Value::from(v));
+                record.insert(
+                    INSTANCE_LABEL.to_string(),
+                    Value::String(instance.to_string()),
+                );
+                for (label, label_value) in key.labels.iter() {
+                    record.insert(label.clone(), Value::String(label_value.clone()));
+                }
+                if let Some(le) = le {
+                    record.insert("le".to_string(), Value::String(le));
+                }
+                Value::Object(record)
+            };
+            let org_records = records.entry(key.org_id.clone()).or_default();
+            match value {
+                SeriesValue::Counter(total) => {
+                    org_records.push(record(
+                        key.rule.clone(),
+                        LogMetricType::Counter,
+                        *total,
+                        None,
+                    ));
+                }
+                SeriesValue::Histogram {
+                    bounds,
+                    buckets,
+                    sum,
+                    count,
+                } => {
+                    let name = format!("{}_bucket", key.rule);
+                    for (bound, bucket) in bounds.iter().zip(buckets.iter()) {
+                        org_records.push(record(
+                            name.clone(),
+                            LogMetricType::Histogram,
+                            *bucket as f64,
+                            Some(bound.to_string()),
+                        ));
+                    }
+                    org_records.push(record(
+                        name,
+                        LogMetricType::Histogram,
+                        *count as f64,
+                        Some("+Inf".to_string()),
+                    ));
+                    org_records.push(record(
+                        format!("{}_sum", key.rule),
+                        LogMetricType::Histogram,
+                        *sum,
+                        None,
+                    ));
+                    org_records.push(record(
+                        format!("{}_count", key.rule),
+                        LogMetricType::Histogram,
+                        *count as f64,
+                        None,
+                    ));
+                }
+            }
+        }
+        records
+    }
+}
+
+/// Updates the metrics of the rules of the stream with the matching records.
+pub fn observe(org_id: &str, stream_name: &str, records: &[(i64, Map<String, Value>)]) {
+    let prefix = format!("{org_id}/");
+    let rules = LOG_METRIC_RULES
+        .iter()
+        .filter(|r| r.key().starts_with(&prefix) && r.enabled && r.stream == stream_name)
+        .map(|r| r.value().clone())
+        .collect::<Vec<_>>();
+    if rules.is_empty() {
+        return;
+    }
+    let mut aggregator = AGGREGATOR.lock();
+    for (_, record) in records.iter() {
+        for rule in rules.iter().filter(|rule| rule.matches(record)) {
+            aggregator.observe(org_id, rule, record);
+        }
+    }
+}
+
+async fn flush() {
+    let records = {
+        let mut aggregator = AGGREGATOR.lock();
+        aggregator.retain(|org_id, rule| {
+            LOG_METRIC_RULES
+                .get(&format!("{org_id}/{rule}"))
+                .is_some_and(|r| r.enabled)
+        });
+        aggregator.records(now_micros(), &LOCAL_NODE.name)
+    };
+    for (org_id, records) in records {
+        let body = match json::to_vec(&records) {
+            Ok(body) => body,
+            Err(e) => {
+                log::error!("[LOG METRICS] serialize metrics of org {org_id} error: {e}");
+                continue;
+            }
+        };
+        match crate::service::metrics::json::ingest(&org_id, body.into()).await {
+            Ok(resp) if resp.code != 200 => log::error!(
+                "[LOG METRICS] write metrics of org {org_id} error: {}",
+                resp.error.unwrap_or_default()
+            ),
+            Ok(_) => {}
+            Err(e) => log::error!("[LOG METRICS] write metrics of org {org_id} error: {e}"),
+        }
+    }
+}
+
+/// Writes the current value of the metrics extracted from logs, runs on ingesters.
+pub async fn run() -> Result<(), anyhow::Error> {
+    let flush_interval = get_config().limit.log_metrics_flush_interval;
+    if flush_interval <= 0 {
+        return Ok(());
+    }
+    let mut interval =
+        tokio::time::interval(tokio::time::Duration::from_secs(flush_interval as u64));
+    interval.tick().await; // trigger the first run
+    loop {
+        interval.tick().await;
+        flush().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregator() {
+        let counter: LogMetricRule = json::from_str(
+            r#"{"name":"http_5xx_total","stream":"nginx","conditions":[{"field":"status","operator":">=","value":"500"}],"labels":["service"]}"#,
+        )
+        .unwrap();
+        let mut histogram = counter.clone();
+        histogram.name = "http_took".to_string();
+        histogram.metric_type = LogMetricType::Histogram;
+        histogram.value_field = Some("took".to_string());
+        histogram.buckets = vec![0.1, 1.0];
+
+        let mut aggregator = Aggregator::default();
+        for took in [0.05, 0.5, 2.0] {
+            let record = json::json!({"status": 500, "service": "api", "took": took});
+            aggregator.observe("org", &counter, record.as_object().unwrap());
+            aggregator.observe("org", &histogram, record.as_object().unwrap());
+        }
+        let records = aggregator.records(1, "node-1");
+        let records = records.get("org").unwrap();
+        let find = |name: &str, le: Option<&str>| {
+            records
+                .iter()
+                .find(|r| r[NAME_LABEL] == name && r.get("le").and_then(|v| v.as_str()) == le)
+                .map(|r| r[VALUE_LABEL].as_f64().unwrap())
+        };
+        assert_eq!(find("http_5xx_total", None), Some(3.0));
+        assert_eq!(find("http_took_bucket", Some("0.1")), Some(1.0));
+        assert_eq!(find("http_took_bucket", Some("1")), Some(2.0));
+        assert_eq!(find("http_took_bucket", Some("+Inf")), Some(3.0));
+        assert_eq!(find("http_took_sum", None), Some(2.55));
+        assert_eq!(records[0][INSTANCE_LABEL], "node-1");
+        assert_eq!(records[0]["service"], "api");
+
+        aggregator.retain(|_, rule| rule != "http_took");
+        assert_eq!(aggregator.records(1, "node-1")["org"].len(), 1);
+    }
+}
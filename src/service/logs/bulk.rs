@@ -25,13 +25,12 @@ use config::{
     get_config,
     meta::{
         self_reporting::usage::UsageType,
-        stream::{StreamParams, StreamType},
+        stream::{StreamParams, StreamType, TimestampSettings},
     },
     metrics,
     utils::{
         flatten,
         json::{self, estimate_json_bytes},
-        time::parse_timestamp_micro_from_value,
     },
 };
 use infra::errors::Result;
@@ -42,6 +41,7 @@ use crate::{
     service::{
         format_stream_name,
         ingestion::check_ingestion_allowed,
+        logs::ingest::get_record_timestamp,
         pipeline::batch_execution::{ExecutablePipeline, ExecutablePipelineBulkInputs},
         schema::{get_future_discard_error, get_upto_discard_error},
     },
@@ -87,6 +87,7 @@ pub async fn ingest(
     let mut stream_executable_pipelines: HashMap<String, Option<ExecutablePipeline>> =
         HashMap::new();
     let mut stream_pipeline_inputs: HashMap<String, ExecutablePipelineBulkInputs> = HashMap::new();
+    let mut stream_ts_settings: HashMap<String, Option<TimestampSettings>> = HashMap::new();
 
     let mut user_defined_schema_map: HashMap<String, Option<HashSet<String>>> = HashMap::new();
     let mut streams_need_original_map: HashMap<String, bool> = HashMap::new();
@@ -153,6 +154,14 @@ pub async fn ingest(
                 continue; // skip
             }
 
+            if !stream_ts_settings.contains_key(&stream_name) {
+                let ts_settings =
+                    infra::schema::get_settings(org_id, &stream_name, StreamType::Logs)
+                        .await
+                        .and_then(|s| s.timestamp);
+                stream_ts_settings.insert(stream_name.clone(), ts_settings);
+            }
+
             let mut streams = vec![StreamParams {
                 org_id: org_id.to_owned().into(),
                 stream_type: StreamType::Logs,
@@ -293,8 +302,11 @@ pub async fn ingest(
                 }
 
                 // handle timestamp
-                let timestamp = match local_val.get(TIMESTAMP_COL_NAME) {
-                    Some(v) => match parse_timestamp_micro_from_value(v) {
+                let ts_settings = stream_ts_settings
+                    .get(&stream_name)
+                    .and_then(|s| s.as_ref());
+                let timestamp =
+                    match get_record_timestamp(&local_val, ts_settings, org_id, &stream_name) {
                         Ok(t) => t,
                         Err(_e) => {
                             bulk_res.errors = true;
@@ -318,9 +330,7 @@ pub async fn ingest(
                             );
                             continue;
                         }
-                    },
-                    None => Utc::now().timestamp_micros(),
-                };
+                    };
 
                 // check ingestion time
                 if timestamp < min_ts || timestamp > max_ts {
@@ -426,6 +436,17 @@ pub async fn ingest(
                             .await;
                         }
 
+                        if !stream_ts_settings.contains_key(&destination_stream) {
+                            let ts_settings = infra::schema::get_settings(
+                                org_id,
+                                &destination_stream,
+                                StreamType::Logs,
+                            )
+                            .await
+                            .and_then(|s| s.timestamp);
+                            stream_ts_settings.insert(destination_stream.clone(), ts_settings);
+                        }
+
                         for (idx, mut res) in stream_pl_results {
                             // we calculate the size BEFORE applying uds
                             let original_size = estimate_json_bytes(&res);
@@ -497,37 +518,38 @@ pub async fn ingest(
                             }
 
                             // handle timestamp
-                            let timestamp = match local_val.get(TIMESTAMP_COL_NAME) {
-                                Some(v) => match parse_timestamp_micro_from_value(v) {
-                                    Ok(t) => t,
-                                    Err(_e) => {
-                                        bulk_res.errors = true;
-                                        metrics::INGEST_ERRORS
-                                            .with_label_values(&[
-                                                org_id,
-                                                StreamType::Logs.as_str(),
-                                                &stream_name,
-                                                TS_PARSE_FAILED,
-                                            ])
-                                            .inc();
-                                        log_failed_record(
-                                            log_ingestion_errors,
-                                            &res,
+                            let ts_settings = stream_ts_settings
+                                .get(&destination_stream)
+                                .and_then(|s| s.as_ref());
+                            let timestamp = match get_record_timestamp(
+                                &local_val,
+                                ts_settings,
+                                org_id,
+                                &destination_stream,
+                            ) {
+                                Ok(t) => t,
+                                Err(_e) => {
+                                    bulk_res.errors = true;
+                                    metrics::INGEST_ERRORS
+                                        .with_label_values(&[
+                                            org_id,
+                                            StreamType::Logs.as_str(),
+                                            &stream_name,
                                             TS_PARSE_FAILED,
-                                        );
-                                        add_record_status(
-                                            stream_name.clone(),
-                                            &doc_id,
-                                            action.clone(),
-                                            Some(res),
-                                            &mut bulk_res,
-                                            Some(TS_PARSE_FAILED.to_string()),
-                                            Some(TS_PARSE_FAILED.to_string()),
-                                        );
-                                        continue;
-                                    }
-                                },
-                                None => Utc::now().timestamp_micros(),
+                                        ])
+                                        .inc();
+                                    log_failed_record(log_ingestion_errors, &res, TS_PARSE_FAILED);
+                                    add_record_status(
+                                        stream_name.clone(),
+                                        &doc_id,
+                                        action.clone(),
+                                        Some(res),
+                                        &mut bulk_res,
+                                        Some(TS_PARSE_FAILED.to_string()),
+                                        Some(TS_PARSE_FAILED.to_string()),
+                                    );
+                                    continue;
+                                }
                             };
 
                             // check ingestion time
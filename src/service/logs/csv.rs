@@ -0,0 +1,181 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//! Converts csv/tsv payloads into json records for the log ingestion, with
+//! header or positional column names and typed values.
+
+use config::{
+    TIMESTAMP_COL_NAME,
+    meta::stream::TimestampFormat,
+    utils::{json, time::parse_timestamp_with_formats},
+};
+
+use crate::common::{meta::ingestion::CsvIngestionQuery, utils::parsers};
+
+pub const CONTENT_TYPE_TSV: &str = "text/tab-separated-values";
+
+#[derive(Debug)]
+pub struct CsvOptions {
+    pub delimiter: u8,
+    pub has_header: bool,
+    pub columns: Vec<String>,
+    pub timestamp_column: Option<String>,
+    pub timestamp_formats: Vec<TimestampFormat>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            has_header: true,
+            columns: vec![],
+            timestamp_column: None,
+            timestamp_formats: vec![],
+        }
+    }
+}
+
+impl CsvOptions {
+    pub fn new(query: &CsvIngestionQuery, content_type: Option<&str>) -> Result<Self, String> {
+        let delimiter = match query.delimiter.as_deref() {
+            None if content_type.is_some_and(|c| c.starts_with(CONTENT_TYPE_TSV)) => b'\t',
+            None => b',',
+            Some("tab" | "\\t" | "\t") => b'\t',
+            Some(d) if d.len() == 1 => d.as_bytes()[0],
+            Some(d) => return Err(format!("Invalid delimiter: {d}")),
+        };
+        let columns = query
+            .columns
+            .as_deref()
+            .map(|c| c.split(',').map(|c| c.trim().to_string()).collect())
+            .unwrap_or_default();
+        let timestamp_formats = match query.timestamp_format.as_ref() {
+            Some(format) => vec![TimestampFormat::try_from(format.clone())?],
+            None => vec![],
+        };
+        Ok(Self {
+            delimiter,
+            has_header: query.has_header.unwrap_or(true),
+            columns,
+            timestamp_column: query
+                .timestamp_column
+                .as_ref()
+                .filter(|c| !c.is_empty())
+                .cloned(),
+            timestamp_formats,
+        })
+    }
+}
+
+/// Parses the csv payload into one json object per row, empty cells are left
+/// out of the record.
+pub fn parse(data: &[u8], options: &CsvOptions) -> Result<Vec<json::Value>, ::csv::Error> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .trim(::csv::Trim::All)
+        .from_reader(data);
+    let mut rows = reader.records();
+
+    let header = match options.has_header {
+        true => match rows.next() {
+            Some(row) => row?.iter().map(|v| v.to_string()).collect(),
+            None => return Ok(vec![]),
+        },
+        false => vec![],
+    };
+    let column_name = |i: usize| -> String {
+        options
+            .columns
+            .get(i)
+            .filter(|c| !c.is_empty())
+            .or_else(|| header.get(i).filter(|c| !c.is_empty()))
+            .cloned()
+            .unwrap_or_else(|| format!("column_{}", i + 1))
+    };
+
+    let mut records = Vec::new();
+    for row in rows {
+        let row = row?;
+        let mut record = json::Map::with_capacity(row.len());
+        for (i, value) in row.iter().enumerate() {
+            if value.is_empty() {
+                continue;
+            }
+            let name = column_name(i);
+            let timestamp = (options.timestamp_column.as_ref() == Some(&name))
+                .then(|| parse_timestamp(value, &options.timestamp_formats))
+                .flatten();
+            match timestamp {
+                Some(ts) => record.insert(TIMESTAMP_COL_NAME.to_string(), json::Value::from(ts)),
+                None => record.insert(name, parsers::typed_value(value)),
+            };
+        }
+        if !record.is_empty() {
+            records.push(json::Value::Object(record));
+        }
+    }
+    Ok(records)
+}
+
+/// Parses the timestamp column, values that can't be parsed are kept as a
+/// regular column, so the stream timestamp settings decide on them.
+fn parse_timestamp(value: &str, formats: &[TimestampFormat]) -> Option<i64> {
+    parse_timestamp_with_formats(&json::Value::String(value.to_string()), formats)
+        .or_else(|_| parse_timestamp_with_formats(&parsers::typed_value(value), formats))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_with_header() {
+        let data = b"time,level,code,zip,msg\n\
+            2024-01-02T03:04:05Z,error,500,01234,\"failed, retrying\"\n\
+            2024-01-02T03:04:06Z,info,,,ok\n";
+        let options = CsvOptions {
+            timestamp_column: Some("time".to_string()),
+            ..Default::default()
+        };
+        let records = parse(data, &options).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0][TIMESTAMP_COL_NAME], 1704164645000000_i64);
+        assert_eq!(records[0]["code"], 500);
+        assert_eq!(records[0]["zip"], "01234");
+        assert_eq!(records[0]["msg"], "failed, retrying");
+        assert!(records[0].get("time").is_none());
+        assert!(records[1].get("code").is_none());
+    }
+
+    #[test]
+    fn test_parse_positional() {
+        let query = CsvIngestionQuery {
+            has_header: Some(false),
+            columns: Some("ts,host".to_string()),
+            timestamp_column: Some("ts".to_string()),
+            timestamp_format: Some("epoch_s".to_string()),
+            ..Default::default()
+        };
+        let options = CsvOptions::new(&query, Some(CONTENT_TYPE_TSV)).unwrap();
+        assert_eq!(options.delimiter, b'\t');
+        let records = parse(b"1700000000\tweb-1\t0.5\nbad\tweb-2\n", &options).unwrap();
+        assert_eq!(records[0][TIMESTAMP_COL_NAME], 1700000000000000_i64);
+        assert_eq!(records[0]["host"], "web-1");
+        assert_eq!(records[0]["column_3"], 0.5);
+        assert_eq!(records[1]["ts"], "bad");
+    }
+}
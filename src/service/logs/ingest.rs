@@ -24,13 +24,13 @@ use config::{
     ALL_VALUES_COL_NAME, ID_COL_NAME, ORIGINAL_DATA_COL_NAME, TIMESTAMP_COL_NAME,
     meta::{
         self_reporting::usage::UsageType,
-        stream::{StreamParams, StreamType},
+        stream::{StreamParams, StreamType, TimestampFallback, TimestampSettings},
     },
     metrics,
     utils::{
         flatten,
         json::{self, estimate_json_bytes},
-        time::parse_timestamp_micro_from_value,
+        time::parse_timestamp_with_formats,
     },
 };
 use flate2::read::GzDecoder;
@@ -87,6 +87,8 @@ pub async fn ingest(
         .timestamp_micros();
     let max_ts = (Utc::now() + Duration::try_hours(cfg.limit.ingest_allowed_in_future).unwrap())
         .timestamp_micros();
+    let stream_settings = infra::schema::get_settings(org_id, &stream_name, StreamType::Logs).await;
+    let ts_settings = stream_settings.as_ref().and_then(|s| s.timestamp.clone());
 
     let mut stream_params = vec![StreamParams::new(org_id, &stream_name, StreamType::Logs)];
 
@@ -168,9 +170,24 @@ pub async fn ingest(
                 IngestionData::JSON(&json_req),
             )
         }
-        IngestionRequest::Hec(logs) => (
-            "/api/org/ingest/logs/_hec",
-            UsageType::Hec,
+        IngestionRequest::Hec(logs) => {
+            // raw hec events are reassembled into multi-line records
+            let logs = match stream_settings.as_ref().and_then(|s| s.multiline.as_ref()) {
+                Some(multiline) => {
+                    json_req = super::multiline::merge(logs.clone(), multiline, "log");
+                    &json_req
+                }
+                None => logs,
+            };
+            (
+                "/api/org/ingest/logs/_hec",
+                UsageType::Hec,
+                IngestionData::JSON(logs),
+            )
+        }
+        IngestionRequest::Csv(logs) => (
+            "/api/org/ingest/logs/_csv",
+            UsageType::Csv,
             IngestionData::JSON(logs),
         ),
     };
@@ -225,7 +242,14 @@ pub async fn ingest(
             let mut res = flatten::flatten_with_level(item, cfg.limit.ingest_flatten_level)?;
 
             // handle timestamp
-            let timestamp = match handle_timestamp(&mut res, min_ts, max_ts) {
+            let timestamp = match handle_timestamp(
+                &mut res,
+                min_ts,
+                max_ts,
+                ts_settings.as_ref(),
+                org_id,
+                &stream_name,
+            ) {
                 Ok(ts) => ts,
                 Err(e) => {
                     stream_status.status.failed += 1;
@@ -352,9 +376,24 @@ pub async fn ingest(
                         .await;
                     }
 
+                    let dest_ts_settings = if destination_stream == stream_name {
+                        ts_settings.clone()
+                    } else {
+                        infra::schema::get_settings(org_id, &destination_stream, StreamType::Logs)
+                            .await
+                            .and_then(|s| s.timestamp)
+                    };
+
                     for (idx, mut res) in stream_pl_results {
                         // handle timestamp
-                        let timestamp = match handle_timestamp(&mut res, min_ts, max_ts) {
+                        let timestamp = match handle_timestamp(
+                            &mut res,
+                            min_ts,
+                            max_ts,
+                            dest_ts_settings.as_ref(),
+                            org_id,
+                            &destination_stream,
+                        ) {
                             Ok(ts) => ts,
                             Err(e) => {
                                 stream_status.status.failed += 1;
@@ -527,17 +566,14 @@ pub fn handle_timestamp(
     value: &mut json::Value,
     min_ts: i64,
     max_ts: i64,
+    ts_settings: Option<&TimestampSettings>,
+    org_id: &str,
+    stream_name: &str,
 ) -> Result<i64, anyhow::Error> {
     let local_val = value
         .as_object_mut()
         .ok_or_else(|| anyhow::Error::msg("Value is not an object"))?;
-    let timestamp = match local_val.get(TIMESTAMP_COL_NAME) {
-        Some(v) => match parse_timestamp_micro_from_value(v) {
-            Ok(t) => t,
-            Err(_) => return Err(anyhow::Error::msg("Can't parse timestamp")),
-        },
-        None => Utc::now().timestamp_micros(),
-    };
+    let timestamp = get_record_timestamp(local_val, ts_settings, org_id, stream_name)?;
     // check ingestion time
     if timestamp < min_ts {
         return Err(get_upto_discard_error());
@@ -552,6 +588,35 @@ pub fn handle_timestamp(
     Ok(timestamp)
 }
 
+/// Picks the event timestamp of a log record following the stream timestamp
+/// settings, records without the field get the ingestion time.
+pub fn get_record_timestamp(
+    record: &json::Map<String, json::Value>,
+    ts_settings: Option<&TimestampSettings>,
+    org_id: &str,
+    stream_name: &str,
+) -> Result<i64, anyhow::Error> {
+    let field = ts_settings.map_or(TIMESTAMP_COL_NAME, |s| s.field());
+    let Some(v) = record.get(field) else {
+        return Ok(Utc::now().timestamp_micros());
+    };
+    let formats = ts_settings
+        .map(|s| s.formats.as_slice())
+        .unwrap_or_default();
+    match parse_timestamp_with_formats(v, formats) {
+        Ok(t) => Ok(t),
+        Err(_) => {
+            metrics::INGEST_TIMESTAMP_UNPARSABLE
+                .with_label_values(&[org_id, StreamType::Logs.as_str(), stream_name])
+                .inc();
+            match ts_settings.map(|s| s.fallback).unwrap_or_default() {
+                TimestampFallback::IngestTime => Ok(Utc::now().timestamp_micros()),
+                TimestampFallback::Reject => Err(anyhow::Error::msg("Can't parse timestamp")),
+            }
+        }
+    }
+}
+
 impl Iterator for IngestionDataIter<'_> {
     type Item = Result<json::Value, IngestionError>;
 
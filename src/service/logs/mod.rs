@@ -42,7 +42,7 @@ use infra::{
 };
 
 use super::{
-    db::organization::get_org_setting,
+    db::organization::{get_cached_org_setting, get_org_setting},
     ingestion::{TriggerAlertData, evaluate_trigger, write_file},
     metadata::{
         MetadataItem, MetadataType,
@@ -54,15 +54,20 @@ use super::{
 use crate::{
     common::meta::{ingestion::IngestionStatus, stream::SchemaRecords},
     service::{
-        alerts::alert::AlertExt, db, ingestion::get_write_partition_key, schema::check_for_schema,
+        alerts::alert::AlertExt,
+        db,
+        ingestion::{get_write_partition_key, semconv::Normalizer},
+        schema::check_for_schema,
         self_reporting::report_request_usage_stats,
     },
 };
 
 pub mod bulk;
+pub mod csv;
 pub mod hec;
 pub mod ingest;
 pub mod loki;
+pub mod multiline;
 pub mod otlp;
 pub mod syslog;
 
@@ -276,7 +281,7 @@ async fn write_logs(
     org_id: &str,
     stream_name: &str,
     status: &mut IngestionStatus,
-    json_data: Vec<(i64, Map<String, Value>)>,
+    mut json_data: Vec<(i64, Map<String, Value>)>,
 ) -> Result<RequestStats> {
     let cfg = get_config();
     let log_ingest_errors = ingestion_log_enabled().await;
@@ -301,6 +306,45 @@ async fn write_logs(
     };
     let stream_settings = infra::schema::unwrap_stream_settings(&schema).unwrap_or_default();
 
+    // extract key-value pairs of the message before the other field based settings, so they
+    // can refer to the extracted fields
+    if let Some(kv_extraction) = stream_settings.kv_extraction.as_ref() {
+        for (_, record) in json_data.iter_mut() {
+            crate::service::ingestion::extract_kv_fields(record, kv_extraction);
+        }
+    }
+
+    // rename field variants to the org's canonical names, the other settings use the latter
+    if let Some(normalizer) = get_cached_org_setting(org_id)
+        .await
+        .and_then(|settings| Normalizer::new(&settings.field_normalization))
+    {
+        for (_, record) in json_data.iter_mut() {
+            normalizer.normalize(record);
+        }
+    }
+
+    // normalize ip fields before checking the schema, so the new fields are added to it
+    if !stream_settings.ip_fields.is_empty() {
+        for (_, record) in json_data.iter_mut() {
+            crate::service::ingestion::normalize_ip_fields(record, &stream_settings.ip_fields);
+        }
+    }
+
+    // tag records hitting threat intel indicators, findings are written in the background
+    if !stream_settings.threat_intel_fields.is_empty() {
+        let findings = crate::service::threat_intel::match_records(
+            org_id,
+            stream_name,
+            &mut json_data,
+            &stream_settings.threat_intel_fields,
+        );
+        crate::service::threat_intel::queue_findings(org_id, findings);
+    }
+
+    // update the metrics extracted from the records, they are written in the background
+    crate::service::log_metrics::observe(org_id, stream_name, &json_data);
+
     let mut partition_keys: Vec<StreamPartition> = vec![];
     let mut partition_time_level = PartitionTimeLevel::from(cfg.limit.logs_file_retention.as_str());
     if stream_schema.has_partition_keys {
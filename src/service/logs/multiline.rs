@@ -0,0 +1,214 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Multi-line reassembly of raw log lines.
+//!
+//! Lines that don't match the stream's start pattern are appended to the
+//! previous record. Batch inputs (HEC) are merged within the request, streaming
+//! inputs (syslog) keep the partial record per stream until the next start line
+//! or until the timeout expires.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use config::{meta::stream::MultilineSettings, utils::json};
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
+use regex::Regex;
+
+static PATTERNS: Lazy<RwLock<HashMap<String, Option<Regex>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+static PENDING: Lazy<Mutex<HashMap<(String, String), PendingRecord>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+struct PendingRecord {
+    value: json::Value,
+    lines: usize,
+    updated_at: Instant,
+    timeout: Duration,
+}
+
+fn start_pattern(pattern: &str) -> Option<Regex> {
+    if let Some(re) = PATTERNS.read().get(pattern) {
+        return re.clone();
+    }
+    let re = match Regex::new(pattern) {
+        Ok(re) => Some(re),
+        Err(e) => {
+            log::warn!("[MULTILINE] invalid start pattern {pattern}: {e}");
+            None
+        }
+    };
+    PATTERNS.write().insert(pattern.to_string(), re.clone());
+    re
+}
+
+/// Returns the line if the record is a continuation of the previous one.
+fn continuation_line<'a>(value: &'a json::Value, field: &str, re: &Regex) -> Option<&'a str> {
+    let line = value.get(field)?.as_str()?;
+    (!re.is_match(line)).then_some(line)
+}
+
+fn append_line(value: &mut json::Value, field: &str, line: &str) {
+    if let Some(json::Value::String(s)) = value.get_mut(field) {
+        s.push('\n');
+        s.push_str(line);
+    }
+}
+
+/// Merges continuation lines of a batch into their start records.
+pub fn merge(
+    records: Vec<json::Value>,
+    settings: &MultilineSettings,
+    default_field: &str,
+) -> Vec<json::Value> {
+    let Some(re) = start_pattern(&settings.start_pattern) else {
+        return records;
+    };
+    let field = settings.field(default_field);
+    let mut merged: Vec<json::Value> = Vec::with_capacity(records.len());
+    // lines of the last merged record, 0 when it can't take more lines
+    let mut lines = 0;
+    for record in records {
+        if lines > 0 && lines < settings.max_lines {
+            if let Some(line) = continuation_line(&record, field, &re) {
+                append_line(merged.last_mut().unwrap(), field, line);
+                lines += 1;
+                continue;
+            }
+        }
+        lines = if record.get(field).is_some_and(|v| v.is_string()) {
+            1
+        } else {
+            0
+        };
+        merged.push(record);
+    }
+    merged
+}
+
+/// Adds a record of a streaming input, returns the previous record of the
+/// stream once it's complete.
+pub fn push(
+    org_id: &str,
+    stream_name: &str,
+    settings: &MultilineSettings,
+    default_field: &str,
+    record: json::Value,
+) -> Option<json::Value> {
+    let Some(re) = start_pattern(&settings.start_pattern) else {
+        return Some(record);
+    };
+    let field = settings.field(default_field);
+    if !record.get(field).is_some_and(|v| v.is_string()) {
+        return Some(record);
+    }
+
+    let key = (org_id.to_string(), stream_name.to_string());
+    let mut pending = PENDING.lock();
+    if let Some(p) = pending.get_mut(&key) {
+        if p.lines < settings.max_lines && p.updated_at.elapsed() < p.timeout {
+            if let Some(line) = continuation_line(&record, field, &re) {
+                append_line(&mut p.value, field, line);
+                p.lines += 1;
+                p.updated_at = Instant::now();
+                return None;
+            }
+        }
+    }
+    pending
+        .insert(
+            key,
+            PendingRecord {
+                value: record,
+                lines: 1,
+                updated_at: Instant::now(),
+                timeout: Duration::from_millis(settings.timeout_ms),
+            },
+        )
+        .map(|p| p.value)
+}
+
+/// Takes the partial records which didn't get new lines within their timeout.
+pub fn take_expired() -> Vec<(String, String, json::Value)> {
+    let mut pending = PENDING.lock();
+    let expired = pending
+        .iter()
+        .filter(|(_, p)| p.updated_at.elapsed() >= p.timeout)
+        .map(|(k, _)| k.clone())
+        .collect::<Vec<_>>();
+    expired
+        .into_iter()
+        .filter_map(|key| pending.remove(&key).map(|p| (key.0, key.1, p.value)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(max_lines: usize) -> MultilineSettings {
+        MultilineSettings {
+            field: String::new(),
+            start_pattern: r"^\d{4}-\d{2}-\d{2}".to_string(),
+            max_lines,
+            timeout_ms: 60_000,
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let records = vec![
+            json::json!({"log": "2025-01-01 ERROR boom"}),
+            json::json!({"log": "java.lang.NullPointerException"}),
+            json::json!({"log": "    at com.example.Foo.bar(Foo.java:42)"}),
+            json::json!({"log": "2025-01-01 INFO ok"}),
+            json::json!({"other": 1}),
+            json::json!({"log": "    at dangling"}),
+        ];
+        let merged = merge(records, &settings(500), "log");
+        assert_eq!(merged.len(), 4);
+        assert_eq!(
+            merged[0]["log"],
+            "2025-01-01 ERROR boom\njava.lang.NullPointerException\n    at com.example.Foo.bar(Foo.java:42)"
+        );
+        assert_eq!(merged[1]["log"], "2025-01-01 INFO ok");
+        assert_eq!(merged[3]["log"], "    at dangling");
+
+        let records = vec![
+            json::json!({"log": "2025-01-01 ERROR boom"}),
+            json::json!({"log": "a"}),
+            json::json!({"log": "b"}),
+        ];
+        let merged = merge(records, &settings(2), "log");
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0]["log"], "2025-01-01 ERROR boom\na");
+    }
+
+    #[test]
+    fn test_push() {
+        let s = settings(500);
+        let org = "test_push_org";
+        let push = |v| push(org, "default", &s, "message", v);
+        assert!(push(json::json!({"message": "2025-01-01 ERROR boom"})).is_none());
+        assert!(push(json::json!({"message": "  at a"})).is_none());
+        let done = push(json::json!({"message": "2025-01-01 INFO ok"})).unwrap();
+        assert_eq!(done["message"], "2025-01-01 ERROR boom\n  at a");
+        assert!(take_expired().iter().all(|(o, ..)| o != org));
+    }
+}
@@ -56,8 +56,6 @@ use crate::{
 };
 
 pub async fn ingest(msg: &str, addr: SocketAddr) -> Result<HttpResponse> {
-    let start = std::time::Instant::now();
-    let started_at: i64 = Utc::now().timestamp_micros();
     let ip = addr.ip();
     let matching_route = get_org_for_ip(ip).await;
 
@@ -77,12 +75,53 @@ pub async fn ingest(msg: &str, addr: SocketAddr) -> Result<HttpResponse> {
         }
     };
 
-    let in_stream_name = &route.stream_name;
     let org_id = &route.org_id;
+    let stream_name = format_stream_name(&route.stream_name);
+
+    // parse msg to json::Value
+    let parsed_msg = syslog_loose::parse_message(msg, Variant::Either);
+    let mut value = message_to_value(parsed_msg);
+
+    // hold the record until the multi-line record is complete
+    if let Some(multiline) = infra::schema::get_settings(org_id, &stream_name, StreamType::Logs)
+        .await
+        .and_then(|s| s.multiline)
+    {
+        match super::multiline::push(org_id, &stream_name, &multiline, "message", value) {
+            Some(record) => value = record,
+            None => {
+                return Ok(HttpResponse::Ok().json(IngestionResponse::new(
+                    http::StatusCode::OK.into(),
+                    vec![StreamStatus::new(&stream_name)],
+                )));
+            }
+        }
+    }
+
+    ingest_record(org_id, &stream_name, value).await
+}
+
+/// Ingests the multi-line records which didn't get new lines within the
+/// timeout.
+pub async fn flush_multiline() {
+    for (org_id, stream_name, value) in super::multiline::take_expired() {
+        if let Err(e) = ingest_record(&org_id, &stream_name, value).await {
+            log::error!("Syslogs multiline flush error for {org_id}/{stream_name}: {e}");
+        }
+    }
+}
+
+async fn ingest_record(
+    org_id: &str,
+    stream_name: &str,
+    mut value: json::Value,
+) -> Result<HttpResponse> {
+    let start = std::time::Instant::now();
+    let started_at: i64 = Utc::now().timestamp_micros();
     let log_ingestion_errors = ingestion_log_enabled().await;
 
     // check stream
-    let stream_name = format_stream_name(in_stream_name);
+    let stream_name = stream_name.to_string();
     if let Err(e) = check_ingestion_allowed(org_id, StreamType::Logs, Some(&stream_name)) {
         log::error!("Syslogs ingestion error: {e}");
         return Ok(map_error_to_http_response(&e, None));
@@ -93,6 +132,9 @@ pub async fn ingest(msg: &str, addr: SocketAddr) -> Result<HttpResponse> {
         .timestamp_micros();
     let max_ts = (Utc::now() + Duration::try_hours(cfg.limit.ingest_allowed_in_future).unwrap())
         .timestamp_micros();
+    let ts_settings = infra::schema::get_settings(org_id, &stream_name, StreamType::Logs)
+        .await
+        .and_then(|s| s.timestamp);
 
     let mut stream_params = vec![StreamParams::new(org_id, &stream_name, StreamType::Logs)];
 
@@ -132,10 +174,6 @@ pub async fn ingest(msg: &str, addr: SocketAddr) -> Result<HttpResponse> {
     let mut json_data_by_stream = HashMap::new();
     let mut size_by_stream = HashMap::new();
 
-    // parse msg to json::Value
-    let parsed_msg = syslog_loose::parse_message(msg, Variant::Either);
-    let mut value = message_to_value(parsed_msg);
-
     // store a copy of original data before it's modified, when
     // 1. original data is an object
     let original_data = if value.is_object() {
@@ -166,7 +204,14 @@ pub async fn ingest(msg: &str, addr: SocketAddr) -> Result<HttpResponse> {
         value = flatten::flatten_with_level(value, cfg.limit.ingest_flatten_level).unwrap();
 
         // handle timestamp
-        let timestamp = match handle_timestamp(&mut value, min_ts, max_ts) {
+        let timestamp = match handle_timestamp(
+            &mut value,
+            min_ts,
+            max_ts,
+            ts_settings.as_ref(),
+            org_id,
+            &stream_name,
+        ) {
             Ok(ts) => ts,
             Err(e) => {
                 stream_status.status.failed += 1;
@@ -294,9 +339,24 @@ pub async fn ingest(msg: &str, addr: SocketAddr) -> Result<HttpResponse> {
                         .await;
                     }
 
+                    let dest_ts_settings = if destination_stream == stream_name {
+                        ts_settings.clone()
+                    } else {
+                        infra::schema::get_settings(org_id, &destination_stream, StreamType::Logs)
+                            .await
+                            .and_then(|s| s.timestamp)
+                    };
+
                     for (idx, mut res) in stream_pl_results {
                         // handle timestamp
-                        if let Err(e) = handle_timestamp(&mut res, min_ts, max_ts) {
+                        if let Err(e) = handle_timestamp(
+                            &mut res,
+                            min_ts,
+                            max_ts,
+                            dest_ts_settings.as_ref(),
+                            org_id,
+                            &destination_stream,
+                        ) {
                             stream_status.status.failed += 1;
                             stream_status.status.error = e.to_string();
                             metrics::INGEST_ERRORS
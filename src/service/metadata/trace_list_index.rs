@@ -206,6 +206,7 @@ impl TraceListIndex {
                 index_fields: vec![],
                 bloom_filter_fields: vec!["trace_id".to_string()],
                 data_retention: 0,
+                derived_data_retention: 0,
                 flatten_level: None,
                 max_query_range: 0,
                 defined_schema_fields: None,
@@ -216,6 +217,12 @@ impl TraceListIndex {
                 extended_retention_days: vec![],
                 index_all_values: false,
                 index_original_data: false,
+                ip_fields: vec![],
+                threat_intel_fields: vec![],
+                timestamp: None,
+                multiline: None,
+                kv_extraction: None,
+                field_metadata: Default::default(),
             };
 
             stream::save_stream_settings(org_id, STREAM_NAME, StreamType::Metadata, settings)
@@ -0,0 +1,319 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Endpoints backing the metrics explorer. Everything here is answered from
+//! the stream schemas, the stream stats and queries that only read the label
+//! columns of a metric, so browsing never evaluates PromQL.
+
+use config::{
+    TIMESTAMP_COL_NAME,
+    meta::{
+        promql::{
+            Cardinality, ExplorerLabel, ExplorerLabels, ExplorerMetric, ExplorerSeriesPreview,
+            HASH_LABEL, MetricType, NAME_LABEL, VALUE_LABEL,
+        },
+        search::default_use_cache,
+        stream::StreamType,
+    },
+    utils::json,
+};
+use datafusion::arrow::datatypes::Schema;
+use hashbrown::HashMap;
+use infra::errors::{Error, Result};
+use promql_parser::parser;
+
+use super::prom::{metric_stream_stats, selector_filters, try_into_metric_name};
+use crate::service::{db, search as search_service};
+
+/// Lists the metrics that have samples in `[start, end]`, along with their
+/// metadata and sample counts.
+pub async fn list_metrics(
+    org_id: &str,
+    search: Option<&str>,
+    limit: Option<usize>,
+    start: i64,
+    end: i64,
+) -> Result<Vec<ExplorerMetric>> {
+    let stream_schemas = db::schema::list(org_id, Some(StreamType::Metrics), true)
+        .await
+        .map_err(|e| Error::Message(format!("failed to get metrics' stream schemas: {e}")))?;
+    let search = search.map(|s| s.to_lowercase());
+    let mut metrics = Vec::new();
+    for schema in stream_schemas {
+        if let Some(search) = search.as_ref() {
+            if !schema.stream_name.to_lowercase().contains(search) {
+                continue;
+            }
+        }
+        let stats = metric_stream_stats(org_id, &schema.stream_name, &schema.schema);
+        if !stats.time_range_intersects(start, end) {
+            continue;
+        }
+        let metadata = super::get_prom_metadata_from_schema(&schema.schema);
+        metrics.push(ExplorerMetric {
+            labels: label_names(&schema.schema).len(),
+            metric_type: metadata
+                .as_ref()
+                .map_or(MetricType::Unknown, |m| m.metric_type),
+            help: metadata
+                .as_ref()
+                .map(|m| m.help.clone())
+                .unwrap_or_default(),
+            unit: metadata.map(|m| m.unit).unwrap_or_default(),
+            samples: stats.doc_num,
+            name: schema.stream_name,
+        });
+    }
+    metrics.sort_by(|a, b| a.name.cmp(&b.name));
+    if let Some(limit) = limit {
+        metrics.truncate(limit);
+    }
+    Ok(metrics)
+}
+
+/// Returns the labels of the metric selected by `selector` with the
+/// approximate number of distinct values of each, computed over the series
+/// matching the selector.
+pub async fn get_labels(
+    org_id: &str,
+    selector: &parser::VectorSelector,
+    start: i64,
+    end: i64,
+) -> Result<ExplorerLabels> {
+    let metric_name = metric_name(selector)?;
+    let schema = infra::schema::get(org_id, &metric_name, StreamType::Metrics).await?;
+    let labels = label_names(&schema);
+    let mut resp = ExplorerLabels {
+        metric: metric_name.clone(),
+        series: 0,
+        labels: vec![],
+    };
+    if labels.is_empty() {
+        return Ok(resp);
+    }
+
+    let filters = selector_filters(&schema, selector);
+    let sql = label_counts_sql(&metric_name, &labels, &filters);
+    let hits = search(org_id, sql, 1, start, end).await?;
+    let Some(counts) = hits.first() else {
+        return Ok(resp);
+    };
+    resp.series = count(counts, HASH_LABEL);
+    resp.labels = labels
+        .into_iter()
+        .map(|name| {
+            let values = count(counts, name);
+            ExplorerLabel {
+                name: name.to_string(),
+                values,
+                cardinality: Cardinality::from(values),
+            }
+        })
+        .collect();
+    Ok(resp)
+}
+
+/// Returns up to `limit` series matching `selector`, along with the
+/// approximate number of series the selector matches in total.
+pub async fn preview_series(
+    org_id: &str,
+    selector: &parser::VectorSelector,
+    limit: usize,
+    start: i64,
+    end: i64,
+) -> Result<ExplorerSeriesPreview> {
+    let metric_name = metric_name(selector)?;
+    let schema = infra::schema::get(org_id, &metric_name, StreamType::Metrics).await?;
+    let labels = label_names(&schema);
+    let mut resp = ExplorerSeriesPreview {
+        metric: metric_name.clone(),
+        total: 0,
+        truncated: false,
+        series: vec![],
+    };
+    if labels.is_empty() || limit == 0 {
+        return Ok(resp);
+    }
+
+    let filters = selector_filters(&schema, selector);
+    let sql = label_counts_sql(&metric_name, &[], &filters);
+    let hits = search(org_id, sql, 1, start, end).await?;
+    resp.total = hits.first().map_or(0, |hit| count(hit, HASH_LABEL));
+    if resp.total == 0 {
+        return Ok(resp);
+    }
+
+    // fetch one more series than requested to know whether the preview is
+    // complete, the total above is only an estimate
+    let sql = series_sql(&metric_name, &labels, &filters);
+    let hits = search(org_id, sql, limit as i64 + 1, start, end).await?;
+    resp.truncated = hits.len() > limit;
+    resp.series = hits
+        .into_iter()
+        .take(limit)
+        .map(|hit| series_labels(&metric_name, hit))
+        .collect();
+    resp.total = resp.total.max(resp.series.len() as u64);
+    Ok(resp)
+}
+
+fn metric_name(selector: &parser::VectorSelector) -> Result<String> {
+    try_into_metric_name(selector)
+        .ok_or_else(|| Error::Message("match[] argument must contain a metric name".to_string()))
+}
+
+/// Returns the label columns of a metric stream, `__name__` excluded.
+fn label_names(schema: &Schema) -> Vec<&str> {
+    schema
+        .fields()
+        .iter()
+        .map(|f| f.name().as_str())
+        .filter(|&s| {
+            s != TIMESTAMP_COL_NAME && s != VALUE_LABEL && s != HASH_LABEL && s != NAME_LABEL
+        })
+        .collect()
+}
+
+fn where_clause(filters: &[String]) -> String {
+    if filters.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", filters.join(" AND "))
+    }
+}
+
+/// Builds a single-row query estimating the number of series, under the
+/// `__hash__` column, and the number of distinct values of each label.
+fn label_counts_sql(metric_name: &str, labels: &[&str], filters: &[String]) -> String {
+    let counts = std::iter::once(HASH_LABEL)
+        .chain(labels.iter().copied())
+        .map(|name| format!("approx_distinct(\"{name}\") AS \"{name}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "SELECT {counts} FROM \"{metric_name}\"{}",
+        where_clause(filters)
+    )
+}
+
+fn series_sql(metric_name: &str, labels: &[&str], filters: &[String]) -> String {
+    let columns = labels
+        .iter()
+        .map(|name| format!("\"{name}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "SELECT DISTINCT \"{HASH_LABEL}\", {columns} FROM \"{metric_name}\"{}",
+        where_clause(filters)
+    )
+}
+
+fn count(hit: &json::Value, name: &str) -> u64 {
+    hit.get(name).and_then(|v| v.as_u64()).unwrap_or_default()
+}
+
+fn series_labels(metric_name: &str, hit: json::Value) -> HashMap<String, String> {
+    let mut labels = HashMap::new();
+    labels.insert(NAME_LABEL.to_string(), metric_name.to_string());
+    if let json::Value::Object(map) = hit {
+        for (name, value) in map {
+            if name == HASH_LABEL || value.is_null() {
+                continue;
+            }
+            let value = match value {
+                json::Value::String(s) => s,
+                v => v.to_string(),
+            };
+            labels.insert(name, value);
+        }
+    }
+    labels
+}
+
+async fn search(
+    org_id: &str,
+    sql: String,
+    size: i64,
+    start: i64,
+    end: i64,
+) -> Result<Vec<json::Value>> {
+    let req = config::meta::search::Request {
+        query: config::meta::search::Query {
+            sql,
+            from: 0,
+            size,
+            start_time: start,
+            end_time: end,
+            ..Default::default()
+        },
+        encoding: config::meta::search::RequestEncoding::Empty,
+        regions: vec![],
+        clusters: vec![],
+        timeout: 0,
+        search_type: None,
+        search_event_context: None,
+        use_cache: default_use_cache(),
+        local_mode: None,
+    };
+    match search_service::search("", org_id, StreamType::Metrics, None, &req).await {
+        Ok(resp) => Ok(resp.hits),
+        Err(err) => {
+            log::error!("[METRICS_EXPLORER] search error: {err}");
+            Err(err)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label_counts_sql() {
+        assert_eq!(
+            label_counts_sql("up", &["job", "instance"], &[]),
+            "SELECT approx_distinct(\"__hash__\") AS \"__hash__\", approx_distinct(\"job\") AS \"job\", approx_distinct(\"instance\") AS \"instance\" FROM \"up\""
+        );
+        assert_eq!(
+            label_counts_sql("up", &[], &["job = 'node'".to_string()]),
+            "SELECT approx_distinct(\"__hash__\") AS \"__hash__\" FROM \"up\" WHERE job = 'node'"
+        );
+    }
+
+    #[test]
+    fn test_series_sql() {
+        assert_eq!(
+            series_sql(
+                "up",
+                &["job", "instance"],
+                &[
+                    "job = 'node'".to_string(),
+                    "re_match(instance, 'a.*')".to_string()
+                ]
+            ),
+            "SELECT DISTINCT \"__hash__\", \"job\", \"instance\" FROM \"up\" WHERE job = 'node' AND re_match(instance, 'a.*')"
+        );
+    }
+
+    #[test]
+    fn test_series_labels() {
+        let hit = json::json!({"__hash__": "123", "job": "node", "code": 200, "empty": null});
+        let labels = series_labels("up", hit);
+        assert_eq!(labels.len(), 3);
+        assert_eq!(labels.get("__name__").unwrap(), "up");
+        assert_eq!(labels.get("job").unwrap(), "node");
+        assert_eq!(labels.get("code").unwrap(), "200");
+    }
+}
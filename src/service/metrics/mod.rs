@@ -21,6 +21,7 @@ use datafusion::arrow::datatypes::Schema;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+pub mod explorer;
 pub mod json;
 pub mod otlp;
 pub mod prom;
@@ -24,9 +24,10 @@ use config::{
     meta::{
         alerts::alert,
         promql::*,
+        remote_write_filter::FilterVerdict,
         search::default_use_cache,
         self_reporting::usage::UsageType,
-        stream::{PartitioningDetails, StreamParams, StreamType},
+        stream::{PartitioningDetails, StreamParams, StreamStats, StreamType},
     },
     metrics,
     utils::{
@@ -48,7 +49,7 @@ use proto::prometheus_rpc;
 
 use crate::{
     common::{
-        infra::config::{METRIC_CLUSTER_LEADER, METRIC_CLUSTER_MAP},
+        infra::config::{METRIC_CLUSTER_LEADER, METRIC_CLUSTER_MAP, REMOTE_WRITE_FILTERS},
         meta::stream::SchemaRecords,
     },
     service::{
@@ -103,6 +104,8 @@ pub async fn remote_write(
     // records buffer
     let mut json_data_by_stream: HashMap<String, Vec<(json::Value, i64)>> = HashMap::new();
 
+    let write_filter = REMOTE_WRITE_FILTERS.get(org_id).map(|f| f.value().clone());
+
     // parse metadata
     for item in request.metadata {
         let metric_name = format_stream_name(&item.metric_family_name.clone());
@@ -206,23 +209,22 @@ pub async fn remote_write(
             None => continue,
         };
 
-        // parse samples
-        for sample in event.samples {
-            let mut sample_val = sample.value;
-            // revisit in future
-            if sample_val.is_infinite() {
-                if sample_val == f64::INFINITY || sample_val > f64::MAX {
-                    sample_val = f64::MAX;
-                } else if sample_val == f64::NEG_INFINITY || sample_val < f64::MIN {
-                    sample_val = f64::MIN;
-                }
-            } else if sample_val.is_nan() {
-                // skip the entry from adding to store
+        if let Some(filter) = write_filter.as_ref() {
+            let verdict = filter.check(&metric_name, &labels);
+            if verdict != FilterVerdict::Accept {
+                metrics::INGEST_REMOTE_WRITE_DROPPED_SAMPLES
+                    .with_label_values(&[org_id, verdict.as_str()])
+                    .inc_by(event.samples.len() as u64);
                 continue;
             }
+        }
+
+        // parse samples
+        for (sample_ts, sample_val, exemplars) in series_rows(&event.samples, &event.exemplars) {
+            let exemplar_only = sample_val.is_none();
             let metric = Metric {
                 labels: &labels,
-                value: sample_val,
+                value: sample_val.unwrap_or_default(),
             };
 
             if first_line && dedup_enabled && !cluster_name.is_empty() {
@@ -324,11 +326,18 @@ pub async fn remote_write(
             }
 
             let mut value: json::Value = json::to_value(&metric).unwrap();
-            let timestamp = parse_i64_to_timestamp_micros(sample.timestamp);
-            value.as_object_mut().unwrap().insert(
+            let timestamp = parse_i64_to_timestamp_micros(sample_ts);
+            let val_map = value.as_object_mut().unwrap();
+            val_map.insert(
                 TIMESTAMP_COL_NAME.to_string(),
                 json::Value::Number(timestamp.into()),
             );
+            if exemplar_only {
+                val_map.remove(VALUE_LABEL);
+            }
+            if let Some(exemplars) = exemplars {
+                val_map.insert(EXEMPLARS_LABEL.to_string(), json::Value::String(exemplars));
+            }
 
             // ready to be buffered for downstream processing
             if stream_executable_pipelines
@@ -417,7 +426,7 @@ pub async fn remote_write(
 
         for (mut value, timestamp) in json_data {
             let val_map = value.as_object_mut().unwrap();
-            let hash = super::signature_without_labels(val_map, &[VALUE_LABEL]);
+            let hash = super::signature_without_labels(val_map, &[VALUE_LABEL, EXEMPLARS_LABEL]);
             val_map.insert(HASH_LABEL.to_string(), json::Value::Number(hash.into()));
             val_map.insert(
                 TIMESTAMP_COL_NAME.to_string(),
@@ -711,34 +720,13 @@ pub(crate) async fn get_series(
     }
 
     let mut sql = format!("SELECT DISTINCT({HASH_LABEL}), \"{label_names}\" FROM {metric_name}");
-    let mut sql_where = Vec::new();
-    if let Some(selector) = selector {
-        for mat in selector.matchers.matchers.iter() {
-            if mat.name == TIMESTAMP_COL_NAME
-                || mat.name == VALUE_LABEL
-                || schema.field_with_name(&mat.name).is_err()
-            {
-                continue;
-            }
-            match &mat.op {
-                MatchOp::Equal => {
-                    sql_where.push(format!("{} = '{}'", mat.name, mat.value));
-                }
-                MatchOp::NotEqual => {
-                    sql_where.push(format!("{} != '{}'", mat.name, mat.value));
-                }
-                MatchOp::Re(_re) => {
-                    sql_where.push(format!("re_match({}, '{}')", mat.name, mat.value));
-                }
-                MatchOp::NotRe(_re) => {
-                    sql_where.push(format!("re_not_match({}, '{}')", mat.name, mat.value));
-                }
-            }
-        }
-        if !sql_where.is_empty() {
-            sql.push_str(" WHERE ");
-            sql.push_str(&sql_where.join(" AND "));
-        }
+    let sql_where = selector
+        .as_ref()
+        .map(|selector| selector_filters(&schema, selector))
+        .unwrap_or_default();
+    if !sql_where.is_empty() {
+        sql.push_str(" WHERE ");
+        sql.push_str(&sql_where.join(" AND "));
     }
 
     let req = config::meta::search::Request {
@@ -778,6 +766,35 @@ pub(crate) async fn get_series(
     Ok(series)
 }
 
+/// Translates the label matchers of `selector` into SQL conditions, skipping
+/// the matchers on labels the metric doesn't have.
+pub(crate) fn selector_filters(schema: &Schema, selector: &parser::VectorSelector) -> Vec<String> {
+    let mut sql_where = Vec::new();
+    for mat in selector.matchers.matchers.iter() {
+        if mat.name == TIMESTAMP_COL_NAME
+            || mat.name == VALUE_LABEL
+            || schema.field_with_name(&mat.name).is_err()
+        {
+            continue;
+        }
+        match &mat.op {
+            MatchOp::Equal => {
+                sql_where.push(format!("{} = '{}'", mat.name, mat.value));
+            }
+            MatchOp::NotEqual => {
+                sql_where.push(format!("{} != '{}'", mat.name, mat.value));
+            }
+            MatchOp::Re(_re) => {
+                sql_where.push(format!("re_match({}, '{}')", mat.name, mat.value));
+            }
+            MatchOp::NotRe(_re) => {
+                sql_where.push(format!("re_not_match({}, '{}')", mat.name, mat.value));
+            }
+        }
+    }
+    sql_where
+}
+
 pub(crate) async fn get_labels(
     org_id: &str,
     selector: Option<parser::VectorSelector>,
@@ -842,22 +859,7 @@ pub(crate) async fn get_label_values(
                     continue;
                 }
             }
-            let stats = match super::get_prom_metadata_from_schema(&schema.schema) {
-                None => stats::get_stream_stats(org_id, &schema.stream_name, stream_type),
-                Some(metadata) => {
-                    if metadata.metric_type == MetricType::Histogram
-                        || metadata.metric_type == MetricType::Summary
-                    {
-                        stats::get_stream_stats(
-                            org_id,
-                            &format!("{}_sum", schema.stream_name),
-                            stream_type,
-                        )
-                    } else {
-                        stats::get_stream_stats(org_id, &schema.stream_name, stream_type)
-                    }
-                }
-            };
+            let stats = metric_stream_stats(org_id, &schema.stream_name, &schema.schema);
             if stats.time_range_intersects(start, end) {
                 label_values.push(schema.stream_name)
             }
@@ -920,6 +922,21 @@ pub(crate) async fn get_label_values(
     Ok(label_values)
 }
 
+/// Returns the stats of a metric stream. Histograms and summaries store their
+/// samples in the `_sum`, `_count` and `_bucket` streams, so the stats of the
+/// `_sum` stream are used for them.
+pub(crate) fn metric_stream_stats(org_id: &str, metric_name: &str, schema: &Schema) -> StreamStats {
+    match super::get_prom_metadata_from_schema(schema) {
+        Some(metadata)
+            if metadata.metric_type == MetricType::Histogram
+                || metadata.metric_type == MetricType::Summary =>
+        {
+            stats::get_stream_stats(org_id, &format!("{metric_name}_sum"), StreamType::Metrics)
+        }
+        _ => stats::get_stream_stats(org_id, metric_name, StreamType::Metrics),
+    }
+}
+
 pub(crate) fn try_into_metric_name(selector: &parser::VectorSelector) -> Option<String> {
     match &selector.name {
         Some(name) => {
@@ -940,6 +957,71 @@ pub(crate) fn try_into_metric_name(selector: &parser::VectorSelector) -> Option<
     }
 }
 
+/// Returns the rows to store for the samples of a series, as timestamp, value and exemplars.
+///
+/// NaN samples are dropped, except staleness markers which are serialized as null values, and
+/// infinite values are clamped. The exemplars of the series are then kept on its last row, or on
+/// a row without value at the latest exemplar when no sample is left, as prometheus sends them in
+/// series without samples.
+fn series_rows(
+    samples: &[prometheus_rpc::Sample],
+    exemplars: &[prometheus_rpc::Exemplar],
+) -> Vec<(i64, Option<f64>, Option<String>)> {
+    let mut rows = samples
+        .iter()
+        .filter_map(|sample| {
+            let mut value = sample.value;
+            // revisit in future
+            if value.is_infinite() {
+                if value == f64::INFINITY || value > f64::MAX {
+                    value = f64::MAX;
+                } else if value == f64::NEG_INFINITY || value < f64::MIN {
+                    value = f64::MIN;
+                }
+            } else if value.is_nan() && !is_stale_nan(value) {
+                return None;
+            }
+            Some((sample.timestamp, Some(value), None))
+        })
+        .collect::<Vec<_>>();
+    if rows.is_empty()
+        && let Some(ts) = exemplars.iter().map(|e| e.timestamp).max()
+    {
+        rows.push((ts, None, None));
+    }
+    if let Some(last) = rows.last_mut() {
+        last.2 = format_exemplars(exemplars);
+    }
+    rows
+}
+
+/// Formats remote write exemplars the same way OTLP stores them, a json array of objects
+/// holding the exemplar labels, value and timestamp.
+fn format_exemplars(exemplars: &[prometheus_rpc::Exemplar]) -> Option<String> {
+    if exemplars.is_empty() {
+        return None;
+    }
+    let exemplars = exemplars
+        .iter()
+        .map(|exemplar| {
+            let mut rec = json::Map::with_capacity(exemplar.labels.len() + 2);
+            for label in &exemplar.labels {
+                rec.insert(
+                    label.name.to_string(),
+                    json::Value::String(label.value.to_string()),
+                );
+            }
+            rec.insert(VALUE_LABEL.to_string(), exemplar.value.into());
+            rec.insert(
+                TIMESTAMP_COL_NAME.to_string(),
+                parse_i64_to_timestamp_micros(exemplar.timestamp).into(),
+            );
+            json::Value::Object(rec)
+        })
+        .collect::<Vec<_>>();
+    json::to_string(&exemplars).ok()
+}
+
 async fn prom_ha_handler(
     has_entry: bool,
     cluster_name: &str,
@@ -1010,3 +1092,50 @@ async fn prom_ha_handler(
 
     _accept_record
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exemplar(value: f64, timestamp: i64) -> prometheus_rpc::Exemplar {
+        prometheus_rpc::Exemplar {
+            labels: vec![prometheus_rpc::Label {
+                name: "trace_id".to_string(),
+                value: "abc".to_string(),
+            }],
+            value,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_series_rows_trailing_nan() {
+        let samples = vec![
+            prometheus_rpc::Sample {
+                value: 1.0,
+                timestamp: 1000,
+            },
+            prometheus_rpc::Sample {
+                value: f64::NAN,
+                timestamp: 2000,
+            },
+        ];
+        let rows = series_rows(&samples, &[exemplar(0.5, 1500)]);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].0, 1000);
+        assert_eq!(rows[0].1, Some(1.0));
+        let exemplars: json::Value = json::from_str(rows[0].2.as_deref().unwrap()).unwrap();
+        assert_eq!(exemplars[0]["trace_id"], "abc");
+
+        // only NaN samples, the exemplars get a row without value
+        let rows = series_rows(&samples[1..], &[exemplar(0.5, 1500), exemplar(0.7, 1800)]);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].0, 1800);
+        assert_eq!(rows[0].1, None);
+        assert!(rows[0].2.is_some());
+
+        // no exemplars
+        let rows = series_rows(&samples, &[]);
+        assert_eq!(rows, vec![(1000, Some(1.0), None)]);
+    }
+}
@@ -18,20 +18,31 @@ use infra::errors::Result;
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 pub mod alerts;
+pub mod annotations;
+pub mod cases;
+pub mod chaos;
 pub mod cluster_info;
+pub mod column_mask;
 pub mod compact;
 pub mod dashboards;
+pub mod detections;
 pub mod db;
 pub mod enrichment;
 pub mod enrichment_table;
 pub mod exporter;
+pub mod external_authz;
 pub mod file_list;
 pub mod file_list_dump;
 pub mod folders;
 pub mod functions;
+pub mod grok;
 pub mod grpc;
+pub mod incident_timeline;
+pub mod index_advisor;
 pub mod ingestion;
+pub mod invitations;
 pub mod kv;
+pub mod log_metrics;
 pub mod logs;
 pub mod metadata;
 pub mod metrics;
@@ -41,8 +52,12 @@ pub mod org_usage;
 pub mod organization;
 pub mod pipeline;
 pub mod promql;
+pub mod query_template;
+pub mod remote_write_filter;
+pub mod replication;
 #[cfg(feature = "enterprise")]
 pub mod ratelimit;
+pub mod row_policy;
 pub mod schema;
 pub mod search;
 pub mod websocket_events;
@@ -52,11 +67,20 @@ pub mod search_jobs;
 pub mod self_reporting;
 pub mod session;
 pub mod short_url;
+pub mod sql_policy;
 pub mod stream;
+pub mod stream_clone;
+pub mod stream_field_usage;
+pub mod stream_hourly_stats;
+pub mod stream_storage_usage;
+pub mod synthetic_data;
 pub mod syslogs_route;
+pub mod threat_intel;
 pub mod tls;
+pub mod ui_profile;
 pub mod traces;
 pub mod users;
+pub mod workspaces;
 
 // format stream name
 pub async fn get_formatted_stream_name(params: StreamParams) -> Result<String> {
@@ -82,6 +82,7 @@ pub fn config_node_to_proto(node: ConfigNode) -> NodeDetails {
         broadcasted: node.broadcasted,
         version: node.version,
         metrics: Some(metrics),
+        labels: node.labels,
     }
 }
 
@@ -148,6 +149,7 @@ pub fn proto_node_to_config(node: NodeDetails) -> ConfigNode {
         broadcasted: node.broadcasted,
         version: node.version,
         metrics,
+        labels: node.labels,
     }
 }
 
@@ -50,6 +50,10 @@ pub async fn get_org_usage(
             skip_wal: false,
             streaming_output: false,
             streaming_id: None,
+            max_points: None,
+            time_ranges: vec![],
+            time_shifts: vec![],
+            variables: vec![],
         },
         encoding: config::meta::search::RequestEncoding::Empty,
         regions: vec![],
@@ -799,9 +799,15 @@ async fn process_node(
                         }
                     };
                 }
-                if let Err(e) =
-                    crate::service::logs::ingest::handle_timestamp(&mut record, min_ts, max_ts)
-                {
+                // remote destinations don't have local stream settings
+                if let Err(e) = crate::service::logs::ingest::handle_timestamp(
+                    &mut record,
+                    min_ts,
+                    max_ts,
+                    None,
+                    &org_id,
+                    &remote_stream.destination_name,
+                ) {
                     let err_msg = format!("DestinationNode error handling timestamp: {}", e);
                     if let Err(send_err) = error_sender
                         .send((node.id.to_string(), node.node_type(), err_msg))
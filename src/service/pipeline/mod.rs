@@ -14,7 +14,10 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use config::meta::{
-    pipeline::{Pipeline, PipelineList, components::PipelineSource},
+    pipeline::{
+        Pipeline, PipelineList,
+        components::{NodeData, PipelineSource},
+    },
     search::SearchEventType,
     stream::ListStreamParams,
 };
@@ -74,6 +77,8 @@ pub async fn update_pipeline(mut pipeline: Pipeline) -> Result<(), PipelineError
     let Ok(existing_pipeline) = pipeline::get_by_id(&pipeline.id).await else {
         return Err(PipelineError::NotFound(pipeline.id));
     };
+    // the creator is kept across updates
+    pipeline.created_by = existing_pipeline.created_by.clone();
 
     if existing_pipeline == pipeline {
         return Ok(());
@@ -210,6 +215,22 @@ pub async fn delete_pipeline(pipeline_id: &str) -> Result<(), PipelineError> {
         return Err(PipelineError::NotFound(pipeline_id.to_string()));
     };
 
+    // keep the retention its source asks for on the streams written by the pipeline
+    let destinations = existing_pipeline
+        .nodes
+        .iter()
+        .filter_map(|node| match &node.data {
+            NodeData::Stream(params) => Some(params.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    super::compact::retention::pin_source_retention(
+        &existing_pipeline.org,
+        &existing_pipeline.get_source_stream_params(),
+        &destinations,
+    )
+    .await;
+
     // delete DerivedStream details if there's any
     if let PipelineSource::Scheduled(derived_stream) = existing_pipeline.source {
         if let Err(error) = super::alerts::derived_streams::delete(
@@ -13,7 +13,12 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::{collections::HashSet, str::FromStr, sync::Arc, time::Duration};
+use std::{
+    collections::HashSet,
+    str::FromStr,
+    sync::{Arc, atomic::Ordering},
+    time::Duration,
+};
 
 use arrow::array::Array;
 use async_recursion::async_recursion;
@@ -33,6 +38,7 @@ use datafusion::{
 };
 use futures::{TryStreamExt, future::try_join_all};
 use hashbrown::HashMap;
+use infra::errors::grpc::SAMPLES_LIMIT_EXCEEDED;
 use promql_parser::{
     label::MatchOp,
     parser::{
@@ -48,7 +54,8 @@ use super::{
     utils::{apply_label_selector, apply_matchers},
 };
 use crate::service::promql::{
-    DEFAULT_MAX_SERIES_PER_QUERY, aggregations, binaries, functions, micros, value::*,
+    DEFAULT_MAX_SAMPLES_PER_QUERY, DEFAULT_MAX_SERIES_PER_QUERY, aggregations, binaries, functions,
+    micros, sharding::SeriesShard, value::*,
 };
 
 pub struct Engine {
@@ -404,8 +411,10 @@ impl Engine {
                 None
             };
             if let Some(sample) = match_sample {
+                // a staleness marker ends the series before the lookback does
                 if sample.timestamp + offset_modifier <= eval_ts
                     && sample.timestamp + offset_modifier > start
+                    && !sample.is_stale()
                 {
                     let last_value = sample.value;
                     values.push(
@@ -495,8 +504,10 @@ impl Engine {
             let end_index = metric
                 .samples
                 .partition_point(|v| v.timestamp + offset_modifier <= eval_ts);
+            // staleness markers are not part of range vectors, cf. prometheus
             let samples = metric.samples[start_index..end_index]
                 .iter()
+                .filter(|v| !v.is_stale())
                 .map(|v| Sample {
                     timestamp: v.timestamp + offset_modifier,
                     value: v.value,
@@ -542,6 +553,26 @@ impl Engine {
             }
         };
 
+        // stop queries loading more samples than allowed before they exhaust the memory
+        let cfg = config::get_config();
+        let max_samples = if cfg.limit.metrics_max_samples_per_query > 0 {
+            cfg.limit.metrics_max_samples_per_query
+        } else {
+            DEFAULT_MAX_SAMPLES_PER_QUERY
+        };
+        let samples = metrics.values().map(|m| m.samples.len()).sum::<usize>();
+        let samples_loaded = self
+            .ctx
+            .samples_loaded
+            .fetch_add(samples, Ordering::Relaxed)
+            + samples;
+        if samples_loaded > max_samples {
+            data_loaded.insert(data_cache_key);
+            return Err(DataFusionError::ResourcesExhausted(format!(
+                "{SAMPLES_LIMIT_EXCEEDED} into memory, loaded {samples_loaded} samples, the limit is {max_samples}, you can change the limit by ZO_METRICS_MAX_SAMPLES_PER_QUERY"
+            )));
+        }
+
         // no data, return immediately
         if metrics.is_empty() {
             self.ctx
@@ -644,6 +675,7 @@ impl Engine {
             let selector = selector.clone();
             let col_filters = &self.col_filters;
             let query_exemplars = self.ctx.query_exemplars;
+            let shard = self.ctx.shard;
             let trace_id = self.trace_id.to_string();
             let task = tokio::time::timeout(Duration::from_secs(self.ctx.timeout), async move {
                 selector_load_data_from_datafusion(
@@ -655,6 +687,7 @@ impl Engine {
                     end,
                     col_filters,
                     query_exemplars,
+                    shard,
                 )
                 .await
             });
@@ -1122,6 +1155,7 @@ async fn selector_load_data_from_datafusion(
     end: i64,
     label_selector: &Option<HashSet<String>>,
     query_exemplars: bool,
+    shard: Option<SeriesShard>,
 ) -> Result<HashMap<HashLabelValue, RangeValue>> {
     let cfg = config::get_config();
     let table_name = selector.name.as_ref().unwrap();
@@ -1175,6 +1209,14 @@ async fn selector_load_data_from_datafusion(
         DEFAULT_MAX_SERIES_PER_QUERY
     };
 
+    // keep only the series of the shard, numeric hashes are filtered by datafusion
+    let hash_field_type = schema.field_with_name(HASH_LABEL).unwrap().data_type();
+    if let Some(shard) = shard
+        && hash_field_type == &DataType::UInt64
+    {
+        df_group = df_group.filter((col(HASH_LABEL) % lit(shard.total)).eq(lit(shard.index)))?;
+    }
+
     // get hash & timestamp
     let start_time = std::time::Instant::now();
     let sub_batch = df_group
@@ -1188,7 +1230,6 @@ async fn selector_load_data_from_datafusion(
         .collect()
         .await?;
 
-    let hash_field_type = schema.field_with_name(HASH_LABEL).unwrap().data_type();
     let (mut timestamp_values, hash_value_set): (Vec<_>, HashSet<HashLabelValue>) =
         if hash_field_type == &DataType::UInt64 {
             sub_batch
@@ -1230,6 +1271,7 @@ async fn selector_load_data_from_datafusion(
                     ts.iter()
                         .zip(hash.iter())
                         .map(|(t, h)| (t.unwrap_or_default(), h.unwrap_or("").into()))
+                        .filter(|(_, h)| shard.is_none_or(|shard| shard.contains(h)))
                 })
                 .unzip()
         };
@@ -1337,6 +1379,18 @@ async fn selector_load_data_from_datafusion(
     if query_exemplars {
         load_exemplars_from_datafusion(trace_id, hash_field_type, &mut metrics, df_group).await?;
     } else {
+        // rows carrying only exemplars have no value, don't mistake them for staleness markers
+        if df_group
+            .schema()
+            .field_with_name(None, EXEMPLARS_LABEL)
+            .is_ok()
+        {
+            df_group = df_group.filter(
+                col(VALUE_LABEL)
+                    .is_not_null()
+                    .or(col(EXEMPLARS_LABEL).is_null()),
+            )?;
+        }
         load_samples_from_datafusion(trace_id, hash_field_type, &mut metrics, df_group).await?;
     }
 
@@ -1396,9 +1450,10 @@ async fn load_samples_from_datafusion(
                                 for i in 0..batch.num_rows() {
                                     let hash: HashLabelValue = hash_values.value(i).into();
                                     if let Some(range_val) = series.get_mut(&hash) {
-                                        range_val.samples.push(Sample::new(
-                                            time_values.value(i),
-                                            value_values.value(i),
+                                        range_val.samples.push(load_sample(
+                                            time_values,
+                                            value_values,
+                                            i,
                                         ));
                                     }
                                 }
@@ -1412,9 +1467,10 @@ async fn load_samples_from_datafusion(
                                 for i in 0..batch.num_rows() {
                                     let hash: HashLabelValue = hash_values.value(i).into();
                                     if let Some(range_val) = series.get_mut(&hash) {
-                                        range_val.samples.push(Sample::new(
-                                            time_values.value(i),
-                                            value_values.value(i),
+                                        range_val.samples.push(load_sample(
+                                            time_values,
+                                            value_values,
+                                            i,
                                         ));
                                     }
                                 }
@@ -1452,6 +1508,15 @@ async fn load_samples_from_datafusion(
     Ok(())
 }
 
+/// Staleness markers are stored as null values.
+fn load_sample(time_values: &Int64Array, value_values: &Float64Array, i: usize) -> Sample {
+    if value_values.is_null(i) {
+        Sample::stale(time_values.value(i))
+    } else {
+        Sample::new(time_values.value(i), value_values.value(i))
+    }
+}
+
 async fn load_exemplars_from_datafusion(
     trace_id: &str,
     hash_field_type: &DataType,
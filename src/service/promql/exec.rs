@@ -14,7 +14,7 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::{
-    sync::Arc,
+    sync::{Arc, atomic::AtomicUsize},
     time::{Duration, SystemTime},
 };
 
@@ -27,7 +27,7 @@ use tokio::sync::{Mutex, RwLock, Semaphore};
 use super::Engine;
 use crate::service::promql::{
     DEFAULT_LOOKBACK, TableProvider, micros, micros_since_epoch,
-    selector_visitor::MetricSelectorVisitor, value::*,
+    selector_visitor::MetricSelectorVisitor, sharding::SeriesShard, value::*,
 };
 
 #[derive(Clone)]
@@ -48,6 +48,10 @@ pub struct PromqlContext {
     pub scan_stats: Arc<RwLock<ScanStats>>,
     pub timeout: u64, // seconds, query timeout
     pub data_loading: Arc<Mutex<HashSet<String>>>,
+    /// Number of samples loaded by the selectors of the query.
+    pub samples_loaded: Arc<AtomicUsize>,
+    /// Slice of the series to evaluate the query on, all the series if not set.
+    pub shard: Option<SeriesShard>,
 }
 
 impl PromqlContext {
@@ -68,6 +72,8 @@ impl PromqlContext {
             data_cache: Arc::new(RwLock::new(HashMap::default())),
             data_loading: Arc::new(Mutex::new(HashSet::default())),
             scan_stats: Arc::new(RwLock::new(ScanStats::default())),
+            samples_loaded: Arc::new(AtomicUsize::new(0)),
+            shard: None,
             timeout,
         }
     }
@@ -269,10 +275,20 @@ impl PromqlContext {
         let mut merged_data = HashMap::new();
         let mut merged_metrics = HashMap::new();
         for value in instant_vectors {
+            // selectors load data with lookback, keep only exemplars of the requested range
+            let exemplars = value
+                .exemplars
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|v| v.timestamp >= self.start && v.timestamp <= self.end)
+                .collect::<Vec<_>>();
+            if exemplars.is_empty() {
+                continue;
+            }
             merged_data
                 .entry(signature(&value.labels))
                 .or_insert_with(Vec::new)
-                .extend(value.exemplars.unwrap_or_default());
+                .extend(exemplars);
             merged_metrics.insert(signature(&value.labels), value.labels);
         }
         let merged_data = merged_data
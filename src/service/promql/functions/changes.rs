@@ -23,6 +23,10 @@ pub(crate) fn changes(data: Value) -> Result<Value> {
 }
 
 fn exec(data: RangeValue) -> Option<f64> {
+    // a series without samples in the range, e.g. after a staleness marker, has no result
+    if data.samples.is_empty() {
+        return None;
+    }
     let changes = data
         .samples
         .iter()
@@ -23,6 +23,10 @@ pub(crate) fn resets(data: Value) -> Result<Value> {
 }
 
 fn exec(data: RangeValue) -> Option<f64> {
+    // a series without samples in the range, e.g. after a staleness marker, has no result
+    if data.samples.is_empty() {
+        return None;
+    }
     let resets = data
         .samples
         .iter()
@@ -31,3 +35,28 @@ fn exec(data: RangeValue) -> Option<f64> {
         .sum();
     Some(resets)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::promql::value::Sample;
+
+    #[test]
+    fn test_resets() {
+        let series = |values: &[f64]| RangeValue {
+            samples: values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| Sample::new(i as i64 * 15_000_000, *v))
+                .collect(),
+            ..Default::default()
+        };
+        // the target restarted twice
+        assert_eq!(
+            exec(series(&[1.0, 5.0, 2.0, 4.0, 4.0, 0.0, 3.0])),
+            Some(2.0)
+        );
+        assert_eq!(exec(series(&[1.0])), Some(0.0));
+        assert_eq!(exec(series(&[])), None);
+    }
+}
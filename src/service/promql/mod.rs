@@ -35,6 +35,7 @@ mod functions;
 pub mod name_visitor;
 pub mod search;
 pub mod selector_visitor;
+mod sharding;
 mod utils;
 pub mod value;
 
@@ -46,6 +47,7 @@ pub(crate) const MINIMAL_INTERVAL: Duration = Duration::from_secs(1); // 1s
 pub(crate) const MAX_DATA_POINTS: i64 = 256; // Width of panel: window.innerWidth / 4
 pub(crate) const DEFAULT_MAX_POINTS_PER_SERIES: usize = 30000; // Maximum number of points per series
 const DEFAULT_MAX_SERIES_PER_QUERY: usize = 30000; // Maximum number of series in a single query
+const DEFAULT_MAX_SAMPLES_PER_QUERY: usize = 50_000_000; // Maximum number of samples loaded by a query
 const DEFAULT_STEP: Duration = Duration::from_secs(15); // default step in seconds
 const MIN_TIMESERIES_POINTS_FOR_TIME_ROUNDING: i64 = 10; // Adjust this value as needed
 
@@ -116,6 +118,14 @@ impl<T: Serialize> ApiFuncResponse<T> {
         }
     }
 
+    pub(crate) fn err_exec(error: impl ToString, trace_id: Option<String>) -> Self {
+        ApiFuncResponse::Error {
+            error_type: ApiErrorType::Exec,
+            error: error.to_string(),
+            trace_id,
+        }
+    }
+
     pub(crate) fn err_internal(error: impl ToString, trace_id: Option<String>) -> Self {
         ApiFuncResponse::Error {
             error_type: ApiErrorType::Internal,
@@ -170,6 +180,18 @@ pub fn round_step(mut step: i64) -> i64 {
     }
 }
 
+/// Widens `step` so that a range query over `[start, end]` returns at most `max_points` points
+/// per series. The widened step is rounded up to whole seconds.
+pub fn clamp_step(start: i64, end: i64, step: i64, max_points: usize) -> i64 {
+    let max_points = max_points.max(1) as i64;
+    if step <= 0 || (end - start) / step <= max_points {
+        return step;
+    }
+    let second = micros(Duration::from_secs(1));
+    let min_step = (end - start + max_points - 1) / max_points;
+    (min_step + second - 1) / second * second
+}
+
 pub fn align_start_end(mut start: i64, mut end: i64, step: i64) -> (i64, i64) {
     // Round start to the nearest smaller value divisible by step.
     start -= start % step;
@@ -214,6 +236,21 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_clamp_step() {
+        let second = 1_000_000;
+        let day = 86_400 * second;
+        // within the limit, the step is kept
+        assert_eq!(clamp_step(0, day, 15 * second, 30_000), 15 * second);
+        // a year at 15s step is widened to fit the limit
+        let step = clamp_step(0, 365 * day, 15 * second, 30_000);
+        assert_eq!(step, 1052 * second);
+        assert!(365 * day / step <= 30_000);
+        assert!(365 * day / (step - second) > 30_000);
+        // instant queries are left alone
+        assert_eq!(clamp_step(0, 0, 0, 30_000), 0);
+    }
+
     #[test]
     fn test_api_func_response_serialize() {
         let ok = ApiFuncResponse::ok("hello".to_owned(), None);
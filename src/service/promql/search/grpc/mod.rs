@@ -35,7 +35,9 @@ use rayon::slice::ParallelSliceMut;
 
 use super::Value;
 use crate::service::{
-    promql::{DEFAULT_LOOKBACK, PromqlContext, TableProvider, name_visitor, value},
+    promql::{
+        DEFAULT_LOOKBACK, PromqlContext, TableProvider, name_visitor, sharding::SeriesShard, value,
+    },
     search,
 };
 
@@ -221,6 +223,7 @@ pub async fn search_inner(
         query.query_exemplars,
         timeout,
     );
+    ctx.shard = SeriesShard::from_request(req);
 
     let (value, result_type, mut scan_stats) = if query.query_exemplars {
         ctx.query_exemplars(&trace_id, eval_stmt).await?
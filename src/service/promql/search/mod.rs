@@ -34,6 +34,7 @@ use config::{
 use futures::future::try_join_all;
 use hashbrown::HashMap;
 use infra::errors::{Error, ErrorCodes, Result};
+use promql_parser::parser;
 use proto::cluster_rpc;
 use tracing::{Instrument, info_span};
 
@@ -43,7 +44,9 @@ use crate::{
         grpc::make_grpc_metrics_client,
         promql::{
             DEFAULT_LOOKBACK, DEFAULT_MAX_POINTS_PER_SERIES, MetricsQueryRequest, adjust_start_end,
-            micros, value::*,
+            clamp_step, micros,
+            sharding::{merge_shards, shard_merge},
+            value::*,
         },
         search::server_internal_error,
         self_reporting::report_request_usage_stats,
@@ -80,13 +83,32 @@ pub async fn search(
 #[tracing::instrument(name = "promql:search:cluster", skip_all, fields(org_id = req.org_id))]
 async fn search_in_cluster(
     trace_id: &str,
-    req: cluster_rpc::MetricsQueryRequest,
+    mut req: cluster_rpc::MetricsQueryRequest,
     user_email: &str,
 ) -> Result<Value> {
     let op_start = std::time::Instant::now();
     let started_at = now_micros();
     let cfg = get_config();
 
+    // widen the step of range queries that would return too many points per series, e.g. a
+    // year at 15s step, instead of evaluating every step
+    let max_points = if cfg.limit.metrics_max_points_per_series > 0 {
+        cfg.limit.metrics_max_points_per_series
+    } else {
+        DEFAULT_MAX_POINTS_PER_SERIES
+    };
+    if let Some(stmt) = req.query.as_mut() {
+        let step = clamp_step(stmt.start, stmt.end, stmt.step, max_points);
+        if step != stmt.step {
+            log::info!(
+                "[trace_id {trace_id}] promql->search->step: clamped step from {} to {} to return at most {max_points} points per series",
+                stmt.step,
+                step,
+            );
+            stmt.step = step;
+        }
+    }
+
     let &cluster_rpc::MetricsQueryStmt {
         ref query,
         start,
@@ -175,17 +197,6 @@ async fn search_in_cluster(
         return Ok(values);
     }
 
-    let max_points = if cfg.limit.metrics_max_points_per_series > 0 {
-        cfg.limit.metrics_max_points_per_series
-    } else {
-        DEFAULT_MAX_POINTS_PER_SERIES
-    };
-    if (end - start) / step > max_points as i64 {
-        return Err(Error::ErrorCode(ErrorCodes::InvalidParams(
-            "too many points per series must be returned on the given, you can change the limit by ZO_METRICS_MAX_POINTS_PER_SERIES".to_string(),
-        )));
-    }
-
     // A span of time covered by an individual querier (worker).
     let worker_dt = if nr_steps > nr_queriers {
         partition_step * ((nr_steps + nr_queriers - 1) / nr_queriers)
@@ -193,6 +204,15 @@ async fn search_in_cluster(
         partition_step
     };
 
+    // aggregations of series computed one by one, like sum by (job) (rate(x[5m])), are split
+    // across the queriers by series and their partial aggregates merged, other queries by time
+    let sharding =
+        if cfg.limit.metrics_query_sharding_enabled && nr_queriers > 1 && !query_exemplars {
+            parser::parse(query).ok().as_ref().and_then(shard_merge)
+        } else {
+            None
+        };
+
     let job = cluster_rpc::Job {
         trace_id: trace_id.to_string(),
         job: generate_random_string(7),
@@ -203,19 +223,31 @@ async fn search_in_cluster(
     // make cluster request
     let mut tasks = Vec::new();
     let mut worker_start = start;
-    for node in nodes.iter() {
+    for (shard, node) in nodes.iter().enumerate() {
         let node = node.clone();
-        if worker_start > end {
-            break;
-        }
+        let (req_start, req_end, shards) = if sharding.is_some() {
+            (start, end, nr_queriers as u32)
+        } else {
+            if worker_start > end {
+                break;
+            }
+            let range = (worker_start, min(end, worker_start + worker_dt));
+            worker_start += worker_dt;
+            (range.0, range.1, 0)
+        };
         let job = Some(cluster_rpc::Job {
             partition: node.id as _,
             ..job.clone()
         });
-        let mut req = cluster_rpc::MetricsQueryRequest { job, ..req.clone() };
+        let mut req = cluster_rpc::MetricsQueryRequest {
+            job,
+            shard: if shards > 0 { shard as u32 } else { 0 },
+            shards,
+            ..req.clone()
+        };
         let req_query = req.query.as_mut().unwrap();
-        req_query.start = worker_start;
-        req_query.end = min(end, worker_start + worker_dt);
+        req_query.start = req_start;
+        req_query.end = req_end;
         // if the end time is within the last 3 retention time, we need to fetch wal data
         if req_query.end
             >= now_micros() - second_micros(cfg.limit.max_file_retention_time as i64 * 3)
@@ -223,14 +255,15 @@ async fn search_in_cluster(
             req.need_wal = true;
         }
         let req_need_wal = req.need_wal;
-        worker_start += worker_dt;
 
         log::info!(
-            "[trace_id {trace_id}] promql->search->partition: node: {}, need_wal: {}, time_range: [{},{})",
+            "[trace_id {trace_id}] promql->search->partition: node: {}, need_wal: {}, time_range: [{},{}), shard: {}/{}",
             &node.grpc_addr,
             req_need_wal,
             req_query.start,
             req_query.end,
+            req.shard,
+            req.shards,
         );
 
         let trace_id = trace_id.to_string();
@@ -299,6 +332,9 @@ async fn search_in_cluster(
             series_data.push(series);
         });
     }
+    if let Some(merge) = sharding {
+        series_data = merge_shards(series_data, merge);
+    }
 
     // add cached values to series_data
     cached_values.into_iter().for_each(|series| {
@@ -0,0 +1,309 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Sharding of PromQL aggregations by series. Aggregations like `sum by (job) (rate(x[5m]))`
+//! aggregate values computed series by series, so each querier can evaluate the whole query on
+//! a slice of the series and the partial aggregates are merged, which spreads the load of
+//! queries over many series on all the queriers instead of splitting them only by time.
+
+use std::sync::Arc;
+
+use config::{
+    meta::promql::HashLabelValue,
+    utils::hash::{Sum64, fnv},
+};
+use hashbrown::HashMap;
+use promql_parser::parser::{
+    AggregateExpr, BinaryExpr, Call, Expr as PromExpr, ParenExpr, SubqueryExpr, UnaryExpr, token,
+};
+use proto::cluster_rpc;
+
+use crate::service::promql::value::{Label, Labels, signature};
+
+/// Functions computing each output series from a single input series.
+const SERIES_FUNCTIONS: [&str; 41] = [
+    "abs",
+    "avg_over_time",
+    "ceil",
+    "changes",
+    "clamp",
+    "clamp_max",
+    "clamp_min",
+    "count_over_time",
+    "day_of_month",
+    "day_of_week",
+    "day_of_year",
+    "days_in_month",
+    "delta",
+    "deriv",
+    "exp",
+    "floor",
+    "holt_winters",
+    "hour",
+    "idelta",
+    "increase",
+    "irate",
+    "label_join",
+    "label_replace",
+    "last_over_time",
+    "ln",
+    "log10",
+    "log2",
+    "max_over_time",
+    "min_over_time",
+    "minute",
+    "month",
+    "predict_linear",
+    "quantile_over_time",
+    "rate",
+    "resets",
+    "round",
+    "sqrt",
+    "stddev_over_time",
+    "stdvar_over_time",
+    "sum_over_time",
+    "year",
+];
+
+/// The series a querier evaluates: the series whose hash modulo `total` is `index`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SeriesShard {
+    pub index: u64,
+    pub total: u64,
+}
+
+impl SeriesShard {
+    /// Returns the shard of a querier request, `None` if the query is not sharded.
+    pub fn from_request(req: &cluster_rpc::MetricsQueryRequest) -> Option<Self> {
+        (req.shards > 1 && req.shard < req.shards).then_some(Self {
+            index: req.shard as u64,
+            total: req.shards as u64,
+        })
+    }
+
+    pub fn contains(&self, hash: &HashLabelValue) -> bool {
+        let hash = match hash {
+            HashLabelValue::Number(hash) => *hash,
+            HashLabelValue::String(hash) => fnv::new().sum64(hash),
+        };
+        hash % self.total == self.index
+    }
+}
+
+/// How the partial results of the shards of an aggregation are merged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShardMerge {
+    Sum,
+    Min,
+    Max,
+}
+
+impl ShardMerge {
+    fn merge(self, a: f64, b: f64) -> f64 {
+        match self {
+            Self::Sum => a + b,
+            Self::Min => a.min(b),
+            Self::Max => a.max(b),
+        }
+    }
+}
+
+/// Returns how to merge the shards of the query if it can be sharded by series: the query must
+/// be an aggregation merging as the aggregation itself does, over an expression which computes
+/// each series from a single input series.
+pub fn shard_merge(expr: &PromExpr) -> Option<ShardMerge> {
+    match expr {
+        PromExpr::Paren(ParenExpr { expr }) => shard_merge(expr),
+        PromExpr::Aggregate(AggregateExpr {
+            op, expr, param, ..
+        }) if param.is_none() && is_series_wise(expr) => match op.id() {
+            token::T_SUM | token::T_COUNT => Some(ShardMerge::Sum),
+            token::T_MIN => Some(ShardMerge::Min),
+            token::T_MAX | token::T_GROUP => Some(ShardMerge::Max),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether every series of the expression result is computed from a single selected series.
+fn is_series_wise(expr: &PromExpr) -> bool {
+    match expr {
+        PromExpr::VectorSelector(_) | PromExpr::MatrixSelector(_) => true,
+        PromExpr::Paren(ParenExpr { expr })
+        | PromExpr::Unary(UnaryExpr { expr })
+        | PromExpr::Subquery(SubqueryExpr { expr, .. }) => is_series_wise(expr),
+        PromExpr::Binary(BinaryExpr { lhs, rhs, .. }) => {
+            (is_scalar(lhs) && is_series_wise(rhs)) || (is_series_wise(lhs) && is_scalar(rhs))
+        }
+        PromExpr::Call(Call { func, args }) => {
+            SERIES_FUNCTIONS.contains(&func.name)
+                && args.args.iter().any(|arg| is_series_wise(arg))
+                && args
+                    .args
+                    .iter()
+                    .all(|arg| is_series_wise(arg) || is_literal(arg))
+        }
+        _ => false,
+    }
+}
+
+fn is_scalar(expr: &PromExpr) -> bool {
+    match expr {
+        PromExpr::NumberLiteral(_) => true,
+        PromExpr::Paren(ParenExpr { expr }) | PromExpr::Unary(UnaryExpr { expr }) => {
+            is_scalar(expr)
+        }
+        _ => false,
+    }
+}
+
+fn is_literal(expr: &PromExpr) -> bool {
+    matches!(expr, PromExpr::StringLiteral(_)) || is_scalar(expr)
+}
+
+/// Merges the series the shards of an aggregation returned, the values of the same series at
+/// the same time are merged.
+pub fn merge_shards(
+    series: Vec<cluster_rpc::Series>,
+    merge: ShardMerge,
+) -> Vec<cluster_rpc::Series> {
+    let mut merged: HashMap<u64, cluster_rpc::Series> = HashMap::with_capacity(series.len());
+    for ser in series {
+        let labels: Labels = ser
+            .metric
+            .iter()
+            .map(|l| Arc::new(Label::from(l)))
+            .collect();
+        let sig = signature(&labels);
+        let Some(entry) = merged.get_mut(&sig) else {
+            merged.insert(sig, ser);
+            continue;
+        };
+        if let (Some(sample), Some(other)) = (entry.sample.as_mut(), ser.sample.as_ref()) {
+            sample.value = merge.merge(sample.value, other.value);
+        } else if entry.sample.is_none() {
+            entry.sample = ser.sample;
+        }
+        let mut samples = entry
+            .samples
+            .drain(..)
+            .map(|s| (s.time, s.value))
+            .collect::<HashMap<_, _>>();
+        for sample in ser.samples {
+            samples
+                .entry(sample.time)
+                .and_modify(|v| *v = merge.merge(*v, sample.value))
+                .or_insert(sample.value);
+        }
+        entry.samples = samples
+            .into_iter()
+            .map(|(time, value)| cluster_rpc::Sample { time, value })
+            .collect();
+        entry.samples.sort_by_key(|s| s.time);
+    }
+    merged.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use promql_parser::parser;
+
+    use super::*;
+
+    fn series(job: &str, samples: &[(i64, f64)]) -> cluster_rpc::Series {
+        cluster_rpc::Series {
+            metric: vec![cluster_rpc::Label {
+                name: "job".to_string(),
+                value: job.to_string(),
+            }],
+            samples: samples
+                .iter()
+                .map(|(time, value)| cluster_rpc::Sample {
+                    time: *time,
+                    value: *value,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_shard_merge() {
+        let cases = [
+            (
+                "sum by (job) (rate(http_requests[5m]))",
+                Some(ShardMerge::Sum),
+            ),
+            ("count(up == 1)", Some(ShardMerge::Sum)),
+            (
+                "(max without (pod) (clamp_min(mem_bytes, 0) / 1024))",
+                Some(ShardMerge::Max),
+            ),
+            (
+                "min(max_over_time(rate(x[5m])[1h:1m]))",
+                Some(ShardMerge::Min),
+            ),
+            (
+                "sum(label_replace(up, \"host\", \"$1\", \"instance\", \"(.*):.*\"))",
+                Some(ShardMerge::Sum),
+            ),
+            ("avg(up)", None),
+            ("topk(5, up)", None),
+            ("sum(rate(x[5m])) / sum(rate(y[5m]))", None),
+            ("sum(x / on(job) y)", None),
+            ("sum(histogram_quantile(0.9, rate(x_bucket[5m])))", None),
+            ("sum(sum by (job) (up))", None),
+            ("rate(x[5m])", None),
+        ];
+        for (query, expected) in cases {
+            let expr = parser::parse(query).unwrap();
+            assert_eq!(shard_merge(&expr), expected, "{query}");
+        }
+    }
+
+    #[test]
+    fn test_series_shard() {
+        let shards = (0..3)
+            .map(|index| SeriesShard { index, total: 3 })
+            .collect::<Vec<_>>();
+        for hash in [
+            HashLabelValue::Number(42),
+            HashLabelValue::String("a1b2".to_string()),
+        ] {
+            assert_eq!(shards.iter().filter(|s| s.contains(&hash)).count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_merge_shards() {
+        let merged = merge_shards(
+            vec![
+                series("api", &[(1, 1.0), (2, 2.0)]),
+                series("db", &[(1, 5.0)]),
+                series("api", &[(2, 3.0), (3, 4.0)]),
+            ],
+            ShardMerge::Sum,
+        );
+        let api = merged.iter().find(|s| s.metric[0].value == "api").unwrap();
+        let samples = api
+            .samples
+            .iter()
+            .map(|s| (s.time, s.value))
+            .collect::<Vec<_>>();
+        assert_eq!(samples, vec![(1, 1.0), (2, 5.0), (3, 4.0)]);
+        assert_eq!(merged.len(), 2);
+    }
+}
@@ -17,6 +17,7 @@ use std::{fmt, hash::Hasher, sync::Arc, time::Duration};
 
 use config::{
     FxIndexMap,
+    meta::promql::{STALE_NAN_BITS, is_stale_nan},
     utils::{json, sort::sort_float},
 };
 use hashbrown::HashSet;
@@ -215,6 +216,15 @@ impl Sample {
         Self { timestamp, value }
     }
 
+    /// A staleness marker, the series has no value from `timestamp` on.
+    pub(crate) fn stale(timestamp: i64) -> Self {
+        Self::new(timestamp, f64::from_bits(STALE_NAN_BITS))
+    }
+
+    pub(crate) fn is_stale(&self) -> bool {
+        is_stale_nan(self.value)
+    }
+
     pub(crate) fn is_nan(&self) -> bool {
         self.value.is_nan()
     }
@@ -239,7 +249,8 @@ impl Serialize for Exemplar {
             .iter()
             .map(|l| (l.name.as_str(), l.value.as_str()))
             .collect::<FxIndexMap<_, _>>();
-        seq.serialize_field("timestamp", &(self.timestamp / 1_000_000))?;
+        // prometheus reports exemplar timestamps as float seconds
+        seq.serialize_field("timestamp", &(self.timestamp as f64 / 1_000_000.0))?;
         seq.serialize_field("value", &self.value.to_string())?;
         seq.serialize_field("labels", &labels_map)?;
         seq.end()
@@ -271,8 +282,8 @@ impl<'de> Deserialize<'de> for Exemplar {
                 while let Some(key) = map.next_key::<String>()? {
                     match key.as_str() {
                         "timestamp" => {
-                            let ts = map.next_value::<i64>()?;
-                            timestamp = Some(ts * 1_000_000); // Convert seconds to microseconds
+                            let ts = map.next_value::<f64>()?;
+                            timestamp = Some((ts * 1_000_000.0).round() as i64); // Convert seconds to microseconds
                         }
                         "value" => {
                             let val_str = map.next_value::<String>()?;
@@ -907,6 +918,29 @@ mod tests {
         assert!(approx_eq!(f64, delta, 4.0));
     }
 
+    #[test]
+    fn test_stale_sample() {
+        let stale = Sample::stale(15_000_000);
+        assert!(stale.is_stale());
+        assert!(stale.is_nan());
+        assert!(!Sample::new(15_000_000, f64::NAN).is_stale());
+        assert!(!Sample::new(15_000_000, 0.0).is_stale());
+    }
+
+    #[test]
+    fn test_exemplar_timestamp_seconds() {
+        let exemplar = Exemplar {
+            timestamp: 1_600_096_945_479_000,
+            value: 19.0,
+            labels: vec![Arc::new(Label::new("trace_id", "abc"))],
+        };
+        let data = json::to_value(&exemplar).unwrap();
+        assert_eq!(data["timestamp"], json::json!(1_600_096_945.479));
+        assert_eq!(data["value"], "19");
+        let exemplar: Exemplar = json::from_value(data).unwrap();
+        assert_eq!(exemplar.timestamp, 1_600_096_945_479_000);
+    }
+
     #[test]
     fn test_invalid_label_name() {
         assert!(!Label::is_valid_label_name("~invalid-label-name"));
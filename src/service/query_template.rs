@@ -0,0 +1,393 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use chrono::Utc;
+use config::{
+    ider,
+    meta::{
+        query_template::{
+            ParamType, QueryTemplate, QueryTemplateClaims, QueryTemplateExecuteRequest,
+            QueryTemplateExecuteResponse, QueryTemplateParam, QueryTemplateTokenRequest,
+            QueryTemplateTokenResponse,
+        },
+        search::{self, SearchEventType},
+    },
+    utils::json::Value,
+};
+use hashbrown::HashMap;
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::service::{db, search as SearchService};
+
+/// Longest lifetime of a template token, 30 days.
+const MAX_TOKEN_TTL: i64 = 30 * 24 * 3600;
+
+static RE_PARAM: Lazy<Regex> = Lazy::new(|| Regex::new(r"\$([A-Za-z][A-Za-z0-9_]*)").unwrap());
+
+#[derive(Debug, thiserror::Error)]
+pub enum QueryTemplateError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Invalid query template name: {0}")]
+    InvalidName(String),
+
+    #[error("Query template not found")]
+    NotFound,
+
+    #[error("Invalid query template: {0}")]
+    InvalidTemplate(String),
+
+    #[error("Invalid token: {0}")]
+    InvalidToken(String),
+
+    #[error("Invalid params: {0}")]
+    InvalidParams(String),
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<QueryTemplate>, QueryTemplateError> {
+    let mut list = db::query_template::list(org_id).await?;
+    for template in list.iter_mut() {
+        template.secret.clear();
+    }
+    Ok(list)
+}
+
+pub async fn get(org_id: &str, name: &str) -> Result<QueryTemplate, QueryTemplateError> {
+    let mut template = db::query_template::get(org_id, name)
+        .await
+        .map_err(|_| QueryTemplateError::NotFound)?;
+    template.secret.clear();
+    Ok(template)
+}
+
+/// Validates and stores a template, the signing key of an existing template is kept so its
+/// tokens stay valid.
+pub async fn save(
+    org_id: &str,
+    name: &str,
+    mut template: QueryTemplate,
+) -> Result<QueryTemplate, QueryTemplateError> {
+    if !is_valid_name(name) {
+        return Err(QueryTemplateError::InvalidName(name.to_string()));
+    }
+    validate(&template).map_err(QueryTemplateError::InvalidTemplate)?;
+
+    let now = Utc::now().timestamp_micros();
+    match db::query_template::get(org_id, name).await {
+        Ok(existing) => {
+            template.secret = existing.secret;
+            template.created_at = existing.created_at;
+        }
+        Err(_) => {
+            template.secret = hex::encode(rand::random::<[u8; 32]>());
+            template.created_at = now;
+        }
+    }
+    template.name = name.to_string();
+    template.updated_at = now;
+    db::query_template::set(org_id, &template).await?;
+    template.secret.clear();
+    Ok(template)
+}
+
+pub async fn delete(org_id: &str, name: &str) -> Result<(), QueryTemplateError> {
+    if db::query_template::get(org_id, name).await.is_err() {
+        return Err(QueryTemplateError::NotFound);
+    }
+    Ok(db::query_template::delete(org_id, name).await?)
+}
+
+fn validate(template: &QueryTemplate) -> Result<(), String> {
+    if template.sql.trim().is_empty() {
+        return Err("sql is empty".to_string());
+    }
+    if template.max_rows <= 0 || template.max_time_range_hours < 0 {
+        return Err("max_rows must be positive and max_time_range_hours not negative".to_string());
+    }
+    for param in template.params.iter() {
+        if !QueryTemplateParam::is_valid_name(&param.name) {
+            return Err(format!("invalid param name: {}", param.name));
+        }
+        if let Some(default) = param.default.as_ref() {
+            check_value(param, default)?;
+        }
+    }
+    for cap in RE_PARAM.captures_iter(&template.sql) {
+        if !template.params.iter().any(|p| p.name == cap[1]) {
+            return Err(format!("param ${} is not declared", &cap[1]));
+        }
+    }
+    Ok(())
+}
+
+fn check_value(param: &QueryTemplateParam, value: &Value) -> Result<(), String> {
+    let type_ok = match param.param_type {
+        ParamType::String => value.is_string(),
+        ParamType::Number => value.is_number(),
+    };
+    if !type_ok || !param.range.allows(value) {
+        return Err(format!(
+            "value {value} is not allowed for param {}",
+            param.name
+        ));
+    }
+    Ok(())
+}
+
+/// Issues a signed token to run the template, restricted to the given parameter values. The
+/// template runs as `user_id` when executed with the token.
+pub async fn create_token(
+    org_id: &str,
+    name: &str,
+    user_id: &str,
+    req: QueryTemplateTokenRequest,
+) -> Result<QueryTemplateTokenResponse, QueryTemplateError> {
+    let template = db::query_template::get(org_id, name)
+        .await
+        .map_err(|_| QueryTemplateError::NotFound)?;
+    if let Some(param) = req
+        .params
+        .keys()
+        .find(|k| !template.params.iter().any(|p| &p.name == *k))
+    {
+        return Err(QueryTemplateError::InvalidParams(format!(
+            "param {param} is not declared by the template"
+        )));
+    }
+    if req.expires_in <= 0 || req.expires_in > MAX_TOKEN_TTL {
+        return Err(QueryTemplateError::InvalidParams(format!(
+            "expires_in must be between 1 and {MAX_TOKEN_TTL} seconds"
+        )));
+    }
+
+    let now = Utc::now().timestamp();
+    let claims = QueryTemplateClaims {
+        org: org_id.to_string(),
+        template: name.to_string(),
+        created_by: user_id.to_string(),
+        params: req.params,
+        iat: now,
+        exp: now + req.expires_in,
+    };
+    let token = encode(
+        &Header::new(Algorithm::HS256),
+        &claims,
+        &EncodingKey::from_secret(template.secret.as_bytes()),
+    )
+    .map_err(|e| QueryTemplateError::InvalidToken(e.to_string()))?;
+    Ok(QueryTemplateTokenResponse {
+        token,
+        expires_at: claims.exp,
+    })
+}
+
+/// Runs the template with the parameters of the request, after checking them against the
+/// template and the token restrictions. The query runs as the user who issued the token, so
+/// their sql policies, row policies and column masks apply.
+pub async fn execute(
+    org_id: &str,
+    name: &str,
+    token: &str,
+    req: QueryTemplateExecuteRequest,
+) -> Result<QueryTemplateExecuteResponse, QueryTemplateError> {
+    let template = db::query_template::get(org_id, name)
+        .await
+        .map_err(|_| QueryTemplateError::NotFound)?;
+    let claims = decode::<QueryTemplateClaims>(
+        token,
+        &DecodingKey::from_secret(template.secret.as_bytes()),
+        &Validation::new(Algorithm::HS256),
+    )
+    .map_err(|e| QueryTemplateError::InvalidToken(e.to_string()))?
+    .claims;
+    if claims.org != org_id || claims.template != name {
+        return Err(QueryTemplateError::InvalidToken(
+            "token is not valid for this template".to_string(),
+        ));
+    }
+    match db::user::get(Some(org_id), &claims.created_by).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Err(QueryTemplateError::InvalidToken(
+                "the user who issued the token no longer exists".to_string(),
+            ));
+        }
+        Err(e) => return Err(infra::errors::Error::Message(e.to_string()).into()),
+    }
+
+    if req.start_time >= req.end_time {
+        return Err(QueryTemplateError::InvalidParams(
+            "start_time must be before end_time".to_string(),
+        ));
+    }
+    if template.max_time_range_hours > 0
+        && req.end_time - req.start_time > template.max_time_range_hours * 3600 * 1_000_000
+    {
+        return Err(QueryTemplateError::InvalidParams(format!(
+            "time range exceeds the maximum of {} hours",
+            template.max_time_range_hours
+        )));
+    }
+
+    let values =
+        bind_params(&template, &claims, &req.params).map_err(QueryTemplateError::InvalidParams)?;
+    let size = req
+        .size
+        .filter(|s| *s > 0)
+        .map_or(template.max_rows, |s| s.min(template.max_rows));
+    let search_req = search::Request {
+        query: search::Query {
+            sql: render(&template.sql, &values),
+            from: 0,
+            size,
+            start_time: req.start_time,
+            end_time: req.end_time,
+            ..Default::default()
+        },
+        search_type: Some(SearchEventType::Other),
+        ..Default::default()
+    };
+    let trace_id = ider::generate_trace_id();
+    let resp = SearchService::search(
+        &trace_id,
+        org_id,
+        template.stream_type,
+        Some(claims.created_by),
+        &search_req,
+    )
+    .await?;
+    Ok(QueryTemplateExecuteResponse {
+        took: resp.took,
+        total: resp.total,
+        hits: resp.hits,
+    })
+}
+
+/// Resolves the value of each template param, from the request or the default, and checks it
+/// against the template and token restrictions.
+fn bind_params(
+    template: &QueryTemplate,
+    claims: &QueryTemplateClaims,
+    params: &HashMap<String, Value>,
+) -> Result<HashMap<String, Value>, String> {
+    if let Some(param) = params
+        .keys()
+        .find(|k| !template.params.iter().any(|p| &p.name == *k))
+    {
+        return Err(format!("param {param} is not declared by the template"));
+    }
+    let mut values = HashMap::with_capacity(template.params.len());
+    for param in template.params.iter() {
+        let Some(value) = params.get(&param.name).or(param.default.as_ref()) else {
+            return Err(format!("param {} is required", param.name));
+        };
+        check_value(param, value)?;
+        if claims
+            .params
+            .get(&param.name)
+            .is_some_and(|range| !range.allows(value))
+        {
+            return Err(format!(
+                "value {value} is not allowed for param {} by the token",
+                param.name
+            ));
+        }
+        values.insert(param.name.clone(), value.clone());
+    }
+    Ok(values)
+}
+
+/// Replaces the `$name` params of the sql with their values as sql literals.
+fn render(sql: &str, values: &HashMap<String, Value>) -> String {
+    RE_PARAM
+        .replace_all(sql, |cap: &regex::Captures| match values.get(&cap[1]) {
+            Some(Value::String(s)) => format!("'{}'", s.replace('\'', "''")),
+            Some(v) => v.to_string(),
+            None => cap[0].to_string(),
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use config::{
+        meta::query_template::ParamRange,
+        utils::json::{self, json},
+    };
+
+    use super::*;
+
+    fn template() -> QueryTemplate {
+        json::from_value(json!({
+            "sql": "SELECT COUNT(*) FROM usage WHERE customer = $customer AND code >= $code",
+            "params": [
+                {"name": "customer"},
+                {"name": "code", "type": "number", "min": 100, "max": 599, "default": 200}
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut t = template();
+        assert!(validate(&t).is_ok());
+        t.sql.push_str(" AND host = $host");
+        assert!(validate(&t).is_err());
+    }
+
+    #[test]
+    fn test_bind_and_render() {
+        let t = template();
+        let mut claims = QueryTemplateClaims {
+            org: "default".to_string(),
+            template: "usage".to_string(),
+            created_by: "root@example.com".to_string(),
+            params: HashMap::new(),
+            iat: 0,
+            exp: 0,
+        };
+        let params = HashMap::from([("customer".to_string(), json!("o'neil"))]);
+        let values = bind_params(&t, &claims, &params).unwrap();
+        assert_eq!(
+            render(&t.sql, &values),
+            "SELECT COUNT(*) FROM usage WHERE customer = 'o''neil' AND code >= 200"
+        );
+
+        claims.params.insert(
+            "customer".to_string(),
+            ParamRange {
+                values: vec![json!("acme")],
+                ..Default::default()
+            },
+        );
+        assert!(bind_params(&t, &claims, &params).is_err());
+        let params = HashMap::from([
+            ("customer".to_string(), json!("acme")),
+            ("code".to_string(), json!(700)),
+        ]);
+        assert!(bind_params(&t, &claims, &params).is_err());
+        assert!(bind_params(&t, &claims, &HashMap::new()).is_err());
+    }
+}
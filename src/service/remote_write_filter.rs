@@ -0,0 +1,81 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use chrono::Utc;
+use config::meta::remote_write_filter::{MetricFilterRule, RemoteWriteFilter};
+
+use crate::service::db;
+
+#[derive(Debug, thiserror::Error)]
+pub enum RemoteWriteFilterError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Remote write filter not found")]
+    NotFound,
+
+    #[error("Invalid remote write filter: {0}")]
+    InvalidFilter(String),
+}
+
+pub async fn get(org_id: &str) -> Result<RemoteWriteFilter, RemoteWriteFilterError> {
+    db::remote_write_filter::get(org_id)
+        .await
+        .map_err(|_| RemoteWriteFilterError::NotFound)
+}
+
+fn trim_rules(rules: Vec<MetricFilterRule>) -> Vec<MetricFilterRule> {
+    rules
+        .into_iter()
+        .map(|rule| MetricFilterRule {
+            metric: rule.metric.trim().to_string(),
+            labels: rule
+                .labels
+                .into_iter()
+                .map(|(name, pattern)| (name.trim().to_string(), pattern))
+                .filter(|(name, _)| !name.is_empty())
+                .collect(),
+        })
+        .filter(|rule| !rule.metric.is_empty() || !rule.labels.is_empty())
+        .collect()
+}
+
+pub async fn save(
+    org_id: &str,
+    mut filter: RemoteWriteFilter,
+) -> Result<RemoteWriteFilter, RemoteWriteFilterError> {
+    filter.allow = trim_rules(filter.allow);
+    filter.deny = trim_rules(filter.deny);
+    if filter.enabled && filter.allow.is_empty() && filter.deny.is_empty() {
+        return Err(RemoteWriteFilterError::InvalidFilter(
+            "an enabled filter needs at least one allow or deny rule".to_string(),
+        ));
+    }
+    // compile regardless of enabled, so a disabled filter can be turned on as is
+    RemoteWriteFilter {
+        enabled: true,
+        ..filter.clone()
+    }
+    .compile()
+    .map_err(RemoteWriteFilterError::InvalidFilter)?;
+    filter.updated_at = Utc::now().timestamp_micros();
+    db::remote_write_filter::set(org_id, &filter).await?;
+    Ok(filter)
+}
+
+pub async fn delete(org_id: &str) -> Result<(), RemoteWriteFilterError> {
+    get(org_id).await?;
+    Ok(db::remote_write_filter::delete(org_id).await?)
+}
@@ -0,0 +1,206 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use config::{
+    cluster::LOCAL_NODE,
+    get_config,
+    meta::stream::FileKey,
+    metrics,
+    utils::{json, time::now_micros},
+};
+use futures::{StreamExt, TryStreamExt};
+use infra::{
+    dist_lock, file_list as infra_file_list,
+    storage::replica::{self, Manifest, ManifestFile},
+};
+use once_cell::sync::Lazy;
+
+use crate::{common::infra::cluster::get_node_by_uuid, service::db};
+
+// the last time the replica caught up with the file list
+static CAUGHT_UP_AT: Lazy<AtomicI64> = Lazy::new(|| AtomicI64::new(now_micros()));
+
+/// Replicate the file list entries added since the last run to the secondary storage.
+///
+/// Only one node runs it at a time, the offset is bound to the node like the stream stats job.
+pub async fn replicate() -> Result<(), anyhow::Error> {
+    let latest_pk = infra_file_list::get_max_pk_value()
+        .await
+        .map_err(|e| anyhow::anyhow!("get max pk value error: {:?}", e))?;
+    while let Some((offset, end)) = replicate_inner(latest_pk).await? {
+        log::debug!("[REPLICATION] replicated file list from {offset} to {end}");
+    }
+    Ok(())
+}
+
+async fn replicate_inner(latest_pk: i64) -> Result<Option<(i64, i64)>, anyhow::Error> {
+    let (mut offset, node) = db::replication::get_offset().await;
+    if !node.is_empty() && LOCAL_NODE.uuid.ne(&node) && get_node_by_uuid(&node).await.is_some() {
+        return Ok(None);
+    }
+
+    // before starting, set current node to lock the job
+    if node.is_empty() || LOCAL_NODE.uuid.ne(&node) {
+        offset = match replicate_lock_node().await? {
+            Some(offset) => offset,
+            None => return Ok(None),
+        }
+    }
+
+    if offset >= latest_pk {
+        update_lag_metrics(0);
+        return Ok(None);
+    }
+
+    let cfg = get_config();
+    let end = std::cmp::min(offset + cfg.replication.batch_size, latest_pk);
+    let ids = (offset + 1..=end).collect::<Vec<_>>();
+    // ids of merged or dumped files are gone, they are covered by the files replacing them
+    let files = infra_file_list::query_by_ids(&ids)
+        .await
+        .map_err(|e| anyhow::anyhow!("query file list by ids error: {e}"))?;
+
+    let results = futures::stream::iter(files)
+        .map(|file| async move {
+            let copied = replica::copy(&file.account, &file.key).await?;
+            Ok::<_, anyhow::Error>((file, copied))
+        })
+        .buffer_unordered(std::cmp::max(1, cfg.limit.cpu_num))
+        .collect::<Vec<_>>()
+        .await;
+    let mut added = Vec::with_capacity(results.len());
+    for result in results {
+        match result {
+            Ok((file, true)) => {
+                metrics::REPLICATION_FILES
+                    .with_label_values(&["copied"])
+                    .inc();
+                added.push(ManifestFile {
+                    account: file.account,
+                    key: file.key,
+                    meta: file.meta,
+                });
+            }
+            Ok((_, false)) => {
+                metrics::REPLICATION_FILES
+                    .with_label_values(&["skipped"])
+                    .inc();
+            }
+            Err(e) => {
+                metrics::REPLICATION_FILES
+                    .with_label_values(&["failed"])
+                    .inc();
+                update_lag_metrics(latest_pk - offset);
+                return Err(anyhow::anyhow!("copy file to replica error: {e}"));
+            }
+        }
+    }
+
+    if !added.is_empty() {
+        replica::put_manifest(&Manifest::new(end, added, vec![]))
+            .await
+            .map_err(|e| anyhow::anyhow!("put replication manifest error: {e}"))?;
+    }
+
+    db::replication::set_offset(end, Some(&LOCAL_NODE.uuid.clone()))
+        .await
+        .map_err(|e| anyhow::anyhow!("set offset error: {e}"))?;
+    update_lag_metrics(latest_pk - end);
+
+    Ok(Some((offset, end)))
+}
+
+async fn replicate_lock_node() -> Result<Option<i64>, anyhow::Error> {
+    let lock_key = "/replication/file_list/offset".to_string();
+    let locker = dist_lock::lock(&lock_key, 0).await?;
+    // check the working node again, maybe other node locked it first
+    let (offset, node) = db::replication::get_offset().await;
+    if !node.is_empty() && LOCAL_NODE.uuid.ne(&node) && get_node_by_uuid(&node).await.is_some() {
+        dist_lock::unlock(&locker).await?;
+        return Ok(None);
+    }
+
+    // bind the job to this node
+    let ret = db::replication::set_offset(offset, Some(&LOCAL_NODE.uuid.clone())).await;
+    // already bind to this node, we can unlock now
+    dist_lock::unlock(&locker).await?;
+    ret.map(|_| Some(offset))
+}
+
+fn update_lag_metrics(pending: i64) {
+    let now = now_micros();
+    if pending <= 0 {
+        CAUGHT_UP_AT.store(now, Ordering::Relaxed);
+    }
+    let lag = (now - CAUGHT_UP_AT.load(Ordering::Relaxed)) / 1_000_000;
+    metrics::REPLICATION_PENDING_FILES
+        .with_label_values(&[])
+        .set(pending.max(0));
+    metrics::REPLICATION_LAG_SECONDS
+        .with_label_values(&[])
+        .set(lag);
+}
+
+/// Write the files removed from the file list on this node to the replica.
+pub async fn flush_removed() -> Result<(), anyhow::Error> {
+    let removed = replica::take_removed();
+    if removed.is_empty() {
+        return Ok(());
+    }
+    let manifest = Manifest::new(0, vec![], removed);
+    if let Err(e) = replica::put_manifest(&manifest).await {
+        replica::restore_removed(manifest.removed);
+        return Err(anyhow::anyhow!("put replication manifest error: {e}"));
+    }
+    Ok(())
+}
+
+/// Rebuild the file list of this deployment from the manifests in the secondary storage.
+///
+/// Run it on the standby after pointing `ZO_S3_*` to the secondary bucket, the files are
+/// registered under the default account. Returns the number of files registered.
+pub async fn promote() -> Result<usize, anyhow::Error> {
+    if get_config().replication.bucket_name.is_empty() {
+        return Err(anyhow::anyhow!(
+            "ZO_REPLICATION_BUCKET_NAME is required to promote the replica"
+        ));
+    }
+    let mut paths = replica::list(replica::MANIFEST_PREFIX).await?;
+    // manifest names start with the creation time
+    paths.sort();
+    let manifests = futures::stream::iter(paths)
+        .map(|path| async move {
+            let data = replica::get_bytes(&path).await?;
+            let manifest: Manifest = json::from_slice(&data)
+                .map_err(|e| anyhow::anyhow!("parse manifest {path} error: {e}"))?;
+            Ok::<_, anyhow::Error>(manifest)
+        })
+        .buffered(std::cmp::max(1, get_config().limit.cpu_num))
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let files = replica::replay(manifests)
+        .into_iter()
+        .map(|f| FileKey::new(0, String::new(), f.key, f.meta, false))
+        .collect::<Vec<_>>();
+    for chunk in files.chunks(1000) {
+        infra_file_list::batch_add(chunk)
+            .await
+            .map_err(|e| anyhow::anyhow!("add file list error: {e}"))?;
+    }
+    Ok(files.len())
+}
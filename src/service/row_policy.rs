@@ -0,0 +1,74 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use chrono::Utc;
+use config::meta::{
+    row_policy::{RowPolicy, USER_ATTRIBUTES},
+    stream::StreamType,
+    user::UserRole,
+};
+
+use crate::service::{db, search::sql::parse_row_predicate};
+
+#[derive(Debug, thiserror::Error)]
+pub enum RowPolicyError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Row policy not found")]
+    NotFound,
+
+    #[error("Invalid row policy: {0}")]
+    InvalidPolicy(String),
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<RowPolicy>, RowPolicyError> {
+    Ok(db::row_policy::list(org_id).await?)
+}
+
+pub async fn save(org_id: &str, mut policy: RowPolicy) -> Result<RowPolicy, RowPolicyError> {
+    policy.stream_name = policy.stream_name.trim().to_string();
+    policy.predicate = policy.predicate.trim().to_string();
+    if policy.stream_name.is_empty() {
+        return Err(RowPolicyError::InvalidPolicy(
+            "stream_name can't be empty".to_string(),
+        ));
+    }
+    if policy.predicate.is_empty() {
+        return Err(RowPolicyError::InvalidPolicy(
+            "predicate can't be empty".to_string(),
+        ));
+    }
+    // the placeholders are checked with any value, the users' values are used by the queries
+    parse_row_predicate(&policy.predicate, |name| {
+        USER_ATTRIBUTES.contains(&name).then(String::new)
+    })
+    .map_err(RowPolicyError::InvalidPolicy)?;
+    policy.updated_at = Utc::now().timestamp_micros();
+    db::row_policy::set(org_id, &policy).await?;
+    Ok(policy)
+}
+
+pub async fn delete(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    role: &UserRole,
+) -> Result<(), RowPolicyError> {
+    db::row_policy::get(org_id, stream_type, stream_name, role)
+        .await
+        .map_err(|_| RowPolicyError::NotFound)?;
+    Ok(db::row_policy::delete(org_id, stream_type, stream_name, role).await?)
+}
@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::str::FromStr;
+use std::{borrow::Cow, str::FromStr};
 
 use chrono::{TimeZone, Utc};
 use config::{
@@ -63,6 +63,110 @@ pub async fn search(
     user_id: Option<String>,
     in_req: &search::Request,
     range_error: String,
+) -> Result<search::Response, Error> {
+    // the queries of a dashboard panel keep to the timeout and rows of the panel
+    let panel_query = SearchService::panel_query::get(org_id, in_req).await;
+    let limited_req;
+    let in_req = match panel_query.as_ref() {
+        Some(panel_query) => {
+            limited_req = panel_query.limit(in_req);
+            &limited_req
+        }
+        None => in_req,
+    };
+
+    // repeated loads of a dashboard panel are served from the panel cache
+    let panel_key =
+        SearchService::panel_cache::key(org_id, stream_type, user_id.as_deref(), in_req).await;
+    if let Some(key) = panel_key
+        && let Some(mut res) = SearchService::panel_cache::get(key)
+    {
+        log::info!("[trace_id {trace_id}] search served from panel cache");
+        res.set_trace_id(trace_id.to_string());
+        return Ok(res);
+    }
+
+    let mut res = if !in_req.query.time_shifts.is_empty() {
+        SearchService::time_shift::search(
+            trace_id,
+            org_id,
+            stream_type,
+            user_id,
+            in_req,
+            range_error,
+        )
+        .await
+    } else {
+        search_with_dedup(trace_id, org_id, stream_type, user_id, in_req, range_error).await
+    };
+    // the panel cache keeps the transformed rows of the trellis page
+    if let Some(panel_query) = panel_query.as_ref()
+        && let Ok(res) = &mut res
+    {
+        panel_query.transform(res);
+        panel_query.page_trellis(res);
+    }
+    if let Some(key) = panel_key
+        && let Ok(res) = &res
+        && !res.is_partial
+    {
+        SearchService::panel_cache::set(key, res);
+    }
+    res
+}
+
+/// Searches without time shifts, identical concurrent queries share one execution when
+/// `search_inflight_dedup_enabled` is set.
+pub(crate) async fn search_with_dedup(
+    trace_id: &str,
+    org_id: &str,
+    stream_type: StreamType,
+    user_id: Option<String>,
+    in_req: &search::Request,
+    range_error: String,
+) -> Result<search::Response, Error> {
+    if !get_config().common.search_inflight_dedup_enabled {
+        return search_inner(trace_id, org_id, stream_type, user_id, in_req, range_error).await;
+    }
+
+    // identical concurrent queries share one execution, the queries of users with access
+    // policies are only shared with the same rewrite
+    let mut fingerprint_req = Cow::Borrowed(in_req);
+    if let Some(user_id) = user_id.as_deref() {
+        let row_sql = SearchService::sql::apply_access_policies(
+            org_id,
+            user_id,
+            stream_type,
+            &in_req.query.sql,
+        )
+        .await?;
+        if row_sql != in_req.query.sql {
+            fingerprint_req.to_mut().query.sql = row_sql;
+        }
+    }
+    let key =
+        SearchService::inflight::fingerprint(org_id, stream_type, &fingerprint_req, &range_error);
+    let mut res = SearchService::inflight::dedup(key, || {
+        search_inner(trace_id, org_id, stream_type, user_id, in_req, range_error)
+    })
+    .await?;
+    if res.trace_id != trace_id {
+        log::info!(
+            "[trace_id {trace_id}] search deduplicated, shared result of trace_id {}",
+            res.trace_id
+        );
+        res.set_trace_id(trace_id.to_string());
+    }
+    Ok(res)
+}
+
+async fn search_inner(
+    trace_id: &str,
+    org_id: &str,
+    stream_type: StreamType,
+    user_id: Option<String>,
+    in_req: &search::Request,
+    range_error: String,
 ) -> Result<search::Response, Error> {
     let start = std::time::Instant::now();
     let started_at = Utc::now().timestamp_micros();
@@ -103,6 +207,19 @@ pub async fn search(
 
     // calculate hash for the query
     let mut hash_body = vec![origin_sql.to_string()];
+    // the results of users with access policies are cached by the rewritten query
+    if let Some(user_id) = user_id.as_deref() {
+        let row_sql = SearchService::sql::apply_access_policies(
+            org_id,
+            user_id,
+            stream_type,
+            &in_req.query.sql,
+        )
+        .await?;
+        if row_sql != in_req.query.sql {
+            hash_body.push(row_sql);
+        }
+    }
     if let Some(vrl_function) = &query_fn {
         hash_body.push(vrl_function.to_string());
     }
@@ -452,6 +569,15 @@ pub async fn search(
     }
     // result cache save changes Ends
 
+    // downsample after caching, so the cache keeps the full resolution
+    if let Some(max_points) = in_req.query.max_points {
+        if let Some(hits) = super::downsample::downsample(&res.hits, &c_resp.ts_column, max_points)
+        {
+            res.downsampled_from = Some(res.hits.len());
+            res.hits = hits;
+        }
+    }
+
     Ok(res)
 }
 
@@ -854,6 +980,7 @@ pub async fn check_cache_v2(
     trace_id: &str,
     org_id: &str,
     stream_type: StreamType,
+    user_id: &str,
     in_req: &search::Request,
     use_cache: bool,
 ) -> Result<MultiCachedQueryResponse, Error> {
@@ -878,6 +1005,13 @@ pub async fn check_cache_v2(
 
     // calculate hash for the query
     let mut hash_body = vec![origin_sql.to_string()];
+    // the results of users with access policies are cached by the rewritten query
+    let row_sql =
+        SearchService::sql::apply_access_policies(org_id, user_id, stream_type, &in_req.query.sql)
+            .await?;
+    if row_sql != in_req.query.sql {
+        hash_body.push(row_sql);
+    }
     if let Some(vrl_function) = &query_fn {
         hash_body.push(vrl_function.to_string());
     }
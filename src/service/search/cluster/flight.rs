@@ -16,6 +16,7 @@
 use std::sync::Arc;
 
 use arrow::array::RecordBatch;
+use arrow_schema::Schema;
 use async_recursion::async_recursion;
 use config::{
     INDEX_FIELD_NAME_FOR_ALL, QUERY_WITH_NO_LIMIT,
@@ -23,7 +24,7 @@ use config::{
     get_config,
     meta::{
         bitvec::BitVec,
-        cluster::{IntoArcVec, Node, Role, RoleGroup},
+        cluster::{IntoArcVec, Node, Role, RoleGroup, get_query_node_selector},
         search::{ScanStats, SearchEventType},
         sql::TableReferenceExt,
         stream::{FileKey, QueryPartitionStrategy, StreamType},
@@ -33,6 +34,7 @@ use config::{
 };
 use datafusion::{
     common::{TableReference, tree_node::TreeNode},
+    datasource::MemTable,
     error::DataFusionError,
     physical_plan::{ExecutionPlan, displayable, visit_execution_plan},
     prelude::SessionContext,
@@ -49,7 +51,7 @@ use tracing::{Instrument, info_span};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use crate::{
-    common::infra::cluster as infra_cluster,
+    common::infra::{cluster as infra_cluster, config::ENRICHMENT_TABLES},
     service::{
         db::enrichment_table,
         search::{
@@ -149,16 +151,12 @@ pub async fn search(
 
     // 3. get nodes
     let get_node_start = std::time::Instant::now();
-    let role_group = req
+    let search_event_type = req
         .search_event_type
         .as_ref()
-        .map(|v| {
-            SearchEventType::try_from(v.as_str())
-                .ok()
-                .map(RoleGroup::from)
-        })
-        .unwrap_or(None);
-    let mut nodes = get_online_querier_nodes(trace_id, role_group).await?;
+        .and_then(|v| SearchEventType::try_from(v.as_str()).ok());
+    let role_group = search_event_type.map(RoleGroup::from);
+    let mut nodes = get_online_querier_nodes(trace_id, role_group, search_event_type).await?;
 
     // local mode, only use local node as querier node
     if req.local_mode.unwrap_or_default() && LOCAL_NODE.is_querier() {
@@ -568,6 +566,7 @@ pub async fn run_datafusion(
 pub async fn get_online_querier_nodes(
     trace_id: &str,
     role_group: Option<RoleGroup>,
+    search_event_type: Option<SearchEventType>,
 ) -> Result<Vec<Node>> {
     // get nodes from cluster
     let cfg = get_config();
@@ -588,6 +587,21 @@ pub async fn get_online_querier_nodes(
     nodes.dedup_by(|a, b| a.grpc_addr == b.grpc_addr);
     nodes.sort_by_key(|x| x.id);
 
+    // route the query to the querier nodes labeled for its search event type, the ingesters are
+    // always kept as they hold the data not yet flushed
+    if let Some(selector) = search_event_type.and_then(get_query_node_selector) {
+        if nodes
+            .iter()
+            .any(|n| n.is_querier() && n.matches_labels(&selector))
+        {
+            nodes.retain(|n| n.is_ingester() || n.matches_labels(&selector));
+        } else {
+            log::warn!(
+                "[trace_id {trace_id}] flight->search: no querier node matches the labels {selector:?}, use all querier nodes"
+            );
+        }
+    }
+
     let querier_num = nodes.iter().filter(|node| node.is_querier()).count();
     if querier_num == 0 {
         log::error!("no querier node online");
@@ -858,13 +872,8 @@ pub(crate) async fn partition_file_by_hash(
                 .expect("there is no querier node in consistent hash ring");
         let idx = match node_idx.get(&node_name) {
             Some(idx) => *idx,
-            None => {
-                log::error!(
-                    "partition_file_by_hash: {} not found in node_idx",
-                    node_name
-                );
-                0
-            }
+            // the node is not used by the query, e.g. excluded by the query routing
+            None => (fk.id as usize) % idx,
         };
         partitions[idx].push(fk.id);
     }
@@ -912,6 +921,13 @@ pub async fn register_table(ctx: &SessionContext, sql: &Sql) -> Result<()> {
             .register_schema(&stream_type, schema_provider);
     }
 
+    // enrichment tables joined with other streams are small, so we register them from the
+    // in-memory cache and let the leader broadcast them to the join instead of scanning files
+    let broadcast_enrich = sql
+        .schemas
+        .keys()
+        .any(|stream| stream.get_stream_type(sql.stream_type) != StreamType::EnrichmentTables);
+
     // register table
     for (stream, schema) in &sql.schemas {
         let schema = schema
@@ -920,6 +936,18 @@ pub async fn register_table(ctx: &SessionContext, sql: &Sql) -> Result<()> {
             .clone()
             .with_metadata(Default::default());
         let stream_name = stream.to_quoted_string();
+        if broadcast_enrich
+            && stream.get_stream_type(sql.stream_type) == StreamType::EnrichmentTables
+        {
+            if let Some(table) = get_enrichment_mem_table(
+                &sql.org_id,
+                &stream.stream_name(),
+                Arc::new(schema.clone()),
+            ) {
+                ctx.register_table(&stream_name, table)?;
+                continue;
+            }
+        }
         let table = Arc::new(
             NewEmptyTable::new(&stream_name, Arc::new(schema))
                 .with_partitions(ctx.state().config().target_partitions())
@@ -931,6 +959,38 @@ pub async fn register_table(ctx: &SessionContext, sql: &Sql) -> Result<()> {
     Ok(())
 }
 
+/// Build an in-memory table from the cached enrichment table data, returns `None`
+/// when the table is not cached yet or is too large to be broadcast.
+fn get_enrichment_mem_table(
+    org_id: &str,
+    stream_name: &str,
+    schema: Arc<Schema>,
+) -> Option<Arc<MemTable>> {
+    let max_rows = get_config().limit.enrichment_table_broadcast_join_max_rows;
+    if max_rows == 0 {
+        return None;
+    }
+    let key = format!("{org_id}/{}/{stream_name}", StreamType::EnrichmentTables);
+    let table = ENRICHMENT_TABLES.get(&key)?;
+    if table.data.is_empty() || table.data.len() > max_rows {
+        return None;
+    }
+    let batch = match table.to_record_batch(&schema) {
+        Ok(batch) => batch,
+        Err(e) => {
+            log::error!("convert enrichment table {key} to record batch error: {e}");
+            return None;
+        }
+    };
+    match MemTable::try_new(schema, vec![vec![batch]]) {
+        Ok(table) => Some(Arc::new(table)),
+        Err(e) => {
+            log::error!("create memtable for enrichment table {key} error: {e}");
+            None
+        }
+    }
+}
+
 #[tracing::instrument(name = "service:search:cluster:flight:get_file_id_lists", skip_all)]
 pub async fn get_file_id_lists(
     trace_id: &str,
@@ -0,0 +1,305 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Search of a stream across organizations, for the operators of a managed service diagnosing
+//! platform-wide issues.
+//!
+//! The query runs in every organization which has its streams and the hits are merged with
+//! their organization in the `_org` column. Only the users listed in `ZO_CROSS_ORG_QUERY_USERS`
+//! can run it, from the meta org, and every search is recorded.
+
+use std::cmp::Ordering;
+
+use config::{
+    META_ORG_ID,
+    cluster::LOCAL_NODE,
+    get_config,
+    meta::{
+        search::{self, CROSS_ORG_COLUMN, CrossOrgSearchRequest, CrossOrgSearchResponse},
+        self_reporting::query_audit::QueryAuditRecord,
+        sql::{OrderBy, resolve_order_by, resolve_stream_names},
+        stream::StreamType,
+    },
+    utils::{json, time::now_micros},
+};
+use futures::StreamExt;
+use infra::errors::{Error, ErrorCodes};
+
+use crate::{common::infra::config::ORGANIZATIONS, service::self_reporting};
+
+/// Returns if the user was granted cross-org search.
+pub fn is_permitted(user_id: &str) -> bool {
+    get_config()
+        .common
+        .cross_org_query_users
+        .split(',')
+        .any(|user| {
+            let user = user.trim();
+            !user.is_empty() && user.eq_ignore_ascii_case(user_id)
+        })
+}
+
+pub async fn search(
+    trace_id: &str,
+    user_id: &str,
+    stream_type: StreamType,
+    req: &CrossOrgSearchRequest,
+) -> Result<CrossOrgSearchResponse, Error> {
+    let start = std::time::Instant::now();
+    let stream_names = resolve_stream_names(&req.sql)
+        .map_err(|e| Error::ErrorCode(ErrorCodes::SearchSQLNotValid(e.to_string())))?;
+    let order_by = resolve_order_by(&req.sql).unwrap_or_default();
+    let orgs = if req.orgs.is_empty() {
+        let mut orgs = ORGANIZATIONS
+            .read()
+            .await
+            .keys()
+            .filter(|org| org.as_str() != META_ORG_ID)
+            .cloned()
+            .collect::<Vec<_>>();
+        orgs.sort();
+        orgs
+    } else {
+        req.orgs.clone()
+    };
+
+    log::info!(
+        "[trace_id {trace_id}] [CROSS ORG] user {user_id} searches {stream_type} streams {} in {} orgs: {}",
+        stream_names.join(","),
+        orgs.len(),
+        req.sql
+    );
+
+    let search_req = search::Request {
+        query: search::Query {
+            sql: req.sql.clone(),
+            start_time: req.start_time,
+            end_time: req.end_time,
+            size: req.size,
+            ..Default::default()
+        },
+        search_type: Some(search::SearchEventType::Other),
+        ..Default::default()
+    };
+    let results = futures::stream::iter(orgs)
+        .map(|org_id| {
+            let search_req = &search_req;
+            async move {
+                let ret = super::search(
+                    &format!("{trace_id}-{org_id}"),
+                    &org_id,
+                    stream_type,
+                    Some(user_id.to_string()),
+                    search_req,
+                )
+                .await;
+                (org_id, ret)
+            }
+        })
+        .buffer_unordered(get_config().limit.cpu_num)
+        .collect::<Vec<_>>()
+        .await;
+
+    let mut resp = CrossOrgSearchResponse::default();
+    let mut org_hits = Vec::with_capacity(results.len());
+    for (org_id, ret) in results {
+        match ret {
+            Ok(res) => {
+                resp.total += res.total;
+                resp.orgs.push(org_id.clone());
+                org_hits.push((org_id, res.hits));
+            }
+            // the organization doesn't have the stream
+            Err(Error::ErrorCode(ErrorCodes::SearchStreamNotFound(_))) => {}
+            Err(e) => {
+                resp.errors.insert(org_id, e.to_string());
+            }
+        }
+    }
+    resp.orgs.sort();
+    org_hits.sort_by(|(a, _), (b, _)| a.cmp(b));
+    resp.hits = merge_hits(org_hits, &order_by, req.size.max(0) as usize);
+    resp.took = start.elapsed().as_millis() as usize;
+
+    self_reporting::publish_query_audit(QueryAuditRecord {
+        _timestamp: now_micros(),
+        org_id: META_ORG_ID.to_string(),
+        user_email: user_id.to_string(),
+        trace_id: trace_id.to_string(),
+        search_type: "cross_org".to_string(),
+        stream_type,
+        streams: resp
+            .orgs
+            .iter()
+            .flat_map(|org| {
+                stream_names
+                    .iter()
+                    .map(move |stream| format!("{org}/{stream}"))
+            })
+            .collect::<Vec<_>>()
+            .join(","),
+        sql: req.sql.clone(),
+        start_time: req.start_time,
+        end_time: req.end_time,
+        rows: resp.hits.len() as i64,
+        node: LOCAL_NODE.name.clone(),
+        ..Default::default()
+    })
+    .await;
+
+    Ok(resp)
+}
+
+/// Adds the organization column to the hits of every organization and keeps the first `size`
+/// hits. The hits of every organization are already in the order of the query, they are merged
+/// on its ORDER BY columns, or concatenated in the order of the organizations without one.
+fn merge_hits(
+    org_hits: Vec<(String, Vec<json::Value>)>,
+    order_by: &[(String, OrderBy)],
+    size: usize,
+) -> Vec<json::Value> {
+    let mut hits = org_hits
+        .into_iter()
+        .flat_map(|(org_id, hits)| {
+            hits.into_iter().map(move |mut hit| {
+                if let Some(hit) = hit.as_object_mut() {
+                    hit.insert(
+                        CROSS_ORG_COLUMN.to_string(),
+                        json::Value::String(org_id.clone()),
+                    );
+                }
+                hit
+            })
+        })
+        .collect::<Vec<_>>();
+    // the sort is stable, so the hits of an organization keep their order on ties
+    if !order_by.is_empty() {
+        hits.sort_by(|a, b| {
+            order_by
+                .iter()
+                .map(|(col, order)| {
+                    let ordering = compare_values(a.get(col), b.get(col));
+                    match order {
+                        OrderBy::Asc => ordering,
+                        OrderBy::Desc => ordering.reverse(),
+                    }
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+    }
+    if size > 0 {
+        hits.truncate(size);
+    }
+    hits
+}
+
+// compares two values of a column, nulls are greater than any value like in datafusion
+fn compare_values(a: Option<&json::Value>, b: Option<&json::Value>) -> Ordering {
+    let a = a.filter(|v| !v.is_null());
+    let b = b.filter(|v| !v.is_null());
+    match (a, b) {
+        (Some(json::Value::Number(a)), Some(json::Value::Number(b))) => {
+            match (a.as_i64(), b.as_i64()) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                _ => a
+                    .as_f64()
+                    .partial_cmp(&b.as_f64())
+                    .unwrap_or(Ordering::Equal),
+            }
+        }
+        (Some(json::Value::String(a)), Some(json::Value::String(b))) => a.cmp(b),
+        (Some(json::Value::Bool(a)), Some(json::Value::Bool(b))) => a.cmp(b),
+        (Some(_), Some(_)) => Ordering::Equal,
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_hits() {
+        let org_hits = vec![
+            (
+                "acme".to_string(),
+                vec![
+                    json::json!({"_timestamp": 30, "log": "a"}),
+                    json::json!({"_timestamp": 10, "log": "b"}),
+                ],
+            ),
+            (
+                "globex".to_string(),
+                vec![json::json!({"_timestamp": 20, "log": "c"})],
+            ),
+        ];
+        let order_by = vec![("_timestamp".to_string(), OrderBy::Desc)];
+        let hits = merge_hits(org_hits.clone(), &order_by, 2);
+        assert_eq!(
+            hits,
+            vec![
+                json::json!({"_timestamp": 30, "log": "a", "_org": "acme"}),
+                json::json!({"_timestamp": 20, "log": "c", "_org": "globex"}),
+            ]
+        );
+        assert_eq!(merge_hits(org_hits, &order_by, 0).len(), 3);
+
+        // without ORDER BY the hits are concatenated
+        let org_hits = vec![
+            ("acme".to_string(), vec![json::json!({"cnt": 3})]),
+            ("globex".to_string(), vec![json::json!({"cnt": 5})]),
+        ];
+        assert_eq!(
+            merge_hits(org_hits, &[], 10),
+            vec![
+                json::json!({"cnt": 3, "_org": "acme"}),
+                json::json!({"cnt": 5, "_org": "globex"}),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_hits_order_by_asc() {
+        let sql = "SELECT * FROM k8s ORDER BY _timestamp ASC";
+        let order_by = resolve_order_by(sql).unwrap();
+        let org_hits = vec![
+            (
+                "acme".to_string(),
+                vec![
+                    json::json!({"_timestamp": 10, "log": "a"}),
+                    json::json!({"_timestamp": 30, "log": "b"}),
+                ],
+            ),
+            (
+                "globex".to_string(),
+                vec![
+                    json::json!({"_timestamp": 10, "log": "c"}),
+                    json::json!({"_timestamp": 20, "log": "d"}),
+                ],
+            ),
+        ];
+        assert_eq!(
+            merge_hits(org_hits, &order_by, 3),
+            vec![
+                json::json!({"_timestamp": 10, "log": "a", "_org": "acme"}),
+                json::json!({"_timestamp": 10, "log": "c", "_org": "globex"}),
+                json::json!({"_timestamp": 20, "log": "d", "_org": "globex"}),
+            ]
+        );
+    }
+}
@@ -79,7 +79,7 @@ impl TreeNodeRewriter for RemoteScanRewriter {
         if node.name() == "RepartitionExec" || node.name() == "CoalescePartitionsExec" {
             let mut visitor = TableNameVisitor::new();
             node.visit(&mut visitor)?;
-            if visitor.is_remote_scan {
+            if visitor.need_remote_scan() {
                 let table_name = visitor.table_name.clone().unwrap();
                 let input = node.children()[0];
                 let remote_scan = Arc::new(RemoteScanExec::new(
@@ -97,7 +97,7 @@ impl TreeNodeRewriter for RemoteScanRewriter {
         } else if node.name() == "SortPreservingMergeExec" {
             let mut visitor = TableNameVisitor::new();
             node.visit(&mut visitor)?;
-            if visitor.is_remote_scan {
+            if visitor.need_remote_scan() {
                 let table_name = visitor.table_name.clone().unwrap();
                 let follow_merge_node = node.clone();
                 let new_input =
@@ -115,11 +115,16 @@ impl TreeNodeRewriter for RemoteScanRewriter {
             let mut visitor = TableNameVisitor::new();
             node.visit(&mut visitor)?;
             // add each remote scan for each child
-            if visitor.is_remote_scan {
+            if visitor.need_remote_scan() {
                 let mut new_children: Vec<Arc<dyn ExecutionPlan>> = vec![];
                 for child in node.children() {
                     let mut visitor = TableNameVisitor::new();
                     child.visit(&mut visitor)?;
+                    // in-memory tables, like broadcast enrichment tables, stay on the leader
+                    if visitor.table_name.is_none() {
+                        new_children.push(child.clone());
+                        continue;
+                    }
                     // For sort, we should add a SortPreservingMergeExec
                     if child.name() == "SortExec" {
                         let table_name = visitor.table_name.clone().unwrap();
@@ -168,6 +173,12 @@ impl TableNameVisitor {
             is_remote_scan: true,
         }
     }
+
+    // only the plans reading from a stream need a remote scan, plans that only read
+    // in-memory tables are executed on the leader
+    fn need_remote_scan(&self) -> bool {
+        self.is_remote_scan && self.table_name.is_some()
+    }
 }
 
 impl<'n> TreeNodeVisitor<'n> for TableNameVisitor {
@@ -187,3 +198,93 @@ impl<'n> TreeNodeVisitor<'n> for TableNameVisitor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{RecordBatch, StringArray};
+    use arrow_schema::{DataType, Field, Schema};
+    use datafusion::{
+        datasource::MemTable,
+        prelude::{SessionConfig, SessionContext},
+    };
+
+    use super::*;
+    use crate::service::search::datafusion::table_provider::empty_table::NewEmptyTable;
+
+    fn visit(plan: &Arc<dyn ExecutionPlan>) -> TableNameVisitor {
+        let mut visitor = TableNameVisitor::new();
+        plan.visit(&mut visitor).unwrap();
+        visitor
+    }
+
+    #[tokio::test]
+    async fn test_enrichment_table_not_remote_scanned() -> Result<()> {
+        let ctx = SessionContext::new_with_config(SessionConfig::new().with_target_partitions(4));
+        let logs_schema = Arc::new(Schema::new(vec![
+            Field::new("name", DataType::Utf8, false),
+            Field::new("message", DataType::Utf8, false),
+        ]));
+        ctx.register_table(
+            "logs",
+            Arc::new(NewEmptyTable::new("logs", logs_schema).with_partitions(4)),
+        )?;
+        // broadcast enrichment tables are registered as in-memory tables
+        let enrich_schema = Arc::new(Schema::new(vec![
+            Field::new("name", DataType::Utf8, false),
+            Field::new("city", DataType::Utf8, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            enrich_schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec!["a", "b"])),
+                Arc::new(StringArray::from(vec!["x", "y"])),
+            ],
+        )?;
+        ctx.register_table(
+            "enrich",
+            Arc::new(MemTable::try_new(enrich_schema, vec![vec![batch]])?),
+        )?;
+
+        let plan = ctx
+            .sql("SELECT city FROM enrich")
+            .await?
+            .create_physical_plan()
+            .await?;
+        assert!(!visit(&plan).need_remote_scan());
+
+        let plan = ctx
+            .sql("SELECT message FROM logs")
+            .await?
+            .create_physical_plan()
+            .await?;
+        let visitor = visit(&plan);
+        assert!(visitor.need_remote_scan());
+        assert_eq!(visitor.table_name, Some(TableReference::from("logs")));
+
+        // only the stream side of the join is scanned by the queriers
+        let plan = ctx
+            .sql(
+                "SELECT logs.message, enrich.city FROM logs JOIN enrich ON logs.name = enrich.name",
+            )
+            .await?
+            .create_physical_plan()
+            .await?;
+        let mut join = None;
+        plan.apply(|node| {
+            if node.name() == "HashJoinExec" {
+                join = Some(node.clone());
+                return Ok(TreeNodeRecursion::Stop);
+            }
+            Ok(TreeNodeRecursion::Continue)
+        })?;
+        let join = join.expect("plan has no hash join");
+        let sides = join
+            .children()
+            .into_iter()
+            .map(|child| visit(child).need_remote_scan())
+            .collect::<Vec<_>>();
+        assert_eq!(sides.len(), 2);
+        assert_eq!(sides.iter().filter(|remote| **remote).count(), 1);
+        Ok(())
+    }
+}
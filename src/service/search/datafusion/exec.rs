@@ -42,7 +42,7 @@ use datafusion::{
         runtime_env::{RuntimeEnv, RuntimeEnvBuilder},
         session_state::SessionStateBuilder,
     },
-    logical_expr::AggregateUDF,
+    logical_expr::{AggregateUDF, WindowUDF},
     optimizer::{AnalyzerRule, OptimizerRule},
     physical_plan::execute_stream,
     prelude::{Expr, SessionContext},
@@ -542,6 +542,15 @@ pub fn register_udf(ctx: &SessionContext, org_id: &str) -> Result<()> {
     ctx.register_udf(super::udf::to_arr_string_udf::TO_ARR_STRING.clone());
     ctx.register_udf(super::udf::histogram_udf::HISTOGRAM_UDF.clone());
     ctx.register_udf(super::udf::match_all_udf::MATCH_ALL_UDF.clone());
+    ctx.register_udf(super::udf::ip_udf::IP_IN_CIDR_UDF.clone());
+    ctx.register_udf(super::udf::ip_udf::IP_IS_PRIVATE_UDF.clone());
+    ctx.register_udf(super::udf::ip_udf::IP_IS_PUBLIC_UDF.clone());
+    ctx.register_udf(super::udf::ip_udf::IP_NORMALIZE_UDF.clone());
+    ctx.register_udf(super::udf::ip_udf::IP_TO_HEX128_UDF.clone());
+    ctx.register_udf(super::udf::ip_udf::IP_FROM_HEX128_UDF.clone());
+    ctx.register_udf(super::udf::parse_udf::PARSE_USER_AGENT_UDF.clone());
+    ctx.register_udf(super::udf::parse_udf::PARSE_URL_UDF.clone());
+    ctx.register_udf(super::udf::parse_udf::PARSE_LOGFMT_UDF.clone());
     #[cfg(feature = "enterprise")]
     ctx.register_udf(super::udf::cipher_udf::DECRYPT_UDF.clone());
     #[cfg(feature = "enterprise")]
@@ -553,7 +562,15 @@ pub fn register_udf(ctx: &SessionContext, org_id: &str) -> Result<()> {
     ctx.register_udaf(AggregateUDF::from(
         super::udaf::summary_percentile::SummaryPercentile::new(),
     ));
+    ctx.register_udaf(AggregateUDF::from(super::udaf::forecast::Forecast::new()));
     ctx.register_udf(super::udf::cast_to_timestamp_udf::CAST_TO_TIMESTAMP_UDF.clone());
+    ctx.register_udwf(WindowUDF::from(super::udwf::counter::CounterUdwf::delta()));
+    ctx.register_udwf(WindowUDF::from(
+        super::udwf::counter::CounterUdwf::increase(),
+    ));
+    ctx.register_udwf(WindowUDF::from(
+        super::udwf::counter::CounterUdwf::rate_per_sec(),
+    ));
     let udf_list = get_all_transform(org_id)?;
     for udf in udf_list {
         ctx.register_udf(udf.clone());
@@ -25,6 +25,7 @@ pub mod storage;
 pub mod table_provider;
 pub mod udaf;
 pub mod udf;
+pub mod udwf;
 
 #[derive(PartialEq, Debug)]
 pub enum MemoryPoolType {
@@ -19,7 +19,7 @@ use add_sort_and_limit::AddSortAndLimitRule;
 use add_timestamp::AddTimestampRule;
 #[cfg(feature = "enterprise")]
 use cipher::{RewriteCipherCall, RewriteCipherKey};
-use config::{ALL_VALUES_COL_NAME, ORIGINAL_DATA_COL_NAME};
+use config::{ALL_VALUES_COL_NAME, ORIGINAL_DATA_COL_NAME, meta::search::SearchEventType};
 use datafusion::optimizer::{
     AnalyzerRule, OptimizerRule, common_subexpr_eliminate::CommonSubexprEliminate,
     decorrelate_predicate_subquery::DecorrelatePredicateSubquery,
@@ -39,7 +39,9 @@ use infra::schema::get_stream_setting_fts_fields;
 use limit_join_right_side::LimitJoinRightSide;
 use remove_index_fields::RemoveIndexFieldsRule;
 use rewrite_histogram::RewriteHistogram;
+use rewrite_ip_cidr::RewriteIpInCidr;
 use rewrite_match::RewriteMatch;
+use rewrite_percentile::RewritePercentile;
 
 use crate::service::search::sql::Sql;
 
@@ -51,7 +53,9 @@ pub mod join_reorder;
 pub mod limit_join_right_side;
 pub mod remove_index_fields;
 pub mod rewrite_histogram;
+pub mod rewrite_ip_cidr;
 pub mod rewrite_match;
+pub mod rewrite_percentile;
 pub mod utils;
 
 pub fn generate_analyzer_rules(sql: &Sql) -> Vec<Arc<dyn AnalyzerRule + Send + Sync>> {
@@ -99,6 +103,30 @@ pub fn generate_optimizer_rules(sql: &Sql) -> Vec<Arc<dyn OptimizerRule + Send +
         // ************************************
     }
 
+    // get the ip fields which have a normalized 128-bit field
+    if sql.stream_names.len() == 1 {
+        let stream_name = &sql.stream_names[0];
+        let schema = sql.schemas.get(stream_name).unwrap();
+        let stream_settings = infra::schema::unwrap_stream_settings(schema.schema());
+        let fields = stream_settings
+            .map(|settings| settings.ip_fields)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|field| {
+                schema.contains_field(field)
+                    && schema.contains_field(&format!(
+                        "{field}{}",
+                        config::utils::ip::IP128_FIELD_SUFFIX
+                    ))
+            })
+            .collect::<Vec<_>>();
+        if !fields.is_empty() {
+            // *********** custom rules ***********
+            rules.push(Arc::new(RewriteIpInCidr::new(fields)));
+            // ************************************
+        }
+    }
+
     rules.push(Arc::new(EliminateNestedUnion::new()));
     rules.push(Arc::new(SimplifyExpressions::new()));
     rules.push(Arc::new(UnwrapCastInComparison::new()));
@@ -124,6 +152,12 @@ pub fn generate_optimizer_rules(sql: &Sql) -> Vec<Arc<dyn OptimizerRule + Send +
 
     // *********** custom rules ***********
     rules.push(Arc::new(RewriteHistogram::new(start_time, end_time)));
+    // dashboards aggregate over many partitions and time buckets, use mergeable sketches there
+    if cfg.common.feature_approx_percentile_enabled
+        && matches!(sql.search_event_type, Some(SearchEventType::Dashboards))
+    {
+        rules.push(Arc::new(RewritePercentile::new()));
+    }
     if let Some(limit) = limit {
         rules.push(Arc::new(AddSortAndLimitRule::new(limit, offset)));
     };
@@ -0,0 +1,239 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use config::utils::ip::{IP128_FIELD_SUFFIX, parse_cidr};
+use datafusion::{
+    self,
+    common::{
+        Column, Result,
+        tree_node::{Transformed, TreeNode, TreeNodeRewriter},
+    },
+    error::DataFusionError,
+    logical_expr::{Between, Expr, LogicalPlan, expr::ScalarFunction},
+    optimizer::{OptimizerConfig, OptimizerRule, optimizer::ApplyOrder, utils::NamePreserver},
+    scalar::ScalarValue,
+};
+
+use crate::service::search::datafusion::udf::ip_udf::IP_IN_CIDR_UDF_NAME;
+
+/// Optimization rule that rewrite ip_in_cidr(field, cidr) to a range filter on the normalized
+/// `<field>_ip128` field, so the min/max statistics of the hex representation can prune files
+#[derive(Default, Debug)]
+pub struct RewriteIpInCidr {
+    fields: Vec<String>,
+}
+
+impl RewriteIpInCidr {
+    #[allow(missing_docs)]
+    pub fn new(fields: Vec<String>) -> Self {
+        Self { fields }
+    }
+}
+
+impl OptimizerRule for RewriteIpInCidr {
+    fn name(&self) -> &str {
+        "rewrite_ip_in_cidr"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::BottomUp)
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        _config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        match plan {
+            LogicalPlan::Filter(_) => {
+                if plan
+                    .expressions()
+                    .iter()
+                    .any(|expr| expr.exists(|expr| Ok(is_ip_in_cidr(expr))).unwrap())
+                {
+                    let mut expr_rewriter = IpInCidrToRange::new(self.fields.clone());
+                    let name_preserver = NamePreserver::new(&plan);
+                    plan.map_expressions(|expr| {
+                        let original_name = name_preserver.save(&expr);
+                        expr.rewrite(&mut expr_rewriter).map(|transformed| {
+                            transformed.update_data(|e| original_name.restore(e))
+                        })
+                    })
+                } else {
+                    Ok(Transformed::no(plan))
+                }
+            }
+            _ => Ok(Transformed::no(plan)),
+        }
+    }
+}
+
+fn is_ip_in_cidr(expr: &Expr) -> bool {
+    match expr {
+        Expr::ScalarFunction(ScalarFunction { func, .. }) => func.name() == IP_IN_CIDR_UDF_NAME,
+        _ => false,
+    }
+}
+
+// Rewriter for ip_in_cidr() to `<field>_ip128` BETWEEN start AND end
+#[derive(Debug, Clone)]
+pub struct IpInCidrToRange {
+    fields: Vec<String>,
+}
+
+impl IpInCidrToRange {
+    pub fn new(fields: Vec<String>) -> Self {
+        Self { fields }
+    }
+}
+
+impl TreeNodeRewriter for IpInCidrToRange {
+    type Node = Expr;
+
+    fn f_up(&mut self, expr: Expr) -> Result<Transformed<Expr>, DataFusionError> {
+        let Expr::ScalarFunction(ScalarFunction { func, args }) = &expr else {
+            return Ok(Transformed::no(expr));
+        };
+        if func.name() != IP_IN_CIDR_UDF_NAME || args.len() != 2 {
+            return Ok(Transformed::no(expr));
+        }
+        // only a column with a normalized field and a constant cidr can be rewritten
+        let (Expr::Column(column), Expr::Literal(ScalarValue::Utf8(Some(cidr)))) =
+            (&args[0], &args[1])
+        else {
+            return Ok(Transformed::no(expr));
+        };
+        if !self.fields.contains(&column.name) {
+            return Ok(Transformed::no(expr));
+        }
+        let Some((start, end)) = parse_cidr(cidr) else {
+            return Ok(Transformed::no(expr));
+        };
+        let new_expr = Expr::Between(Between {
+            expr: Box::new(Expr::Column(Column::new(
+                column.relation.clone(),
+                format!("{}{IP128_FIELD_SUFFIX}", column.name),
+            ))),
+            negated: false,
+            low: Box::new(Expr::Literal(ScalarValue::Utf8(Some(format!(
+                "{start:032x}"
+            ))))),
+            high: Box::new(Expr::Literal(ScalarValue::Utf8(Some(format!(
+                "{end:032x}"
+            ))))),
+        });
+        Ok(Transformed::yes(new_expr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{Int64Array, StringArray};
+    use arrow_schema::DataType;
+    use datafusion::{
+        arrow::{
+            datatypes::{Field, Schema},
+            record_batch::RecordBatch,
+        },
+        assert_batches_eq,
+        datasource::MemTable,
+        execution::{runtime_env::RuntimeEnvBuilder, session_state::SessionStateBuilder},
+        prelude::{SessionConfig, SessionContext},
+    };
+
+    use crate::service::search::datafusion::{
+        optimizer::rewrite_ip_cidr::RewriteIpInCidr, udf::ip_udf,
+    };
+
+    #[tokio::test]
+    async fn test_rewrite_ip_in_cidr() {
+        let sqls = [
+            (
+                "select _timestamp, ip from t where ip_in_cidr(ip, '10.0.0.0/8')",
+                vec![
+                    "+------------+----------+",
+                    "| _timestamp | ip       |",
+                    "+------------+----------+",
+                    "| 1          | 10.0.0.1 |",
+                    "| 2          | 10.9.8.7 |",
+                    "+------------+----------+",
+                ],
+            ),
+            (
+                "select _timestamp, ip from t where ip_in_cidr(ip, '2001:db8::/32')",
+                vec![
+                    "+------------+-------------+",
+                    "| _timestamp | ip          |",
+                    "+------------+-------------+",
+                    "| 4          | 2001:db8::1 |",
+                    "+------------+-------------+",
+                ],
+            ),
+        ];
+
+        // define a schema.
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("_timestamp", DataType::Int64, false),
+            Field::new("ip", DataType::Utf8, false),
+            Field::new("ip_ip128", DataType::Utf8, true),
+        ]));
+
+        // define data, the normalized field is the only one matched after the rewrite
+        let ips = ["10.0.0.1", "10.9.8.7", "11.0.0.1", "2001:db8::1", "unknown"];
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3, 4, 5])),
+                Arc::new(StringArray::from(ips.to_vec())),
+                Arc::new(StringArray::from(
+                    ips.iter()
+                        .map(|ip| {
+                            config::utils::ip::parse_ip(ip).map(config::utils::ip::ip_to_hex128)
+                        })
+                        .collect::<Vec<_>>(),
+                )),
+            ],
+        )
+        .unwrap();
+
+        let state = SessionStateBuilder::new()
+            .with_config(SessionConfig::new())
+            .with_runtime_env(Arc::new(RuntimeEnvBuilder::new().build().unwrap()))
+            .with_default_features()
+            .with_optimizer_rules(vec![Arc::new(RewriteIpInCidr::new(vec!["ip".to_string()]))])
+            .build();
+        let ctx = SessionContext::new_with_state(state);
+        let provider = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+        ctx.register_table("t", Arc::new(provider)).unwrap();
+        ctx.register_udf(ip_udf::IP_IN_CIDR_UDF.clone());
+
+        for item in sqls {
+            let df = ctx.sql(item.0).await.unwrap();
+            let plan = format!(
+                "{}",
+                df.clone().into_optimized_plan().unwrap().display_indent()
+            );
+            assert!(plan.contains("ip_ip128 BETWEEN"), "{plan}");
+            let data = df.collect().await.unwrap();
+            assert_batches_eq!(item.1, &data);
+        }
+    }
+}
@@ -0,0 +1,183 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use datafusion::{
+    common::{
+        Result,
+        tree_node::{Transformed, TreeNode, TreeNodeRewriter},
+    },
+    error::DataFusionError,
+    functions_aggregate::approx_percentile_cont::approx_percentile_cont_udaf,
+    logical_expr::{Expr, LogicalPlan, expr::AggregateFunction},
+    optimizer::{OptimizerConfig, OptimizerRule, optimizer::ApplyOrder, utils::NamePreserver},
+    scalar::ScalarValue,
+};
+
+const PERCENTILE_CONT: &str = "percentile_cont";
+const MEDIAN: &str = "median";
+
+/// Optimization rule that rewrite exact percentile_cont() and median() to the t-digest based
+/// approx_percentile_cont(), the t-digest state can be merged across partitions and time
+/// buckets, so the result of a distributed query doesn't depend on how data was partitioned.
+#[derive(Default, Debug)]
+pub struct RewritePercentile {}
+
+impl RewritePercentile {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for RewritePercentile {
+    fn name(&self) -> &str {
+        "rewrite_percentile"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::BottomUp)
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        _config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        if !matches!(plan, LogicalPlan::Aggregate(_)) {
+            return Ok(Transformed::no(plan));
+        }
+        if plan
+            .expressions()
+            .iter()
+            .any(|expr| expr.exists(|expr| Ok(is_exact_percentile(expr))).unwrap())
+        {
+            let mut expr_rewriter = PercentileToApprox {};
+
+            let name_preserver = NamePreserver::new(&plan);
+            plan.map_expressions(|expr| {
+                let original_name = name_preserver.save(&expr);
+                expr.rewrite(&mut expr_rewriter)
+                    .map(|transformed| transformed.update_data(|e| original_name.restore(e)))
+            })
+        } else {
+            Ok(Transformed::no(plan))
+        }
+    }
+}
+
+// distinct and filtered aggregations are kept as they are, approx_percentile_cont
+// doesn't support them
+fn is_exact_percentile(expr: &Expr) -> bool {
+    matches!(expr, Expr::AggregateFunction(AggregateFunction { func, params })
+        if (func.name() == PERCENTILE_CONT || func.name() == MEDIAN)
+            && !params.distinct
+            && params.filter.is_none())
+}
+
+// Rewriter for percentile_cont() and median() to approx_percentile_cont()
+#[derive(Debug, Clone)]
+pub struct PercentileToApprox {}
+
+impl TreeNodeRewriter for PercentileToApprox {
+    type Node = Expr;
+
+    fn f_up(&mut self, expr: Expr) -> Result<Transformed<Expr>, DataFusionError> {
+        if !is_exact_percentile(&expr) {
+            return Ok(Transformed::no(expr));
+        }
+        let Expr::AggregateFunction(AggregateFunction { func, params }) = &expr else {
+            return Ok(Transformed::no(expr));
+        };
+        let args = if func.name() == MEDIAN {
+            vec![
+                params.args[0].clone(),
+                Expr::Literal(ScalarValue::Float64(Some(0.5))),
+            ]
+        } else {
+            params.args.clone()
+        };
+        Ok(Transformed::yes(approx_percentile_cont_udaf().call(args)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{Float64Array, Int64Array};
+    use arrow_schema::DataType;
+    use datafusion::{
+        arrow::{
+            datatypes::{Field, Schema},
+            record_batch::RecordBatch,
+        },
+        assert_batches_eq,
+        datasource::MemTable,
+        logical_expr::AggregateUDF,
+        prelude::SessionContext,
+    };
+
+    use crate::service::search::datafusion::{
+        optimizer::rewrite_percentile::RewritePercentile, udaf::percentile_cont::PercentileCont,
+    };
+
+    #[tokio::test]
+    async fn test_rewrite_percentile() {
+        let sqls = [
+            (
+                "select percentile_cont(value, 0.5) as p50 from t",
+                vec!["+-----+", "| p50 |", "+-----+", "| 3.0 |", "+-----+"],
+            ),
+            (
+                "select median(value) as p50 from t",
+                vec!["+-----+", "| p50 |", "+-----+", "| 3.0 |", "+-----+"],
+            ),
+        ];
+
+        // define a schema.
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("_timestamp", DataType::Int64, false),
+            Field::new("value", DataType::Float64, false),
+        ]));
+
+        // define data.
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3, 4, 5])),
+                Arc::new(Float64Array::from(vec![1.0, 2.0, 3.0, 4.0, 5.0])),
+            ],
+        )
+        .unwrap();
+
+        let ctx = SessionContext::new();
+        let provider = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+        ctx.register_table("t", Arc::new(provider)).unwrap();
+        ctx.register_udaf(AggregateUDF::from(PercentileCont::new()));
+        ctx.add_optimizer_rule(Arc::new(RewritePercentile::new()));
+
+        for item in sqls {
+            let df = ctx.sql(item.0).await.unwrap();
+            let plan = df.clone().into_optimized_plan().unwrap();
+            assert!(format!("{}", plan.display_indent()).contains("approx_percentile_cont"));
+            let data = df.collect().await.unwrap();
+            assert_batches_eq!(item.1, &data);
+        }
+    }
+}
@@ -0,0 +1,374 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{fmt::Formatter, sync::Arc};
+
+use arrow::{
+    array::{Array, AsArray, RecordBatch},
+    compute::cast,
+    datatypes::{Float64Type, Int64Type},
+};
+use arrow_schema::{Field, Schema};
+use datafusion::{
+    arrow::{array::ArrayRef, datatypes::DataType},
+    common::{internal_err, plan_err},
+    error::Result,
+    logical_expr::{
+        Accumulator, AggregateUDFImpl, ColumnarValue, Signature, TypeSignature, Volatility,
+        function::{AccumulatorArgs, StateFieldsArgs},
+        utils::format_state_name,
+    },
+    physical_plan::PhysicalExpr,
+    scalar::ScalarValue,
+};
+
+const FORECAST: &str = "forecast";
+const HOLT_WINTERS_SMOOTHING_FACTOR: f64 = 0.5;
+const HOLT_WINTERS_TREND_FACTOR: f64 = 0.3;
+
+/// The forecast projects an aggregated series to a future time, used for capacity panels and
+/// alerts such as disk-full or quota-exhaustion projections:
+/// SELECT forecast(used, ts, 86400) AS used_in_one_day
+/// FROM (
+///     SELECT histogram(_timestamp, '1 hour') AS ts, max(disk_used) AS used
+///     FROM default
+///     GROUP BY ts
+/// )
+/// `ts` is a timestamp or microseconds, the horizon is in seconds after the last point of the
+/// series and the optional fourth argument selects the method, `linear` (default) or
+/// `holt_winters`.
+pub(crate) struct Forecast(Signature);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ForecastMethod {
+    Linear,
+    HoltWinters,
+}
+
+impl Forecast {
+    pub fn new() -> Self {
+        Self(Signature::one_of(
+            vec![TypeSignature::Any(3), TypeSignature::Any(4)],
+            Volatility::Immutable,
+        ))
+    }
+}
+
+impl std::fmt::Debug for Forecast {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("Forecast")
+            .field("name", &self.name())
+            .field("signature", &self.0)
+            .finish()
+    }
+}
+
+impl Default for Forecast {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AggregateUDFImpl for Forecast {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        FORECAST
+    }
+
+    fn signature(&self) -> &datafusion::logical_expr::Signature {
+        &self.0
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        if !arg_types[0].is_numeric() {
+            return plan_err!("forecast requires a numeric value");
+        }
+        if !matches!(arg_types[1], DataType::Timestamp(..)) && !arg_types[1].is_integer() {
+            return plan_err!("forecast requires a timestamp or an integer time");
+        }
+        if !arg_types[2].is_numeric() {
+            return plan_err!("forecast requires a numeric horizon in seconds");
+        }
+        Ok(DataType::Float64)
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<Field>> {
+        Ok(vec![
+            Field::new(
+                format_state_name(args.name, "timestamps"),
+                DataType::List(Arc::new(Field::new("item", DataType::Int64, true))),
+                true,
+            ),
+            Field::new(
+                format_state_name(args.name, "values"),
+                DataType::List(Arc::new(Field::new("item", DataType::Float64, true))),
+                true,
+            ),
+        ])
+    }
+
+    fn accumulator(&self, args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        let horizon = match get_scalar_value(&args.exprs[2])? {
+            ScalarValue::Float64(Some(v)) => v,
+            ScalarValue::Float32(Some(v)) => v as f64,
+            ScalarValue::Int64(Some(v)) => v as f64,
+            ScalarValue::Int32(Some(v)) => v as f64,
+            ScalarValue::UInt64(Some(v)) => v as f64,
+            sv => {
+                return plan_err!(
+                    "Horizon for 'FORECAST' must be a numeric literal (got data type {})",
+                    sv.data_type()
+                );
+            }
+        };
+        let method = match args.exprs.get(3).map(get_scalar_value).transpose()? {
+            None => ForecastMethod::Linear,
+            Some(ScalarValue::Utf8(Some(v))) | Some(ScalarValue::LargeUtf8(Some(v))) => {
+                match v.to_lowercase().as_str() {
+                    "linear" => ForecastMethod::Linear,
+                    "holt_winters" => ForecastMethod::HoltWinters,
+                    _ => {
+                        return plan_err!(
+                            "Method for 'FORECAST' must be 'linear' or 'holt_winters', {v} is invalid"
+                        );
+                    }
+                }
+            }
+            Some(sv) => {
+                return plan_err!(
+                    "Method for 'FORECAST' must be a string literal (got data type {})",
+                    sv.data_type()
+                );
+            }
+        };
+        Ok(Box::new(ForecastAccumulator::new(horizon, method)))
+    }
+}
+
+fn get_scalar_value(expr: &Arc<dyn PhysicalExpr>) -> Result<ScalarValue> {
+    let empty_schema = Arc::new(Schema::empty());
+    let batch = RecordBatch::new_empty(Arc::clone(&empty_schema));
+    if let ColumnarValue::Scalar(s) = expr.evaluate(&batch)? {
+        Ok(s)
+    } else {
+        internal_err!("Didn't expect ColumnarValue::Array")
+    }
+}
+
+#[derive(Debug)]
+struct ForecastAccumulator {
+    timestamps: Vec<i64>,
+    values: Vec<f64>,
+    horizon: f64,
+    method: ForecastMethod,
+}
+
+impl ForecastAccumulator {
+    fn new(horizon: f64, method: ForecastMethod) -> Self {
+        Self {
+            timestamps: Vec::new(),
+            values: Vec::new(),
+            horizon,
+            method,
+        }
+    }
+}
+
+impl Accumulator for ForecastAccumulator {
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        let timestamps = ScalarValue::List(ScalarValue::new_list_nullable(
+            &self
+                .timestamps
+                .iter()
+                .map(|v| ScalarValue::Int64(Some(*v)))
+                .collect::<Vec<ScalarValue>>(),
+            &DataType::Int64,
+        ));
+        let values = ScalarValue::List(ScalarValue::new_list_nullable(
+            &self
+                .values
+                .iter()
+                .map(|v| ScalarValue::Float64(Some(*v)))
+                .collect::<Vec<ScalarValue>>(),
+            &DataType::Float64,
+        ));
+        Ok(vec![timestamps, values])
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let mut points = self
+            .timestamps
+            .iter()
+            .copied()
+            .zip(self.values.iter().copied())
+            .collect::<Vec<_>>();
+        Ok(ScalarValue::Float64(forecast(
+            &mut points,
+            self.horizon,
+            self.method,
+        )))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + self.timestamps.capacity() * std::mem::size_of::<i64>()
+            + self.values.capacity() * std::mem::size_of::<f64>()
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let value = cast(&values[0], &DataType::Float64)?;
+        let value = value.as_primitive::<Float64Type>();
+        let ts = cast(&values[1], &DataType::Int64)?;
+        let ts = ts.as_primitive::<Int64Type>();
+        for i in 0..value.len() {
+            if value.is_null(i) || ts.is_null(i) {
+                continue;
+            }
+            self.timestamps.push(ts.value(i));
+            self.values.push(value.value(i));
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        if states.is_empty() {
+            return Ok(());
+        }
+
+        let timestamps = states[0].as_list::<i32>();
+        let values = states[1].as_list::<i32>();
+        for (t, v) in timestamps.iter().flatten().zip(values.iter().flatten()) {
+            self.update_batch(&[v, t])?;
+        }
+        Ok(())
+    }
+}
+
+/// Projects the series `horizon` seconds after its last point, `points` are pairs of
+/// microseconds and values in any order. Returns None when the series has less than two points.
+pub fn forecast(points: &mut [(i64, f64)], horizon: f64, method: ForecastMethod) -> Option<f64> {
+    if points.len() < 2 {
+        return None;
+    }
+    points.sort_by_key(|(ts, _)| *ts);
+    let first_ts = points[0].0;
+    let last_ts = points[points.len() - 1].0;
+    if last_ts == first_ts {
+        return None;
+    }
+    match method {
+        ForecastMethod::Linear => {
+            // least squares on seconds relative to the first point
+            let n = points.len() as f64;
+            let xs = points
+                .iter()
+                .map(|(ts, _)| (ts - first_ts) as f64 / 1_000_000.0);
+            let (sum_x, sum_y, sum_xy, sum_xx) = xs
+                .zip(points.iter())
+                .fold((0.0, 0.0, 0.0, 0.0), |(sx, sy, sxy, sxx), (x, (_, y))| {
+                    (sx + x, sy + y, sxy + x * y, sxx + x * x)
+                });
+            let denominator = n * sum_xx - sum_x * sum_x;
+            if denominator == 0.0 {
+                return None;
+            }
+            let slope = (n * sum_xy - sum_x * sum_y) / denominator;
+            let intercept = (sum_y - slope * sum_x) / n;
+            let target = (last_ts - first_ts) as f64 / 1_000_000.0 + horizon;
+            Some(intercept + slope * target)
+        }
+        ForecastMethod::HoltWinters => {
+            // double exponential smoothing, the trend is per step of the average interval
+            let step = (last_ts - first_ts) as f64 / 1_000_000.0 / (points.len() - 1) as f64;
+            let mut level = points[0].1;
+            let mut trend = points[1].1 - points[0].1;
+            for (_, value) in points[1..].iter() {
+                let prev_level = level;
+                level = HOLT_WINTERS_SMOOTHING_FACTOR * value
+                    + (1.0 - HOLT_WINTERS_SMOOTHING_FACTOR) * (level + trend);
+                trend = HOLT_WINTERS_TREND_FACTOR * (level - prev_level)
+                    + (1.0 - HOLT_WINTERS_TREND_FACTOR) * trend;
+            }
+            Some(level + trend * horizon / step)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use arrow::array::{Float64Array, Int64Array};
+    use datafusion::{
+        common::cast::as_float64_array, datasource::MemTable, logical_expr::AggregateUDF,
+        prelude::SessionContext,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_forecast() {
+        // 10 per minute, starting at 100
+        let mut points = (0..10)
+            .rev()
+            .map(|i| (i * 60_000_000, 100.0 + i as f64 * 10.0))
+            .collect::<Vec<_>>();
+        let linear = forecast(&mut points, 600.0, ForecastMethod::Linear).unwrap();
+        assert!((linear - 290.0).abs() < 1e-9);
+        let holt = forecast(&mut points, 600.0, ForecastMethod::HoltWinters).unwrap();
+        assert!((holt - 290.0).abs() < 1e-9);
+
+        assert_eq!(
+            forecast(&mut [(0, 1.0)], 600.0, ForecastMethod::Linear),
+            None
+        );
+        assert_eq!(
+            forecast(&mut [(0, 1.0), (0, 2.0)], 600.0, ForecastMethod::Linear),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_forecast_udaf() {
+        let ctx = SessionContext::new();
+        let schema = Schema::new(vec![
+            Field::new("ts", DataType::Int64, false),
+            Field::new("used", DataType::Float64, false),
+        ]);
+        let ts: Vec<i64> = (0..24).map(|i| i * 3_600_000_000).collect();
+        let used: Vec<f64> = (0..24).map(|i| 1000.0 + i as f64 * 50.0).collect();
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![
+                Arc::new(Int64Array::from(ts)),
+                Arc::new(Float64Array::from(used)),
+            ],
+        )
+        .unwrap();
+        let table = MemTable::try_new(Arc::new(schema), vec![vec![batch]]).unwrap();
+        ctx.register_table("t", Arc::new(table)).unwrap();
+        ctx.register_udaf(AggregateUDF::from(Forecast::new()));
+
+        let sql =
+            "select forecast(used, ts, 86400), forecast(used, ts, 86400, 'holt_winters') from t";
+        let results = ctx.sql(sql).await.unwrap().collect().await.unwrap();
+        let linear = as_float64_array(results[0].column(0)).unwrap().value(0);
+        let holt = as_float64_array(results[0].column(1)).unwrap().value(0);
+        // the last point is 2150 at 23h, one day later is 24 more hours of 50
+        assert!((linear - 3350.0).abs() < 1e-6);
+        assert!((holt - 3350.0).abs() < 1e-6);
+    }
+}
@@ -15,6 +15,7 @@
 
 use arrow_schema::DataType;
 
+pub mod forecast;
 pub mod percentile_cont;
 pub mod summary_percentile;
 
@@ -0,0 +1,280 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{iter::zip, sync::Arc};
+
+use arrow::array::{BooleanArray, StringArray};
+use config::utils::ip;
+use datafusion::{
+    arrow::{array::ArrayRef, datatypes::DataType},
+    common::cast::as_string_array,
+    error::DataFusionError,
+    logical_expr::{ColumnarValue, ScalarUDF, Volatility},
+    prelude::create_udf,
+    sql::sqlparser::parser::ParserError,
+};
+use once_cell::sync::Lazy;
+
+/// The name of the ip_in_cidr UDF given to DataFusion.
+pub const IP_IN_CIDR_UDF_NAME: &str = "ip_in_cidr";
+/// The name of the ip_is_private UDF given to DataFusion.
+pub const IP_IS_PRIVATE_UDF_NAME: &str = "ip_is_private";
+/// The name of the ip_is_public UDF given to DataFusion.
+pub const IP_IS_PUBLIC_UDF_NAME: &str = "ip_is_public";
+/// The name of the ip_normalize UDF given to DataFusion.
+pub const IP_NORMALIZE_UDF_NAME: &str = "ip_normalize";
+/// The name of the ip_to_hex128 UDF given to DataFusion.
+pub const IP_TO_HEX128_UDF_NAME: &str = "ip_to_hex128";
+/// The name of the ip_from_hex128 UDF given to DataFusion.
+pub const IP_FROM_HEX128_UDF_NAME: &str = "ip_from_hex128";
+
+/// Implementation of ip_in_cidr, `ip_in_cidr(field, '10.0.0.0/8')` returns true if the ip is
+/// inside the cidr, both ipv4 and ipv6 are supported.
+pub(crate) static IP_IN_CIDR_UDF: Lazy<ScalarUDF> = Lazy::new(|| {
+    create_udf(
+        IP_IN_CIDR_UDF_NAME,
+        // expects two string - the ip and the cidr
+        vec![DataType::Utf8, DataType::Utf8],
+        DataType::Boolean,
+        Volatility::Immutable,
+        Arc::new(ip_in_cidr_impl),
+    )
+});
+
+/// Implementation of ip_is_private, returns true for private, loopback, link-local, shared,
+/// multicast and reserved addresses, null if the value is not an ip.
+pub(crate) static IP_IS_PRIVATE_UDF: Lazy<ScalarUDF> = Lazy::new(|| {
+    create_udf(
+        IP_IS_PRIVATE_UDF_NAME,
+        vec![DataType::Utf8],
+        DataType::Boolean,
+        Volatility::Immutable,
+        Arc::new(|args: &[ColumnarValue]| {
+            ip_classify_impl(IP_IS_PRIVATE_UDF_NAME, args, ip::is_private_ip)
+        }),
+    )
+});
+
+/// Implementation of ip_is_public, the opposite of ip_is_private, null if the value is not
+/// an ip.
+pub(crate) static IP_IS_PUBLIC_UDF: Lazy<ScalarUDF> = Lazy::new(|| {
+    create_udf(
+        IP_IS_PUBLIC_UDF_NAME,
+        vec![DataType::Utf8],
+        DataType::Boolean,
+        Volatility::Immutable,
+        Arc::new(|args: &[ColumnarValue]| {
+            ip_classify_impl(IP_IS_PUBLIC_UDF_NAME, args, ip::is_public_ip)
+        }),
+    )
+});
+
+/// Implementation of ip_normalize, returns the canonical text form of the ip, null if the
+/// value is not an ip.
+pub(crate) static IP_NORMALIZE_UDF: Lazy<ScalarUDF> = Lazy::new(|| {
+    create_udf(
+        IP_NORMALIZE_UDF_NAME,
+        vec![DataType::Utf8],
+        DataType::Utf8,
+        Volatility::Immutable,
+        Arc::new(|args: &[ColumnarValue]| {
+            ip_format_impl(IP_NORMALIZE_UDF_NAME, args, ip::normalize_ip)
+        }),
+    )
+});
+
+/// Implementation of ip_to_hex128, returns the 128-bit representation of the ip as a fixed
+/// width hex string which can be compared with the `<field>_ip128` columns.
+pub(crate) static IP_TO_HEX128_UDF: Lazy<ScalarUDF> = Lazy::new(|| {
+    create_udf(
+        IP_TO_HEX128_UDF_NAME,
+        vec![DataType::Utf8],
+        DataType::Utf8,
+        Volatility::Immutable,
+        Arc::new(|args: &[ColumnarValue]| {
+            ip_format_impl(IP_TO_HEX128_UDF_NAME, args, |v| {
+                ip::parse_ip(v).map(ip::ip_to_hex128)
+            })
+        }),
+    )
+});
+
+/// Implementation of ip_from_hex128, formats a `<field>_ip128` value back to an ip.
+pub(crate) static IP_FROM_HEX128_UDF: Lazy<ScalarUDF> = Lazy::new(|| {
+    create_udf(
+        IP_FROM_HEX128_UDF_NAME,
+        vec![DataType::Utf8],
+        DataType::Utf8,
+        Volatility::Immutable,
+        Arc::new(|args: &[ColumnarValue]| {
+            ip_format_impl(IP_FROM_HEX128_UDF_NAME, args, |v| {
+                ip::hex128_to_ip(v).map(|ip| ip.to_string())
+            })
+        }),
+    )
+});
+
+/// ip_in_cidr function for datafusion
+pub fn ip_in_cidr_impl(args: &[ColumnarValue]) -> datafusion::error::Result<ColumnarValue> {
+    if args.len() != 2 {
+        return Err(DataFusionError::SQL(
+            ParserError::ParserError("UDF params should be: ip_in_cidr(field, cidr)".to_string()),
+            None,
+        ));
+    }
+    let args = ColumnarValue::values_to_arrays(args)?;
+    let ips = as_string_array(&args[0])?;
+    let cidrs = as_string_array(&args[1])?;
+
+    let array = zip(ips.iter(), cidrs.iter())
+        .map(|(ip, cidr)| match (ip, cidr) {
+            (Some(ip), Some(cidr)) => Some(ip::ip_in_cidr(ip, cidr)),
+            _ => None,
+        })
+        .collect::<BooleanArray>();
+
+    Ok(ColumnarValue::from(Arc::new(array) as ArrayRef))
+}
+
+fn ip_classify_impl(
+    name: &str,
+    args: &[ColumnarValue],
+    f: fn(std::net::IpAddr) -> bool,
+) -> datafusion::error::Result<ColumnarValue> {
+    if args.len() != 1 {
+        return Err(DataFusionError::SQL(
+            ParserError::ParserError(format!("UDF params should be: {name}(field)")),
+            None,
+        ));
+    }
+    let args = ColumnarValue::values_to_arrays(args)?;
+    let ips = as_string_array(&args[0])?;
+    let array = ips
+        .iter()
+        .map(|ip| ip.and_then(ip::parse_ip).map(f))
+        .collect::<BooleanArray>();
+    Ok(ColumnarValue::from(Arc::new(array) as ArrayRef))
+}
+
+fn ip_format_impl(
+    name: &str,
+    args: &[ColumnarValue],
+    f: impl Fn(&str) -> Option<String>,
+) -> datafusion::error::Result<ColumnarValue> {
+    if args.len() != 1 {
+        return Err(DataFusionError::SQL(
+            ParserError::ParserError(format!("UDF params should be: {name}(field)")),
+            None,
+        ));
+    }
+    let args = ColumnarValue::values_to_arrays(args)?;
+    let values = as_string_array(&args[0])?;
+    let array = values
+        .iter()
+        .map(|v| v.and_then(&f))
+        .collect::<StringArray>();
+    Ok(ColumnarValue::from(Arc::new(array) as ArrayRef))
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::{
+        arrow::{
+            datatypes::{Field, Schema},
+            record_batch::RecordBatch,
+        },
+        assert_batches_eq,
+        datasource::MemTable,
+        prelude::SessionContext,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_ip_udf() {
+        let sqls = [
+            (
+                "select ip, ip_in_cidr(ip, '10.0.0.0/8') as v from t",
+                vec![
+                    "+-------------+-------+",
+                    "| ip          | v     |",
+                    "+-------------+-------+",
+                    "| 10.1.2.3    | true  |",
+                    "| 8.8.8.8     | false |",
+                    "| 2001:DB8::1 | false |",
+                    "| invalid     | false |",
+                    "+-------------+-------+",
+                ],
+            ),
+            (
+                "select ip, ip_is_private(ip) as private, ip_is_public(ip) as public from t",
+                vec![
+                    "+-------------+---------+--------+",
+                    "| ip          | private | public |",
+                    "+-------------+---------+--------+",
+                    "| 10.1.2.3    | true    | false  |",
+                    "| 8.8.8.8     | false   | true   |",
+                    "| 2001:DB8::1 | false   | true   |",
+                    "| invalid     |         |        |",
+                    "+-------------+---------+--------+",
+                ],
+            ),
+            (
+                "select ip_normalize(ip) as v, ip_from_hex128(ip_to_hex128(ip)) as r from t",
+                vec![
+                    "+-------------+-------------+",
+                    "| v           | r           |",
+                    "+-------------+-------------+",
+                    "| 10.1.2.3    | 10.1.2.3    |",
+                    "| 8.8.8.8     | 8.8.8.8     |",
+                    "| 2001:db8::1 | 2001:db8::1 |",
+                    "|             |             |",
+                    "+-------------+-------------+",
+                ],
+            ),
+        ];
+
+        // define a schema.
+        let schema = Arc::new(Schema::new(vec![Field::new("ip", DataType::Utf8, false)]));
+
+        // define data.
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(StringArray::from(vec![
+                "10.1.2.3",
+                "8.8.8.8",
+                "2001:DB8::1",
+                "invalid",
+            ]))],
+        )
+        .unwrap();
+
+        let ctx = SessionContext::new();
+        let provider = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+        ctx.register_table("t", Arc::new(provider)).unwrap();
+        ctx.register_udf(IP_IN_CIDR_UDF.clone());
+        ctx.register_udf(IP_IS_PRIVATE_UDF.clone());
+        ctx.register_udf(IP_IS_PUBLIC_UDF.clone());
+        ctx.register_udf(IP_NORMALIZE_UDF.clone());
+        ctx.register_udf(IP_TO_HEX128_UDF.clone());
+        ctx.register_udf(IP_FROM_HEX128_UDF.clone());
+
+        for item in sqls {
+            let df = ctx.sql(item.0).await.unwrap();
+            let data = df.collect().await.unwrap();
+            assert_batches_eq!(item.1, &data);
+        }
+    }
+}
@@ -28,7 +28,9 @@ pub(crate) mod cipher_udf;
 pub(crate) mod date_format_udf;
 pub(crate) mod fuzzy_match_udf;
 pub(crate) mod histogram_udf;
+pub(crate) mod ip_udf;
 pub(crate) mod match_all_udf;
+pub(crate) mod parse_udf;
 pub(crate) mod regexp_matches_udf;
 pub(crate) mod regexp_udf;
 pub(crate) mod spath_udf;
@@ -0,0 +1,318 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{any::Any, sync::Arc};
+
+use arrow::array::{Array, ArrayRef, MapBuilder, StringArray, StringBuilder};
+use config::utils::json;
+use datafusion::{
+    arrow::datatypes::{
+        DataType::{self, *},
+        Field, Fields,
+    },
+    common::{ScalarValue, cast::as_string_array},
+    error::Result,
+    logical_expr::{
+        ColumnarValue, ScalarFunctionArgs, ScalarUDF, ScalarUDFImpl, Signature, TypeSignature,
+        Volatility,
+    },
+};
+use once_cell::sync::Lazy;
+
+use crate::common::utils::parsers;
+
+/// The name of the parse_user_agent UDF given to DataFusion.
+pub const PARSE_USER_AGENT_UDF_NAME: &str = "parse_user_agent";
+/// The name of the parse_url UDF given to DataFusion.
+pub const PARSE_URL_UDF_NAME: &str = "parse_url";
+/// The name of the parse_logfmt UDF given to DataFusion.
+pub const PARSE_LOGFMT_UDF_NAME: &str = "parse_logfmt";
+
+/// Implementation of parse_user_agent
+pub(crate) static PARSE_USER_AGENT_UDF: Lazy<ScalarUDF> = Lazy::new(|| {
+    ScalarUDF::from(ParseFunc::new(PARSE_USER_AGENT_UDF_NAME, |v| {
+        Some(parsers::parse_user_agent(v))
+    }))
+});
+
+/// Implementation of parse_url
+pub(crate) static PARSE_URL_UDF: Lazy<ScalarUDF> =
+    Lazy::new(|| ScalarUDF::from(ParseFunc::new(PARSE_URL_UDF_NAME, parsers::parse_url)));
+
+/// Implementation of parse_logfmt
+pub(crate) static PARSE_LOGFMT_UDF: Lazy<ScalarUDF> =
+    Lazy::new(|| ScalarUDF::from(ParseFunc::new(PARSE_LOGFMT_UDF_NAME, parsers::parse_logfmt)));
+
+/// # `parse_user_agent`, `parse_url` and `parse_logfmt` User-Defined Functions (UDF)
+///
+/// Parse a user agent, an url or a `key=value` message at query time, using the same parsers as
+/// the `o2_parse_user_agent` and `o2_parse_url` VRL functions and the stream key-value extraction.
+///
+/// ## Function Signature
+///
+/// - `parse_user_agent(field)` / `parse_url(field)` / `parse_logfmt(field)`: returns the whole
+///   parsed map, its keys are read with a subscript, e.g. `parse_url(url)['host']`
+/// - `parse_user_agent(field, 'os.family')` / `parse_url(field, 'query.id')` / `parse_logfmt(field,
+///   'level')`: returns a single value of the parsed map, addressed by a dotted path
+///
+/// ## Return Type
+///
+/// - Without a path, a `Map<Utf8, Utf8>` of the top level keys, as the keys of the url query and of
+///   logfmt messages differ from row to row and have no fixed struct type.
+/// - With a path, `Utf8`.
+///
+/// Strings are returned as they are, numbers and booleans as their text, e.g. `'443'`, and
+/// objects and arrays as json strings, e.g. `parse_url(url)['query']`. The value is null if the
+/// input or the path is null, the input can't be parsed, or the key doesn't exist or holds null.
+#[derive(Debug)]
+pub struct ParseFunc {
+    name: &'static str,
+    signature: Signature,
+    parser: fn(&str) -> Option<json::Value>,
+}
+
+impl ParseFunc {
+    pub fn new(name: &'static str, parser: fn(&str) -> Option<json::Value>) -> Self {
+        Self {
+            name,
+            signature: Signature::one_of(
+                vec![
+                    TypeSignature::Exact(vec![Utf8]),
+                    TypeSignature::Exact(vec![Utf8, Utf8]),
+                ],
+                Volatility::Immutable,
+            ),
+            parser,
+        }
+    }
+}
+
+impl ScalarUDFImpl for ParseFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        if arg_types.len() > 1 {
+            Ok(Utf8)
+        } else {
+            Ok(map_type())
+        }
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let len = args
+            .args
+            .iter()
+            .fold(Option::<usize>::None, |acc, arg| match arg {
+                ColumnarValue::Scalar(_) => acc,
+                ColumnarValue::Array(a) => Some(a.len()),
+            });
+
+        let is_scalar = len.is_none();
+        let inferred_length = len.unwrap_or(1);
+        let args = args
+            .args
+            .iter()
+            .map(|arg| arg.clone().into_array(inferred_length))
+            .collect::<Result<Vec<_>>>()?;
+
+        let values = as_string_array(&args[0])?;
+        let paths = match args.get(1) {
+            Some(paths) => Some(as_string_array(paths)?),
+            None => None,
+        };
+
+        let parse = |i: usize| {
+            if values.is_null(i) {
+                None
+            } else {
+                (self.parser)(values.value(i))
+            }
+        };
+        let result: ArrayRef = match paths {
+            Some(paths) => Arc::new(
+                (0..values.len())
+                    .map(|i| {
+                        if paths.is_null(i) {
+                            return None;
+                        }
+                        parsers::get_path(&parse(i)?, paths.value(i))
+                            .filter(|v| !v.is_null())
+                            .map(json::get_string_value)
+                    })
+                    .collect::<StringArray>(),
+            ),
+            None => {
+                let mut builder = MapBuilder::new(None, StringBuilder::new(), StringBuilder::new());
+                for i in 0..values.len() {
+                    match parse(i) {
+                        Some(json::Value::Object(map)) => {
+                            for (key, value) in map.iter() {
+                                builder.keys().append_value(key);
+                                if value.is_null() {
+                                    builder.values().append_null();
+                                } else {
+                                    builder.values().append_value(json::get_string_value(value));
+                                }
+                            }
+                            builder.append(true)?;
+                        }
+                        _ => builder.append(false)?,
+                    }
+                }
+                Arc::new(builder.finish())
+            }
+        };
+
+        if is_scalar {
+            let scaler = ScalarValue::try_from_array(&result, 0)?;
+            Ok(ColumnarValue::Scalar(scaler))
+        } else {
+            Ok(ColumnarValue::Array(Arc::new(result)))
+        }
+    }
+}
+
+/// The type of the parsed maps, the same as built by the default [`MapBuilder`].
+fn map_type() -> DataType {
+    let entries = Fields::from(vec![
+        Field::new("keys", Utf8, false),
+        Field::new("values", Utf8, true),
+    ]);
+    Map(
+        Arc::new(Field::new("entries", Struct(entries), false)),
+        false,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::{
+        arrow::{
+            datatypes::{Field, Schema},
+            record_batch::RecordBatch,
+        },
+        assert_batches_eq,
+        datasource::MemTable,
+        prelude::SessionContext,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_parse_udf() {
+        let sqls = [
+            (
+                "select parse_url(url, 'host') as host, parse_url(url, 'query.q') as q from t",
+                vec![
+                    "+----------------+-----+",
+                    "| host           | q   |",
+                    "+----------------+-----+",
+                    "| openobserve.ai | sql |",
+                    "|                |     |",
+                    "+----------------+-----+",
+                ],
+            ),
+            (
+                "select parse_url(url, 'port') as port, parse_url(url, 'query') as query from t",
+                vec![
+                    "+------+-------------+",
+                    "| port | query       |",
+                    "+------+-------------+",
+                    "| 443  | {\"q\":\"sql\"} |",
+                    "|      |             |",
+                    "+------+-------------+",
+                ],
+            ),
+            (
+                "select parse_user_agent(ua, 'user_agent.family') as browser from t",
+                vec![
+                    "+---------+",
+                    "| browser |",
+                    "+---------+",
+                    "| Chrome  |",
+                    "| curl    |",
+                    "+---------+",
+                ],
+            ),
+        ];
+
+        // define a schema.
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("url", DataType::Utf8, false),
+            Field::new("ua", DataType::Utf8, false),
+        ]));
+
+        // define data.
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec![
+                    "https://openobserve.ai/docs?q=sql",
+                    "invalid url",
+                ])),
+                Arc::new(StringArray::from(vec![
+                    "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+                    "curl/8.5.0",
+                ])),
+            ],
+        )
+        .unwrap();
+
+        let ctx = SessionContext::new();
+        let provider = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+        ctx.register_table("t", Arc::new(provider)).unwrap();
+        ctx.register_udf(PARSE_URL_UDF.clone());
+        ctx.register_udf(PARSE_USER_AGENT_UDF.clone());
+
+        for item in sqls {
+            let df = ctx.sql(item.0).await.unwrap();
+            let data = df.collect().await.unwrap();
+            assert_batches_eq!(item.1, &data);
+        }
+
+        // without a path, the parsed map is returned and its keys read with a subscript
+        let df = ctx.sql("select parse_url(url) as u from t").await.unwrap();
+        let data = df.collect().await.unwrap();
+        assert_eq!(data[0].schema().field(0).data_type(), &map_type());
+        assert!(data[0].column(0).is_null(1));
+
+        let df = ctx
+            .sql("select parse_url(url)['host'] as host, parse_url(url)['port'] as port, parse_url(url)['query'] as query from t")
+            .await
+            .unwrap();
+        let data = df.collect().await.unwrap();
+        assert_batches_eq!(
+            vec![
+                "+----------------+------+-------------+",
+                "| host           | port | query       |",
+                "+----------------+------+-------------+",
+                "| openobserve.ai | 443  | {\"q\":\"sql\"} |",
+                "|                |      |             |",
+                "+----------------+------+-------------+",
+            ],
+            &data
+        );
+    }
+}
@@ -0,0 +1,362 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{any::Any, fmt::Formatter, sync::Arc};
+
+use arrow::{
+    array::{Array, ArrayRef, AsArray, Float64Array, Int64Array},
+    compute::cast,
+    datatypes::{DataType, Float64Type, Int64Type, TimeUnit},
+};
+use arrow_schema::Field;
+use datafusion::{
+    common::{exec_err, plan_err},
+    error::Result,
+    logical_expr::{
+        PartitionEvaluator, Signature, TypeSignature, Volatility, WindowUDFImpl,
+        function::{PartitionEvaluatorArgs, WindowUDFFieldArgs},
+    },
+};
+
+/// The name of the delta window function given to DataFusion.
+pub(crate) const DELTA_UDWF_NAME: &str = "delta";
+/// The name of the increase window function given to DataFusion.
+pub(crate) const INCREASE_UDWF_NAME: &str = "increase";
+/// The name of the rate_per_sec window function given to DataFusion.
+pub(crate) const RATE_PER_SEC_UDWF_NAME: &str = "rate_per_sec";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CounterFunc {
+    /// difference with the previous bucket
+    Delta,
+    /// difference with the previous bucket, a decrease is treated as a counter reset
+    Increase,
+    /// value of the bucket divided by the bucket width in seconds
+    RatePerSec,
+}
+
+impl CounterFunc {
+    fn name(&self) -> &'static str {
+        match self {
+            CounterFunc::Delta => DELTA_UDWF_NAME,
+            CounterFunc::Increase => INCREASE_UDWF_NAME,
+            CounterFunc::RatePerSec => RATE_PER_SEC_UDWF_NAME,
+        }
+    }
+}
+
+/// Window functions to build counter-rate panels from log-derived counts, they take the
+/// time bucket and optionally the bucket width in seconds, and expect the window to be
+/// ordered by the time bucket:
+///
+/// ```sql
+/// SELECT histogram(_timestamp, '10 second') AS ts, count(*) AS cnt,
+///     rate_per_sec(cnt, ts, 10) OVER (ORDER BY ts) AS rate,
+///     delta(cnt, ts, 10) OVER (ORDER BY ts) AS delta,
+///     increase(cnt, ts, 10) OVER (ORDER BY ts) AS increase
+/// FROM logs GROUP BY ts ORDER BY ts
+/// ```
+///
+/// Without the width, the smallest gap between two buckets of the partition is used.
+/// `delta` and `increase` are null when the previous bucket is missing because it had no
+/// data, instead of reporting the difference over several buckets as if it was one.
+pub(crate) struct CounterUdwf {
+    func: CounterFunc,
+    signature: Signature,
+}
+
+impl CounterUdwf {
+    pub fn delta() -> Self {
+        Self::new(CounterFunc::Delta)
+    }
+
+    pub fn increase() -> Self {
+        Self::new(CounterFunc::Increase)
+    }
+
+    pub fn rate_per_sec() -> Self {
+        Self::new(CounterFunc::RatePerSec)
+    }
+
+    fn new(func: CounterFunc) -> Self {
+        Self {
+            func,
+            signature: Signature::one_of(
+                vec![TypeSignature::Any(2), TypeSignature::Any(3)],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl std::fmt::Debug for CounterUdwf {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("CounterUdwf")
+            .field("name", &self.name())
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+impl WindowUDFImpl for CounterUdwf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        self.func.name()
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn partition_evaluator(
+        &self,
+        _partition_evaluator_args: PartitionEvaluatorArgs,
+    ) -> Result<Box<dyn PartitionEvaluator>> {
+        Ok(Box::new(CounterEvaluator { func: self.func }))
+    }
+
+    fn field(&self, field_args: WindowUDFFieldArgs) -> Result<Field> {
+        let input_types = field_args.input_types();
+        if !input_types[0].is_numeric() && !input_types[0].is_null() {
+            return plan_err!("{} requires a numeric input", self.name());
+        }
+        if !matches!(input_types[1], DataType::Timestamp(_, _) | DataType::Int64) {
+            return plan_err!(
+                "{} requires the time bucket as second argument",
+                self.name()
+            );
+        }
+        if input_types.get(2).is_some_and(|t| !t.is_numeric()) {
+            return plan_err!(
+                "{} requires the bucket width in seconds as third argument",
+                self.name()
+            );
+        }
+        Ok(Field::new(field_args.name(), DataType::Float64, true))
+    }
+}
+
+#[derive(Debug)]
+struct CounterEvaluator {
+    func: CounterFunc,
+}
+
+impl PartitionEvaluator for CounterEvaluator {
+    fn evaluate_all(&mut self, args: &[ArrayRef], num_rows: usize) -> Result<ArrayRef> {
+        if args.len() < 2 {
+            return exec_err!(
+                "{} requires the time bucket as second argument",
+                self.func.name()
+            );
+        }
+        let values = cast(&args[0], &DataType::Float64)?;
+        let values = values.as_primitive::<Float64Type>();
+        let times = bucket_times(&args[1])?;
+        let times = times.as_primitive::<Int64Type>();
+        let width = match args.get(2) {
+            Some(width) => given_bucket_width(width)?,
+            None => min_bucket_width(times),
+        };
+        let Some(width) = width else {
+            return Ok(Arc::new(Float64Array::new_null(num_rows)));
+        };
+        let result: Float64Array = match self.func {
+            CounterFunc::Delta => (0..num_rows)
+                .map(|i| diff(values, times, width, i).map(|(prev, cur)| cur - prev))
+                .collect(),
+            CounterFunc::Increase => (0..num_rows)
+                .map(|i| {
+                    diff(values, times, width, i)
+                        .map(|(prev, cur)| if cur < prev { cur } else { cur - prev })
+                })
+                .collect(),
+            CounterFunc::RatePerSec => {
+                let width = width as f64 / 1_000_000.0;
+                values.iter().map(|v| v.map(|v| v / width)).collect()
+            }
+        };
+        Ok(Arc::new(result))
+    }
+}
+
+// returns the previous and the current value if both are not null and the previous
+// bucket is the adjacent one
+fn diff(values: &Float64Array, times: &Int64Array, width: i64, i: usize) -> Option<(f64, f64)> {
+    if i == 0 || values.is_null(i) || values.is_null(i - 1) {
+        return None;
+    }
+    if times.is_null(i) || times.is_null(i - 1) || times.value(i) - times.value(i - 1) != width {
+        return None;
+    }
+    Some((values.value(i - 1), values.value(i)))
+}
+
+// the time buckets as microseconds
+fn bucket_times(times: &ArrayRef) -> Result<ArrayRef> {
+    let times = match times.data_type() {
+        DataType::Timestamp(TimeUnit::Microsecond, None) | DataType::Int64 => times.clone(),
+        _ => cast(times, &DataType::Timestamp(TimeUnit::Microsecond, None))?,
+    };
+    Ok(cast(&times, &DataType::Int64)?)
+}
+
+// the bucket width in microseconds given in seconds as third argument
+fn given_bucket_width(width: &ArrayRef) -> Result<Option<i64>> {
+    let width = cast(width, &DataType::Float64)?;
+    let width = width.as_primitive::<Float64Type>();
+    match width.iter().flatten().next() {
+        Some(width) if width > 0.0 => Ok(Some((width * 1_000_000.0) as i64)),
+        Some(_) => exec_err!("the bucket width must be a positive number of seconds"),
+        None => Ok(None),
+    }
+}
+
+// the bucket width is the smallest positive gap between two consecutive buckets,
+// returns None when there are less than two buckets
+fn min_bucket_width(times: &Int64Array) -> Option<i64> {
+    times
+        .iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .windows(2)
+        .map(|w| (w[1] - w[0]).abs())
+        .filter(|gap| *gap > 0)
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{Float64Array, Int64Array};
+    use arrow_schema::DataType;
+    use datafusion::{
+        arrow::{
+            datatypes::{Field, Schema},
+            record_batch::RecordBatch,
+        },
+        assert_batches_eq,
+        datasource::MemTable,
+        logical_expr::WindowUDF,
+        prelude::SessionContext,
+    };
+
+    use super::CounterUdwf;
+
+    #[tokio::test]
+    async fn test_counter_udwf() {
+        let sqls = [
+            (
+                "select ts, delta(cnt, ts) over (order by ts) as v from t order by ts",
+                vec![
+                    "+----------+-----+",
+                    "| ts       | v   |",
+                    "+----------+-----+",
+                    "| 0        |     |",
+                    "| 10000000 | 5.0 |",
+                    "| 30000000 |     |",
+                    "| 40000000 | 8.0 |",
+                    "+----------+-----+",
+                ],
+            ),
+            (
+                "select ts, increase(cnt, ts) over (order by ts) as v from t order by ts",
+                vec![
+                    "+----------+-----+",
+                    "| ts       | v   |",
+                    "+----------+-----+",
+                    "| 0        |     |",
+                    "| 10000000 | 5.0 |",
+                    "| 30000000 |     |",
+                    "| 40000000 | 8.0 |",
+                    "+----------+-----+",
+                ],
+            ),
+            (
+                "select ts, delta(cnt, ts, 20) over (order by ts) as v from t order by ts",
+                vec![
+                    "+----------+------+",
+                    "| ts       | v    |",
+                    "+----------+------+",
+                    "| 0        |      |",
+                    "| 10000000 |      |",
+                    "| 30000000 | -5.0 |",
+                    "| 40000000 |      |",
+                    "+----------+------+",
+                ],
+            ),
+            (
+                "select ts, rate_per_sec(cnt, ts) over (order by ts) as v from t order by ts",
+                vec![
+                    "+----------+-----+",
+                    "| ts       | v   |",
+                    "+----------+-----+",
+                    "| 0        | 1.0 |",
+                    "| 10000000 | 1.5 |",
+                    "| 30000000 | 1.0 |",
+                    "| 40000000 | 1.8 |",
+                    "+----------+-----+",
+                ],
+            ),
+            (
+                "select ts, rate_per_sec(cnt, ts, 5) over (order by ts) as v from t order by ts",
+                vec![
+                    "+----------+-----+",
+                    "| ts       | v   |",
+                    "+----------+-----+",
+                    "| 0        | 2.0 |",
+                    "| 10000000 | 3.0 |",
+                    "| 30000000 | 2.0 |",
+                    "| 40000000 | 3.6 |",
+                    "+----------+-----+",
+                ],
+            ),
+        ];
+
+        // define a schema.
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("ts", DataType::Int64, false),
+            Field::new("cnt", DataType::Float64, false),
+        ]));
+
+        // define data, the bucket at 20s is missing
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![
+                    0, 10_000_000, 30_000_000, 40_000_000,
+                ])),
+                Arc::new(Float64Array::from(vec![10.0, 15.0, 10.0, 18.0])),
+            ],
+        )
+        .unwrap();
+
+        let ctx = SessionContext::new();
+        let provider = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+        ctx.register_table("t", Arc::new(provider)).unwrap();
+        ctx.register_udwf(WindowUDF::from(CounterUdwf::delta()));
+        ctx.register_udwf(WindowUDF::from(CounterUdwf::increase()));
+        ctx.register_udwf(WindowUDF::from(CounterUdwf::rate_per_sec()));
+
+        for item in sqls {
+            let df = ctx.sql(item.0).await.unwrap();
+            let data = df.collect().await.unwrap();
+            assert_batches_eq!(item.1, &data);
+        }
+    }
+}
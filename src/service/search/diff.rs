@@ -0,0 +1,239 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Compares the results of one query over two time ranges or two sets of variables.
+
+use std::cmp::Ordering;
+
+use config::{
+    TIMESTAMP_COL_NAME,
+    meta::search::{DiffChange, DiffGroup, SearchDiffResponse},
+    utils::json::{Map, Value},
+};
+use hashbrown::HashMap;
+
+/// Computes the diff of two query results. Rows are matched on the `group_by` columns, the
+/// `value_fields` of matched rows are compared. Compared values are multiplied by `scale`.
+pub fn compute_diff(
+    base: &[Value],
+    compare: &[Value],
+    group_by: &[String],
+    value_fields: &[String],
+    scale: f64,
+    top: usize,
+) -> SearchDiffResponse {
+    let (group_by, value_fields) = if group_by.is_empty() || value_fields.is_empty() {
+        let (keys, values) = detect_columns(base.iter().chain(compare.iter()));
+        (
+            if group_by.is_empty() {
+                keys
+            } else {
+                group_by.to_vec()
+            },
+            if value_fields.is_empty() {
+                values
+                    .into_iter()
+                    .filter(|f| !group_by.contains(f))
+                    .collect()
+            } else {
+                value_fields.to_vec()
+            },
+        )
+    } else {
+        (group_by.to_vec(), value_fields.to_vec())
+    };
+
+    let base_groups = group_rows(base, &group_by);
+    let compare_groups = group_rows(compare, &group_by);
+
+    let mut resp = SearchDiffResponse {
+        base_total: base.len(),
+        compare_total: compare.len(),
+        ..Default::default()
+    };
+    for (key, (keys, row)) in base_groups.iter() {
+        let Some((_, compare_row)) = compare_groups.get(key) else {
+            resp.removed.push(DiffGroup {
+                keys: keys.clone(),
+                values: pick(row, &value_fields, 1.0),
+            });
+            continue;
+        };
+        for field in value_fields.iter() {
+            let base_value = number(row.get(field));
+            let compare_value = number(compare_row.get(field)) * scale;
+            let delta = compare_value - base_value;
+            if delta == 0.0 {
+                continue;
+            }
+            resp.changes.push(DiffChange {
+                keys: keys.clone(),
+                field: field.clone(),
+                base: base_value,
+                compare: compare_value,
+                delta,
+                delta_percent: (base_value != 0.0).then(|| delta / base_value.abs() * 100.0),
+                score: significance(base_value, compare_value),
+            });
+        }
+    }
+    for (key, (keys, row)) in compare_groups.iter() {
+        if !base_groups.contains_key(key) {
+            resp.added.push(DiffGroup {
+                keys: keys.clone(),
+                values: pick(row, &value_fields, scale),
+            });
+        }
+    }
+
+    resp.changes.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| b.delta.abs().total_cmp(&a.delta.abs()))
+    });
+    if top > 0 {
+        resp.changes.truncate(top);
+    }
+    resp.added
+        .sort_by_key(|g| Value::Object(g.keys.clone()).to_string());
+    resp.removed
+        .sort_by_key(|g| Value::Object(g.keys.clone()).to_string());
+    resp
+}
+
+/// Size of a change relative to the expected noise: the delta divided by the standard
+/// deviation two Poisson distributed counts with these values would have.
+fn significance(base: f64, compare: f64) -> f64 {
+    (compare - base).abs() / (base.abs() + compare.abs() + 1.0).sqrt()
+}
+
+/// Splits the columns of the rows into non numeric group columns and numeric value columns.
+pub(crate) fn detect_columns<'a>(
+    rows: impl Iterator<Item = &'a Value>,
+) -> (Vec<String>, Vec<String>) {
+    let mut keys = vec![];
+    let mut values = vec![];
+    for row in rows {
+        let Some(row) = row.as_object() else {
+            continue;
+        };
+        for (name, value) in row {
+            if name == TIMESTAMP_COL_NAME || value.is_null() {
+                continue;
+            }
+            if keys.contains(name) || values.contains(name) {
+                continue;
+            }
+            if value.is_number() {
+                values.push(name.clone());
+            } else {
+                keys.push(name.clone());
+            }
+        }
+    }
+    (keys, values)
+}
+
+/// Indexes the rows by the values of the group columns, later duplicates are ignored.
+fn group_rows<'a>(
+    rows: &'a [Value],
+    group_by: &[String],
+) -> HashMap<String, (Map<String, Value>, &'a Map<String, Value>)> {
+    let mut groups = HashMap::with_capacity(rows.len());
+    for row in rows {
+        let Some(row) = row.as_object() else {
+            continue;
+        };
+        let keys = pick(row, group_by, 1.0);
+        let key = Value::Object(keys.clone()).to_string();
+        groups.entry(key).or_insert((keys, row));
+    }
+    groups
+}
+
+fn pick(row: &Map<String, Value>, fields: &[String], scale: f64) -> Map<String, Value> {
+    fields
+        .iter()
+        .map(|f| {
+            let value = row.get(f).cloned().unwrap_or(Value::Null);
+            let value = match value.as_f64() {
+                Some(v) if scale != 1.0 => Value::from(v * scale),
+                _ => value,
+            };
+            (f.clone(), value)
+        })
+        .collect()
+}
+
+fn number(value: Option<&Value>) -> f64 {
+    value
+        .map(config::utils::json::get_float_value)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use config::utils::json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_compute_diff() {
+        let base = vec![
+            json!({"service": "api", "code": 500, "cnt": 10}),
+            json!({"service": "web", "code": 500, "cnt": 1000}),
+            json!({"service": "db", "code": 500, "cnt": 5}),
+        ];
+        let compare = vec![
+            json!({"service": "api", "code": 500, "cnt": 100}),
+            json!({"service": "web", "code": 500, "cnt": 1100}),
+            json!({"service": "auth", "code": 500, "cnt": 7}),
+        ];
+        let group_by = vec!["service".to_string(), "code".to_string()];
+        let resp = compute_diff(&base, &compare, &group_by, &[], 1.0, 0);
+        assert_eq!(resp.base_total, 3);
+        assert_eq!(resp.added.len(), 1);
+        assert_eq!(resp.added[0].keys["service"], "auth");
+        assert_eq!(resp.added[0].values["cnt"], 7);
+        assert_eq!(resp.removed.len(), 1);
+        assert_eq!(resp.removed[0].keys["service"], "db");
+        // 10 -> 100 is more significant than 1000 -> 1100
+        assert_eq!(resp.changes.len(), 2);
+        assert_eq!(resp.changes[0].keys["service"], "api");
+        assert_eq!(resp.changes[0].field, "cnt");
+        assert_eq!(resp.changes[0].delta, 90.0);
+        assert_eq!(resp.changes[0].delta_percent, Some(900.0));
+        assert_eq!(resp.changes[1].keys["service"], "web");
+
+        // halving the compared values turns web into the largest change
+        let resp = compute_diff(&base, &compare, &group_by, &[], 0.5, 1);
+        assert_eq!(resp.changes.len(), 1);
+        assert_eq!(resp.changes[0].keys["service"], "web");
+        assert_eq!(resp.changes[0].compare, 550.0);
+    }
+
+    #[test]
+    fn test_detect_columns() {
+        let base = vec![json!({"service": "api", "cnt": 10, "_timestamp": 1})];
+        let compare = vec![json!({"service": "api", "cnt": 12, "_timestamp": 2})];
+        let resp = compute_diff(&base, &compare, &[], &[], 1.0, 0);
+        assert!(resp.added.is_empty());
+        assert!(resp.removed.is_empty());
+        assert_eq!(resp.changes.len(), 1);
+        assert_eq!(resp.changes[0].field, "cnt");
+        assert_eq!(resp.changes[0].delta, 2.0);
+    }
+}
@@ -0,0 +1,270 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Downsampling of time-series results to the number of points a panel can render.
+//!
+//! Series with a single value column use Largest-Triangle-Three-Buckets, which keeps the visual
+//! shape of a line. Series with several value columns use M4, which keeps the first, last,
+//! minimum and maximum row of every time bucket so no column loses its extremes.
+
+use config::{
+    TIMESTAMP_COL_NAME,
+    utils::{
+        json::{Value, get_float_value},
+        time::parse_timestamp_micro_from_value,
+    },
+};
+use hashbrown::HashMap;
+
+use super::diff::detect_columns;
+
+/// Downsamples every series of the hits to at most `max_points` rows. Series are identified by
+/// the non numeric columns, `ts_column` (or `_timestamp`) is the time axis. Returns None when
+/// the hits are not a time series or nothing needs to be dropped. The kept rows stay in their
+/// original order.
+pub fn downsample(hits: &[Value], ts_column: &str, max_points: usize) -> Option<Vec<Value>> {
+    if max_points == 0 || hits.len() <= max_points {
+        return None;
+    }
+    let ts_column = if ts_column.is_empty() {
+        TIMESTAMP_COL_NAME
+    } else {
+        ts_column
+    };
+    let (keys, values) = detect_columns(hits.iter());
+    let keys = keys
+        .into_iter()
+        .filter(|k| k != ts_column)
+        .collect::<Vec<_>>();
+    let values = values
+        .into_iter()
+        .filter(|v| v != ts_column)
+        .collect::<Vec<_>>();
+    if values.is_empty() {
+        return None;
+    }
+
+    // row indexes of every series
+    let mut series: HashMap<String, Vec<usize>> = HashMap::new();
+    let mut timestamps = Vec::with_capacity(hits.len());
+    for (i, hit) in hits.iter().enumerate() {
+        let Some(ts) = hit
+            .get(ts_column)
+            .and_then(|v| parse_timestamp_micro_from_value(v).ok())
+        else {
+            return None;
+        };
+        timestamps.push(ts);
+        let key = keys
+            .iter()
+            .map(|k| hit.get(k).map(|v| v.to_string()).unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join("\u{1}");
+        series.entry(key).or_default().push(i);
+    }
+
+    let mut keep = vec![false; hits.len()];
+    let mut dropped = false;
+    for mut rows in series.into_values() {
+        if rows.len() <= max_points {
+            rows.into_iter().for_each(|i| keep[i] = true);
+            continue;
+        }
+        dropped = true;
+        rows.sort_by_key(|i| timestamps[*i]);
+        let selected = if values.len() == 1 {
+            let points = rows
+                .iter()
+                .map(|i| {
+                    (
+                        timestamps[*i] as f64,
+                        hits[*i]
+                            .get(&values[0])
+                            .map(get_float_value)
+                            .unwrap_or_default(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            lttb(&points, max_points)
+        } else {
+            let columns = values
+                .iter()
+                .map(|v| {
+                    rows.iter()
+                        .map(|i| hits[*i].get(v).map(get_float_value).unwrap_or_default())
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let ts = rows.iter().map(|i| timestamps[*i]).collect::<Vec<_>>();
+            m4(&ts, &columns, max_points)
+        };
+        selected.into_iter().for_each(|i| keep[rows[i]] = true);
+    }
+    if !dropped {
+        return None;
+    }
+    Some(
+        hits.iter()
+            .zip(keep)
+            .filter_map(|(hit, keep)| keep.then(|| hit.clone()))
+            .collect(),
+    )
+}
+
+/// Largest-Triangle-Three-Buckets over points sorted by x, returns the indexes of the
+/// `threshold` selected points.
+pub fn lttb(points: &[(f64, f64)], threshold: usize) -> Vec<usize> {
+    let len = points.len();
+    if threshold >= len {
+        return (0..len).collect();
+    }
+    if threshold < 3 {
+        // not enough buckets for the triangles, keep the ends
+        return [0, len - 1][..threshold].to_vec();
+    }
+
+    let mut selected = Vec::with_capacity(threshold);
+    selected.push(0);
+    // the first and last points are always kept, the rest is split in buckets
+    let every = (len - 2) as f64 / (threshold - 2) as f64;
+    let mut a = 0;
+    for bucket in 0..threshold - 2 {
+        // average of the next bucket, the third point of the triangle
+        let next_start = ((bucket + 1) as f64 * every) as usize + 1;
+        let next_end = (((bucket + 2) as f64 * every) as usize + 1).min(len);
+        let next = &points[next_start..next_end];
+        let (avg_x, avg_y) = next.iter().fold((0.0, 0.0), |(x, y), p| (x + p.0, y + p.1));
+        let (avg_x, avg_y) = (avg_x / next.len() as f64, avg_y / next.len() as f64);
+
+        let start = (bucket as f64 * every) as usize + 1;
+        let end = (((bucket + 1) as f64 * every) as usize + 1).min(len - 1);
+        let (ax, ay) = points[a];
+        let mut max_area = -1.0;
+        let mut max_idx = start;
+        for (i, (x, y)) in points.iter().enumerate().take(end).skip(start) {
+            let area = ((ax - avg_x) * (y - ay) - (ax - x) * (avg_y - ay)).abs();
+            if area > max_area {
+                max_area = area;
+                max_idx = i;
+            }
+        }
+        selected.push(max_idx);
+        a = max_idx;
+    }
+    selected.push(len - 1);
+    selected
+}
+
+/// M4 over rows sorted by time: splits the time range in `max_points / 4` buckets and keeps the
+/// first, last, minimum and maximum row of every column in every bucket. Returns the sorted
+/// indexes of the selected rows, which can exceed `max_points` with several columns.
+pub fn m4(timestamps: &[i64], columns: &[Vec<f64>], max_points: usize) -> Vec<usize> {
+    let len = timestamps.len();
+    if len == 0 {
+        return vec![];
+    }
+    let buckets = (max_points / 4).max(1) as i64;
+    let (start, end) = (timestamps[0], timestamps[len - 1]);
+    let width = ((end - start) / buckets).max(1);
+
+    let mut selected = vec![false; len];
+    let mut bucket_start = 0;
+    while bucket_start < len {
+        let bucket = ((timestamps[bucket_start] - start) / width).min(buckets - 1);
+        let mut bucket_end = bucket_start;
+        while bucket_end < len
+            && ((timestamps[bucket_end] - start) / width).min(buckets - 1) == bucket
+        {
+            bucket_end += 1;
+        }
+        selected[bucket_start] = true;
+        selected[bucket_end - 1] = true;
+        for column in columns {
+            let range = bucket_start..bucket_end;
+            let min = range
+                .clone()
+                .min_by(|a, b| column[*a].total_cmp(&column[*b]))
+                .unwrap();
+            let max = range
+                .max_by(|a, b| column[*a].total_cmp(&column[*b]))
+                .unwrap();
+            selected[min] = true;
+            selected[max] = true;
+        }
+        bucket_start = bucket_end;
+    }
+    selected
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, s)| s.then_some(i))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use config::utils::json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_lttb() {
+        let points = (0..1000)
+            .map(|i| (i as f64, if i == 500 { 100.0 } else { (i % 7) as f64 }))
+            .collect::<Vec<_>>();
+        let selected = lttb(&points, 50);
+        assert_eq!(selected.len(), 50);
+        assert_eq!(selected[0], 0);
+        assert_eq!(selected[49], 999);
+        // the spike is always kept
+        assert!(selected.contains(&500));
+        assert!(selected.windows(2).all(|w| w[0] < w[1]));
+
+        assert_eq!(lttb(&points[..10], 50).len(), 10);
+        assert_eq!(lttb(&points, 2), vec![0, 999]);
+    }
+
+    #[test]
+    fn test_m4() {
+        let ts = (0..1000).collect::<Vec<i64>>();
+        let column = (0..1000)
+            .map(|i| if i == 123 { -5.0 } else { i as f64 % 10.0 })
+            .collect::<Vec<_>>();
+        let selected = m4(&ts, &[column], 40);
+        assert!(selected.len() <= 40);
+        assert!(selected.contains(&0));
+        assert!(selected.contains(&999));
+        assert!(selected.contains(&123));
+    }
+
+    #[test]
+    fn test_downsample() {
+        let hits = (0..200)
+            .flat_map(|i| {
+                ["a", "b"]
+                    .into_iter()
+                    .map(move |pod| json!({"_timestamp": 1_700_000_000_000_000i64 + i * 1_000_000, "pod": pod, "cpu": i % 13}))
+            })
+            .collect::<Vec<_>>();
+        let res = downsample(&hits, "", 20).unwrap();
+        assert_eq!(res.len(), 40);
+        assert_eq!(res.iter().filter(|h| h["pod"] == "a").count(), 20);
+
+        // small results are returned as is
+        assert!(downsample(&hits[..10], "", 20).is_none());
+        // results without a time column are not a time series
+        let hits = (0..100).map(|i| json!({"k": i})).collect::<Vec<_>>();
+        assert!(downsample(&hits, "", 20).is_none());
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Estimates the data a query scans from the file list of its streams, without running it.
+
+use config::{
+    get_config,
+    meta::{
+        search::{self, SearchEstimateResponse, SearchPartitionRequest, StreamSearchEstimate},
+        sql::TableReferenceExt,
+        stream::StreamType,
+    },
+};
+use hashbrown::HashSet;
+use infra::{
+    cache::stats,
+    errors::{Error, ErrorCodes},
+    schema::unwrap_stream_settings,
+};
+use proto::cluster_rpc;
+
+use crate::{
+    common::utils::stream::get_settings_max_query_range,
+    service::{file_list, search::sql::Sql},
+};
+
+/// Sums the files of the streams of the query in its time ranges. The files are counted before
+/// the partition keys and the indexes prune them, the estimate is an upper bound.
+pub async fn estimate(
+    trace_id: &str,
+    org_id: &str,
+    user_id: Option<&str>,
+    stream_type: StreamType,
+    req: &SearchPartitionRequest,
+) -> Result<SearchEstimateResponse, Error> {
+    let time_ranges = if req.time_ranges.is_empty() {
+        vec![[req.start_time, req.end_time]]
+    } else {
+        search::normalize_time_ranges(&req.time_ranges, req.start_time, req.end_time)
+            .map_err(|e| Error::ErrorCode(ErrorCodes::InvalidParams(e)))?
+    };
+    let (Some(first), Some(last)) = (time_ranges.first(), time_ranges.last()) else {
+        return Err(Error::ErrorCode(ErrorCodes::InvalidParams(
+            "no time range between start_time and end_time".to_string(),
+        )));
+    };
+    let query = cluster_rpc::SearchQuery {
+        start_time: first[0],
+        end_time: last[1],
+        sql: req.sql.to_string(),
+        ..Default::default()
+    };
+    let sql = Sql::new(&query, org_id, stream_type, None).await?;
+
+    let mut resp = SearchEstimateResponse::default();
+    for (stream, schema) in sql.schemas.iter() {
+        let stream_type = stream.get_stream_type(stream_type);
+        let stream_name = stream.stream_name();
+        let settings = unwrap_stream_settings(schema.schema()).unwrap_or_default();
+
+        let mut estimate = StreamSearchEstimate {
+            stream_type,
+            stream_name: stream_name.clone(),
+            ..Default::default()
+        };
+        // a file overlapping two ranges is scanned once
+        let mut ids = HashSet::new();
+        for [start, end] in time_ranges.iter() {
+            let files = file_list::query_ids(
+                trace_id,
+                org_id,
+                stream_type,
+                &stream_name,
+                Some((*start, *end)),
+            )
+            .await?;
+            for file in files {
+                if ids.insert(file.id) {
+                    estimate.file_num += 1;
+                    estimate.records += file.records;
+                    estimate.original_size += file.original_size;
+                }
+            }
+        }
+        let stream_stats = stats::get_stream_stats(org_id, &stream_name, stream_type);
+        estimate.compressed_size = if stream_stats.storage_size > 0.0 {
+            (estimate.original_size as f64 * stream_stats.compressed_size
+                / stream_stats.storage_size) as i64
+        } else {
+            estimate.original_size
+        };
+
+        let max_query_range =
+            get_settings_max_query_range(settings.max_query_range, org_id, user_id).await;
+        if max_query_range > 0
+            && (resp.max_query_range == 0 || max_query_range < resp.max_query_range)
+        {
+            resp.max_query_range = max_query_range;
+        }
+        resp.add_stream(estimate);
+    }
+
+    let cfg = get_config();
+    resp.compute_credits(
+        cfg.common.search_estimate_credits_per_gb,
+        cfg.common.search_estimate_credits_per_1000_files,
+    );
+    log::info!(
+        "[trace_id {trace_id}] search estimate: files: {}, records: {}, compressed_size: {}, credits: {}",
+        resp.file_num,
+        resp.records,
+        resp.compressed_size,
+        resp.credits
+    );
+    Ok(resp)
+}
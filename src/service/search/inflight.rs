@@ -0,0 +1,191 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Deduplication of identical in-flight search queries.
+//!
+//! When several clients (e.g. viewers of the same dashboard) issue the same
+//! query at the same time, only the first one executes it, the others subscribe
+//! to its result and receive a copy once it is ready.
+
+use std::future::Future;
+
+use config::{
+    meta::{search, stream::StreamType},
+    utils::{hash::Sum64, json},
+};
+use hashbrown::HashMap;
+use infra::errors::Error;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use tokio::sync::oneshot;
+
+type Subscriber = oneshot::Sender<Result<search::Response, Error>>;
+
+static INFLIGHT: Lazy<Mutex<HashMap<u64, Vec<Subscriber>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Fingerprint of a search request, identical queries over the same time range
+/// of the same org share the fingerprint.
+pub fn fingerprint(
+    org_id: &str,
+    stream_type: StreamType,
+    req: &search::Request,
+    range_error: &str,
+) -> u64 {
+    let mut body = vec![
+        org_id.to_string(),
+        stream_type.to_string(),
+        json::to_string(&req.query).unwrap_or_default(),
+        req.search_type.map(|t| t.to_string()).unwrap_or_default(),
+        req.use_cache.to_string(),
+        req.timeout.to_string(),
+        range_error.to_string(),
+    ];
+    body.extend(req.regions.iter().cloned());
+    body.push(String::new());
+    body.extend(req.clusters.iter().cloned());
+    let mut h = config::utils::hash::gxhash::new();
+    h.sum64(&body.join("\u{1f}"))
+}
+
+/// Executes `exec` unless an identical query (same `key`) is already running, in
+/// which case the caller waits for that query and gets a copy of its result.
+pub async fn dedup<F, Fut>(key: u64, exec: F) -> Result<search::Response, Error>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<search::Response, Error>>,
+{
+    loop {
+        let rx = {
+            let mut inflight = INFLIGHT.lock();
+            match inflight.get_mut(&key) {
+                Some(subscribers) => {
+                    let (tx, rx) = oneshot::channel();
+                    subscribers.push(tx);
+                    rx
+                }
+                None => {
+                    inflight.insert(key, Vec::new());
+                    break;
+                }
+            }
+        };
+        if let Ok(res) = rx.await {
+            return res;
+        }
+        // the leading query was cancelled before it finished, take over
+    }
+
+    let mut guard = LeaderGuard {
+        key,
+        finished: false,
+    };
+    let res = exec().await;
+    for tx in guard.finish() {
+        _ = tx.send(clone_result(&res));
+    }
+    res
+}
+
+fn clone_result(res: &Result<search::Response, Error>) -> Result<search::Response, Error> {
+    match res {
+        Ok(res) => Ok(res.clone()),
+        Err(Error::ErrorCode(code)) => Err(Error::ErrorCode(code.clone())),
+        Err(e) => Err(Error::Message(e.to_string())),
+    }
+}
+
+/// Removes the in-flight entry when the leading query completes or its future
+/// is dropped, so waiting subscribers never hang.
+struct LeaderGuard {
+    key: u64,
+    finished: bool,
+}
+
+impl LeaderGuard {
+    fn finish(&mut self) -> Vec<Subscriber> {
+        self.finished = true;
+        INFLIGHT.lock().remove(&self.key).unwrap_or_default()
+    }
+}
+
+impl Drop for LeaderGuard {
+    fn drop(&mut self) {
+        if !self.finished {
+            INFLIGHT.lock().remove(&self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+
+    use super::*;
+
+    fn request(start_time: i64, end_time: i64) -> search::Request {
+        search::Request {
+            query: search::Query {
+                sql: "SELECT count(*) FROM \"logs\"".to_string(),
+                start_time,
+                end_time,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let req = request(0, 100);
+        let key = fingerprint("default", StreamType::Logs, &req, "");
+        assert_eq!(key, fingerprint("default", StreamType::Logs, &req, ""));
+        assert_ne!(key, fingerprint("other", StreamType::Logs, &req, ""));
+        assert_ne!(
+            key,
+            fingerprint("default", StreamType::Logs, &request(0, 200), "")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dedup_fan_out() {
+        let executions = Arc::new(AtomicUsize::new(0));
+        let run = |executions: Arc<AtomicUsize>| {
+            dedup(42, move || async move {
+                executions.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                let mut res = search::Response::new(0, 10);
+                res.total = 7;
+                Ok(res)
+            })
+        };
+        let (a, b, c) = tokio::join!(
+            run(executions.clone()),
+            run(executions.clone()),
+            run(executions.clone())
+        );
+        assert_eq!(executions.load(Ordering::SeqCst), 1);
+        for res in [a, b, c] {
+            assert_eq!(res.unwrap().total, 7);
+        }
+        assert!(!INFLIGHT.lock().contains_key(&42));
+    }
+}
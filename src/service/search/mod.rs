@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::{cmp::max, sync::Arc};
+use std::{cmp::max, collections::BTreeSet, sync::Arc};
 
 use arrow::array::RecordBatch;
 use arrow_schema::{DataType, Field, Schema};
@@ -27,7 +27,10 @@ use config::{
         cluster::RoleGroup,
         function::RESULT_ARRAY,
         search,
-        self_reporting::usage::{RequestStats, UsageType},
+        self_reporting::{
+            query_audit::QueryAuditRecord,
+            usage::{RequestStats, UsageType},
+        },
         sql::{OrderBy, SqlOperator, TableReferenceExt, resolve_stream_names},
         stream::{FileKey, StreamParams, StreamPartition, StreamType},
     },
@@ -67,7 +70,7 @@ use {
     tracing::info_span,
 };
 
-use super::self_reporting::report_request_usage_stats;
+use super::self_reporting::{publish_query_audit, report_request_usage_stats};
 use crate::{
     common::{
         infra::cluster as infra_cluster,
@@ -82,11 +85,19 @@ use crate::{
 
 pub(crate) mod cache;
 pub(crate) mod cluster;
+pub(crate) mod cross_org;
 pub(crate) mod datafusion;
+pub(crate) mod diff;
+pub(crate) mod downsample;
+pub(crate) mod estimate;
 pub(crate) mod grpc;
 pub(crate) mod grpc_search;
 pub(crate) mod index;
+pub(crate) mod inflight;
 pub(crate) mod inspector;
+pub(crate) mod outliers;
+pub(crate) mod panel_cache;
+pub(crate) mod panel_query;
 pub(crate) mod partition;
 pub(crate) mod request;
 pub(crate) mod search_stream;
@@ -94,6 +105,7 @@ pub(crate) mod sql;
 #[cfg(feature = "enterprise")]
 pub(crate) mod super_cluster;
 pub(crate) mod tantivy;
+pub(crate) mod time_shift;
 pub(crate) mod utils;
 
 /// The result of search in cluster
@@ -138,6 +150,28 @@ pub async fn search(
         trace_id.to_string()
     };
 
+    let multi_range_req;
+    let in_req = if in_req.query.time_ranges.is_empty() {
+        in_req
+    } else {
+        multi_range_req = with_time_ranges(in_req)?;
+        &multi_range_req
+    };
+
+    // the archive tier is only searched by search jobs
+    let reads_archive =
+        matches!(
+            in_req.search_type,
+            Some(search::SearchEventType::UI) | Some(search::SearchEventType::Dashboards)
+        ) && search::archive_boundary(cfg.limit.search_archive_after_days, started_at)
+            .is_some_and(|boundary| in_req.query.start_time < boundary);
+    if reads_archive {
+        return Err(Error::ErrorCode(ErrorCodes::InvalidParams(format!(
+            "the query reads data older than {} days from the archive tier, submit it as a search job with include_archived",
+            cfg.limit.search_archive_after_days
+        ))));
+    }
+
     #[cfg(feature = "enterprise")]
     {
         let sql = Some(in_req.query.sql.clone());
@@ -194,6 +228,7 @@ pub async fn search(
     request.set_use_cache(in_req.use_cache);
 
     let meta = Sql::new_from_req(&request, &query).await?;
+    super::stream_field_usage::record_query(&meta);
     let span = tracing::span::Span::current();
     let handle = tokio::task::spawn(
         async move { cluster::http::search(request, query, req_regions, req_clusters, true).await }
@@ -252,7 +287,20 @@ pub async fn search(
                 res = crate::service::websocket_events::sort::order_search_results(res, None);
             }
             res.set_work_group(_work_group.clone());
+            if cfg.common.query_audit_enabled {
+                publish_query_audit(query_audit_record(
+                    org_id,
+                    stream_type,
+                    user_id.as_deref(),
+                    &trace_id,
+                    in_req,
+                    &meta,
+                    &res,
+                ))
+                .await;
+            }
             let time = start.elapsed().as_secs_f64();
+            super::index_advisor::record_query(&meta, &trace_id, user_id.as_deref(), time);
             let (report_usage, search_type, search_event_context) = match in_req.search_type {
                 Some(search_type) => {
                     if matches!(
@@ -325,6 +373,77 @@ pub async fn search(
     }
 }
 
+/// Audit record of an executed query: who read which streams, with which predicates, and the
+/// columns and number of the returned rows.
+fn query_audit_record(
+    org_id: &str,
+    stream_type: StreamType,
+    user_id: Option<&str>,
+    trace_id: &str,
+    in_req: &search::Request,
+    meta: &Sql,
+    res: &search::Response,
+) -> QueryAuditRecord {
+    let columns = if !res.columns.is_empty() {
+        res.columns.clone()
+    } else {
+        res.hits
+            .iter()
+            .filter_map(|hit| hit.as_object())
+            .flat_map(|hit| hit.keys().cloned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    };
+    QueryAuditRecord {
+        _timestamp: now_micros(),
+        org_id: org_id.to_string(),
+        user_email: user_id.unwrap_or_default().to_string(),
+        trace_id: trace_id.to_string(),
+        search_type: in_req
+            .search_type
+            .map(|t| t.to_string())
+            .unwrap_or_default(),
+        stream_type,
+        streams: meta
+            .stream_names
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>()
+            .join(","),
+        sql: meta.sql.clone(),
+        predicates: sql::where_predicates(&meta.sql).join("\n"),
+        columns: columns.join(","),
+        start_time: in_req.query.start_time,
+        end_time: in_req.query.end_time,
+        rows: res.hits.len() as i64,
+        node: LOCAL_NODE.name.clone(),
+        ..Default::default()
+    }
+}
+
+/// Rewrites a multi-range request into a single query over the bounds of its time ranges,
+/// filtered on the union of the ranges.
+fn with_time_ranges(in_req: &search::Request) -> Result<search::Request, Error> {
+    let ranges = search::normalize_time_ranges(
+        &in_req.query.time_ranges,
+        in_req.query.start_time,
+        in_req.query.end_time,
+    )
+    .map_err(|e| Error::ErrorCode(ErrorCodes::InvalidParams(e)))?;
+    let (Some(first), Some(last)) = (ranges.first(), ranges.last()) else {
+        return Err(Error::ErrorCode(ErrorCodes::InvalidParams(
+            "no time range between start_time and end_time".to_string(),
+        )));
+    };
+    let mut req = in_req.clone();
+    req.query.sql = sql::add_time_ranges_filter(&in_req.query.sql, &ranges)?;
+    req.query.start_time = first[0];
+    req.query.end_time = last[1];
+    req.query.time_ranges = ranges;
+    Ok(req)
+}
+
 /// Returns Error if the first query is failed, otherwise returns the partial results.
 /// In case one query fails, the remaining queries are not executed.
 #[tracing::instrument(name = "service:search_multi:enter", skip(multi_req))]
@@ -597,6 +716,32 @@ pub async fn search_partition(
     let start = std::time::Instant::now();
     let cfg = get_config();
 
+    // a single range query is a multi-range query of one range
+    let time_ranges = if req.time_ranges.is_empty() {
+        vec![[req.start_time, req.end_time]]
+    } else {
+        search::normalize_time_ranges(&req.time_ranges, req.start_time, req.end_time)
+            .map_err(|e| Error::ErrorCode(ErrorCodes::InvalidParams(e)))?
+    };
+    let (Some(first), Some(last)) = (time_ranges.first(), time_ranges.last()) else {
+        return Err(Error::ErrorCode(ErrorCodes::InvalidParams(
+            "no time range between start_time and end_time".to_string(),
+        )));
+    };
+    let multi_range_req;
+    let req = if req.time_ranges.is_empty() {
+        req
+    } else {
+        multi_range_req = search::SearchPartitionRequest {
+            start_time: first[0],
+            end_time: last[1],
+            time_ranges: time_ranges.clone(),
+            ..req.clone()
+        };
+        &multi_range_req
+    };
+    let query_duration: i64 = time_ranges.iter().map(|[start, end]| end - start).sum();
+
     let query = cluster_rpc::SearchQuery {
         start_time: req.start_time,
         end_time: req.end_time,
@@ -726,7 +871,7 @@ pub async fn search_partition(
             };
             let mut data_retention = data_retention * 24 * 60 * 60;
             // data duration in seconds
-            let query_duration = (req.end_time - req.start_time) / 1000 / 1000;
+            let query_duration = query_duration / 1000 / 1000;
             let stats = stats::get_stream_stats(org_id, &stream_name, stream_type);
 
             // if stats.doc_time_max is 0, handle the case by using current time
@@ -778,7 +923,7 @@ pub async fn search_partition(
 
     if skip_get_file_list {
         let mut response = search::SearchPartitionResponse::default();
-        response.partitions.push([req.start_time, req.end_time]);
+        response.partitions = time_ranges;
         response.max_query_range = max_query_range_in_hour;
         response.histogram_interval = sql.histogram_interval;
         log::info!("[trace_id {trace_id}] search_partition: returning single partition");
@@ -851,7 +996,7 @@ pub async fn search_partition(
     );
 
     // Calculate step with all constraints
-    let mut step = (req.end_time - req.start_time) / part_num as i64;
+    let mut step = query_duration / part_num as i64;
     // step must be times of min_step
     if step < min_step {
         step = min_step;
@@ -901,14 +1046,22 @@ pub async fn search_partition(
         is_histogram,
     );
 
-    // Generate partitions
-    let partitions = generator.generate_partitions(
-        req.start_time,
-        req.end_time,
-        step,
-        sql_order_by,
-        is_streaming_aggregate,
-    );
+    // Generate partitions, the partitions of the time ranges are unioned in the query order
+    let mut partitions = Vec::new();
+    let ranges: Box<dyn Iterator<Item = &[i64; 2]>> = if sql_order_by == OrderBy::Asc {
+        Box::new(time_ranges.iter())
+    } else {
+        Box::new(time_ranges.iter().rev())
+    };
+    for [start_time, end_time] in ranges {
+        partitions.extend(generator.generate_partitions(
+            *start_time,
+            *end_time,
+            step,
+            sql_order_by,
+            is_streaming_aggregate,
+        ));
+    }
 
     if sql_order_by == OrderBy::Asc {
         resp.order_by = OrderBy::Asc;
@@ -1258,6 +1411,8 @@ pub async fn search_partition_multi(
                 clusters: req.clusters.clone(),
                 query_fn: req.query_fn.clone(),
                 streaming_output: req.streaming_output,
+                time_ranges: vec![],
+                variables: vec![],
             },
             false,
             true,
@@ -0,0 +1,255 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Flags the series of a grouped aggregation that deviate from the rest of the population.
+
+use std::cmp::Ordering;
+
+use config::{
+    TIMESTAMP_COL_NAME,
+    meta::search::{OutlierMethod, OutlierSeries, SearchOutliersResponse},
+    utils::json::{Map, Value, get_float_value},
+};
+use hashbrown::HashMap;
+
+use super::diff::detect_columns;
+
+const DEFAULT_THRESHOLD: f64 = 3.0;
+// scales the median absolute deviation to the standard deviation of a normal distribution
+const MAD_SCALE: f64 = 1.4826;
+// scales the mean absolute deviation to the standard deviation of a normal distribution
+const MEAN_AD_SCALE: f64 = 1.2533;
+// fewer series than this have no meaningful population
+const MIN_SERIES: usize = 3;
+
+/// Scores every series of the rows against the other series of the same time bucket and flags
+/// the series whose mean score is above the threshold.
+pub fn detect_outliers(
+    rows: &[Value],
+    group_by: &[String],
+    value_field: Option<&str>,
+    timestamp_field: Option<&str>,
+    method: OutlierMethod,
+    threshold: Option<f64>,
+) -> SearchOutliersResponse {
+    let threshold = threshold.filter(|t| *t > 0.0).unwrap_or(DEFAULT_THRESHOLD);
+    let timestamp_field = timestamp_field.map(|f| f.to_string()).or_else(|| {
+        rows.iter()
+            .any(|r| r.get(TIMESTAMP_COL_NAME).is_some())
+            .then(|| TIMESTAMP_COL_NAME.to_string())
+    });
+    let (keys, values) = detect_columns(rows.iter());
+    let group_by = if group_by.is_empty() {
+        keys.into_iter()
+            .filter(|k| Some(k) != timestamp_field.as_ref())
+            .collect()
+    } else {
+        group_by.to_vec()
+    };
+    let Some(value_field) = value_field.map(|f| f.to_string()).or_else(|| {
+        values
+            .into_iter()
+            .find(|v| !group_by.contains(v) && Some(v) != timestamp_field.as_ref())
+    }) else {
+        return SearchOutliersResponse::default();
+    };
+
+    // series keys, and the values of every series per time bucket
+    let mut series: Vec<(Map<String, Value>, SeriesScore)> = vec![];
+    let mut series_index: HashMap<String, usize> = HashMap::new();
+    let mut buckets: HashMap<String, Vec<(usize, f64)>> = HashMap::new();
+    for row in rows.iter().filter_map(|r| r.as_object()) {
+        let Some(value) = row.get(&value_field).filter(|v| !v.is_null()) else {
+            continue;
+        };
+        let keys: Map<String, Value> = group_by
+            .iter()
+            .map(|f| (f.clone(), row.get(f).cloned().unwrap_or(Value::Null)))
+            .collect();
+        let key = Value::Object(keys.clone()).to_string();
+        let idx = *series_index.entry(key).or_insert_with(|| {
+            series.push((keys, SeriesScore::default()));
+            series.len() - 1
+        });
+        let bucket = timestamp_field
+            .as_ref()
+            .and_then(|f| row.get(f))
+            .map(|v| v.to_string())
+            .unwrap_or_default();
+        buckets
+            .entry(bucket)
+            .or_default()
+            .push((idx, get_float_value(value)));
+    }
+
+    for points in buckets.values() {
+        if points.len() < MIN_SERIES {
+            continue;
+        }
+        let values = points.iter().map(|(_, v)| *v).collect::<Vec<_>>();
+        let Some((center, spread)) = center_and_spread(&values, method) else {
+            continue;
+        };
+        for (idx, value) in points {
+            let score = if spread > 0.0 {
+                (value - center).abs() / spread
+            } else {
+                0.0
+            };
+            series[*idx].1.add(score, threshold);
+        }
+    }
+
+    let total_series = series.len();
+    let mut series = series
+        .into_iter()
+        .filter(|(_, s)| s.buckets > 0)
+        .map(|(keys, s)| {
+            let score = s.sum / s.buckets as f64;
+            OutlierSeries {
+                keys,
+                score,
+                max_score: s.max,
+                outlier_buckets: s.outlier_buckets,
+                buckets: s.buckets,
+                is_outlier: score > threshold,
+            }
+        })
+        .collect::<Vec<_>>();
+    series.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+    SearchOutliersResponse {
+        total_series,
+        series,
+        ..Default::default()
+    }
+}
+
+#[derive(Default)]
+struct SeriesScore {
+    sum: f64,
+    max: f64,
+    buckets: usize,
+    outlier_buckets: usize,
+}
+
+impl SeriesScore {
+    fn add(&mut self, score: f64, threshold: f64) {
+        self.sum += score;
+        self.max = self.max.max(score);
+        self.buckets += 1;
+        if score > threshold {
+            self.outlier_buckets += 1;
+        }
+    }
+}
+
+/// Returns the center of the values and the spread a deviation score is divided by. A spread
+/// of zero means every value is the same.
+fn center_and_spread(values: &[f64], method: OutlierMethod) -> Option<(f64, f64)> {
+    if values.is_empty() {
+        return None;
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    match method {
+        OutlierMethod::Zscore => {
+            let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+            Some((mean, variance.sqrt()))
+        }
+        OutlierMethod::Mad => {
+            let center = median(values.to_vec());
+            let deviations = values
+                .iter()
+                .map(|v| (v - center).abs())
+                .collect::<Vec<_>>();
+            let mad = median(deviations.clone());
+            if mad > 0.0 {
+                return Some((center, mad * MAD_SCALE));
+            }
+            // more than half of the series have the same value, fall back to the mean
+            // absolute deviation around the median
+            let mean_ad = deviations.iter().sum::<f64>() / n;
+            Some((center, mean_ad * MEAN_AD_SCALE))
+        }
+    }
+}
+
+fn median(mut values: Vec<f64>) -> f64 {
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use config::utils::json::json;
+
+    use super::*;
+
+    fn rows() -> Vec<Value> {
+        let mut rows = vec![];
+        for ts in 0..3 {
+            for pod in 0..10 {
+                let cpu = if pod == 7 {
+                    50.0
+                } else {
+                    10.0 + (pod % 3) as f64
+                };
+                rows.push(json!({"_timestamp": ts, "pod": format!("pod-{pod}"), "cpu": cpu}));
+            }
+        }
+        rows
+    }
+
+    #[test]
+    fn test_detect_outliers_mad() {
+        let resp = detect_outliers(&rows(), &[], None, None, OutlierMethod::Mad, None);
+        assert_eq!(resp.total_series, 10);
+        assert_eq!(resp.series[0].keys["pod"], "pod-7");
+        assert!(resp.series[0].is_outlier);
+        assert_eq!(resp.series[0].buckets, 3);
+        assert_eq!(resp.series[0].outlier_buckets, 3);
+        assert_eq!(resp.series.iter().filter(|s| s.is_outlier).count(), 1);
+    }
+
+    #[test]
+    fn test_detect_outliers_zscore() {
+        let resp = detect_outliers(
+            &rows(),
+            &["pod".to_string()],
+            Some("cpu"),
+            Some("_timestamp"),
+            OutlierMethod::Zscore,
+            Some(2.0),
+        );
+        assert_eq!(resp.series[0].keys["pod"], "pod-7");
+        assert!(resp.series[0].is_outlier);
+        assert_eq!(resp.series.iter().filter(|s| s.is_outlier).count(), 1);
+    }
+
+    #[test]
+    fn test_detect_outliers_same_values() {
+        let rows = (0..5)
+            .map(|i| json!({"host": format!("h{i}"), "count": 4}))
+            .collect::<Vec<_>>();
+        let resp = detect_outliers(&rows, &[], None, None, OutlierMethod::Mad, None);
+        assert_eq!(resp.total_series, 5);
+        assert!(resp.series.iter().all(|s| !s.is_outlier && s.score == 0.0));
+    }
+}
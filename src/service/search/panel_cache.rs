@@ -0,0 +1,220 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Result cache of dashboard panel queries.
+//!
+//! Loading a dashboard runs the query of every panel, the results are kept for
+//! `ZO_PANEL_CACHE_TTL` seconds so that loading the same dashboard again in that
+//! window serves them without executing the queries against the storage.
+
+use config::{
+    get_config,
+    meta::{
+        search::{self, SearchEventType},
+        stream::StreamType,
+    },
+    utils::{hash::Sum64, json, time::now_micros},
+};
+use hashbrown::HashMap;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+use crate::service::{dashboards, search as SearchService};
+
+struct CachedPanel {
+    response: search::Response,
+    expires_at: i64,
+}
+
+static PANELS: Lazy<Mutex<HashMap<u64, CachedPanel>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Cache key of a dashboard panel query, `None` when the request is not a panel
+/// query or the panel cache is disabled.
+///
+/// The key covers the dashboard version, the panel, the query with its resolved
+/// variables and access policies, and the time range rounded down to buckets of
+/// `ZO_PANEL_CACHE_TTL` seconds.
+pub async fn key(
+    org_id: &str,
+    stream_type: StreamType,
+    user_id: Option<&str>,
+    req: &search::Request,
+) -> Option<u64> {
+    let cfg = get_config();
+    if !cfg.common.panel_cache_enabled || cfg.common.panel_cache_ttl <= 0 {
+        return None;
+    }
+    if req.search_type != Some(SearchEventType::Dashboards) {
+        return None;
+    }
+    let ctx = req.search_event_context.as_ref()?;
+    let (dashboard_id, panel_id) = (ctx.dashboard_id.as_deref()?, ctx.panel_id.as_deref()?);
+    let dashboard_hash = dashboards::get_dashboard(org_id, dashboard_id)
+        .await
+        .ok()?
+        .hash;
+    let sql = match user_id {
+        Some(user_id) => {
+            SearchService::sql::apply_access_policies(org_id, user_id, stream_type, &req.query.sql)
+                .await
+                .ok()?
+        }
+        None => req.query.sql.clone(),
+    };
+    Some(panel_key(
+        org_id,
+        stream_type,
+        &dashboard_hash,
+        panel_id,
+        req,
+        &sql,
+        cfg.common.panel_cache_ttl,
+    ))
+}
+
+fn panel_key(
+    org_id: &str,
+    stream_type: StreamType,
+    dashboard_hash: &str,
+    panel_id: &str,
+    req: &search::Request,
+    sql: &str,
+    ttl: i64,
+) -> u64 {
+    let bucket = ttl * 1_000_000;
+    let mut query = req.query.clone();
+    query.sql = sql.to_string();
+    query.start_time -= query.start_time.rem_euclid(bucket);
+    query.end_time -= query.end_time.rem_euclid(bucket);
+    let mut body = vec![
+        org_id.to_string(),
+        stream_type.to_string(),
+        dashboard_hash.to_string(),
+        panel_id.to_string(),
+        json::to_string(&query).unwrap_or_default(),
+        req.use_cache.to_string(),
+    ];
+    body.extend(req.regions.iter().cloned());
+    body.push(String::new());
+    body.extend(req.clusters.iter().cloned());
+    let mut h = config::utils::hash::gxhash::new();
+    h.sum64(&body.join("\u{1f}"))
+}
+
+/// Returns the cached result of the panel query if it has not expired yet.
+pub fn get(key: u64) -> Option<search::Response> {
+    let now = now_micros();
+    let mut panels = PANELS.lock();
+    match panels.get(&key) {
+        Some(cached) if cached.expires_at > now => Some(cached.response.clone()),
+        Some(_) => {
+            panels.remove(&key);
+            None
+        }
+        None => None,
+    }
+}
+
+/// Caches the result of the panel query for `ZO_PANEL_CACHE_TTL` seconds.
+pub fn set(key: u64, response: &search::Response) {
+    let cfg = get_config();
+    insert(
+        key,
+        response,
+        now_micros(),
+        cfg.common.panel_cache_ttl,
+        cfg.common.panel_cache_max_entries,
+    );
+}
+
+fn insert(key: u64, response: &search::Response, now: i64, ttl: i64, max_entries: usize) {
+    if max_entries == 0 {
+        return;
+    }
+    let mut panels = PANELS.lock();
+    if panels.len() >= max_entries && !panels.contains_key(&key) {
+        panels.retain(|_, cached| cached.expires_at > now);
+    }
+    while panels.len() >= max_entries && !panels.contains_key(&key) {
+        let Some(oldest) = panels
+            .iter()
+            .min_by_key(|(_, cached)| cached.expires_at)
+            .map(|(k, _)| *k)
+        else {
+            break;
+        };
+        panels.remove(&oldest);
+    }
+    panels.insert(
+        key,
+        CachedPanel {
+            response: response.clone(),
+            expires_at: now + ttl * 1_000_000,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(start_time: i64, end_time: i64) -> search::Request {
+        let mut req: search::Request = json::from_str(
+            r#"{"query":{"sql":"SELECT count(*) FROM default","start_time":0,"end_time":0}}"#,
+        )
+        .unwrap();
+        req.query.start_time = start_time;
+        req.query.end_time = end_time;
+        req
+    }
+
+    #[test]
+    fn test_panel_key() {
+        let minute = 60_000_000;
+        let req = request(10 * minute + 5, 25 * minute + 10);
+        let key = |req: &search::Request, hash: &str, panel: &str| {
+            panel_key(
+                "org",
+                StreamType::Logs,
+                hash,
+                panel,
+                req,
+                &req.query.sql,
+                60,
+            )
+        };
+        let base = key(&req, "h1", "p1");
+        // same time range bucket
+        assert_eq!(
+            base,
+            key(&request(10 * minute + 30, 25 * minute + 59), "h1", "p1")
+        );
+        // next bucket, dashboard changed, other panel
+        assert_ne!(base, key(&request(11 * minute, 26 * minute), "h1", "p1"));
+        assert_ne!(base, key(&req, "h2", "p1"));
+        assert_ne!(base, key(&req, "h1", "p2"));
+    }
+
+    #[test]
+    fn test_insert_evicts() {
+        let response = search::Response::default();
+        insert(1, &response, 0, 60, 2);
+        insert(2, &response, 10, 60, 2);
+        insert(3, &response, 20, 60, 2);
+        let panels = PANELS.lock();
+        assert!(!panels.contains_key(&1));
+        assert!(panels.contains_key(&2) && panels.contains_key(&3));
+    }
+}
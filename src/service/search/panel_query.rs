@@ -0,0 +1,302 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Settings of dashboard panels applied to their queries.
+//!
+//! A panel can set the `query_timeout_secs` and `max_rows` of its queries in its config, the
+//! limits are enforced here so that a heavy panel can't take the query budget of the whole
+//! dashboard or return more rows than the browser can hold. The `transformations` of a panel
+//! reshape the rows of its queries before they are returned. Trellis panels with `max_panels`
+//! get the rows of one page of their breakdown groups, so that a breakdown over thousands of
+//! values doesn't render thousands of charts.
+
+use config::{
+    meta::{
+        dashboards::{
+            Dashboard,
+            transformations::{self, Transformation},
+            v5,
+        },
+        search::{self, SearchEventType, TrellisGroups},
+    },
+    utils::json,
+};
+use hashbrown::HashSet;
+
+use crate::service::dashboards;
+
+/// Settings of the queries of a panel.
+#[derive(Debug, Default, PartialEq)]
+pub struct PanelQuery {
+    pub timeout_secs: Option<u64>,
+    pub max_rows: Option<u64>,
+    pub transformations: Vec<Transformation>,
+    pub trellis: Option<TrellisPaging>,
+}
+
+/// Page of the breakdown groups of a trellis panel returned by its queries.
+#[derive(Debug, PartialEq)]
+pub struct TrellisPaging {
+    /// Aliases of the breakdown columns of the panel queries.
+    pub breakdown: Vec<String>,
+    pub max_panels: u64,
+    pub page: u64,
+}
+
+impl PanelQuery {
+    /// Returns the request with the timeout and size lowered to the limits of the panel.
+    pub fn limit(&self, req: &search::Request) -> search::Request {
+        let mut req = req.clone();
+        if let Some(timeout) = self.timeout_secs.map(|v| v as i64)
+            && (req.timeout <= 0 || req.timeout > timeout)
+        {
+            req.timeout = timeout;
+        }
+        if let Some(max_rows) = self.max_rows.map(|v| v as i64)
+            && (req.query.size <= 0 || req.query.size > max_rows)
+        {
+            req.query.size = max_rows;
+        }
+        req
+    }
+
+    /// Runs the transformations of the panel on the rows of the response.
+    pub fn transform(&self, res: &mut search::Response) {
+        if self.transformations.is_empty() {
+            return;
+        }
+        let hits = std::mem::take(&mut res.hits);
+        res.hits = transformations::apply(&self.transformations, hits);
+    }
+
+    /// Keeps the rows of the breakdown groups of the trellis page, the groups are numbered in
+    /// the order of the rows.
+    pub fn page_trellis(&self, res: &mut search::Response) {
+        let Some(trellis) = self.trellis.as_ref() else {
+            return;
+        };
+        let Some(column) = trellis.breakdown.iter().find(|column| {
+            res.hits
+                .iter()
+                .any(|hit| hit.get(column.as_str()).is_some())
+        }) else {
+            return;
+        };
+        let group = |hit: &json::Value| match hit.get(column.as_str()) {
+            Some(json::Value::String(v)) => v.to_string(),
+            Some(v) => v.to_string(),
+            None => String::new(),
+        };
+        let mut seen = HashSet::new();
+        let groups = res
+            .hits
+            .iter()
+            .map(group)
+            .filter(|g| seen.insert(g.clone()))
+            .collect::<Vec<_>>();
+        let page_groups = groups
+            .iter()
+            .skip((trellis.page * trellis.max_panels) as usize)
+            .take(trellis.max_panels as usize)
+            .cloned()
+            .collect::<Vec<_>>();
+        let keep = page_groups.iter().collect::<HashSet<_>>();
+        res.hits.retain(|hit| keep.contains(&group(hit)));
+        res.trellis = Some(TrellisGroups {
+            page: trellis.page,
+            max_panels: trellis.max_panels,
+            total: groups.len(),
+            groups: page_groups,
+        });
+    }
+}
+
+/// Settings of the dashboard panel the request comes from, `None` when the request is not a
+/// panel query or the panel has no settings for its queries.
+pub async fn get(org_id: &str, req: &search::Request) -> Option<PanelQuery> {
+    if req.search_type != Some(SearchEventType::Dashboards) {
+        return None;
+    }
+    let ctx = req.search_event_context.as_ref()?;
+    let (dashboard_id, panel_id) = (ctx.dashboard_id.as_deref()?, ctx.panel_id.as_deref()?);
+    let dashboard = dashboards::get_dashboard(org_id, dashboard_id).await.ok()?;
+    panel_query(&dashboard, panel_id)
+}
+
+/// Panel limits and trellis are part of the v5 panel config and transformations of v6 panels,
+/// older dashboards have neither.
+fn panel_query(dashboard: &Dashboard, panel_id: &str) -> Option<PanelQuery> {
+    let (config, queries, transformations) = match dashboard.version {
+        5 => dashboard
+            .v5
+            .as_ref()?
+            .tabs
+            .iter()
+            .flat_map(|tab| tab.panels.iter())
+            .find(|panel| panel.id == panel_id)
+            .map(|panel| (&panel.config, &panel.queries, vec![])),
+        6 => dashboard
+            .v6
+            .as_ref()?
+            .tabs
+            .iter()
+            .flat_map(|tab| tab.panels.iter())
+            .find(|panel| panel.id == panel_id)
+            .map(|panel| {
+                (
+                    &panel.config,
+                    &panel.queries,
+                    panel.transformations.clone().unwrap_or_default(),
+                )
+            }),
+        _ => None,
+    }?;
+    let query = PanelQuery {
+        timeout_secs: config.query_timeout_secs(),
+        max_rows: config.max_rows(),
+        transformations,
+        trellis: trellis_paging(config, queries),
+    };
+    (query != PanelQuery::default()).then_some(query)
+}
+
+fn trellis_paging(config: &v5::PanelConfig, queries: &[v5::Query]) -> Option<TrellisPaging> {
+    let trellis = config.trellis()?;
+    let max_panels = trellis.max_panels.filter(|v| *v > 0)?;
+    let breakdown = queries
+        .iter()
+        .flat_map(|query| query.fields.breakdown.iter().flatten())
+        .map(|item| item.alias.to_string())
+        .filter(|alias| !alias.is_empty())
+        .collect::<Vec<_>>();
+    (!breakdown.is_empty()).then_some(TrellisPaging {
+        breakdown,
+        max_panels,
+        page: trellis.page.unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use config::{
+        meta::dashboards::{v5, v6},
+        utils::json,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_panel_query() {
+        let v5: v5::Dashboard = json::from_str(
+            r#"{"version": 5, "title": "d1", "description": "", "tabs": [{"tabId": "default", "name": "Default", "panels": [
+                {"id": "p1", "type": "table", "title": "rows", "description": "", "queryType": "sql", "queries": [],
+                 "config": {"show_legends": true, "legends_position": null, "base_map": null, "map_view": null, "query_timeout_secs": 10, "max_rows": 500},
+                 "layout": {"x": 0, "y": 0, "w": 12, "h": 9, "i": 1}},
+                {"id": "p2", "type": "line", "title": "errors", "description": "", "queryType": "sql", "queries": [],
+                 "config": {"show_legends": true, "legends_position": null, "base_map": null, "map_view": null},
+                 "layout": {"x": 0, "y": 0, "w": 12, "h": 9, "i": 2}}
+            ]}]}"#,
+        )
+        .unwrap();
+        let dashboard: Dashboard = v5.clone().into();
+        let query = panel_query(&dashboard, "p1").unwrap();
+        assert_eq!(
+            query,
+            PanelQuery {
+                timeout_secs: Some(10),
+                max_rows: Some(500),
+                transformations: vec![],
+                trellis: None,
+            }
+        );
+        assert!(panel_query(&dashboard, "p2").is_none());
+        assert!(panel_query(&dashboard, "p3").is_none());
+
+        let mut req: search::Request = json::from_str(
+            r#"{"query":{"sql":"SELECT * FROM default","start_time":0,"end_time":0,"size":-1},"timeout":60}"#,
+        )
+        .unwrap();
+        let limited = query.limit(&req);
+        assert_eq!((limited.timeout, limited.query.size), (10, 500));
+        req.timeout = 5;
+        req.query.size = 100;
+        let limited = query.limit(&req);
+        assert_eq!((limited.timeout, limited.query.size), (5, 100));
+
+        let mut v6 = v6::Dashboard::from(v5);
+        v6.tabs[0].panels[1].transformations = Some(vec![Transformation::Rename {
+            fields: [("a".to_string(), "b".to_string())].into(),
+        }]);
+        let dashboard: Dashboard = v6.into();
+        let query = panel_query(&dashboard, "p2").unwrap();
+        let mut res = search::Response {
+            hits: vec![json::json!({"a": 1})],
+            ..Default::default()
+        };
+        query.transform(&mut res);
+        assert_eq!(res.hits, vec![json::json!({"b": 1})]);
+    }
+
+    #[test]
+    fn test_page_trellis() {
+        let v5: v5::Dashboard = json::from_str(
+            r#"{"version": 5, "title": "d1", "description": "", "tabs": [{"tabId": "default", "name": "Default", "panels": [
+                {"id": "p1", "type": "line", "title": "errors", "description": "", "queryType": "sql",
+                 "queries": [{"query": "SELECT host, count(*) AS y_axis_1 FROM default GROUP BY host", "vrlFunctionQuery": null, "customQuery": false,
+                   "fields": {"stream": "default", "stream_type": "logs", "x": [], "y": [], "filter": {"filterType": "group", "logicalOperator": "AND", "conditions": []},
+                     "breakdown": [{"label": "host", "alias": "breakdown_1", "column": "host", "color": null}]},
+                   "config": {"promql_legend": ""}}],
+                 "config": {"show_legends": true, "legends_position": null, "base_map": null, "map_view": null,
+                   "trellis": {"layout": null, "num_of_columns": 2, "max_panels": 2, "page": 1}},
+                 "layout": {"x": 0, "y": 0, "w": 12, "h": 9, "i": 1}}
+            ]}]}"#,
+        )
+        .unwrap();
+        let dashboard: Dashboard = v5.into();
+        let query = panel_query(&dashboard, "p1").unwrap();
+        assert_eq!(
+            query.trellis,
+            Some(TrellisPaging {
+                breakdown: vec!["breakdown_1".to_string()],
+                max_panels: 2,
+                page: 1,
+            })
+        );
+
+        let mut res = search::Response {
+            hits: ["a", "b", "a", "c", "d", "e", "c"]
+                .iter()
+                .map(|host| json::json!({"breakdown_1": host, "y_axis_1": 1}))
+                .collect(),
+            ..Default::default()
+        };
+        query.page_trellis(&mut res);
+        let hosts = res
+            .hits
+            .iter()
+            .map(|hit| hit["breakdown_1"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(hosts, vec!["c", "d", "c"]);
+        assert_eq!(
+            res.trellis,
+            Some(TrellisGroups {
+                page: 1,
+                max_panels: 2,
+                total: 5,
+                groups: vec!["c".to_string(), "d".to_string()],
+            })
+        );
+    }
+}
@@ -137,10 +137,11 @@ pub async fn process_search_stream_request(
 
     if req.query.from == 0 && !req.query.track_total_hits && req.query.streaming_id.is_none() {
         // check cache for the first page
-        let c_resp = match cache::check_cache_v2(&trace_id, &org_id, stream_type, &req, use_cache)
-            .instrument(search_span.clone())
-            .await
-        {
+        let cached =
+            cache::check_cache_v2(&trace_id, &org_id, stream_type, &user_id, &req, use_cache)
+                .instrument(search_span.clone())
+                .await;
+        let c_resp = match cached {
             Ok(v) => v,
             Err(e) => {
                 log::error!(
@@ -767,6 +768,8 @@ async fn get_partitions(
         // vrl is not required for _search_partition
         query_fn: Default::default(),
         streaming_output: true,
+        time_ranges: req.query.time_ranges.clone(),
+        variables: req.query.variables.clone(),
     };
 
     let res = SearchService::search_partition(
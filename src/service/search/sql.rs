@@ -20,10 +20,14 @@ use chrono::Duration;
 use config::{
     ALL_VALUES_COL_NAME, ID_COL_NAME, ORIGINAL_DATA_COL_NAME, TIMESTAMP_COL_NAME, get_config,
     meta::{
+        column_mask::ColumnMaskPolicy,
         inverted_index::InvertedIndexOptimizeMode,
-        search::SearchEventType,
+        row_policy::{policy_key, render_placeholders, user_attribute},
+        search::{DASHBOARD_ALL, SearchEventType},
         sql::{OrderBy, Sql as MetaSql, TableReferenceExt, resolve_stream_names_with_type},
+        sql_policy::SqlPolicy,
         stream::StreamType,
+        ui_profile::UiSection,
     },
     utils::sql::AGGREGATE_UDF_LIST,
 };
@@ -43,8 +47,8 @@ use sqlparser::{
     ast::{
         BinaryOperator, DuplicateTreatment, Expr, Function, FunctionArg, FunctionArgExpr,
         FunctionArgumentList, FunctionArguments, GroupByExpr, Ident, ObjectName, OrderByExpr,
-        Query, Select, SelectItem, SetExpr, Statement, TableFactor, TableWithJoins, Value,
-        VisitMut, VisitorMut, helpers::attached_token::AttachedToken,
+        Query, Select, SelectItem, SetExpr, Statement, TableAlias, TableFactor, TableWithJoins,
+        Value, VisitMut, VisitorMut, helpers::attached_token::AttachedToken, visit_expressions_mut,
     },
     dialect::PostgreSqlDialect,
     parser::Parser,
@@ -58,6 +62,7 @@ use super::{
     request::Request,
     utils::{conjunction, is_field, is_value, split_conjunction, trim_quotes},
 };
+use crate::common::infra::config::{COLUMN_MASKS, ROW_POLICIES, SQL_POLICIES};
 
 pub static RE_ONLY_SELECT: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)select[ ]+\*").unwrap());
 pub static RE_SELECT_FROM: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)SELECT (.*) FROM").unwrap());
@@ -66,8 +71,6 @@ pub static RE_WHERE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i) where (.*)").u
 pub static RE_HISTOGRAM: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?i)histogram\(([^\)]*)\)").unwrap());
 
-const DASHBOARD_ALL: &str = "_o2_all_";
-
 #[derive(Clone, Debug)]
 pub struct Sql {
     pub sql: String,
@@ -92,6 +95,7 @@ pub struct Sql {
     pub use_inverted_index: bool, // if can use inverted index
     pub index_condition: Option<IndexCondition>, // use for tantivy index
     pub index_optimize_mode: Option<InvertedIndexOptimizeMode>,
+    pub search_event_type: Option<SearchEventType>,
 }
 
 impl Sql {
@@ -100,6 +104,19 @@ impl Sql {
             .search_event_type
             .as_ref()
             .and_then(|s| SearchEventType::try_from(s.as_str()).ok());
+        if let Some(user_id) = req.user_id.as_deref() {
+            check_sql_policy(&req.org_id, user_id, query).await?;
+            check_ui_profile(&req.org_id, user_id, req.stream_type, search_event_type).await?;
+            let sql =
+                apply_access_policies(&req.org_id, user_id, req.stream_type, &query.sql).await?;
+            if sql != query.sql {
+                let query = SearchQuery {
+                    sql,
+                    ..query.clone()
+                };
+                return Self::new(&query, &req.org_id, req.stream_type, search_event_type).await;
+            }
+        }
         Self::new(query, &req.org_id, req.stream_type, search_event_type).await
     }
 
@@ -339,6 +356,7 @@ impl Sql {
             use_inverted_index,
             index_condition,
             index_optimize_mode,
+            search_event_type,
         })
     }
 }
@@ -1104,6 +1122,33 @@ impl VisitorMut for MatchVisitor {
     }
 }
 
+/// Collects the names of the functions used in the query, in order of appearance
+struct FunctionNameVisitor {
+    pub function_names: Vec<String>,
+}
+
+impl FunctionNameVisitor {
+    fn new() -> Self {
+        Self {
+            function_names: Vec::new(),
+        }
+    }
+}
+
+impl VisitorMut for FunctionNameVisitor {
+    type Break = ();
+
+    fn pre_visit_expr(&mut self, expr: &mut Expr) -> ControlFlow<Self::Break> {
+        if let Expr::Function(func) = expr {
+            let name = func.name.to_string().to_lowercase();
+            if !self.function_names.contains(&name) {
+                self.function_names.push(name);
+            }
+        }
+        ControlFlow::Continue(())
+    }
+}
+
 struct FieldNameVisitor {
     pub field_names: HashSet<String>,
 }
@@ -1644,6 +1689,265 @@ fn checking_inverted_index_inner(index_fields: &HashSet<&String>, expr: &Expr) -
     }
 }
 
+/// Refuses the explorer queries of the user when their UI profile hides the explorer of the
+/// stream type. The dashboards, alerts and reports of the user still query the streams.
+async fn check_ui_profile(
+    org_id: &str,
+    user_id: &str,
+    stream_type: StreamType,
+    search_event_type: Option<SearchEventType>,
+) -> Result<(), Error> {
+    if !matches!(search_event_type, Some(SearchEventType::UI)) {
+        return Ok(());
+    }
+    let Some(section) = UiSection::explorer(stream_type) else {
+        return Ok(());
+    };
+    if crate::service::ui_profile::hides(org_id, user_id, section).await {
+        return Err(Error::ErrorCode(ErrorCodes::SearchSQLNotValid(format!(
+            "the {stream_type} explorer is not available to the user"
+        ))));
+    }
+    Ok(())
+}
+
+/// Checks the query against the sql policy of the org, when the policy applies to the role of
+/// the user. The queries of unknown users are refused while the policy is enabled.
+async fn check_sql_policy(org_id: &str, user_id: &str, query: &SearchQuery) -> Result<(), Error> {
+    let Some(policy) = SQL_POLICIES.get(org_id).map(|p| p.value().clone()) else {
+        return Ok(());
+    };
+    if !policy.enabled {
+        return Ok(());
+    }
+    let Some(user) = crate::service::users::get_user(Some(org_id), user_id).await else {
+        return Err(Error::ErrorCode(ErrorCodes::SearchSQLNotValid(format!(
+            "user {user_id} is not a member of the org, the sql policy can't be checked"
+        ))));
+    };
+    if !policy.applies_to(&user.role) {
+        return Ok(());
+    }
+    validate_sql_policy(&policy, &query.sql, query.start_time, query.end_time)
+}
+
+fn validate_sql_policy(
+    policy: &SqlPolicy,
+    sql: &str,
+    start_time: i64,
+    end_time: i64,
+) -> Result<(), Error> {
+    let not_valid = |msg: String| Error::ErrorCode(ErrorCodes::SearchSQLNotValid(msg));
+    if !policy.allows_time_range(start_time, end_time) {
+        return Err(not_valid(format!(
+            "time range exceeds the maximum of {} hours allowed by the sql policy",
+            policy.max_time_range_hours
+        )));
+    }
+    let stream_names =
+        resolve_stream_names_with_type(sql).map_err(|e| Error::Message(e.to_string()))?;
+    if let Some(stream) = stream_names
+        .iter()
+        .map(|s| s.stream_name())
+        .find(|s| !policy.allows_stream(s))
+    {
+        return Err(not_valid(format!(
+            "stream {stream} is not allowed by the sql policy"
+        )));
+    }
+    let mut statement = Parser::parse_sql(&PostgreSqlDialect {}, sql)
+        .map_err(|e| Error::Message(e.to_string()))?
+        .pop()
+        .unwrap();
+    let mut function_visitor = FunctionNameVisitor::new();
+    let _ = statement.visit(&mut function_visitor);
+    if let Some(name) = function_visitor
+        .function_names
+        .iter()
+        .find(|f| !policy.allows_function(f))
+    {
+        return Err(not_valid(format!(
+            "function {name} is not allowed by the sql policy"
+        )));
+    }
+    Ok(())
+}
+
+/// Applies the column masks and the row policies of the role of the user to the query. The reads
+/// of a stream with masked columns are replaced by a subquery of the masked columns, and the
+/// predicate of a row policy is added with AND to every read of its stream, before the masking.
+/// The sql is unchanged when no policy applies. The queries of unknown users are refused when any
+/// role has a policy on one of the queried streams.
+pub async fn apply_access_policies(
+    org_id: &str,
+    user_id: &str,
+    stream_type: StreamType,
+    sql: &str,
+) -> Result<String, Error> {
+    if COLUMN_MASKS.is_empty() && ROW_POLICIES.is_empty() {
+        return Ok(sql.to_string());
+    }
+    let stream_names =
+        resolve_stream_names_with_type(sql).map_err(|e| Error::Message(e.to_string()))?;
+    let Some(user) = crate::service::users::get_user(Some(org_id), user_id).await else {
+        if let Some(stream) = stream_names.iter().find(|stream| {
+            let prefix = format!(
+                "{org_id}/{}/{}/",
+                stream.get_stream_type(stream_type),
+                stream.stream_name()
+            );
+            COLUMN_MASKS.iter().any(|p| p.key().starts_with(&prefix))
+                || ROW_POLICIES.iter().any(|p| p.key().starts_with(&prefix))
+        }) {
+            return Err(Error::ErrorCode(ErrorCodes::SearchSQLNotValid(format!(
+                "stream {} has access policies, they can't be applied to the unknown user {user_id}",
+                stream.stream_name()
+            ))));
+        }
+        return Ok(sql.to_string());
+    };
+    let mut projections = HashMap::new();
+    let mut predicates = HashMap::new();
+    for stream in stream_names.iter() {
+        let stream_type = stream.get_stream_type(stream_type);
+        let stream_name = stream.stream_name();
+        let key = format!(
+            "{org_id}/{}",
+            policy_key(stream_type, &stream_name, &user.role)
+        );
+        if let Some(policy) = COLUMN_MASKS.get(&key).map(|p| p.value().clone()) {
+            let schema = infra::schema::get(org_id, &stream_name, stream_type).await?;
+            projections.insert(
+                (stream_type, stream_name.clone()),
+                masked_projection(&schema, &policy),
+            );
+        }
+        if let Some(policy) = ROW_POLICIES.get(&key).map(|p| p.value().clone()) {
+            let predicate =
+                parse_row_predicate(&policy.predicate, |name| user_attribute(&user, name))
+                    .map_err(|e| {
+                        Error::ErrorCode(ErrorCodes::SearchSQLNotValid(format!(
+                            "row policy of stream {stream_name}: {e}"
+                        )))
+                    })?;
+            predicates.insert((stream_type, stream_name), predicate);
+        }
+    }
+    let mut sql = sql.to_string();
+    if !projections.is_empty() {
+        sql = add_column_masks(&sql, stream_type, &projections)?;
+    }
+    if !predicates.is_empty() {
+        sql = add_row_policies(&sql, stream_type, &predicates)?;
+    }
+    Ok(sql)
+}
+
+/// Projection of the masked read of a stream, the masked columns keep their name. The columns
+/// holding the whole record are null, they would show the masked values.
+fn masked_projection(schema: &Schema, policy: &ColumnMaskPolicy) -> String {
+    schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let name = field.name();
+            let column = Ident::with_quote('"', name).to_string();
+            match policy.mask(name) {
+                Some(mask) => format!("{} AS {column}", mask.sql_expr(&column)),
+                None if name == ORIGINAL_DATA_COL_NAME || name == ALL_VALUES_COL_NAME => {
+                    format!("NULL AS {column}")
+                }
+                None => column,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Parses the predicate of a row policy, the `{user.<attribute>}` placeholders of the string
+/// literals are replaced with the values given by `attribute`. The predicate must be a single
+/// expression without subqueries.
+pub fn parse_row_predicate(
+    predicate: &str,
+    attribute: impl Fn(&str) -> Option<String>,
+) -> Result<Expr, String> {
+    let not_valid = || "the predicate must be a single boolean expression".to_string();
+    let sql = format!("SELECT * FROM t WHERE {predicate}");
+    let statements = Parser::parse_sql(&PostgreSqlDialect {}, &sql).map_err(|e| e.to_string())?;
+    let [statement @ Statement::Query(query)] = statements.as_slice() else {
+        return Err(not_valid());
+    };
+    let SetExpr::Select(select) = query.body.as_ref() else {
+        return Err(not_valid());
+    };
+    let Some(mut expr) = select.selection.clone() else {
+        return Err(not_valid());
+    };
+    // anything after the expression, like a union, is part of the statement only
+    if statement.to_string() != format!("SELECT * FROM t WHERE {expr}") {
+        return Err(not_valid());
+    }
+    let ret = visit_expressions_mut(&mut expr, |expr| {
+        match expr {
+            Expr::Subquery(_) | Expr::Exists { .. } | Expr::InSubquery { .. } => {
+                return ControlFlow::Break(
+                    "subqueries are not allowed in a row policy".to_string(),
+                );
+            }
+            Expr::Value(Value::SingleQuotedString(s)) => match render_placeholders(s, &attribute) {
+                Ok(v) => *s = v,
+                Err(e) => return ControlFlow::Break(e),
+            },
+            _ => {}
+        }
+        ControlFlow::Continue(())
+    });
+    match ret {
+        ControlFlow::Break(e) => Err(e),
+        ControlFlow::Continue(()) => Ok(expr),
+    }
+}
+
+/// Replaces the reads of the masked streams by a subquery of the masked projection.
+fn add_column_masks(
+    sql: &str,
+    stream_type: StreamType,
+    projections: &HashMap<(StreamType, String), String>,
+) -> Result<String, Error> {
+    let mut statement = Parser::parse_sql(&PostgreSqlDialect {}, sql)
+        .map_err(|e| Error::Message(e.to_string()))?
+        .pop()
+        .ok_or_else(|| Error::Message("empty sql".to_string()))?;
+    let mut visitor = AddColumnMasksVisitor {
+        stream_type,
+        projections,
+    };
+    if let ControlFlow::Break(e) = statement.visit(&mut visitor) {
+        return Err(Error::Message(e));
+    }
+    Ok(statement.to_string())
+}
+
+/// Adds the row policy predicates, by stream, to every read of their stream in the query.
+fn add_row_policies(
+    sql: &str,
+    stream_type: StreamType,
+    predicates: &HashMap<(StreamType, String), Expr>,
+) -> Result<String, Error> {
+    let mut statement = Parser::parse_sql(&PostgreSqlDialect {}, sql)
+        .map_err(|e| Error::Message(e.to_string()))?
+        .pop()
+        .ok_or_else(|| Error::Message("empty sql".to_string()))?;
+    let mut visitor = AddRowPoliciesVisitor {
+        stream_type,
+        predicates,
+    };
+    if let ControlFlow::Break(e) = statement.visit(&mut visitor) {
+        return Err(Error::Message(e));
+    }
+    Ok(statement.to_string())
+}
+
 pub fn generate_histogram_interval(time_range: Option<(i64, i64)>, num: u16) -> String {
     if time_range.is_none() || time_range.unwrap().eq(&(0, 0)) {
         return "1 hour".to_string();
@@ -1737,6 +2041,50 @@ pub fn pickup_where(sql: &str, meta: Option<MetaSql>) -> Result<Option<String>,
     Ok(Some(where_str))
 }
 
+/// WHERE clauses of every SELECT of the query, including the subqueries.
+pub fn where_predicates(sql: &str) -> Vec<String> {
+    let Ok(statements) = Parser::parse_sql(&PostgreSqlDialect {}, sql) else {
+        return vec![];
+    };
+    let mut visitor = WherePredicatesVisitor::default();
+    for statement in statements.iter() {
+        // `Visit` is not imported, its `visit` would shadow the one of `VisitMut`
+        let _ = sqlparser::ast::Visit::visit(statement, &mut visitor);
+    }
+    visitor.predicates
+}
+
+#[derive(Default)]
+struct WherePredicatesVisitor {
+    predicates: Vec<String>,
+}
+
+impl WherePredicatesVisitor {
+    fn collect(&mut self, body: &SetExpr) {
+        match body {
+            SetExpr::Select(select) => {
+                if let Some(selection) = select.selection.as_ref() {
+                    self.predicates.push(selection.to_string());
+                }
+            }
+            SetExpr::SetOperation { left, right, .. } => {
+                self.collect(left);
+                self.collect(right);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl sqlparser::ast::Visitor for WherePredicatesVisitor {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+        self.collect(&query.body);
+        ControlFlow::Continue(())
+    }
+}
+
 fn o2_id_is_needed(
     schemas: &HashMap<TableReference, Arc<SchemaCache>>,
     search_event_type: &Option<SearchEventType>,
@@ -1939,29 +2287,7 @@ impl VisitorMut for AddNewFiltersWithAndOperatorVisitor {
             return ControlFlow::Break(());
         };
         match query.body.as_mut() {
-            SetExpr::Select(statement) => match statement.selection.as_mut() {
-                None => {
-                    statement.selection = Some(filter_exprs);
-                }
-                Some(selection) => {
-                    statement.selection = Some(Expr::BinaryOp {
-                        left: if matches!(
-                            selection,
-                            Expr::BinaryOp {
-                                op: BinaryOperator::Or,
-                                ..
-                            }
-                        ) {
-                            Box::new(Expr::Nested(Box::new(selection.clone())))
-                        } else {
-                            Box::new(selection.clone())
-                        },
-                        op: BinaryOperator::And,
-                        // the right side must be AND so don't need to check
-                        right: Box::new(filter_exprs),
-                    });
-                }
-            },
+            SetExpr::Select(statement) => add_selection_with_and_operator(statement, filter_exprs),
             _ => {
                 return ControlFlow::Break(());
             }
@@ -1970,6 +2296,264 @@ impl VisitorMut for AddNewFiltersWithAndOperatorVisitor {
     }
 }
 
+// the right side must be AND or nested so don't need to check
+fn add_selection_with_and_operator(statement: &mut Select, filter_exprs: Expr) {
+    match statement.selection.as_mut() {
+        None => {
+            statement.selection = Some(filter_exprs);
+        }
+        Some(selection) => {
+            statement.selection = Some(Expr::BinaryOp {
+                left: if matches!(
+                    selection,
+                    Expr::BinaryOp {
+                        op: BinaryOperator::Or,
+                        ..
+                    }
+                ) {
+                    Box::new(Expr::Nested(Box::new(selection.clone())))
+                } else {
+                    Box::new(selection.clone())
+                },
+                op: BinaryOperator::And,
+                right: Box::new(filter_exprs),
+            });
+        }
+    }
+}
+
+/// Restricts every stream scanned by the query to the union of the given `[start, end)` time
+/// ranges. The ranges must be normalized, see [`config::meta::search::normalize_time_ranges`].
+pub fn add_time_ranges_filter(sql: &str, ranges: &[[i64; 2]]) -> infra::errors::Result<String> {
+    if ranges.is_empty() {
+        return Ok(sql.to_string());
+    }
+
+    let mut statement = Parser::parse_sql(&PostgreSqlDialect {}, sql)
+        .map_err(|e| Error::Message(e.to_string()))?
+        .pop()
+        .ok_or_else(|| Error::Message("empty sql".to_string()))?;
+    let mut visitor = AddTimeRangesFilterVisitor::new(ranges);
+    if statement.visit(&mut visitor).is_break() {
+        return Err(Error::ErrorCode(ErrorCodes::InvalidParams(
+            "time_ranges is not supported for join, union or with queries".to_string(),
+        )));
+    }
+    Ok(statement.to_string())
+}
+
+struct AddTimeRangesFilterVisitor<'a> {
+    ranges: &'a [[i64; 2]],
+}
+
+impl<'a> AddTimeRangesFilterVisitor<'a> {
+    fn new(ranges: &'a [[i64; 2]]) -> Self {
+        Self { ranges }
+    }
+
+    fn build_selection(&self) -> Expr {
+        let ts_col = || Box::new(Expr::Identifier(Ident::new(TIMESTAMP_COL_NAME)));
+        let ranges = self
+            .ranges
+            .iter()
+            .map(|[start, end]| {
+                Expr::Nested(Box::new(Expr::BinaryOp {
+                    left: Box::new(Expr::BinaryOp {
+                        left: ts_col(),
+                        op: BinaryOperator::GtEq,
+                        right: Box::new(Expr::Value(Value::Number(start.to_string(), false))),
+                    }),
+                    op: BinaryOperator::And,
+                    right: Box::new(Expr::BinaryOp {
+                        left: ts_col(),
+                        op: BinaryOperator::Lt,
+                        right: Box::new(Expr::Value(Value::Number(end.to_string(), false))),
+                    }),
+                }))
+            })
+            .reduce(|left, right| Expr::BinaryOp {
+                left: Box::new(left),
+                op: BinaryOperator::Or,
+                right: Box::new(right),
+            })
+            .unwrap();
+        if self.ranges.len() > 1 {
+            Expr::Nested(Box::new(ranges))
+        } else {
+            ranges
+        }
+    }
+}
+
+impl VisitorMut for AddTimeRangesFilterVisitor<'_> {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &mut Query) -> ControlFlow<Self::Break> {
+        if query.with.is_some() {
+            return ControlFlow::Break(());
+        }
+        let SetExpr::Select(statement) = query.body.as_mut() else {
+            return ControlFlow::Break(());
+        };
+        if statement.from.len() > 1 || statement.from.iter().any(|from| !from.joins.is_empty()) {
+            return ControlFlow::Break(());
+        }
+        // subqueries in from are filtered when they are visited
+        if statement
+            .from
+            .first()
+            .is_some_and(|from| matches!(from.relation, TableFactor::Table { .. }))
+        {
+            add_selection_with_and_operator(statement, self.build_selection());
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+struct AddColumnMasksVisitor<'a> {
+    stream_type: StreamType,
+    projections: &'a HashMap<(StreamType, String), String>,
+}
+
+impl VisitorMut for AddColumnMasksVisitor<'_> {
+    type Break = String;
+
+    // the subqueries are added after the visit of the read, they are not visited again
+    fn post_visit_table_factor(&mut self, relation: &mut TableFactor) -> ControlFlow<Self::Break> {
+        let TableFactor::Table { name, alias, .. } = relation else {
+            return ControlFlow::Continue(());
+        };
+        let Some(stream_name) = name.0.last().map(|i| i.value.clone()) else {
+            return ControlFlow::Continue(());
+        };
+        let stream_type = match name.0.len() {
+            1 => self.stream_type,
+            n => StreamType::from(name.0[n - 2].value.as_str()),
+        };
+        let Some(projection) = self.projections.get(&(stream_type, stream_name)) else {
+            return ControlFlow::Continue(());
+        };
+        let sql = format!("SELECT {projection} FROM {name}");
+        let subquery = match Parser::parse_sql(&PostgreSqlDialect {}, &sql) {
+            Ok(mut statements) => match statements.pop() {
+                Some(Statement::Query(subquery)) => subquery,
+                _ => return ControlFlow::Break(format!("can't mask the columns of {name}")),
+            },
+            Err(e) => return ControlFlow::Break(e.to_string()),
+        };
+        // the subquery keeps the name of the stream for the qualified columns
+        let alias = alias.take().unwrap_or_else(|| TableAlias {
+            name: name.0[name.0.len() - 1].clone(),
+            columns: vec![],
+        });
+        *relation = TableFactor::Derived {
+            lateral: false,
+            subquery,
+            alias: Some(alias),
+        };
+        ControlFlow::Continue(())
+    }
+}
+
+struct AddRowPoliciesVisitor<'a> {
+    stream_type: StreamType,
+    predicates: &'a HashMap<(StreamType, String), Expr>,
+}
+
+impl AddRowPoliciesVisitor<'_> {
+    fn predicate(&self, name: &ObjectName) -> Option<&Expr> {
+        let stream_name = name.0.last()?.value.clone();
+        let stream_type = match name.0.len() {
+            1 => self.stream_type,
+            n => StreamType::from(name.0[n - 2].value.as_str()),
+        };
+        self.predicates.get(&(stream_type, stream_name))
+    }
+
+    fn add_to_set_expr(&self, body: &mut SetExpr) -> Result<(), String> {
+        match body {
+            SetExpr::Select(select) => self.add_to_select(select),
+            SetExpr::SetOperation { left, right, .. } => {
+                self.add_to_set_expr(left)?;
+                self.add_to_set_expr(right)
+            }
+            // nested queries are visited on their own
+            _ => Ok(()),
+        }
+    }
+
+    fn add_to_select(&self, select: &mut Select) -> Result<(), String> {
+        // a select of one stream is filtered in place, which keeps the partition and index
+        // optimizations of its filters
+        if let [from] = select.from.as_slice()
+            && from.joins.is_empty()
+            && let TableFactor::Table {
+                name, args: None, ..
+            } = &from.relation
+            && let Some(predicate) = self.predicate(name)
+        {
+            let predicate = Expr::Nested(Box::new(predicate.clone()));
+            add_selection_with_and_operator(select, predicate);
+            return Ok(());
+        }
+        for from in select.from.iter_mut() {
+            self.wrap_table_with_joins(from)?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the reads of the streams with a policy by subqueries, the subqueries are
+    /// filtered when they are visited.
+    fn wrap_table_with_joins(&self, from: &mut TableWithJoins) -> Result<(), String> {
+        self.wrap_table_factor(&mut from.relation)?;
+        for join in from.joins.iter_mut() {
+            self.wrap_table_factor(&mut join.relation)?;
+        }
+        Ok(())
+    }
+
+    fn wrap_table_factor(&self, relation: &mut TableFactor) -> Result<(), String> {
+        match relation {
+            TableFactor::NestedJoin {
+                table_with_joins, ..
+            } => self.wrap_table_with_joins(table_with_joins),
+            TableFactor::Table { name, alias, .. } if self.predicate(name).is_some() => {
+                // the subquery keeps the name of the stream for the qualified columns
+                let alias = alias.take().unwrap_or_else(|| TableAlias {
+                    name: name.0[name.0.len() - 1].clone(),
+                    columns: vec![],
+                });
+                let sql = format!("SELECT * FROM {name}");
+                let Some(Statement::Query(subquery)) =
+                    Parser::parse_sql(&PostgreSqlDialect {}, &sql)
+                        .map_err(|e| e.to_string())?
+                        .pop()
+                else {
+                    return Err(format!("can't apply the row policy of {name}"));
+                };
+                *relation = TableFactor::Derived {
+                    lateral: false,
+                    subquery,
+                    alias: Some(alias),
+                };
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl VisitorMut for AddRowPoliciesVisitor<'_> {
+    type Break = String;
+
+    fn pre_visit_query(&mut self, query: &mut Query) -> ControlFlow<Self::Break> {
+        match self.add_to_set_expr(query.body.as_mut()) {
+            Ok(()) => ControlFlow::Continue(()),
+            Err(e) => ControlFlow::Break(e),
+        }
+    }
+}
+
 // replace _o2_all_ with true
 struct RemoveDashboardAllVisitor {}
 
@@ -2089,6 +2673,147 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_validate_sql_policy() {
+        let policy = SqlPolicy {
+            enabled: true,
+            allowed_functions: vec!["histogram".to_string(), "count".to_string()],
+            allowed_streams: vec!["public_*".to_string()],
+            max_time_range_hours: 1,
+            ..Default::default()
+        };
+        let hour = 3600 * 1_000_000;
+        let sql = "SELECT histogram(_timestamp) AS t, COUNT(*) FROM public_web GROUP BY t";
+        assert!(validate_sql_policy(&policy, sql, 0, hour).is_ok());
+        assert!(validate_sql_policy(&policy, sql, 0, 2 * hour).is_err());
+        let sql = "SELECT COUNT(*) FROM internal";
+        assert!(validate_sql_policy(&policy, sql, 0, hour).is_err());
+        let sql = "SELECT * FROM public_web WHERE regexp_like(log, 'a.*')";
+        assert!(validate_sql_policy(&policy, sql, 0, hour).is_err());
+    }
+
+    #[test]
+    fn test_add_row_policies() {
+        let attribute = |name: &str| (name == "domain").then(|| "acme'io".to_string());
+        let predicate =
+            parse_row_predicate("tenant_id = '{user.domain}' OR public", attribute).unwrap();
+        assert_eq!(predicate.to_string(), "tenant_id = 'acme''io' OR public");
+        assert!(parse_row_predicate("a IN (SELECT a FROM b)", attribute).is_err());
+        assert!(parse_row_predicate("a = 1 UNION SELECT * FROM b", attribute).is_err());
+        assert!(parse_row_predicate("a = 1 LIMIT 1", attribute).is_err());
+        assert!(parse_row_predicate("a = '{user.tenant}'", attribute).is_err());
+        assert!(parse_row_predicate("a = {user.domain}", attribute).is_err());
+
+        let predicates = HashMap::from_iter([(
+            (StreamType::Logs, "web".to_string()),
+            parse_row_predicate("tenant_id = 'a'", attribute).unwrap(),
+        )]);
+        let cases = [
+            (
+                "SELECT * FROM web",
+                "SELECT * FROM web WHERE (tenant_id = 'a')",
+            ),
+            (
+                "SELECT * FROM \"web\" WHERE a = 1 OR b = 2",
+                "SELECT * FROM \"web\" WHERE (a = 1 OR b = 2) AND (tenant_id = 'a')",
+            ),
+            ("SELECT * FROM other", "SELECT * FROM other"),
+            ("SELECT * FROM metrics.web", "SELECT * FROM metrics.web"),
+            (
+                "SELECT * FROM logs.web",
+                "SELECT * FROM logs.web WHERE (tenant_id = 'a')",
+            ),
+            (
+                "SELECT w.a FROM web AS w JOIN other o ON w.a = o.a",
+                "SELECT w.a FROM (SELECT * FROM web WHERE (tenant_id = 'a')) AS w JOIN other AS o ON w.a = o.a",
+            ),
+            (
+                "SELECT * FROM other WHERE a IN (SELECT a FROM web)",
+                "SELECT * FROM other WHERE a IN (SELECT a FROM web WHERE (tenant_id = 'a'))",
+            ),
+            (
+                "SELECT a FROM web UNION ALL SELECT a FROM web",
+                "SELECT a FROM web WHERE (tenant_id = 'a') UNION ALL SELECT a FROM web WHERE (tenant_id = 'a')",
+            ),
+            (
+                "WITH x AS (SELECT * FROM web) SELECT * FROM x",
+                "WITH x AS (SELECT * FROM web WHERE (tenant_id = 'a')) SELECT * FROM x",
+            ),
+            (
+                "SELECT * FROM web, other",
+                "SELECT * FROM (SELECT * FROM web WHERE (tenant_id = 'a')) AS web, other",
+            ),
+        ];
+        for (sql, expected) in cases {
+            assert_eq!(
+                add_row_policies(sql, StreamType::Logs, &predicates).unwrap(),
+                expected,
+                "{sql}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_add_column_masks() {
+        use arrow_schema::{DataType, Field};
+        use config::meta::{
+            column_mask::{ColumnMask, MaskType},
+            user::UserRole,
+        };
+
+        let schema = Schema::new(vec![
+            Field::new(TIMESTAMP_COL_NAME, DataType::Int64, false),
+            Field::new("email", DataType::Utf8, true),
+            Field::new(ORIGINAL_DATA_COL_NAME, DataType::Utf8, true),
+        ]);
+        let policy = ColumnMaskPolicy {
+            stream_type: StreamType::Logs,
+            stream_name: "web".to_string(),
+            role: UserRole::Viewer,
+            columns: vec![ColumnMask {
+                field: "email".to_string(),
+                mask_type: MaskType::Hash,
+                ..Default::default()
+            }],
+            updated_at: 0,
+        };
+        let projection = masked_projection(&schema, &policy);
+        assert_eq!(
+            projection,
+            format!(
+                "\"{TIMESTAMP_COL_NAME}\", encode(sha256(CAST(\"email\" AS VARCHAR)), 'hex') AS \"email\", NULL AS \"{ORIGINAL_DATA_COL_NAME}\""
+            )
+        );
+
+        let projections =
+            HashMap::from_iter([((StreamType::Logs, "web".to_string()), projection.clone())]);
+        let sql = "SELECT * FROM web WHERE email = 'a'";
+        assert_eq!(
+            add_column_masks(sql, StreamType::Logs, &projections).unwrap(),
+            format!("SELECT * FROM (SELECT {projection} FROM web) AS web WHERE email = 'a'")
+        );
+        let sql = "SELECT w.email FROM web w JOIN other o ON w.email = o.email";
+        assert_eq!(
+            add_column_masks(sql, StreamType::Logs, &projections).unwrap(),
+            format!(
+                "SELECT w.email FROM (SELECT {projection} FROM web) AS w JOIN other AS o ON w.email = o.email"
+            )
+        );
+
+        // the rows are filtered before the masking
+        let predicates = HashMap::from_iter([(
+            (StreamType::Logs, "web".to_string()),
+            parse_row_predicate("email LIKE '%@a.io'", |_| None).unwrap(),
+        )]);
+        let sql = add_column_masks("SELECT * FROM web", StreamType::Logs, &projections).unwrap();
+        assert_eq!(
+            add_row_policies(&sql, StreamType::Logs, &predicates).unwrap(),
+            format!(
+                "SELECT * FROM (SELECT {projection} FROM web WHERE (email LIKE '%@a.io')) AS web"
+            )
+        );
+    }
+
     #[test]
     fn test_index_visitor1() {
         let sql = "SELECT * FROM t WHERE name = 'a' AND age = 1 AND (name = 'b' OR (match_all('good') AND match_all('bar'))) AND (match_all('foo') OR age = 2)";
@@ -2790,6 +3515,38 @@ mod tests {
         assert_eq!(result, sql.to_string());
     }
 
+    #[test]
+    fn test_add_time_ranges_filter() {
+        let sql = "SELECT * FROM table1 WHERE a = 1 OR b = 2 ORDER BY _timestamp DESC";
+        let result = add_time_ranges_filter(sql, &[[10, 20], [30, 40]]).unwrap();
+        assert_eq!(
+            result,
+            "SELECT * FROM table1 WHERE (a = 1 OR b = 2) AND ((_timestamp >= 10 AND _timestamp < 20) OR (_timestamp >= 30 AND _timestamp < 40)) ORDER BY _timestamp DESC"
+        );
+
+        let sql = "SELECT histogram(_timestamp) AS ts, count(*) FROM table1 GROUP BY ts";
+        let result = add_time_ranges_filter(sql, &[[10, 20]]).unwrap();
+        assert_eq!(
+            result,
+            "SELECT histogram(_timestamp) AS ts, count(*) FROM table1 WHERE (_timestamp >= 10 AND _timestamp < 20) GROUP BY ts"
+        );
+
+        let sql = "SELECT * FROM table1";
+        assert_eq!(add_time_ranges_filter(sql, &[]).unwrap(), sql);
+
+        let sql = "SELECT count(*) FROM (SELECT a FROM table1 GROUP BY a)";
+        let result = add_time_ranges_filter(sql, &[[10, 20]]).unwrap();
+        assert_eq!(
+            result,
+            "SELECT count(*) FROM (SELECT a FROM table1 WHERE (_timestamp >= 10 AND _timestamp < 20) GROUP BY a)"
+        );
+
+        let sql = "SELECT * FROM table1 JOIN table2 ON table1.a = table2.a";
+        assert!(add_time_ranges_filter(sql, &[[10, 20]]).is_err());
+        let sql = "SELECT a FROM table1 UNION SELECT a FROM table2";
+        assert!(add_time_ranges_filter(sql, &[[10, 20]]).is_err());
+    }
+
     #[test]
     fn test_remove_dashboard_all_visitor() {
         let sql = "select * from t where field1 = '_o2_all_'";
@@ -2883,4 +3640,21 @@ mod tests {
         let expected = "SELECT * FROM t WHERE true AND false AND field3 = 'value3'";
         assert_eq!(statement.to_string(), expected);
     }
+
+    #[test]
+    fn test_where_predicates() {
+        assert_eq!(
+            where_predicates(
+                "SELECT * FROM t WHERE a = 1 AND b LIKE '%x%' ORDER BY _timestamp LIMIT 10"
+            ),
+            vec!["a = 1 AND b LIKE '%x%'"]
+        );
+        assert_eq!(
+            where_predicates(
+                "SELECT a FROM (SELECT * FROM t WHERE c > 2) WHERE a = 1 UNION ALL SELECT a FROM u WHERE d IN ('x')"
+            ),
+            vec!["a = 1", "d IN ('x')", "c > 2"]
+        );
+        assert!(where_predicates("SELECT count(*) FROM t").is_empty());
+    }
 }
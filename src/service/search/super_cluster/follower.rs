@@ -174,16 +174,12 @@ pub async fn search(
 
     // get nodes
     let get_node_start = std::time::Instant::now();
-    let role_group = req
+    let search_event_type = req
         .search_event_type
         .as_ref()
-        .map(|v| {
-            SearchEventType::try_from(v.as_str())
-                .ok()
-                .map(RoleGroup::from)
-        })
-        .unwrap_or(None);
-    let mut nodes = get_online_querier_nodes(&trace_id, role_group).await?;
+        .and_then(|v| SearchEventType::try_from(v.as_str()).ok());
+    let role_group = search_event_type.map(RoleGroup::from);
+    let mut nodes = get_online_querier_nodes(&trace_id, role_group, search_event_type).await?;
 
     // local mode, only use local node as querier node
     if req.local_mode.unwrap_or_default() && LOCAL_NODE.is_querier() {
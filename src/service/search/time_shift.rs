@@ -0,0 +1,158 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Time shifted comparisons of a query, e.g. the current values against one day and one week
+//! ago, computed in a single request.
+
+use chrono::DateTime;
+use config::{
+    TIMESTAMP_COL_NAME,
+    meta::{search, stream::StreamType},
+    utils::{json::Value, sql::is_aggregate_query, time::parse_str_to_timestamp_micros},
+};
+use futures::future::try_join_all;
+use infra::errors::{Error, ErrorCodes};
+use proto::cluster_rpc::SearchQuery;
+
+use super::{cache::cacher::get_ts_col_order_by, sql::Sql};
+
+/// Runs the query over its time range and over the time range shifted back by each of its
+/// `time_shifts`. The shifted results are aligned with the current ones and returned in
+/// [`search::Response::time_shifts`].
+pub async fn search(
+    trace_id: &str,
+    org_id: &str,
+    stream_type: StreamType,
+    user_id: Option<String>,
+    in_req: &search::Request,
+    range_error: String,
+) -> Result<search::Response, Error> {
+    let shifts = search::parse_time_shifts(&in_req.query.time_shifts)
+        .map_err(|e| Error::ErrorCode(ErrorCodes::InvalidParams(e)))?;
+    let mut req = in_req.clone();
+    req.query.time_shifts.clear();
+
+    let query = SearchQuery {
+        sql: req.query.sql.clone(),
+        start_time: req.query.start_time,
+        end_time: req.query.end_time,
+        ..Default::default()
+    };
+    let sql = Sql::new(&query, org_id, stream_type, req.search_type).await?;
+    let is_aggregate = is_aggregate_query(&req.query.sql).unwrap_or(false);
+    let ts_column = get_ts_col_order_by(&sql, TIMESTAMP_COL_NAME, is_aggregate).map(|(v, _)| v);
+
+    let shifted_reqs = shifts
+        .iter()
+        .map(|(_, offset)| {
+            let mut req = req.clone();
+            req.query.start_time -= offset;
+            req.query.end_time -= offset;
+            req
+        })
+        .collect::<Vec<_>>();
+    let shifted = shifted_reqs.iter().enumerate().map(|(i, req)| {
+        let trace_id = format!("{trace_id}-shift{i}");
+        let user_id = user_id.clone();
+        async move {
+            super::cache::search_with_dedup(
+                &trace_id,
+                org_id,
+                stream_type,
+                user_id,
+                req,
+                String::new(),
+            )
+            .await
+        }
+    });
+    let (mut res, shifted) = tokio::try_join!(
+        super::cache::search_with_dedup(
+            trace_id,
+            org_id,
+            stream_type,
+            user_id.clone(),
+            &req,
+            range_error,
+        ),
+        try_join_all(shifted),
+    )?;
+
+    for ((time_shift, offset), shifted) in shifts.into_iter().zip(shifted) {
+        let mut hits = shifted.hits;
+        if let Some(ts_column) = &ts_column {
+            align_hits(&mut hits, ts_column, offset);
+        }
+        res.time_shifts.push(search::TimeShiftResult {
+            time_shift,
+            hits,
+            total: shifted.total,
+            scan_size: shifted.scan_size,
+        });
+    }
+    Ok(res)
+}
+
+/// Moves the timestamps in `ts_column` of the hits forward by `offset` microseconds. Numbers
+/// stay microseconds, strings like the output of `histogram` keep their format.
+pub fn align_hits(hits: &mut [Value], ts_column: &str, offset: i64) {
+    for hit in hits.iter_mut() {
+        let Some(ts) = hit.get_mut(ts_column) else {
+            continue;
+        };
+        let aligned = match &*ts {
+            Value::Number(n) => n.as_i64().map(|v| Value::from(v + offset)),
+            Value::String(s) => parse_str_to_timestamp_micros(s)
+                .ok()
+                .and_then(|v| DateTime::from_timestamp_micros(v + offset))
+                .map(|v| {
+                    let format = if s.contains('T') {
+                        "%Y-%m-%dT%H:%M:%S"
+                    } else {
+                        "%Y-%m-%d %H:%M:%S"
+                    };
+                    Value::from(v.naive_utc().format(format).to_string())
+                }),
+            _ => None,
+        };
+        if let Some(aligned) = aligned {
+            *ts = aligned;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use config::utils::json;
+
+    use super::*;
+
+    #[test]
+    fn test_align_hits() {
+        let day = 86_400_000_000;
+        let mut hits = vec![
+            json::json!({"_timestamp": 1_700_000_000_000_000i64, "count": 1}),
+            json::json!({"x_axis_1": "2024-01-01T10:00:00", "count": 2}),
+            json::json!({"x_axis_1": "2024-01-01 10:00:00", "count": 3}),
+            json::json!({"count": 4}),
+        ];
+        align_hits(&mut hits[..1], TIMESTAMP_COL_NAME, day);
+        align_hits(&mut hits[1..], "x_axis_1", day);
+        assert_eq!(hits[0]["_timestamp"], 1_700_000_000_000_000i64 + day);
+        assert_eq!(hits[1]["x_axis_1"], "2024-01-02T10:00:00");
+        assert_eq!(hits[2]["x_axis_1"], "2024-01-02 10:00:00");
+        assert_eq!(hits[3], json::json!({"count": 4}));
+    }
+}
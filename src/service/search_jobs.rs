@@ -15,18 +15,21 @@
 
 use chrono::{DateTime, Datelike, TimeZone, Utc};
 use config::{
+    SMTP_CLIENT,
     meta::{
         cluster::RoleGroup,
-        search::{self, Response, SearchPartitionRequest},
+        search::{self, Response, SearchJobOptions, SearchPartitionRequest},
         stream::StreamType,
     },
-    utils::json,
+    utils::{json, time::now_micros},
 };
+use futures::{StreamExt, TryStreamExt};
 use infra::{
     errors::{Error, ErrorCodes},
     storage,
     table::entity::{search_job_partitions::Model as PartitionJob, search_jobs::Model as Job},
 };
+use lettre::{AsyncTransport, Message};
 use o2_enterprise::enterprise::{
     common::config::get_config as get_o2_config,
     super_cluster::{
@@ -39,6 +42,7 @@ use tokio::sync::mpsc;
 use super::grpc::make_grpc_search_client;
 use crate::service::{
     db::search_job::{search_job_partitions::*, search_job_results::*, search_jobs::*},
+    file_list,
     search::grpc_search::{grpc_search, grpc_search_partition},
 };
 
@@ -83,10 +87,35 @@ pub async fn run(id: i64) -> Result<(), anyhow::Error> {
         }
     });
 
+    let options: SearchJobOptions = json::from_str(&job.payload).unwrap_or_default();
+    let res = execute(id, &job, &options).await;
+    // errors without message are retried, only notify once the job is done
+    let done = match &res {
+        Ok(_) => true,
+        Err(_) => get(&job.id, &job.org_id)
+            .await
+            .is_ok_and(|job| job.error_message.is_some()),
+    };
+    if done {
+        notify(&job, &options, &res).await;
+    }
+    res?;
+
+    log::info!(
+        "[SEARCH JOB {id}] finish running, job_id: {}, time_elapsed: {}ms",
+        job.id,
+        start.elapsed().as_millis()
+    );
+
+    Ok(())
+}
+
+// steps 3 to 6 of `run`, returns the path of the result
+async fn execute(id: i64, job: &Job, options: &SearchJobOptions) -> Result<String, anyhow::Error> {
     // 3. check if the job is previous running (get error then retry, be cancel then retry) (case 1)
     //    or do not have previous run (case 2)
     if job.partition_num.is_none() {
-        let res = handle_search_partition(&job).await;
+        let res = handle_search_partition(job).await;
         if let Err(e) = res {
             set_job_error_message(&job.id, &job.trace_id, &e.to_string()).await?;
             log::error!(
@@ -97,6 +126,21 @@ pub async fn run(id: i64) -> Result<(), anyhow::Error> {
         }
     }
 
+    // rehydrate the archived files before reading them
+    let archive_boundary = search::archive_boundary(
+        config::get_config().limit.search_archive_after_days,
+        now_micros(),
+    );
+    if let Some(boundary) =
+        archive_boundary.filter(|b| options.include_archived && job.start_time < *b)
+    {
+        let files = rehydrate(job, boundary).await?;
+        log::info!(
+            "[SEARCH JOB {id}] job_id: {}, rehydrated {files} archived files",
+            job.id
+        );
+    }
+
     // 4. get all partition jobs from `search_job_partitions` table
     let req: search::Request = json::from_str(&job.payload)?;
     let limit = if req.query.size > 0 {
@@ -113,7 +157,7 @@ pub async fn run(id: i64) -> Result<(), anyhow::Error> {
         for partition_job in partition_jobs.iter() {
             // check if the job is still running
             check_status(id, &job.id, &job.org_id).await?;
-            let res = run_partition_job(id, &job, partition_job, req.clone(), need).await;
+            let res = run_partition_job(id, job, partition_job, req.clone(), need).await;
             let total = match res {
                 Ok(total) => total,
                 Err(e) => {
@@ -148,12 +192,117 @@ pub async fn run(id: i64) -> Result<(), anyhow::Error> {
     // 6. update `search_jobs` table
     set_job_finish(&job.id, &job.trace_id, &path).await?;
 
-    log::info!(
-        "[SEARCH JOB {id}] finish running, job_id: {}, time_elapsed: {}ms",
-        job.id,
-        start.elapsed().as_millis()
-    );
+    Ok(path)
+}
+
+/// Downloads the files of the archived part of the job time range into the cache, so the
+/// partitions read them from the hot tier.
+async fn rehydrate(job: &Job, boundary: i64) -> Result<usize, anyhow::Error> {
+    let stream_type = StreamType::from(job.stream_type.as_str());
+    let stream_names: Vec<String> = json::from_str(&job.stream_names)?;
+    let end_time = std::cmp::min(job.end_time, boundary);
+    let mut files = Vec::new();
+    for stream_name in stream_names.iter() {
+        let settings = infra::schema::get_settings(&job.org_id, stream_name, stream_type)
+            .await
+            .unwrap_or_default();
+        let time_level =
+            infra::schema::unwrap_partition_time_level(settings.partition_time_level, stream_type);
+        files.extend(
+            file_list::query(
+                &job.trace_id,
+                &job.org_id,
+                stream_name,
+                stream_type,
+                time_level,
+                job.start_time,
+                end_time,
+            )
+            .await?,
+        );
+    }
+    let num = files.len();
+    futures::stream::iter(files)
+        .map(|file| async move {
+            infra::cache::file_data::download(
+                &file.account,
+                &file.key,
+                Some(file.meta.compressed_size as usize),
+            )
+            .await
+        })
+        .buffer_unordered(config::get_config().limit.cpu_num)
+        .try_collect::<Vec<_>>()
+        .await?;
+    Ok(num)
+}
+
+/// Sends the status of the finished job to the webhook and the emails of the job options.
+async fn notify(job: &Job, options: &SearchJobOptions, res: &Result<String, anyhow::Error>) {
+    let Some(notify) = options.notify.as_ref() else {
+        return;
+    };
+    let (status, error_message) = match res {
+        Ok(_) => ("finished", None),
+        Err(e) => ("error", Some(e.to_string())),
+    };
+    if let Some(url) = notify.webhook_url.as_ref() {
+        let body = json::json!({
+            "job_id": job.id,
+            "trace_id": job.trace_id,
+            "org_id": job.org_id,
+            "status": status,
+            "error_message": error_message,
+        });
+        let resp = reqwest::Client::new().post(url).json(&body).send().await;
+        if let Err(e) = resp.and_then(|r| r.error_for_status()) {
+            log::error!(
+                "[SEARCH JOB] job_id: {}, failed to call the webhook: {e}",
+                job.id
+            );
+        }
+    }
+    if !notify.emails.is_empty() {
+        let msg = match &error_message {
+            None => format!(
+                "Search job {} of organization {} finished, its result is available in the search jobs page.",
+                job.id, job.org_id
+            ),
+            Some(e) => format!(
+                "Search job {} of organization {} failed: {e}",
+                job.id, job.org_id
+            ),
+        };
+        let subject = format!("Search job {} {status}", job.id);
+        if let Err(e) = send_email(&subject, &notify.emails, msg).await {
+            log::error!(
+                "[SEARCH JOB] job_id: {}, failed to send the email: {e}",
+                job.id
+            );
+        }
+    }
+}
 
+async fn send_email(
+    subject: &str,
+    recipients: &[String],
+    msg: String,
+) -> Result<(), anyhow::Error> {
+    let cfg = config::get_config();
+    if !cfg.smtp.smtp_enabled {
+        return Err(anyhow::anyhow!("SMTP configuration not enabled"));
+    }
+    let mut email = Message::builder()
+        .from(cfg.smtp.smtp_from_email.parse()?)
+        .subject(subject.to_string());
+    for recipient in recipients {
+        email = email.to(recipient.parse()?);
+    }
+    if !cfg.smtp.smtp_reply_to.is_empty() {
+        email = email.reply_to(cfg.smtp.smtp_reply_to.parse()?);
+    }
+    let email = email.body(msg)?;
+    SMTP_CLIENT.as_ref().unwrap().send(email).await?;
     Ok(())
 }
 
@@ -19,19 +19,22 @@ use config::META_ORG_ID;
 use config::{
     SIZE_IN_MB,
     cluster::LOCAL_NODE,
-    get_config,
+    get_config, ider,
     meta::{
         self_reporting::{
             ReportingData,
             error::ErrorData,
+            query_audit::QueryAuditRecord,
             usage::{RequestStats, TriggerData, UsageData, UsageEvent, UsageType},
         },
-        stream::StreamType,
+        stream::{StreamStorageSample, StreamType},
     },
     metrics,
 };
 #[cfg(feature = "enterprise")]
 use o2_enterprise::enterprise::common::auditor;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 #[cfg(feature = "enterprise")]
 use proto::cluster_rpc;
 use tokio::sync::oneshot;
@@ -39,6 +42,10 @@ use tokio::sync::oneshot;
 mod ingestion;
 mod queues;
 
+/// Head of the query audit chain of this node: chain id, next seq and hash of the last record.
+static QUERY_AUDIT_CHAIN: Lazy<Mutex<(String, i64, String)>> =
+    Lazy::new(|| Mutex::new((ider::uuid(), 0, String::new())));
+
 pub async fn run() {
     let cfg = get_config();
     if !cfg.common.usage_enabled {
@@ -185,6 +192,61 @@ pub async fn report_request_usage_stats(
     }
 }
 
+/// Publishes the daily storage sample of a stream for billing.
+pub async fn publish_storage_usage(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    sample: &StreamStorageSample,
+) {
+    if !get_config().common.usage_enabled {
+        return;
+    }
+
+    let Some(day) = DateTime::from_timestamp_micros(sample.day) else {
+        return;
+    };
+    let usage = UsageData {
+        _timestamp: sample.day,
+        event: UsageType::StorageUsage.into(),
+        day: day.day(),
+        hour: day.hour(),
+        month: day.month(),
+        year: day.year(),
+        event_time_hour: format!(
+            "{:04}{:02}{:02}{:02}",
+            day.year(),
+            day.month(),
+            day.day(),
+            day.hour()
+        ),
+        org_id: org_id.to_owned(),
+        request_body: UsageType::StorageUsage.to_string(),
+        size: sample.storage_size as f64 / SIZE_IN_MB,
+        unit: "MB".to_owned(),
+        user_email: "".to_owned(),
+        response_time: 0.0,
+        function: None,
+        num_records: sample.doc_num,
+        dropped_records: 0,
+        stream_type,
+        stream_name: stream_name.to_owned(),
+        min_ts: None,
+        max_ts: None,
+        cached_ratio: None,
+        compressed_size: Some(sample.compressed_size as f64 / SIZE_IN_MB),
+        search_type: None,
+        search_event_context: None,
+        trace_id: None,
+        took_wait_in_queue: None,
+        result_cache_ratio: None,
+        is_partial: false,
+        work_group: None,
+        node_name: Some(LOCAL_NODE.name.clone()),
+    };
+    publish_usage(vec![usage]).await;
+}
+
 async fn publish_usage(usages: Vec<UsageData>) {
     let cfg = get_config();
     if !cfg.common.usage_enabled {
@@ -245,6 +307,27 @@ pub async fn publish_error(error_data: ErrorData) {
     }
 }
 
+/// Appends the record to the query audit chain of this node and queues it for ingestion.
+pub async fn publish_query_audit(mut record: QueryAuditRecord) {
+    if !get_config().common.query_audit_enabled {
+        return;
+    }
+
+    {
+        let mut chain = QUERY_AUDIT_CHAIN.lock();
+        let (chain_id, seq, last_hash) = &mut *chain;
+        record.seal(chain_id, *seq, last_hash);
+        *seq += 1;
+        *last_hash = record.hash.clone();
+    }
+    if let Err(e) = queues::ERROR_QUEUE
+        .enqueue(ReportingData::QueryAudit(Box::new(record)))
+        .await
+    {
+        log::error!("[SELF-REPORTING] Failed to send query audit to background ingesting job: {e}");
+    }
+}
+
 pub async fn flush() {
     // flush audit data
     #[cfg(feature = "enterprise")]
@@ -252,7 +335,9 @@ pub async fn flush() {
 
     let cfg = get_config();
     // only ingester and querier nodes report usage
-    if !cfg.common.usage_enabled || (!LOCAL_NODE.is_ingester() && !LOCAL_NODE.is_querier()) {
+    if !(cfg.common.usage_enabled || cfg.common.query_audit_enabled)
+        || (!LOCAL_NODE.is_ingester() && !LOCAL_NODE.is_querier())
+    {
         return;
     }
 
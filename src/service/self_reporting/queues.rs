@@ -21,6 +21,7 @@ use config::{
         self_reporting::{
             ReportingData, ReportingMessage, ReportingQueue, ReportingRunner,
             error::ErrorData,
+            query_audit::{QUERY_AUDIT_STREAM, QueryAuditRecord},
             usage::{ERROR_STREAM, TRIGGERS_USAGE_STREAM, TriggerData},
         },
         stream::{StreamParams, StreamType},
@@ -144,15 +145,16 @@ async fn ingest_buffered_data(thread_id: usize, buffered: Vec<ReportingData>) {
         buffered.len()
     );
 
-    let (usages, triggers, errors) = buffered.into_iter().fold(
-        (Vec::new(), Vec::new(), Vec::new()),
-        |(mut usages, mut triggers, mut errors), item| {
+    let (usages, triggers, errors, audits) = buffered.into_iter().fold(
+        (Vec::new(), Vec::new(), Vec::new(), Vec::new()),
+        |(mut usages, mut triggers, mut errors, mut audits), item| {
             match item {
                 ReportingData::Usage(usage) => usages.push(*usage),
                 ReportingData::Trigger(trigger) => triggers.push(json::to_value(*trigger).unwrap()),
                 ReportingData::Error(error) => errors.push(json::to_value(*error).unwrap()),
+                ReportingData::QueryAudit(audit) => audits.push(json::to_value(*audit).unwrap()),
             }
-            (usages, triggers, errors)
+            (usages, triggers, errors, audits)
         },
     );
 
@@ -221,4 +223,26 @@ async fn ingest_buffered_data(thread_id: usize, buffered: Vec<ReportingData>) {
             }
         }
     }
+
+    if !audits.is_empty() {
+        let audit_stream = StreamParams::new(META_ORG_ID, QUERY_AUDIT_STREAM, StreamType::Logs);
+        // the audit records are sealed already, push them back as they are so the chain stays
+        // intact, the seq orders them again
+        if super::ingestion::ingest_reporting_data(audits.clone(), audit_stream)
+            .await
+            .is_err()
+        {
+            for audit_json in audits {
+                let audit: QueryAuditRecord = json::from_value(audit_json).unwrap();
+                if let Err(e) = ERROR_QUEUE
+                    .enqueue(ReportingData::QueryAudit(Box::new(audit)))
+                    .await
+                {
+                    log::error!(
+                        "[SELF-REPORTING] Error in pushing back un-ingested QueryAuditRecord to ErrorQueue: {e}"
+                    );
+                }
+            }
+        }
+    }
 }
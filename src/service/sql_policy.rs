@@ -0,0 +1,69 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use chrono::Utc;
+use config::meta::sql_policy::SqlPolicy;
+
+use crate::service::db;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SqlPolicyError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Sql policy not found")]
+    NotFound,
+
+    #[error("Invalid sql policy: {0}")]
+    InvalidPolicy(String),
+}
+
+pub async fn get(org_id: &str) -> Result<SqlPolicy, SqlPolicyError> {
+    db::sql_policy::get(org_id)
+        .await
+        .map_err(|_| SqlPolicyError::NotFound)
+}
+
+pub async fn save(org_id: &str, mut policy: SqlPolicy) -> Result<SqlPolicy, SqlPolicyError> {
+    if policy.enabled && policy.roles.is_empty() {
+        return Err(SqlPolicyError::InvalidPolicy(
+            "an enabled policy needs at least one role".to_string(),
+        ));
+    }
+    if policy.max_time_range_hours < 0 {
+        return Err(SqlPolicyError::InvalidPolicy(
+            "max_time_range_hours can't be negative".to_string(),
+        ));
+    }
+    policy.allowed_functions = policy
+        .allowed_functions
+        .into_iter()
+        .map(|f| f.trim().to_lowercase())
+        .filter(|f| !f.is_empty())
+        .collect();
+    policy.allowed_streams = policy
+        .allowed_streams
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    policy.updated_at = Utc::now().timestamp_micros();
+    db::sql_policy::set(org_id, &policy).await?;
+    Ok(policy)
+}
+
+pub async fn delete(org_id: &str) -> Result<(), SqlPolicyError> {
+    get(org_id).await?;
+    Ok(db::sql_policy::delete(org_id).await?)
+}
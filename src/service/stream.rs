@@ -25,6 +25,7 @@ use config::{
             DistinctField, StreamParams, StreamSettings, StreamStats, StreamType,
             UpdateStreamSettings,
         },
+        workspace::{ResourceType, WorkspaceResource},
     },
     utils::{json, time::now_micros},
 };
@@ -150,12 +151,14 @@ pub fn stream_res(
     stats: Option<StreamStats>,
 ) -> Stream {
     let storage_type = if is_local_disk_storage() { LOCAL } else { S3 };
+    let mut settings = unwrap_stream_settings(&schema).unwrap_or_default();
     let mappings = schema
         .fields()
         .iter()
         .map(|field| StreamProperty {
             prop_type: field.data_type().to_string(),
             name: field.name().to_string(),
+            metadata: settings.field_metadata.get(field.name()).cloned(),
         })
         .collect::<Vec<_>>();
 
@@ -181,7 +184,6 @@ pub fn stream_res(
         None
     };
 
-    let mut settings = unwrap_stream_settings(&schema).unwrap_or_default();
     if settings == StreamSettings::default() {
         settings.approx_partition = get_config()
             .common
@@ -373,6 +375,10 @@ pub async fn update_stream_settings(
                 settings.data_retention = data_retention;
             }
 
+            if let Some(derived_data_retention) = new_settings.derived_data_retention {
+                settings.derived_data_retention = derived_data_retention;
+            }
+
             if let Some(index_original_data) = new_settings.index_original_data {
                 settings.index_original_data = index_original_data;
             }
@@ -451,6 +457,87 @@ pub async fn update_stream_settings(
                     .retain(|field| !new_settings.index_fields.remove.contains(field));
             }
 
+            if !new_settings.ip_fields.add.is_empty() {
+                for field in new_settings.ip_fields.add {
+                    if !settings.ip_fields.contains(&field) {
+                        settings.ip_fields.push(field);
+                    }
+                }
+            }
+            if !new_settings.ip_fields.remove.is_empty() {
+                settings
+                    .ip_fields
+                    .retain(|field| !new_settings.ip_fields.remove.contains(field));
+            }
+
+            if !new_settings.threat_intel_fields.add.is_empty() {
+                for field in new_settings.threat_intel_fields.add {
+                    if !settings.threat_intel_fields.contains(&field) {
+                        settings.threat_intel_fields.push(field);
+                    }
+                }
+            }
+            if !new_settings.threat_intel_fields.remove.is_empty() {
+                settings
+                    .threat_intel_fields
+                    .retain(|field| !new_settings.threat_intel_fields.remove.contains(field));
+            }
+
+            if let Some(timestamp) = new_settings.timestamp {
+                // an empty config resets to the default `_timestamp` handling
+                settings.timestamp = if timestamp == Default::default() {
+                    None
+                } else {
+                    Some(timestamp)
+                };
+            }
+
+            if let Some(multiline) = new_settings.multiline {
+                // an empty start pattern turns multi-line reassembly off
+                if multiline.start_pattern.is_empty() {
+                    settings.multiline = None;
+                } else {
+                    if let Err(e) = regex::Regex::new(&multiline.start_pattern) {
+                        return Ok(HttpResponse::BadRequest().json(MetaHttpResponse::error(
+                            http::StatusCode::BAD_REQUEST,
+                            format!("invalid multiline start_pattern: {e}"),
+                        )));
+                    }
+                    if multiline.max_lines == 0 {
+                        return Ok(HttpResponse::BadRequest().json(MetaHttpResponse::error(
+                            http::StatusCode::BAD_REQUEST,
+                            "multiline max_lines must be greater than 0",
+                        )));
+                    }
+                    settings.multiline = Some(multiline);
+                }
+            }
+
+            if let Some(kv_extraction) = new_settings.kv_extraction {
+                // an empty source field turns the extraction off
+                settings.kv_extraction = if kv_extraction.field.is_empty() {
+                    None
+                } else {
+                    Some(kv_extraction)
+                };
+            }
+
+            for (field, metadata) in new_settings.field_metadata {
+                let field = field.trim().to_string();
+                if field.is_empty() {
+                    return Ok(HttpResponse::BadRequest().json(MetaHttpResponse::error(
+                        http::StatusCode::BAD_REQUEST,
+                        "field_metadata field name can not be empty",
+                    )));
+                }
+                let metadata = metadata.normalize();
+                if metadata.is_empty() {
+                    settings.field_metadata.remove(&field);
+                } else {
+                    settings.field_metadata.insert(field, metadata);
+                }
+            }
+
             if !new_settings.extended_retention_days.add.is_empty() {
                 settings
                     .extended_retention_days
@@ -610,6 +697,11 @@ pub async fn delete_stream(
         )));
     }
 
+    // keep the retention the stream asks for its derived data while its settings and
+    // pipelines still exist
+    crate::service::compact::retention::pin_derived_retention(org_id, stream_type, stream_name)
+        .await;
+
     // delete stream schema
     if let Err(e) = db::schema::delete(org_id, stream_name, Some(stream_type)).await {
         return Ok(HttpResponse::InternalServerError()
@@ -715,6 +807,68 @@ pub async fn stream_delete_inner(
         return Err(e);
     }
 
+    // delete stream hourly stats
+    if let Err(e) =
+        super::stream_hourly_stats::delete_stream(org_id, stream_type, stream_name).await
+    {
+        log::error!(
+            "Failed to delete stream hourly stats for stream: {}/{}/{}, error: {}",
+            org_id,
+            stream_type,
+            stream_name,
+            e
+        );
+    }
+
+    // delete stream storage usage history
+    if let Err(e) =
+        super::stream_storage_usage::delete_stream(org_id, stream_type, stream_name).await
+    {
+        log::error!(
+            "Failed to delete stream storage usage for stream: {}/{}/{}, error: {}",
+            org_id,
+            stream_type,
+            stream_name,
+            e
+        );
+    }
+
+    // delete stream field usage
+    if let Err(e) = super::stream_field_usage::delete_stream(org_id, stream_type, stream_name).await
+    {
+        log::error!(
+            "Failed to delete stream field usage for stream: {}/{}/{}, error: {}",
+            org_id,
+            stream_type,
+            stream_name,
+            e
+        );
+    }
+
+    // delete the slow queries and index recommendations
+    if let Err(e) = super::index_advisor::delete_stream(org_id, stream_type, stream_name).await {
+        log::error!(
+            "Failed to delete index advisor data for stream: {}/{}/{}, error: {}",
+            org_id,
+            stream_type,
+            stream_name,
+            e
+        );
+    }
+
+    // remove the stream from the workspaces
+    let resource_id = WorkspaceResource::stream_id(stream_type, stream_name);
+    if let Err(e) =
+        super::workspaces::delete_resource(org_id, ResourceType::Stream, &resource_id).await
+    {
+        log::error!(
+            "Failed to remove stream from the workspaces: {}/{}, error: {}",
+            org_id,
+            resource_id,
+            e
+        );
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,425 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use arrow_schema::Schema;
+use config::{
+    ALL_VALUES_COL_NAME, ID_COL_NAME, ORIGINAL_DATA_COL_NAME, ider,
+    meta::{
+        pipeline::{
+            Pipeline,
+            components::{NodeData, PipelineSource},
+        },
+        search::{self, SearchEventType},
+        stream::{StreamParams, StreamType},
+    },
+    utils::{json::Value, schema::format_stream_name, time::now_micros},
+};
+use infra::schema::unwrap_stream_settings;
+
+use crate::{
+    common::meta::stream::{StreamCloneRequest, StreamCloneResponse},
+    service::{db, ingestion, pipeline, search as SearchService, stream},
+};
+
+/// Most records copied by a data sample.
+const MAX_SAMPLE_SIZE: i64 = 10_000;
+/// Time window sampled when no start time is given, 24 hours.
+const DEFAULT_SAMPLE_WINDOW: i64 = 86_400_000_000;
+
+#[derive(Debug, thiserror::Error)]
+pub enum StreamCloneError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Stream not found")]
+    NotFound,
+
+    #[error("Stream [{0}] already exists")]
+    AlreadyExists(String),
+
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("Failed to clone stream: {0}")]
+    CloneFailed(String),
+}
+
+/// Clones the schema and settings of a stream into a new stream, optionally together with the
+/// functions and a disabled copy of the pipeline of the source stream and a sample of its records.
+/// The sample is searched as the user, so it only holds the records the user can read.
+pub async fn clone(
+    org_id: &str,
+    user_id: &str,
+    stream_name: &str,
+    stream_type: StreamType,
+    req: StreamCloneRequest,
+) -> Result<StreamCloneResponse, StreamCloneError> {
+    let target_org = req
+        .target_org
+        .as_deref()
+        .map(str::trim)
+        .filter(|o| !o.is_empty())
+        .unwrap_or(org_id)
+        .to_string();
+    let mut target_stream = req.target_stream.trim().to_string();
+    if !config::get_config().common.skip_formatting_stream_name {
+        target_stream = format_stream_name(&target_stream);
+    }
+    if target_stream.is_empty() {
+        return Err(StreamCloneError::InvalidRequest(
+            "target_stream is required".to_string(),
+        ));
+    }
+    if target_org == org_id && target_stream == stream_name {
+        return Err(StreamCloneError::InvalidRequest(
+            "target stream must differ from the source stream".to_string(),
+        ));
+    }
+    let sample = match req.sample.filter(|s| s.size != 0) {
+        None => None,
+        Some(sample) => {
+            if stream_type != StreamType::Logs {
+                return Err(StreamCloneError::InvalidRequest(
+                    "data sample is only supported for logs streams".to_string(),
+                ));
+            }
+            if sample.size < 0 || sample.size > MAX_SAMPLE_SIZE {
+                return Err(StreamCloneError::InvalidRequest(format!(
+                    "sample size should be between 1 and {MAX_SAMPLE_SIZE}"
+                )));
+            }
+            let end_time = sample.end_time.unwrap_or_else(now_micros);
+            let start_time = sample
+                .start_time
+                .unwrap_or(end_time - DEFAULT_SAMPLE_WINDOW);
+            if start_time >= end_time {
+                return Err(StreamCloneError::InvalidRequest(
+                    "sample start_time should be less than end_time".to_string(),
+                ));
+            }
+            Some((sample.size, start_time, end_time))
+        }
+    };
+
+    let schema = infra::schema::get(org_id, stream_name, stream_type).await?;
+    if schema.fields().is_empty() {
+        return Err(StreamCloneError::NotFound);
+    }
+    let existing = infra::schema::get(&target_org, &target_stream, stream_type).await?;
+    if !existing.fields().is_empty() {
+        return Err(StreamCloneError::AlreadyExists(target_stream));
+    }
+
+    // the settings live in the schema metadata, they are saved separately
+    let fields = Schema::new(schema.fields().clone());
+    db::schema::merge(
+        &target_org,
+        &target_stream,
+        stream_type,
+        &fields,
+        Some(now_micros()),
+    )
+    .await
+    .map_err(|e| StreamCloneError::CloneFailed(e.to_string()))?;
+    if let Some(settings) = unwrap_stream_settings(&schema) {
+        let resp = stream::save_stream_settings(&target_org, &target_stream, stream_type, settings)
+            .await
+            .map_err(|e| StreamCloneError::CloneFailed(e.to_string()))?;
+        if !resp.status().is_success() {
+            return Err(StreamCloneError::CloneFailed(
+                "failed to save stream settings".to_string(),
+            ));
+        }
+    }
+
+    let source = StreamParams::new(org_id, stream_name, stream_type);
+    let target = StreamParams::new(&target_org, &target_stream, stream_type);
+    let mut functions = Vec::new();
+    let mut pipeline_name = None;
+    if req.include_functions {
+        if let Some(source_pipeline) = db::pipeline::get_by_stream(&source).await {
+            for name in function_names(&source_pipeline) {
+                if target_org != org_id && db::functions::get(&target_org, &name).await.is_err() {
+                    let mut func = db::functions::get(org_id, &name)
+                        .await
+                        .map_err(|e| StreamCloneError::CloneFailed(e.to_string()))?;
+                    func.streams = None;
+                    db::functions::set(&target_org, &name, &func)
+                        .await
+                        .map_err(|e| StreamCloneError::CloneFailed(e.to_string()))?;
+                }
+                functions.push(name);
+            }
+            let mut cloned = clone_pipeline(&source_pipeline, &source, &target);
+            cloned.id = ider::generate();
+            cloned.created_by = user_id.to_string();
+            let name = cloned.name.clone();
+            pipeline::save_pipeline(cloned)
+                .await
+                .map_err(|e| StreamCloneError::CloneFailed(e.to_string()))?;
+            pipeline_name = Some(name);
+        }
+    }
+
+    let mut records = 0;
+    if let Some((size, start_time, end_time)) = sample {
+        let hits = sample_records(
+            org_id,
+            user_id,
+            stream_name,
+            stream_type,
+            size,
+            (start_time, end_time),
+        )
+        .await?;
+        records = hits.len();
+        ingestion::ingest_internal_records(&target_org, &target_stream, hits)
+            .await
+            .map_err(|e| StreamCloneError::CloneFailed(e.to_string()))?;
+    }
+
+    Ok(StreamCloneResponse {
+        org_id: target_org,
+        stream_name: target_stream,
+        stream_type,
+        fields: fields.fields().len(),
+        functions,
+        pipeline: pipeline_name,
+        records,
+    })
+}
+
+/// Searches the records of the sample with the access policies of the user applied.
+async fn sample_records(
+    org_id: &str,
+    user_id: &str,
+    stream_name: &str,
+    stream_type: StreamType,
+    size: i64,
+    (start_time, end_time): (i64, i64),
+) -> Result<Vec<Value>, StreamCloneError> {
+    let req = search::Request {
+        query: search::Query {
+            sql: format!("SELECT * FROM \"{stream_name}\""),
+            size,
+            start_time,
+            end_time,
+            ..Default::default()
+        },
+        search_type: Some(SearchEventType::Other),
+        ..Default::default()
+    };
+    let trace_id = ider::generate_trace_id();
+    let resp = SearchService::search(
+        &trace_id,
+        org_id,
+        stream_type,
+        Some(user_id.to_string()),
+        &req,
+    )
+    .await?;
+    Ok(resp.hits.into_iter().map(strip_internal_columns).collect())
+}
+
+fn function_names(pipeline: &Pipeline) -> Vec<String> {
+    let mut names = pipeline
+        .nodes
+        .iter()
+        .filter_map(|node| match &node.data {
+            NodeData::Function(func) => Some(func.name.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Returns a disabled copy of the pipeline reading from the target stream. The source stream
+/// destinations are replaced by the target stream and the other stream destinations are
+/// prefixed with the target stream name, so the copy never writes into the original streams.
+fn clone_pipeline(pipeline: &Pipeline, source: &StreamParams, target: &StreamParams) -> Pipeline {
+    let mut cloned = pipeline.clone();
+    cloned.id = String::new();
+    cloned.version = 0;
+    cloned.enabled = false;
+    cloned.org = target.org_id.to_string();
+    cloned.name = format!("{}_{}", pipeline.name, target.stream_name);
+    cloned.source = PipelineSource::Realtime(target.clone());
+    for node in cloned.nodes.iter_mut() {
+        if let NodeData::Stream(params) = &mut node.data {
+            *params = if params == source {
+                target.clone()
+            } else {
+                StreamParams::new(
+                    &target.org_id,
+                    &format!("{}_{}", target.stream_name, params.stream_name),
+                    params.stream_type,
+                )
+            };
+        }
+    }
+    cloned
+}
+
+/// Removes the columns added at ingestion, they are added again when the sample is written.
+fn strip_internal_columns(mut hit: Value) -> Value {
+    if let Some(obj) = hit.as_object_mut() {
+        obj.remove(ID_COL_NAME);
+        obj.remove(ORIGINAL_DATA_COL_NAME);
+        obj.remove(ALL_VALUES_COL_NAME);
+    }
+    hit
+}
+
+#[cfg(test)]
+mod tests {
+    use config::{
+        meta::pipeline::components::{Edge, FunctionParams, Node},
+        utils::json::json,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_clone_pipeline() {
+        let source = StreamParams::new("default", "app", StreamType::Logs);
+        let target = StreamParams::new("staging", "app_test", StreamType::Logs);
+        let func = FunctionParams {
+            name: "parse".to_string(),
+            after_flatten: true,
+            num_args: 0,
+        };
+        let other = StreamParams::new("default", "errors", StreamType::Logs);
+        let pipeline = Pipeline {
+            id: "p1".to_string(),
+            version: 3,
+            enabled: true,
+            org: "default".to_string(),
+            name: "app_pipeline".to_string(),
+            description: String::new(),
+            source: PipelineSource::Realtime(source.clone()),
+            nodes: vec![
+                Node::new(
+                    "1".to_string(),
+                    NodeData::Stream(source.clone()),
+                    0.0,
+                    0.0,
+                    "input".to_string(),
+                ),
+                Node::new(
+                    "2".to_string(),
+                    NodeData::Function(func.clone()),
+                    0.0,
+                    0.0,
+                    "default".to_string(),
+                ),
+                Node::new(
+                    "3".to_string(),
+                    NodeData::Function(func),
+                    0.0,
+                    0.0,
+                    "default".to_string(),
+                ),
+                Node::new(
+                    "4".to_string(),
+                    NodeData::Stream(source.clone()),
+                    0.0,
+                    0.0,
+                    "output".to_string(),
+                ),
+                Node::new(
+                    "5".to_string(),
+                    NodeData::Stream(other),
+                    0.0,
+                    0.0,
+                    "output".to_string(),
+                ),
+            ],
+            edges: vec![Edge {
+                id: "e1".to_string(),
+                source: "1".to_string(),
+                target: "2".to_string(),
+            }],
+            created_by: "root@example.com".to_string(),
+        };
+
+        assert_eq!(function_names(&pipeline), vec!["parse".to_string()]);
+
+        let cloned = clone_pipeline(&pipeline, &source, &target);
+        assert!(!cloned.enabled);
+        assert!(cloned.id.is_empty());
+        assert_eq!(cloned.org, "staging");
+        assert_eq!(cloned.name, "app_pipeline_app_test");
+        assert_eq!(cloned.source, PipelineSource::Realtime(target.clone()));
+        assert_eq!(cloned.nodes[0].data, NodeData::Stream(target.clone()));
+        assert_eq!(cloned.nodes[3].data, NodeData::Stream(target));
+        assert_eq!(
+            cloned.nodes[4].data,
+            NodeData::Stream(StreamParams::new(
+                "staging",
+                "app_test_errors",
+                StreamType::Logs
+            ))
+        );
+        assert_eq!(cloned.edges, pipeline.edges);
+    }
+
+    #[test]
+    fn test_strip_internal_columns() {
+        let hit = json!({
+            "_timestamp": 1,
+            "_o2_id": 2,
+            "_original": "{}",
+            "_all_values": "a",
+            "msg": "ok"
+        });
+        let hit = strip_internal_columns(hit);
+        assert_eq!(hit, json!({"_timestamp": 1, "msg": "ok"}));
+    }
+
+    #[tokio::test]
+    async fn test_sample_records_apply_access_policies() {
+        use config::meta::{row_policy::RowPolicy, user::UserRole};
+
+        use crate::common::infra::config::ROW_POLICIES;
+
+        infra::db::create_table().await.unwrap();
+        infra::table::create_user_tables().await.unwrap();
+        let policy = RowPolicy {
+            stream_type: StreamType::Logs,
+            stream_name: "clone_sample_src".to_string(),
+            role: UserRole::Editor,
+            predicate: "tenant_id = '{user.domain}'".to_string(),
+            updated_at: 0,
+        };
+        let key = format!("default/{}", policy.key());
+        ROW_POLICIES.insert(key.clone(), policy);
+
+        // the policies can't be applied to a user who isn't a member, the sample is refused
+        let ret = sample_records(
+            "default",
+            "stranger@example.com",
+            "clone_sample_src",
+            StreamType::Logs,
+            10,
+            (0, now_micros()),
+        )
+        .await;
+        ROW_POLICIES.remove(&key);
+        let err = ret.unwrap_err().to_string();
+        assert!(err.contains("has access policies"), "{err}");
+    }
+}
@@ -0,0 +1,197 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Counts the fields referenced by the queries of each stream, in their projection or
+//! predicates, so the fields nobody queries can be found before they are dropped or no longer
+//! indexed. The counts are accumulated in memory by the search leader and periodically added
+//! to the daily rows of the `stream_field_usage` table.
+
+use config::{
+    get_config,
+    meta::{
+        sql::TableReferenceExt,
+        stream::{StreamFieldUsage, StreamStorageSample, StreamType},
+    },
+    utils::time::now_micros,
+};
+use hashbrown::HashMap;
+use infra::{errors::Error, table::stream_field_usage as table};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+use crate::service::search::sql::Sql;
+
+/// org, stream type, stream name, day
+type UsageKey = (String, StreamType, String, i64);
+
+static PENDING: Lazy<Mutex<HashMap<UsageKey, HashMap<String, StreamFieldUsage>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Counts the fields referenced by the query, written to the db by the next [`flush`].
+pub fn record_query(sql: &Sql) {
+    if !get_config().common.stream_field_usage_enabled {
+        return;
+    }
+    let now = now_micros();
+    let day = StreamStorageSample::day_of(now);
+    let mut pending = PENDING.lock();
+    for (stream, columns) in sql.columns.iter() {
+        if columns.is_empty() {
+            continue;
+        }
+        let key = (
+            sql.org_id.clone(),
+            stream.get_stream_type(sql.stream_type),
+            stream.stream_name(),
+            day,
+        );
+        let fields = pending.entry(key).or_default();
+        for column in columns {
+            let usage = fields
+                .entry(column.clone())
+                .or_insert_with(|| StreamFieldUsage {
+                    field: column.clone(),
+                    ..Default::default()
+                });
+            usage.queries += 1;
+            usage.last_queried = now;
+        }
+    }
+}
+
+/// Writes the accumulated usage to the db, deltas that fail to be written are kept for the
+/// next run.
+pub async fn flush() -> Result<(), Error> {
+    let pending = std::mem::take(&mut *PENDING.lock());
+    let mut last_error = None;
+    for (key, fields) in pending {
+        let (org_id, stream_type, stream_name, day) = &key;
+        let mut failed = HashMap::new();
+        for (field, usage) in fields {
+            if let Err(e) = table::add(org_id, *stream_type, stream_name, *day, &usage).await {
+                log::error!(
+                    "[STATS] write field usage for {org_id}/{stream_type}/{stream_name} error: {e}"
+                );
+                failed.insert(field, usage);
+                last_error = Some(e);
+            }
+        }
+        if !failed.is_empty() {
+            merge(PENDING.lock().entry(key).or_default(), failed);
+        }
+    }
+    match last_error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+fn merge(fields: &mut HashMap<String, StreamFieldUsage>, other: HashMap<String, StreamFieldUsage>) {
+    for (field, usage) in other {
+        let entry = fields.entry(field).or_insert_with(|| StreamFieldUsage {
+            field: usage.field.clone(),
+            ..Default::default()
+        });
+        entry.queries += usage.queries;
+        entry.last_queried = entry.last_queried.max(usage.last_queried);
+    }
+}
+
+/// Returns the usage of the fields of the stream between `start_time` and `end_time`. Every
+/// field of the schema is listed, the ones never queried with no queries, followed by the
+/// queried fields which are no longer in the schema.
+pub async fn list(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<StreamFieldUsage>, Error> {
+    let mut queried = table::list(
+        org_id,
+        stream_type,
+        stream_name,
+        StreamStorageSample::day_of(start_time),
+        end_time,
+    )
+    .await?;
+    let schema = infra::schema::get(org_id, stream_name, stream_type).await?;
+    let mut fields = Vec::with_capacity(schema.fields().len());
+    for field in schema.fields() {
+        fields.push(
+            queried
+                .remove(field.name())
+                .unwrap_or_else(|| StreamFieldUsage {
+                    field: field.name().to_string(),
+                    ..Default::default()
+                }),
+        );
+    }
+    fields.extend(queried.into_values());
+    fields.sort_by(|a, b| {
+        b.queries
+            .cmp(&a.queries)
+            .then_with(|| a.field.cmp(&b.field))
+    });
+    Ok(fields)
+}
+
+pub async fn delete_stream(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> Result<(), Error> {
+    PENDING.lock().retain(|(org, stype, name, _), _| {
+        !(org == org_id && *stype == stream_type && name == stream_name)
+    });
+    table::delete_stream(org_id, stream_type, stream_name).await
+}
+
+/// Removes the usage older than the configured retention.
+pub async fn clean_expired() -> Result<(), Error> {
+    let retention_days = get_config().common.stream_field_usage_retention_days;
+    if retention_days <= 0 {
+        return Ok(());
+    }
+    let before = now_micros() - retention_days * 24 * 3_600_000_000;
+    table::delete_before(StreamStorageSample::day_of(before)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(field: &str, queries: i64, last_queried: i64) -> StreamFieldUsage {
+        StreamFieldUsage {
+            field: field.to_string(),
+            queries,
+            last_queried,
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut fields = HashMap::from([("status".to_string(), usage("status", 2, 20))]);
+        merge(
+            &mut fields,
+            HashMap::from([
+                ("status".to_string(), usage("status", 3, 10)),
+                ("path".to_string(), usage("path", 1, 30)),
+            ]),
+        );
+        assert_eq!(fields["status"], usage("status", 5, 20));
+        assert_eq!(fields["path"], usage("path", 1, 30));
+    }
+}
@@ -0,0 +1,237 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Per-stream, per-hour statistics, accumulated in memory when the ingester
+//! flushes files and the compactor merges them, and periodically written to
+//! the `stream_hourly_stats` table so overview pages don't need live queries.
+
+use arrow::{
+    array::{Array, AsArray, RecordBatch},
+    compute::cast,
+};
+use arrow_schema::DataType;
+use bytes::Bytes;
+use config::{
+    get_config,
+    meta::stream::{FileKey, StreamHourlyStats, StreamType},
+    utils::{parquet::read_columns_from_bytes, time::now_micros},
+};
+use hashbrown::HashMap;
+use infra::{errors::Error, table::stream_hourly_stats as table};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+/// Fields holding the log level, in order of precedence.
+const LEVEL_FIELDS: [&str; 6] = [
+    "level",
+    "severity",
+    "severity_text",
+    "log_level",
+    "loglevel",
+    "lvl",
+];
+
+const ERROR_LEVELS: [&str; 10] = [
+    "error",
+    "err",
+    "fatal",
+    "critical",
+    "crit",
+    "alert",
+    "emerg",
+    "emergency",
+    "panic",
+    "severe",
+];
+
+type StatsKey = (String, StreamType, String, i64);
+
+static PENDING: Lazy<Mutex<HashMap<StatsKey, StreamHourlyStats>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Accumulates a stats delta, written to the db by the next [`flush`].
+pub fn record(org_id: &str, stream_type: StreamType, stream_name: &str, stats: &StreamHourlyStats) {
+    if !get_config().common.stream_hourly_stats_enabled || stats.is_empty() {
+        return;
+    }
+    let key = (
+        org_id.to_string(),
+        stream_type,
+        stream_name.to_string(),
+        stats.hour,
+    );
+    let mut pending = PENDING.lock();
+    let entry = pending.entry(key).or_insert_with(|| StreamHourlyStats {
+        hour: stats.hour,
+        ..Default::default()
+    });
+    *entry += stats;
+}
+
+/// Accumulates the stats of files added to and deleted from the file list.
+pub fn record_files(files: &[FileKey]) {
+    for file in files {
+        // eg: files/default/logs/olympics/2023/08/21/08/xxx.parquet
+        let columns = file.key.splitn(5, '/').collect::<Vec<_>>();
+        if columns.len() < 5 {
+            continue;
+        }
+        let stats = StreamHourlyStats::from_file(&file.meta, file.deleted);
+        record(columns[1], StreamType::from(columns[2]), columns[3], &stats);
+    }
+}
+
+/// Counts the records of a parquet file that have an error log level.
+pub async fn count_error_records(buf: &Bytes) -> Result<i64, anyhow::Error> {
+    let (_, batches) = read_columns_from_bytes(buf, &LEVEL_FIELDS).await?;
+    Ok(count_error_levels(&batches))
+}
+
+fn count_error_levels(batches: &[RecordBatch]) -> i64 {
+    let mut count = 0;
+    for batch in batches {
+        let Some(column) = LEVEL_FIELDS
+            .iter()
+            .find_map(|name| batch.column_by_name(name))
+        else {
+            continue;
+        };
+        let Ok(column) = cast(column, &DataType::Utf8) else {
+            continue;
+        };
+        let column = column.as_string::<i32>();
+        count += (0..column.len())
+            .filter(|&i| {
+                !column.is_null(i)
+                    && ERROR_LEVELS
+                        .iter()
+                        .any(|level| column.value(i).trim().eq_ignore_ascii_case(level))
+            })
+            .count() as i64;
+    }
+    count
+}
+
+/// Writes the accumulated stats to the db, deltas that fail to be written are
+/// kept for the next run.
+pub async fn flush() -> Result<(), Error> {
+    let pending = std::mem::take(&mut *PENDING.lock());
+    let mut last_error = None;
+    for ((org_id, stream_type, stream_name, _), stats) in pending {
+        if let Err(e) = table::add(&org_id, stream_type, &stream_name, &stats).await {
+            log::error!(
+                "[STATS] write hourly stats for {org_id}/{stream_type}/{stream_name} error: {e}"
+            );
+            record(&org_id, stream_type, &stream_name, &stats);
+            last_error = Some(e);
+        }
+    }
+    match last_error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Returns the hourly stats of the given streams (all streams of the type if
+/// empty) between `start_time` and `end_time`, keyed by stream name.
+pub async fn list(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_names: &[String],
+    start_time: i64,
+    end_time: i64,
+) -> Result<HashMap<String, Vec<StreamHourlyStats>>, Error> {
+    let records = table::list(
+        org_id,
+        stream_type,
+        stream_names,
+        StreamHourlyStats::hour_of(start_time),
+        end_time,
+    )
+    .await?;
+    let mut streams: HashMap<String, Vec<StreamHourlyStats>> = HashMap::new();
+    for (stream_name, stats) in records {
+        streams.entry(stream_name).or_default().push(stats);
+    }
+    Ok(streams)
+}
+
+pub async fn delete_stream(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> Result<(), Error> {
+    PENDING.lock().retain(|(org, stype, name, _), _| {
+        !(org == org_id && *stype == stream_type && name == stream_name)
+    });
+    table::delete_stream(org_id, stream_type, stream_name).await
+}
+
+/// Removes the stats older than the configured retention.
+pub async fn clean_expired() -> Result<(), Error> {
+    let retention_days = get_config().common.stream_hourly_stats_retention_days;
+    if retention_days <= 0 {
+        return Ok(());
+    }
+    let before = now_micros() - retention_days * 24 * 3_600_000_000;
+    table::delete_before(StreamHourlyStats::hour_of(before)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{Int64Array, StringArray};
+    use arrow_schema::{Field, Schema};
+
+    use super::*;
+
+    #[test]
+    fn test_count_error_levels() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("level", DataType::Utf8, true),
+            Field::new("severity", DataType::Int64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(vec![
+                    Some("ERROR"),
+                    Some("info"),
+                    None,
+                    Some(" fatal "),
+                    Some("warn"),
+                ])),
+                Arc::new(Int64Array::from(vec![17, 9, 17, 21, 13])),
+            ],
+        )
+        .unwrap();
+        assert_eq!(count_error_levels(&[batch]), 2);
+
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "severity_text",
+            DataType::Utf8,
+            true,
+        )]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(StringArray::from(vec![
+                "Error", "Critical", "Debug",
+            ]))],
+        )
+        .unwrap();
+        assert_eq!(count_error_levels(&[batch]), 2);
+    }
+}
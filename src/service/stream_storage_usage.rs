@@ -0,0 +1,167 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Daily samples of the storage used by each stream, kept as history so usage
+//! growth can be reported and fed to retention decisions and billing.
+
+use config::{
+    get_config,
+    meta::stream::{StorageGrowth, StreamStorageSample, StreamType},
+    utils::time::now_micros,
+};
+use hashbrown::HashMap;
+use infra::{errors::Error, file_list as infra_file_list, table::stream_storage_usage as table};
+
+use crate::{
+    common::meta::stream::{StorageUsageResponse, StreamStorageUsage},
+    service::{db, self_reporting::publish_storage_usage},
+};
+
+/// Samples the current storage stats of all streams into today's row. The
+/// first sample of a day is published for billing.
+pub async fn sample() -> Result<(), anyhow::Error> {
+    let today = StreamStorageSample::day_of(now_micros());
+    for org_id in db::schema::list_organizations_from_cache().await {
+        let stats = infra_file_list::get_stream_stats(&org_id, None, None)
+            .await
+            .map_err(|e| anyhow::anyhow!("get stream stats error: {e}"))?;
+        for (key, stats) in stats {
+            // eg: default/logs/olympics
+            let columns = key.splitn(3, '/').collect::<Vec<_>>();
+            if columns.len() < 3 {
+                continue;
+            }
+            let stream_type = StreamType::from(columns[1]);
+            let stream_name = columns[2];
+            let sample = StreamStorageSample::from_stats(today, &stats);
+            if table::set(&org_id, stream_type, stream_name, &sample).await? {
+                publish_storage_usage(&org_id, stream_type, stream_name, &sample).await;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the storage history of the given streams (all streams if empty)
+/// between `start_time` and `end_time`, with the growth of each stream and of
+/// their total.
+pub async fn usage(
+    org_id: &str,
+    stream_type: Option<StreamType>,
+    stream_names: &[String],
+    start_time: i64,
+    end_time: i64,
+) -> Result<StorageUsageResponse, Error> {
+    let records = table::list(
+        org_id,
+        stream_type,
+        stream_names,
+        StreamStorageSample::day_of(start_time),
+        end_time,
+    )
+    .await?;
+    Ok(build_usage(records))
+}
+
+fn build_usage(records: Vec<(StreamType, String, StreamStorageSample)>) -> StorageUsageResponse {
+    let mut streams: Vec<StreamStorageUsage> = Vec::new();
+    let mut total: HashMap<i64, StreamStorageSample> = HashMap::new();
+    for (stream_type, stream_name, sample) in records {
+        let entry = total
+            .entry(sample.day)
+            .or_insert_with(|| StreamStorageSample {
+                day: sample.day,
+                ..Default::default()
+            });
+        entry.doc_num += sample.doc_num;
+        entry.storage_size += sample.storage_size;
+        entry.compressed_size += sample.compressed_size;
+        entry.index_size += sample.index_size;
+
+        // records are ordered by stream and day
+        match streams.last_mut() {
+            Some(s) if s.stream_type == stream_type && s.stream_name == stream_name => {
+                s.samples.push(sample)
+            }
+            _ => streams.push(StreamStorageUsage {
+                stream_name,
+                stream_type,
+                samples: vec![sample],
+                ..Default::default()
+            }),
+        }
+    }
+    for stream in streams.iter_mut() {
+        stream.growth = StorageGrowth::from_samples(&stream.samples);
+    }
+    let mut total = total.into_values().collect::<Vec<_>>();
+    total.sort_by_key(|s| s.day);
+    StorageUsageResponse {
+        growth: StorageGrowth::from_samples(&total),
+        total,
+        streams,
+    }
+}
+
+pub async fn delete_stream(
+    org_id: &str,
+    stream_type: StreamType,
+    stream_name: &str,
+) -> Result<(), Error> {
+    table::delete_stream(org_id, stream_type, stream_name).await
+}
+
+/// Removes the samples older than the configured history.
+pub async fn clean_expired() -> Result<(), Error> {
+    let history_days = get_config().common.storage_usage_history_days;
+    if history_days <= 0 {
+        return Ok(());
+    }
+    let before = now_micros() - history_days * 86_400_000_000;
+    table::delete_before(StreamStorageSample::day_of(before)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_usage() {
+        let day = 86_400_000_000;
+        let sample = |d: i64, size: i64| StreamStorageSample {
+            day: d * day,
+            doc_num: size,
+            storage_size: size * 10,
+            compressed_size: size,
+            index_size: 0,
+        };
+        let records = vec![
+            (StreamType::Logs, "a".to_string(), sample(0, 100)),
+            (StreamType::Logs, "a".to_string(), sample(2, 300)),
+            (StreamType::Logs, "b".to_string(), sample(0, 50)),
+            (StreamType::Logs, "b".to_string(), sample(2, 50)),
+            (StreamType::Metrics, "a".to_string(), sample(2, 10)),
+        ];
+        let usage = build_usage(records);
+        assert_eq!(usage.streams.len(), 3);
+        assert_eq!(usage.streams[0].samples.len(), 2);
+        assert_eq!(usage.streams[0].growth.compressed_bytes_per_day, 100.0);
+        assert_eq!(usage.streams[1].growth.compressed_growth_rate, 0.0);
+        assert_eq!(usage.total.len(), 2);
+        assert_eq!(usage.total[0].compressed_size, 150);
+        assert_eq!(usage.total[1].compressed_size, 360);
+        assert_eq!(usage.growth.compressed_bytes_per_day, 105.0);
+    }
+}
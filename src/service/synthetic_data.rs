@@ -0,0 +1,354 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::time::Duration;
+
+use actix_web::web;
+use config::{
+    ider,
+    meta::{
+        otlp::OtlpRequestType,
+        stream::StreamType,
+        synthetic_data::{
+            SyntheticDataRequest, SyntheticField, SyntheticFieldType, SyntheticJob,
+            SyntheticJobStatus,
+        },
+    },
+    utils::{
+        json::{self, Value, json},
+        time::now_micros,
+    },
+};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::service::{db, ingestion, metrics, traces};
+
+/// Number of seconds between two updates of the generated count of a job.
+const PROGRESS_INTERVAL_SECS: u64 = 10;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SyntheticDataError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Synthetic data job not found")]
+    NotFound,
+
+    #[error("Invalid synthetic data request: {0}")]
+    InvalidRequest(String),
+}
+
+/// Starts a job generating records into the stream at the requested rate. The job runs on the
+/// node handling the request and stops early when it is deleted.
+pub async fn start(
+    org_id: &str,
+    request: SyntheticDataRequest,
+) -> Result<SyntheticJob, SyntheticDataError> {
+    request
+        .validate()
+        .map_err(SyntheticDataError::InvalidRequest)?;
+    let now = now_micros();
+    let job = SyntheticJob {
+        id: ider::generate(),
+        request,
+        status: SyntheticJobStatus::Running,
+        generated: 0,
+        error: None,
+        created_at: now,
+        updated_at: now,
+    };
+    db::synthetic_data::set(org_id, &job).await?;
+
+    let org_id = org_id.to_string();
+    let task_job = job.clone();
+    tokio::task::spawn(async move { run(org_id, task_job).await });
+    Ok(job)
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<SyntheticJob>, SyntheticDataError> {
+    let mut list = db::synthetic_data::list(org_id).await?;
+    list.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(list)
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<SyntheticJob, SyntheticDataError> {
+    db::synthetic_data::get(org_id, id)
+        .await
+        .map_err(|_| SyntheticDataError::NotFound)
+}
+
+/// Deletes the job, a running job stops within a second.
+pub async fn delete(org_id: &str, id: &str) -> Result<(), SyntheticDataError> {
+    if db::synthetic_data::get(org_id, id).await.is_err() {
+        return Err(SyntheticDataError::NotFound);
+    }
+    db::synthetic_data::delete(org_id, id).await?;
+    Ok(())
+}
+
+async fn run(org_id: String, mut job: SyntheticJob) {
+    let mut generator = Generator::new(job.request.clone(), rand::random());
+    let mut interval = tokio::time::interval(Duration::from_secs(1));
+    for tick in 1..=job.request.duration_secs {
+        interval.tick().await;
+        // the job was deleted
+        if db::synthetic_data::get(&org_id, &job.id).await.is_err() {
+            log::info!("[SYNTHETIC_DATA] job {}/{} stopped", org_id, job.id);
+            return;
+        }
+        let records = generator.records(now_micros(), job.request.rate);
+        if let Err(e) = write(&org_id, &job.request, records).await {
+            log::error!("[SYNTHETIC_DATA] job {}/{} failed: {e}", org_id, job.id);
+            job.status = SyntheticJobStatus::Failed;
+            job.error = Some(e.to_string());
+            break;
+        }
+        job.generated += job.request.rate;
+        if tick == job.request.duration_secs {
+            job.status = SyntheticJobStatus::Completed;
+        } else if tick % PROGRESS_INTERVAL_SECS != 0 {
+            continue;
+        }
+        job.updated_at = now_micros();
+        if let Err(e) = db::synthetic_data::set(&org_id, &job).await {
+            log::error!(
+                "[SYNTHETIC_DATA] failed to save job {}/{}: {e}",
+                org_id,
+                job.id
+            );
+        }
+    }
+    if job.status == SyntheticJobStatus::Failed
+        && db::synthetic_data::get(&org_id, &job.id).await.is_ok()
+    {
+        job.updated_at = now_micros();
+        if let Err(e) = db::synthetic_data::set(&org_id, &job).await {
+            log::error!(
+                "[SYNTHETIC_DATA] failed to save job {}/{}: {e}",
+                org_id,
+                job.id
+            );
+        }
+    }
+}
+
+async fn write(
+    org_id: &str,
+    request: &SyntheticDataRequest,
+    records: Vec<Value>,
+) -> Result<(), anyhow::Error> {
+    match request.stream_type {
+        StreamType::Metrics => {
+            let body = web::Bytes::from(json::to_vec(&records)?);
+            let resp = metrics::json::ingest(org_id, body).await?;
+            if resp.code != 200 {
+                anyhow::bail!(resp.error.unwrap_or_default());
+            }
+        }
+        StreamType::Traces => {
+            let body = web::Bytes::from(json::to_vec(&records)?);
+            let resp = traces::ingest_json(
+                org_id,
+                body,
+                OtlpRequestType::HttpJson,
+                &request.stream_name,
+            )
+            .await?;
+            if !resp.status().is_success() {
+                anyhow::bail!("traces ingestion failed with status {}", resp.status());
+            }
+        }
+        _ => ingestion::ingest_internal_records(org_id, &request.stream_name, records).await?,
+    }
+    Ok(())
+}
+
+/// Generates the records of a job.
+struct Generator {
+    request: SyntheticDataRequest,
+    rng: StdRng,
+}
+
+impl Generator {
+    fn new(request: SyntheticDataRequest, seed: u64) -> Self {
+        Self {
+            request,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns `count` records spread over the second starting at `start` in microseconds.
+    fn records(&mut self, start: i64, count: u64) -> Vec<Value> {
+        let step = 1_000_000 / count.max(1) as i64;
+        (0..count as i64)
+            .map(|i| self.record(start + i * step))
+            .collect()
+    }
+
+    fn record(&mut self, timestamp: i64) -> Value {
+        let service = format!(
+            "service_{}",
+            self.rng.gen_range(0..self.request.cardinality)
+        );
+        let is_error = self.rng.gen_bool(self.request.error_ratio);
+        let duration_ms = if is_error {
+            self.rng.gen_range(500..5000)
+        } else {
+            self.rng.gen_range(1..500)
+        };
+        let mut record = match self.request.stream_type {
+            StreamType::Metrics => json!({
+                "__name__": self.request.stream_name,
+                "__type__": "gauge",
+                "_timestamp": timestamp,
+                "service": service,
+                "status": if is_error { "error" } else { "ok" },
+                "value": duration_ms as f64,
+            }),
+            StreamType::Traces => {
+                let start_time = timestamp as u64 * 1000;
+                let end_time = start_time + duration_ms * 1_000_000;
+                json!({
+                    "_timestamp": timestamp,
+                    "trace_id": format!("{:032x}", self.rng.r#gen::<u128>()),
+                    "span_id": format!("{:016x}", self.rng.r#gen::<u64>()),
+                    "flags": 1,
+                    "span_status": if is_error { "ERROR" } else { "OK" },
+                    "span_kind": "2",
+                    "operation_name": format!("GET /api/{}", self.rng.gen_range(0..10)),
+                    "start_time": start_time,
+                    "end_time": end_time,
+                    "duration": duration_ms * 1000,
+                    "service_name": service,
+                    "events": "[]",
+                    "links": "[]",
+                })
+            }
+            _ => json!({
+                "_timestamp": timestamp,
+                "service": service,
+                "level": if is_error { "error" } else { "info" },
+                "status_code": if is_error { 500 } else { 200 },
+                "duration_ms": duration_ms,
+                "message": if is_error {
+                    format!("{service} request failed")
+                } else {
+                    format!("{service} request completed")
+                },
+            }),
+        };
+
+        let map = record.as_object_mut().unwrap();
+        for field in self.request.fields.iter() {
+            let value = field_value(field, self.request.cardinality, &mut self.rng);
+            // metrics labels are strings
+            let value = match (self.request.stream_type, value) {
+                (StreamType::Metrics, Value::String(s)) => Value::String(s),
+                (StreamType::Metrics, v) => Value::String(v.to_string()),
+                (_, v) => v,
+            };
+            map.insert(field.name.clone(), value);
+        }
+        record
+    }
+}
+
+fn field_value(field: &SyntheticField, default_cardinality: u64, rng: &mut StdRng) -> Value {
+    let cardinality = if field.cardinality > 0 {
+        field.cardinality
+    } else {
+        default_cardinality
+    };
+    let n = rng.gen_range(0..cardinality);
+    match field.field_type {
+        SyntheticFieldType::String => Value::String(format!("{}_{n}", field.name)),
+        SyntheticFieldType::Integer => json!(n),
+        SyntheticFieldType::Float => json!(n as f64 + rng.r#gen::<f64>()),
+        SyntheticFieldType::Boolean => Value::Bool(n % 2 == 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hashbrown::HashSet;
+
+    use super::*;
+
+    fn request(stream_type: StreamType) -> SyntheticDataRequest {
+        SyntheticDataRequest {
+            stream_name: "load".to_string(),
+            stream_type,
+            rate: 1000,
+            duration_secs: 1,
+            cardinality: 5,
+            error_ratio: 0.2,
+            fields: vec![
+                SyntheticField {
+                    name: "region".to_string(),
+                    field_type: SyntheticFieldType::String,
+                    cardinality: 3,
+                },
+                SyntheticField {
+                    name: "shard".to_string(),
+                    field_type: SyntheticFieldType::Integer,
+                    cardinality: 0,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_generate_logs() {
+        let mut generator = Generator::new(request(StreamType::Logs), 42);
+        let records = generator.records(1_000_000, 1000);
+        assert_eq!(records.len(), 1000);
+        assert_eq!(records[0]["_timestamp"], 1_000_000);
+        assert_eq!(records[999]["_timestamp"], 1_999_000);
+
+        let services = records
+            .iter()
+            .map(|r| r["service"].as_str().unwrap().to_string())
+            .collect::<HashSet<_>>();
+        assert!(services.len() <= 5);
+        let regions = records
+            .iter()
+            .map(|r| r["region"].as_str().unwrap().to_string())
+            .collect::<HashSet<_>>();
+        assert!(regions.len() <= 3);
+        assert!(records.iter().all(|r| r["shard"].as_u64().unwrap() < 5));
+
+        let errors = records.iter().filter(|r| r["level"] == "error").count();
+        assert!(errors > 100 && errors < 300, "errors: {errors}");
+    }
+
+    #[test]
+    fn test_generate_metrics_and_traces() {
+        let mut generator = Generator::new(request(StreamType::Metrics), 7);
+        let record = generator.records(1_000_000, 1).remove(0);
+        assert_eq!(record["__name__"], "load");
+        assert!(record["shard"].is_string());
+        assert!(record["value"].is_f64());
+
+        let mut generator = Generator::new(request(StreamType::Traces), 7);
+        let record = generator.records(1_000_000, 1).remove(0);
+        assert_eq!(record["trace_id"].as_str().unwrap().len(), 32);
+        assert_eq!(record["span_id"].as_str().unwrap().len(), 16);
+        assert_eq!(record["start_time"], 1_000_000_000u64);
+        assert_eq!(
+            record["duration"].as_u64().unwrap() * 1000,
+            record["end_time"].as_u64().unwrap() - 1_000_000_000
+        );
+    }
+}
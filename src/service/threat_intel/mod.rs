@@ -0,0 +1,467 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::time::Duration;
+
+use chrono::{TimeZone, Utc};
+use config::{
+    TIMESTAMP_COL_NAME, ider,
+    meta::{
+        detections::FINDINGS_STREAM,
+        threat_intel::{FeedFormat, Indicator, IndicatorFeed, IndicatorKind},
+    },
+    utils::json::{self, Map, Value},
+};
+use infra::dist_lock;
+use once_cell::sync::Lazy;
+use tokio::sync::{Mutex, mpsc};
+
+use crate::{
+    common::infra::config::THREAT_INDICATORS,
+    service::{db, ingestion::ingest_internal_records},
+};
+
+pub mod stix;
+
+/// Field added to records which hit at least one indicator, holds `kind:value` of the hits.
+pub const MATCH_FIELD: &str = "threat_intel_matches";
+
+const MAX_TAXII_PAGES: usize = 100;
+/// Timeouts of the feed requests.
+const FEED_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const FEED_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+/// Largest response body of a feed request, 64 MiB.
+const MAX_FEED_BODY_SIZE: usize = 64 * 1024 * 1024;
+
+type FindingsChannel = (
+    mpsc::Sender<(String, Vec<Value>)>,
+    Mutex<Option<mpsc::Receiver<(String, Vec<Value>)>>>,
+);
+
+/// Findings produced while ingesting are handed over to a background writer, so that the
+/// ingestion path doesn't ingest into another stream itself.
+static FINDINGS_CHANNEL: Lazy<FindingsChannel> = Lazy::new(|| {
+    let (tx, rx) = mpsc::channel(1024);
+    (tx, Mutex::new(Some(rx)))
+});
+
+#[derive(Debug, thiserror::Error)]
+pub enum ThreatIntelError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Invalid {0} indicator: {1}")]
+    InvalidIndicator(IndicatorKind, String),
+
+    #[error("Feed not found")]
+    FeedNotFound,
+
+    #[error("Invalid feed: {0}")]
+    InvalidFeed(String),
+
+    #[error("Fetching feed failed: {0}")]
+    FetchError(String),
+}
+
+/// Validates, normalizes and stores indicators, returns the stored indicators.
+pub async fn add_indicators(
+    org_id: &str,
+    indicators: Vec<Indicator>,
+) -> Result<Vec<Indicator>, ThreatIntelError> {
+    let now = Utc::now().timestamp_micros();
+    let mut stored = Vec::with_capacity(indicators.len());
+    for mut indicator in indicators {
+        indicator.value = Indicator::normalize(indicator.kind, &indicator.value)
+            .ok_or_else(|| ThreatIntelError::InvalidIndicator(indicator.kind, indicator.value))?;
+        if indicator.created_at == 0 {
+            indicator.created_at = now;
+        }
+        stored.push(indicator);
+    }
+    for indicator in stored.iter() {
+        db::threat_intel::set_indicator(org_id, indicator).await?;
+    }
+    Ok(stored)
+}
+
+pub async fn list_indicators(org_id: &str) -> Result<Vec<Indicator>, ThreatIntelError> {
+    Ok(db::threat_intel::list_indicators(org_id).await?)
+}
+
+pub async fn delete_indicator(
+    org_id: &str,
+    kind: IndicatorKind,
+    value: &str,
+) -> Result<(), ThreatIntelError> {
+    let value = Indicator::normalize(kind, value)
+        .ok_or_else(|| ThreatIntelError::InvalidIndicator(kind, value.to_string()))?;
+    Ok(db::threat_intel::delete_indicator(org_id, &value).await?)
+}
+
+pub async fn create_feed(
+    org_id: &str,
+    mut feed: IndicatorFeed,
+) -> Result<IndicatorFeed, ThreatIntelError> {
+    validate_feed(&feed)?;
+    feed.id = ider::uuid();
+    feed.org_id = org_id.to_string();
+    feed.last_fetched_at = 0;
+    db::threat_intel::set_feed(&feed).await?;
+    Ok(feed)
+}
+
+pub async fn update_feed(
+    org_id: &str,
+    id: &str,
+    mut feed: IndicatorFeed,
+) -> Result<IndicatorFeed, ThreatIntelError> {
+    validate_feed(&feed)?;
+    let old = get_feed(org_id, id).await?;
+    feed.id = old.id;
+    feed.org_id = old.org_id;
+    feed.last_fetched_at = old.last_fetched_at;
+    db::threat_intel::set_feed(&feed).await?;
+    Ok(feed)
+}
+
+pub async fn get_feed(org_id: &str, id: &str) -> Result<IndicatorFeed, ThreatIntelError> {
+    db::threat_intel::get_feed(org_id, id)
+        .await
+        .map_err(|_| ThreatIntelError::FeedNotFound)
+}
+
+pub async fn list_feeds(org_id: &str) -> Result<Vec<IndicatorFeed>, ThreatIntelError> {
+    Ok(db::threat_intel::list_feeds(org_id).await?)
+}
+
+pub async fn delete_feed(org_id: &str, id: &str) -> Result<(), ThreatIntelError> {
+    get_feed(org_id, id).await?;
+    Ok(db::threat_intel::delete_feed(org_id, id).await?)
+}
+
+fn validate_feed(feed: &IndicatorFeed) -> Result<(), ThreatIntelError> {
+    if feed.name.trim().is_empty() {
+        return Err(ThreatIntelError::InvalidFeed(
+            "name cannot be empty".to_string(),
+        ));
+    }
+    if url::Url::parse(&feed.url).is_err() {
+        return Err(ThreatIntelError::InvalidFeed(format!(
+            "invalid url {}",
+            feed.url
+        )));
+    }
+    if feed.interval <= 0 {
+        return Err(ThreatIntelError::InvalidFeed(
+            "interval must be greater than 0".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Pulls the feeds which are due and removes expired indicators, called periodically by the
+/// threat intel job.
+pub async fn run() -> Result<(), ThreatIntelError> {
+    // only one alert manager pulls the feeds at a time, the feeds pulled by another node are no
+    // longer due once the lock is taken
+    let locker = dist_lock::lock("/threat_intel/run", 0).await?;
+    let ret = run_due().await;
+    dist_lock::unlock(&locker).await?;
+    ret
+}
+
+async fn run_due() -> Result<(), ThreatIntelError> {
+    let now = Utc::now().timestamp_micros();
+    for mut feed in db::threat_intel::list_feeds("").await? {
+        if !feed.enabled || now - feed.last_fetched_at < feed.interval * 1_000_000 {
+            continue;
+        }
+        match pull_feed(&feed, now).await {
+            Ok(n) => {
+                log::info!(
+                    "[THREAT INTEL] pulled {n} indicators from feed {}/{}",
+                    feed.org_id,
+                    feed.name
+                );
+                feed.last_fetched_at = now;
+                db::threat_intel::set_feed(&feed).await?;
+            }
+            Err(e) => log::error!(
+                "[THREAT INTEL] pull feed {}/{} error: {e}",
+                feed.org_id,
+                feed.name
+            ),
+        }
+    }
+
+    let expired = THREAT_INDICATORS
+        .iter()
+        .filter(|item| item.value().is_expired(now))
+        .map(|item| item.key().clone())
+        .collect::<Vec<_>>();
+    for key in expired {
+        if let Some((org_id, value)) = key.split_once('/') {
+            db::threat_intel::delete_indicator(org_id, value).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn pull_feed(feed: &IndicatorFeed, now: i64) -> Result<usize, ThreatIntelError> {
+    let client = reqwest::Client::builder()
+        .connect_timeout(FEED_CONNECT_TIMEOUT)
+        .timeout(FEED_REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| ThreatIntelError::FetchError(e.to_string()))?;
+    let mut objects = vec![];
+    match feed.format {
+        FeedFormat::StixBundle => {
+            let bundle = fetch_json(&client, feed, &feed.url, "application/json", &[]).await?;
+            if let Some(items) = bundle.get("objects").and_then(|v| v.as_array()) {
+                objects.extend(items.iter().cloned());
+            }
+        }
+        FeedFormat::Taxii => {
+            let url = format!("{}/objects/", feed.url.trim_end_matches('/'));
+            let mut query = vec![];
+            if feed.last_fetched_at > 0 {
+                let added_after = Utc
+                    .timestamp_micros(feed.last_fetched_at)
+                    .unwrap()
+                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+                query.push(("added_after".to_string(), added_after));
+            }
+            for _ in 0..MAX_TAXII_PAGES {
+                let envelope = fetch_json(
+                    &client,
+                    feed,
+                    &url,
+                    "application/taxii+json;version=2.1",
+                    &query,
+                )
+                .await?;
+                if let Some(items) = envelope.get("objects").and_then(|v| v.as_array()) {
+                    objects.extend(items.iter().cloned());
+                }
+                let more = envelope
+                    .get("more")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                match envelope.get("next").and_then(|v| v.as_str()) {
+                    Some(next) if more => {
+                        query.retain(|(k, _)| k != "next");
+                        query.push(("next".to_string(), next.to_string()));
+                    }
+                    _ => break,
+                }
+            }
+        }
+    }
+
+    let indicators = stix::parse_objects(&objects, feed, now);
+    let count = indicators.len();
+    add_indicators(&feed.org_id, indicators).await?;
+    Ok(count)
+}
+
+async fn fetch_json(
+    client: &reqwest::Client,
+    feed: &IndicatorFeed,
+    url: &str,
+    accept: &str,
+    query: &[(String, String)],
+) -> Result<Value, ThreatIntelError> {
+    let mut req = client
+        .get(url)
+        .header(reqwest::header::ACCEPT, accept)
+        .query(query);
+    if let Some(authorization) = feed.authorization.as_ref() {
+        req = req.header(reqwest::header::AUTHORIZATION, authorization);
+    }
+    let mut resp = req
+        .send()
+        .await
+        .map_err(|e| ThreatIntelError::FetchError(e.to_string()))?;
+    if !resp.status().is_success() {
+        return Err(ThreatIntelError::FetchError(format!(
+            "{url} returned {}",
+            resp.status()
+        )));
+    }
+    let too_large = || {
+        ThreatIntelError::FetchError(format!(
+            "{url} returned more than {MAX_FEED_BODY_SIZE} bytes"
+        ))
+    };
+    if resp
+        .content_length()
+        .is_some_and(|len| len > MAX_FEED_BODY_SIZE as u64)
+    {
+        return Err(too_large());
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = resp
+        .chunk()
+        .await
+        .map_err(|e| ThreatIntelError::FetchError(e.to_string()))?
+    {
+        if body.len() + chunk.len() > MAX_FEED_BODY_SIZE {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    json::from_slice(&body).map_err(|e| ThreatIntelError::FetchError(e.to_string()))
+}
+
+/// Matches the values of `fields` against the indicators of the org. Records with hits are
+/// tagged with [`MATCH_FIELD`] and a finding per hit is returned.
+pub fn match_records(
+    org_id: &str,
+    stream_name: &str,
+    records: &mut [(i64, Map<String, Value>)],
+    fields: &[String],
+) -> Vec<Value> {
+    let now = Utc::now().timestamp_micros();
+    let mut findings = vec![];
+    for (_, record) in records.iter_mut() {
+        let mut hits = vec![];
+        for field in fields {
+            let Some(Value::String(value)) = record.get(field) else {
+                continue;
+            };
+            let value = value.trim();
+            let key = match config::utils::ip::normalize_ip(value) {
+                Some(ip) => format!("{org_id}/{ip}"),
+                None => format!("{org_id}/{}", value.trim_end_matches('.').to_lowercase()),
+            };
+            let Some(indicator) = THREAT_INDICATORS.get(&key) else {
+                continue;
+            };
+            if indicator.is_expired(now) {
+                continue;
+            }
+            hits.push(indicator.value().clone());
+        }
+        if hits.is_empty() {
+            continue;
+        }
+
+        let tags = hits
+            .iter()
+            .map(|i| format!("{}:{}", i.kind, i.value))
+            .collect::<Vec<_>>()
+            .join(",");
+        let event = json::to_string(&record).unwrap_or_default();
+        for indicator in hits {
+            findings.push(json::json!({
+                TIMESTAMP_COL_NAME: now,
+                "detection_id": "threat_intel",
+                "detection_name": format!("Threat intel: {}", indicator.source),
+                "severity": indicator.severity.to_string(),
+                "mitre_tags": "",
+                "source_stream": stream_name,
+                "indicator": indicator.value,
+                "indicator_description": indicator.description,
+                "event_timestamp": record.get(TIMESTAMP_COL_NAME).and_then(|v| v.as_i64()).unwrap_or(now),
+                "event": event,
+            }));
+        }
+        record.insert(MATCH_FIELD.to_string(), Value::String(tags));
+    }
+    findings
+}
+
+/// Queues findings of the org to be written into the `_findings` stream.
+pub fn queue_findings(org_id: &str, findings: Vec<Value>) {
+    if findings.is_empty() {
+        return;
+    }
+    if let Err(e) = FINDINGS_CHANNEL.0.try_send((org_id.to_string(), findings)) {
+        log::warn!("[THREAT INTEL] findings queue is full, dropping findings: {e}");
+    }
+}
+
+/// Writes the queued findings, runs on ingesters.
+pub async fn run_findings_writer() -> Result<(), anyhow::Error> {
+    let Some(mut rx) = FINDINGS_CHANNEL.1.lock().await.take() else {
+        return Ok(());
+    };
+    while let Some((org_id, findings)) = rx.recv().await {
+        if let Err(e) = ingest_internal_records(&org_id, FINDINGS_STREAM, findings).await {
+            log::error!("[THREAT INTEL] write findings for org {org_id} error: {e}");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use config::meta::detections::Severity;
+
+    use super::*;
+
+    #[test]
+    fn test_match_records() {
+        let indicator = Indicator {
+            value: "evil.example.com".to_string(),
+            kind: IndicatorKind::Domain,
+            source: "test".to_string(),
+            description: "".to_string(),
+            severity: Severity::High,
+            expires_at: 0,
+            created_at: 0,
+        };
+        THREAT_INDICATORS.insert("ti_org/evil.example.com".to_string(), indicator);
+        let mut expired = THREAT_INDICATORS
+            .get("ti_org/evil.example.com")
+            .unwrap()
+            .clone();
+        expired.value = "10.1.2.3".to_string();
+        expired.kind = IndicatorKind::Ip;
+        expired.expires_at = 1;
+        THREAT_INDICATORS.insert("ti_org/10.1.2.3".to_string(), expired);
+
+        let mut records = vec![
+            (
+                0,
+                json::json!({"host": "Evil.Example.com", "ip": "10.1.2.3"})
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+            ),
+            (
+                0,
+                json::json!({"host": "good.example.com"})
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+            ),
+        ];
+        let fields = vec!["host".to_string(), "ip".to_string()];
+        let findings = match_records("ti_org", "logs", &mut records, &fields);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0]["severity"], "high");
+        assert_eq!(findings[0]["indicator"], "evil.example.com");
+        assert_eq!(
+            records[0].1.get(MATCH_FIELD),
+            Some(&Value::String("domain:evil.example.com".to_string()))
+        );
+        assert!(records[1].1.get(MATCH_FIELD).is_none());
+
+        // indicators of other orgs don't match
+        let findings = match_records("other_org", "logs", &mut records[1..], &fields);
+        assert!(findings.is_empty());
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Extracts indicators from STIX 2.x `indicator` objects, as found in STIX bundles and TAXII
+//! envelopes. Only comparisons on ip addresses, domain names and file hashes are used, all
+//! other parts of a pattern are ignored.
+
+use chrono::DateTime;
+use config::{
+    meta::threat_intel::{Indicator, IndicatorFeed, IndicatorKind},
+    utils::json,
+};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static PATTERN_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(ipv4-addr|ipv6-addr|domain-name|file):(?:value|hashes\.(?:'[^']+'|[A-Za-z0-9-]+))\s*=\s*'((?:[^'\\]|\\.)*)'",
+    )
+    .unwrap()
+});
+
+/// Parses a STIX pattern into `(kind, value)` pairs.
+pub fn parse_pattern(pattern: &str) -> Vec<(IndicatorKind, String)> {
+    PATTERN_RE
+        .captures_iter(pattern)
+        .filter_map(|cap| {
+            let kind = match &cap[1] {
+                "ipv4-addr" | "ipv6-addr" => IndicatorKind::Ip,
+                "domain-name" => IndicatorKind::Domain,
+                _ => IndicatorKind::Hash,
+            };
+            let value = cap[2].replace("\\'", "'").replace("\\\\", "\\");
+            Some((kind, Indicator::normalize(kind, &value)?))
+        })
+        .collect()
+}
+
+/// Converts the `indicator` objects of a STIX bundle or TAXII envelope into indicators of the
+/// feed. Indicators without `valid_until` expire after the ttl of the feed.
+pub fn parse_objects(objects: &[json::Value], feed: &IndicatorFeed, now: i64) -> Vec<Indicator> {
+    let default_expires_at = if feed.ttl > 0 {
+        now + feed.ttl * 1_000_000
+    } else {
+        0
+    };
+    let mut indicators = vec![];
+    for obj in objects {
+        if obj.get("type").and_then(|v| v.as_str()) != Some("indicator")
+            || obj.get("revoked").and_then(|v| v.as_bool()) == Some(true)
+        {
+            continue;
+        }
+        if let Some(pattern_type) = obj.get("pattern_type").and_then(|v| v.as_str()) {
+            if pattern_type != "stix" {
+                continue;
+            }
+        }
+        let Some(pattern) = obj.get("pattern").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let expires_at = obj
+            .get("valid_until")
+            .and_then(|v| v.as_str())
+            .and_then(|v| DateTime::parse_from_rfc3339(v).ok())
+            .map(|t| t.timestamp_micros())
+            .unwrap_or(default_expires_at);
+        if expires_at > 0 && expires_at <= now {
+            continue;
+        }
+        let description = obj
+            .get("name")
+            .or_else(|| obj.get("description"))
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        for (kind, value) in parse_pattern(pattern) {
+            indicators.push(Indicator {
+                value,
+                kind,
+                source: feed.name.clone(),
+                description: description.to_string(),
+                severity: feed.severity,
+                expires_at,
+                created_at: now,
+            });
+        }
+    }
+    indicators
+}
+
+#[cfg(test)]
+mod tests {
+    use config::meta::threat_intel::FeedFormat;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_pattern() {
+        assert_eq!(
+            parse_pattern("[ipv4-addr:value = '198.51.100.1']"),
+            vec![(IndicatorKind::Ip, "198.51.100.1".to_string())]
+        );
+        assert_eq!(
+            parse_pattern(
+                "[domain-name:value = 'Evil.Example.com'] OR [file:hashes.'SHA-256' = 'ABCDEF01']"
+            ),
+            vec![
+                (IndicatorKind::Domain, "evil.example.com".to_string()),
+                (IndicatorKind::Hash, "abcdef01".to_string()),
+            ]
+        );
+        assert_eq!(
+            parse_pattern("[file:hashes.MD5 = 'd41d8cd98f00b204e9800998ecf8427e']"),
+            vec![(
+                IndicatorKind::Hash,
+                "d41d8cd98f00b204e9800998ecf8427e".to_string()
+            )]
+        );
+        assert!(parse_pattern("[process:name = 'evil.exe']").is_empty());
+    }
+
+    #[test]
+    fn test_parse_objects() {
+        let feed = IndicatorFeed {
+            id: "1".to_string(),
+            org_id: "default".to_string(),
+            name: "feed".to_string(),
+            url: "https://example.com".to_string(),
+            format: FeedFormat::StixBundle,
+            authorization: None,
+            severity: Default::default(),
+            interval: 3600,
+            ttl: 60,
+            enabled: true,
+            last_fetched_at: 0,
+        };
+        let now = 1_700_000_000_000_000;
+        let objects: Vec<json::Value> = json::from_str(
+            r#"[
+                {"type": "indicator", "pattern": "[ipv4-addr:value = '10.0.0.1']", "name": "c2"},
+                {"type": "indicator", "pattern": "[ipv4-addr:value = '10.0.0.2']", "revoked": true},
+                {"type": "indicator", "pattern": "[ipv4-addr:value = '10.0.0.3']", "valid_until": "2001-01-01T00:00:00Z"},
+                {"type": "indicator", "pattern": "[ipv4-addr:value = '10.0.0.4']", "pattern_type": "sigma"},
+                {"type": "malware", "name": "x"}
+            ]"#,
+        )
+        .unwrap();
+        let indicators = parse_objects(&objects, &feed, now);
+        assert_eq!(indicators.len(), 1);
+        assert_eq!(indicators[0].value, "10.0.0.1");
+        assert_eq!(indicators[0].description, "c2");
+        assert_eq!(indicators[0].source, "feed");
+        assert_eq!(indicators[0].expires_at, now + 60_000_000);
+    }
+}
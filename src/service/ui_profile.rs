@@ -0,0 +1,118 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use chrono::Utc;
+use config::meta::{
+    ui_profile::{self as meta_ui_profile, UiProfile, UiSection},
+    user::UserRole,
+};
+use infra::table;
+
+use crate::{
+    common::infra::config::UI_PROFILES,
+    service::{db, users},
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum UiProfileError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Ui profile not found")]
+    NotFound,
+
+    #[error("Invalid ui profile: {0}")]
+    InvalidProfile(String),
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<UiProfile>, UiProfileError> {
+    Ok(db::ui_profile::list(org_id).await?)
+}
+
+pub async fn save(org_id: &str, mut profile: UiProfile) -> Result<UiProfile, UiProfileError> {
+    profile.user_id = profile
+        .user_id
+        .map(|u| u.trim().to_lowercase())
+        .filter(|u| !u.is_empty());
+    profile.landing_dashboard = profile
+        .landing_dashboard
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty());
+    profile.allowed_dashboards = profile
+        .allowed_dashboards
+        .into_iter()
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty())
+        .collect();
+    profile.allowed_dashboards.sort();
+    profile.allowed_dashboards.dedup();
+    profile.hidden_sections.sort();
+    profile.hidden_sections.dedup();
+    profile.validate().map_err(UiProfileError::InvalidProfile)?;
+    if let Some(dashboard_id) = &profile.landing_dashboard
+        && table::dashboards::get_by_id(org_id, dashboard_id)
+            .await?
+            .is_none()
+    {
+        return Err(UiProfileError::InvalidProfile(format!(
+            "landing dashboard {dashboard_id} not found"
+        )));
+    }
+    profile.updated_at = Utc::now().timestamp_micros();
+    // validated above, the profile applies to either a role or a user
+    let key = profile.key().unwrap_or_default();
+    db::ui_profile::set(org_id, &key, &profile).await?;
+    Ok(profile)
+}
+
+/// Deletes the profile of the role or the user, given by its key, see [`UiProfile::key`].
+pub async fn delete(org_id: &str, key: &str) -> Result<(), UiProfileError> {
+    db::ui_profile::get(org_id, key)
+        .await
+        .map_err(|_| UiProfileError::NotFound)?;
+    Ok(db::ui_profile::delete(org_id, key).await?)
+}
+
+/// Returns the profile applying to the user: their own profile, or else the profile of their
+/// role in the org.
+pub async fn effective(org_id: &str, user_id: &str) -> Option<UiProfile> {
+    if UI_PROFILES.is_empty() {
+        return None;
+    }
+    let user_key = format!("{org_id}/{}", meta_ui_profile::user_key(user_id));
+    if let Some(profile) = UI_PROFILES.get(&user_key) {
+        return Some(profile.value().clone());
+    }
+    let role = users::get_user(Some(org_id), user_id).await?.role;
+    if role == UserRole::Root {
+        return None;
+    }
+    let role_key = format!("{org_id}/{}", meta_ui_profile::role_key(&role));
+    UI_PROFILES.get(&role_key).map(|p| p.value().clone())
+}
+
+/// Checks whether the profile of the user lets them list and open the dashboard.
+pub async fn allows_dashboard(org_id: &str, user_id: &str, dashboard_id: &str) -> bool {
+    effective(org_id, user_id)
+        .await
+        .is_none_or(|profile| profile.allows_dashboard(dashboard_id))
+}
+
+/// Checks whether the profile of the user hides the section.
+pub async fn hides(org_id: &str, user_id: &str, section: UiSection) -> bool {
+    effective(org_id, user_id)
+        .await
+        .is_some_and(|profile| profile.hides(section))
+}
@@ -617,6 +617,13 @@ pub async fn add_user_to_org(
         )));
     }
     let email = email.trim().to_lowercase();
+    if is_service_account(&email).await == Some(true)
+        && role.base_role.ne(&UserRole::ServiceAccount)
+    {
+        return Ok(MetaHttpResponse::bad_request(
+            "Service accounts can't be added as users",
+        ));
+    }
     let existing_user = db::user::get_user_record(&email).await;
     let root_user = ROOT_USER.clone();
     if existing_user.is_ok() {
@@ -716,6 +723,31 @@ pub async fn add_user_to_org(
     }
 }
 
+/// Returns whether the user is a service account, `None` when there is no such user.
+pub async fn is_service_account(email: &str) -> Option<bool> {
+    let record = db::user::get_user_record(email).await.ok()?;
+    if record.user_type.is_service_account() {
+        return Some(true);
+    }
+    // service accounts created before the user type was recorded
+    let orgs = db::org_users::list_orgs_by_user(email).await.ok()?;
+    Some(
+        !orgs.is_empty()
+            && orgs
+                .iter()
+                .all(|org| org.role.eq(&UserRole::ServiceAccount)),
+    )
+}
+
+/// Returns the owner to record for a resource created by `user_id`. A service account always
+/// owns what it creates, so automation can't attribute its resources to someone else.
+pub async fn resource_owner(user_id: &str, requested: Option<&str>) -> String {
+    match requested.filter(|owner| !owner.is_empty()) {
+        Some(owner) if is_service_account(user_id).await != Some(true) => owner.to_string(),
+        _ => user_id.to_string(),
+    }
+}
+
 pub async fn get_user(org_id: Option<&str>, name: &str) -> Option<User> {
     let org_id = match org_id {
         Some(local_org) => local_org,
@@ -218,10 +218,16 @@ pub async fn handle_search_request(
 
     // Step 1: Search result cache
     if req.payload.query.from == 0 {
-        let c_resp =
-            cache::check_cache_v2(&trace_id, org_id, stream_type, &req.payload, req.use_cache)
-                .instrument(ws_search_span.clone())
-                .await?;
+        let c_resp = cache::check_cache_v2(
+            &trace_id,
+            org_id,
+            stream_type,
+            user_id,
+            &req.payload,
+            req.use_cache,
+        )
+        .instrument(ws_search_span.clone())
+        .await?;
         let local_c_resp = c_resp.clone();
         let cached_resp = local_c_resp.cached_response;
         let mut deltas = local_c_resp.deltas;
@@ -785,6 +791,8 @@ async fn get_partitions(
         // vrl is not required for _search_partition
         query_fn: Default::default(),
         streaming_output: true,
+        time_ranges: search_payload.query.time_ranges.clone(),
+        variables: search_payload.query.variables.clone(),
     };
 
     let res = SearchService::search_partition(
@@ -113,6 +113,7 @@ pub async fn handle_values_request(
                 &trace_id,
                 org_id,
                 stream_type,
+                user_id,
                 &search_req,
                 search_req.use_cache,
             )
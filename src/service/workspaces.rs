@@ -0,0 +1,340 @@
+// Copyright 2025 OpenObserve Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Workspaces group the streams, dashboards and alerts of a team within an org. The lists of
+//! streams, dashboards and alerts filtered by a workspace only show the resources which belong to
+//! it or are shared with it. A workspace with members restricts its resources to them on top of
+//! the other permissions: only the members can read and change its resources and the workspace,
+//! the members of the workspaces a resource is shared with can read it. The admins of the org
+//! are not restricted.
+
+use std::str::FromStr;
+
+use config::{
+    ider,
+    meta::{
+        stream::StreamType,
+        user::UserRole,
+        workspace::{ResourceType, Workspace, WorkspaceResource},
+    },
+    utils::time::now_micros,
+};
+use infra::{
+    db::{ORM_CLIENT, connect_to_orm},
+    table,
+};
+use svix_ksuid::Ksuid;
+
+use crate::{common::utils::auth::is_root_user, service::db};
+
+#[derive(Debug, thiserror::Error)]
+pub enum WorkspaceError {
+    #[error("InfraError# {0}")]
+    InfraError(#[from] infra::errors::Error),
+
+    #[error("Workspace not found")]
+    NotFound,
+
+    #[error("Workspace with the same name already exists")]
+    AlreadyExists,
+
+    #[error("Invalid workspace: {0}")]
+    Invalid(String),
+
+    #[error("Resource {0} not found")]
+    ResourceNotFound(String),
+
+    #[error("Not permitted by the members of the workspace")]
+    PermissionDenied,
+}
+
+pub async fn list(org_id: &str) -> Result<Vec<Workspace>, WorkspaceError> {
+    Ok(table::workspaces::list(org_id).await?)
+}
+
+pub async fn get(org_id: &str, id: &str) -> Result<Workspace, WorkspaceError> {
+    table::workspaces::get(org_id, id)
+        .await?
+        .ok_or(WorkspaceError::NotFound)
+}
+
+pub async fn create(
+    org_id: &str,
+    user_id: &str,
+    mut workspace: Workspace,
+) -> Result<Workspace, WorkspaceError> {
+    let now = now_micros();
+    workspace.id = ider::uuid();
+    workspace.created_by = user_id.to_string();
+    workspace.created_at = now;
+    workspace.updated_at = now;
+    save(org_id, workspace).await
+}
+
+pub async fn update(
+    org_id: &str,
+    user_id: &str,
+    id: &str,
+    workspace: Workspace,
+) -> Result<Workspace, WorkspaceError> {
+    let existing = get_managed(org_id, user_id, id).await?;
+    let workspace = Workspace {
+        id: existing.id,
+        created_by: existing.created_by,
+        created_at: existing.created_at,
+        updated_at: now_micros(),
+        ..workspace
+    };
+    save(org_id, workspace).await
+}
+
+async fn save(org_id: &str, mut workspace: Workspace) -> Result<Workspace, WorkspaceError> {
+    workspace.name = workspace.name.trim().to_string();
+    for member in workspace.members.iter_mut() {
+        *member = member.trim().to_lowercase();
+    }
+    workspace.members.sort();
+    workspace.members.dedup();
+    workspace.validate().map_err(WorkspaceError::Invalid)?;
+    if let Some(existing) = table::workspaces::get_by_name(org_id, &workspace.name).await?
+        && existing.id != workspace.id
+    {
+        return Err(WorkspaceError::AlreadyExists);
+    }
+    table::workspaces::put(org_id, &workspace).await?;
+    Ok(workspace)
+}
+
+/// Returns the workspace when the user can change it.
+async fn get_managed(org_id: &str, user_id: &str, id: &str) -> Result<Workspace, WorkspaceError> {
+    let workspace = get(org_id, id).await?;
+    if !workspace.is_member(user_id) && !is_admin(org_id, user_id).await {
+        return Err(WorkspaceError::PermissionDenied);
+    }
+    Ok(workspace)
+}
+
+/// Deletes the workspace, its resources are kept and no longer belong to any workspace.
+pub async fn delete(org_id: &str, user_id: &str, id: &str) -> Result<(), WorkspaceError> {
+    get_managed(org_id, user_id, id).await?;
+    if !table::workspaces::delete(org_id, id).await? {
+        return Err(WorkspaceError::NotFound);
+    }
+    Ok(())
+}
+
+pub async fn list_resources(
+    org_id: &str,
+    id: &str,
+) -> Result<Vec<WorkspaceResource>, WorkspaceError> {
+    get(org_id, id).await?;
+    Ok(table::workspaces::list_resources(org_id, id, None).await?)
+}
+
+/// Returns the ids of the resources of the given type which belong to the workspace or are
+/// shared with it.
+pub async fn resource_ids(
+    org_id: &str,
+    id: &str,
+    resource_type: ResourceType,
+) -> Result<Vec<String>, WorkspaceError> {
+    get(org_id, id).await?;
+    let resources = table::workspaces::list_resources(org_id, id, Some(resource_type)).await?;
+    Ok(resources.into_iter().map(|r| r.resource_id).collect())
+}
+
+/// Adds the resource to the workspace. A resource which is not shared is moved from the
+/// workspace it belonged to, a shared resource is listed in the workspace in addition to its
+/// own. The user needs to be able to change the resource.
+pub async fn add_resource(
+    org_id: &str,
+    user_id: &str,
+    id: &str,
+    mut resource: WorkspaceResource,
+) -> Result<WorkspaceResource, WorkspaceError> {
+    get_managed(org_id, user_id, id).await?;
+    resource.resource_id = resource_id(org_id, &resource).await?;
+    if !can_access(
+        org_id,
+        user_id,
+        resource.resource_type,
+        &resource.resource_id,
+        true,
+    )
+    .await?
+    {
+        return Err(WorkspaceError::PermissionDenied);
+    }
+    if resource.shared
+        && table::workspaces::get_owner(org_id, resource.resource_type, &resource.resource_id)
+            .await?
+            .is_some_and(|owner| owner == id)
+    {
+        return Err(WorkspaceError::Invalid(format!(
+            "{} {} already belongs to the workspace",
+            resource.resource_type, resource.resource_id
+        )));
+    }
+    table::workspaces::add_resource(org_id, id, &resource, now_micros()).await?;
+    Ok(resource)
+}
+
+pub async fn remove_resource(
+    org_id: &str,
+    user_id: &str,
+    id: &str,
+    resource_type: ResourceType,
+    resource_id: &str,
+) -> Result<(), WorkspaceError> {
+    get_managed(org_id, user_id, id).await?;
+    if !table::workspaces::remove_resource(org_id, id, resource_type, resource_id).await? {
+        return Err(WorkspaceError::ResourceNotFound(format!(
+            "{resource_type} {resource_id}"
+        )));
+    }
+    Ok(())
+}
+
+/// Checks that the user can read the resource, or change it when `write` is set, according to
+/// the members of the workspace it belongs to and of the workspaces it is shared with.
+pub async fn can_access(
+    org_id: &str,
+    user_id: &str,
+    resource_type: ResourceType,
+    resource_id: &str,
+    write: bool,
+) -> Result<bool, infra::errors::Error> {
+    let Some(owner) = table::workspaces::get_owner(org_id, resource_type, resource_id).await?
+    else {
+        return Ok(true);
+    };
+    let Some(owner) = table::workspaces::get(org_id, &owner).await? else {
+        return Ok(true);
+    };
+    let mut sharing = Vec::new();
+    if !write && !owner.is_member(user_id) {
+        for id in table::workspaces::list_sharing(org_id, resource_type, resource_id).await? {
+            if let Some(workspace) = table::workspaces::get(org_id, &id).await? {
+                sharing.push(workspace);
+            }
+        }
+    }
+    Ok(permits(user_id, &owner, &sharing, write) || is_admin(org_id, user_id).await)
+}
+
+/// Whether the members of the workspace the resource belongs to, and of the workspaces it is
+/// shared with, allow the user to read it or change it.
+fn permits(user_id: &str, owner: &Workspace, sharing: &[Workspace], write: bool) -> bool {
+    if owner.is_member(user_id) {
+        return true;
+    }
+    // the workspaces without members don't grant anything to a resource of a restricted one
+    !write
+        && sharing
+            .iter()
+            .any(|w| !w.members.is_empty() && w.is_member(user_id))
+}
+
+async fn is_admin(org_id: &str, user_id: &str) -> bool {
+    if is_root_user(user_id) {
+        return true;
+    }
+    matches!(
+        db::user::get(Some(org_id), user_id).await,
+        Ok(Some(user)) if matches!(user.role, UserRole::Root | UserRole::Admin)
+    )
+}
+
+/// Removes the deleted resource from the workspaces.
+pub async fn delete_resource(
+    org_id: &str,
+    resource_type: ResourceType,
+    resource_id: &str,
+) -> Result<(), infra::errors::Error> {
+    table::workspaces::delete_resource(org_id, resource_type, resource_id).await
+}
+
+/// Checks that the resource exists and returns its id, with the stream type normalized.
+async fn resource_id(org_id: &str, resource: &WorkspaceResource) -> Result<String, WorkspaceError> {
+    let not_found = || {
+        WorkspaceError::ResourceNotFound(format!(
+            "{} {}",
+            resource.resource_type, resource.resource_id
+        ))
+    };
+    match resource.resource_type {
+        ResourceType::Stream => {
+            let Some((stream_type, stream_name)) = resource.resource_id.split_once('/') else {
+                return Err(WorkspaceError::Invalid(
+                    "stream id must be {stream_type}/{stream_name}".to_string(),
+                ));
+            };
+            let stream_type = StreamType::from(stream_type);
+            let schema = infra::schema::get(org_id, stream_name, stream_type).await?;
+            if schema.fields().is_empty() {
+                return Err(not_found());
+            }
+            Ok(WorkspaceResource::stream_id(stream_type, stream_name))
+        }
+        ResourceType::Dashboard => {
+            table::dashboards::get_by_id(org_id, &resource.resource_id)
+                .await?
+                .ok_or_else(not_found)?;
+            Ok(resource.resource_id.clone())
+        }
+        ResourceType::Alert => {
+            let alert_id = Ksuid::from_str(&resource.resource_id).map_err(|_| not_found())?;
+            let client = ORM_CLIENT.get_or_init(connect_to_orm).await;
+            db::alerts::alert::get_by_id(client, org_id, alert_id)
+                .await?
+                .ok_or_else(not_found)?;
+            Ok(resource.resource_id.clone())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace(name: &str, members: &[&str]) -> Workspace {
+        Workspace {
+            id: name.to_string(),
+            name: name.to_string(),
+            members: members.iter().map(|m| m.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_permits() {
+        let open = workspace("open", &[]);
+        let payments = workspace("payments", &["alice@example.com"]);
+        let billing = workspace("billing", &["bob@example.com"]);
+
+        // a workspace without members doesn't restrict its resources
+        assert!(permits("bob@example.com", &open, &[], true));
+
+        assert!(permits("alice@example.com", &payments, &[], true));
+        assert!(!permits("bob@example.com", &payments, &[], false));
+        assert!(!permits("bob@example.com", &payments, &[], true));
+
+        // sharing only grants reading, and only to the members of the workspace
+        let sharing = [billing, open];
+        assert!(permits("bob@example.com", &payments, &sharing, false));
+        assert!(!permits("bob@example.com", &payments, &sharing, true));
+        assert!(!permits("carol@example.com", &payments, &sharing, false));
+    }
+}